@@ -0,0 +1,30 @@
+// Derleme zamanı sürüm bilgisi — session_summary.json'daki build alanı için.
+// GIT_HASH: kısa commit hash'i (+"-dirty" çalışma ağacı temiz değilse);
+// git yoksa (kaynak arşivi) "unknown".
+
+use std::process::Command;
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn main() {
+    let hash = git(&["rev-parse", "--short=12", "HEAD"])
+        .filter(|h| !h.is_empty())
+        .map(|h| match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(status) if !status.is_empty() => format!("{}-dirty", h),
+            _ => h,
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=GIT_HASH={}", hash);
+    println!("cargo:rerun-if-changed=build.rs");
+    if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        println!("cargo:rerun-if-changed={}/index", git_dir);
+    }
+}
@@ -0,0 +1,50 @@
+// ============================================================================
+//  FUZZ — Kompakt Calldata: Rust Çözücüsü ↔ Kontrat (Diferansiyel REVM)
+//
+//  Çalıştırma (Bot/ içinde, cargo-fuzz + nightly):
+//    (cd ../Contract && forge build)
+//    cargo +nightly fuzz run compact_calldata_diff
+//
+//  Girdinin ilk byte'ı modu seçer:
+//    tek  → kalanı 134 byte'a tamamlanır / kesilir; alan düzeyi karşılaştırma
+//    çift → kalanı olduğu gibi; yalnızca kabul / ret karşılaştırması
+//  Asimetriler ve kontrol listesi: src/calldata_diff.rs
+// ============================================================================
+
+#![no_main]
+
+#[path = "../../src/calldata_codec.rs"]
+#[allow(dead_code)]
+mod calldata_codec;
+#[path = "../../src/calldata_diff.rs"]
+mod calldata_diff;
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use libfuzzer_sys::fuzz_target;
+
+use calldata_codec::COMPACT_CALLDATA_LEN;
+use calldata_diff::{load_artifact, ContractHarness};
+
+static HARNESS: LazyLock<ContractHarness> = LazyLock::new(|| {
+    let bot_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let code = load_artifact(&bot_dir).unwrap_or_else(|e| panic!("{}", e));
+    ContractHarness::deploy(&code).unwrap_or_else(|e| panic!("{}", e))
+});
+
+fuzz_target!(|input: &[u8]| {
+    let Some((&mode, payload)) = input.split_first() else {
+        return;
+    };
+    let harness = &*HARNESS;
+    if mode & 1 == 1 {
+        let mut data = payload.to_vec();
+        data.resize(COMPACT_CALLDATA_LEN, 0);
+        if let Err(e) = harness.check_compact(&data) {
+            panic!("{}", e);
+        }
+    } else if let Err(e) = harness.check_layout(payload) {
+        panic!("{}", e);
+    }
+});
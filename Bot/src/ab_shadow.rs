@@ -0,0 +1,411 @@
+// ============================================================================
+//  AB_SHADOW — Tek Süreçte İki Parametre Setinin Gölge Karşılaştırması
+//
+//  Rüşvet eğrisi, kâr eşiği, NR ön filtreleri gibi ayarları karşılaştırmak
+//  için iki ayrı bot çalıştırmak aynı piyasayı farklı RPC zamanlamasıyla
+//  görür. Bunun yerine:
+//
+//  ✓ SECONDARY_CONFIG_PATH → env formatında ayar katmanı (yalnızca
+//    TUNABLE_KEYS); ana .env'in üstüne yüklenir, varyant B olur
+//  ✓ Her blokta ana pipeline bittikten sonra, aynı (zaten senkron)
+//    PoolState'ler üzerinde check_arbitrage_opportunity B ayarlarıyla
+//    yeniden çalışır — ek RPC yok, yürütme yok
+//  ✓ En az bir varyantın fırsat bulduğu her combo ab_shadow.jsonl'e
+//    variant=A / variant=B satırlarıyla yazılır
+//  ✓ Sıkı zaman kutusu: blok bütçesi darsa veya kutu aşılırsa blok
+//    atlanır ve sayılır (yarım blok kaydedilmez)
+// ============================================================================
+
+use eyre::{Result, WrapErr};
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::opportunity_filter::FilterChain;
+use crate::types::{ArbitrageOpportunity, BotConfig};
+
+/// Karşılaştırma kayıtlarının yazıldığı dosya
+pub const AB_SHADOW_LOG_PATH: &str = "ab_shadow.jsonl";
+
+/// B değerlendirmesinin blok başına en fazla süresi
+pub const AB_TIME_BOX: Duration = Duration::from_millis(150);
+
+/// İkincil katmanda izin verilen (saf hesaplamayı etkileyen) değişkenler.
+/// RPC, anahtar, kontrat gibi ayarlar iki varyantta da aynı kalmalı.
+pub const TUNABLE_KEYS: &[&str] = &[
+    "MIN_NET_PROFIT_WETH",
+    "MIN_PROFIT_ROI",
+    "MAX_TRADE_SIZE_WETH",
+    "GAS_COST_FALLBACK_WETH",
+    "BRIBE_PCT",
+    "MAX_POOL_FEE_BPS",
+    "MAX_STALENESS_MS",
+    "ADAPTIVE_STALENESS",
+    "TICK_BITMAP_MAX_AGE_BLOCKS",
+    "SPREAD_PROBE_SIZE_WETH",
+    "SIZING_BACKEND",
+    "OPPORTUNITY_FILTERS",
+    "REQUIRE_BITMAP_FOR_EXECUTION",
+    "ADVERSE_FRONTRUN_WETH",
+    "REQUIRE_ADVERSE_POSITIVE",
+    "COMPETITION_MIN_TXS",
+    "COMPETITION_PROFIT_MULT",
+    "COMPETITION_SKIP_LIMIT",
+];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kararlar
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Bir varyantın tek combo için kararı (fırsat bulunduysa)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Decision {
+    pub buy_pool_idx: usize,
+    pub sell_pool_idx: usize,
+    pub optimal_amount_weth: f64,
+    pub expected_profit_weth: f64,
+    /// Fırsatı gölgeye alan filtre (yürütülmezdi), yoksa None
+    pub shadow_filter: Option<&'static str>,
+}
+
+impl From<&ArbitrageOpportunity> for Decision {
+    fn from(opp: &ArbitrageOpportunity) -> Self {
+        Self {
+            buy_pool_idx: opp.buy_pool_idx,
+            sell_pool_idx: opp.sell_pool_idx,
+            optimal_amount_weth: opp.optimal_amount_weth,
+            expected_profit_weth: opp.expected_profit_weth,
+            shadow_filter: opp.shadow_only.as_ref().map(|v| v.filter),
+        }
+    }
+}
+
+/// Aynı combo'da iki varyantın kararı
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComboComparison {
+    pub combo_idx: usize,
+    pub a: Option<Decision>,
+    pub b: Option<Decision>,
+}
+
+impl ComboComparison {
+    pub fn agreement(&self) -> &'static str {
+        match (&self.a, &self.b) {
+            (Some(_), Some(_)) => "both",
+            (Some(_), None) => "only_a",
+            (None, Some(_)) => "only_b",
+            (None, None) => "neither",
+        }
+    }
+}
+
+/// Varyant başına toplamlar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct VariantTotals {
+    pub opportunities: u64,
+    pub expected_profit_weth: f64,
+}
+
+impl VariantTotals {
+    fn record(&mut self, decision: &Option<Decision>) {
+        if let Some(d) = decision {
+            self.opportunities += 1;
+            self.expected_profit_weth += d.expected_profit_weth;
+        }
+    }
+}
+
+/// Oturum boyunca A/B sayaçları (stats kutusu + session summary)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AbStats {
+    pub a: VariantTotals,
+    pub b: VariantTotals,
+    /// Yalnızca A'nın / yalnızca B'nin fırsat bulduğu combo sayısı
+    pub only_a: u64,
+    pub only_b: u64,
+    pub blocks_compared: u64,
+    /// Bütçe darlığı veya zaman kutusu yüzünden atlanan bloklar
+    pub blocks_skipped: u64,
+}
+
+impl AbStats {
+    pub fn is_active(&self) -> bool {
+        self.blocks_compared + self.blocks_skipped > 0
+    }
+
+    fn record(&mut self, comparisons: &[ComboComparison]) {
+        for c in comparisons {
+            self.a.record(&c.a);
+            self.b.record(&c.b);
+            match c.agreement() {
+                "only_a" => self.only_a += 1,
+                "only_b" => self.only_b += 1,
+                _ => {}
+            }
+        }
+        self.blocks_compared += 1;
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Varyant B
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// İkincil parametre seti ve ona ait filtre zinciri (sayaçlar A'dan ayrı)
+pub struct AbShadow {
+    config: BotConfig,
+    filters: FilterChain,
+}
+
+impl AbShadow {
+    /// SECONDARY_CONFIG_PATH tanımlıysa katmanı yükle
+    pub fn from_config(primary: &BotConfig) -> Result<Option<Self>> {
+        let Some(ref path) = primary.secondary_config_path else {
+            return Ok(None);
+        };
+        let overlay = read_overlay(Path::new(path))?;
+        let (config, _) = BotConfig::load_with_overlay(overlay)
+            .wrap_err_with(|| format!("SECONDARY_CONFIG_PATH={}", path))?;
+        Ok(Some(Self::new(config)))
+    }
+
+    pub fn new(config: BotConfig) -> Self {
+        let filters = FilterChain::from_config(&config);
+        Self { config, filters }
+    }
+
+    /// A'nın bu bloktaki kararlarını B ile karşılaştır.
+    ///
+    /// `evaluate_b(config, filters, combo_idx)` aynı state'lerle saf hesaplamayı
+    /// yapar. `deadline` aşılırsa blok atlanır (sayılır) ve None döner.
+    pub fn compare_block(
+        &self,
+        stats: &mut AbStats,
+        primary: &[(usize, Option<Decision>)],
+        deadline: Instant,
+        mut evaluate_b: impl FnMut(&BotConfig, &FilterChain, usize) -> Option<Decision>,
+    ) -> Option<Vec<ComboComparison>> {
+        let mut comparisons = Vec::with_capacity(primary.len());
+        for (combo_idx, a) in primary {
+            if Instant::now() >= deadline {
+                stats.blocks_skipped += 1;
+                return None;
+            }
+            comparisons.push(ComboComparison {
+                combo_idx: *combo_idx,
+                a: a.clone(),
+                b: evaluate_b(&self.config, &self.filters, *combo_idx),
+            });
+        }
+        stats.record(&comparisons);
+        Some(comparisons)
+    }
+}
+
+/// Env formatındaki katmanı oku — TUNABLE_KEYS dışındaki anahtarlar hata
+fn read_overlay(path: &Path) -> Result<Vec<(String, String)>> {
+    let iter = dotenvy::from_path_iter(path)
+        .wrap_err_with(|| format!("cannot read secondary config {}", path.display()))?;
+    let mut overlay = Vec::new();
+    for item in iter {
+        let (key, value) =
+            item.wrap_err_with(|| format!("parse error in {}", path.display()))?;
+        overlay.push((key, value));
+    }
+    check_overlay_keys(&overlay)?;
+    Ok(overlay)
+}
+
+fn check_overlay_keys(overlay: &[(String, String)]) -> Result<()> {
+    let unknown: Vec<&str> = overlay
+        .iter()
+        .map(|(k, _)| k.as_str())
+        .filter(|k| !TUNABLE_KEYS.contains(k))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(eyre::eyre!(
+            "secondary config may only override tunable parameters — not allowed: {} (allowed: {})",
+            unknown.join(", "),
+            TUNABLE_KEYS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kayıt
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// En az bir varyantın fırsat bulduğu combo'lar için variant=A / variant=B
+/// satırları. `pair_name(combo_idx)` çift adını verir.
+pub fn log_lines(
+    block: u64,
+    comparisons: &[ComboComparison],
+    pair_name: impl Fn(usize) -> String,
+) -> Vec<serde_json::Value> {
+    let ts = crate::clock::log_timestamp();
+    comparisons
+        .iter()
+        .filter(|c| c.a.is_some() || c.b.is_some())
+        .flat_map(|c| {
+            let pair = pair_name(c.combo_idx);
+            [("A", &c.a), ("B", &c.b)].map(|(variant, decision)| {
+                serde_json::json!({
+                    "ts": ts,
+                    "block": block,
+                    "pair": pair,
+                    "variant": variant,
+                    "agreement": c.agreement(),
+                    "found": decision.is_some(),
+                    "decision": decision,
+                })
+            })
+        })
+        .collect()
+}
+
+pub fn append_lines(path: &Path, lines: &[serde_json::Value]) -> std::io::Result<()> {
+    use std::io::Write;
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let mut file = crate::secure_fs::open_append(path)?;
+    for line in lines {
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::gas_spike_tests::{make_pool_configs, make_pool_state, make_test_config};
+    use crate::strategy::check_arbitrage_opportunity;
+    use crate::types::{CompetitionSnapshot, PoolConfig, SharedPoolState};
+
+    /// Spread'i dardan genişe giden fikstür combo'ları (A fiyatı, B fiyatı)
+    const FIXTURE_PRICES: [(f64, f64); 5] = [
+        (2500.0, 2500.25),
+        (2490.0, 2500.0),
+        (2475.0, 2500.0),
+        (2460.0, 2500.0),
+        (2450.0, 2500.0),
+    ];
+
+    fn fixture() -> (Vec<PoolConfig>, Vec<[SharedPoolState; 2]>) {
+        let liq = 50_000_000_000_000_000_000u128;
+        let states = FIXTURE_PRICES
+            .iter()
+            .map(|&(a, b)| [make_pool_state(a, liq, 100), make_pool_state(b, liq, 100)])
+            .collect();
+        (make_pool_configs(), states)
+    }
+
+    fn evaluate(
+        pools: &[PoolConfig],
+        states: &[SharedPoolState; 2],
+        config: &BotConfig,
+        filters: &FilterChain,
+    ) -> Option<Decision> {
+        check_arbitrage_opportunity(
+            pools,
+            states,
+            config,
+            500_000_000_000,
+            Some(150_000),
+            0,
+            &CompetitionSnapshot::default(),
+            filters,
+            None,
+        )
+        .as_ref()
+        .map(Decision::from)
+    }
+
+    /// A = ana config ile fikstür kararları, B = `variant` ile karşılaştırma
+    fn run(variant: BotConfig) -> (AbStats, Vec<ComboComparison>) {
+        let (pools, states) = fixture();
+        let primary_config = make_test_config(0.0002, 0.00005);
+        let primary_filters = FilterChain::from_config(&primary_config);
+        let primary: Vec<(usize, Option<Decision>)> = states
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (i, evaluate(&pools, s, &primary_config, &primary_filters)))
+            .collect();
+
+        let ab = AbShadow::new(variant);
+        let mut stats = AbStats::default();
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let comparisons = ab
+            .compare_block(&mut stats, &primary, deadline, |cfg, filters, i| {
+                evaluate(&pools, &states[i], cfg, filters)
+            })
+            .expect("generous deadline");
+        (stats, comparisons)
+    }
+
+    #[test]
+    fn test_identical_configs_make_identical_decisions() {
+        let (stats, comparisons) = run(make_test_config(0.0002, 0.00005));
+        assert_eq!(comparisons.len(), FIXTURE_PRICES.len());
+        for c in &comparisons {
+            assert_eq!(c.a, c.b, "combo {} diverged", c.combo_idx);
+        }
+        assert!(stats.a.opportunities > 0, "fixture should contain opportunities");
+        assert_eq!(stats.a, stats.b);
+        assert_eq!((stats.only_a, stats.only_b), (0, 0));
+        assert_eq!(stats.blocks_compared, 1);
+    }
+
+    #[test]
+    fn test_lower_threshold_finds_superset() {
+        // B: kâr eşiği 100x düşük
+        let (stats, comparisons) = run(make_test_config(0.000002, 0.00005));
+        for c in &comparisons {
+            if c.a.is_some() {
+                assert!(c.b.is_some(), "B lost A's opportunity on combo {}", c.combo_idx);
+            }
+        }
+        assert_eq!(stats.only_a, 0);
+        assert!(stats.b.opportunities >= stats.a.opportunities);
+
+        // Yalnızca bir varyantın bulduğu combo'lar da iki satırla loglanır
+        let lines = log_lines(100, &comparisons, |i| format!("combo-{}", i));
+        assert_eq!(lines.len(), 2 * stats.b.opportunities as usize);
+        for pair in lines.chunks(2) {
+            assert_eq!((pair[0]["variant"].as_str(), pair[1]["variant"].as_str()), (Some("A"), Some("B")));
+            assert_eq!(pair[0]["agreement"], pair[1]["agreement"]);
+        }
+    }
+
+    #[test]
+    fn test_expired_deadline_skips_block() {
+        let ab = AbShadow::new(make_test_config(0.0002, 0.00005));
+        let mut stats = AbStats::default();
+        let primary = vec![(0, None), (1, None)];
+        let mut calls = 0;
+        let result = ab.compare_block(&mut stats, &primary, Instant::now(), |_, _, _| {
+            calls += 1;
+            None
+        });
+        assert!(result.is_none());
+        assert_eq!(calls, 0);
+        assert_eq!((stats.blocks_compared, stats.blocks_skipped), (0, 1));
+    }
+
+    #[test]
+    fn test_overlay_rejects_non_tunable_keys() {
+        let ok = vec![("MIN_NET_PROFIT_WETH".to_string(), "0.001".to_string())];
+        assert!(check_overlay_keys(&ok).is_ok());
+        let bad = vec![
+            ("BRIBE_PCT".to_string(), "0.3".to_string()),
+            ("PRIVATE_KEY".to_string(), "0xabc".to_string()),
+        ];
+        let err = check_overlay_keys(&bad).unwrap_err().to_string();
+        assert!(err.contains("PRIVATE_KEY") && !err.contains("0xabc"), "{}", err);
+    }
+}
@@ -0,0 +1,646 @@
+// ============================================================================
+//  APPROVAL_AUDIT v1.0 — Kontrat Token Onayları (allowance) Denetimi
+//
+//  Kontrat yeniden deploy edildiğinde veya bir onay geri alındığında ilk
+//  işaret revert olan TX'tir (gas yanar). Yürütme açıkken başlangıçta ve
+//  günde bir kez:
+//  ✓ Her havuzun iki token'ı için allowance(kontrat → havuz) ve
+//    allowance(kontrat → flash loan sağlayıcı) TEK Multicall3 çağrısıyla okunur
+//  ✓ Gerekli miktar: MAX_TRADE_SIZE_WETH'in token birimine çevrilmiş hali
+//  ✓ Eksik (sıfır) / yetersiz onaylar konsolda yüksek sesle raporlanır,
+//    eksikliğe geçişte Telegram bildirimi gönderilir
+//  ✓ APPROVAL_AUDIT_MODE=hard iken zorunlu bir onay sıfırsa yürütme gölge
+//    moda düşürülür; soft yalnızca uyarır
+//
+//  Arbitraj.sol borçları callback içinde push transfer ile öder ve flash
+//  swap kaynağı havuzun kendisidir — bugünkü kontrat hiçbir onaya ihtiyaç
+//  duymaz. Bu yüzden APPROVAL_REQUIRED_SPENDERS varsayılanı "none"dır;
+//  okunan değerler yine de raporlanır. Pull tabanlı ödeme yapan bir kontrat
+//  sürümünde ilgili harcayıcı türü zorunlu işaretlenir.
+// ============================================================================
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use colored::*;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+use crate::errors::{BotError, BotResult};
+use crate::state_sync::{with_rpc_retry, CallBudget, IMulticall3, MULTICALL3_ADDRESS};
+use crate::telegram::{TelegramMessage, TelegramSender};
+use crate::types::{PoolConfig, SharedPoolState};
+
+/// Periyodik denetim aralığı (saniye) — onaylar nadiren değişir
+pub const APPROVAL_AUDIT_INTERVAL_SECS: u64 = 86_400;
+
+/// Multicall3 denetim çağrısı zaman aşımı
+const AUDIT_TIMEOUT_MS: u64 = 10_000;
+
+sol! {
+    interface IERC20Allowance {
+        function allowance(address owner, address spender) external view returns (uint256);
+    }
+}
+
+/// Eksik onayda davranış (APPROVAL_AUDIT_MODE)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalAuditMode {
+    /// Denetim yapılmaz
+    Off,
+    /// Raporla + bildir, yürütmeye devam et
+    Soft,
+    /// Zorunlu onay sıfırsa yürütmeyi gölge moda düşür
+    Hard,
+}
+
+impl std::fmt::Display for ApprovalAuditMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApprovalAuditMode::Off => write!(f, "off"),
+            ApprovalAuditMode::Soft => write!(f, "soft"),
+            ApprovalAuditMode::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+/// Onayı zorunlu sayılan harcayıcı türleri (APPROVAL_REQUIRED_SPENDERS)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequiredSpenders {
+    /// Hiçbiri — push transfer ile ödeyen kontrat (Arbitraj.sol)
+    None,
+    /// Yalnızca flash loan sağlayıcı (geri ödemeyi transferFrom ile çeker)
+    FlashLender,
+    /// Yalnızca havuzlar
+    Pools,
+    /// Havuzlar + flash loan sağlayıcı
+    All,
+}
+
+impl RequiredSpenders {
+    pub fn requires(self, kind: SpenderKind) -> bool {
+        matches!(
+            (self, kind),
+            (RequiredSpenders::All, _)
+                | (RequiredSpenders::Pools, SpenderKind::Pool)
+                | (RequiredSpenders::FlashLender, SpenderKind::FlashLender)
+        )
+    }
+}
+
+impl std::fmt::Display for RequiredSpenders {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequiredSpenders::None => write!(f, "none"),
+            RequiredSpenders::FlashLender => write!(f, "flash_lender"),
+            RequiredSpenders::Pools => write!(f, "pools"),
+            RequiredSpenders::All => write!(f, "all"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpenderKind {
+    Pool,
+    FlashLender,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Denetim Planı + Sonuç Değerlendirmesi (saf fonksiyonlar)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek (token, harcayıcı) çifti için okunan onay
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllowanceCheck {
+    pub token: Address,
+    pub token_label: String,
+    pub spender: Address,
+    pub spender_label: String,
+    pub kind: SpenderKind,
+    /// MAX_TRADE_SIZE_WETH karşılığı (token'ın en küçük biriminde)
+    pub required: U256,
+    /// Okunan allowance — çağrı başarısız / decode edilemezse None
+    pub allowance: Option<U256>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllowanceStatus {
+    Ok,
+    /// Sıfırdan büyük ama gereken miktarın altında
+    Shortfall,
+    /// Sıfır
+    Missing,
+    /// Okunamadı (RPC / revert)
+    Unreadable,
+}
+
+impl AllowanceCheck {
+    pub fn status(&self) -> AllowanceStatus {
+        match self.allowance {
+            None => AllowanceStatus::Unreadable,
+            Some(a) if a.is_zero() => AllowanceStatus::Missing,
+            Some(a) if a < self.required => AllowanceStatus::Shortfall,
+            Some(_) => AllowanceStatus::Ok,
+        }
+    }
+
+    /// Rapor etiketi: "USDC→uniswap-WETH/USDC"
+    pub fn label(&self) -> String {
+        format!("{}→{}", self.token_label, self.spender_label)
+    }
+}
+
+/// Havuz adından ("{dex}-{BASE}/{QUOTE}") token sembollerini çıkar;
+/// çözülemezse kısa adres kullanılır.
+fn token_labels(pool: &PoolConfig) -> (String, String) {
+    let symbols = pool
+        .name
+        .rsplit_once('-')
+        .and_then(|(_, pair)| pair.split_once('/'))
+        .filter(|(base, quote)| !base.is_empty() && !quote.is_empty());
+    match symbols {
+        Some((base, quote)) => (base.to_string(), quote.to_string()),
+        None => (
+            short_address(pool.base_token_address),
+            short_address(pool.quote_token_address),
+        ),
+    }
+}
+
+fn short_address(addr: Address) -> String {
+    let hex = addr.to_string();
+    format!("{}…{}", &hex[..6], &hex[hex.len() - 4..])
+}
+
+/// WETH cinsinden miktarı token'ın en küçük birimine çevir.
+/// `quote_per_weth` yalnızca quote token için kullanılır (havuz fiyatı).
+pub fn required_amount(max_trade_weth: f64, decimals: u8, quote_per_weth: Option<f64>) -> U256 {
+    let units = max_trade_weth * quote_per_weth.unwrap_or(1.0) * 10f64.powi(decimals as i32);
+    if units.is_finite() && units > 0.0 {
+        U256::from(units.ceil() as u128)
+    } else {
+        U256::ZERO
+    }
+}
+
+/// Okunacak (token, harcayıcı) çiftlerini üret. Aynı çift tekrar edilmez;
+/// gereken miktar çiftin göründüğü havuzlar arasında en büyüğüdür.
+/// `prices[i]` = havuz i'nin WETH başına quote fiyatı (yoksa quote atlanmaz,
+/// gereken miktar 0 kalır — yalnızca sıfır onay yakalanır).
+pub fn plan_checks(
+    pools: &[PoolConfig],
+    prices: &[Option<f64>],
+    flash_lender: Address,
+    max_trade_weth: f64,
+) -> Vec<AllowanceCheck> {
+    let mut checks: Vec<AllowanceCheck> = Vec::new();
+    let mut upsert = |check: AllowanceCheck| {
+        match checks
+            .iter_mut()
+            .find(|c| c.token == check.token && c.spender == check.spender)
+        {
+            Some(existing) => existing.required = existing.required.max(check.required),
+            None => checks.push(check),
+        }
+    };
+
+    for (i, pool) in pools.iter().enumerate() {
+        let (base_label, quote_label) = token_labels(pool);
+        let (base_decimals, quote_decimals) = if pool.token0_is_weth {
+            (pool.token0_decimals, pool.token1_decimals)
+        } else {
+            (pool.token1_decimals, pool.token0_decimals)
+        };
+        let price = prices.get(i).copied().flatten().filter(|p| *p > 0.0);
+        let tokens = [
+            (pool.base_token_address, base_label, required_amount(max_trade_weth, base_decimals, None)),
+            (
+                pool.quote_token_address,
+                quote_label,
+                price
+                    .map(|p| required_amount(max_trade_weth, quote_decimals, Some(p)))
+                    .unwrap_or(U256::ZERO),
+            ),
+        ];
+
+        for (token, token_label, required) in tokens {
+            for (spender, spender_label, kind) in [
+                (pool.address, pool.name.clone(), SpenderKind::Pool),
+                (flash_lender, "FlashLender".to_string(), SpenderKind::FlashLender),
+            ] {
+                upsert(AllowanceCheck {
+                    token,
+                    token_label: token_label.clone(),
+                    spender,
+                    spender_label,
+                    kind,
+                    required,
+                    allowance: None,
+                });
+            }
+        }
+    }
+    checks
+}
+
+/// Multicall3 sonucunu allowance'a çevir (başarısız / kısa veri → None)
+pub fn decode_allowance(success: bool, data: &[u8]) -> Option<U256> {
+    if !success {
+        return None;
+    }
+    IERC20Allowance::allowanceCall::abi_decode_returns(data).ok()
+}
+
+/// Tek denetim turunun sonucu
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalReport {
+    pub checks: Vec<AllowanceCheck>,
+    pub required_spenders: RequiredSpenders,
+    /// hard modda zorunlu bir onay sıfırdı → yürütme gölge modda
+    pub downgraded: bool,
+}
+
+impl ApprovalReport {
+    pub fn new(checks: Vec<AllowanceCheck>, required: RequiredSpenders, mode: ApprovalAuditMode) -> Self {
+        let mut report = Self { checks, required_spenders: required, downgraded: false };
+        report.downgraded = mode == ApprovalAuditMode::Hard && !report.missing().is_empty();
+        report
+    }
+
+    fn required_with(&self, status: AllowanceStatus) -> Vec<String> {
+        self.checks
+            .iter()
+            .filter(|c| self.required_spenders.requires(c.kind) && c.status() == status)
+            .map(AllowanceCheck::label)
+            .collect()
+    }
+
+    /// Zorunlu ve sıfır olan onaylar
+    pub fn missing(&self) -> Vec<String> {
+        self.required_with(AllowanceStatus::Missing)
+    }
+
+    /// Zorunlu, sıfırdan büyük ama MAX_TRADE_SIZE karşılığının altında
+    pub fn shortfalls(&self) -> Vec<String> {
+        self.required_with(AllowanceStatus::Shortfall)
+    }
+
+    /// Zorunlu ama okunamayan onaylar
+    pub fn unreadable(&self) -> Vec<String> {
+        self.required_with(AllowanceStatus::Unreadable)
+    }
+
+    /// İstatistik kutusu satırı: "OK" / "MISSING: USDC→PoolB"
+    pub fn status_line(&self) -> String {
+        let missing = self.missing();
+        if !missing.is_empty() {
+            let suffix = if self.downgraded { " (execution → shadow)" } else { "" };
+            return format!("MISSING: {}{}", missing.join(", "), suffix);
+        }
+        let shortfalls = self.shortfalls();
+        if !shortfalls.is_empty() {
+            return format!("SHORTFALL: {}", shortfalls.join(", "));
+        }
+        let unreadable = self.unreadable();
+        if !unreadable.is_empty() {
+            return format!("UNREADABLE: {}", unreadable.join(", "));
+        }
+        if self.required_spenders == RequiredSpenders::None {
+            "OK (none required)".to_string()
+        } else {
+            "OK".to_string()
+        }
+    }
+
+    /// Yürütmeyi engelleyen gerekçe (yalnızca hard modda eksik onay varken)
+    pub fn downgrade_reason(&self) -> Option<String> {
+        self.downgraded
+            .then(|| format!("missing approval: {}", self.missing().join(", ")))
+    }
+}
+
+/// Önceki rapora göre YENİ eksik onaylar (bildirim yalnızca geçişte)
+pub fn newly_missing(previous: Option<&ApprovalReport>, current: &ApprovalReport) -> Vec<String> {
+    let before = previous.map(ApprovalReport::missing).unwrap_or_default();
+    current
+        .missing()
+        .into_iter()
+        .filter(|label| !before.contains(label))
+        .collect()
+}
+
+/// Ana döngü ile denetim görevi arasında paylaşılan son rapor
+pub type SharedApprovalReport = Arc<RwLock<Option<ApprovalReport>>>;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// On-Chain Okuma + Raporlama
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tüm allowance'ları tek Multicall3 çağrısıyla oku (sırası `checks` ile aynı).
+async fn read_allowances<P: Provider + Sync>(
+    provider: &P,
+    owner: Address,
+    checks: &[AllowanceCheck],
+) -> BotResult<Vec<Option<U256>>> {
+    let calls: Vec<IMulticall3::Call3> = checks
+        .iter()
+        .map(|c| IMulticall3::Call3 {
+            target: c.token,
+            allowFailure: true,
+            callData: Bytes::from(
+                IERC20Allowance::allowanceCall { owner, spender: c.spender }.abi_encode(),
+            ),
+        })
+        .collect();
+
+    let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
+    let budget = CallBudget::new("multicall", AUDIT_TIMEOUT_MS);
+    let results = with_rpc_retry("Approval audit Multicall3", &budget, || {
+        let call = multicall.aggregate3(calls.clone());
+        async move {
+            call.call().await.map_err(|e| BotError::from_call("Approval audit Multicall3", e))
+        }
+    })
+    .await?;
+
+    Ok((0..checks.len())
+        .map(|i| {
+            results
+                .get(i)
+                .and_then(|r| decode_allowance(r.success, &r.returnData))
+        })
+        .collect())
+}
+
+/// Raporu konsola yaz — her satır bir (token, harcayıcı) çifti
+fn print_report(report: &ApprovalReport, mode: ApprovalAuditMode) {
+    println!(
+        "\n  {} Approval audit (mode={}, required={}, {} allowance(s)):",
+        "🔐".yellow(),
+        mode,
+        report.required_spenders,
+        report.checks.len(),
+    );
+    for check in &report.checks {
+        let required = report.required_spenders.requires(check.kind);
+        let allowance = check
+            .allowance
+            .map(|a| a.to_string())
+            .unwrap_or_else(|| "?".into());
+        let line = format!(
+            "{} — allowance {} / needed {}{}",
+            check.label(),
+            allowance,
+            check.required,
+            if required { "" } else { " (informational)" },
+        );
+        match (required, check.status()) {
+            (_, AllowanceStatus::Ok) => println!("  {}   {}", "✅".green(), line),
+            (false, _) => println!("  {}   {}", "ℹ️".blue(), line),
+            (true, AllowanceStatus::Missing) => {
+                eprintln!("  {}   {}", "🚫".red(), line.red().bold())
+            }
+            (true, _) => eprintln!("  {}   {}", "⚠️".yellow(), line.yellow()),
+        }
+    }
+
+    let status = report.status_line();
+    if !report.missing().is_empty() {
+        let action = if report.downgraded {
+            "execution DOWNGRADED to shadow mode (APPROVAL_AUDIT_MODE=hard)"
+        } else {
+            "execution continues (APPROVAL_AUDIT_MODE=soft) — trades through these spenders WILL revert"
+        };
+        eprintln!(
+            "  {} {}",
+            "🚨".red(),
+            format!("[ApprovalAudit] {} — {}", status, action).red().bold()
+        );
+    } else if !report.shortfalls().is_empty() {
+        eprintln!(
+            "  {} {}",
+            "🚨".red(),
+            format!("[ApprovalAudit] {} — below MAX_TRADE_SIZE_WETH requirement", status)
+                .red()
+                .bold()
+        );
+    } else {
+        println!("  {} [ApprovalAudit] approvals: {}", "✅".green(), status);
+    }
+}
+
+/// Tek denetim turu: planla → oku → değerlendir → raporla → paylaşılan raporu güncelle.
+/// Okuma başarısızsa önceki rapor korunur (geçici RPC hatası gölge moda düşürmez).
+#[allow(clippy::too_many_arguments)]
+pub async fn run_audit<P: Provider + Sync>(
+    provider: &P,
+    contract: Address,
+    flash_lender: Address,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    max_trade_weth: f64,
+    mode: ApprovalAuditMode,
+    required: RequiredSpenders,
+    shared: &SharedApprovalReport,
+    telegram: &Option<TelegramSender>,
+) {
+    let prices: Vec<Option<f64>> = states
+        .iter()
+        .map(|s| Some(s.load().eth_price_usd))
+        .collect();
+    let mut checks = plan_checks(pools, &prices, flash_lender, max_trade_weth);
+
+    match read_allowances(provider, contract, &checks).await {
+        Ok(allowances) => {
+            for (check, allowance) in checks.iter_mut().zip(allowances) {
+                check.allowance = allowance;
+            }
+        }
+        Err(e) => {
+            eprintln!("  ⚠️ [ApprovalAudit] allowance read failed: {} — keeping previous result", e);
+            return;
+        }
+    }
+
+    let report = ApprovalReport::new(checks, required, mode);
+    print_report(&report, mode);
+
+    let previous = shared.read().clone();
+    let newly = newly_missing(previous.as_ref(), &report);
+    if !newly.is_empty() {
+        if let Some(tg) = telegram {
+            tg.send(TelegramMessage::ApprovalMissing {
+                missing: newly,
+                downgraded: report.downgraded,
+            });
+        }
+    } else if previous.as_ref().is_some_and(|p| !p.missing().is_empty()) && report.missing().is_empty() {
+        println!("  {} [ApprovalAudit] missing approvals restored — execution resumes", "✅".green());
+    }
+    *shared.write() = Some(report);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DexType, FeeTier};
+    use alloy::primitives::address;
+
+    const WETH: Address = address!("4200000000000000000000000000000000000006");
+    const USDC: Address = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+    const POOL_A: Address = address!("00000000000000000000000000000000000000a1");
+    const POOL_B: Address = address!("00000000000000000000000000000000000000b2");
+    const LENDER: Address = address!("A238Dd80C259a72e81d7e4664a9801593F98d1c5");
+
+    fn pool(address: Address, name: &str) -> PoolConfig {
+        PoolConfig {
+            address,
+            name: name.into(),
+            fee: FeeTier::from_bps(5),
+            token0_decimals: 18,
+            token1_decimals: 6,
+            dex: DexType::UniswapV3,
+            token0_is_weth: true,
+            tick_spacing: 10,
+            quote_token_address: USDC,
+            base_token_address: WETH,
+        }
+    }
+
+    fn planned() -> Vec<AllowanceCheck> {
+        let pools = vec![
+            pool(POOL_A, "uniswap-WETH/USDC"),
+            pool(POOL_B, "aerodrome-slipstream-WETH/USDC"),
+        ];
+        plan_checks(&pools, &[Some(2500.0), Some(2510.0)], LENDER, 2.0)
+    }
+
+    fn word(value: U256) -> Vec<u8> {
+        value.to_be_bytes::<32>().to_vec()
+    }
+
+    /// Tüm onaylar `value`, yalnızca (token, spender) eşleşeni `override_value`
+    fn fill(
+        checks: &mut [AllowanceCheck],
+        value: U256,
+        target: Option<(Address, Address, U256)>,
+    ) {
+        for c in checks.iter_mut() {
+            let v = match target {
+                Some((t, s, v)) if c.token == t && c.spender == s => v,
+                _ => value,
+            };
+            c.allowance = decode_allowance(true, &word(v));
+        }
+    }
+
+    #[test]
+    fn test_decode_allowance() {
+        assert_eq!(decode_allowance(true, &word(U256::from(1234u64))), Some(U256::from(1234u64)));
+        assert_eq!(decode_allowance(true, &word(U256::MAX)), Some(U256::MAX));
+        // Revert veya kısa veri → okunamadı
+        assert_eq!(decode_allowance(false, &word(U256::from(1u64))), None);
+        assert_eq!(decode_allowance(true, &[0u8; 4]), None);
+    }
+
+    #[test]
+    fn test_plan_dedups_and_converts_max_trade_size() {
+        let checks = planned();
+        // 2 token × (2 havuz + 1 lender) — lender çiftleri tekrarlanmaz
+        assert_eq!(checks.len(), 6);
+
+        let weth_pool = checks.iter().find(|c| c.token == WETH && c.spender == POOL_A).unwrap();
+        assert_eq!(weth_pool.required, U256::from(2_000_000_000_000_000_000u128));
+        assert_eq!(weth_pool.label(), "WETH→uniswap-WETH/USDC");
+
+        // Lender için USDC gereksinimi havuzlar arasında en büyüğü: 2 × 2510 × 1e6
+        let usdc_lender = checks.iter().find(|c| c.token == USDC && c.spender == LENDER).unwrap();
+        assert_eq!(usdc_lender.required, U256::from(5_020_000_000u64));
+        assert_eq!(usdc_lender.kind, SpenderKind::FlashLender);
+
+        let usdc_b = checks.iter().find(|c| c.token == USDC && c.spender == POOL_B).unwrap();
+        assert_eq!(usdc_b.token_label, "USDC");
+    }
+
+    #[test]
+    fn test_threshold_comparison() {
+        let mut check = planned().remove(0);
+        check.allowance = Some(check.required);
+        assert_eq!(check.status(), AllowanceStatus::Ok);
+        check.allowance = Some(check.required - U256::from(1u64));
+        assert_eq!(check.status(), AllowanceStatus::Shortfall);
+        check.allowance = Some(U256::ZERO);
+        assert_eq!(check.status(), AllowanceStatus::Missing);
+        check.allowance = None;
+        assert_eq!(check.status(), AllowanceStatus::Unreadable);
+    }
+
+    #[test]
+    fn test_hard_mode_downgrades_on_missing_required_approval() {
+        let mut checks = planned();
+        fill(&mut checks, U256::MAX, Some((USDC, POOL_B, U256::ZERO)));
+
+        let hard = ApprovalReport::new(checks.clone(), RequiredSpenders::Pools, ApprovalAuditMode::Hard);
+        assert!(hard.downgraded);
+        assert_eq!(hard.missing(), vec!["USDC→aerodrome-slipstream-WETH/USDC".to_string()]);
+        assert!(hard.downgrade_reason().unwrap().contains("USDC→aerodrome-slipstream-WETH/USDC"));
+        assert!(hard.status_line().starts_with("MISSING: USDC→aerodrome-slipstream-WETH/USDC"));
+
+        // soft: raporlar ama düşürmez
+        let soft = ApprovalReport::new(checks.clone(), RequiredSpenders::Pools, ApprovalAuditMode::Soft);
+        assert!(!soft.downgraded);
+        assert_eq!(soft.downgrade_reason(), None);
+        assert_eq!(soft.missing().len(), 1);
+
+        // Sıfır onay zorunlu olmayan türdeyse (yalnızca lender zorunlu) → OK
+        let lender_only =
+            ApprovalReport::new(checks, RequiredSpenders::FlashLender, ApprovalAuditMode::Hard);
+        assert!(!lender_only.downgraded);
+        assert_eq!(lender_only.status_line(), "OK");
+    }
+
+    #[test]
+    fn test_shortfall_and_unreadable_do_not_downgrade() {
+        let mut checks = planned();
+        fill(&mut checks, U256::MAX, Some((WETH, LENDER, U256::from(1u64))));
+        let report = ApprovalReport::new(checks.clone(), RequiredSpenders::All, ApprovalAuditMode::Hard);
+        assert!(!report.downgraded);
+        assert_eq!(report.status_line(), "SHORTFALL: WETH→FlashLender");
+
+        fill(&mut checks, U256::MAX, None);
+        checks[0].allowance = None;
+        let report = ApprovalReport::new(checks, RequiredSpenders::All, ApprovalAuditMode::Hard);
+        assert!(!report.downgraded);
+        assert!(report.status_line().starts_with("UNREADABLE"));
+    }
+
+    #[test]
+    fn test_default_requires_nothing() {
+        // Push transfer ödeyen kontrat: sıfır onaylar yalnızca bilgi amaçlı
+        let mut checks = planned();
+        fill(&mut checks, U256::ZERO, None);
+        let report = ApprovalReport::new(checks, RequiredSpenders::None, ApprovalAuditMode::Hard);
+        assert!(!report.downgraded);
+        assert_eq!(report.status_line(), "OK (none required)");
+    }
+
+    #[test]
+    fn test_notification_only_on_transition_to_missing() {
+        let mut checks = planned();
+        fill(&mut checks, U256::MAX, Some((USDC, POOL_B, U256::ZERO)));
+        let first = ApprovalReport::new(checks.clone(), RequiredSpenders::Pools, ApprovalAuditMode::Soft);
+        assert_eq!(newly_missing(None, &first).len(), 1);
+        // Aynı eksiklik tekrar bildirilmez
+        assert!(newly_missing(Some(&first), &first).is_empty());
+
+        fill(&mut checks, U256::MAX, None);
+        let restored = ApprovalReport::new(checks, RequiredSpenders::Pools, ApprovalAuditMode::Soft);
+        assert!(newly_missing(Some(&first), &restored).is_empty());
+        assert_eq!(newly_missing(Some(&restored), &first).len(), 1);
+    }
+}
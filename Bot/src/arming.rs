@@ -0,0 +1,463 @@
+// ============================================================================
+//  ARMING v1.0 — Canlı Yürütme İçin Diskte Kurma Dosyası
+//
+//  EXECUTION_ENABLED=true önceki bir denemeden .env'de unutulabilir. Canlı
+//  yürütme bu yüzden ikinci, bilinçli bir adım ister: geçerli bir kurma
+//  dosyası (ARM_FILE, varsayılan ./ARMED).
+//
+//  ✓ --arm --hours N: kurulan parametreleri gösterir, yürütücü adresi
+//    yazılarak onaylanır; dosyaya yürütücü adresi + bitiş zamanı yazılır
+//  ✓ Başlangıçta dosya geçersizse yürütme kapanır (gölge mod)
+//  ✓ Her yürütme denemesinde dosya yeniden okunur: süre dolunca veya dosya
+//    silinince fırsatlar gölgeye alınır (uzaktan durdurma: rm ARMED)
+//  ✓ Başka bir yürütücü adresi için kurulmuş dosya reddedilir
+//  ✓ Gölge / gözlem modunda dosya hiç okunmaz
+// ============================================================================
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{BotConfig, PoolConfig};
+
+/// Tek seferde kurulabilecek en uzun süre — dosya süresiz bırakılamaz
+pub const MAX_ARM_HOURS: f64 = 168.0;
+
+/// Kurma dosyasının içeriği — JSON olarak yazılır
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArmToken {
+    pub executor: Address,
+    /// RFC 3339 (UTC)
+    pub armed_at: String,
+    /// RFC 3339 (UTC) — bu andan sonra yürütme gölgeye düşer
+    pub expires_at: String,
+    /// Kurulduğu andaki kontrat (teşhis için, doğrulanmaz)
+    pub contract: Option<Address>,
+}
+
+impl ArmToken {
+    pub fn new(
+        executor: Address,
+        contract: Option<Address>,
+        now: DateTime<Utc>,
+        hours: f64,
+    ) -> Self {
+        let expires = now + chrono::Duration::seconds((hours * 3600.0).round() as i64);
+        Self {
+            executor,
+            armed_at: now.to_rfc3339(),
+            expires_at: expires.to_rfc3339(),
+            contract,
+        }
+    }
+
+    fn expiry(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.expires_at)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+}
+
+/// Kurma dosyası kontrolünün sonucu
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArmStatus {
+    Armed {
+        until: DateTime<Utc>,
+    },
+    /// Dosya yok (hiç kurulmadı ya da silindi)
+    Missing,
+    Expired {
+        at: DateTime<Utc>,
+    },
+    /// Dosya başka bir yürütücü adresi için kurulmuş
+    WrongExecutor {
+        armed: Address,
+    },
+    /// Okunamadı / ayrıştırılamadı
+    Invalid(String),
+}
+
+impl ArmStatus {
+    pub fn is_armed(&self) -> bool {
+        matches!(self, Self::Armed { .. })
+    }
+}
+
+impl std::fmt::Display for ArmStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Armed { until } => {
+                write!(f, "armed until {}", until.format("%Y-%m-%d %H:%M UTC"))
+            }
+            Self::Missing => write!(f, "no arm file"),
+            Self::Expired { at } => {
+                write!(f, "arm file expired at {}", at.format("%Y-%m-%d %H:%M UTC"))
+            }
+            Self::WrongExecutor { armed } => {
+                write!(f, "arm file is for another executor ({})", armed)
+            }
+            Self::Invalid(e) => write!(f, "unreadable arm file: {}", e),
+        }
+    }
+}
+
+/// Kurma dosyasını yaz — geçici dosya + rename: yarım dosya okunmaz.
+/// Başkalarının yazabildiği dizin reddedilir (dosyayı değiştiren botu kurar).
+pub fn write_token(path: &Path, token: &ArmToken) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(token).map_err(io::Error::other)?;
+    crate::secure_fs::write_secret(path, (json + "\n").as_bytes())
+}
+
+/// Dosya `executor` için `now` anında kurulu mu?
+pub fn check(path: &Path, executor: Address, now: DateTime<Utc>) -> ArmStatus {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return ArmStatus::Missing,
+        Err(e) => return ArmStatus::Invalid(e.to_string()),
+    };
+    let token: ArmToken = match serde_json::from_str(content.trim()) {
+        Ok(token) => token,
+        Err(e) => return ArmStatus::Invalid(e.to_string()),
+    };
+    if token.executor != executor {
+        return ArmStatus::WrongExecutor {
+            armed: token.executor,
+        };
+    }
+    match token.expiry() {
+        Some(until) if now < until => ArmStatus::Armed { until },
+        Some(at) => ArmStatus::Expired { at },
+        None => ArmStatus::Invalid(format!("bad expires_at '{}'", token.expires_at)),
+    }
+}
+
+/// Kalan süre: "3h12m"
+fn remaining(until: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (until - now).num_minutes().max(0);
+    format!("{}h{:02}m", minutes / 60, minutes % 60)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Oturum kapısı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Canlı oturumda her yürütme denemesinden önce sorulan kurma kapısı
+#[derive(Debug)]
+pub struct ArmingGuard {
+    path: PathBuf,
+    executor: Address,
+    status: ArmStatus,
+}
+
+impl ArmingGuard {
+    /// Yalnızca canlı yürütmede kurulur — gölge / gözlem modunda None
+    /// (dosya hiç okunmaz, davranış değişmez)
+    pub fn for_session(config: &BotConfig, executor: Option<Address>) -> Option<Self> {
+        let executor = executor.filter(|_| config.execution_enabled())?;
+        let path = PathBuf::from(&config.arm_file);
+        let status = check(&path, executor, crate::clock::now());
+        Some(Self {
+            path,
+            executor,
+            status,
+        })
+    }
+
+    pub fn status(&self) -> &ArmStatus {
+        &self.status
+    }
+
+    /// Dosyayı yeniden oku. Kurulu değilse gölge gerekçesini döndürür;
+    /// durum değişimi (silme, süre dolması, yeniden kurma) bir kez yazdırılır.
+    pub fn gate(&mut self, now: DateTime<Utc>) -> Option<String> {
+        let status = check(&self.path, self.executor, now);
+        if status != self.status {
+            if let ArmStatus::Armed { until } = status {
+                println!(
+                    "  {} Arming: {} re-armed until {} ({} left) — live execution resumes",
+                    "🔫".green(),
+                    self.path.display(),
+                    until.format("%Y-%m-%d %H:%M UTC"),
+                    remaining(until, now),
+                );
+            } else {
+                eprintln!(
+                    "  {} {} {} — reverting to shadow mode",
+                    "🛑".red(),
+                    "DISARMED:".red().bold(),
+                    status,
+                );
+            }
+            self.status = status;
+        }
+        (!self.status.is_armed()).then(|| self.status.to_string())
+    }
+
+    /// İstatistik kutusu satırı
+    pub fn status_line(&self, now: DateTime<Utc>) -> String {
+        match self.status {
+            ArmStatus::Armed { until } => format!(
+                "{} ({} left)",
+                until.format("%Y-%m-%d %H:%M UTC"),
+                remaining(until, now)
+            ),
+            ref status => format!("DISARMED — {}", status),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CLI: --arm --hours N
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Onay: yazılan adres yürütücü adresiyle aynı olmalı (büyük/küçük harf serbest)
+fn confirms(answer: &str, executor: Address) -> bool {
+    answer
+        .trim()
+        .parse::<Address>()
+        .is_ok_and(|typed| typed == executor)
+}
+
+/// Kurulan parametreleri göster, adres onayı iste ve kurma dosyasını yaz
+pub fn cli_arm(
+    config: &BotConfig,
+    pools: &[PoolConfig],
+    executor: Address,
+    hours: f64,
+) -> eyre::Result<()> {
+    if !(hours > 0.0 && hours <= MAX_ARM_HOURS) {
+        return Err(eyre::eyre!("--hours must be in (0, {}]", MAX_ARM_HOURS));
+    }
+    let now = crate::clock::now();
+    let token = ArmToken::new(executor, config.contract_address, now, hours);
+    let until = token
+        .expiry()
+        .ok_or_else(|| eyre::eyre!("arm token expiry is not valid RFC 3339: {}", token.expires_at))?;
+    let path = Path::new(&config.arm_file);
+
+    let bar = "═".repeat(66);
+    println!();
+    println!("  {}", bar.red());
+    println!(
+        "  {}",
+        "  ARMING LIVE EXECUTION — REAL FUNDS, REAL TRANSACTIONS"
+            .red()
+            .bold()
+    );
+    println!("  {}", bar.red());
+    println!(
+        "  {} Executor       : {}",
+        "▸".cyan(),
+        executor.to_string().yellow().bold()
+    );
+    println!(
+        "  {} Contract       : {}",
+        "▸".cyan(),
+        config.contract_address.map_or(
+            "(not set — execution stays off)".red().to_string(),
+            |a| a.to_string().yellow().to_string()
+        )
+    );
+    println!(
+        "  {} Chain          : {} (chain id {})",
+        "▸".cyan(),
+        config.chain_profile.display_name(),
+        config.chain_id
+    );
+    println!("  {} Pools ({})      :", "▸".cyan(), pools.len());
+    for pool in pools {
+        println!(
+            "      {} {} ({:.2}% fee)",
+            "·".dimmed(),
+            pool.name,
+            pool.fee_bps() as f64 / 100.0
+        );
+    }
+    println!(
+        "  {} Max Trade Size : {:.4} WETH",
+        "▸".cyan(),
+        config.max_trade_size_weth
+    );
+    println!(
+        "  {} Min Net Profit : {:.6} WETH",
+        "▸".cyan(),
+        config.min_net_profit_weth
+    );
+    println!(
+        "  {} Min ROI        : {}",
+        "▸".cyan(),
+        config.min_profit_roi
+    );
+    println!(
+        "  {} Bribe          : {:.1}%",
+        "▸".cyan(),
+        config.effective_bribe_pct() * 100.0
+    );
+    println!(
+        "  {} Armed Until    : {} ({} from now)",
+        "▸".cyan(),
+        until
+            .format("%Y-%m-%d %H:%M UTC")
+            .to_string()
+            .yellow()
+            .bold(),
+        remaining(until, now)
+    );
+    println!("  {} Arm File       : {}", "▸".cyan(), path.display());
+    if !config.execution_enabled_flag {
+        println!(
+            "  {} EXECUTION_ENABLED=false — the file is written but the bot stays in shadow mode",
+            "ℹ️".blue()
+        );
+    }
+    println!("  {}", bar.red());
+
+    print!("  Type the executor address to arm: ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    if !confirms(&answer, executor) {
+        println!("  {} Address mismatch — NOT armed", "🔒".yellow());
+        return Ok(());
+    }
+
+    write_token(path, &token)?;
+    println!(
+        "  {} Armed until {} — remove {} to disarm a running bot",
+        "🔫".green(),
+        until.format("%Y-%m-%d %H:%M UTC"),
+        path.display()
+    );
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXECUTOR: Address = Address::repeat_byte(0x11);
+    const OTHER: Address = Address::repeat_byte(0x22);
+
+    fn temp_arm_file(tag: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("arb_arming_{}_{}", tag, std::process::id()))
+            .join("ARMED");
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn live_config(arm_file: &Path) -> BotConfig {
+        let mut config = BotConfig::for_tests();
+        config.execution_enabled_flag = true;
+        config.private_key = Some("0x01".into());
+        config.contract_address = Some(Address::repeat_byte(0x33));
+        config.arm_file = arm_file.display().to_string();
+        config
+    }
+
+    #[test]
+    fn test_arm_file_roundtrip_and_validation() {
+        let path = temp_arm_file("roundtrip");
+        let now = at("2026-03-01T08:00:00Z");
+        assert_eq!(check(&path, EXECUTOR, now), ArmStatus::Missing);
+
+        let token = ArmToken::new(EXECUTOR, Some(OTHER), now, 12.0);
+        write_token(&path, &token).unwrap();
+        let written: ArmToken = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, token);
+        assert_eq!(
+            check(&path, EXECUTOR, now),
+            ArmStatus::Armed {
+                until: at("2026-03-01T20:00:00Z")
+            }
+        );
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(check(&path, EXECUTOR, now), ArmStatus::Invalid(_)));
+    }
+
+    #[test]
+    fn test_address_mismatch_rejected() {
+        let path = temp_arm_file("mismatch");
+        let now = at("2026-03-01T08:00:00Z");
+        write_token(&path, &ArmToken::new(OTHER, None, now, 12.0)).unwrap();
+        assert_eq!(
+            check(&path, EXECUTOR, now),
+            ArmStatus::WrongExecutor { armed: OTHER }
+        );
+
+        // --arm onayı: yazılan adres birebir (checksum'dan bağımsız) eşleşmeli
+        assert!(confirms(&format!("{}\n", EXECUTOR), EXECUTOR));
+        assert!(confirms(&EXECUTOR.to_string().to_lowercase(), EXECUTOR));
+        assert!(!confirms(&OTHER.to_string(), EXECUTOR));
+        assert!(!confirms("yes", EXECUTOR));
+    }
+
+    /// Oturum ortasında süre dolması ve dosyanın silinmesi yürütmeyi gölgeye
+    /// alır; yeniden kurma yürütmeyi geri açar
+    #[test]
+    fn test_expiry_and_removal_mid_session() {
+        let path = temp_arm_file("expiry");
+        let armed_at = Utc::now();
+        write_token(&path, &ArmToken::new(EXECUTOR, None, armed_at, 1.0)).unwrap();
+
+        let mut guard = ArmingGuard::for_session(&live_config(&path), Some(EXECUTOR))
+            .expect("live config gets a guard");
+        assert!(guard.status().is_armed());
+        assert_eq!(guard.gate(armed_at + chrono::Duration::minutes(59)), None);
+
+        let reason = guard
+            .gate(armed_at + chrono::Duration::minutes(61))
+            .expect("expired arm file must block execution");
+        assert!(reason.contains("expired"), "{}", reason);
+        assert!(guard.status_line(armed_at).starts_with("DISARMED"));
+
+        let rearmed_at = armed_at + chrono::Duration::minutes(62);
+        write_token(&path, &ArmToken::new(EXECUTOR, None, rearmed_at, 2.0)).unwrap();
+        assert_eq!(guard.gate(rearmed_at), None);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(guard.gate(rearmed_at).as_deref(), Some("no arm file"));
+    }
+
+    /// Gölge ve gözlem modunda kapı yoktur — dosya okunmaz, hiçbir fırsat
+    /// arming gerekçesiyle gölgeye alınmaz
+    #[test]
+    fn test_shadow_and_observation_unaffected() {
+        let path = temp_arm_file("shadow");
+        let mut shadow = live_config(&path);
+        shadow.execution_enabled_flag = false;
+        assert!(ArmingGuard::for_session(&shadow, Some(EXECUTOR)).is_none());
+
+        let mut observe = live_config(&path);
+        observe.private_key = None;
+        assert!(ArmingGuard::for_session(&observe, None).is_none());
+
+        // Canlı yapılandırma dosyasız başlarsa kapı kapalıdır
+        let mut guard = ArmingGuard::for_session(&live_config(&path), Some(EXECUTOR)).unwrap();
+        assert_eq!(guard.status(), &ArmStatus::Missing);
+        assert!(guard.gate(Utc::now()).is_some());
+    }
+
+    #[test]
+    fn test_hours_bounds() {
+        let config = live_config(&temp_arm_file("bounds"));
+        assert!(cli_arm(&config, &[], EXECUTOR, 0.0).is_err());
+        assert!(cli_arm(&config, &[], EXECUTOR, MAX_ARM_HOURS + 1.0).is_err());
+    }
+}
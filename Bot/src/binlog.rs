@@ -0,0 +1,999 @@
+// ============================================================================
+//  BINLOG v1.0 — state_diff / gölge akışları için kompakt ikili format
+//
+//  LOG_FORMAT_BINARY=true iken NDJSON satırları aynı JSON şemasıyla, ama
+//  ikili kodlanarak yazılır (state_diff.bin, shadow_analytics.bin):
+//  ✓ Dosya başlığı: "ARBLOG" + format sürümü — okuyucular başlığı koklar,
+//    JSON ve ikili dosyaları aynı yoldan okur
+//  ✓ Öz-tanımlı değer kodlaması: alan adları kayıtta taşınır (şema tablosu
+//    yok), hex string'ler (U256, hash) ham bayt, EIP-55 adresler 20 bayt,
+//    tamsayılar varint, f64 8 bayt. Kanonik olmayan sayı literalleri
+//    aynen saklanır → ikili → JSON dönüşümü bayt bayt kayıpsız
+//  ✓ Çerçeve: FRAME_RECORDS kayıt deflate ile sıkıştırılır; işaret +
+//    sürüm + uzunluk + checksum. Bozuk çerçeve atlanır, okuyucu bir sonraki
+//    işarette yeniden senkronlanır. Tipik state_diff akışı ~10x küçülür
+//  ✓ Şema evrimi: çerçeve başına sürüm baytı (yeni sürüm çerçevesi atlanır,
+//    sayılır); bilinmeyen alanlar JSON'a aynen döner, tipli okuyucular
+//    (serde) yok sayar
+//
+//  Sınır: bekleyen çerçeve bellekte tutulur — çökmede en fazla
+//  FRAME_RECORDS-1 kayıt kaybolur (Drop / kapanışta flush edilir).
+//
+//  --convert-log <girdi> <çıktı>: ikili → JSONL (mevcut araçlar için),
+//  JSONL → ikili (arşiv).
+// ============================================================================
+
+use alloy::primitives::Address;
+use colored::*;
+use eyre::Result;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Dosya başlığı — okuyucular bu öneki koklar
+const MAGIC: &[u8; 6] = b"ARBLOG";
+
+/// Çerçeve işareti — bozuk bölgeden sonra yeniden senkronlanma noktası
+const FRAME_MARKER: [u8; 2] = [0xA7, 0x1B];
+
+/// Kodlama sürümü. Değer etiketleri yalnızca eklenerek genişler; eski
+/// okuyucu daha yeni sürümlü çerçeveleri atlar ve sayar.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Çerçeve başına kayıt — sıkıştırma oranı ile çökmede kayıp arasında denge
+pub const FRAME_RECORDS: usize = 32;
+
+/// Açılmış çerçevenin üst sınırı (bozuk uzunluk / sıkıştırma bombası koruması)
+const MAX_FRAME_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Bozuk veride özyineleme sınırı
+const MAX_DEPTH: usize = 64;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Değer Modeli
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// JSON değeri — alan sırası ve sayı literalleri korunur (serde_json::Value
+/// alanları sıralar ve u64'ü aşan tamsayıları f64'e düşürür)
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    UInt(u128),
+    /// -(n + 1)
+    NegInt(u128),
+    Float(f64),
+    /// Kanonik biçimde yeniden üretilemeyen sayı literali (ör. "1E5")
+    Number(String),
+    Str(String),
+    /// "0x" + küçük harf hex; `odd` → tek sayıda hane (baştaki 0 eklenmiş)
+    Hex {
+        bytes: Vec<u8>,
+        odd: bool,
+    },
+    /// EIP-55 checksum'lı adres
+    Address([u8; 20]),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+mod tag {
+    pub const NULL: u8 = 0;
+    pub const FALSE: u8 = 1;
+    pub const TRUE: u8 = 2;
+    pub const UINT: u8 = 3;
+    pub const NEG_INT: u8 = 4;
+    pub const FLOAT: u8 = 5;
+    pub const NUMBER: u8 = 6;
+    pub const STR: u8 = 7;
+    pub const HEX_EVEN: u8 = 8;
+    pub const HEX_ODD: u8 = 9;
+    pub const ADDRESS: u8 = 10;
+    pub const ARRAY: u8 = 11;
+    pub const OBJECT: u8 = 12;
+}
+
+/// String'i en kompakt kayıpsız gösterimine sınıflandır
+fn classify_string(s: String) -> Value {
+    let Some(digits) = s.strip_prefix("0x") else {
+        return Value::Str(s);
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Value::Str(s);
+    }
+    if digits.bytes().all(|b| !b.is_ascii_uppercase()) {
+        let odd = digits.len() % 2 == 1;
+        let padded = if odd {
+            format!("0{}", digits)
+        } else {
+            digits.to_string()
+        };
+        if let Ok(bytes) = alloy::primitives::hex::decode(padded) {
+            return Value::Hex { bytes, odd };
+        }
+    }
+    match s.parse::<Address>() {
+        Ok(address) if address.to_checksum(None) == s => Value::Address(address.into_array()),
+        _ => Value::Str(s),
+    }
+}
+
+/// Sayı literalini sınıflandır — yalnızca aynen geri üretilebilenler sayısal
+fn classify_number(literal: &str) -> Value {
+    if literal.contains(['.', 'e', 'E']) {
+        return match literal.parse::<f64>() {
+            Ok(f) if f.is_finite() && format_float(f) == literal => Value::Float(f),
+            _ => Value::Number(literal.to_string()),
+        };
+    }
+    let parsed = match literal.strip_prefix('-') {
+        Some(abs) => abs
+            .parse::<u128>()
+            .ok()
+            .filter(|n| *n > 0 && format!("-{}", n) == literal)
+            .map(|n| Value::NegInt(n - 1)),
+        None => literal
+            .parse::<u128>()
+            .ok()
+            .filter(|n| n.to_string() == literal)
+            .map(Value::UInt),
+    };
+    parsed.unwrap_or_else(|| Value::Number(literal.to_string()))
+}
+
+/// serde_json'un f64 biçimi (yazıcılar satırları serde_json ile üretir)
+fn format_float(f: f64) -> String {
+    serde_json::to_string(&f).unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// JSON Okuma / Yazma (alan sırası ve literaller korunur)
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct JsonParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn parse(src: &'a str) -> std::result::Result<Value, String> {
+        let mut parser = Self { src, pos: 0 };
+        let value = parser.value(0)?;
+        parser.skip_ws();
+        if parser.pos != src.len() {
+            return Err(format!("trailing data at byte {}", parser.pos));
+        }
+        Ok(value)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> std::result::Result<(), String> {
+        self.skip_ws();
+        if self.peek() != Some(byte) {
+            return Err(format!("expected '{}' at byte {}", byte as char, self.pos));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> std::result::Result<Value, String> {
+        if !self.src[self.pos..].starts_with(word) {
+            return Err(format!("invalid literal at byte {}", self.pos));
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn value(&mut self, depth: usize) -> std::result::Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err("nesting too deep".into());
+        }
+        self.skip_ws();
+        match self.peek().ok_or("unexpected end of input")? {
+            b'n' => self.literal("null", Value::Null),
+            b't' => self.literal("true", Value::Bool(true)),
+            b'f' => self.literal("false", Value::Bool(false)),
+            b'"' => Ok(classify_string(self.string()?)),
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                self.skip_ws();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                loop {
+                    items.push(self.value(depth + 1)?);
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Value::Array(items));
+                        }
+                        _ => return Err(format!("expected ',' or ']' at byte {}", self.pos)),
+                    }
+                }
+            }
+            b'{' => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                self.skip_ws();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(fields));
+                }
+                loop {
+                    self.skip_ws();
+                    let key = self.string()?;
+                    self.expect(b':')?;
+                    fields.push((key, self.value(depth + 1)?));
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Value::Object(fields));
+                        }
+                        _ => return Err(format!("expected ',' or '}}' at byte {}", self.pos)),
+                    }
+                }
+            }
+            b'-' | b'0'..=b'9' => {
+                let start = self.pos;
+                while matches!(
+                    self.peek(),
+                    Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                ) {
+                    self.pos += 1;
+                }
+                Ok(classify_number(&self.src[start..self.pos]))
+            }
+            other => Err(format!(
+                "unexpected '{}' at byte {}",
+                other as char, self.pos
+            )),
+        }
+    }
+
+    /// Kaçış dizileri serde_json'a bırakılır — yalnızca string sınırı bulunur
+    fn string(&mut self) -> std::result::Result<String, String> {
+        if self.peek() != Some(b'"') {
+            return Err(format!("expected string at byte {}", self.pos));
+        }
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.peek().ok_or("unterminated string")? {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        let raw = self.src.get(start..self.pos).ok_or("unterminated string")?;
+        serde_json::from_str::<String>(raw).map_err(|e| e.to_string())
+    }
+}
+
+fn write_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::UInt(n) => out.push_str(&n.to_string()),
+        Value::NegInt(n) => {
+            out.push('-');
+            out.push_str(&(n + 1).to_string());
+        }
+        Value::Float(f) => out.push_str(&format_float(*f)),
+        Value::Number(literal) => out.push_str(literal),
+        Value::Str(s) => out.push_str(&serde_json::to_string(s).unwrap_or_default()),
+        Value::Hex { bytes, odd } => {
+            let digits = alloy::primitives::hex::encode(bytes);
+            out.push_str("\"0x");
+            out.push_str(if *odd { &digits[1..] } else { &digits });
+            out.push('"');
+        }
+        Value::Address(bytes) => {
+            out.push('"');
+            out.push_str(&Address::from(*bytes).to_checksum(None));
+            out.push('"');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            out.push('{');
+            for (i, (key, item)) in fields.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key).unwrap_or_default());
+                out.push(':');
+                write_json(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// İkili Kodlama
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn write_varint(out: &mut Vec<u8>, mut n: u128) {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len() as u128);
+    out.extend_from_slice(bytes);
+}
+
+fn encode_value(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.push(tag::NULL),
+        Value::Bool(false) => out.push(tag::FALSE),
+        Value::Bool(true) => out.push(tag::TRUE),
+        Value::UInt(n) => {
+            out.push(tag::UINT);
+            write_varint(out, *n);
+        }
+        Value::NegInt(n) => {
+            out.push(tag::NEG_INT);
+            write_varint(out, *n);
+        }
+        Value::Float(f) => {
+            out.push(tag::FLOAT);
+            out.extend_from_slice(&f.to_le_bytes());
+        }
+        Value::Number(literal) => {
+            out.push(tag::NUMBER);
+            write_bytes(out, literal.as_bytes());
+        }
+        Value::Str(s) => {
+            out.push(tag::STR);
+            write_bytes(out, s.as_bytes());
+        }
+        Value::Hex { bytes, odd } => {
+            out.push(if *odd { tag::HEX_ODD } else { tag::HEX_EVEN });
+            write_bytes(out, bytes);
+        }
+        Value::Address(bytes) => {
+            out.push(tag::ADDRESS);
+            out.extend_from_slice(bytes);
+        }
+        Value::Array(items) => {
+            out.push(tag::ARRAY);
+            write_varint(out, items.len() as u128);
+            for item in items {
+                encode_value(item, out);
+            }
+        }
+        Value::Object(fields) => {
+            out.push(tag::OBJECT);
+            write_varint(out, fields.len() as u128);
+            for (key, item) in fields {
+                write_bytes(out, key.as_bytes());
+                encode_value(item, out);
+            }
+        }
+    }
+}
+
+/// Sınır kontrollü okuyucu — bozuk veride panik yok, None döner
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.buf.len()
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        let b = *self.buf.get(self.pos)?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(n)?;
+        let slice = self.buf.get(self.pos..end)?;
+        self.pos = end;
+        Some(slice)
+    }
+
+    fn varint(&mut self) -> Option<u128> {
+        let mut n = 0u128;
+        for shift in (0..128).step_by(7) {
+            let b = self.byte()?;
+            n |= ((b & 0x7f) as u128).checked_shl(shift)?;
+            if b & 0x80 == 0 {
+                return Some(n);
+            }
+        }
+        None
+    }
+
+    /// Uzunluk önekli alan — kalan bayttan uzunsa bozuk
+    fn prefixed_len(&mut self) -> Option<usize> {
+        let n = usize::try_from(self.varint()?).ok()?;
+        (n <= self.buf.len() - self.pos.min(self.buf.len())).then_some(n)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let n = self.prefixed_len()?;
+        String::from_utf8(self.take(n)?.to_vec()).ok()
+    }
+}
+
+fn decode_value(cur: &mut Cursor<'_>, depth: usize) -> Option<Value> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    Some(match cur.byte()? {
+        tag::NULL => Value::Null,
+        tag::FALSE => Value::Bool(false),
+        tag::TRUE => Value::Bool(true),
+        tag::UINT => Value::UInt(cur.varint()?),
+        tag::NEG_INT => Value::NegInt(cur.varint().filter(|n| *n < u128::MAX)?),
+        tag::FLOAT => Value::Float(f64::from_le_bytes(cur.take(8)?.try_into().ok()?)),
+        tag::NUMBER => Value::Number(cur.string()?),
+        tag::STR => Value::Str(cur.string()?),
+        t @ (tag::HEX_EVEN | tag::HEX_ODD) => {
+            let n = cur.prefixed_len()?;
+            let bytes = cur.take(n)?.to_vec();
+            let odd = t == tag::HEX_ODD;
+            // Tek haneli gösterimin baştaki yarım baytı 0 olmalı
+            if (odd && bytes.first().is_none_or(|b| *b > 0x0f)) || (!odd && bytes.is_empty()) {
+                return None;
+            }
+            Value::Hex { bytes, odd }
+        }
+        tag::ADDRESS => Value::Address(cur.take(20)?.try_into().ok()?),
+        tag::ARRAY => {
+            let n = cur.prefixed_len()?;
+            let mut items = Vec::with_capacity(n);
+            for _ in 0..n {
+                items.push(decode_value(cur, depth + 1)?);
+            }
+            Value::Array(items)
+        }
+        tag::OBJECT => {
+            let n = cur.prefixed_len()?;
+            let mut fields = Vec::with_capacity(n);
+            for _ in 0..n {
+                let key = cur.string()?;
+                fields.push((key, decode_value(cur, depth + 1)?));
+            }
+            Value::Object(fields)
+        }
+        _ => return None,
+    })
+}
+
+/// FNV-1a (32 bit) — çerçeve bütünlüğü için yeterli, bağımlılık gerektirmez
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5u32, |h, b| {
+        (h ^ *b as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Dosya başlığı: MAGIC + oluşturan sürüm
+fn header() -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.push(FORMAT_VERSION);
+    out
+}
+
+/// Kayıt dizisini (varint uzunluk önekli) tek çerçeveye sıkıştır
+fn encode_frame(records: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(records)?;
+    let payload = encoder.finish()?;
+
+    let mut frame = FRAME_MARKER.to_vec();
+    frame.push(FORMAT_VERSION);
+    write_varint(&mut frame, payload.len() as u128);
+    frame.extend_from_slice(&checksum(&payload).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+fn encode_record(line: &str, out: &mut Vec<u8>) -> std::io::Result<()> {
+    let value = JsonParser::parse(line)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let mut record = Vec::new();
+    encode_value(&value, &mut record);
+    write_bytes(out, &record);
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Yazıcı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Ekleme-yalnız ikili log yazıcısı. Kayıtlar bellekte biriktirilir;
+/// FRAME_RECORDS dolunca (veya `flush` / Drop'ta) tek çerçeve olarak eklenir.
+/// Dosya yoksa / boşsa (rotasyon sonrası) başlık yeniden yazılır.
+pub struct BinaryLogWriter {
+    path: PathBuf,
+    pending: Vec<u8>,
+    pending_records: usize,
+}
+
+impl BinaryLogWriter {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            pending: Vec::new(),
+            pending_records: 0,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// JSON satırını kodla ve bekleyen çerçeveye ekle
+    pub fn append_json(&mut self, line: &str) -> std::io::Result<()> {
+        encode_record(line, &mut self.pending)?;
+        self.pending_records += 1;
+        if self.pending_records >= FRAME_RECORDS {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Bekleyen kayıtları çerçeve olarak diske yaz
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        if self.pending_records == 0 {
+            return Ok(());
+        }
+        let frame = encode_frame(&self.pending)?;
+        let mut file = crate::secure_fs::open_append(&self.path)?;
+        if file.metadata()?.len() == 0 {
+            file.write_all(&header())?;
+        }
+        file.write_all(&frame)?;
+        self.pending.clear();
+        self.pending_records = 0;
+        Ok(())
+    }
+}
+
+impl Drop for BinaryLogWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            eprintln!("  ⚠️ {} flush error: {}", self.path.display(), e);
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Okuyucu (format koklanır)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Log dosyasının JSON satırları + okunamayan birim sayısı
+#[derive(Debug, Default)]
+pub struct LogLines {
+    pub lines: Vec<String>,
+    /// İkili: bozuk bölge / desteklenmeyen sürümlü çerçeve / çözülemeyen kayıt.
+    /// JSONL: her zaman 0 (satır ayrıştırma hataları çağıranda sayılır)
+    pub skipped: usize,
+    pub binary: bool,
+}
+
+/// Bayt dizisi ikili log mu?
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Çerçeve ayrıştırma sonucu
+enum Frame {
+    /// Geçerli çerçeve: sonraki konum + açılmış kayıtlar
+    Records(usize, Vec<u8>),
+    /// Sağlam ama daha yeni sürüm — atlanır
+    Unsupported(usize),
+}
+
+fn parse_frame(buf: &[u8], pos: usize) -> Option<Frame> {
+    let mut cur = Cursor { buf, pos };
+    if cur.take(2)? != FRAME_MARKER {
+        return None;
+    }
+    let version = cur.byte()?;
+    let len = cur.prefixed_len()?;
+    let expected = u32::from_le_bytes(cur.take(4)?.try_into().ok()?);
+    let payload = cur.take(len)?;
+    if checksum(payload) != expected {
+        return None;
+    }
+    if version > FORMAT_VERSION {
+        return Some(Frame::Unsupported(cur.pos));
+    }
+    let mut records = Vec::new();
+    DeflateDecoder::new(payload)
+        .take(MAX_FRAME_BYTES)
+        .read_to_end(&mut records)
+        .ok()?;
+    Some(Frame::Records(cur.pos, records))
+}
+
+/// İkili gövdeyi JSON satırlarına çöz. Bozuk bölge bir kez sayılır ve
+/// sonraki geçerli çerçeve işaretine kadar atlanır.
+fn decode_binary(bytes: &[u8]) -> LogLines {
+    let mut out = LogLines {
+        binary: true,
+        ..LogLines::default()
+    };
+    let mut pos = MAGIC.len() + 1;
+    let mut in_bad_region = false;
+    while pos < bytes.len() {
+        match parse_frame(bytes, pos) {
+            Some(Frame::Records(next, records)) => {
+                in_bad_region = false;
+                let mut cur = Cursor::new(&records);
+                while !cur.is_empty() {
+                    let Some(record) = cur.prefixed_len().and_then(|n| cur.take(n)) else {
+                        out.skipped += 1;
+                        break;
+                    };
+                    let mut rec = Cursor::new(record);
+                    match decode_value(&mut rec, 0).filter(|_| rec.is_empty()) {
+                        Some(value) => {
+                            let mut line = String::new();
+                            write_json(&value, &mut line);
+                            out.lines.push(line);
+                        }
+                        None => out.skipped += 1,
+                    }
+                }
+                pos = next;
+            }
+            Some(Frame::Unsupported(next)) => {
+                in_bad_region = false;
+                out.skipped += 1;
+                pos = next;
+            }
+            None => {
+                if !in_bad_region {
+                    out.skipped += 1;
+                    in_bad_region = true;
+                }
+                pos += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Log dosyasını oku — ikili başlık varsa çözülür, yoksa JSONL satırları
+pub fn read_json_lines(path: &Path) -> Result<LogLines> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| eyre::eyre!("{} could not be read: {}", path.display(), e))?;
+    if is_binary(&bytes) {
+        if bytes.get(MAGIC.len()).is_some_and(|v| *v > FORMAT_VERSION) {
+            eprintln!(
+                "  ⚠️ {} was created by a newer log format (v{}) — reading supported frames only",
+                path.display(),
+                bytes[MAGIC.len()],
+            );
+        }
+        return Ok(decode_binary(&bytes));
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    Ok(LogLines {
+        lines: text
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(String::from)
+            .collect(),
+        ..LogLines::default()
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CLI: --convert-log
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// İkili → JSONL veya JSONL → ikili (yön girdiden koklanır).
+/// Var olan çıktı dosyasının üzerine yazılmaz.
+pub fn cli_convert_log(input: &str, output: &str) -> Result<()> {
+    let out_path = Path::new(output);
+    if out_path.exists() {
+        return Err(eyre::eyre!(
+            "{} already exists — refusing to overwrite",
+            output
+        ));
+    }
+    let log = read_json_lines(Path::new(input))?;
+
+    let (written, rejected) = if log.binary {
+        let mut text = String::new();
+        for line in &log.lines {
+            text.push_str(line);
+            text.push('\n');
+        }
+        crate::secure_fs::write(out_path, text)?;
+        (log.lines.len(), 0)
+    } else {
+        let mut writer = BinaryLogWriter::new(out_path);
+        let mut rejected = 0;
+        for line in &log.lines {
+            if writer.append_json(line).is_err() {
+                rejected += 1;
+            }
+        }
+        writer.flush()?;
+        (log.lines.len() - rejected, rejected)
+    };
+
+    let (from, to) = if log.binary {
+        ("binary", "JSONL")
+    } else {
+        ("JSONL", "binary")
+    };
+    let in_size = std::fs::metadata(input).map(|m| m.len()).unwrap_or(0);
+    let out_size = std::fs::metadata(out_path).map(|m| m.len()).unwrap_or(0);
+    println!(
+        "  {} {} → {}: {} record(s) | {} skipped | {} invalid JSON line(s)",
+        "🔁".cyan(),
+        from,
+        to,
+        written,
+        log.skipped,
+        rejected,
+    );
+    println!(
+        "     {} ({} bytes) → {} ({} bytes)",
+        input, in_size, output, out_size,
+    );
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{BlockRecord, PoolScalars};
+    use alloy::primitives::{address, U256};
+
+    fn temp_path(tag: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("arb_binlog_{}_{}.bin", tag, std::process::id()))
+    }
+
+    /// Sentetik state_diff akışı — tekrarlanabilir LCG, fiyatlar yürür,
+    /// likidite ara sıra değişir (canlı oturum benzeri)
+    fn synthetic_state_diff(count: usize) -> Vec<String> {
+        let pools = [
+            address!("d0b53D9277642d899DF5C87A3966A349A798F224"),
+            address!("cDAC0d6c6C59727a65F871236188350531885C43"),
+            address!("4c36388be6f416a29c8d8eee81c771ce6be14b18"),
+            address!("72ab388e2e2f6facef59e3c3fa2c4e29011c2d38"),
+        ];
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            seed >> 11
+        };
+        let mut liquidity = [7_000_000_000_000_000_000u128; 4];
+        let mut price = [2500.0f64; 4];
+        (0..count)
+            .map(|i| {
+                let block = 30_000_000 + i as u64;
+                let changed = 1 + (next() % 4) as usize;
+                let scalars = (0..changed)
+                    .map(|p| {
+                        price[p] *= 1.0 + ((next() % 2001) as f64 - 1000.0) * 1e-7;
+                        if next() % 10 == 0 {
+                            liquidity[p] = 5_000_000_000_000_000_000 + (next() as u128) * 1_000;
+                        }
+                        PoolScalars {
+                            address: pools[p],
+                            sqrt_price_x96: U256::from(next()) << 40,
+                            tick: -198_000 + (next() % 2000) as i32,
+                            liquidity: liquidity[p],
+                            eth_price_usd: price[p],
+                            live_fee_bps: (p == 1).then_some(5),
+                            last_block: block,
+                            is_stale: false,
+                            quarantined: false,
+                            bitmap_block: Some(block - (next() % 5)),
+                            pinned_block: Some(block),
+                        }
+                    })
+                    .collect();
+                let record = BlockRecord {
+                    block,
+                    base_fee: 1_000_000 + next() % 50_000_000,
+                    l1_data_fee_wei: (next() % 1_000_000_000_000) as u128,
+                    last_simulated_gas: Some(150_000 + next() % 50_000),
+                    competitor_txs: (next() % 6) as u32,
+                    full: i % 100 == 0,
+                    shed_rungs: (i % 7) as u8,
+                    pools: scalars,
+                };
+                serde_json::to_string(&record).unwrap()
+            })
+            .collect()
+    }
+
+    fn write_binary(path: &Path, lines: &[String]) {
+        let _ = std::fs::remove_file(path);
+        let mut writer = BinaryLogWriter::new(path);
+        for line in lines {
+            writer.append_json(line).unwrap();
+        }
+        writer.flush().unwrap();
+    }
+
+    /// JSON → ikili → JSON bayt bayt aynı: state_diff satırları (u128
+    /// likidite, U256 hex, checksum'lı adres), gölge benzeri satır ve
+    /// serde_json'un üretmeyeceği literaller (1E5, -0, 1.50, u128 taşması)
+    #[test]
+    fn test_json_binary_json_roundtrip_is_lossless() {
+        let mut lines = synthetic_state_diff(100);
+        lines.push(
+            serde_json::json!({
+                "timestamp": "2026-10-16T12:00:00.123",
+                "buy_pool_addr": "0xd0b53D9277642d899DF5C87A3966A349A798F224",
+                "opportunity_id": "30000000-d0b53d92-cdac0d6c",
+                "expected_profit": 0.00012345,
+                "latency_ms": 12.5,
+                "gas_used": 151_234,
+                "adverse": { "base_profit_wei": "340282366920938463463374607431768211455" },
+                "sim_error": null,
+                "unicode": "ç\"\\\n\u{1F680}",
+                "nested": [[], {}, [-1, i64::MIN, u64::MAX, 1e-7, 2500.0]],
+                "odd_hex": "0xabc",
+                "upper_hex": "0xABCDEF",
+            })
+            .to_string(),
+        );
+        lines.push(r#"{"b":1E5,"a":-0,"c":1.50,"d":"0x","e":340282366920938463463374607431768211456,"f":-18446744073709551617}"#.into());
+
+        let path = temp_path("roundtrip");
+        write_binary(&path, &lines);
+        let log = read_json_lines(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(log.binary);
+        assert_eq!(log.skipped, 0);
+        assert_eq!(log.lines, lines);
+        // Tipli okuyucu ikili kaynaktan aynı kaydı kurar
+        let first: BlockRecord = serde_json::from_str(&log.lines[0]).unwrap();
+        assert_eq!(
+            first,
+            serde_json::from_str::<BlockRecord>(&lines[0]).unwrap()
+        );
+    }
+
+    /// Bozuk çerçeve atlanır ve bir kez sayılır; sonraki çerçeveler ve
+    /// yarım kalmış son çerçeve (çökme) okumayı durdurmaz
+    #[test]
+    fn test_corrupted_frames_are_skipped() {
+        let lines = synthetic_state_diff(FRAME_RECORDS * 3);
+        let path = temp_path("corrupt");
+        write_binary(&path, &lines);
+        let mut bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        // Çerçeve başlangıçlarını bul, ikinci çerçevenin gövdesini boz
+        let starts: Vec<usize> = (MAGIC.len() + 1..bytes.len())
+            .filter(|&i| matches!(parse_frame(&bytes, i), Some(Frame::Records(..))))
+            .collect();
+        assert_eq!(starts.len(), 3);
+        let mid = (starts[1] + starts[2]) / 2;
+        bytes[mid] ^= 0xFF;
+        // Yarım yazılmış dördüncü çerçeve
+        let partial = encode_frame(b"\x02\x0c\x00").unwrap();
+        bytes.extend_from_slice(&partial[..partial.len() - 1]);
+
+        let log = decode_binary(&bytes);
+        assert_eq!(log.skipped, 2, "corrupted frame + truncated tail");
+        let mut expected = lines[..FRAME_RECORDS].to_vec();
+        expected.extend_from_slice(&lines[FRAME_RECORDS * 2..]);
+        assert_eq!(log.lines, expected);
+
+        // Çözülemeyen kayıt (bilinmeyen etiket) yalnızca kendisini düşürür
+        let mut records = Vec::new();
+        encode_record(&lines[0], &mut records).unwrap();
+        write_bytes(&mut records, &[0xEE]);
+        encode_record(&lines[1], &mut records).unwrap();
+        let mut file = header();
+        file.extend(encode_frame(&records).unwrap());
+        let log = decode_binary(&file);
+        assert_eq!(log.lines, lines[..2].to_vec());
+        assert_eq!(log.skipped, 1);
+    }
+
+    /// Daha yeni sürümlü çerçeve atlanır; bilinmeyen alan JSON'a aynen
+    /// döner ve tipli okuyucu onu yok sayar
+    #[test]
+    fn test_schema_evolution_tolerance() {
+        let lines = synthetic_state_diff(2);
+        let mut with_new_field: serde_json::Value = serde_json::from_str(&lines[1]).unwrap();
+        with_new_field["future_field"] = serde_json::json!({ "x": 1 });
+        let new_line = with_new_field.to_string();
+
+        let mut records = Vec::new();
+        encode_record(&lines[0], &mut records).unwrap();
+        let mut future = encode_frame(&records).unwrap();
+        future[2] = FORMAT_VERSION + 1;
+
+        let mut file = header();
+        file.extend(future);
+        let mut records = Vec::new();
+        encode_record(&new_line, &mut records).unwrap();
+        file.extend(encode_frame(&records).unwrap());
+
+        let log = decode_binary(&file);
+        assert_eq!(log.skipped, 1, "newer-version frame skipped");
+        assert_eq!(log.lines, vec![new_line]);
+        let record: BlockRecord = serde_json::from_str(&log.lines[0]).unwrap();
+        assert_eq!(
+            record,
+            serde_json::from_str::<BlockRecord>(&lines[1]).unwrap()
+        );
+    }
+
+    /// 10k kayıtlık sentetik state_diff akışı ~10x küçülür
+    #[test]
+    fn test_binary_stream_is_much_smaller() {
+        let lines = synthetic_state_diff(10_000);
+        let json_bytes: usize = lines.iter().map(|l| l.len() + 1).sum();
+        let path = temp_path("size");
+        write_binary(&path, &lines);
+        let binary_bytes = std::fs::metadata(&path).unwrap().len() as usize;
+        let log = read_json_lines(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(log.lines.len(), 10_000);
+        let ratio = json_bytes as f64 / binary_bytes as f64;
+        assert!(
+            ratio >= 8.0,
+            "json {} B, binary {} B, ratio {:.2}",
+            json_bytes,
+            binary_bytes,
+            ratio
+        );
+    }
+}
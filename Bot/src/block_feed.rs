@@ -0,0 +1,486 @@
+// ============================================================================
+//  BLOCK_FEED v1.0 — Çift Kaynaklı Blok Sinyali (Sequencer Unsafe Head)
+//
+//  Base sequencer'ı unsafe payload'ları genel RPC sağlayıcılarından önce
+//  yayınlar; üçüncü parti sağlayıcıdaki newHeads aboneliği onların yayılım
+//  gecikmesini ekler. Bu modül isteğe bağlı ikinci bir blok sinyali kaynağı
+//  ekler:
+//
+//  ✓ SEQUENCER_WS_URL → newHeads'i daha erken veren uç nokta (op-node unsafe
+//    head WS'i ya da sağlayıcının "fast head" akışı)
+//  ✓ Küçük bir görev başlıkları mpsc kanalı üzerinden ana döngüye iletir;
+//    bağlantı koparsa backoff ile kendi kendine yeniden bağlanır
+//  ✓ Ana döngü (numara, hash) ile tekilleştirir: bir bloğu hangi kaynak önce
+//    getirirse o işlenir, diğer kaynaktan gelen kopya yok sayılır
+//  ✓ Kaynak başına kazanma sayısı ve hızlı kaynağın ortalama öncülük süresi
+//
+//  Değişken tanımsızsa ya da akış hata verirse tek kaynaklı davranış aynen
+//  devam eder — birincil abonelik her zaman ana sinyaldir.
+// ============================================================================
+
+use alloy::primitives::B256;
+use alloy::providers::{Provider, ProviderBuilder, WsConnect};
+use alloy::rpc::types::Header;
+use colored::*;
+use futures_util::StreamExt;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// Sequencer → ana döngü kanal kapasitesi (dolarsa başlık düşürülür —
+/// birincil akış aynı bloğu zaten getirecek)
+pub const SEQUENCER_CHANNEL_CAPACITY: usize = 64;
+
+/// Tekilleştirme penceresi (blok) — daha eski kopyalar zaten anlamsız
+const DEDUP_WINDOW: usize = 64;
+
+/// Yeniden bağlanma backoff sınırları
+const RECONNECT_BASE_MS: u64 = 500;
+const RECONNECT_MAX_MS: u64 = 30_000;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kaynak Etiketi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Blok başlığının geldiği kaynak
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadSource {
+    /// Ana RPC aboneliği (WS/IPC)
+    Primary,
+    /// SEQUENCER_WS_URL akışı
+    Sequencer,
+}
+
+impl HeadSource {
+    fn index(self) -> usize {
+        match self {
+            HeadSource::Primary => 0,
+            HeadSource::Sequencer => 1,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HeadSource::Primary => "primary",
+            HeadSource::Sequencer => "sequencer",
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tekilleştirme (İlk Gelen Kazanır)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// observe() sonucu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadVerdict {
+    /// Blok ilk kez görüldü — işle
+    Process,
+    /// Aynı blok daha önce işlendi — yok say
+    Duplicate,
+}
+
+/// Kaynak başına kazanma ve öncülük istatistikleri
+#[derive(Debug, Clone, Default)]
+pub struct HeadFeedStats {
+    /// Bloğu ilk getiren kaynak sayacı: [primary, sequencer]
+    pub wins: [u64; 2],
+    /// Her iki kaynaktan da gelen bloklarda kazananın toplam öncülüğü (µs)
+    pub lead_us_sum: [u64; 2],
+    /// Öncülük örnek sayısı (her iki kaynaktan gelen bloklar)
+    pub lead_samples: [u64; 2],
+}
+
+impl HeadFeedStats {
+    pub fn wins(&self, source: HeadSource) -> u64 {
+        self.wins[source.index()]
+    }
+
+    /// Kaynağın kazandığı (iki kaynaklı) bloklardaki ortalama öncülük (ms)
+    pub fn avg_lead_ms(&self, source: HeadSource) -> Option<f64> {
+        let i = source.index();
+        (self.lead_samples[i] > 0)
+            .then(|| self.lead_us_sum[i] as f64 / self.lead_samples[i] as f64 / 1000.0)
+    }
+
+    /// İkinci kaynaktan en az bir başlık geldi mi
+    pub fn is_dual(&self) -> bool {
+        self.lead_samples.iter().sum::<u64>() > 0 || self.wins[HeadSource::Sequencer.index()] > 0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SeenHead {
+    number: u64,
+    hash: B256,
+    source: HeadSource,
+    received_at: Instant,
+    /// Diğer kaynaktan kopya geldi ve öncülük kaydedildi
+    paired: bool,
+}
+
+/// (numara, hash) bazlı ilk-gelen-kazanır tekilleştirici.
+/// Aynı numara farklı hash ile gelirse (unsafe head reorg) yeni blok sayılır.
+#[derive(Debug, Default)]
+pub struct HeadDeduplicator {
+    seen: VecDeque<SeenHead>,
+    stats: HeadFeedStats,
+}
+
+impl HeadDeduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stats(&self) -> &HeadFeedStats {
+        &self.stats
+    }
+
+    pub fn observe(
+        &mut self,
+        number: u64,
+        hash: B256,
+        source: HeadSource,
+        received_at: Instant,
+    ) -> HeadVerdict {
+        if let Some(first) = self
+            .seen
+            .iter_mut()
+            .find(|s| s.number == number && s.hash == hash)
+        {
+            // Öncülük yalnızca diğer kaynaktan gelen ilk kopyada ölçülür
+            // (aynı kaynaktan yeniden teslim, örn. reconnect sonrası, sayılmaz)
+            if first.source != source && !first.paired {
+                first.paired = true;
+                let lead = received_at.saturating_duration_since(first.received_at);
+                let i = first.source.index();
+                self.stats.lead_us_sum[i] += lead.as_micros() as u64;
+                self.stats.lead_samples[i] += 1;
+            }
+            return HeadVerdict::Duplicate;
+        }
+
+        self.seen.push_back(SeenHead {
+            number,
+            hash,
+            source,
+            received_at,
+            paired: false,
+        });
+        if self.seen.len() > DEDUP_WINDOW {
+            self.seen.pop_front();
+        }
+        self.stats.wins[source.index()] += 1;
+        HeadVerdict::Process
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Geri Basınç (Biriken Başlıklar → En Yenisi)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// select() sonucu: işlenecek başlık + arada işlenmeden atlanan blok sayısı
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacklogPick<T> {
+    pub head: T,
+    pub skipped: u64,
+}
+
+/// Bir bloğun işlenmesi blok süresini aştığında abonelik kanallarında
+/// başlıklar birikir. Seri işlemek her sonraki kararı bir öncekinden daha
+/// geç verir — bunun yerine biriken başlıklardan yalnızca en yenisi seçilir.
+#[derive(Debug, Default)]
+pub struct BacklogSkipper {
+    /// Son seçilen blok numarası (gerçek zincir numarası)
+    last_selected: Option<u64>,
+}
+
+impl BacklogSkipper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `heads` varış sırasındadır: ilki await ile gelen, kalanlar kanallardan
+    /// beklemeden boşaltılanlar. En yüksek numaralı başlık seçilir (eşitlikte
+    /// ilk gelen — tekilleştiricinin ilk-gelen-kazanır kuralıyla tutarlı).
+    /// Atlanan = son seçilen ile yeni seçilen arasındaki farklı numaralar
+    /// (iki kaynaktan gelen kopyalar bir kez sayılır). Son seçilenden eski
+    /// başlıklar — yavaş kaynağın atlanmış bloğun geç kopyası — işlenmez: None.
+    pub fn select<T>(&mut self, heads: Vec<T>, number: impl Fn(&T) -> u64) -> Option<BacklogPick<T>> {
+        let newest = heads.iter().map(&number).max()?;
+        if self.last_selected.is_some_and(|last| newest < last) {
+            return None;
+        }
+
+        let mut skipped: Vec<u64> = heads
+            .iter()
+            .map(&number)
+            .filter(|&n| n < newest && self.last_selected.is_none_or(|last| n > last))
+            .collect();
+        skipped.sort_unstable();
+        skipped.dedup();
+
+        let head = heads.into_iter().find(|h| number(h) == newest)?;
+        self.last_selected = Some(newest);
+        Some(BacklogPick {
+            head,
+            skipped: skipped.len() as u64,
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sequencer Akış Görevi (Denetimli Yeniden Bağlanma)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// SEQUENCER_WS_URL'den newHeads dinler ve başlıkları ana döngüye iletir.
+/// Hata veya akış kapanmasında exponential backoff ile yeniden bağlanır;
+/// iptal edildiğinde ya da alıcı kapandığında çıkar.
+pub async fn run_sequencer_feed(
+    url: String,
+    tx: mpsc::Sender<Header>,
+    cancel: CancellationToken,
+) {
+    let mut attempt: u32 = 0;
+    loop {
+        let result = tokio::select! {
+            _ = cancel.cancelled() => return,
+            r = forward_heads(&url, &tx, &mut attempt) => r,
+        };
+        if tx.is_closed() {
+            return;
+        }
+        if let Err(e) = result {
+            eprintln!(
+                "  {} Sequencer feed error (primary head source continues): {}",
+                "⚠️".yellow(),
+                e
+            );
+        }
+
+        attempt = attempt.saturating_add(1);
+        let delay_ms = (RECONNECT_BASE_MS << (attempt - 1).min(6)).min(RECONNECT_MAX_MS);
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = tokio::time::sleep(Duration::from_millis(delay_ms)) => {}
+        }
+    }
+}
+
+/// Tek bağlantı ömrü: abone ol, akış bitene kadar ilet.
+/// İlk başlık geldiğinde backoff sayacı sıfırlanır.
+async fn forward_heads(
+    url: &str,
+    tx: &mpsc::Sender<Header>,
+    attempt: &mut u32,
+) -> eyre::Result<()> {
+    let provider = ProviderBuilder::new().connect_ws(WsConnect::new(url)).await?;
+    let mut stream = provider.subscribe_blocks().await?.into_stream();
+    eprintln!("  {} Sequencer head feed connected", "🛰️".cyan());
+
+    while let Some(header) = stream.next().await {
+        *attempt = 0;
+        match tx.try_send(header) {
+            Ok(()) | Err(mpsc::error::TrySendError::Full(_)) => {}
+            Err(mpsc::error::TrySendError::Closed(_)) => return Ok(()),
+        }
+    }
+    Err(eyre::eyre!("sequencer head stream closed"))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_of(number: u64) -> B256 {
+        B256::left_padding_from(&number.to_be_bytes())
+    }
+
+    /// İki sentetik başlık akışını varış zamanına göre birleştirir:
+    /// blok N, primary'de `N*2000 + primary_offset` ms, sequencer'da
+    /// `N*2000 + seq_offset` ms'de gelir.
+    fn merged_streams(
+        blocks: std::ops::Range<u64>,
+        primary_offset_ms: u64,
+        seq_offset_ms: u64,
+    ) -> Vec<(u64, HeadSource, u64)> {
+        let mut events: Vec<(u64, HeadSource, u64)> = blocks
+            .flat_map(|n| {
+                [
+                    (n, HeadSource::Primary, n * 2000 + primary_offset_ms),
+                    (n, HeadSource::Sequencer, n * 2000 + seq_offset_ms),
+                ]
+            })
+            .collect();
+        events.sort_by_key(|&(_, _, at)| at);
+        events
+    }
+
+    fn replay(
+        dedup: &mut HeadDeduplicator,
+        events: &[(u64, HeadSource, u64)],
+    ) -> Vec<(u64, HeadSource)> {
+        let t0 = Instant::now();
+        events
+            .iter()
+            .filter(|&&(n, source, at)| {
+                dedup.observe(n, hash_of(n), source, t0 + Duration::from_millis(at))
+                    == HeadVerdict::Process
+            })
+            .map(|&(n, source, _)| (n, source))
+            .collect()
+    }
+
+    #[test]
+    fn test_faster_source_wins_every_block_once() {
+        let mut dedup = HeadDeduplicator::new();
+        // Sequencer 150ms önde
+        let processed = replay(&mut dedup, &merged_streams(1..11, 200, 50));
+
+        assert_eq!(processed.len(), 10, "Her blok tam bir kez işlenmeli");
+        assert!(processed.iter().all(|&(_, s)| s == HeadSource::Sequencer));
+        assert_eq!(
+            processed.iter().map(|&(n, _)| n).collect::<Vec<_>>(),
+            (1..11).collect::<Vec<_>>()
+        );
+
+        let stats = dedup.stats();
+        assert_eq!(stats.wins(HeadSource::Sequencer), 10);
+        assert_eq!(stats.wins(HeadSource::Primary), 0);
+        let lead = stats.avg_lead_ms(HeadSource::Sequencer).unwrap();
+        assert!((lead - 150.0).abs() < 1e-6, "lead={}", lead);
+        assert!(stats.avg_lead_ms(HeadSource::Primary).is_none());
+    }
+
+    #[test]
+    fn test_mixed_winners_and_missing_blocks() {
+        let mut dedup = HeadDeduplicator::new();
+        // Çift bloklarda sequencer 100ms önde, tek bloklarda primary 40ms önde;
+        // blok 7 sequencer'dan hiç gelmez
+        let mut events: Vec<(u64, HeadSource, u64)> = (1..9u64)
+            .flat_map(|n| {
+                let (p, s) = if n % 2 == 0 { (100, 0) } else { (0, 40) };
+                let mut ev = vec![(n, HeadSource::Primary, n * 2000 + p)];
+                if n != 7 {
+                    ev.push((n, HeadSource::Sequencer, n * 2000 + s));
+                }
+                ev
+            })
+            .collect();
+        events.sort_by_key(|&(_, _, at)| at);
+
+        let processed = replay(&mut dedup, &events);
+        assert_eq!(processed.len(), 8);
+        for &(n, source) in &processed {
+            let expected = if n % 2 == 0 { HeadSource::Sequencer } else { HeadSource::Primary };
+            assert_eq!(source, expected, "block {}", n);
+        }
+
+        let stats = dedup.stats();
+        assert_eq!(stats.wins(HeadSource::Sequencer), 4);
+        assert_eq!(stats.wins(HeadSource::Primary), 4);
+        // Blok 7 tek kaynaklı → öncülük örneği yok
+        assert_eq!(stats.lead_samples, [3, 4]);
+        assert!((stats.avg_lead_ms(HeadSource::Primary).unwrap() - 40.0).abs() < 1e-6);
+        assert!((stats.avg_lead_ms(HeadSource::Sequencer).unwrap() - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reorg_and_redelivery() {
+        let mut dedup = HeadDeduplicator::new();
+        let t0 = Instant::now();
+        let b1 = hash_of(1);
+        let b1_reorg = hash_of(0xdead);
+
+        assert_eq!(dedup.observe(1, b1, HeadSource::Sequencer, t0), HeadVerdict::Process);
+        // Aynı kaynaktan yeniden teslim: kopya, öncülük ölçülmez
+        assert_eq!(dedup.observe(1, b1, HeadSource::Sequencer, t0), HeadVerdict::Duplicate);
+        assert_eq!(dedup.stats().lead_samples, [0, 0]);
+        // Aynı numara, farklı hash → yeni blok
+        assert_eq!(
+            dedup.observe(1, b1_reorg, HeadSource::Primary, t0 + Duration::from_millis(10)),
+            HeadVerdict::Process
+        );
+        // Primary'nin asıl kopyası ve ikinci kopyası → tek öncülük örneği
+        let later = t0 + Duration::from_millis(30);
+        assert_eq!(dedup.observe(1, b1, HeadSource::Primary, later), HeadVerdict::Duplicate);
+        assert_eq!(dedup.observe(1, b1, HeadSource::Primary, later), HeadVerdict::Duplicate);
+        assert_eq!(dedup.stats().lead_samples, [0, 1]);
+    }
+
+    /// Sentetik akış: her işleme turunda kanallarda bekleyen başlıklar
+    /// (kaynak, numara) — yavaş bir blok sonrası patlama halinde gelir.
+    fn drive(skipper: &mut BacklogSkipper, bursts: &[Vec<(HeadSource, u64)>]) -> (Vec<u64>, u64) {
+        let mut processed = Vec::new();
+        let mut skipped_total = 0;
+        for burst in bursts {
+            if let Some(pick) = skipper.select(burst.clone(), |&(_, n)| n) {
+                processed.push(pick.head.1);
+                skipped_total += pick.skipped;
+            }
+        }
+        (processed, skipped_total)
+    }
+
+    #[test]
+    fn test_backlog_burst_jumps_to_newest() {
+        let mut skipper = BacklogSkipper::new();
+        let p = HeadSource::Primary;
+        let bursts = vec![
+            vec![(p, 100)],
+            vec![(p, 101)],
+            // #101 yavaş işlendi (bitmap sync) → 102..105 birikti
+            vec![(p, 102), (p, 103), (p, 104), (p, 105)],
+            vec![(p, 106)],
+            // İkinci gecikme: 107..108
+            vec![(p, 107), (p, 108)],
+        ];
+        let (processed, skipped) = drive(&mut skipper, &bursts);
+
+        assert_eq!(processed, vec![100, 101, 105, 106, 108]);
+        assert_eq!(skipped, 3 + 1, "102,103,104 + 107");
+    }
+
+    #[test]
+    fn test_backlog_dual_source_copies_and_late_arrivals() {
+        let mut skipper = BacklogSkipper::new();
+        let (p, s) = (HeadSource::Primary, HeadSource::Sequencer);
+        let bursts = vec![
+            vec![(s, 10), (p, 10)],
+            // İki kaynaktan kopyalar: 11 ve 12 birer kez sayılır; 13'te sequencer önce
+            vec![(s, 11), (p, 11), (s, 12), (s, 13), (p, 12), (p, 13)],
+            // Primary'nin atlanmış #12 kopyası geç geldi → işlenmez
+            vec![(p, 12)],
+            // Aynı blok yeniden teslim → seçilir, tekilleştirici Duplicate der
+            vec![(p, 13)],
+            vec![(s, 14)],
+        ];
+        let mut picks = Vec::new();
+        let mut skipped = 0;
+        for burst in &bursts {
+            if let Some(pick) = skipper.select(burst.clone(), |&(_, n)| n) {
+                picks.push(pick.head);
+                skipped += pick.skipped;
+            }
+        }
+
+        assert_eq!(picks, vec![(s, 10), (s, 13), (p, 13), (s, 14)]);
+        assert_eq!(skipped, 2, "11 ve 12 — kopyalar tek sayılır, geç #12 sayılmaz");
+        assert!(skipper.select(Vec::<(HeadSource, u64)>::new(), |&(_, n)| n).is_none());
+    }
+
+    #[test]
+    fn test_window_is_bounded() {
+        let mut dedup = HeadDeduplicator::new();
+        let t0 = Instant::now();
+        for n in 0..(DEDUP_WINDOW as u64 * 3) {
+            dedup.observe(n, hash_of(n), HeadSource::Primary, t0);
+        }
+        assert_eq!(dedup.seen.len(), DEDUP_WINDOW);
+    }
+}
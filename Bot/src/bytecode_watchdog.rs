@@ -0,0 +1,565 @@
+// ============================================================================
+//  BYTECODE_WATCHDOG v1.0 — REVM base_db Bytecode Tazelik Bekçisi
+//
+//  initialize_base_db havuz, token ve arbitraj kontratı bytecode'larını
+//  başlangıçta bir kez yükler. Kontrat aynı proxy adresinde yükseltilirse
+//  ya da bir havuz oturum ortasında taşınırsa simülasyon eski kodu
+//  çalıştırmaya devam eder ve zincirdeki hiçbir şeyle uyuşmaz.
+//
+//  Her BYTECODE_RECHECK_BLOCKS blokta (0 = kapalı):
+//  ✓ base_db'deki her adresin kod hash'i zincirle karşılaştırılır —
+//    eth_getProof'un codeHash alanı (bytecode indirilmez); düğüm getProof
+//    desteklemiyorsa eth_getCode + keccak
+//  ✓ Bytecode yalnızca hash değiştiyse indirilir
+//  ✓ Değişiklikte: belirgin log + Telegram, bytecode değiştirilir, base_db
+//    yeniden kurulur, gas kalibrasyonu sıfırlanır
+//  ✓ Arbitraj kontratı değiştiyse başlangıç el sıkışması yeniden koşulur;
+//    geçene kadar fırsatlar gölge loglanır
+//
+//  Tarama arka planda koşar (hot-reload deseni): ana döngü yalnızca biten
+//  görevin raporunu uygular.
+// ============================================================================
+
+use alloy::primitives::{keccak256, Address, B256, KECCAK256_EMPTY};
+use alloy::providers::Provider;
+use colored::*;
+
+use crate::errors::{BotError, BotResult};
+use crate::gas_calibration;
+use crate::simulator::SimulationEngine;
+use crate::telegram::{TelegramMessage, TelegramSender};
+use crate::types::{PoolConfig, SharedPoolState};
+
+/// Başarısız el sıkışmasının yeniden denenmesi için beklenen blok sayısı
+pub const HANDSHAKE_RETRY_BLOCKS: u64 = 50;
+
+/// base_db'deki bytecode'un rolü
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeRole {
+    Pool,
+    Token,
+    /// Arbitraj kontratı — değişirse el sıkışması gerekir
+    Contract,
+}
+
+impl CodeRole {
+    pub fn label(self) -> &'static str {
+        match self {
+            CodeRole::Pool => "pool",
+            CodeRole::Token => "token",
+            CodeRole::Contract => "arbitrage contract",
+        }
+    }
+}
+
+/// base_db'ye yüklenmiş tek bytecode — hash yüklenen koddan hesaplanır
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackedCode {
+    pub address: Address,
+    pub role: CodeRole,
+    pub code_hash: B256,
+}
+
+/// Zincirde hash'i değişmiş bytecode (yeni kod indirilmiş)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeChange {
+    pub address: Address,
+    pub role: CodeRole,
+    pub old_hash: B256,
+    pub new_hash: B256,
+    pub code: Vec<u8>,
+}
+
+/// Tek taramanın sonucu
+#[derive(Debug, Clone, Default)]
+pub struct ScanReport {
+    pub changes: Vec<CodeChange>,
+    /// Hash'i okunabilen adres sayısı
+    pub checked: usize,
+    /// Okunamayan adresler (sonraki taramada tekrar denenir)
+    pub errors: usize,
+    /// Düğüm eth_getProof'u destekliyor mu — sonraki taramaya taşınır
+    pub proof_supported: bool,
+}
+
+/// Uygulanan raporun etkileri
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApplyOutcome {
+    /// Değişen bytecode sayısı
+    pub changed: usize,
+    pub base_db_rebuilt: bool,
+    /// Gas kalibrasyonu sıfırlandı (düşen örnek sayısı ayrıca)
+    pub calibration_reset: bool,
+    pub calibration_samples_dropped: u64,
+    /// Arbitraj kontratı değişti → el sıkışması yeniden gerekli
+    pub handshake_required: bool,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Zincir Okumaları
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Adresin zincirdeki kod hash'i. `proof_supported` iken eth_getProof'un
+/// codeHash'i okunur (kod indirilmez); düğüm yöntemi reddederse bayrak
+/// düşer ve eth_getCode'a geçilir. İkinci alan: indirilmişse kodun kendisi.
+async fn on_chain_code_hash<P: Provider + Sync>(
+    provider: &P,
+    address: Address,
+    proof_supported: &mut bool,
+) -> BotResult<(B256, Option<Vec<u8>>)> {
+    if *proof_supported {
+        match provider.get_proof(address, Vec::new()).await {
+            // Kodsuz / olmayan hesapta bazı düğümler sıfır hash döner
+            Ok(proof) if proof.code_hash == B256::ZERO => return Ok((KECCAK256_EMPTY, None)),
+            Ok(proof) => return Ok((proof.code_hash, None)),
+            Err(alloy::transports::RpcError::ErrorResp(payload)) => {
+                eprintln!(
+                    "  {} [Bytecode] eth_getProof rejected ({}) — falling back to eth_getCode",
+                    "⚠️".yellow(),
+                    payload.message,
+                );
+                *proof_supported = false;
+            }
+            Err(e) => return Err(BotError::from_transport(format!("eth_getProof {}", address), e)),
+        }
+    }
+    let code = fetch_code(provider, address).await?;
+    Ok((keccak256(&code), Some(code)))
+}
+
+async fn fetch_code<P: Provider + Sync>(provider: &P, address: Address) -> BotResult<Vec<u8>> {
+    provider
+        .get_code_at(address)
+        .await
+        .map(|code| code.to_vec())
+        .map_err(|e| BotError::from_transport(format!("eth_getCode {}", address), e))
+}
+
+/// İzlenen her adresin kod hash'ini zincirle karşılaştır; yalnızca hash'i
+/// değişenlerin bytecode'u indirilir.
+pub async fn scan<P: Provider + Sync>(
+    provider: &P,
+    tracked: &[TrackedCode],
+    proof_supported: bool,
+) -> ScanReport {
+    let mut report = ScanReport { proof_supported, ..ScanReport::default() };
+    for entry in tracked {
+        let (hash, code) =
+            match on_chain_code_hash(provider, entry.address, &mut report.proof_supported).await {
+                Ok(read) => read,
+                Err(e) => {
+                    eprintln!("  {} [Bytecode] {} {}: {}", "⚠️".yellow(), entry.role.label(), entry.address, e);
+                    report.errors += 1;
+                    continue;
+                }
+            };
+        report.checked += 1;
+        if hash == entry.code_hash {
+            continue;
+        }
+        let code = match code {
+            Some(code) => code,
+            None => match fetch_code(provider, entry.address).await {
+                Ok(code) => code,
+                Err(e) => {
+                    eprintln!("  {} [Bytecode] {} {}: {}", "⚠️".yellow(), entry.role.label(), entry.address, e);
+                    report.errors += 1;
+                    continue;
+                }
+            },
+        };
+        // İndirilen kod otoritedir (getProof ile getCode arasında değişmiş olabilir)
+        let new_hash = keccak256(&code);
+        if new_hash != entry.code_hash {
+            report.changes.push(CodeChange {
+                address: entry.address,
+                role: entry.role,
+                old_hash: entry.code_hash,
+                new_hash,
+                code,
+            });
+        }
+    }
+    report
+}
+
+/// Arbitraj kontratı el sıkışması: zincirdeki kod boş değil ve simülasyona
+/// yüklenen kodla aynı. Hata → açıklama (yürütme gölgede kalır).
+pub async fn contract_handshake<P: Provider + Sync>(
+    provider: &P,
+    contract: Address,
+    expected_hash: B256,
+) -> Result<(), String> {
+    let code = fetch_code(provider, contract).await.map_err(|e| e.to_string())?;
+    if code.is_empty() {
+        return Err(format!("no code at {}", contract));
+    }
+    let hash = keccak256(&code);
+    if hash != expected_hash {
+        return Err(format!("code hash {} ≠ simulated {}", hash, expected_hash));
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Bekçi Durumu
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Bekleyen kontrat el sıkışması
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingHandshake {
+    contract: Address,
+    expected_hash: B256,
+    /// Bu bloktan itibaren (yeniden) denenebilir
+    next_attempt_block: u64,
+    last_error: Option<String>,
+}
+
+/// Tarama zamanlaması + el sıkışması kapısı (ana döngüye ait)
+#[derive(Debug)]
+pub struct BytecodeWatchdog {
+    recheck_blocks: u64,
+    /// İlk blokta belirlenir — başlangıçta yüklenen kod zaten taze
+    next_check_block: Option<u64>,
+    proof_supported: bool,
+    handshake: Option<PendingHandshake>,
+}
+
+impl BytecodeWatchdog {
+    pub fn new(recheck_blocks: u64) -> Self {
+        Self {
+            recheck_blocks,
+            next_check_block: None,
+            proof_supported: true,
+            handshake: None,
+        }
+    }
+
+    /// Bu blokta tarama başlamalı mı? (0 = kapalı)
+    pub fn due(&mut self, block: u64) -> bool {
+        if self.recheck_blocks == 0 {
+            return false;
+        }
+        match self.next_check_block {
+            Some(next) if block < next => false,
+            Some(_) => {
+                self.next_check_block = Some(block + self.recheck_blocks);
+                true
+            }
+            None => {
+                self.next_check_block = Some(block + self.recheck_blocks);
+                false
+            }
+        }
+    }
+
+    /// Sonraki taramaya verilecek getProof bayrağı
+    pub fn proof_supported(&self) -> bool {
+        self.proof_supported
+    }
+
+    /// Tarama raporunu uygula: belirgin log + Telegram, bytecode'ları değiştir,
+    /// base_db'yi yeniden kur, gas kalibrasyonunu sıfırla; kontrat değiştiyse
+    /// el sıkışmasını `block` itibarıyla beklemeye al.
+    pub fn apply(
+        &mut self,
+        report: ScanReport,
+        sim_engine: &mut SimulationEngine,
+        pools: &[PoolConfig],
+        states: &[SharedPoolState],
+        block: u64,
+        telegram: &Option<TelegramSender>,
+    ) -> ApplyOutcome {
+        self.proof_supported = report.proof_supported;
+        if report.changes.is_empty() {
+            return ApplyOutcome::default();
+        }
+
+        let mut outcome = ApplyOutcome { changed: report.changes.len(), ..ApplyOutcome::default() };
+        let mut labels = Vec::with_capacity(report.changes.len());
+        for change in report.changes {
+            let name = pools
+                .iter()
+                .find(|p| p.address == change.address)
+                .map_or_else(|| change.address.to_string(), |p| p.name.clone());
+            eprintln!(
+                "  {} {}",
+                "🚨".red(),
+                format!(
+                    "[Bytecode] {} {} CODE CHANGED on-chain: {} → {} ({} bytes)",
+                    change.role.label(),
+                    name,
+                    change.old_hash,
+                    change.new_hash,
+                    change.code.len(),
+                )
+                .red()
+                .bold()
+            );
+            labels.push(format!("{} {}", change.role.label(), name));
+            if change.role == CodeRole::Contract {
+                outcome.handshake_required = true;
+                self.handshake = Some(PendingHandshake {
+                    contract: change.address,
+                    expected_hash: change.new_hash,
+                    next_attempt_block: block,
+                    last_error: None,
+                });
+            }
+            sim_engine.replace_bytecode(change.address, change.role, change.code);
+        }
+
+        outcome.base_db_rebuilt = sim_engine.rebuild_base_db(pools, states);
+        if outcome.base_db_rebuilt {
+            eprintln!(
+                "  {} [Bytecode] REVM base_db rebuilt with {} new bytecode(s)",
+                "🔧".yellow(),
+                outcome.changed,
+            );
+        }
+        outcome.calibration_samples_dropped = gas_calibration::invalidate();
+        outcome.calibration_reset = true;
+        eprintln!(
+            "  {} [Bytecode] gas calibration reset ({} samples tied to old code dropped)",
+            "⛽".yellow(),
+            outcome.calibration_samples_dropped,
+        );
+        if outcome.handshake_required {
+            eprintln!(
+                "  {} [Bytecode] arbitrage contract upgraded — execution shadow-only until handshake passes",
+                "👻".yellow(),
+            );
+        }
+
+        if let Some(tg) = telegram {
+            tg.send(TelegramMessage::BytecodeChanged {
+                changed: labels,
+                contract_upgraded: outcome.handshake_required,
+            });
+        }
+        outcome
+    }
+
+    /// El sıkışması bu blokta (yeniden) başlatılmalı mı? → (kontrat, beklenen hash).
+    /// Başlatılan deneme sonuçlanana kadar tekrar verilmez.
+    pub fn handshake_due(&mut self, block: u64) -> Option<(Address, B256)> {
+        let pending = self.handshake.as_mut()?;
+        if block < pending.next_attempt_block {
+            return None;
+        }
+        pending.next_attempt_block = u64::MAX;
+        Some((pending.contract, pending.expected_hash))
+    }
+
+    /// El sıkışması sonucunu işle. Başarı kapıyı açar; hata kapıyı kapalı
+    /// tutar ve HANDSHAKE_RETRY_BLOCKS sonra yeniden dener. Bu arada yeni
+    /// bir yükseltme görüldüyse (farklı hash) eski sonuç yok sayılır.
+    pub fn complete_handshake(&mut self, expected_hash: B256, result: Result<(), String>, block: u64) {
+        let Some(pending) = self.handshake.as_mut().filter(|p| p.expected_hash == expected_hash) else {
+            return;
+        };
+        match result {
+            Ok(()) => {
+                println!(
+                    "  {} [Bytecode] contract handshake passed ({}) — execution re-enabled",
+                    "✅".green(),
+                    expected_hash,
+                );
+                self.handshake = None;
+            }
+            Err(reason) => {
+                eprintln!(
+                    "  {} [Bytecode] contract handshake failed: {} — retry in {} blocks",
+                    "🚫".red(),
+                    reason,
+                    HANDSHAKE_RETRY_BLOCKS,
+                );
+                pending.next_attempt_block = block + HANDSHAKE_RETRY_BLOCKS;
+                pending.last_error = Some(reason);
+            }
+        }
+    }
+
+    /// El sıkışması bekleniyorsa yürütmeyi gölgeye alan gerekçe
+    pub fn execution_block(&self) -> Option<String> {
+        self.handshake.as_ref().map(|pending| match &pending.last_error {
+            Some(error) => format!("contract bytecode changed, handshake failed: {}", error),
+            None => "contract bytecode changed, handshake pending".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DexType, FeeTier, PoolState, StaticPoolData, StaticPoolStore};
+    use alloy::primitives::{address, Bytes};
+    use alloy::providers::ProviderBuilder;
+    use alloy::rpc::types::EIP1186AccountProofResponse;
+    use alloy::transports::mock::Asserter;
+    use arc_swap::ArcSwap;
+    use parking_lot::RwLock;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    const POOL: Address = address!("d0b53D9277642d899DF5C87A3966A349A798F224");
+    const CONTRACT: Address = address!("1111111111111111111111111111111111111111");
+    const CALLER: Address = address!("2222222222222222222222222222222222222222");
+
+    // PUSH1 0 PUSH1 0 RETURN / PUSH1 1 PUSH1 0 RETURN
+    const POOL_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xF3];
+    const CONTRACT_V1: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xF3];
+    const CONTRACT_V2: [u8; 5] = [0x60, 0x01, 0x60, 0x00, 0xF3];
+
+    fn make_pool() -> PoolConfig {
+        PoolConfig {
+            address: POOL,
+            name: "watched".into(),
+            fee: FeeTier::from_bps(5),
+            token0_decimals: 18,
+            token1_decimals: 6,
+            dex: DexType::UniswapV3,
+            token0_is_weth: true,
+            tick_spacing: 10,
+            quote_token_address: address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+            base_token_address: address!("4200000000000000000000000000000000000006"),
+        }
+    }
+
+    fn engine(pools: &[PoolConfig], states: &[SharedPoolState]) -> SimulationEngine {
+        let store: StaticPoolStore = Arc::new(RwLock::new(HashMap::new()));
+        store
+            .write()
+            .insert(POOL, StaticPoolData { bytecode: Some(Arc::new(POOL_CODE.to_vec())) });
+        let mut sim = SimulationEngine::new(8453);
+        sim.cache_bytecodes(pools, &store);
+        sim.set_contract_bytecode(CONTRACT_V1.to_vec());
+        sim.initialize_base_db(pools, states, CALLER, CONTRACT);
+        sim
+    }
+
+    fn proof(code_hash: B256) -> EIP1186AccountProofResponse {
+        EIP1186AccountProofResponse { code_hash, ..Default::default() }
+    }
+
+    /// Yükseltilmiş kontrat: yalnızca değişen kod indirilir, base_db yeni kodla
+    /// kurulur, kalibrasyon sıfırlanır ve el sıkışması kapıyı kapatır
+    #[tokio::test]
+    async fn test_changed_contract_code_rebuilds_and_requires_handshake() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let pools = [make_pool()];
+        let states: Vec<SharedPoolState> = vec![Arc::new(ArcSwap::from_pointee(PoolState::default()))];
+        let mut sim = engine(&pools, &states);
+
+        let tracked = sim.loaded_code();
+        assert_eq!(
+            tracked.iter().map(|t| (t.address, t.role)).collect::<Vec<_>>(),
+            vec![(POOL, CodeRole::Pool), (CONTRACT, CodeRole::Contract)],
+        );
+
+        // Havuz aynı; kontrat yükseltilmiş → yalnızca kontrat için eth_getCode
+        asserter.push_success(&proof(keccak256(POOL_CODE)));
+        asserter.push_success(&proof(keccak256(CONTRACT_V2)));
+        asserter.push_success(&Bytes::from(CONTRACT_V2.to_vec()));
+
+        let report = scan(&provider, &tracked, true).await;
+        assert_eq!((report.checked, report.errors), (2, 0));
+        assert!(report.proof_supported);
+        assert_eq!(report.changes.len(), 1);
+        let change = &report.changes[0];
+        assert_eq!((change.address, change.role), (CONTRACT, CodeRole::Contract));
+        assert_eq!((change.old_hash, change.new_hash), (keccak256(CONTRACT_V1), keccak256(CONTRACT_V2)));
+
+        let mut watchdog = BytecodeWatchdog::new(1000);
+        assert_eq!(watchdog.execution_block(), None);
+        let outcome = watchdog.apply(report, &mut sim, &pools, &states, 5_000, &None);
+        assert_eq!(outcome.changed, 1);
+        assert!(outcome.base_db_rebuilt);
+        assert!(outcome.calibration_reset);
+        assert!(outcome.handshake_required);
+        assert_eq!(sim.base_code(CONTRACT), Some(CONTRACT_V2.to_vec()));
+        assert_eq!(sim.base_code(POOL), Some(POOL_CODE.to_vec()));
+
+        // El sıkışması yeniden tetiklendi; geçene kadar yürütme gölgede
+        assert!(watchdog.execution_block().is_some());
+        let (contract, expected) = watchdog.handshake_due(5_000).expect("handshake must be due");
+        assert_eq!((contract, expected), (CONTRACT, keccak256(CONTRACT_V2)));
+        assert_eq!(watchdog.handshake_due(5_001), None, "attempt in flight");
+
+        asserter.push_success(&Bytes::from(CONTRACT_V2.to_vec()));
+        let result = contract_handshake(&provider, contract, expected).await;
+        assert_eq!(result, Ok(()));
+        watchdog.complete_handshake(expected, result, 5_002);
+        assert_eq!(watchdog.execution_block(), None);
+
+        // Bir sonraki tarama artık değişiklik görmez
+        asserter.push_success(&proof(keccak256(POOL_CODE)));
+        asserter.push_success(&proof(keccak256(CONTRACT_V2)));
+        let report = scan(&provider, &sim.loaded_code(), true).await;
+        assert!(report.changes.is_empty());
+        assert_eq!(watchdog.apply(report, &mut sim, &pools, &states, 6_000, &None), ApplyOutcome::default());
+    }
+
+    /// getProof reddedilirse eth_getCode'a düşülür; bayrak sonraki taramaya taşınır
+    #[tokio::test]
+    async fn test_get_code_fallback_when_proof_unsupported() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let pools = [make_pool()];
+        let states: Vec<SharedPoolState> = vec![Arc::new(ArcSwap::from_pointee(PoolState::default()))];
+        let mut sim = engine(&pools, &states);
+        let tracked = sim.loaded_code();
+
+        // Havuz taşındı (kod boşaldı), kontrat aynı
+        asserter.push_failure_msg("the method eth_getProof does not exist");
+        asserter.push_success(&Bytes::new());
+        asserter.push_success(&Bytes::from(CONTRACT_V1.to_vec()));
+
+        let report = scan(&provider, &tracked, true).await;
+        assert!(!report.proof_supported);
+        assert_eq!((report.checked, report.errors), (2, 0));
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!((report.changes[0].address, report.changes[0].new_hash), (POOL, KECCAK256_EMPTY));
+
+        let mut watchdog = BytecodeWatchdog::new(1000);
+        let outcome = watchdog.apply(report, &mut sim, &pools, &states, 7_000, &None);
+        assert!(outcome.base_db_rebuilt && outcome.calibration_reset);
+        assert!(!outcome.handshake_required);
+        assert!(!watchdog.proof_supported());
+        assert_eq!(watchdog.execution_block(), None);
+        assert_eq!(sim.base_code(POOL), Some(Vec::new()));
+    }
+
+    /// Başarısız el sıkışması kapıyı kapalı tutar ve bekleme sonrası yeniden dener;
+    /// eski bir yükseltmenin sonucu yenisini açamaz
+    #[test]
+    fn test_handshake_retry_and_schedule() {
+        let mut watchdog = BytecodeWatchdog::new(100);
+        assert!(!watchdog.due(10), "first block only anchors the schedule");
+        assert!(!watchdog.due(109));
+        assert!(watchdog.due(110));
+        assert!(!watchdog.due(111));
+        assert!(!BytecodeWatchdog::new(0).due(1_000_000));
+
+        let v2 = keccak256(CONTRACT_V2);
+        watchdog.handshake = Some(PendingHandshake {
+            contract: CONTRACT,
+            expected_hash: v2,
+            next_attempt_block: 200,
+            last_error: None,
+        });
+        assert_eq!(watchdog.handshake_due(199), None);
+        assert_eq!(watchdog.handshake_due(200), Some((CONTRACT, v2)));
+
+        watchdog.complete_handshake(v2, Err("no code".into()), 201);
+        assert!(watchdog.execution_block().unwrap().contains("no code"));
+        assert_eq!(watchdog.handshake_due(201 + HANDSHAKE_RETRY_BLOCKS - 1), None);
+        assert_eq!(watchdog.handshake_due(201 + HANDSHAKE_RETRY_BLOCKS), Some((CONTRACT, v2)));
+
+        watchdog.complete_handshake(keccak256(CONTRACT_V1), Ok(()), 260);
+        assert!(watchdog.execution_block().is_some(), "stale result ignored");
+        watchdog.complete_handshake(v2, Ok(()), 261);
+        assert_eq!(watchdog.execution_block(), None);
+    }
+}
@@ -0,0 +1,520 @@
+// ============================================================================
+//  CALLDATA CODEC — Kontrat fallback() Girdisinin Bot Tarafı Aynası
+//
+//  Kontratta fonksiyon seçici / ABI yok: fallback() ham byte'ları
+//  assembly `calldataload` + `shr` ile okur ve biçimi UZUNLUKTAN seçer
+//  (134 byte = 2 havuz, diğer her uzunluk = multi-hop). Buradaki yerleşim
+//  kontratınkiyle bayt bayt aynı olmak zorunda — kayma, endian ya da
+//  uzunluk farkı yanlış havuza / yanlış miktarla işlem demektir.
+//
+//  ✓ Yalnızca alloy primitives'e bağlı: fuzz hedefi (fuzz/) bu dosyayı
+//    #[path] ile doğrudan derler
+//  ✓ Kontratın kendi çözücüsüyle farkı calldata_diff (REVM) ölçer
+// ============================================================================
+
+use alloy::hex;
+use alloy::primitives::{Address, U256};
+
+/// 2 havuzlu kompakt biçimin tek geçerli uzunluğu (kontrat bu uzunlukta
+/// _executeTwoPool'a, diğer her uzunlukta _executeMultiHop'a gider)
+pub const COMPACT_CALLDATA_LEN: usize = 134;
+
+/// Kontratın kabul ettiği hop aralığı (`hopCount < 3 || hopCount > 4` → revert).
+/// 2 havuzlu rotalar multi-hop değil kompakt biçimle gönderilir.
+pub const MULTI_HOP_MIN_HOPS: usize = 3;
+pub const MULTI_HOP_MAX_HOPS: usize = 4;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Calldata Payload Mühendisliği — 134 Byte Kompakt Kodlama (v9.0 Kontrat)
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Kontrat v9.0 ile uyumlu 134-byte calldata formatı:
+//
+//   Offset  Boy   Alan
+//   ──────  ────  ─────────────────────────────────
+//   0x00    20B   Pool A adresi (UniV3 flash swap)
+//   0x14    20B   Pool B adresi (Slipstream satış)
+//   0x28    20B   owedToken (flash loan'a geri ödenen token adresi)
+//   0x3C    20B   receivedToken (flash loan'dan alınan + slipstream'e ödenen)
+//   0x50    32B   Miktar (uint256, big-endian)
+//   0x70     1B   UniV3 Yön (0=zeroForOne, 1=oneForZero)
+//   0x71     1B   Slipstream Yön (0=zeroForOne, 1=oneForZero)
+//   0x72    16B   minProfit (uint128, big-endian — sandviç koruması)
+//   0x82     4B   deadlineBlock (uint32, big-endian — blok son kullanma)
+//   ──────  ────  ─────────────────────────────────
+//   Toplam: 134B  (v8.0: 130B + 4B deadlineBlock)
+//
+// Gas tasarrufu: ABI'nin 260+ byte'ına karşı ~%48 tasarruf
+// Güvenlik: minProfit + deadlineBlock ile MEV + stale TX koruması
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 134-byte kompakt calldata kodla (kontrat v9.0 uyumlu)
+///
+/// # Parametreler
+/// - `pool_a`: UniV3 havuzu (flash swap kaynağı)
+/// - `pool_b`: Slipstream havuzu (satış hedefi)
+/// - `owed_token`: Flash loan geri ödemesi için token adresi
+/// - `received_token`: Flash loan'dan alınan token adresi
+/// - `amount_in_wei`: İşlem miktarı (uint256, big-endian)
+/// - `uni_direction`: UniV3 yön (0=zeroForOne, 1=oneForZero)
+/// - `aero_direction`: Slipstream yön (0=zeroForOne, 1=oneForZero)
+/// - `min_profit`: Minimum kâr eşiği (uint128, wei cinsinden)
+/// - `deadline_block`: Son geçerli blok numarası (uint32)
+#[allow(clippy::too_many_arguments)]
+pub fn encode_compact_calldata(
+    pool_a: Address,
+    pool_b: Address,
+    owed_token: Address,
+    received_token: Address,
+    amount_in_wei: U256,
+    uni_direction: u8,
+    aero_direction: u8,
+    min_profit: u128,
+    deadline_block: u32,
+) -> Vec<u8> {
+    // Tam 134 byte: 20+20+20+20+32+1+1+16+4
+    let mut calldata = Vec::with_capacity(134);
+
+    // [0x00..0x14] Pool A adresi (UniV3) — 20 byte
+    calldata.extend_from_slice(pool_a.as_slice());
+
+    // [0x14..0x28] Pool B adresi (Slipstream) — 20 byte
+    calldata.extend_from_slice(pool_b.as_slice());
+
+    // [0x28..0x3C] owedToken adresi — 20 byte
+    calldata.extend_from_slice(owed_token.as_slice());
+
+    // [0x3C..0x50] receivedToken adresi — 20 byte
+    calldata.extend_from_slice(received_token.as_slice());
+
+    // [0x50..0x70] Miktar — uint256, 32 byte big-endian
+    calldata.extend_from_slice(&amount_in_wei.to_be_bytes::<32>());
+
+    // [0x70] UniV3 Yön — 1 byte
+    calldata.push(uni_direction);
+
+    // [0x71] Slipstream Yön — 1 byte
+    calldata.push(aero_direction);
+
+    // [0x72..0x82] minProfit — uint128, 16 byte big-endian
+    calldata.extend_from_slice(&min_profit.to_be_bytes());
+
+    // [0x82..0x86] deadlineBlock — uint32, 4 byte big-endian
+    calldata.extend_from_slice(&deadline_block.to_be_bytes());
+
+    debug_assert_eq!(calldata.len(), 134, "Kompakt calldata tam 134 byte olmalı");
+    calldata
+}
+
+/// Kompakt calldata'yı çözümle (test/debug için)
+///
+/// 134 byte → (pool_a, pool_b, owed_token, received_token, amount, uni_dir, aero_dir, min_profit, deadline_block)
+#[allow(dead_code, clippy::type_complexity)]
+pub fn decode_compact_calldata(
+    data: &[u8],
+) -> Option<(Address, Address, Address, Address, U256, u8, u8, u128, u32)> {
+    if data.len() != COMPACT_CALLDATA_LEN {
+        return None;
+    }
+
+    let pool_a = Address::from_slice(&data[0..20]);
+    let pool_b = Address::from_slice(&data[20..40]);
+    let owed_token = Address::from_slice(&data[40..60]);
+    let received_token = Address::from_slice(&data[60..80]);
+    let amount = U256::from_be_bytes::<32>(data[80..112].try_into().ok()?);
+    let uni_direction = data[112];
+    let aero_direction = data[113];
+    let min_profit = u128::from_be_bytes(data[114..130].try_into().ok()?);
+    let deadline_block = u32::from_be_bytes(data[130..134].try_into().ok()?);
+
+    Some((
+        pool_a,
+        pool_b,
+        owed_token,
+        received_token,
+        amount,
+        uni_direction,
+        aero_direction,
+        min_profit,
+        deadline_block,
+    ))
+}
+
+/// Kompakt calldata'yı hex string olarak formatla (log/debug)
+pub fn format_compact_calldata_hex(calldata: &[u8]) -> String {
+    format!("0x{}", hex::encode(calldata))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Multi-Hop Calldata Kodlayıcı (v29.0: Route Engine)
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Multi-hop calldata formatı (değişken uzunluk):
+//
+//   Offset   Boyut   Alan
+//   ─────────────────────────────────────────────────────
+//   0x00      1 B    hopCount (3 veya 4)
+//   0x01     32 B    amount (uint256, big-endian)
+//   0x21     16 B    minProfit (uint128, big-endian)
+//   0x31      4 B    deadlineBlock (uint32, big-endian)
+//
+//   --- Her hop için (hopCount × 21 byte): ---
+//   +0x00    20 B    pool adresi
+//   +0x14     1 B    yön (0x00=zeroForOne, 0x01=oneForZero)
+//
+//   Toplam: 53 + (hopCount × 21) byte
+//   3-hop: 53 + 63 = 116 byte
+//   4-hop: 53 + 84 = 137 byte
+//
+// Kontrat: fallback() uzunluğa bakarak 134-byte legacy mi, multi-hop mu karar verir.
+// hopCount 2 (95 byte) kontratta InvalidCalldataLength — 2 havuz = kompakt biçim.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Multi-hop calldata kodla (değişken uzunluk — 3/4 hop)
+pub fn encode_multi_hop_calldata(
+    pools: &[Address],
+    directions: &[u8],
+    amount_in_wei: U256,
+    min_profit: u128,
+    deadline_block: u32,
+) -> Vec<u8> {
+    let hop_count = pools.len();
+    debug_assert!(
+        (MULTI_HOP_MIN_HOPS..=MULTI_HOP_MAX_HOPS).contains(&hop_count),
+        "Hop sayısı 3-4 arası olmalı"
+    );
+    debug_assert_eq!(
+        pools.len(),
+        directions.len(),
+        "Pool ve direction sayıları eşit olmalı"
+    );
+
+    // Header: 1 + 32 + 16 + 4 = 53 byte
+    // Hops: hopCount × 21 byte
+    let total_size = 53 + hop_count * 21;
+    let mut calldata = Vec::with_capacity(total_size);
+
+    // [0x00] hopCount — 1 byte
+    calldata.push(hop_count as u8);
+
+    // [0x01..0x21] amount — uint256, 32 byte big-endian
+    calldata.extend_from_slice(&amount_in_wei.to_be_bytes::<32>());
+
+    // [0x21..0x31] minProfit — uint128, 16 byte big-endian
+    calldata.extend_from_slice(&min_profit.to_be_bytes());
+
+    // [0x31..0x35] deadlineBlock — uint32, 4 byte big-endian
+    calldata.extend_from_slice(&deadline_block.to_be_bytes());
+
+    // [0x35..] Hop dizisi: her biri 21 byte (20 pool + 1 direction)
+    for (pool, &dir) in pools.iter().zip(directions.iter()) {
+        calldata.extend_from_slice(pool.as_slice()); // 20 byte
+        calldata.push(dir); // 1 byte
+    }
+
+    debug_assert_eq!(
+        calldata.len(),
+        total_size,
+        "Multi-hop calldata boyutu yanlış: beklenen={}, gerçek={}",
+        total_size,
+        calldata.len()
+    );
+
+    calldata
+}
+
+/// Multi-hop calldata çözümle (test/debug için). Kontratla aynı girdileri
+/// reddeder: hopCount 3-4 dışı ya da uzunluk 53 + hopCount×21 değil → None.
+#[allow(dead_code, clippy::type_complexity)]
+pub fn decode_multi_hop_calldata(data: &[u8]) -> Option<(Vec<Address>, Vec<u8>, U256, u128, u32)> {
+    let hop_count = *data.first()? as usize;
+    if !(MULTI_HOP_MIN_HOPS..=MULTI_HOP_MAX_HOPS).contains(&hop_count) {
+        return None;
+    }
+
+    let expected_len = 53 + hop_count * 21;
+    if data.len() != expected_len {
+        return None;
+    }
+
+    let amount = U256::from_be_bytes::<32>(data[1..33].try_into().ok()?);
+    let min_profit = u128::from_be_bytes(data[33..49].try_into().ok()?);
+    let deadline_block = u32::from_be_bytes(data[49..53].try_into().ok()?);
+
+    let mut pools = Vec::with_capacity(hop_count);
+    let mut directions = Vec::with_capacity(hop_count);
+
+    for i in 0..hop_count {
+        let offset = 53 + i * 21;
+        pools.push(Address::from_slice(&data[offset..offset + 20]));
+        directions.push(data[offset + 20]);
+    }
+
+    Some((pools, directions, amount, min_profit, deadline_block))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Calldata Testleri (134-byte v9.0 formatı)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod calldata_tests {
+    use super::*;
+    use alloy::primitives::{address, U256};
+
+    // Sabit test adresleri
+    const POOL_A: Address = address!("d0b53D9277642d899DF5C87A3966A349A798F224");
+    const POOL_B: Address = address!("cDAC0d6c6C59727a65F871236188350531885C43");
+    const WETH: Address = address!("4200000000000000000000000000000000000006");
+    const USDC: Address = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+
+    #[test]
+    fn test_compact_calldata_is_134_bytes() {
+        let amount = U256::from(5_000_000_000_000_000_000u128); // 5 WETH
+
+        let calldata = encode_compact_calldata(
+            POOL_A,
+            POOL_B,
+            WETH,
+            USDC,
+            amount,
+            0x00,
+            0x01,
+            1_000_000u128,
+            99_999_999u32,
+        );
+
+        assert_eq!(calldata.len(), 134, "Kompakt calldata 134 byte olmalı");
+    }
+
+    #[test]
+    fn test_compact_calldata_encode_decode_roundtrip() {
+        let amount = U256::from(25_000_000_000_000_000_000u128); // 25 WETH
+        let min_profit: u128 = 50_000_000; // 50 USDC (6 decimal)
+        let deadline: u32 = 12_345_678;
+
+        // Kodla
+        let calldata = encode_compact_calldata(
+            POOL_A, POOL_B, USDC, WETH, amount, 0x01, 0x00, min_profit, deadline,
+        );
+        assert_eq!(calldata.len(), 134);
+
+        // Çözümle (round-trip)
+        let (
+            dec_a,
+            dec_b,
+            dec_owed,
+            dec_recv,
+            dec_amount,
+            dec_uni,
+            dec_aero,
+            dec_profit,
+            dec_deadline,
+        ) = decode_compact_calldata(&calldata).expect("Decode başarısız");
+
+        assert_eq!(dec_a, POOL_A, "Pool A adresi eşleşmeli");
+        assert_eq!(dec_b, POOL_B, "Pool B adresi eşleşmeli");
+        assert_eq!(dec_owed, USDC, "owedToken eşleşmeli");
+        assert_eq!(dec_recv, WETH, "receivedToken eşleşmeli");
+        assert_eq!(dec_amount, amount, "Miktar eşleşmeli");
+        assert_eq!(dec_uni, 0x01, "UniV3 yön eşleşmeli");
+        assert_eq!(dec_aero, 0x00, "Slipstream yön eşleşmeli");
+        assert_eq!(dec_profit, min_profit, "minProfit eşleşmeli");
+        assert_eq!(dec_deadline, deadline, "deadlineBlock eşleşmeli");
+    }
+
+    #[test]
+    fn test_compact_calldata_byte_layout() {
+        let pool_a = address!("0000000000000000000000000000000000000001");
+        let pool_b = address!("0000000000000000000000000000000000000002");
+        let owed = address!("0000000000000000000000000000000000000003");
+        let recv = address!("0000000000000000000000000000000000000004");
+        let amount = U256::from(1u64);
+        let deadline: u32 = 0x01020304;
+
+        let cd = encode_compact_calldata(
+            pool_a, pool_b, owed, recv, amount, 0x00, 0x01, 0xFF, deadline,
+        );
+
+        // [0..20] = pool_a → son byte 0x01
+        assert_eq!(cd[19], 0x01, "Pool A son byte = 0x01");
+        // [20..40] = pool_b → son byte 0x02
+        assert_eq!(cd[39], 0x02, "Pool B son byte = 0x02");
+        // [40..60] = owedToken → son byte 0x03
+        assert_eq!(cd[59], 0x03, "owedToken son byte = 0x03");
+        // [60..80] = receivedToken → son byte 0x04
+        assert_eq!(cd[79], 0x04, "receivedToken son byte = 0x04");
+        // [80..112] = amount (32 byte big-endian, değer=1, son byte=0x01)
+        assert_eq!(cd[111], 0x01, "Amount son byte = 0x01");
+        assert_eq!(cd[80], 0x00, "Amount ilk byte = 0x00");
+        // [112] = UniV3 direction
+        assert_eq!(cd[112], 0x00, "UniV3 dir = 0x00");
+        // [113] = Aero direction
+        assert_eq!(cd[113], 0x01, "Aero dir = 0x01");
+        // [114..130] = minProfit (16 byte big-endian, 0xFF = last byte)
+        assert_eq!(cd[129], 0xFF, "minProfit son byte = 0xFF");
+        assert_eq!(cd[114], 0x00, "minProfit ilk byte = 0x00");
+        // [130..134] = deadlineBlock (4 byte big-endian)
+        assert_eq!(cd[130], 0x01, "deadlineBlock byte 0 = 0x01");
+        assert_eq!(cd[131], 0x02, "deadlineBlock byte 1 = 0x02");
+        assert_eq!(cd[132], 0x03, "deadlineBlock byte 2 = 0x03");
+        assert_eq!(cd[133], 0x04, "deadlineBlock byte 3 = 0x04");
+    }
+
+    #[test]
+    fn test_compact_calldata_invalid_length_rejected() {
+        // 133 byte (eksik) — decode None döndürmeli
+        let short = vec![0u8; 133];
+        assert!(
+            decode_compact_calldata(&short).is_none(),
+            "133 byte reddedilmeli"
+        );
+
+        // 135 byte (fazla) — decode None döndürmeli
+        let long = vec![0u8; 135];
+        assert!(
+            decode_compact_calldata(&long).is_none(),
+            "135 byte reddedilmeli"
+        );
+
+        // Eski 130 byte — reddedilmeli
+        let old = vec![0u8; 130];
+        assert!(
+            decode_compact_calldata(&old).is_none(),
+            "130 byte eski format reddedilmeli"
+        );
+
+        // Boş veri
+        let empty: Vec<u8> = vec![];
+        assert!(
+            decode_compact_calldata(&empty).is_none(),
+            "Boş veri reddedilmeli"
+        );
+    }
+
+    #[test]
+    fn test_compact_vs_abi_size_comparison() {
+        // Eski ABI: 4 (selector) + 32*9 (params) = 292 byte
+        // Yeni kompakt: 20+20+20+20+32+1+1+16+4 = 134 byte
+        // Tasarruf: 292 - 134 = 158 byte (~%54 azalma)
+        let amount = U256::from(10_000_000_000_000_000_000u128);
+
+        let compact = encode_compact_calldata(
+            POOL_A,
+            POOL_B,
+            WETH,
+            USDC,
+            amount,
+            0x00,
+            0x01,
+            1_000_000u128,
+            99_999_999u32,
+        );
+        let abi_size: usize = 4 + 32 * 9; // 9 parametreli ABI
+
+        assert_eq!(compact.len(), 134);
+        assert_eq!(abi_size, 292);
+        assert!(
+            compact.len() < abi_size,
+            "Kompakt format ABI'den küçük olmalı"
+        );
+
+        let saved = abi_size - compact.len();
+        assert_eq!(saved, 158, "158 byte tasarruf");
+    }
+
+    #[test]
+    fn test_format_compact_calldata_hex() {
+        let pool_a = address!("0000000000000000000000000000000000000001");
+        let pool_b = address!("0000000000000000000000000000000000000002");
+        let owed = address!("0000000000000000000000000000000000000003");
+        let recv = address!("0000000000000000000000000000000000000004");
+        let amount = U256::from(0xFFu64);
+
+        let cd = encode_compact_calldata(pool_a, pool_b, owed, recv, amount, 0x00, 0x01, 0, 100u32);
+        let hex_str = format_compact_calldata_hex(&cd);
+
+        // "0x" ile başlamalı
+        assert!(hex_str.starts_with("0x"), "Hex 0x ile başlamalı");
+        // 134 byte = 268 hex karakter + "0x" = 270 karakter
+        assert_eq!(hex_str.len(), 270, "Hex string 270 karakter olmalı");
+    }
+
+    #[test]
+    fn test_min_profit_max_u128() {
+        // u128::MAX minProfit testi
+        let max_profit = u128::MAX;
+        let calldata = encode_compact_calldata(
+            POOL_A,
+            POOL_B,
+            WETH,
+            USDC,
+            U256::from(1u64),
+            0x00,
+            0x01,
+            max_profit,
+            u32::MAX,
+        );
+        assert_eq!(calldata.len(), 134);
+
+        let (_, _, _, _, _, _, _, decoded_profit, decoded_deadline) =
+            decode_compact_calldata(&calldata).expect("Decode başarısız");
+        assert_eq!(decoded_profit, max_profit, "u128::MAX minProfit round-trip");
+        assert_eq!(
+            decoded_deadline,
+            u32::MAX,
+            "u32::MAX deadlineBlock round-trip"
+        );
+    }
+
+    #[test]
+    fn test_real_base_scenario() {
+        // Gerçek Base Network senaryosu:
+        // UniV3 WETH/USDC 0.05% havuzundan flash swap ile USDC al
+        // Slipstream'de USDC ile WETH geri al
+        // owedToken = WETH (UniV3'e geri öde)
+        // receivedToken = USDC (UniV3'den al, Slipstream'e ver)
+        let amount = U256::from(1_000_000_000_000_000_000u128); // 1 WETH
+        let min_profit = 500_000u128; // 0.5 USDC (6 decimal)
+        let deadline = 20_000_000u32; // Blok ~20M
+
+        let calldata = encode_compact_calldata(
+            POOL_A, POOL_B, WETH, // owedToken (WETH borçlu)
+            USDC, // receivedToken (USDC alınan)
+            amount, 0x01, // UniV3: oneForZero (WETH al = zeroForOne=false → direction=1)
+            0x00, // Slipstream: zeroForOne (USDC sat → WETH al)
+            min_profit, deadline,
+        );
+
+        assert_eq!(calldata.len(), 134);
+
+        // Round-trip doğrula
+        let decoded = decode_compact_calldata(&calldata).expect("Decode başarısız");
+        assert_eq!(decoded.0, POOL_A);
+        assert_eq!(decoded.1, POOL_B);
+        assert_eq!(decoded.2, WETH);
+        assert_eq!(decoded.3, USDC);
+        assert_eq!(decoded.4, amount);
+        assert_eq!(decoded.5, 0x01);
+        assert_eq!(decoded.6, 0x00);
+        assert_eq!(decoded.7, min_profit);
+        assert_eq!(decoded.8, deadline);
+    }
+
+    #[test]
+    fn test_multi_hop_hop_count_matches_contract() {
+        let pools = [POOL_A, POOL_B, POOL_A];
+        let cd = encode_multi_hop_calldata(&pools, &[0, 1, 1], U256::from(7u64), 3, 99);
+        assert_eq!(cd.len(), 53 + 3 * 21);
+        let (p, d, amount, min_profit, deadline) = decode_multi_hop_calldata(&cd).unwrap();
+        assert_eq!((p, d), (pools.to_vec(), vec![0, 1, 1]));
+        assert_eq!((amount, min_profit, deadline), (U256::from(7u64), 3, 99));
+
+        // hopCount 2 / 5 — uzunluk tutsa da kontrat InvalidCalldataLength atar
+        for hops in [2u8, 5] {
+            let mut cd = vec![0u8; 53 + hops as usize * 21];
+            cd[0] = hops;
+            assert!(decode_multi_hop_calldata(&cd).is_none(), "{} hop reddedilmeli", hops);
+        }
+    }
+}
@@ -0,0 +1,563 @@
+// ============================================================================
+//  CALLDATA DIFF — Rust Çözücüsü ↔ Kontratın Çözücüsü (Diferansiyel REVM)
+//
+//  calldata_codec kontratın assembly çözücüsünün bot tarafı aynası; tek
+//  güvencesi kodlayıcıyla aynı varsayımları paylaşan round-trip testleriydi.
+//  Burada aynı byte'lar hem Rust çözücüsünden hem gerçek kontratın
+//  fallback()'inden (REVM) geçer:
+//
+//  ✓ Gerçek kontrat: forge artifact'ının creation bytecode'u REVM'de
+//    (executor, admin) ile deploy edilir — immutable'lar gerçek
+//  ✓ Alan düzeyi (134 byte): havuz / token stub'ları Rust'ın çözdüğü
+//    adreslere kurulur — kontrat başka adres çözerse çağrısı boş hesaba
+//    gider ve revert eder. Havuz stub'ları aldıkları swap argümanlarını
+//    (yön, miktar) storage'a yazar; deadline ve minProfit sınırın iki
+//    yanında koşularak sabitlenir
+//  ✓ Kabul / ret: bot (uzunluğa göre kompakt ya da multi-hop) ile kontrat
+//    (InvalidCalldataLength) aynı girdileri reddetmeli — kesme, uzatma,
+//    hop sayısı bozma
+//  ✓ Fuzz hedefi (fuzz/) bu dosyayı #[path] ile derler; sınırlı proptest
+//    sürümü aşağıda (artifact gerektirenler #[ignore]); bu yüzden yalnızca
+//    alloy + revm + serde_json + calldata_codec kullanır, simulator'ı değil
+//
+//  Bilinçli asimetriler:
+//  • Yön byte'ı: Rust ham u8 döndürür; kontrat `== 0` → zeroForOne, diğer
+//    HER değer (2..=255 dahil) oneForZero. Kodlayıcı yalnızca 0/1 üretir;
+//    karşılaştırma kontratın yorumuyla yapılır.
+//  • Kontrat alanları çözdükten sonra anlamsal kontrol yapar (amount = 0,
+//    whitelist, deadline, kâr) — bunlar çözücü reddi değil yürütme reddidir;
+//    Rust çözücüsü bu girdileri kabul eder.
+//  • hopCount 2 (95 byte): kontrat reddeder. Çözücü önceden kabul ediyordu
+//    (kodlayıcı 2 hop'a izin veriyordu); artık ikisi de 3-4 ile sınırlı.
+// ============================================================================
+
+use std::path::{Path, PathBuf};
+
+use alloy::hex;
+use alloy::primitives::{address, keccak256, Address, Bytes, U256};
+use revm::{
+    bytecode::Bytecode,
+    context::{BlockEnv, Context, Journal, TxEnv},
+    context_interface::result::ExecutionResult,
+    database::InMemoryDB,
+    handler::{ExecuteEvm, MainBuilder},
+    primitives::{hardfork::SpecId, TxKind},
+    state::{AccountInfo, EvmState},
+};
+
+use crate::calldata_codec::{decode_compact_calldata, decode_multi_hop_calldata};
+
+/// Artifact yolu (varsayılanı ezer) — `forge build` çıktısı
+pub const ARTIFACT_ENV: &str = "CONTRACT_ARTIFACT";
+/// Bot/ dizinine göre forge artifact'ı
+const DEFAULT_ARTIFACT: &str = "../Contract/out/Arbitraj.sol/ArbitrajBotu.json";
+
+/// fallback()'i çağırabilen tek adres (constructor'a verilir)
+const EXECUTOR: Address = address!("00000000000000000000000000000000000e0e01");
+const ADMIN: Address = address!("00000000000000000000000000000000000e0e02");
+const DEPLOYER: Address = address!("00000000000000000000000000000000000e0e03");
+
+/// `mapping(address => bool) poolWhitelist` — kontratın tek storage değişkeni
+const POOL_WHITELIST_SLOT: u64 = 0;
+
+/// uniswapV3SwapCallback(int256,int256,bytes)
+const SWAP_CALLBACK_SELECTOR: [u8; 4] = [0xfa, 0x46, 0x1e, 0x33];
+/// balanceOf(address)
+const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+/// Havuz stub'ının storage düzeni: 0 = flash swap'ta bildirilecek kâr,
+/// 1 = zeroForOne, 2 = amountSpecified, 3 = çağrıldı işareti
+const SLOT_PROFIT: u64 = 0;
+const SLOT_ZERO_FOR_ONE: u64 = 1;
+const SLOT_AMOUNT: u64 = 2;
+const SLOT_CALLED: u64 = 3;
+
+const GAS_LIMIT: u64 = 1_500_000;
+const DEPLOY_GAS_LIMIT: u64 = 10_000_000;
+
+fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// `CONTRACT_ARTIFACT` ya da `<bot_dir>/../Contract/out/...` — forge
+/// artifact'ının creation bytecode'u (`bytecode.object`)
+pub fn load_artifact(bot_dir: &Path) -> Result<Vec<u8>, String> {
+    let path = std::env::var_os(ARTIFACT_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| bot_dir.join(DEFAULT_ARTIFACT));
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|e| format!("{}: {} (Contract/ içinde `forge build`)", path.display(), e))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&raw).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let object = json["bytecode"]["object"]
+        .as_str()
+        .ok_or_else(|| format!("{}: bytecode.object yok", path.display()))?;
+    hex::decode(object.trim_start_matches("0x")).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Stub Bytecode'ları
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn insert_code(db: &mut InMemoryDB, addr: Address, code: &[u8]) {
+    let bytecode = Bytecode::new_raw(Bytes::copy_from_slice(code));
+    let info = AccountInfo::new(U256::ZERO, 0, bytecode.hash_slow(), bytecode);
+    db.insert_account_info(addr, info);
+}
+
+fn set_slot(db: &mut InMemoryDB, addr: Address, slot: U256, value: U256) {
+    let _ = db.insert_account_storage(addr, slot, value);
+}
+
+fn whitelist_key(pool: Address) -> U256 {
+    let mut buf = [0u8; 64];
+    buf[12..32].copy_from_slice(pool.as_slice());
+    buf[32..].copy_from_slice(&U256::from(POOL_WHITELIST_SLOT).to_be_bytes::<32>());
+    U256::from_be_bytes(keccak256(buf).0)
+}
+
+/// Sahte ERC20: balanceOf → slot 0; diğer her çağrı (transfer) slot 0'a
+/// calldata[0x24..0x44] miktarını ekler ve true döner. Kontratın kâr
+/// ölçümü (balAfter - balBefore) böylece callback'teki ödeme kadar olur.
+fn token_stub() -> Vec<u8> {
+    let mut code = vec![0x60, 0x00, 0x35, 0x60, 0xe0, 0x1c, 0x63]; // selector, PUSH4
+    code.extend_from_slice(&BALANCE_OF_SELECTOR);
+    code.extend_from_slice(&[0x14, 0x60, 0x00, 0x57]); // EQ PUSH1 <balanceOf> JUMPI
+    let balance_of_label = code.len() - 2;
+    code.extend_from_slice(&[
+        0x60, 0x24, 0x35, 0x60, 0x00, 0x54, 0x01, 0x60, 0x00, 0x55, // slot0 += amount
+        0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3, // return true
+    ]);
+    code[balance_of_label] = code.len() as u8;
+    code.extend_from_slice(&[
+        0x5b, 0x60, 0x00, 0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3, // return slot0
+    ]);
+    code
+}
+
+/// Kaydedici havuz: swap(recipient, zeroForOne, amountSpecified, limit, data)
+/// argümanlarını storage'a yazar, 64 byte döner. `flash` ise önce çağırana
+/// uniswapV3SwapCallback(+slot0, -1, "") yapar (kontratın A yolu: Pool B'de
+/// satış + owedToken ile slot0 kadar geri ödeme); callback revert'ü aktarılır.
+fn pool_stub(flash: bool) -> Vec<u8> {
+    let mut code = vec![
+        0x60, 0x24, 0x35, 0x60, SLOT_ZERO_FOR_ONE as u8, 0x55, // zeroForOne
+        0x60, 0x44, 0x35, 0x60, SLOT_AMOUNT as u8, 0x55, // amountSpecified
+        0x60, 0x01, 0x60, SLOT_CALLED as u8, 0x55, // çağrıldı
+    ];
+    if flash {
+        code.push(0x63); // PUSH4 uniswapV3SwapCallback
+        code.extend_from_slice(&SWAP_CALLBACK_SELECTOR);
+        code.extend_from_slice(&[0x60, 0xe0, 0x1b, 0x60, 0x00, 0x52]); // mstore(0, sel << 224)
+        code.extend_from_slice(&[0x60, SLOT_PROFIT as u8, 0x54, 0x60, 0x04, 0x52]); // amount0Delta = +kâr
+        code.extend_from_slice(&[0x60, 0x00, 0x19, 0x60, 0x24, 0x52]); // amount1Delta = -1
+        code.extend_from_slice(&[0x60, 0x60, 0x60, 0x44, 0x52]); // bytes offset (uzunluk 0)
+        code.extend_from_slice(&[0x60, 0x00, 0x60, 0x00, 0x60, 0x84, 0x60, 0x00, 0x60, 0x00, 0x33, 0x5a, 0xf1]);
+        code.extend_from_slice(&[0x60, 0x00, 0x57]); // PUSH1 <ok> JUMPI
+        let ok_label = code.len() - 2;
+        code.extend_from_slice(&[0x3d, 0x60, 0x00, 0x60, 0x00, 0x3e, 0x3d, 0x60, 0x00, 0xfd]); // revert
+        code[ok_label] = code.len() as u8;
+        code.push(0x5b); // JUMPDEST
+    }
+    code.extend_from_slice(&[0x60, 0x40, 0x60, 0x00, 0xf3]); // return (amount0, amount1)
+    code
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Koşucu
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Kontrat çağrısının sonucu
+#[derive(Debug)]
+pub enum Outcome {
+    Success(EvmState),
+    /// Özel hatanın seçicisi — boş / kısa revert verisi → None
+    Revert(Option<[u8; 4]>),
+    Halt(String),
+}
+
+impl Outcome {
+    fn reverted_with(&self, signature: &str) -> bool {
+        matches!(self, Outcome::Revert(Some(sel)) if *sel == selector(signature))
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Outcome::Success(_) => "success".into(),
+            Outcome::Revert(Some(sel)) => format!("revert 0x{}", hex::encode(sel)),
+            Outcome::Revert(None) => "revert (no data)".into(),
+            Outcome::Halt(reason) => format!("halt {}", reason),
+        }
+    }
+}
+
+/// Rust çözücüsünün 134 byte'tan okuduğu alanlar
+#[derive(Debug, Clone, Copy)]
+struct CompactFields {
+    pool_a: Address,
+    pool_b: Address,
+    owed_token: Address,
+    received_token: Address,
+    amount: U256,
+    uni_direction: u8,
+    aero_direction: u8,
+    min_profit: u128,
+    deadline_block: u32,
+}
+
+/// Deploy edilmiş kontratlı temel veritabanı
+pub struct ContractHarness {
+    db: InMemoryDB,
+    contract: Address,
+}
+
+impl ContractHarness {
+    /// Creation bytecode'u (executor, admin) argümanlarıyla deploy et
+    pub fn deploy(creation_code: &[u8]) -> Result<Self, String> {
+        let mut init = creation_code.to_vec();
+        for role in [EXECUTOR, ADMIN] {
+            init.extend_from_slice(&[0u8; 12]);
+            init.extend_from_slice(role.as_slice());
+        }
+        let tx = TxEnv::builder()
+            .caller(DEPLOYER)
+            .kind(TxKind::Create)
+            .data(Bytes::from(init))
+            .gas_limit(DEPLOY_GAS_LIMIT)
+            .nonce(0)
+            .build()
+            .map_err(|e| format!("TxEnv build failed: {:?}", e))?;
+        let ctx: Context<BlockEnv, _, _, InMemoryDB, Journal<InMemoryDB>, ()> =
+            Context::new(InMemoryDB::default(), SpecId::CANCUN);
+        let mut evm = ctx.build_mainnet();
+        let result = evm.transact(tx).map_err(|e| format!("EVM error: {:?}", e))?.result;
+        let runtime = match result {
+            ExecutionResult::Success { output, .. } => output.data().clone(),
+            ExecutionResult::Revert { output, .. } => {
+                return Err(format!("deploy REVERT: 0x{}", hex::encode(&output)))
+            }
+            ExecutionResult::Halt { reason, .. } => return Err(format!("deploy HALT: {:?}", reason)),
+        };
+        if runtime.is_empty() {
+            return Err("deploy returned empty runtime code".into());
+        }
+        let contract = DEPLOYER.create(0);
+        let mut db = InMemoryDB::default();
+        insert_code(&mut db, contract, &runtime);
+        Ok(Self { db, contract })
+    }
+
+    fn run(&self, db: InMemoryDB, calldata: &[u8], block: u64) -> Result<Outcome, String> {
+        let ctx: Context<BlockEnv, _, _, InMemoryDB, Journal<InMemoryDB>, ()> =
+            Context::new(db, SpecId::CANCUN).modify_block_chained(|b: &mut BlockEnv| {
+                b.number = U256::from(block);
+            });
+        let tx = TxEnv::builder()
+            .caller(EXECUTOR)
+            .kind(TxKind::Call(self.contract))
+            .data(Bytes::copy_from_slice(calldata))
+            .gas_limit(GAS_LIMIT)
+            .nonce(0)
+            .build()
+            .map_err(|e| format!("TxEnv build failed: {:?}", e))?;
+        let mut evm = ctx.build_mainnet();
+        let result_and_state = evm.transact(tx).map_err(|e| format!("EVM error: {:?}", e))?;
+        Ok(match result_and_state.result {
+            ExecutionResult::Success { .. } => Outcome::Success(result_and_state.state),
+            ExecutionResult::Revert { output, .. } => {
+                Outcome::Revert(output.get(..4).map(|s| [s[0], s[1], s[2], s[3]]))
+            }
+            ExecutionResult::Halt { reason, .. } => Outcome::Halt(format!("{:?}", reason)),
+        })
+    }
+
+    /// Rust'ın çözdüğü alanlara göre kurulmuş dünya: iki havuz whitelist'te,
+    /// havuz ve token stub'ları o adreslerde; flash havuzu `profit` bildirir
+    fn world(&self, f: &CompactFields, profit: U256) -> InMemoryDB {
+        let mut db = self.db.clone();
+        for pool in [f.pool_a, f.pool_b] {
+            set_slot(&mut db, self.contract, whitelist_key(pool), U256::from(1u64));
+        }
+        insert_code(&mut db, f.owed_token, &token_stub());
+        insert_code(&mut db, f.received_token, &token_stub());
+        set_slot(&mut db, f.received_token, U256::ZERO, U256::from(1u64));
+        insert_code(&mut db, f.pool_a, &pool_stub(true));
+        insert_code(&mut db, f.pool_b, &pool_stub(false));
+        set_slot(&mut db, f.pool_a, U256::from(SLOT_PROFIT), profit);
+        db
+    }
+
+    /// 134 byte'lık girdi: kontratın her alanı Rust çözücüsüyle aynı okuduğunu
+    /// doğrula. Uyuşmazlıkta hangi alanın / koşunun ayrıştığını döner.
+    pub fn check_compact(&self, data: &[u8]) -> Result<(), String> {
+        let Some((pool_a, pool_b, owed_token, received_token, amount, uni_direction, aero_direction, min_profit, deadline_block)) =
+            decode_compact_calldata(data)
+        else {
+            return Err(format!("Rust decoder rejected a {}-byte compact payload", data.len()));
+        };
+        let f = CompactFields {
+            pool_a,
+            pool_b,
+            owed_token,
+            received_token,
+            amount,
+            uni_direction,
+            aero_direction,
+            min_profit,
+            deadline_block,
+        };
+        let ctx = |what: &str, outcome: &Outcome| {
+            format!("{}: {} — calldata 0x{} (Rust: {:?})", what, outcome.label(), hex::encode(data), f)
+        };
+        let deadline = u64::from(f.deadline_block);
+        let profit = U256::from(f.min_profit.max(1));
+
+        // amount = 0 kontratın ilk kontrolü — başka hiçbir şeye bakılmaz
+        if f.amount.is_zero() {
+            let outcome = self.run(self.world(&f, profit), data, deadline)?;
+            if !outcome.reverted_with("ZeroAmount()") {
+                return Err(ctx("amount = 0 → ZeroAmount() expected", &outcome));
+            }
+            return Ok(());
+        }
+
+        // Deadline bloğunda, tam minProfit kâr → başarı; stub kayıtları alanları verir
+        let outcome = self.run(self.world(&f, profit), data, deadline)?;
+        let Outcome::Success(ref state) = outcome else {
+            return Err(ctx("success expected at the deadline block with profit = minProfit", &outcome));
+        };
+        let word = |addr: Address, slot: u64| {
+            state
+                .get(&addr)
+                .and_then(|account| account.storage.get(&U256::from(slot)))
+                .map_or(U256::ZERO, |s| s.present_value)
+        };
+        // Kontratın yorumu: yön byte'ı == 0 → zeroForOne
+        let checks = [
+            ("pool A swap", word(f.pool_a, SLOT_CALLED), U256::from(1u64)),
+            ("pool B swap", word(f.pool_b, SLOT_CALLED), U256::from(1u64)),
+            ("amount", word(f.pool_a, SLOT_AMOUNT), f.amount),
+            ("uni direction", word(f.pool_a, SLOT_ZERO_FOR_ONE), U256::from((f.uni_direction == 0) as u8)),
+            ("aero direction", word(f.pool_b, SLOT_ZERO_FOR_ONE), U256::from((f.aero_direction == 0) as u8)),
+        ];
+        for (field, contract_value, rust_value) in checks {
+            if contract_value != rust_value {
+                return Err(ctx(&format!("{}: contract {} ≠ Rust {}", field, contract_value, rust_value), &outcome));
+            }
+        }
+
+        // Deadline sınırı: bir sonraki blokta DeadlineExpired
+        let outcome = self.run(self.world(&f, profit), data, deadline + 1)?;
+        if !outcome.reverted_with("DeadlineExpired()") {
+            return Err(ctx("block = deadline + 1 → DeadlineExpired() expected", &outcome));
+        }
+
+        // minProfit sınırı: bir wei eksik kâr → InsufficientProfit
+        if f.min_profit >= 2 {
+            let outcome = self.run(self.world(&f, U256::from(f.min_profit - 1)), data, deadline)?;
+            if !outcome.reverted_with("InsufficientProfit()") {
+                return Err(ctx("profit = minProfit - 1 → InsufficientProfit() expected", &outcome));
+            }
+        }
+        Ok(())
+    }
+
+    /// Her uzunlukta girdi: bot (uzunluğa göre kompakt / multi-hop) ve
+    /// kontrat aynı girdileri biçim olarak reddetmeli
+    pub fn check_layout(&self, data: &[u8]) -> Result<(), String> {
+        let bot_accepts =
+            decode_compact_calldata(data).is_some() || decode_multi_hop_calldata(data).is_some();
+        let outcome = self.run(self.db.clone(), data, 0)?;
+        let contract_accepts = !outcome.reverted_with("InvalidCalldataLength()");
+        if bot_accepts != contract_accepts {
+            return Err(format!(
+                "{} bytes (first byte {:?}): bot accepts={}, contract {} — calldata 0x{}",
+                data.len(),
+                data.first(),
+                bot_accepts,
+                outcome.label(),
+                hex::encode(data),
+            ));
+        }
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Gerçek kontrata karşı olanlar forge artifact'ı ister:
+//   (cd Contract && forge build) && cargo test -- --ignored calldata_diff
+// Kontratın çözücü assembly bloğunun opcode kopyası artifact'sız da koşar.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calldata_codec::{COMPACT_CALLDATA_LEN, MULTI_HOP_MAX_HOPS, MULTI_HOP_MIN_HOPS};
+    use proptest::prelude::*;
+    use std::sync::OnceLock;
+
+    fn harness() -> &'static ContractHarness {
+        static HARNESS: OnceLock<ContractHarness> = OnceLock::new();
+        HARNESS.get_or_init(|| {
+            let code = load_artifact(Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap_or_else(|e| panic!("{}", e));
+            ContractHarness::deploy(&code).unwrap_or_else(|e| panic!("{}", e))
+        })
+    }
+
+    /// _executeTwoPool'un çözücü bloğu, satır satır: (calldataload ofseti, shr)
+    const TWO_POOL_DECODE: [(u8, u8); 9] = [
+        (0x00, 96),  // poolA
+        (0x14, 96),  // poolB
+        (0x28, 96),  // owedToken
+        (0x3C, 96),  // receivedToken
+        (0x50, 0),   // amount
+        (0x70, 248), // uniDirection
+        (0x71, 248), // aeroDirection
+        (0x72, 128), // minProfit
+        (0x82, 224), // deadlineBlock
+    ];
+
+    /// Çözücü bloğun opcode kopyası: dokuz kelimeyi sırayla döner
+    fn decoder_block_stub() -> Vec<u8> {
+        let mut code = Vec::new();
+        for (i, &(offset, shift)) in TWO_POOL_DECODE.iter().enumerate() {
+            code.extend_from_slice(&[0x60, offset, 0x35]); // CALLDATALOAD
+            if shift > 0 {
+                code.extend_from_slice(&[0x60, shift, 0x1c]); // SHR
+            }
+            let [hi, lo] = (i as u16 * 32).to_be_bytes(); // 9. kelime 256'da — PUSH1'e sığmaz
+            code.extend_from_slice(&[0x61, hi, lo, 0x52]); // MSTORE
+        }
+        code.extend_from_slice(&[0x61, 0x01, 0x20, 0x60, 0x00, 0xf3]); // return 9 × 32
+        code
+    }
+
+    /// Rastgele 134 byte; yön byte'ları 0/1 ağırlıklı (2..=255 de gelir),
+    /// ara sıra amount = 0
+    fn compact_payload() -> impl Strategy<Value = Vec<u8>> {
+        let direction = prop_oneof![3 => Just(0u8), 3 => Just(1u8), 1 => any::<u8>()];
+        (
+            prop::collection::vec(any::<u8>(), COMPACT_CALLDATA_LEN),
+            direction.clone(),
+            direction,
+            prop::bool::weighted(0.1),
+        )
+            .prop_map(|(mut data, uni, aero, zero_amount)| {
+                data[0x70] = uni;
+                data[0x71] = aero;
+                if zero_amount {
+                    data[0x50..0x70].fill(0);
+                }
+                data
+            })
+    }
+
+    /// Geçerli kompakt girdinin bozulmuş hali: kesme, uzatma ya da multi-hop
+    /// biçimine yakın (hop sayısı / uzunluk ±1) girdiler
+    fn malformed_payload() -> impl Strategy<Value = Vec<u8>> {
+        prop_oneof![
+            (compact_payload(), 0..COMPACT_CALLDATA_LEN).prop_map(|(data, len)| data[..len].to_vec()),
+            (compact_payload(), prop::collection::vec(any::<u8>(), 1..8)).prop_map(|(mut data, tail)| {
+                data.extend(tail);
+                data
+            }),
+            (0u8..=6, 0usize..=6, -1i64..=1, prop::collection::vec(any::<u8>(), 53 + 6 * 21 + 1)).prop_map(
+                |(hop_byte, hops, delta, mut data)| {
+                    data[0] = hop_byte;
+                    data.truncate((53 + hops as i64 * 21 + delta).max(0) as usize);
+                    data
+                },
+            ),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(512))]
+
+        /// Rust çözücüsü ↔ kontratın çözücü assembly'si (EVM semantiğiyle)
+        #[test]
+        fn decoder_block_matches_rust_decoder(data in compact_payload()) {
+            let target = address!("00000000000000000000000000000000000e0e04");
+            let mut db = InMemoryDB::default();
+            insert_code(&mut db, target, &decoder_block_stub());
+            let ctx: Context<BlockEnv, _, _, InMemoryDB, Journal<InMemoryDB>, ()> =
+                Context::new(db, SpecId::CANCUN);
+            let tx = TxEnv::builder()
+                .caller(EXECUTOR)
+                .kind(TxKind::Call(target))
+                .data(Bytes::from(data.clone()))
+                .gas_limit(GAS_LIMIT)
+                .nonce(0)
+                .build()
+                .unwrap();
+            let output = match ctx.build_mainnet().transact(tx).unwrap().result {
+                ExecutionResult::Success { output, .. } => output.data().clone(),
+                other => panic!("decoder stub failed: {:?}", other),
+            };
+            let words: Vec<U256> = output.chunks(32).map(U256::from_be_slice).collect();
+
+            let (a, b, owed, received, amount, uni, aero, min_profit, deadline) =
+                decode_compact_calldata(&data).unwrap();
+            let rust = [
+                U256::from_be_slice(a.as_slice()),
+                U256::from_be_slice(b.as_slice()),
+                U256::from_be_slice(owed.as_slice()),
+                U256::from_be_slice(received.as_slice()),
+                amount,
+                U256::from(uni),
+                U256::from(aero),
+                U256::from(min_profit),
+                U256::from(deadline),
+            ];
+            prop_assert_eq!(words, rust.to_vec());
+        }
+
+        /// Bot tarafı biçim kuralı: kompakt yalnızca 134 byte, multi-hop
+        /// yalnızca hopCount 3-4 ve 53 + hopCount×21 byte (kontratın kuralı)
+        #[test]
+        fn bot_layout_rule_matches_contract_rule(data in malformed_payload()) {
+            let hops = data.first().map_or(0, |&h| h as usize);
+            let contract_rule = data.len() == COMPACT_CALLDATA_LEN
+                || ((MULTI_HOP_MIN_HOPS..=MULTI_HOP_MAX_HOPS).contains(&hops) && data.len() == 53 + hops * 21);
+            let bot = decode_compact_calldata(&data).is_some() || decode_multi_hop_calldata(&data).is_some();
+            prop_assert_eq!(bot, contract_rule, "len {} hop byte {}", data.len(), hops);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(128))]
+
+        /// Alan düzeyinde: gerçek kontrat her alanı Rust çözücüsüyle aynı okur
+        #[test]
+        #[ignore]
+        fn calldata_diff_contract_fields_match(data in compact_payload()) {
+            if let Err(e) = harness().check_compact(&data) {
+                prop_assert!(false, "{}", e);
+            }
+        }
+
+        /// Tek bit çevirme her alanda: bozulan girdi yine 134 byte'tır — iki
+        /// taraf da kabul eder ve yine alan alan aynı okur
+        #[test]
+        #[ignore]
+        fn calldata_diff_bit_flips_agree(data in compact_payload(), bit in 0..COMPACT_CALLDATA_LEN * 8) {
+            let mut flipped = data;
+            flipped[bit / 8] ^= 1 << (bit % 8);
+            if let Err(e) = harness().check_layout(&flipped).and_then(|_| harness().check_compact(&flipped)) {
+                prop_assert!(false, "bit {}: {}", bit, e);
+            }
+        }
+
+        /// Kesme / uzatma / hop sayısı bozma: aynı girdiler reddedilir
+        #[test]
+        #[ignore]
+        fn calldata_diff_malformed_rejected_alike(data in malformed_payload()) {
+            if let Err(e) = harness().check_layout(&data) {
+                prop_assert!(false, "{}", e);
+            }
+        }
+    }
+}
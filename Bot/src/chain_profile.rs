@@ -0,0 +1,432 @@
+// ============================================================================
+//  CHAIN_PROFILE v1.0 — Zincire Özgü Varsayılanlar (CHAIN_PROFILE)
+//
+//  Çekirdek döngü V3 tipi havuzları olan her EVM zincirinde çalışır; zincire
+//  bağlı parçalar küçük bir profil tablosundan gelir:
+//  ✓ chain id (REVM cfg + imzalama + başlangıçta RPC doğrulaması)
+//  ✓ WETH / USDC adresleri ve güvenli token listesi
+//  ✓ L1 data fee oracle'ı (OP Stack GasPriceOracle; Arbitrum'da yok)
+//  ✓ Aave V3 Pool (flash loan kaynağı)
+//  ✓ Priority fee stratejisi: FIFO bribe (sıralama priority fee ile) veya
+//    standart EIP-1559 (sıralama bahşişe bağlı değil — Arbitrum FCFS)
+//
+//  CHAIN_PROFILE=base|optimism|arbitrum|custom. Her alan env ile ezilebilir
+//  (CHAIN_ID, WETH_ADDRESS, USDC_ADDRESS, L1_FEE_ORACLE_ADDRESS,
+//  AAVE_POOL_ADDRESS, PRIORITY_FEE_STRATEGY); custom profilde tablo yoktur,
+//  adresler ve CHAIN_ID zorunludur. Multicall3 her zincirde aynı adrestedir.
+// ============================================================================
+
+use alloy::primitives::{address, Address};
+use std::collections::HashSet;
+
+use crate::env_loader::EnvLoader;
+
+/// OP Stack GasPriceOracle predeploy'u (Base, OP Mainnet ve diğer OP zincirleri)
+pub const OP_STACK_GAS_PRICE_ORACLE: Address = address!("420000000000000000000000000000000000000F");
+
+/// OP Stack WETH predeploy'u
+const OP_STACK_WETH: Address = address!("4200000000000000000000000000000000000006");
+
+/// Aave V3 Pool — Optimism ve Arbitrum'da aynı adres
+const AAVE_V3_POOL_OP_ARB: Address = address!("794a61358D6845594F94dc1DB02A252b5b4814aD");
+
+/// Optimism ve Arbitrum'da aynı adresteki DAI
+const DAI_OP_ARB: Address = address!("DA10009cBd5D07dd0CeCc66161FC93D7c9000da1");
+
+/// Base üzerindeki güvenli tokenlar (v10.1 beyaz listesi)
+const BASE_SAFE_TOKENS: &[Address] = &[
+    // WETH — Base canonical
+    OP_STACK_WETH,
+    // USDC — Circle (native)
+    address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+    // USDbC — USD Base Coin (bridged via Base bridge)
+    address!("d9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA"),
+    // DAI — Dai Stablecoin (bridged)
+    address!("50c5725949A6F0c72E6C4a641F24049A917DB0Cb"),
+    // cbETH — Coinbase Wrapped Staked ETH
+    address!("2Ae3F1Ec7F1F5012CFEab0185bfc7aa3cf0DEc22"),
+    // cbBTC — Coinbase Wrapped BTC (8 decimals)
+    address!("cbB7C0000aB88B473b1f5aFd9ef808440eed33Bf"),
+    // AERO — Aerodrome Finance token
+    address!("940181a94A35A4569E4529A3CDfB74e38FD98631"),
+    // DEGEN — Degen token (18 decimals)
+    address!("4ed4E862860beD51a9570b96d89aF5E1B0Efefed"),
+];
+
+const OPTIMISM_SAFE_TOKENS: &[Address] = &[
+    OP_STACK_WETH,
+    // USDC — Circle (native)
+    address!("0b2C639c533813f4Aa9D7837cAf62653d097Ff85"),
+    // USDC.e — bridged
+    address!("7F5c764cBc14f9669B88837ca1490cCa17c31607"),
+    DAI_OP_ARB,
+];
+
+const ARBITRUM_SAFE_TOKENS: &[Address] = &[
+    // WETH
+    address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+    // USDC — Circle (native)
+    address!("af88d065e77c8cC2239327C5EDb3A432268e5831"),
+    // USDC.e — bridged
+    address!("FF970A61A04b1cA14834A43f5dE4533eBDDB5CC8"),
+    DAI_OP_ARB,
+];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Profil Tablosu
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// CHAIN_PROFILE seçimi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainProfile {
+    Base,
+    Optimism,
+    Arbitrum,
+    /// Tablo yok — tüm zincir alanları env'den
+    Custom,
+}
+
+impl ChainProfile {
+    /// Konsol başlığındaki ağ adı
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ChainProfile::Base => "Base",
+            ChainProfile::Optimism => "OP Mainnet",
+            ChainProfile::Arbitrum => "Arbitrum One",
+            ChainProfile::Custom => "Custom chain",
+        }
+    }
+
+    /// Profilin varsayılanları (custom → None)
+    pub fn defaults(self) -> Option<ChainDefaults> {
+        match self {
+            ChainProfile::Base => Some(ChainDefaults {
+                chain_id: 8453,
+                weth: OP_STACK_WETH,
+                usdc: BASE_SAFE_TOKENS[1],
+                l1_fee_oracle: Some(OP_STACK_GAS_PRICE_ORACLE),
+                aave_pool: crate::fee_monitor::DEFAULT_AAVE_V3_POOL,
+                priority_fee: PriorityFeeStrategy::FifoBribe,
+                safe_tokens: BASE_SAFE_TOKENS,
+            }),
+            ChainProfile::Optimism => Some(ChainDefaults {
+                chain_id: 10,
+                weth: OP_STACK_WETH,
+                usdc: OPTIMISM_SAFE_TOKENS[1],
+                l1_fee_oracle: Some(OP_STACK_GAS_PRICE_ORACLE),
+                aave_pool: AAVE_V3_POOL_OP_ARB,
+                priority_fee: PriorityFeeStrategy::FifoBribe,
+                safe_tokens: OPTIMISM_SAFE_TOKENS,
+            }),
+            // L1 maliyeti Arbitrum'da gas'ın içinde faturalanır — ayrı oracle yok
+            ChainProfile::Arbitrum => Some(ChainDefaults {
+                chain_id: 42161,
+                weth: ARBITRUM_SAFE_TOKENS[0],
+                usdc: ARBITRUM_SAFE_TOKENS[1],
+                l1_fee_oracle: None,
+                aave_pool: AAVE_V3_POOL_OP_ARB,
+                priority_fee: PriorityFeeStrategy::Eip1559Competitive,
+                safe_tokens: ARBITRUM_SAFE_TOKENS,
+            }),
+            ChainProfile::Custom => None,
+        }
+    }
+
+    /// Profilin güvenli token listesi (custom → boş; WETH/USDC ayrıca eklenir)
+    pub fn safe_tokens(self) -> &'static [Address] {
+        self.defaults().map_or(&[], |d| d.safe_tokens)
+    }
+}
+
+impl std::fmt::Display for ChainProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ChainProfile::Base => "base",
+            ChainProfile::Optimism => "optimism",
+            ChainProfile::Arbitrum => "arbitrum",
+            ChainProfile::Custom => "custom",
+        })
+    }
+}
+
+/// Priority fee stratejisi (PRIORITY_FEE_STRATEGY)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityFeeStrategy {
+    /// Sıralama priority fee ile — kârın dinamik yüzdesi bahşiş (OP Stack)
+    FifoBribe,
+    /// Sıralama bahşişe bağlı değil — kârdan pay verilmez, sabit küçük bahşiş
+    Eip1559Competitive,
+}
+
+impl std::fmt::Display for PriorityFeeStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PriorityFeeStrategy::FifoBribe => "fifo_bribe",
+            PriorityFeeStrategy::Eip1559Competitive => "eip1559",
+        })
+    }
+}
+
+/// Tablo satırı
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainDefaults {
+    pub chain_id: u64,
+    pub weth: Address,
+    pub usdc: Address,
+    pub l1_fee_oracle: Option<Address>,
+    pub aave_pool: Address,
+    pub priority_fee: PriorityFeeStrategy,
+    pub safe_tokens: &'static [Address],
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Env Çözümlemesi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Profil + env ezmeleri sonrası etkin zincir ayarları
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainSettings {
+    pub profile: ChainProfile,
+    pub chain_id: u64,
+    pub weth: Address,
+    pub usdc: Address,
+    pub l1_fee_oracle: Option<Address>,
+    pub aave_pool: Address,
+    pub priority_fee: PriorityFeeStrategy,
+}
+
+impl ChainSettings {
+    /// CHAIN_PROFILE'ı oku, tablo varsayılanlarını env ile ez. Adlandırılmış
+    /// profilde farklı bir CHAIN_ID yanlış zincirin adresleriyle çalışmak
+    /// demektir → sorun olarak raporlanır (custom kullanılmalı).
+    pub fn load(env: &mut EnvLoader) -> Self {
+        let profile = env.choice(
+            "CHAIN_PROFILE",
+            &[
+                ("base", ChainProfile::Base),
+                ("optimism", ChainProfile::Optimism),
+                ("arbitrum", ChainProfile::Arbitrum),
+                ("custom", ChainProfile::Custom),
+            ],
+            ("base", ChainProfile::Base),
+        );
+        let defaults = profile.defaults();
+        let custom_hint = "required when CHAIN_PROFILE=custom";
+
+        let chain_id = match defaults {
+            Some(d) => {
+                let chain_id = env.parse_or("CHAIN_ID", d.chain_id, "positive integer");
+                if chain_id != d.chain_id {
+                    env.issue(
+                        "CHAIN_ID",
+                        format!(
+                            "{} contradicts CHAIN_PROFILE={} (chain id {})",
+                            chain_id, profile, d.chain_id
+                        ),
+                        "remove CHAIN_ID, or use CHAIN_PROFILE=custom with explicit addresses",
+                    );
+                }
+                chain_id
+            }
+            None => {
+                let chain_id = env.parse_or("CHAIN_ID", 0u64, "positive integer");
+                if chain_id == 0 {
+                    env.issue("CHAIN_ID", "not set", custom_hint);
+                }
+                chain_id
+            }
+        };
+
+        let address =
+            |env: &mut EnvLoader, var: &'static str, default: Option<Address>| match default {
+                Some(d) => env.address_or(var, d),
+                None => env.optional_address(var, None).unwrap_or_else(|| {
+                    env.issue(var, "not set", custom_hint);
+                    Address::ZERO
+                }),
+            };
+        let weth = address(env, "WETH_ADDRESS", defaults.map(|d| d.weth));
+        let usdc = address(env, "USDC_ADDRESS", defaults.map(|d| d.usdc));
+        let aave_pool = address(env, "AAVE_POOL_ADDRESS", defaults.map(|d| d.aave_pool));
+
+        // "none" → L1 data fee bileşeni yok (OP Stack dışı zincirler)
+        let default_oracle = defaults.and_then(|d| d.l1_fee_oracle);
+        let l1_fee_oracle = match env.optional_string("L1_FEE_ORACLE_ADDRESS", None) {
+            Some(raw) if raw.eq_ignore_ascii_case("none") => None,
+            Some(_) => env.optional_address("L1_FEE_ORACLE_ADDRESS", None),
+            None => default_oracle,
+        };
+
+        let default_strategy =
+            defaults.map_or(PriorityFeeStrategy::Eip1559Competitive, |d| d.priority_fee);
+        let default_name = default_strategy.to_string();
+        let priority_fee = env.choice(
+            "PRIORITY_FEE_STRATEGY",
+            &[
+                ("fifo_bribe", PriorityFeeStrategy::FifoBribe),
+                ("eip1559", PriorityFeeStrategy::Eip1559Competitive),
+            ],
+            (default_name.as_str(), default_strategy),
+        );
+
+        Self {
+            profile,
+            chain_id,
+            weth,
+            usdc,
+            l1_fee_oracle,
+            aave_pool,
+            priority_fee,
+        }
+    }
+}
+
+/// Güvenli token listesi: profilin tokenları + etkin (env ile ezilmiş olabilir)
+/// WETH/USDC. Egzotik veya yeni çıkan tokenlarla işlem yapılması engellenir
+/// (rug-pull, düşük likidite, token manipülasyonu).
+pub fn token_whitelist(profile: ChainProfile, weth: Address, usdc: Address) -> HashSet<Address> {
+    let mut tokens: HashSet<Address> = profile.safe_tokens().iter().copied().collect();
+    tokens.insert(weth);
+    tokens.insert(usdc);
+    tokens
+}
+
+/// RPC'nin bildirdiği chain id etkin profille uyuşuyor mu?
+pub fn verify_rpc_chain_id(
+    profile: ChainProfile,
+    chain_id: u64,
+    reported: u64,
+) -> Result<(), String> {
+    if reported == chain_id {
+        return Ok(());
+    }
+    Err(format!(
+        "RPC reports chain id {} but CHAIN_PROFILE={} expects {} — refusing to run {} addresses on the wrong chain",
+        reported,
+        profile,
+        chain_id,
+        profile.display_name(),
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN_VARS: [&str; 7] = [
+        "CHAIN_PROFILE",
+        "CHAIN_ID",
+        "WETH_ADDRESS",
+        "USDC_ADDRESS",
+        "AAVE_POOL_ADDRESS",
+        "L1_FEE_ORACLE_ADDRESS",
+        "PRIORITY_FEE_STRATEGY",
+    ];
+
+    /// Overlay önce bakılır: boş değer = tanımsız, süreç ortamı (paralel
+    /// env testleri) sızmaz
+    fn load(vars: &[(&str, &str)]) -> (ChainSettings, eyre::Result<Vec<String>>) {
+        let overlay = CHAIN_VARS
+            .iter()
+            .map(|k| {
+                let v = vars
+                    .iter()
+                    .find(|(var, _)| var == k)
+                    .map_or("", |(_, v)| *v);
+                (k.to_string(), v.to_string())
+            })
+            .collect();
+        let mut env = EnvLoader::with_overlay(overlay);
+        let settings = ChainSettings::load(&mut env);
+        (settings, env.finish())
+    }
+
+    #[test]
+    fn test_profile_table_resolution_with_overrides() {
+        // Varsayılan profil: Base
+        let (base, ok) = load(&[]);
+        assert!(ok.is_ok());
+        assert_eq!(base.profile, ChainProfile::Base);
+        assert_eq!(base.chain_id, 8453);
+        assert_eq!(base.weth, OP_STACK_WETH);
+        assert_eq!(base.l1_fee_oracle, Some(OP_STACK_GAS_PRICE_ORACLE));
+        assert_eq!(base.priority_fee, PriorityFeeStrategy::FifoBribe);
+        let cb_btc = address!("cbB7C0000aB88B473b1f5aFd9ef808440eed33Bf");
+        assert!(token_whitelist(base.profile, base.weth, base.usdc).contains(&cb_btc));
+
+        // Arbitrum: oracle yok, EIP-1559 stratejisi, kendi token'ları
+        let (arb, ok) = load(&[("CHAIN_PROFILE", "Arbitrum")]);
+        assert!(ok.is_ok());
+        assert_eq!(arb.chain_id, 42161);
+        assert_eq!(arb.l1_fee_oracle, None);
+        assert_eq!(arb.priority_fee, PriorityFeeStrategy::Eip1559Competitive);
+        assert_eq!(
+            arb.weth,
+            address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1")
+        );
+        assert!(!token_whitelist(arb.profile, arb.weth, arb.usdc).contains(&OP_STACK_WETH));
+
+        // Env ezmeleri tablo değerlerinin önüne geçer
+        let usdc_override = "0x1111111111111111111111111111111111111111";
+        let (tuned, ok) = load(&[
+            ("CHAIN_PROFILE", "arbitrum"),
+            ("USDC_ADDRESS", usdc_override),
+            ("PRIORITY_FEE_STRATEGY", "fifo_bribe"),
+            (
+                "L1_FEE_ORACLE_ADDRESS",
+                "0x420000000000000000000000000000000000000F",
+            ),
+        ]);
+        assert!(ok.is_ok());
+        assert_eq!(tuned.usdc, usdc_override.parse::<Address>().unwrap());
+        assert!(token_whitelist(tuned.profile, tuned.weth, tuned.usdc).contains(&tuned.usdc));
+        assert_eq!(tuned.priority_fee, PriorityFeeStrategy::FifoBribe);
+        assert_eq!(tuned.l1_fee_oracle, Some(OP_STACK_GAS_PRICE_ORACLE));
+        let (no_oracle, _) = load(&[("L1_FEE_ORACLE_ADDRESS", "none")]);
+        assert_eq!(no_oracle.l1_fee_oracle, None);
+
+        // Adlandırılmış profilde çelişen CHAIN_ID reddedilir
+        let (_, err) = load(&[("CHAIN_PROFILE", "optimism"), ("CHAIN_ID", "8453")]);
+        assert!(err.unwrap_err().to_string().contains("CHAIN_ID"));
+
+        // Custom: CHAIN_ID ve adresler zorunlu
+        let (_, err) = load(&[("CHAIN_PROFILE", "custom")]);
+        let report = err.unwrap_err().to_string();
+        for var in [
+            "CHAIN_ID",
+            "WETH_ADDRESS",
+            "USDC_ADDRESS",
+            "AAVE_POOL_ADDRESS",
+        ] {
+            assert!(report.contains(var), "{} missing from: {}", var, report);
+        }
+        let (custom, ok) = load(&[
+            ("CHAIN_PROFILE", "custom"),
+            ("CHAIN_ID", "84532"),
+            ("WETH_ADDRESS", "0x4200000000000000000000000000000000000006"),
+            ("USDC_ADDRESS", usdc_override),
+            (
+                "AAVE_POOL_ADDRESS",
+                "0x2222222222222222222222222222222222222222",
+            ),
+        ]);
+        assert!(ok.is_ok());
+        assert_eq!(custom.chain_id, 84532);
+        assert_eq!(custom.l1_fee_oracle, None);
+        assert_eq!(custom.priority_fee, PriorityFeeStrategy::Eip1559Competitive);
+        assert_eq!(
+            token_whitelist(custom.profile, custom.weth, custom.usdc).len(),
+            2
+        );
+
+        // Başlangıç doğrulaması
+        assert!(verify_rpc_chain_id(custom.profile, custom.chain_id, 84532).is_ok());
+        assert!(verify_rpc_chain_id(base.profile, base.chain_id, 10)
+            .unwrap_err()
+            .contains("CHAIN_PROFILE=base expects 8453"));
+    }
+}
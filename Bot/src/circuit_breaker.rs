@@ -0,0 +1,755 @@
+// ============================================================================
+//  CIRCUIT_BREAKER v1.0 — Duraklatılabilir Soğuma + Operatör Sıfırlaması
+//
+//  Çift başına kara liste (main, CIRCUIT_BREAKER_THRESHOLD) tek bir çiftin
+//  tekrarlayan hatalarını durdurur; bot geneli ardışık hatalar (kontrat
+//  revert'leri, bozuk simülasyonlar) ise hangi çiftte olursa olsun gas yakar.
+//
+//  ✓ Hata türü ayrımı: revert / RPC / simülasyon — oturum sayaçları
+//    ArbitrageStats.failure_kinds'ta. RPC hatası 1, revert ve simülasyon
+//    hatası STRIKE_WEIGHT ağırlıklı: üç RPC takılması tek revert sayılır
+//  ✓ Eşikte duraklama: fırsat değerlendirme / yürütme durur, senkron ve
+//    spread logu sürer. DATA_DIR/circuit_breaker.json hata nedenleri ve
+//    zaman damgalarıyla yazılır (yeniden başlatmada duraklama sürer)
+//  ✓ Yalnızca landed receipt seriyi sıfırlar. Başarılı doğrulama yalnızca
+//    simülasyon serisini siler — revert serisi doğrulamalar arasında birikir
+//  ✓ CIRCUIT_BREAKER_COOLDOWN_SECS sonra tek otomatik deneme: ilk landed
+//    receipt kapatır, revert / simülasyon hatası operatör sıfırlamasına kadar
+//    duraklatır
+//  ✓ Operatör sıfırlaması: dosyada "paused": false, dosyayı silmek ya da
+//    --reset-breaker ile başlatmak
+//  ✓ CIRCUIT_BREAKER_HARD_EXIT=true: eski davranış — dosya yazılır, exit(1)
+//
+//  Hatalar strategy'den (simülasyon) ve executor görevlerinden (revert, RPC)
+//  gelir; durum süreç geneli tek kilitte (contract_state deseni), main her
+//  blok geçişleri raporlar ve değerlendirmeden önce sorar.
+// ============================================================================
+
+use colored::*;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::console::{eprintln_high, println_high};
+use crate::log_event::{self, LogEvent};
+use crate::telegram::{TelegramMessage, TelegramSender};
+use crate::types::BotConfig;
+
+/// Breaker dosyası (DATA_DIR altında)
+pub const CIRCUIT_BREAKER_PATH: &str = "circuit_breaker.json";
+
+/// Revert / simülasyon hatasının RPC hatasına göre ağırlığı
+const STRIKE_WEIGHT: u32 = 3;
+
+/// Dosyada tutulan en fazla hata kaydı (en yeniler)
+const MAX_RECORDED_FAILURES: usize = 20;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Hata Türleri
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// status=0 receipt — kontrat gas yaktı
+    Revert,
+    /// Gönderim / receipt sorgusu transport hatası
+    Rpc,
+    /// Fırsat simülasyonu reddetti
+    Simulation,
+}
+
+impl FailureKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Revert => "revert",
+            Self::Rpc => "rpc",
+            Self::Simulation => "simulation",
+        }
+    }
+
+    fn weight(self) -> u32 {
+        match self {
+            Self::Rpc => 1,
+            Self::Revert | Self::Simulation => STRIKE_WEIGHT,
+        }
+    }
+}
+
+/// Oturum boyunca türe göre hata sayıları
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureCounts {
+    pub revert: u64,
+    pub rpc: u64,
+    pub simulation: u64,
+}
+
+impl FailureCounts {
+    fn record(&mut self, kind: FailureKind) {
+        match kind {
+            FailureKind::Revert => self.revert += 1,
+            FailureKind::Rpc => self.rpc += 1,
+            FailureKind::Simulation => self.simulation += 1,
+        }
+    }
+}
+
+/// Tek hata — dosyaya yazılır
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureRecord {
+    pub kind: FailureKind,
+    pub reason: String,
+    pub at: String,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// circuit_breaker.json
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Dosya içeriği. Operatör "paused" alanını false yapar (ya da dosyayı siler).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakerFile {
+    pub paused: bool,
+    pub tripped_at: String,
+    /// Otomatik denemenin açılacağı an — None: deneme kullanıldı, operatör bekleniyor
+    pub probe_at: Option<String>,
+    pub trips: u64,
+    pub counts: FailureCounts,
+    /// Tetiklemeye götüren (ve sonraki) hatalar, en yeniler
+    pub failures: Vec<FailureRecord>,
+}
+
+impl BreakerFile {
+    /// Dosya yoksa None; bozuksa uyarı + None (duraklama kabul edilmez)
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                eprintln_high!("  ⚠️  {} parse error: {} — ignored", path.display(), e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        crate::session_summary::write_atomic(path, &json)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Durum Makinesi
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,
+    /// Değerlendirme durdu — `probe_at` geçince tek deneme (None: operatör bekleniyor)
+    Paused { probe_at: Option<Instant> },
+    /// Soğuma bitti: değerlendirme açık, ilk sonuç kararı verir
+    Probing,
+}
+
+impl BreakerState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Paused { probe_at: Some(_) } => "PAUSED (cooldown)",
+            Self::Paused { probe_at: None } => "PAUSED (awaiting reset)",
+            Self::Probing => "probing",
+        }
+    }
+}
+
+/// Breaker'ın yeniden açılma nedeni
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeReason {
+    ProbeSucceeded,
+    /// Dosyada "paused": false ya da dosya silindi
+    OperatorReset,
+}
+
+impl ResumeReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ProbeSucceeded => "probe succeeded",
+            Self::OperatorReset => "operator reset",
+        }
+    }
+}
+
+/// Raporlanacak durum değişimi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Tripped,
+    Probing,
+    Resumed(ResumeReason),
+}
+
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    hard_exit: bool,
+    path: PathBuf,
+    state: BreakerState,
+    /// Son landed receipt'ten bu yana ağırlıklı revert + RPC hatası
+    exec_streak: u32,
+    /// Son başarılı doğrulamadan bu yana simülasyon hatası — iki seri
+    /// toplamı `threshold × STRIKE_WEIGHT`'te tetikler
+    sim_streak: u32,
+    /// Son başarıdan bu yana hatalar (en fazla MAX_RECORDED_FAILURES)
+    recent: Vec<FailureRecord>,
+    counts: FailureCounts,
+    trips: u64,
+    tripped_at: String,
+    /// Bu duraklamanın otomatik denemesi kullanıldı
+    probe_used: bool,
+    /// Dosya en son başarıyla yazıldı — yazılamadıysa "dosya yok" sıfırlama sayılmaz
+    persisted: bool,
+    /// Duraklamada değerlendirilmeyen bloklar
+    suppressed: u64,
+    /// Geçişler main'in blok sonu raporuna kadar bekler
+    pending: Vec<Transition>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: &BotConfig, path: PathBuf) -> Self {
+        Self {
+            threshold: config.circuit_breaker_threshold,
+            cooldown: Duration::from_secs(config.circuit_breaker_cooldown_secs),
+            hard_exit: config.circuit_breaker_hard_exit,
+            path,
+            state: BreakerState::Closed,
+            exec_streak: 0,
+            sim_streak: 0,
+            recent: Vec::new(),
+            counts: FailureCounts::default(),
+            trips: 0,
+            tripped_at: String::new(),
+            probe_used: false,
+            persisted: false,
+            suppressed: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Önceki çalıştırmanın dosyası duraklama diyorsa soğumayı baştan başlat
+    pub fn restore(&mut self, now: Instant) -> bool {
+        let Some(file) = BreakerFile::load(&self.path).filter(|f| f.paused) else {
+            return false;
+        };
+        self.state = BreakerState::Paused { probe_at: Some(now + self.cooldown) };
+        self.trips = file.trips;
+        self.tripped_at = file.tripped_at;
+        self.recent = file.failures;
+        self.persisted = true;
+        true
+    }
+
+    pub fn counts(&self) -> FailureCounts {
+        self.counts
+    }
+
+    pub fn record_failure(&mut self, kind: FailureKind, reason: String, now: Instant) {
+        self.counts.record(kind);
+        self.recent.push(FailureRecord { kind, reason, at: crate::clock::log_timestamp() });
+        let overflow = self.recent.len().saturating_sub(MAX_RECORDED_FAILURES);
+        self.recent.drain(..overflow);
+
+        match self.state {
+            // Uçuştaki TX'lerin sonuçları gelir — sayılır, karar değişmez
+            BreakerState::Paused { .. } => {}
+            // Deneme revert / simülasyon hatasıyla biterse operatör beklenir
+            BreakerState::Probing if kind != FailureKind::Rpc => self.trip(now),
+            BreakerState::Closed | BreakerState::Probing => {
+                match kind {
+                    FailureKind::Simulation => self.sim_streak += kind.weight(),
+                    FailureKind::Revert | FailureKind::Rpc => self.exec_streak += kind.weight(),
+                }
+                if self.exec_streak + self.sim_streak >= self.threshold.saturating_mul(STRIKE_WEIGHT) {
+                    self.trip(now);
+                }
+            }
+        }
+    }
+
+    /// Landed receipt: seri sıfırlanır, deneme başarılı sayılır
+    pub fn record_success(&mut self) {
+        match self.state {
+            BreakerState::Paused { .. } => return,
+            BreakerState::Probing => {
+                self.state = BreakerState::Closed;
+                self.probe_used = false;
+                self.pending.push(Transition::Resumed(ResumeReason::ProbeSucceeded));
+                self.persist();
+            }
+            BreakerState::Closed => {}
+        }
+        self.exec_streak = 0;
+        self.sim_streak = 0;
+        self.recent.clear();
+    }
+
+    /// Başarılı doğrulama: yalnızca simülasyon serisi — revert serisi ve
+    /// açık deneme receipt'e kadar sürer
+    pub fn record_validation(&mut self) {
+        if !matches!(self.state, BreakerState::Paused { .. }) {
+            self.sim_streak = 0;
+        }
+    }
+
+    fn trip(&mut self, now: Instant) {
+        // Başarısız deneme aynı duraklamanın devamı — yeni tetik sayılmaz
+        self.trips += u64::from(self.state != BreakerState::Probing);
+        self.exec_streak = 0;
+        self.sim_streak = 0;
+        self.tripped_at = crate::clock::log_timestamp();
+        self.state = BreakerState::Paused { probe_at: (!self.probe_used).then(|| now + self.cooldown) };
+        self.pending.push(Transition::Tripped);
+        self.persist();
+    }
+
+    /// Blok başı: operatör sıfırlaması ve soğuma sonu
+    pub fn on_block(&mut self, now: Instant) {
+        let BreakerState::Paused { probe_at } = self.state else {
+            return;
+        };
+        let released = self.persisted && BreakerFile::load(&self.path).is_none_or(|f| !f.paused);
+        if released {
+            self.state = BreakerState::Closed;
+            self.probe_used = false;
+            self.recent.clear();
+            self.pending.push(Transition::Resumed(ResumeReason::OperatorReset));
+        } else if probe_at.is_some_and(|at| now >= at) {
+            self.state = BreakerState::Probing;
+            self.probe_used = true;
+            self.pending.push(Transition::Probing);
+        }
+    }
+
+    /// Değerlendirme kapısı — duraklamadaysa true (sayaç artar)
+    pub fn evaluation_blocked(&mut self) -> bool {
+        let blocked = matches!(self.state, BreakerState::Paused { .. });
+        self.suppressed += u64::from(blocked);
+        blocked
+    }
+
+    fn snapshot(&self) -> BreakerFile {
+        let paused = self.state != BreakerState::Closed;
+        let probe_at = match self.state {
+            BreakerState::Paused { probe_at: Some(at) } => {
+                let wait = at.saturating_duration_since(Instant::now());
+                let wait = chrono::Duration::from_std(wait).unwrap_or(chrono::Duration::zero());
+                let at = crate::clock::local_now() + wait;
+                Some(at.format("%Y-%m-%dT%H:%M:%S%.3f").to_string())
+            }
+            _ => None,
+        };
+        BreakerFile {
+            paused,
+            tripped_at: self.tripped_at.clone(),
+            probe_at,
+            trips: self.trips,
+            counts: self.counts,
+            failures: self.recent.clone(),
+        }
+    }
+
+    fn persist(&mut self) {
+        let written = self
+            .path
+            .parent()
+            .map_or(Ok(()), crate::secure_fs::create_dir_all)
+            .and_then(|()| self.snapshot().save(&self.path));
+        self.persisted = written.is_ok();
+        if let Err(e) = written {
+            eprintln_high!(
+                "  ⚠️  [CircuitBreaker] {} write error: {} — pause lasts until cooldown / restart",
+                self.path.display(),
+                e
+            );
+        }
+    }
+
+    pub fn stats_line(&self) -> String {
+        format!(
+            "{} | {} trips | {} blocks paused | failures: {} revert, {} rpc, {} sim",
+            self.state.label(),
+            self.trips,
+            self.suppressed,
+            self.counts.revert,
+            self.counts.rpc,
+            self.counts.simulation,
+        )
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Durum
+// ─────────────────────────────────────────────────────────────────────────────
+
+static BREAKER: Mutex<Option<CircuitBreaker>> = parking_lot::const_mutex(None);
+
+fn breaker_path() -> PathBuf {
+    crate::secure_fs::data_path(CIRCUIT_BREAKER_PATH)
+}
+
+/// Eşikleri config'den al, önceki duraklamayı geri yükle (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    let mut breaker = CircuitBreaker::new(config, breaker_path());
+    if breaker.restore(Instant::now()) {
+        eprintln_high!(
+            "  {} [CircuitBreaker] {} says paused (tripped {}) — evaluation paused; probe in {}s, or set \"paused\": false / --reset-breaker",
+            "🛑".red(),
+            breaker.path.display(),
+            breaker.tripped_at,
+            config.circuit_breaker_cooldown_secs,
+        );
+    }
+    *BREAKER.lock() = Some(breaker);
+}
+
+/// --reset-breaker: dosyayı sil (başlangıçta, init'ten önce)
+pub fn reset() -> bool {
+    let path = breaker_path();
+    let removed = std::fs::remove_file(&path).is_ok();
+    if removed {
+        println_high!("  {} [CircuitBreaker] reset — removed {}", "✅".green(), path.display());
+    }
+    removed
+}
+
+/// strategy / executor: hata bildir
+pub fn record_failure(kind: FailureKind, reason: impl Into<String>) {
+    if let Some(breaker) = BREAKER.lock().as_mut() {
+        breaker.record_failure(kind, reason.into(), Instant::now());
+    }
+}
+
+/// executor: landed receipt
+pub fn record_success() {
+    if let Some(breaker) = BREAKER.lock().as_mut() {
+        breaker.record_success();
+    }
+}
+
+/// strategy: başarılı doğrulama / simülasyon
+pub fn record_validation() {
+    if let Some(breaker) = BREAKER.lock().as_mut() {
+        breaker.record_validation();
+    }
+}
+
+/// Ana döngü: blok başı, fırsat taramasından önce
+pub fn evaluation_blocked() -> bool {
+    BREAKER.lock().as_mut().is_some_and(CircuitBreaker::evaluation_blocked)
+}
+
+/// ArbitrageStats.failure_kinds kaynağı
+pub fn failure_counts() -> FailureCounts {
+    BREAKER.lock().as_ref().map(CircuitBreaker::counts).unwrap_or_default()
+}
+
+pub fn stats_line() -> Option<String> {
+    BREAKER.lock().as_ref().map(CircuitBreaker::stats_line)
+}
+
+/// Ana döngü, her blok: soğuma / sıfırlama kontrolü, bekleyen geçişlerin
+/// raporu. HARD_EXIT açıksa tetiklemede süreç burada kapanır.
+pub fn on_block(block: u64, telegram: Option<&TelegramSender>) {
+    let (transitions, file, hard_exit, cooldown, path) = {
+        let mut breaker = BREAKER.lock();
+        let Some(breaker) = breaker.as_mut() else {
+            return;
+        };
+        breaker.on_block(Instant::now());
+        let transitions = std::mem::take(&mut breaker.pending);
+        if transitions.is_empty() {
+            return;
+        }
+        (transitions, breaker.snapshot(), breaker.hard_exit, breaker.cooldown, breaker.path.clone())
+    };
+
+    for transition in transitions {
+        match transition {
+            Transition::Tripped => {
+                let reasons: Vec<String> = file
+                    .failures
+                    .iter()
+                    .rev()
+                    .take(3)
+                    .map(|f| format!("{}: {}", f.kind.label(), f.reason))
+                    .collect();
+                let next = match (&file.probe_at, hard_exit) {
+                    (_, true) => "CIRCUIT_BREAKER_HARD_EXIT=true — exiting".to_string(),
+                    (Some(at), false) => format!("probe at {} ({}s cooldown)", at, cooldown.as_secs()),
+                    (None, false) => format!("probe failed — set \"paused\": false in {} to resume", path.display()),
+                };
+                let emitted = log_event::json(|| {
+                    LogEvent::CircuitBreaker(log_event::CircuitBreaker {
+                        state: "paused",
+                        block,
+                        pair: None,
+                        consecutive_failures: None,
+                        reason: Some(format!("{} | last: {}", next, reasons.join(" | "))),
+                    })
+                });
+                if !emitted {
+                    eprintln_high!(
+                        "\n  {} {} evaluation paused at block #{} — {} | last: {}",
+                        "🛑".red(),
+                        "[CIRCUIT BREAKER]".red().bold(),
+                        block,
+                        next,
+                        reasons.join(" | "),
+                    );
+                }
+                crate::json_logger::log_json("warn", "circuit_breaker_paused", serde_json::json!({
+                    "block": block,
+                    "trips": file.trips,
+                    "probe_at": file.probe_at,
+                    "counts": file.counts,
+                    "failures": file.failures,
+                    "hard_exit": hard_exit,
+                }));
+                if let Some(tg) = telegram {
+                    tg.send(TelegramMessage::CircuitBreakerPaused {
+                        block_number: block,
+                        reasons: reasons.join("\n"),
+                        probe_in_secs: file.probe_at.as_ref().map(|_| cooldown.as_secs()),
+                    });
+                }
+                if hard_exit {
+                    // Telegram görevi kuyruktakini göndersin
+                    std::thread::sleep(Duration::from_millis(500));
+                    std::process::exit(1);
+                }
+            }
+            Transition::Probing => {
+                let emitted = log_event::json(|| {
+                    LogEvent::CircuitBreaker(log_event::CircuitBreaker {
+                        state: "probing",
+                        block,
+                        pair: None,
+                        consecutive_failures: None,
+                        reason: None,
+                    })
+                });
+                if !emitted {
+                    println_high!(
+                        "  {} [CircuitBreaker] cooldown over at block #{} — probing: next landed TX closes, a revert / simulation failure re-pauses",
+                        "🔎".yellow(),
+                        block,
+                    );
+                }
+                crate::json_logger::log_json("info", "circuit_breaker_probing", serde_json::json!({
+                    "block": block,
+                }));
+            }
+            Transition::Resumed(reason) => {
+                let emitted = log_event::json(|| {
+                    LogEvent::CircuitBreaker(log_event::CircuitBreaker {
+                        state: "resumed",
+                        block,
+                        pair: None,
+                        consecutive_failures: None,
+                        reason: Some(reason.label().to_string()),
+                    })
+                });
+                if !emitted {
+                    println_high!(
+                        "  {} [CircuitBreaker] evaluation resumed at block #{} ({})",
+                        "✅".green(),
+                        block,
+                        reason.label(),
+                    );
+                }
+                crate::json_logger::log_json("info", "circuit_breaker_resumed", serde_json::json!({
+                    "block": block,
+                    "reason": reason.label(),
+                }));
+                if let Some(tg) = telegram {
+                    tg.send(TelegramMessage::CircuitBreakerResumed {
+                        block_number: block,
+                        reason: reason.label().to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(tag: &str, cooldown_secs: u64) -> CircuitBreaker {
+        let path = std::env::temp_dir().join(format!("arb_breaker_{}_{}.json", tag, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let config = BotConfig {
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_secs: cooldown_secs,
+            ..BotConfig::for_tests()
+        };
+        CircuitBreaker::new(&config, path)
+    }
+
+    /// Üç revert tetikler; üç RPC hatası tek revert sayılır
+    #[test]
+    fn test_failure_kinds_are_weighted() {
+        let now = Instant::now();
+        let mut b = breaker("weights", 60);
+        for i in 0..8 {
+            b.record_failure(FailureKind::Rpc, format!("timeout {}", i), now);
+        }
+        assert_eq!(b.state, BreakerState::Closed, "8 RPC errors < 3 strikes");
+        b.record_success();
+        for _ in 0..2 {
+            b.record_failure(FailureKind::Revert, "status=0".into(), now);
+        }
+        b.record_failure(FailureKind::Simulation, "sim reverted".into(), now);
+        assert!(matches!(b.state, BreakerState::Paused { probe_at: Some(_) }));
+        assert_eq!(b.counts(), FailureCounts { revert: 2, rpc: 8, simulation: 1 });
+        assert_eq!(std::mem::take(&mut b.pending), vec![Transition::Tripped]);
+        assert!(b.evaluation_blocked());
+
+        // Dosya: nedenler + zaman damgaları, son başarıdan sonraki hatalar
+        let file = BreakerFile::load(&b.path).expect("written");
+        assert!(file.paused);
+        assert_eq!(file.trips, 1);
+        assert!(file.probe_at.is_some());
+        let kinds: Vec<_> = file.failures.iter().map(|f| f.kind).collect();
+        assert_eq!(kinds, [FailureKind::Revert, FailureKind::Revert, FailureKind::Simulation]);
+        assert!(file.failures.iter().all(|f| !f.at.is_empty()));
+        let _ = std::fs::remove_file(&b.path);
+    }
+
+    /// Soğuma → tek deneme: başarı kapatır; ikinci tetiklemede başarısız
+    /// deneme operatör sıfırlamasını bekler
+    #[test]
+    fn test_single_probe_then_operator_reset() {
+        let t0 = Instant::now();
+        let mut b = breaker("probe", 60);
+        for _ in 0..3 {
+            b.record_failure(FailureKind::Revert, "status=0".into(), t0);
+        }
+        b.on_block(t0 + Duration::from_secs(30));
+        assert!(matches!(b.state, BreakerState::Paused { .. }));
+        b.on_block(t0 + Duration::from_secs(61));
+        assert_eq!(b.state, BreakerState::Probing);
+        assert!(!b.evaluation_blocked());
+        // Deneme sırasında RPC takılması denemeyi bitirmez
+        b.record_failure(FailureKind::Rpc, "send timeout".into(), t0);
+        assert_eq!(b.state, BreakerState::Probing);
+        b.record_success();
+        assert_eq!(b.state, BreakerState::Closed);
+        assert!(!BreakerFile::load(&b.path).unwrap().paused);
+
+        // Yeniden tetik → deneme → simülasyon hatası: artık otomatik deneme yok
+        let t1 = t0 + Duration::from_secs(100);
+        for _ in 0..3 {
+            b.record_failure(FailureKind::Simulation, "sim reverted".into(), t1);
+        }
+        b.on_block(t1 + Duration::from_secs(61));
+        assert_eq!(b.state, BreakerState::Probing);
+        b.record_failure(FailureKind::Simulation, "sim reverted".into(), t1);
+        assert_eq!(b.state, BreakerState::Paused { probe_at: None });
+        b.on_block(t1 + Duration::from_secs(10_000));
+        assert_eq!(b.state, BreakerState::Paused { probe_at: None });
+        assert!(BreakerFile::load(&b.path).unwrap().probe_at.is_none());
+
+        // Operatör dosyada paused=false yapar → açılır
+        let mut file = BreakerFile::load(&b.path).unwrap();
+        file.paused = false;
+        file.save(&b.path).unwrap();
+        b.on_block(t1 + Duration::from_secs(10_001));
+        assert_eq!(b.state, BreakerState::Closed);
+        assert_eq!(
+            b.pending.last(),
+            Some(&Transition::Resumed(ResumeReason::OperatorReset))
+        );
+        assert_eq!(b.trips, 2);
+        let _ = std::fs::remove_file(&b.path);
+    }
+
+    /// Doğrula → gönder → revert döngüsü: aradaki başarılı doğrulamalar
+    /// revert serisini sıfırlamaz
+    #[test]
+    fn test_validations_do_not_reset_revert_streak() {
+        let now = Instant::now();
+        let mut b = breaker("interleaved", 60);
+        for _ in 0..2 {
+            b.record_validation();
+            b.record_failure(FailureKind::Revert, "status=0".into(), now);
+        }
+        b.record_validation();
+        assert_eq!(b.state, BreakerState::Closed);
+        b.record_failure(FailureKind::Revert, "status=0".into(), now);
+        assert!(matches!(b.state, BreakerState::Paused { .. }));
+
+        // Simülasyon hataları ise başarılı doğrulamayla silinir
+        let mut sim = breaker("interleaved_sim", 60);
+        for _ in 0..5 {
+            sim.record_failure(FailureKind::Simulation, "sim reverted".into(), now);
+            sim.record_failure(FailureKind::Simulation, "sim reverted".into(), now);
+            sim.record_validation();
+        }
+        assert_eq!(sim.state, BreakerState::Closed);
+        let _ = std::fs::remove_file(&b.path);
+        let _ = std::fs::remove_file(&sim.path);
+    }
+
+    /// Deneme yalnızca landed receipt ile kapanır — doğrulama yetmez
+    #[test]
+    fn test_probe_waits_for_landed_receipt() {
+        let t0 = Instant::now();
+        let mut b = breaker("probe_receipt", 60);
+        for _ in 0..3 {
+            b.record_failure(FailureKind::Revert, "status=0".into(), t0);
+        }
+        b.on_block(t0 + Duration::from_secs(61));
+        assert_eq!(b.state, BreakerState::Probing);
+        b.record_validation();
+        b.record_validation();
+        assert_eq!(b.state, BreakerState::Probing);
+        assert!(BreakerFile::load(&b.path).unwrap().paused);
+        b.record_success();
+        assert_eq!(b.state, BreakerState::Closed);
+        assert_eq!(
+            b.pending.last(),
+            Some(&Transition::Resumed(ResumeReason::ProbeSucceeded))
+        );
+        let _ = std::fs::remove_file(&b.path);
+    }
+
+    /// Yeniden başlatma: duraklama dosyası soğumayı baştan başlatır;
+    /// bozuk / paused=false dosya duraklatmaz
+    #[test]
+    fn test_restore_from_file() {
+        let now = Instant::now();
+        let mut b = breaker("restore", 60);
+        for _ in 0..3 {
+            b.record_failure(FailureKind::Revert, "status=0".into(), now);
+        }
+        let mut restarted = breaker("restore_new", 60);
+        restarted.path = b.path.clone();
+        assert!(restarted.restore(now));
+        assert!(matches!(restarted.state, BreakerState::Paused { probe_at: Some(_) }));
+        assert_eq!(restarted.trips, 1);
+        assert_eq!(restarted.recent.len(), 3);
+
+        std::fs::write(&b.path, "{not json").unwrap();
+        let mut corrupt = breaker("restore_corrupt", 60);
+        corrupt.path = b.path.clone();
+        assert!(!corrupt.restore(now));
+        assert_eq!(corrupt.state, BreakerState::Closed);
+        let _ = std::fs::remove_file(&b.path);
+    }
+}
@@ -0,0 +1,382 @@
+// ============================================================================
+//  CLOCK v1.0 — Monoton Çapalı Duvar Saati + Saat Hijyeni
+//
+//  Tazelik ve gecikme ölçümleri Instant ile yapılır; log zaman damgaları,
+//  günlük pencereler, kurma süresi ve gönderim kirası duvar saatine bakar.
+//  NTP adım düzeltmesi duvar saatini geri alırsa loglar geriye gider,
+//  günlük pencereler yeniden açılır, kira süreleri uzar.
+//
+//  ✓ Clock trait: monotonic (süreç içi, geri gitmez) + system (ham duvar
+//    saati, adımlanabilir) — SystemClock üretimde, TestClock testlerde
+//  ✓ AnchoredClock: duvar saati = çapa + monoton geçen süre → asla geri
+//    gitmez. Yeniden çapalamada ileri adım hemen benimsenir; geri adım
+//    "borç" olur ve saat yarı hızda akarak sistem saatine yetişir
+//  ✓ Süreç geneli saat: now() / local_now() / log_timestamp() — dağınık
+//    Local::now() / Utc::now() çağrılarının tek kaynağı
+//  ✓ Saat hijyeni (ilk blokta, sonra saatte bir): sistem saati ↔ son bloğun
+//    timestamp'i ve sistem saati ↔ monoton beklenti; CLOCK_SKEW_WARN_MS
+//    aşılınca yüksek sesle uyarı, son ölçümler istatistik kutusunda
+//
+//  Linux'ta CLOCK_MONOTONIC da NTP ile frekans düzeltmesi alır (yalnızca
+//  adımları görmez) — iki saat arasında kalıcı sürüklenme beklenmez.
+// ============================================================================
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use colored::*;
+use parking_lot::Mutex;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// Saat kayması uyarı eşiği — Base'de blok timestamp'i ~2s çözünürlüklü
+const CLOCK_SKEW_WARN_MS: i64 = 3_000;
+/// Hijyen kontrolü aralığı
+const CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Saat Kaynakları
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub trait Clock: Send + Sync {
+    /// Süreç içi monoton süre (kaynağın kökeninden beri)
+    fn monotonic(&self) -> Duration;
+    /// Ham sistem duvar saati — NTP ile ileri / geri adımlanabilir
+    fn system(&self) -> DateTime<Utc>;
+}
+
+/// Üretim kaynağı: Instant + Utc::now()
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self { origin: Instant::now() }
+    }
+}
+
+impl Clock for SystemClock {
+    fn monotonic(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    fn system(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Test kaynağı: monoton süre elle ilerletilir, sistem saati elle adımlanır
+#[cfg(test)]
+pub struct TestClock {
+    monotonic: Mutex<Duration>,
+    system: Mutex<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl TestClock {
+    pub fn new(system: DateTime<Utc>) -> Self {
+        Self { monotonic: Mutex::new(Duration::ZERO), system: Mutex::new(system) }
+    }
+
+    /// Gerçek zaman akışı: ikisi birlikte ilerler
+    pub fn advance(&self, by: Duration) {
+        *self.monotonic.lock() += by;
+        *self.system.lock() += chrono::Duration::from_std(by).expect("test duration");
+    }
+
+    /// NTP adımı: yalnızca sistem saati değişir (negatif → geri)
+    pub fn step_system(&self, by: chrono::Duration) {
+        *self.system.lock() += by;
+    }
+}
+
+#[cfg(test)]
+impl Clock for TestClock {
+    fn monotonic(&self) -> Duration {
+        *self.monotonic.lock()
+    }
+
+    fn system(&self) -> DateTime<Utc> {
+        *self.system.lock()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Monoton Çapalı Saat
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+struct Anchor {
+    monotonic: Duration,
+    wall: DateTime<Utc>,
+    /// Geri adımdan kalan, yarı hızda ödenecek süre
+    debt: Duration,
+}
+
+pub struct AnchoredClock<C> {
+    source: C,
+    anchor: Mutex<Anchor>,
+}
+
+impl<C: Clock> AnchoredClock<C> {
+    pub fn new(source: C) -> Self {
+        let anchor = Anchor { monotonic: source.monotonic(), wall: source.system(), debt: Duration::ZERO };
+        Self { source, anchor: Mutex::new(anchor) }
+    }
+
+    fn at(anchor: &Anchor, monotonic: Duration) -> DateTime<Utc> {
+        let elapsed = monotonic.saturating_sub(anchor.monotonic);
+        // Borç ödenirken saat yarı hızda akar: yavaşlar ama durmaz, geri gitmez
+        let repaid = (elapsed / 2).min(anchor.debt);
+        anchor.wall + chrono::Duration::from_std(elapsed - repaid).unwrap_or(chrono::Duration::zero())
+    }
+
+    /// Geri gitmeyen duvar saati (UTC)
+    pub fn now(&self) -> DateTime<Utc> {
+        Self::at(&self.anchor.lock(), self.source.monotonic())
+    }
+
+    /// Sistem saatine yeniden çapala. Döner: sistem − çapalı saat (ms);
+    /// pozitif (ileri adım) hemen benimsenir, negatif (geri adım) borç olur.
+    pub fn resync(&self) -> i64 {
+        let mut anchor = self.anchor.lock();
+        let monotonic = self.source.monotonic();
+        let ours = Self::at(&anchor, monotonic);
+        let system = self.source.system();
+        let skew = system - ours;
+        *anchor = match skew.to_std() {
+            Ok(_) => Anchor { monotonic, wall: system, debt: Duration::ZERO },
+            Err(_) => Anchor { monotonic, wall: ours, debt: (-skew).to_std().unwrap_or_default() },
+        };
+        skew.num_milliseconds()
+    }
+
+    /// Ham sistem saati (hijyen karşılaştırması için)
+    fn system(&self) -> DateTime<Utc> {
+        self.source.system()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Saat
+// ─────────────────────────────────────────────────────────────────────────────
+
+static CLOCK: LazyLock<AnchoredClock<SystemClock>> =
+    LazyLock::new(|| AnchoredClock::new(SystemClock::default()));
+
+/// Geri gitmeyen UTC duvar saati — Utc::now() yerine
+pub fn now() -> DateTime<Utc> {
+    CLOCK.now()
+}
+
+/// Geri gitmeyen yerel saat — Local::now() yerine
+pub fn local_now() -> DateTime<Local> {
+    now().with_timezone(&Local)
+}
+
+/// Yerel takvim günü (günlük pencereler)
+pub fn local_date() -> NaiveDate {
+    local_now().date_naive()
+}
+
+/// JSONL logların zaman damgası
+pub fn log_timestamp() -> String {
+    local_now().format("%Y-%m-%dT%H:%M:%S%.3f").to_string()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Saat Hijyeni
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek hijyen ölçümü
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkewReading {
+    /// Sistem saati − blok timestamp'i (ms)
+    pub block_skew_ms: i64,
+    /// Sistem saati − monoton beklenti (ms) — NTP adımı
+    pub monotonic_skew_ms: i64,
+}
+
+impl SkewReading {
+    fn new(system: DateTime<Utc>, block_timestamp: u64, monotonic_skew_ms: i64) -> Self {
+        let block_ms = i64::try_from(block_timestamp).unwrap_or(i64::MAX).saturating_mul(1000);
+        Self { block_skew_ms: system.timestamp_millis().saturating_sub(block_ms), monotonic_skew_ms }
+    }
+
+    /// Eşiği aşan ölçümlerin açıklaması (boş → sağlıklı)
+    fn violations(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        if self.block_skew_ms.abs() > CLOCK_SKEW_WARN_MS {
+            out.push(format!("system clock vs block timestamp {:+}ms", self.block_skew_ms));
+        }
+        if self.monotonic_skew_ms.abs() > CLOCK_SKEW_WARN_MS {
+            out.push(format!("system clock stepped {:+}ms vs monotonic", self.monotonic_skew_ms));
+        }
+        out
+    }
+}
+
+#[derive(Debug, Default)]
+struct Hygiene {
+    last_check: Option<Instant>,
+    last: Option<SkewReading>,
+    max_abs_block_skew_ms: i64,
+    checks: u64,
+    warnings: u64,
+}
+
+impl Hygiene {
+    fn due(&self, now: Instant) -> bool {
+        self.last_check.is_none_or(|t| now.duration_since(t) >= CHECK_INTERVAL)
+    }
+
+    fn record(&mut self, reading: SkewReading, now: Instant) -> Vec<String> {
+        self.last_check = Some(now);
+        self.last = Some(reading);
+        self.checks += 1;
+        self.max_abs_block_skew_ms = self.max_abs_block_skew_ms.max(reading.block_skew_ms.abs());
+        let violations = reading.violations();
+        if !violations.is_empty() {
+            self.warnings += 1;
+        }
+        violations
+    }
+}
+
+static HYGIENE: Mutex<Hygiene> = parking_lot::const_mutex(Hygiene {
+    last_check: None,
+    last: None,
+    max_abs_block_skew_ms: 0,
+    checks: 0,
+    warnings: 0,
+});
+
+/// Ana döngü, her blok: ilk blokta ve sonra saatte bir sistem saatini blok
+/// timestamp'i ve monoton beklentiyle karşılaştır, saati yeniden çapala
+pub fn check(block_timestamp: u64) {
+    let now = Instant::now();
+    if !HYGIENE.lock().due(now) {
+        return;
+    }
+    let monotonic_skew_ms = CLOCK.resync();
+    let reading = SkewReading::new(CLOCK.system(), block_timestamp, monotonic_skew_ms);
+    let violations = HYGIENE.lock().record(reading, now);
+    if violations.is_empty() {
+        return;
+    }
+    eprintln!(
+        "\n  {} {} {} — log timestamps and daily windows use the monotonic-anchored clock; check NTP",
+        "🕰️".red(),
+        "CLOCK SKEW:".red().bold(),
+        violations.join(" | ").red(),
+    );
+    crate::json_logger::log_json("warn", "clock_skew", serde_json::json!({
+        "block_skew_ms": reading.block_skew_ms,
+        "monotonic_skew_ms": reading.monotonic_skew_ms,
+        "threshold_ms": CLOCK_SKEW_WARN_MS,
+    }));
+}
+
+/// İstatistik kutusu satırı — ilk kontrolden önce None
+pub fn stats_line() -> Option<String> {
+    let hygiene = HYGIENE.lock();
+    let last = hygiene.last?;
+    Some(format!(
+        "vs block {:+}ms (max |{}|ms) | step {:+}ms | checks {} | warnings {}",
+        last.block_skew_ms,
+        hygiene.max_abs_block_skew_ms,
+        last.monotonic_skew_ms,
+        hygiene.checks,
+        hygiene.warnings,
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 23, 59, 50).unwrap()
+    }
+
+    /// Geri adım: saat geri gitmez, yarı hızda akarak sistem saatine yetişir
+    #[test]
+    fn test_backward_step_never_goes_backwards_and_converges() {
+        let clock = AnchoredClock::new(TestClock::new(start()));
+        clock.source.advance(Duration::from_secs(5));
+        let before = clock.now();
+        assert_eq!(before, start() + chrono::Duration::seconds(5));
+
+        clock.source.step_system(chrono::Duration::seconds(-60));
+        assert_eq!(clock.now(), before, "raw step is invisible until resync");
+        assert_eq!(clock.resync(), -60_000);
+        assert_eq!(clock.now(), before);
+
+        let mut last = clock.now();
+        for _ in 0..240 {
+            clock.source.advance(Duration::from_millis(500));
+            let now = clock.now();
+            assert!(now > last, "clock must keep moving forward");
+            last = now;
+        }
+        // 120s geçti: 60s borç yarı hızda ödendi → sistem saatiyle aynı
+        assert_eq!(clock.now(), clock.source.system());
+        clock.source.advance(Duration::from_secs(1));
+        assert_eq!(clock.now(), clock.source.system());
+    }
+
+    /// İleri adım yeniden çapalamada hemen benimsenir (monoton, sıçrar)
+    #[test]
+    fn test_forward_step_adopted_on_resync() {
+        let clock = AnchoredClock::new(TestClock::new(start()));
+        clock.source.step_system(chrono::Duration::seconds(30));
+        let before = clock.now();
+        assert_eq!(clock.resync(), 30_000);
+        assert!(clock.now() > before);
+        assert_eq!(clock.now(), clock.source.system());
+    }
+
+    /// Gece yarısını geri geçen adım: günlük pencere (UTC günü) yeniden açılmaz
+    #[test]
+    fn test_daily_window_does_not_reopen_after_backward_step() {
+        let clock = AnchoredClock::new(TestClock::new(start()));
+        let day_one = clock.now().date_naive();
+        clock.source.advance(Duration::from_secs(20));
+        let day_two = clock.now().date_naive();
+        assert_eq!(day_two, day_one.succ_opt().unwrap());
+
+        clock.source.step_system(chrono::Duration::seconds(-30));
+        clock.resync();
+        for _ in 0..100 {
+            clock.source.advance(Duration::from_millis(300));
+            assert_eq!(clock.now().date_naive(), day_two);
+        }
+    }
+
+    /// Hijyen: blok timestamp'i ve NTP adımı eşikle değerlendirilir; kontrol
+    /// ilk seferde ve sonra yalnızca CHECK_INTERVAL'de bir yapılır
+    #[test]
+    fn test_skew_reading_thresholds_and_schedule() {
+        let system = Utc.timestamp_opt(1_700_000_000, 500_000_000).unwrap();
+        let healthy = SkewReading::new(system, 1_700_000_000, 0);
+        assert_eq!(healthy.block_skew_ms, 500);
+        assert!(healthy.violations().is_empty());
+
+        let behind = SkewReading::new(system, 1_700_000_010, -4_000);
+        assert_eq!(behind.block_skew_ms, -9_500);
+        assert_eq!(behind.violations().len(), 2);
+
+        let mut hygiene = Hygiene::default();
+        let t0 = Instant::now();
+        assert!(hygiene.due(t0));
+        assert!(hygiene.record(healthy, t0).is_empty());
+        assert!(!hygiene.due(t0 + Duration::from_secs(60)));
+        assert!(hygiene.due(t0 + CHECK_INTERVAL));
+        assert_eq!(hygiene.record(behind, t0 + CHECK_INTERVAL).len(), 2);
+        assert_eq!((hygiene.checks, hygiene.warnings, hygiene.max_abs_block_skew_ms), (2, 1, 9_500));
+    }
+}
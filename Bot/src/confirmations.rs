@@ -0,0 +1,475 @@
+// ============================================================================
+//  CONFIRMATIONS v1.0 — Reorg Güvenli İşlem Muhasebesi
+//
+//  İlk receipt kesin değildir: reorg'da receipt kaybolabilir, TX başka bir
+//  blokta farklı gas / sonuçla yeniden dahil edilebilir ya da hiç edilmez.
+//  Sonuç ilk receipt'te geçici işlenir, CONFIRMATION_BLOCKS sonra yeniden
+//  okunur.
+//
+//  ✓ Durum makinesi: Provisional → Final (aynı blok hash'i)
+//                    Provisional → Provisional (başka blokta yeniden dahil,
+//                                  onay sayacı yeni bloktan başlar)
+//                    Provisional → Reorged (receipt yok) → yeniden sorgu →
+//                                  Provisional (yeniden dahil) | Vanished
+//  ✓ Yeniden kontrol receipt görevinde (arka plan) — sıcak yolda bekleme yok;
+//    yürütme slotu ilk receipt'te bırakılır
+//  ✓ Düzeltme kaydı: eski sonuç geri alınır, yenisi işlenir — her defterde
+//    ayrı sayaç (TradeOutcomes, yön defteri, ısı haritası hücresi) ve
+//    trade_correction JSON logu; sessiz düzenleme yok
+//  ✓ RPC hatası receipt kaybı sayılmaz: yeniden denenir, sürerse sonuç
+//    geçici kalır
+// ============================================================================
+
+use std::future::Future;
+use std::time::Duration;
+
+use alloy::primitives::B256;
+use alloy::rpc::types::TransactionReceipt;
+use chrono::{DateTime, Utc};
+use colored::*;
+use parking_lot::Mutex;
+
+use crate::direction_gate::{self, Attempt, Direction};
+use crate::errors::BotResult;
+use crate::executor::TradeOutcomes;
+use crate::types::BotConfig;
+
+/// Görev vazgeçmeden önce art arda izin verilen RPC hatası
+const MAX_RPC_FAILURES: u32 = 10;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Dahil Edilme + Düzeltme
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Receipt'in muhasebeye giren özeti
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Inclusion {
+    pub landed: bool,
+    pub gas_used: u64,
+    /// gas_used × effective_gas_price (wei)
+    pub gas_spent_wei: u128,
+    pub block: u64,
+    pub block_hash: B256,
+}
+
+impl Inclusion {
+    pub fn from_receipt(receipt: &TransactionReceipt) -> Self {
+        Self {
+            landed: receipt.status(),
+            gas_used: receipt.gas_used,
+            gas_spent_wei: receipt.gas_used as u128 * receipt.effective_gas_price,
+            block: receipt.block_number.unwrap_or_default(),
+            block_hash: receipt.block_hash.unwrap_or_default(),
+        }
+    }
+
+    /// TradeOutcomes::record_receipt ile aynı tanım: landed → beklenen − gas, reverted → −gas
+    pub fn pnl_weth(&self, expected_profit_weth: f64) -> f64 {
+        let gas_weth = self.gas_spent_wei as f64 / 1e18;
+        if self.landed {
+            expected_profit_weth - gas_weth
+        } else {
+            -gas_weth
+        }
+    }
+}
+
+/// Açık düzeltme kaydı: `before` geri alınır, `after` işlenir
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Correction {
+    pub tx_hash: B256,
+    pub expected_profit_weth: f64,
+    pub before: Option<Inclusion>,
+    pub after: Option<Inclusion>,
+}
+
+impl Correction {
+    pub fn kind(&self) -> &'static str {
+        match (self.before, self.after) {
+            (Some(_), Some(_)) => "reincluded_elsewhere",
+            (Some(_), None) => "receipt_disappeared",
+            (None, _) => "reincluded",
+        }
+    }
+
+    pub fn realized_before(&self) -> f64 {
+        self.before.map_or(0.0, |i| i.pnl_weth(self.expected_profit_weth))
+    }
+
+    pub fn realized_after(&self) -> f64 {
+        self.after.map_or(0.0, |i| i.pnl_weth(self.expected_profit_weth))
+    }
+
+    pub fn pnl_delta_weth(&self) -> f64 {
+        self.realized_after() - self.realized_before()
+    }
+
+    fn attempt(&self, inclusion: Option<Inclusion>) -> Option<Attempt> {
+        inclusion.map(|i| Attempt::from_receipt(i.landed, i.gas_spent_wei, self.expected_profit_weth))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Durum Makinesi
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finality {
+    /// Receipt var, onay bloğu bekleniyor
+    Provisional,
+    /// Onay bloğunda receipt aynı blok hash'inde
+    Final,
+    /// Receipt kayboldu — yeniden sorgulanıyor
+    Reorged,
+    /// Yeniden sorgular boyunca dahil edilmedi (sonuç geri alındı)
+    Vanished,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfirmPolicy {
+    /// Receipt bloğundan sonra beklenecek blok (CONFIRMATION_BLOCKS)
+    pub confirmations: u64,
+    /// Onay bloğuna kadar kalan blok başına bekleme
+    pub block_time: Duration,
+    /// Receipt kaybolduktan sonra sorgular arası bekleme
+    pub requery_interval: Duration,
+    /// Bu kadar sorguda dahil edilmezse Vanished
+    pub requery_attempts: u32,
+}
+
+impl ConfirmPolicy {
+    /// Base: ~2s blok, 10 onay
+    pub const DEFAULT: Self = Self {
+        confirmations: 10,
+        block_time: Duration::from_secs(2),
+        requery_interval: Duration::from_secs(2),
+        requery_attempts: 5,
+    };
+
+    pub fn from_config(config: &BotConfig) -> Self {
+        Self { confirmations: config.confirmation_blocks, ..Self::DEFAULT }
+    }
+}
+
+/// Onayı izlenen tek TX
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedTrade {
+    pub tx_hash: B256,
+    pub expected_profit_weth: f64,
+    pub finality: Finality,
+    /// Defterlerde şu an işli olan dahil edilme (Reorged/Vanished → None)
+    pub current: Option<Inclusion>,
+    /// Bu bloktan itibaren yeniden kontrol edilir
+    pub confirm_at: u64,
+    requeries: u32,
+}
+
+impl TrackedTrade {
+    /// İlk receipt — defterlere geçici işlendi
+    pub fn provisional(tx_hash: B256, expected_profit_weth: f64, inclusion: Inclusion, policy: &ConfirmPolicy) -> Self {
+        Self {
+            tx_hash,
+            expected_profit_weth,
+            finality: Finality::Provisional,
+            current: Some(inclusion),
+            confirm_at: inclusion.block + policy.confirmations,
+            requeries: 0,
+        }
+    }
+
+    pub fn is_settled(&self) -> bool {
+        matches!(self.finality, Finality::Final | Finality::Vanished)
+    }
+
+    fn correction(&self, after: Option<Inclusion>) -> Correction {
+        Correction { tx_hash: self.tx_hash, expected_profit_weth: self.expected_profit_weth, before: self.current, after }
+    }
+
+    /// Yeniden okunan receipt ile durumu ilerlet; defterler değişmeliyse
+    /// düzeltme kaydı döner
+    pub fn recheck(&mut self, observed: Option<Inclusion>, policy: &ConfirmPolicy) -> Option<Correction> {
+        match (self.finality, self.current, observed) {
+            (Finality::Provisional, Some(old), Some(new)) if old.block_hash == new.block_hash => {
+                self.finality = Finality::Final;
+                None
+            }
+            // Başka blokta (farklı gas / sonuç) — onay yeni bloktan sayılır
+            (Finality::Provisional | Finality::Reorged, _, Some(new)) => {
+                let correction = self.correction(Some(new));
+                self.current = Some(new);
+                self.finality = Finality::Provisional;
+                self.confirm_at = new.block + policy.confirmations;
+                self.requeries = 0;
+                Some(correction)
+            }
+            (Finality::Provisional, _, None) => {
+                let correction = self.correction(None);
+                self.current = None;
+                self.finality = Finality::Reorged;
+                self.requeries = 0;
+                Some(correction)
+            }
+            (Finality::Reorged, _, None) => {
+                self.requeries += 1;
+                if self.requeries >= policy.requery_attempts {
+                    self.finality = Finality::Vanished;
+                }
+                None
+            }
+            (Finality::Final | Finality::Vanished, _, _) => None,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Onay Görevi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Onay bloğuna kadar bekle, receipt'i yeniden oku; kesinleşene kadar tekrarla.
+/// `head` güncel blok numarasını, `receipt` TX'in güncel receipt'ini okur.
+/// Kesinleşen durum döner — RPC hataları sürerse None (sonuç geçici kalır).
+pub async fn watch<H, HF, R, RF>(
+    mut trade: TrackedTrade,
+    policy: ConfirmPolicy,
+    mut head: H,
+    mut receipt: R,
+    mut on_correction: impl FnMut(&Correction),
+) -> Option<Finality>
+where
+    H: FnMut() -> HF,
+    HF: Future<Output = BotResult<u64>>,
+    R: FnMut() -> RF,
+    RF: Future<Output = BotResult<Option<Inclusion>>>,
+{
+    let mut failures = 0u32;
+    while !trade.is_settled() {
+        if trade.finality == Finality::Provisional {
+            match head().await {
+                Ok(current) if current < trade.confirm_at => {
+                    let remaining = (trade.confirm_at - current).min(u32::MAX as u64) as u32;
+                    tokio::time::sleep(policy.block_time.saturating_mul(remaining)).await;
+                    continue;
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    failures += 1;
+                    if failures >= MAX_RPC_FAILURES {
+                        return None;
+                    }
+                    tokio::time::sleep(policy.block_time).await;
+                    continue;
+                }
+            }
+        }
+        let observed = match receipt().await {
+            Ok(observed) => observed,
+            Err(_) => {
+                failures += 1;
+                if failures >= MAX_RPC_FAILURES {
+                    return None;
+                }
+                tokio::time::sleep(policy.requery_interval).await;
+                continue;
+            }
+        };
+        if let Some(correction) = trade.recheck(observed, &policy) {
+            on_correction(&correction);
+        }
+        if trade.finality == Finality::Reorged {
+            tokio::time::sleep(policy.requery_interval).await;
+        }
+    }
+    Some(trade.finality)
+}
+
+/// Düzeltmeyi etkilenen her deftere ayrı kayıt olarak işle
+pub fn book_correction(
+    outcomes: &Mutex<TradeOutcomes>,
+    submitted_at: DateTime<Utc>,
+    direction: Option<&Direction>,
+    correction: &Correction,
+) {
+    outcomes.lock().apply_correction(correction);
+    crate::heatmap::record_correction(submitted_at, correction.pnl_delta_weth());
+    if let Some(direction) = direction {
+        direction_gate::record_correction(
+            direction,
+            correction.attempt(correction.before),
+            correction.attempt(correction.after),
+        );
+        // Çift skoru: geri alınan gerçekleşen kâr yerine yenisi
+        crate::pair_scheduler::record_realized(
+            direction.key.buy_pool,
+            direction.key.sell_pool,
+            correction.realized_before(),
+            correction.realized_after(),
+        );
+    }
+    let describe = |i: Option<Inclusion>| match i {
+        Some(i) => format!("#{} {}", i.block, if i.landed { "landed" } else { "reverted" }),
+        None => "not included".to_string(),
+    };
+    eprintln!(
+        "     {} [Reorg] {:?} {}: {} → {} | PnL {:+.6} WETH",
+        "🔄".yellow(),
+        correction.tx_hash,
+        correction.kind(),
+        describe(correction.before),
+        describe(correction.after),
+        correction.pnl_delta_weth(),
+    );
+    crate::json_logger::log_json("trade", "trade_correction", serde_json::json!({
+        "tx_hash": format!("{:?}", correction.tx_hash),
+        "kind": correction.kind(),
+        "before_block": correction.before.map(|i| i.block),
+        "after_block": correction.after.map(|i| i.block),
+        "before_landed": correction.before.map(|i| i.landed),
+        "after_landed": correction.after.map(|i| i.landed),
+        "before_gas_used": correction.before.map(|i| i.gas_used),
+        "after_gas_used": correction.after.map(|i| i.gas_used),
+        "realized_before_weth": correction.realized_before(),
+        "realized_after_weth": correction.realized_after(),
+        "pnl_delta_weth": correction.pnl_delta_weth(),
+    }));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::BotError;
+    use std::collections::VecDeque;
+
+    const TX: B256 = B256::with_last_byte(0x77);
+    const EXPECTED: f64 = 0.004;
+
+    fn policy() -> ConfirmPolicy {
+        ConfirmPolicy {
+            confirmations: 10,
+            block_time: Duration::ZERO,
+            requery_interval: Duration::ZERO,
+            requery_attempts: 3,
+        }
+    }
+
+    fn inclusion(block: u64, hash: u8, landed: bool, gas_used: u64) -> Inclusion {
+        // 1 gwei effective → gas_used × 1e9 wei
+        Inclusion {
+            landed,
+            gas_used,
+            gas_spent_wei: gas_used as u128 * 1_000_000_000,
+            block,
+            block_hash: B256::with_last_byte(hash),
+        }
+    }
+
+    #[test]
+    fn test_same_hash_at_confirmation_promotes_to_final() {
+        let first = inclusion(100, 1, true, 200_000);
+        let mut trade = TrackedTrade::provisional(TX, EXPECTED, first, &policy());
+        assert_eq!(trade.confirm_at, 110);
+        assert_eq!(trade.recheck(Some(first), &policy()), None);
+        assert_eq!(trade.finality, Finality::Final);
+        assert!(trade.is_settled());
+        // Kesinleşmiş işlem artık düzeltilmez
+        assert_eq!(trade.recheck(None, &policy()), None);
+    }
+
+    #[test]
+    fn test_disappeared_receipt_is_reversed_then_reincluded_with_new_result() {
+        let first = inclusion(100, 1, true, 200_000);
+        let mut trade = TrackedTrade::provisional(TX, EXPECTED, first, &policy());
+
+        let out = trade.recheck(None, &policy()).expect("disappearance is a correction");
+        assert_eq!(out.kind(), "receipt_disappeared");
+        assert_eq!(trade.finality, Finality::Reorged);
+        assert!((out.pnl_delta_weth() + (EXPECTED - 0.0002)).abs() < 1e-12, "{}", out.pnl_delta_weth());
+
+        // Yeniden sorgu: henüz yok → düzeltme yok, sayaç ilerler
+        assert_eq!(trade.recheck(None, &policy()), None);
+        // Başka blokta, daha pahalı gas ile revert
+        let second = inclusion(104, 2, false, 350_000);
+        let back = trade.recheck(Some(second), &policy()).expect("reinclusion is a correction");
+        assert_eq!(back.kind(), "reincluded");
+        assert_eq!((back.before, back.after), (None, Some(second)));
+        assert!((back.pnl_delta_weth() + 0.00035).abs() < 1e-12);
+        assert_eq!((trade.finality, trade.confirm_at), (Finality::Provisional, 114));
+
+        // İki düzeltmenin toplamı: ilk geçici sonuçtan son sonuca
+        let net = out.pnl_delta_weth() + back.pnl_delta_weth();
+        assert!((first.pnl_weth(EXPECTED) + net - second.pnl_weth(EXPECTED)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reincluded_elsewhere_and_vanished() {
+        let first = inclusion(100, 1, true, 200_000);
+        let mut trade = TrackedTrade::provisional(TX, EXPECTED, first, &policy());
+        let moved = inclusion(101, 9, true, 180_000);
+        let c = trade.recheck(Some(moved), &policy()).unwrap();
+        assert_eq!(c.kind(), "reincluded_elsewhere");
+        assert!((c.pnl_delta_weth() - 0.00002).abs() < 1e-12);
+        assert_eq!(trade.confirm_at, 111);
+
+        trade.recheck(None, &policy()).unwrap();
+        for _ in 0..3 {
+            assert_eq!(trade.recheck(None, &policy()), None);
+        }
+        assert_eq!(trade.finality, Finality::Vanished);
+        assert_eq!(trade.current, None);
+    }
+
+    /// Senaryolu düğüm: head + receipt yanıt kuyruğu
+    async fn run_watch(
+        first: Inclusion,
+        heads: Vec<BotResult<u64>>,
+        receipts: Vec<BotResult<Option<Inclusion>>>,
+    ) -> (Option<Finality>, Vec<Correction>) {
+        let heads = Mutex::new(VecDeque::from(heads));
+        let receipts = Mutex::new(VecDeque::from(receipts));
+        let mut corrections = Vec::new();
+        let trade = TrackedTrade::provisional(TX, EXPECTED, first, &policy());
+        let finality = watch(
+            trade,
+            policy(),
+            || std::future::ready(heads.lock().pop_front().expect("unexpected head read")),
+            || std::future::ready(receipts.lock().pop_front().expect("unexpected receipt read")),
+            |c| corrections.push(*c),
+        )
+        .await;
+        (finality, corrections)
+    }
+
+    fn rpc_error() -> BotError {
+        BotError::ExecutionUnavailable { reason: "node down" }
+    }
+
+    #[tokio::test]
+    async fn test_watch_waits_for_confirmation_block_and_follows_reorg() {
+        let first = inclusion(100, 1, true, 200_000);
+        let second = inclusion(106, 2, true, 260_000);
+        let (finality, corrections) = run_watch(
+            first,
+            // 105: erken → bekle; RPC hatası kayıp sayılmaz; 110: kontrol
+            // ikinci dahil edilmeden sonra 116'ya kadar beklenir
+            vec![Ok(105), Err(rpc_error()), Ok(110), Ok(115), Ok(116)],
+            vec![Ok(None), Err(rpc_error()), Ok(None), Ok(Some(second)), Ok(Some(second))],
+        )
+        .await;
+        assert_eq!(finality, Some(Finality::Final));
+        let kinds: Vec<_> = corrections.iter().map(Correction::kind).collect();
+        assert_eq!(kinds, ["receipt_disappeared", "reincluded"]);
+        assert_eq!(corrections[1].after, Some(second));
+    }
+
+    #[tokio::test]
+    async fn test_watch_gives_up_on_persistent_rpc_failure() {
+        let first = inclusion(100, 1, true, 200_000);
+        let heads = (0..MAX_RPC_FAILURES).map(|_| Err(rpc_error())).collect();
+        let (finality, corrections) = run_watch(first, heads, Vec::new()).await;
+        assert_eq!(finality, None, "unknown status stays provisional");
+        assert!(corrections.is_empty());
+    }
+}
@@ -0,0 +1,339 @@
+// ============================================================================
+//  CONSOLE v1.0 — Sıcak Yol İçin Bloklamayan Konsol Çıktısı
+//
+//  systemd/journald altında stdout bir pipe'tır: journal meşgulken write()
+//  bloklar ve blok işleme doğrudan durur (ölçülen: birkaç ms). Sıcak yol
+//  artık stdout'a yazmaz; satırı sınırlı bir kuyruğa bırakır, ayrı bir
+//  yazıcı thread'i stdout/stderr'e aktarır.
+//
+//  ✓ Sınırlı kuyruk (CONSOLE_QUEUE_CAPACITY satır) + tek yazıcı thread'i
+//  ✓ Düşük öncelik (blok satırı, spread bilgisi): kuyruk doluysa atılır,
+//    atılan satır sayacı tutulur
+//  ✓ Yüksek öncelik (fırsat / yürütme / hata): asla atılmaz — kapasite
+//    üstünde küçük bir taşma payı, o da doluysa yer açılana kadar bekler
+//  ✓ FIFO: yazım sırası kuyruğa giriş sırasıdır (stdout ve stderr ortak)
+//  ✓ init öncesi (testler, CLI alt komutları) doğrudan println!/eprintln!
+//  ✓ flush(): kapanışta kuyruk boşalana kadar bekler (süre sınırlı)
+// ============================================================================
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use parking_lot::{Condvar, Mutex};
+
+/// Kuyruk kapasitesi — düşük öncelikli satırlar bunun üstünde atılır
+pub const CONSOLE_QUEUE_CAPACITY: usize = 1024;
+/// Yüksek öncelikli satırlar için kapasite üstü taşma payı
+pub const CONSOLE_OVERFLOW: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    /// Blok başı güncellemeler, spread bilgisi — doluysa atılır
+    Low,
+    /// Fırsat, yürütme, hata — atılmaz
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug)]
+struct Line {
+    stream: Stream,
+    text: String,
+}
+
+#[derive(Default)]
+struct QueueState {
+    lines: VecDeque<Line>,
+    /// Yazıcı şu an bir satırı yazıyor (flush bunu da bekler)
+    writing: bool,
+}
+
+/// Üreticiler (sıcak yol) ile yazıcı thread'i arasındaki sınırlı kuyruk
+pub struct ConsoleQueue {
+    state: Mutex<QueueState>,
+    /// Yazıcıyı uyandırır (yeni satır)
+    ready: Condvar,
+    /// Bekleyen yüksek öncelikli üreticileri / flush'ı uyandırır (yer açıldı)
+    drained: Condvar,
+    capacity: usize,
+    overflow: usize,
+    dropped: AtomicU64,
+}
+
+impl ConsoleQueue {
+    pub fn new(capacity: usize, overflow: usize) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(QueueState::default()),
+            ready: Condvar::new(),
+            drained: Condvar::new(),
+            capacity,
+            overflow,
+            dropped: AtomicU64::new(0),
+        })
+    }
+
+    /// Satırı kuyruğa bırak. Düşük öncelik doluysa atılır (false döner);
+    /// yüksek öncelik kapasite + taşma payı dolana kadar beklemeden girer.
+    pub fn push(&self, priority: Priority, stream: Stream, text: String) -> bool {
+        let mut state = self.state.lock();
+        match priority {
+            Priority::Low if state.lines.len() >= self.capacity => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                return false;
+            }
+            Priority::Low => {}
+            Priority::High => {
+                while state.lines.len() >= self.capacity + self.overflow {
+                    self.drained.wait(&mut state);
+                }
+            }
+        }
+        state.lines.push_back(Line { stream, text });
+        drop(state);
+        self.ready.notify_one();
+        true
+    }
+
+    /// Kuyruk dolu olduğu için atılan düşük öncelikli satırlar
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Kuyruk boşalana (ve son satır yazılana) kadar bekle — süre aşılırsa false
+    pub fn flush(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state.lock();
+        while !state.lines.is_empty() || state.writing {
+            if self.drained.wait_until(&mut state, deadline).timed_out() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Yazıcı döngüsü: satırları sırayla alır, kilit dışında yazar
+    fn run_writer(&self, out: &mut dyn Write, err: &mut dyn Write) {
+        loop {
+            let line = {
+                let mut state = self.state.lock();
+                state.writing = false;
+                self.drained.notify_all();
+                while state.lines.is_empty() {
+                    self.ready.wait(&mut state);
+                }
+                state.writing = true;
+                state.lines.pop_front()
+            };
+            let Some(line) = line else { continue };
+            self.drained.notify_all();
+            let sink: &mut dyn Write = match line.stream {
+                Stream::Stdout => out,
+                Stream::Stderr => err,
+            };
+            // Kapanmış pipe sıcak yolu etkilememeli — hata yutulur
+            let _ = writeln!(sink, "{}", line.text);
+            let _ = sink.flush();
+        }
+    }
+}
+
+/// Yazıcı thread'ini verilen çıkışlarla başlat (testler yavaş yazıcı verir)
+pub fn spawn_writer<O, E>(queue: &Arc<ConsoleQueue>, mut out: O, mut err: E) -> io::Result<()>
+where
+    O: Write + Send + 'static,
+    E: Write + Send + 'static,
+{
+    let queue = Arc::clone(queue);
+    std::thread::Builder::new()
+        .name("console-writer".into())
+        .spawn(move || queue.run_writer(&mut out, &mut err))
+        .map(|_| ())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Kuyruk
+// ─────────────────────────────────────────────────────────────────────────────
+
+static CONSOLE: OnceLock<Arc<ConsoleQueue>> = OnceLock::new();
+
+/// Kuyruğu ve stdout/stderr yazıcısını başlat (main, bir kez)
+pub fn init() -> io::Result<()> {
+    if CONSOLE.get().is_some() {
+        return Ok(());
+    }
+    let queue = ConsoleQueue::new(CONSOLE_QUEUE_CAPACITY, CONSOLE_OVERFLOW);
+    spawn_writer(&queue, io::stdout(), io::stderr())?;
+    let _ = CONSOLE.set(queue);
+    Ok(())
+}
+
+/// Satırı yayınla — init öncesi doğrudan yazar.
+/// LOG_FORMAT=json iken satır {"event":"message"} nesnesine sarılır.
+pub fn emit(priority: Priority, stream: Stream, text: String) {
+    if crate::log_event::is_json() {
+        if let Some(line) = crate::log_event::message_line(stream, &text) {
+            write_line(priority, stream, line);
+        }
+        return;
+    }
+    write_line(priority, stream, text);
+}
+
+/// Hazır satırı kuyruğa / doğrudan yaz (sarmalama yok)
+pub fn write_line(priority: Priority, stream: Stream, text: String) {
+    match CONSOLE.get() {
+        Some(queue) => {
+            queue.push(priority, stream, text);
+        }
+        None => match stream {
+            Stream::Stdout => println!("{}", text),
+            Stream::Stderr => eprintln!("{}", text),
+        },
+    }
+}
+
+/// Atılan düşük öncelikli satır sayısı (init öncesi 0)
+pub fn dropped_lines() -> u64 {
+    CONSOLE.get().map_or(0, |queue| queue.dropped())
+}
+
+/// Kapanış: bekleyen satırlar yazılsın (en fazla `timeout`)
+pub fn flush(timeout: Duration) {
+    if let Some(queue) = CONSOLE.get() {
+        queue.flush(timeout);
+    }
+}
+
+/// Düşük öncelikli stdout satırı (blok / spread bilgisi) — kuyruk doluysa atılır
+macro_rules! println_low {
+    ($($arg:tt)*) => {
+        $crate::console::emit(
+            $crate::console::Priority::Low,
+            $crate::console::Stream::Stdout,
+            format!($($arg)*),
+        )
+    };
+}
+
+/// Yüksek öncelikli stdout satırı (fırsat / yürütme) — atılmaz
+macro_rules! println_high {
+    () => {
+        $crate::console::emit(
+            $crate::console::Priority::High,
+            $crate::console::Stream::Stdout,
+            String::new(),
+        )
+    };
+    ($($arg:tt)*) => {
+        $crate::console::emit(
+            $crate::console::Priority::High,
+            $crate::console::Stream::Stdout,
+            format!($($arg)*),
+        )
+    };
+}
+
+/// Hata satırı (stderr) — her zaman yüksek öncelik
+macro_rules! eprintln_high {
+    ($($arg:tt)*) => {
+        $crate::console::emit(
+            $crate::console::Priority::High,
+            $crate::console::Stream::Stderr,
+            format!($($arg)*),
+        )
+    };
+}
+
+pub(crate) use {eprintln_high, println_high, println_low};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Her satırda bekleyen, yazdıklarını paylaşılan tampona koyan yazıcı
+    #[derive(Clone)]
+    struct SlowSink {
+        delay: Duration,
+        lines: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Write for SlowSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            std::thread::sleep(self.delay);
+            let text = String::from_utf8_lossy(buf).trim_end().to_string();
+            if !text.is_empty() {
+                self.lines.lock().push(text);
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn slow_sink(delay: Duration) -> SlowSink {
+        SlowSink { delay, lines: Arc::new(Mutex::new(Vec::new())) }
+    }
+
+    #[test]
+    fn test_slow_writer_does_not_delay_producer() {
+        let queue = ConsoleQueue::new(64, 8);
+        let sink = slow_sink(Duration::from_millis(20));
+        spawn_writer(&queue, sink.clone(), sink.clone()).unwrap();
+
+        // 500 satır × 20ms = 10s yazım; üretici bunu beklememeli
+        let started = Instant::now();
+        for i in 0..500 {
+            queue.push(Priority::Low, Stream::Stdout, format!("block {}", i));
+        }
+        let elapsed = started.elapsed();
+        assert!(elapsed < Duration::from_millis(200), "producer blocked for {:?}", elapsed);
+        assert!(queue.dropped() > 0, "doymuş kuyruk düşük öncelikli satır atmalı");
+    }
+
+    #[test]
+    fn test_priority_lines_survive_saturation_and_drop_count_is_exact() {
+        let queue = ConsoleQueue::new(4, 2);
+        // Yazıcı henüz yok: kuyruk deterministik biçimde doyar
+        for i in 0..4 {
+            assert!(queue.push(Priority::Low, Stream::Stdout, format!("low {}", i)));
+        }
+        for i in 0..10 {
+            assert!(!queue.push(Priority::Low, Stream::Stdout, format!("dropped {}", i)));
+        }
+        assert_eq!(queue.dropped(), 10);
+
+        // Taşma payı kadar yüksek öncelik beklemeden girer
+        assert!(queue.push(Priority::High, Stream::Stdout, "opportunity 0".into()));
+        assert!(queue.push(Priority::High, Stream::Stderr, "error 1".into()));
+
+        // Pay da dolu: yüksek öncelik yazıcı yer açana kadar bekler, atılmaz
+        let sink = slow_sink(Duration::from_millis(5));
+        let blocked = {
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || queue.push(Priority::High, Stream::Stdout, "execution 2".into()))
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!blocked.is_finished(), "kapasite + pay doluyken yüksek öncelik beklemeli");
+        spawn_writer(&queue, sink.clone(), sink.clone()).unwrap();
+        assert!(blocked.join().unwrap());
+
+        assert!(queue.flush(Duration::from_secs(5)));
+        assert_eq!(queue.dropped(), 10, "yüksek öncelik sayaca girmez");
+        let written = sink.lines.lock().clone();
+        assert_eq!(
+            written,
+            vec!["low 0", "low 1", "low 2", "low 3", "opportunity 0", "error 1", "execution 2"],
+            "FIFO, atılan satır yok"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+// ============================================================================
+//  CONTRACT_STATE v1.0 — Arbitraj Kontratı Pause / Kill-Switch Kapısı
+//
+//  Admin kontratı başka bir makineden oturum ortasında duraklatırsa bot TX
+//  göndermeye devam eder; her biri "paused" hatasıyla revert edip gas yakar,
+//  ta ki circuit breaker tetiklenene kadar.
+//
+//  ✓ paused() view'ı (CONTRACT_PAUSE_SELECTOR ile başka ad / 4-byte selector)
+//    başlangıçta bir kez, sonra her blok havuz senkronizasyonunun Multicall3
+//    batch'ine eklenerek okunur — ayrı eth_call yok
+//  ✓ Duraklatılmışsa yürütme "contract paused" gerekçesiyle gölgeye düşer
+//  ✓ Her iki yöndeki geçiş (duraklatıldı / devam) log + JSON + Telegram
+//  ✓ Kontrat view'ı sunmuyorsa (revert / boş dönüş) tek seferlik uyarı,
+//    kapı devre dışı — yürütme engellenmez
+//
+//  Durum süreç geneli tek kilitte (direction_gate deseni): state_sync batch
+//  sonucunu yazar, main her blok geçişleri raporlar, strategy yürütme anında
+//  sorar.
+// ============================================================================
+
+use alloy::primitives::{keccak256, Address, Bytes};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use colored::*;
+use parking_lot::Mutex;
+
+use crate::telegram::{TelegramMessage, TelegramSender};
+use crate::types::BotConfig;
+
+/// Duraklatılmış kontrat için gölge gerekçesi
+pub const PAUSED_REASON: &str = "contract paused";
+
+sol! {
+    interface IPausable {
+        /// OpenZeppelin Pausable ile aynı imza (selector: 0x5c975abb)
+        function paused() external view returns (bool);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Selector
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Pause view'ının 4-byte selector'ı — `paused()` gibi parametresiz imza ya da
+/// doğrudan `0x5c975abb`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PauseSelector(pub [u8; 4]);
+
+impl PauseSelector {
+    pub const DEFAULT: Self = Self(IPausable::pausedCall::SELECTOR);
+
+    pub fn calldata(&self) -> Bytes {
+        Bytes::copy_from_slice(&self.0)
+    }
+}
+
+impl std::str::FromStr for PauseSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix("0x") {
+            let bytes = alloy::primitives::hex::decode(hex).map_err(|_| format!("invalid selector '{}'", s))?;
+            return bytes
+                .try_into()
+                .map(Self)
+                .map_err(|_| format!("selector must be 4 bytes, got '{}'", s));
+        }
+        let name = s
+            .strip_suffix("()")
+            .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+            .ok_or_else(|| format!("expected a parameterless view like 'paused()', got '{}'", s))?;
+        let hash = keccak256(format!("{}()", name));
+        Ok(Self([hash[0], hash[1], hash[2], hash[3]]))
+    }
+}
+
+impl std::fmt::Display for PauseSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", alloy::primitives::hex::encode(self.0))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Okuma + Durum Makinesi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek view okumasının sonucu (Multicall3 `allowFailure` çağrısı)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewRead {
+    Value(bool),
+    /// Revert ya da çözülemeyen dönüş — kontrat view'ı sunmuyor
+    Missing,
+}
+
+impl ViewRead {
+    /// ABI `bool`: tam 32 byte, 0 ya da 1 — başka her şey Missing
+    /// (fallback'i olan kontrat boş ya da anlamsız veri döndürebilir)
+    pub fn from_result(success: bool, data: &[u8]) -> Self {
+        if !success || data.len() != 32 || data[..31].iter().any(|&b| b != 0) {
+            return Self::Missing;
+        }
+        match data[31] {
+            0 => Self::Value(false),
+            1 => Self::Value(true),
+            _ => Self::Missing,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseState {
+    /// Henüz okunmadı — yürütme engellenmez
+    Unknown,
+    Running,
+    Paused,
+    /// View yok — kapı kalıcı olarak devre dışı
+    Unsupported,
+}
+
+impl PauseState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Running => "running",
+            Self::Paused => "PAUSED",
+            Self::Unsupported => "unsupported",
+        }
+    }
+}
+
+/// Raporlanacak durum değişimi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Paused,
+    Resumed,
+    /// İlk okuma view'ı bulamadı — tek seferlik uyarı
+    Unsupported,
+}
+
+#[derive(Debug)]
+pub struct PauseGate {
+    contract: Address,
+    selector: PauseSelector,
+    state: PauseState,
+    /// Geçişler main'in blok sonu raporuna kadar bekler
+    pending: Vec<Transition>,
+    paused_since_block: Option<u64>,
+    reads: u64,
+    suppressed: u64,
+}
+
+impl PauseGate {
+    pub fn new(contract: Address, selector: PauseSelector) -> Self {
+        Self {
+            contract,
+            selector,
+            state: PauseState::Unknown,
+            pending: Vec::new(),
+            paused_since_block: None,
+            reads: 0,
+            suppressed: 0,
+        }
+    }
+
+    /// Batch'e eklenecek çağrı — view yoksa None (okuma durur)
+    pub fn call(&self) -> Option<(Address, Bytes)> {
+        (self.state != PauseState::Unsupported).then(|| (self.contract, self.selector.calldata()))
+    }
+
+    /// Okumayı işle. View bir kez çözüldükten sonra gelen Missing (ör. yükseltme
+    /// sırasında tek revert) son bilinen durumu korur.
+    pub fn observe(&mut self, read: ViewRead) {
+        self.reads += 1;
+        let next = match (read, self.state) {
+            (ViewRead::Value(true), _) => PauseState::Paused,
+            (ViewRead::Value(false), _) => PauseState::Running,
+            (ViewRead::Missing, PauseState::Unknown) => PauseState::Unsupported,
+            (ViewRead::Missing, current) => current,
+        };
+        let transition = match (self.state, next) {
+            (prev, PauseState::Paused) if prev != PauseState::Paused => Some(Transition::Paused),
+            (PauseState::Paused, PauseState::Running) => Some(Transition::Resumed),
+            (PauseState::Unknown, PauseState::Unsupported) => Some(Transition::Unsupported),
+            _ => None,
+        };
+        self.state = next;
+        self.pending.extend(transition);
+    }
+
+    pub fn state(&self) -> PauseState {
+        self.state
+    }
+
+    /// Yürütme anı kapısı — duraklatılmışsa gerekçe (sayaç artar)
+    pub fn execution_block(&mut self) -> Option<&'static str> {
+        if self.state != PauseState::Paused {
+            return None;
+        }
+        self.suppressed += 1;
+        Some(PAUSED_REASON)
+    }
+
+    pub fn stats_line(&self) -> String {
+        let since = self
+            .paused_since_block
+            .filter(|_| self.state == PauseState::Paused)
+            .map(|b| format!(" since #{}", b))
+            .unwrap_or_default();
+        format!(
+            "{}{} ({} {}) | {} reads | {} trades suppressed",
+            self.state.label(),
+            since,
+            self.contract,
+            self.selector,
+            self.reads,
+            self.suppressed,
+        )
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Durum
+// ─────────────────────────────────────────────────────────────────────────────
+
+static GATE: Mutex<Option<PauseGate>> = parking_lot::const_mutex(None);
+
+/// Kontrat adresi tanımlıysa kapıyı kur (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    *GATE.lock() = config
+        .contract_address
+        .map(|contract| PauseGate::new(contract, config.contract_pause_selector));
+}
+
+/// state_sync: batch'e eklenecek (hedef, calldata)
+pub fn piggyback_call() -> Option<(Address, Bytes)> {
+    GATE.lock().as_ref().and_then(PauseGate::call)
+}
+
+/// state_sync: batch'ten ayrılan sonuç
+pub fn observe(success: bool, data: &[u8]) {
+    if let Some(gate) = GATE.lock().as_mut() {
+        gate.observe(ViewRead::from_result(success, data));
+    }
+}
+
+/// Henüz hiç okunmadı (başlangıç senkronizasyonu view'ı taşımadıysa)
+pub fn needs_read() -> bool {
+    GATE.lock().as_ref().is_some_and(|g| g.state() == PauseState::Unknown)
+}
+
+pub fn is_paused() -> bool {
+    GATE.lock().as_ref().is_some_and(|g| g.state() == PauseState::Paused)
+}
+
+/// strategy: yürütme öncesi son kapı
+pub fn execution_block() -> Option<&'static str> {
+    GATE.lock().as_mut().and_then(PauseGate::execution_block)
+}
+
+/// /status: çözülmüş pause durumu (okunamadıysa / view yoksa None)
+pub fn paused_status() -> Option<bool> {
+    GATE.lock().as_ref().and_then(|g| match g.state() {
+        PauseState::Paused => Some(true),
+        PauseState::Running => Some(false),
+        PauseState::Unknown | PauseState::Unsupported => None,
+    })
+}
+
+pub fn stats_line() -> Option<String> {
+    GATE.lock().as_ref().map(PauseGate::stats_line)
+}
+
+/// Ana döngü (ve başlangıç okuması sonrası): bekleyen geçişleri raporla
+pub fn report(block: u64, telegram: Option<&TelegramSender>) {
+    let (transitions, contract, selector, paused_blocks) = {
+        let mut gate = GATE.lock();
+        let Some(gate) = gate.as_mut() else {
+            return;
+        };
+        let transitions = std::mem::take(&mut gate.pending);
+        let mut paused_blocks = None;
+        for transition in &transitions {
+            match transition {
+                Transition::Paused => gate.paused_since_block = Some(block),
+                Transition::Resumed => {
+                    paused_blocks = gate.paused_since_block.take().map(|since| block.saturating_sub(since));
+                }
+                Transition::Unsupported => {}
+            }
+        }
+        (transitions, gate.contract, gate.selector, paused_blocks)
+    };
+
+    for transition in transitions {
+        match transition {
+            Transition::Paused => {
+                eprintln!(
+                    "\n  {} {} {} paused at block #{} — execution forced to shadow until unpaused",
+                    "🛑".red(),
+                    "[CONTRACT PAUSED]".red().bold(),
+                    contract,
+                    block,
+                );
+                crate::json_logger::log_json("warn", "contract_paused", serde_json::json!({
+                    "contract": contract.to_string(),
+                    "block": block,
+                }));
+                if let Some(tg) = telegram {
+                    tg.send(TelegramMessage::ContractPaused { contract: contract.to_string(), block_number: block });
+                }
+            }
+            Transition::Resumed => {
+                println!(
+                    "  {} [Contract] {} unpaused at block #{} — execution resumes",
+                    "✅".green(),
+                    contract,
+                    block,
+                );
+                crate::json_logger::log_json("info", "contract_unpaused", serde_json::json!({
+                    "contract": contract.to_string(),
+                    "block": block,
+                    "paused_blocks": paused_blocks,
+                }));
+                if let Some(tg) = telegram {
+                    tg.send(TelegramMessage::ContractUnpaused {
+                        contract: contract.to_string(),
+                        block_number: block,
+                        paused_blocks,
+                    });
+                }
+            }
+            Transition::Unsupported => {
+                eprintln!(
+                    "  {} [Contract] {} has no pause view {} (reverted / undecodable) — pause gate disabled",
+                    "⚠️".yellow(),
+                    contract,
+                    selector,
+                );
+                crate::json_logger::log_json("warn", "contract_pause_view_missing", serde_json::json!({
+                    "contract": contract.to_string(),
+                    "selector": selector.to_string(),
+                }));
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    const CONTRACT: Address = address!("00000000000000000000000000000000000000aa");
+
+    fn word(value: u8) -> Vec<u8> {
+        let mut data = vec![0u8; 32];
+        data[31] = value;
+        data
+    }
+
+    #[test]
+    fn test_selector_parsing() {
+        assert_eq!(PauseSelector::DEFAULT.to_string(), "0x5c975abb");
+        assert_eq!("paused()".parse::<PauseSelector>(), Ok(PauseSelector::DEFAULT));
+        assert_eq!("0x5c975abb".parse::<PauseSelector>(), Ok(PauseSelector::DEFAULT));
+        // isPaused() — keccak256("isPaused()")[..4]
+        assert_eq!("isPaused()".parse::<PauseSelector>().unwrap().to_string(), "0xb187bd26");
+        assert!("paused(bool)".parse::<PauseSelector>().is_err());
+        assert!("0x5c975a".parse::<PauseSelector>().is_err());
+        assert!("paused".parse::<PauseSelector>().is_err());
+    }
+
+    #[test]
+    fn test_paused_and_unpaused_results() {
+        let mut gate = PauseGate::new(CONTRACT, PauseSelector::DEFAULT);
+        assert_eq!(gate.call(), Some((CONTRACT, Bytes::from(vec![0x5c, 0x97, 0x5a, 0xbb]))));
+
+        // Başlangıç: çalışıyor — geçiş yok, yürütme serbest
+        gate.observe(ViewRead::from_result(true, &word(0)));
+        assert_eq!(gate.state(), PauseState::Running);
+        assert!(gate.pending.is_empty());
+        assert_eq!(gate.execution_block(), None);
+
+        // Admin duraklattı → gölge + tek geçiş (tekrar okumalar yeni geçiş üretmez)
+        gate.observe(ViewRead::from_result(true, &word(1)));
+        gate.observe(ViewRead::from_result(true, &word(1)));
+        assert_eq!(gate.pending, vec![Transition::Paused]);
+        assert_eq!(gate.execution_block(), Some(PAUSED_REASON));
+
+        // Tek seferlik revert son bilinen durumu korur
+        gate.observe(ViewRead::from_result(false, &[]));
+        assert_eq!(gate.state(), PauseState::Paused);
+
+        gate.observe(ViewRead::from_result(true, &word(0)));
+        assert_eq!(gate.pending, vec![Transition::Paused, Transition::Resumed]);
+        assert_eq!(gate.execution_block(), None);
+        assert_eq!(gate.suppressed, 1);
+        assert!(gate.call().is_some());
+    }
+
+    #[test]
+    fn test_missing_selector_disables_gate_once() {
+        // Revert (view yok)
+        let mut gate = PauseGate::new(CONTRACT, PauseSelector::DEFAULT);
+        gate.observe(ViewRead::from_result(false, &[]));
+        assert_eq!(gate.state(), PauseState::Unsupported);
+        assert_eq!(gate.pending, vec![Transition::Unsupported]);
+        assert_eq!(gate.call(), None);
+        assert_eq!(gate.execution_block(), None);
+
+        // Fallback'li kontrat: başarılı ama boş / bool olmayan dönüş
+        for data in [Vec::new(), word(7), vec![1u8; 32]] {
+            let mut gate = PauseGate::new(CONTRACT, PauseSelector::DEFAULT);
+            gate.observe(ViewRead::from_result(true, &data));
+            assert_eq!(gate.state(), PauseState::Unsupported, "{:?}", data);
+            gate.observe(ViewRead::from_result(true, &[]));
+            assert_eq!(gate.pending, vec![Transition::Unsupported], "uyarı tek sefer");
+        }
+    }
+
+    #[test]
+    fn test_paused_at_startup_notifies() {
+        let mut gate = PauseGate::new(CONTRACT, "isPaused()".parse().unwrap());
+        gate.observe(ViewRead::from_result(true, &word(1)));
+        assert_eq!(gate.pending, vec![Transition::Paused]);
+        assert!(gate.stats_line().starts_with("PAUSED"));
+        assert!(gate.stats_line().contains("0xb187bd26"));
+    }
+}
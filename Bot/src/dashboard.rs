@@ -0,0 +1,897 @@
+// ============================================================================
+//  DASHBOARD v1.0 — Etkileşimli Terminal Paneli (--tui)
+//
+//  Kayan println akışının yerine tek ekran:
+//  ✓ Havuz başına fiyat / tick / likidite + son blokların sparkline'ı
+//  ✓ Executor sign / send gecikme histogramları (oturum özetiyle aynı kaynak)
+//  ✓ Isınma, circuit breaker, pipeline bütçesi ve mod (LIVE/SHADOW/PAUSED)
+//  ✓ Son fırsatlar tablosu: yürütülen / gölge / filtrelenen / başarısız
+//  ✓ Alt satır: transport, sağlıklı RPC düğümü, son blok yaşı
+//
+//  Tuşlar:
+//  q / Ctrl+C → terminal geri yüklenir, normal kapanış yolu tetiklenir
+//  p          → yürütmeyi duraklat / sürdür (çalışırken gölge moda zorlar)
+//  s          → bir sonraki blokta anlık oturum özeti yazılır
+//
+//  Sayaçlar ayrı tutulmaz: headless moddaki ArbitrageStats'ın blok başı
+//  kopyası çizilir. Panel açıkken stdout/stderr TUI_CONSOLE_LOG_PATH'e
+//  yönlendirilir (yalnızca unix). Terminal yoksa veya platform desteklemiyorsa
+//  uyarı verilir ve println çıktısı aynen sürer.
+// ============================================================================
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use alloy::primitives::Address;
+use parking_lot::Mutex;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use tokio_util::sync::CancellationToken;
+
+use crate::executor::HistogramSnapshot;
+use crate::types::{ArbitrageStats, PoolConfig, SharedPoolState};
+
+/// Panel açıkken println/eprintln çıktısının yazıldığı dosya
+pub const TUI_CONSOLE_LOG_PATH: &str = "tui_console.log";
+/// Havuz başına tutulan fiyat geçmişi (blok)
+pub const PRICE_HISTORY_LEN: usize = 100;
+/// Fırsat tablosunda tutulan satır sayısı
+pub const MAX_TABLE_ROWS: usize = 50;
+/// Yeniden çizim + tuş yoklama aralığı
+const FRAME_INTERVAL: Duration = Duration::from_millis(250);
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Modeli
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct PoolPanel {
+    pub address: Address,
+    pub name: String,
+    pub price: f64,
+    pub tick: i32,
+    pub liquidity: f64,
+    /// En eski → en yeni, en fazla PRICE_HISTORY_LEN
+    pub history: VecDeque<f64>,
+}
+
+/// Fırsat tablosu satırının sonucu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowStatus {
+    /// Simülasyon geçti, TX gönderildi
+    Executed,
+    /// Simülasyon geçti, yürütme kapalı (shadow mode)
+    Shadow,
+    /// Filtre / ısınma / duraklatma yürütmeyi engelledi
+    Skipped(&'static str),
+    /// Değerlendirme veya simülasyon başarısız
+    Failed,
+}
+
+impl RowStatus {
+    pub fn label(self) -> String {
+        match self {
+            RowStatus::Executed => "executed".to_string(),
+            RowStatus::Shadow => "shadow".to_string(),
+            RowStatus::Skipped(filter) => format!("skip:{}", filter),
+            RowStatus::Failed => "failed".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeRow {
+    pub block: u64,
+    pub pair: String,
+    pub amount_weth: f64,
+    pub profit_weth: f64,
+    pub status: RowStatus,
+}
+
+/// run_bot'un blok sonunda panele verdiği görünüm
+pub struct BlockView<'a> {
+    pub block_number: u64,
+    pub pools: &'a [PoolConfig],
+    pub states: &'a [SharedPoolState],
+    pub stats: &'a ArbitrageStats,
+    pub latency: BTreeMap<&'static str, HistogramSnapshot>,
+    /// None = ısınma tamamlandı
+    pub warmup_label: Option<String>,
+    pub blacklisted_pairs: usize,
+    pub pipeline_ms: u128,
+    pub pipeline_budget_ms: u128,
+    pub transport: String,
+    pub healthy_nodes: usize,
+}
+
+#[derive(Clone)]
+pub struct DashboardModel {
+    pub execution_enabled: bool,
+    pub pools: Vec<PoolPanel>,
+    pub stats: Option<ArbitrageStats>,
+    pub latency: BTreeMap<&'static str, HistogramSnapshot>,
+    /// En yeni başta
+    pub rows: VecDeque<TradeRow>,
+    pub warmup_label: Option<String>,
+    pub blacklisted_pairs: usize,
+    pub pipeline_ms: u128,
+    pub pipeline_budget_ms: u128,
+    pub transport: String,
+    pub healthy_nodes: usize,
+    pub last_block: u64,
+    pub last_block_at: Option<Instant>,
+    /// Son tuş işleminin geri bildirimi
+    pub notice: Option<String>,
+}
+
+impl DashboardModel {
+    pub fn new(execution_enabled: bool) -> Self {
+        Self {
+            execution_enabled,
+            pools: Vec::new(),
+            stats: None,
+            latency: BTreeMap::new(),
+            rows: VecDeque::with_capacity(MAX_TABLE_ROWS),
+            warmup_label: None,
+            blacklisted_pairs: 0,
+            pipeline_ms: 0,
+            pipeline_budget_ms: 0,
+            transport: String::new(),
+            healthy_nodes: 0,
+            last_block: 0,
+            last_block_at: None,
+            notice: None,
+        }
+    }
+
+    /// Blok sonu güncellemesi. Havuzlar adrese göre eşlenir — hot-reload'da
+    /// kalan havuzların geçmişi korunur, çıkarılanlar düşer.
+    pub fn on_block(&mut self, view: BlockView<'_>, now: Instant) {
+        let mut previous = std::mem::take(&mut self.pools);
+        for (config, shared) in view.pools.iter().zip(view.states) {
+            let state = shared.load();
+            let mut panel = match previous.iter().position(|p| p.address == config.address) {
+                Some(i) => previous.swap_remove(i),
+                None => PoolPanel {
+                    address: config.address,
+                    name: config.name.clone(),
+                    price: 0.0,
+                    tick: 0,
+                    liquidity: 0.0,
+                    history: VecDeque::with_capacity(PRICE_HISTORY_LEN),
+                },
+            };
+            if state.is_active() {
+                panel.price = state.eth_price_usd;
+                panel.tick = state.tick;
+                panel.liquidity = state.liquidity_approx();
+                if panel.history.len() == PRICE_HISTORY_LEN {
+                    panel.history.pop_front();
+                }
+                panel.history.push_back(state.eth_price_usd);
+            }
+            self.pools.push(panel);
+        }
+
+        self.stats = Some(view.stats.clone());
+        self.latency = view.latency;
+        self.warmup_label = view.warmup_label;
+        self.blacklisted_pairs = view.blacklisted_pairs;
+        self.pipeline_ms = view.pipeline_ms;
+        self.pipeline_budget_ms = view.pipeline_budget_ms;
+        self.transport = view.transport;
+        self.healthy_nodes = view.healthy_nodes;
+        self.last_block = view.block_number;
+        self.last_block_at = Some(now);
+    }
+
+    /// Yeni satır başa eklenir; MAX_TABLE_ROWS üstü en eskiden düşer
+    pub fn push_row(&mut self, row: TradeRow) {
+        self.rows.push_front(row);
+        self.rows.truncate(MAX_TABLE_ROWS);
+    }
+
+    pub fn mode_label(&self, paused: bool) -> &'static str {
+        if paused {
+            "PAUSED"
+        } else if self.execution_enabled {
+            "LIVE"
+        } else {
+            "SHADOW"
+        }
+    }
+}
+
+/// Son `width` fiyatı 0..=100 aralığına ölçekler (min → 0, max → 100).
+/// Düz seri ortada çizilir.
+pub fn sparkline_points(history: &VecDeque<f64>, width: usize) -> Vec<u64> {
+    let skip = history.len().saturating_sub(width);
+    let window: Vec<f64> = history.iter().skip(skip).copied().collect();
+    let min = window.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    window
+        .iter()
+        .map(|&p| {
+            if range > 0.0 {
+                (((p - min) / range) * 100.0).round() as u64
+            } else {
+                50
+            }
+        })
+        .collect()
+}
+
+/// Histogram kovası → (kısa etiket, sayım); son kova taşma kovasıdır
+pub fn histogram_bars(snapshot: &HistogramSnapshot) -> Vec<(String, u64)> {
+    let mut bars: Vec<(String, u64)> = snapshot
+        .bucket_upper_us
+        .iter()
+        .zip(&snapshot.bucket_counts)
+        .map(|(&upper, &count)| (format_us(upper), count))
+        .collect();
+    if let Some(&overflow) = snapshot.bucket_counts.get(snapshot.bucket_upper_us.len()) {
+        bars.push(("∞".to_string(), overflow));
+    }
+    bars
+}
+
+fn format_us(us: u64) -> String {
+    if us >= 1_000 {
+        format!("{}m", us / 1_000)
+    } else {
+        format!("{}µ", us)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kontroller (render thread ↔ bot döngüsü)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Default)]
+pub struct Controls {
+    paused: AtomicBool,
+    summary_requested: AtomicBool,
+    stop: AtomicBool,
+    /// `q` ile iptal edilir — main() bunu Ctrl+C ile aynı yoldan karşılar
+    pub shutdown: CancellationToken,
+}
+
+impl Controls {
+    /// Duraklatmayı tersine çevirir, yeni durumu döndürür
+    pub fn toggle_pause(&self) -> bool {
+        !self.paused.fetch_xor(true, Ordering::Relaxed)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn request_summary(&self) {
+        self.summary_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Bekleyen özet isteğini tüketir (istek başına bir kez true)
+    pub fn take_summary_request(&self) -> bool {
+        self.summary_requested.swap(false, Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    Quit,
+    Paused(bool),
+    SummaryRequested,
+    Ignored,
+}
+
+pub fn handle_key(controls: &Controls, key: KeyEvent) -> KeyAction {
+    if key.kind != KeyEventKind::Press {
+        return KeyAction::Ignored;
+    }
+    match key.code {
+        // Raw mode'da Ctrl+C SIGINT üretmez — tuş olarak gelir
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Quit,
+        KeyCode::Char('q') => KeyAction::Quit,
+        KeyCode::Char('p') => KeyAction::Paused(controls.toggle_pause()),
+        KeyCode::Char('s') => {
+            controls.request_summary();
+            KeyAction::SummaryRequested
+        }
+        _ => KeyAction::Ignored,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Sahibi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// run_bot'a paslanan paylaşımlı tutamaç
+#[derive(Clone)]
+pub struct DashboardHandle {
+    pub model: Arc<Mutex<DashboardModel>>,
+    pub controls: Arc<Controls>,
+}
+
+/// main() scope'unda yaşar; drop'ta render thread durdurulur ve terminal
+/// geri yüklenir
+pub struct Dashboard {
+    handle: DashboardHandle,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl DashboardHandle {
+    pub fn push_row(&self, row: TradeRow) {
+        self.model.lock().push_row(row);
+    }
+}
+
+impl Dashboard {
+    /// Terminal yoksa / desteklenmiyorsa uyarı basıp None döner
+    pub fn start(execution_enabled: bool) -> Option<Self> {
+        use std::io::IsTerminal;
+        if !std::io::stdout().is_terminal() {
+            eprintln!("  ⚠️ [TUI] stdout is not a terminal — falling back to line output");
+            return None;
+        }
+        let session = match terminal::TerminalSession::enter() {
+            Ok(session) => session,
+            Err(e) => {
+                eprintln!("  ⚠️ [TUI] Terminal unavailable ({}) — falling back to line output", e);
+                return None;
+            }
+        };
+
+        let handle = DashboardHandle {
+            model: Arc::new(Mutex::new(DashboardModel::new(execution_enabled))),
+            controls: Arc::new(Controls::default()),
+        };
+        let thread_handle = handle.clone();
+        let thread = std::thread::Builder::new()
+            .name("tui-render".to_string())
+            .spawn(move || terminal::render_loop(session, thread_handle))
+            .ok()?;
+        Some(Self { handle, thread: Some(thread) })
+    }
+
+    pub fn handle(&self) -> &DashboardHandle {
+        &self.handle
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        self.handle.controls.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Terminal + Çizim
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(unix)]
+mod terminal {
+    use std::fs::{File, OpenOptions};
+    use std::io::{self, Write};
+    use std::os::unix::io::AsRawFd;
+    use std::sync::atomic::Ordering;
+    use std::time::Instant;
+
+    use ratatui::backend::CrosstermBackend;
+    use ratatui::crossterm::event::{self, Event};
+    use ratatui::crossterm::execute;
+    use ratatui::crossterm::terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+    };
+    use ratatui::layout::{Constraint, Layout, Rect};
+    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::text::{Line, Span};
+    use ratatui::widgets::{BarChart, Block, Cell, Paragraph, Row, Sparkline, Table};
+    use ratatui::{Frame, Terminal};
+
+    use super::*;
+
+    /// stdout/stderr → dosya; drop'ta özgün fd'ler geri yüklenir
+    struct ConsoleRedirect {
+        saved: [libc::c_int; 2],
+    }
+
+    const CONSOLE_FDS: [libc::c_int; 2] = [libc::STDOUT_FILENO, libc::STDERR_FILENO];
+
+    impl ConsoleRedirect {
+        fn to_file(path: &std::path::Path) -> io::Result<Self> {
+            let file = crate::secure_fs::open_append(path)?;
+            let _ = io::stdout().flush();
+            let _ = io::stderr().flush();
+            // Kısmi hatada drop zaten yönlendirilmiş fd'leri geri alır
+            let mut redirect = Self { saved: [-1; 2] };
+            for (slot, fd) in redirect.saved.iter_mut().zip(CONSOLE_FDS) {
+                // SAFETY: dup yalnızca açık bir fd'nin kopyasını üretir; hata -1 döner
+                let copy = unsafe { libc::dup(fd) };
+                if copy < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                *slot = copy;
+                // SAFETY: iki fd de açık; dup2 hedefi atomik olarak değiştirir.
+                // `file` kapansa da fd 1/2 kendi kopyalarını tutar.
+                if unsafe { libc::dup2(file.as_raw_fd(), fd) } < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            Ok(redirect)
+        }
+    }
+
+    impl Drop for ConsoleRedirect {
+        fn drop(&mut self) {
+            let _ = io::stdout().flush();
+            let _ = io::stderr().flush();
+            for (saved, fd) in self.saved.into_iter().zip(CONSOLE_FDS) {
+                if saved >= 0 {
+                    // SAFETY: `saved` to_file'da dup ile alınmış ve yalnızca burada kapanır
+                    unsafe {
+                        libc::dup2(saved, fd);
+                        libc::close(saved);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Raw mode + alternate screen; drop sırası: terminal geri yüklenir,
+    /// ardından konsol yönlendirmesi kalkar
+    pub(super) struct TerminalSession {
+        terminal: Terminal<CrosstermBackend<File>>,
+        _console: ConsoleRedirect,
+    }
+
+    impl TerminalSession {
+        pub(super) fn enter() -> io::Result<Self> {
+            // stdout birazdan log dosyasına gideceği için doğrudan tty'ye çizilir
+            let mut tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+            enable_raw_mode()?;
+            let setup = (|| -> io::Result<Self> {
+                execute!(tty, EnterAlternateScreen)?;
+                let console =
+                    ConsoleRedirect::to_file(&crate::secure_fs::data_path(TUI_CONSOLE_LOG_PATH))?;
+                let mut terminal = Terminal::new(CrosstermBackend::new(tty))?;
+                terminal.hide_cursor()?;
+                terminal.clear()?;
+                Ok(Self { terminal, _console: console })
+            })();
+            if setup.is_err() {
+                let _ = disable_raw_mode();
+            }
+            setup
+        }
+    }
+
+    impl Drop for TerminalSession {
+        fn drop(&mut self) {
+            let _ = disable_raw_mode();
+            let _ = execute!(self.terminal.backend_mut(), LeaveAlternateScreen);
+            let _ = self.terminal.show_cursor();
+        }
+    }
+
+    pub(super) fn render_loop(mut session: TerminalSession, handle: DashboardHandle) {
+        let controls = &handle.controls;
+        let mut quit = false;
+        while !controls.stop.load(Ordering::Relaxed) {
+            // Kilit çizim süresince tutulmaz — bot döngüsü beklemez
+            let model = handle.model.lock().clone();
+            let paused = controls.is_paused();
+            if session.terminal.draw(|f| draw(f, &model, paused)).is_err() {
+                break;
+            }
+            if !event::poll(FRAME_INTERVAL).unwrap_or(false) {
+                continue;
+            }
+            let Ok(Event::Key(key)) = event::read() else {
+                continue;
+            };
+            let notice = match handle_key(controls, key) {
+                KeyAction::Quit => {
+                    quit = true;
+                    break;
+                }
+                KeyAction::Paused(true) => "Execution paused — opportunities forced to shadow",
+                KeyAction::Paused(false) => "Execution resumed",
+                KeyAction::SummaryRequested => "Session summary will be written on next block",
+                KeyAction::Ignored => continue,
+            };
+            handle.model.lock().notice = Some(notice.to_string());
+        }
+        // Terminal kapanış mesajlarından önce geri yüklenir
+        drop(session);
+        if quit {
+            controls.shutdown.cancel();
+        }
+    }
+
+    fn draw(frame: &mut Frame, model: &DashboardModel, paused: bool) {
+        let [pools_area, middle_area, table_area, footer_area] = Layout::vertical([
+            Constraint::Length(model.pools.len().max(1) as u16 + 2),
+            Constraint::Length(10),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        draw_pools(frame, pools_area, model);
+
+        let [sign_area, send_area, status_area] = Layout::horizontal([
+            Constraint::Length(44),
+            Constraint::Length(44),
+            Constraint::Min(30),
+        ])
+        .areas(middle_area);
+        draw_histogram(frame, sign_area, model, "sign");
+        draw_histogram(frame, send_area, model, "send");
+        draw_status(frame, status_area, model, paused);
+
+        draw_table(frame, table_area, model);
+        draw_footer(frame, footer_area, model);
+    }
+
+    fn draw_pools(frame: &mut Frame, area: Rect, model: &DashboardModel) {
+        let block = Block::bordered().title(" Pools ");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::vertical(vec![Constraint::Length(1); model.pools.len()]).split(inner);
+        for (panel, row) in model.pools.iter().zip(rows.iter()) {
+            let [text_area, spark_area] =
+                Layout::horizontal([Constraint::Length(56), Constraint::Min(10)]).areas(*row);
+            let text = format!(
+                "{:<14} {:>12.6}Q  tick {:>8}  L {:>10.3e}",
+                panel.name.chars().take(14).collect::<String>(),
+                panel.price,
+                panel.tick,
+                panel.liquidity,
+            );
+            frame.render_widget(Paragraph::new(text), text_area);
+            let points = sparkline_points(&panel.history, spark_area.width as usize);
+            frame.render_widget(
+                Sparkline::default()
+                    .data(points.as_slice())
+                    .max(100)
+                    .style(Style::default().fg(Color::Cyan)),
+                spark_area,
+            );
+        }
+    }
+
+    fn draw_histogram(frame: &mut Frame, area: Rect, model: &DashboardModel, stage: &str) {
+        let snapshot = model.latency.get(stage).cloned().unwrap_or_default();
+        let avg_us = snapshot.total_us.checked_div(snapshot.count).unwrap_or(0);
+        let title = format!(
+            " {} µs — n={} avg={} max={} ",
+            stage, snapshot.count, avg_us, snapshot.max_us
+        );
+        let bars = histogram_bars(&snapshot);
+        let data: Vec<(&str, u64)> = bars.iter().map(|(label, n)| (label.as_str(), *n)).collect();
+        frame.render_widget(
+            BarChart::default()
+                .block(Block::bordered().title(title))
+                .data(data.as_slice())
+                .bar_width(4)
+                .bar_gap(1)
+                .bar_style(Style::default().fg(Color::Magenta)),
+            area,
+        );
+    }
+
+    fn draw_status(frame: &mut Frame, area: Rect, model: &DashboardModel, paused: bool) {
+        let mode = model.mode_label(paused);
+        let mode_color = match mode {
+            "LIVE" => Color::Green,
+            "PAUSED" => Color::Red,
+            _ => Color::Yellow,
+        };
+        let warmup = model.warmup_label.clone().unwrap_or_else(|| "armed".to_string());
+        let budget_color = if model.pipeline_ms > model.pipeline_budget_ms {
+            Color::Red
+        } else {
+            Color::Reset
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::raw("Mode: "),
+                Span::styled(mode, Style::default().fg(mode_color).add_modifier(Modifier::BOLD)),
+            ]),
+            Line::from(format!("Warmup: {}", warmup)),
+            Line::from(Span::styled(
+                format!(
+                    "Pipeline: {}ms / {}ms budget",
+                    model.pipeline_ms, model.pipeline_budget_ms
+                ),
+                Style::default().fg(budget_color),
+            )),
+        ];
+        if let Some(ref stats) = model.stats {
+            lines.push(Line::from(format!(
+                "Breaker: {} trips | {} pairs blacklisted | {} consecutive failures",
+                stats.breaker_trips, model.blacklisted_pairs, stats.consecutive_failures,
+            )));
+            lines.push(Line::from(format!(
+                "Block latency: avg {:.1}ms | min {:.1}ms | max {:.1}ms | spikes {}",
+                stats.avg_block_latency_ms,
+                stats.min_block_latency_ms,
+                stats.max_block_latency_ms,
+                stats.latency_spikes,
+            )));
+            lines.push(Line::from(format!(
+                "Trades: {} executed | {} sim fail | shadow {}/{} ({:.6} WETH)",
+                stats.executed_trades,
+                stats.failed_simulations,
+                stats.shadow_sim_success,
+                stats.shadow_sim_fail,
+                stats.shadow_cumulative_profit,
+            )));
+        }
+        if let Some(ref notice) = model.notice {
+            lines.push(Line::from(Span::styled(
+                notice.clone(),
+                Style::default().fg(Color::Cyan),
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Status ")),
+            area,
+        );
+    }
+
+    fn draw_table(frame: &mut Frame, area: Rect, model: &DashboardModel) {
+        let rows = model.rows.iter().map(|row| {
+            let color = match row.status {
+                RowStatus::Executed => Color::Green,
+                RowStatus::Shadow => Color::Cyan,
+                RowStatus::Skipped(_) => Color::Yellow,
+                RowStatus::Failed => Color::Red,
+            };
+            Row::new(vec![
+                Cell::from(row.block.to_string()),
+                Cell::from(row.pair.clone()),
+                Cell::from(format!("{:.4}", row.amount_weth)),
+                Cell::from(format!("{:.8}", row.profit_weth)),
+                Cell::from(Span::styled(row.status.label(), Style::default().fg(color))),
+            ])
+        });
+        let header = Row::new(["Block", "Pair", "Amount WETH", "Profit WETH", "Status"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Min(16),
+                Constraint::Length(12),
+                Constraint::Length(14),
+                Constraint::Length(24),
+            ],
+        )
+        .header(header)
+        .block(Block::bordered().title(" Opportunities "));
+        frame.render_widget(table, area);
+    }
+
+    fn draw_footer(frame: &mut Frame, area: Rect, model: &DashboardModel) {
+        let age = model
+            .last_block_at
+            .map(|t| format!("{:.1}s ago", Instant::now().duration_since(t).as_secs_f64()))
+            .unwrap_or_else(|| "—".to_string());
+        let text = format!(
+            " {} | {} healthy nodes | block #{} ({}) | q quit · p pause · s summary",
+            model.transport, model.healthy_nodes, model.last_block, age,
+        );
+        frame.render_widget(
+            Paragraph::new(text).style(Style::default().fg(Color::DarkGray)),
+            area,
+        );
+    }
+}
+
+#[cfg(not(unix))]
+mod terminal {
+    use std::io;
+
+    use super::*;
+
+    /// stdout yönlendirmesi unix fd'lerine dayanır — diğer platformlarda kapalı
+    pub(super) struct TerminalSession;
+
+    impl TerminalSession {
+        pub(super) fn enter() -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "TUI is only supported on unix terminals",
+            ))
+        }
+    }
+
+    pub(super) fn render_loop(_session: TerminalSession, _handle: DashboardHandle) {}
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::LATENCY_BUCKETS_US;
+    use crate::strategy::gas_spike_tests::{make_pool_configs, make_pool_state};
+
+    fn view<'a>(
+        block: u64,
+        pools: &'a [PoolConfig],
+        states: &'a [SharedPoolState],
+        stats: &'a ArbitrageStats,
+    ) -> BlockView<'a> {
+        BlockView {
+            block_number: block,
+            pools,
+            states,
+            stats,
+            latency: BTreeMap::from([("sign", HistogramSnapshot::default())]),
+            warmup_label: Some("warmup 3/10".to_string()),
+            blacklisted_pairs: 1,
+            pipeline_ms: 120,
+            pipeline_budget_ms: 1500,
+            transport: "WS".to_string(),
+            healthy_nodes: 2,
+        }
+    }
+
+    fn row(block: u64) -> TradeRow {
+        TradeRow {
+            block,
+            pair: "WETH/USDC".to_string(),
+            amount_weth: 1.0,
+            profit_weth: 0.001,
+            status: RowStatus::Shadow,
+        }
+    }
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_on_block_updates_panels_and_caps_history() {
+        let pools = make_pool_configs();
+        let stats = ArbitrageStats::new();
+        let mut model = DashboardModel::new(false);
+        let now = Instant::now();
+
+        for block in 0..(PRICE_HISTORY_LEN as u64 + 5) {
+            let price = 2500.0 + block as f64;
+            let states = vec![
+                make_pool_state(price, 1_000_000_000_000_000_000, block),
+                make_pool_state(price + 1.0, 1_000_000_000_000_000_000, block),
+            ];
+            model.on_block(view(block, &pools, &states, &stats), now);
+        }
+
+        assert_eq!(model.pools.len(), 2);
+        assert_eq!(model.pools[0].address, pools[0].address);
+        let history = &model.pools[0].history;
+        assert_eq!(history.len(), PRICE_HISTORY_LEN);
+        // En eski 5 blok düştü, son fiyat sonda
+        assert!((history[0] - 2505.0).abs() < 1e-6, "oldest={}", history[0]);
+        assert!((history[PRICE_HISTORY_LEN - 1] - 2604.0).abs() < 1e-6);
+        assert!((model.pools[0].price - 2604.0).abs() < 1e-6);
+        assert_eq!(model.last_block, PRICE_HISTORY_LEN as u64 + 4);
+        assert_eq!(model.warmup_label.as_deref(), Some("warmup 3/10"));
+        assert_eq!(model.blacklisted_pairs, 1);
+        assert!(model.latency.contains_key("sign"));
+        assert!(model.stats.is_some());
+    }
+
+    #[test]
+    fn test_on_block_matches_pools_by_address_on_reload() {
+        let pools = make_pool_configs();
+        let stats = ArbitrageStats::new();
+        let mut model = DashboardModel::new(true);
+        let now = Instant::now();
+        let states = vec![
+            make_pool_state(2500.0, 1_000_000_000_000_000_000, 1),
+            make_pool_state(2501.0, 1_000_000_000_000_000_000, 1),
+        ];
+        model.on_block(view(1, &pools, &states, &stats), now);
+        model.on_block(view(2, &pools, &states, &stats), now);
+
+        // Hot-reload: B önce gelir, A çıkarılır
+        let reloaded = vec![pools[1].clone()];
+        let reloaded_states = vec![make_pool_state(2502.0, 1_000_000_000_000_000_000, 3)];
+        model.on_block(view(3, &reloaded, &reloaded_states, &stats), now);
+
+        assert_eq!(model.pools.len(), 1);
+        assert_eq!(model.pools[0].address, pools[1].address);
+        // B'nin geçmişi korundu: 2 eski + 1 yeni
+        assert_eq!(model.pools[0].history.len(), 3);
+    }
+
+    #[test]
+    fn test_sparkline_points_normalize_recent_window() {
+        let history: VecDeque<f64> = [10.0, 2000.0, 2500.0, 2750.0, 3000.0].into_iter().collect();
+        // Genişlik 4 → ilk (10.0) nokta dışarıda kalır, ölçek 2000..3000
+        assert_eq!(sparkline_points(&history, 4), vec![0, 50, 75, 100]);
+
+        let flat: VecDeque<f64> = [2500.0; 3].into_iter().collect();
+        assert_eq!(sparkline_points(&flat, 10), vec![50, 50, 50]);
+        assert!(sparkline_points(&VecDeque::new(), 10).is_empty());
+    }
+
+    #[test]
+    fn test_histogram_bars_include_overflow_bucket() {
+        let mut snapshot = HistogramSnapshot::default();
+        snapshot.bucket_counts[0] = 3;
+        snapshot.bucket_counts[LATENCY_BUCKETS_US.len()] = 2;
+        let bars = histogram_bars(&snapshot);
+        assert_eq!(bars.len(), LATENCY_BUCKETS_US.len() + 1);
+        assert_eq!(bars[0], ("100µ".to_string(), 3));
+        assert_eq!(bars[2].0, "1m");
+        assert_eq!(bars.last().unwrap(), &("∞".to_string(), 2));
+    }
+
+    #[test]
+    fn test_push_row_newest_first_and_capped() {
+        let mut model = DashboardModel::new(false);
+        for block in 0..(MAX_TABLE_ROWS as u64 + 10) {
+            model.push_row(row(block));
+        }
+        assert_eq!(model.rows.len(), MAX_TABLE_ROWS);
+        assert_eq!(model.rows.front().unwrap().block, MAX_TABLE_ROWS as u64 + 9);
+        assert_eq!(model.rows.back().unwrap().block, 10);
+    }
+
+    #[test]
+    fn test_pause_toggle_forces_shadow_mode_label() {
+        let controls = Controls::default();
+        let model = DashboardModel::new(true);
+        assert_eq!(model.mode_label(controls.is_paused()), "LIVE");
+
+        assert_eq!(handle_key(&controls, press(KeyCode::Char('p'))), KeyAction::Paused(true));
+        assert!(controls.is_paused());
+        assert_eq!(model.mode_label(controls.is_paused()), "PAUSED");
+
+        assert_eq!(handle_key(&controls, press(KeyCode::Char('p'))), KeyAction::Paused(false));
+        assert!(!controls.is_paused());
+        assert_eq!(DashboardModel::new(false).mode_label(false), "SHADOW");
+    }
+
+    #[test]
+    fn test_summary_request_consumed_once() {
+        let controls = Controls::default();
+        assert!(!controls.take_summary_request());
+        assert_eq!(
+            handle_key(&controls, press(KeyCode::Char('s'))),
+            KeyAction::SummaryRequested
+        );
+        assert!(controls.take_summary_request());
+        assert!(!controls.take_summary_request());
+    }
+
+    #[test]
+    fn test_quit_keys_and_ignored_events() {
+        let controls = Controls::default();
+        assert_eq!(handle_key(&controls, press(KeyCode::Char('q'))), KeyAction::Quit);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(handle_key(&controls, ctrl_c), KeyAction::Quit);
+        assert_eq!(handle_key(&controls, press(KeyCode::Char('c'))), KeyAction::Ignored);
+
+        // Bırakma olayları (kitty protokolü) ikinci kez toggle etmez
+        let mut release = press(KeyCode::Char('p'));
+        release.kind = KeyEventKind::Release;
+        assert_eq!(handle_key(&controls, release), KeyAction::Ignored);
+        assert!(!controls.is_paused());
+        // handle_key shutdown'ı kendisi iptal etmez — terminal önce geri yüklenir
+        assert!(!controls.shutdown.is_cancelled());
+    }
+}
@@ -0,0 +1,365 @@
+// ============================================================================
+//  DEGRADATION LADDER v1.0 — Gecikme Bütçesi Aşımında Sıralı Özellik Atma
+//
+//  Baskı altında (yavaş RPC, CPU çekişmesi) bot her aşamada biraz yavaşlamak
+//  yerine isteğe bağlı aşamaları tanımlı sırayla bırakır; çekirdek tespit en
+//  son daralır.
+//
+//  ✓ Basamaklar (atılma sırası): 1 A/B ikincil değerlendirme + multi-hop
+//    taraması → 2 near-miss defteri (filtrelenen fırsatın gölge simülasyonu)
+//    → 3 ters senaryo, kâr hassasiyeti, iki adımlı plan → 4 bitmap
+//    genişletme (bayat bitmap kullanılır) → 5 iki yönlü flash rota
+//    karşılaştırması → 6 çekirdek tespit (yalnızca ilk sıradaki çift)
+//  ✓ LADDER tek kaynak: isteğe bağlı her özellik basamak numarasıyla kayıtlı
+//  ✓ GUARDS (staleness, pool_health, execution_sanity, freshness_gate,
+//    warmup) merdivende değildir — hiçbir baskıda atılmaz
+//  ✓ Kontrol noktaları: blok başından birikimli süre / blok bütçesi; basamak
+//    eşikleri DEGRADE_START_PCT'den %100'e eşit aralıklı. Blok içinde seviye
+//    yalnızca artar (bloğun kalanı için)
+//  ✓ Histerezis: atılan basamak bir sonraki bloğa taşınır; tepe baskı en üst
+//    basamağın eşiğinin HYSTERESIS_PCT altında DEGRADE_RECOVERY_BLOCKS blok
+//    kalınca basamaklar tek tek, ters sırayla geri açılır
+//  ✓ Blok başına atılan basamaklar stats kutusunda ve state_diff kaydında
+// ============================================================================
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use crate::types::BotConfig;
+
+/// Basamak sayısı — 6 = çekirdek tespit
+pub const RUNGS: usize = 6;
+
+/// Geri açma payı: tepe baskı eşiğin bu kadar yüzde puan altında kalmalı
+const HYSTERESIS_PCT: f64 = 10.0;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kayıtlı Özellikler
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Merdivene kayıtlı isteğe bağlı aşama — `rung` küçük olan önce atılır
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Feature {
+    pub name: &'static str,
+    pub rung: u8,
+}
+
+pub const AB_SHADOW: Feature = Feature { name: "ab_shadow", rung: 1 };
+pub const MULTI_HOP_SCAN: Feature = Feature { name: "multi_hop_scan", rung: 1 };
+pub const NEAR_MISS: Feature = Feature { name: "near_miss", rung: 2 };
+pub const ADVERSE_SCENARIO: Feature = Feature { name: "adverse_scenario", rung: 3 };
+pub const PROFIT_SENSITIVITY: Feature = Feature { name: "profit_sensitivity", rung: 3 };
+pub const TWO_STEP_PLAN: Feature = Feature { name: "two_step_plan", rung: 3 };
+pub const BITMAP_REFRESH: Feature = Feature { name: "bitmap_refresh", rung: 4 };
+pub const BOTH_DIRECTIONS: Feature = Feature { name: "both_directions", rung: 5 };
+/// Atılınca blokta yalnızca ilk sıradaki çift değerlendirilir
+pub const CORE_DETECTION: Feature = Feature { name: "core_detection", rung: RUNGS as u8 };
+
+/// Tek kaynak: yeni isteğe bağlı aşama buraya basamağıyla eklenir
+pub const LADDER: [Feature; 9] = [
+    AB_SHADOW,
+    MULTI_HOP_SCAN,
+    NEAR_MISS,
+    ADVERSE_SCENARIO,
+    PROFIT_SENSITIVITY,
+    TWO_STEP_PLAN,
+    BITMAP_REFRESH,
+    BOTH_DIRECTIONS,
+    CORE_DETECTION,
+];
+
+/// Hiçbir baskıda atılmayan koruyucular (filtre / kapı adları) — merdivende yok
+pub const GUARDS: [&str; 5] = ["staleness", "pool_health", "execution_sanity", "freshness_gate", "warmup"];
+
+/// `level`'e kadar atılan özelliklerin adları (log)
+pub fn shed_names(level: u8) -> Vec<&'static str> {
+    LADDER.iter().filter(|f| f.rung <= level).map(|f| f.name).collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Senkron Aşamalar İçin Seviye (check_arbitrage_opportunity)
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Tespit senkron çalışır; seviye çağrı boyunca iş parçacığına yazılır. Ayar
+// yapılmamış çağıranlar (replay, what-if, testler) her aşamayı çalıştırır.
+
+thread_local! {
+    static SHED_LEVEL: Cell<u8> = const { Cell::new(0) };
+}
+
+/// `f` süresince `level`'e kadar olan basamaklar atılmış sayılır
+pub fn with_shed<T>(level: u8, f: impl FnOnce() -> T) -> T {
+    let previous = SHED_LEVEL.replace(level);
+    let output = f();
+    SHED_LEVEL.set(previous);
+    output
+}
+
+/// Senkron aşama çalışsın mı? (with_shed dışında her zaman true)
+pub fn allows(feature: Feature) -> bool {
+    feature.rung > SHED_LEVEL.get()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Politika
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Bloğun ölçüldüğü nokta (log etiketi)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checkpoint {
+    /// Sync + koruyucular bitti, tarama başlıyor
+    PreScan,
+    /// Bir çift değerlendirilmeden önce
+    PairScan,
+    /// En iyi fırsat seçildi, hassasiyet / yürütme öncesi
+    Selected,
+    /// Yürütme sonrası ikincil değerlendirmeler (A/B, multi-hop)
+    Secondary,
+}
+
+impl Checkpoint {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PreScan => "pre_scan",
+            Self::PairScan => "pair_scan",
+            Self::Selected => "selected",
+            Self::Secondary => "secondary",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DegradationPolicy {
+    budget: Duration,
+    /// Basamak 1'in eşiği (bütçe yüzdesi) — None = merdiven kapalı
+    start_pct: Option<f64>,
+    recovery_blocks: u64,
+    /// Bloğa taşınan seviye (histerezis durumu)
+    carried: u8,
+    /// Bloğun güncel seviyesi (≥ carried)
+    level: u8,
+    /// Bloğun kontrol noktalarındaki en yüksek baskı (bütçe yüzdesi)
+    peak_pct: f64,
+    calm_blocks: u64,
+}
+
+impl DegradationPolicy {
+    /// `start_pct` = 0 → merdiven kapalı (seviye hep 0)
+    pub fn new(budget: Duration, start_pct: u64, recovery_blocks: u64) -> Self {
+        Self {
+            budget,
+            start_pct: (start_pct > 0).then_some(start_pct as f64),
+            recovery_blocks: recovery_blocks.max(1),
+            carried: 0,
+            level: 0,
+            peak_pct: 0.0,
+            calm_blocks: 0,
+        }
+    }
+
+    pub fn from_config(config: &BotConfig, budget: Duration) -> Self {
+        Self::new(budget, config.degrade_start_pct, config.degrade_recovery_blocks)
+    }
+
+    /// Basamağın atıldığı baskı (bütçe yüzdesi): start'tan 100'e eşit aralık
+    /// Merdiven kapalıysa sonsuz (hiç aşılmaz)
+    pub fn threshold_pct(&self, rung: u8) -> f64 {
+        self.start_pct
+            .map_or(f64::INFINITY, |start| start + (100.0 - start) * rung.saturating_sub(1) as f64 / RUNGS as f64)
+    }
+
+    pub fn begin_block(&mut self) {
+        self.level = self.carried;
+        self.peak_pct = 0.0;
+    }
+
+    /// Birikimli süreyi ölç; seviye yükseldiyse yeni seviye döner
+    pub fn checkpoint(&mut self, elapsed: Duration) -> Option<u8> {
+        let pressure_pct = elapsed.as_secs_f64() * 100.0 / self.budget.as_secs_f64().max(f64::EPSILON);
+        self.peak_pct = self.peak_pct.max(pressure_pct);
+        let target = (1..=RUNGS as u8).filter(|&r| pressure_pct >= self.threshold_pct(r)).count() as u8;
+        (target > self.level).then(|| {
+            self.level = target;
+            target
+        })
+    }
+
+    /// Blok sonu: bloğun seviyesi döner, histerezis güncellenir
+    pub fn end_block(&mut self) -> u8 {
+        if self.level > self.carried {
+            self.carried = self.level;
+            self.calm_blocks = 0;
+        } else if self.carried > 0 && self.peak_pct < self.threshold_pct(self.carried) - HYSTERESIS_PCT {
+            self.calm_blocks += 1;
+            if self.calm_blocks >= self.recovery_blocks {
+                self.carried -= 1;
+                self.calm_blocks = 0;
+            }
+        } else {
+            self.calm_blocks = 0;
+        }
+        self.level
+    }
+
+    pub fn shed_level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn allows(&self, feature: Feature) -> bool {
+        feature.rung > self.level
+    }
+}
+
+/// Stats kutusu: basamak başına atıldığı blok sayısı (`shed_blocks[r-1]`)
+pub fn stats_line(shed_blocks: &[u64; RUNGS]) -> Option<String> {
+    if shed_blocks[0] == 0 {
+        return None;
+    }
+    let rungs: Vec<String> = (1..=RUNGS as u8)
+        .zip(shed_blocks)
+        .filter(|(_, blocks)| **blocks > 0)
+        .map(|(rung, blocks)| format!("r{} {}", rung, blocks))
+        .collect();
+    Some(format!("shed blocks — {}", rungs.join(" | ")))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET_MS: u64 = 1_000;
+
+    fn policy() -> DegradationPolicy {
+        DegradationPolicy::new(Duration::from_millis(BUDGET_MS), 40, 3)
+    }
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    /// Bir blok: kontrol noktalarında verilen birikimli süreler; bloğun seviyesi
+    fn run_block(p: &mut DegradationPolicy, checkpoints_ms: &[u64]) -> u8 {
+        p.begin_block();
+        for &elapsed in checkpoints_ms {
+            p.checkpoint(ms(elapsed));
+        }
+        p.end_block()
+    }
+
+    #[test]
+    fn test_ladder_sheds_in_declared_order() {
+        let p = policy();
+        // 40, 50, 60, 70, 80, 90 %
+        let thresholds: Vec<f64> = (1..=RUNGS as u8).map(|r| p.threshold_pct(r)).collect();
+        assert_eq!(thresholds, [40.0, 50.0, 60.0, 70.0, 80.0, 90.0]);
+
+        let mut p = policy();
+        p.begin_block();
+        let mut shed_order = Vec::new();
+        for elapsed in (0..=BUDGET_MS).step_by(25) {
+            if let Some(level) = p.checkpoint(ms(elapsed)) {
+                shed_order.push(level);
+                // Yeni seviyeye kadar olan her özellik atılmış, üstü açık
+                for feature in LADDER {
+                    assert_eq!(p.allows(feature), feature.rung > level, "{} at level {}", feature.name, level);
+                }
+            }
+        }
+        assert_eq!(shed_order, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            shed_names(3),
+            ["ab_shadow", "multi_hop_scan", "near_miss", "adverse_scenario", "profit_sensitivity", "two_step_plan"]
+        );
+    }
+
+    #[test]
+    fn test_spec_order_and_guards_never_on_ladder() {
+        let rung_of = |name: &str| LADDER.iter().find(|f| f.name == name).map(|f| f.rung).unwrap();
+        let order = [
+            "ab_shadow",
+            "near_miss",
+            "adverse_scenario",
+            "profit_sensitivity",
+            "bitmap_refresh",
+            "both_directions",
+            "core_detection",
+        ];
+        assert!(order.windows(2).all(|w| rung_of(w[0]) <= rung_of(w[1])), "{:?}", order);
+        assert_eq!(rung_of("core_detection") as usize, RUNGS, "core detection degrades last");
+        assert_eq!(LADDER.iter().filter(|f| f.rung as usize == RUNGS).count(), 1);
+
+        for guard in GUARDS {
+            assert!(LADDER.iter().all(|f| f.name != guard), "guard {} must not be sheddable", guard);
+        }
+        // Bütçenin çok üstünde bile koruyucu kapatılamaz: kayıtlı değil, seviye 6'da kalır
+        let mut p = policy();
+        p.begin_block();
+        assert_eq!(p.checkpoint(ms(BUDGET_MS * 10)), Some(RUNGS as u8));
+        assert_eq!(p.checkpoint(ms(BUDGET_MS * 100)), None);
+        assert_eq!(p.shed_level() as usize, RUNGS);
+    }
+
+    #[test]
+    fn test_level_only_rises_within_block_and_disabled_ladder_never_sheds() {
+        let mut p = policy();
+        p.begin_block();
+        assert_eq!(p.checkpoint(ms(550)), Some(2));
+        // Bloğun kalanında baskı düşse de seviye korunur
+        assert_eq!(p.checkpoint(ms(100)), None);
+        assert!(!p.allows(NEAR_MISS));
+        assert_eq!(p.end_block(), 2);
+
+        let mut off = DegradationPolicy::new(ms(BUDGET_MS), 0, 3);
+        assert_eq!(run_block(&mut off, &[5_000, 50_000]), 0);
+        assert!(LADDER.iter().all(|&f| off.allows(f)));
+    }
+
+    #[test]
+    fn test_hysteresis_restores_one_rung_after_calm_blocks() {
+        let mut p = policy();
+        // Seviye 3'e çıkan blok (60%)
+        assert_eq!(run_block(&mut p, &[200, 610]), 3);
+        // Sonraki blok atılmış seviyeyle başlar — hafiflemiş blok bile
+        assert_eq!(run_block(&mut p, &[100]), 3);
+        assert_eq!(run_block(&mut p, &[100]), 3);
+        // 3. sakin blok (eşik 60 − 10 = 50% altında) → bir basamak geri
+        assert_eq!(run_block(&mut p, &[100]), 3);
+        assert_eq!(run_block(&mut p, &[100]), 2);
+
+        // Eşiğe yakın salınım (45..55%) geri açmaz: 50 − 10 = 40% altı gerekir
+        for _ in 0..10 {
+            assert_eq!(run_block(&mut p, &[450]), 2);
+        }
+        // Sayaç sıfırlanır: 2 sakin + 1 gergin + 2 sakin → hâlâ 2
+        for elapsed in [100, 100, 450, 100, 100] {
+            assert_eq!(run_block(&mut p, &[elapsed]), 2);
+        }
+        assert_eq!(run_block(&mut p, &[100]), 2);
+        assert_eq!(run_block(&mut p, &[100]), 1);
+        for _ in 0..3 {
+            run_block(&mut p, &[100]);
+        }
+        assert_eq!(run_block(&mut p, &[100]), 0);
+    }
+
+    #[test]
+    fn test_with_shed_scopes_sync_stages() {
+        assert!(allows(ADVERSE_SCENARIO) && allows(BOTH_DIRECTIONS));
+        let inside = with_shed(3, || (allows(ADVERSE_SCENARIO), allows(BOTH_DIRECTIONS)));
+        assert_eq!(inside, (false, true));
+        assert!(allows(ADVERSE_SCENARIO), "level restored after the call");
+    }
+
+    #[test]
+    fn test_stats_line_counts_rungs() {
+        assert_eq!(stats_line(&[0; RUNGS]), None);
+        assert_eq!(
+            stats_line(&[5, 2, 0, 0, 0, 0]).unwrap(),
+            "shed blocks — r1 5 | r2 2"
+        );
+    }
+}
@@ -0,0 +1,730 @@
+// ============================================================================
+//  DIRECTION_GATE v1.0 — Yön Bazlı Geçmiş Başarı Kapısı
+//
+//  Bir çiftin iki yönü aynı modelle fiyatlanır ama zincirde aynı davranmaz:
+//  sistematik bir model asimetrisi bir yönü sürekli MinProfitNotMet revert'üne
+//  düşürürken bot iki yöne de gas ödemeye devam eder.
+//
+//  ✓ (buy_pool, sell_pool) başına receipt defteri: landed / reverted /
+//    gerçekleşen PnL (receipt hattından beslenir)
+//  ✓ Son DIRECTION_GATE_WINDOW denemenin başarı oranı eşiğin altına inerse
+//    yön kısıtlanır: multiplier (kâr ≥ MIN_NET_PROFIT × çarpan) ya da suspend
+//  ✓ Kısıtlı yönün fırsatları "direction_gate" Degrade'i ile gölge loglanır
+//  ✓ Kısıtlama DIRECTION_GATE_COOLDOWN_BLOCKS sonra, gölge fırsatların
+//    modellenen kârı kısıtlama anındaki kenarın DIRECTION_GATE_EDGE_WIDEN
+//    katına çıkınca ya da (multiplier modunda) başarı oranı toparlanınca kalkar
+//  ✓ Yön istatistikleri stats kutusunda ve oturum özetinde
+//
+//  GATE kilidi yön defterini üç bağlam arasında tutarlı tutar: receipt ve
+//  reorg onay görevleri yazar, filtre zinciri ve stats kutusu okur. Eşikler
+//  init'te bir kez GatePolicy'ye çevrilir — receipt hattına config inmez.
+// ============================================================================
+
+use alloy::primitives::Address;
+use colored::*;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+use crate::opportunity_filter::FilterDecision;
+use crate::types::{BotConfig, PoolConfig};
+
+/// Kenar genişlemesi kararı için gereken son gölge gözlem sayısı
+const LIFT_SAMPLES: usize = 5;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Anahtar + Politika
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Kısıtlama biçimi (DIRECTION_GATE_MODE)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectionGateMode {
+    /// Yön yalnızca kâr ≥ MIN_NET_PROFIT_WETH × çarpan ise yürütülür
+    Multiplier,
+    /// Yön bekleme süresince hiç yürütülmez (gölge log sürer)
+    Suspend,
+}
+
+impl std::fmt::Display for DirectionGateMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DirectionGateMode::Multiplier => write!(f, "multiplier"),
+            DirectionGateMode::Suspend => write!(f, "suspend"),
+        }
+    }
+}
+
+/// Yön: ucuz havuzdan al, pahalı havuza sat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirectionKey {
+    pub buy_pool: Address,
+    pub sell_pool: Address,
+}
+
+/// Receipt hattına taşınan yön (anahtar + stats etiketi)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Direction {
+    pub key: DirectionKey,
+    pub label: String,
+}
+
+impl Direction {
+    pub fn new(buy: &PoolConfig, sell: &PoolConfig) -> Self {
+        Self {
+            key: DirectionKey { buy_pool: buy.address, sell_pool: sell.address },
+            label: format!("{} → {}", buy.name, sell.name),
+        }
+    }
+}
+
+/// Kapı eşikleri (DIRECTION_GATE_*)
+#[derive(Debug, Clone, Copy)]
+pub struct GatePolicy {
+    /// Başarı oranının hesaplandığı son deneme sayısı (0 = kapı kapalı)
+    pub window: usize,
+    /// Karar için pencerede gereken en az deneme
+    pub min_attempts: usize,
+    pub min_success_rate: f64,
+    pub mode: DirectionGateMode,
+    pub profit_multiplier: f64,
+    pub cooldown_blocks: u64,
+    /// Gölge kenarı / kısıtlama kenarı bu oranı geçince kısıtlama kalkar
+    pub edge_widen: f64,
+}
+
+impl GatePolicy {
+    pub fn from_config(config: &BotConfig) -> Self {
+        Self {
+            window: config.direction_gate_window as usize,
+            min_attempts: config.direction_gate_min_attempts as usize,
+            min_success_rate: config.direction_gate_min_success_rate,
+            mode: config.direction_gate_mode,
+            profit_multiplier: config.direction_gate_profit_multiplier,
+            cooldown_blocks: config.direction_gate_cooldown_blocks,
+            edge_widen: config.direction_gate_edge_widen,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Yön Defteri
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek receipt'in yön defterine giren özeti
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Attempt {
+    pub landed: bool,
+    /// landed → beklenen kâr − gas, reverted → −gas (WETH)
+    pub pnl_weth: f64,
+    /// Gönderim anında modellenen kâr (kenar)
+    pub modeled_profit_weth: f64,
+}
+
+impl Attempt {
+    /// Receipt'ten (TradeOutcomes::record_receipt ile aynı PnL tanımı)
+    pub fn from_receipt(landed: bool, gas_spent_wei: u128, expected_profit_weth: f64) -> Self {
+        let gas_weth = gas_spent_wei as f64 / 1e18;
+        Self {
+            landed,
+            pnl_weth: if landed { expected_profit_weth - gas_weth } else { -gas_weth },
+            modeled_profit_weth: expected_profit_weth,
+        }
+    }
+}
+
+/// Yürürlükteki kısıtlama
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Restriction {
+    pub mode: DirectionGateMode,
+    pub since_block: u64,
+    /// Bu bloğa ulaşılınca kalkar
+    pub until_block: u64,
+    /// Kısıtlama anındaki pencere başarı oranı
+    pub success_rate: f64,
+    /// Kısıtlama anında penceredeki denemelerin ortalama modellenen kârı
+    pub baseline_edge_weth: f64,
+}
+
+/// Kısıtlamanın kalkma nedeni
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiftReason {
+    CooldownExpired,
+    EdgeWidened,
+    Recovered,
+}
+
+impl LiftReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            LiftReason::CooldownExpired => "cooldown expired",
+            LiftReason::EdgeWidened => "shadow edge widened",
+            LiftReason::Recovered => "success rate recovered",
+        }
+    }
+}
+
+/// Kapı olayı (konsol + JSON log)
+#[derive(Debug, Clone, PartialEq)]
+pub enum GateEvent {
+    Restricted(Restriction),
+    Lifted(LiftReason),
+}
+
+#[derive(Debug, Clone, Default)]
+struct DirectionBook {
+    label: String,
+    attempts: u64,
+    landed: u64,
+    reverted: u64,
+    realized_pnl_weth: f64,
+    /// Son `window` deneme
+    window: VecDeque<Attempt>,
+    restriction: Option<Restriction>,
+    restrictions: u64,
+    /// Kısıtlıyken gölge loglanan fırsatların modellenen kârı (son LIFT_SAMPLES)
+    shadow_edges: VecDeque<f64>,
+    shadow_skips: u64,
+    /// Reorg düzeltme kayıtları (receipt kayboldu / başka blokta dahil edildi)
+    corrections: u64,
+}
+
+impl DirectionBook {
+    fn success_rate(&self) -> Option<f64> {
+        if self.window.is_empty() {
+            return None;
+        }
+        let landed = self.window.iter().filter(|a| a.landed).count();
+        Some(landed as f64 / self.window.len() as f64)
+    }
+
+    fn mean_window_edge(&self) -> f64 {
+        if self.window.is_empty() {
+            return 0.0;
+        }
+        self.window.iter().map(|a| a.modeled_profit_weth).sum::<f64>() / self.window.len() as f64
+    }
+
+    /// Kısıtlamayı kaldır — pencere ve gölge örnekleri sıfırlanır ki eski
+    /// denemeler yönü hemen yeniden kısıtlamasın
+    fn lift(&mut self) {
+        self.restriction = None;
+        self.window.clear();
+        self.shadow_edges.clear();
+    }
+}
+
+/// Oturum özeti satırı (yön başına)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DirectionSummary {
+    pub direction: String,
+    pub buy_pool: String,
+    pub sell_pool: String,
+    pub attempts: u64,
+    pub landed: u64,
+    pub reverted: u64,
+    pub realized_pnl_weth: f64,
+    /// Son penceredeki başarı oranı (deneme yoksa None)
+    pub trailing_success_rate: Option<f64>,
+    pub restrictions: u64,
+    pub shadow_skips: u64,
+    pub corrections: u64,
+    pub active_restriction: Option<Restriction>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kapı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Yön defterleri + politika
+pub struct DirectionGate {
+    policy: GatePolicy,
+    books: HashMap<DirectionKey, DirectionBook>,
+}
+
+impl DirectionGate {
+    pub fn new(policy: GatePolicy) -> Self {
+        Self { policy, books: HashMap::new() }
+    }
+
+    fn enabled(&self) -> bool {
+        self.policy.window > 0
+    }
+
+    /// Reorg düzeltmesi: geçici deneme sayaçlardan ve pencereden çıkarılır,
+    /// yeni sonuç (varsa) aynı pencere yerine konur. Kapı olayı üretmez —
+    /// oran bir sonraki receipt'te düzeltilmiş pencereyle değerlendirilir.
+    pub fn correct(&mut self, direction: &Direction, before: Option<Attempt>, after: Option<Attempt>) {
+        let enabled = self.enabled();
+        let window = self.policy.window;
+        let book = self.books.entry(direction.key).or_default();
+        if book.label.is_empty() {
+            book.label = direction.label.clone();
+        }
+        book.corrections += 1;
+        // Geçici deneme pencereden düştüyse yenisi de pencereye girmez
+        let mut slot = Some(book.window.len());
+        if let Some(before) = before {
+            book.attempts = book.attempts.saturating_sub(1);
+            if before.landed {
+                book.landed = book.landed.saturating_sub(1);
+            } else {
+                book.reverted = book.reverted.saturating_sub(1);
+            }
+            book.realized_pnl_weth -= before.pnl_weth;
+            slot = book.window.iter().rposition(|a| *a == before);
+            if let Some(i) = slot {
+                book.window.remove(i);
+            }
+        }
+        if let Some(after) = after {
+            book.attempts += 1;
+            if after.landed {
+                book.landed += 1;
+            } else {
+                book.reverted += 1;
+            }
+            book.realized_pnl_weth += after.pnl_weth;
+            if let (true, Some(i)) = (enabled, slot) {
+                book.window.insert(i, after);
+                while book.window.len() > window {
+                    book.window.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Receipt sonucunu işle. Pencere dolu ve başarı oranı eşiğin altındaysa
+    /// yön `block`'tan itibaren kısıtlanır; kısıtlıyken oran toparlanırsa kalkar.
+    pub fn record(&mut self, direction: &Direction, attempt: Attempt, block: u64) -> Option<GateEvent> {
+        let policy = self.policy;
+        let enabled = self.enabled();
+        let book = self.books.entry(direction.key).or_default();
+        if book.label.is_empty() {
+            book.label = direction.label.clone();
+        }
+        book.attempts += 1;
+        if attempt.landed {
+            book.landed += 1;
+        } else {
+            book.reverted += 1;
+        }
+        book.realized_pnl_weth += attempt.pnl_weth;
+        if !enabled {
+            return None;
+        }
+        book.window.push_back(attempt);
+        while book.window.len() > policy.window {
+            book.window.pop_front();
+        }
+
+        let min_attempts = policy.min_attempts.clamp(1, policy.window);
+        let rate = book.success_rate()?;
+        if book.window.len() < min_attempts {
+            return None;
+        }
+        match book.restriction {
+            Some(_) if rate >= policy.min_success_rate => {
+                book.lift();
+                Some(GateEvent::Lifted(LiftReason::Recovered))
+            }
+            None if rate < policy.min_success_rate => {
+                let restriction = Restriction {
+                    mode: policy.mode,
+                    since_block: block,
+                    until_block: block + policy.cooldown_blocks,
+                    success_rate: rate,
+                    baseline_edge_weth: book.mean_window_edge(),
+                };
+                book.restriction = Some(restriction);
+                book.restrictions += 1;
+                book.shadow_edges.clear();
+                Some(GateEvent::Restricted(restriction))
+            }
+            _ => None,
+        }
+    }
+
+    /// Kısıtlı yönün gölge loglanan fırsatı: son LIFT_SAMPLES fırsatın
+    /// ortalama modellenen kârı kısıtlama kenarının DIRECTION_GATE_EDGE_WIDEN
+    /// katına ulaşırsa kısıtlama kalkar.
+    pub fn observe_shadow(&mut self, key: &DirectionKey, modeled_profit_weth: f64) -> Option<GateEvent> {
+        let edge_widen = self.policy.edge_widen;
+        let book = self.books.get_mut(key)?;
+        let restriction = book.restriction?;
+        book.shadow_skips += 1;
+        book.shadow_edges.push_back(modeled_profit_weth);
+        while book.shadow_edges.len() > LIFT_SAMPLES {
+            book.shadow_edges.pop_front();
+        }
+        if book.shadow_edges.len() < LIFT_SAMPLES {
+            return None;
+        }
+        let mean = book.shadow_edges.iter().sum::<f64>() / LIFT_SAMPLES as f64;
+        if mean >= restriction.baseline_edge_weth * edge_widen {
+            book.lift();
+            return Some(GateEvent::Lifted(LiftReason::EdgeWidened));
+        }
+        None
+    }
+
+    /// Bekleme süresi dolan kısıtlamaları kaldır
+    pub fn on_block(&mut self, block: u64) -> Vec<(String, GateEvent)> {
+        self.books
+            .values_mut()
+            .filter(|b| b.restriction.is_some_and(|r| block >= r.until_block))
+            .map(|b| {
+                b.lift();
+                (b.label.clone(), GateEvent::Lifted(LiftReason::CooldownExpired))
+            })
+            .collect()
+    }
+
+    /// Filtre kararı: kısıtlı yön Degrade (yürütme yok, gölge log sürer)
+    pub fn decision(&self, key: &DirectionKey, expected_profit_weth: f64, min_profit_weth: f64) -> FilterDecision {
+        let Some(book) = self.books.get(key) else {
+            return FilterDecision::Pass;
+        };
+        let Some(restriction) = book.restriction else {
+            return FilterDecision::Pass;
+        };
+        match restriction.mode {
+            DirectionGateMode::Suspend => FilterDecision::Degrade(format!(
+                "{} suspended: trailing success {:.0}% < {:.0}% (until block #{})",
+                book.label,
+                restriction.success_rate * 100.0,
+                self.policy.min_success_rate * 100.0,
+                restriction.until_block,
+            )),
+            DirectionGateMode::Multiplier => {
+                let required = min_profit_weth * self.policy.profit_multiplier;
+                // Tam eşikteki kâr geçer — çarpımın yuvarlama artığı reddetmesin
+                if expected_profit_weth >= required - required.abs() * 1e-12 {
+                    FilterDecision::Pass
+                } else {
+                    FilterDecision::Degrade(format!(
+                        "{} restricted: profit={:.8} < {:.8} WETH ({}x, trailing success {:.0}%, until block #{})",
+                        book.label,
+                        expected_profit_weth,
+                        required,
+                        self.policy.profit_multiplier,
+                        restriction.success_rate * 100.0,
+                        restriction.until_block,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Yön başına özet (etikete göre sıralı)
+    pub fn summaries(&self) -> Vec<DirectionSummary> {
+        let mut rows: Vec<DirectionSummary> = self
+            .books
+            .iter()
+            .map(|(key, b)| DirectionSummary {
+                direction: b.label.clone(),
+                buy_pool: key.buy_pool.to_string(),
+                sell_pool: key.sell_pool.to_string(),
+                attempts: b.attempts,
+                landed: b.landed,
+                reverted: b.reverted,
+                realized_pnl_weth: b.realized_pnl_weth,
+                trailing_success_rate: b.success_rate(),
+                restrictions: b.restrictions,
+                shadow_skips: b.shadow_skips,
+                corrections: b.corrections,
+                active_restriction: b.restriction,
+            })
+            .collect();
+        rows.sort_by(|a, b| a.direction.cmp(&b.direction));
+        rows
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Kapı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// init öncesi None — kayıt yok, her yön geçer
+static GATE: Mutex<Option<DirectionGate>> = parking_lot::const_mutex(None);
+
+/// Eşikleri config'den al (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    *GATE.lock() = Some(DirectionGate::new(GatePolicy::from_config(config)));
+}
+
+fn report(label: &str, event: &GateEvent) {
+    match event {
+        GateEvent::Restricted(r) => {
+            eprintln!(
+                "  {} [DirectionGate] {} {}: trailing success {:.0}%, edge {:.6} WETH — until block #{}",
+                "🚧".yellow(),
+                label,
+                if r.mode == DirectionGateMode::Suspend { "SUSPENDED" } else { "profit multiplier applied" },
+                r.success_rate * 100.0,
+                r.baseline_edge_weth,
+                r.until_block,
+            );
+            crate::json_logger::log_json("warn", "direction_restricted", serde_json::json!({
+                "direction": label,
+                "mode": r.mode.to_string(),
+                "since_block": r.since_block,
+                "until_block": r.until_block,
+                "success_rate": r.success_rate,
+                "baseline_edge_weth": r.baseline_edge_weth,
+            }));
+        }
+        GateEvent::Lifted(reason) => {
+            eprintln!(
+                "  {} [DirectionGate] {} restriction lifted ({})",
+                "✅".green(),
+                label,
+                reason.label(),
+            );
+            crate::json_logger::log_json("info", "direction_lifted", serde_json::json!({
+                "direction": label,
+                "reason": reason.label(),
+            }));
+        }
+    }
+}
+
+/// Receipt görevinden: sonucu yön defterine işle
+pub fn record_receipt(direction: &Direction, landed: bool, gas_spent_wei: u128, expected_profit_weth: f64, block: u64) {
+    let attempt = Attempt::from_receipt(landed, gas_spent_wei, expected_profit_weth);
+    let event = GATE.lock().as_mut().and_then(|g| g.record(direction, attempt, block));
+    if let Some(event) = event {
+        report(&direction.label, &event);
+    }
+}
+
+/// Onay görevinden: reorg düzeltmesini yön defterine işle
+pub fn record_correction(direction: &Direction, before: Option<Attempt>, after: Option<Attempt>) {
+    if let Some(gate) = GATE.lock().as_mut() {
+        gate.correct(direction, before, after);
+    }
+}
+
+/// Kısıtlı yönün gölge loglanan fırsatı (main, "direction_gate" Degrade'i)
+pub fn observe_shadow(direction: &Direction, modeled_profit_weth: f64) {
+    let event = GATE
+        .lock()
+        .as_mut()
+        .and_then(|g| g.observe_shadow(&direction.key, modeled_profit_weth));
+    if let Some(event) = event {
+        report(&direction.label, &event);
+    }
+}
+
+/// Her blok: bekleme süresi dolan kısıtlamaları kaldır
+pub fn on_block(block: u64) {
+    let events = GATE.lock().as_mut().map(|g| g.on_block(block)).unwrap_or_default();
+    for (label, event) in &events {
+        report(label, event);
+    }
+}
+
+/// DirectionGateFilter'ın kararı
+pub fn decision(key: &DirectionKey, expected_profit_weth: f64, min_profit_weth: f64) -> FilterDecision {
+    GATE.lock()
+        .as_ref()
+        .map_or(FilterDecision::Pass, |g| g.decision(key, expected_profit_weth, min_profit_weth))
+}
+
+/// Oturum özeti için yön satırları
+pub fn summaries() -> Vec<DirectionSummary> {
+    GATE.lock().as_ref().map(|g| g.summaries()).unwrap_or_default()
+}
+
+/// İstatistik kutusu satırları (yön başına) — receipt yoksa boş
+pub fn stats_lines() -> Vec<String> {
+    summaries()
+        .iter()
+        .map(|s| {
+            let mut line = format!(
+                "{} | {}/{} landed | reverted {} | PnL {:+.6} WETH",
+                s.direction, s.landed, s.attempts, s.reverted, s.realized_pnl_weth,
+            );
+            if let Some(rate) = s.trailing_success_rate {
+                line.push_str(&format!(" | trailing {:.0}%", rate * 100.0));
+            }
+            if let Some(r) = s.active_restriction {
+                line.push_str(&format!(" | {} until #{}", r.mode.to_string().to_uppercase(), r.until_block));
+                if s.shadow_skips > 0 {
+                    line.push_str(&format!(" ({} shadow)", s.shadow_skips));
+                }
+            }
+            if s.corrections > 0 {
+                line.push_str(&format!(" | {} reorg corrections", s.corrections));
+            }
+            line
+        })
+        .collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNI: Address = Address::repeat_byte(0x0A);
+    const AERO: Address = Address::repeat_byte(0x0B);
+
+    fn direction(buy: Address, sell: Address, label: &str) -> Direction {
+        Direction { key: DirectionKey { buy_pool: buy, sell_pool: sell }, label: label.into() }
+    }
+
+    fn policy(mode: DirectionGateMode) -> GatePolicy {
+        GatePolicy {
+            window: 10,
+            min_attempts: 5,
+            min_success_rate: 0.3,
+            mode,
+            profit_multiplier: 3.0,
+            cooldown_blocks: 100,
+            edge_widen: 1.5,
+        }
+    }
+
+    /// `pattern`: 'L' landed, 'R' mined-revert; modellenen kâr 0.001 WETH
+    fn feed(gate: &mut DirectionGate, dir: &Direction, pattern: &str, block: u64) -> Vec<GateEvent> {
+        pattern
+            .chars()
+            .filter_map(|c| gate.record(dir, Attempt::from_receipt(c == 'L', 100_000_000_000_000, 0.001), block))
+            .collect()
+    }
+
+    #[test]
+    fn test_losing_direction_suspended_other_keeps_executing() {
+        let mut gate = DirectionGate::new(policy(DirectionGateMode::Suspend));
+        let winning = direction(UNI, AERO, "UniV3 → Aerodrome");
+        let losing = direction(AERO, UNI, "Aerodrome → UniV3");
+
+        assert!(feed(&mut gate, &winning, "LLLLRLLL", 1_000).is_empty());
+        // 4 deneme: karar için pencere henüz yetersiz
+        assert!(feed(&mut gate, &losing, "RRRR", 1_000).is_empty());
+        let events = feed(&mut gate, &losing, "R", 1_005);
+        let GateEvent::Restricted(r) = events[0] else { panic!("expected restriction") };
+        assert_eq!((r.since_block, r.until_block), (1_005, 1_105));
+        assert_eq!(r.success_rate, 0.0);
+        assert!((r.baseline_edge_weth - 0.001).abs() < 1e-12);
+
+        // Kısıtlı yön Degrade — yürütülmez ama fırsat gölge loglanır; diğer yön geçer
+        let FilterDecision::Degrade(reason) = gate.decision(&losing.key, 1.0, 0.0002) else {
+            panic!("suspended direction must degrade");
+        };
+        assert!(reason.contains("Aerodrome → UniV3 suspended"), "{}", reason);
+        assert_eq!(gate.decision(&winning.key, 0.0003, 0.0002), FilterDecision::Pass);
+
+        // Gölge gözlemleri sayılır (kenar daralmış: kalkmaz)
+        for _ in 0..6 {
+            assert_eq!(gate.observe_shadow(&losing.key, 0.0008), None);
+        }
+        let rows = gate.summaries();
+        assert_eq!(rows.len(), 2);
+        let lost = &rows[0];
+        assert_eq!(lost.direction, "Aerodrome → UniV3");
+        assert_eq!((lost.attempts, lost.landed, lost.reverted, lost.shadow_skips), (5, 0, 5, 6));
+        assert!((lost.realized_pnl_weth + 0.0005).abs() < 1e-12);
+        assert!(lost.active_restriction.is_some());
+        let won = &rows[1];
+        assert_eq!((won.attempts, won.landed), (8, 7));
+        assert!((won.realized_pnl_weth - (7.0 * 0.0009 - 0.0001)).abs() < 1e-12);
+        assert_eq!(won.trailing_success_rate, Some(0.875));
+        // Kısıtlanmamış yönün gölge fırsatı deftere girmez
+        assert_eq!(gate.observe_shadow(&winning.key, 0.5), None);
+        assert_eq!(gate.summaries()[1].shadow_skips, 0);
+    }
+
+    #[test]
+    fn test_multiplier_mode_requires_larger_profit() {
+        let mut gate = DirectionGate::new(policy(DirectionGateMode::Multiplier));
+        let losing = direction(AERO, UNI, "Aerodrome → UniV3");
+        let events = feed(&mut gate, &losing, "RLRRRR", 50);
+        assert!(matches!(events[..], [GateEvent::Restricted(Restriction { mode: DirectionGateMode::Multiplier, .. })]));
+
+        // MIN_NET_PROFIT 0.0002 × 3 = 0.0006
+        assert!(matches!(gate.decision(&losing.key, 0.0005, 0.0002), FilterDecision::Degrade(_)));
+        assert_eq!(gate.decision(&losing.key, 0.0006, 0.0002), FilterDecision::Pass);
+
+        // Çarpanı geçen denemeler landed → oran eşiği geçince kısıtlama kalkar
+        // (pencere RLRRRR + L = 2/7 < 0.3, + L = 3/8 ≥ 0.3)
+        assert!(feed(&mut gate, &losing, "L", 60).is_empty());
+        assert_eq!(feed(&mut gate, &losing, "L", 61), [GateEvent::Lifted(LiftReason::Recovered)]);
+        assert_eq!(gate.decision(&losing.key, 0.0003, 0.0002), FilterDecision::Pass);
+        // Pencere sıfırlandı — tek revert yeniden kısıtlamaz
+        assert!(feed(&mut gate, &losing, "R", 62).is_empty());
+        assert_eq!(gate.summaries()[0].restrictions, 1);
+    }
+
+    #[test]
+    fn test_cooldown_expiry_and_edge_widening_lift() {
+        let mut gate = DirectionGate::new(policy(DirectionGateMode::Suspend));
+        let losing = direction(AERO, UNI, "Aerodrome → UniV3");
+        feed(&mut gate, &losing, "RRRRR", 200);
+
+        assert!(gate.on_block(299).is_empty());
+        assert!(matches!(gate.decision(&losing.key, 1.0, 0.0002), FilterDecision::Degrade(_)));
+        let lifted = gate.on_block(300);
+        assert_eq!(lifted, [("Aerodrome → UniV3".to_string(), GateEvent::Lifted(LiftReason::CooldownExpired))]);
+        assert_eq!(gate.decision(&losing.key, 0.0003, 0.0002), FilterDecision::Pass);
+        assert!(gate.on_block(301).is_empty());
+
+        // Yeniden kısıtlanır; gölge fırsatlar kenarın 1.5 katına çıkınca erken kalkar
+        assert!(matches!(feed(&mut gate, &losing, "RRRRR", 400)[..], [GateEvent::Restricted(_)]));
+        for edge in [0.0015, 0.0016, 0.0017, 0.0013] {
+            assert_eq!(gate.observe_shadow(&losing.key, edge), None);
+        }
+        // Son 5 ortalaması 0.00152 ≥ 0.001 × 1.5
+        assert_eq!(
+            gate.observe_shadow(&losing.key, 0.0015),
+            Some(GateEvent::Lifted(LiftReason::EdgeWidened))
+        );
+        assert_eq!(gate.decision(&losing.key, 0.0003, 0.0002), FilterDecision::Pass);
+        let row = &gate.summaries()[0];
+        assert_eq!((row.restrictions, row.shadow_skips), (2, 5));
+        assert!(row.active_restriction.is_none());
+        assert_eq!(row.trailing_success_rate, None);
+    }
+
+    /// Reorg: geçici landed deneme başka blokta revert olarak dahil edildi,
+    /// sonra bir başkasının receipt'i kayboldu — oran ve PnL düzeltme kaydıyla değişir
+    #[test]
+    fn test_reorg_correction_rewrites_window_and_counters() {
+        let mut gate = DirectionGate::new(policy(DirectionGateMode::Suspend));
+        let dir = direction(UNI, AERO, "UniV3 → Aerodrome");
+        feed(&mut gate, &dir, "LLRLL", 10);
+        assert_eq!(gate.summaries()[0].trailing_success_rate, Some(0.8));
+
+        let provisional = Attempt::from_receipt(true, 100_000_000_000_000, 0.001);
+        let reincluded = Attempt::from_receipt(false, 300_000_000_000_000, 0.001);
+        gate.correct(&dir, Some(provisional), Some(reincluded));
+        let row = &gate.summaries()[0];
+        assert_eq!((row.attempts, row.landed, row.reverted, row.corrections), (5, 3, 2, 1));
+        assert_eq!(row.trailing_success_rate, Some(0.6));
+        assert!((row.realized_pnl_weth - (3.0 * 0.0009 - 0.0001 - 0.0003)).abs() < 1e-12);
+
+        gate.correct(&dir, Some(provisional), None);
+        let row = &gate.summaries()[0];
+        assert_eq!((row.attempts, row.landed, row.corrections), (4, 2, 2));
+        assert_eq!(row.trailing_success_rate, Some(0.5));
+        // Yeniden dahil edilme pencerenin sonuna girer
+        gate.correct(&dir, None, Some(provisional));
+        assert_eq!(gate.summaries()[0].trailing_success_rate, Some(0.6));
+        assert_eq!(gate.summaries()[0].attempts, 5);
+    }
+
+    #[test]
+    fn test_disabled_gate_only_keeps_stats() {
+        let mut gate = DirectionGate::new(GatePolicy { window: 0, ..policy(DirectionGateMode::Suspend) });
+        let losing = direction(AERO, UNI, "Aerodrome → UniV3");
+        assert!(feed(&mut gate, &losing, "RRRRRRRRRR", 10).is_empty());
+        assert_eq!(gate.decision(&losing.key, 0.0, 0.0002), FilterDecision::Pass);
+        let row = &gate.summaries()[0];
+        assert_eq!((row.attempts, row.reverted), (10, 10));
+        assert_eq!(row.trailing_success_rate, None);
+    }
+}
@@ -22,9 +22,11 @@ use std::time::Instant;
 use tokio_util::sync::CancellationToken;
 use colored::*;
 
-use crate::pool_discovery::PairCombo;
+use crate::pool_discovery::{
+    is_constant_product_pool, validated_fee, PairCombo, CONSTANT_PRODUCT_DEFAULT_FEE_BPS,
+};
 use crate::types::{
-    token_whitelist, DexType, PoolConfig, PoolState, SharedPoolState,
+    DexType, FeeTier, PoolConfig, PoolState, SharedPoolState,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -112,6 +114,10 @@ pub struct DiscoveryConfig {
     pub wss_url: String,
     /// WETH adresi
     pub weth_address: Address,
+    /// Güvenli token listesi (zincir profilinden)
+    pub token_whitelist: std::collections::HashSet<Address>,
+    /// V2 / vAMM adayları alınsın mı (DISCOVER_CONSTANT_PRODUCT — yalnızca gölge)
+    pub constant_product: bool,
 }
 
 impl DiscoveryConfig {
@@ -130,6 +136,8 @@ impl DiscoveryConfig {
             max_fee_bps: bot_config.max_pool_fee_bps,
             wss_url: bot_config.rpc_wss_url.clone(),
             weth_address: bot_config.weth_address,
+            token_whitelist: bot_config.token_whitelist(),
+            constant_product: bot_config.discover_constant_product,
         }
     }
 }
@@ -393,13 +401,13 @@ impl LivePoolRegistry {
                 0.0
             };
 
-            let score = PoolScore::calculate(volume, spread_vol, pool.fee_fraction);
+            let score = PoolScore::calculate(volume, spread_vol, pool.fee_fraction());
 
             self.scores.insert(idx, PoolScore {
                 score,
                 volume_1h: volume,
                 spread_volatility: spread_vol,
-                fee_fraction: pool.fee_fraction,
+                fee_fraction: pool.fee_fraction(),
                 updated_at: Instant::now(),
             });
         }
@@ -570,7 +578,7 @@ async fn factory_listener(
         "🏭".green()
     );
 
-    let whitelist = token_whitelist();
+    let whitelist = &config.token_whitelist;
 
     while let Some(log) = stream.next().await {
         let topics = log.topics();
@@ -622,6 +630,9 @@ async fn factory_listener(
         if fee_bps > config.max_fee_bps {
             continue;
         }
+        let Some(fee) = validated_fee(&format!("{:?}", parsed.pool_address), fee_bps) else {
+            continue;
+        };
 
         // PoolConfig oluştur
         let (token0_is_weth, quote_addr) = if parsed.token0 == config.weth_address {
@@ -638,11 +649,11 @@ async fn factory_listener(
                     DexType::UniswapV3 => "UniV3",
                     DexType::PancakeSwapV3 => "PCS",
                     DexType::Aerodrome => "Aero",
+                    DexType::UniswapV2 => "UniV2",
                 },
                 quote_addr
             ),
-            fee_bps,
-            fee_fraction: fee_bps as f64 / 10_000.0,
+            fee,
             token0_decimals: if token0_is_weth { 18 } else { infer_decimals(&parsed.token0) },
             token1_decimals: if token0_is_weth { infer_decimals(&parsed.token1) } else { 18 },
             dex: parsed.dex_type,
@@ -837,8 +848,8 @@ async fn api_aggregator_loop(
                             dex: format!("{:?}", p.config.dex),
                             liquidity_usd: p.score * 1000.0,
                             volume_24h: p.score * 1000.0,
-                            fee_tier: Some(p.config.fee_fraction),
-                            labels: None,
+                            fee_tier: Some(p.config.fee_fraction()),
+                            labels: candidate_labels(&p.config),
                             gecko_name: None,
                             source: "dexscreener".to_string(),
                         }
@@ -879,8 +890,8 @@ async fn api_aggregator_loop(
                                     dex: format!("{:?}", p.config.dex),
                                     liquidity_usd: p.score * 1000.0,
                                     volume_24h: p.score * 1000.0,
-                                    fee_tier: Some(p.config.fee_fraction),
-                                    labels: None,
+                                    fee_tier: Some(p.config.fee_fraction()),
+                                    labels: candidate_labels(&p.config),
                                     gecko_name: None,
                                     source: "geckoterminal".to_string(),
                                 }
@@ -957,7 +968,7 @@ async fn discover_dexscreener(config: &DiscoveryConfig) -> Result<Vec<PendingPoo
 }
 
 fn parse_dexscreener_pools(json: &serde_json::Value, config: &DiscoveryConfig) -> Result<Vec<PendingPool>> {
-    let whitelist = token_whitelist();
+    let whitelist = &config.token_whitelist;
     let mut results = Vec::new();
 
     let pairs = json.get("pairs")
@@ -980,13 +991,18 @@ fn parse_dexscreener_pools(json: &serde_json::Value, config: &DiscoveryConfig) -
             continue;
         }
 
-        // v26.0: V2 label blacklist — reject pools with V2/stable/vAMM labels
-        // DexScreener returns labels like ["v2"], ["v3"], ["stable"]
-        let labels = pair.get("labels").and_then(|v| v.as_array());
-        if let Some(label_arr) = labels {
+        // v26.0: V2 label blacklist — reject pools with V1/stable labels
+        // DexScreener returns labels like ["v2"], ["v3"], ["stable"].
+        // DISCOVER_CONSTANT_PRODUCT açıksa v2 / vAMM etiketli havuz sabit çarpım
+        // (DexType::UniswapV2, yalnızca gölge) olarak alınır; kapalıysa reddedilir.
+        let labels: Option<Vec<String>> = pair.get("labels").and_then(|v| v.as_array()).map(|arr| {
+            arr.iter().filter_map(|l| l.as_str().map(str::to_string)).collect()
+        });
+        let constant_product = config.constant_product && is_constant_product_pool(&labels, &None);
+        if let (false, Some(label_arr)) = (constant_product, &labels) {
             let has_v2_label = label_arr.iter().any(|l| {
-                let s = l.as_str().unwrap_or("").to_lowercase();
-                s == "v1" || s == "v2" || s == "stable" || s == "vamm"
+                let s = l.to_lowercase();
+                s == "v1" || s == "v2" || s == "stable" || s == "vamm" || s == "samm"
             });
             if has_v2_label {
                 continue;
@@ -1048,16 +1064,23 @@ fn parse_dexscreener_pools(json: &serde_json::Value, config: &DiscoveryConfig) -
 
         // Fee filtresi
         let fee_tier = pair.get("feeTier").and_then(|v| v.as_f64()).unwrap_or(0.0);
-        let fee_bps = (fee_tier * 100.0).round() as u32;
+        let fee_bps = match (fee_tier * 100.0).round() as u32 {
+            0 if constant_product => CONSTANT_PRODUCT_DEFAULT_FEE_BPS,
+            bps => bps,
+        };
         if fee_bps > config.max_fee_bps {
             continue;
         }
 
         // DEX type
         let dex_type = match infer_dex_type_from_id(dex_id) {
+            Some(_) if constant_product => DexType::UniswapV2,
             Some(dt) => dt,
             None => continue,
         };
+        let Some(fee) = validated_fee(&format!("{:?}", pool_addr), fee_bps) else {
+            continue;
+        };
 
         let token0_is_weth = base_addr == config.weth_address
             || (quote_addr != config.weth_address && base_addr < quote_addr);
@@ -1074,8 +1097,7 @@ fn parse_dexscreener_pools(json: &serde_json::Value, config: &DiscoveryConfig) -
         let pool_config = PoolConfig {
             address: pool_addr,
             name: format!("{}-WETH", dex_id),
-            fee_bps,
-            fee_fraction: fee_bps as f64 / 10_000.0,
+            fee,
             token0_decimals: t0_decimals,
             token1_decimals: t1_decimals,
             dex: dex_type,
@@ -1145,7 +1167,7 @@ fn parse_gecko_terminal_pools(
     json: &serde_json::Value,
     config: &DiscoveryConfig,
 ) -> Result<Vec<PendingPool>> {
-    let whitelist = token_whitelist();
+    let whitelist = &config.token_whitelist;
     let mut results = Vec::new();
 
     let data = json.get("data")
@@ -1240,8 +1262,7 @@ fn parse_gecko_terminal_pools(
         let pool_config = PoolConfig {
             address: pool_addr,
             name: "Gecko-WETH".to_string(),
-            fee_bps: 5, // GeckoTerminal genelde fee bilgisi vermez, 0.05% varsay
-            fee_fraction: 0.0005,
+            fee: FeeTier::from_bps(5), // GeckoTerminal genelde fee bilgisi vermez, 0.05% varsay
             token0_decimals: t0_dec,
             token1_decimals: t1_dec,
             dex: dex_type,
@@ -1481,6 +1502,12 @@ pub fn record_swap_activity(
 // Yardımcı Fonksiyonlar
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// on_chain_validate girdisi: sabit çarpım havuzu slot0() yerine
+/// getReserves() ile doğrulansın
+fn candidate_labels(pool: &PoolConfig) -> Option<Vec<String>> {
+    pool.is_constant_product().then(|| vec!["v2".to_string()])
+}
+
 fn infer_dex_type_from_id(dex_id: &str) -> Option<DexType> {
     let lower = dex_id.to_lowercase();
 
@@ -0,0 +1,530 @@
+// ============================================================================
+//  DIVERGENCE_GUARD v1.0 — Kalıcı Spread = Yapısal Ayrışma Alarmı
+//
+//  Aynı varlık çiftinde gerçek arbitraj spread'i botlar hareket edince birkaç
+//  blokta kapanır. Yüzlerce blok açık kalan spread fırsat değildir: havuzlardan
+//  biri başka token varyantına taşınmış, token depeg olmuş ya da havuzlardan
+//  birini yanlış çözüyoruz. Bot bunu sonsuza dek "tespit eder", istatistikleri
+//  kirletir ve bozuk bir varsayıma karşı yürütmeye kalkabilir.
+//
+//  ✓ Çift başına sayaç: spot spread PERSISTENT_SPREAD_FLOOR_PCT üstündeki
+//    ardışık bloklar; RESET_PCT altına inmek sayacı sıfırlar, aradaki bant
+//    sayacı tutar (ne ilerletir ne sıfırlar)
+//  ✓ PERSISTENT_SPREAD_BLOCKS'a ulaşınca yapısal: çiftin fırsatları
+//    "structural_divergence" ile gölgeye alınır, iki fiyat + süre ile yüksek
+//    sesli uyarı + Telegram + JSON log
+//  ✓ DATA_DIR/divergence/<pool>_<pool>.marker yazılır — yeniden başlatmada
+//    gölge sürer. Kalkış: spread gerçekten kapanır (marker silinir) ya da
+//    operatör marker'ı siler / --ack-divergence ile başlatır (onay)
+//
+//  Sayaç spot fiyatlardan (eth_price_usd) her blok beslenir — sessiz bloklar
+//  tarama yapmasa da süre kesintisiz işler. Durum süreç geneli tek kilitte
+//  (direction_gate deseni), main run_bot öncesi init çağırır.
+// ============================================================================
+
+use alloy::primitives::Address;
+use colored::*;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::pool_discovery::PairCombo;
+use crate::telegram::{TelegramMessage, TelegramSender};
+use crate::types::{BotConfig, PoolConfig, SharedPoolState};
+
+/// Marker dosyalarının DATA_DIR altındaki dizini
+const MARKER_DIR: &str = "divergence";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Politika + Sayaç
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Eşikler (PERSISTENT_SPREAD_*)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DivergencePolicy {
+    /// 0 = kapalı
+    pub blocks: u64,
+    pub floor_pct: f64,
+    pub reset_pct: f64,
+}
+
+impl DivergencePolicy {
+    pub fn from_config(config: &BotConfig) -> Self {
+        Self {
+            blocks: config.persistent_spread_blocks,
+            floor_pct: config.persistent_spread_floor_pct,
+            reset_pct: config.persistent_spread_reset_pct,
+        }
+    }
+}
+
+/// İki spot fiyat arasındaki mutlak spread (%, küçük fiyata göre)
+pub fn spot_spread_pct(price_a: f64, price_b: f64) -> f64 {
+    (price_a - price_b).abs() / price_a.min(price_b) * 100.0
+}
+
+/// Yapısal olarak işaretlenmiş ayrışma
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Structural {
+    pub since_block: u64,
+    /// Tetiklendiği anda spread'in taban üstünde kaldığı blok sayısı
+    /// (önceki çalıştırmadan gelen marker → 0)
+    pub run_blocks: u64,
+    pub price_a: f64,
+    pub price_b: f64,
+    pub spread_pct: f64,
+}
+
+/// Gölgenin kalkma nedeni
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClearReason {
+    /// Spread RESET altına indi
+    SpreadClosed,
+    /// Marker silindi / --ack-divergence
+    Acknowledged,
+}
+
+impl ClearReason {
+    fn label(self) -> &'static str {
+        match self {
+            ClearReason::SpreadClosed => "spread closed",
+            ClearReason::Acknowledged => "acknowledged",
+        }
+    }
+}
+
+/// Sayaç geçişi
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    Triggered(Structural),
+    Cleared(ClearReason),
+}
+
+/// Çift başına sayaç
+#[derive(Debug, Clone, Default)]
+pub struct PairTracker {
+    /// Ardışık taban üstü blok (bant içinde sabit)
+    pub run_blocks: u64,
+    pub structural: Option<Structural>,
+}
+
+impl PairTracker {
+    /// Bloğun spot fiyatlarını işle
+    pub fn observe(&mut self, policy: &DivergencePolicy, block: u64, price_a: f64, price_b: f64) -> Option<Transition> {
+        let spread = spot_spread_pct(price_a, price_b);
+        if spread < policy.reset_pct {
+            self.run_blocks = 0;
+            return self.structural.take().map(|_| Transition::Cleared(ClearReason::SpreadClosed));
+        }
+        if self.structural.is_some() || spread <= policy.floor_pct {
+            return None;
+        }
+        self.run_blocks += 1;
+        if policy.blocks == 0 || self.run_blocks < policy.blocks {
+            return None;
+        }
+        let structural = Structural {
+            since_block: block,
+            run_blocks: self.run_blocks,
+            price_a,
+            price_b,
+            spread_pct: spread,
+        };
+        self.structural = Some(structural);
+        Some(Transition::Triggered(structural))
+    }
+
+    /// Operatör onayı: gölge kalkar, sayaç baştan başlar
+    pub fn acknowledge(&mut self) -> Option<Transition> {
+        self.run_blocks = 0;
+        self.structural.take().map(|_| Transition::Cleared(ClearReason::Acknowledged))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Çift Anahtarı + Marker
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sırasız havuz çifti
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PairKey(Address, Address);
+
+impl PairKey {
+    pub fn new(a: Address, b: Address) -> Self {
+        if a <= b { Self(a, b) } else { Self(b, a) }
+    }
+
+    fn marker_name(&self) -> String {
+        format!("{}_{}.marker", self.0, self.1)
+    }
+}
+
+struct PairBook {
+    label: String,
+    tracker: PairTracker,
+}
+
+pub struct DivergenceGuard {
+    policy: DivergencePolicy,
+    marker_dir: PathBuf,
+    books: HashMap<PairKey, PairBook>,
+    triggers: u64,
+    cleared: u64,
+}
+
+/// on_block olayı (rapor için)
+pub struct DivergenceEvent {
+    pub label: String,
+    pub marker: PathBuf,
+    pub transition: Transition,
+}
+
+impl DivergenceGuard {
+    pub fn new(policy: DivergencePolicy, marker_dir: PathBuf) -> Self {
+        Self { policy, marker_dir, books: HashMap::new(), triggers: 0, cleared: 0 }
+    }
+
+    fn marker_path(&self, key: &PairKey) -> PathBuf {
+        self.marker_dir.join(key.marker_name())
+    }
+
+    /// Tek çiftin bloğu. Yapısal çiftte önce onay (marker silinmiş mi) bakılır;
+    /// ilk görülen çiftin marker'ı varsa önceki çalıştırmanın gölgesi sürer.
+    pub fn observe(&mut self, key: PairKey, label: &str, block: u64, price_a: f64, price_b: f64) -> Option<DivergenceEvent> {
+        if self.policy.blocks == 0 {
+            return None;
+        }
+        let marker = self.marker_path(&key);
+        let book = self.books.entry(key).or_insert_with(|| {
+            let structural = marker.exists().then(|| Structural {
+                since_block: block,
+                run_blocks: 0,
+                price_a,
+                price_b,
+                spread_pct: spot_spread_pct(price_a, price_b),
+            });
+            PairBook { label: label.to_string(), tracker: PairTracker { run_blocks: 0, structural } }
+        });
+        let transition = if book.tracker.structural.is_some() && !marker.exists() {
+            book.tracker.acknowledge()
+        } else {
+            book.tracker.observe(&self.policy, block, price_a, price_b)
+        }?;
+        match transition {
+            Transition::Triggered(_) => self.triggers += 1,
+            Transition::Cleared(_) => self.cleared += 1,
+        }
+        Some(DivergenceEvent { label: book.label.clone(), marker, transition })
+    }
+
+    /// Gölge nedeni — çift yapısal değilse None
+    pub fn suppression(&self, key: &PairKey) -> Option<String> {
+        let book = self.books.get(key)?;
+        let s = book.tracker.structural?;
+        Some(format!(
+            "structural divergence since block #{} (spread {:.3}%: {:.6} vs {:.6}) -- delete {} or restart with --ack-divergence",
+            s.since_block,
+            s.spread_pct,
+            s.price_a,
+            s.price_b,
+            self.marker_path(key).display(),
+        ))
+    }
+
+    fn stats_line(&self) -> Option<String> {
+        if self.triggers == 0 && self.books.values().all(|b| b.tracker.structural.is_none()) {
+            return None;
+        }
+        let active: Vec<&str> = self
+            .books
+            .values()
+            .filter(|b| b.tracker.structural.is_some())
+            .map(|b| b.label.as_str())
+            .collect();
+        Some(format!(
+            "structural {} [{}] | triggered {} | cleared {}",
+            active.len(),
+            active.join(", "),
+            self.triggers,
+            self.cleared,
+        ))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Durum
+// ─────────────────────────────────────────────────────────────────────────────
+
+static GUARD: Mutex<Option<DivergenceGuard>> = parking_lot::const_mutex(None);
+
+fn marker_dir() -> PathBuf {
+    crate::secure_fs::data_path(MARKER_DIR)
+}
+
+/// Eşikleri config'den al (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    *GUARD.lock() = Some(DivergenceGuard::new(DivergencePolicy::from_config(config), marker_dir()));
+}
+
+/// --ack-divergence: tüm marker'ları sil (başlangıçta, init'ten önce)
+pub fn acknowledge_all() -> usize {
+    let dir = marker_dir();
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "marker"))
+        .filter(|p| std::fs::remove_file(p).is_ok())
+        .inspect(|p| println!("  {} [Divergence] acknowledged {}", "✅".green(), p.display()))
+        .count()
+}
+
+fn write_marker(path: &Path, label: &str, s: &Structural) {
+    let body = serde_json::json!({
+        "pair": label,
+        "since_block": s.since_block,
+        "blocks": s.run_blocks,
+        "price_a": s.price_a,
+        "price_b": s.price_b,
+        "spread_pct": s.spread_pct,
+        "triggered_at": crate::clock::log_timestamp(),
+    });
+    let written = path
+        .parent()
+        .map_or(Ok(()), crate::secure_fs::create_dir_all)
+        .and_then(|()| crate::secure_fs::write(path, format!("{:#}\n", body)));
+    if let Err(e) = written {
+        eprintln!("  ⚠️  [Divergence] marker {} write error: {} — suppression lasts this session only", path.display(), e);
+    }
+}
+
+fn report(event: &DivergenceEvent, block: u64, telegram: Option<&TelegramSender>) {
+    match event.transition {
+        Transition::Triggered(s) => {
+            write_marker(&event.marker, &event.label, &s);
+            eprintln!(
+                "\n  {} {} {} spread {:.3}% ({:.6} vs {:.6}) for {} blocks — depeg, token migration or decoding bug? Pair is shadow-only until the spread closes or {} is deleted",
+                "🚨".red(),
+                "[STRUCTURAL DIVERGENCE]".red().bold(),
+                event.label.red().bold(),
+                s.spread_pct,
+                s.price_a,
+                s.price_b,
+                s.run_blocks,
+                event.marker.display(),
+            );
+            crate::json_logger::log_json("warn", "structural_divergence", serde_json::json!({
+                "pair": event.label,
+                "block": block,
+                "blocks": s.run_blocks,
+                "price_a": s.price_a,
+                "price_b": s.price_b,
+                "spread_pct": s.spread_pct,
+            }));
+            if let Some(tg) = telegram {
+                tg.send(TelegramMessage::StructuralDivergence {
+                    pair_name: event.label.clone(),
+                    price_a: s.price_a,
+                    price_b: s.price_b,
+                    spread_pct: s.spread_pct,
+                    blocks: s.run_blocks,
+                    marker: event.marker.display().to_string(),
+                });
+            }
+        }
+        Transition::Cleared(reason) => {
+            if reason == ClearReason::SpreadClosed {
+                let _ = std::fs::remove_file(&event.marker);
+            }
+            println!(
+                "  {} [Divergence] {} re-enabled at block #{} ({})",
+                "✅".green(),
+                event.label,
+                block,
+                reason.label(),
+            );
+            crate::json_logger::log_json("info", "structural_divergence_cleared", serde_json::json!({
+                "pair": event.label,
+                "block": block,
+                "reason": reason.label(),
+            }));
+            if let Some(tg) = telegram {
+                tg.send(TelegramMessage::StructuralDivergenceCleared {
+                    pair_name: event.label.clone(),
+                    reason: reason.label().to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Ana döngü, her blok: aktif çiftlerin spot spread'ini işle
+pub fn on_block(
+    block: u64,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    combos: &[PairCombo],
+    telegram: Option<&TelegramSender>,
+) {
+    let events: Vec<DivergenceEvent> = {
+        let mut guard = GUARD.lock();
+        let Some(guard) = guard.as_mut() else {
+            return;
+        };
+        combos
+            .iter()
+            .filter(|c| c.pool_a_idx < pools.len().min(states.len()) && c.pool_b_idx < pools.len().min(states.len()))
+            .filter_map(|c| {
+                let (a, b) = (states[c.pool_a_idx].load(), states[c.pool_b_idx].load());
+                if !a.is_active() || !b.is_active() {
+                    return None;
+                }
+                let (pool_a, pool_b) = (&pools[c.pool_a_idx], &pools[c.pool_b_idx]);
+                guard.observe(
+                    PairKey::new(pool_a.address, pool_b.address),
+                    &format!("{} ↔ {}", pool_a.name, pool_b.name),
+                    block,
+                    a.eth_price_usd,
+                    b.eth_price_usd,
+                )
+            })
+            .collect()
+    };
+    for event in &events {
+        report(event, block, telegram);
+    }
+}
+
+/// Çift yapısal ayrışmadaysa gölge nedeni
+pub fn suppression(pool_a: Address, pool_b: Address) -> Option<String> {
+    GUARD.lock().as_ref()?.suppression(&PairKey::new(pool_a, pool_b))
+}
+
+/// İstatistik kutusu satırı — hiç tetiklenmediyse None
+pub fn stats_line() -> Option<String> {
+    GUARD.lock().as_ref()?.stats_line()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: DivergencePolicy = DivergencePolicy { blocks: 300, floor_pct: 0.5, reset_pct: 0.1 };
+
+    /// Yüzde spread'li fiyat serisini işle, geçişleri (blok, geçiş) döndür
+    fn run(tracker: &mut PairTracker, from_block: u64, spreads_pct: &[f64]) -> Vec<(u64, Transition)> {
+        spreads_pct
+            .iter()
+            .enumerate()
+            .filter_map(|(i, spread)| {
+                let block = from_block + i as u64;
+                tracker.observe(&POLICY, block, 2500.0, 2500.0 * (1.0 + spread / 100.0)).map(|t| (block, t))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_spot_spread_pct() {
+        assert!((spot_spread_pct(2500.0, 2512.5) - 0.5).abs() < 1e-9);
+        assert!((spot_spread_pct(2512.5, 2500.0) - 0.5).abs() < 1e-9);
+        assert_eq!(spot_spread_pct(2500.0, 2500.0), 0.0);
+    }
+
+    /// Geçici sıçrama: birkaç blok açılıp kapanır — tetik yok, sayaç sıfırlanır
+    #[test]
+    fn test_transient_spike_does_not_trigger() {
+        let mut tracker = PairTracker::default();
+        let mut series = vec![0.02; 10];
+        series.extend([1.2, 2.0, 0.9, 0.6]);
+        series.extend([0.05; 5]);
+        assert!(run(&mut tracker, 100, &series).is_empty());
+        assert_eq!(tracker.run_blocks, 0);
+
+        // Tekrarlayan ama her seferinde kapanan sıçramalar birikmez
+        let mut choppy = Vec::new();
+        for _ in 0..100 {
+            choppy.extend([0.8, 0.8, 0.8, 0.04]);
+        }
+        assert!(run(&mut tracker, 200, &choppy).is_empty());
+        assert!(tracker.structural.is_none());
+    }
+
+    /// Kalıcı ayrışma: 300. taban üstü blokta tetiklenir (bant sayacı tutar),
+    /// gölge RESET altına inene dek sürer, sonra sayaç baştan başlar
+    #[test]
+    fn test_persistent_divergence_triggers_and_recovers() {
+        let mut tracker = PairTracker::default();
+        let mut series = vec![0.7; 150];
+        series.extend([0.3; 40]); // bant: ne ilerletir ne sıfırlar
+        series.extend([0.7; 200]);
+        let events = run(&mut tracker, 1_000, &series);
+        assert_eq!(events.len(), 1);
+        let (block, Transition::Triggered(s)) = events[0] else {
+            panic!("expected trigger, got {:?}", events[0]);
+        };
+        assert_eq!(block, 1_000 + 40 + 299);
+        assert_eq!(s.run_blocks, 300);
+        assert!((s.spread_pct - 0.7).abs() < 1e-9);
+        assert!(tracker.structural.is_some());
+
+        // Hâlâ açık: yeni olay yok, gölge sürer
+        assert!(run(&mut tracker, 2_000, &[2.0; 50]).is_empty());
+        assert!(tracker.structural.is_some());
+
+        // Spread kapanır → gölge kalkar
+        let events = run(&mut tracker, 3_000, &[0.3, 0.05]);
+        assert_eq!(events, vec![(3_001, Transition::Cleared(ClearReason::SpreadClosed))]);
+        assert_eq!(tracker.run_blocks, 0);
+        assert!(run(&mut tracker, 3_100, &[0.7; 299]).is_empty());
+    }
+
+    /// Onay yolu: marker silinince gölge kalkar; önceki çalıştırmanın marker'ı
+    /// yeni süreçte gölgeyi sürdürür; --ack-divergence hepsini siler
+    #[test]
+    fn test_acknowledgment_via_marker_file() {
+        let dir = std::env::temp_dir().join(format!("arb_divergence_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let key = PairKey::new(Address::repeat_byte(0xd1), Address::repeat_byte(0xd0));
+        assert_eq!(key, PairKey::new(Address::repeat_byte(0xd0), Address::repeat_byte(0xd1)));
+        let policy = DivergencePolicy { blocks: 3, ..POLICY };
+
+        let mut guard = DivergenceGuard::new(policy, dir.clone());
+        for block in 1..3 {
+            assert!(guard.observe(key, "A ↔ B", block, 2500.0, 2550.0).is_none());
+        }
+        let event = guard.observe(key, "A ↔ B", 3, 2500.0, 2550.0).expect("trigger");
+        let Transition::Triggered(structural) = event.transition else {
+            panic!("expected trigger");
+        };
+        assert_eq!(structural.run_blocks, 3);
+        // Üretimde report() yazar
+        write_marker(&event.marker, &event.label, &structural);
+        assert!(event.marker.exists());
+        let reason = guard.suppression(&key).expect("suppressed");
+        assert!(reason.contains("structural divergence since block #3"), "{}", reason);
+
+        // Yeniden başlatma: marker varsa ilk gözlemde gölge geri gelir
+        let mut restarted = DivergenceGuard::new(policy, dir.clone());
+        assert!(restarted.observe(key, "A ↔ B", 50, 2500.0, 2550.0).is_none());
+        assert!(restarted.suppression(&key).is_some());
+
+        // Operatör marker'ı siler → onay, spread açık olsa da gölge kalkar
+        std::fs::remove_file(&event.marker).unwrap();
+        let cleared = restarted.observe(key, "A ↔ B", 51, 2500.0, 2550.0).expect("ack");
+        assert_eq!(cleared.transition, Transition::Cleared(ClearReason::Acknowledged));
+        assert!(restarted.suppression(&key).is_none());
+        // Sayaç baştan: yeniden tetik için tekrar policy.blocks blok gerekir
+        assert!(restarted.observe(key, "A ↔ B", 52, 2500.0, 2550.0).is_none());
+        assert!(restarted.observe(key, "A ↔ B", 53, 2500.0, 2550.0).is_none());
+        assert!(restarted.observe(key, "A ↔ B", 54, 2500.0, 2550.0).is_some());
+        assert_eq!(restarted.stats_line().unwrap(), "structural 1 [A ↔ B] | triggered 1 | cleared 1");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,1004 @@
+// ============================================================================
+//  ENV_LOADER — Toplu .env Doğrulama
+//
+//  Her değişken tek tek okunur; hatalar ilk bulunanda durmak yerine
+//  (değişken, sorun, ipucu) olarak biriktirilir. Sonunda ya eksiksiz
+//  yapılandırma döner ya da TÜM sorunları listeleyen tek bir eyre hatası.
+//
+//  Tanımsız opsiyonel değişkenler sessizce varsayılana düşmez:
+//  "using default X for VAR (not set)" satırı başlangıçta yazdırılır,
+//  böylece etkin yapılandırma eksiksiz görünür.
+// ============================================================================
+
+use alloy::primitives::Address;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sorun Kaydı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek bir yapılandırma sorunu
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub var: &'static str,
+    pub problem: String,
+    pub hint: String,
+}
+
+/// Değişkenleri okurken sorunları ve varsayılan kullanımlarını biriktirir
+#[derive(Debug, Default)]
+pub struct EnvLoader {
+    issues: Vec<ConfigIssue>,
+    defaults: Vec<String>,
+    /// Süreç ortamından önce bakılan değerler (A/B ikincil config katmanı)
+    overlay: Vec<(String, String)>,
+}
+
+impl EnvLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Süreç ortamının üstüne `overlay` değerlerini koyan yükleyici
+    pub fn with_overlay(overlay: Vec<(String, String)>) -> Self {
+        Self {
+            overlay,
+            ..Self::default()
+        }
+    }
+
+    /// Ham değer — tanımsız veya boş (trim sonrası) ise None
+    fn raw(&self, var: &str) -> Option<String> {
+        self.overlay
+            .iter()
+            .find(|(k, _)| k == var)
+            .map(|(_, v)| v.clone())
+            .or_else(|| std::env::var(var).ok())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Sorun ekle (aynı değişken için ikinci kez eklenmez)
+    pub fn issue(&mut self, var: &'static str, problem: impl Into<String>, hint: impl Into<String>) {
+        if self.issues.iter().any(|i| i.var == var) {
+            return;
+        }
+        self.issues.push(ConfigIssue {
+            var,
+            problem: problem.into(),
+            hint: hint.into(),
+        });
+    }
+
+    fn note_default(&mut self, var: &str, value: impl Display) {
+        self.defaults
+            .push(format!("using default {} for {} (not set)", value, var));
+    }
+
+    // ── Zorunlu değişkenler ──────────────────────────────────
+
+    /// Zorunlu URL — tanımlı, şablon değeri değil ve şeması uygun olmalı
+    pub fn required_url(
+        &mut self,
+        var: &'static str,
+        schemes: &[&str],
+        placeholder_prefix: &str,
+    ) -> String {
+        let expected = schemes.join(" or ");
+        match self.raw(var) {
+            None => {
+                self.issue(
+                    var,
+                    "not set",
+                    format!("add {}={}... to .env", var, schemes[0]),
+                );
+                String::new()
+            }
+            Some(url) if url.starts_with(placeholder_prefix) => {
+                self.issue(
+                    var,
+                    "still contains the template placeholder",
+                    "replace it with your Alchemy/Infura/QuickNode endpoint",
+                );
+                String::new()
+            }
+            Some(url) if !schemes.iter().any(|s| url.starts_with(s)) => {
+                self.issue(
+                    var,
+                    format!("'{}' is not a valid URL", redact_url(&url)),
+                    format!("must start with {}", expected),
+                );
+                String::new()
+            }
+            Some(url) => url,
+        }
+    }
+
+    // ── Opsiyonel değişkenler ────────────────────────────────
+
+    /// Opsiyonel metin — boş veya şablon değeri ise None
+    pub fn optional_string(&mut self, var: &'static str, placeholder: Option<&str>) -> Option<String> {
+        self.raw(var).filter(|v| !placeholder.is_some_and(|p| v.starts_with(p)))
+    }
+
+    /// Opsiyonel adres — tanımlı ama geçersizse sorun olarak raporlanır
+    pub fn optional_address(&mut self, var: &'static str, placeholder: Option<&str>) -> Option<Address> {
+        let raw = self.optional_string(var, placeholder)?;
+        match raw.parse::<Address>() {
+            Ok(addr) => Some(addr),
+            Err(_) => {
+                self.issue(
+                    var,
+                    format!("'{}' is not a valid address", raw),
+                    "expected a 0x-prefixed 20-byte hex address, or leave empty",
+                );
+                None
+            }
+        }
+    }
+
+    /// Adres (varsayılanlı)
+    pub fn address_or(&mut self, var: &'static str, default: Address) -> Address {
+        if self.raw(var).is_none() {
+            self.note_default(var, default);
+            return default;
+        }
+        self.optional_address(var, None).unwrap_or(default)
+    }
+
+    /// Virgülle ayrılmış adres listesi — her geçersiz girdi raporlanır
+    pub fn address_list(&mut self, var: &'static str) -> Vec<Address> {
+        let Some(raw) = self.raw(var) else {
+            return Vec::new();
+        };
+        let mut out = Vec::new();
+        let mut bad = Vec::new();
+        for item in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match item.parse::<Address>() {
+                Ok(addr) => out.push(addr),
+                Err(_) => bad.push(item.to_string()),
+            }
+        }
+        if !bad.is_empty() {
+            self.issue(
+                var,
+                format!("invalid address(es): {}", bad.join(", ")),
+                "comma-separated 0x-prefixed addresses",
+            );
+        }
+        out
+    }
+
+    /// Sayısal / ayrıştırılabilir değer (varsayılanlı).
+    /// Tanımsız → varsayılan (not edilir). Geçersiz → sorun + varsayılan.
+    pub fn parse_or<T>(&mut self, var: &'static str, default: T, hint: &str) -> T
+    where
+        T: FromStr + Display + Copy,
+    {
+        match self.raw(var) {
+            None => {
+                self.note_default(var, default);
+                default
+            }
+            Some(raw) => match raw.parse::<T>() {
+                Ok(v) => v,
+                Err(_) => {
+                    self.issue(var, format!("'{}' could not be parsed", raw), hint);
+                    default
+                }
+            },
+        }
+    }
+
+    /// Aralık kontrollü sayısal değer ([min, max], uçlar dahil).
+    /// Aralık dışı → sorun + varsayılan; ayrıştırma kuralları parse_or ile aynı.
+    pub fn parse_in_range<T>(
+        &mut self,
+        var: &'static str,
+        default: T,
+        min: T,
+        max: T,
+        hint: &str,
+    ) -> T
+    where
+        T: FromStr + Display + Copy + PartialOrd,
+    {
+        let issues_before = self.issues.len();
+        let value = self.parse_or(var, default, hint);
+        // NaN hiçbir karşılaştırmayı geçemez → aralık dışı sayılır
+        let in_range = min <= value && value <= max;
+        if self.issues.len() == issues_before && !in_range {
+            self.issue(
+                var,
+                format!("'{}' is out of range", value),
+                format!("must be between {} and {}", min, max),
+            );
+            return default;
+        }
+        value
+    }
+
+    /// Boolean (true/false, büyük/küçük harf duyarsız)
+    pub fn bool_or(&mut self, var: &'static str, default: bool) -> bool {
+        match self.raw(var) {
+            None => {
+                self.note_default(var, default);
+                default
+            }
+            Some(raw) => match raw.to_lowercase().parse::<bool>() {
+                Ok(v) => v,
+                Err(_) => {
+                    self.issue(var, format!("'{}' is not a boolean", raw), "use true or false");
+                    default
+                }
+            },
+        }
+    }
+
+    /// Sabit seçeneklerden biri (eşleşme küçük harfle yapılır)
+    pub fn choice<T: Copy>(
+        &mut self,
+        var: &'static str,
+        options: &[(&str, T)],
+        default: (&str, T),
+    ) -> T {
+        let Some(raw) = self.raw(var) else {
+            self.note_default(var, default.0);
+            return default.1;
+        };
+        let key = raw.to_lowercase();
+        if let Some((_, v)) = options.iter().find(|(name, _)| *name == key) {
+            return *v;
+        }
+        let names: Vec<&str> = options.iter().map(|(n, _)| *n).collect();
+        self.issue(
+            var,
+            format!("unknown value '{}'", raw),
+            format!("one of: {}", names.join(", ")),
+        );
+        default.1
+    }
+
+    // ── Sonuç ────────────────────────────────────────────────
+
+    /// Sorun yoksa varsayılan satırlarını döndür, varsa tek bir toplu hata
+    pub fn finish(self) -> eyre::Result<Vec<String>> {
+        if self.issues.is_empty() {
+            return Ok(self.defaults);
+        }
+        let mut report = format!(
+            "Configuration has {} problem(s) — fix them all in .env and restart:",
+            self.issues.len()
+        );
+        for i in &self.issues {
+            report.push_str(&format!("\n  • {}: {} (hint: {})", i.var, i.problem, i.hint));
+        }
+        Err(eyre::eyre!(report))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gizli Değer Maskeleme (--print-config)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// URL'den API anahtarı taşıyabilecek kısımları maskele — sadece şema + host kalır
+pub fn redact_url(url: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return "***".into();
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    // Kullanıcı bilgisi (user:pw@) atılır
+    let host = authority.rsplit('@').next().unwrap_or("");
+    if authority.len() == rest.len() {
+        format!("{}://{}", scheme, host)
+    } else {
+        format!("{}://{}/***", scheme, host)
+    }
+}
+
+/// Gizli değeri tamamen maskele (yalnızca tanımlı olup olmadığı görünür)
+pub fn redact_secret(value: Option<&str>) -> String {
+    match value {
+        Some(v) if !v.is_empty() => format!("*** ({} chars)", v.len()),
+        _ => "(not set)".into(),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BotConfig;
+    use std::sync::{Mutex, MutexGuard};
+
+    /// Süreç ortamı paylaşımlı — env değiştiren testler sırayla çalışır
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Geçici env değişkenleri; drop'ta eski değerler geri yüklenir
+    struct EnvGuard {
+        saved: Vec<(&'static str, Option<String>)>,
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl EnvGuard {
+        fn set(vars: &[(&'static str, Option<&str>)]) -> Self {
+            // Başka bir test panikle bitse bile kilit kullanılabilir kalsın
+            let lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let mut saved = Vec::new();
+            for (k, v) in vars {
+                saved.push((*k, std::env::var(k).ok()));
+                match v {
+                    Some(v) => std::env::set_var(k, v),
+                    None => std::env::remove_var(k),
+                }
+            }
+            Self { saved, _lock: lock }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            for (k, v) in &self.saved {
+                match v {
+                    Some(v) => std::env::set_var(k, v),
+                    None => std::env::remove_var(k),
+                }
+            }
+        }
+    }
+
+    const VALID_RPC: [(&str, Option<&str>); 2] = [
+        ("RPC_WSS_URL", Some("wss://base-mainnet.example.io/v2/KEY")),
+        ("RPC_HTTP_URL", Some("https://base-mainnet.example.io/v2/KEY")),
+    ];
+
+    fn error_text(vars: &[(&'static str, Option<&str>)]) -> String {
+        let _g = EnvGuard::set(vars);
+        match BotConfig::load_from_env() {
+            Ok(_) => panic!("config should be rejected"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    fn mentions(report: &str, var: &str) -> usize {
+        report.matches(&format!("• {}:", var)).count()
+    }
+
+    #[test]
+    fn test_all_problems_reported_at_once() {
+        let report = error_text(&[
+            ("RPC_WSS_URL", None),
+            ("RPC_HTTP_URL", Some("https://your-endpoint")),
+            ("MIN_NET_PROFIT_WETH", Some("abc")),
+            ("STATS_INTERVAL", Some("-5")),
+            ("TRANSPORT_MODE", Some("carrier-pigeon")),
+            ("ARBITRAGE_CONTRACT_ADDRESS", Some("0x1234")),
+            ("EXECUTION_ENABLED", Some("yes please")),
+            ("COMPETITION_ROUTERS", Some("0xnope, also-bad")),
+        ]);
+        for var in [
+            "RPC_WSS_URL",
+            "RPC_HTTP_URL",
+            "MIN_NET_PROFIT_WETH",
+            "STATS_INTERVAL",
+            "TRANSPORT_MODE",
+            "ARBITRAGE_CONTRACT_ADDRESS",
+            "EXECUTION_ENABLED",
+            "COMPETITION_ROUTERS",
+        ] {
+            assert_eq!(mentions(&report, var), 1, "{} in:\n{}", var, report);
+        }
+        assert!(report.contains("8 problem(s)"), "{}", report);
+        // Tek bir geçersiz router listesi → tek satır, iki girdi
+        assert!(report.contains("0xnope, also-bad"));
+    }
+
+    #[test]
+    fn test_only_broken_vars_reported() {
+        let mut vars = VALID_RPC.to_vec();
+        vars.push(("BRIBE_PCT", Some("quarter")));
+        vars.push(("MAX_STALENESS_MS", None));
+        let report = error_text(&vars);
+        assert_eq!(mentions(&report, "BRIBE_PCT"), 1);
+        assert_eq!(mentions(&report, "RPC_WSS_URL"), 0);
+        assert_eq!(mentions(&report, "MAX_STALENESS_MS"), 0);
+        assert!(report.contains("1 problem(s)"));
+    }
+
+    #[test]
+    fn test_staleness_bounds_must_be_ordered() {
+        let mut vars = VALID_RPC.to_vec();
+        vars.push(("STALENESS_MIN_MS", Some("4000")));
+        vars.push(("STALENESS_MAX_MS", Some("2000")));
+        let report = error_text(&vars);
+        assert_eq!(mentions(&report, "STALENESS_MIN_MS"), 1);
+        assert_eq!(mentions(&report, "STALENESS_MAX_MS"), 0);
+    }
+
+    #[test]
+    fn test_wrong_scheme_rejected_and_redacted() {
+        let report = error_text(&[
+            ("RPC_WSS_URL", Some("https://base.example.io/v2/SECRETKEY")),
+            ("RPC_HTTP_URL", Some("https://base.example.io/v2/SECRETKEY")),
+        ]);
+        assert_eq!(mentions(&report, "RPC_WSS_URL"), 1);
+        assert!(!report.contains("SECRETKEY"), "API key leaked: {}", report);
+    }
+
+    #[test]
+    fn test_valid_env_lists_defaults() {
+        let mut vars = VALID_RPC.to_vec();
+        vars.push(("MAX_STALENESS_MS", None));
+        vars.push(("TRANSPORT_MODE", Some("WS")));
+        vars.push(("MIN_NET_PROFIT_WETH", Some("0.0001")));
+        let _g = EnvGuard::set(&vars);
+        let (config, defaults) = BotConfig::load_from_env().expect("valid config");
+        assert_eq!(config.max_staleness_ms, 3000);
+        assert_eq!(config.transport_mode, crate::types::TransportMode::Ws);
+        assert!((config.min_net_profit_weth - 0.0001).abs() < 1e-12);
+        assert!(defaults
+            .iter()
+            .any(|d| d == "using default 3000 for MAX_STALENESS_MS (not set)"));
+        assert!(!defaults.iter().any(|d| d.contains("MIN_NET_PROFIT_WETH")));
+        assert!(!defaults.iter().any(|d| d.contains("TRANSPORT_MODE")));
+    }
+
+    #[test]
+    fn test_empty_value_treated_as_unset() {
+        let _g = EnvGuard::set(&[("ENV_LOADER_TEST_EMPTY", Some("  "))]);
+        let mut env = EnvLoader::new();
+        assert_eq!(env.parse_or("ENV_LOADER_TEST_EMPTY", 7u32, "integer"), 7);
+        assert_eq!(
+            env.finish().unwrap(),
+            vec!["using default 7 for ENV_LOADER_TEST_EMPTY (not set)".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_overlay_takes_precedence_over_env() {
+        let _g = EnvGuard::set(&[
+            ("ENV_LOADER_TEST_A", Some("1")),
+            ("ENV_LOADER_TEST_B", Some("2")),
+        ]);
+        let mut env = EnvLoader::with_overlay(vec![("ENV_LOADER_TEST_A".into(), "10".into())]);
+        assert_eq!(env.parse_or("ENV_LOADER_TEST_A", 0u32, "integer"), 10);
+        assert_eq!(env.parse_or("ENV_LOADER_TEST_B", 0u32, "integer"), 2);
+        assert!(env.finish().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_range_checked_values() {
+        let mut vars = VALID_RPC.to_vec();
+        vars.push(("BRIBE_PCT", Some("0.95")));
+        vars.push(("DEADLINE_BLOCKS", Some("0")));
+        vars.push(("CIRCUIT_BREAKER_THRESHOLD", Some("0")));
+        let report = error_text(&vars);
+        for var in ["BRIBE_PCT", "DEADLINE_BLOCKS", "CIRCUIT_BREAKER_THRESHOLD"] {
+            assert_eq!(mentions(&report, var), 1, "{} in:\n{}", var, report);
+        }
+        assert!(report.contains("'0.95' is out of range"), "{}", report);
+        assert!(report.contains("between 1 and 100"), "{}", report);
+
+        // Uç değerler geçerli
+        let mut vars = VALID_RPC.to_vec();
+        vars.push(("BRIBE_PCT", Some("0.9")));
+        vars.push(("DEADLINE_BLOCKS", Some("100")));
+        vars.push(("CIRCUIT_BREAKER_THRESHOLD", Some("1")));
+        let _g = EnvGuard::set(&vars);
+        let (config, _) = BotConfig::load_from_env().expect("boundary values are valid");
+        assert_eq!(config.bribe_pct, 0.9);
+        assert_eq!(config.deadline_blocks, 100);
+        assert_eq!(config.circuit_breaker_threshold, 1);
+    }
+
+    /// Tüm değişkenleri varsayılandan farklı değerlerle doldurup her alanı
+    /// kontrol eder. Destructuring `..` içermez: BotConfig'e eklenen yeni
+    /// alan bu test güncellenmeden derlenmez.
+    #[test]
+    fn test_fully_populated_env_sets_every_field() {
+        const ROUTER_A: &str = "0x1111111111111111111111111111111111111111";
+        const ROUTER_B: &str = "0x2222222222222222222222222222222222222222";
+        let routers = format!("{},{}", ROUTER_A, ROUTER_B);
+        let _g = EnvGuard::set(&[
+            ("RPC_WSS_URL", Some("wss://primary.example.io/KEY")),
+            ("RPC_HTTP_URL", Some("https://primary.example.io/KEY")),
+            ("RPC_WSS_URL_BACKUP", Some("wss://backup.example.io")),
+            ("RPC_WSS_URL_2", Some("wss://extra2.example.io")),
+            ("RPC_WSS_URL_3", Some("wss://extra3.example.io")),
+            ("RPC_IPC_PATH", Some("/tmp/base.ipc")),
+            ("TRANSPORT_MODE", Some("ipc")),
+            ("PRIVATE_KEY", Some("0xabc123")),
+            ("ARBITRAGE_CONTRACT_ADDRESS", Some("0x3333333333333333333333333333333333333333")),
+            ("CONTRACT_PAUSE_SELECTOR", Some("isPaused()")),
+            ("ADMIN_ADDRESS", Some("0x4444444444444444444444444444444444444444")),
+            ("KEYSTORE_PATH", Some("keys/bot.json")),
+            ("PRIVATE_RPC_URL", Some("https://private.example.io")),
+            ("GAS_COST_FALLBACK_WETH", Some("0.0001")),
+            ("FLASH_LOAN_FEE_BPS", Some("5")),
+            ("MIN_NET_PROFIT_WETH", Some("0.002")),
+            ("MIN_PROFIT_ROI", Some("0.001")),
+            ("MAX_TRADE_SIZE_WETH", Some("7.5")),
+            ("MIN_TRADE_SIZE_WETH", Some("0.05")),
+            ("STATS_INTERVAL", Some("20")),
+            ("MAX_RETRIES", Some("9")),
+            ("MAX_STALENESS_MS", Some("1500")),
+            ("CHAIN_PROFILE", Some("custom")),
+            ("CHAIN_ID", Some("84532")),
+            ("WETH_ADDRESS", Some("0x4200000000000000000000000000000000000006")),
+            ("USDC_ADDRESS", Some("0x6666666666666666666666666666666666666666")),
+            ("L1_FEE_ORACLE_ADDRESS", Some("0x420000000000000000000000000000000000000F")),
+            ("PRIORITY_FEE_STRATEGY", Some("fifo_bribe")),
+            ("TICK_BITMAP_RANGE", Some("250")),
+            ("TICK_BITMAP_MAX_AGE_BLOCKS", Some("8")),
+            ("EXECUTION_ENABLED", Some("true")),
+            ("DEADLINE_BLOCKS", Some("4")),
+            ("BRIBE_PCT", Some("0.4")),
+            ("CIRCUIT_BREAKER_THRESHOLD", Some("6")),
+            ("LATENCY_SPIKE_THRESHOLD_MS", Some("350")),
+            ("RUNTIME_STALL_THRESHOLD_MS", Some("80")),
+            ("MAX_POOL_FEE_BPS", Some("30")),
+            ("MIN_TVL_USD", Some("250000")),
+            ("MIN_VOLUME_24H_USD", Some("125000")),
+            ("MAX_TRACKED_POOLS", Some("12")),
+            ("TELEGRAM_BOT_TOKEN", Some("123:token")),
+            ("TELEGRAM_CHAT_ID", Some("-100987")),
+            ("TELEGRAM_ENABLED", Some("TRUE")),
+            ("TELEGRAM_SHIFT_INTERVAL_SECS", Some("3600")),
+            ("TELEGRAM_BALANCE_WARN_ETH", Some("0.2")),
+            ("SNAPSHOT_KEEP", Some("50")),
+            ("AAVE_POOL_ADDRESS", Some("0x5555555555555555555555555555555555555555")),
+            ("AUTO_UPDATE_FEES", Some("true")),
+            ("COMPETITION_ROUTERS", Some(routers.as_str())),
+            ("COMPETITION_MIN_TXS", Some("3")),
+            ("COMPETITION_PROFIT_MULT", Some("1.5")),
+            ("COMPETITION_SKIP_LIMIT", Some("8")),
+            ("SIM_MATH_MAX_DISCREPANCY", Some("1000")),
+            ("LIQUIDITY_ANALYTICS_BUDGET_MS", Some("150")),
+            ("OPPORTUNITY_FILTERS", Some(" Staleness, profit_floor ")),
+            ("REQUIRE_BITMAP_FOR_EXECUTION", Some("false")),
+            ("SEQUENCER_WS_URL", Some("wss://sequencer.example.io")),
+            ("ADVERSE_FRONTRUN_WETH", Some("0.5")),
+            ("REQUIRE_ADVERSE_POSITIVE", Some("true")),
+            ("MIN_ROBUSTNESS", Some("0.4")),
+            ("WARMUP_CLEAN_BLOCKS", Some("6")),
+            ("SUMMARY_DIR", Some("out/summaries")),
+            ("SPREAD_PROBE_SIZE_WETH", Some("0.25")),
+            ("SIZING_BACKEND", Some("Auto")),
+            ("SIZING_NUMERICS", Some("fixed")),
+            ("TWO_STEP_SIZING", Some("true")),
+            ("EXECUTE_FOLLOWUP", Some("true")),
+            ("OPTIMISTIC_UPDATE_LOG", Some("true")),
+            ("ALLOW_NONSTANDARD_TOKENS", Some("true")),
+            ("SECONDARY_CONFIG_PATH", Some("config/variant_b.env")),
+            ("APPROVAL_AUDIT_MODE", Some("hard")),
+            ("APPROVAL_REQUIRED_SPENDERS", Some("flash_lender")),
+            ("STATE_DIFF_LOG", Some("true")),
+            ("LOG_FORMAT_BINARY", Some("true")),
+            ("PROCESS_ALL_BLOCKS", Some("true")),
+            ("FAST_PATH_SYNC", Some("false")),
+            ("DISCOVER_CONSTANT_PRODUCT", Some("true")),
+            ("FULL_SYNC_INTERVAL_BLOCKS", Some("120")),
+            ("GAS_ESTIMATE", Some("420000")),
+            ("MULTICALL_RETRIES", Some("4")),
+            ("GAS_BUFFER_MODE", Some("auto")),
+            ("MIN_CALIBRATION_SAMPLES", Some("50")),
+            ("GAS_BASE_OVERHEAD", Some("140000")),
+            ("GAS_PER_TICK_CROSSING", Some("25000")),
+            ("GAS_CROSSING_CALIBRATION", Some("false")),
+            ("IMPACT_WARN_TICKS", Some("25")),
+            ("IMPACT_SLIPPAGE_BUMP_BPS", Some("50")),
+            ("IMPACT_BUMP_BLOCKS", Some("900")),
+            ("AUDIT_INTERVAL_BLOCKS", Some("250")),
+            ("AUDIT_MAX_DEVIATION_BPS", Some("2.5")),
+            ("EVENT_DIVERGENCE_TICKS", Some("0")),
+            ("BYTECODE_RECHECK_BLOCKS", Some("250")),
+            ("MAX_INFLIGHT_EXECUTIONS", Some("3")),
+            ("DIRECTION_GATE_WINDOW", Some("30")),
+            ("DIRECTION_GATE_MIN_ATTEMPTS", Some("8")),
+            ("DIRECTION_GATE_MIN_SUCCESS_RATE", Some("0.25")),
+            ("DIRECTION_GATE_MODE", Some("suspend")),
+            ("DIRECTION_GATE_PROFIT_MULTIPLIER", Some("3")),
+            ("DIRECTION_GATE_COOLDOWN_BLOCKS", Some("900")),
+            ("DIRECTION_GATE_EDGE_WIDEN", Some("2")),
+            ("PERSISTENT_SPREAD_BLOCKS", Some("600")),
+            ("PERSISTENT_SPREAD_FLOOR_PCT", Some("0.8")),
+            ("PERSISTENT_SPREAD_RESET_PCT", Some("0.2")),
+            ("SUBMIT_JITTER_MS", Some("2-15")),
+            ("MIN_SUBMIT_SPACING_MS", Some("8")),
+            ("REVISIT_TOP_K", Some("16")),
+            ("REVISIT_QUIET_BLOCKS", Some("45")),
+            ("REVISIT_BUDGET_MS", Some("400")),
+            ("POOL_A_RPC_URL", Some("https://uni-fast.example.io/KEY")),
+            ("POOL_B_RPC_URL", Some("wss://aero-fast.example.io/KEY")),
+            ("ARM_FILE", Some("/run/arb/ARMED")),
+            ("STATUS_LISTEN_ADDR", Some("0.0.0.0:9090")),
+            ("STATUS_ALLOW_REMOTE", Some("true")),
+            ("PAPER_EXECUTION", Some("true")),
+            ("PAPER_FAILURE_RATE", Some("0.25")),
+            ("PAPER_DROP_RATE", Some("0.05")),
+            ("PAPER_RECEIPT_LATENCY_MS", Some("100-900")),
+            ("STRATEGY_TIME_BUDGET_MS", Some("250")),
+            ("PAIR_ROTATION_BLOCKS", Some("12")),
+            ("PAIR_SCORE_DECAY", Some("0.2")),
+            ("REDUNDANCY_WARN", Some("4")),
+            ("DEGRADE_START_PCT", Some("60")),
+            ("DEGRADE_RECOVERY_BLOCKS", Some("8")),
+            ("CONFIRMATION_BLOCKS", Some("30")),
+            ("ADAPTIVE_STALENESS", Some("false")),
+            ("STALENESS_MIN_MS", Some("400")),
+            ("STALENESS_MAX_MS", Some("2500")),
+            ("STALENESS_EXEC_FRACTION", Some("0.6")),
+            ("SHADOW_VERIFY_BLOCKS", Some("5")),
+            ("SHADOW_VERIFY_PER_MIN", Some("12")),
+            ("TICK_BITMAP_FULL_RESYNC_BLOCKS", Some("900")),
+            ("METRICS_LISTEN_ADDR", Some("0.0.0.0:9100")),
+            ("EXACT_SIZING_MIN_WETH", Some("2.5")),
+            ("PREDICTED_STATE_TRUST_BLOCKS", Some("2")),
+            ("CIRCUIT_BREAKER_COOLDOWN_SECS", Some("600")),
+            ("CIRCUIT_BREAKER_HARD_EXIT", Some("true")),
+            ("LOG_FORMAT", Some("json")),
+        ]);
+        let (config, defaults) = BotConfig::load_from_env().expect("fully populated env is valid");
+        assert!(defaults.is_empty(), "nothing should fall back to defaults: {:?}", defaults);
+
+        let addr = |s: &str| s.parse::<Address>().unwrap();
+        let BotConfig {
+            rpc_wss_url,
+            rpc_http_url,
+            rpc_ipc_path,
+            transport_mode,
+            private_key,
+            contract_address,
+            contract_pause_selector,
+            weth_address,
+            usdc_address,
+            gas_cost_fallback_weth,
+            flash_loan_fee_bps,
+            min_net_profit_weth,
+            min_profit_roi,
+            stats_interval,
+            max_retries,
+            initial_retry_delay_secs,
+            max_retry_delay_secs,
+            max_staleness_ms,
+            max_trade_size_weth,
+            min_trade_size_weth,
+            chain_profile,
+            chain_id,
+            l1_fee_oracle,
+            priority_fee_strategy,
+            tick_bitmap_range,
+            tick_bitmap_max_age_blocks,
+            execution_enabled_flag,
+            admin_address,
+            deadline_blocks,
+            bribe_pct,
+            keystore_path,
+            key_source,
+            circuit_breaker_threshold,
+            rpc_wss_url_backup,
+            latency_spike_threshold_ms,
+            runtime_stall_threshold_ms,
+            private_rpc_url,
+            rpc_wss_url_extra,
+            max_pool_fee_bps,
+            min_tvl_usd,
+            min_volume_24h_usd,
+            max_tracked_pools,
+            telegram_bot_token,
+            telegram_chat_id,
+            telegram_enabled,
+            telegram_shift_interval_secs,
+            telegram_balance_warn_eth,
+            snapshot_keep,
+            aave_pool_address,
+            auto_update_fees,
+            competition_routers,
+            competition_min_txs,
+            competition_profit_mult,
+            competition_skip_limit,
+            sim_math_max_discrepancy,
+            liquidity_analytics_budget_ms,
+            opportunity_filters,
+            require_bitmap_for_execution,
+            sequencer_ws_url,
+            adverse_frontrun,
+            require_adverse_positive,
+            min_robustness,
+            warmup_clean_blocks,
+            summary_dir,
+            spread_probe_size_weth,
+            sizing_backend,
+            sizing_numerics,
+            two_step_sizing,
+            execute_followup,
+            optimistic_update_log,
+            allow_nonstandard_tokens,
+            secondary_config_path,
+            approval_audit_mode,
+            approval_required_spenders,
+            state_diff_log,
+            log_format_binary,
+            process_all_blocks,
+            fast_path_sync,
+            discover_constant_product,
+            full_sync_interval_blocks,
+            gas_estimate,
+            multicall_retries,
+            gas_buffer_mode,
+            min_calibration_samples,
+            gas_base_overhead,
+            gas_per_tick_crossing,
+            gas_crossing_calibration,
+            impact_warn_ticks,
+            impact_slippage_bump_bps,
+            impact_bump_blocks,
+            audit_interval_blocks,
+            audit_max_deviation_bps,
+            event_divergence_ticks,
+            bytecode_recheck_blocks,
+            max_inflight_executions,
+            direction_gate_window,
+            direction_gate_min_attempts,
+            direction_gate_min_success_rate,
+            direction_gate_mode,
+            direction_gate_profit_multiplier,
+            direction_gate_cooldown_blocks,
+            direction_gate_edge_widen,
+            persistent_spread_blocks,
+            persistent_spread_floor_pct,
+            persistent_spread_reset_pct,
+            submit_jitter_ms,
+            min_submit_spacing_ms,
+            revisit_top_k,
+            revisit_quiet_blocks,
+            revisit_budget_ms,
+            pool_a_rpc_url,
+            pool_b_rpc_url,
+            arm_file,
+            status_listen_addr,
+            status_allow_remote,
+            paper_execution,
+            paper_failure_rate,
+            paper_drop_rate,
+            paper_receipt_latency,
+            strategy_time_budget_ms,
+            pair_rotation_blocks,
+            pair_score_decay,
+            redundancy_warn,
+            degrade_start_pct,
+            degrade_recovery_blocks,
+            confirmation_blocks,
+            adaptive_staleness,
+            staleness_min_ms,
+            staleness_max_ms,
+            staleness_exec_fraction,
+            shadow_verify_blocks,
+            shadow_verify_per_min,
+            tick_bitmap_full_resync_blocks,
+            metrics_listen_addr,
+            exact_sizing_min_weth,
+            predicted_state_trust_blocks,
+            circuit_breaker_cooldown_secs,
+            circuit_breaker_hard_exit,
+            log_format,
+        } = config;
+
+        assert_eq!(rpc_wss_url, "wss://primary.example.io/KEY");
+        assert_eq!(rpc_http_url, "https://primary.example.io/KEY");
+        assert_eq!(rpc_ipc_path.as_deref(), Some("/tmp/base.ipc"));
+        assert_eq!(transport_mode, crate::types::TransportMode::Ipc);
+        assert_eq!(private_key.as_deref(), Some("0xabc123"));
+        assert_eq!(contract_address, Some(addr("0x3333333333333333333333333333333333333333")));
+        assert_eq!(contract_pause_selector.to_string(), "0xb187bd26");
+        assert_eq!(weth_address, addr("0x4200000000000000000000000000000000000006"));
+        assert_eq!(usdc_address, addr("0x6666666666666666666666666666666666666666"));
+        assert_eq!(gas_cost_fallback_weth, 0.0001);
+        assert_eq!(flash_loan_fee_bps, 5.0);
+        assert_eq!(min_net_profit_weth, 0.002);
+        assert_eq!(min_profit_roi, 0.001);
+        assert_eq!(stats_interval, 20);
+        assert_eq!(max_retries, 9);
+        // Sabit — env'den okunmaz
+        assert_eq!((initial_retry_delay_secs, max_retry_delay_secs), (2, 60));
+        assert_eq!(max_staleness_ms, 1500);
+        assert_eq!(max_trade_size_weth, 7.5);
+        assert_eq!(min_trade_size_weth, 0.05);
+        assert_eq!(chain_profile, crate::chain_profile::ChainProfile::Custom);
+        assert_eq!(chain_id, 84532);
+        assert_eq!(l1_fee_oracle, Some(addr("0x420000000000000000000000000000000000000F")));
+        assert_eq!(priority_fee_strategy, crate::chain_profile::PriorityFeeStrategy::FifoBribe);
+        assert_eq!(tick_bitmap_range, 250);
+        assert_eq!(tick_bitmap_max_age_blocks, 8);
+        assert!(execution_enabled_flag);
+        assert_eq!(admin_address, Some(addr("0x4444444444444444444444444444444444444444")));
+        assert_eq!(deadline_blocks, 4);
+        assert_eq!(bribe_pct, 0.4);
+        assert_eq!(keystore_path.as_deref(), Some("keys/bot.json"));
+        // Türetilmiş durum — KeyManager bağlanana kadar boş
+        assert!(matches!(key_source, crate::key_manager::KeySource::None));
+        assert_eq!(circuit_breaker_threshold, 6);
+        assert_eq!(rpc_wss_url_backup.as_deref(), Some("wss://backup.example.io"));
+        assert_eq!(latency_spike_threshold_ms, 350.0);
+        assert_eq!(runtime_stall_threshold_ms, 80);
+        assert_eq!(private_rpc_url.as_deref(), Some("https://private.example.io"));
+        assert_eq!(rpc_wss_url_extra, vec!["wss://extra2.example.io", "wss://extra3.example.io"]);
+        assert_eq!(max_pool_fee_bps, 30);
+        assert_eq!(min_tvl_usd, 250_000.0);
+        assert_eq!(min_volume_24h_usd, 125_000.0);
+        assert_eq!(max_tracked_pools, 12);
+        assert_eq!(telegram_bot_token.as_deref(), Some("123:token"));
+        assert_eq!(telegram_chat_id.as_deref(), Some("-100987"));
+        assert!(telegram_enabled);
+        assert_eq!(telegram_shift_interval_secs, 3600);
+        assert_eq!(telegram_balance_warn_eth, 0.2);
+        assert_eq!(snapshot_keep, 50);
+        assert_eq!(aave_pool_address, addr("0x5555555555555555555555555555555555555555"));
+        assert!(auto_update_fees);
+        assert_eq!(competition_routers, vec![addr(ROUTER_A), addr(ROUTER_B)]);
+        assert_eq!(competition_min_txs, 3);
+        assert_eq!(competition_profit_mult, 1.5);
+        assert_eq!(competition_skip_limit, 8);
+        assert_eq!(sim_math_max_discrepancy, 1000);
+        assert_eq!(liquidity_analytics_budget_ms, 150);
+        assert_eq!(opportunity_filters, vec!["staleness", "profit_floor"]);
+        assert!(!require_bitmap_for_execution);
+        assert_eq!(sequencer_ws_url.as_deref(), Some("wss://sequencer.example.io"));
+        assert_eq!(adverse_frontrun, crate::types::FrontrunSize::Weth(0.5));
+        assert!(require_adverse_positive);
+        assert_eq!(min_robustness, 0.4);
+        assert_eq!(warmup_clean_blocks, 6);
+        assert_eq!(summary_dir, "out/summaries");
+        assert_eq!(spread_probe_size_weth, 0.25);
+        assert_eq!(sizing_backend, crate::types::SizingBackend::Auto);
+        assert_eq!(sizing_numerics, crate::types::SizingNumerics::Fixed);
+        assert!(two_step_sizing);
+        assert!(execute_followup);
+        assert!(optimistic_update_log);
+        assert!(allow_nonstandard_tokens);
+        assert_eq!(secondary_config_path.as_deref(), Some("config/variant_b.env"));
+        assert_eq!(approval_audit_mode, crate::approval_audit::ApprovalAuditMode::Hard);
+        assert_eq!(
+            approval_required_spenders,
+            crate::approval_audit::RequiredSpenders::FlashLender
+        );
+        assert!(state_diff_log);
+        assert!(log_format_binary);
+        assert!(process_all_blocks);
+        assert!(!fast_path_sync);
+        assert!(discover_constant_product);
+        assert_eq!(full_sync_interval_blocks, 120);
+        assert_eq!(gas_estimate, 420_000);
+        assert_eq!(multicall_retries, 4);
+        assert_eq!(gas_buffer_mode, crate::gas_calibration::GasBufferMode::Auto);
+        assert_eq!(min_calibration_samples, 50);
+        assert_eq!((gas_base_overhead, gas_per_tick_crossing, gas_crossing_calibration), (140_000, 25_000, false));
+        assert_eq!((impact_warn_ticks, impact_slippage_bump_bps, impact_bump_blocks), (25, 50, 900));
+        assert_eq!((audit_interval_blocks, audit_max_deviation_bps), (250, 2.5));
+        assert_eq!(event_divergence_ticks, 0);
+        assert_eq!(bytecode_recheck_blocks, 250);
+        assert_eq!(max_inflight_executions, 3);
+        assert_eq!((direction_gate_window, direction_gate_min_attempts), (30, 8));
+        assert_eq!(direction_gate_min_success_rate, 0.25);
+        assert_eq!(direction_gate_mode, crate::direction_gate::DirectionGateMode::Suspend);
+        assert_eq!((direction_gate_profit_multiplier, direction_gate_edge_widen), (3.0, 2.0));
+        assert_eq!(direction_gate_cooldown_blocks, 900);
+        assert_eq!(persistent_spread_blocks, 600);
+        assert_eq!((persistent_spread_floor_pct, persistent_spread_reset_pct), (0.8, 0.2));
+        assert_eq!(submit_jitter_ms, crate::submit_pacing::JitterRange { min_ms: 2, max_ms: 15 });
+        assert_eq!(min_submit_spacing_ms, 8);
+        assert_eq!((revisit_top_k, revisit_quiet_blocks, revisit_budget_ms), (16, 45, 400));
+        assert_eq!(pool_a_rpc_url.as_deref(), Some("https://uni-fast.example.io/KEY"));
+        assert_eq!(pool_b_rpc_url.as_deref(), Some("wss://aero-fast.example.io/KEY"));
+        assert_eq!(arm_file, "/run/arb/ARMED");
+        assert_eq!(status_listen_addr, Some("0.0.0.0:9090".parse().unwrap()));
+        assert!(status_allow_remote);
+        assert!(paper_execution);
+        assert_eq!((paper_failure_rate, paper_drop_rate), (0.25, 0.05));
+        assert_eq!(paper_receipt_latency, crate::paper::LatencyRange { min_ms: 100, max_ms: 900 });
+        assert_eq!((strategy_time_budget_ms, pair_rotation_blocks), (250, 12));
+        assert_eq!(pair_score_decay, 0.2);
+        assert_eq!(redundancy_warn, 4);
+        assert_eq!((degrade_start_pct, degrade_recovery_blocks), (60, 8));
+        assert_eq!(confirmation_blocks, 30);
+        assert!(!adaptive_staleness);
+        assert_eq!((staleness_min_ms, staleness_max_ms), (400, 2500));
+        assert_eq!(staleness_exec_fraction, 0.6);
+        assert_eq!((shadow_verify_blocks, shadow_verify_per_min), (5, 12));
+        assert_eq!(tick_bitmap_full_resync_blocks, 900);
+        assert_eq!(metrics_listen_addr, Some("0.0.0.0:9100".parse().unwrap()));
+        assert_eq!(exact_sizing_min_weth, 2.5);
+        assert_eq!(predicted_state_trust_blocks, 2);
+        assert_eq!(circuit_breaker_cooldown_secs, 600);
+        assert!(circuit_breaker_hard_exit);
+        assert_eq!(log_format, crate::log_event::LogFormat::Json);
+    }
+
+    /// Durum uç noktası loopback dışına yalnızca açık onayla bağlanır
+    #[test]
+    fn test_status_listen_addr_requires_opt_in_for_remote() {
+        let mut vars = VALID_RPC.to_vec();
+        vars.push(("STATUS_LISTEN_ADDR", Some("0.0.0.0:9090")));
+        vars.push(("STATUS_ALLOW_REMOTE", None));
+        let report = error_text(&vars);
+        assert_eq!(mentions(&report, "STATUS_LISTEN_ADDR"), 1, "{}", report);
+        assert!(report.contains("STATUS_ALLOW_REMOTE=true"), "{}", report);
+
+        let mut vars = VALID_RPC.to_vec();
+        vars.push(("STATUS_LISTEN_ADDR", Some("localhost")));
+        assert_eq!(mentions(&error_text(&vars), "STATUS_LISTEN_ADDR"), 1);
+
+        let mut vars = VALID_RPC.to_vec();
+        vars.push(("STATUS_LISTEN_ADDR", Some("127.0.0.1:9090")));
+        vars.push(("STATUS_ALLOW_REMOTE", None));
+        let _g = EnvGuard::set(&vars);
+        let (config, _) = BotConfig::load_from_env().expect("loopback needs no opt-in");
+        assert_eq!(config.status_listen_addr, Some("127.0.0.1:9090".parse().unwrap()));
+        assert!(!config.status_allow_remote);
+    }
+
+    /// CHAIN_PROFILE tek başına tutarlı bir yapılandırma kurar: chain id,
+    /// tokenlar, oracle, Aave ve strateji aynı zincirden gelir
+    #[test]
+    fn test_chain_profiles_build_coherent_configs() {
+        let addr = |s: &str| s.parse::<Address>().unwrap();
+        let chain_vars = [
+            "CHAIN_ID",
+            "WETH_ADDRESS",
+            "USDC_ADDRESS",
+            "AAVE_POOL_ADDRESS",
+            "L1_FEE_ORACLE_ADDRESS",
+            "PRIORITY_FEE_STRATEGY",
+        ];
+        let load = |profile: &str| {
+            let mut vars = VALID_RPC.to_vec();
+            vars.push(("CHAIN_PROFILE", Some(profile)));
+            vars.push(("BRIBE_PCT", Some("0.3")));
+            vars.extend(chain_vars.iter().map(|v| (*v, None)));
+            let _g = EnvGuard::set(&vars);
+            BotConfig::load_from_env().expect("profile defaults are valid").0
+        };
+
+        let op = load("optimism");
+        assert_eq!(op.chain_id, 10);
+        assert_eq!(op.weth_address, addr("0x4200000000000000000000000000000000000006"));
+        assert_eq!(op.usdc_address, addr("0x0b2C639c533813f4Aa9D7837cAf62653d097Ff85"));
+        assert_eq!(op.l1_fee_oracle, Some(addr("0x420000000000000000000000000000000000000F")));
+        assert_eq!(op.aave_pool_address, addr("0x794a61358D6845594F94dc1DB02A252b5b4814aD"));
+        assert_eq!(op.priority_fee_strategy, crate::chain_profile::PriorityFeeStrategy::FifoBribe);
+        assert_eq!(op.effective_bribe_pct(), 0.3);
+        let whitelist = op.token_whitelist();
+        assert!(whitelist.contains(&op.usdc_address));
+        // Base'e özgü tokenlar OP profilinde yok (cbBTC)
+        assert!(!whitelist.contains(&addr("0xcbB7C0000aB88B473b1f5aFd9ef808440eed33Bf")));
+
+        let arb = load("arbitrum");
+        assert_eq!(arb.chain_id, 42161);
+        assert_eq!(arb.l1_fee_oracle, None);
+        assert_eq!(
+            arb.priority_fee_strategy,
+            crate::chain_profile::PriorityFeeStrategy::Eip1559Competitive
+        );
+        // FCFS sıralama: kâr modelinde builder payı yok
+        assert_eq!(arb.bribe_pct, 0.3);
+        assert_eq!(arb.effective_bribe_pct(), 0.0);
+        assert!(arb.token_whitelist().contains(&arb.weth_address));
+    }
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("wss://base-mainnet.g.alchemy.com/v2/abcdef"),
+            "wss://base-mainnet.g.alchemy.com/***"
+        );
+        assert_eq!(redact_url("https://user:pw@rpc.example.io"), "https://rpc.example.io");
+        assert_eq!(redact_url("https://rpc.example.io?key=1"), "https://rpc.example.io/***");
+        assert_eq!(redact_url("not a url"), "***");
+        assert_eq!(redact_secret(Some("0xdeadbeef")), "*** (10 chars)");
+        assert_eq!(redact_secret(None), "(not set)");
+    }
+}
@@ -0,0 +1,315 @@
+// ============================================================================
+//  ERRORS — Tipli Hata Sınıflandırması
+//
+//  state_sync, simulator ve yürütme yolu eyre string'i yerine BotError döner.
+//  Yeniden deneme / fallback / karantina kararları varyanta göre verilir
+//  (substring eşleştirme yok); istatistik sayaçları `kind()` adını anahtar
+//  olarak kullanır. eyre yalnızca main sınırında sarar — BotError
+//  std::error::Error olduğu için `?` ile doğrudan eyre::Report'a dönüşür.
+// ============================================================================
+
+use crate::types::DataQualityIssue;
+
+/// Kaynak hata kutusu (alloy transport, contract, signer hataları)
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+pub type BotResult<T> = std::result::Result<T, BotError>;
+
+/// Düğümün istenen bloğun state'ini sunamadığını bildiren JSON-RPC mesajları.
+/// Bunun için ayrı bir hata kodu yok (geth/reth/erigon hepsi -32000 döner),
+/// bu yüzden yalnızca ErrorResp mesajında — küçük harfe çevrilerek — aranır.
+const BLOCK_UNAVAILABLE_MESSAGES: [&str; 6] = [
+    "header not found",
+    "missing trie node",
+    "unknown block",
+    "block not found",
+    "state is not available",
+    "pruned",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum BotError {
+    /// RPC taşıma katmanı: bağlantı, HTTP/WS, JSON-RPC hata yanıtı
+    #[error("{context}: RPC transport error: {source}")]
+    RpcTransport {
+        context: String,
+        #[source]
+        source: BoxError,
+    },
+
+    /// Yerel zaman aşımı — category: "sync", "multicall", "bitmap", ...
+    #[error("{context}: RPC timeout ({category}, {timeout_ms}ms)")]
+    RpcTimeout {
+        context: String,
+        category: &'static str,
+        timeout_ms: u64,
+    },
+
+    /// Yanıt geldi ama çözülemedi (boş veri, revert, yanlış ABI)
+    #[error("{context}: ABI decode failed: {what}")]
+    AbiDecode { context: String, what: String },
+
+    /// Okuma başarılı, veri geçersiz — state yazılmadı (PoolState::validate)
+    #[error("[{pool}] invalid pool data: {field}={value} (expected {expected})")]
+    PoolDataInvalid {
+        pool: String,
+        field: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+
+    /// Toplu sync'te bazı havuzlar (indeksler) fallback sonrası da okunamadı
+    #[error("multicall sync failed for {} pool(s): {failed_indices:?}", .failed_indices.len())]
+    MulticallPartialFailure { failed_indices: Vec<usize> },
+
+    /// REVM simülasyonu / quote başarısız (revert, halt, eksik bytecode)
+    #[error("simulation failed: {reason}")]
+    SimulationFailed { reason: String },
+
+    /// İmzalama veya private RPC gönderimi başarısız
+    #[error("{context}: {source}")]
+    ExecutionSendFailed {
+        context: String,
+        #[source]
+        source: BoxError,
+    },
+
+    /// Yürütme önkoşulu eksik (imzalayıcı, private RPC) — denemeye gerek yok
+    #[error("execution unavailable: {reason}")]
+    ExecutionUnavailable { reason: &'static str },
+
+    /// Tetikleyen fırsat yeni blokta geçersizleşti — TX gönderilmedi
+    #[error("execution expired before {stage}: {reason}")]
+    ExecutionExpired { stage: &'static str, reason: String },
+
+    /// f64 miktar wei'ye çevrilemedi (NaN/∞, negatif, aralık dışı) ya da
+    /// MIN_TRADE_SIZE_WETH tabanının altında — calldata üretilmedi
+    #[error("invalid amount {value}: {reason}")]
+    InvalidAmount { value: f64, reason: String },
+}
+
+impl BotError {
+    /// İstatistik / log anahtarı — varyant adı
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::RpcTransport { .. } => "rpc_transport",
+            Self::RpcTimeout { .. } => "rpc_timeout",
+            Self::AbiDecode { .. } => "abi_decode",
+            Self::PoolDataInvalid { .. } => "pool_data_invalid",
+            Self::MulticallPartialFailure { .. } => "multicall_partial_failure",
+            Self::SimulationFailed { .. } => "simulation_failed",
+            Self::ExecutionSendFailed { .. } => "execution_send_failed",
+            Self::ExecutionUnavailable { .. } => "execution_unavailable",
+            Self::ExecutionExpired { .. } => "execution_expired",
+            Self::InvalidAmount { .. } => "invalid_amount",
+        }
+    }
+
+    /// Aynı çağrıyı tekrarlamak sonucu değiştirebilir mi?
+    /// Yalnızca ağ kaynaklı hatalar; bozuk veri ve decode hataları tekrarlanmaz.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::RpcTransport { .. } | Self::RpcTimeout { .. })
+    }
+
+    /// Blok sabitli okuma düğümde budanmış / henüz görülmemiş bir bloğa mı
+    /// denk geldi? true ise aynı çağrı "latest" ile tekrarlanabilir.
+    pub fn is_block_unavailable(&self) -> bool {
+        let Self::RpcTransport { source, .. } = self else {
+            return false;
+        };
+        match source.downcast_ref::<alloy::transports::TransportError>() {
+            Some(alloy::transports::RpcError::ErrorResp(payload)) => {
+                let message = payload.message.to_lowercase();
+                BLOCK_UNAVAILABLE_MESSAGES.iter().any(|m| message.contains(m))
+            }
+            _ => false,
+        }
+    }
+
+    /// sol! kontrat çağrısı hatasını sınıflandır: taşıma katmanı → RpcTransport
+    /// (JSON deserialize hatası hariç), geri kalan her şey → AbiDecode.
+    pub fn from_call(context: impl Into<String>, err: alloy::contract::Error) -> Self {
+        match err {
+            alloy::contract::Error::TransportError(source) => Self::from_transport(context, source),
+            other => Self::AbiDecode {
+                context: context.into(),
+                what: other.to_string(),
+            },
+        }
+    }
+
+    /// Provider çağrısı hatasını sınıflandır (get_code_at, subscribe_logs, ...)
+    pub fn from_transport(
+        context: impl Into<String>,
+        err: alloy::transports::TransportError,
+    ) -> Self {
+        match err {
+            alloy::transports::RpcError::DeserError { err, .. } => Self::AbiDecode {
+                context: context.into(),
+                what: err.to_string(),
+            },
+            other => Self::RpcTransport {
+                context: context.into(),
+                source: Box::new(other),
+            },
+        }
+    }
+
+    /// PoolState::validate reddini havuz adıyla taşı
+    pub fn pool_data(pool: &str, issue: DataQualityIssue) -> Self {
+        Self::PoolDataInvalid {
+            pool: pool.to_string(),
+            field: issue.field,
+            value: issue.value,
+            expected: issue.expected,
+        }
+    }
+
+    /// Tek bir toplu hatayı birden çok havuza dağıtmak için aynı varyantın
+    /// kopyası (kaynak hata mesajıyla korunur, yeni bağlam eklenir)
+    pub fn replicate(&self, context: impl Into<String>) -> Self {
+        let context = context.into();
+        match self {
+            Self::RpcTransport { source, .. } => Self::RpcTransport {
+                context,
+                source: source.to_string().into(),
+            },
+            Self::RpcTimeout { category, timeout_ms, .. } => Self::RpcTimeout {
+                context,
+                category,
+                timeout_ms: *timeout_ms,
+            },
+            Self::AbiDecode { what, .. } => Self::AbiDecode {
+                context,
+                what: what.clone(),
+            },
+            Self::PoolDataInvalid { field, value, expected, .. } => Self::PoolDataInvalid {
+                pool: context,
+                field,
+                value: value.clone(),
+                expected,
+            },
+            Self::MulticallPartialFailure { failed_indices } => Self::MulticallPartialFailure {
+                failed_indices: failed_indices.clone(),
+            },
+            Self::SimulationFailed { reason } => Self::SimulationFailed {
+                reason: reason.clone(),
+            },
+            Self::ExecutionSendFailed { source, .. } => Self::ExecutionSendFailed {
+                context,
+                source: source.to_string().into(),
+            },
+            Self::ExecutionUnavailable { reason } => Self::ExecutionUnavailable { reason },
+            Self::ExecutionExpired { stage, reason } => Self::ExecutionExpired {
+                stage,
+                reason: reason.clone(),
+            },
+            Self::InvalidAmount { value, reason } => Self::InvalidAmount {
+                value: *value,
+                reason: reason.clone(),
+            },
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::transports::{RpcError, TransportErrorKind};
+
+    #[test]
+    fn test_transport_errors_are_retryable() {
+        let err = BotError::from_transport("[pool-a] slot0", TransportErrorKind::backend_gone());
+        assert_eq!(err.kind(), "rpc_transport");
+        assert!(err.is_retryable());
+        assert!(err.to_string().starts_with("[pool-a] slot0: RPC transport error"), "{}", err);
+
+        let err = BotError::from_call(
+            "[pool-a] liquidity",
+            alloy::contract::Error::TransportError(RpcError::NullResp),
+        );
+        assert_eq!(err.kind(), "rpc_transport");
+    }
+
+    #[test]
+    fn test_decode_failures_are_not_retryable() {
+        let deser = serde_json::from_str::<u64>("\"0xzz\"").unwrap_err();
+        let err = BotError::from_transport(
+            "[pool-a] slot0",
+            RpcError::DeserError { err: deser, text: "\"0xzz\"".into() },
+        );
+        assert_eq!(err.kind(), "abi_decode");
+        assert!(!err.is_retryable());
+
+        let err = BotError::from_call(
+            "[pool-a] slot0",
+            alloy::contract::Error::UnknownFunction("slot0".into()),
+        );
+        assert_eq!(err.kind(), "abi_decode");
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_block_unavailable_only_for_state_errors() {
+        let resp = |message: &str| {
+            let payload = serde_json::json!({ "code": -32000, "message": message }).to_string();
+            BotError::from_call(
+                "[pool-a] slot0",
+                alloy::contract::Error::TransportError(RpcError::ErrorResp(
+                    serde_json::from_str(&payload).unwrap(),
+                )),
+            )
+        };
+        assert!(resp("header not found").is_block_unavailable());
+        assert!(resp("Missing trie node abc (path )").is_block_unavailable());
+        assert!(!resp("execution reverted").is_block_unavailable());
+        assert!(
+            !BotError::from_transport("x", TransportErrorKind::backend_gone()).is_block_unavailable()
+        );
+    }
+
+    #[test]
+    fn test_pool_data_invalid_keeps_field_and_value() {
+        let issue = DataQualityIssue { field: "liquidity", value: "0".into(), expected: "> 0" };
+        let err = BotError::pool_data("WETH/USDC-500", issue);
+        assert_eq!(err.kind(), "pool_data_invalid");
+        assert!(!err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "[WETH/USDC-500] invalid pool data: liquidity=0 (expected > 0)"
+        );
+    }
+
+    #[test]
+    fn test_replicate_keeps_variant_and_message() {
+        let batch = BotError::from_transport("Multicall3 batch", TransportErrorKind::backend_gone());
+        let copy = batch.replicate("[pool-b] Multicall3 batch sync");
+        assert_eq!(copy.kind(), batch.kind());
+        assert!(copy.to_string().starts_with("[pool-b] Multicall3 batch sync: RPC transport error"));
+
+        let timeout = BotError::RpcTimeout { context: "x".into(), category: "multicall", timeout_ms: 3000 };
+        assert_eq!(
+            timeout.replicate("[pool-b] Multicall3").to_string(),
+            "[pool-b] Multicall3: RPC timeout (multicall, 3000ms)"
+        );
+    }
+
+    /// main sınırı: eyre sarması okunabilir mesajı ve varyantı korur
+    #[test]
+    fn test_eyre_boundary_preserves_message_and_variant() {
+        fn boundary() -> eyre::Result<()> {
+            Err(BotError::MulticallPartialFailure { failed_indices: vec![1, 4] })?;
+            Ok(())
+        }
+        let report = boundary().unwrap_err();
+        assert_eq!(report.to_string(), "multicall sync failed for 2 pool(s): [1, 4]");
+        assert_eq!(
+            report.downcast_ref::<BotError>().map(BotError::kind),
+            Some("multicall_partial_failure")
+        );
+    }
+}
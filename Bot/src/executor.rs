@@ -9,19 +9,474 @@
 //  ✓ 10s timeout (5 blok Base L2)
 //  ✓ Dinamik bribe hesabı (kârın %25'i priority fee olarak)
 //  ✓ Zero-copy calldata referansları
-//  ✓ unwrap() yasak — tüm hatalar eyre ile yönetilir
+//  ✓ unwrap() yasak — imza/gönderim yolu tipli BotError döner
+//  ✓ İmzalayıcı + chain id + private RPC provider başlangıçta BİR KEZ kurulur
+//    (işlem başına key parse / wallet / filler kurulumu yok)
+//  ✓ Fırsat ömrü: yeni blokta geçersizleşen fırsat imza / gönderim öncesi
+//    iptal edilir (OpportunityExpiry)
 // ============================================================================
 
-use alloy::primitives::Address;
-use alloy::providers::{Provider, ProviderBuilder};
+use alloy::eips::eip2718::Encodable2718;
+use alloy::network::{Ethereum, EthereumWallet, TransactionBuilder};
+use alloy::primitives::{Address, B256};
+use alloy::providers::{DynProvider, Provider, ProviderBuilder};
 use alloy::rpc::types::TransactionRequest;
 use alloy::signers::local::PrivateKeySigner;
-use alloy::network::EthereumWallet;
 use eyre::Result;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
+use crate::chain_profile::PriorityFeeStrategy;
+use crate::console::eprintln_high;
+use crate::confirmations::{self, ConfirmPolicy, Correction, Finality, Inclusion, TrackedTrade};
+use crate::direction_gate::{self, Direction};
+use crate::errors::{BotError, BotResult};
+use crate::followup;
+use crate::gas_calibration::{self, GasSample};
+use crate::heatmap;
+use crate::impact_verification::{self, ConfirmedTrade, PredictedImpact};
+use crate::log_event::{self, LogEvent};
+use crate::nonce_checkpoint::{self, InflightTx};
+use crate::pair_scheduler;
+use crate::paper::{self, PaperLane, PaperSubmission};
+use crate::submit_pacing::SubmitPacer;
 use crate::types::*;
 
+// ─────────────────────────────────────────────────────────────────────────────
+// İmzalama Bağlamı (başlangıçta bir kez kurulur)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+thread_local! {
+    /// Test kancası: bu thread'de kaç kez private key parse edildi
+    static KEY_PARSES: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// Private key → imzalayıcı. Süreç boyunca yalnızca SigningContext::new çağırır.
+fn parse_signer(private_key: &str) -> Result<PrivateKeySigner> {
+    #[cfg(test)]
+    KEY_PARSES.with(|c| c.set(c.get() + 1));
+    private_key
+        .parse()
+        .map_err(|_| eyre::eyre!("Invalid private key"))
+}
+
+/// Önceden hazırlanmış imzalama durumu.
+///
+/// Key bir kez parse edilir (KeyManager'ın doğruladığı anahtar), chain id
+/// başlangıçta RPC'den doğrulanıp saklanır, private RPC provider filler'sız
+/// kurulur: nonce, gas limit ve iki fee alanı zaten açıkça set edildiği için
+/// nonce/gas/chain-id filler'larının RPC turlarına gerek yoktur. TX yerelde
+/// imzalanır ve eth_sendRawTransaction ile ham olarak gönderilir.
+pub struct SigningContext {
+    wallet: EthereumWallet,
+    address: Address,
+    chain_id: u64,
+    /// Private RPC (filler yok) — PRIVATE_RPC_URL tanımsızsa None
+    private_provider: Option<DynProvider>,
+}
+
+impl SigningContext {
+    pub fn new(private_key: &str, chain_id: u64, private_rpc_url: Option<&str>) -> Result<Self> {
+        let signer = parse_signer(private_key)?;
+        let address = signer.address();
+        let private_provider = match private_rpc_url {
+            Some(url) => {
+                let url: reqwest::Url = url
+                    .parse()
+                    .map_err(|e| eyre::eyre!("Private RPC URL parse error: {}", e))?;
+                Some(
+                    ProviderBuilder::new()
+                        .disable_recommended_fillers()
+                        .connect_http(url)
+                        .erased(),
+                )
+            }
+            None => None,
+        };
+        Ok(Self {
+            wallet: EthereumWallet::from(signer),
+            address,
+            chain_id,
+            private_provider,
+        })
+    }
+
+    /// İmzalayıcı adresi (executor / REVM caller)
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Başlangıçta doğrulanmış chain id
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Tam doldurulmuş TX'i yerelde imzala → (tx hash, EIP-2718 ham bayt).
+    /// Ağ erişimi yok; chain id önbellekten eklenir.
+    pub async fn sign(&self, tx: TransactionRequest) -> BotResult<(B256, Vec<u8>)> {
+        let tx = tx.with_chain_id(self.chain_id);
+        let envelope = <TransactionRequest as TransactionBuilder<Ethereum>>::build(tx, &self.wallet)
+            .await
+            .map_err(|e| BotError::ExecutionSendFailed {
+                context: "TX signing".into(),
+                source: e.to_string().into(),
+            })?;
+        Ok((*envelope.tx_hash(), envelope.encoded_2718()))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gecikme Histogramı (imza / gönderim aşamaları)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Kova üst sınırları (µs) — son kova taşma
+pub const LATENCY_BUCKETS_US: [u64; 7] = [100, 500, 1_000, 5_000, 10_000, 50_000, 100_000];
+
+/// Kilitsiz sabit kovalı gecikme histogramı
+#[derive(Default)]
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS_US.len() + 1],
+    count: AtomicU64,
+    total_us: AtomicU64,
+    max_us: AtomicU64,
+}
+
+impl LatencyHistogram {
+    /// `static` histogramlar için (Default ile aynı)
+    pub const fn new() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; LATENCY_BUCKETS_US.len() + 1],
+            count: AtomicU64::new(0),
+            total_us: AtomicU64::new(0),
+            max_us: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, elapsed_us: u64) {
+        let idx = LATENCY_BUCKETS_US
+            .iter()
+            .position(|&b| elapsed_us <= b)
+            .unwrap_or(LATENCY_BUCKETS_US.len());
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_us.fetch_add(elapsed_us, Ordering::Relaxed);
+        self.max_us.fetch_max(elapsed_us, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// (örnek sayısı, ortalama µs, p50 kova üst sınırı µs, maks µs)
+    pub fn summary(&self) -> (u64, u64, u64, u64) {
+        let count = self.count();
+        if count == 0 {
+            return (0, 0, 0, 0);
+        }
+        let max = self.max_us.load(Ordering::Relaxed);
+        let mut seen = 0;
+        let mut p50 = max;
+        for (i, b) in self.buckets.iter().enumerate() {
+            seen += b.load(Ordering::Relaxed);
+            if seen * 2 >= count {
+                p50 = LATENCY_BUCKETS_US.get(i).copied().unwrap_or(max);
+                break;
+            }
+        }
+        (count, self.total_us.load(Ordering::Relaxed) / count, p50, max)
+    }
+
+    /// Kova sayaçlarının anlık kopyası (oturum özeti için)
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        HistogramSnapshot {
+            bucket_upper_us: LATENCY_BUCKETS_US.to_vec(),
+            bucket_counts: self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect(),
+            count: self.count(),
+            total_us: self.total_us.load(Ordering::Relaxed),
+            max_us: self.max_us.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// LatencyHistogram kopyası — son sayaç taşma kovasıdır
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistogramSnapshot {
+    pub bucket_upper_us: Vec<u64>,
+    pub bucket_counts: Vec<u64>,
+    pub count: u64,
+    pub total_us: u64,
+    pub max_us: u64,
+}
+
+impl Default for HistogramSnapshot {
+    fn default() -> Self {
+        Self {
+            bucket_upper_us: LATENCY_BUCKETS_US.to_vec(),
+            bucket_counts: vec![0; LATENCY_BUCKETS_US.len() + 1],
+            count: 0,
+            total_us: 0,
+            max_us: 0,
+        }
+    }
+}
+
+impl HistogramSnapshot {
+    /// Önceki bağlantının histogramını bu kopyaya ekle
+    pub fn merge(&mut self, other: &HistogramSnapshot) {
+        for (mine, theirs) in self.bucket_counts.iter_mut().zip(&other.bucket_counts) {
+            *mine += theirs;
+        }
+        self.count += other.count;
+        self.total_us += other.total_us;
+        self.max_us = self.max_us.max(other.max_us);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// İşlem Sonuçları (receipt takibi)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Gönderilen TX'lerin zincir sonuçları. Receipt görevi yazar, oturum özeti okur.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TradeOutcomes {
+    /// Private RPC'nin kabul ettiği TX
+    pub submitted: u64,
+    /// status=1 receipt
+    pub landed: u64,
+    /// status=0 receipt (gas ödendi, kâr yok)
+    pub reverted: u64,
+    /// 10s içinde receipt gelmedi / receipt hatası
+    pub dropped: u64,
+    /// Fırsat yeni blokta geçersizleşti — imza / gönderim öncesi iptal
+    pub expired: u64,
+    /// L2 yürütme gas'ı (gas_used × effective_gas_price, wei)
+    pub gas_spent_wei: u128,
+    /// Gerçekleşen PnL: landed → beklenen kâr − gas, reverted → −gas (WETH)
+    pub realized_pnl_weth: f64,
+    /// Receipt'i var, CONFIRMATION_BLOCKS onayı bekleniyor
+    pub provisional: u64,
+    /// Onay bloğunda receipt aynı blokta bulundu
+    pub finalized: u64,
+    /// Receipt'i kaybolan / başka bloğa taşınan reorg olayları
+    pub reorged: u64,
+    /// Reorg sonrası yeniden sorgularda dahil edilmeyen (sonucu geri alınan) TX
+    pub vanished: u64,
+    /// Defterlere işlenen düzeltme kayıtları ve PnL'e net etkileri (WETH)
+    pub corrections: u64,
+    pub correction_pnl_weth: f64,
+}
+
+impl TradeOutcomes {
+    pub fn merge(&mut self, other: &TradeOutcomes) {
+        self.submitted += other.submitted;
+        self.landed += other.landed;
+        self.reverted += other.reverted;
+        self.dropped += other.dropped;
+        self.expired += other.expired;
+        self.gas_spent_wei += other.gas_spent_wei;
+        self.realized_pnl_weth += other.realized_pnl_weth;
+        self.provisional += other.provisional;
+        self.finalized += other.finalized;
+        self.reorged += other.reorged;
+        self.vanished += other.vanished;
+        self.corrections += other.corrections;
+        self.correction_pnl_weth += other.correction_pnl_weth;
+    }
+
+    /// Receipt sonucunu işle
+    pub fn record_receipt(&mut self, success: bool, gas_spent_wei: u128, expected_profit_weth: f64) {
+        let gas_weth = gas_spent_wei as f64 / 1e18;
+        self.gas_spent_wei += gas_spent_wei;
+        if success {
+            self.landed += 1;
+            self.realized_pnl_weth += expected_profit_weth - gas_weth;
+        } else {
+            self.reverted += 1;
+            self.realized_pnl_weth -= gas_weth;
+        }
+    }
+
+    /// Reorg düzeltmesi: geçici sonuç geri alınır, yenisi (varsa) işlenir
+    pub fn apply_correction(&mut self, correction: &Correction) {
+        if let Some(before) = correction.before {
+            self.reorged += 1;
+            self.gas_spent_wei = self.gas_spent_wei.saturating_sub(before.gas_spent_wei);
+            if before.landed {
+                self.landed = self.landed.saturating_sub(1);
+            } else {
+                self.reverted = self.reverted.saturating_sub(1);
+            }
+            self.realized_pnl_weth -= correction.realized_before();
+        }
+        if let Some(after) = correction.after {
+            self.record_receipt(after.landed, after.gas_spent_wei, correction.expected_profit_weth);
+        }
+        self.corrections += 1;
+        self.correction_pnl_weth += correction.pnl_delta_weth();
+    }
+
+    /// Onay görevi kesinleşti (None: durum bilinemedi, geçici kalır)
+    pub fn record_finality(&mut self, finality: Option<Finality>) {
+        let counter = match finality {
+            Some(Finality::Final) => &mut self.finalized,
+            Some(Finality::Vanished) => &mut self.vanished,
+            _ => return,
+        };
+        *counter += 1;
+        self.provisional = self.provisional.saturating_sub(1);
+    }
+
+    /// İstatistik kutusu: onay durumu — receipt yoksa None
+    pub fn confirmation_line(&self) -> Option<String> {
+        (self.finalized + self.provisional + self.corrections > 0).then(|| {
+            format!(
+                "{} final / {} provisional | {} reorged ({} vanished) | {} corrections {:+.6} WETH",
+                self.finalized,
+                self.provisional,
+                self.reorged,
+                self.vanished,
+                self.corrections,
+                self.correction_pnl_weth,
+            )
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Eşzamanlı Yürütme Sınırı (MAX_INFLIGHT_EXECUTIONS)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Uçuştaki yürütme görevlerini sınırlar. Ardışık bloklarda fırsat patlaması
+/// sınırsız görev açarsa TX'ler nonce tüketir, birbirine karşı teklif verir
+/// ve en kötü durum kaybı katlanır. İzin nonce tahsisinden önce alınır;
+/// sınır doluysa fırsat kuyruğa alınmaz, atlanır (bayat arbitraj, atlanmış
+/// olandan kötüdür).
+pub struct InflightLimiter {
+    semaphore: Arc<tokio::sync::Semaphore>,
+    active: Arc<AtomicU64>,
+    peak: AtomicU64,
+}
+
+impl InflightLimiter {
+    pub fn new(max_inflight: usize) -> Self {
+        Self {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(max_inflight.max(1))),
+            active: Arc::new(AtomicU64::new(0)),
+            peak: AtomicU64::new(0),
+        }
+    }
+
+    /// Boş slot varsa izin al — yoksa None (beklemez)
+    pub fn try_acquire(&self) -> Option<InflightGuard> {
+        let permit = Arc::clone(&self.semaphore).try_acquire_owned().ok()?;
+        let active = self.active.fetch_add(1, Ordering::Relaxed) + 1;
+        self.peak.fetch_max(active, Ordering::Relaxed);
+        Some(InflightGuard {
+            _permit: permit,
+            active: Arc::clone(&self.active),
+        })
+    }
+
+    /// Şu an uçuştaki yürütme sayısı
+    pub fn active(&self) -> u64 {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Oturumdaki en yüksek eşzamanlı yürütme sayısı
+    pub fn peak(&self) -> u64 {
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+/// Yürütme görevinin sahip olduğu slot. Görev gövdesi ve receipt görevi
+/// `Arc` kopyalarını tutar; son kopya düştüğünde slot geri verilir — başarı
+/// (receipt / timeout), gönderim hatası (nonce geri alındıktan sonra) ve
+/// panik dahil her çıkış yolunda.
+pub struct InflightGuard {
+    _permit: tokio::sync::OwnedSemaphorePermit,
+    active: Arc<AtomicU64>,
+}
+
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Fırsat Ömrü (yürütme görevine taşınan iptal sinyali)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Yürütme görevinin fırsatı hâlâ geçerli mi? Ana döngü yeni bloğun
+/// sync'inde fırsatı ölü bulursa `expire` eder; görev her aşama sınırında
+/// (imza öncesi, gönderim öncesi) `check` ile durur. Deadline yalnızca
+/// birkaç blok sonrasını korur — bu sinyal blok-N state'i için hazırlanan
+/// TX'in N+2'de gönderilmesini engeller.
+#[derive(Debug, Clone, Default)]
+pub struct OpportunityExpiry {
+    token: CancellationToken,
+    reason: Arc<Mutex<Option<String>>>,
+    /// Tetik bloğunun pipeline bütçesinin bittiği an (gönderim zamanlaması)
+    send_deadline: Option<Instant>,
+}
+
+impl OpportunityExpiry {
+    pub fn with_send_deadline(send_deadline: Option<Instant>) -> Self {
+        Self { send_deadline, ..Self::default() }
+    }
+
+    /// Gönderimin daha ne kadar gecikebileceği (bütçe verilmediyse None)
+    pub fn send_budget(&self) -> Option<Duration> {
+        self.send_deadline.map(|d| d.saturating_duration_since(Instant::now()))
+    }
+
+    /// Fırsatı geçersiz say (ilk sebep korunur)
+    pub fn expire(&self, reason: impl Into<String>) {
+        self.reason.lock().get_or_insert_with(|| reason.into());
+        self.token.cancel();
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Aşama sınırı kontrolü — geçersizse `ExecutionExpired`
+    pub fn check(&self, stage: &'static str) -> BotResult<()> {
+        if !self.is_expired() {
+            return Ok(());
+        }
+        Err(BotError::ExecutionExpired {
+            stage,
+            reason: self.reason.lock().clone().unwrap_or_default(),
+        })
+    }
+}
+
+/// İmza → gönderim aşamaları. `sign` tembel bir future'dır: imza öncesi
+/// kontrol geçilmeden çalışmaz; imza sürerken fırsat ölürse gönderim
+/// öncesi kontrol TX'i ağa çıkmadan durdurur.
+pub(crate) async fn staged_send<S, T, TF>(
+    expiry: &OpportunityExpiry,
+    sign: S,
+    send: T,
+) -> BotResult<String>
+where
+    S: Future<Output = BotResult<Vec<u8>>>,
+    T: FnOnce(Vec<u8>) -> TF,
+    TF: Future<Output = BotResult<String>>,
+{
+    expiry.check("signing")?;
+    let raw_tx = sign.await?;
+    expiry.check("send")?;
+    send(raw_tx).await
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // MEV Korumalı Executor
 // ─────────────────────────────────────────────────────────────────────────────
@@ -41,15 +496,33 @@ use crate::types::*;
 /// Bribe (validator tip) hesabı:
 ///   - Kârın dinamik yüzdesi (%25 base, margin'e göre uyarlanır)
 ///   - Priority fee olarak TX'e eklenir
-///   - Base L2 FIFO: priority fee sıralama belirler
+///   - FIFO bribe (OP Stack): priority fee sıralama belirler
+///   - EIP-1559 (Arbitrum FCFS): sıralama bahşişe bağlı değil → sabit
+///     standart bahşiş, kârdan pay verilmez
 pub struct MevExecutor {
     /// Private RPC URL (eth_sendRawTransaction için)
     /// Örn: https://rpc.flashbots.net/fast?chainId=8453
     private_rpc_url: Option<String>,
-    /// Standart RPC URL (whitelist TX, receipt polling)
-    standard_rpc_url: String,
     /// Dinamik bribe yüzde tabanı (0.25 = %25)
     base_bribe_pct: f64,
+    /// Priority fee stratejisi (zincir profilinden)
+    priority_fee: PriorityFeeStrategy,
+    /// Başlangıçta kurulan imzalama bağlamı (key yüklü değilse None)
+    signing: Option<Arc<SigningContext>>,
+    /// TX oluştur + imzala aşaması gecikmesi
+    pub sign_latency: LatencyHistogram,
+    /// eth_sendRawTransaction gecikmesi
+    pub send_latency: LatencyHistogram,
+    /// Gönderilen TX'lerin receipt sonuçları (arka plan görevi yazar)
+    pub trade_outcomes: Arc<Mutex<TradeOutcomes>>,
+    /// Uçuştaki yürütme görevi sınırı
+    pub inflight: InflightLimiter,
+    /// Gönderim öncesi jitter + yerel örnekler arası aralık (kapalıysa None)
+    submit_pacer: Option<SubmitPacer>,
+    /// PAPER_EXECUTION şeridi — bağlıysa hiçbir TX süreçten çıkmaz
+    paper: Option<Arc<PaperLane>>,
+    /// Receipt sonrası reorg kontrolü (CONFIRMATION_BLOCKS)
+    confirmation: ConfirmPolicy,
 }
 
 impl MevExecutor {
@@ -57,23 +530,72 @@ impl MevExecutor {
     ///
     /// # Argümanlar
     /// - `private_rpc_url`: Flashbots/Private RPC URL (None ise fallback)
-    /// - `standard_rpc_url`: Normal RPC URL
     /// - `base_bribe_pct`: Kâr bribe yüzdesi (0.25 = %25)
+    /// - `signing`: Başlangıçta kurulan imzalama bağlamı (paylaşımlı)
+    /// - `max_inflight`: Aynı anda uçuşta olabilecek yürütme sayısı
     pub fn new(
         private_rpc_url: Option<String>,
-        standard_rpc_url: String,
         base_bribe_pct: f64,
+        signing: Option<Arc<SigningContext>>,
+        max_inflight: usize,
     ) -> Self {
         Self {
             private_rpc_url,
-            standard_rpc_url,
             base_bribe_pct,
+            priority_fee: PriorityFeeStrategy::FifoBribe,
+            signing,
+            sign_latency: LatencyHistogram::default(),
+            send_latency: LatencyHistogram::default(),
+            trade_outcomes: Arc::new(Mutex::new(TradeOutcomes::default())),
+            inflight: InflightLimiter::new(max_inflight),
+            submit_pacer: None,
+            paper: None,
+            confirmation: ConfirmPolicy::DEFAULT,
         }
     }
 
-    /// v25.0: Standart RPC URL'sine erişim (whitelist TX göndermek için)
-    pub fn standard_rpc_url(&self) -> &str {
-        &self.standard_rpc_url
+    /// Priority fee stratejisini ayarla (varsayılan: FIFO bribe)
+    pub fn with_priority_fee_strategy(mut self, strategy: PriorityFeeStrategy) -> Self {
+        self.priority_fee = strategy;
+        self
+    }
+
+    /// Receipt onay politikasını ayarla (varsayılan: 10 blok)
+    pub fn with_confirmation_policy(mut self, policy: ConfirmPolicy) -> Self {
+        self.confirmation = policy;
+        self
+    }
+
+    /// Gönderim zamanlayıcısını bağla (SUBMIT_JITTER_MS / MIN_SUBMIT_SPACING_MS)
+    pub fn with_submit_pacer(mut self, pacer: Option<SubmitPacer>) -> Self {
+        self.submit_pacer = pacer;
+        self
+    }
+
+    /// Kağıt yürütme şeridini bağla (PAPER_EXECUTION)
+    pub fn with_paper_lane(mut self, lane: Option<Arc<PaperLane>>) -> Self {
+        self.paper = lane;
+        self
+    }
+
+    /// Kağıt şerit — bağlıysa nonce / sonuç muhasebesi onun defterlerinde
+    pub fn paper(&self) -> Option<&Arc<PaperLane>> {
+        self.paper.as_ref()
+    }
+
+    /// Gönderim zamanlaması istatistiği — zamanlayıcı yoksa None
+    pub fn pacing_stats_line(&self) -> Option<String> {
+        self.submit_pacer.as_ref().map(SubmitPacer::stats_line)
+    }
+
+    /// İmzalama bağlamı (whitelist TX gibi yan yollar için)
+    pub fn signing(&self) -> Option<&Arc<SigningContext>> {
+        self.signing.as_ref()
+    }
+
+    /// İmzalayıcı adresi — key yoksa Address::ZERO (REVM caller)
+    pub fn signer_address(&self) -> Address {
+        self.signing.as_ref().map(|s| s.address()).unwrap_or_default()
     }
 
     /// İşlemi MEV-korumalı olarak gönder.
@@ -89,29 +611,222 @@ impl MevExecutor {
     /// gas ödemese dahi L1 Data Fee ödemek zorundadır. Bu durum cüzdanın
     /// sürekli L1 ücretleri ile kanamasına yol açıyordu.
     /// Artık Private RPC başarısız olursa işlem iptal edilir.
+    ///
+    /// `route`: gas kalibrasyon defterinin anahtarı (bkz. gas_calibration).
+    /// `gas`: REVM gas'ı + tamponlu limit (+ optimumdaki tick geçişleri ve
+    /// modellenen gas) — receipt'in gas_used'ı ile deftere ve trades.json'a.
+    /// `inflight`: yürütme slotu — gönderim başarılıysa receipt görevi
+    /// sonuca (ya da timeout'a) kadar tutar.
+    /// `expiry`: fırsat yeni blokta ölürse imza / gönderim öncesi
+    /// `ExecutionExpired` döner (çağıran nonce'u geri alır).
+    /// `impact`: havuzların öngörülen son durumu — status=1 receipt'te
+    /// etki doğrulamasına gider (multi-hop: None).
+    /// `deadline_block`: kontratın kabul ettiği son blok — gönderilen TX
+    /// receipt'e kadar nonce checkpoint kaydında bununla izlenir.
+    /// `robustness`: fırsatın 1/2 tick sağlamlık skoru — kırılgan fırsatta
+    /// bribe artırılır (ölçülmediyse None).
+    /// `direction`: (buy, sell) havuz yönü — receipt sonucu yön kapısına
+    /// işlenir (multi-hop: None).
+    ///
+    /// Zamanlayıcı bağlıysa gönderimden hemen önce jitter + yerel aralık
+    /// beklenir; bekleme `expiry`'nin gönderim bütçesini aşarsa TX gönderilmez
+    /// (`ExecutionExpired`, fırsat sonraki blokta yeniden değerlendirilir).
+    ///
+    /// Kağıt şerit bağlıysa (PAPER_EXECUTION) aynı aşama sınırlarından
+    /// geçilir ama imza ve gönderim `execute_paper`'a yönlenir.
     #[allow(clippy::too_many_arguments)]
     pub async fn execute_protected(
         &self,
-        private_key: &str,
         contract_address: Address,
         calldata: &[u8],
         nonce: u64,
         expected_profit_weth: f64,
-        simulated_gas: u64,
+        gas: GasSample,
         block_base_fee: u64,
         current_block: u64,
+        deadline_block: u64,
+        route: &'static str,
+        inflight: Arc<InflightGuard>,
         _nonce_manager: &Arc<NonceManager>,
-    ) -> Result<String> {
+        expiry: &OpportunityExpiry,
+        impact: Option<PredictedImpact>,
+        robustness: Option<f64>,
+        direction: Option<Direction>,
+    ) -> BotResult<String> {
+        if let Some(paper) = &self.paper {
+            return self
+                .execute_paper(
+                    paper,
+                    calldata,
+                    nonce,
+                    expected_profit_weth,
+                    gas,
+                    block_base_fee,
+                    current_block,
+                    deadline_block,
+                    inflight,
+                    expiry,
+                    robustness,
+                    direction,
+                )
+                .await;
+        }
+
+        // Private RPC yoksa imzalamaya bile gerek yok
+        let Some(ref private_url) = self.private_rpc_url else {
+            eprintln_high!(
+                "     ❌ [v25.0] PRIVATE_RPC_URL not defined — trade CANCELLED"
+            );
+            return Err(BotError::ExecutionUnavailable {
+                reason: "PRIVATE_RPC_URL not defined — not sending to the public mempool",
+            });
+        };
+
+        // 1-3. TX oluştur + imzala (önbellekteki imzalayıcı, ağ turu yok)
+        let sign = async {
+            self.prepare_signed(
+                contract_address,
+                calldata,
+                nonce,
+                expected_profit_weth,
+                gas.simulated,
+                block_base_fee,
+                robustness,
+            )
+            .await
+            .map(|(_, raw_tx)| raw_tx)
+        };
+
+        // 4. Gönder — YALNIZCA Private RPC (eth_sendRawTransaction).
+        //
+        // v25.0: Base L2'de Flashbots builder yapısı yoktur.
+        // İşlemler Private RPC endpoint'ine eth_sendRawTransaction ile gönderilir.
+        // Private RPC yoksa işlem İPTAL EDİLİR.
+        let send = |raw_tx: Vec<u8>| async move {
+            // Jitter + yerel aralık; bekleme sırasında fırsat ölmüş olabilir
+            if let Some(pacer) = &self.submit_pacer {
+                pacer.pace(expiry.send_budget()).await?;
+                expiry.check("send")?;
+            }
+            let tracked = InflightTx { nonce, hash: B256::ZERO, deadline_block };
+            self.send_private_tx(
+                private_url,
+                &raw_tx,
+                current_block,
+                tracked,
+                expected_profit_weth,
+                route,
+                gas,
+                inflight,
+                impact,
+                direction,
+            )
+            .await
+                .inspect_err(|e| {
+                    eprintln_high!(
+                        "     ❌ [v25.0] Private RPC TX failed — trade CANCELLED: {}",
+                        e
+                    );
+                })
+        };
+
+        match staged_send(expiry, sign, send).await {
+            Ok(hash) => Ok(hash),
+            Err(e @ BotError::ExecutionExpired { .. }) => {
+                self.trade_outcomes.lock().expired += 1;
+                eprintln_high!("     ⏹️  Opportunity superseded — trade CANCELLED: {}", e);
+                crate::json_logger::log_json("trade", "execution_expired", serde_json::json!({
+                    "trigger_block": current_block,
+                    "nonce": nonce,
+                    "reason": e.to_string(),
+                }));
+                Err(e)
+            }
+            Err(e) => {
+                crate::circuit_breaker::record_failure(crate::circuit_breaker::FailureKind::Rpc, e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// Kağıt yürütme: imza yerine sahte ham TX, gönderim yerine kağıt şerit.
+    /// Aşama sınırları (imza / gönderim öncesi) ve dinamik bribe gerçek yolla
+    /// aynı; imzalayıcı, private RPC, zamanlayıcı ve gerçek kayıtlar
+    /// (nonce checkpoint, trade_outcomes, gas defteri, yön kapısı) kullanılmaz.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_paper(
+        &self,
+        paper: &Arc<PaperLane>,
+        calldata: &[u8],
+        nonce: u64,
+        expected_profit_weth: f64,
+        gas: GasSample,
+        block_base_fee: u64,
+        current_block: u64,
+        deadline_block: u64,
+        inflight: Arc<InflightGuard>,
+        expiry: &OpportunityExpiry,
+        robustness: Option<f64>,
+        direction: Option<Direction>,
+    ) -> BotResult<String> {
+        let sign = async { Ok(paper::paper_raw_tx(nonce, calldata)) };
+        let send = |raw_tx: Vec<u8>| async move {
+            let bribe = self.compute_dynamic_bribe(expected_profit_weth, gas.simulated, block_base_fee, robustness);
+            Ok(paper.submit(
+                PaperSubmission {
+                    raw_tx,
+                    nonce,
+                    deadline_block,
+                    current_block,
+                    expected_profit_weth,
+                    gas_used: gas.simulated,
+                    gas_price_wei: (block_base_fee as u128).saturating_add(bribe.priority_fee_per_gas),
+                    direction,
+                },
+                inflight,
+            ))
+        };
+
+        match staged_send(expiry, sign, send).await {
+            Err(e @ BotError::ExecutionExpired { .. }) => {
+                eprintln_high!("     ⏹️  [Paper] Opportunity superseded — trade CANCELLED: {}", e);
+                paper.record_expired(nonce, current_block, &e.to_string());
+                Err(e)
+            }
+            other => other,
+        }
+    }
+
+    /// TX oluştur (calldata + dinamik bribe priority fee) ve imzala.
+    /// Ağ erişimi yok — dönüş: (tx hash, EIP-2718 ham bayt).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn prepare_signed(
+        &self,
+        contract_address: Address,
+        calldata: &[u8],
+        nonce: u64,
+        expected_profit_weth: f64,
+        simulated_gas: u64,
+        block_base_fee: u64,
+        robustness: Option<f64>,
+    ) -> BotResult<(B256, Vec<u8>)> {
+        let started = Instant::now();
+        let signing = self
+            .signing
+            .as_ref()
+            .ok_or(BotError::ExecutionUnavailable { reason: "no signer loaded" })?;
+
         // 1. Dinamik bribe hesabı
         let bribe_info = self.compute_dynamic_bribe(
             expected_profit_weth,
             simulated_gas,
             block_base_fee,
+            robustness,
         );
 
         // 2. TX oluştur
-        let gas_limit = ((simulated_gas as f64) * 1.10) as u64;
-        let gas_limit = gas_limit.max(100_000);
+        // Tampon: GAS_BUFFER_MODE (fixed 1.10 ya da receipt'lerden öğrenilen)
+        let gas_limit = gas_calibration::gas_limit(simulated_gas);
 
         let max_fee = {
             let base_component = (block_base_fee as u128).saturating_mul(2);
@@ -129,7 +844,7 @@ impl MevExecutor {
             .max_fee_per_gas(max_fee)
             .max_priority_fee_per_gas(bribe_info.priority_fee_per_gas);
 
-        eprintln!(
+        eprintln_high!(
             "     💰 MEV Bribe: {:.0}% (marj: {:.1}x, priority: {} Gwei, profit: {:.6} WETH)",
             bribe_info.effective_pct * 100.0,
             bribe_info.profit_margin_ratio,
@@ -137,39 +852,11 @@ impl MevExecutor {
             expected_profit_weth,
         );
 
-        // 3. İmzala
-        let signer: PrivateKeySigner = private_key
-            .parse()
-            .map_err(|_| eyre::eyre!("Invalid private key"))?;
-        let wallet = EthereumWallet::from(signer.clone());
-
-        // 4. Gönder — YALNIZCA Private RPC (eth_sendRawTransaction).
-        //
-        // v25.0: Base L2'de Flashbots builder yapısı yoktur.
-        // İşlemler Private RPC endpoint'ine eth_sendRawTransaction ile gönderilir.
-        // Private RPC yoksa işlem İPTAL EDİLİR.
-        if let Some(ref private_url) = self.private_rpc_url {
-            match self.send_private_tx(
-                private_url,
-                &wallet,
-                tx.clone(),
-                current_block,
-            ).await {
-                Ok(hash) => Ok(hash),
-                Err(e) => {
-                    eprintln!(
-                        "     ❌ [v25.0] Private RPC TX failed — trade CANCELLED: {}",
-                        e
-                    );
-                    Err(eyre::eyre!("Private RPC TX failed: {}", e))
-                }
-            }
-        } else {
-            eprintln!(
-                "     ❌ [v25.0] PRIVATE_RPC_URL not defined — trade CANCELLED"
-            );
-            Err(eyre::eyre!("Private RPC URL not defined. Not sending to public mempool for security reasons."))
-        }
+        // 3. İmzala — önbellekteki imzalayıcı + chain id
+        let signed = signing.sign(tx).await?;
+        self.sign_latency
+            .record(started.elapsed().as_micros() as u64);
+        Ok(signed)
     }
 
     /// eth_sendRawTransaction ile Private RPC endpoint'ine gönder.
@@ -180,69 +867,213 @@ impl MevExecutor {
     /// eth_sendRawTransaction metodu ile POST edilir.
     ///
     /// TX yalnızca private endpoint'e ulaşır, public mempool'a DÜŞMEZ.
+    /// Receipt gelince gerçek gas_used, `gas` kaydıyla birlikte kalibrasyon
+    /// defterine (`route` anahtarı) işlenir. Gönderim başarılıysa TX
+    /// (`tracked`, hash burada doldurulur) receipt'e kadar nonce checkpoint
+    /// kaydında kalır; receipt gelmezse deadline'a kadar izlenir.
+    /// Receipt sonucu `direction` verildiyse yön kapısına ve bekleyen
+    /// iki adımlı takibe (EXECUTE_FOLLOWUP) da işlenir.
+    #[allow(clippy::too_many_arguments)]
     async fn send_private_tx(
         &self,
         private_rpc_url: &str,
-        wallet: &EthereumWallet,
-        tx: TransactionRequest,
+        raw_tx: &[u8],
         current_block: u64,
-    ) -> Result<String> {
-        let private_url: reqwest::Url = private_rpc_url.parse()
-            .map_err(|e| eyre::eyre!("Private RPC URL parse error: {}", e))?;
-        let provider = ProviderBuilder::new()
-            .wallet(wallet.clone())
-            .connect_http(private_url);
-
-        // TX'i private RPC üzerinden gönder (imzala + eth_sendRawTransaction)
+        tracked: InflightTx,
+        expected_profit_weth: f64,
+        route: &'static str,
+        gas: GasSample,
+        inflight: Arc<InflightGuard>,
+        impact: Option<PredictedImpact>,
+        direction: Option<Direction>,
+    ) -> BotResult<String> {
+        let provider = self
+            .signing
+            .as_ref()
+            .and_then(|s| s.private_provider.clone())
+            .ok_or(BotError::ExecutionUnavailable { reason: "private RPC provider not initialized" })?;
+
+        // Önceden imzalanmış TX'i private RPC'ye gönder (eth_sendRawTransaction)
         // TX yalnızca private endpoint'e ulaşır, public mempool'a DÜŞMEZ
-        let pending = provider.send_transaction(tx)
+        let started = Instant::now();
+        let pending = provider.send_raw_transaction(raw_tx)
             .await
-            .map_err(|e| eyre::eyre!("Private RPC TX send error: {}", e))?;
+            .map_err(|e| BotError::ExecutionSendFailed {
+                context: "Private RPC TX send".into(),
+                source: Box::new(e),
+            })?;
+        self.send_latency
+            .record(started.elapsed().as_micros() as u64);
 
         let tx_hash = format!("{:?}", pending.tx_hash());
         let tx_hash_alloy = *pending.tx_hash();
         drop(pending);
+        nonce_checkpoint::register(InflightTx { hash: tx_hash_alloy, ..tracked });
 
-        eprintln!(
-            "     📤 TX sent → blok #{} | private RPC: {}",
-            current_block + 1,
-            &private_rpc_url[..private_rpc_url.len().min(50)]
-        );
+        let emitted = log_event::json(|| {
+            LogEvent::TxSent(log_event::TxSent {
+                tx_hash: tx_hash.clone(),
+                nonce: tracked.nonce,
+                block: current_block,
+                route,
+                expected_profit_weth,
+            })
+        });
+        if !emitted {
+            eprintln_high!(
+                "     📤 TX sent → blok #{} | private RPC: {}",
+                current_block + 1,
+                &private_rpc_url[..private_rpc_url.len().min(50)]
+            );
+        }
+        crate::json_logger::log_json("trade", "tx_sent", serde_json::json!({
+            "tx_hash": &tx_hash,
+            "block": current_block,
+            "route": route,
+        }));
 
-        // Fire-and-forget: Receipt bekleme arka plana taşınır
-        let rpc_url_clone = private_rpc_url.to_string();
+        // Fire-and-forget: Receipt bekleme arka plana taşınır (aynı provider)
         let hash_clone = tx_hash.clone();
+        let poll_provider = provider;
+        let outcomes = Arc::clone(&self.trade_outcomes);
+        outcomes.lock().submitted += 1;
+        // Gerçekleşen kâr, TX'in gönderildiği ısı haritası hücresine yazılır
+        let submitted_at = crate::clock::now();
+        heatmap::record_executed(submitted_at);
+        let confirmation = self.confirmation;
         tokio::spawn(async move {
+            // Slot ilk receipt'e (ya da timeout'a) kadar dolu kalır
+            let inflight = inflight;
+            let mut impact = impact;
             let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(10);
-            let poll_url: reqwest::Url = match rpc_url_clone.parse() {
-                Ok(u) => u,
-                Err(e) => {
-                    eprintln!("     ⚠️  Receipt polling URL parse error: {}", e);
-                    return;
-                }
-            };
-            let poll_provider = ProviderBuilder::new().connect_http(poll_url);
-            loop {
+            let first = loop {
                 if tokio::time::Instant::now() > deadline {
-                    eprintln!("     ⏰ TX timeout (10s) — may not be included: {}", &hash_clone);
-                    break;
+                    eprintln_high!("     ⏰ TX timeout (10s) — may not be included: {}", &hash_clone);
+                    outcomes.lock().dropped += 1;
+                    break None;
                 }
                 match poll_provider.get_transaction_receipt(tx_hash_alloy).await {
                     Ok(Some(receipt)) => {
-                        eprintln!(
-                            "     ✅ TX dahil edildi: blok #{}",
-                            receipt.block_number.unwrap_or_default()
+                        nonce_checkpoint::resolve(tracked.nonce);
+                        let inclusion = Inclusion::from_receipt(&receipt);
+                        let realized_weth = inclusion.pnl_weth(expected_profit_weth);
+                        let emitted = log_event::json(|| {
+                            LogEvent::TxConfirmed(log_event::TxConfirmed {
+                                tx_hash: hash_clone.clone(),
+                                block: inclusion.block,
+                                landed: inclusion.landed,
+                                gas_used: inclusion.gas_used,
+                                gas_spent_weth: inclusion.gas_spent_wei as f64 / 1e18,
+                                realized_pnl_weth: realized_weth,
+                            })
+                        });
+                        if !emitted {
+                            eprintln_high!(
+                                "     {} TX dahil edildi: blok #{} (geçici, {} onay bekleniyor)",
+                                if inclusion.landed { "✅" } else { "❌ (reverted)" },
+                                inclusion.block,
+                                confirmation.confirmations,
+                            );
+                        }
+                        {
+                            let mut o = outcomes.lock();
+                            o.record_receipt(inclusion.landed, inclusion.gas_spent_wei, expected_profit_weth);
+                            o.provisional += 1;
+                        }
+                        if inclusion.landed {
+                            crate::circuit_breaker::record_success();
+                        } else {
+                            crate::circuit_breaker::record_failure(
+                                crate::circuit_breaker::FailureKind::Revert,
+                                format!("{} reverted in block #{}", hash_clone, inclusion.block),
+                            );
+                        }
+                        heatmap::record_realized(submitted_at, realized_weth);
+                        gas_calibration::record_receipt(
+                            route,
+                            GasSample { actual: inclusion.gas_used, ..gas },
+                            &hash_clone,
+                            receipt.block_number.unwrap_or(current_block + 1),
+                            inclusion.landed,
                         );
-                        break;
+                        if let Some(ref direction) = direction {
+                            direction_gate::record_receipt(
+                                direction,
+                                inclusion.landed,
+                                inclusion.gas_spent_wei,
+                                expected_profit_weth,
+                                receipt.block_number.unwrap_or(current_block + 1),
+                            );
+                            followup::record_receipt(direction, inclusion.landed);
+                            // Çift skoru modellenen kâr yerine gerçekleşenle düzeltilir
+                            pair_scheduler::record_realized(
+                                direction.key.buy_pool,
+                                direction.key.sell_pool,
+                                expected_profit_weth,
+                                realized_weth,
+                            );
+                        }
+                        // Havuzların gerçek son durumu dahil edilme bloğunda okunur
+                        if let (true, Some(block), Some(impact)) =
+                            (inclusion.landed, receipt.block_number, impact.take())
+                        {
+                            impact_verification::submit_confirmed(ConfirmedTrade {
+                                tx_hash: tx_hash_alloy,
+                                block,
+                                impact,
+                            });
+                        }
+                        break Some(inclusion);
                     }
                     Ok(None) => {
                         tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                     }
                     Err(e) => {
-                        eprintln!("     ⚠️  TX receipt error: {}", e);
-                        break;
+                        eprintln_high!("     ⚠️  TX receipt error: {}", e);
+                        outcomes.lock().dropped += 1;
+                        crate::circuit_breaker::record_failure(
+                            crate::circuit_breaker::FailureKind::Rpc,
+                            format!("receipt for {}: {}", hash_clone, e),
+                        );
+                        break None;
                     }
                 }
+            };
+            // Onay beklemesi yürütme slotunu tutmaz
+            drop(inflight);
+            let Some(first) = first else {
+                return;
+            };
+
+            // Reorg: CONFIRMATION_BLOCKS sonra receipt yeniden okunur; değişen
+            // sonuç her deftere düzeltme kaydı olarak işlenir
+            let trade = TrackedTrade::provisional(tx_hash_alloy, expected_profit_weth, first, &confirmation);
+            let finality = confirmations::watch(
+                trade,
+                confirmation,
+                || async {
+                    poll_provider
+                        .get_block_number()
+                        .await
+                        .map_err(|e| BotError::from_transport("eth_blockNumber", e))
+                },
+                || async {
+                    poll_provider
+                        .get_transaction_receipt(tx_hash_alloy)
+                        .await
+                        .map(|receipt| receipt.as_ref().map(Inclusion::from_receipt))
+                        .map_err(|e| BotError::from_transport("eth_getTransactionReceipt", e))
+                },
+                |correction: &Correction| {
+                    confirmations::book_correction(&outcomes, submitted_at, direction.as_ref(), correction)
+                },
+            )
+            .await;
+            outcomes.lock().record_finality(finality);
+            match finality {
+                Some(Finality::Final) => {}
+                Some(_) => eprintln_high!("     ⚠️  TX {} dropped by reorg — outcome reversed", &hash_clone),
+                None => eprintln_high!("     ⚠️  TX {} confirmation unknown (RPC) — outcome stays provisional", &hash_clone),
             }
         });
 
@@ -252,14 +1083,17 @@ impl MevExecutor {
     // ── Dinamik Bribe Hesabı ─────────────────────────────────────────────────
 
     /// Bribe hesaplama sonucu
+    ///
+    /// `robustness` (0..1, kâr hassasiyeti): kırılgan fırsat beklerse ölür —
+    /// bloğu kazanmak için kademe yüzdesine (1 - skor) × FRAGILITY_BRIBE_BOOST
+    /// eklenir. None = ölçülmedi, kademe aynen uygulanır.
     pub fn compute_dynamic_bribe(
         &self,
         expected_profit_weth: f64,
         simulated_gas: u64,
         block_base_fee: u64,
+        robustness: Option<f64>,
     ) -> BribeInfo {
-        let _expected_profit_wei = safe_f64_to_u128(expected_profit_weth * 1e18);
-
         // Gas maliyeti (WETH cinsinden)
         let gas_cost_weth = (simulated_gas as f64 * block_base_fee as f64) / 1e18;
 
@@ -270,6 +1104,23 @@ impl MevExecutor {
             10.0
         };
 
+        // EIP-1559 stratejisi: bahşiş sıralamayı değiştirmez, kâr paylaşılmaz
+        if self.priority_fee == PriorityFeeStrategy::Eip1559Competitive {
+            let actual_gas = gas_calibration::gas_limit(simulated_gas) as u128;
+            let bribe_wei = STANDARD_PRIORITY_FEE_WEI.saturating_mul(actual_gas);
+            return BribeInfo {
+                bribe_wei,
+                priority_fee_per_gas: STANDARD_PRIORITY_FEE_WEI,
+                effective_pct: if expected_profit_weth > 0.0 {
+                    bribe_wei as f64 / 1e18 / expected_profit_weth
+                } else {
+                    0.0
+                },
+                profit_margin_ratio,
+                gas_cost_weth,
+            };
+        }
+
         // v24.0: Agresif PGA modülü — %10 ile %95 aralığında dinamik bribe.
         //
         // Base L2 sequencer sıralaması yalnızca priority fee ile belirlenir.
@@ -296,6 +1147,13 @@ impl MevExecutor {
         } else {
             0.95 // v24.0: Eski %70 → %95 (maksimum rekabet gücü)
         };
+        let effective_pct = match robustness {
+            Some(score) => {
+                let fragility = 1.0 - score.clamp(0.0, 1.0);
+                (effective_pct + fragility * FRAGILITY_BRIBE_BOOST).min(MAX_BRIBE_PCT)
+            }
+            None => effective_pct,
+        };
 
         // v20.0: Minimum mutlak kâr koruması
         // Bribe sonrası kalan kâr en az 0.000005 WETH olmalı.
@@ -311,16 +1169,13 @@ impl MevExecutor {
         };
 
         // Bribe wei
-        let bribe_wei = safe_f64_to_u128(actual_bribe_weth * 1e18);
+        let bribe_wei = weth_to_wei(actual_bribe_weth).map_or(0, |wei| wei.to::<u128>());
 
         // Priority fee per gas
-        let gas_with_buffer = safe_f64_to_u128((simulated_gas as f64) * 1.10);
-        let actual_gas = gas_with_buffer.max(100_000);
-        let priority_fee = if actual_gas > 0 {
-            (bribe_wei / actual_gas).max(1_000_000) // Min 1 Mwei
-        } else {
-            1_000_000_000 // Fallback 1 Gwei
-        };
+        let actual_gas = gas_calibration::gas_limit(simulated_gas) as u128;
+        let priority_fee = bribe_wei
+            .checked_div(actual_gas)
+            .map_or(1_000_000_000, |fee| fee.max(1_000_000)); // Min 1 Mwei; gas 0 → 1 Gwei
 
         BribeInfo {
             bribe_wei,
@@ -332,6 +1187,15 @@ impl MevExecutor {
     }
 }
 
+/// Sağlamlık skoru 0 olan (1 tick'te ölen) fırsata eklenen bribe yüzdesi
+const FRAGILITY_BRIBE_BOOST: f64 = 0.20;
+/// Kırılganlık eklemesiyle bile aşılmayan bribe yüzdesi tavanı
+const MAX_BRIBE_PCT: f64 = 0.95;
+
+/// EIP-1559 stratejisinde sabit bahşiş (0.01 Gwei) — FCFS zincirlerde
+/// sıralama priority fee'ye bağlı değildir
+const STANDARD_PRIORITY_FEE_WEI: u128 = 10_000_000;
+
 /// Bribe hesaplama sonucu
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -365,3 +1229,233 @@ pub fn encode_whitelist_calldata(pool_addresses: &[Address]) -> Vec<u8> {
     };
     executorBatchAddPoolsCall::abi_encode(&call)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::consensus::{Transaction, TxEnvelope};
+    use alloy::eips::eip2718::Decodable2718;
+    use alloy::primitives::address;
+
+    // Anvil/Hardhat test hesabı #0 — gerçek fon içermez
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const TEST_ADDR: Address = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+    const CONTRACT: Address = address!("1111111111111111111111111111111111111111");
+
+    fn key_parses() -> u64 {
+        KEY_PARSES.with(|c| c.get())
+    }
+
+    fn make_executor() -> MevExecutor {
+        let ctx = SigningContext::new(TEST_KEY, 8453, Some("https://rpc.example.io")).unwrap();
+        MevExecutor::new(
+            Some("https://rpc.example.io".into()),
+            0.25,
+            Some(Arc::new(ctx)),
+            2,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_execution_path_performs_no_key_parsing() {
+        let before = key_parses();
+        let exec = make_executor();
+        assert_eq!(key_parses() - before, 1, "signer must be parsed exactly once at startup");
+
+        // Mock gönderim yolu: imzalı ham TX'ler ağ yerine bir vektöre yazılır
+        let mut sent: Vec<Vec<u8>> = Vec::new();
+        for nonce in 0..20u64 {
+            let (hash, raw) = exec
+                .prepare_signed(CONTRACT, &[0xAB; 134], nonce, 0.001, 250_000, 10_000_000, None)
+                .await
+                .unwrap();
+            let decoded = TxEnvelope::decode_2718(&mut raw.as_slice()).unwrap();
+            assert_eq!(*decoded.tx_hash(), hash);
+            assert_eq!(decoded.nonce(), nonce);
+            assert_eq!(decoded.chain_id(), Some(8453));
+            sent.push(raw);
+        }
+
+        assert_eq!(sent.len(), 20);
+        assert_eq!(key_parses() - before, 1, "per-trade path must not parse the key");
+        assert_eq!(exec.sign_latency.count(), 20);
+        assert_eq!(exec.send_latency.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_signer_address_cached() {
+        let exec = make_executor();
+        assert_eq!(exec.signer_address(), TEST_ADDR);
+        assert_eq!(exec.signing().map(|s| s.chain_id()), Some(8453));
+
+        // Key yoksa: caller sıfır adres, imzalama reddedilir
+        let bare = MevExecutor::new(None, 0.25, None, 2);
+        assert_eq!(bare.signer_address(), Address::ZERO);
+        let res = bare.prepare_signed(CONTRACT, &[], 0, 0.001, 250_000, 10_000_000, None).await;
+        assert!(matches!(res, Err(BotError::ExecutionUnavailable { reason: "no signer loaded" })));
+    }
+
+    #[tokio::test]
+    async fn test_no_private_rpc_cancels_before_signing() {
+        let ctx = SigningContext::new(TEST_KEY, 8453, None).unwrap();
+        let exec = MevExecutor::new(None, 0.25, Some(Arc::new(ctx)), 2);
+        let nm = Arc::new(NonceManager::new(0));
+        let guard = Arc::new(exec.inflight.try_acquire().unwrap());
+        let res = exec
+            .execute_protected(
+                CONTRACT, &[0xAB; 134], 0, 0.001, GasSample::planned(250_000, None, None), 10_000_000, 1, 4, "test", guard, &nm,
+                &OpportunityExpiry::default(), None, None, None,
+            )
+            .await;
+        // İptal edilen yürütme slotu geri verir
+        assert_eq!(exec.inflight.active(), 0);
+        let err = res.unwrap_err();
+        assert_eq!(err.kind(), "execution_unavailable");
+        assert!(!err.is_retryable());
+        assert!(err.to_string().contains("PRIVATE_RPC_URL"), "{}", err);
+        assert_eq!(exec.sign_latency.count(), 0);
+    }
+
+    /// Kağıt şerit bağlıyken imzalayıcı ve private RPC hazır olsa bile
+    /// hiçbir gerçek gönderim yolu çalışmaz; muhasebe kağıt defterlerine gider
+    #[tokio::test]
+    async fn test_paper_lane_touches_no_real_send_path() {
+        use crate::direction_gate::GatePolicy;
+        use crate::paper::{LatencyRange, PaperSettings};
+
+        let settings = PaperSettings { failure_rate: 0.0, drop_rate: 0.0, latency: LatencyRange { min_ms: 0, max_ms: 0 } };
+        let lane = Arc::new(PaperLane::new(settings, GatePolicy::from_config(&BotConfig::for_tests())));
+        let exec = make_executor().with_paper_lane(Some(Arc::clone(&lane)));
+        let nm = Arc::clone(&lane.nonce_manager);
+        let run = |nonce: u64, expiry: OpportunityExpiry| {
+            let guard = Arc::new(exec.inflight.try_acquire().unwrap());
+            let exec = &exec;
+            let nm = &nm;
+            async move {
+                exec.execute_protected(
+                    CONTRACT, &[0xAB; 134], nonce, 0.001, GasSample::planned(250_000, None, None), 10_000_000, 1, 4, "test", guard, nm,
+                    &expiry, None, None, None,
+                )
+                .await
+            }
+        };
+
+        let hash = run(nm.get_and_increment(), OpportunityExpiry::default()).await.unwrap();
+        assert_eq!(hash, format!("{:?}", alloy::primitives::keccak256(paper::paper_raw_tx(0, &[0xAB; 134]))));
+
+        let expired = OpportunityExpiry::default();
+        expired.expire("spread closed");
+        let err = run(nm.get_and_increment(), expired).await.unwrap_err();
+        assert_eq!(err.kind(), "execution_expired");
+
+        assert_eq!(exec.sign_latency.count(), 0, "signer must not run");
+        assert_eq!(exec.send_latency.count(), 0, "private RPC must not be called");
+        assert_eq!(*exec.trade_outcomes.lock(), TradeOutcomes::default());
+        let paper = *lane.outcomes.lock();
+        assert_eq!((paper.submitted, paper.expired), (1, 1));
+    }
+
+    #[test]
+    fn test_eip1559_strategy_pays_fixed_tip() {
+        let fifo = make_executor();
+        let eip1559 = make_executor().with_priority_fee_strategy(PriorityFeeStrategy::Eip1559Competitive);
+        let profit = 0.01;
+        let fifo_bribe = fifo.compute_dynamic_bribe(profit, 250_000, 10_000_000, None);
+        let tip = eip1559.compute_dynamic_bribe(profit, 250_000, 10_000_000, None);
+        assert!(fifo_bribe.effective_pct >= 0.10);
+        assert_eq!(tip.priority_fee_per_gas, STANDARD_PRIORITY_FEE_WEI);
+        assert!(tip.bribe_wei < fifo_bribe.bribe_wei / 100);
+        assert!(tip.effective_pct < 0.01);
+    }
+
+    /// Kırılgan fırsat (düşük sağlamlık) daha yüksek bribe öder; skor 1.0 ya da
+    /// ölçülmemiş fırsat kademe yüzdesinde kalır, tavan ve EIP-1559 bahşişi değişmez
+    #[test]
+    fn test_fragile_opportunity_raises_bribe() {
+        let exec = make_executor();
+        let profit = 0.01;
+        let pct = |robustness: Option<f64>| {
+            exec.compute_dynamic_bribe(profit, 250_000, 10_000_000, robustness).effective_pct
+        };
+        // Marj ~4000x → kademe = base_bribe_pct (0.25)
+        assert!((pct(None) - 0.25).abs() < 1e-9);
+        assert!((pct(Some(1.0)) - 0.25).abs() < 1e-9);
+        assert!((pct(Some(0.5)) - 0.35).abs() < 1e-9);
+        assert!((pct(Some(0.0)) - 0.45).abs() < 1e-9);
+        assert!(pct(Some(0.0)) > pct(Some(0.5)) && pct(Some(0.5)) > pct(Some(1.0)));
+
+        // Minimum mutlak kâr koruması eklemeden sonra da geçerli:
+        // 0.00001 - gas 0.0000025 - 0.000005 → en fazla %25 (kademe %40 + %20)
+        let thin = exec.compute_dynamic_bribe(0.00001, 250_000, 10_000_000, Some(0.0));
+        assert!((thin.effective_pct - 0.25).abs() < 1e-6, "{}", thin.effective_pct);
+
+        // EIP-1559: sıralama bahşişe bağlı değil — kırılganlık bahşişi değiştirmez
+        let eip1559 = make_executor().with_priority_fee_strategy(PriorityFeeStrategy::Eip1559Competitive);
+        assert_eq!(
+            eip1559.compute_dynamic_bribe(profit, 250_000, 10_000_000, Some(0.0)).bribe_wei,
+            eip1559.compute_dynamic_bribe(profit, 250_000, 10_000_000, None).bribe_wei,
+        );
+    }
+
+    #[test]
+    fn test_invalid_key_rejected() {
+        assert!(SigningContext::new("not-a-key", 8453, None).is_err());
+    }
+
+    #[test]
+    fn test_latency_histogram_summary() {
+        let h = LatencyHistogram::default();
+        assert_eq!(h.summary(), (0, 0, 0, 0));
+        for us in [50, 80, 300, 2_000, 250_000] {
+            h.record(us);
+        }
+        let (count, avg, p50, max) = h.summary();
+        assert_eq!(count, 5);
+        assert_eq!(avg, (50 + 80 + 300 + 2_000 + 250_000) / 5);
+        assert_eq!(p50, 500); // 3. örnek ≤500µs kovasında
+        assert_eq!(max, 250_000);
+    }
+
+    /// Geçici landed sonuç reorg'da kaybolur, TX başka blokta daha pahalı
+    /// gas ile revert olarak dahil edilir: sayaçlar ve PnL düzeltme kaydıyla döner
+    #[test]
+    fn test_trade_outcomes_reorg_corrections() {
+        let landed = Inclusion {
+            landed: true,
+            gas_used: 200_000,
+            gas_spent_wei: 200_000_000_000_000,
+            block: 100,
+            block_hash: B256::with_last_byte(1),
+        };
+        let reverted = Inclusion {
+            landed: false,
+            gas_used: 300_000,
+            gas_spent_wei: 300_000_000_000_000,
+            block: 103,
+            block_hash: B256::with_last_byte(2),
+        };
+        let tx_hash = B256::with_last_byte(0x77);
+        let mut o = TradeOutcomes { submitted: 1, ..Default::default() };
+        o.record_receipt(true, landed.gas_spent_wei, 0.004);
+        o.provisional += 1;
+        assert!((o.realized_pnl_weth - 0.0038).abs() < 1e-12);
+
+        let gone = Correction { tx_hash, expected_profit_weth: 0.004, before: Some(landed), after: None };
+        o.apply_correction(&gone);
+        assert_eq!((o.landed, o.reverted, o.gas_spent_wei, o.reorged), (0, 0, 0, 1));
+        assert!(o.realized_pnl_weth.abs() < 1e-12);
+
+        let back = Correction { tx_hash, expected_profit_weth: 0.004, before: None, after: Some(reverted) };
+        o.apply_correction(&back);
+        o.record_finality(Some(Finality::Final));
+        assert_eq!((o.landed, o.reverted, o.gas_spent_wei), (0, 1, 300_000_000_000_000));
+        assert!((o.realized_pnl_weth + 0.0003).abs() < 1e-12);
+        assert_eq!((o.corrections, o.reorged, o.provisional, o.finalized), (2, 1, 0, 1));
+        assert!((o.correction_pnl_weth + 0.0041).abs() < 1e-12);
+        assert_eq!(
+            o.confirmation_line().unwrap(),
+            "1 final / 0 provisional | 1 reorged (0 vanished) | 2 corrections -0.004100 WETH"
+        );
+        assert_eq!(TradeOutcomes::default().confirmation_line(), None);
+    }
+}
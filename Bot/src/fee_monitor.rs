@@ -0,0 +1,273 @@
+// ============================================================================
+//  FEE_MONITOR v1.0 — Ücret Rejimi Doğrulaması (Aave Premium + Havuz fee())
+//
+//  FLASH_LOAN_FEE_BPS ve havuz fee'leri bir kez yapılandırılır; ancak Aave
+//  flash loan primini değiştirebilir, havuzlar taşınabilir. Gerçek ücret
+//  yükselirse kâr modeli her fırsatı sessizce olduğundan kârlı görür.
+//
+//  Saatte bir:
+//  ✓ Aave V3 Pool.FLASHLOAN_PREMIUM_TOTAL() okunur, config ile karşılaştırılır
+//  ✓ Her havuzun fee() değeri okunur, PoolConfig::fee_bps() ile karşılaştırılır
+//  ✓ Uyuşmazlıkta AUTO_UPDATE_FEES=true ise yürürlükteki değer güncellenir,
+//    aksi halde yüksek sesle uyarılır (konsol + Telegram) ve config korunur
+//
+//  Yürürlükteki değerler lock-free atomiklerde tutulur (GLOBAL_L1_FEE deseni),
+//  hot path (strategy / state_sync) RPC beklemeden okur.
+// ============================================================================
+
+use alloy::primitives::{address, Address, Bytes};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use colored::*;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::state_sync::read_pool_fee_bps;
+use crate::telegram::{TelegramMessage, TelegramSender};
+use crate::types::{BotConfig, PoolConfig};
+
+/// Aave V3 Pool (Base mainnet proxy)
+pub const DEFAULT_AAVE_V3_POOL: Address = address!("A238Dd80C259a72e81d7e4664a9801593F98d1c5");
+
+/// Doğrulama aralığı (saniye) — ücret rejimi değişiklikleri nadirdir
+pub const FEE_CHECK_INTERVAL_SECS: u64 = 3600;
+
+/// İki ücret değeri bu toleranstan yakınsa eşit sayılır (bps)
+const FEE_EPSILON_BPS: f64 = 1e-9;
+
+sol! {
+    interface IAaveV3Pool {
+        /// Toplam flash loan primi (1e4 = %100 → değer doğrudan bps'dir)
+        function FLASHLOAN_PREMIUM_TOTAL() external view returns (uint128);
+    }
+}
+
+/// Kâr modelinin kullandığı flash loan ücreti (f64 bps, `to_bits` ile saklanır)
+static EFFECTIVE_FLASH_FEE_BITS: AtomicU64 = AtomicU64::new(0);
+/// AUTO_UPDATE_FEES bayrağı (state_sync hot path'i config taşımaz)
+static AUTO_UPDATE_FEES: AtomicBool = AtomicBool::new(false);
+/// Tespit edilen toplam ücret uyuşmazlığı sayısı (oturum boyunca)
+static FEE_DRIFT_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+/// Başlangıç değerlerini config'den yükle (main, run_bot öncesi).
+pub fn init(config: &BotConfig) {
+    EFFECTIVE_FLASH_FEE_BITS.store(config.flash_loan_fee_bps.to_bits(), Ordering::Relaxed);
+    AUTO_UPDATE_FEES.store(config.auto_update_fees, Ordering::Relaxed);
+}
+
+/// Kâr modelinde yürürlükteki flash loan ücreti (bps)
+pub fn effective_flash_loan_fee_bps() -> f64 {
+    f64::from_bits(EFFECTIVE_FLASH_FEE_BITS.load(Ordering::Relaxed))
+}
+
+/// AUTO_UPDATE_FEES aktif mi?
+pub fn auto_update_enabled() -> bool {
+    AUTO_UPDATE_FEES.load(Ordering::Relaxed)
+}
+
+/// Oturum boyunca tespit edilen ücret uyuşmazlığı sayısı
+pub fn fee_drift_events() -> u64 {
+    FEE_DRIFT_EVENTS.load(Ordering::Relaxed)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Karşılaştırma / Güncelleme Mantığı (saf fonksiyonlar)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek bir ücret kontrolünün sonucu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FeeCheck {
+    /// Zincirdeki değer yürürlükteki değerle aynı
+    Match,
+    /// AUTO_UPDATE_FEES=true — yürürlükteki değer zincirdekiyle değiştirildi
+    Updated { previous_bps: f64, on_chain_bps: f64 },
+    /// AUTO_UPDATE_FEES=false — uyarı verildi, yapılandırılan değer korundu
+    Mismatch { configured_bps: f64, on_chain_bps: f64 },
+}
+
+/// Yürürlükteki ücreti zincirdeki değerle karşılaştır.
+pub fn reconcile_fee(in_force_bps: f64, on_chain_bps: f64, auto_update: bool) -> FeeCheck {
+    if (in_force_bps - on_chain_bps).abs() <= FEE_EPSILON_BPS {
+        FeeCheck::Match
+    } else if auto_update {
+        FeeCheck::Updated { previous_bps: in_force_bps, on_chain_bps }
+    } else {
+        FeeCheck::Mismatch { configured_bps: in_force_bps, on_chain_bps }
+    }
+}
+
+/// Blok bazlı okunan canlı havuz fee'sinin modele girip girmeyeceğine karar ver.
+///
+/// `None` dönerse strateji `PoolConfig::fee_fraction()`'a düşer (yapılandırılan
+/// değer korunur). Eşleşen canlı değer her zaman kabul edilir.
+pub fn gate_live_fee(configured_bps: u32, live_bps: Option<u32>) -> Option<u32> {
+    select_pool_fee(configured_bps, live_bps, auto_update_enabled())
+}
+
+fn select_pool_fee(configured_bps: u32, live_bps: Option<u32>, auto_update: bool) -> Option<u32> {
+    match live_bps {
+        Some(live) if live == configured_bps || auto_update => Some(live),
+        _ => None,
+    }
+}
+
+/// FLASHLOAN_PREMIUM_TOTAL() eth_call dönüşünü decode et (uint128 → bps).
+pub fn decode_premium_total(data: &[u8]) -> Option<f64> {
+    IAaveV3Pool::FLASHLOAN_PREMIUM_TOTALCall::abi_decode_returns(data)
+        .ok()
+        .map(|premium| premium as f64)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Periyodik On-Chain Doğrulama
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Aave V3 Pool'dan güncel flash loan primini oku (bps).
+async fn read_flash_premium_bps<P: Provider + Sync>(provider: &P, aave_pool: Address) -> Option<f64> {
+    let calldata = IAaveV3Pool::FLASHLOAN_PREMIUM_TOTALCall {}.abi_encode();
+    let tx = TransactionRequest::default()
+        .to(aave_pool)
+        .input(Bytes::copy_from_slice(&calldata).into());
+
+    match provider.call(tx).await {
+        Ok(raw) => decode_premium_total(&raw),
+        Err(e) => {
+            eprintln!("  ⚠️ [FeeMonitor] FLASHLOAN_PREMIUM_TOTAL read failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Ücret uyuşmazlığını konsola ve Telegram'a raporla.
+fn report_drift(source: &str, check: FeeCheck, telegram: &Option<TelegramSender>) {
+    let (in_force_bps, on_chain_bps, auto_updated) = match check {
+        FeeCheck::Match => return,
+        FeeCheck::Updated { previous_bps, on_chain_bps } => (previous_bps, on_chain_bps, true),
+        FeeCheck::Mismatch { configured_bps, on_chain_bps } => (configured_bps, on_chain_bps, false),
+    };
+    FEE_DRIFT_EVENTS.fetch_add(1, Ordering::Relaxed);
+
+    if auto_updated {
+        eprintln!(
+            "  {} [FeeMonitor] {} fee changed on-chain: {:.2} → {:.2} bps — profit model UPDATED",
+            "🔁".yellow(),
+            source,
+            in_force_bps,
+            on_chain_bps,
+        );
+    } else {
+        eprintln!(
+            "  {} {}",
+            "🚨".red(),
+            format!(
+                "[FeeMonitor] {} FEE MISMATCH: configured {:.2} bps ≠ on-chain {:.2} bps — keeping configured value (AUTO_UPDATE_FEES=false), profit model may be WRONG",
+                source, in_force_bps, on_chain_bps,
+            )
+            .red()
+            .bold()
+        );
+    }
+
+    if let Some(tg) = telegram {
+        tg.send(TelegramMessage::FeeDrift {
+            source: source.to_string(),
+            in_force_bps,
+            on_chain_bps,
+            auto_updated,
+        });
+    }
+}
+
+/// Tek doğrulama turu: Aave primi + tüm havuzların fee() değeri.
+pub async fn run_fee_check<P: Provider + Sync>(
+    provider: &P,
+    aave_pool: Address,
+    pools: &[PoolConfig],
+    telegram: &Option<TelegramSender>,
+) {
+    let auto_update = auto_update_enabled();
+
+    if let Some(on_chain) = read_flash_premium_bps(provider, aave_pool).await {
+        let check = reconcile_fee(effective_flash_loan_fee_bps(), on_chain, auto_update);
+        if let FeeCheck::Updated { on_chain_bps, .. } = check {
+            EFFECTIVE_FLASH_FEE_BITS.store(on_chain_bps.to_bits(), Ordering::Relaxed);
+        }
+        report_drift("Aave V3 flash loan", check, telegram);
+    }
+
+    // Havuz fee'leri: canlı değer her blokta state_sync'te okunur ve
+    // gate_live_fee() ile modele alınır — burada yalnızca uyuşmazlık raporlanır.
+    for pool in pools {
+        if let Some(on_chain) = read_pool_fee_bps(provider, pool).await {
+            let check = reconcile_fee(pool.fee_bps() as f64, on_chain as f64, auto_update);
+            report_drift(&pool.name, check, telegram);
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    #[test]
+    fn test_reconcile_match_within_epsilon() {
+        assert_eq!(reconcile_fee(5.0, 5.0, false), FeeCheck::Match);
+        assert_eq!(reconcile_fee(5.0, 5.0 + 1e-12, true), FeeCheck::Match);
+    }
+
+    #[test]
+    fn test_reconcile_auto_update_replaces_value() {
+        assert_eq!(
+            reconcile_fee(0.0, 5.0, true),
+            FeeCheck::Updated { previous_bps: 0.0, on_chain_bps: 5.0 }
+        );
+    }
+
+    #[test]
+    fn test_reconcile_without_auto_update_keeps_configured() {
+        assert_eq!(
+            reconcile_fee(5.0, 9.0, false),
+            FeeCheck::Mismatch { configured_bps: 5.0, on_chain_bps: 9.0 }
+        );
+    }
+
+    #[test]
+    fn test_select_pool_fee_gating() {
+        // Eşleşen canlı değer her zaman kabul edilir
+        assert_eq!(select_pool_fee(5, Some(5), false), Some(5));
+        // Uyuşmazlık + auto_update kapalı → config'e düş
+        assert_eq!(select_pool_fee(5, Some(30), false), None);
+        // Uyuşmazlık + auto_update açık → canlı değer
+        assert_eq!(select_pool_fee(5, Some(30), true), Some(30));
+        // Okuma başarısız → config'e düş
+        assert_eq!(select_pool_fee(5, None, true), None);
+    }
+
+    #[test]
+    fn test_decode_premium_total() {
+        // uint128 = 5 (Aave V3 Base: %0.05) — 32 byte sağ hizalı ABI word
+        let data = U256::from(5u64).to_be_bytes::<32>();
+        assert_eq!(decode_premium_total(&data), Some(5.0));
+
+        let data = U256::from(9u64).to_be_bytes::<32>();
+        assert_eq!(decode_premium_total(&data), Some(9.0));
+    }
+
+    #[test]
+    fn test_decode_premium_total_rejects_short_data() {
+        assert_eq!(decode_premium_total(&[]), None);
+        assert_eq!(decode_premium_total(&[0u8; 16]), None);
+    }
+
+    #[test]
+    fn test_premium_selector() {
+        // keccak256("FLASHLOAN_PREMIUM_TOTAL()")[..4] = 0x074b2e43
+        assert_eq!(IAaveV3Pool::FLASHLOAN_PREMIUM_TOTALCall::SELECTOR, [0x07, 0x4b, 0x2e, 0x43]);
+    }
+}
@@ -0,0 +1,256 @@
+// ============================================================================
+//  FOLLOWUP v1.0 — İki Adımlı Planın İkinci Adımı (EXECUTE_FOLLOWUP)
+//
+//  Tek seferlik optimum bir tavana dayandığında kalan spread ikinci bir
+//  işlemi taşıyabilir (strategy::evaluate_two_step_plan). İkinci adım ilk
+//  TX zincire girmeden gönderilirse aynı spread iki kez sayılır.
+//
+//  ✓ 1. adım yürütülünce combo + yön + plan kuyruğa alınır (tek takip)
+//  ✓ Receipt hattı sonucu işler: status=1 → hazır, revert → iptal
+//  ✓ Hazır olana kadar combo'nun yeni fırsatları yürütülmez
+//  ✓ Ana döngü hazır takibi sonraki blokta tüketir: 2. adım yalnızca taze
+//    state'teki fırsat kontrolü aynı yönü hâlâ destekliyorsa yürütülür
+//  ✓ FOLLOWUP_MAX_WAIT_BLOCKS içinde receipt gelmezse takip düşer
+//
+//  BOOK kilidi receipt görevinin yazdığı 1. adım sonucunu ana döngünün
+//  awaiting / take_ready okumalarıyla sıralar — hazır takip tek kez tüketilir.
+//  EXECUTE_FOLLOWUP kapalıyken defter kurulmaz, tüm çağrılar no-op.
+// ============================================================================
+
+use colored::*;
+use parking_lot::Mutex;
+
+use crate::direction_gate::{Direction, DirectionKey};
+use crate::types::{BotConfig, TwoStepPlan};
+
+/// Receipt bu kadar blokta onaylanmazsa takip düşer (receipt görevi 10s bekler)
+const FOLLOWUP_MAX_WAIT_BLOCKS: u64 = 10;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Takip Defteri
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// 1. adımı gönderilmiş, receipt bekleyen ya da onaylanmış takip
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingFollowup {
+    /// Fırsatın combo indeksi (pair_combos)
+    pub combo_idx: usize,
+    pub direction: Direction,
+    pub plan: TwoStepPlan,
+    /// 1. adımın yürütüldüğü blok
+    pub armed_block: u64,
+    /// 1. adımın receipt'i status=1 ile geldi mi?
+    confirmed: bool,
+}
+
+/// Defterin döndürdüğü, loglanacak olay
+#[derive(Debug, Clone, PartialEq)]
+enum FollowupEvent {
+    /// 1. adım revert etti — takip iptal
+    Cancelled(String),
+    /// Receipt FOLLOWUP_MAX_WAIT_BLOCKS içinde gelmedi — takip düştü
+    Expired(String),
+}
+
+#[derive(Debug, Default)]
+struct FollowupBook {
+    pending: Option<PendingFollowup>,
+    armed: u64,
+    confirmed: u64,
+    cancelled: u64,
+    expired: u64,
+    fired: u64,
+    unsupported: u64,
+}
+
+impl FollowupBook {
+    /// Yeni takip öncekinin yerine geçer (önceki hazır değilse düşmüş sayılır)
+    fn arm(&mut self, combo_idx: usize, direction: Direction, plan: TwoStepPlan, block: u64) {
+        if self.pending.take().is_some() {
+            self.expired += 1;
+        }
+        self.armed += 1;
+        self.pending = Some(PendingFollowup { combo_idx, direction, plan, armed_block: block, confirmed: false });
+    }
+
+    fn record_receipt(&mut self, key: &DirectionKey, landed: bool) -> Option<FollowupEvent> {
+        let pending = self.pending.as_mut().filter(|p| !p.confirmed && p.direction.key == *key)?;
+        if landed {
+            pending.confirmed = true;
+            self.confirmed += 1;
+            return None;
+        }
+        let label = pending.direction.label.clone();
+        self.pending = None;
+        self.cancelled += 1;
+        Some(FollowupEvent::Cancelled(label))
+    }
+
+    fn awaiting(&self, combo_idx: usize) -> bool {
+        self.pending.as_ref().is_some_and(|p| p.combo_idx == combo_idx && !p.confirmed)
+    }
+
+    fn take_ready(&mut self, block: u64) -> (Option<PendingFollowup>, Option<FollowupEvent>) {
+        match self.pending.as_ref() {
+            Some(p) if p.confirmed => (self.pending.take(), None),
+            Some(p) if block > p.armed_block + FOLLOWUP_MAX_WAIT_BLOCKS => {
+                let label = p.direction.label.clone();
+                self.pending = None;
+                self.expired += 1;
+                (None, Some(FollowupEvent::Expired(label)))
+            }
+            _ => (None, None),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Defter
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// init öncesi None — takip kurulmaz
+static BOOK: Mutex<Option<FollowupBook>> = parking_lot::const_mutex(None);
+
+/// EXECUTE_FOLLOWUP açıksa defteri kur (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    if !config.execute_followup {
+        return;
+    }
+    if !config.two_step_sizing {
+        eprintln!(
+            "  {} [Followup] EXECUTE_FOLLOWUP=true but TWO_STEP_SIZING=false — no plan, no step 2",
+            "⚠️".yellow(),
+        );
+    }
+    *BOOK.lock() = Some(FollowupBook::default());
+}
+
+fn report(event: &FollowupEvent) {
+    let (label, reason) = match event {
+        FollowupEvent::Cancelled(label) => (label, "step 1 reverted"),
+        FollowupEvent::Expired(label) => (label, "no receipt in time"),
+    };
+    eprintln!("  {} [Followup] {} step 2 dropped: {}", "↩️".yellow(), label, reason);
+}
+
+/// 1. adım yürütüldü: 2. adım receipt onayına kadar bekler
+pub fn arm(combo_idx: usize, direction: Direction, plan: TwoStepPlan, block: u64) {
+    if let Some(book) = BOOK.lock().as_mut() {
+        book.arm(combo_idx, direction, plan, block);
+    }
+}
+
+/// Receipt görevinden: bekleyen takibin 1. adımı dahil edildi mi?
+pub fn record_receipt(direction: &Direction, landed: bool) {
+    let event = BOOK.lock().as_mut().and_then(|b| b.record_receipt(&direction.key, landed));
+    if let Some(event) = event {
+        report(&event);
+    }
+}
+
+/// Combo'nun takibi receipt bekliyor mu? (bekliyorsa yeni fırsat yürütülmez)
+pub fn awaiting(combo_idx: usize) -> bool {
+    BOOK.lock().as_ref().is_some_and(|b| b.awaiting(combo_idx))
+}
+
+/// Her blok: receipt'i onaylanmış takibi tüket, süresi dolanı düşür
+pub fn take_ready(block: u64) -> Option<PendingFollowup> {
+    let (ready, event) = BOOK.lock().as_mut().map(|b| b.take_ready(block)).unwrap_or_default();
+    if let Some(event) = event {
+        report(&event);
+    }
+    ready
+}
+
+/// Tüketilen takibin sonucu: taze kontrol destekledi (yürütüldü) ya da desteklemedi
+pub fn record_outcome(fired: bool) {
+    if let Some(book) = BOOK.lock().as_mut() {
+        if fired {
+            book.fired += 1;
+        } else {
+            book.unsupported += 1;
+        }
+    }
+}
+
+/// İstatistik kutusu satırı — EXECUTE_FOLLOWUP kapalıysa None
+pub fn stats_line() -> Option<String> {
+    BOOK.lock().as_ref().map(|b| {
+        format!(
+            "armed {} | confirmed {} | fired {} | unsupported {} | reverted {} | expired {}",
+            b.armed, b.confirmed, b.fired, b.unsupported, b.cancelled, b.expired,
+        )
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+
+    fn direction(buy: u8, sell: u8) -> Direction {
+        Direction {
+            key: DirectionKey { buy_pool: Address::repeat_byte(buy), sell_pool: Address::repeat_byte(sell) },
+            label: format!("{buy} → {sell}"),
+        }
+    }
+
+    fn plan() -> TwoStepPlan {
+        TwoStepPlan {
+            first_amount_weth: 2.0,
+            first_profit_weth: 0.003,
+            second_amount_weth: 2.0,
+            second_profit_weth: 0.001,
+        }
+    }
+
+    /// Receipt onayına kadar combo bekler; onaylı takip bir kez tüketilir
+    #[test]
+    fn test_step_two_waits_for_confirmed_receipt() {
+        let mut book = FollowupBook::default();
+        book.arm(3, direction(1, 2), plan(), 100);
+        assert!(book.awaiting(3));
+        assert!(!book.awaiting(4));
+        assert_eq!(book.take_ready(101), (None, None));
+
+        // Başka yönün receipt'i takibi etkilemez
+        assert_eq!(book.record_receipt(&direction(2, 1).key, true), None);
+        assert!(book.awaiting(3));
+
+        assert_eq!(book.record_receipt(&direction(1, 2).key, true), None);
+        assert!(!book.awaiting(3));
+        let (ready, event) = book.take_ready(102);
+        let ready = ready.expect("confirmed follow-up must be ready");
+        assert_eq!((ready.combo_idx, ready.plan, ready.armed_block), (3, plan(), 100));
+        assert_eq!(event, None);
+        assert_eq!(book.take_ready(103), (None, None));
+        assert_eq!((book.armed, book.confirmed), (1, 1));
+    }
+
+    /// Revert eden 1. adım takibi iptal eder; receipt gelmezse süre dolunca düşer
+    #[test]
+    fn test_reverted_or_missing_receipt_drops_step_two() {
+        let mut book = FollowupBook::default();
+        book.arm(0, direction(1, 2), plan(), 100);
+        assert_eq!(
+            book.record_receipt(&direction(1, 2).key, false),
+            Some(FollowupEvent::Cancelled("1 → 2".into())),
+        );
+        assert!(!book.awaiting(0));
+        assert_eq!(book.take_ready(101), (None, None));
+
+        book.arm(0, direction(1, 2), plan(), 200);
+        assert_eq!(book.take_ready(200 + FOLLOWUP_MAX_WAIT_BLOCKS), (None, None));
+        assert!(book.awaiting(0));
+        assert_eq!(
+            book.take_ready(201 + FOLLOWUP_MAX_WAIT_BLOCKS),
+            (None, Some(FollowupEvent::Expired("1 → 2".into()))),
+        );
+        assert!(!book.awaiting(0));
+        assert_eq!((book.armed, book.cancelled, book.expired), (2, 1, 1));
+    }
+}
@@ -0,0 +1,788 @@
+// ============================================================================
+//  GAS_CALIBRATION v1.0 — Gas Limiti Tamponunun Receipt'lerle Kalibrasyonu
+//
+//  Gas limiti REVM ölçümünün sabit %10 fazlasıydı; bu tampon bir tahmindir.
+//  Dar kalırsa TX out-of-gas ile revert eder, genelde ise geniştir: fazla
+//  rezerve edilen gas, gas başına bribe hesabını ve max fee'yi şişirir.
+//
+//  Her canlı işlem için:
+//  ✓ REVM gas'ı, tamponlu limit ve receipt'in gerçek gas_used'ı kaydedilir
+//  ✓ Yön başına kayan pencere: actual / simulated oranları (son RATIO_WINDOW)
+//  ✓ İstatistik kutusunda yön başına oranın p95'i
+//  ✓ GAS_BUFFER_MODE=auto: en az MIN_CALIBRATION_SAMPLES işlem varsa
+//    tampon = en kötü yönün p95'i × güvenlik payı, [1.02, 1.5] aralığında
+//  ✓ Defter gas_ledger.json'a atomik yazılır — öğrenilen tampon yeniden
+//    başlatmalarda korunur
+//  ✓ Kontrat / havuz bytecode'u değişince (bytecode_watchdog) defter
+//    sıfırlanır — eski koda ait oranlar yeni kodun gas'ını temsil etmez
+//
+//  Boyutlandırma gas modeli (tick geçişi bileşimi):
+//  ✓ gas = taban (flash swap + iki swap'ın geçişsiz maliyeti)
+//          + geçiş başına gas × (sat + al bacağının geçtiği tick sayısı)
+//  ✓ Önsel GAS_BASE_OVERHEAD / GAS_PER_TICK_CROSSING; GAS_CROSSING_CALIBRATION
+//    açıkken katsayılar REVM ölçümlerinden (bellek içi) ve receipt'lerden
+//    (defterde) ağırlıklı en küçük kareler ile oturtulur
+//  ✓ Her canlı işlem trades.json'a geçiş sayıları + modellenen / gerçek
+//    gas ile eklenir
+//
+//  Executor hot path'i config taşımaz: durum süreç geneli tek bir kilitte
+//  (fee_monitor deseni), main run_bot öncesi init çağırır.
+// ============================================================================
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use crate::math::TickCrossings;
+use crate::types::BotConfig;
+
+/// Kalibrasyon defteri (çalışma dizininde)
+pub const GAS_LEDGER_PATH: &str = "gas_ledger.json";
+
+/// Canlı işlem gas kaydı (JSONL, işlem başına bir satır)
+pub const TRADES_LOG_PATH: &str = "trades.json";
+
+/// GAS_BUFFER_MODE=fixed tamponu (ve auto modda yeterli örnek yokken)
+pub const FIXED_GAS_BUFFER: f64 = 1.10;
+
+/// Tampon ne olursa olsun gas limiti bunun altına inmez
+pub const MIN_GAS_LIMIT: u64 = 100_000;
+
+/// Multi-hop rotaların defter anahtarı
+pub const MULTI_HOP_ROUTE: &str = "multi_hop";
+
+/// Öğrenilen tamponun kırpıldığı aralık
+const AUTO_BUFFER_MIN: f64 = 1.02;
+const AUTO_BUFFER_MAX: f64 = 1.5;
+
+/// p95 oranının üzerine eklenen güvenlik payı
+const AUTO_BUFFER_SAFETY: f64 = 1.05;
+
+/// Yön başına tutulan son örnek sayısı
+const RATIO_WINDOW: usize = 200;
+
+/// Geçiş modeli için tutulan son (geçiş, gas) örnek sayısı
+const CROSSING_WINDOW: usize = 400;
+
+/// Receipt örneğinin REVM ölçümüne göre ağırlığı — gerçek gas daha güvenilir
+const RECEIPT_WEIGHT: f64 = 4.0;
+
+/// Gas limiti tamponu seçimi (GAS_BUFFER_MODE)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasBufferMode {
+    /// Her zaman FIXED_GAS_BUFFER
+    Fixed,
+    /// Receipt'lerden öğrenilen tampon (yeterli örnek yoksa FIXED_GAS_BUFFER)
+    Auto,
+}
+
+impl std::fmt::Display for GasBufferMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GasBufferMode::Fixed => write!(f, "fixed"),
+            GasBufferMode::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// İkili arbitrajın defter anahtarı — UniV3 bacağının swap yönü
+pub fn pair_route(uni_direction: u8) -> &'static str {
+    if uni_direction == 0 {
+        "pair_zero_for_one"
+    } else {
+        "pair_one_for_zero"
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Geçiş Modeli
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Swap yolunun gas bileşimi: sabit ek yük + tick geçişi başına gas
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CrossingGasModel {
+    /// Flash swap + iki swap'ın geçişsiz gas'ı
+    pub base_overhead: f64,
+    /// Tick geçişi başına ek gas
+    pub per_crossing: f64,
+}
+
+impl CrossingGasModel {
+    /// Config yokken (init öncesi) kullanılan önsel
+    pub const PRIOR: Self = Self { base_overhead: 160_000.0, per_crossing: 20_000.0 };
+
+    pub fn from_config(config: &BotConfig) -> Self {
+        Self {
+            base_overhead: config.gas_base_overhead as f64,
+            per_crossing: config.gas_per_tick_crossing as f64,
+        }
+    }
+
+    /// `crossings` tick geçişli swap yolunun gas'ı
+    pub fn estimate(&self, crossings: u32) -> u64 {
+        (self.base_overhead + self.per_crossing * crossings as f64).round() as u64
+    }
+
+    /// Ağırlıklı en küçük kareler (receipt ×RECEIPT_WEIGHT). Geçiş sayısı
+    /// hiç değişmiyorsa eğim belirlenemez: önselin eğimi korunur, yalnızca
+    /// taban oturtulur. Katsayılar negatif olamaz.
+    pub fn fit(samples: impl IntoIterator<Item = CrossingSample>, prior: Self) -> Option<Self> {
+        let (mut sw, mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for sample in samples {
+            let w = sample.source.weight();
+            let (x, y) = (sample.crossings as f64, sample.gas as f64);
+            sw += w;
+            sx += w * x;
+            sy += w * y;
+            sxx += w * x * x;
+            sxy += w * x * y;
+        }
+        if sw <= 0.0 {
+            return None;
+        }
+        let (mean_x, mean_y) = (sx / sw, sy / sw);
+        let var_x = sxx / sw - mean_x * mean_x;
+        let per_crossing = if var_x > 1e-9 {
+            ((sxy / sw - mean_x * mean_y) / var_x).max(0.0)
+        } else {
+            prior.per_crossing
+        };
+        Some(Self { base_overhead: (mean_y - per_crossing * mean_x).max(0.0), per_crossing })
+    }
+}
+
+/// Geçiş örneğinin kaynağı
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasSource {
+    /// REVM ölçümü (bellek içi)
+    Simulation,
+    /// Receipt'teki gerçek gas_used (defterde)
+    Receipt,
+}
+
+impl GasSource {
+    fn weight(self) -> f64 {
+        match self {
+            GasSource::Simulation => 1.0,
+            GasSource::Receipt => RECEIPT_WEIGHT,
+        }
+    }
+}
+
+/// Tek (geçiş sayısı, gas) gözlemi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossingSample {
+    /// İki bacağın toplam tick geçişi
+    pub crossings: u32,
+    pub gas: u64,
+    pub source: GasSource,
+}
+
+/// Boyutlandırmada kullanılan gas tahmini
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GasEstimate {
+    /// Geçiş modeli — gas boyuta (geçiş sayısına) bağlı
+    Crossing(CrossingGasModel),
+    /// Son REVM ölçümü — model oturana kadar boyuttan bağımsız
+    Flat(u64),
+}
+
+impl GasEstimate {
+    /// `crossings` tick geçişli yolun gas'ı
+    pub fn units(&self, crossings: u32) -> u64 {
+        match self {
+            GasEstimate::Crossing(model) => model.estimate(crossings),
+            GasEstimate::Flat(gas) => *gas,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Defter
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek canlı işlemin gas kaydı
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSample {
+    /// REVM'in ölçtüğü gas
+    pub simulated: u64,
+    /// TX'e yazılan tamponlu gas limiti
+    pub limit: u64,
+    /// Receipt'teki gerçek gas_used
+    pub actual: u64,
+    /// Optimum boyutta bacakların tick geçişleri (multi-hop: None)
+    #[serde(default)]
+    pub crossings: Option<TickCrossings>,
+    /// Geçiş modelinin optimum boyuttaki gas tahmini
+    #[serde(default)]
+    pub modeled: Option<u64>,
+}
+
+impl GasSample {
+    /// Gönderim öncesi kayıt: limit yürürlükteki tampondan, actual receipt'te
+    pub fn planned(simulated: u64, crossings: Option<TickCrossings>, modeled: Option<u64>) -> Self {
+        Self { simulated, limit: gas_limit(simulated), actual: 0, crossings, modeled }
+    }
+
+    /// Gerçek / simüle gas oranı
+    pub fn ratio(&self) -> f64 {
+        self.actual as f64 / self.simulated as f64
+    }
+}
+
+/// gas_ledger.json içeriği
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GasLedger {
+    /// Rota anahtarı → son RATIO_WINDOW örnek (eskiden yeniye)
+    pub routes: BTreeMap<String, VecDeque<GasSample>>,
+    /// Kaydedilen toplam canlı işlem (pencereden düşenler dahil)
+    pub total_samples: u64,
+    /// Öğrenilen tampon — MIN_CALIBRATION_SAMPLES dolana kadar None
+    pub learned_buffer: Option<f64>,
+    /// Geçiş modeli örnekleri (son CROSSING_WINDOW, eskiden yeniye) —
+    /// diske yalnızca receipt örnekleri yazılır
+    #[serde(default, skip_serializing_if = "VecDeque::is_empty")]
+    pub crossing_samples: VecDeque<CrossingSample>,
+    /// Oturtulmuş geçiş modeli — MIN_CALIBRATION_SAMPLES dolana kadar None
+    #[serde(default)]
+    pub crossing_fit: Option<CrossingGasModel>,
+}
+
+impl GasLedger {
+    /// Örneği rotanın penceresine ekle; pencere dolarsa en eski düşer.
+    /// REVM ölçümü olmayan (simulated=0) işlemler oran üretmez, atlanır.
+    pub fn record(&mut self, route: &str, sample: GasSample) {
+        if sample.simulated == 0 {
+            return;
+        }
+        let window = self.routes.entry(route.to_string()).or_default();
+        window.push_back(sample);
+        while window.len() > RATIO_WINDOW {
+            window.pop_front();
+        }
+        self.total_samples += 1;
+    }
+
+    /// Geçiş örneğini pencereye ekle; pencere dolarsa en eski düşer
+    pub fn record_crossing(&mut self, sample: CrossingSample) {
+        self.crossing_samples.push_back(sample);
+        while self.crossing_samples.len() > CROSSING_WINDOW {
+            self.crossing_samples.pop_front();
+        }
+    }
+
+    /// En az `min_samples` geçiş örneği varsa model oturt
+    pub fn crossing_model(&self, min_samples: u32, prior: CrossingGasModel) -> Option<CrossingGasModel> {
+        if self.crossing_samples.len() < min_samples.max(1) as usize {
+            return None;
+        }
+        CrossingGasModel::fit(self.crossing_samples.iter().copied(), prior)
+    }
+
+    /// Rotanın penceresindeki actual/simulated oranlarının p95'i
+    pub fn ratio_p95(&self, route: &str) -> Option<f64> {
+        let window = self.routes.get(route)?;
+        percentile_95(window.iter().map(GasSample::ratio).collect())
+    }
+
+    /// Rotalar arasında en yüksek p95 — tek tampon her yönü kapsamalı
+    pub fn worst_p95(&self) -> Option<f64> {
+        self.routes.keys().filter_map(|route| self.ratio_p95(route)).reduce(f64::max)
+    }
+
+    /// En az `min_samples` işlem varsa p95 × güvenlik payı, kırpılmış
+    pub fn auto_buffer(&self, min_samples: u32) -> Option<f64> {
+        if self.total_samples < min_samples.max(1) as u64 {
+            return None;
+        }
+        let p95 = self.worst_p95()?;
+        Some((p95 * AUTO_BUFFER_SAFETY).clamp(AUTO_BUFFER_MIN, AUTO_BUFFER_MAX))
+    }
+
+    /// Defteri oku — dosya yoksa ya da bozuksa boş (kalibrasyon baştan)
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                eprintln!(
+                    "  ⚠️  {} parse error: {} — gas calibration restarts",
+                    path.display(),
+                    e
+                );
+                GasLedger::default()
+            }),
+            Err(_) => GasLedger::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        // REVM örnekleri anlık havuz durumuna bağlı: yalnızca receipt'ler kalıcı
+        let mut persisted = self.clone();
+        persisted.crossing_samples.retain(|s| s.source == GasSource::Receipt);
+        let json = serde_json::to_vec_pretty(&persisted).map_err(std::io::Error::other)?;
+        crate::session_summary::write_atomic(path, &json)
+    }
+}
+
+/// En yakın sıra yöntemiyle p95 (boş → None)
+fn percentile_95(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let rank = (values.len() as f64 * 0.95).ceil() as usize;
+    Some(values[rank.clamp(1, values.len()) - 1])
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kalibrasyon Durumu
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Mod + defter + kalıcılık yolu
+#[derive(Debug, Clone)]
+pub struct GasCalibration {
+    pub mode: GasBufferMode,
+    pub min_samples: u32,
+    pub ledger: GasLedger,
+    /// None → defter diske yazılmaz (testler)
+    path: Option<PathBuf>,
+    /// Geçiş modeli önseli (GAS_BASE_OVERHEAD / GAS_PER_TICK_CROSSING)
+    pub prior: CrossingGasModel,
+    /// false → geçiş modeli oturtulmaz, hep önsel kullanılır
+    pub crossing_calibration: bool,
+}
+
+impl GasCalibration {
+    pub fn new(mode: GasBufferMode, min_samples: u32, ledger: GasLedger, path: Option<PathBuf>) -> Self {
+        Self { mode, min_samples, ledger, path, prior: CrossingGasModel::PRIOR, crossing_calibration: true }
+    }
+
+    pub fn with_crossing_model(mut self, prior: CrossingGasModel, calibrate: bool) -> Self {
+        self.prior = prior;
+        self.crossing_calibration = calibrate;
+        self
+    }
+
+    /// Boyutlandırma gas'ı: oturtulmuş model > son REVM gas'ı > önsel.
+    /// Kalibrasyon kapalıysa her zaman önsel.
+    pub fn estimate(&self, last_simulated_gas: Option<u64>) -> GasEstimate {
+        if !self.crossing_calibration {
+            return GasEstimate::Crossing(self.prior);
+        }
+        match (self.ledger.crossing_fit, last_simulated_gas) {
+            (Some(fit), _) => GasEstimate::Crossing(fit),
+            (None, Some(gas)) => GasEstimate::Flat(gas),
+            (None, None) => GasEstimate::Crossing(self.prior),
+        }
+    }
+
+    /// Geçiş örneğini ekle ve modeli yeniden oturt
+    fn record_crossing(&mut self, sample: CrossingSample) {
+        if !self.crossing_calibration {
+            return;
+        }
+        self.ledger.record_crossing(sample);
+        if let Some(fit) = self.ledger.crossing_model(self.min_samples, self.prior) {
+            self.ledger.crossing_fit = Some(fit);
+        }
+    }
+
+    /// REVM ölçümü: geçiş modeline bellek içi örnek (diske yazılmaz)
+    pub fn record_simulation(&mut self, crossings: u32, gas: u64) {
+        if gas > 0 {
+            self.record_crossing(CrossingSample { crossings, gas, source: GasSource::Simulation });
+        }
+    }
+
+    /// Yürürlükteki tampon çarpanı
+    pub fn buffer(&self) -> f64 {
+        match self.mode {
+            GasBufferMode::Fixed => FIXED_GAS_BUFFER,
+            GasBufferMode::Auto => self.ledger.learned_buffer.unwrap_or(FIXED_GAS_BUFFER),
+        }
+    }
+
+    /// REVM gas'ından TX gas limiti
+    pub fn gas_limit(&self, simulated_gas: u64) -> u64 {
+        ((simulated_gas as f64 * self.buffer()) as u64).max(MIN_GAS_LIMIT)
+    }
+
+    /// Receipt'i deftere işle, öğrenilen tamponu güncelle ve diske yaz.
+    /// Tampon fixed modda da öğrenilir — auto'ya geçişte hazır olur.
+    pub fn record(&mut self, route: &str, sample: GasSample) {
+        self.ledger.record(route, sample);
+        if let (Some(crossings), true) = (sample.crossings, sample.actual > 0) {
+            self.record_crossing(CrossingSample {
+                crossings: crossings.total(),
+                gas: sample.actual,
+                source: GasSource::Receipt,
+            });
+        }
+        if let Some(buffer) = self.ledger.auto_buffer(self.min_samples) {
+            self.ledger.learned_buffer = Some(buffer);
+        }
+        if let Some(path) = &self.path {
+            if let Err(e) = self.ledger.save(path) {
+                eprintln!("  ⚠️  {} write error: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Defteri boşalt (öğrenilen tampon dahil) ve diske yaz — düşen örnek sayısı
+    pub fn invalidate(&mut self) -> u64 {
+        let dropped = self.ledger.total_samples;
+        self.ledger = GasLedger::default();
+        if let Some(path) = &self.path {
+            if let Err(e) = self.ledger.save(path) {
+                eprintln!("  ⚠️  {} write error: {}", path.display(), e);
+            }
+        }
+        dropped
+    }
+}
+
+/// Süreç geneli kalibrasyon — init öncesi None (FIXED_GAS_BUFFER kullanılır)
+static CALIBRATION: Mutex<Option<GasCalibration>> = parking_lot::const_mutex(None);
+
+/// Defteri yükle, modu ve geçiş modeli önselini config'den al (main,
+/// run_bot öncesi). Eski konumdaki defter okunur; ilk kayıt DATA_DIR altına yazılır.
+pub fn init(config: &BotConfig) {
+    let ledger = GasLedger::load(&crate::secure_fs::read_path(GAS_LEDGER_PATH));
+    let path = crate::secure_fs::data_path(GAS_LEDGER_PATH);
+    *CALIBRATION.lock() = Some(
+        GasCalibration::new(config.gas_buffer_mode, config.min_calibration_samples, ledger, Some(path))
+            .with_crossing_model(CrossingGasModel::from_config(config), config.gas_crossing_calibration),
+    );
+}
+
+/// REVM gas'ından TX gas limiti (yürürlükteki tamponla)
+pub fn gas_limit(simulated_gas: u64) -> u64 {
+    match CALIBRATION.lock().as_ref() {
+        Some(calibration) => calibration.gas_limit(simulated_gas),
+        None => ((simulated_gas as f64 * FIXED_GAS_BUFFER) as u64).max(MIN_GAS_LIMIT),
+    }
+}
+
+/// Boyutlandırma gas tahmini (init öncesi: son REVM gas'ı, yoksa önsel)
+pub fn estimate(last_simulated_gas: Option<u64>) -> GasEstimate {
+    match CALIBRATION.lock().as_ref() {
+        Some(calibration) => calibration.estimate(last_simulated_gas),
+        None => last_simulated_gas.map_or(GasEstimate::Crossing(CrossingGasModel::PRIOR), GasEstimate::Flat),
+    }
+}
+
+/// Optimum boyuttaki REVM ölçümünü geçiş modeline ekle
+pub fn record_simulation(crossings: u32, gas: u64) {
+    if let Some(calibration) = CALIBRATION.lock().as_mut() {
+        calibration.record_simulation(crossings, gas);
+    }
+}
+
+/// Canlı işlemin receipt'ini kaydet ve trades.json'a ekle (executor receipt görevi)
+pub fn record_receipt(route: &str, sample: GasSample, tx_hash: &str, block: u64, landed: bool) {
+    if let Some(calibration) = CALIBRATION.lock().as_mut() {
+        calibration.record(route, sample);
+    }
+    let line = trade_line(route, &sample, tx_hash, block, landed);
+    if let Err(e) = append_trade(&crate::secure_fs::data_path(TRADES_LOG_PATH), &line) {
+        eprintln!("  ⚠️  {} write error: {}", TRADES_LOG_PATH, e);
+    }
+}
+
+/// trades.json satırı: geçiş sayıları + modellenen / simüle / gerçek gas
+fn trade_line(route: &str, sample: &GasSample, tx_hash: &str, block: u64, landed: bool) -> serde_json::Value {
+    serde_json::json!({
+        "ts": crate::clock::log_timestamp(),
+        "tx_hash": tx_hash,
+        "block": block,
+        "route": route,
+        "landed": landed,
+        "sell_crossings": sample.crossings.map(|c| c.sell),
+        "buy_crossings": sample.crossings.map(|c| c.buy),
+        "modeled_gas": sample.modeled,
+        "simulated_gas": sample.simulated,
+        "gas_limit": sample.limit,
+        "actual_gas": sample.actual,
+    })
+}
+
+fn append_trade(path: &Path, line: &serde_json::Value) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut file = crate::secure_fs::open_append(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// Bytecode değişti: kalibrasyon baştan (init öncesi 0)
+pub fn invalidate() -> u64 {
+    CALIBRATION.lock().as_mut().map_or(0, GasCalibration::invalidate)
+}
+
+/// İstatistik kutusu satırı — örnek yoksa ve mod fixed ise None
+pub fn stats_line() -> Option<String> {
+    let guard = CALIBRATION.lock();
+    let calibration = guard.as_ref()?;
+    let ledger = &calibration.ledger;
+    if ledger.total_samples == 0 && ledger.crossing_samples.is_empty() && calibration.mode == GasBufferMode::Fixed {
+        return None;
+    }
+    let mut line = format!("buffer {:.3} ({})", calibration.buffer(), calibration.mode);
+    for (route, window) in &ledger.routes {
+        if let Some(p95) = ledger.ratio_p95(route) {
+            line.push_str(&format!(" | {} p95 {:.3} (n={})", route, p95, window.len()));
+        }
+    }
+    let (model, kind) = match (calibration.crossing_calibration, ledger.crossing_fit) {
+        (true, Some(fit)) => (fit, "fit"),
+        _ => (calibration.prior, "prior"),
+    };
+    line.push_str(&format!(
+        " | crossing {:.0} + {:.0}/tick ({}, n={})",
+        model.base_overhead,
+        model.per_crossing,
+        kind,
+        ledger.crossing_samples.len(),
+    ));
+    Some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(simulated: u64, ratio: f64) -> GasSample {
+        GasSample {
+            simulated,
+            limit: (simulated as f64 * FIXED_GAS_BUFFER) as u64,
+            actual: (simulated as f64 * ratio).round() as u64,
+            ..Default::default()
+        }
+    }
+
+    fn temp_path(tag: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("arb_gas_ledger_{}_{}.json", tag, std::process::id()))
+    }
+
+    #[test]
+    fn test_ratio_window_per_route() {
+        let mut ledger = GasLedger::default();
+        // 1..=100 → oranlar 0.01..1.00; p95 = 0.95
+        for i in 1..=100u64 {
+            ledger.record("pair_zero_for_one", GasSample { simulated: 100_000, limit: 110_000, actual: i * 1_000, ..Default::default() });
+        }
+        ledger.record("pair_one_for_zero", sample(200_000, 1.2));
+        // REVM ölçümü yok → oran yok
+        ledger.record("pair_one_for_zero", GasSample { simulated: 0, limit: 350_000, actual: 180_000, ..Default::default() });
+
+        assert_eq!(ledger.total_samples, 101);
+        assert!((ledger.ratio_p95("pair_zero_for_one").unwrap() - 0.95).abs() < 1e-12);
+        assert!((ledger.ratio_p95("pair_one_for_zero").unwrap() - 1.2).abs() < 1e-12);
+        assert_eq!(ledger.ratio_p95(MULTI_HOP_ROUTE), None);
+        assert!((ledger.worst_p95().unwrap() - 1.2).abs() < 1e-12);
+
+        // Pencere dolunca en eski örnekler düşer
+        for _ in 0..RATIO_WINDOW {
+            ledger.record("pair_zero_for_one", sample(100_000, 1.01));
+        }
+        assert_eq!(ledger.routes["pair_zero_for_one"].len(), RATIO_WINDOW);
+        assert!((ledger.ratio_p95("pair_zero_for_one").unwrap() - 1.01).abs() < 1e-12);
+        assert_eq!(ledger.total_samples, 101 + RATIO_WINDOW as u64);
+    }
+
+    #[test]
+    fn test_auto_buffer_min_samples_and_clamps() {
+        let mut ledger = GasLedger::default();
+        for _ in 0..9 {
+            ledger.record("pair_zero_for_one", sample(200_000, 1.0));
+        }
+        assert_eq!(ledger.auto_buffer(10), None);
+        ledger.record("pair_zero_for_one", sample(200_000, 1.0));
+        assert!((ledger.auto_buffer(10).unwrap() - 1.05).abs() < 1e-9);
+
+        // Receipt simülasyondan hep düşük → alt sınır
+        let mut low = GasLedger::default();
+        for _ in 0..10 {
+            low.record("pair_zero_for_one", sample(200_000, 0.8));
+        }
+        assert_eq!(low.auto_buffer(10), Some(AUTO_BUFFER_MIN));
+
+        // Simülasyon ciddi eksik ölçüyor → üst sınır
+        let mut high = GasLedger::default();
+        for _ in 0..10 {
+            high.record(MULTI_HOP_ROUTE, sample(200_000, 1.9));
+        }
+        assert_eq!(high.auto_buffer(10), Some(AUTO_BUFFER_MAX));
+
+        // Fixed mod öğrenilen değeri kullanmaz; auto kullanır
+        let mut calibration = GasCalibration::new(GasBufferMode::Fixed, 10, high.clone(), None);
+        calibration.record(MULTI_HOP_ROUTE, sample(200_000, 1.9));
+        assert_eq!(calibration.ledger.learned_buffer, Some(AUTO_BUFFER_MAX));
+        assert_eq!(calibration.buffer(), FIXED_GAS_BUFFER);
+        assert_eq!(calibration.gas_limit(200_000), 220_000);
+        calibration.mode = GasBufferMode::Auto;
+        assert_eq!(calibration.gas_limit(200_000), 300_000);
+        // Taban limit
+        assert_eq!(calibration.gas_limit(10_000), MIN_GAS_LIMIT);
+    }
+
+    #[test]
+    fn test_ledger_persistence_round_trip() {
+        let path = temp_path("round_trip");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(GasLedger::load(&path), GasLedger::default());
+
+        let mut calibration = GasCalibration::new(GasBufferMode::Auto, 3, GasLedger::default(), Some(path.clone()));
+        for ratio in [0.97, 1.0, 1.02] {
+            calibration.record("pair_one_for_zero", sample(250_000, ratio));
+        }
+        let learned = calibration.ledger.learned_buffer.expect("3 örnek yeterli");
+
+        // Yeniden başlatma: diskteki defter aynı tamponu verir
+        let restored = GasCalibration::new(GasBufferMode::Auto, 3, GasLedger::load(&path), None);
+        assert_eq!(restored.ledger.routes, calibration.ledger.routes);
+        assert_eq!(restored.ledger.total_samples, 3);
+        assert!((restored.buffer() - learned).abs() < 1e-12);
+
+        // Bozuk dosya → boş defter (sabit tampona dönülür)
+        std::fs::write(&path, b"{not json").unwrap();
+        assert_eq!(GasLedger::load(&path), GasLedger::default());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// Bytecode değişimi: öğrenilen tampon düşer, boş defter diske yazılır
+    #[test]
+    fn test_invalidate_resets_learned_buffer_on_disk() {
+        let path = temp_path("invalidate");
+        let mut calibration = GasCalibration::new(GasBufferMode::Auto, 3, GasLedger::default(), Some(path.clone()));
+        for ratio in [1.3, 1.3, 1.3] {
+            calibration.record(MULTI_HOP_ROUTE, sample(200_000, ratio));
+        }
+        assert!(calibration.buffer() > FIXED_GAS_BUFFER);
+
+        assert_eq!(calibration.invalidate(), 3);
+        assert_eq!(calibration.ledger, GasLedger::default());
+        assert_eq!(calibration.buffer(), FIXED_GAS_BUFFER);
+        assert_eq!(GasLedger::load(&path), GasLedger::default());
+        assert_eq!(calibration.invalidate(), 0);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// Sahte receipt akışı: gerçek gas simülasyonun ~1.08 katı (±0.01 sapma).
+    /// auto tampon sabit 1.10'dan öğrenilen değere yakınsar ve yakınsadıktan
+    /// sonra hiçbir işlem limiti aşmaz.
+    #[test]
+    fn test_auto_buffer_converges_on_stub_receipt_feed() {
+        const TRUE_RATIO: f64 = 1.08;
+        let mut calibration = GasCalibration::new(GasBufferMode::Auto, 20, GasLedger::default(), None);
+        let receipt_feed = (0..300u64).map(|i| {
+            let simulated = 180_000 + (i % 7) * 15_000;
+            let jitter = ((i * 37 % 21) as f64 - 10.0) / 1_000.0; // -0.010..=+0.010
+            (i, simulated, (simulated as f64 * (TRUE_RATIO + jitter)).round() as u64)
+        });
+
+        let mut buffers = Vec::new();
+        for (i, simulated, actual) in receipt_feed {
+            let limit = calibration.gas_limit(simulated);
+            if i >= 20 {
+                assert!(actual <= limit, "trade {} out of gas: actual={} limit={}", i, actual, limit);
+            }
+            let route = pair_route((i % 2) as u8);
+            calibration.record(route, GasSample { simulated, limit, actual, ..Default::default() });
+            buffers.push(calibration.buffer());
+        }
+
+        // Yeterli örnek yokken sabit tampon
+        assert!(buffers[..19].iter().all(|&b| b == FIXED_GAS_BUFFER));
+        let expected = (TRUE_RATIO + 0.010) * AUTO_BUFFER_SAFETY;
+        let last = *buffers.last().unwrap();
+        assert!((last - expected).abs() < 0.005, "buffer={} expected≈{}", last, expected);
+        for route in ["pair_zero_for_one", "pair_one_for_zero"] {
+            let p95 = calibration.ledger.ratio_p95(route).unwrap();
+            assert!((p95 - TRUE_RATIO).abs() <= 0.0105, "{} p95={}", route, p95);
+        }
+    }
+
+    fn crossing_sample(crossings: u32, gas: u64, source: GasSource) -> CrossingSample {
+        CrossingSample { crossings, gas, source }
+    }
+
+    /// Sentetik REVM akışı: gas = 150k + 22k × geçiş (±300 sapma). Model
+    /// MIN_CALIBRATION_SAMPLES dolunca katsayıları geri bulur; o zamana kadar
+    /// son REVM gas'ı kullanılır.
+    #[test]
+    fn test_crossing_fit_recovers_coefficients_from_synthetic_samples() {
+        let mut calibration = GasCalibration::new(GasBufferMode::Fixed, 20, GasLedger::default(), None);
+        for i in 0..60u64 {
+            let crossings = (i * 7 % 13) as u32;
+            let jitter = (i * 31 % 7) as i64 * 100 - 300;
+            let gas = (150_000 + 22_000 * crossings as i64 + jitter) as u64;
+            if i < 19 {
+                assert_eq!(calibration.estimate(Some(210_000)), GasEstimate::Flat(210_000));
+            }
+            calibration.record_simulation(crossings, gas);
+        }
+        let GasEstimate::Crossing(fit) = calibration.estimate(Some(210_000)) else {
+            panic!("fit expected after 20 samples");
+        };
+        assert!((fit.base_overhead - 150_000.0).abs() < 500.0, "{:?}", fit);
+        assert!((fit.per_crossing - 22_000.0).abs() < 100.0, "{:?}", fit);
+        assert!(fit.estimate(10).abs_diff(370_000) < 1_000);
+
+        // REVM ölçümü olmayan simülasyon örnek üretmez
+        calibration.record_simulation(3, 0);
+        assert_eq!(calibration.ledger.crossing_samples.len(), 60);
+    }
+
+    /// Receipt'ler REVM örneklerinden ağır basar; diske yalnızca onlar yazılır
+    #[test]
+    fn test_receipts_refine_crossing_fit_and_persist() {
+        let path = temp_path("crossing");
+        let mut calibration = GasCalibration::new(GasBufferMode::Auto, 10, GasLedger::default(), Some(path.clone()));
+        for crossings in 0..10u32 {
+            calibration.record_simulation(crossings, 150_000 + 20_000 * crossings as u64);
+        }
+        let simulated_fit = calibration.ledger.crossing_fit.expect("10 samples");
+        assert!((simulated_fit.base_overhead - 150_000.0).abs() < 1e-3);
+
+        // Zincirde taban 20k daha yüksek (ör. L1 veri / soğuk slot)
+        for crossings in 0..10u32 {
+            let simulated = 150_000 + 20_000 * crossings as u64;
+            let sample = GasSample {
+                actual: simulated + 20_000,
+                ..GasSample::planned(simulated, Some(TickCrossings { sell: crossings, buy: 0 }), Some(simulated))
+            };
+            calibration.record(pair_route(0), sample);
+        }
+        let fit = calibration.ledger.crossing_fit.unwrap();
+        // Ağırlıklı taban: (150k × 1 + 170k × RECEIPT_WEIGHT) / (1 + RECEIPT_WEIGHT)
+        let expected_base = (150_000.0 + 170_000.0 * RECEIPT_WEIGHT) / (1.0 + RECEIPT_WEIGHT);
+        assert!((fit.base_overhead - expected_base).abs() < 1e-3, "{:?}", fit);
+        assert!((fit.per_crossing - 20_000.0).abs() < 1e-3);
+
+        let restored = GasLedger::load(&path);
+        assert_eq!(restored.crossing_samples.len(), 10);
+        assert!(restored.crossing_samples.iter().all(|s| s.source == GasSource::Receipt));
+        assert_eq!(restored.crossing_fit, Some(fit));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// Geçiş sayısı değişmiyorsa eğim önselden; kalibrasyon kapalıysa hep önsel
+    #[test]
+    fn test_crossing_fit_without_variance_keeps_prior_slope() {
+        let prior = CrossingGasModel { base_overhead: 140_000.0, per_crossing: 25_000.0 };
+        let flat = [crossing_sample(4, 260_000, GasSource::Simulation); 5];
+        let fit = CrossingGasModel::fit(flat, prior).unwrap();
+        assert_eq!(fit, CrossingGasModel { base_overhead: 160_000.0, per_crossing: 25_000.0 });
+        assert_eq!(CrossingGasModel::fit([], prior), None);
+
+        // Gas geçişle azalıyorsa eğim 0'a kırpılır
+        let falling = [crossing_sample(0, 200_000, GasSource::Receipt), crossing_sample(10, 150_000, GasSource::Receipt)];
+        assert_eq!(CrossingGasModel::fit(falling, prior).unwrap().per_crossing, 0.0);
+
+        let mut off = GasCalibration::new(GasBufferMode::Fixed, 1, GasLedger::default(), None).with_crossing_model(prior, false);
+        off.record_simulation(4, 260_000);
+        assert!(off.ledger.crossing_samples.is_empty());
+        assert_eq!(off.estimate(Some(300_000)), GasEstimate::Crossing(prior));
+        assert_eq!(off.estimate(Some(300_000)).units(2), 190_000);
+    }
+}
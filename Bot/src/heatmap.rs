@@ -0,0 +1,525 @@
+// ============================================================================
+//  HEATMAP v1.0 — Kalıcı Fırsat Isı Haritası (haftanın günü × UTC saat)
+//
+//  Yürütme penceresi seçimi tek oturumun istatistiğiyle değil, haftalar
+//  boyunca biriken yapıyla yapılmalı. 7×24 hücrelik matris (Pzt..Paz ×
+//  00..23 UTC), yeniden başlatmalar arasında heatmap.json'da birikir.
+//
+//  ✓ Hücre başına: tespit edilen fırsat, eşik üstü kârlı fırsat, toplam
+//    beklenen kâr, yürütülen TX, gerçekleşen kâr (receipt, gas düşülmüş)
+//  ✓ Reorg düzeltmeleri gerçekleşen kâra işlenir, hücrede ayrıca sayılır
+//  ✓ Gözlenen gün sayısı: bot o saatte açıkken geçen farklı UTC günleri —
+//    "opps/day" kapsama ile normalize edilir (bot kapalıyken geçen saatler
+//    hücreyi seyreltmez)
+//  ✓ Periyodik + kapanışta atomik yazım (tmp + fsync + rename)
+//  ✓ schema_version: eksik alanlar varsayılanla dolar; daha yeni sürümün
+//    yazdığı dosya salt okunur açılır (bilinmeyen alanlar ezilmez)
+//  ✓ --print-heatmap: hizalı ASCII tablo + en yoğun hücreler
+//  ✓ Fırsat raporunda mevcut hücrenin geçmişi (yumuşak bağlam)
+// ============================================================================
+
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use colored::*;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// DATA_DIR altındaki ısı haritası dosyası
+pub const HEATMAP_PATH: &str = "heatmap.json";
+
+/// Dosya şeması — alan eklendiğinde artar, `migrate` adımı eklenir
+pub const HEATMAP_SCHEMA_VERSION: u32 = 2;
+
+/// Ana döngünün periyodik yazım aralığı (~5 dk, 2s blok)
+pub const PERSIST_INTERVAL_BLOCKS: u64 = 150;
+
+const DAYS: usize = 7;
+const HOURS: usize = 24;
+const DAY_NAMES: [&str; DAYS] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Hücre + Matris
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// (haftanın günü, UTC saat) — Pazartesi = 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub weekday: u8,
+    pub hour: u8,
+}
+
+impl Bucket {
+    /// Her zaman UTC'ye çevrilerek kovalanır — yerel saat dilimi ve gece
+    /// yarısı sınırı hücreyi değiştirmez
+    pub fn of<Tz: TimeZone>(at: &DateTime<Tz>) -> Self {
+        let utc = at.with_timezone(&Utc);
+        Self { weekday: utc.weekday().num_days_from_monday() as u8, hour: utc.hour() as u8 }
+    }
+
+    fn index(self) -> usize {
+        self.weekday as usize * HOURS + self.hour as usize
+    }
+
+    pub fn label(self) -> String {
+        format!("{} {:02}h UTC", DAY_NAMES[self.weekday as usize], self.hour)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cell {
+    pub detected: u64,
+    /// MIN_NET_PROFIT_WETH üstü beklenen kârlı fırsatlar
+    pub profitable: u64,
+    pub expected_profit_weth: f64,
+    pub executed: u64,
+    /// Receipt'li TX'lerin gerçekleşen PnL'i (WETH, gas düşülmüş)
+    pub realized_profit_weth: f64,
+    /// v2: reorg düzeltme kayıtları ve gerçekleşen kâra net etkileri
+    pub reorg_corrections: u64,
+    pub reorg_correction_weth: f64,
+    /// Bu saatin gözlendiği farklı UTC günleri
+    pub days_observed: u32,
+    /// Son gözlenen gün (Unix epoch'tan gün) — aynı gün iki kez sayılmaz
+    pub last_day: Option<i64>,
+}
+
+impl Cell {
+    fn observe(&mut self, day: i64) {
+        if self.last_day != Some(day) {
+            self.last_day = Some(day);
+            self.days_observed += 1;
+        }
+    }
+
+    pub fn opps_per_day(&self) -> f64 {
+        self.detected as f64 / self.days_observed.max(1) as f64
+    }
+
+    pub fn avg_profit_weth(&self) -> f64 {
+        if self.detected == 0 {
+            return 0.0;
+        }
+        self.expected_profit_weth / self.detected as f64
+    }
+
+    /// Gözlenen gün başına beklenen kâr (WETH)
+    pub fn profit_density(&self) -> f64 {
+        self.expected_profit_weth / self.days_observed.max(1) as f64
+    }
+}
+
+/// heatmap.json — hücreler gün-öncelikli: indeks = gün × 24 + saat
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Heatmap {
+    pub schema_version: u32,
+    pub cells: Vec<Cell>,
+}
+
+impl Default for Heatmap {
+    fn default() -> Self {
+        Self { schema_version: HEATMAP_SCHEMA_VERSION, cells: vec![Cell::default(); DAYS * HOURS] }
+    }
+}
+
+fn epoch_day(at: &DateTime<Utc>) -> i64 {
+    at.timestamp().div_euclid(86_400)
+}
+
+impl Heatmap {
+    pub fn cell(&self, bucket: Bucket) -> &Cell {
+        &self.cells[bucket.index()]
+    }
+
+    fn cell_at(&mut self, at: &DateTime<Utc>) -> &mut Cell {
+        let cell = &mut self.cells[Bucket::of(at).index()];
+        cell.observe(epoch_day(at));
+        cell
+    }
+
+    /// Bot bu saatte açık — gün kapsamı (her blok)
+    pub fn observe(&mut self, at: &DateTime<Utc>) {
+        self.cell_at(at);
+    }
+
+    pub fn record_opportunity(&mut self, at: &DateTime<Utc>, expected_profit_weth: f64, profitable: bool) {
+        let cell = self.cell_at(at);
+        cell.detected += 1;
+        cell.profitable += u64::from(profitable);
+        if expected_profit_weth.is_finite() {
+            cell.expected_profit_weth += expected_profit_weth;
+        }
+    }
+
+    pub fn record_executed(&mut self, at: &DateTime<Utc>) {
+        self.cell_at(at).executed += 1;
+    }
+
+    /// Receipt PnL'i TX'in gönderildiği hücreye yazılır
+    pub fn record_realized(&mut self, submitted_at: &DateTime<Utc>, pnl_weth: f64) {
+        if pnl_weth.is_finite() {
+            self.cell_at(submitted_at).realized_profit_weth += pnl_weth;
+        }
+    }
+
+    /// Reorg düzeltmesi: gerçekleşen kâr farkı kadar düzelir, kayıt ayrıca sayılır
+    pub fn record_correction(&mut self, submitted_at: &DateTime<Utc>, delta_weth: f64) {
+        let cell = self.cell_at(submitted_at);
+        cell.reorg_corrections += 1;
+        if delta_weth.is_finite() {
+            cell.realized_profit_weth += delta_weth;
+            cell.reorg_correction_weth += delta_weth;
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Şema + Kalıcılık
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Çözülmüş dosya: yazılabilir değilse daha yeni bir sürüm yazmıştır
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoded {
+    pub heatmap: Heatmap,
+    pub writable: bool,
+}
+
+/// Dosya içeriğini güncel şemaya taşı. Hücre alanları `serde(default)` ile
+/// dolar; bilinmeyen alanlar yok sayılır. Sürüm adımları sırayla uygulanır —
+/// yeni şemada `1 => …` kolu eklenir ve HEATMAP_SCHEMA_VERSION artar.
+pub fn migrate(content: &str) -> Result<Decoded, String> {
+    let doc: serde_json::Value = serde_json::from_str(content).map_err(|e| format!("invalid JSON: {}", e))?;
+    let version = doc
+        .get("schema_version")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or("missing schema_version")? as u32;
+    let mut heatmap: Heatmap = serde_json::from_value(doc).map_err(|e| format!("schema v{}: {}", version, e))?;
+    if version == 0 {
+        return Err("schema_version 0 is not a heatmap layout".into());
+    }
+    // Matris boyutu sabit — eksik hücreler boş, fazlası atılır
+    heatmap.cells.resize(DAYS * HOURS, Cell::default());
+    if version > HEATMAP_SCHEMA_VERSION {
+        return Ok(Decoded { heatmap, writable: false });
+    }
+    // v1 → v2: reorg düzeltme alanları varsayılanla (0) dolar, ek adım yok
+    heatmap.schema_version = HEATMAP_SCHEMA_VERSION;
+    Ok(Decoded { heatmap, writable: true })
+}
+
+/// Dosyayı oku — yoksa boş harita. Bozuksa kenara alınır (.corrupt) ve boş başlanır.
+fn load(path: &Path) -> Decoded {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Decoded { heatmap: Heatmap::default(), writable: true },
+    };
+    match migrate(&content) {
+        Ok(decoded) => {
+            if !decoded.writable {
+                eprintln!(
+                    "  ⚠️  {} was written by a newer build (schema v{} > v{}) — read-only this session",
+                    path.display(),
+                    decoded.heatmap.schema_version,
+                    HEATMAP_SCHEMA_VERSION,
+                );
+            }
+            decoded
+        }
+        Err(e) => {
+            let mut aside = path.as_os_str().to_os_string();
+            aside.push(".corrupt");
+            let moved = std::fs::rename(path, &aside).is_ok();
+            eprintln!(
+                "  ⚠️  {} unreadable ({}){} — heatmap restarts",
+                path.display(),
+                e,
+                if moved { format!(", moved to {}", PathBuf::from(&aside).display()) } else { String::new() },
+            );
+            Decoded { heatmap: Heatmap::default(), writable: true }
+        }
+    }
+}
+
+fn save(path: &Path, heatmap: &Heatmap) -> std::io::Result<()> {
+    let json = serde_json::to_vec_pretty(heatmap).map_err(std::io::Error::other)?;
+    crate::session_summary::write_atomic(path, &json)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Durum
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Store {
+    heatmap: Heatmap,
+    path: PathBuf,
+    writable: bool,
+    dirty: bool,
+}
+
+static STORE: Mutex<Option<Store>> = parking_lot::const_mutex(None);
+
+/// heatmap.json'ı yükle (main, run_bot öncesi — reconnect'lerde korunur)
+pub fn init() {
+    let path = crate::secure_fs::data_path(HEATMAP_PATH);
+    let Decoded { heatmap, writable } = load(&path);
+    *STORE.lock() = Some(Store { heatmap, path, writable, dirty: false });
+}
+
+fn update(f: impl FnOnce(&mut Heatmap)) {
+    if let Some(store) = STORE.lock().as_mut() {
+        f(&mut store.heatmap);
+        store.dirty = true;
+    }
+}
+
+/// Ana döngü, her blok: bot bu saatte açık
+pub fn observe(at: DateTime<Utc>) {
+    update(|h| h.observe(&at));
+}
+
+pub fn record_opportunity(at: DateTime<Utc>, expected_profit_weth: f64, profitable: bool) {
+    update(|h| h.record_opportunity(&at, expected_profit_weth, profitable));
+}
+
+pub fn record_executed(at: DateTime<Utc>) {
+    update(|h| h.record_executed(&at));
+}
+
+pub fn record_realized(submitted_at: DateTime<Utc>, pnl_weth: f64) {
+    update(|h| h.record_realized(&submitted_at, pnl_weth));
+}
+
+pub fn record_correction(submitted_at: DateTime<Utc>, delta_weth: f64) {
+    update(|h| h.record_correction(&submitted_at, delta_weth));
+}
+
+/// Değişiklik varsa diske yaz (periyodik ve kapanışta). Yazım kilit dışında.
+pub fn persist() {
+    let (heatmap, path) = {
+        let mut store = STORE.lock();
+        let Some(store) = store.as_mut().filter(|s| s.dirty && s.writable) else {
+            return;
+        };
+        store.dirty = false;
+        (store.heatmap.clone(), store.path.clone())
+    };
+    if let Err(e) = save(&path, &heatmap) {
+        eprintln!("  ⚠️  {} write error: {}", path.display(), e);
+    }
+}
+
+/// Fırsat raporu için mevcut hücrenin geçmişi — veri yoksa None
+pub fn current_context(at: DateTime<Utc>) -> Option<String> {
+    let store = STORE.lock();
+    let bucket = Bucket::of(&at);
+    let cell = store.as_ref()?.heatmap.cell(bucket);
+    (cell.detected > 0).then(|| {
+        format!(
+            "{}: {:.1} opps/day, {:.6} WETH avg ({} days)",
+            bucket.label(),
+            cell.opps_per_day(),
+            cell.avg_profit_weth(),
+            cell.days_observed,
+        )
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CLI: --print-heatmap
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek hücre genişliği (sağa hizalı)
+const CELL_WIDTH: usize = 8;
+/// "En yoğun hücreler" listesinin uzunluğu
+const TOP_CELLS: usize = 10;
+
+/// Saat satırları × gün sütunları, hücrede gözlenen gün başına beklenen kâr
+/// (mWETH). Hiç gözlenmemiş hücre ".". Renk yok — dosyaya yönlendirilebilir.
+pub fn render(heatmap: &Heatmap) -> String {
+    let mut out = String::new();
+    out.push_str("Profit density: expected mWETH per observed day (\".\" = never observed), UTC\n\n");
+    out.push_str(&format!("{:<6}|", "Hour"));
+    for day in DAY_NAMES {
+        out.push_str(&format!("{:>width$}", day, width = CELL_WIDTH));
+    }
+    out.push('\n');
+    out.push_str(&format!("{}+{}\n", "-".repeat(6), "-".repeat(CELL_WIDTH * DAYS)));
+    for hour in 0..HOURS {
+        out.push_str(&format!("{:02}    |", hour));
+        for weekday in 0..DAYS {
+            let cell = heatmap.cell(Bucket { weekday: weekday as u8, hour: hour as u8 });
+            let text = if cell.days_observed == 0 {
+                ".".to_string()
+            } else {
+                format!("{:.3}", cell.profit_density() * 1_000.0)
+            };
+            out.push_str(&format!("{:>width$}", text, width = CELL_WIDTH));
+        }
+        out.push('\n');
+    }
+
+    let mut ranked: Vec<(Bucket, &Cell)> = (0..DAYS * HOURS)
+        .map(|i| (Bucket { weekday: (i / HOURS) as u8, hour: (i % HOURS) as u8 }, &heatmap.cells[i]))
+        .filter(|(_, cell)| cell.detected > 0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.profit_density().total_cmp(&a.1.profit_density()));
+    if ranked.is_empty() {
+        out.push_str("\nNo opportunities recorded yet.\n");
+        return out;
+    }
+    out.push_str(&format!("\nTop {} cells by profit density:\n", TOP_CELLS.min(ranked.len())));
+    out.push_str(&format!(
+        "  {:<14} {:>9} {:>10} {:>12} {:>8} {:>14} {:>5}\n",
+        "cell", "opps/day", "profitable", "avg WETH", "executed", "realized WETH", "days"
+    ));
+    for (bucket, cell) in ranked.into_iter().take(TOP_CELLS) {
+        out.push_str(&format!(
+            "  {:<14} {:>9.1} {:>10} {:>12.6} {:>8} {:>14.6} {:>5}\n",
+            bucket.label(),
+            cell.opps_per_day(),
+            cell.profitable,
+            cell.avg_profit_weth(),
+            cell.executed,
+            cell.realized_profit_weth,
+            cell.days_observed,
+        ));
+    }
+    out
+}
+
+pub fn cli_print_heatmap() -> eyre::Result<()> {
+    let path = crate::secure_fs::data_path(HEATMAP_PATH);
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| eyre::eyre!("{} could not be read: {} — run the bot to start collecting", path.display(), e))?;
+    let decoded = migrate(&content).map_err(|e| eyre::eyre!("{}: {}", path.display(), e))?;
+    println!("  {} Opportunity heatmap: {}", "🗓️".cyan(), path.display());
+    println!();
+    print!("{}", render(&decoded.heatmap));
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn utc(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_bucket_across_midnight_and_timezones() {
+        // 2026-10-18 Pazar, 2026-10-19 Pazartesi
+        assert_eq!(Bucket::of(&utc("2026-10-18T23:59:59Z")), Bucket { weekday: 6, hour: 23 });
+        assert_eq!(Bucket::of(&utc("2026-10-19T00:00:00Z")), Bucket { weekday: 0, hour: 0 });
+
+        // Yerel Pazartesi 01:30 (+03:00) → UTC Pazar 22:30
+        let istanbul = FixedOffset::east_opt(3 * 3600).unwrap();
+        let local = istanbul.with_ymd_and_hms(2026, 10, 19, 1, 30, 0).unwrap();
+        assert_eq!(Bucket::of(&local), Bucket { weekday: 6, hour: 22 });
+
+        // Yerel Pazar 21:00 (-05:00) → UTC Pazartesi 02:00
+        let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+        let local = new_york.with_ymd_and_hms(2026, 10, 18, 21, 0, 0).unwrap();
+        assert_eq!(Bucket::of(&local), Bucket { weekday: 0, hour: 2 });
+        assert_eq!(Bucket::of(&local).label(), "Mon 02h UTC");
+    }
+
+    #[test]
+    fn test_counters_and_day_coverage() {
+        let mut heatmap = Heatmap::default();
+        let bucket = Bucket { weekday: 1, hour: 14 };
+        // İki Salı, aynı saat: günde birden çok blok / fırsat tek gün sayılır
+        for t in ["2026-10-13T14:00:02Z", "2026-10-13T14:59:58Z", "2026-10-20T14:30:00Z"] {
+            heatmap.observe(&utc(t));
+            heatmap.record_opportunity(&utc(t), 0.002, true);
+        }
+        heatmap.record_opportunity(&utc("2026-10-20T14:31:00Z"), 0.0005, false);
+        heatmap.record_executed(&utc("2026-10-20T14:31:00Z"));
+        heatmap.record_realized(&utc("2026-10-20T14:31:00Z"), 0.0015);
+        // Reorg: TX başka blokta daha düşük kârla dahil edildi
+        heatmap.record_correction(&utc("2026-10-20T14:31:00Z"), -0.0004);
+        heatmap.record_opportunity(&utc("2026-10-20T14:32:00Z"), f64::NAN, false);
+
+        let cell = heatmap.cell(bucket);
+        assert_eq!((cell.detected, cell.profitable, cell.executed, cell.days_observed), (5, 3, 1, 2));
+        assert!((cell.expected_profit_weth - 0.0065).abs() < 1e-12);
+        assert!((cell.opps_per_day() - 2.5).abs() < 1e-12);
+        assert!((cell.profit_density() - 0.00325).abs() < 1e-12);
+        assert!((cell.realized_profit_weth - 0.0011).abs() < 1e-12);
+        assert_eq!(cell.reorg_corrections, 1);
+        assert!((cell.reorg_correction_weth + 0.0004).abs() < 1e-12);
+        // Komşu saat etkilenmez
+        assert_eq!(heatmap.cell(Bucket { weekday: 1, hour: 15 }), &Cell::default());
+    }
+
+    #[test]
+    fn test_schema_migration_path() {
+        // Eski sürüm, eksik alanlar (eski yazıcı) ve eksik hücreler → varsayılan
+        let partial = r#"{"schema_version":1,"cells":[{"detected":4,"expected_profit_weth":0.01}]}"#;
+        let decoded = migrate(partial).unwrap();
+        assert!(decoded.writable);
+        assert_eq!(decoded.heatmap.schema_version, HEATMAP_SCHEMA_VERSION);
+        assert_eq!(decoded.heatmap.cells[0].reorg_corrections, 0);
+        assert_eq!(decoded.heatmap.cells.len(), DAYS * HOURS);
+        let first = &decoded.heatmap.cells[0];
+        assert_eq!((first.detected, first.executed, first.days_observed, first.last_day), (4, 0, 0, None));
+
+        // Daha yeni sürüm: okunur ama üzerine yazılmaz; bilinmeyen alanlar yok sayılır
+        let newer = r#"{"schema_version":7,"cells":[{"detected":2,"p99_latency_ms":40}],"regions":{}}"#;
+        let decoded = migrate(newer).unwrap();
+        assert!(!decoded.writable);
+        assert_eq!(decoded.heatmap.schema_version, 7);
+        assert_eq!(decoded.heatmap.cells[0].detected, 2);
+
+        // Sürümsüz / bozuk → hata (init kenara alır, boş başlar)
+        assert!(migrate(r#"{"cells":[]}"#).is_err());
+        assert!(migrate(r#"{"schema_version":0,"cells":[]}"#).is_err());
+        assert!(migrate("not json").is_err());
+
+        // Gidiş-dönüş
+        let mut heatmap = Heatmap::default();
+        heatmap.record_opportunity(&utc("2026-10-16T09:00:00Z"), 0.001, true);
+        let json = serde_json::to_string(&heatmap).unwrap();
+        assert_eq!(migrate(&json).unwrap(), Decoded { heatmap, writable: true });
+    }
+
+    #[test]
+    fn test_atomic_persist_and_corrupt_file_moved_aside() {
+        let dir = std::env::temp_dir().join(format!("arb_heatmap_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        crate::secure_fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(HEATMAP_PATH);
+
+        let mut heatmap = Heatmap::default();
+        heatmap.record_executed(&utc("2026-10-16T09:00:00Z"));
+        save(&path, &heatmap).unwrap();
+        assert!(!dir.join("heatmap.json.tmp").exists());
+        assert_eq!(load(&path), Decoded { heatmap, writable: true });
+
+        std::fs::write(&path, "{ truncated").unwrap();
+        assert_eq!(load(&path), Decoded { heatmap: Heatmap::default(), writable: true });
+        assert!(dir.join("heatmap.json.corrupt").exists());
+        assert!(!path.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_render_is_aligned() {
+        let mut heatmap = Heatmap::default();
+        heatmap.record_opportunity(&utc("2026-10-16T09:10:00Z"), 0.0123, true);
+        heatmap.observe(&utc("2026-10-17T03:00:00Z"));
+        let text = render(&heatmap);
+        let table: Vec<&str> = text.lines().skip(2).take(2 + HOURS).collect();
+        assert!(table.iter().all(|l| l.len() == 6 + 1 + CELL_WIDTH * DAYS), "{}", text);
+        // Cuma 09 → 12.300 mWETH/gün; Cumartesi 03 gözlendi ama fırsat yok
+        assert!(table[2 + 9].ends_with(&format!("{:>8}{:>8}{:>8}", "12.300", ".", ".")));
+        assert!(table[2 + 3].ends_with(&format!("{:>8}{:>8}", "0.000", ".")));
+        assert!(text.contains("Fri 09h UTC"));
+    }
+}
@@ -0,0 +1,577 @@
+// ============================================================================
+//  IMPACT_VERIFICATION v1.0 — İşlem Sonrası Havuz Etkisi Doğrulaması
+//
+//  Başarılı işlemden sonra havuzların yeni fiyatı, exact simülasyonun
+//  öngördüğü son fiyatla (bacak başına final_sqrt_price_x96 / final_tick)
+//  örtüşmeli. Kalıcı sapma model hatasıdır (fee, bitmap boşluğu) ve sonraki
+//  her kâr tahminini sessizce bozar.
+//
+//  Onaylanan her işlem için:
+//  ✓ Yürütme yolunda iki bacağın öngörülen son durumu taşınır
+//  ✓ Receipt sonrası iki havuzun slot0'ı dahil edilme bloğunda okunur (sabitli)
+//  ✓ Bacak başına sapma: tick farkı + gözlenen fiyatın ima ettiği çıktı farkı
+//  ✓ İstatistik kutusunda bacak başına ortalama |Δtick|
+//  ✓ IMPACT_WARN_TICKS aşılırsa uyarı (konsol + Telegram); opsiyonel olarak
+//    IMPACT_BUMP_BLOCKS boyunca minProfit slippage faktörü düşürülür
+//
+//  Not: dahil edilme bloğundaki sonraki işlemler de fiyatı oynatır — tek
+//  işlemin sapması gürültülüdür, ortalama ve eşik bunu tolere eder.
+//  VERIFIER kilidi doğrulama görevinin biriktirdiği sapma istatistiğini ve
+//  slippage artışını yürütme yolunun minProfit hesabına taşır. CONFIRMED
+//  receipt görevinden doğrulamaya kuyruktur; spawn_verifier her bağlantıda
+//  yeniler, doğrulayıcı yokken gönderim sessizce düşer.
+// ============================================================================
+
+use alloy::eips::BlockId;
+use alloy::primitives::{B256, U256};
+use alloy::providers::Provider;
+use colored::*;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::math::exact::{
+    compute_exact_swap, get_amount0_delta, get_amount1_delta, tick_after_swap,
+};
+use crate::telegram::{TelegramMessage, TelegramSender};
+use crate::types::{BotConfig, PoolConfig, PoolState};
+
+/// İkili arbitrajın bacak sayısı (pool A flash swap, pool B satış)
+pub const LEG_COUNT: usize = 2;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Öngörü ve Sapma
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek bacağın simülasyondaki etkisi
+#[derive(Debug, Clone)]
+pub struct LegPrediction {
+    pub pool: PoolConfig,
+    pub zero_for_one: bool,
+    /// Kâr hesabının gördüğü swap öncesi fiyat
+    pub sqrt_price_before: U256,
+    /// Swap öncesi aktif likidite
+    pub liquidity_before: u128,
+    /// Exact swap sonrası sqrtPriceX96
+    pub final_sqrt_price_x96: U256,
+    /// Exact swap sonrası tick (kontrat semantiği)
+    pub final_tick: i32,
+}
+
+impl LegPrediction {
+    /// Swap'ı exact motorla yürüt → (öngörü, çıktı wei)
+    fn simulate(
+        pool: &PoolConfig,
+        state: &PoolState,
+        amount_in: U256,
+        zero_for_one: bool,
+    ) -> (Self, U256) {
+        let result = compute_exact_swap(
+            state.sqrt_price_x96,
+            state.liquidity,
+            state.tick,
+            amount_in,
+            zero_for_one,
+            pool.fee_pips(),
+            state.tick_bitmap.as_deref(),
+        );
+        let prediction = Self {
+            pool: pool.clone(),
+            zero_for_one,
+            sqrt_price_before: state.sqrt_price_x96,
+            liquidity_before: state.liquidity,
+            final_sqrt_price_x96: result.sqrt_price_x96_after,
+            final_tick: result.final_tick,
+        };
+        (prediction, result.amount_out)
+    }
+
+    /// Son fiyatın ima ettiği çıktı (wei): fiyat, swap öncesi likiditeyle
+    /// önceki değerden son değere yürütülür. Tick geçişleri yok sayılır —
+    /// öngörü ve gözlem aynı yaklaşımla hesaplandığından fark model hatasını
+    /// yine ölçer. Fiyat swap yönünün tersine gittiyse sıfır.
+    pub fn implied_output(&self, final_sqrt_price: U256) -> U256 {
+        let before = self.sqrt_price_before;
+        if self.zero_for_one {
+            if final_sqrt_price >= before {
+                return U256::ZERO;
+            }
+            get_amount1_delta(final_sqrt_price, before, self.liquidity_before, false)
+        } else {
+            if final_sqrt_price <= before {
+                return U256::ZERO;
+            }
+            get_amount0_delta(before, final_sqrt_price, self.liquidity_before, false)
+        }
+    }
+
+    /// Gözlenen son fiyatın öngörüden sapması (gözlenen − öngörülen)
+    pub fn deviation(&self, observed_sqrt_price: U256) -> LegDeviation {
+        let observed_tick = tick_after_swap(observed_sqrt_price, self.zero_for_one);
+        LegDeviation {
+            observed_tick,
+            ticks: observed_tick - self.final_tick,
+            output_wei: signed_diff(
+                self.implied_output(observed_sqrt_price),
+                self.implied_output(self.final_sqrt_price_x96),
+            ),
+        }
+    }
+}
+
+/// İki bacağın öngörülen son durumu — yürütme görevinden receipt'e taşınır
+#[derive(Debug, Clone)]
+pub struct PredictedImpact {
+    pub legs: [LegPrediction; LEG_COUNT],
+}
+
+impl PredictedImpact {
+    /// Flash swap akışı (exact_profit_for_amount ile aynı): pool A'ya
+    /// `amount_wei` girer, çıktısı pool B'ye satılır.
+    pub fn for_pair(
+        pools: &[PoolConfig],
+        state_a: &PoolState,
+        state_b: &PoolState,
+        amount_wei: U256,
+        uni_zero_for_one: bool,
+        aero_zero_for_one: bool,
+    ) -> Self {
+        let (leg_a, received) =
+            LegPrediction::simulate(&pools[0], state_a, amount_wei, uni_zero_for_one);
+        let (leg_b, _) = LegPrediction::simulate(&pools[1], state_b, received, aero_zero_for_one);
+        Self {
+            legs: [leg_a, leg_b],
+        }
+    }
+
+    /// Okunan slot0'lara göre bacak başına sapma — bir okuma eksikse None
+    pub fn verify(&self, observed: &[Option<U256>]) -> Option<[LegDeviation; LEG_COUNT]> {
+        let [a, b] = [observed.first().copied()??, observed.get(1).copied()??];
+        Some([self.legs[0].deviation(a), self.legs[1].deviation(b)])
+    }
+}
+
+/// Tek bacağın gözlenen − öngörülen farkı
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LegDeviation {
+    /// Dahil edilme bloğunda okunan tick
+    pub observed_tick: i32,
+    pub ticks: i32,
+    /// Gözlenen fiyatın ima ettiği çıktı − öngörülen (wei, çıktı token'ı)
+    pub output_wei: i128,
+}
+
+/// a − b, i128'e doygun
+pub(crate) fn signed_diff(a: U256, b: U256) -> i128 {
+    let magnitude = |d: U256| i128::try_from(&d).unwrap_or(i128::MAX);
+    if a >= b {
+        magnitude(a - b)
+    } else {
+        -magnitude(b - a)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Model Hatası İstatistiği + Slippage Artışı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Oturum boyunca doğrulanan işlemlerin sapma özeti
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ImpactStats {
+    pub verified: u64,
+    /// Bacak başına Σ|Δtick|
+    pub abs_tick_sum: [u64; LEG_COUNT],
+    /// Bacak başına en büyük |Δtick|
+    pub max_abs_ticks: [u32; LEG_COUNT],
+    /// IMPACT_WARN_TICKS'i aşan işlem sayısı
+    pub warnings: u64,
+}
+
+impl ImpactStats {
+    pub fn record(&mut self, deviations: &[LegDeviation; LEG_COUNT]) {
+        self.verified += 1;
+        for (leg, dev) in deviations.iter().enumerate() {
+            let abs = dev.ticks.unsigned_abs();
+            self.abs_tick_sum[leg] += abs as u64;
+            self.max_abs_ticks[leg] = self.max_abs_ticks[leg].max(abs);
+        }
+    }
+
+    /// Bacağın ortalama mutlak tick sapması
+    pub fn mean_abs_ticks(&self, leg: usize) -> f64 {
+        if self.verified == 0 {
+            return 0.0;
+        }
+        self.abs_tick_sum[leg] as f64 / self.verified as f64
+    }
+}
+
+/// Eşikler + istatistik + yürürlükteki slippage artışı
+#[derive(Debug, Clone)]
+pub struct ImpactVerifier {
+    warn_ticks: u32,
+    bump_bps: u64,
+    bump_blocks: u64,
+    pub stats: ImpactStats,
+    /// Artışın geçerli olduğu son blok (dahil)
+    bump_until: Option<u64>,
+}
+
+impl ImpactVerifier {
+    pub fn new(config: &BotConfig) -> Self {
+        Self {
+            warn_ticks: config.impact_warn_ticks,
+            bump_bps: config.impact_slippage_bump_bps,
+            bump_blocks: config.impact_bump_blocks,
+            stats: ImpactStats::default(),
+            bump_until: None,
+        }
+    }
+
+    /// Sapmaları işle. Eşiği aşan bacak varsa en kötüsü döner (uyarı gerekir)
+    /// ve artış açıksa `block`'tan itibaren IMPACT_BUMP_BLOCKS uzatılır.
+    pub fn record(&mut self, block: u64, deviations: &[LegDeviation; LEG_COUNT]) -> Option<usize> {
+        self.stats.record(deviations);
+        let worst = (0..LEG_COUNT)
+            .filter(|&leg| deviations[leg].ticks.unsigned_abs() > self.warn_ticks)
+            .max_by_key(|&leg| deviations[leg].ticks.unsigned_abs())?;
+        self.stats.warnings += 1;
+        if self.bump_bps > 0 {
+            self.bump_until = Some(block + self.bump_blocks);
+        }
+        Some(worst)
+    }
+
+    /// `block`'ta yürürlükteki slippage artışı (bps)
+    pub fn slippage_bump_bps(&self, block: u64) -> u64 {
+        match self.bump_until {
+            Some(until) if block <= until => self.bump_bps,
+            _ => 0,
+        }
+    }
+}
+
+/// Süreç geneli doğrulayıcı — init öncesi None (artış yok, doğrulama yok)
+static VERIFIER: Mutex<Option<ImpactVerifier>> = parking_lot::const_mutex(None);
+
+/// Receipt görevinden doğrulama görevine kuyruk — her bağlantıda yenilenir
+static CONFIRMED: Mutex<Option<mpsc::UnboundedSender<ConfirmedTrade>>> =
+    parking_lot::const_mutex(None);
+
+/// Eşikleri config'den al (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    *VERIFIER.lock() = Some(ImpactVerifier::new(config));
+}
+
+/// minProfit slippage faktörüne yürürlükteki artışı uygula (ör. 9950 → 9900)
+pub fn apply_slippage_bump(slippage_bps: u64, block: u64) -> u64 {
+    let bump = VERIFIER
+        .lock()
+        .as_ref()
+        .map_or(0, |v| v.slippage_bump_bps(block));
+    slippage_bps.saturating_sub(bump)
+}
+
+/// İstatistik kutusu satırı — doğrulanmış işlem yoksa None
+pub fn stats_line(block: u64) -> Option<String> {
+    let guard = VERIFIER.lock();
+    let verifier = guard.as_ref()?;
+    let stats = &verifier.stats;
+    if stats.verified == 0 {
+        return None;
+    }
+    let mut line = format!(
+        "n={} | leg A mean |Δtick| {:.1} (max {}) | leg B mean |Δtick| {:.1} (max {}) | warnings {}",
+        stats.verified,
+        stats.mean_abs_ticks(0),
+        stats.max_abs_ticks[0],
+        stats.mean_abs_ticks(1),
+        stats.max_abs_ticks[1],
+        stats.warnings,
+    );
+    let bump = verifier.slippage_bump_bps(block);
+    if bump > 0 {
+        line.push_str(&format!(" | slippage +{}bps", bump));
+    }
+    Some(line)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Receipt → Sabitli Okuma → Doğrulama
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// status=1 receipt'i gelen işlem
+#[derive(Debug, Clone)]
+pub struct ConfirmedTrade {
+    pub tx_hash: B256,
+    /// Dahil edilme bloğu — okumalar bu bloğa sabitlenir
+    pub block: u64,
+    pub impact: PredictedImpact,
+}
+
+/// Onaylanan işlemi doğrulama kuyruğuna ekle (executor receipt görevi).
+/// Doğrulayıcı çalışmıyorsa sessizce düşer.
+pub fn submit_confirmed(trade: ConfirmedTrade) {
+    if let Some(tx) = CONFIRMED.lock().as_ref() {
+        let _ = tx.send(trade);
+    }
+}
+
+/// Doğrulama görevini başlat (run_bot, bağlantı başına). İşlem başına tek
+/// sabitli Multicall3 slot0 okuması; bağlantı kesilince görev biter.
+pub fn spawn_verifier<P>(provider: P, telegram: Option<TelegramSender>, cancel: CancellationToken)
+where
+    P: Provider + Clone + Send + Sync + 'static,
+{
+    let (tx, mut rx) = mpsc::unbounded_channel::<ConfirmedTrade>();
+    *CONFIRMED.lock() = Some(tx);
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                trade = rx.recv() => match trade {
+                    Some(trade) => verify_trade(&provider, &trade, &telegram).await,
+                    None => break,
+                },
+            }
+        }
+    });
+}
+
+/// İki havuzun slot0'ını dahil edilme bloğunda oku, sapmayı kaydet ve raporla
+async fn verify_trade<P: Provider + Sync>(
+    provider: &P,
+    trade: &ConfirmedTrade,
+    telegram: &Option<TelegramSender>,
+) {
+    let legs = &trade.impact.legs;
+    let pools = [legs[0].pool.clone(), legs[1].pool.clone()];
+    let observed =
+        match crate::state_sync::read_all_slot0(provider, &pools, BlockId::number(trade.block))
+            .await
+        {
+            Ok(observed) => observed,
+            Err(e) => {
+                eprintln!(
+                    "     ⚠️  [Impact] slot0 read at block #{} failed: {}",
+                    trade.block, e
+                );
+                return;
+            }
+        };
+    let Some(deviations) = trade.impact.verify(&observed) else {
+        eprintln!(
+            "     ⚠️  [Impact] slot0 missing at block #{} — not verified",
+            trade.block
+        );
+        return;
+    };
+
+    let (warn_leg, warn_ticks, bump_bps) = {
+        let mut guard = VERIFIER.lock();
+        let Some(verifier) = guard.as_mut() else {
+            return;
+        };
+        let warn_leg = verifier.record(trade.block, &deviations);
+        (
+            warn_leg,
+            verifier.warn_ticks,
+            verifier.slippage_bump_bps(trade.block),
+        )
+    };
+
+    eprintln!(
+        "     🔬 [Impact] #{} | {} Δ{:+} tick ({:+} wei) | {} Δ{:+} tick ({:+} wei)",
+        trade.block,
+        legs[0].pool.name,
+        deviations[0].ticks,
+        deviations[0].output_wei,
+        legs[1].pool.name,
+        deviations[1].ticks,
+        deviations[1].output_wei,
+    );
+    crate::json_logger::log_json(
+        "trade",
+        "impact_verification",
+        serde_json::json!({
+            "tx_hash": format!("{:?}", trade.tx_hash),
+            "block": trade.block,
+            "legs": legs.iter().zip(&deviations).map(|(leg, dev)| serde_json::json!({
+                "pool": leg.pool.name,
+                "predicted_tick": leg.final_tick,
+                "predicted_sqrt_price_x96": leg.final_sqrt_price_x96.to_string(),
+                "observed_tick": dev.observed_tick,
+                "tick_deviation": dev.ticks,
+                "output_deviation_wei": dev.output_wei.to_string(),
+            })).collect::<Vec<_>>(),
+            "warning": warn_leg.is_some(),
+        }),
+    );
+
+    if let Some(leg) = warn_leg {
+        eprintln!(
+            "     {} [Impact] {} off by {:+} ticks (threshold {}) — model error?{}",
+            "⚠️".yellow(),
+            legs[leg].pool.name,
+            deviations[leg].ticks,
+            warn_ticks,
+            if bump_bps > 0 {
+                format!(" minProfit slippage +{}bps", bump_bps)
+            } else {
+                String::new()
+            },
+        );
+        if let Some(tg) = telegram {
+            tg.send(TelegramMessage::ImpactDeviation {
+                pool_name: legs[leg].pool.name.clone(),
+                tick_deviation: deviations[leg].ticks,
+                threshold_ticks: warn_ticks,
+                block_number: trade.block,
+                slippage_bump_bps: bump_bps,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::exact::get_sqrt_ratio_at_tick;
+    use crate::strategy::gas_spike_tests::make_pool_configs;
+
+    const START_TICK: i32 = -197_000;
+    const LIQUIDITY: u128 = 5_000_000_000_000_000_000;
+
+    fn leg(zero_for_one: bool, final_tick: i32) -> LegPrediction {
+        LegPrediction {
+            pool: make_pool_configs()[0].clone(),
+            zero_for_one,
+            sqrt_price_before: get_sqrt_ratio_at_tick(START_TICK),
+            liquidity_before: LIQUIDITY,
+            final_sqrt_price_x96: get_sqrt_ratio_at_tick(final_tick),
+            final_tick: tick_after_swap(get_sqrt_ratio_at_tick(final_tick), zero_for_one),
+        }
+    }
+
+    fn deviations(ticks: [i32; LEG_COUNT]) -> [LegDeviation; LEG_COUNT] {
+        ticks.map(|ticks| LegDeviation {
+            observed_tick: START_TICK + ticks,
+            ticks,
+            output_wei: 0,
+        })
+    }
+
+    fn verifier(warn_ticks: u32, bump_bps: u64) -> ImpactVerifier {
+        let mut config = BotConfig::for_tests();
+        config.impact_warn_ticks = warn_ticks;
+        config.impact_slippage_bump_bps = bump_bps;
+        config.impact_bump_blocks = 100;
+        ImpactVerifier::new(&config)
+    }
+
+    #[test]
+    fn test_deviation_ticks_and_implied_output() {
+        // zeroForOne: fiyat düşer; öngörü 20 tick, gözlem 25 tick
+        let prediction = leg(true, START_TICK - 20);
+        let observed = get_sqrt_ratio_at_tick(START_TICK - 25);
+        let dev = prediction.deviation(observed);
+        // Tam tick sınırına inen zeroForOne fiyatı kontrattaki gibi bir alt tick'tir
+        assert_eq!(dev.observed_tick, START_TICK - 26);
+        assert_eq!(dev.ticks, -5);
+
+        let before = get_sqrt_ratio_at_tick(START_TICK);
+        let expected = get_amount1_delta(observed, before, LIQUIDITY, false)
+            - get_amount1_delta(
+                get_sqrt_ratio_at_tick(START_TICK - 20),
+                before,
+                LIQUIDITY,
+                false,
+            );
+        assert!(dev.output_wei > 0, "further move implies more output");
+        assert_eq!(U256::from(dev.output_wei as u128), expected);
+
+        // Birebir isabet → sıfır sapma
+        let exact = prediction.deviation(prediction.final_sqrt_price_x96);
+        assert_eq!((exact.ticks, exact.output_wei), (0, 0));
+
+        // oneForZero, fiyat öngörülenden az yükseldi → eksi çıktı
+        let up = leg(false, START_TICK + 30);
+        let dev = up.deviation(get_sqrt_ratio_at_tick(START_TICK + 10));
+        assert_eq!(dev.ticks, -20);
+        assert!(dev.output_wei < 0);
+
+        // Fiyat swap yönünün tersine gitti → ima edilen çıktı sıfır
+        assert_eq!(
+            up.implied_output(get_sqrt_ratio_at_tick(START_TICK - 5)),
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn test_for_pair_chains_legs_and_verify_needs_both_reads() {
+        let pools = make_pool_configs();
+        let state = |tick: i32| PoolState {
+            sqrt_price_x96: get_sqrt_ratio_at_tick(tick),
+            tick,
+            liquidity: LIQUIDITY,
+            is_initialized: true,
+            ..PoolState::default()
+        };
+        let amount = U256::from(1_000_000_000_000_000_000u128);
+        let impact = PredictedImpact::for_pair(
+            &pools,
+            &state(START_TICK),
+            &state(START_TICK + 40),
+            amount,
+            true,
+            false,
+        );
+
+        // Bacak A WETH satar (fiyat düşer), bacak B geri alır (fiyat yükselir)
+        assert!(impact.legs[0].final_sqrt_price_x96 < impact.legs[0].sqrt_price_before);
+        assert!(impact.legs[1].final_sqrt_price_x96 > impact.legs[1].sqrt_price_before);
+        assert!(impact.legs[0].final_tick < START_TICK);
+        assert!(impact.legs[1].final_tick >= START_TICK + 40);
+
+        let finals = [
+            Some(impact.legs[0].final_sqrt_price_x96),
+            Some(impact.legs[1].final_sqrt_price_x96),
+        ];
+        let devs = impact.verify(&finals).unwrap();
+        assert_eq!(devs.map(|d| d.ticks), [0, 0]);
+        assert_eq!(impact.verify(&[finals[0], None]), None);
+        assert_eq!(impact.verify(&finals[..1]), None);
+    }
+
+    #[test]
+    fn test_warning_triggers_temporary_slippage_bump() {
+        let mut v = verifier(10, 50);
+        assert_eq!(
+            v.record(1_000, &deviations([3, -10])),
+            None,
+            "at threshold: no warning"
+        );
+        assert_eq!(v.slippage_bump_bps(1_000), 0);
+
+        // Bacak B eşiği aşar → uyarı + IMPACT_BUMP_BLOCKS boyunca artış
+        assert_eq!(v.record(1_010, &deviations([-4, 12])), Some(1));
+        assert_eq!(v.slippage_bump_bps(1_010), 50);
+        assert_eq!(v.slippage_bump_bps(1_110), 50);
+        assert_eq!(v.slippage_bump_bps(1_111), 0);
+
+        // İki bacak da aşarsa en kötüsü raporlanır
+        assert_eq!(v.record(1_020, &deviations([-30, 15])), Some(0));
+
+        assert_eq!(v.stats.verified, 3);
+        assert_eq!(v.stats.warnings, 2);
+        assert!((v.stats.mean_abs_ticks(0) - 37.0 / 3.0).abs() < 1e-12);
+        assert!((v.stats.mean_abs_ticks(1) - 37.0 / 3.0).abs() < 1e-12);
+        assert_eq!(v.stats.max_abs_ticks, [30, 15]);
+
+        // Artış kapalı (0): uyarı verilir, slippage değişmez
+        let mut off = verifier(10, 0);
+        assert_eq!(off.record(1_000, &deviations([50, 0])), Some(0));
+        assert_eq!(off.slippage_bump_bps(1_000), 0);
+    }
+}
@@ -0,0 +1,405 @@
+// ============================================================================
+//  INSTANCE_LOCK v1.0 — Yürütücü Anahtarı Başına Tek Süreç Kilidi
+//
+//  Aynı keystore ile iki süreç çalışırsa ikisi de NonceManager'ı aynı zincir
+//  nonce'undan başlatır: nonce'lar çakışır, TX'ler birbirini ezer.
+//
+//  ✓ Yürütme açıksa ~/.arb/locks/<adres>.lock üzerinde flock(LOCK_EX|LOCK_NB)
+//  ✓ Kilit dosyasına PID + başlangıç zamanı yazılır (teşhis için)
+//  ✓ Kilit canlı bir süreçteyse yürütme kapatılır (gözlem / gölge mod)
+//  ✓ Çökmüş süreçten kalan kilit (PID ölü) devralınır
+//  ✓ Zarif kapanışta kilit bırakılır ve dosya silinir (Drop)
+//  ✓ --force-unlock <adres>: onay istemiyle kilit dosyasını kaldırır
+//
+//  Kilit danışma niteliğindedir (advisory): yalnızca bu botun süreçleri
+//  uyar. flock unix'e özgüdür — diğer platformlarda dosya yalnızca teşhis
+//  bilgisi taşır.
+// ============================================================================
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use alloy::primitives::Address;
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+/// Kilit dosyasının sahibi — dosyaya JSON olarak yazılır
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockOwner {
+    pub pid: u32,
+    /// RFC 3339 (UTC)
+    pub started_at: String,
+}
+
+impl LockOwner {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            started_at: crate::clock::now().to_rfc3339(),
+        }
+    }
+
+    /// Yazan süreç hâlâ çalışıyor mu?
+    pub fn is_alive(&self) -> bool {
+        sys::pid_alive(self.pid)
+    }
+}
+
+impl std::fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PID {} (started {})", self.pid, self.started_at)
+    }
+}
+
+/// Tutulan kilit — drop'ta dosya silinir ve flock bırakılır
+#[derive(Debug)]
+pub struct InstanceLock {
+    file: Option<File>,
+    path: PathBuf,
+}
+
+impl InstanceLock {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        if let Some(file) = self.file.take() {
+            // Önce dosya silinir, sonra fd kapanır (flock bırakılır): kilit
+            // açıldığı anda başka bir sürecin yeni dosyasını silmiş olmayız.
+            let _ = fs::remove_file(&self.path);
+            drop(file);
+        }
+    }
+}
+
+/// Kilit alma sonucu
+#[derive(Debug)]
+pub enum LockAttempt {
+    /// Kilit alındı; `stale` çökmüş bir sürecin devralınan kaydıdır
+    Acquired {
+        lock: InstanceLock,
+        stale: Option<LockOwner>,
+    },
+    /// Kilit canlı bir süreçte (sahip okunamadıysa None) — yürütme açılmaz
+    Held(Option<LockOwner>),
+}
+
+/// Varsayılan kilit dizini: ~/.arb/locks (HOME yoksa çalışma dizini)
+pub fn default_lock_dir() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".arb")
+        .join("locks")
+}
+
+/// Yürütücü adresine bağlı kilit dosyası
+pub fn lock_path(dir: &Path, executor: Address) -> PathBuf {
+    dir.join(format!("{}.lock", executor.to_string().to_lowercase()))
+}
+
+/// Kilidi almayı dene (bloklamaz).
+///
+/// flock tutuluyor ama dosyadaki PID ölü ise (ör. fd'yi miras almış bir alt
+/// süreç) dosya silinip yeniden oluşturulur — yeni inode üzerinde kilit alınır.
+pub fn acquire(path: &Path) -> io::Result<LockAttempt> {
+    crate::secure_fs::create_parent_dir(path)?;
+    try_acquire(path, None)
+}
+
+fn try_acquire(path: &Path, taken_over: Option<LockOwner>) -> io::Result<LockAttempt> {
+    let mut file = crate::secure_fs::open_read_write(path)?;
+    let previous = read_owner(&mut file);
+
+    if sys::try_lock_exclusive(&file)? {
+        // Zarif kapanış dosyayı siler: kalan kayıt çökmüş bir süreçten
+        let stale = taken_over.or(previous);
+        write_owner(&mut file, &LockOwner::current())?;
+        return Ok(LockAttempt::Acquired {
+            lock: InstanceLock {
+                file: Some(file),
+                path: path.to_path_buf(),
+            },
+            stale,
+        });
+    }
+
+    match previous {
+        Some(owner) if taken_over.is_none() && !owner.is_alive() => {
+            fs::remove_file(path)?;
+            try_acquire(path, Some(owner))
+        }
+        owner => Ok(LockAttempt::Held(owner)),
+    }
+}
+
+fn read_owner(file: &mut File) -> Option<LockOwner> {
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    serde_json::from_str(content.trim()).ok()
+}
+
+fn write_owner(file: &mut File, owner: &LockOwner) -> io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    let json = serde_json::to_string(owner).map_err(io::Error::other)?;
+    writeln!(file, "{}", json)?;
+    file.sync_all()
+}
+
+/// Kilit dosyasını `confirm` onay verirse sil. Dönüş: silindi mi?
+pub fn force_unlock(
+    path: &Path,
+    confirm: impl FnOnce(Option<&LockOwner>) -> bool,
+) -> io::Result<bool> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let owner = read_owner(&mut file);
+    if !confirm(owner.as_ref()) {
+        return Ok(false);
+    }
+    fs::remove_file(path)?;
+    Ok(true)
+}
+
+/// CLI: --force-unlock <executor_address>
+pub fn cli_force_unlock(address: &str) -> eyre::Result<()> {
+    let executor: Address = address
+        .parse()
+        .map_err(|e| eyre::eyre!("Invalid executor address '{}': {}", address, e))?;
+    let path = lock_path(&default_lock_dir(), executor);
+
+    let removed = force_unlock(&path, |owner| {
+        match owner {
+            Some(owner) if owner.is_alive() => eprintln!(
+                "  {} Lock holder {} is STILL RUNNING — unlocking lets a second instance share the executor nonce!",
+                "⚠️".red(),
+                owner
+            ),
+            Some(owner) => println!("  {} Lock holder {} is not running", "ℹ️".blue(), owner),
+            None => println!("  {} Lock file has no readable owner", "ℹ️".blue()),
+        }
+        print!("  Type 'unlock' to remove {}: ", path.display());
+        let _ = io::stdout().flush();
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer).is_ok() && answer.trim() == "unlock"
+    })?;
+
+    if removed {
+        println!("  {} Instance lock removed: {}", "🔓".green(), path.display());
+    } else if path.exists() {
+        println!("  {} Aborted — lock kept: {}", "🔒".yellow(), path.display());
+    } else {
+        println!("  {} No instance lock for {}", "ℹ️".blue(), executor);
+    }
+    Ok(())
+}
+
+/// Bloklayan flock(LOCK_EX) — kısa kritik bölgeler için (submit_pacing
+/// lease'i); fd kapanınca bırakılır
+pub(crate) fn lock_blocking(file: &File) -> io::Result<()> {
+    sys::lock_exclusive(file)
+}
+
+/// `pid` hâlâ çalışan bir süreç mi? (doğrulanamayan platformda true)
+pub(crate) fn pid_alive(pid: u32) -> bool {
+    sys::pid_alive(pid)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Platform: flock + PID canlılık kontrolü
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(unix)]
+mod sys {
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+
+    /// flock(LOCK_EX | LOCK_NB): false = başka bir açık dosya kilidi tutuyor
+    pub(super) fn try_lock_exclusive(file: &File) -> io::Result<bool> {
+        // SAFETY: fd açık; flock yalnızca danışma kilidini değiştirir
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+            return Ok(true);
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() == io::ErrorKind::WouldBlock {
+            Ok(false)
+        } else {
+            Err(err)
+        }
+    }
+
+    /// flock(LOCK_EX): kilit bırakılana kadar bekler (EINTR'de yeniden dener)
+    pub(super) fn lock_exclusive(file: &File) -> io::Result<()> {
+        loop {
+            // SAFETY: fd açık; flock yalnızca danışma kilidini değiştirir
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+                return Ok(());
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+
+    /// kill(pid, 0): sinyal gönderilmez, yalnızca süreç varlığı sınanır
+    pub(super) fn pid_alive(pid: u32) -> bool {
+        let Ok(pid) = libc::pid_t::try_from(pid) else {
+            return false;
+        };
+        if pid <= 0 {
+            return false;
+        }
+        // SAFETY: sinyal 0 hiçbir sürece teslim edilmez
+        if unsafe { libc::kill(pid, 0) } == 0 {
+            return true;
+        }
+        // EPERM: süreç var ama başka bir kullanıcıya ait
+        io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use std::fs::File;
+    use std::io;
+
+    pub(super) fn try_lock_exclusive(_file: &File) -> io::Result<bool> {
+        Ok(true)
+    }
+
+    pub(super) fn lock_exclusive(_file: &File) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Doğrulanamayan PID canlı sayılır (kilit devralınmaz)
+    pub(super) fn pid_alive(_pid: u32) -> bool {
+        true
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// pid_max (en çok 2^22) üzerinde: hiçbir süreç bu PID'yi taşıyamaz
+    const DEAD_PID: u32 = 999_999_999;
+
+    fn temp_lock(tag: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("arb_instance_lock_{}_{}", tag, std::process::id()))
+            .join("executor.lock");
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn write_dead_owner(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let owner = LockOwner { pid: DEAD_PID, started_at: "2026-01-01T00:00:00+00:00".into() };
+        fs::write(path, serde_json::to_string(&owner).unwrap()).unwrap();
+    }
+
+    fn file_owner(path: &Path) -> LockOwner {
+        serde_json::from_str(fs::read_to_string(path).unwrap().trim()).unwrap()
+    }
+
+    #[test]
+    fn test_lock_path_per_executor() {
+        let executor: Address = "0xAbCdEf0123456789aBcDeF0123456789abCDef01".parse().unwrap();
+        let path = lock_path(Path::new("/tmp/locks"), executor);
+        assert_eq!(path, Path::new("/tmp/locks/0xabcdef0123456789abcdef0123456789abcdef01.lock"));
+    }
+
+    #[test]
+    fn test_acquire_writes_owner_and_refuses_second_instance() {
+        let path = temp_lock("refuse");
+        let LockAttempt::Acquired { lock, stale } = acquire(&path).unwrap() else {
+            panic!("fresh lock must be acquired");
+        };
+        assert_eq!(stale, None);
+        assert_eq!(file_owner(&path).pid, std::process::id());
+
+        // İkinci açılış (ayrı açık dosya) kilidi alamaz; sahip canlı
+        match acquire(&path).unwrap() {
+            LockAttempt::Held(Some(owner)) => {
+                assert_eq!(owner.pid, std::process::id());
+                assert!(owner.is_alive());
+            }
+            other => panic!("second instance must be refused, got {:?}", other),
+        }
+        drop(lock);
+    }
+
+    /// Zarif kapanış: drop dosyayı siler, sonraki başlangıç temiz kilit alır
+    #[test]
+    fn test_shutdown_releases_and_removes_lock() {
+        let path = temp_lock("shutdown");
+        let LockAttempt::Acquired { lock, .. } = acquire(&path).unwrap() else {
+            panic!("fresh lock must be acquired");
+        };
+        assert_eq!(lock.path(), path);
+        drop(lock);
+        assert!(!path.exists());
+
+        match acquire(&path).unwrap() {
+            LockAttempt::Acquired { stale, .. } => assert_eq!(stale, None),
+            other => panic!("released lock must be reacquired, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stale_lock_from_crashed_process_taken_over() {
+        // Çökmüş süreç: dosya kaldı, flock çekirdek tarafından bırakıldı
+        let path = temp_lock("stale");
+        write_dead_owner(&path);
+        match acquire(&path).unwrap() {
+            LockAttempt::Acquired { lock, stale } => {
+                assert_eq!(stale.map(|o| o.pid), Some(DEAD_PID));
+                assert_eq!(file_owner(&path).pid, std::process::id());
+                drop(lock);
+            }
+            other => panic!("stale lock must be taken over, got {:?}", other),
+        }
+
+        // flock hâlâ tutuluyor ama kaydı yazan PID ölü → yeni dosyada devral
+        write_dead_owner(&path);
+        let orphan = File::open(&path).unwrap();
+        assert!(sys::try_lock_exclusive(&orphan).unwrap());
+        match acquire(&path).unwrap() {
+            LockAttempt::Acquired { stale, .. } => assert_eq!(stale.map(|o| o.pid), Some(DEAD_PID)),
+            other => panic!("dead holder must not block execution, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_force_unlock_requires_confirmation() {
+        let path = temp_lock("force");
+        assert!(!force_unlock(&path, |_| true).unwrap(), "no lock file → nothing to remove");
+
+        write_dead_owner(&path);
+        let mut seen = None;
+        assert!(!force_unlock(&path, |owner| {
+            seen = owner.map(|o| o.pid);
+            false
+        })
+        .unwrap());
+        assert_eq!(seen, Some(DEAD_PID));
+        assert!(path.exists(), "declined prompt keeps the lock");
+
+        assert!(force_unlock(&path, |_| true).unwrap());
+        assert!(!path.exists());
+    }
+}
@@ -11,9 +11,7 @@
 //  File is append-only, auto-rotated at 50MB.
 // ============================================================================
 
-use chrono::Local;
 use serde_json::json;
-use std::fs::OpenOptions;
 use std::io::Write;
 use std::sync::Mutex;
 
@@ -23,6 +21,20 @@ const MAX_LOG_SIZE_BYTES: u64 = 50 * 1024 * 1024; // 50MB
 static LOGGER: std::sync::LazyLock<Mutex<JsonLogger>> =
     std::sync::LazyLock::new(|| Mutex::new(JsonLogger::new()));
 
+/// Log file location (under DATA_DIR)
+#[cfg(not(test))]
+fn log_path() -> std::path::PathBuf {
+    crate::secure_fs::data_path(LOG_FILE)
+}
+
+/// Tests log to a per-process temp dir so `cargo test` leaves the tree untouched
+#[cfg(test)]
+pub(crate) fn log_path() -> std::path::PathBuf {
+    std::env::temp_dir()
+        .join(format!("arb_json_logger_{}", std::process::id()))
+        .join(LOG_FILE)
+}
+
 pub struct JsonLogger {
     path: String,
 }
@@ -30,7 +42,7 @@ pub struct JsonLogger {
 impl JsonLogger {
     fn new() -> Self {
         Self {
-            path: LOG_FILE.to_string(),
+            path: log_path().to_string_lossy().into_owned(),
         }
     }
 
@@ -38,7 +50,7 @@ impl JsonLogger {
         if let Ok(meta) = std::fs::metadata(&self.path) {
             if meta.len() > MAX_LOG_SIZE_BYTES {
                 let rotated = format!("{}.{}.bak", self.path,
-                    Local::now().format("%Y%m%d_%H%M%S"));
+                    crate::clock::local_now().format("%Y%m%d_%H%M%S"));
                 let _ = std::fs::rename(&self.path, &rotated);
             }
         }
@@ -46,10 +58,7 @@ impl JsonLogger {
 
     fn write_entry(&self, entry: &serde_json::Value) {
         self.rotate_if_needed();
-        let file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.path);
+        let file = crate::secure_fs::open_append(std::path::Path::new(&self.path));
         if let Ok(mut f) = file {
             if let Ok(line) = serde_json::to_string(entry) {
                 let _ = writeln!(f, "{}", line);
@@ -61,7 +70,7 @@ impl JsonLogger {
 /// Log a structured JSON entry to bot_logs.jsonl
 pub fn log_json(level: &str, event: &str, data: serde_json::Value) {
     let entry = json!({
-        "ts": Local::now().format("%Y-%m-%dT%H:%M:%S%.3f").to_string(),
+        "ts": crate::clock::log_timestamp(),
         "level": level,
         "event": event,
         "data": data,
@@ -158,3 +167,24 @@ pub fn log_discovery(event: &str, pool_count: usize, source: &str) {
         "source": source,
     }));
 }
+
+/// Log per-pool liquidity distribution analytics (background, per bitmap sync)
+pub fn log_pool_analytics(
+    pool_name: &str,
+    block_number: u64,
+    analytics: &crate::liquidity_analytics::PoolAnalytics,
+) {
+    log_json("info", "liquidity_state_diff", json!({
+        "pool": pool_name,
+        "block": block_number,
+        "bitmap_block": analytics.snapshot_block,
+        // u128/i128 → string (JSON numbers overflow above u64)
+        "liquidity_within_1pct": analytics.liquidity_within[0].to_string(),
+        "liquidity_within_2pct": analytics.liquidity_within[1].to_string(),
+        "liquidity_within_5pct": analytics.liquidity_within[2].to_string(),
+        "concentration_gini": analytics.concentration_gini,
+        "total_position_liquidity": analytics.total_position_liquidity.to_string(),
+        "net_liquidity_change": analytics.net_liquidity_change.to_string(),
+        "compute_us": analytics.compute_us,
+    }));
+}
@@ -174,7 +174,9 @@ impl KeyManager {
         let json = serde_json::to_string_pretty(&keystore)
             .map_err(|e| eyre::eyre!("JSON serialization error: {}", e))?;
 
-        std::fs::write(path, json)
+        // 0o600 + grup/herkes yazabilir dizin reddi (keystore değiştirilebilirse
+        // bir sonraki açılışta saldırganın anahtarı yüklenir)
+        crate::secure_fs::write_secret(Path::new(path), json.as_bytes())
             .map_err(|e| eyre::eyre!("Keystore file write error: {}", e))?;
 
         Ok(())
@@ -411,11 +413,18 @@ mod tests {
     use super::*;
     use std::fs;
 
+    /// Keystore yalnızca özel dizine yazılır — çalışma dizini grup yazılabilir olabilir
+    fn keystore_path(name: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("arb_keystore_{}", std::process::id()));
+        crate::secure_fs::create_dir_all(&dir).unwrap();
+        dir.join(name).to_string_lossy().into_owned()
+    }
+
     #[test]
     fn test_encrypt_decrypt_roundtrip() {
         let private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
         let password = "test_password_123";
-        let path = "test_keystore_roundtrip.enc";
+        let path = &keystore_path("test_keystore_roundtrip.enc");
 
         // Şifrele
         KeyManager::encrypt_and_save(private_key, password, path).unwrap();
@@ -438,7 +447,7 @@ mod tests {
         let private_key = "0xdeadbeef";
         let password = "correct_password";
         let wrong_password = "wrong_password";
-        let path = "test_keystore_wrong_pwd.enc";
+        let path = &keystore_path("test_keystore_wrong_pwd.enc");
 
         KeyManager::encrypt_and_save(private_key, password, path).unwrap();
 
@@ -450,7 +459,7 @@ mod tests {
 
     #[test]
     fn test_corrupted_file_fails() {
-        let path = "test_keystore_corrupt.enc";
+        let path = &keystore_path("test_keystore_corrupt.enc");
         fs::write(path, "this is not valid json").unwrap();
 
         let result = KeyManager::load_from_keystore(path, "any_password");
@@ -476,8 +485,8 @@ mod tests {
     #[test]
     fn test_different_keys_produce_different_ciphertexts() {
         let password = "same_password";
-        let path1 = "test_keystore_diff1.enc";
-        let path2 = "test_keystore_diff2.enc";
+        let path1 = &keystore_path("test_keystore_diff1.enc");
+        let path2 = &keystore_path("test_keystore_diff2.enc");
 
         KeyManager::encrypt_and_save("key_one", password, path1).unwrap();
         KeyManager::encrypt_and_save("key_two", password, path2).unwrap();
@@ -0,0 +1,628 @@
+// ============================================================================
+//  LIQUIDITY_ANALYTICS v1.0 — Boşta Zaman Likidite Dağılımı İstatistikleri
+//
+//  Bloklar arasında bot ~1.9 saniye boyunca boştadır. Bu modül o zamanın
+//  küçük bir dilimini, hot path'e dokunmadan havuz başına likidite
+//  analitiği üretmek için kullanır:
+//
+//  ✓ Mevcut fiyatın ±%1, ±%2, ±%5 bandındaki toplam aktif likidite
+//  ✓ Tick kovaları arasında Gini tarzı likidite yoğunlaşması (0 = düz,
+//    1'e yakın = tek kovada toplanmış)
+//  ✓ Önceki bitmap senkronizasyonundan bu yana eklenen/çekilen net likidite
+//
+//  Ana döngü her bitmap değişikliğinde TickBitmapData'yı (Arc<PoolState>
+//  üzerinden, kopyasız) bir watch kanalına yazar. watch::Sender::send_replace
+//  asla beklemez — analitik görevi ne kadar yavaş olursa olsun blok işleme
+//  gecikmez. Hesaplama sırasında yeni bitmap gelirse tur terk edilir ve en
+//  güncel veriyle yeniden başlanır; zaman bütçesi aşılırsa tur yarıda kalır.
+// ============================================================================
+
+use alloy::primitives::Address;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+use crate::types::{PoolConfig, PoolState, SharedPoolState, TickBitmapData};
+
+/// Raporlanan fiyat bantları (±%1, ±%2, ±%5)
+pub const PRICE_BANDS: [f64; 3] = [0.01, 0.02, 0.05];
+
+/// Kova yürüyüşünde iptal/bütçe kontrolü sıklığı
+const ABORT_CHECK_EVERY: usize = 64;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Veri Yapıları
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Watch kanalına yazılan tek havuz girdisi.
+/// `state` ArcSwap'tan alınan Arc'tır — bitmap kopyalanmaz.
+#[derive(Debug, Clone)]
+pub struct PoolBitmapFeed {
+    pub address: Address,
+    pub name: String,
+    pub tick_spacing: i32,
+    pub state: Arc<PoolState>,
+}
+
+/// Bir bitmap senkronizasyonundan sonra yayınlanan havuz kümesi
+#[derive(Debug, Clone, Default)]
+pub struct AnalyticsFeed {
+    pub block_number: u64,
+    pub pools: Vec<PoolBitmapFeed>,
+}
+
+pub type AnalyticsFeedSender = watch::Sender<Arc<AnalyticsFeed>>;
+pub type AnalyticsFeedReceiver = watch::Receiver<Arc<AnalyticsFeed>>;
+
+/// Havuz başına likidite dağılımı istatistikleri
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoolAnalytics {
+    /// Hesaplamanın dayandığı bitmap bloğu
+    pub snapshot_block: u64,
+    /// PRICE_BANDS sırasıyla bant içi toplam aktif likidite
+    /// (bandı kesen her tick_spacing kovasının aktif likiditesi toplanır)
+    pub liquidity_within: [u128; 3],
+    /// ±%5 penceresindeki kovalar üzerinde Gini katsayısı
+    pub concentration_gini: f64,
+    /// Taranan aralıktaki pozisyon likiditesi toplamı (Σ liquidity_gross / 2)
+    pub total_position_liquidity: u128,
+    /// Önceki bitmap senkronizasyonuna göre net likidite değişimi
+    pub net_liquidity_change: i128,
+    /// Hesaplama süresi (mikrosaniye)
+    pub compute_us: u64,
+}
+
+/// Tur sonucu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// Tüm havuzlar hesaplandı
+    Completed,
+    /// Hesaplama sırasında yeni bitmap geldi — tur terk edildi
+    Abandoned,
+    /// Zaman bütçesi aşıldı — tamamlanan havuzlar kaydedildi
+    OverBudget,
+}
+
+/// Paylaşılan analitik deposu (stats kutusu okur, analitik görevi yazar)
+#[derive(Debug, Default)]
+pub struct AnalyticsStore {
+    pub pools: HashMap<Address, PoolAnalytics>,
+    /// Son tamamlanan turun feed bloğu
+    pub last_block: u64,
+    pub completed_runs: u64,
+    pub abandoned_runs: u64,
+    pub over_budget_runs: u64,
+}
+
+pub type SharedPoolAnalytics = Arc<RwLock<AnalyticsStore>>;
+
+impl AnalyticsStore {
+    /// Havuz sonucunu kaydet; net değişimi önceki senkronizasyona göre hesapla.
+    /// Aynı bitmap bloğu yeniden hesaplanırsa önceki net değişim korunur.
+    fn record(&mut self, address: Address, mut analytics: PoolAnalytics) -> PoolAnalytics {
+        if let Some(prev) = self.pools.get(&address) {
+            analytics.net_liquidity_change = if prev.snapshot_block == analytics.snapshot_block {
+                prev.net_liquidity_change
+            } else {
+                analytics.total_position_liquidity as i128 - prev.total_position_liquidity as i128
+            };
+        }
+        self.pools.insert(address, analytics.clone());
+        analytics
+    }
+}
+
+/// Boş feed ile watch kanalı oluştur
+pub fn channel() -> (AnalyticsFeedSender, AnalyticsFeedReceiver) {
+    watch::channel(Arc::new(AnalyticsFeed::default()))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Metrik Hesaplamaları (saf fonksiyonlar)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Fiyat bandını tick ofsetlerine çevir: price = 1.0001^tick
+/// ±pct → [ln(1-pct), ln(1+pct)] / ln(1.0001)
+pub fn band_tick_offsets(pct: f64) -> (i32, i32) {
+    let ln_base = 1.0001f64.ln();
+    (
+        ((1.0 - pct).ln() / ln_base).round() as i32,
+        ((1.0 + pct).ln() / ln_base).round() as i32,
+    )
+}
+
+/// [lo, hi) tick aralığını kesen kovaların aktif likiditesi (artan sırada).
+///
+/// Mevcut kovadaki aktif likidite `active_liquidity`'dir. Yukarı yürürken
+/// kova sınırı t geçildiğinde L += liquidityNet[t], aşağı yürürken
+/// L -= liquidityNet[t]. `should_abort` true dönerse None.
+pub fn bucket_liquidity(
+    bitmap: &TickBitmapData,
+    current_tick: i32,
+    active_liquidity: u128,
+    tick_spacing: i32,
+    lo: i32,
+    hi: i32,
+    should_abort: &dyn Fn() -> bool,
+) -> Option<Vec<(i32, u128)>> {
+    let spacing = tick_spacing.max(1);
+    let net = |tick: i32| bitmap.ticks.get(&tick).map(|t| t.liquidity_net).unwrap_or(0);
+    let current_start = current_tick.div_euclid(spacing) * spacing;
+    let mut steps = 0usize;
+
+    // Aşağı: mevcut kovanın altındaki kovalar
+    let mut below = Vec::new();
+    let mut liquidity = active_liquidity as i128;
+    let mut start = current_start;
+    while start > lo {
+        liquidity = (liquidity - net(start)).max(0);
+        start -= spacing;
+        below.push((start, liquidity as u128));
+        steps += 1;
+        if steps.is_multiple_of(ABORT_CHECK_EVERY) && should_abort() {
+            return None;
+        }
+    }
+    below.reverse();
+
+    // Yukarı: mevcut kova ve üstü
+    let mut buckets = below;
+    let mut liquidity = active_liquidity as i128;
+    let mut start = current_start;
+    while start < hi {
+        buckets.push((start, liquidity as u128));
+        start += spacing;
+        liquidity = (liquidity + net(start)).max(0);
+        steps += 1;
+        if steps.is_multiple_of(ABORT_CHECK_EVERY) && should_abort() {
+            return None;
+        }
+    }
+
+    Some(buckets)
+}
+
+/// Gini katsayısı: 0 = tamamen düz dağılım, (n-1)/n = tek kovada toplanmış
+pub fn gini(values: &[u128]) -> f64 {
+    let n = values.len();
+    if n == 0 {
+        return 0.0;
+    }
+    let mut sorted: Vec<f64> = values.iter().map(|&v| v as f64).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let total: f64 = sorted.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (i as f64 + 1.0) * v)
+        .sum();
+    let n = n as f64;
+    (2.0 * weighted) / (n * total) - (n + 1.0) / n
+}
+
+/// Taranan aralıktaki toplam pozisyon likiditesi.
+/// Her pozisyon iki sınır tick'inin liquidity_gross'una katkı yapar.
+pub fn total_position_liquidity(bitmap: &TickBitmapData) -> u128 {
+    bitmap
+        .ticks
+        .values()
+        .fold(0u128, |acc, t| acc.saturating_add(t.liquidity_gross))
+        / 2
+}
+
+/// Tek havuz için tüm metrikleri hesapla. Bitmap yoksa veya iptal edilirse None.
+/// `net_liquidity_change` burada 0'dır — depo önceki sonuçla karşılaştırır.
+pub fn compute_pool_analytics(
+    feed: &PoolBitmapFeed,
+    should_abort: &dyn Fn() -> bool,
+) -> Option<PoolAnalytics> {
+    let start = Instant::now();
+    let bitmap = feed.state.tick_bitmap.as_deref()?;
+    let tick = feed.state.tick;
+
+    // ±%5 penceresi, bitmap'in taradığı aralıkla sınırlı
+    let (wide_lo, wide_hi) = band_tick_offsets(PRICE_BANDS[PRICE_BANDS.len() - 1]);
+    let (mut window_lo, mut window_hi) = (tick + wide_lo, tick + wide_hi);
+    if bitmap.scan_range > 0 {
+        let range = bitmap.scan_range as i32;
+        window_lo = window_lo.max(tick - range);
+        window_hi = window_hi.min(tick + range);
+    }
+
+    let buckets = bucket_liquidity(
+        bitmap,
+        tick,
+        feed.state.liquidity,
+        feed.tick_spacing,
+        window_lo,
+        window_hi,
+        should_abort,
+    )?;
+    let spacing = feed.tick_spacing.max(1);
+
+    let mut liquidity_within = [0u128; 3];
+    for (slot, pct) in liquidity_within.iter_mut().zip(PRICE_BANDS.iter()) {
+        let (lo_off, hi_off) = band_tick_offsets(*pct);
+        let (lo, hi) = (tick + lo_off, tick + hi_off);
+        *slot = buckets
+            .iter()
+            .filter(|(b, _)| b + spacing > lo && *b < hi)
+            .fold(0u128, |acc, (_, l)| acc.saturating_add(*l));
+    }
+
+    let values: Vec<u128> = buckets.iter().map(|(_, l)| *l).collect();
+
+    Some(PoolAnalytics {
+        snapshot_block: bitmap.snapshot_block,
+        liquidity_within,
+        concentration_gini: gini(&values),
+        total_position_liquidity: total_position_liquidity(bitmap),
+        net_liquidity_change: 0,
+        compute_us: start.elapsed().as_micros() as u64,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Yayın (ana döngü) + Arka Plan Görevi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Bitmap imzası değiştiyse feed'i yayınla (ana döngü, blok başına bir kez).
+///
+/// Yalnızca Arc klonlanır; `send_replace` alıcıyı beklemez.
+/// İmza: (havuz, bitmap bloğu, başlatılmış tick sayısı).
+pub fn publish_if_changed(
+    tx: &AnalyticsFeedSender,
+    last_signature: &mut Vec<(Address, u64, usize)>,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    block_number: u64,
+) -> bool {
+    let mut signature = Vec::with_capacity(pools.len());
+    let mut feeds = Vec::with_capacity(pools.len());
+    for (config, state) in pools.iter().zip(states.iter()) {
+        let state = state.load_full();
+        if let Some(ref bitmap) = state.tick_bitmap {
+            signature.push((config.address, bitmap.snapshot_block, bitmap.ticks.len()));
+            feeds.push(PoolBitmapFeed {
+                address: config.address,
+                name: config.name.clone(),
+                tick_spacing: config.tick_spacing,
+                state,
+            });
+        }
+    }
+    if signature.is_empty() || signature == *last_signature {
+        return false;
+    }
+    *last_signature = signature;
+    tx.send_replace(Arc::new(AnalyticsFeed {
+        block_number,
+        pools: feeds,
+    }));
+    true
+}
+
+/// Tek analitik turu: feed'deki havuzları sırayla hesapla.
+/// Her havuz arasında ve kova yürüyüşü içinde yeni feed / bütçe kontrol edilir.
+async fn run_once<F>(
+    feed: &AnalyticsFeed,
+    rx: &AnalyticsFeedReceiver,
+    budget: Duration,
+    compute: &F,
+) -> (RunOutcome, Vec<(Address, String, PoolAnalytics)>)
+where
+    F: Fn(&PoolBitmapFeed, &dyn Fn() -> bool) -> Option<PoolAnalytics> + Sync,
+{
+    let deadline = Instant::now() + budget;
+    let newer_feed = || rx.has_changed().unwrap_or(true);
+    let should_abort = || newer_feed() || Instant::now() >= deadline;
+    let mut results = Vec::with_capacity(feed.pools.len());
+
+    for pool in &feed.pools {
+        if newer_feed() {
+            return (RunOutcome::Abandoned, results);
+        }
+        if Instant::now() >= deadline {
+            return (RunOutcome::OverBudget, results);
+        }
+        match compute(pool, &should_abort) {
+            Some(analytics) => results.push((pool.address, pool.name.clone(), analytics)),
+            None if newer_feed() => return (RunOutcome::Abandoned, results),
+            None if Instant::now() >= deadline => return (RunOutcome::OverBudget, results),
+            // Bitmap yok — havuz atlanır
+            None => {}
+        }
+        // Diğer görevlere (blok işleme) nefes aldır
+        tokio::task::yield_now().await;
+    }
+    (RunOutcome::Completed, results)
+}
+
+/// Arka plan analitik döngüsü. Her yeni feed'de bir tur çalıştırır;
+/// terk edilen turlar en güncel feed ile hemen yeniden başlar.
+pub async fn run_analytics_loop<F>(
+    mut rx: AnalyticsFeedReceiver,
+    store: SharedPoolAnalytics,
+    budget: Duration,
+    cancel: CancellationToken,
+    compute: F,
+) where
+    F: Fn(&PoolBitmapFeed, &dyn Fn() -> bool) -> Option<PoolAnalytics> + Send + Sync,
+{
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            changed = rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+            }
+        }
+        let feed = rx.borrow_and_update().clone();
+        let (outcome, results) = run_once(&feed, &rx, budget, &compute).await;
+
+        let mut guard = store.write();
+        match outcome {
+            RunOutcome::Abandoned => {
+                guard.abandoned_runs += 1;
+                continue;
+            }
+            RunOutcome::OverBudget => guard.over_budget_runs += 1,
+            RunOutcome::Completed => {
+                guard.completed_runs += 1;
+                guard.last_block = feed.block_number;
+            }
+        }
+        for (address, name, analytics) in results {
+            let recorded = guard.record(address, analytics);
+            crate::json_logger::log_pool_analytics(&name, feed.block_number, &recorded);
+            if recorded.net_liquidity_change != 0 {
+                eprintln!(
+                    "     🌊 [LiqAnalytics] {} ΔL {:+} | ±1% {} | Gini {:.3} [bitmap #{}]",
+                    name,
+                    recorded.net_liquidity_change,
+                    recorded.liquidity_within[0],
+                    recorded.concentration_gini,
+                    recorded.snapshot_block,
+                );
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TickInfo;
+    use alloy::primitives::address;
+
+    const POOL: Address = address!("d0b53D9277642d899DF5C87A3966A349A798F224");
+
+    /// Pozisyon listesinden sentetik bitmap: (tick_lower, tick_upper, L)
+    fn synthetic_bitmap(positions: &[(i32, i32, u128)], scan_range: u32) -> TickBitmapData {
+        let mut bitmap = TickBitmapData::empty();
+        bitmap.scan_range = scan_range;
+        for &(lower, upper, amount) in positions {
+            for (tick, net) in [(lower, amount as i128), (upper, -(amount as i128))] {
+                let info = bitmap.ticks.entry(tick).or_insert(TickInfo {
+                    liquidity_gross: 0,
+                    liquidity_net: 0,
+                    initialized: true,
+                    staked_liquidity_net: 0,
+                });
+                info.liquidity_gross += amount;
+                info.liquidity_net += net;
+            }
+        }
+        bitmap
+    }
+
+    fn make_feed(bitmap: TickBitmapData, tick: i32, liquidity: u128, spacing: i32) -> PoolBitmapFeed {
+        PoolBitmapFeed {
+            address: POOL,
+            name: "UniV3-test".into(),
+            tick_spacing: spacing,
+            state: Arc::new(PoolState {
+                tick,
+                liquidity,
+                tick_bitmap: Some(Box::new(bitmap)),
+                ..PoolState::default()
+            }),
+        }
+    }
+
+    fn never() -> bool {
+        false
+    }
+
+    #[test]
+    fn test_band_tick_offsets() {
+        assert_eq!(band_tick_offsets(0.01), (-101, 100));
+        assert_eq!(band_tick_offsets(0.02), (-202, 198));
+        assert_eq!(band_tick_offsets(0.05), (-513, 488));
+    }
+
+    #[test]
+    fn test_gini_known_values() {
+        assert!(gini(&[]).abs() < 1e-12);
+        assert!(gini(&[5, 5, 5, 5]).abs() < 1e-12);
+        assert!((gini(&[0, 0, 0, 4]) - 0.75).abs() < 1e-12);
+        assert!(gini(&[0, 0, 0]).abs() < 1e-12);
+    }
+
+    /// Düz dağılım: her kovada aynı L → bant toplamları kova sayısıyla orantılı, Gini 0
+    #[test]
+    fn test_uniform_distribution() {
+        let l = 1_000_000u128;
+        let bitmap = synthetic_bitmap(&[(-10_000, 10_000, l)], 1000);
+        let feed = make_feed(bitmap, 0, l, 10);
+        let a = compute_pool_analytics(&feed, &never).unwrap();
+
+        // Kovalar [b, b+10): ±1% = (-111, 100) → 21 kova, ±2% → 41, ±5% → 101
+        assert_eq!(a.liquidity_within, [21 * l, 41 * l, 101 * l]);
+        assert!(a.concentration_gini.abs() < 1e-9);
+        assert_eq!(a.total_position_liquidity, l);
+    }
+
+    /// Tek dar pozisyon: tüm likidite 2 kovada → Gini = 1 - 2/n
+    #[test]
+    fn test_concentrated_distribution() {
+        let l = 5_000u128;
+        let bitmap = synthetic_bitmap(&[(-10, 10, l)], 1000);
+        let feed = make_feed(bitmap, 0, l, 10);
+        let a = compute_pool_analytics(&feed, &never).unwrap();
+
+        assert_eq!(a.liquidity_within, [2 * l, 2 * l, 2 * l]);
+        assert!((a.concentration_gini - (1.0 - 2.0 / 101.0)).abs() < 1e-9);
+    }
+
+    /// Geniş + dar pozisyon, fiyat dar pozisyonun içinde: yukarı/aşağı yürüyüş doğru
+    #[test]
+    fn test_overlapping_positions_walk() {
+        let wide = 1_000u128;
+        let narrow = 300u128;
+        let bitmap = synthetic_bitmap(&[(-1000, 1000, wide), (0, 50, narrow)], 1000);
+        let feed = make_feed(bitmap.clone(), 20, wide + narrow, 10);
+
+        let buckets = bucket_liquidity(&bitmap, 20, wide + narrow, 10, -30, 70, &never).unwrap();
+        assert_eq!(
+            buckets,
+            vec![
+                (-30, wide),
+                (-20, wide),
+                (-10, wide),
+                (0, wide + narrow),
+                (10, wide + narrow),
+                (20, wide + narrow),
+                (30, wide + narrow),
+                (40, wide + narrow),
+                (50, wide),
+                (60, wide),
+            ]
+        );
+
+        // ±1% etrafında 20: (-81, 120) → kovalar -90..=110 → 21 kova, 5'i dar pozisyonlu
+        let a = compute_pool_analytics(&feed, &never).unwrap();
+        assert_eq!(a.liquidity_within[0], 21 * wide + 5 * narrow);
+        assert_eq!(a.total_position_liquidity, wide + narrow);
+    }
+
+    /// Bant, bitmap'in taradığı aralıkla sınırlanır
+    #[test]
+    fn test_window_clamped_to_scan_range() {
+        let l = 100u128;
+        let bitmap = synthetic_bitmap(&[(-10_000, 10_000, l)], 50);
+        let feed = make_feed(bitmap, 0, l, 10);
+        let a = compute_pool_analytics(&feed, &never).unwrap();
+        // (-50, 50) → kovalar -50..=40 → 10 kova, tüm bantlar aynı
+        assert_eq!(a.liquidity_within, [10 * l, 10 * l, 10 * l]);
+    }
+
+    #[test]
+    fn test_abort_returns_none() {
+        let bitmap = synthetic_bitmap(&[(-10_000, 10_000, 1)], 10_000);
+        let feed = make_feed(bitmap, 0, 1, 1);
+        assert!(compute_pool_analytics(&feed, &|| true).is_none());
+    }
+
+    #[test]
+    fn test_net_liquidity_change_between_syncs() {
+        let mut store = AnalyticsStore::default();
+        let mut first = PoolAnalytics {
+            snapshot_block: 100,
+            total_position_liquidity: 1_000,
+            ..PoolAnalytics::default()
+        };
+        assert_eq!(store.record(POOL, first.clone()).net_liquidity_change, 0);
+
+        // Yeni senkronizasyon: 250 çekildi
+        first.snapshot_block = 105;
+        first.total_position_liquidity = 750;
+        assert_eq!(store.record(POOL, first.clone()).net_liquidity_change, -250);
+
+        // Aynı bitmap yeniden hesaplandı → önceki değişim korunur
+        assert_eq!(store.record(POOL, first).net_liquidity_change, -250);
+    }
+
+    /// Yavaş hesaplama watch gönderimini asla geciktirmez; terk edilen turlar
+    /// en güncel feed ile yeniden başlar.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_slow_computation_never_delays_sender() {
+        let (tx, rx) = channel();
+        let store: SharedPoolAnalytics = Arc::new(RwLock::new(AnalyticsStore::default()));
+        let cancel = CancellationToken::new();
+
+        // Her havuz için ~40ms süren, iptale duyarlı yavaş hesaplama
+        let slow = |feed: &PoolBitmapFeed, should_abort: &dyn Fn() -> bool| {
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(40) {
+                if should_abort() {
+                    return None;
+                }
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            Some(PoolAnalytics {
+                snapshot_block: feed.state.last_block,
+                ..PoolAnalytics::default()
+            })
+        };
+
+        let task = tokio::spawn(run_analytics_loop(
+            rx,
+            Arc::clone(&store),
+            Duration::from_secs(5),
+            cancel.clone(),
+            slow,
+        ));
+
+        let mut worst_send = Duration::ZERO;
+        for block in 1..=20u64 {
+            let feed = AnalyticsFeed {
+                block_number: block,
+                pools: vec![PoolBitmapFeed {
+                    address: POOL,
+                    name: "slow".into(),
+                    tick_spacing: 10,
+                    state: Arc::new(PoolState {
+                        last_block: block,
+                        ..PoolState::default()
+                    }),
+                }],
+            };
+            let send_start = Instant::now();
+            tx.send_replace(Arc::new(feed));
+            worst_send = worst_send.max(send_start.elapsed());
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(worst_send < Duration::from_millis(5), "send gecikti: {:?}", worst_send);
+
+        // Son feed tamamlanmalı
+        let wait_start = Instant::now();
+        while store.read().last_block != 20 && wait_start.elapsed() < Duration::from_secs(2) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        cancel.cancel();
+        let _ = task.await;
+
+        let guard = store.read();
+        assert_eq!(guard.last_block, 20);
+        assert_eq!(guard.pools.get(&POOL).map(|a| a.snapshot_block), Some(20));
+        assert!(guard.abandoned_runs >= 1, "yeni feed yavaş turu terk ettirmeli");
+
+        // liquidity_state_diff satırları çalışma dizinine değil test loguna gider
+        let log_path = crate::json_logger::log_path();
+        assert!(!log_path.starts_with(std::env::current_dir().unwrap()));
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        assert!(log.lines().any(|l| l.contains("liquidity_state_diff") && l.contains("\"slow\"")));
+    }
+}
@@ -0,0 +1,355 @@
+// ============================================================================
+//  LOG_EVENT v1.0 — Terminal Çıktısının Yapılandırılmış JSON Modu (LOG_FORMAT)
+//
+//  Emoji / renkli satırlar etkileşimde okunaklı, journald / Loki'ye
+//  borulanınca ayrıştırılamaz. LOG_FORMAT=json iken stdout/stderr satır
+//  başına tek bir JSON nesnesidir:
+//
+//  ✓ Olaylar: block_update, spread, opportunity, simulation_failure,
+//    tx_sent, tx_confirmed, circuit_breaker, reconnect — tutarlı alan adları,
+//    sayılar biçimlenmiş string değil sayı
+//  ✓ Olayı olan yerde güzel satırlar basılmaz; kalan konsol satırları
+//    {"event":"message"} nesnesine sarılır (ANSI renkleri kapalı, yalnızca
+//    çerçeve çizgisinden oluşan satırlar atılır)
+//  ✓ Zarf bot_logs.jsonl ile aynı: ts, level, event
+//  ✓ Gölge log (shadow_analytics.jsonl) ayrı yazılır ama fırsat alanlarını
+//    aynı OpportunityFields'tan alır — alan adları birebir eşleşir
+//  ✓ LOG_FORMAT=pretty (varsayılan): bugünkü çıktı, değişiklik yok
+//
+//  Biçim süreç geneli tek bayrakta (config yüklenince bir kez); console
+//  sarmalamayı, çağıranlar olay/güzel satır seçimini buradan sorar.
+// ============================================================================
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::console::{Priority, Stream};
+use crate::types::{ArbitrageOpportunity, PoolConfig};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Biçim
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Emoji / renkli terminal çıktısı
+    #[default]
+    Pretty,
+    /// Satır başına bir JSON nesnesi
+    Json,
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Pretty => write!(f, "pretty"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Biçimi ayarla (main, config yüklenince — ilk konsol satırından önce)
+pub fn init(format: LogFormat) {
+    let json = format == LogFormat::Json;
+    JSON.store(json, Ordering::Relaxed);
+    if json {
+        // Sarılan mesajlara ANSI kaçışları girmesin
+        colored::control::set_override(false);
+    }
+}
+
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Olay Yapıları
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Fırsatın kimlik + fiyat alanları — opportunity / simulation_failure
+/// olayları ve gölge log satırı aynı yapıyı serileştirir
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpportunityFields {
+    pub block: u64,
+    pub opportunity_id: String,
+    pub pool_pair: String,
+    pub buy_pool: String,
+    pub buy_pool_addr: String,
+    pub buy_price_quote: f64,
+    pub sell_pool: String,
+    pub sell_pool_addr: String,
+    pub sell_price_quote: f64,
+    pub spread_pct: f64,
+    pub optimal_amount_weth: f64,
+    /// Net kâr tahmini (WETH)
+    pub expected_profit: f64,
+}
+
+impl OpportunityFields {
+    pub fn new(opportunity: &ArbitrageOpportunity, pools: &[PoolConfig], block: u64) -> Self {
+        let buy_pool = &pools[opportunity.buy_pool_idx];
+        let sell_pool = &pools[opportunity.sell_pool_idx];
+        let round = |v: f64, scale: f64| (v * scale).round() / scale;
+        Self {
+            block,
+            opportunity_id: crate::replay::opportunity_id(block, buy_pool.address, sell_pool.address),
+            // "UniV3-WETH/cbBTC - Aero-WETH/cbBTC"
+            pool_pair: format!("{} - {}", buy_pool.name, sell_pool.name),
+            buy_pool: buy_pool.name.clone(),
+            buy_pool_addr: format!("{}", buy_pool.address),
+            buy_price_quote: round(opportunity.buy_price_quote, 1e6),
+            sell_pool: sell_pool.name.clone(),
+            sell_pool_addr: format!("{}", sell_pool.address),
+            sell_price_quote: round(opportunity.sell_price_quote, 1e6),
+            spread_pct: round(opportunity.spread_pct, 1e6),
+            optimal_amount_weth: round(opportunity.optimal_amount_weth, 1e8),
+            expected_profit: round(opportunity.expected_profit_weth, 1e8),
+        }
+    }
+}
+
+/// Havuzun blok sonu fiyatı (quote / WETH)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PoolPrice {
+    pub pool: String,
+    pub price_quote: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockUpdate {
+    pub block: u64,
+    pub sync_ms: u64,
+    pub pools: Vec<PoolPrice>,
+    pub warmup_armed: bool,
+}
+
+/// Çiftin spot spread'i + referans boyutta yürütülebilir fiyatlar
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Spread {
+    pub block: u64,
+    pub buy_pool: String,
+    pub sell_pool: String,
+    pub spread_pct: f64,
+    pub spread_quote: f64,
+    pub probe_weth: f64,
+    pub pool_a_sell: f64,
+    pub pool_a_buy: f64,
+    pub pool_b_sell: f64,
+    pub pool_b_buy: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Opportunity {
+    #[serde(flatten)]
+    pub fields: OpportunityFields,
+    pub gas_used: u64,
+    pub sim_source: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulationFailure {
+    #[serde(flatten)]
+    pub fields: OpportunityFields,
+    pub sim_error: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TxSent {
+    pub tx_hash: String,
+    pub nonce: u64,
+    /// Fırsatın bulunduğu blok (hedef: block + 1)
+    pub block: u64,
+    pub route: &'static str,
+    pub expected_profit_weth: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TxConfirmed {
+    pub tx_hash: String,
+    pub block: u64,
+    /// status=1 receipt
+    pub landed: bool,
+    pub gas_used: u64,
+    pub gas_spent_weth: f64,
+    pub realized_pnl_weth: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CircuitBreaker {
+    /// "pair_blacklisted" | "paused" | "probing" | "resumed"
+    pub state: &'static str,
+    pub block: u64,
+    /// Yalnızca çift kara listesinde
+    pub pair: Option<String>,
+    pub consecutive_failures: Option<u32>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Reconnect {
+    pub attempt: u32,
+    pub delay_ms: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum LogEvent {
+    BlockUpdate(BlockUpdate),
+    Spread(Spread),
+    Opportunity(Opportunity),
+    SimulationFailure(SimulationFailure),
+    TxSent(TxSent),
+    TxConfirmed(TxConfirmed),
+    CircuitBreaker(CircuitBreaker),
+    Reconnect(Reconnect),
+}
+
+impl LogEvent {
+    /// bot_logs.jsonl ile aynı seviye adları
+    pub fn level(&self) -> &'static str {
+        match self {
+            LogEvent::BlockUpdate(_) | LogEvent::Spread(_) => "info",
+            LogEvent::Opportunity(_) | LogEvent::TxSent(_) | LogEvent::TxConfirmed(_) => "trade",
+            LogEvent::SimulationFailure(_) | LogEvent::CircuitBreaker(_) | LogEvent::Reconnect(_) => "warn",
+        }
+    }
+
+    /// Blok / spread satırları kuyruk doluysa atılabilir (güzel karşılıkları gibi)
+    fn priority(&self) -> Priority {
+        match self {
+            LogEvent::BlockUpdate(_) | LogEvent::Spread(_) => Priority::Low,
+            _ => Priority::High,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Satır Üretimi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Zarfı ekle: {"ts", "level", "event", ...alanlar}
+fn envelope(level: &str, mut value: serde_json::Value) -> String {
+    if let Some(object) = value.as_object_mut() {
+        object.insert("ts".into(), crate::clock::log_timestamp().into());
+        object.insert("level".into(), level.into());
+    }
+    value.to_string()
+}
+
+pub fn event_line(event: &LogEvent) -> String {
+    envelope(event.level(), serde_json::to_value(event).unwrap_or_default())
+}
+
+/// Konsol satırını {"event":"message"} nesnesine sar — çerçeve / boş satırda None
+pub fn message_line(stream: Stream, text: &str) -> Option<String> {
+    let message = text.trim();
+    let decoration = |c: char| c.is_whitespace() || ('\u{2500}'..='\u{257f}').contains(&c) || "=-|".contains(c);
+    if message.chars().all(decoration) {
+        return None;
+    }
+    let level = match stream {
+        Stream::Stdout => "info",
+        Stream::Stderr => "warn",
+    };
+    Some(envelope(level, serde_json::json!({ "event": "message", "message": message })))
+}
+
+/// JSON modunda olayı yaz ve true döndür — çağıran güzel satırları atlar.
+/// Pretty modda olay hiç kurulmaz.
+pub fn json(event: impl FnOnce() -> LogEvent) -> bool {
+    if !is_json() {
+        return false;
+    }
+    let event = event();
+    crate::console::write_line(event.priority(), Stream::Stdout, event_line(&event));
+    true
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> serde_json::Value {
+        serde_json::from_str(line).expect("one JSON object per line")
+    }
+
+    /// Olay etiketi + zarf + sayısal alanlar (string değil)
+    #[test]
+    fn test_event_line_shape() {
+        let line = event_line(&LogEvent::TxConfirmed(TxConfirmed {
+            tx_hash: "0xabc".into(),
+            block: 1_001,
+            landed: false,
+            gas_used: 210_000,
+            gas_spent_weth: 0.00021,
+            realized_pnl_weth: -0.00021,
+        }));
+        assert!(!line.contains('\n'));
+        let json = parse(&line);
+        assert_eq!(json["event"], "tx_confirmed");
+        assert_eq!(json["level"], "trade");
+        assert!(json["ts"].is_string());
+        assert_eq!(json["block"], 1_001);
+        assert_eq!(json["gas_used"], 210_000);
+        assert!(json["gas_spent_weth"].is_f64());
+        assert_eq!(json["landed"], false);
+
+        let reconnect = parse(&event_line(&LogEvent::Reconnect(Reconnect {
+            attempt: 4,
+            delay_ms: 350,
+            reason: "WSS stream closed".into(),
+        })));
+        assert_eq!(reconnect["event"], "reconnect");
+        assert_eq!(reconnect["level"], "warn");
+        assert_eq!(reconnect["delay_ms"], 350);
+    }
+
+    /// Fırsat alanları olayda düz (flatten) — gölge log ile aynı adlar
+    #[test]
+    fn test_opportunity_fields_are_flattened() {
+        let fields = OpportunityFields {
+            block: 500,
+            opportunity_id: "500-aaaaaaaa-bbbbbbbb".into(),
+            pool_pair: "A - B".into(),
+            buy_pool: "A".into(),
+            buy_pool_addr: "0xaa".into(),
+            buy_price_quote: 2500.0,
+            sell_pool: "B".into(),
+            sell_pool_addr: "0xbb".into(),
+            sell_price_quote: 2512.5,
+            spread_pct: 0.5,
+            optimal_amount_weth: 1.25,
+            expected_profit: 0.0042,
+        };
+        let json = parse(&event_line(&LogEvent::SimulationFailure(SimulationFailure {
+            fields: fields.clone(),
+            sim_error: "execution reverted".into(),
+        })));
+        assert_eq!(json["event"], "simulation_failure");
+        let shared = serde_json::to_value(&fields).unwrap();
+        for (key, value) in shared.as_object().unwrap() {
+            assert_eq!(&json[key], value, "field {}", key);
+        }
+        assert_eq!(json["spread_pct"], 0.5);
+    }
+
+    /// Konsol satırları mesaja sarılır; çerçeve ve boş satırlar atılır
+    #[test]
+    fn test_message_line_wrapping() {
+        let json = parse(&message_line(Stream::Stderr, "     ⚠️ Safety net: timeout  ").unwrap());
+        assert_eq!(json["event"], "message");
+        assert_eq!(json["level"], "warn");
+        assert_eq!(json["message"], "⚠️ Safety net: timeout");
+        assert!(message_line(Stream::Stdout, "").is_none());
+        assert!(message_line(Stream::Stdout, "  └──────────────┘").is_none());
+        assert!(message_line(Stream::Stdout, "  =========").is_none());
+        assert_eq!(parse(&message_line(Stream::Stdout, "│  Uptime : 1h").unwrap())["level"], "info");
+    }
+}
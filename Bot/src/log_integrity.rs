@@ -0,0 +1,464 @@
+// ============================================================================
+//  LOG_INTEGRITY v1.0 — Gölge / İşlem Logu Bütünlük Meta Verisi
+//
+//  Paylaşılan gölge loglarının hangi kod sürümü ve hangi girdilerle
+//  üretildiği mekanik olarak kontrol edilebilsin diye her satıra:
+//
+//  ✓ build        — sürüm + git hash (+"-dirty"), build.rs'ten gömülü
+//  ✓ config       — o an yürürlükteki ayarların parmak izi (sırlar hariç)
+//  ✓ input_digest — iki havuzun sqrt_price_x96 / tick / liquidity / efektif
+//                   fee / bitmap snapshot_block'u + NR yön ve referans fiyatı
+//                   üzerinden SHA-256 (kanonik bayt dizilimi, JSON sırasından
+//                   bağımsız)
+//  ✓ snapshot_id  — aynı girdilerin arşivlendiği snapshot (varsa)
+//
+//  Maliyet fırsat başına bir küçük struct + bir SHA-256 — blok başına değil.
+//  --verify-log <dosya>: snapshot'ı bulunan satırların digest'ini yeniden
+//  hesaplar ve log alanlarını snapshot'la karşılaştırır.
+// ============================================================================
+
+use alloy::primitives::{Address, U256};
+use colored::*;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::snapshot::{OpportunitySnapshot, PoolSnapshot};
+use crate::types::{ArbitrageOpportunity, BotConfig, PoolConfig, PoolState};
+
+/// Derleme kimliği: "<sürüm>+<git hash>[-dirty]"
+pub const BUILD_ID: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("GIT_HASH"));
+
+/// Digest şeması değişirse artırılır — eski satırlar ayırt edilebilsin
+const DIGEST_DOMAIN: &[u8] = b"arb-decision-inputs-v1";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Karar Girdileri ve Digest
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek havuzun digest'e giren durumu
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolDigestInput {
+    pub address: Address,
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    pub liquidity: u128,
+    /// NR'nin kullandığı fee (canlı varsa canlı, yoksa yapılandırılan)
+    pub fee_bps: u32,
+    /// Bitmap'in okunduğu blok (bitmap yoksa None)
+    pub bitmap_block: Option<u64>,
+}
+
+impl PoolDigestInput {
+    pub fn from_state(config: &PoolConfig, state: &PoolState) -> Self {
+        Self {
+            address: config.address,
+            sqrt_price_x96: state.sqrt_price_x96,
+            tick: state.tick,
+            liquidity: state.liquidity,
+            fee_bps: config.effective_fee(state.live_fee_bps).bps(),
+            bitmap_block: state.tick_bitmap.as_deref().map(|b| b.snapshot_block),
+        }
+    }
+
+    pub fn from_snapshot(pool: &PoolSnapshot) -> Self {
+        Self {
+            address: pool.address,
+            sqrt_price_x96: pool.sqrt_price_x96,
+            tick: pool.tick,
+            liquidity: pool.liquidity,
+            fee_bps: pool.live_fee_bps.unwrap_or(pool.fee_bps),
+            bitmap_block: pool.tick_bitmap.as_ref().map(|b| b.snapshot_block),
+        }
+    }
+}
+
+/// Strateji kararının girdileri — [pool_a, pool_b] sırası snapshot'la aynı
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionInputs {
+    pub pools: [PoolDigestInput; 2],
+    pub buy_pool_idx: usize,
+    pub sell_pool_idx: usize,
+    /// NR'nin kâr birimi olarak kullandığı referans fiyat (quote)
+    pub reference_price_quote: f64,
+}
+
+impl DecisionInputs {
+    /// Hot path: fırsatın değerlendirildiği state'lerden
+    pub fn capture(
+        opportunity: &ArbitrageOpportunity,
+        pools: &[PoolConfig],
+        states: [&PoolState; 2],
+    ) -> Self {
+        Self {
+            pools: [
+                PoolDigestInput::from_state(&pools[0], states[0]),
+                PoolDigestInput::from_state(&pools[1], states[1]),
+            ],
+            buy_pool_idx: opportunity.buy_pool_idx,
+            sell_pool_idx: opportunity.sell_pool_idx,
+            reference_price_quote: opportunity.reference_price_quote,
+        }
+    }
+
+    /// Doğrulama: arşivlenmiş snapshot'tan aynı girdiler
+    pub fn from_snapshot(snapshot: &OpportunitySnapshot) -> Option<Self> {
+        let [a, b] = snapshot.pools.as_slice() else {
+            return None;
+        };
+        Some(Self {
+            pools: [PoolDigestInput::from_snapshot(a), PoolDigestInput::from_snapshot(b)],
+            buy_pool_idx: snapshot.buy_pool_idx,
+            sell_pool_idx: snapshot.sell_pool_idx,
+            reference_price_quote: snapshot.nr.reference_price_quote,
+        })
+    }
+
+    /// Sabit alan sırası, sabit genişlikli big-endian alanlar — serileştirme
+    /// biçimi veya JSON anahtar sırası digest'i değiştirmez. Fiyat 1e-6
+    /// adımına yuvarlanır (f64 metin gidiş-dönüşü son biti oynatabilir).
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(DIGEST_DOMAIN.len() + 2 * 90 + 24);
+        out.extend_from_slice(DIGEST_DOMAIN);
+        for pool in &self.pools {
+            out.extend_from_slice(pool.address.as_slice());
+            out.extend_from_slice(&pool.sqrt_price_x96.to_be_bytes::<32>());
+            out.extend_from_slice(&pool.tick.to_be_bytes());
+            out.extend_from_slice(&pool.liquidity.to_be_bytes());
+            out.extend_from_slice(&pool.fee_bps.to_be_bytes());
+            match pool.bitmap_block {
+                Some(block) => {
+                    out.push(1);
+                    out.extend_from_slice(&block.to_be_bytes());
+                }
+                None => out.push(0),
+            }
+        }
+        out.extend_from_slice(&(self.buy_pool_idx as u64).to_be_bytes());
+        out.extend_from_slice(&(self.sell_pool_idx as u64).to_be_bytes());
+        let price_micros = (self.reference_price_quote * 1e6).round() as i64;
+        out.extend_from_slice(&price_micros.to_be_bytes());
+        out
+    }
+
+    /// SHA-256 (hex)
+    pub fn digest(&self) -> String {
+        hex::encode(Sha256::digest(self.canonical_bytes()))
+    }
+}
+
+/// Yürürlükteki ayarların parmak izi — sırlar (URL, key, token) hariç,
+/// anahtar sırasından bağımsız. Havuz listesi girmez: satırdaki havuzlar
+/// digest'te zaten var.
+pub fn config_fingerprint(config: &BotConfig) -> String {
+    let settings: BTreeMap<&'static str, String> = config
+        .redacted_entries()
+        .into_iter()
+        .filter(|(key, _)| !crate::session_summary::is_secret_key(key))
+        .collect();
+    let mut hasher = Sha256::new();
+    for (key, value) in &settings {
+        hasher.update(format!("{}={}|", key, value).as_bytes());
+    }
+    hex::encode(&hasher.finalize()[..8])
+}
+
+/// Log satırına eklenen bütünlük bloğu
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogIntegrity {
+    pub build: String,
+    pub config: String,
+    pub input_digest: String,
+    pub snapshot_id: Option<String>,
+}
+
+impl LogIntegrity {
+    pub fn new(config: &BotConfig, inputs: &DecisionInputs, snapshot_id: Option<String>) -> Self {
+        Self {
+            build: BUILD_ID.to_string(),
+            config: config_fingerprint(config),
+            input_digest: inputs.digest(),
+            snapshot_id,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Doğrulama — --verify-log
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek log satırının kontrol sonucu
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineCheck {
+    /// Satırda integrity bloğu yok (eski sürüm)
+    NoIntegrity,
+    /// snapshot_id yok ya da snapshot silinmiş (saklama politikası)
+    NoSnapshot,
+    Verified,
+    /// Uyuşmayan alanlar: "alan: log=… snapshot=…"
+    Mismatch(Vec<String>),
+}
+
+/// Loglanan değerle snapshot'tan türetilen değer aynı yuvarlamada eşit mi?
+fn same_rounded(logged: Option<f64>, recorded: f64, scale: f64) -> bool {
+    logged.is_some_and(|v| (v - (recorded * scale).round() / scale).abs() < 0.5 / scale)
+}
+
+/// Satırı snapshot'la karşılaştır: digest yeniden hesaplanır, satırın karar
+/// alanları (blok, havuz adresleri, NR çıktısı) snapshot'takiyle eşleşmeli.
+pub fn check_line(line: &serde_json::Value, snapshot: Option<&OpportunitySnapshot>) -> LineCheck {
+    let Some(integrity) = line
+        .get("integrity")
+        .and_then(|v| serde_json::from_value::<LogIntegrity>(v.clone()).ok())
+    else {
+        return LineCheck::NoIntegrity;
+    };
+    let Some(snapshot) = snapshot.filter(|_| integrity.snapshot_id.is_some()) else {
+        return LineCheck::NoSnapshot;
+    };
+
+    let mut mismatches = Vec::new();
+    match DecisionInputs::from_snapshot(snapshot) {
+        Some(inputs) => {
+            let recomputed = inputs.digest();
+            if recomputed != integrity.input_digest {
+                mismatches.push(format!(
+                    "input_digest: log={} snapshot={}",
+                    integrity.input_digest, recomputed
+                ));
+            }
+        }
+        None => mismatches.push(format!("pools: snapshot has {} pools", snapshot.pools.len())),
+    }
+
+    if line["block"].as_u64() != Some(snapshot.block) {
+        mismatches.push(format!("block: log={} snapshot={}", line["block"], snapshot.block));
+    }
+    for (field, idx) in [("buy_pool_addr", snapshot.buy_pool_idx), ("sell_pool_addr", snapshot.sell_pool_idx)] {
+        let recorded = snapshot.pools.get(idx).map(|p| p.address.to_string());
+        if line[field].as_str() != recorded.as_deref() {
+            mismatches.push(format!("{}: log={} snapshot={:?}", field, line[field], recorded));
+        }
+    }
+    for (field, recorded) in [
+        ("optimal_amount_weth", snapshot.nr.optimal_amount_weth),
+        ("expected_profit", snapshot.nr.expected_profit_weth),
+    ] {
+        if !same_rounded(line[field].as_f64(), recorded, 1e8) {
+            mismatches.push(format!("{}: log={} snapshot={}", field, line[field], recorded));
+        }
+    }
+
+    if mismatches.is_empty() {
+        LineCheck::Verified
+    } else {
+        LineCheck::Mismatch(mismatches)
+    }
+}
+
+/// --verify-log özeti
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    pub lines: usize,
+    pub unparsable: usize,
+    pub no_integrity: usize,
+    pub no_snapshot: usize,
+    pub verified: usize,
+    /// (satır no, opportunity_id, uyuşmazlıklar)
+    pub mismatches: Vec<(usize, String, Vec<String>)>,
+    /// build kimliği → satır sayısı
+    pub builds: BTreeMap<String, usize>,
+    /// config parmak izi → satır sayısı
+    pub configs: BTreeMap<String, usize>,
+}
+
+/// Log dosyasındaki her satırı `snapshot_dir`'deki arşive karşı doğrula
+pub fn verify_log(path: &Path, snapshot_dir: &Path) -> Result<VerifyReport> {
+    // JSONL veya ikili (LOG_FORMAT_BINARY) — atlanan çerçeveler okunamaz sayılır
+    let log = crate::binlog::read_json_lines(path)
+        .map_err(|e| eyre::eyre!("Log could not be read: {}", e))?;
+    let mut report = VerifyReport {
+        lines: log.skipped,
+        unparsable: log.skipped,
+        ..VerifyReport::default()
+    };
+
+    for (idx, raw) in log.lines.iter().enumerate() {
+        report.lines += 1;
+        let Ok(line) = serde_json::from_str::<serde_json::Value>(raw) else {
+            report.unparsable += 1;
+            continue;
+        };
+        if let Some(integrity) = line.get("integrity") {
+            for (key, counts) in [("build", &mut report.builds), ("config", &mut report.configs)] {
+                if let Some(value) = integrity[key].as_str() {
+                    *counts.entry(value.to_string()).or_default() += 1;
+                }
+            }
+        }
+        let snapshot = line["integrity"]["snapshot_id"]
+            .as_str()
+            .and_then(|id| crate::snapshot::read_snapshot(snapshot_dir, id).ok());
+
+        match check_line(&line, snapshot.as_ref()) {
+            LineCheck::NoIntegrity => report.no_integrity += 1,
+            LineCheck::NoSnapshot => report.no_snapshot += 1,
+            LineCheck::Verified => report.verified += 1,
+            LineCheck::Mismatch(fields) => {
+                let id = line["opportunity_id"].as_str().unwrap_or("?").to_string();
+                report.mismatches.push((idx + 1, id, fields));
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// CLI: --verify-log <dosya> — uyuşmazlık varsa hata döner
+pub fn cli_verify_log(path: &str) -> Result<()> {
+    let snapshot_dir = crate::secure_fs::read_path(crate::snapshot::SNAPSHOT_DIR);
+    let report = verify_log(Path::new(path), &snapshot_dir)?;
+
+    println!("  {} Log integrity: {}", "🔏".cyan(), path);
+    println!(
+        "     lines {} | verified {} | no snapshot {} | no integrity {} | unparsable {}",
+        report.lines, report.verified, report.no_snapshot, report.no_integrity, report.unparsable,
+    );
+    for (build, count) in &report.builds {
+        println!("     build  {} ({} lines)", build, count);
+    }
+    for (config, count) in &report.configs {
+        println!("     config {} ({} lines)", config, count);
+    }
+    for (line_no, id, fields) in &report.mismatches {
+        println!("  {} line {} [{}]", "❌".red(), line_no, id);
+        for field in fields {
+            println!("       {}", field);
+        }
+    }
+
+    if report.mismatches.is_empty() {
+        println!("  {} No mismatches against archived snapshots", "✅".green());
+        Ok(())
+    } else {
+        Err(eyre::eyre!("{} log line(s) do not match their snapshots", report.mismatches.len()))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::tests::make_job;
+
+    /// Gölge log satırının snapshot'la ilgili alanları (write_shadow_log ile aynı)
+    fn log_line(snapshot: &OpportunitySnapshot, integrity: &LogIntegrity) -> serde_json::Value {
+        serde_json::json!({
+            "block": snapshot.block,
+            "opportunity_id": "test",
+            "buy_pool_addr": snapshot.pools[snapshot.buy_pool_idx].address.to_string(),
+            "sell_pool_addr": snapshot.pools[snapshot.sell_pool_idx].address.to_string(),
+            "optimal_amount_weth": (snapshot.nr.optimal_amount_weth * 1e8).round() / 1e8,
+            "expected_profit": (snapshot.nr.expected_profit_weth * 1e8).round() / 1e8,
+            "integrity": integrity,
+        })
+    }
+
+    #[test]
+    fn test_digest_matches_between_hot_path_and_snapshot() {
+        let job = make_job();
+        let captured = DecisionInputs::capture(&job.opportunity, &job.pools, [&*job.states[0], &*job.states[1]]);
+        let snapshot = job.into_snapshot();
+        let restored = DecisionInputs::from_snapshot(&snapshot).unwrap();
+        assert_eq!(captured.digest(), restored.digest());
+        assert_eq!(captured.digest().len(), 64);
+    }
+
+    /// JSON metnini tüm nesnelerde anahtarlar ters sırada olacak şekilde yaz
+    fn reversed_json(value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::Object(map) => {
+                let fields: Vec<String> = map
+                    .iter()
+                    .rev()
+                    .map(|(k, v)| format!("{}:{}", serde_json::Value::from(k.as_str()), reversed_json(v)))
+                    .collect();
+                format!("{{{}}}", fields.join(","))
+            }
+            serde_json::Value::Array(items) => {
+                let items: Vec<String> = items.iter().map(reversed_json).collect();
+                format!("[{}]", items.join(","))
+            }
+            other => other.to_string(),
+        }
+    }
+
+    /// Digest alanların JSON'daki sırasından ve metin gidiş-dönüşünden bağımsız
+    #[test]
+    fn test_digest_stable_across_serialization_ordering() {
+        let snapshot = make_job().into_snapshot();
+        let inputs = DecisionInputs::from_snapshot(&snapshot).unwrap();
+
+        let mut value = serde_json::to_value(&inputs).unwrap();
+        let text = reversed_json(&value);
+        assert!(text.starts_with("{\"sell_pool_idx\""), "{}", text);
+        let reordered: DecisionInputs = serde_json::from_str(&text).unwrap();
+        assert_eq!(reordered, inputs);
+        assert_eq!(reordered.digest(), inputs.digest());
+
+        // Tek bir girdinin değişmesi digest'i değiştirir
+        value["pools"][1]["tick"] = serde_json::json!(inputs.pools[1].tick + 1);
+        let changed: DecisionInputs = serde_json::from_value(value).unwrap();
+        assert_ne!(changed.digest(), inputs.digest());
+    }
+
+    #[test]
+    fn test_verifier_catches_tampered_fields() {
+        let config = BotConfig::for_tests();
+        let snapshot = make_job().into_snapshot();
+        let inputs = DecisionInputs::from_snapshot(&snapshot).unwrap();
+        let integrity = LogIntegrity::new(&config, &inputs, Some(snapshot.id.clone()));
+        let line = log_line(&snapshot, &integrity);
+        assert_eq!(check_line(&line, Some(&snapshot)), LineCheck::Verified);
+
+        // Log tarafında oynanmış kâr
+        let mut tampered_line = line.clone();
+        tampered_line["expected_profit"] = serde_json::json!(0.5);
+        match check_line(&tampered_line, Some(&snapshot)) {
+            LineCheck::Mismatch(fields) => {
+                assert_eq!(fields.len(), 1);
+                assert!(fields[0].starts_with("expected_profit:"), "{:?}", fields);
+            }
+            other => panic!("expected mismatch, got {:?}", other),
+        }
+
+        // Snapshot tarafında oynanmış likidite → digest uyuşmaz
+        let mut tampered_snapshot = snapshot.clone();
+        tampered_snapshot.pools[0].liquidity += 1;
+        match check_line(&line, Some(&tampered_snapshot)) {
+            LineCheck::Mismatch(fields) => assert!(fields[0].starts_with("input_digest:"), "{:?}", fields),
+            other => panic!("expected mismatch, got {:?}", other),
+        }
+
+        // Snapshot'sız ve integrity'siz satırlar doğrulanmış sayılmaz
+        assert_eq!(check_line(&line, None), LineCheck::NoSnapshot);
+        assert_eq!(check_line(&serde_json::json!({"block": 1}), Some(&snapshot)), LineCheck::NoIntegrity);
+    }
+
+    #[test]
+    fn test_config_fingerprint_tracks_tunables_not_secrets() {
+        let base = BotConfig::for_tests();
+        let fingerprint = config_fingerprint(&base);
+        assert_eq!(fingerprint.len(), 16);
+
+        let secret_changed = BotConfig { rpc_wss_url: "wss://other.example/KEY2".into(), ..BotConfig::for_tests() };
+        assert_eq!(config_fingerprint(&secret_changed), fingerprint);
+
+        let tuned = BotConfig { bribe_pct: base.bribe_pct + 0.05, ..BotConfig::for_tests() };
+        assert_ne!(config_fingerprint(&tuned), fingerprint);
+    }
+}
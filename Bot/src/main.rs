@@ -13,42 +13,102 @@
 //  ✓ Modüler mimari (types, math, state_sync, simulator, strategy)
 // ============================================================================
 
+mod ab_shadow;
+mod approval_audit;
+mod arming;
+mod binlog;
+mod block_feed;
+mod bytecode_watchdog;
+mod calldata_codec;
+#[cfg(test)]
+mod calldata_diff;
+mod chain_profile;
+mod circuit_breaker;
+mod clock;
+mod confirmations;
+mod console;
+mod contract_state;
+mod dashboard;
+mod degradation;
+#[cfg(feature = "dev")]
+mod devnet;
+mod direction_gate;
 mod discovery_engine;
+mod divergence_guard;
 mod dust_sweeper;
+mod env_loader;
+mod errors;
 mod executor;
+mod fee_monitor;
+mod followup;
+mod gas_calibration;
+mod heatmap;
+mod impact_verification;
+mod instance_lock;
 mod json_logger;
 mod key_manager;
+mod liquidity_analytics;
+mod log_integrity;
+mod log_event;
 mod math;
+mod metrics;
+mod nonce_checkpoint;
+mod opportunity_filter;
+mod paper;
+mod pair_scheduler;
 mod pool_discovery;
+mod pool_snapshot;
+mod quoter_audit;
+mod replay;
+mod revisit;
+mod runtime_health;
 mod route_engine;
+mod rpc_census;
+mod secure_fs;
+mod session_summary;
+mod shadow_verify;
 mod simulator;
+mod snapshot;
+mod staleness;
 mod state_sync;
+mod status_server;
 mod strategy;
+mod submit_pacing;
+mod systemd;
 mod telegram;
+mod token_probe;
 mod transport;
 mod types;
+#[cfg(test)]
+mod v3_fixture;
+mod warmup;
+mod what_if;
 
+use console::{eprintln_high, println_high, println_low};
 use discovery_engine::{DiscoveryConfig, DiscoveryEngine, LivePoolRegistry};
 use simulator::SimulationEngine;
 use state_sync::*;
 use strategy::*;
 use types::*;
 
-use alloy::primitives::Address;
-use alloy::providers::{Provider, ProviderBuilder, WsConnect};
+use alloy::eips::BlockId;
+use alloy::primitives::{Address, B256};
+use alloy::providers::{Provider, ProviderBuilder, RootProvider};
 use arc_swap::ArcSwap;
-use chrono::Local;
 use colored::*;
 use eyre::Result;
 use futures_util::future::join_all;
-use futures_util::StreamExt;
+use futures_util::{FutureExt, StreamExt};
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 
+/// Kapanış / reconnect'te konsol kuyruğunun boşalması için en fazla bekleme
+const CONSOLE_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
 // ── OPT-4: L1 Data Fee arka plan cache'i ──
 // OP Stack'te L1 fee ~12 saniyede bir değişir (L1 blok süresi).
 // Her 2 saniyede (Base blok) RPC sorgusu yapmak yerine, arka planda 12s'de bir güncelle.
@@ -59,87 +119,99 @@ static GLOBAL_L1_FEE: AtomicU64 = AtomicU64::new(5_000_000_000_000); // 0.000005
 // ─────────────────────────────────────────────────────────────────────────────
 
 fn timestamp() -> String {
-    Local::now().format("%H:%M:%S%.3f").to_string()
+    clock::local_now().format("%H:%M:%S%.3f").to_string()
 }
 
 fn print_banner(config: &BotConfig) {
-    println!();
-    println!(
+    println_high!();
+    println_high!(
         "{}",
         "╔══════════════════════════════════════════════════════════════════╗"
             .cyan()
             .bold()
     );
-    println!(
+    println_high!(
         "{}",
         "║       ARBITRAGE BOT v25.0 — Quantum Brain IV                    ║"
             .cyan()
             .bold()
     );
-    println!(
+    println_high!(
         "{}",
         "║    Base Network Cross-DEX Arbitrage System                       ║"
             .cyan()
             .bold()
     );
-    println!(
+    println_high!(
         "{}",
         "╠══════════════════════════════════════════════════════════════════╣"
             .cyan()
             .bold()
     );
-    println!(
+    println_high!(
         "{}",
         "║  [v25] Autonomous Discovery: Factory WSS + Multi-API + Scoring + GC ║".cyan()
     );
-    println!(
+    println_high!(
         "{}",
         "║  [v9] Executor/Admin Role Separation + Deadline Block             ║".cyan()
     );
-    println!(
+    println_high!(
         "{}",
         "║  [v9] Encrypted Key Management (AES-256-GCM + PBKDF2)             ║".cyan()
     );
-    println!(
+    println_high!(
         "{}",
         "║  [v9] Dynamic Bribe/Priority Fee + 134-Byte Calldata              ║".cyan()
     );
-    println!(
+    println_high!(
         "{}",
         "║  [v6] TickBitmap + Multi-Tick Depth + REVM Simulation              ║".cyan()
     );
-    println!(
+    println_high!(
         "{}",
         "║  [v5] State Sync + Newton-Raphson + Multi-Transport            ║".cyan()
     );
-    println!(
+    println_high!(
         "{}",
         "╚══════════════════════════════════════════════════════════════════╝"
             .cyan()
             .bold()
     );
-    println!();
-    println!(
+    println_high!();
+    println_high!(
         "  {} Engine         : {}",
         "▸".cyan(),
         "Rust + Alloy + REVM (Zero Latency)".white()
     );
-    println!(
+    println_high!(
         "  {} Network        : {}",
         "▸".cyan(),
-        format!("Base Network (Chain ID: {})", config.chain_id).white()
+        format!(
+            "{} (Chain ID: {}, CHAIN_PROFILE={})",
+            config.chain_profile.display_name(),
+            config.chain_id,
+            config.chain_profile
+        )
+        .white()
     );
-    println!(
+    println_high!(
         "  {} Transport      : {}",
         "▸".cyan(),
-        format!("{:?} (Priority: IPC > WSS > HTTP)", config.transport_mode).white()
+        match (config.transport_mode, config.rpc_ipc_path.as_deref()) {
+            (TransportMode::Ipc | TransportMode::Auto, Some(path)) => {
+                format!("{:?} (IPC {} → WSS standby if socket missing / handshake fails)", config.transport_mode, path)
+            }
+            _ => format!("{:?} (WSS primary, IPC not configured)", config.transport_mode),
+        }
+        .white()
     );
-    println!(
+    println_high!(
         "  {} Strategy       : {}",
         "▸".cyan(),
         "Cross-DEX Spread Arbitrage (Uniswap V3 + Aerodrome)".white()
     );
-    println!(
+    println_high!(
         "  {} Depth          : {}",
         "▸".cyan(),
         format!(
@@ -148,7 +220,7 @@ fn print_banner(config: &BotConfig) {
         )
         .white()
     );
-    println!(
+    println_high!(
         "  {} Calldata       : {}",
         "▸".cyan(),
         format!(
@@ -157,19 +229,24 @@ fn print_banner(config: &BotConfig) {
         )
         .white()
     );
-    println!(
+    println_high!(
         "  {} Bribe          : {}",
         "▸".cyan(),
-        format!(
-            "Dynamic %{:.0} profit → priority fee",
-            config.bribe_pct * 100.0
-        )
+        match config.priority_fee_strategy {
+            chain_profile::PriorityFeeStrategy::FifoBribe => format!(
+                "Dynamic %{:.0} profit → priority fee (FIFO)",
+                config.bribe_pct * 100.0
+            ),
+            chain_profile::PriorityFeeStrategy::Eip1559Competitive => {
+                "Standard EIP-1559 tip (ordering not fee-based, no profit share)".to_string()
+            }
+        }
         .white()
     );
-    println!(
+    println_high!(
         "  {} Key Mgmt       : {}",
         "▸".cyan(),
-        if config.key_manager_active {
+        if config.key_manager_active() {
             "Encrypted Keystore (AES-256-GCM)".green().to_string()
         } else if config.private_key.is_some() {
             "Env Var (UNSAFE)".yellow().to_string()
@@ -177,30 +254,66 @@ fn print_banner(config: &BotConfig) {
             "None".red().to_string()
         }
     );
-    println!(
+    println_high!(
         "  {} Flash Loan     : {}",
         "▸".cyan(),
         "Direct Flash Swap (No External Fee)".white()
     );
-    println!(
+    println_high!(
         "  {} Max Trade      : {}",
         "▸".cyan(),
         format!("{:.1} WETH", config.max_trade_size_weth).white()
     );
-    println!(
+    println_high!(
+        "  {} Min Trade      : {}",
+        "▸".cyan(),
+        format!("{} WETH", config.min_trade_size_weth).white()
+    );
+    println_high!(
         "  {} Min Net Profit : {}",
         "▸".cyan(),
         format!("{:.6} WETH", config.min_net_profit_weth).white()
     );
-    println!(
+    println_high!(
+        "  {} Staleness      : {}",
+        "▸".cyan(),
+        if config.adaptive_staleness {
+            format!(
+                "Adaptive (p95 block interval − p95 sync, {}–{}ms, exec ×{}; {}ms until calibrated)",
+                config.staleness_min_ms,
+                config.staleness_max_ms,
+                config.staleness_exec_fraction,
+                config.max_staleness_ms,
+            )
+        } else {
+            format!("Fixed {}ms (ADAPTIVE_STALENESS=false)", config.max_staleness_ms)
+        }
+        .white()
+    );
+    println_high!(
+        "  {} Degradation    : {}",
+        "▸".cyan(),
+        if config.degrade_start_pct == 0 {
+            "Off (every stage runs regardless of block budget)".to_string()
+        } else {
+            format!(
+                "Shed from {}% of block budget, restore after {} calm blocks (never shed: {})",
+                config.degrade_start_pct,
+                config.degrade_recovery_blocks,
+                degradation::GUARDS.join(", "),
+            )
+        }
+        .white()
+    );
+    println_high!(
         "  {} Start Time     : {}",
         "▸".cyan(),
-        Local::now()
+        clock::local_now()
             .format("%Y-%m-%d %H:%M:%S")
             .to_string()
             .yellow()
     );
-    println!(
+    println_high!(
         "  {} Mode           : {}",
         "▸".cyan(),
         if config.execution_enabled() {
@@ -217,15 +330,15 @@ fn print_banner(config: &BotConfig) {
             "OBSERVE (Watch Only)".yellow().bold().to_string()
         }
     );
-    println!();
+    println_high!();
 }
 
 fn print_pool_header(pools: &[PoolConfig], states: &[SharedPoolState]) {
-    println!(
+    println_high!(
         "{}",
         "  ┌──────────────────────────────────────────────────────────────┐".dimmed()
     );
-    println!("  {} {}", "│".dimmed(), "Monitored Pools:".white().bold());
+    println_high!("  {} {}", "│".dimmed(), "Monitored Pools:".white().bold());
     for (i, p) in pools.iter().enumerate() {
         let icon = if i == 0 { "🔵" } else { "🟣" };
         let fee_display = if i < states.len() {
@@ -233,11 +346,11 @@ fn print_pool_header(pools: &[PoolConfig], states: &[SharedPoolState]) {
                 .load()
                 .live_fee_bps
                 .map(|b| b as f64 / 100.0)
-                .unwrap_or(p.fee_bps as f64 / 100.0)
+                .unwrap_or(p.fee_bps() as f64 / 100.0)
         } else {
-            p.fee_bps as f64 / 100.0
+            p.fee_bps() as f64 / 100.0
         };
-        println!(
+        println_high!(
             "  {}   {} {} ({} — Fee: %{:.2})",
             "│".dimmed(),
             icon,
@@ -245,17 +358,17 @@ fn print_pool_header(pools: &[PoolConfig], states: &[SharedPoolState]) {
             p.dex,
             fee_display
         );
-        println!(
+        println_high!(
             "  {}     {}",
             "│".dimmed(),
             format!("{}", p.address).dimmed()
         );
     }
-    println!(
+    println_high!(
         "{}",
         "  └──────────────────────────────────────────────────────────────┘".dimmed()
     );
-    println!();
+    println_high!();
 }
 
 fn print_block_update(
@@ -263,7 +376,33 @@ fn print_block_update(
     pools: &[PoolConfig],
     states: &[SharedPoolState],
     sync_ms: u128,
+    warmup_gate: &warmup::WarmupGate,
 ) {
+    // JSON structured log: block processed
+    json_logger::log_block(block_number, sync_ms, pools.len());
+
+    let emitted = log_event::json(|| {
+        log_event::LogEvent::BlockUpdate(log_event::BlockUpdate {
+            block: block_number,
+            sync_ms: sync_ms as u64,
+            pools: pools
+                .iter()
+                .zip(states.iter())
+                .filter_map(|(config, state_lock)| {
+                    let state = state_lock.load();
+                    state.is_active().then(|| log_event::PoolPrice {
+                        pool: config.name.clone(),
+                        price_quote: state.eth_price_usd,
+                    })
+                })
+                .collect(),
+            warmup_armed: warmup_gate.is_armed(),
+        })
+    });
+    if emitted {
+        return;
+    }
+
     let mut pool_info = String::new();
     for (i, (config, state_lock)) in pools.iter().zip(states.iter()).enumerate() {
         let state = state_lock.load();
@@ -280,46 +419,59 @@ fn print_block_update(
         }
     }
 
-    println!(
-        "  {} [{}] Block #{} | {} | Sync: {}ms",
+    let warmup_status = if warmup_gate.is_armed() {
+        String::new()
+    } else {
+        format!(" | {}", warmup_gate.status_label().yellow())
+    };
+
+    println_low!(
+        "  {} [{}] Block #{} | {} | Sync: {}ms{}",
         "🧱".blue(),
         timestamp().dimmed(),
         format!("{}", block_number).white().bold(),
         pool_info,
         sync_ms,
+        warmup_status,
     );
-
-    // JSON structured log: block processed
-    json_logger::log_block(block_number, sync_ms, pools.len());
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_stats_summary(
     stats: &ArbitrageStats,
     states: &[SharedPoolState],
     pools: &[PoolConfig],
     pair_combos: &[pool_discovery::PairCombo],
+    pool_analytics: &liquidity_analytics::SharedPoolAnalytics,
+    filter_chain: &opportunity_filter::FilterChain,
+    mev_executor: &executor::MevExecutor,
+    head_stats: &block_feed::HeadFeedStats,
+    warmup_gate: &warmup::WarmupGate,
+    approvals: &approval_audit::SharedApprovalReport,
+    providers: &transport::ProviderRegistry<RootProvider>,
+    arming: Option<&arming::ArmingGuard>,
 ) {
-    println!();
-    println!(
+    println_high!();
+    println_high!(
         "{}",
         "  ┌───── SESSION STATISTICS (v16.2) ──────────────────────────────┐".yellow()
     );
-    println!(
+    println_high!(
         "  {}  Uptime               : {}",
         "│".yellow(),
         stats.uptime_str().white().bold()
     );
-    println!(
+    println_high!(
         "  {}  Blocks Processed     : {}",
         "│".yellow(),
         format!("{}", stats.total_blocks_processed).white()
     );
-    println!(
+    println_high!(
         "  {}  Opportunities Detected: {}",
         "│".yellow(),
         format!("{}", stats.total_opportunities).white()
     );
-    println!(
+    println_high!(
         "  {}  Net Profitable       : {}",
         "│".yellow(),
         if stats.profitable_opportunities > 0 {
@@ -333,12 +485,394 @@ fn print_stats_summary(
                 .to_string()
         }
     );
-    println!(
+    println_high!(
         "  {}  Failed Simulations   : {}",
         "│".yellow(),
         stats.failed_simulations
     );
-    println!(
+    // Aralık dışı tick kısıtlaması = bozuk bitmap/state verisi işareti
+    let clamped_ticks = math::exact::tick_clamp_events();
+    if clamped_ticks > 0 {
+        println_high!(
+            "  {}  Clamped Ticks        : {}",
+            "│".yellow(),
+            format!("{} (corrupted tick data!)", clamped_ticks).red().bold()
+        );
+    }
+    // Geçici RPC hatası sonrası yapılan Multicall3 / sync yeniden denemeleri
+    let rpc_retries = state_sync::rpc_retries();
+    if rpc_retries > 0 {
+        println_high!("  {}  RPC Retries          : {}", "│".yellow(), rpc_retries);
+    }
+    // Budanmış state yüzünden "latest"e düşen sync okumaları (blok sabitlenemedi)
+    let unpinned_syncs = state_sync::unpinned_syncs();
+    if unpinned_syncs > 0 {
+        println_high!("  {}  Unpinned Syncs       : {}", "│".yellow(), unpinned_syncs);
+    }
+    // Doğrulamadan geçemeyip reddedilen sync yazımları (NaN/Inf, aralık dışı)
+    let rejected_writes = state_sync::data_quality_errors();
+    if rejected_writes > 0 {
+        let quarantined: Vec<&str> = pools
+            .iter()
+            .zip(states.iter())
+            .filter(|(_, s)| s.load().quarantined)
+            .map(|(p, _)| p.name.as_str())
+            .collect();
+        println_high!(
+            "  {}  Data Quality Errors  : {} | quarantined: {:?}",
+            "│".yellow(),
+            format!("{} rejected writes", rejected_writes).red(),
+            quarantined,
+        );
+    }
+    // Kuyruk derinliği cezası: atlanan fırsatlar + simülasyonda başarılı olacaklar
+    if stats.competition_penalty_blocks > 0 {
+        println_high!(
+            "  {}  Competition Skips    : {} (would succeed: {}) | penalty blocks: {}",
+            "│".yellow(),
+            stats.competition_skips,
+            stats.competition_skips_would_succeed,
+            stats.competition_penalty_blocks,
+        );
+    }
+    // V2 / vAMM bacaklı fırsatlar: fiyatlanır, yürütülmez
+    if stats.constant_product_shadowed > 0 {
+        println_high!(
+            "  {}  V2 Shadow-Only       : {} opportunities (no V2 swap path in contract)",
+            "│".yellow(),
+            stats.constant_product_shadowed,
+        );
+    }
+    // Çift kaynaklı blok sinyali: hangi kaynak önce geldi, ne kadar önde
+    if head_stats.is_dual() {
+        use block_feed::HeadSource;
+        let lead = |source: HeadSource| {
+            head_stats
+                .avg_lead_ms(source)
+                .map_or("-".to_string(), |ms| format!("{:.1}ms", ms))
+        };
+        println_high!(
+            "  {}  Head Source Wins     : {} {} (avg lead {}) | {} {} (avg lead {})",
+            "│".yellow(),
+            HeadSource::Sequencer.label(),
+            head_stats.wins(HeadSource::Sequencer),
+            lead(HeadSource::Sequencer),
+            HeadSource::Primary.label(),
+            head_stats.wins(HeadSource::Primary),
+            lead(HeadSource::Primary),
+        );
+    }
+    // Optimum taranan bitmap kenarına dayandığında yapılan genişletmeler
+    if stats.bitmap_extensions > 0 {
+        println_high!(
+            "  {}  Bitmap Extensions    : {} (optimum at scanned edge)",
+            "│".yellow(),
+            stats.bitmap_extensions,
+        );
+    }
+    // Etkin tazelik kesimleri + uyarlamalı sıkılaştırma retleri
+    if let Some(line) = staleness::stats_line() {
+        println_high!("  {}  Staleness Cutoffs    : {}", "│".yellow(), line);
+    }
+    // Degradation ladder: gecikme bütçesi aşımında atılan basamaklar
+    if let Some(line) = degradation::stats_line(&stats.shed_blocks) {
+        println_high!("  {}  Degradation Ladder   : {}", "│".yellow(), line);
+    }
+    // Geri basınç: geride kalınca en yeniye atlanan bloklar
+    if stats.blocks_skipped_backlog > 0 {
+        println_high!(
+            "  {}  Backlog Skips        : {} stale blocks skipped (newest header wins)",
+            "│".yellow(),
+            stats.blocks_skipped_backlog,
+        );
+    }
+    // Okuma endpoint'i başına sync gecikmesi ve sağlık (yalnızca özel endpoint varsa)
+    if providers.endpoints().count() > 1 {
+        for endpoint in providers.endpoints() {
+            let (count, avg_us, p50_us, max_us) = endpoint.latency.summary();
+            println_high!(
+                "  {}  RPC Reads [{:<7}]  : {} calls | avg {}µs | p50 ≤{}µs | max {}µs | failures {} | fallbacks {}",
+                "│".yellow(),
+                endpoint.label,
+                count,
+                avg_us,
+                p50_us,
+                max_us,
+                endpoint.failures(),
+                endpoint.fallbacks(),
+            );
+        }
+    }
+    // İki fazlı sync: sessiz bloklar ve iki yolun ortalama işleme süresi
+    if stats.quiet_blocks > 0 {
+        let (quiet, quiet_avg_us, _, _) = state_sync::QUIET_BLOCK_LATENCY.summary();
+        let (full, full_avg_us, _, _) = state_sync::FULL_BLOCK_LATENCY.summary();
+        println_high!(
+            "  {}  Fast Path Sync       : quiet {} (avg {}µs) | full {} (avg {}µs)",
+            "│".yellow(),
+            quiet,
+            quiet_avg_us,
+            full,
+            full_avg_us,
+        );
+    }
+    // Sync aşamaları: RPC beklemesi (ağ + sağlayıcı) ve yerel işlem ayrı
+    for stage in state_sync::SyncStage::ALL {
+        let timing = stage.timing();
+        let (count, rpc_avg_us, rpc_p50_us, rpc_max_us) = timing.rpc.summary();
+        if count == 0 {
+            continue;
+        }
+        let (_, local_avg_us, _, local_max_us) = timing.local.summary();
+        let payload = timing.payload_summary();
+        let payload_info = if payload.calls > 0 {
+            format!(
+                " | req avg {}B | resp avg {}B (max {}B)",
+                payload.avg_request_bytes(),
+                payload.avg_response_bytes(),
+                payload.max_response_bytes,
+            )
+        } else {
+            String::new()
+        };
+        println_high!(
+            "  {}  Sync [{:<11}]   : {} | rpc avg {}µs p50 ≤{}µs max {}µs | local avg {}µs max {}µs{}",
+            "│".yellow(),
+            stage.label(),
+            count,
+            rpc_avg_us,
+            rpc_p50_us,
+            rpc_max_us,
+            local_avg_us,
+            local_max_us,
+            payload_info,
+        );
+    }
+    // Kâr ayrıştırması: LP ücretleri brüt spread'in ne kadarını yedi
+    if stats.profit_attribution.opportunities > 0 {
+        let t = &stats.profit_attribution.totals;
+        println_high!(
+            "  {}  Profit Attribution   : gross {:.6} | LP fees {:.6} ({:.0}% of gross) | flash {:.6} | gas {:.6} | bribe {:.6} | net {:.6} WETH",
+            "│".yellow(),
+            t.gross_weth,
+            t.lp_fees_weth(),
+            stats.profit_attribution.fee_share_of_gross_pct,
+            t.flash_fee_weth,
+            t.gas_weth,
+            t.bribe_weth,
+            t.net_weth,
+        );
+    }
+    // Event tazelik kahini: Swap eventiyle çelişen sabitli okumalar
+    if stats.state_divergence_alarms > 0 {
+        println_high!(
+            "  {}  State Divergence     : {} alarms (re-synced from fallback RPC)",
+            "│".yellow(),
+            stats.state_divergence_alarms,
+        );
+    }
+    // Bytecode bekçisi: zincirde kodu değişen adresler (base_db yeniden kuruldu)
+    if stats.bytecode_changes > 0 {
+        println_high!(
+            "  {}  Bytecode Changes     : {} (base_db rebuilt, gas calibration reset)",
+            "│".yellow(),
+            stats.bytecode_changes,
+        );
+    }
+    // Bloklamayan konsol: kuyruk doluyken atılan düşük öncelikli satırlar
+    let dropped_lines = console::dropped_lines();
+    if dropped_lines > 0 {
+        println_high!(
+            "  {}  Console Dropped      : {} low-priority lines (stdout back-pressure)",
+            "│".yellow(),
+            dropped_lines,
+        );
+    }
+    // Yön / token değişmezi ihlalleri: kod ya da config hatası, fırsat gönderilmedi
+    if stats.direction_invariant_rejections > 0 {
+        println_high!(
+            "  {}  Direction Invariant  : {} rejected (owed/received token or profit unit mismatch)",
+            "│".red(),
+            stats.direction_invariant_rejections,
+        );
+    }
+    // Eşzamanlı yürütme sınırı: atlanan fırsatlar ve zirve eşzamanlılık
+    if stats.inflight_cap_skips > 0 || stats.peak_inflight_executions > 0 {
+        println_high!(
+            "  {}  Inflight Executions  : peak {} | cap skips {}",
+            "│".yellow(),
+            stats.peak_inflight_executions,
+            stats.inflight_cap_skips,
+        );
+    }
+    // Pool B kaynaklı ters rotanın anlamlı ölçüde kârlı olduğu fırsatlar (kontrat yükseltmesi verisi)
+    if stats.reversed_route_wins > 0 {
+        println_high!(
+            "  {}  Reversed Route Wins  : {} | foregone {:.6} WETH (contract flashes from pool_a)",
+            "│".yellow(),
+            stats.reversed_route_wins,
+            stats.foregone_route_improvement_weth,
+        );
+    }
+    // Gas limiti tamponu + yön başına receipt/REVM gas oranı p95'i
+    if let Some(line) = gas_calibration::stats_line() {
+        println_high!("  {}  Gas Buffer           : {}", "│".yellow(), line);
+    }
+    // İşlem sonrası havuz etkisi: bacak başına ortalama tick sapması (model hatası)
+    let head_block = states.iter().map(|s| s.load().last_block).max().unwrap_or(0);
+    if let Some(line) = impact_verification::stats_line(head_block) {
+        println_high!("  {}  Model Impact Error   : {}", "│".yellow(), line);
+    }
+    // Quoter eşliği: havuz × motor başına son denetimlerin en büyük sapması
+    if let Some(line) = quoter_audit::stats_line() {
+        println_high!("  {}  Quoter Parity        : {}", "│".yellow(), line);
+    }
+    // Gölge fırsatların birkaç blok sonraki akıbeti
+    if let Some(line) = shadow_verify::stats_line() {
+        println_high!("  {}  Shadow Outcomes      : {}", "│".yellow(), line);
+    }
+    // Yön kapısı: (buy, sell) başına receipt sonucu, PnL ve etkin kısıtlama
+    for line in direction_gate::stats_lines() {
+        println_high!("  {}  Direction Gate       : {}", "│".yellow(), line);
+    }
+    // Kalıcı spread: yapısal ayrışmadaki (gölgedeki) çiftler
+    if let Some(line) = divergence_guard::stats_line() {
+        println_high!("  {}  Structural Diverg.   : {}", "│".yellow(), line);
+    }
+    // Kontrat pause view'ı: son çözülen durum ve bastırılan yürütmeler
+    if let Some(line) = contract_state::stats_line() {
+        println_high!("  {}  Contract Pause       : {}", "│".yellow(), line);
+    }
+    // Global circuit breaker: durum, tetiklemeler ve türe göre hatalar
+    if let Some(line) = circuit_breaker::stats_line() {
+        println_high!("  {}  Circuit Breaker      : {}", "│".yellow(), line);
+    }
+    // Debug build: fırsat yolunun canlı state okuma trafiği (snapshot başına ≤ havuz)
+    #[cfg(debug_assertions)]
+    println_high!("  {}  Pool Snapshots       : {}", "│".yellow(), pool_snapshot::stats_line());
+    // Kağıt yürütme: sahte receipt'li yürütme hattının ayrı muhasebesi
+    if let Some(line) = paper::stats_line() {
+        println_high!("  {}  Paper Execution      : {}", "│".yellow(), line);
+    }
+    // Çift önceliği: blok bütçesi kesintileri, rotasyon ve en yüksek skorlu çiftler
+    for line in pair_scheduler::stats_lines() {
+        println_high!("  {}  Pair Schedule        : {}", "│".yellow(), line);
+    }
+    // RPC census: en çok çağrılan yerler (sayı, toplam bekleme) ve gereksiz okumalar
+    for line in rpc_census::stats_lines() {
+        println_high!("  {}  RPC Call Census      : {}", "│".yellow(), line);
+    }
+    // İki adımlı planın 2. adımı: kurulan / onaylanan / yürütülen takipler
+    if let Some(line) = followup::stats_line() {
+        println_high!("  {}  Follow-up Step 2     : {}", "│".yellow(), line);
+    }
+    // Sessiz dönem yeniden değerlendirmesi: hızlı yolun bıraktığı / fazla vaat ettiği kâr
+    if let Some(line) = revisit::stats_line() {
+        println_high!("  {}  Idle Revisits        : {}", "│".yellow(), line);
+    }
+    // Reorg güvenli muhasebe: kesinleşen / geçici sonuçlar ve düzeltmeler
+    if let Some(line) = mev_executor.trade_outcomes.lock().confirmation_line() {
+        println_high!("  {}  Confirmations        : {}", "│".yellow(), line);
+    }
+    // Gönderim zamanlaması: jitter, yerel aralık beklemeleri, ertelenenler
+    if let Some(line) = mev_executor.pacing_stats_line() {
+        println_high!("  {}  Submit Pacing        : {}", "│".yellow(), line);
+    }
+    // Runtime sağlığı: probe gecikmesi, executor stall'ları, worker busy oranları
+    if let Some(line) = runtime_health::stats_line() {
+        println_high!(
+            "  {}  Runtime Health       : {} | stalled blocks {}",
+            "│".yellow(),
+            line,
+            stats.stalled_blocks,
+        );
+    }
+    // Saat hijyeni: sistem saati ↔ blok timestamp'i ve ↔ monoton beklenti
+    if let Some(line) = clock::stats_line() {
+        println_high!("  {}  Clock Skew           : {}", "│".yellow(), line);
+    }
+    // Bağlantı sonrası ısınma: durum, gölgeye alınan fırsatlar, sıfırlamalar
+    println_high!(
+        "  {}  Warmup Gate          : {} | suppressed {} | resets {} | interrupted by reconnect {}",
+        "│".yellow(),
+        warmup_gate.status_label(),
+        stats.warmup_suppressed,
+        warmup_gate.resets(),
+        warmup_gate.interrupted(),
+    );
+    // Canlı yürütme kurma dosyası (yalnızca canlı mod)
+    if let Some(guard) = arming {
+        println_high!(
+            "  {}  Armed Until          : {}",
+            "│".yellow(),
+            guard.status_line(clock::now()),
+        );
+    }
+    // Kontrat token onayları (son denetim — yalnızca yürütme açıkken yapılır)
+    if let Some(ref report) = *approvals.read() {
+        println_high!(
+            "  {}  Approvals            : {}",
+            "│".yellow(),
+            report.status_line(),
+        );
+    }
+    // A/B gölge: iki parametre setinin aynı bloklardaki fırsat/kâr toplamı
+    let ab = &stats.ab_shadow;
+    if ab.is_active() {
+        println_high!(
+            "  {}  A/B Shadow           : A {} opp / {:.6} WETH | B {} opp / {:.6} WETH | only A {} | only B {} | skipped {}/{} blocks",
+            "│".yellow(),
+            ab.a.opportunities,
+            ab.a.expected_profit_weth,
+            ab.b.opportunities,
+            ab.b.expected_profit_weth,
+            ab.only_a,
+            ab.only_b,
+            ab.blocks_skipped,
+            ab.blocks_compared + ab.blocks_skipped,
+        );
+    }
+    // Fırsatların hangi bitmap kalitesiyle hesaplandığı
+    let [bm_real, bm_stale, bm_fallback] = stats.bitmap_quality_counts;
+    if bm_real + bm_stale + bm_fallback > 0 {
+        println_high!(
+            "  {}  Bitmap Quality       : real {} | stale {} | fallback {}",
+            "│".yellow(),
+            bm_real,
+            bm_stale,
+            bm_fallback,
+        );
+    }
+    // Fırsatların hangi simülasyon kaynağıyla değerlendirildiği
+    let [sim_revm, sim_math] = stats.sim_source_counts;
+    if sim_revm + sim_math > 0 {
+        println_high!(
+            "  {}  Sim Source           : revm {} | math fallback {} (heuristic gas)",
+            "│".yellow(),
+            sim_revm,
+            sim_math,
+        );
+    }
+    // REVM ↔ exact matematik kâr uyumu
+    if stats.sim_math_checks > 0 {
+        println_high!(
+            "  {}  SimMath Checks       : {} | max discrepancy: {} wei | skipped: {}",
+            "│".yellow(),
+            stats.sim_math_checks,
+            stats.sim_math_max_discrepancy_wei,
+            stats.sim_math_discrepancy_skips,
+        );
+    }
+    // REVM gas'ıyla ikinci kârlılık kapısı
+    if stats.sim_gas_checks > 0 {
+        println_high!(
+            "  {}  Gas Gate             : {} checked | rejected: {}",
+            "│".yellow(),
+            stats.sim_gas_checks,
+            stats.gas_rejected_opportunities,
+        );
+    }
+    println_high!(
         "  {}  Executed Trades      : {}",
         "│".yellow(),
         if stats.executed_trades > 0 {
@@ -350,53 +884,38 @@ fn print_stats_summary(
             format!("{}", stats.executed_trades).dimmed().to_string()
         }
     );
-    println!(
+    println_high!(
         "  {}  Max Spread           : {:.4}%",
         "│".yellow(),
         stats.max_spread_pct
     );
-    println!(
+    println_high!(
         "  {}  Max Profit (single)  : {:.6} WETH",
         "│".yellow(),
         stats.max_profit_weth
     );
-    println!(
+    println_high!(
         "  {}  Total Pot. Profit    : {:.6} WETH",
         "│".yellow(),
         stats.total_potential_profit
     );
 
     // v11.0: Fee & break-even — tüm çiftler
-    println!(
+    println_high!(
         "  {} ─── Fee & Economic Analysis ───────────────",
         "│".yellow()
     );
     let mut min_total_fee_pct = f64::MAX;
+    let live_fee = |idx: usize| states.get(idx).and_then(|s| s.load().live_fee_bps);
     for combo in pair_combos {
         if combo.pool_a_idx < pools.len() && combo.pool_b_idx < pools.len() {
-            let fee_a = if combo.pool_a_idx < states.len() {
-                states[combo.pool_a_idx]
-                    .load()
-                    .live_fee_bps
-                    .map(|b| b as f64 / 10_000.0)
-                    .unwrap_or(pools[combo.pool_a_idx].fee_fraction)
-            } else {
-                pools[combo.pool_a_idx].fee_fraction
-            };
-            let fee_b = if combo.pool_b_idx < states.len() {
-                states[combo.pool_b_idx]
-                    .load()
-                    .live_fee_bps
-                    .map(|b| b as f64 / 10_000.0)
-                    .unwrap_or(pools[combo.pool_b_idx].fee_fraction)
-            } else {
-                pools[combo.pool_b_idx].fee_fraction
-            };
+            let fee_a = pools[combo.pool_a_idx].effective_fee(live_fee(combo.pool_a_idx)).fraction();
+            let fee_b = pools[combo.pool_b_idx].effective_fee(live_fee(combo.pool_b_idx)).fraction();
             let total = (fee_a + fee_b) * 100.0;
             if total < min_total_fee_pct {
                 min_total_fee_pct = total;
             }
-            println!(
+            println_high!(
                 "  {}  {} : {:.2}% + {:.2}% = {:.2}%",
                 "│".yellow(),
                 combo.pair_name,
@@ -406,16 +925,31 @@ fn print_stats_summary(
             );
         }
     }
+    // Kâr modelinin yürürlükteki flash loan ücreti (fee_monitor)
+    println_high!(
+        "  {}  Flash Loan Fee       : {:.2} bps{}",
+        "│".yellow(),
+        fee_monitor::effective_flash_loan_fee_bps(),
+        if fee_monitor::auto_update_enabled() { " (auto-update)" } else { "" },
+    );
+    let fee_drifts = fee_monitor::fee_drift_events();
+    if fee_drifts > 0 {
+        println_high!(
+            "  {}  Fee Drift Alerts     : {}",
+            "│".yellow(),
+            format!("{} (on-chain fee ≠ model!)", fee_drifts).red().bold()
+        );
+    }
     if min_total_fee_pct < f64::MAX {
         let profitable = stats.max_spread_pct > min_total_fee_pct;
         if profitable {
-            println!(
+            println_high!(
                 "  {}  Status               : {} (spread > fee)",
                 "│".yellow(),
                 "POTENTIALLY PROFITABLE".green().bold()
             );
         } else {
-            println!(
+            println_high!(
                 "  {}  Status               : {} (spread {:.4}% < min fee {:.2}%)",
                 "│".yellow(),
                 "UNPROFITABLE".red().bold(),
@@ -426,31 +960,34 @@ fn print_stats_summary(
     }
 
     // v6.0: Gecikme istatistikleri
-    println!(
+    println_high!(
         "  {} ─── Latency (State Sync) ─────────────────",
         "│".yellow()
     );
-    println!(
+    println_high!(
         "  {}  Avg Latency          : {:.1}ms",
         "│".yellow(),
         stats.avg_block_latency_ms
     );
-    println!(
+    println_high!(
         "  {}  Min Latency          : {:.1}ms",
         "│".yellow(),
         stats.min_block_latency_ms
     );
-    println!(
+    println_high!(
         "  {}  Max Latency          : {:.1}ms",
         "│".yellow(),
         stats.max_block_latency_ms
     );
-    println!(
+    if let Some(line) = stats.transport_latency_line() {
+        println_high!("  {}  By Transport         : {}", "│".yellow(), line);
+    }
+    println_high!(
         "  {}  Latency Spikes       : {} times",
         "│".yellow(),
         stats.latency_spikes
     );
-    println!(
+    println_high!(
         "  {}  TickBitmap Sync       : {} times",
         "│".yellow(),
         stats.tick_bitmap_syncs
@@ -464,7 +1001,7 @@ fn print_stats_summary(
             } else {
                 " | Bitmap: NONE".to_string()
             };
-            println!(
+            println_high!(
                 "  {}  Pool {} Price        : {:.6} Q (tick: {}){}",
                 "│".yellow(),
                 i + 1,
@@ -475,11 +1012,81 @@ fn print_stats_summary(
         }
     }
 
-    println!(
+    // İmza + gönderim aşaması gecikmeleri (işlem yolunda key parse / filler yok)
+    for (label, hist) in [
+        ("Sign Latency     ", &mev_executor.sign_latency),
+        ("Send Latency     ", &mev_executor.send_latency),
+    ] {
+        let (count, avg_us, p50_us, max_us) = hist.summary();
+        if count > 0 {
+            println_high!(
+                "  {}  {}    : {} tx | avg {}µs | p50 ≤{}µs | max {}µs",
+                "│".yellow(),
+                label,
+                count,
+                avg_us,
+                p50_us,
+                max_us,
+            );
+        }
+    }
+
+    // Fırsat filtre zinciri: filtre başına geçti / ret / gölge sayaçları
+    for f in filter_chain.stats() {
+        if f.passed + f.rejected + f.degraded > 0 {
+            println_high!(
+                "  {}  Filter {:<16}: {} pass | {} reject | {} shadow",
+                "│".yellow(),
+                f.name,
+                f.passed,
+                f.rejected,
+                f.degraded,
+            );
+        }
+    }
+    if let Some(last) = filter_chain.last_rejection() {
+        println_high!(
+            "  {}  Last Rejection       : [{}] {}",
+            "│".yellow(),
+            last.filter,
+            last.reason,
+        );
+    }
+
+    // Boşta zaman likidite analitiği (±%1/2/5 bant, Gini, net ΔL)
+    {
+        let analytics = pool_analytics.read();
+        for (i, pool) in pools.iter().enumerate() {
+            if let Some(a) = analytics.pools.get(&pool.address) {
+                println_high!(
+                    "  {}  Pool {} Liquidity    : ±1% {:.3e} | ±2% {:.3e} | ±5% {:.3e} | Gini {:.3} | ΔL {:+.3e}",
+                    "│".yellow(),
+                    i + 1,
+                    a.liquidity_within[0] as f64,
+                    a.liquidity_within[1] as f64,
+                    a.liquidity_within[2] as f64,
+                    a.concentration_gini,
+                    a.net_liquidity_change as f64,
+                );
+            }
+        }
+        if analytics.completed_runs + analytics.abandoned_runs + analytics.over_budget_runs > 0 {
+            println_high!(
+                "  {}  Liq Analytics Runs   : {} done (last #{}) | {} restarted | {} over budget",
+                "│".yellow(),
+                analytics.completed_runs,
+                analytics.last_block,
+                analytics.abandoned_runs,
+                analytics.over_budget_runs,
+            );
+        }
+    }
+
+    println_high!(
         "{}",
         "  └──────────────────────────────────────────────────────────────┘".yellow()
     );
-    println!();
+    println_high!();
 
     // JSON structured log: session statistics snapshot
     json_logger::log_stats(
@@ -514,46 +1121,323 @@ RPC_HTTP_URL=
 RPC_WSS_URL_BACKUP=
 RPC_WSS_URL_2=
 RPC_WSS_URL_3=
+# Local node IPC socket. TRANSPORT_MODE=auto|ipc makes it the primary (verified
+# with eth_blockNumber); a missing socket or failed handshake falls back to
+# RPC_WSS_URL. ws|http ignore it; ipc without RPC_IPC_PATH is a config error
 RPC_IPC_PATH=
 TRANSPORT_MODE=auto
-
-# ─── Chain Config (Base Mainnet) ───
-CHAIN_ID=8453
+# Optional per-pool read endpoints (HTTP or WSS) for pool state / TickBitmap /
+# optimistic reads. POOL_A = UniV3 leg, POOL_B = Slipstream leg. Block
+# subscription and execution stay on the primary; failed reads fall back to it
+POOL_A_RPC_URL=
+POOL_B_RPC_URL=
+
+# ─── Chain Config ───
+# base | optimism | arbitrum | custom. Each field below is taken from the
+# profile table and can be overridden; custom requires CHAIN_ID, WETH_ADDRESS,
+# USDC_ADDRESS and AAVE_POOL_ADDRESS. The RPC chain id is verified at startup
+CHAIN_PROFILE=base
+# CHAIN_ID=8453
+# WETH_ADDRESS=
+# USDC_ADDRESS=
+# L1 data fee oracle (OP Stack GasPriceOracle); "none" disables the L1 fee component
+# L1_FEE_ORACLE_ADDRESS=
+# fifo_bribe (profit share as priority fee) | eip1559 (fixed standard tip)
+# PRIORITY_FEE_STRATEGY=
 
 # ─── Wallet and Contract ───
 PRIVATE_KEY=
 KEYSTORE_PATH=
 ARBITRAGE_CONTRACT_ADDRESS=
+# Pause view read every block (batched with pool sync); while it returns true
+# execution is forced to shadow. Another parameterless view (isPaused()) or a
+# raw 4-byte selector (0xb187bd26) also works; a contract without it disables the gate
+CONTRACT_PAUSE_SELECTOR=paused()
 
 # ─── MEV Protection (optional) ───
 PRIVATE_RPC_URL=
 
 # ─── Cost and Strategy (in WETH) ───
 GAS_COST_FALLBACK_WETH=0.00005
+# Gas used for gas-limit / bribe math when no REVM measurement exists
+# (no CONTRACT_ADDRESS → math validation only, its gas is a heuristic)
+GAS_ESTIMATE=350000
 FLASH_LOAN_FEE_BPS=0.0
+# Aave V3 Pool (defaults to the chain profile's) — FLASHLOAN_PREMIUM_TOTAL is verified hourly
+# AAVE_POOL_ADDRESS=
+# true: adopt on-chain flash loan / pool fees on mismatch; false: warn and keep configured
+AUTO_UPDATE_FEES=false
 MIN_NET_PROFIT_WETH=0.000005
 MAX_TRADE_SIZE_WETH=5.0
+# Dust floor: NR never sizes below this and no calldata is built under it
+MIN_TRADE_SIZE_WETH=0.01
+# Fixed staleness cutoff: fallback until the adaptive estimate is calibrated,
+# and the cutoff for both stages when ADAPTIVE_STALENESS=false
 MAX_STALENESS_MS=3000
 STATS_INTERVAL=10
 MAX_RETRIES=0
+# Spread screen uses executable prices for this size instead of raw spot (0 = spot)
+SPREAD_PROBE_SIZE_WETH=0.1
+
+# ─── Competition Penalty (queue depth) ───
+# Extra competitor targets besides watched pools (comma-separated router addresses)
+COMPETITION_ROUTERS=
+# Penalty when the previous block had >= N txs to watched pools/routers (0 = off)
+COMPETITION_MIN_TXS=2
+COMPETITION_PROFIT_MULT=2.0
+# Skip the block entirely above this count (0 = never skip)
+COMPETITION_SKIP_LIMIT=4
+
+# ─── REVM vs Exact Math ───
+# Max REVM/exact-math profit discrepancy in wei before execution is skipped (0 = log only)
+SIM_MATH_MAX_DISCREPANCY=0
+
+# ─── Sequencer Head Feed ───
+# Optional second new-heads source that delivers blocks earlier than RPC_WSS_URL
+# (op-node unsafe head WS or a provider's fast-head stream). Empty = single source
+SEQUENCER_WS_URL=
+
+# ─── Opportunity Filter Chain ───
+# Ordered, comma-separated filter names (empty = default order):
+# staleness,pool_health,prefilter,profit_floor,bitmap_quality,block_pin,adverse_selection,
+# competition,direction_gate,execution_sanity,freshness_gate,robustness
+OPPORTUNITY_FILTERS=
+
+# ─── TickBitmap Quality ───
+# Only shadow-log opportunities priced without a real TickBitmap (single-tick fallback)
+REQUIRE_BITMAP_FOR_EXECUTION=true
+
+# ─── Adverse Selection (capital at risk) ───
+# Hypothetical competitor front-run on our route before our TX: WETH amount (0.5)
+# or percent of our trade size (25%). 0 = scenario off
+ADVERSE_FRONTRUN_WETH=25%
+# Only shadow-log opportunities whose profit drops to zero under that front-run
+REQUIRE_ADVERSE_POSITIVE=false
+
+# ─── Profit Sensitivity ───
+# Before execution the selected opportunity is re-priced with each pool moved
+# 1 and 2 ticks against us; robustness = share of profit kept (0..1). Fragile
+# opportunities get a higher bribe. Minimum robustness to execute (also requires
+# profit after a 1-tick move > 0). 0 = filter off
+MIN_ROBUSTNESS=0
+
+# ─── Warmup Gate ───
+# After every (re)connect, execution stays shadow-only until this many consecutive
+# clean blocks (all pools synced, bitmaps fresh, no sync errors). 0 = off
+WARMUP_CLEAN_BLOCKS=3
+
+# ─── Data Directory ───
+# Every artifact (shadow/state-diff logs, snapshots/, gas_ledger.json, tokens.json,
+# matched_pools.json, session summaries) is written under this directory with
+# 0600 files / 0700 dirs. Empty = working directory. Files still found at the
+# old working-directory location are read from there until rewritten.
+DATA_DIR=
+
+# ─── Session Summary ───
+# session_summary_<UTC time>_<reason>.json is written here on Ctrl+C, on the
+# MAX_RETRIES exit and once per UTC day (config fingerprint without secrets)
+SUMMARY_DIR=session_summaries
+
+# ─── Liquidity Analytics (idle-time, background) ───
+# Per-run time budget in ms for ±1/2/5% liquidity, Gini and net ΔL stats (0 = off)
+LIQUIDITY_ANALYTICS_BUDGET_MS=300
 
 # ─── Pool Fee Filter ───
 MAX_POOL_FEE_BPS=100
 
+# ─── Constant-Product Pools ───
+# Discovery also takes Uniswap V2 / Aerodrome vAMM pools. The arbitrage
+# contract has no V2 swap path: these opportunities are priced and shadow
+# logged only ("constant_product"), never executed
+DISCOVER_CONSTANT_PRODUCT=false
+
 # ─── TickBitmap Depth Settings ───
 TICK_BITMAP_RANGE=100
 TICK_BITMAP_MAX_AGE_BLOCKS=5
 
+# ─── NR Sizing Backend ───
+# math: exact U256 math | revm: pool bytecode quotes (~0.5ms/probe, 12-point scan)
+# auto: revm only when a pool has no fresh TickBitmap
+SIZING_BACKEND=math
+# Pre-filter (executable prices + PreFilter) and NR coarse scan numerics:
+# f64 (default) | fixed: Q63.64 fixed-point + U256, bit-identical on every
+# CPU/compiler. The secant refinement uses exact U256 swap math either way
+SIZING_NUMERICS=f64
+# Two-step (partial-fill) plan for the selected opportunity: the optimum is
+# applied to both pools with exact math and the check re-runs on the post-trade
+# state; a second profitable trade in the same direction is reported + logged
+TWO_STEP_SIZING=false
+# Execute step 2 of that plan: only after the first TX's receipt confirms and a
+# fresh state check still supports the direction (needs TWO_STEP_SIZING=true)
+EXECUTE_FOLLOWUP=false
+
+# ─── Optimistic Updates (pending TX) ───
+# Also append every optimistic pool refresh (pool, old/new price, trigger TX hash)
+# to optimistic_updates.jsonl
+OPTIMISTIC_UPDATE_LOG=false
+
+# ─── Token Behavior Probe ───
+# Tokens are probed at startup (REVM transfer test, cached in tokens.json).
+# Pairs with fee-on-transfer / rebasing tokens are shadow-logged only unless true
+ALLOW_NONSTANDARD_TOKENS=false
+
+# ─── A/B Shadow Evaluation ───
+# Env-style overlay of tunable parameters (MIN_NET_PROFIT_WETH, BRIBE_PCT, ...).
+# Variant B re-runs opportunity detection on the same states every block and
+# both variants' decisions go to ab_shadow.jsonl. Empty = disabled
+SECONDARY_CONFIG_PATH=
+
+# ─── Approval Audit ───
+# Contract allowance(contract → pool / flash lender) check at startup + daily
+# (execution only). off | soft (warn) | hard (downgrade to shadow if zero)
+APPROVAL_AUDIT_MODE=soft
+# Spenders whose allowance is mandatory: none | flash_lender | pools | all
+# Arbitraj.sol repays by push transfer inside callbacks → none
+APPROVAL_REQUIRED_SPENDERS=none
+
+# ─── Replay ───
+# Per-block pool scalars → state_diff.jsonl (changed pools + periodic full
+# keyframes). Replay with: arbitraj_botu --replay-diff state_diff.jsonl
+# What-if on one recorded block (repeatable --override, optional --combo I):
+#   arbitraj_botu --what-if --block N --override 'poolA.liquidity*=2' --override 'poolB.tick+=50'
+STATE_DIFF_LOG=false
+# Compact framed binary (state_diff.bin / shadow_analytics.bin) instead of
+# JSONL — same records, ~10x smaller. Readers detect the format; convert with
+#   arbitraj_botu --convert-log state_diff.bin state_diff.jsonl
+LOG_FORMAT_BINARY=false
+# Terminal output: pretty = emoji lines, json = one JSON object per line
+# (block_update, spread, opportunity, simulation_failure, tx_sent,
+# tx_confirmed, circuit_breaker, reconnect; other lines as "message")
+LOG_FORMAT=pretty
+
+# ─── Block Backlog ───
+# When processing falls behind the 2s block time, buffered headers are dropped
+# and the loop jumps to the newest one. true = process every block serially
+# (analytics where completeness matters more than latency)
+PROCESS_ALL_BLOCKS=false
+
+# ─── Fast Path Sync ───
+# Phase 1 reads only slot0 for all pools (one Multicall3); when no price moved
+# since the last evaluated block, the opportunity check is skipped ("quiet")
+FAST_PATH_SYNC=true
+# Full sync (slot0 + liquidity + fee) every N blocks — catches missed mint/burn
+FULL_SYNC_INTERVAL_BLOCKS=50
+# Each pool Swap event carries the post-swap price; a pinned read at or after
+# that block whose tick differs by more than this raises a divergence alarm and
+# the pool is re-read from RPC_HTTP_URL (0 = exact match required)
+EVENT_DIVERGENCE_TICKS=1
+# Every N blocks the code hash of each contract loaded into the REVM base_db
+# (pools, tokens, arbitrage contract) is compared with the chain; on change the
+# base_db is rebuilt and gas calibration reset (0 = disabled)
+BYTECODE_RECHECK_BLOCKS=1000
+
+# ─── Status Endpoint ───
+# Read-only JSON over HTTP for external dashboards / risk monitors:
+#   GET /status  /pools  /opportunities?limit=N  /config (secrets masked)
+# Empty = disabled. A non-loopback address also needs STATUS_ALLOW_REMOTE=true
+STATUS_LISTEN_ADDR=
+STATUS_ALLOW_REMOTE=false
+
 # ─── Shadow Mode ───
 EXECUTION_ENABLED=false
+# Live execution also needs an arm file (executor address + expiry), written by
+#   arbitraj_botu --arm --hours 12
+# Checked at startup and before every execution; deleting it reverts a running
+# bot to shadow mode
+ARM_FILE=ARMED
+# Paper execution (forces shadow mode): opportunities run through the full
+# execution path (nonce allocation, in-flight registry, rollback, stuck-TX
+# replacement, direction gate) against simulated receipts. Nothing is signed
+# or sent; results are logged and counted separately, tagged "paper"
+PAPER_EXECUTION=false
+# Simulated outcomes: revert probability, no-receipt (stuck until deadline)
+# probability, and receipt latency "min-max" ms (<= 10000)
+PAPER_FAILURE_RATE=0.1
+PAPER_DROP_RATE=0.02
+PAPER_RECEIPT_LATENCY_MS=300-2500
 
 # ─── RPC Failover & Latency Settings ───
 LATENCY_SPIKE_THRESHOLD_MS=200
+# Executor stall threshold (ms, 0 = off): a 10ms runtime probe waking this late,
+# or a marked synchronous section running this long, flags the block
+RUNTIME_STALL_THRESHOLD_MS=50
+# Extra attempts for Multicall3 / pool sync on transport errors (0..5, 25-75ms jitter)
+MULTICALL_RETRIES=2
 
 # ─── MEV & TX Settings ───
+# DEADLINE_BLOCKS: 1..100 | BRIBE_PCT: 0..0.9 | CIRCUIT_BREAKER_THRESHOLD: >= 1
 DEADLINE_BLOCKS=2
 BRIBE_PCT=0.25
 CIRCUIT_BREAKER_THRESHOLD=3
+# Global breaker: this many consecutive reverts / simulation failures (3 RPC
+# errors count as one) pause opportunity evaluation — sync and spread logging
+# continue. After the cooldown one probe re-enables evaluation; a failed probe
+# stays paused until circuit_breaker.json is set to "paused": false (or deleted)
+# or the bot is started with --reset-breaker. HARD_EXIT=true exits instead
+CIRCUIT_BREAKER_COOLDOWN_SECS=900
+CIRCUIT_BREAKER_HARD_EXIT=false
+# Concurrent execution tasks (1..16). When all slots are busy the opportunity is
+# skipped and shadow-logged ("inflight cap") instead of queued
+MAX_INFLIGHT_EXECUTIONS=2
+# Gas limit = REVM gas × buffer. fixed: 1.10 | auto: p95(actual/simulated receipt
+# gas) × 1.05, clamped 1.02..1.5, once MIN_CALIBRATION_SAMPLES live trades exist
+# (learned value persists in gas_ledger.json)
+GAS_BUFFER_MODE=fixed
+MIN_CALIBRATION_SAMPLES=20
+# Sizing gas = GAS_BASE_OVERHEAD + GAS_PER_TICK_CROSSING × (ticks crossed on both
+# legs at the evaluated size). With GAS_CROSSING_CALIBRATION=true the two
+# coefficients are fitted from REVM simulations and receipts once
+# MIN_CALIBRATION_SAMPLES samples exist; until then the last REVM gas is used
+GAS_BASE_OVERHEAD=160000
+GAS_PER_TICK_CROSSING=20000
+GAS_CROSSING_CALIBRATION=true
+# Post-trade impact check: each confirmed trade's pools are read at the
+# inclusion block and compared with the simulated final price per leg.
+# A leg off by more than IMPACT_WARN_TICKS warns; IMPACT_SLIPPAGE_BUMP_BPS > 0
+# then lowers the minProfit slippage factor for IMPACT_BUMP_BLOCKS blocks
+IMPACT_WARN_TICKS=10
+IMPACT_SLIPPAGE_BUMP_BPS=0
+IMPACT_BUMP_BLOCKS=300
+# Quoter parity audit: every AUDIT_INTERVAL_BLOCKS blocks (0 = off) each pool's
+# current state is quoted off-chain (sizing path + exact engine) at 0.1 / 1 / 5
+# WETH and the latest NR size, and compared with the official QuoterV2 /
+# Slipstream quoter at the same block. Deviation above AUDIT_MAX_DEVIATION_BPS
+# warns (console + Telegram) — stale bitmap or math bug. --audit-now runs once.
+AUDIT_INTERVAL_BLOCKS=500
+AUDIT_MAX_DEVIATION_BPS=10
+# Direction gate: each (buy pool, sell pool) direction keeps its own receipt
+# record. When the success rate over its last DIRECTION_GATE_WINDOW attempts
+# (0 = off, stats only; decided after DIRECTION_GATE_MIN_ATTEMPTS) drops below
+# DIRECTION_GATE_MIN_SUCCESS_RATE the direction is restricted:
+#   multiplier: executes only if profit >= MIN_NET_PROFIT_WETH × DIRECTION_GATE_PROFIT_MULTIPLIER
+#   suspend:    not executed at all (opportunities are still shadow-logged)
+# The restriction lifts after DIRECTION_GATE_COOLDOWN_BLOCKS, or earlier when
+# shadow-logged opportunities' modeled profit reaches DIRECTION_GATE_EDGE_WIDEN ×
+# the edge at restriction time
+DIRECTION_GATE_WINDOW=20
+DIRECTION_GATE_MIN_ATTEMPTS=5
+DIRECTION_GATE_MIN_SUCCESS_RATE=0.3
+DIRECTION_GATE_MODE=multiplier
+DIRECTION_GATE_PROFIT_MULTIPLIER=2
+DIRECTION_GATE_COOLDOWN_BLOCKS=1800
+DIRECTION_GATE_EDGE_WIDEN=1.5
+# Structural divergence: a real spread closes within blocks. When a pair's spot
+# spread stays above PERSISTENT_SPREAD_FLOOR_PCT for PERSISTENT_SPREAD_BLOCKS
+# blocks (0 = off) without dipping below PERSISTENT_SPREAD_RESET_PCT, it is
+# treated as a depeg / token migration / decoding problem: the pair goes
+# shadow-only and a marker file DATA_DIR/divergence/<pool>_<pool>.marker is
+# written. Execution resumes when the spread closes, the marker is deleted, or
+# the bot is started with --ack-divergence
+PERSISTENT_SPREAD_BLOCKS=300
+PERSISTENT_SPREAD_FLOOR_PCT=0.5
+PERSISTENT_SPREAD_RESET_PCT=0.1
+# Submission pacing for several instances on one host (both 0 = off):
+# SUBMIT_JITTER_MS: random delay before each send, "min-max" ms (e.g. 0-15)
+# MIN_SUBMIT_SPACING_MS: local instances sharing DATA_DIR never submit closer
+# than this (submit_spacing.lease); the later one waits, or skips the trade
+# when the wait would overrun the block's pipeline budget
+SUBMIT_JITTER_MS=0
+MIN_SUBMIT_SPACING_MS=0
 
 # ─── Admin (optional) ───
 ADMIN_ADDRESS=
@@ -564,9 +1448,107 @@ TELEGRAM_CHAT_ID=
 TELEGRAM_ENABLED=false
 TELEGRAM_SHIFT_INTERVAL_SECS=21600
 TELEGRAM_BALANCE_WARN_ETH=0.05
+
+# ─── Opportunity Snapshot Archive ───
+# snapshots/<id>.json.gz — 0 disables archival
+SNAPSHOT_KEEP=200
+
+# ─── Idle Re-simulation of Shadow Opportunities ───
+# The day's top-K shadow opportunities are re-evaluated at full precision
+# (REVM with pool bytecode, widened bitmap, large probe budget) once no
+# opportunity has been seen for REVISIT_QUIET_BLOCKS consecutive blocks.
+# One entry per quiet block, at most REVISIT_BUDGET_MS each; market activity
+# preempts it. Results go to revisits.json. REVISIT_TOP_K=0 disables
+REVISIT_TOP_K=8
+REVISIT_QUIET_BLOCKS=30
+REVISIT_BUDGET_MS=250
+
+# ─── Pair Evaluation Priority ───
+# Pairs are evaluated each block in order of a per-pair expected-value score
+# (exponentially decayed profit found per evaluation, corrected by realized
+# receipts; PAIR_SCORE_DECAY = weight of the newest evaluation). Once
+# STRATEGY_TIME_BUDGET_MS of the block is spent, remaining pairs are skipped
+# (0 = no cutoff). A pair not evaluated for PAIR_ROTATION_BLOCKS blocks gets
+# the guaranteed second slot so low-scoring pairs are still sampled
+STRATEGY_TIME_BUDGET_MS=400
+PAIR_ROTATION_BLOCKS=20
+PAIR_SCORE_DECAY=0.1
+
+# ─── RPC Call Census ───
+# Every pool read is counted per block and per opportunity, keyed by
+# (method, target contract, call site), with await time; verbose logs and the
+# stats box show the top call sites. The same (method, target) read more than
+# REDUNDANCY_WARN times within one opportunity is flagged (0 = no warning).
+# slot0/liquidity reads pinned to the current block hash are memoized per block
+REDUNDANCY_WARN=2
+
+# ─── Degradation Ladder ───
+# When a block runs long, optional stages are shed in a fixed order instead of
+# slowing everything: 1 A/B shadow + multi-hop, 2 near-miss shadow sims,
+# 3 adverse scenario / sensitivity / two-step, 4 bitmap refresh (stale bitmap),
+# 5 both-direction flash routes, 6 core detection (top-ranked pair only).
+# Rung 1 sheds at DEGRADE_START_PCT of the block budget, rung 6 near 100%
+# (0 = ladder off). Staleness / pool health / sanity guards are never shed.
+# A shed rung is restored one at a time after DEGRADE_RECOVERY_BLOCKS calm blocks
+DEGRADE_START_PCT=50
+DEGRADE_RECOVERY_BLOCKS=5
+
+# ─── Reorg-Safe Accounting ───
+# Trade outcomes are provisional at the first receipt. CONFIRMATION_BLOCKS
+# later the receipt is re-read: same block → final; moved or gone (reorg) →
+# PnL, direction win rates and heatmap get explicit correction entries
+CONFIRMATION_BLOCKS=10
+
+# ─── Adaptive Staleness ───
+# Detection cutoff = p95 observed block interval − p95 sync time, execution
+# cutoff = detection × STALENESS_EXEC_FRACTION; recomputed every 300 blocks
+# and clamped to [STALENESS_MIN_MS, STALENESS_MAX_MS]. Rejections that the fixed
+# MAX_STALENESS_MS would have passed are counted separately in the stats box
+ADAPTIVE_STALENESS=true
+STALENESS_MIN_MS=300
+STALENESS_MAX_MS=3000
+STALENESS_EXEC_FRACTION=0.75
+
+# ─── Shadow Outcome Verification ───
+# SHADOW_VERIFY_BLOCKS after a shadow entry, both pools are re-read at that
+# block and the same optimal_amount_weth is re-priced; a "shadow_verify" record
+# with the entry's opportunity_id is appended to shadow_analytics. At most
+# SHADOW_VERIFY_PER_MIN entries per minute (the rest are counted as dropped).
+# SHADOW_VERIFY_BLOCKS=0 disables
+SHADOW_VERIFY_BLOCKS=2
+SHADOW_VERIFY_PER_MIN=30
+
+# ─── Tick Bitmap Refresh ───
+# Between full rescans the bitmap is refreshed incrementally: all words in the
+# window are re-read, but tick details only for ticks that entered the window
+# or sit in a changed word. Every TICK_BITMAP_FULL_RESYNC_BLOCKS blocks a full
+# rescan bounds any drift
+TICK_BITMAP_FULL_RESYNC_BLOCKS=600
+
+# ─── Prometheus Metrics ───
+# Separate listener serving only GET /metrics (Prometheus text format):
+# block / opportunity / trade counters, per-pool price, liquidity, tick and
+# staleness gauges, block sync and simulate() latency histograms.
+# Empty = disabled (STATUS_LISTEN_ADDR also serves /metrics). A non-loopback
+# address also needs STATUS_ALLOW_REMOTE=true
+METRICS_LISTEN_ADDR=
+
+# ─── Exact Sizing ───
+# When both pools have tick bitmaps and the f64 optimum is at least this many
+# WETH, the trade size is re-searched (coarse scan + golden-section) on the
+# exact U256 swap math that later produces minProfit. 0 = always
+EXACT_SIZING_MIN_WETH=1.0
+
+# ─── Pending Swap Prediction ───
+# A pending swap() sent directly to a watched pool is applied locally (exact
+# multi-tick swap on the current state and tick bitmap). Opportunity detection
+# uses the predicted price while the chain head is at most this many blocks
+# past the state the prediction was built on
+PREDICTED_STATE_TRUST_BLOCKS=1
 "#;
 
-    match std::fs::write(".env", template) {
+    // .env gizli anahtarlar taşır — 0o600
+    match secure_fs::write(std::path::Path::new(".env"), template) {
         Ok(_) => {
             println!();
             println!("╔══════════════════════════════════════════════════════════════════╗");
@@ -592,6 +1574,21 @@ TELEGRAM_BALANCE_WARN_ETH=0.05
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // ═══ CLI: --devnet-scenario <file.toml> ile yerel devnet senaryosu ═══
+    // .env okunmadan önce: bot alt süreci kendi geçici .env'iyle koşar
+    if let Some(pos) = std::env::args().position(|a| a == "--devnet-scenario") {
+        let path = std::env::args()
+            .nth(pos + 1)
+            .ok_or_else(|| eyre::eyre!("Usage: --devnet-scenario <file.toml>"))?;
+        #[cfg(feature = "dev")]
+        return devnet::cli_devnet_scenario(std::path::Path::new(&path)).await;
+        #[cfg(not(feature = "dev"))]
+        return Err(eyre::eyre!(
+            "--devnet-scenario {} needs a dev build: cargo run --features dev -- --devnet-scenario <file.toml>",
+            path
+        ));
+    }
+
     // ═══ GÖREV 3: Kendi Kendini Onaran .env Şablonu ═══
     // .env dosyası yoksa standart HFT şablonu oluştur ve zarifçe kapat.
     if dotenvy::dotenv().is_err() {
@@ -599,12 +1596,15 @@ async fn main() -> Result<()> {
             generate_default_env_and_exit();
         }
         // .env var ama parse hatası olabilir — devam et, env::var fallback'leri yeterli
-        eprintln!(
+        eprintln_high!(
             "  {} .env file was read but some lines could not be parsed — defaults will be used.",
             "⚠️".yellow()
         );
     }
 
+    // ═══ Veri dizini (DATA_DIR) — CLI alt komutları da aynı yolları okur ═══
+    secure_fs::init_from_env().map_err(|e| eyre::eyre!("DATA_DIR could not be created: {}", e))?;
+
     // ═══ CLI: --encrypt-key argümanı ile keystore oluşturma ═══
     let args: Vec<String> = std::env::args().collect();
     if args.iter().any(|a| a == "--encrypt-key") {
@@ -622,8 +1622,48 @@ async fn main() -> Result<()> {
         return dust_sweeper::run_sweep(execute).await;
     }
 
+    // ═══ CLI: --inspect-snapshot <id> [--recompute] ile fırsat arşivi incelemesi ═══
+    if let Some(pos) = args.iter().position(|a| a == "--inspect-snapshot") {
+        let id = args.get(pos + 1).ok_or_else(|| {
+            eyre::eyre!("Usage: --inspect-snapshot <opportunity_id> [--recompute]")
+        })?;
+        let recompute = args.iter().any(|a| a == "--recompute");
+        return snapshot::cli_inspect_snapshot(id, recompute);
+    }
+
+    // ═══ CLI: --verify-log <shadow_analytics.jsonl> ile log bütünlük doğrulaması ═══
+    if let Some(pos) = args.iter().position(|a| a == "--verify-log") {
+        let path = args
+            .get(pos + 1)
+            .ok_or_else(|| eyre::eyre!("Usage: --verify-log <shadow_analytics.jsonl>"))?;
+        return log_integrity::cli_verify_log(path);
+    }
+
+    // ═══ CLI: --convert-log <girdi> <çıktı> ile JSONL ↔ ikili log dönüşümü ═══
+    if let Some(pos) = args.iter().position(|a| a == "--convert-log") {
+        let usage = "Usage: --convert-log <input> <output>";
+        let input = args.get(pos + 1).ok_or_else(|| eyre::eyre!("{}", usage))?;
+        let output = args.get(pos + 2).ok_or_else(|| eyre::eyre!("{}", usage))?;
+        return binlog::cli_convert_log(input, output);
+    }
+
+    // ═══ CLI: --force-unlock <executor_address> ile takılı kalmış tek süreç kilidini kaldırma ═══
+    if let Some(pos) = args.iter().position(|a| a == "--force-unlock") {
+        let address = args
+            .get(pos + 1)
+            .ok_or_else(|| eyre::eyre!("Usage: --force-unlock <executor_address>"))?;
+        return instance_lock::cli_force_unlock(address);
+    }
+
+    // ═══ CLI: --print-heatmap ile gün × saat fırsat ısı haritası ═══
+    if args.iter().any(|a| a == "--print-heatmap") {
+        return heatmap::cli_print_heatmap();
+    }
+
     // Yapılandırmayı oku
     let mut config = BotConfig::from_env()?;
+    // LOG_FORMAT=json: bundan sonraki tüm konsol satırları JSON nesnesi
+    log_event::init(config.log_format);
 
     // ═══ CLI: --mode shadow|live ile mod geçersiz kılma ═══
     if let Some(pos) = args.iter().position(|a| a == "--mode") {
@@ -631,14 +1671,14 @@ async fn main() -> Result<()> {
             match mode.to_lowercase().as_str() {
                 "shadow" => {
                     config.execution_enabled_flag = false;
-                    println!(
+                    println_high!(
                         "  {} CLI: --mode shadow → Shadow mode forced",
                         "👻".yellow()
                     );
                 }
                 "live" => {
                     config.execution_enabled_flag = true;
-                    println!("  {} CLI: --mode live → Live mode forced", "🚀".green());
+                    println_high!("  {} CLI: --mode live → Live mode forced", "🚀".green());
                 }
                 other => {
                     return Err(eyre::eyre!(
@@ -650,16 +1690,127 @@ async fn main() -> Result<()> {
         }
     }
 
+    // ═══ CLI: --print-config ile çözümlenmiş yapılandırma dökümü (gizli değerler maskeli) ═══
+    if args.iter().any(|a| a == "--print-config") {
+        println_high!("  {} Resolved configuration:", "🧾".cyan());
+        for (key, value) in config.redacted_entries() {
+            println_high!("    {:<30} = {}", key, value);
+        }
+        return Ok(());
+    }
+
+    // ═══ CLI: --replay-diff <state_diff.jsonl> [--size-tol-pct X] ile oturum yeniden oynatma ═══
+    // Ağ erişimi yok: havuzlar mevcut core_pools.json / matched_pools.json'dan okunur.
+    if let Some(pos) = args.iter().position(|a| a == "--replay-diff") {
+        let path = args.get(pos + 1).ok_or_else(|| {
+            eyre::eyre!("Usage: --replay-diff <state_diff.jsonl> [--size-tol-pct X]")
+        })?;
+        let tolerance_pct = match args.iter().position(|a| a == "--size-tol-pct") {
+            Some(i) => args
+                .get(i + 1)
+                .and_then(|v| v.parse::<f64>().ok())
+                .ok_or_else(|| eyre::eyre!("--size-tol-pct expects a number"))?,
+            None => replay::DEFAULT_SIZE_TOLERANCE_PCT,
+        };
+        let matched_cfg = match pool_discovery::load_core_pools() {
+            Some(core_cfg) => core_cfg,
+            None => pool_discovery::load_matched_pools()?,
+        };
+        let (pools, pair_combos) =
+            pool_discovery::build_runtime(&matched_cfg, config.max_tracked_pools)?;
+        return replay::cli_replay_diff(path, &config, &pools, &pair_combos, tolerance_pct);
+    }
+
+    // ═══ CLI: --what-if --block N --override '<ifade>' ... ile hipotetik blok değerlendirmesi ═══
+    // Durum state_diff.jsonl'dan kurulur; RPC yalnızca REVM için havuz bytecode'u okur.
+    if args.iter().any(|a| a == "--what-if") {
+        let usage = "Usage: --what-if --block N --override '<expr>' [--override ...] [--combo I] [--state-diff path]";
+        let flag_value = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1));
+        let block = flag_value("--block")
+            .and_then(|v| v.parse::<u64>().ok())
+            .ok_or_else(|| eyre::eyre!("{}", usage))?;
+        let overrides: Vec<String> = args
+            .windows(2)
+            .filter(|w| w[0] == "--override")
+            .map(|w| w[1].clone())
+            .collect();
+        if overrides.is_empty() {
+            return Err(eyre::eyre!("{}", usage));
+        }
+        let combo = match flag_value("--combo") {
+            Some(v) => v.parse::<usize>().map_err(|_| eyre::eyre!("--combo expects an index"))?,
+            None => 0,
+        };
+        let state_diff = flag_value("--state-diff")
+            .cloned()
+            .unwrap_or_else(|| {
+                let default = if config.log_format_binary {
+                    replay::STATE_DIFF_BIN_PATH
+                } else {
+                    replay::STATE_DIFF_LOG_PATH
+                };
+                secure_fs::read_path(default).to_string_lossy().into_owned()
+            });
+        let matched_cfg = match pool_discovery::load_core_pools() {
+            Some(core_cfg) => core_cfg,
+            None => pool_discovery::load_matched_pools()?,
+        };
+        let (pools, pair_combos) =
+            pool_discovery::build_runtime(&matched_cfg, config.max_tracked_pools)?;
+        let what_if_args = what_if::WhatIfArgs { block, overrides, combo, state_diff };
+        return what_if::cli_what_if(&what_if_args, &config, &pools, &pair_combos).await;
+    }
+
+    // ═══ CLI: --arm --hours N ile canlı yürütme kurma dosyası ═══
+    if args.iter().any(|a| a == "--arm") {
+        let hours = args
+            .iter()
+            .position(|a| a == "--hours")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|v| v.parse::<f64>().ok())
+            .ok_or_else(|| eyre::eyre!("Usage: --arm --hours N"))?;
+        let key_manager = tokio::task::spawn_blocking(key_manager::KeyManager::auto_load).await??;
+        let executor = key_manager
+            .private_key()
+            .ok_or_else(|| eyre::eyre!("--arm needs the executor key (KEYSTORE_PATH or PRIVATE_KEY)"))?
+            .parse::<alloy::signers::local::PrivateKeySigner>()
+            .map_err(|_| eyre::eyre!("Invalid private key"))?
+            .address();
+        let matched_cfg = match pool_discovery::load_core_pools() {
+            Some(core_cfg) => core_cfg,
+            None => pool_discovery::load_matched_pools()?,
+        };
+        let (pools, _) = pool_discovery::build_runtime(&matched_cfg, config.max_tracked_pools)?;
+        return arming::cli_arm(&config, &pools, executor, hours);
+    }
+
+    // ═══ CLI: --audit-now ile tek seferlik quoter eşlik denetimi ═══
+    if args.iter().any(|a| a == "--audit-now") {
+        let matched_cfg = match pool_discovery::load_core_pools() {
+            Some(core_cfg) => core_cfg,
+            None => pool_discovery::load_matched_pools()?,
+        };
+        let (pools, _) = pool_discovery::build_runtime(&matched_cfg, config.max_tracked_pools)?;
+        return quoter_audit::cli_audit_now(&config, &pools).await;
+    }
+
     // ═══ GÖREV 2: Auto-Bootstrap — Her başlangıçta havuz keşfi (v32.0) ═══
     // ═══ v29.0: CORE POOLS — Statik beyaz liste öncelikli ═══
     let matched_cfg = if let Some(core_cfg) = pool_discovery::load_core_pools() {
-        eprintln!(
+        eprintln_high!(
             "  {} core_pools.json found — skipping auto-discovery.",
             "⚙️".cyan()
         );
         core_cfg
     } else {
-        eprintln!(
+        // DexScreener keşfi ve factory listesi Base'e özgü
+        if config.chain_profile != chain_profile::ChainProfile::Base {
+            return Err(eyre::eyre!(
+                "Auto pool discovery only supports Base — provide core_pools.json for CHAIN_PROFILE={}",
+                config.chain_profile
+            ));
+        }
+        eprintln_high!(
             "  {} Auto pool discovery (Holy Trinity) starting...",
             "🔍".cyan()
         );
@@ -686,7 +1837,7 @@ async fn main() -> Result<()> {
 
     // ═══ v11.0: TOKEN WHITELIST DOĞRULAMA (tüm çiftler) ═══
     {
-        let wl = crate::types::token_whitelist();
+        let wl = config.token_whitelist();
         if !wl.contains(&config.weth_address) {
             return Err(eyre::eyre!(
                 "WETH address ({}) NOT in whitelist!",
@@ -702,26 +1853,146 @@ async fn main() -> Result<()> {
             }
         }
     }
-    println!(
+    println_high!(
         "  {} Token Whitelist: All token addresses verified ({} pools)",
         "✅".green(),
         pools.len()
     );
 
+    // ═══ ZİNCİR DOĞRULAMA — RPC'nin zinciri CHAIN_PROFILE ile uyuşmalı ═══
+    // Yanlış zincirde profilin adresleriyle (WETH, Aave, oracle) çalışmak
+    // sessizce yanlış fiyat / başarısız TX demektir → başlatmayı reddet.
+    {
+        let http_url: reqwest::Url = config
+            .rpc_http_url
+            .parse()
+            .map_err(|e| eyre::eyre!("RPC_HTTP_URL parse error: {}", e))?;
+        match ProviderBuilder::new().connect_http(http_url).get_chain_id().await {
+            Ok(reported) => {
+                chain_profile::verify_rpc_chain_id(config.chain_profile, config.chain_id, reported)
+                    .map_err(|e| eyre::eyre!(e))?;
+                println_high!(
+                    "  {} Chain: {} (chain id {} verified against RPC)",
+                    "✅".green(),
+                    config.chain_profile.display_name(),
+                    reported
+                );
+            }
+            Err(e) => {
+                eprintln_high!(
+                    "  {} Chain id check failed, trusting CHAIN_PROFILE={} (chain id {}): {}",
+                    "⚠️".yellow(),
+                    config.chain_profile,
+                    config.chain_id,
+                    e
+                );
+            }
+        }
+    }
+
     // ═══ v9.0: KEY MANAGER BAŞLATMA ═══
     // Öncelik: 1) Şifreli keystore → 2) Env var (uyarıyla) → 3) Key yok
-    let key_manager = key_manager::KeyManager::auto_load()?;
+    // PBKDF2 (600K tur) + parola istemi senkron — runtime worker'ı bloklamasın
+    let key_manager = tokio::task::spawn_blocking(key_manager::KeyManager::auto_load).await??;
+    config.attach_key_manager(&key_manager);
     if key_manager.has_key() {
-        config.key_manager_active = true;
-        // Keystore'dan gelen key'i config.private_key'e de aktar (geriye uyumluluk)
-        if config.private_key.is_none() {
-            config.private_key = key_manager.private_key().map(|k: &str| k.to_string());
-        }
-        println!("  {} Key Mgmt: {}", "🔐".green(), key_manager.source());
+        println_high!("  {} Key Mgmt: {}", "🔐".green(), key_manager.source());
     } else {
-        println!("  {} Key Mgmt: No key loaded (observe mode)", "ℹ️".blue());
+        println_high!("  {} Key Mgmt: No key loaded (observe mode)", "ℹ️".blue());
+    }
+
+    // ═══ İMZALAMA BAĞLAMI — key bir kez parse edilir ═══
+    // Chain id yukarıda RPC'ye karşı doğrulandı. İşlem yolunda key parse /
+    // wallet / filler kurulumu yapılmaz; tüm execution task'ları bu Arc'ı
+    // paylaşır (reconnect'ler arasında da).
+    let signing_ctx: Option<Arc<executor::SigningContext>> = match key_manager.private_key() {
+        Some(pk) => {
+            let ctx = executor::SigningContext::new(
+                pk,
+                config.chain_id,
+                config.private_rpc_url.as_deref(),
+            )?;
+            println_high!(
+                "  {} Signer: {} (chain id {} cached, no fillers)",
+                "✍️".green(),
+                ctx.address(),
+                ctx.chain_id()
+            );
+            Some(Arc::new(ctx))
+        }
+        None => None,
+    };
+
+    // ═══ KURMA DOSYASI — EXECUTION_ENABLED tek başına canlı yürütme açmaz ═══
+    // Geçerli kurma dosyası yoksa (--arm ile yazılır) oturum gölge modda başlar.
+    if let Some(guard) = arming::ArmingGuard::for_session(&config, signing_ctx.as_ref().map(|c| c.address())) {
+        if guard.status().is_armed() {
+            println_high!(
+                "  {} Arming: {} {}",
+                "🔫".green(),
+                config.arm_file,
+                guard.status_line(clock::now())
+            );
+        } else {
+            config.execution_enabled_flag = false;
+            eprintln_high!(
+                "  {} {} {} ({}) — EXECUTION DISABLED, running shadow only. Arm with --arm --hours N.",
+                "🚨".red(),
+                "NOT ARMED:".red().bold(),
+                guard.status(),
+                config.arm_file
+            );
+        }
     }
 
+    // ═══ TEK SÜREÇ KİLİDİ — aynı yürütücü anahtarını iki süreç paylaşamaz ═══
+    // İki süreç aynı zincir nonce'undan başlarsa TX'ler birbirini ezer. Kilit
+    // main() scope'unda yaşar; zarif kapanışta (return) drop ile bırakılır.
+    let _instance_lock = match signing_ctx.as_ref().filter(|_| config.execution_enabled()) {
+        Some(ctx) => {
+            let path =
+                instance_lock::lock_path(&instance_lock::default_lock_dir(), ctx.address());
+            match instance_lock::acquire(&path) {
+                Ok(instance_lock::LockAttempt::Acquired { lock, stale }) => {
+                    if let Some(owner) = stale {
+                        println_high!(
+                            "  {} Instance lock: stale lock from {} (not running) taken over",
+                            "🔓".yellow(),
+                            owner
+                        );
+                    }
+                    println_high!("  {} Instance Lock: {}", "🔒".green(), lock.path().display());
+                    Some(lock)
+                }
+                Ok(instance_lock::LockAttempt::Held(owner)) => {
+                    config.execution_enabled_flag = false;
+                    let holder = owner.map_or("another process".to_string(), |o| o.to_string());
+                    eprintln_high!(
+                        "  {} {} {} holds {} — EXECUTION DISABLED, running observation/shadow only. \
+                         Stop the other instance or use --force-unlock {}.",
+                        "🚨".red(),
+                        "EXECUTOR KEY ALREADY IN USE:".red().bold(),
+                        holder,
+                        path.display(),
+                        ctx.address()
+                    );
+                    None
+                }
+                Err(e) => {
+                    config.execution_enabled_flag = false;
+                    eprintln_high!(
+                        "  {} Instance lock {} could not be acquired: {} — EXECUTION DISABLED",
+                        "🚨".red(),
+                        path.display(),
+                        e
+                    );
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
     // Banner göster
     print_banner(&config);
 
@@ -741,7 +2012,7 @@ async fn main() -> Result<()> {
             // (reconnect döngüsü boyunca aktif kalmalı)
             let tg_cancel = tokio_util::sync::CancellationToken::new();
             let sender = telegram::spawn_telegram_service(tg_config, tg_cancel);
-            println!(
+            println_high!(
                 "  {} Telegram Telemetry: {} (Chat: {})",
                 "📡".green(),
                 "ACTIVE".green().bold(),
@@ -749,7 +2020,7 @@ async fn main() -> Result<()> {
             );
             Some(sender)
         } else {
-            eprintln!(
+            eprintln_high!(
                 "  {} Telegram enabled but TELEGRAM_BOT_TOKEN or TELEGRAM_CHAT_ID missing",
                 "⚠️".yellow(),
             );
@@ -759,41 +2030,211 @@ async fn main() -> Result<()> {
         None
     };
 
-    // Yeniden bağlanma döngüsü
-    let mut retry_count: u32 = 0;
+    // ═══ Veri Dizini: çözümlenen artefakt yolları ═══
+    secure_fs::log_resolved_paths(&[
+        replay::SHADOW_LOG_PATH,
+        replay::SHADOW_BIN_PATH,
+        replay::STATE_DIFF_LOG_PATH,
+        snapshot::SNAPSHOT_DIR,
+        gas_calibration::GAS_LEDGER_PATH,
+        gas_calibration::TRADES_LOG_PATH,
+        nonce_checkpoint::NONCE_STATE_PATH,
+        circuit_breaker::CIRCUIT_BREAKER_PATH,
+        token_probe::TOKENS_CACHE_PATH,
+        ab_shadow::AB_SHADOW_LOG_PATH,
+        state_sync::OPTIMISTIC_UPDATES_PATH,
+        revisit::REVISITS_PATH,
+        heatmap::HEATMAP_PATH,
+        config.summary_dir.as_str(),
+    ]);
+
+    // ═══ Fırsat Snapshot Arşivi (snapshots/<id>.json.gz) ═══
+    // Yazıcı main() scope'unda yaşar — reconnect döngüsü boyunca aktif kalır
+    snapshot::spawn_snapshot_writer(config.snapshot_keep);
+    if config.snapshot_keep > 0 {
+        println_high!(
+            "  {} Snapshot Archive: {}/ (keep last {})",
+            "🗄️".cyan(),
+            secure_fs::data_path(snapshot::SNAPSHOT_DIR).display(),
+            config.snapshot_keep,
+        );
+    }
 
-    loop {
-        if retry_count > 0 {
-            println!("  {} Reconnection attempt #{}", "🔄".yellow(), retry_count);
-        }
+    // Multicall3 / sync yeniden deneme sınırı (MULTICALL_RETRIES)
+    state_sync::init_retry_policy(&config);
+
+    // ═══ Ücret Rejimi Doğrulaması (Aave premium + havuz fee()) ═══
+    // Yürürlükteki değerler reconnect'ler arasında korunur — init bir kez
+    fee_monitor::init(&config);
+    println_high!(
+        "  {} Fee Monitor: Aave Pool {} — hourly check (AUTO_UPDATE_FEES={})",
+        "🧾".cyan(),
+        config.aave_pool_address,
+        config.auto_update_fees,
+    );
 
-        match run_bot(&config, &mut pools, &mut pair_combos, &telegram_sender).await {
-            Ok(_) => {
-                println!("\n  {} Connection lost. Reconnecting...", "⚠️".yellow());
-                // v32.0: Telegram — bağlantı kopma bildirimi
-                if let Some(ref tg) = telegram_sender {
-                    tg.send(telegram::TelegramMessage::ConnectionLost {
-                        error: "WSS stream closed".to_string(),
-                        retry_count,
-                    });
-                }
-            }
-            Err(e) => {
-                // CancellationToken .cancel() eski listener'ları temizler.
-                // run_bot döndüğünde token scope'u biter, yeni döngüde
-                // yeni token üretilir.
-                let err_msg = format!("{:#}", e);
-                println!("\n  {} Error: {}", "❌".red(), &err_msg);
-                // v32.0: Telegram — hata bildirimi
-                if let Some(ref tg) = telegram_sender {
-                    tg.send(telegram::TelegramMessage::ConnectionLost {
-                        error: err_msg,
-                        retry_count,
-                    });
-                }
-            }
+    // ═══ Gas Limiti Kalibrasyonu (GAS_BUFFER_MODE) ═══
+    // Öğrenilen tampon gas_ledger.json'dan gelir — yeniden başlatmada korunur
+    gas_calibration::init(&config);
+    println_high!(
+        "  {} Gas Buffer: {} (min {} samples, ledger {})",
+        "⛽".cyan(),
+        config.gas_buffer_mode,
+        config.min_calibration_samples,
+        secure_fs::data_path(gas_calibration::GAS_LEDGER_PATH).display(),
+    );
+
+    // ═══ Fırsat Isı Haritası (haftanın günü × UTC saat) ═══
+    heatmap::init();
+
+    // ═══ Kağıt Yürütme (PAPER_EXECUTION — gölge modda sahte receipt'ler) ═══
+    paper::init(&config);
+    pair_scheduler::init(&config);
+    rpc_census::init(&config);
+    staleness::init(&config);
+    println_high!(
+        "  {} Gas Model: {} + {}/tick crossed ({})",
+        "⛽".cyan(),
+        config.gas_base_overhead,
+        config.gas_per_tick_crossing,
+        if config.gas_crossing_calibration { "calibrated" } else { "fixed" },
+    );
+
+    // ═══ İşlem Sonrası Havuz Etkisi Doğrulaması ═══
+    impact_verification::init(&config);
+
+    // ═══ Quoter Eşlik Denetimi (AUDIT_INTERVAL_BLOCKS) ═══
+    quoter_audit::init(&config);
+
+    // ═══ Yön Bazlı Başarı Kapısı (DIRECTION_GATE_*) ═══
+    direction_gate::init(&config);
+
+    // ═══ Kalıcı Spread → Yapısal Ayrışma (PERSISTENT_SPREAD_*) ═══
+    // --ack-divergence: önceki çalıştırmanın marker'ları onaylanır (silinir)
+    if args.iter().any(|a| a == "--ack-divergence") {
+        let acknowledged = divergence_guard::acknowledge_all();
+        println_high!("  {} [Divergence] {} structural divergence marker(s) acknowledged", "✅".green(), acknowledged);
+    }
+    divergence_guard::init(&config);
+
+    // ═══ Kontrat Pause Kapısı (CONTRACT_PAUSE_SELECTOR) ═══
+    contract_state::init(&config);
+
+    // ═══ Global Circuit Breaker (CIRCUIT_BREAKER_*) ═══
+    // --reset-breaker: önceki duraklama operatörce onaylanır (dosya silinir)
+    if args.iter().any(|a| a == "--reset-breaker") {
+        circuit_breaker::reset();
+    }
+    circuit_breaker::init(&config);
+
+    // ═══ İki Adımlı Planın İkinci Adımı (EXECUTE_FOLLOWUP) ═══
+    followup::init(&config);
+
+    // ═══ Sessiz Dönemde Gölge Fırsat Yeniden Değerlendirmesi (REVISIT_TOP_K) ═══
+    revisit::init(&config);
+
+    // ═══ Gölge Fırsatların Zincir Üstü Akıbeti (SHADOW_VERIFY_BLOCKS) ═══
+    shadow_verify::init(&config);
+
+    // ═══ Runtime Sağlığı: Executor Stall Probe'u (RUNTIME_STALL_THRESHOLD_MS) ═══
+    runtime_health::init(&config);
+
+    // ═══ TUI Paneli (--tui) ═══
+    // Terminal yoksa uyarı verilir, düz satır çıktısı sürer. Panel main() scope'unda
+    // yaşar — drop'ta terminal geri yüklenir (hata ile çıkışta da).
+    let tui = if args.iter().any(|a| a == "--tui") {
+        dashboard::Dashboard::start(config.execution_enabled())
+    } else {
+        None
+    };
+    let tui_handle = tui.as_ref().map(|d| d.handle().clone());
+    let tui_shutdown = tui_handle
+        .as_ref()
+        .map(|h| h.controls.shutdown.clone())
+        .unwrap_or_default();
+
+    // Isınma kapısı reconnect'ler arasında yaşar — her run_bot girişinde yeniden kapanır
+    let mut warmup_gate = warmup::WarmupGate::new(config.warmup_clean_blocks);
+
+    // Oturum sayaçları ve özet izleyici de reconnect'ler boyunca yaşar
+    let mut stats = ArbitrageStats::new();
+    let mut session =
+        session_summary::SessionTracker::new(&config.summary_dir, clock::now());
+
+    // Sıcak yol çıktısı bloklamayan kuyruktan geçer (journald pipe'ı blok işlemeyi durdurmaz)
+    if let Err(e) = console::init() {
+        eprintln_high!("  {} Console writer thread failed to start: {} — direct output", "⚠️".yellow(), e);
+    }
+
+    // Yeniden bağlanma döngüsü
+    let mut retry_count: u32 = 0;
+
+    loop {
+        if retry_count > 0 {
+            println_high!("  {} Reconnection attempt #{}", "🔄".yellow(), retry_count);
+            session.reconnects += 1;
         }
 
+        let outcome = tokio::select! {
+            result = run_bot(
+                &config,
+                &mut pools,
+                &mut pair_combos,
+                &telegram_sender,
+                &signing_ctx,
+                &mut warmup_gate,
+                &mut stats,
+                &mut session,
+                tui_handle.as_ref(),
+            ) => result,
+            _ = shutdown_signal(&tui_shutdown) => {
+                println_high!("\n  {} Shutdown signal received.", "🛑".yellow());
+                nonce_checkpoint::persist();
+                heatmap::persist();
+                strategy::flush_shadow_log();
+                console::flush(CONSOLE_FLUSH_TIMEOUT);
+                session.write_final(
+                    session_summary::SummaryReason::Shutdown,
+                    &config,
+                    &pools,
+                    &stats,
+                    &warmup_gate,
+                );
+                return Ok(());
+            }
+        };
+
+        // run_bot'un kuyruktaki satırları reconnect mesajlarından önce yazılsın
+        console::flush(CONSOLE_FLUSH_TIMEOUT);
+        let reason = match outcome {
+            Ok(_) => {
+                println_high!("\n  {} Connection lost. Reconnecting...", "⚠️".yellow());
+                // v32.0: Telegram — bağlantı kopma bildirimi
+                if let Some(ref tg) = telegram_sender {
+                    tg.send(telegram::TelegramMessage::ConnectionLost {
+                        error: "WSS stream closed".to_string(),
+                        retry_count,
+                    });
+                }
+                "WSS stream closed".to_string()
+            }
+            Err(e) => {
+                // CancellationToken .cancel() eski listener'ları temizler.
+                // run_bot döndüğünde token scope'u biter, yeni döngüde
+                // yeni token üretilir.
+                let err_msg = format!("{:#}", e);
+                println_high!("\n  {} Error: {}", "❌".red(), &err_msg);
+                // v32.0: Telegram — hata bildirimi
+                if let Some(ref tg) = telegram_sender {
+                    tg.send(telegram::TelegramMessage::ConnectionLost {
+                        error: err_msg.clone(),
+                        retry_count,
+                    });
+                }
+                err_msg
+            }
+        };
+
         retry_count += 1;
 
         if config.max_retries > 0 && retry_count >= config.max_retries {
@@ -805,11 +2246,22 @@ async fn main() -> Result<()> {
                 // Mesajın gönderilmesi için kısa bekleme
                 tokio::time::sleep(Duration::from_millis(500)).await;
             }
-            println!(
+            println_high!(
                 "  {} Maximum retries ({}) exceeded. Bot shutting down.",
                 "🛑".red(),
                 config.max_retries
             );
+            nonce_checkpoint::persist();
+            heatmap::persist();
+            strategy::flush_shadow_log();
+            console::flush(CONSOLE_FLUSH_TIMEOUT);
+            session.write_final(
+                session_summary::SummaryReason::BreakerExit,
+                &config,
+                &pools,
+                &stats,
+                &warmup_gate,
+            );
             return Err(eyre::eyre!("Maximum reconnection attempts exceeded"));
         }
 
@@ -827,13 +2279,100 @@ async fn main() -> Result<()> {
         let jitter_range = (delay_ms / 2).clamp(1, 2000);
         let jitter = rand::random::<u64>() % jitter_range;
         let delay_ms = delay_ms + jitter;
-        println!(
-            "  {} Reconnecting in {}ms... (attempt #{})",
-            "⚡".yellow(),
-            delay_ms,
-            retry_count
+        let emitted = log_event::json(|| {
+            log_event::LogEvent::Reconnect(log_event::Reconnect {
+                attempt: retry_count,
+                delay_ms,
+                reason,
+            })
+        });
+        if !emitted {
+            println_high!(
+                "  {} Reconnecting in {}ms... (attempt #{})",
+                "⚡".yellow(),
+                delay_ms,
+                retry_count
+            );
+        }
+        // ctrl_c dinleyicisi kurulduktan sonra SIGINT varsayılan davranışı
+        // kapanır — bekleme sırasında da yakalanmalı
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(delay_ms)) => {}
+            _ = shutdown_signal(&tui_shutdown) => {
+                println_high!("\n  {} Shutdown signal received.", "🛑".yellow());
+                nonce_checkpoint::persist();
+                heatmap::persist();
+                strategy::flush_shadow_log();
+                console::flush(CONSOLE_FLUSH_TIMEOUT);
+                session.write_final(
+                    session_summary::SummaryReason::Shutdown,
+                    &config,
+                    &pools,
+                    &stats,
+                    &warmup_gate,
+                );
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Ctrl+C veya TUI'de `q` — ikisi de aynı kapanış yolunu izler
+async fn shutdown_signal(tui_shutdown: &CancellationToken) {
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = tui_shutdown.cancelled() => {}
+    }
+    systemd::stopping();
+}
+
+/// Yeniden değerlendirme için yalnızca havuz bytecode'u taşıyan quote motoru —
+/// ana motorun base_db'si blok döngüsüne ait, paylaşılmaz
+fn attach_revisit_engine(config: &BotConfig, pools: &[PoolConfig], static_store: &StaticPoolStore) {
+    if config.revisit_top_k == 0 {
+        return;
+    }
+    let mut engine = SimulationEngine::new(config.chain_id);
+    engine.cache_bytecodes(pools, static_store);
+    revisit::attach_engine(engine);
+}
+
+/// Havuzların token bakiyelerini zincirden oku ve simülatöre yaz. base_db
+/// her kurulduğunda (açılış, hot-reload, bytecode değişimi) önce çağrılır —
+/// açılıştaki bakiyeler REVM'de bayatlamaz. Okunamayan bakiye eski değerde kalır.
+async fn refresh_pool_token_balances<P: Provider + Sync>(
+    provider: &P,
+    sim_engine: &mut SimulationEngine,
+    pools: &[PoolConfig],
+    balance_slots: &HashMap<Address, u64>,
+) {
+    for (&token, &slot) in balance_slots {
+        for pool in pools.iter().filter(|p| p.base_token_address == token || p.quote_token_address == token) {
+            match state_sync::read_token_balance(provider, token, pool.address, slot).await {
+                Ok(balance) => sim_engine.set_token_balance(token, pool.address, slot, balance),
+                Err(e) => eprintln_high!("  {} [{}] token balance read failed: {} — {}", "⚠️".yellow(), pool.name, token, e),
+            }
+        }
+    }
+}
+
+/// Degradation ladder kontrol noktası — yeni basamak atıldıysa bir kez loglanır
+fn ladder_checkpoint(
+    ladder: &mut degradation::DegradationPolicy,
+    at: degradation::Checkpoint,
+    block_start: Instant,
+    block_number: u64,
+) {
+    let elapsed = block_start.elapsed();
+    if let Some(level) = ladder.checkpoint(elapsed) {
+        eprintln_high!(
+            "     🪜 [Ladder] #{} {}ms at {} → shed up to rung {}: {}",
+            block_number,
+            elapsed.as_millis(),
+            at.label(),
+            level,
+            degradation::shed_names(level).join(", "),
         );
-        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
     }
 }
 
@@ -841,11 +2380,17 @@ async fn main() -> Result<()> {
 // BOT MOTORU — Blok Dinle → State Sync → Fırsat Tara → Simüle → Yürüt
 // ─────────────────────────────────────────────────────────────────────────────
 
+#[allow(clippy::too_many_arguments)]
 async fn run_bot(
     config: &BotConfig,
     pools: &mut Vec<PoolConfig>,
     pair_combos: &mut Vec<pool_discovery::PairCombo>,
     telegram_sender: &Option<telegram::TelegramSender>,
+    signing_ctx: &Option<Arc<executor::SigningContext>>,
+    warmup_gate: &mut warmup::WarmupGate,
+    stats: &mut ArbitrageStats,
+    session: &mut session_summary::SessionTracker,
+    tui: Option<&dashboard::DashboardHandle>,
 ) -> Result<()> {
     // ══════════════ CANCELLATION TOKEN (v11.0: Zombi Thread Önleme) ══════════════
     // Her run_bot çağrısında yeni bir CancellationToken üretilir.
@@ -865,57 +2410,78 @@ async fn run_bot(
     // Per-pair ardışık hata sayacı: combo_index → consecutive_failures
     let mut pair_failures: HashMap<usize, u32> = HashMap::new();
 
-    // ══════════════ MULTI-TRANSPORT BAĞLANTI (v10.0: RpcPool) ══════════════
-    // IPC öncelikli, Round-Robin WSS fallback, arka plan sağlık kontrolü
-    println!(
+    // ══════════════ BAĞLANTI YÖNETİCİSİ (v11.0: ConnectionManager) ══════════════
+    // IPC öncelikli primary, standby WSS'ler, opsiyonel submit endpoint'i.
+    // Tüm alt sistemler provider'ı buradan alır — ayrı soket açılmaz.
+    println_high!(
         "  {} Establishing transport connection ({:?} mode)...",
         "⏳".yellow(),
         config.transport_mode
     );
     let connect_start = Instant::now();
 
-    // RPC Pool için WSS URL listesi oluştur
-    let mut ws_urls = vec![config.rpc_wss_url.clone()];
-    if let Some(ref backup) = config.rpc_wss_url_backup {
-        ws_urls.push(backup.clone());
-    }
-    ws_urls.extend(config.rpc_wss_url_extra.iter().cloned());
-
-    // RpcPool oluştur ve bağlan
-    let mut rpc_pool = transport::RpcPool::new(config.rpc_ipc_path.clone(), &ws_urls);
-    rpc_pool.connect_all().await?;
-    let rpc_pool = Arc::new(rpc_pool);
+    let endpoint_plan = transport::plan_endpoints(
+        config.transport_mode,
+        config.rpc_ipc_path.as_deref(),
+        &config.rpc_wss_url,
+        config.rpc_wss_url_backup.as_deref(),
+        &config.rpc_wss_url_extra,
+        config.private_rpc_url.as_deref(),
+    );
+    let ipc_planned = endpoint_plan
+        .iter()
+        .any(|(_, url)| transport::TransportKind::of(url) == transport::TransportKind::Ipc);
+    let connections =
+        Arc::new(transport::ConnectionManager::connect(transport::RpcConnector, endpoint_plan).await?);
 
-    // Arka plan sağlık kontrolü başlat (2s aralıkla node yoklama)
-    rpc_pool.spawn_health_checker();
+    // Arka plan sağlık kontrolü (2s yoklama, aralıklı yeniden bağlanma) —
+    // run_bot çıkışında token ile durur
+    connections.spawn_health_checker(cancel_token.clone());
 
-    println!(
-        "  {} RpcPool ready: {} | Healthy nodes: {}",
+    println_high!(
+        "  {} Connections ready: {} | Healthy: {}",
         "✅".green(),
-        rpc_pool.transport_info().cyan(),
-        rpc_pool.healthy_node_count(),
+        connections.transport_info().cyan(),
+        connections.healthy_count(),
     );
 
-    // Primary provider al (ana döngü için)
-    let provider = rpc_pool.get_provider().await?;
-    let active_transport = rpc_pool.transport_info();
+    // Primary provider klonu (ana döngü, state sync okumaları). IPC primary
+    // kurulamadıysa handle WSS standby'dan gelir — etkin transport ondan
+    let primary = connections.acquire(transport::ConnectionRole::Primary)?;
+    let provider = primary.provider().clone();
+    let active_transport = primary.transport().label();
+    if ipc_planned && primary.transport() != transport::TransportKind::Ipc {
+        eprintln_high!(
+            "  {} IPC unavailable — running on {} (health check keeps retrying IPC; used from the next reconnect)",
+            "⚠️".yellow(),
+            active_transport,
+        );
+    }
 
     let total_connect_ms = connect_start.elapsed().as_millis();
 
     // ══════════════ MEV EXECUTOR (v21.0) ══════════════
-    let mev_executor = Arc::new(executor::MevExecutor::new(
-        config.private_rpc_url.clone(),
-        config.rpc_wss_url.clone(),
-        config.bribe_pct,
-    ));
+    let mev_executor = Arc::new(
+        executor::MevExecutor::new(
+            config.private_rpc_url.clone(),
+            config.bribe_pct,
+            signing_ctx.clone(),
+            config.max_inflight_executions as usize,
+        )
+        .with_priority_fee_strategy(config.priority_fee_strategy)
+        .with_submit_pacer(submit_pacing::SubmitPacer::from_config(config))
+        .with_confirmation_policy(confirmations::ConfirmPolicy::from_config(config))
+        .with_paper_lane(paper::lane()),
+    );
+    session.attach_executor(Arc::clone(&mev_executor));
     if config.private_rpc_url.is_some() {
-        println!(
+        println_high!(
             "  {} MEV Protection: {} (eth_sendRawTransaction active)",
             "🛡️".green(),
             "ACTIVE".green().bold()
         );
     } else {
-        println!(
+        println_high!(
             "  {} MEV Protection: {} (define PRIVATE_RPC_URL)",
             "⚠️".yellow(),
             "DISABLED".yellow().bold()
@@ -924,7 +2490,7 @@ async fn run_bot(
 
     // Son blok
     let block = provider.get_block_number().await?;
-    println!(
+    println_high!(
         "  {} Current block: #{} | Transport: {} | Connection: {}ms",
         "🧱".blue(),
         format!("{}", block).white().bold(),
@@ -939,14 +2505,16 @@ async fn run_bot(
         .collect();
 
     // ══════════════ İLK SENKRONİZASYON ══════════════
-    println!("\n  {} Performing initial state sync...", "🔄".yellow());
+    println_high!("\n  {} Performing initial state sync...", "🔄".yellow());
 
     // Bytecode önbelleğe al (bir kez — REVM için)
-    let bytecode_results = cache_all_bytecodes(&provider, pools, &states).await;
+    // Bytecode PoolState'ten ayrı tutulur: per-block state klonları küçük kalır
+    let static_store: StaticPoolStore = Arc::new(RwLock::new(HashMap::new()));
+    let bytecode_results = cache_all_bytecodes(&provider, pools, &static_store).await;
     for (i, result) in bytecode_results.iter().enumerate() {
         match result {
-            Ok(_) => println!("  {}   {} bytecode cached", "✅".green(), pools[i].name),
-            Err(e) => println!(
+            Ok(_) => println_high!("  {}   {} bytecode cached", "✅".green(), pools[i].name),
+            Err(e) => println_high!(
                 "  {}   {} bytecode error: {}",
                 "⚠️".yellow(),
                 pools[i].name,
@@ -959,14 +2527,14 @@ async fn run_bot(
     // v10.0: Başlangıçta tüm havuzları on-chain doğrula. Geçersiz havuzlar
     // (execution reverted, slot0/liquidity okunamayan) listeden çıkarılır.
     // Bu, runtime'da "error code 3: execution reverted" hatalarını önler.
-    println!(
+    println_high!(
         "\n  {} Performing pool health check ({} pools)...",
         "🔍".yellow(),
         pools.len()
     );
     let invalid_pool_indices = validate_pools(&provider, pools).await;
     if !invalid_pool_indices.is_empty() {
-        println!(
+        println_high!(
             "  {} {} invalid pools detected — removing from list",
             "⚠️".yellow(),
             invalid_pool_indices.len(),
@@ -975,7 +2543,7 @@ async fn run_bot(
         let mut sorted_invalid = invalid_pool_indices;
         sorted_invalid.sort_unstable_by(|a, b| b.cmp(a));
         for &idx in &sorted_invalid {
-            println!(
+            println_high!(
                 "  {}   Removed: {} ({})",
                 "🗑️".red(),
                 pools[idx].name,
@@ -997,52 +2565,84 @@ async fn run_bot(
                 ));
             }
         }
-        println!(
+        println_high!(
             "  {} Pool list updated: {} valid pools remaining",
             "✅".green(),
             pools.len(),
         );
     } else {
-        println!(
+        println_high!(
             "  {} All pools validated — {} pools valid",
             "✅".green(),
             pools.len(),
         );
     }
 
+    // ══════════════ HAVUZ BAŞINA OKUMA ENDPOINT'LERİ ══════════════
+    // POOL_A/B_RPC_URL tanımlıysa o havuzun state / bitmap / iyimser okumaları
+    // kendi endpoint'inden yapılır (hata → primary). Abonelik + yürütme primary'de.
+    let providers = transport::ProviderRegistry::connect(
+        provider.clone(),
+        pools,
+        config.pool_a_rpc_url.as_deref(),
+        config.pool_b_rpc_url.as_deref(),
+    )
+    .await;
+    if config.pool_a_rpc_url.is_some() || config.pool_b_rpc_url.is_some() {
+        println_high!("\n  {} Pool read endpoints:", "🔀".cyan());
+        for pool in pools.iter() {
+            let endpoint = providers.endpoint_for(pool.address);
+            println_high!(
+                "  {}   {} → {} ({})",
+                "•".cyan(),
+                pool.name,
+                endpoint.label.cyan(),
+                endpoint.url,
+            );
+        }
+    }
+
     // İlk state sync
-    let sync_results = sync_all_pools(&provider, pools, &states, block).await;
+    let sync_results = sync_all_pools(&providers, pools, &states, block, BlockId::number(block)).await;
     for (i, result) in sync_results.iter().enumerate() {
         match result {
             Ok(_) => {
                 let state = states[i].load();
                 let fee_info = match state.live_fee_bps {
                     Some(bps) => format!("Fee: {}bps ({:.2}%)", bps, bps as f64 / 100.0),
-                    None => format!("Fee: N/A (config: {}bps)", pools[i].fee_bps),
+                    None => format!("Fee: N/A (config: {}bps)", pools[i].fee_bps()),
                 };
-                println!(
+                println_high!(
                     "  {}   {} → {:.6} Q | Tick: {} | Liquidity: {:.2e} | {}",
                     "✅".green(),
                     pools[i].name,
                     state.eth_price_usd,
                     state.tick,
-                    state.liquidity_f64,
+                    state.liquidity_approx(),
                     fee_info,
                 );
             }
-            Err(e) => println!("  {}   {} state error: {}", "❌".red(), pools[i].name, e),
+            Err(e) => println_high!("  {}   {} state error: {}", "❌".red(), pools[i].name, e),
+        }
+    }
+
+    // Kontrat pause durumu — ilk sync batch'i view'ı taşımadıysa tek başına oku
+    if contract_state::needs_read() {
+        if let Err(e) = state_sync::read_pause_view(providers.primary(), BlockId::number(block)).await {
+            eprintln_high!("  {} [Contract] pause view read failed: {} — retried every block", "⚠️".yellow(), e);
         }
     }
+    contract_state::report(block, telegram_sender.as_ref());
 
     // ══════════════ İLK TİCKBİTMAP SENKRONİZASYONU ══════════════
-    println!(
+    println_high!(
         "\n  {} Fetching TickBitmap depth map (±{} tick)...",
         "🗺️".yellow(),
         config.tick_bitmap_range
     );
     let bitmap_start = Instant::now();
     let bitmap_results =
-        sync_all_tick_bitmaps(&provider, pools, &states, block, config.tick_bitmap_range).await;
+        sync_all_tick_bitmaps(&providers, pools, &states, block, config.tick_bitmap_range).await;
     let bitmap_ms = bitmap_start.elapsed().as_millis();
 
     for (i, result) in bitmap_results.iter().enumerate() {
@@ -1050,7 +2650,7 @@ async fn run_bot(
             Ok(_) => {
                 let state = states[i].load();
                 if let Some(ref bm) = state.tick_bitmap {
-                    println!(
+                    println_high!(
                         "  {}   {} → {} inicialize tick, {} word | {}ms",
                         "✅".green(),
                         pools[i].name,
@@ -1058,9 +2658,20 @@ async fn run_bot(
                         bm.words.len(),
                         bm.sync_duration_us / 1000,
                     );
+                    if let Some(staked) = bm.staked {
+                        println_high!(
+                            "  {}     staked share {:.1}% | unstakedFee {} | nets {}",
+                            "🔒".cyan(),
+                            staked.staked_share() * 100.0,
+                            staked
+                                .unstaked_fee_pips
+                                .map_or("n/a".to_string(), |pips| format!("{:.2}%", pips as f64 / 10_000.0)),
+                            if staked.verified { "verified" } else { "UNVERIFIED" },
+                        );
+                    }
                 }
             }
-            Err(e) => println!(
+            Err(e) => println_high!(
                 "  {}   {} bitmap error: {}",
                 "⚠️".yellow(),
                 pools[i].name,
@@ -1068,35 +2679,39 @@ async fn run_bot(
             ),
         }
     }
-    println!("  {} TickBitmap total time: {}ms", "🗺️".cyan(), bitmap_ms);
+    println_high!("  {} TickBitmap total time: {}ms", "🗺️".cyan(), bitmap_ms);
 
     // State sync tamamlandı — havuz başlığını canlı fee'lerle göster
     print_pool_header(pools, &states);
+    // systemd (Type=notify): ilk başarılı tam senkronizasyon → READY=1
+    let active_pools = states.iter().filter(|s| s.load().is_active()).count();
+    if active_pools > 0 {
+        systemd::ready(active_pools, block);
+    }
 
     // ══════════════ REVM SİMÜLASYON MOTORU ══════════════
-    let mut sim_engine = SimulationEngine::new();
-    sim_engine.set_chain_id(config.chain_id);
-    sim_engine.cache_bytecodes(pools, &states);
+    let mut sim_engine = SimulationEngine::new(config.chain_id);
+    sim_engine.cache_bytecodes(pools, &static_store);
+    attach_revisit_engine(config, pools, &static_store);
 
     // v22.1: Kontrat bytecode'unu zincirden al — simülasyonda gerçek kontrat çalışsın
     // v24.0: Zincirden alınamazsa Foundry artifact'ten yükle (local fallback)
     if let Some(contract_addr) = config.contract_address {
         let mut bytecode_loaded = false;
-        match provider.get_code_at(contract_addr).await {
-            Ok(code) if !code.is_empty() => {
-                println!(
+        match state_sync::cache_contract_bytecode(&provider, contract_addr, &mut sim_engine).await {
+            Ok(len) if len > 0 => {
+                println_high!(
                     "  {} Contract bytecode loaded ({} bytes — from chain)",
                     "✅".green(),
-                    code.len()
+                    len
                 );
-                sim_engine.set_contract_bytecode(code.to_vec());
                 bytecode_loaded = true;
             }
             Ok(_) => {
-                eprintln!("  {} Contract bytecode empty — may not be deployed, searching local artifact...", "⚠️".yellow());
+                eprintln_high!("  {} Contract bytecode empty — may not be deployed, searching local artifact...", "⚠️".yellow());
             }
             Err(e) => {
-                eprintln!(
+                eprintln_high!(
                     "  {} Contract bytecode fetch failed: {} — searching local artifact...",
                     "⚠️".yellow(),
                     e
@@ -1120,7 +2735,7 @@ async fn run_bot(
                             let hex_str = deployed.strip_prefix("0x").unwrap_or(deployed);
                             if let Ok(bytes) = alloy::primitives::hex::decode(hex_str) {
                                 if !bytes.is_empty() {
-                                    println!("  {} Contract bytecode loaded ({} bytes — local artifact: {})", "✅".green(), bytes.len(), path);
+                                    println_high!("  {} Contract bytecode loaded ({} bytes — local artifact: {})", "✅".green(), bytes.len(), path);
                                     sim_engine.set_contract_bytecode(bytes);
                                     bytecode_loaded = true;
                                     break;
@@ -1131,43 +2746,218 @@ async fn run_bot(
                 }
             }
             if !bytecode_loaded {
-                eprintln!("  {} Contract bytecode not found — REVM simulation will use mathematical fallback", "⚠️".yellow());
+                eprintln_high!(
+                    "  {} Contract bytecode not found — REVM simulations fail with \"{}\" (results would be meaningless)",
+                    "⚠️".yellow(),
+                    simulator::CONTRACT_CODE_MISSING,
+                );
+            }
+        }
+    }
+
+    // ══════════════ TOKEN BYTECODE + DAVRANIŞ PROBE'U ══════════════
+    // Bytecode'lar REVM state diff'inden owedToken bakiye değişimini okumak için
+    // yüklenir; aynı bytecode ile her token'ın transfer davranışı bir kez test
+    // edilir (tokens.json önbelleği). Fee-on-transfer / rebasing token içeren
+    // çiftler ALLOW_NONSTANDARD_TOKENS=false iken yalnızca gölge loglanır.
+    let mut tokens: Vec<Address> = Vec::new();
+    for pool in pools.iter() {
+        for token in [pool.base_token_address, pool.quote_token_address] {
+            if !tokens.contains(&token) {
+                tokens.push(token);
             }
         }
     }
+    println_high!(
+        "\n  {} Probing token transfer behavior ({} tokens)...",
+        "🧪".yellow(),
+        tokens.len()
+    );
+    let token_cache_path = secure_fs::data_path(token_probe::TOKENS_CACHE_PATH);
+    let mut token_cache =
+        token_probe::load_cache(&secure_fs::read_path(token_probe::TOKENS_CACHE_PATH));
+    let mut nonstandard_tokens: HashSet<Address> = HashSet::new();
+    // token → balanceOf mapping slot'u: base_db her kurulduğunda havuz bakiyeleri tazelenir
+    let mut balance_slots: HashMap<Address, u64> = HashMap::new();
+    for token in tokens {
+        let token_code = match token_probe::fetch_token_code(&provider, token).await {
+            Ok(code) => code,
+            Err(e) => {
+                eprintln_high!("  {} Token bytecode fetch failed: {} — {}", "⚠️".yellow(), token, e);
+                continue;
+            }
+        };
+        if token_code.code.is_empty() {
+            eprintln_high!("  {} Token bytecode empty: {} — SimMath balance diff disabled for it", "⚠️".yellow(), token);
+        } else if config.contract_address.is_some() {
+            println_high!("  {} Token bytecode loaded: {} ({} bytes)", "✅".green(), token, token_code.code.len());
+            sim_engine.set_token_bytecode(token, token_code.code.clone());
+            if let Some(ref proxy) = token_code.proxy {
+                sim_engine.set_token_proxy(token, proxy);
+            }
+        }
+
+        let (probe, cached) =
+            token_probe::cached_or_probe(&mut token_cache, token, &token_code, config.chain_id);
+        // Havuz bakiyeleri okunabilir token: flash-swap callback'indeki transfer'ler
+        // REVM'de sıfır bakiyeden revert etmesin
+        if let (Some(slot), false, Some(_)) =
+            (probe.balance_slot, token_code.code.is_empty(), config.contract_address)
+        {
+            balance_slots.insert(token, slot);
+        }
+        let source = if cached { "cached" } else { "probed" };
+        let detail = probe.detail.as_deref().unwrap_or("-");
+        match probe.behavior {
+            token_probe::TokenBehavior::Standard => println_high!(
+                "  {}   {} → {} ({}, balance slot {:?})",
+                "✅".green(),
+                token,
+                probe.behavior.label(),
+                source,
+                probe.balance_slot,
+            ),
+            token_probe::TokenBehavior::Inconclusive => eprintln_high!(
+                "  {}   {} → {} ({}) — {}",
+                "⚠️".yellow(),
+                token,
+                probe.behavior.label().yellow(),
+                source,
+                detail,
+            ),
+            _ => {
+                eprintln_high!(
+                    "  {}   {} → {} ({}) — {}",
+                    "🚫".red(),
+                    token,
+                    probe.behavior.label().red().bold(),
+                    source,
+                    detail,
+                );
+            }
+        }
+        if probe.behavior.is_nonstandard() {
+            nonstandard_tokens.insert(token);
+        }
+    }
+    if let Err(e) = token_probe::save_cache(&token_cache_path, &token_cache) {
+        eprintln_high!(
+            "  {} {} write failed: {}",
+            "⚠️".yellow(),
+            token_cache_path.display(),
+            e
+        );
+    }
+    if !nonstandard_tokens.is_empty() {
+        let blocked_pools = pools
+            .iter()
+            .filter(|p| token_probe::flagged_token(p, &nonstandard_tokens).is_some())
+            .count();
+        if config.allow_nonstandard_tokens {
+            eprintln_high!(
+                "  {} {} non-standard token(s) in {} pool(s) — ALLOW_NONSTANDARD_TOKENS=true, executing anyway",
+                "⚠️".yellow(),
+                nonstandard_tokens.len(),
+                blocked_pools,
+            );
+        } else {
+            eprintln_high!(
+                "  {} {} non-standard token(s) in {} pool(s) — those pairs are shadow-only",
+                "🚫".red(),
+                nonstandard_tokens.len(),
+                blocked_pools,
+            );
+        }
+    }
+    // Bayraklı token'lar yalnızca izin yokken yürütmeyi engeller
+    let blocked_tokens: HashSet<Address> = if config.allow_nonstandard_tokens {
+        HashSet::new()
+    } else {
+        nonstandard_tokens
+    };
+
+    refresh_pool_token_balances(&provider, &mut sim_engine, pools, &balance_slots).await;
 
     // v10.0: Singleton base_db — bytecode bir kez yüklenir, sonra her blokta klonlanır
     {
-        let caller_addr = config
-            .private_key
-            .as_ref()
-            .and_then(|pk| pk.parse::<alloy::signers::local::PrivateKeySigner>().ok())
-            .map(|signer| signer.address())
-            .unwrap_or_default();
+        let caller_addr = mev_executor.signer_address();
         let contract_addr = config.contract_address.unwrap_or_default();
         sim_engine.initialize_base_db(pools, &states, caller_addr, contract_addr);
-        println!(
+        println_high!(
             "\n  {} REVM simulation engine ready (Singleton base_db)",
             "✅".green()
         );
+
+        // Deadline sınırı: deadline = current calldata'sı current ve current + 1
+        // bloklarında çalıştırılır — kontratın gerçek semantiği kaydedilir
+        let current_block = states[0].load().last_block;
+        match sim_engine.verify_deadline_semantics(
+            pools,
+            &states,
+            caller_addr,
+            contract_addr,
+            current_block,
+            clock::now().timestamp().max(0) as u64,
+            0,
+        ) {
+            Ok(semantics) => {
+                set_deadline_semantics(semantics);
+                println_high!(
+                    "  {} Deadline semantics verified in REVM: {}",
+                    "✅".green(),
+                    semantics.label()
+                );
+            }
+            Err(e) => {
+                eprintln_high!(
+                    "  {} Deadline semantics not verified ({}) — assuming {}",
+                    "⚠️".yellow(),
+                    e,
+                    deadline_semantics().label()
+                );
+            }
+        }
     }
 
     // ══════════════ ATOMİK NONCE YÖNETİCİSİ ══════════════
-    let executor_address: Option<Address> = config
-        .private_key
-        .as_ref()
-        .and_then(|pk| pk.parse::<alloy::signers::local::PrivateKeySigner>().ok())
-        .map(|signer| signer.address());
+    let executor_address: Option<Address> = signing_ctx.as_ref().map(|s| s.address());
 
+    // Checkpoint (nonce_state.json) zincirin latest + pending sayımlarıyla
+    // uzlaştırılır — kapanışta uçuşta olan TX'lerin nonce'u yeniden verilmez
     let nonce_manager = if let Some(address) = executor_address {
-        println!("  {} Reading nonce ({})...", "🔢".yellow(), address);
-        match provider.get_transaction_count(address).await {
-            Ok(nonce) => {
-                println!("  {} Initial nonce: {} (from RPC)", "✅".green(), nonce);
-                Arc::new(NonceManager::new(nonce))
+        println_high!("  {} Reading nonce ({})...", "🔢".yellow(), address);
+        let latest = provider.get_transaction_count(address).latest().await;
+        let pending = provider.get_transaction_count(address).pending().await;
+        match (latest, pending) {
+            (Ok(latest), Ok(pending)) => {
+                let block = states.iter().map(|s| s.load().last_block).max().unwrap_or(0);
+                let checkpoint = nonce_checkpoint::load();
+                let reconciled =
+                    nonce_checkpoint::reconcile(checkpoint.as_ref(), latest, pending, block);
+                match &checkpoint {
+                    Some(cp) => println_high!(
+                        "  {} Initial nonce: {} (chain latest {} / pending {}, checkpoint {}) — in-flight: {} live, {} confirmed, {} expired",
+                        "✅".green(),
+                        reconciled.start_nonce,
+                        latest,
+                        pending,
+                        cp.next_nonce,
+                        reconciled.live.len(),
+                        reconciled.confirmed,
+                        reconciled.expired,
+                    ),
+                    None => println_high!(
+                        "  {} Initial nonce: {} (from RPC, no checkpoint)",
+                        "✅".green(),
+                        reconciled.start_nonce
+                    ),
+                }
+                let nonce_manager = Arc::new(NonceManager::new(reconciled.start_nonce));
+                nonce_checkpoint::attach(&nonce_manager, reconciled.live);
+                nonce_manager
             }
-            Err(e) => {
-                println!(
+            (Err(e), _) | (_, Err(e)) => {
+                println_high!(
                     "  {} Nonce read failed, starting from 0: {}",
                     "⚠️".yellow(),
                     e
@@ -1178,10 +2968,12 @@ async fn run_bot(
     } else {
         Arc::new(NonceManager::new(0))
     };
+    // Uçuştaki yürütmeler — her blok sync'inden sonra yeniden doğrulanır
+    let mut live_opportunities = strategy::LiveOpportunities::default();
 
     // Execution modu
     if config.execution_enabled() {
-        println!(
+        println_high!(
             "  {} Contract execution: {} (Address: {})",
             "🚀".green(),
             "ACTIVE".green().bold(),
@@ -1190,7 +2982,7 @@ async fn run_bot(
                 .expect("BUG: execution_enabled() true but contract_address None")
         );
     } else {
-        println!(
+        println_high!(
             "  {} Contract execution: {} (Observe only)",
             "ℹ️".blue(),
             "DISABLED".yellow().bold()
@@ -1204,9 +2996,7 @@ async fn run_bot(
         let all_pool_addrs: Vec<Address> = pools.iter().map(|p| p.address).collect();
         if !all_pool_addrs.is_empty() {
             let calldata = crate::executor::encode_whitelist_calldata(&all_pool_addrs);
-            if let (Some(ref pk), Some(contract_addr)) =
-                (&config.private_key, config.contract_address)
-            {
+            if let Some(contract_addr) = config.contract_address {
                 let startup_base_fee = provider
                     .get_block_by_number(alloy::eips::BlockNumberOrTag::Latest)
                     .await
@@ -1218,7 +3008,7 @@ async fn run_bot(
                 let nonce = nonce_manager.get_and_increment();
                 match whitelist_pools_on_chain(
                     Arc::clone(&mev_executor),
-                    pk.clone(),
+                    Arc::clone(&connections),
                     contract_addr,
                     calldata,
                     nonce,
@@ -1227,12 +3017,12 @@ async fn run_bot(
                 )
                 .await
                 {
-                    Ok(_) => println!(
+                    Ok(_) => println_high!(
                         "  {} [Whitelist] {} pools added to on-chain whitelist (startup sync)",
                         "✅".green(),
                         all_pool_addrs.len(),
                     ),
-                    Err(e) => eprintln!(
+                    Err(e) => eprintln_high!(
                         "  {} [Whitelist] Startup whitelist error: {} — admin must add manually",
                         "⚠️".yellow(),
                         e,
@@ -1242,25 +3032,82 @@ async fn run_bot(
         }
     }
 
+    // ══════════════ TOKEN ONAYI DENETİMİ (approval_audit) ══════════════
+    // Başlangıçta bir kez (sonuç rapora yazılır), sonra günde bir arka planda.
+    // hard modda zorunlu onay sıfırsa fırsatlar gölge loglanır, yürütülmez.
+    let approvals: approval_audit::SharedApprovalReport = Arc::new(RwLock::new(None));
+    if config.execution_enabled() && config.approval_audit_mode != approval_audit::ApprovalAuditMode::Off {
+        if let Some(contract_addr) = config.contract_address {
+            approval_audit::run_audit(
+                &provider,
+                contract_addr,
+                config.aave_pool_address,
+                pools,
+                &states,
+                config.max_trade_size_weth,
+                config.approval_audit_mode,
+                config.approval_required_spenders,
+                &approvals,
+                telegram_sender,
+            )
+            .await;
+
+            let provider_audit = provider.clone();
+            let token_audit = cancel_token.clone();
+            let audit_pools = pools.clone();
+            let audit_states: Vec<SharedPoolState> = states.iter().map(Arc::clone).collect();
+            let audit_shared = Arc::clone(&approvals);
+            let tg_audit = telegram_sender.clone();
+            let lender = config.aave_pool_address;
+            let max_trade = config.max_trade_size_weth;
+            let mode = config.approval_audit_mode;
+            let required = config.approval_required_spenders;
+            tokio::spawn(async move {
+                let period = Duration::from_secs(approval_audit::APPROVAL_AUDIT_INTERVAL_SECS);
+                let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                loop {
+                    tokio::select! {
+                        _ = token_audit.cancelled() => break,
+                        _ = interval.tick() => {
+                            approval_audit::run_audit(
+                                &provider_audit,
+                                contract_addr,
+                                lender,
+                                &audit_pools,
+                                &audit_states,
+                                max_trade,
+                                mode,
+                                required,
+                                &audit_shared,
+                                &tg_audit,
+                            )
+                            .await;
+                        }
+                    }
+                }
+            });
+        }
+    }
+
     // ══════════════ BLOK BAŞLIĞI ABONELİĞİ ══════════════
-    println!();
-    println!(
+    println_high!();
+    println_high!(
         "{}",
         "  ════════════════════════════════════════════════════════════════".green()
     );
-    println!(
+    println_high!(
         "  {}  LIVE FEED v9.0 — Listening for new blocks + Pending TX...",
         "📡".green()
     );
-    println!(
+    println_high!(
         "  {}  Loop: Pending TX → State Sync → TickBitmap → NR → REVM → Execute",
         "📡".green()
     );
-    println!(
+    println_high!(
         "{}",
         "  ════════════════════════════════════════════════════════════════".green()
     );
-    println!();
+    println_high!();
 
     // v32.0: Telegram — sistem başlatıldı bildirimi
     if let Some(ref tg) = telegram_sender {
@@ -1273,7 +3120,7 @@ async fn run_bot(
         };
         tg.send(telegram::TelegramMessage::SystemStartup {
             pool_count: pools.len(),
-            transport: active_transport.to_string(),
+            transport: format!("{} ({})", active_transport, connections.transport_info()),
             mode: mode_str.to_string(),
         });
     }
@@ -1292,24 +3139,29 @@ async fn run_bot(
         let pools_bg = pools.to_vec();
         let states_bg: Vec<SharedPoolState> = states.iter().map(Arc::clone).collect();
         let pool_addrs_bg = pool_addresses.clone();
-        let rpc_url_bg = config.rpc_wss_url.clone();
+        // Abonelik primary soketi paylaşır — kiralama task bitene kadar sayılır
+        let lease_bg = connections.subscribe(transport::ConnectionRole::Primary)?;
+        let log_updates_bg = config.optimistic_update_log;
         let token_bg = cancel_token.clone();
+        let providers_bg = providers.clone();
 
         tokio::spawn(async move {
             tokio::select! {
                 _ = token_bg.cancelled() => {
-                    eprintln!("  🔌 Pending TX listener graceful shutdown (CancellationToken)");
+                    eprintln_high!("  🔌 Pending TX listener graceful shutdown (CancellationToken)");
                 }
                 result = pending_tx_listener(
-                    &rpc_url_bg,
+                    &lease_bg,
+                    &providers_bg,
                     &pools_bg,
                     &states_bg,
                     &pool_addrs_bg,
+                    log_updates_bg,
                 ) => {
                     match result {
                         Ok(_) => {}
                         Err(e) => {
-                            eprintln!(
+                            eprintln_high!(
                                 "  ⚠️ Pending TX listener error (block-based flow continues): {}", e
                             );
                         }
@@ -1327,49 +3179,33 @@ async fn run_bot(
     // v30.0: Event listener CancellationToken -- hot-reload'da yeniden baslatilabilir.
     // Child token kullanilir: parent cancel olunca child da cancel olur,
     // ama child tek basina da cancel edilebilir (hot-reload icin).
+    //
+    // Swap eventleri ayrıca tazelik kahinine yazılır: sabitli okuma eventle
+    // çelişirse ayrışma alarmı + yedek HTTP provider'dan yeniden sync.
+    // Dinleyici denetimli çalışır — akış koparsa backoff ile yeniden bağlanır.
+    let event_oracle = Arc::new(state_sync::EventFreshnessOracle::default());
+    let fallback_provider = match config.rpc_http_url.parse::<reqwest::Url>() {
+        Ok(url) => ProviderBuilder::default().connect_http(url),
+        Err(_) => provider.clone(),
+    };
     let mut event_listener_cancel = cancel_token.child_token();
-    {
-        let pools_ev = pools.to_vec();
-        let states_ev: Vec<SharedPoolState> = states.iter().map(Arc::clone).collect();
-        let rpc_url_ev = config.rpc_wss_url.clone();
-        let token_ev = event_listener_cancel.clone();
-
-        tokio::spawn(async move {
-            let ws = WsConnect::new(&rpc_url_ev);
-            match ProviderBuilder::default().connect_ws(ws).await {
-                Ok(ws_provider) => {
-                    match state_sync::start_pool_event_listener(
-                        &ws_provider,
-                        &pools_ev,
-                        &states_ev,
-                        token_ev,
-                    )
-                    .await
-                    {
-                        Ok(_) => {}
-                        Err(e) => {
-                            eprintln!(
-                                "  [EventListener] error (block-based flow continues): {}",
-                                e
-                            );
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("  [EventListener] WS connection error: {}", e);
-                }
-            }
-        });
-    }
+    spawn_pool_event_listener(
+        Arc::clone(&connections),
+        pools.to_vec(),
+        states.iter().map(Arc::clone).collect(),
+        Arc::clone(&event_oracle),
+        event_listener_cancel.clone(),
+    );
 
     // ══════════════ L1 FEE ARKA PLAN GÖREVİ (OPT-4) ══════════════
     // OP Stack L1 data fee ~12 saniyede bir değişir (L1 blok süresi).
-    // Her Base bloğunda RPC sorgulamak yerine, 12s'de bir arka planda güncelle.
+    // Her blokta RPC sorgulamak yerine, 12s'de bir arka planda güncelle.
     // Ana döngü AtomicU64'ten lock-free okur → sıfır RPC latency hot path'te.
-    {
-        let initial_l1_fee = estimate_l1_data_fee(&provider).await;
+    // Oracle'ı olmayan profillerde (Arbitrum) L1 maliyeti gas'ın içindedir.
+    if let Some(oracle) = config.l1_fee_oracle {
+        let initial_l1_fee = estimate_l1_data_fee(&provider, oracle).await;
         GLOBAL_L1_FEE.store(initial_l1_fee as u64, Ordering::Relaxed);
-        eprintln!(
+        eprintln_high!(
             "  {} L1 Data Fee cached: {} wei ({:.8} ETH) — background refresh every 12s",
             "⛽".cyan(),
             initial_l1_fee,
@@ -1384,135 +3220,590 @@ async fn run_bot(
             loop {
                 tokio::select! {
                     _ = token_l1.cancelled() => {
-                        eprintln!("  🔌 L1 Fee background task graceful shutdown");
+                        eprintln_high!("  🔌 L1 Fee background task graceful shutdown");
                         break;
                     }
                     _ = interval.tick() => {
-                        let fee = estimate_l1_data_fee(&provider_l1).await;
+                        let fee = estimate_l1_data_fee(&provider_l1, oracle).await;
                         GLOBAL_L1_FEE.store(fee as u64, Ordering::Relaxed);
                     }
                 }
             }
         });
+    } else {
+        GLOBAL_L1_FEE.store(0, Ordering::Relaxed);
+        eprintln_high!(
+            "  {} L1 Data Fee: no oracle for {} — L1 fee component disabled",
+            "ℹ️".blue(),
+            config.chain_profile.display_name()
+        );
+    }
+
+    // ══════════════ ÜCRET REJİMİ DOĞRULAMASI (fee_monitor) ══════════════
+    // Aave FLASHLOAN_PREMIUM_TOTAL + havuz fee() saatte bir zincirden okunur.
+    // İlk tick anında çalışır — başlangıçtaki uyuşmazlık hemen raporlanır.
+    {
+        let provider_fee = provider.clone();
+        let token_fee = cancel_token.clone();
+        let fee_pools = pools.clone();
+        let aave_pool = config.aave_pool_address;
+        let tg_fee = telegram_sender.clone();
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(fee_monitor::FEE_CHECK_INTERVAL_SECS));
+            loop {
+                tokio::select! {
+                    _ = token_fee.cancelled() => {
+                        eprintln_high!("  🔌 Fee Monitor background task graceful shutdown");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        fee_monitor::run_fee_check(&provider_fee, aave_pool, &fee_pools, &tg_fee).await;
+                    }
+                }
+            }
+        });
+    }
+
+    // ══════════════ HAVUZ ETKİSİ DOĞRULAMASI (impact_verification) ══════════════
+    // status=1 receipt → iki havuzun slot0'ı dahil edilme bloğunda okunur,
+    // simülasyonun öngördüğü son fiyatla karşılaştırılır.
+    impact_verification::spawn_verifier(provider.clone(), telegram_sender.clone(), cancel_token.clone());
+
+    // ══════════════ GÖLGE FIRSAT DOĞRULAMASI (shadow_verify) ══════════════
+    // Gölge kaydından SHADOW_VERIFY_BLOCKS blok sonra iki havuz o bloğa
+    // sabitli okunur, aynı miktar yeniden fiyatlanır — ana döngü beklemez.
+    shadow_verify::spawn_verifier(provider.clone(), config, cancel_token.clone());
+
+    // ══════════════ FIRSAT FİLTRE ZİNCİRİ ══════════════
+    // staleness → pool_health → prefilter → [NR] → profit_floor → competition
+    // → direction_gate → [evaluate] execution_sanity → freshness_gate (OPPORTUNITY_FILTERS ile sıralanır)
+    let filter_chain = opportunity_filter::FilterChain::from_config(config);
+    // İki adımlı plan kontrolü ayrı zincirle: hipotetik işlem sonrası state'in
+    // geçiş/ret sayaçları canlı filtre istatistiklerine karışmaz
+    let two_step_filters = opportunity_filter::FilterChain::from_config(config);
+
+    // ══════════════ A/B GÖLGE DEĞERLENDİRMESİ (opsiyonel) ══════════════
+    // SECONDARY_CONFIG_PATH: aynı state'ler üzerinde ikinci parametre seti,
+    // ana pipeline'dan sonra zaman kutusu içinde — yürütme yok.
+    let ab_shadow = ab_shadow::AbShadow::from_config(config)?;
+    if let Some(ref path) = config.secondary_config_path {
+        println_high!(
+            "  {} A/B shadow: variant B = {} (time box {}ms) → {}",
+            "🆎".cyan(),
+            path,
+            ab_shadow::AB_TIME_BOX.as_millis(),
+            secure_fs::data_path(ab_shadow::AB_SHADOW_LOG_PATH).display(),
+        );
+    }
+
+    // ══════════════ LİKİDİTE ANALİTİĞİ (Boşta Zaman, Arka Plan) ══════════════
+    // Ana döngü bitmap değiştiğinde watch kanalına yazar (beklemesiz);
+    // analitik görevi bloklar arası boşlukta zaman bütçesiyle hesaplar.
+    let (analytics_tx, analytics_rx) = liquidity_analytics::channel();
+    let pool_analytics: liquidity_analytics::SharedPoolAnalytics =
+        Arc::new(RwLock::new(liquidity_analytics::AnalyticsStore::default()));
+    let mut analytics_signature: Vec<(Address, u64, usize)> = Vec::new();
+    if config.liquidity_analytics_budget_ms > 0 {
+        tokio::spawn(liquidity_analytics::run_analytics_loop(
+            analytics_rx,
+            Arc::clone(&pool_analytics),
+            Duration::from_millis(config.liquidity_analytics_budget_ms),
+            cancel_token.clone(),
+            liquidity_analytics::compute_pool_analytics,
+        ));
     }
 
     // ══════════════ KEŞİF MOTORU v25.0 (Otonom Keşif) ══════════════
     // On-Chain Factory Listener + Multi-API Aggregator + Skorlama + GC
     let discovery_config = DiscoveryConfig::from_bot_config(config);
     let discovery_registry = Arc::new(RwLock::new(LivePoolRegistry::new(pools)));
-    {
+    // Factory listener ve API aggregator Base'e özgü — diğer profillerde kapalı
+    if config.chain_profile == chain_profile::ChainProfile::Base {
         let engine = DiscoveryEngine::new(discovery_registry.clone(), discovery_config.clone());
         engine.start(cancel_token.clone());
+    } else {
+        eprintln_high!(
+            "  {} Discovery engine disabled on {} (Base-only factories)",
+            "ℹ️".blue(),
+            config.chain_profile.display_name()
+        );
     }
 
-    let sub = provider.subscribe_blocks().await?;
+    // ══════════════ SEQUENCER HEAD FEED (opsiyonel 2. blok sinyali) ══════════════
+    // SEQUENCER_WS_URL tanımlıysa newHeads ikinci bir kaynaktan da dinlenir.
+    // Ana döngü iki kaynağı (numara, hash) ile tekilleştirir — önce gelen işlenir.
+    let mut sequencer_rx = config.sequencer_ws_url.as_ref().map(|url| {
+        let (tx, rx) = tokio::sync::mpsc::channel(block_feed::SEQUENCER_CHANNEL_CAPACITY);
+        tokio::spawn(block_feed::run_sequencer_feed(
+            url.clone(),
+            tx,
+            cancel_token.clone(),
+        ));
+        println_high!(
+            "  {} Sequencer head feed: {}",
+            "🛰️".cyan(),
+            env_loader::redact_url(url)
+        );
+        rx
+    });
+    let mut head_dedup = block_feed::HeadDeduplicator::new();
+    // Geri basınç: işleme geride kalınca biriken başlıklardan en yenisi seçilir
+    let mut backlog = block_feed::BacklogSkipper::new();
+    // Birincil akışın sağlığı ayrıca izlenir: sequencer blok getirse bile
+    // birincil RPC susarsa reconnect gerekir (sync çağrıları ona bağlı).
+    let mut last_primary_head = Instant::now();
+
+    // Blok aboneliği primary soket üzerinde çoğullanır; kiralama run_bot boyunca yaşar
+    let block_lease = connections.subscribe(transport::ConnectionRole::Primary)?;
+    let sub = block_lease.subscribe_blocks().await?;
     let mut stream = sub.into_stream();
-    let mut stats = ArbitrageStats::new();
+
+    // Bağlantı kuruldu — state oturana kadar yürütme kapalı
+    warmup_gate.on_connect();
+    if !warmup_gate.is_armed() {
+        println_high!(
+            "  {} Warmup: execution shadow-only until {} consecutive clean blocks",
+            "⏳".yellow(),
+            config.warmup_clean_blocks,
+        );
+    }
     stats.active_transport = active_transport.to_string();
-    let mut last_bitmap_block: u64 = block;
+    // Periyodik tam sync zamanlaması + FAST_PATH_SYNC sessiz blok tespiti
+    let mut fast_path = state_sync::FastPathSync::new(config, block);
     // v14.0: Son REVM simülasyonundan gelen gerçek gas değeri
     // İlk blokta None → check_arbitrage_opportunity 150K fallback kullanır
     // Sonraki bloklarda REVM'den dönen kesin gas ile dinamik maliyet hesaplanır
     let mut last_simulated_gas: Option<u64> = None;
+    // Kurma dosyası: her yürütme denemesinden önce yeniden okunur (yalnızca canlı mod)
+    let mut arming =
+        arming::ArmingGuard::for_session(config, signing_ctx.as_ref().map(|c| c.address()));
+    // STATE_DIFF_LOG: blok başına karar girdileri → state_diff.jsonl (--replay-diff)
+    // LOG_FORMAT_BINARY → state_diff.bin (bekleyen çerçeve run_bot dönüşünde yazılır)
+    let mut state_diff_recorder = config.state_diff_log.then(|| {
+        if config.log_format_binary {
+            replay::StateDiffRecorder::binary(secure_fs::data_path(replay::STATE_DIFF_BIN_PATH))
+        } else {
+            replay::StateDiffRecorder::new(secure_fs::data_path(replay::STATE_DIFF_LOG_PATH))
+        }
+    });
 
     // v29.0: Hot-Reload arka plan görevi handle'ı
     // Yeni havuz keşfi → bytecode + state sync işlemleri arka planda çalışır,
     // ana ticaret döngüsünü BLOKLAMAZ. Tamamlandığında REVM base_db rebuild edilir.
     let mut hot_reload_task: Option<tokio::task::JoinHandle<()>> = None;
 
+    // Salt okunur durum uç noktası: blok sonu görüntüsü + son fırsatlar
+    // METRICS_LISTEN_ADDR aynı görüntüyü yalnızca /metrics olarak sunar
+    let mut status_feed = (config.status_listen_addr.is_some() || config.metrics_listen_addr.is_some())
+        .then(|| status_server::StatusFeed::new(config));
+    let mut listeners = 0;
+    for (addr, routes, label, path) in [
+        (config.status_listen_addr, status_server::Routes::All, "Status", "status (read-only)"),
+        (config.metrics_listen_addr, status_server::Routes::MetricsOnly, "Metrics", "metrics (Prometheus)"),
+    ] {
+        let (Some(addr), Some(feed)) = (addr, status_feed.as_ref()) else {
+            continue;
+        };
+        match status_server::spawn(addr, feed.reader(), routes, cancel_token.clone()).await {
+            Ok(local) => {
+                listeners += 1;
+                println_high!("  {} {} endpoint: http://{}/{}", "📡".cyan(), label, local, path);
+            }
+            Err(e) => {
+                eprintln_high!("  {} {} endpoint bind failed ({}): {} — disabled", "⚠️".yellow(), label, addr, e);
+            }
+        }
+    }
+    if listeners == 0 {
+        status_feed = None;
+    }
+
+    // Bytecode bekçisi: arka plan kod hash taraması + kontrat el sıkışması
+    let mut code_watchdog = bytecode_watchdog::BytecodeWatchdog::new(config.bytecode_recheck_blocks);
+    let mut bytecode_scan_task: Option<tokio::task::JoinHandle<bytecode_watchdog::ScanReport>> = None;
+    let mut handshake_task: Option<(B256, tokio::task::JoinHandle<Result<(), String>>)> = None;
+
     // v32.0: Telegram Telemetri sayıçları (vardiya raporu için)
     let mut tg_counters = telegram::TelemetryCounters::new();
 
+    const PIPELINE_BUDGET_MS: u128 = 1500; // Base L2 ~2s blok, %75 bütçe
+    // Gecikme bütçesi aşımında isteğe bağlı aşamalar sırayla atılır (histerezisli)
+    let mut ladder = degradation::DegradationPolicy::from_config(
+        config,
+        Duration::from_millis(PIPELINE_BUDGET_MS as u64),
+    );
+
     // ══════════════ ANA DÖNGÜ — BLOK BAZLI + WSS HEARTBEAT ══════════════
     // v10.1: WSS bağlantı sağlığı kontrolü (Heartbeat)
     // 15 saniye içinde yeni blok gelmezse bağlantı kopmuş sayılır
     // ve run_bot() hata döndürerek agresif reconnect tetiklenir.
     // Base L2: ~2s blok süresi → 15s = ~7 blok kaybı toleransı
     loop {
-        let block_header = match tokio::time::timeout(Duration::from_secs(15), stream.next()).await
-        {
-            Ok(Some(header)) => header,
-            Ok(None) => {
-                // Stream kapandı — reconnect gerekli
-                println!("  {} WSS stream closed — reconnecting...", "⚠️".yellow());
-                return Err(eyre::eyre!("WSS stream closed"));
-            }
-            Err(_) => {
-                // 15s timeout — bağlantı muhtemelen koptu
-                println!(
-                    "  {} WSS heartbeat timeout (no block for 15s) — reconnecting",
-                    "💔".red()
-                );
-                return Err(eyre::eyre!(
-                    "WSS heartbeat timeout: no block received for 15 seconds"
-                ));
+        let next_head = async {
+            tokio::select! {
+                header = stream.next() => header.map(|h| (h, block_feed::HeadSource::Primary)),
+                Some(header) = async {
+                    match sequencer_rx.as_mut() {
+                        Some(rx) => rx.recv().await,
+                        None => std::future::pending().await,
+                    }
+                } => Some((header, block_feed::HeadSource::Sequencer)),
             }
         };
+        let (block_header, head_source) =
+            match tokio::time::timeout(Duration::from_secs(15), next_head).await {
+                Ok(Some(head)) => head,
+                Ok(None) => {
+                    // Stream kapandı — reconnect gerekli
+                    println_high!("  {} WSS stream closed — reconnecting...", "⚠️".yellow());
+                    return Err(eyre::eyre!("WSS stream closed"));
+                }
+                Err(_) => {
+                    // 15s timeout — bağlantı muhtemelen koptu
+                    println_high!(
+                        "  {} WSS heartbeat timeout (no block for 15s) — reconnecting",
+                        "💔".red()
+                    );
+                    return Err(eyre::eyre!(
+                        "WSS heartbeat timeout: no block received for 15 seconds"
+                    ));
+                }
+            };
+
+        // Önceki blok blok süresini aştıysa kanallarda başlık birikmiştir —
+        // beklemeden boşalt, yalnızca en yenisini işle (PROCESS_ALL_BLOCKS=false)
+        let (block_header, head_source, backlog_skipped) = if config.process_all_blocks {
+            (block_header, head_source, 0)
+        } else {
+            let mut heads = vec![(block_header, head_source)];
+            while let Some(Some(header)) = stream.next().now_or_never() {
+                heads.push((header, block_feed::HeadSource::Primary));
+            }
+            if let Some(rx) = sequencer_rx.as_mut() {
+                while let Ok(header) = rx.try_recv() {
+                    heads.push((header, block_feed::HeadSource::Sequencer));
+                }
+            }
+            // Birincil akış sağlığı: atlanan başlıklar da birincilden geldiyse canlıdır
+            if heads[1..]
+                .iter()
+                .any(|(_, source)| *source == block_feed::HeadSource::Primary)
+            {
+                last_primary_head = Instant::now();
+            }
+            match backlog.select(heads, |(header, _)| header.number) {
+                Some(pick) => (pick.head.0, pick.head.1, pick.skipped),
+                // Atlanmış bir bloğun yavaş kaynaktan geç kopyası
+                None => continue,
+            }
+        };
+
+        if head_source == block_feed::HeadSource::Primary {
+            last_primary_head = Instant::now();
+        } else if last_primary_head.elapsed() > Duration::from_secs(15) {
+            println_high!(
+                "  {} Primary head stream silent for 15s (sequencer still live) — reconnecting",
+                "💔".red()
+            );
+            return Err(eyre::eyre!(
+                "WSS heartbeat timeout: primary stream silent for 15 seconds"
+            ));
+        }
+        // Aynı blok diğer kaynaktan zaten geldiyse yalnızca öncülük ölçülür
+        if head_dedup.observe(
+            block_header.number,
+            block_header.hash,
+            head_source,
+            Instant::now(),
+        ) == block_feed::HeadVerdict::Duplicate
+        {
+            continue;
+        }
 
         let block_start = Instant::now();
         let block_number = block_header.number;
+        ladder.begin_block();
+        live_opportunities.set_send_deadline(block_start + Duration::from_millis(PIPELINE_BUDGET_MS as u64));
+
+        if backlog_skipped > 0 {
+            stats.blocks_skipped_backlog += backlog_skipped;
+            eprintln_high!(
+                "  {} [Backlog] skipped {} stale blocks, jumping to #{}",
+                "⏭️".yellow(),
+                backlog_skipped,
+                block_number,
+            );
+        }
+
+        // RPC census: önceki bloğun çağrı özeti; bellekli okumalar bu hash'e sabitlenir
+        if let Some(report) = rpc_census::begin_block(block_number, block_header.hash) {
+            println_low!("     📞 [RPC] {}", report.summary());
+        }
 
         // v10.0: Dinamik timestamp ve base_fee — zincir verisinden
         let block_timestamp = block_header.timestamp;
-        let block_base_fee = block_header.base_fee_per_gas.unwrap_or(0) as u64;
+        let block_base_fee = block_header.base_fee_per_gas.unwrap_or(0);
+        // Saat hijyeni: ilk blokta ve saatte bir (sistem saati ↔ blok / monoton)
+        clock::check(block_timestamp);
+
+        // ── 0. EVENT TAZELİK KONTROLÜ ────────────────────────────────────
+        // Önceki blokların Swap eventleri ile sabitli okumalar karşılaştırılır
+        // (bir blokluk yerleşme payı). Çelişen havuz bayat işaretlenmiştir;
+        // yedek HTTP provider'dan bu bloğa sabitli yeniden okunur.
+        let divergences =
+            event_oracle.check(pools, &states, config.event_divergence_ticks);
+        if !divergences.is_empty() {
+            stats.state_divergence_alarms += divergences.len() as u64;
+            for d in &divergences {
+                eprintln_high!(
+                    "  {} [Divergence] {}: synced tick {} @ #{} vs Swap event tick {} @ #{} — re-syncing from fallback RPC",
+                    "🚨".red(),
+                    pools[d.pool_idx].name,
+                    d.synced_tick,
+                    d.synced_block,
+                    d.event.tick,
+                    d.event.block,
+                );
+            }
+            let pools_rs = pools.to_vec();
+            let states_rs: Vec<SharedPoolState> = states.iter().map(Arc::clone).collect();
+            let fallback = fallback_provider.clone();
+            tokio::spawn(async move {
+                state_sync::resync_diverged(&fallback, &pools_rs, &states_rs, &divergences, block_number)
+                    .await;
+            });
+        }
 
         // ── 1. L1 FEE + SAFETY NET (EVENT-DRIVEN MİMARİ) ────────────────
         // v31.0: State artık event-driven güncellenir (Swap + Mint + Burn).
         // Per-block Multicall3 sync kaldırıldı → RPC yükü %95 azaldı.
-        // Her 50 blokta hafif doğrulama sync'i yapılır (chain reorg koruması).
-        const SAFETY_NET_INTERVAL: u64 = 50;
-        let needs_safety_sync =
-            block_number.saturating_sub(last_bitmap_block) >= SAFETY_NET_INTERVAL;
+        // Her FULL_SYNC_INTERVAL_BLOCKS (varsayılan 50) blokta tam sync yapılır
+        // (chain reorg + kaçırılmış mint/burn koruması).
+        let needs_safety_sync = fast_path.full_sync_due(
+            block_number,
+            backlog_skipped,
+            config.tick_bitmap_max_age_blocks,
+        );
 
         let safety_future = async {
             if needs_safety_sync {
-                let results = sync_all_pools(&provider, pools, &states, block_number).await;
+                // Okumalar abonelikten gelen blok hash'ine sabitlenir (A ve B aynı blok)
+                let results = sync_all_pools(
+                    &providers,
+                    pools,
+                    &states,
+                    block_number,
+                    BlockId::hash(block_header.hash),
+                )
+                .await;
                 Some(results)
             } else {
                 None
             }
         };
 
+        // Kuyruk derinliği: bu bloktaki rakip işlemler (havuz + router hedefli).
+        // Safety net ile paralel çalışır; 250ms'de dönmezse ceza uygulanmaz.
+        let competition_future = async {
+            let watched: Vec<Address> = pools
+                .iter()
+                .map(|p| p.address)
+                .chain(config.competition_routers.iter().copied())
+                .collect();
+            tokio::time::timeout(
+                Duration::from_millis(250),
+                state_sync::fetch_competitor_tx_count(&provider, block_number, &watched),
+            )
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+        };
+
+        // FAST_PATH_SYNC faz 1: yalnızca slot0 (tek Multicall3, abonelik bloğunda).
+        // Okunamazsa blok olağan yoldan işlenir.
+        let slot0_probe_future = async {
+            if !fast_path.probes(needs_safety_sync) {
+                return None;
+            }
+            rpc_census::traced(
+                rpc_census::CallKey::new("aggregate3", state_sync::MULTICALL3_ADDRESS, "slot0_probe"),
+                state_sync::read_all_slot0(providers.primary(), pools, BlockId::hash(block_header.hash)),
+            )
+            .await
+                .map_err(|e| eprintln_high!("     ⚠️ Fast-path slot0 probe failed ({}) — full path", e))
+                .ok()
+        };
+
+        // Kontrat pause view'ı tam sync / faz 1 batch'ine eklenir; ikisi de
+        // koşmuyorsa (FAST_PATH_SYNC kapalı, tam sync zamanı değil) tek çağrı
+        let pause_view_future = async {
+            if needs_safety_sync || fast_path.probes(needs_safety_sync) {
+                return;
+            }
+            if let Err(e) =
+                state_sync::read_pause_view(providers.primary(), BlockId::hash(block_header.hash)).await
+            {
+                eprintln_high!("     ⚠️ [Contract] pause view read failed: {}", e);
+            }
+        };
+
+        // Bitmap tazelemesi: tam sync bloğunda ya da bir bitmap yaş sınırını
+        // aştığında. Artımlı yol yalnızca değişen word'lerin tick'lerini okur;
+        // TICK_BITMAP_FULL_RESYNC_BLOCKS'ta bir tam taramaya düşer.
+        let bitmap_refresh_due = needs_safety_sync
+            || states.iter().any(|s| {
+                let st = s.load();
+                st.tick_bitmap
+                    .as_deref()
+                    .is_some_and(|bm| !bm.is_fresh(block_number, config.tick_bitmap_max_age_blocks))
+            });
+        let bitmap_future = async {
+            if !bitmap_refresh_due {
+                return None;
+            }
+            Some(
+                state_sync::refresh_all_tick_bitmaps(
+                    &providers,
+                    pools,
+                    &states,
+                    block_number,
+                    config.tick_bitmap_range,
+                    config.tick_bitmap_full_resync_blocks,
+                )
+                .await,
+            )
+        };
+
         // OPT-4: Safety net async + L1 fee from AtomicU64 cache
-        let safety_result = safety_future.await;
+        let (safety_result, competitor_txs, slot0_probe, (), bitmap_result) = tokio::join!(
+            safety_future,
+            competition_future,
+            slot0_probe_future,
+            pause_view_future,
+            bitmap_future
+        );
+        contract_state::report(block_number, telegram_sender.as_ref());
+        circuit_breaker::on_block(block_number, telegram_sender.as_ref());
+        stats.failure_kinds = circuit_breaker::failure_counts();
         let l1_data_fee_wei = GLOBAL_L1_FEE.load(Ordering::Relaxed) as u128;
+        let competition = CompetitionSnapshot {
+            block_number,
+            competitor_tx_last_block: competitor_txs,
+        };
+        if strategy::competition_penalty(competitor_txs, config) != strategy::CompetitionPenalty::None {
+            stats.competition_penalty_blocks += 1;
+        }
 
         // Safety net sonuçlarını işle
+        let mut sync_errors = 0usize;
         if let Some(sync_results) = safety_result {
             let ok_count = sync_results.iter().filter(|r| r.is_ok()).count();
+            sync_errors = sync_results.len() - ok_count;
             if ok_count > 0 {
-                eprintln!(
+                eprintln_high!(
                     "     🔄 Safety net sync ({}/{} pools) [Block #{}]",
                     ok_count,
                     pools.len(),
                     block_number,
                 );
             }
-            last_bitmap_block = block_number;
+            for e in sync_results.iter().filter_map(|r| r.as_ref().err()) {
+                stats.record_error(e);
+            }
+            if let Err(e) = state_sync::sync_outcome(&sync_results) {
+                eprintln_high!("     ⚠️ Safety net: {}", e);
+            }
+            fast_path.record_full_sync(block_number);
+        }
+
+        if let Some(refresh_results) = bitmap_result {
+            let (mut full, mut incremental, mut ticks_read) = (0usize, 0usize, 0usize);
+            for result in &refresh_results {
+                match result {
+                    Ok(state_sync::BitmapRefresh::Full) => full += 1,
+                    Ok(state_sync::BitmapRefresh::Incremental { ticks_read: n, .. }) => {
+                        incremental += 1;
+                        ticks_read += n;
+                    }
+                    Err(e) => stats.record_error(e),
+                }
+            }
+            stats.tick_bitmap_syncs += (full + incremental) as u64;
+            if full > 0 {
+                eprintln_high!(
+                    "     🗺️ TickBitmap full rescan ({} pools) [Block #{}]",
+                    full, block_number,
+                );
+            }
+            if incremental > 0 {
+                println_low!(
+                    "     🗺️ TickBitmap delta ({} pools, {} ticks re-read) [Block #{}]",
+                    incremental, ticks_read, block_number,
+                );
+            }
+        }
+
+        // Sessiz blok: hiçbir fiyat son değerlendirmeden beri değişmedi →
+        // likidite okuması, spread/fırsat kontrolü ve replay kaydı atlanır
+        let block_sync = fast_path.classify(needs_safety_sync, slot0_probe.as_deref(), &states);
+        let quiet_block = !block_sync.runs_opportunity_check();
+        if quiet_block {
+            state_sync::mark_quiet_block(&states, block_number);
+            stats.quiet_blocks += 1;
         }
 
         // v27.0: L1 Data Fee teşhis logu — 0 gelmesi OP Stack'te anormal
         let l1_fee_eth = l1_data_fee_wei as f64 / 1e18;
         if l1_data_fee_wei == 0 {
-            eprintln!(
-                "  ⚠️ [L1 Fee] WARNING: L1 data fee = 0 wei — GasPriceOracle may not be responding!",
-            );
+            if config.l1_fee_oracle.is_some() {
+                eprintln_high!(
+                    "  ⚠️ [L1 Fee] WARNING: L1 data fee = 0 wei — GasPriceOracle may not be responding!",
+                );
+            }
         } else {
-            eprintln!(
+            eprintln_high!(
                 "  ⛽ [L1 Fee] {} wei ({:.8} ETH)",
                 l1_data_fee_wei, l1_fee_eth,
             );
         }
 
+        // Uçuştaki yürütmeler: fırsat bu blokta ölmüşse imza/gönderim öncesi iptal
+        let expired_executions =
+            live_opportunities.revalidate(block_number, config.spread_probe_size_weth, pools, &states);
+        if expired_executions > 0 {
+            eprintln_high!(
+                "  ⏹️ [Expiry] {} in-flight execution(s) superseded at block #{} — cancelling",
+                expired_executions, block_number,
+            );
+        }
+
         let sync_ms = block_start.elapsed().as_millis();
 
-        // Gecikme ölçümü
+        // Tazelik eşiği: başlık varış aralığı + senkron süresi dağılımı
+        if let Some(change) = staleness::observe_block(block_number, block_start, sync_ms) {
+            eprintln_high!(
+                "  📏 [Staleness] recalibrated: detection {}→{}ms, execution {}→{}ms (p95 interval {:.0}ms, p95 sync {:.0}ms, n={})",
+                change.previous.detection_ms,
+                change.current.detection_ms,
+                change.previous.execution_ms,
+                change.current.execution_ms,
+                change.p95_interval_ms,
+                change.p95_sync_ms,
+                change.samples,
+            );
+        }
+
+        // Gecikme ölçümü (toplam + transport başına)
+        metrics::BLOCK_SYNC_LATENCY.record(block_start.elapsed().as_micros() as u64);
         stats.update_latency(sync_ms as f64);
+        stats.record_transport_latency(active_transport, sync_ms as f64);
 
         // v15.0: Gecikme spike tespiti ve uyarısı
         if (sync_ms as f64) > config.latency_spike_threshold_ms {
             stats.latency_spikes += 1;
-            eprintln!(
+            eprintln_high!(
                 "  ⚡ [Block #{}] Latency SPIKE: {}ms (threshold: {:.0}ms) — #{} spike",
                 block_number, sync_ms, config.latency_spike_threshold_ms, stats.latency_spikes,
             );
@@ -1539,7 +3830,7 @@ async fn run_bot(
         for (i, state) in states.iter().enumerate() {
             let st = state.load();
             if st.is_stale && i < pools.len() {
-                println!(
+                println_high!(
                     "  {} [Block #{}] {} stale (event-driven, last update: {}ms ago)",
                     "⚠️".yellow(),
                     block_number,
@@ -1551,6 +3842,89 @@ async fn run_bot(
 
         stats.total_blocks_processed += 1;
 
+        // ── 1.3. ISINMA KAPISI ──────────────────────────────────
+        // Sayaç yalnızca ardışık temiz bloklarda ilerler; ısınmadaki ilk hata sıfırlar.
+        let bitmaps_fresh = states.iter().all(|s| {
+            let st = s.load();
+            !st.is_initialized
+                || st
+                    .tick_bitmap
+                    .as_deref()
+                    .is_none_or(|bm| bm.is_fresh(st.last_block, config.tick_bitmap_max_age_blocks))
+        });
+        let block_health = warmup::BlockHealth {
+            all_synced,
+            bitmaps_fresh,
+            sync_errors,
+        };
+        match warmup_gate.on_block(&block_health) {
+            warmup::WarmupStep::JustArmed => println_high!(
+                "  {} [Block #{}] Warmup complete — {} clean blocks, execution ARMED",
+                "🟢".green(),
+                block_number,
+                config.warmup_clean_blocks,
+            ),
+            warmup::WarmupStep::Reset(problem) => eprintln_high!(
+                "  {} [Block #{}] Warmup reset: {} — counter back to 0/{}",
+                "⏳".yellow(),
+                block_number,
+                problem,
+                config.warmup_clean_blocks,
+            ),
+            warmup::WarmupStep::Progress { .. } | warmup::WarmupStep::Armed => {}
+        }
+
+        // ── 1.35. LİKİDİTE ANALİTİĞİ FEED ──────────────────────
+        // Sadece Arc klonlanır; analitik görevi yavaş olsa bile beklenmez.
+        if config.liquidity_analytics_budget_ms > 0 {
+            liquidity_analytics::publish_if_changed(
+                &analytics_tx,
+                &mut analytics_signature,
+                pools,
+                &states,
+                block_number,
+            );
+        }
+
+        // ── 1.36. YÖN KAPISI BEKLEME SÜRESİ ────────────────────
+        direction_gate::on_block(block_number);
+
+        // ── 1.365. KALICI SPREAD / YAPISAL AYRIŞMA ─────────────
+        // Sessiz bloklar da sayılır: spot fiyatlar her blok işlenir
+        divergence_guard::on_block(block_number, pools, &states, pair_combos, telegram_sender.as_ref());
+
+        // ── 1.366. FIRSAT ISI HARİTASI KAPSAMI ─────────────────
+        // Bot bu saatte açık — opps/day, gözlenen güne göre normalize edilir
+        heatmap::observe(clock::now());
+
+        // ── 1.367. KAĞIT YÜRÜTME DEFTERİ ───────────────────────
+        // Deadline'ı geçen kağıt TX'ler takılı sayılır, nonce yeniden verilir
+        paper::on_block(block_number);
+
+        // ── 1.37. QUOTER EŞLİK DENETİMİ ────────────────────────
+        // Her AUDIT_INTERVAL_BLOCKS blokta arka planda; sync hatası, gecikme
+        // spike'ı ya da süren denetim varken ilk temiz bloğa ertelenir.
+        match quoter_audit::poll(
+            block_number,
+            sync_errors,
+            sync_ms as f64,
+            config.latency_spike_threshold_ms,
+        ) {
+            quoter_audit::AuditDecision::Run => quoter_audit::spawn_audit(
+                provider.clone(),
+                pools.clone(),
+                states.iter().map(Arc::clone).collect(),
+                telegram_sender.clone(),
+            ),
+            quoter_audit::AuditDecision::Deferred(reason) => eprintln_high!(
+                "  {} [QuoterAudit] due at block #{} — deferred ({})",
+                "⏸️".yellow(),
+                block_number,
+                reason,
+            ),
+            quoter_audit::AuditDecision::Idle => {}
+        }
+
         // ── 1.4. KEŞİF MOTORU: HOT-RELOAD + GC + SKORLAMA ─────
 
         // v29.0: Önceki arka plan hot-reload tamamlandı mı kontrol et
@@ -1559,11 +3933,13 @@ async fn run_bot(
             if handle.is_finished() {
                 let handle = hot_reload_task.take().unwrap();
                 if let Ok(()) = handle.await {
-                    sim_engine.cache_bytecodes(pools, &states);
+                    sim_engine.cache_bytecodes(pools, &static_store);
+                    attach_revisit_engine(config, pools, &static_store);
+                    refresh_pool_token_balances(&provider, &mut sim_engine, pools, &balance_slots).await;
                     let reload_caller = executor_address.unwrap_or_default();
                     let reload_contract = config.contract_address.unwrap_or_default();
                     sim_engine.initialize_base_db(pools, &states, reload_caller, reload_contract);
-                    eprintln!(
+                    eprintln_high!(
                         "  🔧 [Hot-Reload BG] REVM base_db rebuilt ({} pools)",
                         pools.len(),
                     );
@@ -1571,6 +3947,95 @@ async fn run_bot(
             }
         }
 
+        // ── 1.45. BYTECODE TAZELİK BEKÇİSİ ─────────────────────
+        // Her BYTECODE_RECHECK_BLOCKS blokta base_db'deki kod hash'leri zincirle
+        // karşılaştırılır (arka planda). Biten taramanın değişiklikleri burada
+        // uygulanır: base_db yeniden kurulur, gas kalibrasyonu sıfırlanır.
+        if bytecode_scan_task.as_ref().is_some_and(|h| h.is_finished()) {
+            if let Ok(report) = bytecode_scan_task.take().unwrap().await {
+                if report.errors > 0 {
+                    eprintln_high!(
+                        "  {} [Bytecode] {} of {} code hashes unreadable — retried next scan",
+                        "⚠️".yellow(),
+                        report.errors,
+                        report.errors + report.checked,
+                    );
+                }
+                if !report.changes.is_empty() {
+                    // apply base_db'yi yeniden kurar — bakiyeler açılıştan kalmasın
+                    refresh_pool_token_balances(&provider, &mut sim_engine, pools, &balance_slots).await;
+                }
+                let outcome = code_watchdog.apply(
+                    report,
+                    &mut sim_engine,
+                    pools,
+                    &states,
+                    block_number,
+                    telegram_sender,
+                );
+                stats.bytecode_changes += outcome.changed as u64;
+                if outcome.calibration_reset {
+                    // Eski koda ait ölçüm — sonraki fırsat 150K fallback ile başlar
+                    last_simulated_gas = None;
+                }
+            }
+        }
+        if bytecode_scan_task.is_none() && code_watchdog.due(block_number) {
+            let tracked = sim_engine.loaded_code();
+            let provider_bc = provider.clone();
+            let use_proof = code_watchdog.proof_supported();
+            bytecode_scan_task = Some(tokio::spawn(async move {
+                bytecode_watchdog::scan(&provider_bc, &tracked, use_proof).await
+            }));
+        }
+        // Kontrat yükseltildiyse başlangıç kontrolleri yeniden: kod + token onayları
+        if handshake_task.as_ref().is_some_and(|(_, h)| h.is_finished()) {
+            let (expected, handle) = handshake_task.take().unwrap();
+            let result = handle
+                .await
+                .unwrap_or_else(|e| Err(format!("handshake task failed: {}", e)));
+            code_watchdog.complete_handshake(expected, result, block_number);
+        }
+        if handshake_task.is_none() {
+            if let Some((contract, expected)) = code_watchdog.handshake_due(block_number) {
+                let provider_hs = provider.clone();
+                let audit = (config.execution_enabled()
+                    && config.approval_audit_mode != approval_audit::ApprovalAuditMode::Off)
+                    .then(|| {
+                        (
+                            pools.clone(),
+                            states.iter().map(Arc::clone).collect::<Vec<SharedPoolState>>(),
+                            Arc::clone(&approvals),
+                            telegram_sender.clone(),
+                        )
+                    });
+                let lender = config.aave_pool_address;
+                let max_trade = config.max_trade_size_weth;
+                let mode = config.approval_audit_mode;
+                let required = config.approval_required_spenders;
+                let handle = tokio::spawn(async move {
+                    bytecode_watchdog::contract_handshake(&provider_hs, contract, expected).await?;
+                    if let Some((audit_pools, audit_states, audit_shared, tg_audit)) = audit {
+                        approval_audit::run_audit(
+                            &provider_hs,
+                            contract,
+                            lender,
+                            &audit_pools,
+                            &audit_states,
+                            max_trade,
+                            mode,
+                            required,
+                            &audit_shared,
+                            &tg_audit,
+                        )
+                        .await;
+                    }
+                    Ok::<(), String>(())
+                });
+                handshake_task = Some((expected, handle));
+            }
+        }
+
         // [Adım 3] Bekleyen havuzları canlı sisteme enjekte et
         let hot_reload_count = discovery_engine::apply_pending_updates(
             &discovery_registry,
@@ -1589,6 +4054,7 @@ async fn run_bot(
             let bg_states: Vec<SharedPoolState> = states[new_start..].to_vec();
             let bg_bitmap_range = config.tick_bitmap_range;
             let bg_block = block_number;
+            let bg_static_store = Arc::clone(&static_store);
 
             hot_reload_task = Some(tokio::spawn(async move {
                 // Adım 1: Bytecode al (paralel)
@@ -1605,12 +4071,11 @@ async fn run_bot(
                 for (i, result) in bytecode_results {
                     if let Ok(code) = result {
                         if !code.is_empty() {
-                            let code_vec = code.to_vec();
-                            bg_states[i].rcu(|old| {
-                                let mut s = (**old).clone();
-                                s.bytecode = Some(code_vec.clone());
-                                s
-                            });
+                            bg_static_store
+                                .write()
+                                .entry(bg_pools[i].address)
+                                .or_default()
+                                .bytecode = Some(Arc::new(code.to_vec()));
                         }
                     }
                 }
@@ -1625,11 +4090,15 @@ async fn run_bot(
                         let bitmap_range = bg_bitmap_range;
                         async move {
                             if let Err(e) = crate::state_sync::sync_pool_state(
-                                provider, pool_cfg, pool_state, bg_block,
+                                provider,
+                                pool_cfg,
+                                pool_state,
+                                bg_block,
+                                BlockId::number(bg_block),
                             )
                             .await
                             {
-                                eprintln!(
+                                eprintln_high!(
                                     "  ⚠️ [Hot-Reload BG] {} state sync failed: {}",
                                     pool_cfg.name, e
                                 );
@@ -1643,7 +4112,7 @@ async fn run_bot(
                             )
                             .await
                             {
-                                eprintln!(
+                                eprintln_high!(
                                     "  ⚠️ [Hot-Reload BG] {} bitmap sync failed: {}",
                                     pool_cfg.name, e
                                 );
@@ -1652,7 +4121,7 @@ async fn run_bot(
                     })
                     .collect();
                 join_all(sync_futs).await;
-                eprintln!(
+                eprintln_high!(
                     "  ✅ [Hot-Reload BG] {} new pools synced in background",
                     bg_pools.len(),
                 );
@@ -1666,24 +4135,22 @@ async fn run_bot(
                     pools[new_start..].iter().map(|p| p.address).collect();
                 if !new_addrs.is_empty() {
                     let calldata = crate::executor::encode_whitelist_calldata(&new_addrs);
-                    if let (Some(ref pk), Some(contract_addr)) =
-                        (&config.private_key, config.contract_address)
-                    {
-                        let pk_clone = pk.clone();
+                    if let Some(contract_addr) = config.contract_address {
                         let mev_exec_clone = Arc::clone(&mev_executor);
+                        let connections_clone = Arc::clone(&connections);
                         let nonce = nonce_manager.get_and_increment();
                         let nm_clone = Arc::clone(&nonce_manager);
                         let base_fee = block_base_fee;
                         let addr_count = new_addrs.len();
                         tokio::spawn(async move {
                             match whitelist_pools_on_chain(
-                                mev_exec_clone, pk_clone, contract_addr,
+                                mev_exec_clone, connections_clone, contract_addr,
                                 calldata, nonce, nm_clone, base_fee,
                             ).await {
-                                Ok(_) => eprintln!(
+                                Ok(_) => eprintln_high!(
                                     "  ✅ [Whitelist] {} pools added to on-chain whitelist", addr_count,
                                 ),
-                                Err(e) => eprintln!(
+                                Err(e) => eprintln_high!(
                                     "  ⚠️ [Whitelist] On-chain whitelist error: {} — admin must add manually", e,
                                 ),
                             }
@@ -1697,30 +4164,14 @@ async fn run_bot(
             event_listener_cancel.cancel();
             event_listener_cancel = cancel_token.child_token();
             {
-                let pools_ev = pools.to_vec();
-                let states_ev: Vec<SharedPoolState> = states.iter().map(Arc::clone).collect();
-                let rpc_url_ev = config.rpc_wss_url.clone();
-                let token_ev = event_listener_cancel.clone();
-
-                tokio::spawn(async move {
-                    let ws = WsConnect::new(&rpc_url_ev);
-                    match ProviderBuilder::default().connect_ws(ws).await {
-                        Ok(ws_provider) => {
-                            if let Err(e) = state_sync::start_pool_event_listener(
-                                &ws_provider,
-                                &pools_ev,
-                                &states_ev,
-                                token_ev,
-                            )
-                            .await
-                            {
-                                eprintln!("  [EventListener] Hot-reload restart error: {}", e);
-                            }
-                        }
-                        Err(e) => eprintln!("  [EventListener] Hot-reload WS error: {}", e),
-                    }
-                });
-                eprintln!(
+                spawn_pool_event_listener(
+                    Arc::clone(&connections),
+                    pools.to_vec(),
+                    states.iter().map(Arc::clone).collect(),
+                    Arc::clone(&event_oracle),
+                    event_listener_cancel.clone(),
+                );
+                eprintln_high!(
                     "  [Hot-Reload] Event listener restarted with {} pools",
                     pools.len(),
                 );
@@ -1750,24 +4201,13 @@ async fn run_bot(
                 // Yeniden aktiflesen havuzlar icin event listener'i guncelle
                 event_listener_cancel.cancel();
                 event_listener_cancel = cancel_token.child_token();
-                {
-                    let pools_ev = pools.to_vec();
-                    let states_ev: Vec<SharedPoolState> = states.iter().map(Arc::clone).collect();
-                    let rpc_url_ev = config.rpc_wss_url.clone();
-                    let token_ev = event_listener_cancel.clone();
-                    tokio::spawn(async move {
-                        let ws = WsConnect::new(&rpc_url_ev);
-                        if let Ok(ws_provider) = ProviderBuilder::default().connect_ws(ws).await {
-                            let _ = state_sync::start_pool_event_listener(
-                                &ws_provider,
-                                &pools_ev,
-                                &states_ev,
-                                token_ev,
-                            )
-                            .await;
-                        }
-                    });
-                }
+                spawn_pool_event_listener(
+                    Arc::clone(&connections),
+                    pools.to_vec(),
+                    states.iter().map(Arc::clone).collect(),
+                    Arc::clone(&event_oracle),
+                    event_listener_cancel.clone(),
+                );
             }
         }
 
@@ -1795,55 +4235,99 @@ async fn run_bot(
         // OPT-5: Üç ayrı pair_combos iterasyonu birleştirildi.
         // print_spread_info + istatistik güncelleme tek pass'ta yapılır.
         // PoolConfig clone yerine referans kullanılır.
-        print_block_update(block_number, pools, &states, sync_ms);
-        for combo in pair_combos.iter() {
-            let sa = states[combo.pool_a_idx].load();
-            let sb = states[combo.pool_b_idx].load();
-
-            // Spread bilgisi yazdır (referansla, clone yok)
-            if sa.is_active() && sb.is_active() {
-                let spread = (sa.eth_price_usd - sb.eth_price_usd).abs();
-                let min_p = sa.eth_price_usd.min(sb.eth_price_usd);
-                if min_p > 0.0 {
-                    let spread_pct = (spread / min_p) * 100.0;
-                    let direction = if sa.eth_price_usd < sb.eth_price_usd {
-                        format!(
-                            "{} \u{2192} {}",
-                            pools[combo.pool_a_idx].name, pools[combo.pool_b_idx].name
-                        )
-                    } else {
-                        format!(
-                            "{} \u{2192} {}",
-                            pools[combo.pool_b_idx].name, pools[combo.pool_a_idx].name
-                        )
-                    };
-                    if spread_pct > 0.001 {
-                        println!(
-                            "     Spread: {:.4}% ({:.6}Q) | {} BUY\u{2192}SELL",
-                            spread_pct, spread, direction,
-                        );
-                    }
+        if !quiet_block {
+            print_block_update(block_number, pools, &states, sync_ms, warmup_gate);
+            for combo in pair_combos.iter() {
+                let sa = states[combo.pool_a_idx].load();
+                let sb = states[combo.pool_b_idx].load();
+
+                // Spread bilgisi yazdır (referansla, clone yok)
+                if sa.is_active() && sb.is_active() {
+                    let spread = (sa.eth_price_usd - sb.eth_price_usd).abs();
+                    let min_p = sa.eth_price_usd.min(sb.eth_price_usd);
+                    if min_p > 0.0 {
+                        let spread_pct = (spread / min_p) * 100.0;
+                        let (buy_pool, sell_pool) = if sa.eth_price_usd < sb.eth_price_usd {
+                            (&pools[combo.pool_a_idx].name, &pools[combo.pool_b_idx].name)
+                        } else {
+                            (&pools[combo.pool_b_idx].name, &pools[combo.pool_a_idx].name)
+                        };
+                        if spread_pct > 0.001 {
+                            // Referans boyutta yürütülebilir fiyatlar (sell/buy, quote/WETH)
+                            let probe = config.spread_probe_size_weth;
+                            let ea = strategy::executable_price(&pools[combo.pool_a_idx], &sa, probe);
+                            let eb = strategy::executable_price(&pools[combo.pool_b_idx], &sb, probe);
+                            let emitted = log_event::json(|| {
+                                log_event::LogEvent::Spread(log_event::Spread {
+                                    block: block_number,
+                                    buy_pool: buy_pool.clone(),
+                                    sell_pool: sell_pool.clone(),
+                                    spread_pct,
+                                    spread_quote: spread,
+                                    probe_weth: probe,
+                                    pool_a_sell: ea.sell,
+                                    pool_a_buy: ea.buy,
+                                    pool_b_sell: eb.sell,
+                                    pool_b_buy: eb.buy,
+                                })
+                            });
+                            if !emitted {
+                                println_low!(
+                                    "     Spread: {:.4}% ({:.6}Q) | {} \u{2192} {} BUY\u{2192}SELL | exec@{}: A {:.6}/{:.6} B {:.6}/{:.6}",
+                                    spread_pct, spread, buy_pool, sell_pool, probe, ea.sell, ea.buy, eb.sell, eb.buy,
+                                );
+                            }
+                        }
 
-                    // İstatistik güncelleme
-                    if spread_pct > stats.max_spread_pct {
-                        stats.max_spread_pct = spread_pct;
-                    }
-                    if spread_pct > 0.001 {
-                        stats.total_opportunities += 1;
+                        // İstatistik güncelleme
+                        if spread_pct > stats.max_spread_pct {
+                            stats.max_spread_pct = spread_pct;
+                        }
+                        if spread_pct > 0.001 {
+                            stats.total_opportunities += 1;
+                        }
                     }
                 }
             }
         }
 
+        // hard modda eksik token onayı → bu blokta hiçbir fırsat yürütülmez
+        let approval_block: Option<String> =
+            approvals.read().as_ref().and_then(|r| r.downgrade_reason());
+        // Kontrat bytecode'u değişti ve el sıkışması geçmedi → yürütme yok
+        let bytecode_block = code_watchdog.execution_block();
+        // TUI'de `p` → bu blokta hiçbir fırsat yürütülmez (gölge log sürer)
+        let paused = tui.is_some_and(|t| t.controls.is_paused());
+        // Global circuit breaker duraklamada → aynı şekilde gölge (senkron sürer)
+        let breaker_paused = circuit_breaker::evaluation_blocked();
+
+        // Sync + koruyucular bitti — bloğun ilk bütçe ölçümü
+        ladder_checkpoint(&mut ladder, degradation::Checkpoint::PreScan, block_start, block_number);
+
+        // Replay kaydı — fırsat kontrolünün gördüğü girdilerle aynı; atılan
+        // basamaklar blok sonunda bilinir, satır o zaman yazılır
+        let state_diff_row = state_diff_recorder.as_mut().filter(|_| !quiet_block).map(|recorder| {
+            let inputs = replay::BlockInputs {
+                base_fee: block_base_fee,
+                l1_data_fee_wei,
+                last_simulated_gas,
+                competitor_txs,
+            };
+            recorder.build_record(block_number, inputs, pools, &states)
+        });
+
         // ── 3. ARBİTRAJ FIRSATI KONTROLÜ ────────────────────
         // v28.0: Pipeline Bütçesi — toplam blok işleme süresi Base L2 blok
         // süresinin (2s) %75'ini aşıyorsa, gönderilecek TX hedef bloğu
         // kaçıracağı için işlem atlanır. Eski/gecikmeli veriyle yapılan
         // simülasyonlar frontrun ve sandwich saldırılarına açıktır.
         let pipeline_elapsed_ms = block_start.elapsed().as_millis();
-        const PIPELINE_BUDGET_MS: u128 = 1500; // Base L2 ~2s blok, %75 bütçe
+        let mut opportunities_seen = 0usize;
+
+        if all_synced && !quiet_block && pipeline_elapsed_ms <= PIPELINE_BUDGET_MS {
+            // Sonraki blokların sessizlik karşılaştırması bu fiyatlara göre
+            fast_path.record_evaluated(&states);
 
-        if all_synced && pipeline_elapsed_ms <= PIPELINE_BUDGET_MS {
             // OPT-6: Discovery registry aktif snapshot — tek seferde al, combo loop'ta RwLock yok
             let active_snapshot: Vec<bool> = {
                 let reg = discovery_registry.read();
@@ -1853,6 +4337,12 @@ async fn run_bot(
             // OPT-3: İki fazlı arbitraj taraması — tüm combo'ları tara, EN İYİ fırsatı seç
             // Eski: Sıralı tarama, ilk kârlı fırsat bulununca simulate+execute (suboptimal)
             // Yeni: Tüm combo'lar değerlendirilir, en yüksek kârlı fırsat seçilir
+            // A/B: varyant A'nın combo başına saf kararı (gölge/yürütme işaretinden önce)
+            let mut ab_primary: Vec<(usize, Option<ab_shadow::Decision>)> = Vec::new();
+            // A/B varyant B'si combo'yu A'nın gördüğü aynı donmuş state'lerle tekrarlar
+            let mut ab_snapshots: HashMap<usize, [SharedPoolState; 2]> = HashMap::new();
+            // Tarama kenarı genişletmesi: blok başına en fazla bir (gecikme sınırı)
+            let mut bitmap_extended = false;
             let mut opportunities: Vec<(
                 usize,
                 ArbitrageOpportunity,
@@ -1860,6 +4350,8 @@ async fn run_bot(
                 [SharedPoolState; 2],
             )> = Vec::new();
 
+            // Değerlendirilebilir çiftler (aktif, kara listede değil, takip beklemiyor)
+            let mut candidates: Vec<pair_scheduler::Candidate> = Vec::with_capacity(pair_combos.len());
             for (combo_idx, combo) in pair_combos.iter().enumerate() {
                 // OPT-6: Snapshot'tan aktiflik kontrolü (RwLock yok)
                 if combo.pool_a_idx >= active_snapshot.len()
@@ -1875,67 +4367,367 @@ async fn run_bot(
                 if let Some(&until_block) = pair_cooldown.get(&combo_idx) {
                     if block_number < until_block {
                         if block_number % 25 == 0 {
-                            eprintln!(
+                            eprintln_high!(
                                 "     \u{26d4} [Blacklist] {} \u{2192} blocked until block #{} (remaining: {} blocks)", combo.pair_name, until_block,
                                 until_block.saturating_sub(block_number),
                             );
                         }
                         continue;
+                    } else if !warmup_gate.is_armed() {
+                        // Süre doldu ama probe denemesi ısınma bitene kadar bekler
+                        continue;
                     } else {
                         pair_cooldown.remove(&combo_idx);
                         pair_failures.remove(&combo_idx);
-                        eprintln!(
+                        eprintln_high!(
                             "     \u{2705} [Blacklist] {} cool-down expired — reactivated",
                             combo.pair_name,
                         );
                     }
                 }
 
+                // İki adımlı plan: 1. adımın receipt'i gelene kadar combo yürütülmez
+                // (işlem sonrası state okunmadan kalan spread iki kez sayılır)
+                if followup::awaiting(combo_idx) {
+                    continue;
+                }
+
+                candidates.push(pair_scheduler::Candidate {
+                    idx: combo_idx,
+                    key: pair_scheduler::pair_key(
+                        pools[combo.pool_a_idx].address,
+                        pools[combo.pool_b_idx].address,
+                    ),
+                    name: &combo.pair_name,
+                });
+            }
+
+            // Çift önceliği: EV skoruna göre sıra + garanti rotasyon slotu;
+            // STRATEGY_TIME_BUDGET_MS dolunca kalan çiftler bu blokta atlanır
+            let schedule = pair_scheduler::plan(block_number, &candidates);
+            let scan_started = Instant::now();
+            for (rank, &(combo_idx, scheduled_pair)) in schedule.order.iter().enumerate() {
+                if !pair_scheduler::admit(&schedule, rank, scan_started.elapsed()) {
+                    continue;
+                }
+                // Son basamak: çekirdek tespit yalnızca ilk sıradaki çifte daralır
+                ladder_checkpoint(&mut ladder, degradation::Checkpoint::PairScan, block_start, block_number);
+                if rank > 0 && !ladder.allows(degradation::CORE_DETECTION) {
+                    continue;
+                }
+                let shed_level = ladder.shed_level();
+                let combo = &pair_combos[combo_idx];
+                let evaluation_started = Instant::now();
+                let rpc_scope = rpc_census::OpportunityScope::new();
+
                 let pp = [
                     pools[combo.pool_a_idx].clone(),
                     pools[combo.pool_b_idx].clone(),
                 ];
-                let ps = [
-                    states[combo.pool_a_idx].clone(),
-                    states[combo.pool_b_idx].clone(),
-                ];
-                if let Some(opportunity) = check_arbitrage_opportunity(
-                    &pp,
-                    &ps,
-                    config,
-                    block_base_fee,
-                    last_simulated_gas,
-                    l1_data_fee_wei,
-                ) {
+                // Tespitten yürütmeye tek tutarlı görüntü: fırsatın tüm aşamaları
+                // (hassasiyet, iki adım, simülasyon, calldata, gölge log) bu donmuş
+                // state'leri okur — canlı state'ler fırsat boyunca bir daha okunmaz
+                // Pending swap tahmini donmuş kopyada — doğrulama ve REVM de onu okur
+                let mut ps = pool_snapshot::capture_pair_predicted(
+                    &states[combo.pool_a_idx],
+                    &states[combo.pool_b_idx],
+                    config.predicted_state_trust_blocks,
+                );
+                // Ters senaryo / iki yönlü rota tespitin içinde — seviye çağrıya taşınır
+                let check = |ps: &[SharedPoolState; 2]| {
+                    degradation::with_shed(shed_level, || {
+                        check_arbitrage_opportunity(
+                            &pp,
+                            ps,
+                            config,
+                            block_base_fee,
+                            last_simulated_gas,
+                            l1_data_fee_wei,
+                            &competition,
+                            &filter_chain,
+                            Some(&sim_engine),
+                        )
+                    })
+                };
+                let mut checked = {
+                    let _scan = runtime_health::section("opportunity_scan");
+                    check(&ps)
+                };
+                // Optimum taranan bitmap kenarına dayandı → yalnızca o bacağın o
+                // kenarını genişlet, boyutlandırmayı BİR kez tekrarla (fırsat başına 1)
+                if let Some(hit) = checked.as_ref().and_then(|o| o.scan_boundary) {
+                    if !bitmap_extended && ladder.allows(degradation::BITMAP_REFRESH) {
+                        bitmap_extended = true;
+                        // Genişletme canlı state'e yazılır; yeniden boyutlandırma taze snapshot'la
+                        let live_idx = if hit.pool_idx == 0 { combo.pool_a_idx } else { combo.pool_b_idx };
+                        match rpc_scope
+                            .within(state_sync::extend_tick_bitmap(
+                                &providers,
+                                &pp[hit.pool_idx],
+                                &states[live_idx],
+                                hit.edge,
+                                block_number,
+                            ))
+                            .await
+                        {
+                            Ok(()) => {
+                                stats.bitmap_extensions += 1;
+                                ps = pool_snapshot::capture_pair_predicted(
+                                    &states[combo.pool_a_idx],
+                                    &states[combo.pool_b_idx],
+                                    config.predicted_state_trust_blocks,
+                                );
+                                checked = check(&ps);
+                            }
+                            Err(e) => {
+                                stats.record_error(&e);
+                                eprintln_high!(
+                                    "     \u{26a0}\u{fe0f} [Bitmap] {} {:?} edge extension failed: {}",
+                                    pp[hit.pool_idx].name, hit.edge, e,
+                                );
+                            }
+                        }
+                    }
+                }
+                pair_scheduler::record_evaluation(
+                    scheduled_pair,
+                    block_number,
+                    checked.as_ref().map_or(0.0, |o| o.expected_profit_weth),
+                    evaluation_started.elapsed(),
+                );
+                let rpc_report = rpc_scope.finish();
+                if !rpc_report.redundant.is_empty() {
+                    eprintln_high!("     🔁 [RPC] {} redundant reads: {}", combo.pair_name, rpc_report.summary());
+                } else if rpc_report.total.calls + rpc_report.total.cached > 0 {
+                    println_low!("     📞 [RPC] {}: {}", combo.pair_name, rpc_report.summary());
+                }
+                if ab_shadow.is_some() && ladder.allows(degradation::AB_SHADOW) {
+                    ab_primary.push((combo_idx, checked.as_ref().map(ab_shadow::Decision::from)));
+                    ab_snapshots.insert(combo_idx, ps.clone());
+                }
+                if let Some(mut opportunity) = checked {
+                    opportunities_seen += 1;
+                    stats.record_bitmap_quality(opportunity.bitmap_quality);
+                    heatmap::record_opportunity(
+                        clock::now(),
+                        opportunity.expected_profit_weth,
+                        opportunity.expected_profit_weth >= config.min_net_profit_weth,
+                    );
+                    if !warmup_gate.is_armed() && opportunity.shadow_only.is_none() {
+                        stats.warmup_suppressed += 1;
+                        opportunity.shadow_only = Some(opportunity_filter::FilterVerdict {
+                            filter: "warmup",
+                            reason: warmup_gate.status_label(),
+                        });
+                    }
+                    if opportunity.shadow_only.is_none() {
+                        if let Some(ref reason) = approval_block {
+                            opportunity.shadow_only = Some(opportunity_filter::FilterVerdict {
+                                filter: "approval_audit",
+                                reason: reason.clone(),
+                            });
+                        }
+                    }
+                    if opportunity.shadow_only.is_none() {
+                        if let Some(ref reason) = bytecode_block {
+                            opportunity.shadow_only = Some(opportunity_filter::FilterVerdict {
+                                filter: "bytecode_watchdog",
+                                reason: reason.clone(),
+                            });
+                        }
+                    }
+                    if opportunity.shadow_only.is_none() {
+                        if let Some(token) = pp
+                            .iter()
+                            .find_map(|p| token_probe::flagged_token(p, &blocked_tokens))
+                        {
+                            opportunity.shadow_only = Some(opportunity_filter::FilterVerdict {
+                                filter: "token_behavior",
+                                reason: format!("non-standard token {}", token),
+                            });
+                        }
+                    }
+                    if opportunity.shadow_only.is_none() {
+                        opportunity.shadow_only = strategy::constant_product_verdict(&pp);
+                    }
+                    if opportunity.shadow_only.is_none() {
+                        if let Some(reason) = divergence_guard::suppression(pp[0].address, pp[1].address) {
+                            opportunity.shadow_only = Some(opportunity_filter::FilterVerdict {
+                                filter: "structural_divergence",
+                                reason,
+                            });
+                        }
+                    }
+                    if opportunity.shadow_only.is_none() && paused {
+                        opportunity.shadow_only = Some(opportunity_filter::FilterVerdict {
+                            filter: "paused",
+                            reason: "execution paused from TUI".to_string(),
+                        });
+                    }
+                    if opportunity.shadow_only.is_none() && breaker_paused {
+                        opportunity.shadow_only = Some(opportunity_filter::FilterVerdict {
+                            filter: "circuit_breaker",
+                            reason: format!("circuit breaker paused — see {}", circuit_breaker::CIRCUIT_BREAKER_PATH),
+                        });
+                    }
+                    // Son kapı: kurma dosyası silinmiş / süresi dolmuşsa gölge
+                    if opportunity.shadow_only.is_none() {
+                        if let Some(reason) =
+                            arming.as_mut().and_then(|g| g.gate(clock::now()))
+                        {
+                            opportunity.shadow_only = Some(opportunity_filter::FilterVerdict {
+                                filter: "arming",
+                                reason,
+                            });
+                        }
+                    }
+                    if let Some(ref verdict) = opportunity.shadow_only {
+                        // Yürütme yok — sezgi doğrulaması için simüle et + gölge logla
+                        // (near-miss defteri; baskı altında atılır, filtre kararı değişmez)
+                        let would_succeed = ladder.allows(degradation::NEAR_MISS)
+                            && strategy::shadow_log_degraded(&pp, &ps, &opportunity, &sim_engine, config);
+                        if verdict.filter == "constant_product" {
+                            stats.constant_product_shadowed += 1;
+                        }
+                        if verdict.filter == "competition" {
+                            stats.competition_skips += 1;
+                            if would_succeed {
+                                stats.competition_skips_would_succeed += 1;
+                            }
+                        }
+                        if verdict.filter == "direction_gate" {
+                            // Kısıtlı yönün modellenen kenarı genişliyor mu?
+                            direction_gate::observe_shadow(
+                                &direction_gate::Direction::new(
+                                    &pp[opportunity.buy_pool_idx],
+                                    &pp[opportunity.sell_pool_idx],
+                                ),
+                                opportunity.expected_profit_weth,
+                            );
+                        }
+                        let row = dashboard::TradeRow {
+                            block: block_number,
+                            pair: combo.pair_name.clone(),
+                            amount_weth: opportunity.optimal_amount_weth,
+                            profit_weth: opportunity.expected_profit_weth,
+                            status: dashboard::RowStatus::Skipped(verdict.filter),
+                        };
+                        if let Some(feed) = status_feed.as_mut() {
+                            feed.record(&row);
+                        }
+                        if let Some(t) = tui {
+                            t.push_row(row);
+                        }
+                        continue;
+                    }
                     opportunities.push((combo_idx, opportunity, pp, ps));
                 }
             }
 
             // Faz 2: En yüksek kârlı fırsatı seç, sadece onu simulate+execute et
             let opp_count = opportunities.len();
-            if let Some((best_idx, best_opp, best_pp, best_ps)) =
-                opportunities.into_iter().max_by(|a, b| {
+            // Receipt'i onaylanmış 2. adım önceliklidir — yalnızca taze state'teki
+            // kontrol aynı yönde, yürütülebilir ve MIN_NET_PROFIT üstü fırsat verdiyse
+            let followup_idx = followup::take_ready(block_number).and_then(|pending| {
+                let idx = opportunities.iter().position(|(combo_idx, opp, pp, _)| {
+                    *combo_idx == pending.combo_idx
+                        && pp[opp.buy_pool_idx].address == pending.direction.key.buy_pool
+                        && opp.shadow_only.is_none()
+                        && opp.expected_profit_weth >= config.min_net_profit_weth
+                });
+                match idx {
+                    Some(i) => eprintln_high!(
+                        "     \u{1f501} [Followup] {} step 2 supported: {:.6} WETH → {:.8} WETH (planned {:.6} → {:.8})",
+                        pending.direction.label,
+                        opportunities[i].1.optimal_amount_weth,
+                        opportunities[i].1.expected_profit_weth,
+                        pending.plan.second_amount_weth,
+                        pending.plan.second_profit_weth,
+                    ),
+                    None => eprintln_high!(
+                        "     \u{21a9}\u{fe0f} [Followup] {} step 2 dropped: fresh state no longer supports it",
+                        pending.direction.label,
+                    ),
+                }
+                followup::record_outcome(idx.is_some());
+                idx
+            });
+            let best = match followup_idx {
+                Some(i) => Some(opportunities.swap_remove(i)),
+                None => opportunities.into_iter().max_by(|a, b| {
                     a.1.expected_profit_weth
                         .partial_cmp(&b.1.expected_profit_weth)
                         .unwrap_or(std::cmp::Ordering::Equal)
-                })
-            {
+                }),
+            };
+            if let Some((best_idx, mut best_opp, best_pp, best_ps)) = best {
                 if opp_count > 1 {
-                    eprintln!(
+                    eprintln_high!(
                         "     \u{1f3af} [BestPick] Selected {} (profit={:.8} WETH) from {} candidates",
                         pair_combos[best_idx].pair_name, best_opp.expected_profit_weth, opp_count,
                     );
                 }
+                quoter_audit::note_nr_size(best_opp.optimal_amount_weth);
+                ladder_checkpoint(&mut ladder, degradation::Checkpoint::Selected, block_start, block_number);
+
+                // Kâr hassasiyeti: her havuz 1/2 tick ters kaydırılır (base + 4 exact
+                // çağrı). Bütçe yetmezse atlanır — filtre ve bribe ölçümsüz davranır.
+                let remaining = Duration::from_millis(PIPELINE_BUDGET_MS as u64)
+                    .saturating_sub(block_start.elapsed());
+                if !ladder.allows(degradation::PROFIT_SENSITIVITY) {
+                    println_low!("     🪜 [Sensitivity] shed by degradation ladder");
+                } else if remaining >= strategy::SENSITIVITY_MIN_BUDGET {
+                    let (state_a, state_b) = (best_ps[0].load_full(), best_ps[1].load_full());
+                    best_opp.sensitivity = Some(strategy::evaluate_profit_sensitivity(
+                        &best_pp,
+                        &state_a,
+                        &state_b,
+                        best_opp.buy_pool_idx,
+                        best_opp.reference_price_quote,
+                        best_opp.optimal_amount_weth,
+                    ));
+                } else {
+                    eprintln_high!(
+                        "     \u{23f1}\u{fe0f} [Sensitivity] skipped: {}ms left in pipeline budget",
+                        remaining.as_millis(),
+                    );
+                }
+
+                // İki adımlı plan: optimum iki havuzda yürütülür, kontrol işlem
+                // sonrası state'te tekrarlanır (tam boyutlandırma turu)
+                if config.two_step_sizing && ladder.allows(degradation::TWO_STEP_PLAN) {
+                    let remaining = Duration::from_millis(PIPELINE_BUDGET_MS as u64)
+                        .saturating_sub(block_start.elapsed());
+                    if remaining >= strategy::TWO_STEP_MIN_BUDGET {
+                        best_opp.two_step = strategy::evaluate_two_step_plan(
+                            &best_pp,
+                            &best_ps,
+                            &best_opp,
+                            config,
+                            block_base_fee,
+                            last_simulated_gas,
+                            l1_data_fee_wei,
+                            &competition,
+                            &two_step_filters,
+                            Some(&sim_engine),
+                        );
+                    } else {
+                        eprintln_high!(
+                            "     \u{23f1}\u{fe0f} [TwoStep] skipped: {}ms left in pipeline budget",
+                            remaining.as_millis(),
+                        );
+                    }
+                }
 
                 // ── 4. DEĞERLENDİR + SİMÜLE + YÜRÜT ────────────────
-                if let Some(gas) = evaluate_and_execute(
+                let outcome = evaluate_and_execute(
                     &provider,
                     config,
                     &best_pp,
                     &best_ps,
                     &best_opp,
                     &sim_engine,
-                    &mut stats,
+                    stats,
                     &nonce_manager,
                     block_timestamp,
                     block_base_fee,
@@ -1944,11 +4736,48 @@ async fn run_bot(
                     &mev_executor,
                     telegram_sender,
                     &mut tg_counters,
+                    &filter_chain,
+                    &mut live_opportunities,
                 )
-                .await
-                {
+                .await;
+                let row = dashboard::TradeRow {
+                    block: block_number,
+                    pair: pair_combos[best_idx].pair_name.clone(),
+                    amount_weth: best_opp.optimal_amount_weth,
+                    profit_weth: best_opp.expected_profit_weth,
+                    status: match outcome {
+                        Some(_) if config.execution_enabled() && contract_state::is_paused() => {
+                            dashboard::RowStatus::Skipped("contract_paused")
+                        }
+                        Some(_) if config.execution_enabled() => dashboard::RowStatus::Executed,
+                        Some(_) => dashboard::RowStatus::Shadow,
+                        None => dashboard::RowStatus::Failed,
+                    },
+                };
+                if let Some(feed) = status_feed.as_mut() {
+                    feed.record(&row);
+                }
+                if let Some(t) = tui {
+                    t.push_row(row);
+                }
+                if let Some(gas) = outcome {
                     last_simulated_gas = Some(gas);
                     pair_failures.remove(&best_idx);
+                    // 2. adım kuyruğa: receipt onaylanınca taze state'le yeniden denenir
+                    let executable = config.execution_enabled()
+                        && config.execute_followup
+                        && best_opp.shadow_only.is_none();
+                    if let (true, None, Some(plan)) = (executable, followup_idx, best_opp.two_step.clone()) {
+                        followup::arm(
+                            best_idx,
+                            direction_gate::Direction::new(
+                                &best_pp[best_opp.buy_pool_idx],
+                                &best_pp[best_opp.sell_pool_idx],
+                            ),
+                            plan,
+                            block_number,
+                        );
+                    }
                 } else {
                     let failures = pair_failures.entry(best_idx).or_insert(0);
                     *failures += 1;
@@ -1956,14 +4785,32 @@ async fn run_bot(
                     if *failures >= config.circuit_breaker_threshold {
                         let cooldown_until = block_number + 100;
                         pair_cooldown.insert(best_idx, cooldown_until);
-                        eprintln!(
-                            "\n  \u{1f6d1} CIRCUIT BREAKER: {} {} consecutive failures — blacklisted until block #{} (~{}s)",
-                            pair_combos[best_idx].pair_name,
-                            failures,
-                            cooldown_until,
-                            100 * 2,
-                        );
+                        let emitted = log_event::json(|| {
+                            log_event::LogEvent::CircuitBreaker(log_event::CircuitBreaker {
+                                state: "pair_blacklisted",
+                                block: block_number,
+                                pair: Some(pair_combos[best_idx].pair_name.clone()),
+                                consecutive_failures: Some(*failures),
+                                reason: Some(format!("blacklisted until block #{}", cooldown_until)),
+                            })
+                        });
+                        if !emitted {
+                            eprintln_high!(
+                                "\n  \u{1f6d1} CIRCUIT BREAKER: {} {} consecutive failures — blacklisted until block #{} (~{}s)",
+                                pair_combos[best_idx].pair_name,
+                                failures,
+                                cooldown_until,
+                                100 * 2,
+                            );
+                        }
                         stats.consecutive_failures = 0;
+                        stats.breaker_trips += 1;
+                        json_logger::log_json("warn", "circuit_breaker_tripped", serde_json::json!({
+                            "pair": &pair_combos[best_idx].pair_name,
+                            "failures": *failures,
+                            "block": block_number,
+                            "cooldown_until": cooldown_until,
+                        }));
                         // v32.0: Telegram — circuit breaker bildirimi
                         if let Some(ref tg) = telegram_sender {
                             tg.send(telegram::TelegramMessage::CircuitBreakerTripped {
@@ -1975,9 +4822,58 @@ async fn run_bot(
                     }
                 }
             }
+
+            // ── A/B: varyant B aynı state'lerle (ek RPC yok, yürütme yok) ──
+            ladder_checkpoint(&mut ladder, degradation::Checkpoint::Secondary, block_start, block_number);
+            if let Some(ref ab) = ab_shadow {
+                let remaining = Duration::from_millis(PIPELINE_BUDGET_MS as u64)
+                    .saturating_sub(block_start.elapsed());
+                if remaining < ab_shadow::AB_TIME_BOX || !ladder.allows(degradation::AB_SHADOW) {
+                    stats.ab_shadow.blocks_skipped += 1;
+                } else if let Some(comparisons) = ab.compare_block(
+                    &mut stats.ab_shadow,
+                    &ab_primary,
+                    Instant::now() + ab_shadow::AB_TIME_BOX,
+                    |config_b, filters_b, combo_idx| {
+                        let combo = &pair_combos[combo_idx];
+                        let pp = [pools[combo.pool_a_idx].clone(), pools[combo.pool_b_idx].clone()];
+                        let ps = &ab_snapshots[&combo_idx];
+                        check_arbitrage_opportunity(
+                            &pp,
+                            ps,
+                            config_b,
+                            block_base_fee,
+                            last_simulated_gas,
+                            l1_data_fee_wei,
+                            &competition,
+                            filters_b,
+                            Some(&sim_engine),
+                        )
+                        .as_ref()
+                        .map(ab_shadow::Decision::from)
+                    },
+                ) {
+                    let lines = ab_shadow::log_lines(block_number, &comparisons, |i| {
+                        pair_combos[i].pair_name.clone()
+                    });
+                    // Disk yazımı blocking havuzunda; sıra için beklenir
+                    let path = secure_fs::data_path(ab_shadow::AB_SHADOW_LOG_PATH);
+                    let written = tokio::task::spawn_blocking(move || ab_shadow::append_lines(&path, &lines))
+                        .await
+                        .unwrap_or_else(|e| Err(std::io::Error::other(e)));
+                    if let Err(e) = written {
+                        eprintln_high!(
+                            "  {} {} write error: {}",
+                            "⚠️".yellow(),
+                            ab_shadow::AB_SHADOW_LOG_PATH,
+                            e
+                        );
+                    }
+                }
+            }
         } else if pipeline_elapsed_ms > PIPELINE_BUDGET_MS {
             // v28.0: Pipeline bütçesi aşıldı — bu bloğu atla
-            eprintln!(
+            eprintln_high!(
                 "  \u{26a0}\u{fe0f} [Pipeline] Block #{} processing time {}ms > budget {}ms — opportunity scan skipped (MEV protection)",
                 block_number, pipeline_elapsed_ms, PIPELINE_BUDGET_MS,
             );
@@ -1986,16 +4882,23 @@ async fn run_bot(
         // ── 4. MULTI-HOP ROTA TARAMASI (v25.0: Simülasyon + Yürütme) ─────
         //    LiquidityGraph'ı mevcut havuz verileriyle oluştur,
         //    3+ hop rotalarını tara ve kârlı olanları yürüt.
-        if all_synced && block_number % 3 == 0 {
-            let graph = route_engine::LiquidityGraph::build(pools, &states, config.weth_address);
-            let routes = graph.find_routes(4, 200);
+        ladder_checkpoint(&mut ladder, degradation::Checkpoint::Secondary, block_start, block_number);
+        if all_synced && !quiet_block && block_number % 3 == 0 && ladder.allows(degradation::MULTI_HOP_SCAN) {
+            // Graf, tarama, exact doğrulama ve yürütme aynı donmuş state'leri okur
+            let frozen = pool_snapshot::capture_all(&states);
+            let (graph, routes) = {
+                let _section = runtime_health::section("route_graph");
+                let graph = route_engine::LiquidityGraph::build(pools, &frozen, config.weth_address);
+                let routes = graph.find_routes(4, 200);
+                (graph, routes)
+            };
 
             if !routes.is_empty() {
                 let two_hop_count = graph.two_hop_routes(&routes).len();
                 let multi_hop_routes = graph.multi_hop_routes(&routes);
                 let multi_hop_count = multi_hop_routes.len();
 
-                eprintln!(
+                eprintln_high!(
                     "     {} [Graph] {} nodes, {} edges | {} routes (2-hop: {}, 3+hop: {})",
                     "🔀".cyan(),
                     graph.node_count(),
@@ -2008,7 +4911,7 @@ async fn run_bot(
                 let multi_hop_opps = strategy::check_multi_hop_opportunities(
                     &routes,
                     pools,
-                    &states,
+                    &frozen,
                     config,
                     block_base_fee,
                     l1_data_fee_wei,
@@ -2020,7 +4923,7 @@ async fn run_bot(
                     let pool_states_ex: Vec<crate::types::PoolState> = best
                         .pool_indices
                         .iter()
-                        .map(|&i| states[i].load_full().as_ref().clone())
+                        .map(|&i| frozen[i].load_full().as_ref().clone())
                         .collect();
                     let pool_configs_ex: Vec<&crate::types::PoolConfig> =
                         best.pool_indices.iter().map(|&i| &pools[i]).collect();
@@ -2044,7 +4947,7 @@ async fn run_bot(
                         .iter()
                         .map(|&d| if d { 0u8 } else { 1u8 })
                         .collect();
-                    let calldata = crate::simulator::encode_multi_hop_calldata(
+                    let calldata = crate::calldata_codec::encode_multi_hop_calldata(
                         &pool_addrs,
                         &dirs_u8,
                         amount_wei,
@@ -2052,7 +4955,7 @@ async fn run_bot(
                         0,
                     );
 
-                    eprintln!(
+                    eprintln_high!(
                         "     {} [Multi-Hop] #{} {} | {:.4} WETH → {:.6} WETH profit | {} | exact={} wei | {}B calldata | {}-hop NR({}/{})",
                         "🔀".cyan(),
                         best.route_idx,
@@ -2068,14 +4971,31 @@ async fn run_bot(
                     );
 
                     // v25.0: Multi-hop fırsatı değerlendir ve yürüt
-                    if let Some(gas) = strategy::evaluate_and_execute_multi_hop(
+                    // (ısınma sürerken yalnızca yukarıdaki log kalır)
+                    let route_blocked = best
+                        .pool_indices
+                        .iter()
+                        .any(|&i| token_probe::flagged_token(&pools[i], &blocked_tokens).is_some());
+                    if !warmup_gate.is_armed() {
+                        stats.warmup_suppressed += 1;
+                    } else if route_blocked
+                        || approval_block.is_some()
+                        || bytecode_block.is_some()
+                        || paused
+                        || breaker_paused
+                        || arming.as_mut().is_some_and(|g| g.gate(clock::now()).is_some())
+                    {
+                        // Standart dışı token / eksik onay / kontrat el sıkışması /
+                        // TUI / circuit breaker duraklatması / kurulu değil
+                        // — yalnızca yukarıdaki log
+                    } else if let Some(gas) = strategy::evaluate_and_execute_multi_hop(
                         &provider,
                         config,
                         pools,
-                        &states,
+                        &frozen,
                         best,
                         &sim_engine,
-                        &mut stats,
+                        stats,
                         &nonce_manager,
                         block_timestamp,
                         block_base_fee,
@@ -2084,6 +5004,7 @@ async fn run_bot(
                         &mev_executor,
                         telegram_sender,
                         &mut tg_counters,
+                        &mut live_opportunities,
                     )
                     .await
                     {
@@ -2093,17 +5014,112 @@ async fn run_bot(
             }
         }
 
+        // Ladder: bloğun ulaştığı basamak stats'a ve replay satırına
+        let shed_rungs = ladder.end_block();
+        stats.record_shed_level(shed_rungs);
+        if let (Some(recorder), Some(mut row)) = (state_diff_recorder.as_mut(), state_diff_row) {
+            row.shed_rungs = shed_rungs;
+            if let Err(e) = recorder.append(&row) {
+                eprintln_high!("  {} {} write error: {}", "⚠️".yellow(), recorder.path().display(), e);
+            }
+        }
+
+        // Hızlı yol ölçümü: sessiz ve tam yoldan işlenen bloklar ayrı histogramda
+        let block_us = block_start.elapsed().as_micros() as u64;
+        if quiet_block {
+            state_sync::QUIET_BLOCK_LATENCY.record(block_us);
+        } else {
+            state_sync::FULL_BLOCK_LATENCY.record(block_us);
+        }
+        // systemd watchdog: yalnızca uçtan uca, beklenen sürede işlenen blok besler
+        systemd::block_processed(block_start.elapsed());
+        // Blok işlenirken executor stall'ı olduysa sync_ms sıçraması RPC değildir
+        // Sessiz dönem: günün gölge fırsatlarından biri arka planda yeniden değerlendirilir
+        revisit::on_block(opportunities_seen > 0, pools, &states, config);
+        // Vadesi gelen gölge kayıtları arka plan doğrulamasına devredilir
+        shadow_verify::on_block(block_number);
+
+        if let Some(stall) = runtime_health::stall_overlapping(block_start, Instant::now()) {
+            stats.stalled_blocks += 1;
+            eprintln_high!(
+                "  ⏱️ [Runtime] Block #{} overlapped a {:.1}ms executor stall ({}) — sync {}ms",
+                block_number,
+                stall.duration().as_secs_f64() * 1000.0,
+                stall.culprit.unwrap_or("unattributed"),
+                sync_ms,
+            );
+        }
+
         // ── 5. PERİYODİK İSTATİSTİK ────────────────────────
         if stats
             .total_blocks_processed
             .is_multiple_of(config.stats_interval)
             && stats.total_blocks_processed > 0
         {
-            print_stats_summary(&stats, &states, pools, pair_combos);
+            print_stats_summary(
+                stats,
+                &states,
+                pools,
+                pair_combos,
+                &pool_analytics,
+                &filter_chain,
+                &mev_executor,
+                head_dedup.stats(),
+                warmup_gate,
+                &approvals,
+                &providers,
+                arming.as_ref(),
+            );
             // Keşif motoru istatistikleri
             discovery_engine::print_discovery_stats(&discovery_registry, pools);
         }
 
+        // TUI: blok sonu görünümü + bekleyen anlık özet isteği (`s`)
+        if let Some(t) = tui {
+            t.model.lock().on_block(
+                dashboard::BlockView {
+                    block_number,
+                    pools,
+                    states: &states,
+                    stats,
+                    latency: session.latency(),
+                    warmup_label: (!warmup_gate.is_armed()).then(|| warmup_gate.status_label()),
+                    blacklisted_pairs: pair_cooldown
+                        .values()
+                        .filter(|&&until| block_number < until)
+                        .count(),
+                    pipeline_ms: pipeline_elapsed_ms,
+                    pipeline_budget_ms: PIPELINE_BUDGET_MS,
+                    transport: connections.transport_info(),
+                    healthy_nodes: connections.healthy_count(),
+                },
+                Instant::now(),
+            );
+            if t.controls.take_summary_request() {
+                let summary = session.build(
+                    session_summary::SummaryReason::OnDemand,
+                    clock::now(),
+                    session_summary::ConfigFingerprint::new(config, pools),
+                    stats,
+                    warmup_gate,
+                );
+                session.spawn_write(summary);
+            }
+        }
+
+        // UTC gün dönümü: o anki sayaçların kopyasıyla arka planda özet yaz
+        let now_utc = clock::now();
+        if session.rollover_due(now_utc) {
+            let summary = session.build(
+                session_summary::SummaryReason::DailyRollover,
+                now_utc,
+                session_summary::ConfigFingerprint::new(config, pools),
+                stats,
+                warmup_gate,
+            );
+            session.spawn_write(summary);
+        }
+
         // ── v32.0: TELEGRAM VARDIYA RAPORU WATCHDOG ────────────
         // Telemetri sayaclarini guncelle
         tg_counters.scanned_opportunities = stats.total_opportunities;
@@ -2132,7 +5148,7 @@ async fn run_bot(
                     wallet_balance_eth: 0.0, // Bakiye RPC sorgusu gerektirir, sonraki versiyonda
                     uptime: stats.uptime_str(),
                 });
-                eprintln!(
+                eprintln_high!(
                     "  📡 [Telegram] Shift report sent (period: {}s)",
                     period_secs,
                 );
@@ -2141,16 +5157,59 @@ async fn run_bot(
             tg_counters.reset();
         }
 
+        // Durum uç noktası: blok sonu görüntüsü (son sayaç yazımından sonra — sunucu yalnızca Arc okur)
+        if let Some(feed) = status_feed.as_ref() {
+            feed.publish(status_server::StatusView {
+                block_number,
+                pools,
+                states: &states,
+                stats,
+                execution: status_server::ExecutionStatus {
+                    execution_enabled: config.execution_enabled(),
+                    inflight: mev_executor.inflight.active(),
+                    max_inflight: config.max_inflight_executions,
+                    peak_inflight: stats.peak_inflight_executions,
+                },
+                gates: status_server::GateStatus {
+                    warmup_armed: warmup_gate.is_armed(),
+                    warmup_label: (!warmup_gate.is_armed()).then(|| warmup_gate.status_label()),
+                    blacklisted_pairs: pair_cooldown
+                        .iter()
+                        .filter(|&(_, &until)| block_number < until)
+                        .filter_map(|(&idx, _)| pair_combos.get(idx).map(|c| c.pair_name.clone()))
+                        .collect(),
+                    pipeline_ms: pipeline_elapsed_ms,
+                    pipeline_budget_ms: PIPELINE_BUDGET_MS,
+                    paused,
+                    approval_block: approval_block.clone(),
+                    bytecode_block: bytecode_block.clone(),
+                    contract_paused: contract_state::paused_status(),
+                },
+                pair_schedule: pair_scheduler::status(),
+            });
+        }
+
         // ── 6. PERİYODİK NONCE SENKRONİZASYONU (v10.0) ──────
         // Her 50 blokta bir zincirdeki gerçek nonce ile lokal nonce'u karşılaştır.
         // Uyumsuzluk varsa zincir değeri ile düzelt (TX kayıpları veya dış müdahale).
+        // Hedef pending sayımdır; canlı uçuştaki bir TX'in nonce'unun altına inilmez.
         if stats.total_blocks_processed.is_multiple_of(50) && stats.total_blocks_processed > 0 {
             if let Some(addr) = executor_address {
-                match provider.get_transaction_count(addr).await {
-                    Ok(onchain_nonce) => {
+                let latest = provider.get_transaction_count(addr).latest().await;
+                let pending = provider.get_transaction_count(addr).pending().await;
+                match latest.and_then(|latest| pending.map(|pending| (latest, pending))) {
+                    Ok((latest, pending)) => {
+                        nonce_checkpoint::prune(latest, block_number);
+                        // Zincirin tükettiği düşmüş nonce'lar artık yeniden verilmez
+                        let stale = nonce_manager.reconcile(pending);
+                        if stale > 0 {
+                            println_low!("  🔄 Nonce sync: {} stale released nonce(s) cleared", stale);
+                        }
+                        let onchain_nonce =
+                            nonce_checkpoint::sync_target(pending, nonce_checkpoint::live_floor());
                         let local_nonce = nonce_manager.current();
                         if local_nonce != onchain_nonce {
-                            println!(
+                            println_high!(
                                 "  {} Nonce mismatch detected: local={} chain={} → correcting",
                                 "🔄".yellow(),
                                 local_nonce,
@@ -2167,11 +5226,25 @@ async fn run_bot(
                         }
                     }
                     Err(e) => {
-                        println!("  {} Nonce sync failed: {}", "⚠️".yellow(), e);
+                        println_high!("  {} Nonce sync failed: {}", "⚠️".yellow(), e);
                     }
                 }
             }
         }
+
+        // ── 7. NONCE CHECKPOINT ──────
+        // Sıradaki nonce + uçuştaki TX kaydı nonce_state.json'a — yeniden
+        // başlatmada zincirle uzlaştırılır. Disk yazımı blok döngüsünü bekletmez.
+        if executor_address.is_some()
+            && block_number.is_multiple_of(nonce_checkpoint::CHECKPOINT_INTERVAL_BLOCKS)
+        {
+            tokio::task::spawn_blocking(nonce_checkpoint::persist);
+        }
+
+        // ── 8. ISI HARİTASI ──────
+        if block_number.is_multiple_of(heatmap::PERSIST_INTERVAL_BLOCKS) {
+            tokio::task::spawn_blocking(heatmap::persist);
+        }
     } // heartbeat loop sonu — loop sadece return Err() ile çıkar
 }
 
@@ -2190,66 +5263,140 @@ async fn run_bot(
 // Pending TX bulunamasa bile mevcut blok bazlı akış aynen devam eder.
 // ─────────────────────────────────────────────────────────────────────────────
 
+// ─────────────────────────────────────────────────────────────────────────────
+// HAVUZ EVENT DİNLEYİCİ DENETÇİSİ
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Havuz event dinleyicisini paylaşımlı primary sokette başlat. Akış koparsa
+/// ya da sağlıklı bağlantı yoksa 1s'den 30s'ye katlanan bekleme ile yöneticiden
+/// yeniden kiralar (yönetici soketi yeniden kurmuşsa yenisi alınır);
+/// `cancel` (hot-reload / run_bot çıkışı) döngüyü sonlandırır.
+fn spawn_pool_event_listener(
+    connections: Arc<transport::RpcConnections>,
+    pools: Vec<PoolConfig>,
+    states: Vec<SharedPoolState>,
+    oracle: Arc<state_sync::EventFreshnessOracle>,
+    cancel: CancellationToken,
+) {
+    tokio::spawn(async move {
+        let mut backoff = Duration::from_secs(1);
+        while !cancel.is_cancelled() {
+            let connected_at = Instant::now();
+            match connections.subscribe(transport::ConnectionRole::Primary) {
+                Ok(lease) => {
+                    if let Err(e) = state_sync::start_pool_event_listener(
+                        lease.provider(),
+                        &pools,
+                        &states,
+                        &oracle,
+                        cancel.clone(),
+                    )
+                    .await
+                    {
+                        eprintln_high!("  [EventListener] error (block-based flow continues): {}", e);
+                        lease.report_failure();
+                    }
+                }
+                Err(e) => eprintln_high!("  [EventListener] {}", e),
+            }
+            // Uzun süre ayakta kalan akıştan sonra bekleme sıfırlanır
+            if connected_at.elapsed() > Duration::from_secs(60) {
+                backoff = Duration::from_secs(1);
+            }
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = tokio::time::sleep(backoff) => {}
+            }
+            eprintln_high!("  [EventListener] reconnecting (backoff {}s)", backoff.as_secs());
+            backoff = (backoff * 2).min(Duration::from_secs(30));
+        }
+    });
+}
+
+/// Abonelik paylaşımlı primary sokette (`lease`); havuz okumaları `providers`
+/// üzerinden (havuza özel endpoint ya da primary).
 async fn pending_tx_listener(
-    rpc_url: &str,
+    lease: &transport::SubscriptionLease<RootProvider>,
+    providers: &transport::ProviderRegistry<RootProvider>,
     pools: &[PoolConfig],
     states: &[SharedPoolState],
     pool_addresses: &[Address],
+    log_updates: bool,
 ) -> Result<()> {
-    use alloy::providers::WsConnect;
-
-    let ws = WsConnect::new(rpc_url);
-    let provider = ProviderBuilder::default()
-        .connect_ws(ws)
-        .await
-        .map_err(|e| eyre::eyre!("Pending TX provider connection error: {}", e))?;
-
-    println!(
+    println_high!(
         "  {} Pending TX listener started (optimistic mode)",
         "🔮".cyan()
     );
 
     // Pending TX stream — full TX nesneleri ile
-    let sub: alloy::pubsub::Subscription<alloy::rpc::types::Transaction> = provider
+    let sub: alloy::pubsub::Subscription<alloy::rpc::types::Transaction> = lease
         .subscribe_full_pending_transactions()
         .await
-        .map_err(|e| eyre::eyre!("Pending TX subscription error: {}", e))?;
+        .map_err(|e| {
+            lease.report_failure();
+            eyre::eyre!("Pending TX subscription error: {}", e)
+        })?;
     let mut stream = sub.into_stream();
 
     while let Some(tx) = stream.next().await {
-        // TX'in hedef adresi izlenen havuzlardan biri mi?
+        // TX izlenen havuzlardan birine (veya router üzerinden birkaçına) dokunuyor mu?
         use alloy::consensus::Transaction as TxTrait;
+        use alloy::network::TransactionResponse;
         let tx_kind = TxTrait::kind(&*tx.inner);
         let tx_to = tx_kind.to().copied();
         let tx_input = TxTrait::input(&*tx.inner);
 
-        if let Some(pool_idx) =
-            state_sync::check_pending_tx_relevance(tx_to, tx_input, pool_addresses)
-        {
-            // Etkilenen havuzun durumunu anlık oku (optimistic refresh)
-            let current_block = states[0].load().last_block;
-            match state_sync::optimistic_refresh_pool(
-                &provider,
+        let affected = state_sync::check_pending_tx_relevance(tx_to, tx_input, pool_addresses);
+        if affected.is_empty() {
+            continue;
+        }
+
+        let trigger = OptimisticTrigger {
+            tx_hash: tx.tx_hash(),
+            sender: tx.from(),
+            observed_at: Instant::now(),
+        };
+
+        // Havuza doğrudan swap(): calldata yerel olarak uygulanır — slot0
+        // henüz swap öncesini gösterir, yeniden okumak bir şey kazandırmaz
+        let direct_swap = tx_to
+            .and_then(|to| pool_addresses.iter().position(|&addr| addr == to))
+            .zip(state_sync::decode_pending_swap(tx_input));
+        if let Some((pool_idx, swap)) = direct_swap {
+            if let Some(update) =
+                state_sync::apply_pending_swap(&pools[pool_idx], &states[pool_idx], swap, trigger)
+            {
+                report_optimistic_update(&update, "predicted", log_updates);
+                continue;
+            }
+        }
+
+        // Havuz 0'ın değil, tüm havuzlar arasındaki en güncel blok
+        let current_block = state_sync::latest_known_block(states);
+        // Aynı bloktaki sonraki pending TX'ler havuzu bellekten okur
+        let pinned = rpc_census::pinned_block().map(|(_, hash)| hash);
+
+        // Etkilenen her havuzu bir kez, paralel oku (optimistic refresh)
+        let refreshes = affected.iter().map(|&pool_idx| {
+            state_sync::optimistic_refresh_pool(
+                providers,
                 &pools[pool_idx],
                 &states[pool_idx],
                 current_block,
+                pinned,
+                trigger,
             )
-            .await
-            {
-                Ok(true) => {
-                    // Fiyat değişti — havuz güncellendi
-                    let state = states[pool_idx].load();
-                    println!(
-                        "     {} [Pending TX] {} optimistic update: {:.6} Q",
-                        "🔮".magenta(),
-                        pools[pool_idx].name,
-                        state.eth_price_usd,
-                    );
-                }
-                Ok(false) => {} // Fiyat değişmedi, sessiz geç
+        });
+        let results = join_all(refreshes).await;
+
+        for (&pool_idx, result) in affected.iter().zip(results) {
+            match result {
+                // Fiyat değişti — havuz güncellendi
+                Ok(Some(update)) => report_optimistic_update(&update, "optimistic update", log_updates),
+                Ok(None) => {} // Fiyat değişmedi, sessiz geç
                 Err(e) => {
                     // Hata — sessiz devam et, blok bazlı akış zaten çalışıyor
-                    eprintln!(
+                    eprintln_high!(
                         "     ⚠️ [Pending TX] {} refresh error: {}",
                         pools[pool_idx].name, e
                     );
@@ -2261,6 +5408,29 @@ async fn pending_tx_listener(
     Ok(())
 }
 
+/// İyimser güncellemeyi bas + (OPTIMISTIC_UPDATE_LOG) JSONL'e ve JSON loga yaz
+fn report_optimistic_update(update: &state_sync::OptimisticUpdate, kind: &str, log_updates: bool) {
+    println_high!(
+        "     {} [Pending TX] {} {}: {:.6} → {:.6} Q (tx {})",
+        "🔮".magenta(),
+        update.pool,
+        kind,
+        update.old_price,
+        update.new_price,
+        update.trigger.tx_hash,
+    );
+    if log_updates {
+        let path = secure_fs::data_path(state_sync::OPTIMISTIC_UPDATES_PATH);
+        if let Err(e) = state_sync::append_optimistic_update(&path, update) {
+            eprintln_high!(
+                "     ⚠️ [Pending TX] {} write error: {}",
+                state_sync::OPTIMISTIC_UPDATES_PATH, e
+            );
+        }
+    }
+    json_logger::log_json("info", "optimistic_update", update.to_json());
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // v25.0: On-Chain Pool Whitelist Güncelleme
 // ─────────────────────────────────────────────────────────────────────────────
@@ -2272,27 +5442,21 @@ async fn pending_tx_listener(
 
 async fn whitelist_pools_on_chain(
     mev_executor: Arc<executor::MevExecutor>,
-    private_key: String,
+    connections: Arc<transport::RpcConnections>,
     contract_address: Address,
     calldata: Vec<u8>,
     nonce: u64,
     nonce_manager: Arc<NonceManager>,
     block_base_fee: u64,
 ) -> Result<()> {
-    use alloy::network::EthereumWallet;
-    use alloy::signers::local::PrivateKeySigner;
-
-    let signer: PrivateKeySigner = private_key
-        .parse()
-        .map_err(|e| eyre::eyre!("Whitelist TX: key parse error: {}", e))?;
-    let wallet = EthereumWallet::from(signer.clone());
-
-    // Basit TX gönderimi — whitelist işlemi düşük öncelikli
-    let ws = alloy::providers::WsConnect::new(mev_executor.standard_rpc_url());
-    let provider = ProviderBuilder::new()
-        .wallet(wallet)
-        .connect_ws(ws)
-        .await
+    let signing = mev_executor
+        .signing()
+        .ok_or_else(|| eyre::eyre!("Whitelist TX: no signer loaded"))?;
+
+    // Basit TX gönderimi — whitelist işlemi düşük öncelikli. Submit endpoint'i
+    // (yoksa primary) paylaşılır; TX tamamen imzalı olduğundan filler gerekmez.
+    let provider = connections
+        .acquire(transport::ConnectionRole::Submit)
         .map_err(|e| eyre::eyre!("Whitelist TX: provider error: {}", e))?;
 
     let max_fee = block_base_fee as u128 + 1_000_000_000; // base_fee + 1 Gwei tip
@@ -2304,15 +5468,27 @@ async fn whitelist_pools_on_chain(
         .max_fee_per_gas(max_fee)
         .max_priority_fee_per_gas(1_000_000_000); // 1 Gwei — düşük öncelik yeterli
 
-    match provider.send_transaction(tx).await {
+    let (_, raw_tx) = match signing.sign(tx).await {
+        Ok(signed) => signed,
+        Err(e) => {
+            nonce_manager.release(nonce);
+            return Err(e.into());
+        }
+    };
+
+    match provider.send_raw_transaction(&raw_tx).await {
         Ok(pending) => {
-            eprintln!("  📤 [Whitelist] TX sent: {:?}", pending.tx_hash());
+            eprintln_high!(
+                "  📤 [Whitelist] TX sent via {}: {:?}",
+                provider.role().label(),
+                pending.tx_hash()
+            );
             // Fire-and-forget — receipt beklenmez
             Ok(())
         }
         Err(e) => {
-            // Nonce'u geri al (TX gönderilemedi)
-            nonce_manager.force_set(nonce);
+            // Nonce'u geri ver (TX gönderilemedi)
+            nonce_manager.release(nonce);
             Err(eyre::eyre!("Whitelist TX failed to send: {}", e))
         }
     }
@@ -22,7 +22,8 @@
 //  ✓ Newton-Raphson'a likidite-tabanlı üst sınır ve tick-impact freni
 // ============================================================================
 
-use crate::types::{PoolState, TickBitmapData};
+use crate::types::{PoolState, SizingNumerics, TickBitmapData};
+use serde::{Deserialize, Serialize};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // O(1) PreFilter — NR'den Önce Hızlı Kârlılık Eleme
@@ -141,6 +142,47 @@ impl PreFilter {
             spread_ratio,
         }
     }
+
+    /// `check`'in deterministik karşılığı (SIZING_NUMERICS=fixed): fiyatlar
+    /// ve ara değerler Q63.64 tam sayı; parametreler kesmeyle (bit-kesin)
+    /// çevrilir, yalnızca sonuç f64'e döner — eşik kararı her platformda aynı.
+    pub fn check_fixed(
+        &self,
+        price_a: fixed::Fx,
+        price_b: fixed::Fx,
+        trade_amount_weth: fixed::Fx,
+    ) -> PreFilterResult {
+        use fixed::Fx;
+
+        if price_a <= Fx::ZERO || price_b <= Fx::ZERO || trade_amount_weth <= Fx::ZERO {
+            return PreFilterResult::Unprofitable {
+                reason: PreFilterRejectReason::InvalidPriceData,
+            };
+        }
+
+        let spread_ratio = price_a.abs_diff(price_b) / price_a.min(price_b);
+        let total_fee_ratio = Fx::from_f64(self.fee_a) + Fx::from_f64(self.fee_b);
+        if spread_ratio <= total_fee_ratio {
+            return PreFilterResult::Unprofitable {
+                reason: PreFilterRejectReason::SpreadBelowFees,
+            };
+        }
+
+        let gross_profit = (spread_ratio - total_fee_ratio) * trade_amount_weth;
+        let net_after_bribe = gross_profit * (Fx::ONE - Fx::from_f64(self.bribe_pct));
+        let estimated_profit = net_after_bribe - Fx::from_f64(self.estimated_gas_cost_weth);
+
+        if estimated_profit < Fx::from_f64(self.min_profit_weth) {
+            return PreFilterResult::Unprofitable {
+                reason: PreFilterRejectReason::ProfitBelowThreshold,
+            };
+        }
+
+        PreFilterResult::Profitable {
+            estimated_profit_weth: estimated_profit.to_f64(),
+            spread_ratio: spread_ratio.to_f64(),
+        }
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -224,67 +266,297 @@ pub fn compute_eth_price(
     price_from_sqrt
 }
 
+/// V2 rezervlerinden ETH fiyatı — ham oran reserve1/reserve0, token
+/// ondalık farkıyla düzeltilir (tick çapraz kontrolü yok: tick türetilmiştir)
+pub fn compute_eth_price_from_reserves(
+    reserve0: alloy::primitives::U256,
+    reserve1: alloy::primitives::U256,
+    token0_decimals: u8,
+    token1_decimals: u8,
+    token0_is_weth: bool,
+) -> f64 {
+    if reserve0.is_zero() {
+        return 0.0;
+    }
+    let price_ratio = exact::u256_to_f64(reserve1) / exact::u256_to_f64(reserve0);
+    raw_price_to_eth_price(price_ratio, token0_decimals, token1_decimals, token0_is_weth)
+}
+
 
-/// OPT-1: Pre-sorted tick'lerle arbitraj kâr hesaplaması.
-/// `compute_arbitrage_profit_with_bitmap` ile aynı mantık, ama tick sıralama sıfır maliyetli.
+// ─────────────────────────────────────────────────────────────────────────────
+// Kâr Ayrıştırması — Spread Yakalama ↔ Ücret / Gas Sürtünmesi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Beklenen kârın bileşenleri (WETH).
+///
+/// `gross_weth` ücretsiz havuzlarda yakalanacak spread'dir; diğer kalemler
+/// ondan düşülür: net = gross − fee_leg1 − fee_leg2 − flash_fee − gas − bribe.
+/// Bacak ücretleri kârın hesaplandığı swap yolundan okunur (ikinci simülasyon yok).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ProfitBreakdown {
+    pub gross_weth: f64,
+    /// 1. bacak (flash kaynağı) LP ücreti
+    pub fee_leg1_weth: f64,
+    /// 2. bacak LP ücreti — bacağın gerçekleşen kuruyla owedToken'a çevrilir
+    pub fee_leg2_weth: f64,
+    pub flash_fee_weth: f64,
+    pub gas_weth: f64,
+    pub bribe_weth: f64,
+    pub net_weth: f64,
+}
+
+/// Ayrıştırmaya giren swap dışı maliyetler (WETH) — kâr hesabının düştüğü değerler
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AttributionCosts {
+    pub flash_fee_weth: f64,
+    pub gas_weth: f64,
+}
+
+/// İki bacağın geçtiği başlatılmış tick sayısı. Her geçiş ek soğuk
+/// SLOAD/SSTORE seti demektir — gas boyutla birlikte büyür
+/// (bkz. gas_calibration geçiş modeli).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickCrossings {
+    /// Satış bacağı (WETH girdisi)
+    pub sell: u32,
+    /// Alış bacağı (ara token girdisi)
+    pub buy: u32,
+}
+
+impl TickCrossings {
+    fn of(sell: &exact::ExactSwapResult, buy: &exact::ExactSwapResult) -> Self {
+        Self { sell: sell.tick_crossings, buy: buy.tick_crossings }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.sell + self.buy
+    }
+}
+
+impl ProfitBreakdown {
+    /// İki bacağın swap sonucundan ayrıştır. 1. bacağın girdisi owedToken'dır
+    /// (geri ödenen token); `owed_unit_weth` onun bir ham biriminin WETH
+    /// karşılığıdır (WETH için 1e-18).
+    pub fn from_legs(
+        amount_in: alloy::primitives::U256,
+        leg1: &exact::ExactSwapResult,
+        leg2: &exact::ExactSwapResult,
+        owed_unit_weth: f64,
+        costs: AttributionCosts,
+    ) -> Self {
+        let to_weth = |raw: alloy::primitives::U256| exact::u256_to_f64(raw) * owed_unit_weth;
+        let fee_leg1_weth = to_weth(leg1.fee_paid);
+
+        // 2. bacak ücreti ara token cinsinden: ücret düşülmüş girdinin
+        // ürettiği çıktı oranıyla owedToken'a çevrilir
+        let leg2_in = leg1.amount_out.saturating_sub(leg2.amount_remaining);
+        let leg2_swapped = exact::u256_to_f64(leg2_in.saturating_sub(leg2.fee_paid));
+        let fee_leg2_weth = if leg2_swapped > 0.0 {
+            to_weth(leg2.amount_out) * exact::u256_to_f64(leg2.fee_paid) / leg2_swapped
+        } else {
+            0.0
+        };
+
+        let swap_result_weth = to_weth(leg2.amount_out) - to_weth(amount_in);
+        let breakdown = Self {
+            gross_weth: swap_result_weth + fee_leg1_weth + fee_leg2_weth,
+            fee_leg1_weth,
+            fee_leg2_weth,
+            flash_fee_weth: costs.flash_fee_weth,
+            gas_weth: costs.gas_weth,
+            bribe_weth: 0.0,
+            net_weth: swap_result_weth - costs.flash_fee_weth - costs.gas_weth,
+        };
+        debug_assert!((breakdown.component_sum() - breakdown.net_weth).abs() <= 1e-9 * breakdown.gross_weth.abs().max(1.0));
+        breakdown
+    }
+
+    /// Builder rüşvetini düş (fırsat anında bilinmez — yürütmede eklenir)
+    pub fn with_bribe(mut self, bribe_weth: f64) -> Self {
+        self.net_weth -= bribe_weth - self.bribe_weth;
+        self.bribe_weth = bribe_weth;
+        self
+    }
+
+    /// LP ücretleri toplamı (iki bacak)
+    pub fn lp_fees_weth(&self) -> f64 {
+        self.fee_leg1_weth + self.fee_leg2_weth
+    }
+
+    /// Bileşenlerden yeniden kurulan net — `net_weth` ile yuvarlama dahilinde eşit
+    pub fn component_sum(&self) -> f64 {
+        self.gross_weth
+            - self.fee_leg1_weth
+            - self.fee_leg2_weth
+            - self.flash_fee_weth
+            - self.gas_weth
+            - self.bribe_weth
+    }
+}
+
+/// Oturum boyunca raporlanan fırsatların ayrıştırma toplamı
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProfitAttribution {
+    pub opportunities: u64,
+    pub totals: ProfitBreakdown,
+    /// LP ücretlerinin brüt spread'e oranı (%)
+    pub fee_share_of_gross_pct: f64,
+}
+
+impl ProfitAttribution {
+    pub fn record(&mut self, b: &ProfitBreakdown) {
+        self.opportunities += 1;
+        let t = &mut self.totals;
+        t.gross_weth += b.gross_weth;
+        t.fee_leg1_weth += b.fee_leg1_weth;
+        t.fee_leg2_weth += b.fee_leg2_weth;
+        t.flash_fee_weth += b.flash_fee_weth;
+        t.gas_weth += b.gas_weth;
+        t.bribe_weth += b.bribe_weth;
+        t.net_weth += b.net_weth;
+        self.fee_share_of_gross_pct = if t.gross_weth > 0.0 {
+            t.lp_fees_weth() / t.gross_weth * 100.0
+        } else {
+            0.0
+        };
+    }
+}
+
+/// Satış bacağı (WETH girdisi) + alış bacağı → (girdi wei, satış, alış).
+/// Geçersiz miktar, boş çıktı ya da havuzu tüketen bacak None.
 #[allow(clippy::too_many_arguments)]
-pub fn compute_arbitrage_profit_presorted(
+fn presorted_legs(
     amount_in_weth: f64,
     sell_pool: &PoolState,
     sell_fee_fraction: f64,
     buy_pool: &PoolState,
     buy_fee_fraction: f64,
-    gas_cost_usd: f64,
-    eth_price_usd: f64,
     sell_token0_is_weth: bool,
     buy_token0_is_weth: bool,
     sell_sorted: &[(i32, i128, alloy::primitives::U256)],
     buy_sorted: &[(i32, i128, alloy::primitives::U256)],
-) -> f64 {
+) -> Option<(alloy::primitives::U256, exact::ExactSwapResult, exact::ExactSwapResult)> {
     if amount_in_weth <= 0.0 {
-        return f64::NEG_INFINITY;
+        return None;
     }
 
-    let amount_in_wei = alloy::primitives::U256::from(
-        crate::types::safe_f64_to_u128(amount_in_weth * 1e18)
-    );
+    let amount_in_wei = crate::types::weth_to_wei(amount_in_weth).ok()?;
+    presorted_legs_wei(
+        amount_in_wei,
+        sell_pool, sell_fee_fraction, buy_pool, buy_fee_fraction,
+        sell_token0_is_weth, buy_token0_is_weth,
+        sell_sorted, buy_sorted,
+    )
+}
+
+/// `presorted_legs`'in wei girdili hali (sabit nokta kaba tarama da kullanır)
+#[allow(clippy::too_many_arguments)]
+fn presorted_legs_wei(
+    amount_in_wei: alloy::primitives::U256,
+    sell_pool: &PoolState,
+    sell_fee_fraction: f64,
+    buy_pool: &PoolState,
+    buy_fee_fraction: f64,
+    sell_token0_is_weth: bool,
+    buy_token0_is_weth: bool,
+    sell_sorted: &[(i32, i128, alloy::primitives::U256)],
+    buy_sorted: &[(i32, i128, alloy::primitives::U256)],
+) -> Option<(alloy::primitives::U256, exact::ExactSwapResult, exact::ExactSwapResult)> {
     if amount_in_wei.is_zero() {
-        return f64::NEG_INFINITY;
+        return None;
     }
 
     let sell_fee_pips = exact::fee_fraction_to_pips(sell_fee_fraction);
     let buy_fee_pips = exact::fee_fraction_to_pips(buy_fee_fraction);
 
-    let sell_zero_for_one = sell_token0_is_weth;
-    let sell_result = exact::compute_exact_swap_presorted(
-        sell_pool.sqrt_price_x96,
-        sell_pool.liquidity,
-        sell_pool.tick,
-        amount_in_wei,
-        sell_zero_for_one,
-        sell_fee_pips,
-        sell_sorted,
-    );
+    // Havuz başına eğri: V2 bacakları x·y=k, CL bacakları multi-tick
+    let sell_result = if sell_pool.constant_product {
+        exact::swap_weth_to_usdc_v2(
+            sell_pool.sqrt_price_x96,
+            sell_pool.liquidity,
+            sell_pool.tick,
+            amount_in_wei,
+            sell_token0_is_weth,
+            sell_fee_pips,
+        )
+    } else {
+        exact::compute_exact_swap_presorted(
+            sell_pool.sqrt_price_x96,
+            sell_pool.liquidity,
+            sell_pool.tick,
+            amount_in_wei,
+            sell_token0_is_weth,
+            sell_fee_pips,
+            sell_sorted,
+        )
+    };
 
-    if sell_result.amount_out.is_zero() {
-        return f64::NEG_INFINITY;
+    // Bacaklardan biri havuzu tüketiyorsa girdinin bir kısmı hiç swap edilmez —
+    // kontrat bu boyutta ya revert eder ya da eksik çıktıyla zarar eder
+    if sell_result.amount_out.is_zero()
+        || sell_result.termination_reason == exact::SwapTermination::LiquidityExhausted
+    {
+        return None;
     }
 
-    let buy_zero_for_one = !buy_token0_is_weth;
-    let buy_result = exact::compute_exact_swap_presorted(
-        buy_pool.sqrt_price_x96,
-        buy_pool.liquidity,
-        buy_pool.tick,
-        sell_result.amount_out,
-        buy_zero_for_one,
-        buy_fee_pips,
-        buy_sorted,
-    );
+    let buy_result = if buy_pool.constant_product {
+        exact::swap_usdc_to_weth_v2(
+            buy_pool.sqrt_price_x96,
+            buy_pool.liquidity,
+            buy_pool.tick,
+            sell_result.amount_out,
+            buy_token0_is_weth,
+            buy_fee_pips,
+        )
+    } else {
+        exact::compute_exact_swap_presorted(
+            buy_pool.sqrt_price_x96,
+            buy_pool.liquidity,
+            buy_pool.tick,
+            sell_result.amount_out,
+            !buy_token0_is_weth,
+            buy_fee_pips,
+            buy_sorted,
+        )
+    };
 
-    if buy_result.amount_out.is_zero() {
-        return f64::NEG_INFINITY;
+    if buy_result.amount_out.is_zero()
+        || buy_result.termination_reason == exact::SwapTermination::LiquidityExhausted
+    {
+        return None;
     }
 
+    Some((amount_in_wei, sell_result, buy_result))
+}
+
+/// OPT-1: Pre-sorted tick'lerle arbitraj kâr hesaplaması.
+/// `compute_arbitrage_profit_with_bitmap` ile aynı mantık, ama tick sıralama sıfır maliyetli.
+/// `gas_cost_usd(geçiş)`: iki bacağın toplam tick geçişinde quote cinsinden
+/// gas — geçiş sayısı boyutla değiştiği için maliyet her miktarda yeniden okunur.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_arbitrage_profit_presorted(
+    amount_in_weth: f64,
+    sell_pool: &PoolState,
+    sell_fee_fraction: f64,
+    buy_pool: &PoolState,
+    buy_fee_fraction: f64,
+    gas_cost_usd: impl Fn(u32) -> f64,
+    eth_price_usd: f64,
+    sell_token0_is_weth: bool,
+    buy_token0_is_weth: bool,
+    sell_sorted: &[(i32, i128, alloy::primitives::U256)],
+    buy_sorted: &[(i32, i128, alloy::primitives::U256)],
+) -> f64 {
+    let Some((amount_in_wei, sell_result, buy_result)) = presorted_legs(
+        amount_in_weth,
+        sell_pool, sell_fee_fraction, buy_pool, buy_fee_fraction,
+        sell_token0_is_weth, buy_token0_is_weth,
+        sell_sorted, buy_sorted,
+    ) else {
+        return f64::NEG_INFINITY;
+    };
+    let gas_cost_usd = gas_cost_usd(TickCrossings::of(&sell_result, &buy_result).total());
+
     // Flash swap: havuz içi swap — ek flash loan ücreti yok.
     // AMM fee zaten compute_swap_step içinde düşülüyor.
     let repay_amount = amount_in_wei;
@@ -300,39 +572,130 @@ pub fn compute_arbitrage_profit_presorted(
     }
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Newton-Raphson Türev Hesaplayıcı
-// ─────────────────────────────────────────────────────────────────────────────
-
-/// OPT-1+2: Pre-sorted tick'lerle türev hesaplayıcı.
-/// OPT-D: Forward difference türev — iterasyon başına 1 swap hesabı (central difference'ın yarısı).
-/// Secant method zaten f(x) ve f(x_{n-1}) cache'liyor, forward difference yeterli hassasiyet sağlar.
+/// `compute_arbitrage_profit_presorted`'un ayrıştırılmış hali (WETH).
+/// NR optimumunda bir kez çağrılır; bacak ücretleri aynı swap yolundan gelir.
+/// Bacaklardan biri boşsa / havuzu tüketiyorsa None.
 #[allow(clippy::too_many_arguments)]
-fn profit_derivative_presorted(
+pub fn compute_arbitrage_profit_breakdown_with_bitmap(
     amount_in_weth: f64,
     sell_pool: &PoolState,
-    sell_fee: f64,
+    sell_fee_fraction: f64,
     buy_pool: &PoolState,
+    buy_fee_fraction: f64,
+    costs: AttributionCosts,
+    sell_token0_is_weth: bool,
+    buy_token0_is_weth: bool,
+    sell_bitmap: Option<&TickBitmapData>,
+    buy_bitmap: Option<&TickBitmapData>,
+) -> Option<ProfitBreakdown> {
+    let sorted = |bitmap: Option<&TickBitmapData>, tick: i32| match bitmap {
+        Some(bm) => exact::SortedTicks::from_bitmap(bm, tick),
+        None => exact::SortedTicks::empty(),
+    };
+    let sell_ticks = sorted(sell_bitmap, sell_pool.tick);
+    let buy_ticks = sorted(buy_bitmap, buy_pool.tick);
+    let (amount_in_wei, sell_leg, buy_leg) = presorted_legs(
+        amount_in_weth,
+        sell_pool, sell_fee_fraction, buy_pool, buy_fee_fraction,
+        sell_token0_is_weth, buy_token0_is_weth,
+        sell_ticks.get_ordered(sell_token0_is_weth),
+        buy_ticks.get_ordered(!buy_token0_is_weth),
+    )?;
+    Some(ProfitBreakdown::from_legs(amount_in_wei, &sell_leg, &buy_leg, 1e-18, costs))
+}
+
+/// Boyutlandırma yolunun tek WETH satış bacağı: NR ile aynı f64 → wei
+/// dönüşümü, fee kesri → pips yuvarlaması ve önceden sıralı tick yolu.
+/// Quoter eşlik denetimi bu çıktıyı zincirle karşılaştırır.
+pub fn sizing_leg_output(
+    amount_in_weth: f64,
+    pool: &PoolState,
+    fee_fraction: f64,
+    token0_is_weth: bool,
+) -> alloy::primitives::U256 {
+    let Ok(amount_in_wei) = crate::types::weth_to_wei(amount_in_weth) else {
+        return alloy::primitives::U256::ZERO;
+    };
+    let ticks = match pool.tick_bitmap.as_deref() {
+        Some(bm) => exact::SortedTicks::from_bitmap(bm, pool.tick),
+        None => exact::SortedTicks::empty(),
+    };
+    exact::compute_exact_swap_presorted(
+        pool.sqrt_price_x96,
+        pool.liquidity,
+        pool.tick,
+        amount_in_wei,
+        token0_is_weth,
+        exact::fee_fraction_to_pips(fee_fraction),
+        ticks.get_ordered(token0_is_weth),
+    )
+    .amount_out
+}
+
+/// Boyuta göre iki bacağın tick geçişlerini sayan yardımcı. Tick'ler bir
+/// kez sıralanır; REVM boyutlandırması (quote geçiş sayısı vermez) ve NR
+/// optimumundaki gas kalemi bunu kullanır.
+pub struct CrossingCounter<'a> {
+    sell_pool: &'a PoolState,
+    sell_fee: f64,
+    buy_pool: &'a PoolState,
     buy_fee: f64,
-    gas_cost_usd: f64,
-    eth_price_usd: f64,
     sell_token0_is_weth: bool,
     buy_token0_is_weth: bool,
-    sell_sorted: &[(i32, i128, alloy::primitives::U256)],
-    buy_sorted: &[(i32, i128, alloy::primitives::U256)],
-    f_at_x: f64,
-) -> f64 {
-    let h = (amount_in_weth * 1e-7).max(1e-10);
+    sell_ticks: exact::SortedTicks,
+    buy_ticks: exact::SortedTicks,
+}
 
-    let f_plus = compute_arbitrage_profit_presorted(
-        amount_in_weth + h,
-        sell_pool, sell_fee, buy_pool, buy_fee,
-        gas_cost_usd, eth_price_usd,
-        sell_token0_is_weth, buy_token0_is_weth,
-        sell_sorted, buy_sorted,
-    );
+impl<'a> CrossingCounter<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sell_pool: &'a PoolState,
+        sell_fee: f64,
+        buy_pool: &'a PoolState,
+        buy_fee: f64,
+        sell_token0_is_weth: bool,
+        buy_token0_is_weth: bool,
+        sell_bitmap: Option<&TickBitmapData>,
+        buy_bitmap: Option<&TickBitmapData>,
+    ) -> Self {
+        let sorted = |bitmap: Option<&TickBitmapData>, tick: i32| match bitmap {
+            Some(bm) => exact::SortedTicks::from_bitmap(bm, tick),
+            None => exact::SortedTicks::empty(),
+        };
+        Self {
+            sell_ticks: sorted(sell_bitmap, sell_pool.tick),
+            buy_ticks: sorted(buy_bitmap, buy_pool.tick),
+            sell_pool,
+            sell_fee,
+            buy_pool,
+            buy_fee,
+            sell_token0_is_weth,
+            buy_token0_is_weth,
+        }
+    }
+
+    /// `amount_in_weth` boyutunda geçişler — bacak boş / havuz tükeniyorsa None
+    pub fn at(&self, amount_in_weth: f64) -> Option<TickCrossings> {
+        let (_, sell, buy) = presorted_legs(
+            amount_in_weth,
+            self.sell_pool, self.sell_fee, self.buy_pool, self.buy_fee,
+            self.sell_token0_is_weth, self.buy_token0_is_weth,
+            self.sell_ticks.get_ordered(self.sell_token0_is_weth),
+            self.buy_ticks.get_ordered(!self.buy_token0_is_weth),
+        )?;
+        Some(TickCrossings::of(&sell, &buy))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Newton-Raphson Türev Hesaplayıcı
+// ─────────────────────────────────────────────────────────────────────────────
 
-    (f_plus - f_at_x) / h
+/// OPT-D: Forward difference türev — iterasyon başına 1 kâr hesabı (central difference'ın yarısı).
+/// Secant method zaten f(x) ve f(x_{n-1}) cache'liyor, forward difference yeterli hassasiyet sağlar.
+fn profit_derivative(profit_fn: &impl Fn(f64) -> f64, amount_in_weth: f64, f_at_x: f64) -> f64 {
+    let h = (amount_in_weth * 1e-7).max(1e-10);
+    (profit_fn(amount_in_weth + h) - f_at_x) / h
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -348,42 +711,57 @@ pub struct OptimalAmountResult {
     pub iterations: u32,
 }
 
+/// Optimizer probe bütçesi — kâr fonksiyonunun bir çağrısının maliyetine göre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NrBudget {
+    /// Kaba tarama nokta sayısı (None = kapasiteye göre adaptif 6/10/16/24)
+    pub coarse_steps: Option<u32>,
+    /// Secant iterasyon üst sınırı
+    pub max_iterations: u32,
+}
+
+impl NrBudget {
+    /// Exact matematik — probe mikrosaniyeler sürer
+    pub const MATH: Self = Self { coarse_steps: None, max_iterations: 50 };
+    /// REVM quote — probe ~0.5ms, tarama 12 noktaya iner
+    pub const REVM: Self = Self { coarse_steps: Some(12), max_iterations: 12 };
+
+    /// v30.0: Adaptif kaba tarama -- havuz kapasitesine gore adim sayisi.
+    /// Kucuk havuzlarda az adim yeterli, buyuk havuzlarda daha yuksek cozunurluk gerekir
+    fn coarse_steps_for(self, effective_max: f64) -> u32 {
+        self.coarse_steps.unwrap_or(
+            if effective_max < 0.1 { 6 }
+            else if effective_max < 1.0 { 10 }
+            else if effective_max < 10.0 { 16 }
+            else { 24 },
+        )
+    }
+}
+
 /// Newton-Raphson ile optimal flash loan miktarını bul.
 ///        Her havuzun kendi token0_is_weth değeri bağımsız kullanılır.
+/// `min_amount_weth` (MIN_TRADE_SIZE_WETH) arama alt sınırıdır: tavan bunun
+/// altındaysa ya da optimum toz boyuttaysa sonuç tabana oturur.
+/// `gas_cost_usd(geçiş)`: toplam tick geçişinde quote cinsinden gas — her
+/// probe kendi boyutunun geçiş sayısıyla fiyatlanır (sabit değil).
 #[allow(clippy::too_many_arguments)]
 pub fn find_optimal_amount_with_bitmap(
     sell_pool: &PoolState,
     sell_fee: f64,
     buy_pool: &PoolState,
     buy_fee: f64,
-    gas_cost_usd: f64,
+    gas_cost_usd: &dyn Fn(u32) -> f64,
     eth_price_usd: f64,
     max_amount_weth: f64,
+    min_amount_weth: f64,
     sell_token0_is_weth: bool,
     _sell_tick_spacing: i32,
     _buy_tick_spacing: i32,
     sell_bitmap: Option<&TickBitmapData>,
     buy_bitmap: Option<&TickBitmapData>,
     buy_token0_is_weth: bool,
+    numerics: SizingNumerics,
 ) -> OptimalAmountResult {
-    let max_iterations: u32 = 50;
-    let tolerance = 1e-8;
-    let min_amount = 0.0001;
-
-    // OPT-7: Redundant cap hesaplamaları kaldırıldı.
-    // Caller (strategy.rs) zaten effective_cap hesaplayıp max_amount_weth olarak geçiriyor.
-    // max_amount_weth = effective_cap.min(config.max_trade_size_weth)
-    let effective_max = max_amount_weth;
-
-    if effective_max <= min_amount {
-        return OptimalAmountResult {
-            optimal_amount: 0.0,
-            expected_profit: 0.0,
-            converged: false,
-            iterations: 0,
-        };
-    }
-
     // OPT-1: Tick'leri BİR KEZ sırala — NR döngüsü boyunca yeniden sıralama yok.
     // sell pool: zeroForOne (WETH satış yönü) → descending ticks
     // buy pool: oneForZero (!buy_token0_is_weth = buy_zero_for_one) → direction-specific ticks
@@ -401,29 +779,110 @@ pub fn find_optimal_amount_with_bitmap(
     let sell_sorted = sell_ticks.get_ordered(sell_zero_for_one);
     let buy_sorted = buy_ticks.get_ordered(buy_zero_for_one);
 
+    // Sert üst sınır: satış bacağı (WETH girdisi) bitmap'e göre bu miktarda
+    // tükeniyorsa NR ötesini taramaz. Alış bacağının tükenmesi kâr fonksiyonunda elenir.
+    let max_amount_weth = match exact::liquidity_exhaustion_capacity(
+        sell_pool.sqrt_price_x96,
+        sell_pool.liquidity,
+        sell_zero_for_one,
+        exact::fee_fraction_to_pips(sell_fee),
+        sell_sorted,
+    ) {
+        // f64 → wei yuvarlaması kapasiteyi aşmasın
+        Some(capacity) => max_amount_weth.min(exact::u256_to_f64(capacity) / 1e18 * (1.0 - 1e-9)),
+        None => max_amount_weth,
+    };
+
+    let profit_fn = |amount: f64| {
+        compute_arbitrage_profit_presorted(
+            amount,
+            sell_pool, sell_fee, buy_pool, buy_fee,
+            gas_cost_usd, eth_price_usd,
+            sell_token0_is_weth, buy_token0_is_weth,
+            sell_sorted, buy_sorted,
+        )
+    };
+    match numerics {
+        SizingNumerics::F64 => {
+            find_optimal_amount_with(profit_fn, max_amount_weth, min_amount_weth, eth_price_usd, NrBudget::MATH)
+        }
+        SizingNumerics::Fixed => {
+            if max_amount_weth <= min_amount_weth {
+                return OptimalAmountResult::not_found(0.0);
+            }
+            // Kaba tarama tam sayı wei ızgarasında, kâr wei cinsinden
+            // karşılaştırılır. Gas geçiş sayısıyla değişir — argmax'ı
+            // etkiler: geçiş başına maliyet wei'ye bir kez yuvarlanır
+            let gas_wei = |crossings: u32| {
+                (gas_cost_usd(crossings) / eth_price_usd.max(f64::MIN_POSITIVE) * 1e18) as i128
+            };
+            let legs = fixed::ScanLegs {
+                sell_pool, sell_fee, buy_pool, buy_fee,
+                sell_token0_is_weth, buy_token0_is_weth,
+                sell_sorted, buy_sorted,
+                gas_wei: &gas_wei,
+            };
+            let steps = NrBudget::MATH.coarse_steps_for(max_amount_weth);
+            let best = fixed::coarse_scan(&legs, min_amount_weth, max_amount_weth, steps)
+                .map(|amount_wei| {
+                    let amount = exact::u256_to_f64(amount_wei) / 1e18;
+                    (amount, profit_fn(amount))
+                });
+            let (best_amount, best_profit) = best.unwrap_or((0.0, f64::NEG_INFINITY));
+            refine_secant(
+                &profit_fn,
+                best_amount,
+                best_profit,
+                min_amount_weth,
+                max_amount_weth,
+                eth_price_usd,
+                NrBudget::MATH.max_iterations,
+            )
+        }
+    }
+}
+
+impl OptimalAmountResult {
+    /// Kârlı boyut bulunamadı
+    fn not_found(expected_profit: f64) -> Self {
+        Self { optimal_amount: 0.0, expected_profit, converged: false, iterations: 0 }
+    }
+}
+
+/// Kâr fonksiyonundan bağımsız optimizer: kaba tarama → ince tarama → secant.
+/// `profit_fn(amount_weth)` quote cinsinden net kâr döndürür (gas düşülmüş);
+/// exact matematik veya REVM quote'u aynı arama ile kullanılır.
+/// Arama [min_amount_weth, max_amount_weth] aralığındadır.
+pub fn find_optimal_amount_with(
+    profit_fn: impl Fn(f64) -> f64,
+    max_amount_weth: f64,
+    min_amount_weth: f64,
+    eth_price_usd: f64,
+    budget: NrBudget,
+) -> OptimalAmountResult {
+    let min_amount = min_amount_weth;
+
+    // OPT-7: Redundant cap hesaplamaları kaldırıldı.
+    // Caller (strategy.rs) zaten effective_cap hesaplayıp max_amount_weth olarak geçiriyor.
+    // max_amount_weth = effective_cap.min(config.max_trade_size_weth)
+    let effective_max = max_amount_weth;
+
+    if effective_max <= min_amount {
+        return OptimalAmountResult::not_found(0.0);
+    }
+
     // ── AŞAMA 1: Adaptif 2-Fazlı Kaba Tarama (OPT-E) ──────────────
     // Faz 1: 8 kaba adım ile bölgeyi tara
     // Faz 2: En iyi bölge etrafında 8 ince adım
-    // v30.0: Adaptif kaba tarama -- havuz kapasitesine gore adim sayisi
-    // Kucuk havuzlarda az adim yeterli, buyuk havuzlarda daha yuksek cozunurluk gerekir
     let mut best_amount = 0.0;
     let mut best_profit = f64::NEG_INFINITY;
-    let coarse_steps: u32 = if effective_max < 0.1 { 6 }
-        else if effective_max < 1.0 { 10 }
-        else if effective_max < 10.0 { 16 }
-        else { 24 };
+    let coarse_steps = budget.coarse_steps_for(effective_max);
 
     for i in 1..=coarse_steps {
         let fraction = i as f64 / coarse_steps as f64;
         let amount = min_amount + (effective_max - min_amount) * fraction * fraction;
 
-        let profit = compute_arbitrage_profit_presorted(
-            amount,
-            sell_pool, sell_fee, buy_pool, buy_fee,
-            gas_cost_usd, eth_price_usd,
-            sell_token0_is_weth, buy_token0_is_weth,
-            sell_sorted, buy_sorted,
-        );
+        let profit = profit_fn(amount);
 
         if profit > best_profit {
             best_profit = profit;
@@ -440,13 +899,7 @@ pub fn find_optimal_amount_with_bitmap(
         for i in 0..=fine_steps {
             let amount = fine_lo + (fine_hi - fine_lo) * (i as f64 / fine_steps as f64);
 
-            let profit = compute_arbitrage_profit_presorted(
-                amount,
-                sell_pool, sell_fee, buy_pool, buy_fee,
-                gas_cost_usd, eth_price_usd,
-                sell_token0_is_weth, buy_token0_is_weth,
-                sell_sorted, buy_sorted,
-            );
+            let profit = profit_fn(amount);
 
             if profit > best_profit {
                 best_profit = profit;
@@ -455,32 +908,32 @@ pub fn find_optimal_amount_with_bitmap(
         }
     }
 
-    if best_profit <= f64::NEG_INFINITY + 1.0 || best_amount <= 0.0 {
-        return OptimalAmountResult {
-            optimal_amount: 0.0,
-            expected_profit: best_profit.max(0.0),
-            converged: false,
-            iterations: 0,
-        };
+    refine_secant(&profit_fn, best_amount, best_profit, min_amount, effective_max, eth_price_usd, budget.max_iterations)
+}
+
+/// AŞAMA 2: kaba taramanın en iyi noktasından secant ince ayarı.
+/// Tarama hiçbir geçerli boyut bulamadıysa (−∞) sonuç boş döner.
+fn refine_secant(
+    profit_fn: &impl Fn(f64) -> f64,
+    best_amount: f64,
+    best_profit: f64,
+    min_amount: f64,
+    effective_max: f64,
+    eth_price_usd: f64,
+    max_iterations: u32,
+) -> OptimalAmountResult {
+    let tolerance = 1e-8;
+
+    if best_profit <= f64::NEG_INFINITY + 1.0 || best_amount <= 0.0 {
+        return OptimalAmountResult::not_found(best_profit.max(0.0));
     }
 
     // ── AŞAMA 2: Secant Method İnce Ayar (OPT-2 + OPT-D) ────────────
     // Forward difference + secant: iterasyon başına sadece 1 swap hesabı.
     // f'(x_{n-1}) önceki iterasyondan cache'lenir.
     let mut x_prev = (best_amount * 0.9).max(min_amount);
-    let f_at_x_prev = compute_arbitrage_profit_presorted(
-        x_prev, sell_pool, sell_fee, buy_pool, buy_fee,
-        gas_cost_usd, eth_price_usd,
-        sell_token0_is_weth, buy_token0_is_weth,
-        sell_sorted, buy_sorted,
-    );
-    let mut fp_prev = profit_derivative_presorted(
-        x_prev, sell_pool, sell_fee, buy_pool, buy_fee,
-        gas_cost_usd, eth_price_usd,
-        sell_token0_is_weth, buy_token0_is_weth,
-        sell_sorted, buy_sorted,
-        f_at_x_prev,
-    );
+    let f_at_x_prev = profit_fn(x_prev);
+    let mut fp_prev = profit_derivative(profit_fn, x_prev, f_at_x_prev);
     let mut x = best_amount;
     let mut converged = false;
     let mut final_iterations: u32 = 0;
@@ -489,13 +942,7 @@ pub fn find_optimal_amount_with_bitmap(
     for i in 0..max_iterations {
         final_iterations = i + 1;
 
-        let fp = profit_derivative_presorted(
-            x, sell_pool, sell_fee, buy_pool, buy_fee,
-            gas_cost_usd, eth_price_usd,
-            sell_token0_is_weth, buy_token0_is_weth,
-            sell_sorted, buy_sorted,
-            current_profit,
-        );
+        let fp = profit_derivative(profit_fn, x, current_profit);
 
         // f'(x) ≈ 0 → optimum bulundu
         if fp.abs() < tolerance * eth_price_usd.max(1.0) {
@@ -529,12 +976,7 @@ pub fn find_optimal_amount_with_bitmap(
         x = x_new;
 
         // Yeni x'teki profit'i hesapla (bir sonraki iterasyonun forward diff'i için)
-        current_profit = compute_arbitrage_profit_presorted(
-            x, sell_pool, sell_fee, buy_pool, buy_fee,
-            gas_cost_usd, eth_price_usd,
-            sell_token0_is_weth, buy_token0_is_weth,
-            sell_sorted, buy_sorted,
-        );
+        current_profit = profit_fn(x);
     }
 
     x = x.clamp(min_amount, effective_max);
@@ -546,6 +988,17 @@ pub fn find_optimal_amount_with_bitmap(
         current_profit
     };
 
+    // Geçiş sayısına bağlı gas kâr eğrisinde basamak yaratır — secant bir
+    // basamağın ötesine kaçarsa taramanın en iyi noktası korunur
+    if final_profit < best_profit {
+        return OptimalAmountResult {
+            optimal_amount: best_amount,
+            expected_profit: best_profit,
+            converged,
+            iterations: final_iterations,
+        };
+    }
+
     OptimalAmountResult {
         optimal_amount: x,
         expected_profit: final_profit,
@@ -562,6 +1015,7 @@ pub fn find_optimal_amount_with_bitmap(
 mod tests {
     use super::*;
     use crate::types::TickInfo;
+    use alloy::primitives::U256;
     use std::collections::HashMap;
     use std::time::Instant;
     use proptest::prelude::*;
@@ -588,10 +1042,15 @@ mod tests {
             last_block: 0,
             last_update: Instant::now(),
             is_initialized: true,
-            bytecode: None,
             tick_bitmap: None,
             live_fee_bps: None,
             is_stale: false,
+            data_quality_errors: 0,
+            quarantined: false,
+            optimistic_trigger: None,
+            pinned_block: Some(0),
+            predicted_state: None,
+            constant_product: false,
         }
     }
 
@@ -616,6 +1075,7 @@ mod tests {
                 liquidity_gross: liq_net.unsigned_abs(),
                 liquidity_net: liq_net,
                 initialized: true,
+                staked_liquidity_net: 0,
             });
         }
 
@@ -625,6 +1085,9 @@ mod tests {
             snapshot_block: 0,
             sync_duration_us: 0,
             scan_range: 500,
+            scanned_ticks: None,
+            staked: None,
+            last_full_sync_block: 0,
         }
     }
 
@@ -665,15 +1128,17 @@ mod tests {
         let result = find_optimal_amount_with_bitmap(
             &sell_pool, 0.0005,
             &buy_pool, 0.01,
-            0.10,
+            &|_| 0.10,
             2000.0,
             10.0,
+            0.0001,
             true,
             10,
             10,
             Some(&sell_bitmap),
             Some(&buy_bitmap),
             true,
+            SizingNumerics::F64,
         );
 
         println!(
@@ -686,6 +1151,428 @@ mod tests {
         assert!(result.optimal_amount > 0.0, "Optimal miktar > 0 olmalı");
     }
 
+    /// Optimumu toz boyutta (0.002 WETH) olan kâr eğrisi: taban 0.0001'de NR
+    /// tozu bulur; MIN_TRADE_SIZE_WETH=0.01 tabanında arama tabana oturur ve
+    /// kâr negatiftir — kâr eşiği fırsatı eler. Tavan tabanın altındaysa arama yok.
+    #[test]
+    fn test_nr_lower_bound_excludes_dust_optimum() {
+        let profit_usd = |x: f64| 1000.0 * x - 250_000.0 * x * x;
+
+        let dust = find_optimal_amount_with(profit_usd, 10.0, 0.0001, 2000.0, NrBudget::MATH);
+        assert!((dust.optimal_amount - 0.002).abs() < 1e-4, "{}", dust.optimal_amount);
+        assert!(dust.expected_profit > 0.99);
+
+        let floored = find_optimal_amount_with(profit_usd, 10.0, 0.01, 2000.0, NrBudget::MATH);
+        assert!(floored.optimal_amount >= 0.01, "{}", floored.optimal_amount);
+        assert!(floored.expected_profit < 0.0);
+
+        let capped = find_optimal_amount_with(profit_usd, 0.005, 0.01, 2000.0, NrBudget::MATH);
+        assert_eq!((capped.optimal_amount, capped.converged), (0.0, false));
+    }
+
+    /// Satış havuzu birkaç tick sonra tükeniyorsa NR optimumu kapasiteyi aşmaz
+    /// (kâr boyutla artsa bile).
+    #[test]
+    fn test_newton_raphson_never_exceeds_exhaustion_capacity() {
+        let mut sell_pool = make_test_pool(2020.0);
+        sell_pool.liquidity = 250_000_000_000_000_000; // 2.5e17
+        let buy_pool = make_test_pool(1980.0);
+
+        // Tek pozisyon: mevcut fiyat ile 10-19 tick aşağısı arası
+        let exhaust_tick = sell_pool.tick.div_euclid(10) * 10 - 10;
+        let mut ticks = HashMap::new();
+        ticks.insert(exhaust_tick, TickInfo {
+            liquidity_gross: sell_pool.liquidity,
+            liquidity_net: sell_pool.liquidity as i128,
+            initialized: true,
+            staked_liquidity_net: 0,
+        });
+        let sell_bitmap = TickBitmapData { ticks, ..TickBitmapData::empty() };
+
+        let sorted = exact::SortedTicks::from_bitmap(&sell_bitmap, sell_pool.tick);
+        let capacity = exact::liquidity_exhaustion_capacity(
+            sell_pool.sqrt_price_x96, sell_pool.liquidity, true, 500, sorted.get_ordered(true),
+        ).expect("tek pozisyon tükenir");
+        let capacity_weth = exact::u256_to_f64(capacity) / 1e18;
+        assert!(capacity_weth > 1.0 && capacity_weth < 10.0, "capacity={}", capacity_weth);
+
+        for numerics in [SizingNumerics::F64, SizingNumerics::Fixed] {
+            let result = find_optimal_amount_with_bitmap(
+                &sell_pool, 0.0005,
+                &buy_pool, 0.01,
+                &|_| 0.10,
+                2000.0,
+                10.0,
+                0.0001,
+                true,
+                10,
+                10,
+                Some(&sell_bitmap),
+                None,
+                true,
+                numerics,
+            );
+            assert!(
+                result.optimal_amount <= capacity_weth,
+                "{}: NR {} > kapasite {}", numerics, result.optimal_amount, capacity_weth,
+            );
+            assert!(result.expected_profit.is_finite() && result.expected_profit > 0.0);
+        }
+        // Kapasitenin üstü kâr fonksiyonunda elenir
+        let over = compute_arbitrage_profit_presorted(
+            capacity_weth * 1.01, &sell_pool, 0.0005, &buy_pool, 0.01, |_| 0.10, 2000.0,
+            true, true, sorted.get_ordered(true), &[],
+        );
+        assert_eq!(over, f64::NEG_INFINITY);
+    }
+
+    /// Her 10 tick'te başlatılmış sınır (liquidityNet=0): likidite sabit
+    /// kalır, boyut büyüdükçe yalnızca geçiş sayısı artar
+    fn dense_bitmap(current_tick: i32, count: i32) -> TickBitmapData {
+        let base = current_tick.div_euclid(10) * 10;
+        let ticks = (-count..=count)
+            .map(|i| {
+                let info = TickInfo { liquidity_gross: 1, liquidity_net: 0, initialized: true, staked_liquidity_net: 0 };
+                (base + i * 10, info)
+            })
+            .collect();
+        TickBitmapData { ticks, ..TickBitmapData::empty() }
+    }
+
+    /// Gas geçiş sayısına bağlıyken NR her probe'u kendi boyutunun geçişiyle
+    /// fiyatlar: sonuç kârı = brüt − gas(optimumdaki geçiş). Geçiş başına
+    /// maliyet ezici ise optimum en az geçişli boyutta kalır; sabit maliyetle
+    /// aynı eğrinin optimumu çok daha fazla tick geçer.
+    #[test]
+    fn test_nr_prices_gas_by_crossings_at_each_size() {
+        let mut sell_pool = make_test_pool(2020.0);
+        let mut buy_pool = make_test_pool(1980.0);
+        sell_pool.liquidity = 500_000_000_000_000_000; // 5e17 — ~5.6 WETH / 10 tick
+        buy_pool.liquidity = 500_000_000_000_000_000;
+        let sell_bitmap = dense_bitmap(sell_pool.tick, 60);
+        let buy_bitmap = dense_bitmap(buy_pool.tick, 60);
+        let counter = CrossingCounter::new(
+            &sell_pool, 0.0005, &buy_pool, 0.003, true, true, Some(&sell_bitmap), Some(&buy_bitmap),
+        );
+        let min_crossings = counter.at(0.001).expect("küçük boyut swap edilir").total();
+        assert!(counter.at(20.0).unwrap().total() > min_crossings);
+
+        let sell_ticks = exact::SortedTicks::from_bitmap(&sell_bitmap, sell_pool.tick);
+        let buy_ticks = exact::SortedTicks::from_bitmap(&buy_bitmap, buy_pool.tick);
+        let gross = |amount: f64| {
+            compute_arbitrage_profit_presorted(
+                amount, &sell_pool, 0.0005, &buy_pool, 0.003, |_| 0.0, 2000.0,
+                true, true, sell_ticks.get_ordered(true), buy_ticks.get_ordered(false),
+            )
+        };
+        let flat = |_: u32| 0.10;
+        let per_crossing = |crossings: u32| 0.10 + 1_000.0 * crossings as f64;
+
+        for numerics in [SizingNumerics::F64, SizingNumerics::Fixed] {
+            let run = |gas: &dyn Fn(u32) -> f64| {
+                find_optimal_amount_with_bitmap(
+                    &sell_pool, 0.0005, &buy_pool, 0.003, gas, 2000.0, 100.0, 0.0001,
+                    true, 10, 10, Some(&sell_bitmap), Some(&buy_bitmap), true, numerics,
+                )
+            };
+            let flat_result = run(&flat);
+            let crossing_result = run(&per_crossing);
+
+            // Sonuç kendi boyutunun geçiş sayısıyla fiyatlanmış
+            let at_opt = counter.at(crossing_result.optimal_amount).expect("optimum swap edilir");
+            let repriced = gross(crossing_result.optimal_amount) - per_crossing(at_opt.total());
+            assert!(
+                (crossing_result.expected_profit - repriced).abs() < 1e-5,
+                "{}: NR {} ≠ brüt − gas(geçiş) {}", numerics, crossing_result.expected_profit, repriced,
+            );
+            assert_eq!(at_opt.total(), min_crossings, "{}: geçiş ağırlıklı boyut seçildi", numerics);
+            assert!(
+                counter.at(flat_result.optimal_amount).unwrap().total() > min_crossings + 4,
+                "{}: sabit maliyet optimumu {} WETH", numerics, flat_result.optimal_amount,
+            );
+            assert!(crossing_result.optimal_amount < flat_result.optimal_amount);
+        }
+    }
+
+    /// Fiyattan uzaklaştıkça likiditesi azalan bitmap: her iki yönde geçilen
+    /// her tick aktif likiditeden `step` düşer
+    fn tapered_bitmap(current_tick: i32, count: i32, step: i128) -> TickBitmapData {
+        let base = current_tick.div_euclid(10) * 10;
+        let ticks = (-count..=count)
+            .filter(|&i| i != 0)
+            .map(|i| {
+                let net = if i < 0 { step } else { -step };
+                let info = TickInfo { liquidity_gross: step as u128, liquidity_net: net, initialized: true, staked_liquidity_net: 0 };
+                (base + i * 10, info)
+            })
+            .collect();
+        TickBitmapData { ticks, ..TickBitmapData::empty() }
+    }
+
+    /// Çok tick geçen boyutta exact optimum, f64 optimumunun exact kârından
+    /// kötü değil: ipucuyla kesin ≥, ipucusuz arama da aynı tepeye oturur
+    #[test]
+    fn test_exact_optimum_not_worse_than_f64_optimum() {
+        let mut sell_pool = make_test_pool(2020.0);
+        let mut buy_pool = make_test_pool(1980.0);
+        sell_pool.liquidity = 500_000_000_000_000_000; // 5e17
+        buy_pool.liquidity = 500_000_000_000_000_000;
+        sell_pool.liquidity_f64 = sell_pool.liquidity as f64;
+        buy_pool.liquidity_f64 = buy_pool.liquidity as f64;
+        let sell_bitmap = tapered_bitmap(sell_pool.tick, 120, 2_000_000_000_000_000);
+        let buy_bitmap = tapered_bitmap(buy_pool.tick, 120, 2_000_000_000_000_000);
+
+        let f64_result = find_optimal_amount_with_bitmap(
+            &sell_pool, 0.0005, &buy_pool, 0.003, &|_| 0.0, 2000.0, 100.0, 0.0001,
+            true, 10, 10, Some(&sell_bitmap), Some(&buy_bitmap), true, SizingNumerics::F64,
+        );
+        assert!(f64_result.optimal_amount > 1.0, "çok tick geçen boyut: {:?}", f64_result);
+
+        // Flash kaynağı = satış havuzu (WETH → USDC), ikinci bacak USDC → WETH; owedToken WETH
+        let exact_profit = |amount: U256| {
+            exact::compute_exact_directional_profit(
+                sell_pool.sqrt_price_x96, sell_pool.liquidity, sell_pool.tick, 500, Some(&sell_bitmap),
+                buy_pool.sqrt_price_x96, buy_pool.liquidity, buy_pool.tick, 3000, Some(&buy_bitmap),
+                amount, true, false,
+            )
+        };
+        let search = |hint: Option<U256>| {
+            exact::find_optimal_amount_exact(
+                sell_pool.sqrt_price_x96, sell_pool.liquidity, sell_pool.tick, 500, Some(&sell_bitmap),
+                buy_pool.sqrt_price_x96, buy_pool.liquidity, buy_pool.tick, 3000, Some(&buy_bitmap),
+                true, false,
+                exact::f64_to_u256_wei(0.0001), exact::f64_to_u256_wei(100.0), hint,
+            )
+        };
+        let f64_wei = exact::f64_to_u256_wei(f64_result.optimal_amount);
+        let f64_exact_profit = exact_profit(f64_wei);
+        assert!(f64_exact_profit > U256::ZERO);
+
+        let (hinted_amount, hinted_profit, hinted_evals) = search(Some(f64_wei));
+        assert!(
+            hinted_profit >= f64_exact_profit,
+            "ipuçlu exact {} wei @ {} < f64 optimumu {:.6} WETH → {} wei",
+            hinted_profit, hinted_amount, f64_result.optimal_amount, f64_exact_profit,
+        );
+        assert_eq!(exact_profit(hinted_amount), hinted_profit, "dönen kâr kendi miktarının exact kârı");
+        assert!(hinted_evals <= 128, "değerlendirme bütçesi: {}", hinted_evals);
+
+        let (amount, profit, evals) = search(None);
+        let f64_exact = exact::u256_to_f64(f64_exact_profit);
+        assert!(
+            exact::u256_to_f64(profit) >= f64_exact * (1.0 - 1e-9),
+            "exact {} wei @ {} ↔ f64 optimumu {} wei @ {}", profit, amount, f64_exact_profit, f64_wei,
+        );
+        assert!(
+            evals > 17 && evals <= 128,
+            "tarama + altın oran: {} değerlendirme (exact optimum {:.6} WETH → {} wei)",
+            evals, exact::u256_to_f64(amount) / 1e18, profit,
+        );
+    }
+
+    /// Kârsız havuz çifti ve boş aralık: miktar/kâr sıfır
+    #[test]
+    fn test_exact_optimum_without_profit_is_zero() {
+        let pool = make_test_pool(2000.0);
+        let bitmap = make_test_bitmap(pool.tick, 10);
+        let search = |min_wei: U256, max_wei: U256| {
+            exact::find_optimal_amount_exact(
+                pool.sqrt_price_x96, pool.liquidity, pool.tick, 500, Some(&bitmap),
+                pool.sqrt_price_x96, pool.liquidity, pool.tick, 500, Some(&bitmap),
+                true, false, min_wei, max_wei, None,
+            )
+        };
+        let (amount, profit, evals) = search(U256::from(1_000_000u64), exact::f64_to_u256_wei(50.0));
+        assert_eq!((amount, profit), (U256::ZERO, U256::ZERO));
+        assert!(evals > 0);
+        let one = exact::f64_to_u256_wei(1.0);
+        assert_eq!(search(one, one), (U256::ZERO, U256::ZERO, 0));
+    }
+
+    /// WETH = token1 havuzu (quote token0, 6 decimal) — ters token sırası
+    fn make_test_pool_token1_weth(eth_price: f64) -> PoolState {
+        let price_ratio = 1e12 / eth_price;
+        let tick = (price_ratio.ln() / LOG_TICK_BASE).floor() as i32;
+        let sqrt_price_x96 = exact::get_sqrt_ratio_at_tick(tick);
+        PoolState {
+            sqrt_price_x96,
+            sqrt_price_f64: exact::u256_to_f64(sqrt_price_x96),
+            tick,
+            ..make_test_pool(eth_price)
+        }
+    }
+
+    /// Ayrıştırma: bileşenler net'e toplanır, net kâr fonksiyonuyla aynı,
+    /// ücretler bacak fee oranlarıyla orantılı — iki token sırasında da
+    #[test]
+    fn test_profit_breakdown_sum_identity_both_orderings() {
+        let costs = AttributionCosts { flash_fee_weth: 0.0005, gas_weth: 0.0002 };
+        let scenarios = [
+            (make_test_pool(2020.0), true, make_test_pool(1980.0), true),
+            (make_test_pool_token1_weth(2020.0), false, make_test_pool_token1_weth(1980.0), false),
+            (make_test_pool(2020.0), true, make_test_pool_token1_weth(1980.0), false),
+            (make_test_pool_token1_weth(2020.0), false, make_test_pool(1980.0), true),
+        ];
+        for (i, (sell, sell_t0_weth, buy, buy_t0_weth)) in scenarios.iter().enumerate() {
+            let sell_bitmap = make_test_bitmap(sell.tick, 10);
+            let b = compute_arbitrage_profit_breakdown_with_bitmap(
+                2.0, sell, 0.0005, buy, 0.003, costs,
+                *sell_t0_weth, *buy_t0_weth, Some(&sell_bitmap), None,
+            )
+            .unwrap_or_else(|| panic!("senaryo {}", i));
+
+            assert!((b.component_sum() - b.net_weth).abs() < 1e-12, "senaryo {}: {:?}", i, b);
+            assert!(b.gross_weth > b.net_weth && b.net_weth > 0.0, "senaryo {}: {:?}", i, b);
+            assert_eq!((b.flash_fee_weth, b.gas_weth, b.bribe_weth), (0.0005, 0.0002, 0.0));
+            // 1. bacak: 2 WETH × %0.05; 2. bacak: ~2 WETH × %0.3
+            assert!((b.fee_leg1_weth - 0.001).abs() < 1e-6, "senaryo {}: {}", i, b.fee_leg1_weth);
+            assert!((b.fee_leg2_weth / 0.006 - 1.0).abs() < 0.05, "senaryo {}: {}", i, b.fee_leg2_weth);
+
+            // Net = kâr fonksiyonu (gas düşülmüş, quote → WETH) − flash
+            let sell_ticks = exact::SortedTicks::from_bitmap(&sell_bitmap, sell.tick);
+            let profit_quote = compute_arbitrage_profit_presorted(
+                2.0, sell, 0.0005, buy, 0.003, |_| costs.gas_weth * 2000.0, 2000.0,
+                *sell_t0_weth, *buy_t0_weth, sell_ticks.get_ordered(*sell_t0_weth), &[],
+            );
+            let expected_net = profit_quote / 2000.0 - costs.flash_fee_weth;
+            assert!((b.net_weth - expected_net).abs() < 1e-12, "senaryo {}", i);
+        }
+    }
+
+    /// Sıfır kâr kenarı: aynı fiyatlı havuzlarda spread yok — brüt ≈ 0
+    /// (yalnızca fiyat etkisi), net yalnızca maliyetler kadar negatif
+    #[test]
+    fn test_profit_breakdown_zero_profit_edge() {
+        let pool = make_test_pool(2000.0);
+        let costs = AttributionCosts::default();
+        let b = compute_arbitrage_profit_breakdown_with_bitmap(
+            0.01, &pool, 0.0005, &pool, 0.0005, costs, true, true, None, None,
+        )
+        .expect("küçük miktar iki bacakta da swap edilir");
+
+        assert!(b.gross_weth.abs() < 1e-6, "{:?}", b);
+        assert!(b.net_weth < 0.0);
+        assert!((b.component_sum() - b.net_weth).abs() < 1e-15);
+        assert!((b.net_weth + b.lp_fees_weth()).abs() < 1e-6, "net ≈ −ücretler");
+
+        // Sıfır girdi: swap yok → ayrıştırma yok
+        assert!(compute_arbitrage_profit_breakdown_with_bitmap(
+            0.0, &pool, 0.0005, &pool, 0.0005, costs, true, true, None, None,
+        )
+        .is_none());
+
+        let mut totals = ProfitAttribution::default();
+        totals.record(&b);
+        assert_eq!(totals.fee_share_of_gross_pct, if b.gross_weth > 0.0 {
+            b.lp_fees_weth() / b.gross_weth * 100.0
+        } else {
+            0.0
+        });
+    }
+
+    /// WETH/USDC V2 havuzu: 1000 WETH / 2.5M USDC (token0 = WETH) → 2500 USDC
+    fn v2_reserves_2500() -> (U256, U256) {
+        (U256::from(1_000u64) * U256::from(10u64).pow(U256::from(18u64)), U256::from(2_500_000_000_000u64))
+    }
+
+    fn make_v2_pool(reserve0: U256, reserve1: U256) -> PoolState {
+        let (sqrt_price_x96, tick, liquidity) = exact::v2_virtual_state(reserve0, reserve1).unwrap();
+        PoolState {
+            sqrt_price_x96,
+            sqrt_price_f64: exact::u256_to_f64(sqrt_price_x96),
+            tick,
+            liquidity,
+            liquidity_f64: liquidity as f64,
+            eth_price_usd: compute_eth_price_from_reserves(reserve0, reserve1, 18, 6, true),
+            is_initialized: true,
+            constant_product: true,
+            ..PoolState::default()
+        }
+    }
+
+    /// x·y=k: getAmountOut ile birebir, sanal state rezervleri geri verir,
+    /// fiyat rezerv oranından
+    #[test]
+    fn test_v2_constant_product_math() {
+        let (r0, r1) = v2_reserves_2500();
+        let pool = make_v2_pool(r0, r1);
+        assert!((pool.eth_price_usd - 2500.0).abs() < 1e-6, "{}", pool.eth_price_usd);
+        let from_sqrt = compute_eth_price(pool.sqrt_price_f64, pool.tick, 18, 6, true);
+        assert!((from_sqrt - pool.eth_price_usd).abs() / pool.eth_price_usd < 1e-9);
+        assert!(pool.validate().is_ok(), "{:?}", pool.validate());
+
+        let (back0, back1) = exact::v2_reserves(pool.sqrt_price_x96, pool.liquidity);
+        let gap = |a: U256, b: U256| if a > b { a - b } else { b - a };
+        assert!(gap(back0, r0) <= U256::from(1u64) && gap(back1, r1) <= U256::from(1u64));
+
+        // 3000 pips = UniswapV2Library.getAmountOut (997/1000)
+        let one_weth = U256::from(10u64).pow(U256::from(18u64));
+        for amount in [U256::from(1u64), one_weth, one_weth * U256::from(250u64)] {
+            let in_with_fee = amount * U256::from(997u64);
+            let expected = in_with_fee * r1 / (r0 * U256::from(1000u64) + in_with_fee);
+            assert_eq!(exact::v2_amount_out(amount, r0, r1, 3000), expected);
+        }
+
+        let sell = exact::swap_weth_to_usdc_v2(pool.sqrt_price_x96, pool.liquidity, pool.tick, one_weth, true, 3000);
+        assert!(gap(sell.amount_out, exact::v2_amount_out(one_weth, r0, r1, 3000)) <= U256::from(1u64));
+        assert_eq!(sell.tick_crossings, 0);
+        assert_eq!(sell.termination_reason, exact::SwapTermination::InputExhausted);
+        assert!(sell.sqrt_price_x96_after < pool.sqrt_price_x96, "WETH satışı fiyatı düşürür");
+        assert_eq!(sell.final_tick, exact::get_tick_at_sqrt_ratio(sell.sqrt_price_x96_after));
+        assert!(sell.liquidity_after >= pool.liquidity, "ücret k'yı büyütür");
+
+        // Geri dönüş iki kez ücret öder
+        let buy = exact::swap_usdc_to_weth_v2(pool.sqrt_price_x96, pool.liquidity, pool.tick, sell.amount_out, true, 3000);
+        assert!(buy.amount_out < one_weth && buy.amount_out > one_weth * U256::from(99u64) / U256::from(100u64));
+
+        assert!(exact::v2_virtual_state(U256::ZERO, r1).is_none());
+        assert_eq!(exact::v2_amount_out(one_weth, r0, r1, 1_000_000), U256::ZERO);
+    }
+
+    /// Kâr bacakları havuz başına eğri seçer: V2 bacağı x·y=k, CL bacağı multi-tick
+    #[test]
+    fn test_presorted_legs_dispatch_constant_product() {
+        let (r0, r1) = v2_reserves_2500();
+        // V2'de 2500'e satılır, CL havuzunda 2480'den alınır
+        let v2 = make_v2_pool(r0, r1);
+        let cl = make_test_pool(2480.0);
+        let one_weth = U256::from(10u64).pow(U256::from(18u64));
+
+        let (_, sell, buy) =
+            presorted_legs_wei(one_weth, &v2, 0.003, &cl, 0.0005, true, true, &[], &[]).expect("iki bacak da swap eder");
+        let (res0, res1) = exact::v2_reserves(v2.sqrt_price_x96, v2.liquidity);
+        assert_eq!(sell.amount_out, exact::v2_amount_out(one_weth, res0, res1, 3000));
+        let cl_buy = exact::compute_exact_swap_presorted(
+            cl.sqrt_price_x96, cl.liquidity, cl.tick, sell.amount_out, false, 500, &[],
+        );
+        assert_eq!(buy.amount_out, cl_buy.amount_out);
+
+        // Aynı sanal state CL matematiğiyle de aynı eğridir — fark yuvarlama düzeyinde
+        let as_cl = PoolState { constant_product: false, ..v2.clone() };
+        let (_, cl_sell, _) =
+            presorted_legs_wei(one_weth, &as_cl, 0.003, &cl, 0.0005, true, true, &[], &[]).unwrap();
+        let (a, b) = (exact::u256_to_f64(sell.amount_out), exact::u256_to_f64(cl_sell.amount_out));
+        assert!((a - b).abs() / a < 1e-9, "v2={} cl={}", a, b);
+
+        // V2 derinliği WETH rezervidir (tick aralığı yok)
+        let pool = crate::types::PoolConfig {
+            address: alloy::primitives::Address::ZERO,
+            name: "v2".into(),
+            fee: crate::types::FeeTier::from_bps(30),
+            token0_decimals: 18,
+            token1_decimals: 6,
+            dex: crate::types::DexType::UniswapV2,
+            token0_is_weth: true,
+            tick_spacing: 1,
+            quote_token_address: alloy::primitives::Address::ZERO,
+            base_token_address: alloy::primitives::Address::ZERO,
+        };
+        assert!((exact::pool_liquidity_cap_weth(&v2, &pool, None) - 1000.0).abs() < 1e-9);
+        let (after, out) = exact::advance_pool_state(&v2, &pool, one_weth, true);
+        assert_eq!(out, sell.amount_out);
+        assert!(after.constant_product && after.eth_price_usd < v2.eth_price_usd);
+    }
+
     // ─────────────────────────────────────────────────────────────────────
     // PROPTEST — Çökme Dayanıklılık Testleri (Property-Based Stress Test)
     //
@@ -785,23 +1672,19 @@ pub fn compute_arbitrage_profit_multi_hop(
         return f64::NEG_INFINITY;
     }
 
-    // f64 → U256 wei
-    let initial_amount_wei = alloy::primitives::U256::from(
-        crate::types::safe_f64_to_u128(amount_in_weth * 1e18)
-    );
-    if initial_amount_wei.is_zero() {
-        return f64::NEG_INFINITY;
-    }
+    // f64 → U256 wei (kontrollü — geçersiz miktar doyurulmaz, reddedilir)
+    let initial_amount_wei = match crate::types::weth_to_wei(amount_in_weth) {
+        Ok(wei) if !wei.is_zero() => wei,
+        _ => return f64::NEG_INFINITY,
+    };
 
     // Zincir boyunca swap et: her hop'un çıktısı bir sonrakinin girdisi
     let mut current_amount = initial_amount_wei;
 
     for (i, (state, config)) in pool_states.iter().zip(pool_configs.iter()).enumerate() {
-        let fee_pips = state.live_fee_bps
-            .map(|b| b * 100)
-            .unwrap_or(config.fee_bps * 100);
+        let fee_pips = config.effective_fee(state.live_fee_bps).pips();
 
-        let bitmap = state.tick_bitmap.as_ref();
+        let bitmap = state.tick_bitmap.as_deref();
 
         let result = exact::compute_exact_swap(
             state.sqrt_price_x96,
@@ -856,22 +1739,16 @@ pub fn find_optimal_amount_multi_hop(
     gas_cost_usd: f64,
     eth_price_usd: f64,
     max_amount_weth: f64,
+    min_amount_weth: f64,
 ) -> MultiHopOptimalResult {
     let max_iterations: u32 = 40;
     let tolerance = 1e-8;
-    let min_amount = 0.0001;
+    let min_amount = min_amount_weth;
 
     // Multi-hop likidite kapasitesini hesapla (her havuzun minimum cap'i)
     let mut effective_max = max_amount_weth;
     for (state, config) in pool_states.iter().zip(pool_configs.iter()) {
-        let cap = exact::hard_liquidity_cap_weth(
-            state.sqrt_price_x96,
-            state.liquidity,
-            state.tick,
-            config.token0_is_weth,
-            state.tick_bitmap.as_ref(),
-            config.tick_spacing,
-        );
+        let cap = exact::pool_liquidity_cap_weth(state, config, state.tick_bitmap.as_deref());
         effective_max = effective_max.min(cap);
     }
 
@@ -1013,9 +1890,7 @@ pub fn compute_exact_profit_multi_hop(
 
     let mut current = amount_wei;
     for (i, (state, config)) in pool_states.iter().zip(pool_configs.iter()).enumerate() {
-        let fee_pips = state.live_fee_bps
-            .map(|b| b * 100)
-            .unwrap_or(config.fee_bps * 100);
+        let fee_pips = config.effective_fee(state.live_fee_bps).pips();
 
         let result = exact::compute_exact_swap(
             state.sqrt_price_x96,
@@ -1024,7 +1899,7 @@ pub fn compute_exact_profit_multi_hop(
             current,
             directions[i],
             fee_pips,
-            state.tick_bitmap.as_ref(),
+            state.tick_bitmap.as_deref(),
         );
 
         if result.amount_out.is_zero() {
@@ -1059,7 +1934,7 @@ pub fn compute_exact_profit_multi_hop(
 // ═══════════════════════════════════════════════════════════════════════════════
 
 pub mod exact {
-    use alloy::primitives::U256;
+    use alloy::primitives::{U256, U512};
     use crate::types::TickBitmapData;
 
     // ── Sabitler ─────────────────────────────────────────────────────────────
@@ -1078,22 +1953,45 @@ pub mod exact {
     const MIN_SQRT_RATIO: U256 = U256::from_limbs([4295128739, 0, 0, 0]);
 
     /// Precomputed MIN_SQRT_RATIO + 1 (swap limit boundary)
-    const MIN_SQRT_RATIO_PLUS_1: U256 = U256::from_limbs([4295128740, 0, 0, 0]);
+    pub const MIN_SQRT_RATIO_PLUS_1: U256 = U256::from_limbs([4295128740, 0, 0, 0]);
 
     /// Precomputed MAX_SQRT_RATIO - 1 (swap limit boundary)
-    const MAX_SQRT_RATIO_MINUS_1: U256 = U256::from_limbs([
+    pub const MAX_SQRT_RATIO_MINUS_1: U256 = U256::from_limbs([
         0x5D951D5263988D25, 0xEFD1FC6A50648849, 0x00000000FFFD8963, 0
     ]);
 
+    /// UniV3 TickMath tick sınırları
+    pub const MIN_TICK: i32 = -887272;
+    pub const MAX_TICK: i32 = 887272;
+
+    /// Aralık dışı tick nedeniyle kısıtlanan get_sqrt_ratio_at_tick çağrı sayısı.
+    /// Sıfırdan büyükse bitmap/state verisi bozulmuş demektir (panic yerine bayrak).
+    static TICK_CLAMP_EVENTS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    /// Şimdiye kadar kısıtlanan (aralık dışı) tick sayısı
+    pub fn tick_clamp_events() -> u64 {
+        TICK_CLAMP_EVENTS.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// sqrtPriceX96'yı swap limitleri [MIN_SQRT_RATIO+1, MAX_SQRT_RATIO-1] içine kısıtla
+    /// (UniswapV3Pool.swap sqrtPriceLimitX96 kontrolünün karşılığı)
+    #[inline]
+    pub fn clamp_sqrt_price(sqrt_price_x96: U256) -> U256 {
+        sqrt_price_x96.clamp(MIN_SQRT_RATIO_PLUS_1, MAX_SQRT_RATIO_MINUS_1)
+    }
+
     // ── FullMath — U256 Tam Çarpma / Bölme ──────────────────────────────────
 
     /// a * b / denominator (taşma güvenli, floor rounding)
     /// Uniswap V3 FullMath.mulDiv port'u.
     ///
-    /// v22.1 DÜZELTME: Rekürsif ayrıştırma algoritması.
-    /// Eski: İç taşmada saturating_mul + U256::ZERO fallback → sessiz hata.
-    /// Yeni: mul_div(big%c, small, c) rekürsifi — big%c < c garantisi ile
-    ///       her adımda operand kesinlikle küçülür → sonlanma garantili.
+    /// Çarpım 256 biti aşarsa 512-bit ara sonuçla hesaplanır; U256'ya
+    /// sığmayan sonuç (kontratta revert) U256::MAX'a doyurulur.
+    ///
+    /// DÜZELTME: v22.1'in rekürsif ayrıştırması iki operand da paydadan
+    /// küçükken (phantom overflow: a, b < c ama a·b > 2^256) kendini aynı
+    /// argümanlarla çağırıyordu → sonsuz rekürsiyon. Derin havuzda
+    /// (L << 96) · sqrtPrice tam bu bölgeye düşer.
     pub fn mul_div(a: U256, b: U256, denominator: U256) -> U256 {
         if denominator.is_zero() || a.is_zero() || b.is_zero() {
             return U256::ZERO;
@@ -1102,18 +2000,10 @@ pub mod exact {
         if let Some(product) = a.checked_mul(b) {
             return product / denominator;
         }
-        // Taşma: rekürsif ayrıştırma ile hesapla
-        // a*b/c = (big/c)*small + mul_div(big%c, small, c)
-        // Her rekürsif çağrıda ilk argüman = big%c < c → kesinlikle küçülür
-        // Sonlanma garantili (logaritmik derinlik)
-        let (big, small) = if a >= b { (a, b) } else { (b, a) };
-        let q = big / denominator;
-        let r = big % denominator;
-        // term1 = (big/c) * small — saturating: taşma durumunda sonuç U256'ya sığmıyordur
-        let term1 = q.saturating_mul(small);
-        // term2 = mul_div(big%c, small, c) — rekürsif, big%c < c → sonlanır
-        let term2 = mul_div(r, small, denominator);
-        term1.saturating_add(term2)
+        // Taşma: tam 512-bit çarpım / payda
+        let product: U512 = a.widening_mul(b);
+        let quotient = product / U512::saturating_from_limbs_slice(denominator.as_limbs());
+        U256::saturating_from_limbs_slice(quotient.as_limbs())
     }
 
     /// a * b / denominator (taşma güvenli, ceil rounding)
@@ -1130,7 +2020,8 @@ pub mod exact {
         // mul_mod: (a * b) % denominator — 512-bit ara sonuç, taşma güvenli
         let remainder = a.mul_mod(b, denominator);
         if remainder > U256::ZERO {
-            result + U256::from(1)
+            // Doymuş sonuç sarmamalı (MAX + 1 → 0)
+            result.saturating_add(U256::from(1))
         } else {
             result
         }
@@ -1149,9 +2040,17 @@ pub mod exact {
     /// Tick'ten sqrtPriceX96 hesapla — UniV3 TickMath.getSqrtRatioAtTick birebir port'u.
     /// İnput: -887272 ≤ tick ≤ 887272
     /// Çıktı: uint160 sqrtPriceX96 (U256 olarak)
+    ///
+    /// Aralık dışı tick (bozuk bitmap verisi vb.) hot path'te panic yerine
+    /// en yakın sınıra kısıtlanır ve TICK_CLAMP_EVENTS sayacı artırılır.
     pub fn get_sqrt_ratio_at_tick(tick: i32) -> U256 {
+        let tick = if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+            TICK_CLAMP_EVENTS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            tick.clamp(MIN_TICK, MAX_TICK)
+        } else {
+            tick
+        };
         let abs_tick = tick.unsigned_abs();
-        assert!(abs_tick <= 887272, "tick aralık dışı");
 
         // Başlangıç ratio (Q128 formatında)
         let mut ratio: U256 = if abs_tick & 0x1 != 0 {
@@ -1226,8 +2125,8 @@ pub mod exact {
             if denominator >= numerator1 {
                 return mul_div_rounding_up(numerator1, sqrt_price_x96, denominator);
             }
-            // Taşma fallback
-            div_rounding_up(numerator1, numerator1 / sqrt_price_x96 + amount)
+            // Taşma fallback — dev girdide payda sarmamalı (fiyat sıçrardı)
+            div_rounding_up(numerator1, (numerator1 / sqrt_price_x96).saturating_add(amount))
         } else {
             // sqrtPriceNext = numerator1 * sqrtP / (numerator1 - amount * sqrtP)
             let product = amount.checked_mul(sqrt_price_x96).unwrap_or(U256::MAX);
@@ -1249,7 +2148,8 @@ pub mod exact {
     ) -> U256 {
         if add {
             let quotient = mul_div(amount, Q96, U256::from(liquidity));
-            sqrt_price_x96 + quotient
+            // Doymuş bölüm sarmamalı (fiyat sıfıra dönerdi)
+            sqrt_price_x96.saturating_add(quotient)
         } else {
             let quotient = mul_div_rounding_up(amount, Q96, U256::from(liquidity));
             if sqrt_price_x96 <= quotient {
@@ -1404,11 +2304,76 @@ pub mod exact {
 
     // ── Tam Multi-Tick Swap Simülasyonu (Exact) ─────────────────────────────
 
+    /// Tick geçişi üst sınırı — sonrası mevcut likiditede tek adımla tamamlanır
+    const MAX_SWAP_CROSSINGS: u32 = 50;
+
+    /// Swap döngüsünün neden durduğu.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SwapTermination {
+        /// Girdinin tamamı bilinen tick'ler içinde tüketildi
+        InputExhausted,
+        /// Tick geçişi aktif likiditeyi sıfırladı — havuz bu yönde tükendi,
+        /// girdinin kalanı (`amount_remaining`) harcanmadı
+        LiquidityExhausted,
+        /// Bilinen tick'ler (bitmap tarama aralığı) bitti veya fiyat limitine
+        /// ulaşıldı — kalan girdi son aktif likiditeyle hesaplandı
+        TickRangeExhausted,
+        /// MAX_SWAP_CROSSINGS geçişe ulaşıldı — kalan girdi mevcut likiditeyle hesaplandı
+        CrossingCapReached,
+    }
+
     /// Exact multi-tick swap sonucu (U256 hassasiyetinde)
     #[derive(Debug, Clone)]
     pub struct ExactSwapResult {
         /// Toplam çıktı miktarı (raw wei)
         pub amount_out: U256,
+        /// Swap sonrası sqrtPriceX96
+        pub sqrt_price_x96_after: U256,
+        /// Swap sonrası aktif likidite (geçilen tick'lerin liquidityNet'i uygulanmış)
+        pub liquidity_after: u128,
+        /// Harcanamayan girdi (yalnızca LiquidityExhausted / fiyat limitinde sıfırdan büyük)
+        pub amount_remaining: U256,
+        /// LP'ye ödenen toplam ücret (girdi tokeni, raw wei — adım fee_amount toplamı)
+        pub fee_paid: U256,
+        /// Döngünün durma nedeni
+        pub termination_reason: SwapTermination,
+        /// Geçilen başlatılmış tick sayısı (liquidityNet uygulanan sınırlar)
+        pub tick_crossings: u32,
+        /// Swap sonrası tick (son sqrtPrice'tan, kontrattaki sınır kuralıyla)
+        pub final_tick: i32,
+    }
+
+    impl ExactSwapResult {
+        /// Swap başlamadan biten sonuç (sıfır girdi / likiditesiz havuz)
+        fn unswapped(sqrt_price_x96: U256, tick: i32, liquidity: u128, amount_in: U256) -> Self {
+            ExactSwapResult {
+                amount_out: U256::ZERO,
+                sqrt_price_x96_after: sqrt_price_x96,
+                liquidity_after: liquidity,
+                amount_remaining: amount_in,
+                fee_paid: U256::ZERO,
+                termination_reason: if amount_in.is_zero() {
+                    SwapTermination::InputExhausted
+                } else {
+                    SwapTermination::LiquidityExhausted
+                },
+                tick_crossings: 0,
+                final_tick: tick,
+            }
+        }
+    }
+
+    /// Tick sınırında liquidityNet uygula (zeroForOne yönünde çıkarılır).
+    /// Negatife düşen likidite sıfırda, u128'i aşan likidite u128::MAX'ta kesilir
+    /// (bozuk bitmap'teki i128::MIN / dev liquidityNet i128 taşması yapmaz).
+    #[inline]
+    fn cross_tick(liquidity: u128, liquidity_net: i128, zero_for_one: bool) -> u128 {
+        let magnitude = liquidity_net.unsigned_abs();
+        if (liquidity_net >= 0) != zero_for_one {
+            liquidity.saturating_add(magnitude)
+        } else {
+            liquidity.saturating_sub(magnitude)
+        }
     }
 
     // ─────────────────────────────────────────────────────────────────────────
@@ -1443,7 +2408,7 @@ pub mod exact {
                 }
             }
 
-            descending.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+            descending.sort_unstable_by_key(|(t, _, _)| std::cmp::Reverse(*t));
             ascending.sort_unstable_by_key(|(t, _, _)| *t);
 
             SortedTicks { ascending, descending }
@@ -1466,40 +2431,55 @@ pub mod exact {
     pub fn compute_exact_swap_presorted(
         sqrt_price_x96: U256,
         liquidity: u128,
-        _current_tick: i32,
+        current_tick: i32,
         amount_in: U256,
         zero_for_one: bool,
         fee_pips: u32,
         sorted_ticks: &[(i32, i128, U256)],
     ) -> ExactSwapResult {
         if amount_in.is_zero() || liquidity == 0 || sqrt_price_x96.is_zero() {
-            return ExactSwapResult {
-                amount_out: U256::ZERO,
-            };
+            return ExactSwapResult::unswapped(sqrt_price_x96, current_tick, liquidity, amount_in);
         }
 
-        let mut state_sqrt_price = sqrt_price_x96;
+        let mut state_sqrt_price = clamp_sqrt_price(sqrt_price_x96);
         let mut state_liquidity = liquidity;
         let mut amount_remaining = amount_in;
         let mut total_amount_out = U256::ZERO;
+        let mut fee_paid = U256::ZERO;
         let mut crossings: u32 = 0;
-        let max_crossings: u32 = 50;
+        let mut stopped: Option<SwapTermination> = None;
+        let price_limit = if zero_for_one { MIN_SQRT_RATIO_PLUS_1 } else { MAX_SQRT_RATIO_MINUS_1 };
 
         // Ana swap döngüsü — pre-sorted tick'ler boyunca ilerle (sqrtPrice ön-hesaplanmış)
         for &(_next_tick, liquidity_net, sqrt_price_target) in sorted_ticks {
-            if amount_remaining.is_zero() || crossings >= max_crossings {
+            if amount_remaining.is_zero() {
+                break;
+            }
+            if crossings >= MAX_SWAP_CROSSINGS {
+                stopped = Some(SwapTermination::CrossingCapReached);
+                break;
+            }
+            if state_sqrt_price == price_limit {
                 break;
             }
 
+            // Kontrattaki gibi adım hedefi swap limitini aşamaz
+            let step_target = if zero_for_one {
+                sqrt_price_target.max(price_limit)
+            } else {
+                sqrt_price_target.min(price_limit)
+            };
+
             let step = compute_swap_step(
                 state_sqrt_price,
-                sqrt_price_target,
+                step_target,
                 state_liquidity,
                 amount_remaining,
                 fee_pips,
             );
 
             total_amount_out += step.amount_out;
+            fee_paid += step.fee_amount;
             let consumed = step.amount_in + step.fee_amount;
             if amount_remaining >= consumed {
                 amount_remaining -= consumed;
@@ -1509,35 +2489,51 @@ pub mod exact {
             state_sqrt_price = step.sqrt_ratio_next;
 
             if step.sqrt_ratio_next == sqrt_price_target && !amount_remaining.is_zero() {
-                if zero_for_one {
-                    if state_liquidity as i128 >= liquidity_net {
-                        state_liquidity = (state_liquidity as i128 - liquidity_net) as u128;
-                    } else {
-                        state_liquidity = 0;
-                    }
-                } else {
-                    let new_liq = state_liquidity as i128 + liquidity_net;
-                    state_liquidity = if new_liq > 0 { new_liq as u128 } else { 0 };
-                }
+                state_liquidity = cross_tick(state_liquidity, liquidity_net, zero_for_one);
                 crossings += 1;
+                // Havuz bu yönde tükendi — kalan girdi sessizce düşürülmez, raporlanır
+                if state_liquidity == 0 {
+                    stopped = Some(SwapTermination::LiquidityExhausted);
+                    break;
+                }
             }
         }
 
+        let termination_reason = stopped.unwrap_or(if amount_remaining.is_zero() {
+            SwapTermination::InputExhausted
+        } else {
+            SwapTermination::TickRangeExhausted
+        });
+
         // Kalan girdi varsa mevcut likiditede son bir adım daha
-        if !amount_remaining.is_zero() && state_liquidity > 0 {
-            let final_target = if zero_for_one { MIN_SQRT_RATIO_PLUS_1 } else { MAX_SQRT_RATIO_MINUS_1 };
+        if !amount_remaining.is_zero() && state_liquidity > 0 && state_sqrt_price != price_limit {
             let step = compute_swap_step(
                 state_sqrt_price,
-                final_target,
+                price_limit,
                 state_liquidity,
                 amount_remaining,
                 fee_pips,
             );
             total_amount_out += step.amount_out;
+            fee_paid += step.fee_amount;
+            amount_remaining = amount_remaining.saturating_sub(step.amount_in + step.fee_amount);
+            state_sqrt_price = step.sqrt_ratio_next;
         }
 
         ExactSwapResult {
             amount_out: total_amount_out,
+            sqrt_price_x96_after: state_sqrt_price,
+            liquidity_after: state_liquidity,
+            amount_remaining,
+            fee_paid,
+            termination_reason,
+            tick_crossings: crossings,
+            // Fiyat hiç kımıldamadıysa havuzun kendi tick'i korunur
+            final_tick: if state_sqrt_price == sqrt_price_x96 {
+                current_tick
+            } else {
+                tick_after_swap(state_sqrt_price, zero_for_one)
+            },
         }
     }
 
@@ -1568,106 +2564,304 @@ pub mod exact {
         bitmap: Option<&TickBitmapData>,
     ) -> ExactSwapResult {
         if amount_in.is_zero() || liquidity == 0 || sqrt_price_x96.is_zero() {
-            return ExactSwapResult {
-                amount_out: U256::ZERO,
-            };
+            return ExactSwapResult::unswapped(sqrt_price_x96, current_tick, liquidity, amount_in);
         }
 
-        let mut state_sqrt_price = sqrt_price_x96;
-        let mut state_liquidity = liquidity;
-        let mut amount_remaining = amount_in;
-        let mut total_amount_out = U256::ZERO;
-        let mut crossings: u32 = 0;
-        let max_crossings: u32 = 50;
-
-        // Sıralı tick'leri al
-        let ordered_ticks = if let Some(bm) = bitmap {
-            let mut ticks: Vec<(i32, i128)> = bm.ticks.iter()
-                .filter(|(_, info)| info.initialized)
-                .map(|(&t, info)| (t, info.liquidity_net))
-                .collect();
+        // Sıralı tick'leri al — döngü pre-sorted varyantla ortak
+        let sorted = match bitmap {
+            Some(bm) => SortedTicks::from_bitmap(bm, current_tick),
+            None => SortedTicks::empty(),
+        };
+        compute_exact_swap_presorted(
+            sqrt_price_x96,
+            liquidity,
+            current_tick,
+            amount_in,
+            zero_for_one,
+            fee_pips,
+            sorted.get_ordered(zero_for_one),
+        )
+    }
 
-            if zero_for_one {
-                ticks.retain(|(t, _)| *t <= current_tick);
-                ticks.sort_by(|a, b| b.0.cmp(&a.0));
-            } else {
-                ticks.retain(|(t, _)| *t > current_tick);
-                ticks.sort_by_key(|(t, _)| *t);
-            }
-            ticks
+    /// sqrtPriceX96 → tick (TickMath.getTickAtSqrtRatio eşdeğeri).
+    /// f64 tahmin + get_sqrt_ratio_at_tick ile kesin düzeltme:
+    /// sonuç, sqrt_ratio_at(tick) <= sqrt_price < sqrt_ratio_at(tick + 1) koşulunu sağlar.
+    pub fn get_tick_at_sqrt_ratio(sqrt_price_x96: U256) -> i32 {
+        let ratio = u256_to_f64(sqrt_price_x96) / u256_to_f64(Q96);
+        let estimate = (2.0 * ratio.ln() / 1.0001f64.ln()).floor();
+        let mut tick = if estimate.is_finite() {
+            (estimate as i64).clamp(MIN_TICK as i64, MAX_TICK as i64) as i32
         } else {
-            Vec::new()
+            MIN_TICK
         };
-
-        // Ana swap döngüsü — tick'ler boyunca ilerle
-        for &(next_tick, liquidity_net) in &ordered_ticks {
-            if amount_remaining.is_zero() || crossings >= max_crossings {
-                break;
-            }
-
-            // Hedef tick sınırının sqrtPrice'ını hesapla
-            let sqrt_price_target = get_sqrt_ratio_at_tick(next_tick);
-
-            // Bu aralıkta swap yap
-            let step = compute_swap_step(
-                state_sqrt_price,
-                sqrt_price_target,
-                state_liquidity,
-                amount_remaining,
-                fee_pips,
-            );
-
-            total_amount_out += step.amount_out;
-            let consumed = step.amount_in + step.fee_amount;
-            if amount_remaining >= consumed {
-                amount_remaining -= consumed;
-            } else {
-                amount_remaining = U256::ZERO;
-            }
-            state_sqrt_price = step.sqrt_ratio_next;
-
-            // Tick sınırına ulaştık mı?
-            if step.sqrt_ratio_next == sqrt_price_target && !amount_remaining.is_zero() {
-                // Likiditeyi güncelle
-                if zero_for_one {
-                    if state_liquidity as i128 >= liquidity_net {
-                        state_liquidity = (state_liquidity as i128 - liquidity_net) as u128;
-                    } else {
-                        state_liquidity = 0;
-                    }
-                } else {
-                    let new_liq = state_liquidity as i128 + liquidity_net;
-                    state_liquidity = if new_liq > 0 { new_liq as u128 } else { 0 };
-                }
-                crossings += 1;
-            }
+        while tick > MIN_TICK && get_sqrt_ratio_at_tick(tick) > sqrt_price_x96 {
+            tick -= 1;
         }
-
-        // Kalan girdi varsa mevcut likiditede son bir adım daha
-        if !amount_remaining.is_zero() && state_liquidity > 0 {
-            let final_target = if zero_for_one { MIN_SQRT_RATIO_PLUS_1 } else { MAX_SQRT_RATIO_MINUS_1 };
-            let step = compute_swap_step(
-                state_sqrt_price,
-                final_target,
-                state_liquidity,
-                amount_remaining,
-                fee_pips,
-            );
-            total_amount_out += step.amount_out;
+        while tick < MAX_TICK && get_sqrt_ratio_at_tick(tick + 1) <= sqrt_price_x96 {
+            tick += 1;
         }
+        tick
+    }
 
-        ExactSwapResult {
-            amount_out: total_amount_out,
+    /// Swap sonrası tick. zeroForOne yönünde fiyat tam bir tick sınırına inerse
+    /// kontrattaki gibi tick = sınır - 1 olur (sınır tick'i geçilmiş sayılır).
+    pub fn tick_after_swap(sqrt_price_after: U256, zero_for_one: bool) -> i32 {
+        let tick = get_tick_at_sqrt_ratio(sqrt_price_after);
+        if zero_for_one && tick > MIN_TICK && get_sqrt_ratio_at_tick(tick) == sqrt_price_after {
+            tick - 1
+        } else {
+            tick
         }
     }
 
-    // İki havuz arasında exact arbitraj kârı hesapla (U256, wei bazında)
-    //
-    // v23.0 (D-3): compute_exact_arbitrage_profit tamamen kaldırıldı.
-    // Tek token0_is_weth parametresi çapraz-DEX'te hatalı sonuç veriyordu.
-    // Yerine compute_exact_directional_profit kullanılır.
-
-    // ── Yön-Bazlı Exact Kâr Hesaplama (Flash Swap Akışı Birebir Model) ─────
+    /// Swap yönünde fiyat `target_sqrt_price`'a inene (çıkana) kadar aktif
+    /// likidite — aradaki başlatılmış tick'lerin liquidityNet'i uygulanır.
+    /// Hedef tam bir tick sınırındaysa o tick geçilmiş sayılır (kontratta
+    /// adım sınıra ulaşınca cross edilir). Fiyat limitinde kesilen swap'ın
+    /// likiditesi buradan alınır; limit ötesi geçişler sayılmaz.
+    pub fn liquidity_at_sqrt_price(
+        liquidity: u128,
+        current_tick: i32,
+        target_sqrt_price: U256,
+        zero_for_one: bool,
+        bitmap: Option<&TickBitmapData>,
+    ) -> u128 {
+        let Some(bm) = bitmap else {
+            return liquidity;
+        };
+        let sorted = SortedTicks::from_bitmap(bm, current_tick);
+        sorted
+            .get_ordered(zero_for_one)
+            .iter()
+            .take_while(|&&(_, _, sqrt_price)| {
+                if zero_for_one { sqrt_price >= target_sqrt_price } else { sqrt_price <= target_sqrt_price }
+            })
+            .fold(liquidity, |l, &(_, liquidity_net, _)| cross_tick(l, liquidity_net, zero_for_one))
+    }
+
+    /// Simüle edilmiş bir swap'ı havuz durumuna uygula → (yeni PoolState, çıktı).
+    ///
+    /// sqrtPrice, tick, likidite ve türetilmiş f64/fiyat alanları güncellenir;
+    /// bitmap, blok ve canlı fee korunur. Aynı state üzerinde ardışık swap'lar
+    /// (ör. rakip front-run + bizim işlemimiz) zincirlenebilir.
+    pub fn advance_pool_state(
+        state: &crate::types::PoolState,
+        pool: &crate::types::PoolConfig,
+        amount_in: U256,
+        zero_for_one: bool,
+    ) -> (crate::types::PoolState, U256) {
+        let result = if state.constant_product {
+            constant_product_swap(state.sqrt_price_x96, state.liquidity, state.tick, amount_in, zero_for_one, pool.fee_pips())
+        } else {
+            compute_exact_swap(
+                state.sqrt_price_x96,
+                state.liquidity,
+                state.tick,
+                amount_in,
+                zero_for_one,
+                pool.fee_pips(),
+                state.tick_bitmap.as_deref(),
+            )
+        };
+        if result.amount_out.is_zero() {
+            return (state.clone(), U256::ZERO);
+        }
+
+        let tick = result.final_tick;
+        let sqrt_price_f64 = u256_to_f64(result.sqrt_price_x96_after);
+        let mut next = state.clone();
+        next.sqrt_price_x96 = result.sqrt_price_x96_after;
+        next.sqrt_price_f64 = sqrt_price_f64;
+        next.tick = tick;
+        next.set_liquidity(result.liquidity_after);
+        next.eth_price_usd = super::compute_eth_price(
+            sqrt_price_f64,
+            tick,
+            pool.token0_decimals,
+            pool.token1_decimals,
+            pool.token0_is_weth,
+        );
+        (next, result.amount_out)
+    }
+
+    /// Havuz fiyatını swap yönünde `ticks` tick (fiyat oranı 1.0001^ticks) kaydır.
+    ///
+    /// Hedef sqrtPrice'a ulaşan girdi mevcut likiditeyle hesaplanır (fee dahil,
+    /// yukarı yuvarlanmış) ve advance_pool_state ile uygulanır — aralıkta
+    /// başlatılmış tick varsa likidite değişimi swap motorunda izlenir.
+    pub fn shift_pool_state_ticks(
+        state: &crate::types::PoolState,
+        pool: &crate::types::PoolConfig,
+        ticks: u32,
+        zero_for_one: bool,
+    ) -> crate::types::PoolState {
+        if ticks == 0 || state.liquidity == 0 || state.sqrt_price_x96.is_zero() {
+            return state.clone();
+        }
+        let step = ticks.min(MAX_TICK as u32) as i32;
+        let ratio = get_sqrt_ratio_at_tick(if zero_for_one { -step } else { step });
+        let target = clamp_sqrt_price(mul_div(state.sqrt_price_x96, ratio, Q96));
+        let net_in = if zero_for_one {
+            get_amount0_delta(target, state.sqrt_price_x96, state.liquidity, true)
+        } else {
+            get_amount1_delta(state.sqrt_price_x96, target, state.liquidity, true)
+        };
+        let fee_pips = pool.fee_pips().min(999_999);
+        let gross_in = mul_div_rounding_up(
+            net_in,
+            U256::from(1_000_000u32),
+            U256::from(1_000_000 - fee_pips),
+        );
+        advance_pool_state(state, pool, gross_in, zero_for_one).0
+    }
+
+    // ── Sabit Çarpım (Uniswap V2 / Aerodrome vAMM) ──────────────────────────
+    //
+    // V2 havuzu, tüm fiyat eğrisine yayılmış tek aralıklı bir CL pozisyonu
+    // olarak saklanır: L = √(r0·r1), sqrtPriceX96 = √(r1/r0)·2^96. f64
+    // optimizasyonu ve fiyat hesabı bu sanal state üzerinde değişmeden
+    // çalışır; swap bacakları rezervleri geri türetip x·y=k'yı uygular.
+
+    /// Tam sayı karekök (floor) — Newton iterasyonu, yukarıdan yakınsar
+    fn isqrt(n: U256) -> U256 {
+        if n < U256::from(2u64) {
+            return n;
+        }
+        let mut x = U256::from(1u64) << n.bit_len().div_ceil(2);
+        loop {
+            let y = (x + n / x) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    /// getReserves → sanal CL state (sqrtPriceX96, tick, L).
+    /// Boş / tek taraflı havuz veya TickMath aralığı dışı fiyat → None.
+    pub fn v2_virtual_state(reserve0: U256, reserve1: U256) -> Option<(U256, i32, u128)> {
+        if reserve0.is_zero() || reserve1.is_zero() {
+            return None;
+        }
+        let liquidity = isqrt(reserve0.checked_mul(reserve1)?);
+        let sqrt_price_x96 = mul_div(liquidity, Q96, reserve0);
+        if !(MIN_SQRT_RATIO_PLUS_1..=MAX_SQRT_RATIO_MINUS_1).contains(&sqrt_price_x96) {
+            return None;
+        }
+        let liquidity = u128::try_from(liquidity).ok()?;
+        Some((sqrt_price_x96, get_tick_at_sqrt_ratio(sqrt_price_x96), liquidity))
+    }
+
+    /// Sanal state'ten rezervler: r0 = L·2^96/√P, r1 = L·√P/2^96.
+    /// `v2_virtual_state`'in tersi — L·2^96 ≫ r0² olan gerçek havuzlarda
+    /// geri dönüş farkı 1 wei'yi geçmez.
+    pub fn v2_reserves(sqrt_price_x96: U256, liquidity: u128) -> (U256, U256) {
+        if sqrt_price_x96.is_zero() {
+            return (U256::ZERO, U256::ZERO);
+        }
+        let liquidity = U256::from(liquidity);
+        (mul_div(liquidity, Q96, sqrt_price_x96), mul_div(liquidity, sqrt_price_x96, Q96))
+    }
+
+    /// UniswapV2Library.getAmountOut'un pips genellemesi — 3000 pips'te
+    /// 997/1000 formülüyle wei'si wei'sine aynı sonucu verir
+    pub fn v2_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256, fee_pips: u32) -> U256 {
+        if amount_in.is_zero() || reserve_in.is_zero() || reserve_out.is_zero() {
+            return U256::ZERO;
+        }
+        let in_with_fee = amount_in.saturating_mul(U256::from(1_000_000 - fee_pips.min(1_000_000)));
+        let denominator = reserve_in.saturating_mul(U256::from(1_000_000u32)).saturating_add(in_with_fee);
+        mul_div(in_with_fee, reserve_out, denominator)
+    }
+
+    /// Sanal state üzerinde x·y=k swap'ı — exact multi-tick sonucuyla aynı şekil
+    /// (tick geçişi yok, girdi her zaman tükenir)
+    pub fn constant_product_swap(
+        sqrt_price_x96: U256,
+        liquidity: u128,
+        tick: i32,
+        amount_in: U256,
+        zero_for_one: bool,
+        fee_pips: u32,
+    ) -> ExactSwapResult {
+        let (reserve0, reserve1) = v2_reserves(sqrt_price_x96, liquidity);
+        let (reserve_in, reserve_out) = if zero_for_one { (reserve0, reserve1) } else { (reserve1, reserve0) };
+        let amount_out = v2_amount_out(amount_in, reserve_in, reserve_out, fee_pips);
+        if amount_out.is_zero() {
+            return ExactSwapResult::unswapped(sqrt_price_x96, tick, liquidity, amount_in);
+        }
+
+        let (in_after, out_after) = (reserve_in.saturating_add(amount_in), reserve_out - amount_out);
+        let (reserve0_after, reserve1_after) =
+            if zero_for_one { (in_after, out_after) } else { (out_after, in_after) };
+        let (sqrt_price_x96_after, final_tick, liquidity_after) =
+            v2_virtual_state(reserve0_after, reserve1_after).unwrap_or((sqrt_price_x96, tick, liquidity));
+        ExactSwapResult {
+            amount_out,
+            sqrt_price_x96_after,
+            liquidity_after,
+            amount_remaining: U256::ZERO,
+            fee_paid: mul_div_rounding_up(amount_in, U256::from(fee_pips), U256::from(1_000_000u32)),
+            termination_reason: SwapTermination::InputExhausted,
+            tick_crossings: 0,
+            final_tick,
+        }
+    }
+
+    /// V2 satış bacağı: WETH girer, quote token çıkar
+    pub fn swap_weth_to_usdc_v2(
+        sqrt_price_x96: U256,
+        liquidity: u128,
+        tick: i32,
+        amount_in: U256,
+        token0_is_weth: bool,
+        fee_pips: u32,
+    ) -> ExactSwapResult {
+        constant_product_swap(sqrt_price_x96, liquidity, tick, amount_in, token0_is_weth, fee_pips)
+    }
+
+    /// V2 alış bacağı: quote token girer, WETH çıkar
+    pub fn swap_usdc_to_weth_v2(
+        sqrt_price_x96: U256,
+        liquidity: u128,
+        tick: i32,
+        amount_in: U256,
+        token0_is_weth: bool,
+        fee_pips: u32,
+    ) -> ExactSwapResult {
+        constant_product_swap(sqrt_price_x96, liquidity, tick, amount_in, !token0_is_weth, fee_pips)
+    }
+
+    /// Havuzun absorbe edebileceği WETH (f64) — V2'de sınır yoktur, WETH
+    /// rezervi derinlik ölçüsü olarak kullanılır; CL havuzlarında
+    /// `hard_liquidity_cap_weth`
+    pub fn pool_liquidity_cap_weth(
+        state: &crate::types::PoolState,
+        pool: &crate::types::PoolConfig,
+        bitmap: Option<&TickBitmapData>,
+    ) -> f64 {
+        if state.constant_product {
+            let (reserve0, reserve1) = v2_reserves(state.sqrt_price_x96, state.liquidity);
+            let weth_reserve = if pool.token0_is_weth { reserve0 } else { reserve1 };
+            return u256_to_f64(weth_reserve) / 1e18;
+        }
+        hard_liquidity_cap_weth(
+            state.sqrt_price_x96,
+            state.liquidity,
+            state.tick,
+            pool.token0_is_weth,
+            bitmap,
+            pool.tick_spacing,
+        )
+    }
+
+    // İki havuz arasında exact arbitraj kârı hesapla (U256, wei bazında)
+    //
+    // v23.0 (D-3): compute_exact_arbitrage_profit tamamen kaldırıldı.
+    // Tek token0_is_weth parametresi çapraz-DEX'te hatalı sonuç veriyordu.
+    // Yerine compute_exact_directional_profit kullanılır.
+
+    // ── Yön-Bazlı Exact Kâr Hesaplama (Flash Swap Akışı Birebir Model) ─────
 
     /// Flash swap akışını birebir modelleyerek kârı hesapla.
     ///
@@ -1700,8 +2894,83 @@ pub mod exact {
         uni_zero_for_one: bool,
         aero_zero_for_one: bool,
     ) -> U256 {
-        if amount_wei.is_zero() {
+        let Some((_, slipstream_result)) = directional_legs(
+            pool_a_sqrt_price, pool_a_liquidity, pool_a_tick, pool_a_fee_pips, pool_a_bitmap,
+            pool_b_sqrt_price, pool_b_liquidity, pool_b_tick, pool_b_fee_pips, pool_b_bitmap,
+            amount_wei, uni_zero_for_one, aero_zero_for_one,
+        ) else {
             return U256::ZERO;
+        };
+
+        // Adım 3: Kâr = Slipstream çıktısı - UniV3'e borç
+        // Kontrat akışı: balAfter(owedToken) - balBefore(owedToken)
+        // owed_output (Slipstream'den) - amount_wei (UniV3'e ödeme)
+        if slipstream_result.amount_out > amount_wei {
+            slipstream_result.amount_out - amount_wei
+        } else {
+            U256::ZERO
+        }
+    }
+
+    /// `compute_exact_directional_profit` + aynı swap yolundan kâr ayrıştırması.
+    /// `owed_unit_weth`: owedToken'ın bir ham biriminin WETH karşılığı.
+    /// Kâr yoksa (U256::ZERO, ayrıştırma yine döner — net ≤ 0); swap yoksa None.
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute_exact_directional_profit_attributed(
+        pool_a_sqrt_price: U256,
+        pool_a_liquidity: u128,
+        pool_a_tick: i32,
+        pool_a_fee_pips: u32,
+        pool_a_bitmap: Option<&TickBitmapData>,
+        pool_b_sqrt_price: U256,
+        pool_b_liquidity: u128,
+        pool_b_tick: i32,
+        pool_b_fee_pips: u32,
+        pool_b_bitmap: Option<&TickBitmapData>,
+        amount_wei: U256,
+        uni_zero_for_one: bool,
+        aero_zero_for_one: bool,
+        owed_unit_weth: f64,
+        costs: super::AttributionCosts,
+    ) -> (U256, Option<super::ProfitBreakdown>) {
+        let Some((univ3_result, slipstream_result)) = directional_legs(
+            pool_a_sqrt_price, pool_a_liquidity, pool_a_tick, pool_a_fee_pips, pool_a_bitmap,
+            pool_b_sqrt_price, pool_b_liquidity, pool_b_tick, pool_b_fee_pips, pool_b_bitmap,
+            amount_wei, uni_zero_for_one, aero_zero_for_one,
+        ) else {
+            return (U256::ZERO, None);
+        };
+        let profit = slipstream_result.amount_out.saturating_sub(amount_wei);
+        let breakdown = super::ProfitBreakdown::from_legs(
+            amount_wei,
+            &univ3_result,
+            &slipstream_result,
+            owed_unit_weth,
+            costs,
+        );
+        (profit, Some(breakdown))
+    }
+
+    /// Flash swap'ın iki bacağı: UniV3 (amount_wei → ara token), Slipstream
+    /// (ara token → owedToken). Girdi sıfırsa / ilk bacak çıktısızsa None.
+    #[allow(clippy::too_many_arguments)]
+    fn directional_legs(
+        pool_a_sqrt_price: U256,
+        pool_a_liquidity: u128,
+        pool_a_tick: i32,
+        pool_a_fee_pips: u32,
+        pool_a_bitmap: Option<&TickBitmapData>,
+        pool_b_sqrt_price: U256,
+        pool_b_liquidity: u128,
+        pool_b_tick: i32,
+        pool_b_fee_pips: u32,
+        pool_b_bitmap: Option<&TickBitmapData>,
+        amount_wei: U256,
+        uni_zero_for_one: bool,
+        aero_zero_for_one: bool,
+    ) -> Option<(ExactSwapResult, ExactSwapResult)> {
+        if amount_wei.is_zero() {
+            return None;
         }
 
         // Adım 1: UniV3 flash swap
@@ -1717,7 +2986,7 @@ pub mod exact {
         );
 
         if univ3_result.amount_out.is_zero() {
-            return U256::ZERO;
+            return None;
         }
 
         // Adım 2: Slipstream swap
@@ -1732,14 +3001,107 @@ pub mod exact {
             pool_b_bitmap,
         );
 
-        // Adım 3: Kâr = Slipstream çıktısı - UniV3'e borç
-        // Kontrat akışı: balAfter(owedToken) - balBefore(owedToken)
-        // owed_output (Slipstream'den) - amount_wei (UniV3'e ödeme)
-        if slipstream_result.amount_out > amount_wei {
-            slipstream_result.amount_out - amount_wei
-        } else {
-            U256::ZERO
+        Some((univ3_result, slipstream_result))
+    }
+
+    // ── Exact Optimum Arama ─────────────────────────────────────────────────
+
+    /// Kaba tarama nokta sayısı (aralık EXACT_COARSE_STEPS eşit parçaya bölünür)
+    const EXACT_COARSE_STEPS: u32 = 16;
+
+    /// Toplam kâr değerlendirmesi üst sınırı (tarama + altın oran + ipucu)
+    const EXACT_MAX_EVALUATIONS: u32 = 128;
+
+    /// U256 exact optimum: `[min_wei, max_wei]` aralığında kaba tarama, en iyi
+    /// noktanın komşu aralığında altın oran (golden-section) araması.
+    ///
+    /// Amaç fonksiyonu `compute_exact_directional_profit` — minProfit'i üreten
+    /// swap yolunun kendisi (owedToken wei, gas hariç). İki bacağın çıktısı
+    /// girdide konkav olduğundan (tick geçişleri dahil) kâr tek tepelidir.
+    /// `hint` (ör. f64 optimumu) aralıktaysa aday olarak değerlendirilir —
+    /// sonuç onun exact kârından düşük olamaz.
+    ///
+    /// # Dönüş
+    /// (optimal_amount_wei, expected_profit_wei, iterations). Kâr yoksa
+    /// miktar ve kâr sıfır; `iterations` kâr fonksiyonu çağrı sayısıdır.
+    #[allow(clippy::too_many_arguments)]
+    pub fn find_optimal_amount_exact(
+        pool_a_sqrt_price: U256,
+        pool_a_liquidity: u128,
+        pool_a_tick: i32,
+        pool_a_fee_pips: u32,
+        pool_a_bitmap: Option<&TickBitmapData>,
+        pool_b_sqrt_price: U256,
+        pool_b_liquidity: u128,
+        pool_b_tick: i32,
+        pool_b_fee_pips: u32,
+        pool_b_bitmap: Option<&TickBitmapData>,
+        uni_zero_for_one: bool,
+        aero_zero_for_one: bool,
+        min_wei: U256,
+        max_wei: U256,
+        hint: Option<U256>,
+    ) -> (U256, U256, u32) {
+        if max_wei <= min_wei {
+            return (U256::ZERO, U256::ZERO, 0);
+        }
+
+        let mut best = (U256::ZERO, U256::ZERO);
+        let mut profit_at = |amount: U256| {
+            let profit = compute_exact_directional_profit(
+                pool_a_sqrt_price, pool_a_liquidity, pool_a_tick, pool_a_fee_pips, pool_a_bitmap,
+                pool_b_sqrt_price, pool_b_liquidity, pool_b_tick, pool_b_fee_pips, pool_b_bitmap,
+                amount, uni_zero_for_one, aero_zero_for_one,
+            );
+            if profit > best.1 {
+                best = (amount, profit);
+            }
+            profit
+        };
+
+        // Kaba tarama: tepeyi içeren komşu aralığı bul
+        let span: U256 = max_wei - min_wei;
+        let steps = U256::from(EXACT_COARSE_STEPS);
+        let grid = |i: u32| min_wei + span * U256::from(i) / steps;
+        let (mut best_step, mut best_profit) = (0u32, U256::ZERO);
+        for i in 0..=EXACT_COARSE_STEPS {
+            let profit = profit_at(grid(i));
+            if profit > best_profit {
+                (best_step, best_profit) = (i, profit);
+            }
         }
+
+        // Altın oran: her adımda aralık ~%38.2 daralır, bir değerlendirme yeniden kullanılır.
+        // Tolerans aralığın 2^-40'ı (100 WETH'te ~1e-10 WETH); en az 3 wei ki
+        // tamsayı bölme iç noktaları uçlara yapıştırmasın.
+        let mut lo = grid(best_step.saturating_sub(1));
+        let mut hi = grid((best_step + 1).min(EXACT_COARSE_STEPS));
+        let tolerance = (span >> 40usize).max(U256::from(3u64));
+        let golden = |width: U256| width * U256::from(381_966u64) / U256::from(1_000_000u64);
+        let (mut x1, mut x2) = (lo + golden(hi - lo), hi - golden(hi - lo));
+        let (mut f1, mut f2) = (profit_at(x1), profit_at(x2));
+        let mut iterations = EXACT_COARSE_STEPS + 3;
+        while hi - lo > tolerance && iterations < EXACT_MAX_EVALUATIONS - 1 {
+            iterations += 1;
+            if f1 < f2 {
+                lo = x1;
+                (x1, f1) = (x2, f2);
+                x2 = hi - golden(hi - lo);
+                f2 = profit_at(x2);
+            } else {
+                hi = x2;
+                (x2, f2) = (x1, f1);
+                x1 = lo + golden(hi - lo);
+                f1 = profit_at(x1);
+            }
+        }
+
+        if let Some(amount) = hint.filter(|h| (min_wei..=max_wei).contains(h)) {
+            profit_at(amount);
+            iterations += 1;
+        }
+
+        (best.0, best.1, iterations)
     }
 
     // ── Dönüşüm Yardımcıları ────────────────────────────────────────────────
@@ -1782,11 +3144,8 @@ pub mod exact {
     /// Multi-hop shadow-mode doğrulamasında kullanılır.
     #[inline]
     pub fn f64_to_u256_wei(weth_f64: f64) -> U256 {
-        if weth_f64 <= 0.0 {
-            return U256::ZERO;
-        }
-        let wei = weth_f64 * 1e18;
-        U256::from(wei as u128)
+        // Geçersiz (NaN/negatif/u128 dışı) miktar sıfır — kırpılmış değer değil
+        crate::types::weth_to_wei(weth_f64).unwrap_or_default()
     }
 
     /// Fee fraction (ör: 0.0005) → fee pips (ör: 500, 1e6 bazında).
@@ -1820,8 +3179,6 @@ pub mod exact {
         }
 
         // Tick'i Uniswap V3 geçerli aralığa kısıtla
-        const MIN_TICK: i32 = -887272;
-        const MAX_TICK: i32 = 887272;
         let clamped_tick = current_tick.clamp(MIN_TICK, MAX_TICK);
 
         // Mevcut tick aralığının alt ve üst sınırlarını bul
@@ -1861,6 +3218,60 @@ pub mod exact {
         u256_to_f64(capacity_raw) / 1e18
     }
 
+    /// Likidite tükenme kapasitesi — `max_safe_swap_amount_u256`'nın bitmap'li eşi.
+    ///
+    /// Swap yönündeki her aralığın girdi kapasitesi (fee dahil, ham birim) aktif
+    /// likidite sıfıra inene kadar toplanır. Bu miktar girilirse swap tam
+    /// tükenme noktasında InputExhausted ile biter; fazlası LiquidityExhausted olur.
+    ///
+    /// # Dönüş
+    /// Bilinen tick'ler içinde (MAX_SWAP_CROSSINGS geçiş dahilinde) likidite
+    /// sıfıra inmiyorsa `None` — kapasite bitmap'ten belirlenemez.
+    pub fn liquidity_exhaustion_capacity(
+        sqrt_price_x96: U256,
+        liquidity: u128,
+        zero_for_one: bool,
+        fee_pips: u32,
+        sorted_ticks: &[(i32, i128, U256)],
+    ) -> Option<U256> {
+        if liquidity == 0 || sqrt_price_x96.is_zero() {
+            return Some(U256::ZERO);
+        }
+
+        let one_minus_fee = U256::from(1_000_000u64 - fee_pips as u64);
+        let price_limit = if zero_for_one { MIN_SQRT_RATIO_PLUS_1 } else { MAX_SQRT_RATIO_MINUS_1 };
+        let mut state_sqrt_price = clamp_sqrt_price(sqrt_price_x96);
+        let mut state_liquidity = liquidity;
+        let mut capacity = U256::ZERO;
+
+        for &(_tick, liquidity_net, sqrt_price_target) in sorted_ticks.iter().take(MAX_SWAP_CROSSINGS as usize) {
+            let step_target = if zero_for_one {
+                sqrt_price_target.max(price_limit)
+            } else {
+                sqrt_price_target.min(price_limit)
+            };
+            // Fiyat limitinde tick geçilmez — swap orada durur
+            if step_target != sqrt_price_target {
+                return None;
+            }
+
+            // compute_swap_step'in tam-aralık girdisi + fee'si (aynı yuvarlama)
+            let amount_in = if zero_for_one {
+                get_amount0_delta(sqrt_price_target, state_sqrt_price, state_liquidity, true)
+            } else {
+                get_amount1_delta(state_sqrt_price, sqrt_price_target, state_liquidity, true)
+            };
+            capacity += amount_in + mul_div_rounding_up(amount_in, U256::from(fee_pips), one_minus_fee);
+            state_sqrt_price = sqrt_price_target;
+
+            state_liquidity = cross_tick(state_liquidity, liquidity_net, zero_for_one);
+            if state_liquidity == 0 {
+                return Some(capacity);
+            }
+        }
+        None
+    }
+
     /// Hard Liquidity Cap — TickBitmap'ten gerçek mevcut likiditeyi hesapla.
     ///
     /// Bu fonksiyon, mevcut tick'ten itibaren swap yönündeki tüm başlatılmış
@@ -1911,7 +3322,7 @@ pub mod exact {
 
             if zero_for_one {
                 ticks.retain(|(t, _)| *t <= current_tick);
-                ticks.sort_by(|a, b| b.0.cmp(&a.0)); // büyükten küçüğe
+                ticks.sort_by_key(|(t, _)| std::cmp::Reverse(*t)); // büyükten küçüğe
             } else {
                 ticks.retain(|(t, _)| *t > current_tick);
                 ticks.sort_by_key(|(t, _)| *t); // küçükten büyüğe
@@ -1952,16 +3363,7 @@ pub mod exact {
             state_sqrt_price = sqrt_price_target;
 
             // Tick sınırında likiditeyi güncelle
-            if zero_for_one {
-                if state_liquidity as i128 >= liquidity_net {
-                    state_liquidity = (state_liquidity as i128 - liquidity_net) as u128;
-                } else {
-                    state_liquidity = 0;
-                }
-            } else {
-                let new_liq = state_liquidity as i128 + liquidity_net;
-                state_liquidity = if new_liq > 0 { new_liq as u128 } else { 0 };
-            }
+            state_liquidity = cross_tick(state_liquidity, liquidity_net, zero_for_one);
         }
 
         // Son tick'ten sonra kalan likiditede de bir miktar daha absorbe edilebilir
@@ -1985,7 +3387,8 @@ pub mod exact {
     #[cfg(test)]
     mod exact_tests {
         use super::*;
-        use alloy::primitives::U256;
+        use alloy::primitives::{U256, U512};
+        use proptest::prelude::*;
 
         #[test]
         fn test_get_sqrt_ratio_at_tick_zero() {
@@ -2003,6 +3406,142 @@ pub mod exact {
             assert!(min_ratio < max_ratio, "min < max");
         }
 
+        /// Uniswap V3 TickMath.getSqrtRatioAtTick referans vektörleri (tick, sqrtPriceX96).
+        /// Solidity algoritmasıyla 256-bit tam sayı aritmetiğinde üretildi ve
+        /// 120 basamak Decimal sqrt(1.0001^tick)·2^96 ile çapraz kontrol edildi.
+        /// MIN/MAX ve MIN+1/MAX-1 değerleri v3-core TickMath testleriyle birebir aynıdır.
+        const TICK_MATH_REFERENCE: &[(i32, &str)] = &[
+                (-887272, "4295128739"),
+                (-887271, "4295343490"),
+                (-887270, "4295558252"),
+                (-887220, "4306310044"),
+                (-800000, "337263108622"),
+                (-600000, "7425001144658883"),
+                (-443636, "18447090764788882728"),
+                (-400000, "163464786360687385626"),
+                (-276330, "79204503519858955838074"),
+                (-276320, "79244113692861321940131"),
+                (-250000, "295440463448801648376846"),
+                (-200000, "3598751819609688046946419"),
+                (-198080, "3961339671291671466576572"),
+                (-197000, "4181121738663038312290666"),
+                (-100000, "533968626430936354154228408"),
+                (-69082, "2505290050365003892876723467"),
+                (-50000, "6504256538020985011912221507"),
+                (-23028, "25052894984021797146183221489"),
+                (-10000, "48055510970269007215549348797"),
+                (-1000, "75364347830767020784054125655"),
+                (-500, "77272108795590369356373805297"),
+                (-200, "78439868342809377387252074393"),
+                (-60, "78990846045029531151608375686"),
+                (-50, "79030349367926598376800521322"),
+                (-10, "79188560314459151373725315960"),
+                (-2, "79220240490215316061937756561"),
+                (-1, "79224201403219477170569942574"),
+                (0, "79228162514264337593543950336"),
+                (1, "79232123823359799118286999568"),
+                (2, "79236085330515764027303304732"),
+                (10, "79267784519130042428790663799"),
+                (50, "79426470787362580746886972461"),
+                (60, "79466191966197645195421774833"),
+                (200, "80024378775772204256025656563"),
+                (500, "81233731461783161732293370115"),
+                (1000, "83290069058676223003182343270"),
+                (10000, "130621891405341611593710811006"),
+                (23028, "250553947533412109193337304115"),
+                (50000, "965075977353221155028623082916"),
+                (69082, "2505538923316343871269983126944"),
+                (100000, "11755562826496067164730007768450"),
+                (195000, "1358435673239453248152483143175383"),
+                (200000, "1744244129640337381386292603617838"),
+                (250000, "21246587762933397357449903968194344"),
+                (276320, "79212214546506452527748886075123928"),
+                (400000, "38400329974042030913961448288742562464"),
+                (443636, "340275971719517849884101479065584693834"),
+                (600000, "845400776793423922697130608897531771147615"),
+                (800000, "18611883644907511909590774894315720731532604461"),
+                (887220, "1457652066949847389969617340386294118487833376468"),
+                (887270, "1461300573427867316570072651998408279850435624081"),
+                (887271, "1461373636630004318706518188784493106690254656249"),
+                (887272, "1461446703485210103287273052203988822378723970342"),
+        ];
+
+        #[test]
+        fn test_get_sqrt_ratio_at_tick_reference_vectors() {
+            assert!(TICK_MATH_REFERENCE.len() >= 50);
+            for &(tick, expected) in TICK_MATH_REFERENCE {
+                let expected = U256::from_str_radix(expected, 10).unwrap();
+                assert_eq!(
+                    get_sqrt_ratio_at_tick(tick),
+                    expected,
+                    "tick={} → sqrtPriceX96 referans değerle birebir eşleşmeli",
+                    tick
+                );
+            }
+            assert_eq!(get_sqrt_ratio_at_tick(MIN_TICK), MIN_SQRT_RATIO);
+            assert_eq!(get_sqrt_ratio_at_tick(MAX_TICK), MAX_SQRT_RATIO);
+        }
+
+        #[test]
+        fn test_get_sqrt_ratio_out_of_range_clamped_not_panic() {
+            let before = tick_clamp_events();
+            assert_eq!(get_sqrt_ratio_at_tick(900_000), MAX_SQRT_RATIO);
+            assert_eq!(get_sqrt_ratio_at_tick(-900_000), MIN_SQRT_RATIO);
+            assert_eq!(get_sqrt_ratio_at_tick(i32::MIN), MIN_SQRT_RATIO);
+            assert!(tick_clamp_events() >= before + 3, "Kısıtlama bayrağı artmalı");
+        }
+
+        #[test]
+        fn test_exact_swap_corrupted_tick_stays_within_limits() {
+            use std::collections::HashMap;
+            use crate::types::TickInfo;
+
+            // Bozuk bitmap: aralık dışı tick'ler (900000 / -900000)
+            let mut ticks = HashMap::new();
+            for t in [-900_000i32, 900_000] {
+                ticks.insert(t, TickInfo {
+                    liquidity_gross: 1_000_000_000_000_000_000u128,
+                    liquidity_net: 1_000_000_000_000_000_000i128,
+                    initialized: true,
+                    staked_liquidity_net: 0,
+                });
+            }
+            let bitmap = TickBitmapData {
+                words: HashMap::new(),
+                ticks,
+                snapshot_block: 0,
+                sync_duration_us: 0,
+                scan_range: 500,
+                scanned_ticks: None,
+                staked: None,
+                last_full_sync_block: 0,
+            };
+
+            for zero_for_one in [true, false] {
+                let result = compute_exact_swap(
+                    get_sqrt_ratio_at_tick(0), 1_000_000_000_000_000_000u128, 0,
+                    U256::MAX >> 1, zero_for_one, 500, Some(&bitmap),
+                );
+                assert!(result.amount_out > U256::ZERO);
+
+                // Sınır dışındaki başlangıç fiyatı da kısıtlanmalı (panic yok)
+                let _ = compute_exact_swap(
+                    U256::from(1u64), 1_000_000_000_000_000_000u128, MIN_TICK,
+                    U256::from(1_000_000u64), zero_for_one, 500, Some(&bitmap),
+                );
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn prop_get_sqrt_ratio_any_i32_no_panic(tick in any::<i32>()) {
+                let ratio = get_sqrt_ratio_at_tick(tick);
+                prop_assert!(ratio >= MIN_SQRT_RATIO && ratio <= MAX_SQRT_RATIO);
+                let clamped = get_sqrt_ratio_at_tick(tick.clamp(MIN_TICK, MAX_TICK));
+                prop_assert_eq!(ratio, clamped);
+            }
+        }
+
         #[test]
         fn test_get_sqrt_ratio_negative_tick() {
             let ratio_neg = get_sqrt_ratio_at_tick(-1);
@@ -2075,6 +3614,7 @@ pub mod exact {
                                    else if i > 0 { -5_000_000_000_000_000_000i128 }
                                    else { 0 },
                     initialized: true,
+                    staked_liquidity_net: 0,
                 });
             }
             let bitmap = TickBitmapData {
@@ -2083,6 +3623,9 @@ pub mod exact {
                 snapshot_block: 0,
                 sync_duration_us: 0,
                 scan_range: 500,
+                scanned_ticks: None,
+                staked: None,
+                last_full_sync_block: 0,
             };
 
             let amount = U256::from(5_000_000_000_000_000_000u128); // 5 WETH
@@ -2097,5 +3640,1142 @@ pub mod exact {
                 result.amount_out
             );
         }
+
+        /// Ayrıştırmalı exact kâr düz varyantla aynı kârı verir; bileşenler
+        /// net'e toplanır — iki swap yönünde ve kârsız kenarda
+        #[test]
+        fn test_exact_directional_profit_attributed_matches_plain() {
+            let costs = super::super::AttributionCosts { flash_fee_weth: 0.0, gas_weth: 0.0001 };
+            let liquidity: u128 = 50_000_000_000_000_000_000;
+            let amount = U256::from(1_000_000_000_000_000_000u128);
+            // (tick_a, tick_b, uni_zfo, aero_zfo): B, A'nın ters yönünde ~%2 avantajlı.
+            // owedToken = A'nın girdisi; token1 girdisinde ~1 token0 karşılığı kullanılır
+            for (tick_a, tick_b, uni_zfo, aero_zfo) in [
+                (-200_000, -200_200, true, false),
+                (-200_000, -199_800, false, true),
+            ] {
+                let token1_per_token0 = 1.0001f64.powi(tick_a);
+                let (amount_in, owed_unit_weth) = if uni_zfo {
+                    (amount, 1e-18)
+                } else {
+                    (U256::from((1e18 * token1_per_token0) as u128), 1e-18 / token1_per_token0)
+                };
+                let args = (get_sqrt_ratio_at_tick(tick_a), get_sqrt_ratio_at_tick(tick_b));
+                let plain = compute_exact_directional_profit(
+                    args.0, liquidity, tick_a, 500, None,
+                    args.1, liquidity, tick_b, 500, None,
+                    amount_in, uni_zfo, aero_zfo,
+                );
+                let (profit, breakdown) = compute_exact_directional_profit_attributed(
+                    args.0, liquidity, tick_a, 500, None,
+                    args.1, liquidity, tick_b, 500, None,
+                    amount_in, uni_zfo, aero_zfo, owed_unit_weth, costs,
+                );
+                let b = breakdown.expect("swap yapıldı");
+                assert_eq!(profit, plain);
+                assert!(profit > U256::ZERO);
+                assert!((b.component_sum() - b.net_weth).abs() < 1e-12, "{:?}", b);
+                let profit_weth = u256_to_f64(profit) * owed_unit_weth;
+                assert!((b.net_weth - (profit_weth - 0.0001)).abs() < 1e-12);
+                assert!((b.fee_leg1_weth - 0.0005).abs() < 1e-8, "~1 WETH × %0.05: {:?}", b);
+                assert!(b.fee_leg2_weth > 0.0004 && b.fee_leg2_weth < 0.0006, "{:?}", b);
+            }
+
+            // Kârsız yön: kâr 0, ayrıştırma net < 0 ile yine döner
+            let sqrt = get_sqrt_ratio_at_tick(-200_000);
+            let (profit, breakdown) = compute_exact_directional_profit_attributed(
+                sqrt, liquidity, -200_000, 500, None,
+                sqrt, liquidity, -200_000, 500, None,
+                amount, true, false, 1e-18, costs,
+            );
+            let b = breakdown.unwrap();
+            assert_eq!(profit, U256::ZERO);
+            assert!(b.net_weth < 0.0 && (b.component_sum() - b.net_weth).abs() < 1e-12);
+
+            let (profit, breakdown) = compute_exact_directional_profit_attributed(
+                sqrt, liquidity, -200_000, 500, None,
+                sqrt, liquidity, -200_000, 500, None,
+                U256::ZERO, true, false, 1e-18, costs,
+            );
+            assert_eq!((profit, breakdown), (U256::ZERO, None));
+        }
+
+        #[test]
+        fn test_get_tick_at_sqrt_ratio_roundtrip() {
+            for tick in [MIN_TICK + 1, -276330, -197310, -1, 0, 1, 12345, 276330, MAX_TICK - 1] {
+                let sqrt = get_sqrt_ratio_at_tick(tick);
+                assert_eq!(get_tick_at_sqrt_ratio(sqrt), tick, "tam sınır");
+                assert_eq!(get_tick_at_sqrt_ratio(sqrt + U256::from(1u64)), tick, "sınırın hemen üstü");
+                assert_eq!(get_tick_at_sqrt_ratio(sqrt - U256::from(1u64)), tick - 1, "sınırın hemen altı");
+            }
+            // zeroForOne ile tam sınıra inen fiyat: sınır tick'i geçilmiş sayılır
+            let sqrt = get_sqrt_ratio_at_tick(-100);
+            assert_eq!(tick_after_swap(sqrt, true), -101);
+            assert_eq!(tick_after_swap(sqrt, false), -100);
+        }
+
+        /// Tüm tick aralığında (uçlar dahil) tick → sqrtPrice → tick gidiş-dönüşü
+        #[test]
+        fn test_get_tick_at_sqrt_ratio_full_range() {
+            for tick in (MIN_TICK..=MAX_TICK).step_by(997).chain([MIN_TICK, MIN_TICK + 1, -1, 0, 1, MAX_TICK - 1, MAX_TICK]) {
+                assert_eq!(get_tick_at_sqrt_ratio(get_sqrt_ratio_at_tick(tick)), tick, "tick {}", tick);
+            }
+            assert_eq!(get_tick_at_sqrt_ratio(MIN_SQRT_RATIO), MIN_TICK);
+            assert_eq!(get_tick_at_sqrt_ratio(MAX_SQRT_RATIO - U256::from(1u64)), MAX_TICK - 1);
+        }
+
+        /// ExactSwapResult::final_tick son fiyatın tick'i; swap yoksa havuz tick'i
+        #[test]
+        fn test_exact_swap_reports_final_tick() {
+            let tick = -197_310;
+            let sqrt_price = get_sqrt_ratio_at_tick(tick);
+            let liquidity: u128 = 500_000_000_000_000_000;
+            let bitmap = TickBitmapData {
+                ticks: (-20..=20)
+                    .filter(|&i| i != 0)
+                    .map(|i| {
+                        let info = crate::types::TickInfo { liquidity_gross: 1, liquidity_net: 0, initialized: true, staked_liquidity_net: 0 };
+                        ((tick.div_euclid(10) + i) * 10, info)
+                    })
+                    .collect(),
+                ..TickBitmapData::empty()
+            };
+            for zero_for_one in [true, false] {
+                let amount = if zero_for_one {
+                    U256::from(20_000_000_000_000_000_000u128) // 20 WETH
+                } else {
+                    U256::from(50_000_000_000u64) // 50k USDC
+                };
+                let result = compute_exact_swap(sqrt_price, liquidity, tick, amount, zero_for_one, 500, Some(&bitmap));
+                assert!(result.tick_crossings > 0, "çok tick geçen swap");
+                assert_eq!(result.final_tick, tick_after_swap(result.sqrt_price_x96_after, zero_for_one));
+                assert!(get_sqrt_ratio_at_tick(result.final_tick) <= result.sqrt_price_x96_after);
+                assert!(result.sqrt_price_x96_after <= get_sqrt_ratio_at_tick(result.final_tick + 1));
+                if zero_for_one {
+                    assert!(result.final_tick < tick);
+                } else {
+                    assert!(result.final_tick > tick);
+                }
+            }
+            let idle = compute_exact_swap(sqrt_price, liquidity, tick + 3, U256::ZERO, true, 500, Some(&bitmap));
+            assert_eq!(idle.final_tick, tick + 3);
+        }
+
+        fn make_weth_usdc(tick: i32, bitmap: Option<TickBitmapData>) -> (crate::types::PoolState, crate::types::PoolConfig) {
+            let sqrt_price_x96 = get_sqrt_ratio_at_tick(tick);
+            let liquidity: u128 = 50_000_000_000_000_000_000;
+            let sqrt_price_f64 = u256_to_f64(sqrt_price_x96);
+            let state = crate::types::PoolState {
+                sqrt_price_x96,
+                sqrt_price_f64,
+                tick,
+                liquidity,
+                liquidity_f64: liquidity as f64,
+                eth_price_usd: crate::math::compute_eth_price(sqrt_price_f64, tick, 18, 6, true),
+                last_block: 100,
+                tick_bitmap: bitmap.map(Box::new),
+                ..Default::default()
+            };
+            let pool = crate::types::PoolConfig {
+                address: alloy::primitives::Address::ZERO,
+                name: "WETH/USDC-test".into(),
+                fee: crate::types::FeeTier::from_bps(5),
+                token0_decimals: 18,
+                token1_decimals: 6,
+                dex: crate::types::DexType::UniswapV3,
+                token0_is_weth: true,
+                tick_spacing: 10,
+                quote_token_address: alloy::primitives::Address::ZERO,
+                base_token_address: alloy::primitives::Address::ZERO,
+            };
+            (state, pool)
+        }
+
+        /// %0.05 havuz regresyonu: config → pips → exact motor zinciri sabitlenir.
+        /// Birim kayması (bps'i pips sanmak = %0.0005, ×100 fazladan = %5) çıktıyı
+        /// belirgin biçimde değiştirir ve bu test yüksek sesle kırılır.
+        #[test]
+        fn test_exact_output_pinned_for_five_bps_pool() {
+            let (state, pool) = make_weth_usdc(-197310, None);
+            let one_weth = U256::from(1_000_000_000_000_000_000u128);
+            assert_eq!(pool.fee_pips(), 500);
+            assert_eq!(state.sqrt_price_x96, U256::from(4_116_817_214_310_767_641_906_135u128));
+
+            let (after, out) = advance_pool_state(&state, &pool, one_weth, true);
+            assert_eq!(out, U256::from(2_698_648_676u64), "1 WETH → 2698.648676 USDC");
+            assert_eq!(after.sqrt_price_x96, U256::from(4_116_812_938_131_249_895_226_756u128));
+
+            let direct = compute_exact_swap(
+                state.sqrt_price_x96, state.liquidity, state.tick, one_weth, true, pool.fee_pips(), None,
+            );
+            assert_eq!(direct.fee_paid, U256::from(500_000_000_000_000u64), "fee = girdinin %0.05'i");
+
+            // Yanlış birimlerin üreteceği değerler — pinlenen sonuçtan ayrışmalı
+            let as_bps = compute_exact_swap(
+                state.sqrt_price_x96, state.liquidity, state.tick, one_weth, true, pool.fee_bps(), None,
+            );
+            assert_eq!(as_bps.amount_out, U256::from(2_699_985_174u64));
+            let times_100 = compute_exact_swap(
+                state.sqrt_price_x96, state.liquidity, state.tick, one_weth, true, pool.fee_pips() * 100, None,
+            );
+            assert_eq!(times_100.amount_out, U256::from(2_564_998_873u64));
+        }
+
+        /// advance_pool_state: çıktı compute_exact_swap ile birebir, fiyat doğru yönde,
+        /// ardışık swap'lar tek swap'a (yuvarlama payıyla) eşdeğer.
+        #[test]
+        fn test_advance_pool_state_no_bitmap() {
+            let (state, pool) = make_weth_usdc(-197310, None);
+            let one_weth = U256::from(1_000_000_000_000_000_000u128);
+
+            let (after, out) = advance_pool_state(&state, &pool, one_weth, true);
+            let direct = compute_exact_swap(
+                state.sqrt_price_x96, state.liquidity, state.tick, one_weth, true, 500, None,
+            );
+            assert_eq!(out, direct.amount_out);
+            assert!(after.sqrt_price_x96 < state.sqrt_price_x96, "WETH satışı fiyatı düşürür");
+            assert!(after.tick <= state.tick);
+            assert!(after.eth_price_usd < state.eth_price_usd);
+            assert_eq!(after.liquidity, state.liquidity, "tick geçilmedi");
+            assert_eq!(after.tick, get_tick_at_sqrt_ratio(after.sqrt_price_x96));
+            assert_eq!(after.last_block, state.last_block);
+
+            // USDC ile geri al → fiyat yükselir
+            let (back, weth_out) = advance_pool_state(&after, &pool, out, false);
+            assert!(back.sqrt_price_x96 > after.sqrt_price_x96);
+            assert!(weth_out < one_weth, "fee iki kez ödenir");
+
+            // 1 + 1 WETH ≈ 2 WETH
+            let (_, out_second) = advance_pool_state(&after, &pool, one_weth, true);
+            let (_, out_double) = advance_pool_state(&state, &pool, one_weth * U256::from(2u64), true);
+            let chained = out + out_second;
+            let diff = if chained > out_double { chained - out_double } else { out_double - chained };
+            assert!(diff <= U256::from(10u64), "chained={} single={}", chained, out_double);
+
+            // Sıfır girdi → state değişmez
+            let (same, zero) = advance_pool_state(&state, &pool, U256::ZERO, true);
+            assert!(zero.is_zero());
+            assert_eq!(same.sqrt_price_x96, state.sqrt_price_x96);
+            assert_eq!(same.tick, state.tick);
+        }
+
+        /// shift_pool_state_ticks: fiyat swap yönünde tam 1.0001^n oranında kayar
+        #[test]
+        fn test_shift_pool_state_ticks() {
+            let (state, pool) = make_weth_usdc(-197310, None);
+            let price_ratio = |a: &crate::types::PoolState, b: &crate::types::PoolState| {
+                (u256_to_f64(a.sqrt_price_x96) / u256_to_f64(b.sqrt_price_x96)).powi(2)
+            };
+
+            for n in [1u32, 2, 3] {
+                let down = shift_pool_state_ticks(&state, &pool, n, true);
+                let moved = price_ratio(&state, &down);
+                assert!((moved - 1.0001f64.powi(n as i32)).abs() < 1e-9, "n={} down={}", n, moved);
+                assert!(down.eth_price_usd < state.eth_price_usd, "WETH satışı fiyatı düşürür");
+                assert_eq!(down.tick, get_tick_at_sqrt_ratio(down.sqrt_price_x96));
+
+                let up = shift_pool_state_ticks(&state, &pool, n, false);
+                let moved = price_ratio(&up, &state);
+                assert!((moved - 1.0001f64.powi(n as i32)).abs() < 1e-9, "n={} up={}", n, moved);
+                assert!(up.eth_price_usd > state.eth_price_usd);
+            }
+
+            // 0 tick ya da likiditesiz havuz → state değişmez
+            assert_eq!(shift_pool_state_ticks(&state, &pool, 0, true).sqrt_price_x96, state.sqrt_price_x96);
+            let dry = crate::types::PoolState { liquidity: 0, ..state.clone() };
+            assert_eq!(shift_pool_state_ticks(&dry, &pool, 1, true).sqrt_price_x96, state.sqrt_price_x96);
+        }
+
+        /// make_weth_usdc havuzunun REVM fixture'ı: aynı fee / spacing, çakışmayan
+        /// havuz ve token adresleri (sahte token'lar havuzun üstüne yazılmasın)
+        fn fixture_builder() -> crate::v3_fixture::V3PoolBuilder {
+            use alloy::primitives::address;
+            let (_, pool) = make_weth_usdc(0, None);
+            crate::v3_fixture::V3PoolBuilder::new(crate::types::PoolConfig {
+                address: address!("00000000000000000000000000000000000f1f10"),
+                base_token_address: address!("4200000000000000000000000000000000000006"),
+                quote_token_address: address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+                ..pool
+            })
+        }
+
+        /// Fixture havuzunda REVM quote'u (exact-input çıktısı)
+        fn fixture_quote(builder: &crate::v3_fixture::V3PoolBuilder, amount_in: U256, zero_for_one: bool) -> U256 {
+            let (sim, state, _) = builder.build();
+            let pool = builder.pool();
+            sim.quote_swap(pool, &state, amount_in, zero_for_one).unwrap_or_else(|e| panic!("fixture quote: {}", e))
+        }
+
+        /// Tick geçişlerinde likidite liquidityNet kadar güncellenir; aynı
+        /// pozisyonlarla kurulan REVM havuzu aynı çıktıyı verir
+        #[test]
+        fn test_advance_pool_state_crosses_ticks() {
+            let tick = -197310;
+            let step_liquidity = 5_000_000_000_000_000_000u128;
+            // ±10·i simetrik pozisyonlar + tam aralık: tick'te L = 5e19 (make_weth_usdc)
+            let fixture = (1..=5i32)
+                .fold(fixture_builder().at_tick(tick), |b, i| b.position(tick - i * 10, tick + i * 10, step_liquidity))
+                .position(-887_270, 887_270, 5 * step_liquidity);
+            let bitmap = fixture.bitmap();
+            let (state, pool) = make_weth_usdc(tick, Some(bitmap.clone()));
+            assert_eq!(fixture.state().liquidity, state.liquidity);
+
+            // ~%2 fiyat hareketi: soldaki tüm tick'ler geçilir
+            let amount = U256::from(10_000u64) * U256::from(1_000_000_000_000_000_000u128);
+            let (after, out) = advance_pool_state(&state, &pool, amount, true);
+            assert!(out > U256::ZERO);
+
+            let crossed: Vec<i128> = bitmap
+                .ticks
+                .iter()
+                .filter(|(t, _)| **t <= state.tick && **t > after.tick)
+                .map(|(_, info)| info.liquidity_net)
+                .collect();
+            assert!(crossed.len() >= 5, "crossed={}", crossed.len());
+            let expected = state.liquidity as i128 - crossed.iter().sum::<i128>();
+            assert_eq!(after.liquidity as i128, expected);
+            assert!(after.tick < tick - 50);
+            assert!(after.tick_bitmap.is_some(), "bitmap korunur");
+
+            // Geçişler tek bitmap kelimesi içinde: kontrat da yalnızca bu tick'lerde durur
+            assert_eq!(fixture_quote(&fixture, amount, true), out);
+        }
+
+        // ── Likidite tükenmesi ──────────────────────────────────────────
+
+        const EXHAUST_LIQ: u128 = 1_000_000_000_000_000_000; // 1e18
+
+        fn bitmap_from(ticks: &[(i32, i128)]) -> TickBitmapData {
+            use crate::types::TickInfo;
+            let ticks = ticks
+                .iter()
+                .map(|&(t, net)| (t, TickInfo { liquidity_gross: net.unsigned_abs(), liquidity_net: net, initialized: true, staked_liquidity_net: 0 }))
+                .collect();
+            TickBitmapData { ticks, ..TickBitmapData::empty() }
+        }
+
+        /// Tam aralık girdisi + fee (zeroForOne, compute_swap_step yuvarlaması)
+        fn range_input(lower: i32, upper: i32, liquidity: u128, fee_pips: u32) -> U256 {
+            let amount = get_amount0_delta(get_sqrt_ratio_at_tick(lower), get_sqrt_ratio_at_tick(upper), liquidity, true);
+            amount + mul_div_rounding_up(amount, U256::from(fee_pips), U256::from(1_000_000u64 - fee_pips as u64))
+        }
+
+        /// İki aralık (25→20 L, 20→10 L/2) sonra likidite sıfır: kapasite aralık
+        /// toplamı; tam kapasite InputExhausted, fazlası LiquidityExhausted.
+        #[test]
+        fn test_swap_exhausts_liquidity_at_known_input() {
+            let half = EXHAUST_LIQ / 2;
+            // 20 ve 10'da +L/2 (zeroForOne'da düşülür); 30 swap yönünün dışında
+            let fixture = fixture_builder().at_tick(25).position(10, 30, half).position(20, 30, half);
+            let bitmap = fixture.bitmap();
+            assert_eq!(fixture.state().liquidity, EXHAUST_LIQ);
+            let sqrt = get_sqrt_ratio_at_tick(25);
+            let sorted = SortedTicks::from_bitmap(&bitmap, 25);
+
+            let capacity = liquidity_exhaustion_capacity(sqrt, EXHAUST_LIQ, true, 500, sorted.get_ordered(true))
+                .expect("likidite sıfıra iner");
+            let expected = range_input(20, 25, EXHAUST_LIQ, 500) + range_input(10, 20, EXHAUST_LIQ / 2, 500);
+            assert_eq!(capacity, expected);
+
+            let exact_fit = compute_exact_swap(sqrt, EXHAUST_LIQ, 25, capacity, true, 500, Some(&bitmap));
+            assert_eq!(exact_fit.termination_reason, SwapTermination::InputExhausted);
+            assert!(exact_fit.amount_remaining.is_zero());
+            assert_eq!(exact_fit.sqrt_price_x96_after, get_sqrt_ratio_at_tick(10));
+
+            let excess = U256::from(1_000u64);
+            let over = compute_exact_swap(sqrt, EXHAUST_LIQ, 25, capacity + excess, true, 500, Some(&bitmap));
+            assert_eq!(over.termination_reason, SwapTermination::LiquidityExhausted);
+            assert_eq!(over.amount_remaining, excess, "kalan girdi raporlanır");
+            assert_eq!(over.liquidity_after, 0);
+            assert_eq!(over.amount_out, exact_fit.amount_out, "tükenen havuz ek çıktı vermez");
+
+            // Kontrat tam kapasitede tick 10'da durur: çıktı birebir aynı
+            assert_eq!(fixture_quote(&fixture, capacity, true), exact_fit.amount_out);
+        }
+
+        /// Bilinen tick'ler biter ama likidite kalır → kapasite yok, kalan girdi
+        /// son likiditeyle hesaplanır (TickRangeExhausted).
+        #[test]
+        fn test_swap_tick_range_exhausted_keeps_filling() {
+            let half = (EXHAUST_LIQ / 2) as i128;
+            let bitmap = bitmap_from(&[(20, half)]);
+            let sqrt = get_sqrt_ratio_at_tick(25);
+            let sorted = SortedTicks::from_bitmap(&bitmap, 25);
+            assert_eq!(liquidity_exhaustion_capacity(sqrt, EXHAUST_LIQ, true, 500, sorted.get_ordered(true)), None);
+
+            let first_range = range_input(20, 25, EXHAUST_LIQ, 500);
+            let result = compute_exact_swap(
+                sqrt, EXHAUST_LIQ, 25, first_range * U256::from(2u64), true, 500, Some(&bitmap),
+            );
+            assert_eq!(result.termination_reason, SwapTermination::TickRangeExhausted);
+            assert!(result.amount_remaining.is_zero());
+            assert_eq!(result.liquidity_after, EXHAUST_LIQ / 2);
+            assert!(result.sqrt_price_x96_after < get_sqrt_ratio_at_tick(20));
+
+            // Bitmap'siz havuz her zaman bilinen aralığın dışında hesaplanır
+            let no_bitmap = compute_exact_swap(sqrt, EXHAUST_LIQ, 25, first_range, true, 500, None);
+            assert_eq!(no_bitmap.termination_reason, SwapTermination::TickRangeExhausted);
+        }
+
+        #[test]
+        fn test_swap_crossing_cap_and_degenerate_inputs() {
+            // 60 tick, her biri likiditenin çok küçük bir kısmını kapatır
+            let ticks: Vec<(i32, i128)> = (1..=60).map(|i| (-i * 10, 1_000i128)).collect();
+            let bitmap = bitmap_from(&ticks);
+            let sqrt = get_sqrt_ratio_at_tick(0);
+            let sorted = SortedTicks::from_bitmap(&bitmap, 0);
+            assert_eq!(liquidity_exhaustion_capacity(sqrt, EXHAUST_LIQ, true, 500, sorted.get_ordered(true)), None);
+
+            let huge = U256::from(1_000u64) * U256::from(EXHAUST_LIQ);
+            let capped = compute_exact_swap(sqrt, EXHAUST_LIQ, 0, huge, true, 500, Some(&bitmap));
+            assert_eq!(capped.termination_reason, SwapTermination::CrossingCapReached);
+            assert_eq!(capped.liquidity_after, EXHAUST_LIQ - 50 * 1_000);
+
+            let empty_pool = compute_exact_swap(sqrt, 0, 0, huge, true, 500, None);
+            assert_eq!(empty_pool.termination_reason, SwapTermination::LiquidityExhausted);
+            assert_eq!(empty_pool.amount_remaining, huge);
+            assert_eq!(liquidity_exhaustion_capacity(sqrt, 0, true, 500, &[]), Some(U256::ZERO));
+
+            let nothing = compute_exact_swap(sqrt, EXHAUST_LIQ, 0, U256::ZERO, true, 500, None);
+            assert_eq!(nothing.termination_reason, SwapTermination::InputExhausted);
+        }
+
+        // ── Exact modül stres testleri (f64 paketinin karşılığı) ─────────
+        //
+        // minProfit zincire bu modülün çıktısıyla yazılır: özellikler her
+        // fonksiyonu U512 referansına / Uniswap değişmezlerine karşı dener.
+
+        fn to_u512(v: U256) -> U512 {
+            U512::saturating_from_limbs_slice(v.as_limbs())
+        }
+
+        /// floor / ceil(a·b / c) — 512-bit referans, U256'ya sığmazsa MAX
+        fn reference_mul_div(a: U256, b: U256, c: U256, round_up: bool) -> U256 {
+            let product = to_u512(a) * to_u512(b);
+            let mut quotient = product / to_u512(c);
+            if round_up && !(product % to_u512(c)).is_zero() {
+                quotient += U512::from(1u64);
+            }
+            U256::saturating_from_limbs_slice(quotient.as_limbs())
+        }
+
+        /// Rastgele bit uzunluğunda U256 — küçük, orta ve 256-bit değerler eşit sıklıkta
+        fn arb_u256() -> impl Strategy<Value = U256> {
+            (any::<[u64; 4]>(), 0usize..=256).prop_map(|(limbs, bits)| {
+                let value = U256::from_limbs(limbs);
+                if bits == 256 { value } else { value & ((U256::from(1u64) << bits) - U256::from(1u64)) }
+            })
+        }
+
+        /// Phantom overflow bölgesi: a, b < c ama a·b ≥ 2^256
+        fn arb_phantom_overflow() -> impl Strategy<Value = (U256, U256, U256)> {
+            (any::<[u64; 4]>(), any::<[u64; 4]>(), any::<u64>()).prop_map(|(a, b, gap)| {
+                let floor = U256::from(1u64) << 128;
+                let a: U256 = (U256::from_limbs(a) >> 2usize) | floor;
+                let b: U256 = (U256::from_limbs(b) >> 2usize) | floor;
+                (a, b, a.max(b) + U256::from(gap) + U256::from(1u64))
+            })
+        }
+
+        /// Kenar fee'leri (0 ve 1e6 - 1) ağırlıklı fee_pips
+        fn arb_fee_pips() -> impl Strategy<Value = u32> {
+            prop_oneof![
+                1 => Just(0u32),
+                1 => Just(999_999u32),
+                4 => 0u32..1_000_000,
+            ]
+        }
+
+        /// Bozuk bitmap liquidityNet'i: uç değerler + likiditeyi negatife süren değerler
+        fn arb_liquidity_net() -> impl Strategy<Value = i128> {
+            prop_oneof![
+                4 => any::<i128>(),
+                1 => Just(i128::MIN),
+                1 => Just(i128::MAX),
+                2 => -1_000_000_000_000_000_000_000i128..1_000_000_000_000_000_000_000i128,
+            ]
+        }
+
+        #[test]
+        fn test_mul_div_phantom_overflow_terminates() {
+            // Eski rekürsif ayrıştırma bu girdide kendini aynı argümanlarla çağırıyordu
+            let (a, c) = (U256::from(1u64) << 200, U256::from(1u64) << 255);
+            assert_eq!(mul_div(a, a, c), U256::from(1u64) << 145);
+            assert_eq!(mul_div_rounding_up(a, a, c), U256::from(1u64) << 145);
+            assert_eq!(mul_div(U256::MAX, U256::MAX, U256::from(1u64)), U256::MAX);
+            assert_eq!(mul_div_rounding_up(U256::MAX, U256::MAX, U256::from(2u64)), U256::MAX, "MAX + 1 sarmaz");
+            assert_eq!(mul_div(a, a, U256::ZERO), U256::ZERO);
+        }
+
+        #[test]
+        fn test_next_sqrt_price_huge_input_saturates() {
+            let sqrt = get_sqrt_ratio_at_tick(0);
+            // amount1: bölüm doyar, toplam sarmaz
+            assert_eq!(get_next_sqrt_price_from_amount1(sqrt, 1, U256::MAX, true), U256::MAX);
+            // amount0: fallback paydası sarmaz, fiyat tabana iner
+            assert_eq!(get_next_sqrt_price_from_amount0(sqrt, 1, U256::MAX, true), U256::from(1u64));
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+            // ─── TEST E1: mul_div / mul_div_rounding_up = U512 referansı ───
+            #[test]
+            fn stres_exact_mul_div_matches_u512(
+                a in arb_u256(),
+                b in arb_u256(),
+                c in arb_u256(),
+            ) {
+                prop_assume!(!c.is_zero());
+                prop_assert_eq!(mul_div(a, b, c), reference_mul_div(a, b, c, false),
+                    "mul_div({}, {}, {})", a, b, c);
+                prop_assert_eq!(mul_div_rounding_up(a, b, c), reference_mul_div(a, b, c, true),
+                    "mul_div_rounding_up({}, {}, {})", a, b, c);
+            }
+
+            // ─── TEST E2: phantom overflow bölgesi (a, b < c, a·b ≥ 2^256) ───
+            #[test]
+            fn stres_exact_mul_div_phantom_overflow(
+                (a, b, c) in arb_phantom_overflow(),
+            ) {
+                prop_assert_eq!(mul_div(a, b, c), reference_mul_div(a, b, c, false),
+                    "mul_div({}, {}, {})", a, b, c);
+                prop_assert_eq!(mul_div_rounding_up(a, b, c), reference_mul_div(a, b, c, true),
+                    "mul_div_rounding_up({}, {}, {})", a, b, c);
+            }
+
+            // ─── TEST E3: sonraki sqrtPrice — fazla girdi asla daha iyi fiyat vermez ───
+            #[test]
+            fn stres_exact_next_sqrt_price_monotone(
+                tick in MIN_TICK..=MAX_TICK,
+                liquidity in 1u128..=u128::MAX,
+                amount in arb_u256(),
+                extra in arb_u256(),
+            ) {
+                let sqrt = get_sqrt_ratio_at_tick(tick);
+                let more = amount.saturating_add(extra);
+
+                // token0 girdisi fiyatı düşürür
+                let p0 = get_next_sqrt_price_from_amount0(sqrt, liquidity, amount, true);
+                let p0_more = get_next_sqrt_price_from_amount0(sqrt, liquidity, more, true);
+                prop_assert!(p0 <= sqrt, "amount0 fiyatı yükseltti: {} → {}", sqrt, p0);
+                prop_assert!(p0_more <= p0,
+                    "amount0: fazla girdi daha iyi fiyat verdi ({} → {} vs {} → {})", amount, p0, more, p0_more);
+
+                // token1 girdisi fiyatı yükseltir
+                let p1 = get_next_sqrt_price_from_amount1(sqrt, liquidity, amount, true);
+                let p1_more = get_next_sqrt_price_from_amount1(sqrt, liquidity, more, true);
+                prop_assert!(p1 >= sqrt, "amount1 fiyatı düşürdü: {} → {}", sqrt, p1);
+                prop_assert!(p1_more >= p1,
+                    "amount1: fazla girdi daha iyi fiyat verdi ({} → {} vs {} → {})", amount, p1, more, p1_more);
+            }
+
+            // ─── TEST E4: fiyat sınırına kadar olan girdi MIN/MAX_SQRT_RATIO içinde kalır ───
+            #[test]
+            fn stres_exact_next_sqrt_price_bounded(
+                tick in MIN_TICK..=MAX_TICK,
+                liquidity in 1u128..=u128::MAX,
+                fraction in any::<u64>(),
+            ) {
+                let sqrt = get_sqrt_ratio_at_tick(tick);
+                let share = |capacity: U256| mul_div(capacity, U256::from(fraction), U256::from(u64::MAX));
+
+                let to_min = get_amount0_delta(MIN_SQRT_RATIO, sqrt, liquidity, false);
+                let p0 = get_next_sqrt_price_from_amount0(sqrt, liquidity, share(to_min), true);
+                prop_assert!((MIN_SQRT_RATIO..=sqrt).contains(&p0),
+                    "amount0 sınır dışı: tick={} L={} → {}", tick, liquidity, p0);
+
+                let to_max = get_amount1_delta(sqrt, MAX_SQRT_RATIO, liquidity, false);
+                let p1 = get_next_sqrt_price_from_amount1(sqrt, liquidity, share(to_max), true);
+                prop_assert!((sqrt..=MAX_SQRT_RATIO).contains(&p1),
+                    "amount1 sınır dışı: tick={} L={} → {}", tick, liquidity, p1);
+            }
+
+            // ─── TEST E5: compute_swap_step değişmezleri ───
+            #[test]
+            fn stres_exact_swap_step_invariants(
+                tick in (MIN_TICK + 1)..MAX_TICK,
+                distance in 1i32..=20_000,
+                zero_for_one in proptest::bool::ANY,
+                liquidity in prop_oneof![1 => Just(0u128), 9 => any::<u128>()],
+                amount in arb_u256(),
+                extra in arb_u256(),
+                fee_pips in arb_fee_pips(),
+            ) {
+                let current = get_sqrt_ratio_at_tick(tick);
+                let target_tick = if zero_for_one {
+                    (tick - distance).max(MIN_TICK)
+                } else {
+                    (tick + distance).min(MAX_TICK)
+                };
+                let target = get_sqrt_ratio_at_tick(target_tick);
+                let step = compute_swap_step(current, target, liquidity, amount, fee_pips);
+
+                let spent = step.amount_in.checked_add(step.fee_amount);
+                prop_assert!(spent.is_some_and(|spent| spent <= amount),
+                    "amount_in {} + fee {} > amount_remaining {}", step.amount_in, step.fee_amount, amount);
+                let (low, high) = if zero_for_one { (target, current) } else { (current, target) };
+                prop_assert!((low..=high).contains(&step.sqrt_ratio_next),
+                    "sonraki fiyat aralık dışı: {} ∉ [{}, {}]", step.sqrt_ratio_next, low, high);
+
+                if step.sqrt_ratio_next != target {
+                    // Hedefe ulaşılmadı: girdinin tamamı harcanır
+                    prop_assert_eq!(step.amount_in + step.fee_amount, amount);
+                } else if fee_pips == 0 {
+                    prop_assert_eq!(step.fee_amount, U256::ZERO);
+                }
+                if liquidity == 0 {
+                    prop_assert_eq!(step.amount_in, U256::ZERO);
+                    prop_assert_eq!(step.amount_out, U256::ZERO);
+                    prop_assert_eq!(step.fee_amount, U256::ZERO);
+                }
+
+                let bigger = compute_swap_step(current, target, liquidity, amount.saturating_add(extra), fee_pips);
+                prop_assert!(bigger.amount_out >= step.amount_out,
+                    "çıktı girdiyle azaldı: {} → {}", step.amount_out, bigger.amount_out);
+            }
+
+            // ─── TEST E6: compute_exact_swap — başlangıç fiyatında sonsuz likiditeyi aşamaz, panic yok ───
+            #[test]
+            fn stres_exact_swap_bounded_by_start_price(
+                tick in (MIN_TICK + 5_000)..(MAX_TICK - 5_000),
+                liquidity in any::<u128>(),
+                amount_bits in 0usize..=190,
+                amount_limbs in any::<[u64; 4]>(),
+                zero_for_one in proptest::bool::ANY,
+                fee_pips in arb_fee_pips(),
+                with_bitmap in proptest::bool::ANY,
+                ticks in proptest::collection::vec((-5_000i32..=5_000, arb_liquidity_net()), 0..=8),
+            ) {
+                let amount = U256::from_limbs(amount_limbs) & ((U256::from(1u64) << amount_bits) - U256::from(1u64));
+                let sqrt = get_sqrt_ratio_at_tick(tick);
+                let bitmap = bitmap_from(&ticks.iter().map(|&(offset, net)| (tick + offset, net)).collect::<Vec<_>>());
+                let result = compute_exact_swap(
+                    sqrt, liquidity, tick, amount, zero_for_one, fee_pips, with_bitmap.then_some(&bitmap),
+                );
+
+                prop_assert!(result.amount_remaining <= amount);
+                let consumed = to_u512(amount - result.amount_remaining);
+                let (out, price_sq, q192) =
+                    (to_u512(result.amount_out), to_u512(sqrt) * to_u512(sqrt), U512::from(1u64) << 192);
+                // zeroForOne: out ≤ in · P, oneForZero: out ≤ in / P  (P = sqrt² / 2^192)
+                let within = if zero_for_one {
+                    out * q192 <= consumed * price_sq
+                } else {
+                    out.checked_mul(price_sq).is_some_and(|lhs| lhs <= consumed * q192)
+                };
+                prop_assert!(within,
+                    "çıktı başlangıç fiyatını aştı: tick={} L={} in={} out={} zfo={}",
+                    tick, liquidity, amount, result.amount_out, zero_for_one);
+
+                if zero_for_one {
+                    prop_assert!(result.sqrt_price_x96_after <= sqrt);
+                } else {
+                    prop_assert!(result.sqrt_price_x96_after >= sqrt);
+                }
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+//  BÖLÜM: SABİT NOKTA ÖN ELEME — SIZING_NUMERICS=fixed
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+//  Neden? f64 yolunun son bitleri CPU / derleyici / libm'e göre değişebilir
+//  (fused multiply-add, exp/powi farkları): aynı iki kurulum sınırdaki bir
+//  fırsatta farklı karar verebilir. Bu bölüm ön elemeyi tam sayıya taşır:
+//
+//  ✓ Fx: işaretli Q63.64 (i128) — çarpma 128×128 yarım çarpımlarla,
+//    bölme / oran U256 mul_div ile; f64 girdiler kesmeyle (bit-kesin) çevrilir
+//  ✓ Tek aralık swap: aktif likiditeyle tek SwapMath adımı (U256 Q64.96)
+//  ✓ Yürütülebilir fiyat + spread + PreFilter::check_fixed
+//  ✓ NR kaba taraması: tam sayı wei ızgarası, kâr wei olarak karşılaştırılır
+//
+//  Hassasiyet gerektiren ince ayar (secant + exact multi-tick swap) exact
+//  modülünde kalır. f64 yolu varsayılan: sabit nokta yolunun performansı
+//  (hedef: kaba taramada f64'ün en fazla 2 katı) doğrulanana kadar.
+// ═══════════════════════════════════════════════════════════════════════════════
+
+pub mod fixed {
+    use alloy::primitives::U256;
+    use std::ops::{Add, Div, Mul, Sub};
+
+    use super::exact;
+    use crate::types::PoolState;
+
+    /// 2^64 (f64) — Fx kesir ölçeği
+    const SCALE: f64 = 18_446_744_073_709_551_616.0;
+
+    /// 2^64 (U256)
+    const ONE_U256: U256 = U256::from_limbs([0, 1, 0, 0]);
+
+    /// 2^96 — sqrtPriceX96 ölçeği
+    const Q96: U256 = U256::from_limbs([0, 0x1_0000_0000, 0, 0]);
+
+    /// 1 WETH (wei)
+    const WAD: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+    /// 64 bitlik alt yarı maskesi
+    const LO_MASK: u128 = u64::MAX as u128;
+
+    // ── Fx — Q63.64 Sabit Nokta ──────────────────────────────────────────────
+
+    /// İşaretli Q63.64 sabit nokta sayı: değer = bits / 2^64.
+    /// Tüm işlemler doyurucudur (taşma → i128 sınırı), sonuç yalnızca
+    /// tam sayı aritmetiğine bağlıdır.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+    pub struct Fx(i128);
+
+    impl Fx {
+        pub const ZERO: Fx = Fx(0);
+        pub const ONE: Fx = Fx(1 << 64);
+        pub const MAX: Fx = Fx(i128::MAX);
+
+        /// Ham Q63.64 bitleri
+        #[cfg(test)]
+        pub const fn from_bits(bits: i128) -> Fx {
+            Fx(bits)
+        }
+
+        #[cfg(test)]
+        pub const fn to_bits(self) -> i128 {
+            self.0
+        }
+
+        pub const fn from_int(n: i64) -> Fx {
+            Fx((n as i128) << 64)
+        }
+
+        /// f64 → Q63.64: 2^64 ile çarpım yalnızca üssü kaydırır (yuvarlama
+        /// yok), `as` sıfıra doğru keser ve doyurur → her platformda aynı bitler.
+        /// NaN → 0.
+        pub fn from_f64(x: f64) -> Fx {
+            Fx((x * SCALE) as i128)
+        }
+
+        /// Q63.64 → f64 (en yakına yuvarlama — IEEE'de tanımlı, deterministik)
+        pub fn to_f64(self) -> f64 {
+            self.0 as f64 / SCALE
+        }
+
+        /// num / den (floor). den = 0 veya sonuç sığmıyorsa Fx::MAX.
+        pub fn from_ratio(num: U256, den: U256) -> Fx {
+            if den.is_zero() {
+                return Fx::MAX;
+            }
+            Fx::from_magnitude(exact::mul_div(num, ONE_U256, den), false)
+        }
+
+        fn from_magnitude(mag: U256, negative: bool) -> Fx {
+            let mag = u128::try_from(mag)
+                .ok()
+                .and_then(|m| i128::try_from(m).ok())
+                .unwrap_or(i128::MAX);
+            Fx(if negative { -mag } else { mag })
+        }
+
+        pub fn abs_diff(self, rhs: Fx) -> Fx {
+            Fx::from_magnitude(U256::from(self.0.abs_diff(rhs.0)), false)
+        }
+    }
+
+    impl Add for Fx {
+        type Output = Fx;
+        fn add(self, rhs: Fx) -> Fx {
+            Fx(self.0.saturating_add(rhs.0))
+        }
+    }
+
+    impl Sub for Fx {
+        type Output = Fx;
+        fn sub(self, rhs: Fx) -> Fx {
+            Fx(self.0.saturating_sub(rhs.0))
+        }
+    }
+
+    /// self × rhs (|sonuç| floor)
+    impl Mul for Fx {
+        type Output = Fx;
+        fn mul(self, rhs: Fx) -> Fx {
+            let mag = mul_shr64(self.0.unsigned_abs(), rhs.0.unsigned_abs());
+            Fx::from_magnitude(U256::from(mag), (self.0 < 0) != (rhs.0 < 0))
+        }
+    }
+
+    /// self / rhs (|sonuç| floor). rhs = 0 → işarete göre doyar.
+    impl Div for Fx {
+        type Output = Fx;
+        fn div(self, rhs: Fx) -> Fx {
+            let negative = (self.0 < 0) != (rhs.0 < 0);
+            if rhs.0 == 0 {
+                return if negative { Fx(-i128::MAX) } else { Fx::MAX };
+            }
+            let mag = exact::mul_div(
+                U256::from(self.0.unsigned_abs()),
+                ONE_U256,
+                U256::from(rhs.0.unsigned_abs()),
+            );
+            Fx::from_magnitude(mag, negative)
+        }
+    }
+
+    /// (a × b) >> 64, floor — 256 bit ara sonuç 64 bitlik yarılarla:
+    /// a·b = a1b1·2^128 + (a1b0 + a0b1)·2^64 + a0b0. Sığmazsa u128::MAX.
+    fn mul_shr64(a: u128, b: u128) -> u128 {
+        let (a1, a0) = (a >> 64, a & LO_MASK);
+        let (b1, b0) = (b >> 64, b & LO_MASK);
+        let hi = a1 * b1;
+        if hi >> 64 != 0 {
+            return u128::MAX;
+        }
+        (hi << 64)
+            .checked_add(a1 * b0)
+            .and_then(|v| v.checked_add(a0 * b1))
+            .and_then(|v| v.checked_add((a0 * b0) >> 64))
+            .unwrap_or(u128::MAX)
+    }
+
+    // ── Tek Aralık Swap (Q64.96) ─────────────────────────────────────────────
+
+    /// Aktif likiditeyle tek SwapMath adımı (fiyat limitine kadar, tick
+    /// geçişi yok) — `exact::compute_exact_swap_presorted`'un boş tick
+    /// listesiyle verdiği sonucun döngüsüz hali.
+    /// Dönüş: (çıktı, yeni sqrtPriceX96)
+    pub fn single_tick_swap(
+        sqrt_price_x96: U256,
+        liquidity: u128,
+        amount_in: U256,
+        zero_for_one: bool,
+        fee_pips: u32,
+    ) -> (U256, U256) {
+        if amount_in.is_zero() || liquidity == 0 || sqrt_price_x96.is_zero() {
+            return (U256::ZERO, sqrt_price_x96);
+        }
+        let current = exact::clamp_sqrt_price(sqrt_price_x96);
+        let limit = if zero_for_one {
+            exact::MIN_SQRT_RATIO_PLUS_1
+        } else {
+            exact::MAX_SQRT_RATIO_MINUS_1
+        };
+        if current == limit {
+            return (U256::ZERO, current);
+        }
+        let step = exact::compute_swap_step(current, limit, liquidity, amount_in, fee_pips);
+        (step.amount_out, step.sqrt_ratio_next)
+    }
+
+    // ── Yürütülebilir Fiyat + Spread ─────────────────────────────────────────
+
+    /// Referans boyutta WETH başına ortalama quote (insan birimi)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ExecPrice {
+        /// `probe` WETH satınca alınan (0 = satılamaz)
+        pub sell: Fx,
+        /// `probe` WETH almak için ödenen (None = alınamaz)
+        pub buy: Option<Fx>,
+    }
+
+    impl ExecPrice {
+        const UNUSABLE: ExecPrice = ExecPrice { sell: Fx::ZERO, buy: None };
+    }
+
+    /// Spot'ta `weth_wei` eden ham quote (P = (√P / 2^96)², token1/token0)
+    fn spot_quote_raw(sqrt_price_x96: U256, weth_wei: U256, token0_is_weth: bool) -> U256 {
+        if token0_is_weth {
+            exact::mul_div(exact::mul_div(weth_wei, sqrt_price_x96, Q96), sqrt_price_x96, Q96)
+        } else {
+            exact::mul_div(exact::mul_div(weth_wei, Q96, sqrt_price_x96), Q96, sqrt_price_x96)
+        }
+    }
+
+    /// ham quote / WETH wei → WETH başına quote (insan birimi)
+    fn quote_per_weth(quote_raw: U256, weth_wei: U256, quote_decimals: u8) -> Fx {
+        let quote_unit = U256::from(10u128.pow(quote_decimals.min(38) as u32));
+        Fx::from_ratio(quote_raw.saturating_mul(WAD), weth_wei.saturating_mul(quote_unit))
+    }
+
+    /// `strategy::executable_price`'ın sabit nokta karşılığı: satış ve alış
+    /// fiyatı aktif likiditeyle tek adım swap çıktılarından, doğrudan ham
+    /// miktar oranıyla (spot f64'ü kullanılmaz). `probe_wei = 0` → spot.
+    pub fn exec_price(
+        state: &PoolState,
+        token0_is_weth: bool,
+        quote_decimals: u8,
+        probe_wei: U256,
+    ) -> ExecPrice {
+        let sqrt = state.sqrt_price_x96;
+        if sqrt.is_zero() {
+            return ExecPrice::UNUSABLE;
+        }
+        if probe_wei.is_zero() {
+            let spot = quote_per_weth(spot_quote_raw(sqrt, WAD, token0_is_weth), WAD, quote_decimals);
+            return ExecPrice { sell: spot, buy: Some(spot) };
+        }
+        if state.liquidity == 0 {
+            return ExecPrice::UNUSABLE;
+        }
+
+        // WETH → quote
+        let (quote_out, _) = single_tick_swap(sqrt, state.liquidity, probe_wei, token0_is_weth, 0);
+        let sell = quote_per_weth(quote_out, probe_wei, quote_decimals);
+        // quote → WETH: spot'ta probe WETH eden quote ile alınan WETH
+        let quote_in = spot_quote_raw(sqrt, probe_wei, token0_is_weth);
+        let (weth_out, _) = single_tick_swap(sqrt, state.liquidity, quote_in, !token0_is_weth, 0);
+        let buy = (!weth_out.is_zero()).then(|| quote_per_weth(quote_in, weth_out, quote_decimals));
+        ExecPrice { sell, buy }
+    }
+
+    /// `strategy::executable_spread_pct` karşılığı: (satış − alış) / alış × 100,
+    /// alınamıyorsa ya da negatifse 0
+    pub fn spread_pct(buy_pool: &ExecPrice, sell_pool: &ExecPrice) -> Fx {
+        match buy_pool.buy {
+            Some(buy) if buy > Fx::ZERO => {
+                ((sell_pool.sell - buy) / buy * Fx::from_int(100)).max(Fx::ZERO)
+            }
+            _ => Fx::ZERO,
+        }
+    }
+
+    // ── NR Kaba Taraması ─────────────────────────────────────────────────────
+
+    /// Kaba taramanın iki bacağı (önceden sıralı tick'ler)
+    pub struct ScanLegs<'a> {
+        pub sell_pool: &'a PoolState,
+        pub sell_fee: f64,
+        pub buy_pool: &'a PoolState,
+        pub buy_fee: f64,
+        pub sell_token0_is_weth: bool,
+        pub buy_token0_is_weth: bool,
+        pub sell_sorted: &'a [(i32, i128, U256)],
+        pub buy_sorted: &'a [(i32, i128, U256)],
+        /// Toplam tick geçişinde gas maliyeti (wei)
+        pub gas_wei: &'a dyn Fn(u32) -> i128,
+    }
+
+    impl ScanLegs<'_> {
+        /// `amount_wei` WETH girdisinin wei cinsinden net kârı (geçiş sayısına
+        /// göre gas düşülmüş); bacak boş / havuz tükeniyorsa None
+        fn profit_wei(&self, amount_wei: U256) -> Option<i128> {
+            let (amount_in, sell, buy) = super::presorted_legs_wei(
+                amount_wei,
+                self.sell_pool,
+                self.sell_fee,
+                self.buy_pool,
+                self.buy_fee,
+                self.sell_token0_is_weth,
+                self.buy_token0_is_weth,
+                self.sell_sorted,
+                self.buy_sorted,
+            )?;
+            let wei = |v: U256| Fx::from_magnitude(v, false).0;
+            let gas = (self.gas_wei)(super::TickCrossings::of(&sell, &buy).total());
+            Some(wei(buy.amount_out).saturating_sub(wei(amount_in)).saturating_sub(gas))
+        }
+    }
+
+    fn consider(legs: &ScanLegs, best: &mut Option<(U256, i128)>, amount: U256) {
+        let Some(profit) = legs.profit_wei(amount) else {
+            return;
+        };
+        match best {
+            Some((_, best_profit)) if profit <= *best_profit => {}
+            _ => *best = Some((amount, profit)),
+        }
+    }
+
+    /// `find_optimal_amount_with`'in kaba + ince taramasının tam sayı
+    /// karşılığı: `min..=max` WETH aralığında karesel wei ızgarası (aynı
+    /// noktalar), sonra en iyi noktanın ±%25'inde ince adımlar. Kâr wei
+    /// olarak karşılaştırılır (eşitlikte önce bulunan kalır). Geçerli nokta
+    /// yoksa None.
+    pub fn coarse_scan(legs: &ScanLegs, min_weth: f64, max_weth: f64, steps: u32) -> Option<U256> {
+        let min = exact::f64_to_u256_wei(min_weth);
+        let max = exact::f64_to_u256_wei(max_weth);
+        let span = max.saturating_sub(min);
+        let steps_sq = U256::from(steps) * U256::from(steps);
+        let mut best = None;
+
+        for i in 1..=steps {
+            let i_sq = U256::from(i) * U256::from(i);
+            consider(legs, &mut best, min + exact::mul_div(span, i_sq, steps_sq));
+        }
+
+        let (best_amount, _) = best?;
+        let lo = (best_amount * U256::from(3) / U256::from(4)).max(min);
+        let hi = (best_amount * U256::from(5) / U256::from(4)).min(max);
+        let fine_steps = (steps / 2).max(6);
+        for i in 0..=fine_steps {
+            let offset = exact::mul_div(hi - lo, U256::from(i), U256::from(fine_steps));
+            consider(legs, &mut best, lo + offset);
+        }
+        best.map(|(amount, _)| amount)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Testler
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[cfg(test)]
+    mod fixed_tests {
+        use super::*;
+        use crate::math::{PreFilter, PreFilterResult};
+        use proptest::prelude::*;
+
+        /// Fiyatı 1 olan (√P = 2^96), 1e24 likiditeli, token0=WETH havuz
+        fn unit_pool() -> PoolState {
+            PoolState {
+                sqrt_price_x96: Q96,
+                liquidity: 1_000_000_000_000_000_000_000_000,
+                ..PoolState::default()
+            }
+        }
+
+        #[test]
+        fn test_fx_conversions_are_exact() {
+            assert_eq!(Fx::from_int(3).to_bits(), 3 << 64);
+            assert_eq!(Fx::from_f64(0.25).to_bits(), 1 << 62);
+            assert_eq!(Fx::from_f64(-1.5).to_bits(), -(3 << 63));
+            assert_eq!(Fx::from_f64(2450.125).to_f64(), 2450.125);
+            // Kesme sıfıra doğru, NaN → 0, taşma doyar
+            assert_eq!(Fx::from_f64(0.0005).to_bits(), 9_223_372_036_854_776);
+            assert_eq!(Fx::from_f64(f64::NAN), Fx::ZERO);
+            assert_eq!(Fx::from_f64(1e40), Fx::MAX);
+            assert_eq!(Fx::from_ratio(U256::from(3), U256::from(2)).to_bits(), 3 << 63);
+            assert_eq!(Fx::from_ratio(U256::from(1), U256::ZERO), Fx::MAX);
+            assert_eq!(Fx::from_int(7) / Fx::ZERO, Fx::MAX);
+            assert_eq!(Fx::from_int(-7) / Fx::ZERO, Fx::from_bits(-i128::MAX));
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(2_000))]
+
+            /// Fx çarpma / bölme / oran = exact U256 mul_div referansı (floor, işaret ayrı)
+            #[test]
+            fn prop_fx_mul_div_match_exact_reference(
+                a in 0u128..(1u128 << 100),
+                b in 1u128..(1u128 << 100),
+                neg_a in proptest::bool::ANY,
+                neg_b in proptest::bool::ANY,
+            ) {
+                let sign = |v: u128, neg: bool| if neg { -(v as i128) } else { v as i128 };
+                let (x, y) = (Fx::from_bits(sign(a, neg_a)), Fx::from_bits(sign(b, neg_b)));
+                let negative = a != 0 && neg_a != neg_b;
+                let expect = |mag: U256| {
+                    let mag = u128::try_from(mag)
+                        .ok()
+                        .and_then(|m| i128::try_from(m).ok())
+                        .unwrap_or(i128::MAX);
+                    if negative { -mag } else { mag }
+                };
+
+                let mul_ref = expect(exact::mul_div(U256::from(a), U256::from(b), ONE_U256));
+                prop_assert_eq!((x * y).to_bits(), mul_ref);
+                let div_ref = expect(exact::mul_div(U256::from(a), ONE_U256, U256::from(b)));
+                prop_assert_eq!((x / y).to_bits(), div_ref);
+                prop_assert_eq!(
+                    Fx::from_ratio(U256::from(a), U256::from(b)).to_bits(),
+                    u128::try_from(exact::mul_div(U256::from(a), ONE_U256, U256::from(b)))
+                        .ok()
+                        .and_then(|m| i128::try_from(m).ok())
+                        .unwrap_or(i128::MAX)
+                );
+            }
+
+            /// Tek aralık swap = exact motorun boş tick listeli sonucu; fiyat
+            /// güncellemesi SqrtPriceMath.getNextSqrtPriceFromInput ile aynı
+            #[test]
+            fn prop_single_tick_swap_matches_exact_engine(
+                tick in -200_000i32..200_000,
+                liquidity in 1_000_000u128..(1u128 << 100),
+                amount in 1u128..(1u128 << 90),
+                zero_for_one in proptest::bool::ANY,
+                fee_pips in prop_oneof![Just(0u32), Just(100), Just(500), Just(3_000), Just(10_000)],
+            ) {
+                let sqrt = exact::get_sqrt_ratio_at_tick(tick);
+                let amount = U256::from(amount);
+                let (out, next) = single_tick_swap(sqrt, liquidity, amount, zero_for_one, fee_pips);
+                let reference = exact::compute_exact_swap_presorted(
+                    sqrt, liquidity, tick, amount, zero_for_one, fee_pips, &[],
+                );
+                prop_assert_eq!(out, reference.amount_out);
+                prop_assert_eq!(next, reference.sqrt_price_x96_after);
+
+                let limit = if zero_for_one {
+                    exact::MIN_SQRT_RATIO_PLUS_1
+                } else {
+                    exact::MAX_SQRT_RATIO_MINUS_1
+                };
+                if fee_pips == 0 && next != limit {
+                    prop_assert_eq!(
+                        next,
+                        exact::get_next_sqrt_price_from_input(sqrt, liquidity, amount, zero_for_one)
+                    );
+                }
+            }
+        }
+
+        /// Altın değerler (Python tam sayı aritmetiğiyle bağımsız üretildi):
+        /// √P = 2^96, L = 1e24, probe 1 WETH, iki token 18 decimal
+        #[test]
+        fn test_exec_price_golden_bits() {
+            let pool = unit_pool();
+            let (out, next) = single_tick_swap(Q96, pool.liquidity, WAD, true, 0);
+            assert_eq!(out, U256::from(999_999_000_000_999_998u128));
+            assert_eq!(next, U256::from(79_228_083_286_181_051_412_492_537_844u128));
+
+            let price = exec_price(&pool, true, 18, WAD);
+            assert_eq!(price.sell.to_bits(), 18_446_725_626_983_924_613);
+            assert_eq!(price.buy.map(Fx::to_bits), Some(18_446_762_520_453_625_343));
+            // Tekrar hesap bit-özdeş
+            assert_eq!(exec_price(&pool, true, 18, WAD), price);
+
+            // probe 0 → spot; likiditesiz havuz kullanılamaz
+            assert_eq!(exec_price(&pool, true, 18, U256::ZERO), ExecPrice { sell: Fx::ONE, buy: Some(Fx::ONE) });
+            let dry = PoolState { liquidity: 0, ..unit_pool() };
+            assert_eq!(exec_price(&dry, true, 18, WAD), ExecPrice::UNUSABLE);
+
+            // %1 pahalı havuza göre spread: (sell_b − buy_a) / buy_a × 100
+            let rich = ExecPrice { sell: Fx::from_f64(1.01), buy: None };
+            let spread = spread_pct(&price, &rich);
+            assert!((spread.to_f64() - 0.9999).abs() < 1e-4, "spread={}", spread.to_f64());
+            assert_eq!(spread_pct(&rich, &price), Fx::ZERO);
+        }
+
+        /// PreFilter::check_fixed altın değerleri (Python ile bağımsız üretildi)
+        #[test]
+        fn test_prefilter_fixed_golden() {
+            let filter = PreFilter {
+                fee_a: 0.0005,
+                fee_b: 0.0001,
+                estimated_gas_cost_weth: 0.0001,
+                min_profit_weth: 0.0002,
+                bribe_pct: 0.25,
+            };
+            let result = filter.check_fixed(Fx::from_int(2000), Fx::from_int(2010), Fx::from_int(10));
+            let PreFilterResult::Profitable { estimated_profit_weth, spread_ratio } = result else {
+                panic!("expected profitable: {:?}", result);
+            };
+            assert_eq!(estimated_profit_weth, 0.0329);
+            assert_eq!(spread_ratio, 0.005);
+            // f64 yolu aynı kararı verir (son bitler farklı olabilir)
+            let PreFilterResult::Profitable { estimated_profit_weth: f, .. } = filter.check(2000.0, 2010.0, 10.0) else {
+                panic!("f64 path must agree");
+            };
+            assert!((f - estimated_profit_weth).abs() < 1e-15);
+
+            assert!(matches!(
+                filter.check_fixed(Fx::from_int(2000), Fx::from_f64(2000.5), Fx::from_int(10)),
+                PreFilterResult::Unprofitable { reason: crate::math::PreFilterRejectReason::SpreadBelowFees }
+            ));
+            assert!(matches!(
+                filter.check_fixed(Fx::from_int(2000), Fx::from_int(2002), Fx::from_f64(0.01)),
+                PreFilterResult::Unprofitable { reason: crate::math::PreFilterRejectReason::ProfitBelowThreshold }
+            ));
+            assert!(matches!(
+                filter.check_fixed(Fx::ZERO, Fx::from_int(2000), Fx::ONE),
+                PreFilterResult::Unprofitable { reason: crate::math::PreFilterRejectReason::InvalidPriceData }
+            ));
+        }
     }
 }
@@ -0,0 +1,143 @@
+// ============================================================================
+//  METRICS v1.0 — Prometheus Sayaç / Gösterge / Histogramları
+//
+//  Grafana için terminal çıktısı yerine GET /metrics (metin formatı 0.0.4):
+//  ✓ Sayaçlar: işlenen blok, fırsat, kârlı fırsat, yürütülen işlem,
+//    başarısız simülasyon, bitmap senkronu (ArbitrageStats'tan)
+//  ✓ Havuz başına göstergeler: eth_price_usd, liquidity, tick, staleness_ms
+//  ✓ Histogramlar: blok senkron gecikmesi ve simulate() süresi
+//  ✓ Sync aşaması histogramları ve Multicall payload sayaçları
+//    (status_server::render_metrics) aynı yanıta eklenir
+//
+//  Kaynak, status_server'ın blok sonu görüntüsüdür: sayaçlar orada birkaç
+//  u64 olarak kopyalanır, ArbitrageStats'ın tamamı kopyalanmaz.
+//  METRICS_LISTEN_ADDR ayrı bir dinleyici açar ve yalnızca /metrics sunar;
+//  STATUS_LISTEN_ADDR'deki /metrics de aynı gövdeyi döner. Port doluysa
+//  uyarı basılır, bot sürer.
+// ============================================================================
+
+use crate::executor::LatencyHistogram;
+use crate::status_server::{render_metrics, write_histogram, PoolStatus, StatusSnapshot};
+
+/// Blok başından senkron bitimine süre (ana döngü kaydeder)
+pub static BLOCK_SYNC_LATENCY: LatencyHistogram = LatencyHistogram::new();
+
+/// SimulationEngine::simulate_detailed süresi (REVM db kurulumu dahil)
+pub static SIMULATE_LATENCY: LatencyHistogram = LatencyHistogram::new();
+
+/// Havuz başı gösterge: (ad, açıklama, PoolStatus'tan değer)
+type PoolGauge = (&'static str, &'static str, fn(&PoolStatus) -> f64);
+
+/// Görüntüden tam /metrics gövdesi
+pub fn render(snapshot: &StatusSnapshot) -> String {
+    let mut out = String::new();
+
+    if let Some(stats) = snapshot.stats {
+        let counters = [
+            ("arb_blocks_processed_total", "Blocks processed", stats.total_blocks_processed),
+            ("arb_opportunities_total", "Opportunities detected", stats.total_opportunities),
+            ("arb_profitable_opportunities_total", "Opportunities above the profit threshold", stats.profitable_opportunities),
+            ("arb_executed_trades_total", "Trades sent for execution", stats.executed_trades),
+            ("arb_failed_simulations_total", "Simulations that rejected an opportunity", stats.failed_simulations),
+            ("arb_tick_bitmap_syncs_total", "Successful tick bitmap refreshes", stats.tick_bitmap_syncs),
+        ];
+        for (name, help, value) in counters {
+            out.push_str(&format!("# HELP {} {}\n# TYPE {} counter\n{} {}\n", name, help, name, name, value));
+        }
+    }
+
+    let gauges: [PoolGauge; 4] = [
+        ("arb_pool_eth_price_usd", "Pool-implied ETH price (USD)", |p| p.eth_price_usd),
+        ("arb_pool_liquidity", "Active in-range liquidity", |p| p.liquidity.parse().unwrap_or(0.0)),
+        ("arb_pool_tick", "Current tick", |p| p.tick as f64),
+        ("arb_pool_staleness_ms", "Age of the pool state at publish time (ms)", |p| p.staleness_ms as f64),
+    ];
+    for (name, help, value) in gauges {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n", name, help, name));
+        for pool in &snapshot.pools {
+            out.push_str(&format!(
+                "{}{{pool=\"{}\",address=\"{}\"}} {}\n",
+                name,
+                escape_label(&pool.name),
+                pool.address,
+                value(pool),
+            ));
+        }
+    }
+
+    for (name, help, hist) in [
+        ("arb_block_sync_seconds", "Block sync latency (header to synced state)", &BLOCK_SYNC_LATENCY),
+        ("arb_simulate_seconds", "simulate() duration", &SIMULATE_LATENCY),
+    ] {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} histogram\n", name, help, name));
+        write_histogram(&mut out, name, "", &hist.snapshot());
+    }
+
+    out.push_str(&render_metrics());
+    out
+}
+
+/// Prometheus etiket değeri: `\`, `"` ve satır sonu kaçışlı
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_sync::test_pool_config;
+    use crate::status_server::{ExecutionStatus, GateStatus, StatusFeed, StatusView};
+    use crate::types::{ArbitrageStats, BotConfig, PoolState, SharedPoolState};
+    use alloy::primitives::U256;
+    use arc_swap::ArcSwap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_render_exposes_counters_pool_gauges_and_histograms() {
+        let pools = [test_pool_config("UniV3 \"WETH\"/USDC")];
+        let states: [SharedPoolState; 1] = [Arc::new(ArcSwap::from_pointee(PoolState {
+            sqrt_price_x96: U256::from(1u128 << 96),
+            tick: -197_000,
+            liquidity: 5_000,
+            eth_price_usd: 2_500.0,
+            ..PoolState::default()
+        }))];
+        let mut stats = ArbitrageStats::new();
+        stats.total_blocks_processed = 42;
+        stats.executed_trades = 3;
+        stats.tick_bitmap_syncs = 7;
+        SIMULATE_LATENCY.record(250);
+        BLOCK_SYNC_LATENCY.record(4_000);
+
+        let feed = StatusFeed::new(&BotConfig::for_tests());
+        feed.publish(StatusView {
+            block_number: 1_000,
+            pools: &pools,
+            states: &states,
+            stats: &stats,
+            execution: ExecutionStatus::default(),
+            gates: GateStatus::default(),
+            pair_schedule: None,
+        });
+        let body = render(&feed.reader().load());
+
+        for line in [
+            "arb_blocks_processed_total 42",
+            "arb_executed_trades_total 3",
+            "arb_tick_bitmap_syncs_total 7",
+            "# TYPE arb_pool_tick gauge",
+            "# TYPE arb_block_sync_seconds histogram",
+            "# TYPE arb_simulate_seconds histogram",
+            "# TYPE arb_sync_rpc_seconds histogram",
+        ] {
+            assert!(body.lines().any(|l| l == line), "missing {:?}", line);
+        }
+        let labels = "{pool=\"UniV3 \\\"WETH\\\"/USDC\",address=\"0xd0b53D9277642d899DF5C87A3966A349A798F224\"}";
+        assert!(body.contains(&format!("arb_pool_tick{} -197000\n", labels)), "{}", body);
+        assert!(body.contains(&format!("arb_pool_liquidity{} 5000\n", labels)));
+        assert!(body.contains(&format!("arb_pool_eth_price_usd{} 2500\n", labels)));
+        assert!(body.lines().any(|l| l.starts_with("arb_simulate_seconds_bucket{le=\"+Inf\"} ")));
+        assert!(body.lines().any(|l| l.starts_with("arb_simulate_seconds_count ")));
+        assert!(body.lines().any(|l| l.starts_with("arb_block_sync_seconds_sum ")));
+    }
+}
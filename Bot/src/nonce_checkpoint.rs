@@ -0,0 +1,298 @@
+// ============================================================================
+//  NONCE_CHECKPOINT v1.0 — Nonce + Uçuştaki TX Kaydının Yeniden Başlatmada Korunması
+//
+//  Yeniden başlatmada (ve reconnect'te) nonce zincirden okunuyordu. Kapanışta
+//  private RPC'de bekleyen TX varsa onaylı sayım o nonce'u yeniden verir ve
+//  ilk fırsatlar "replacement transaction underpriced" ile düşer.
+//
+//  ✓ Uçuştaki TX kaydı: {nonce, hash, deadline_block} — gönderimde eklenir,
+//    receipt'te silinir; receipt gelmeyen TX deadline'a kadar kayıtta kalır
+//  ✓ nonce_state.json: {next_nonce, in_flight} — periyodik ve kapanışta
+//    atomik yazılır
+//  ✓ Başlangıç uzlaştırması: zincirin latest + pending sayımları ile
+//    checkpoint karşılaştırılır, başlangıç = max(zincir, checkpoint);
+//    onaylanmış ve deadline'ı geçmiş kayıtlar düşülür, canlılar yeniden kaydedilir
+//  ✓ Periyodik nonce senkronizasyonu canlı bir TX'in nonce'unun altına inmez
+//  ✓ Checkpoint yok / bozuk → eski davranış (zincir değeri)
+//
+//  Executor receipt görevi config taşımaz: kayıt süreç geneli tek bir kilitte
+//  (gas_calibration deseni).
+// ============================================================================
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use alloy::primitives::B256;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::types::NonceManager;
+
+/// Checkpoint dosyası (DATA_DIR altında)
+pub const NONCE_STATE_PATH: &str = "nonce_state.json";
+
+/// Checkpoint yazım periyodu (blok)
+pub const CHECKPOINT_INTERVAL_BLOCKS: u64 = 10;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Checkpoint Dosyası
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Gönderilmiş, receipt'i henüz görülmemiş TX
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InflightTx {
+    pub nonce: u64,
+    pub hash: B256,
+    /// Kontratın TX'i kabul ettiği son blok — sonrası revert eder
+    pub deadline_block: u64,
+}
+
+/// nonce_state.json içeriği
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonceCheckpoint {
+    /// Sıradaki TX'e verilecek nonce
+    pub next_nonce: u64,
+    pub in_flight: Vec<InflightTx>,
+}
+
+impl NonceCheckpoint {
+    /// Dosya yoksa None; bozuksa uyarı + None (başlangıç zincir değerine düşer)
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(checkpoint) => Some(checkpoint),
+            Err(e) => {
+                eprintln!(
+                    "  ⚠️  {} parse error: {} — nonce from chain",
+                    path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        crate::session_summary::write_atomic(path, &json)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Uzlaştırma
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Başlangıç uzlaştırmasının sonucu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// NonceManager'ın başlayacağı değer
+    pub start_nonce: u64,
+    /// Onaylanmamış ve deadline'ı geçmemiş TX'ler — kayda geri alınır
+    pub live: Vec<InflightTx>,
+    /// Zincirde onaylanmış (nonce < latest sayım) kayıtlar
+    pub confirmed: usize,
+    /// Deadline'ı geçmiş kayıtlar
+    pub expired: usize,
+}
+
+/// Checkpoint'i zincirle uzlaştır. `chain_latest` / `chain_pending`:
+/// eth_getTransactionCount(latest / pending), `block`: mevcut blok.
+/// Checkpoint yoksa başlangıç zincir değeridir (eski davranış).
+pub fn reconcile(
+    checkpoint: Option<&NonceCheckpoint>,
+    chain_latest: u64,
+    chain_pending: u64,
+    block: u64,
+) -> Reconciliation {
+    let chain_next = chain_latest.max(chain_pending);
+    let mut result = Reconciliation {
+        start_nonce: chain_next,
+        live: Vec::new(),
+        confirmed: 0,
+        expired: 0,
+    };
+    let Some(checkpoint) = checkpoint else {
+        return result;
+    };
+    for tx in &checkpoint.in_flight {
+        if tx.nonce < chain_latest {
+            result.confirmed += 1;
+        } else if tx.deadline_block < block {
+            result.expired += 1;
+        } else {
+            result.live.push(*tx);
+        }
+    }
+    result.start_nonce = chain_next.max(checkpoint.next_nonce);
+    result
+}
+
+/// Periyodik senkronizasyonun hedefi: zincirin pending sayımı, ancak canlı
+/// uçuştaki bir TX'in nonce'unun altına inilmez (`live_floor` = en yüksek
+/// canlı nonce + 1)
+pub fn sync_target(chain_pending: u64, live_floor: Option<u64>) -> u64 {
+    live_floor.map_or(chain_pending, |floor| floor.max(chain_pending))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Kayıt
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Registry {
+    in_flight: BTreeMap<u64, InflightTx>,
+    /// run_bot girişinde bağlanır — kapanışta next_nonce buradan okunur
+    nonce_manager: Option<Arc<NonceManager>>,
+}
+
+static REGISTRY: Mutex<Registry> = parking_lot::const_mutex(Registry {
+    in_flight: BTreeMap::new(),
+    nonce_manager: None,
+});
+
+/// Checkpoint'i oku (eski konum dahil) — yoksa / bozuksa None
+pub fn load() -> Option<NonceCheckpoint> {
+    NonceCheckpoint::load(&crate::secure_fs::read_path(NONCE_STATE_PATH))
+}
+
+/// Yeni NonceManager'ı bağla ve uzlaştırmadan gelen canlı TX'leri kayda al
+pub fn attach(nonce_manager: &Arc<NonceManager>, live: Vec<InflightTx>) {
+    let mut registry = REGISTRY.lock();
+    registry.nonce_manager = Some(Arc::clone(nonce_manager));
+    registry.in_flight = live.into_iter().map(|tx| (tx.nonce, tx)).collect();
+}
+
+/// Gönderilen TX'i kaydet (executor, gönderim başarılıysa)
+pub fn register(tx: InflightTx) {
+    REGISTRY.lock().in_flight.insert(tx.nonce, tx);
+}
+
+/// Receipt geldi — kayıttan sil
+pub fn resolve(nonce: u64) {
+    REGISTRY.lock().in_flight.remove(&nonce);
+}
+
+/// Onaylanmış (nonce < `chain_latest`) ve deadline'ı `block`'tan önce
+/// geçmiş kayıtları düş — düşen sayısı
+pub fn prune(chain_latest: u64, block: u64) -> usize {
+    let mut registry = REGISTRY.lock();
+    let before = registry.in_flight.len();
+    registry
+        .in_flight
+        .retain(|&nonce, tx| nonce >= chain_latest && tx.deadline_block >= block);
+    before - registry.in_flight.len()
+}
+
+/// En yüksek canlı nonce + 1 (kayıt boşsa None)
+pub fn live_floor() -> Option<u64> {
+    REGISTRY
+        .lock()
+        .in_flight
+        .last_key_value()
+        .map(|(&nonce, _)| nonce + 1)
+}
+
+/// Kaydın anlık görüntüsü — NonceManager bağlanmamışsa None
+fn snapshot() -> Option<NonceCheckpoint> {
+    let registry = REGISTRY.lock();
+    let nonce_manager = registry.nonce_manager.as_ref()?;
+    Some(NonceCheckpoint {
+        next_nonce: nonce_manager.current(),
+        in_flight: registry.in_flight.values().copied().collect(),
+    })
+}
+
+/// Checkpoint'i DATA_DIR altına yaz (periyodik ve kapanışta)
+pub fn persist() {
+    let Some(checkpoint) = snapshot() else {
+        return;
+    };
+    let path = crate::secure_fs::data_path(NONCE_STATE_PATH);
+    if let Err(e) = checkpoint.save(&path) {
+        eprintln!("  ⚠️  {} write error: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(nonce: u64, deadline_block: u64) -> InflightTx {
+        InflightTx {
+            nonce,
+            hash: B256::with_last_byte(nonce as u8),
+            deadline_block,
+        }
+    }
+
+    fn checkpoint(next_nonce: u64, in_flight: Vec<InflightTx>) -> NonceCheckpoint {
+        NonceCheckpoint { next_nonce, in_flight }
+    }
+
+    #[test]
+    fn test_reconcile_checkpoint_ahead_of_chain_keeps_live_inflight() {
+        // Kapanışta 42 ve 43 private RPC'de bekliyordu; zincir henüz görmüyor
+        let cp = checkpoint(44, vec![tx(42, 1_005), tx(43, 1_006)]);
+        let result = reconcile(Some(&cp), 42, 42, 1_000);
+        assert_eq!(result.start_nonce, 44, "uçuştaki nonce'lar yeniden verilmemeli");
+        assert_eq!(result.live, vec![tx(42, 1_005), tx(43, 1_006)]);
+        assert_eq!((result.confirmed, result.expired), (0, 0));
+    }
+
+    #[test]
+    fn test_reconcile_checkpoint_behind_chain_adopts_chain() {
+        // Checkpoint'ten sonra TX'ler onaylandı (ve dışarıdan bir TX gönderildi)
+        let cp = checkpoint(44, vec![tx(42, 1_005), tx(43, 1_006)]);
+        let result = reconcile(Some(&cp), 45, 46, 1_000);
+        assert_eq!(result.start_nonce, 46, "pending sayım checkpoint'in önünde");
+        assert!(result.live.is_empty());
+        assert_eq!(result.confirmed, 2);
+    }
+
+    #[test]
+    fn test_reconcile_drops_inflight_past_deadline() {
+        let cp = checkpoint(45, vec![tx(42, 990), tx(43, 1_000), tx(44, 1_010)]);
+        let result = reconcile(Some(&cp), 42, 42, 1_001);
+        assert_eq!(result.expired, 2, "deadline'ı geçmiş kayıtlar izlenmez");
+        assert_eq!(result.live, vec![tx(44, 1_010)]);
+        assert_eq!(result.start_nonce, 45);
+
+        // Deadline bloğu hâlâ geçerli (son geçerli blok dahil)
+        let result = reconcile(Some(&cp), 42, 42, 1_000);
+        assert_eq!(result.live.len(), 2);
+    }
+
+    #[test]
+    fn test_reconcile_without_checkpoint_uses_chain() {
+        let result = reconcile(None, 42, 44, 1_000);
+        assert_eq!(result.start_nonce, 44);
+        assert!(result.live.is_empty());
+        // Sağlayıcı pending'i latest'in gerisinde raporlasa da geri gidilmez
+        assert_eq!(reconcile(None, 42, 40, 1_000).start_nonce, 42);
+    }
+
+    #[test]
+    fn test_sync_target_never_rewinds_below_live_inflight() {
+        assert_eq!(sync_target(42, None), 42, "kayıt boş → zincir değeri");
+        assert_eq!(sync_target(42, Some(44)), 44);
+        assert_eq!(sync_target(46, Some(44)), 46);
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip_and_corrupt_file() {
+        let dir = std::env::temp_dir().join(format!("nonce_checkpoint_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(NONCE_STATE_PATH);
+
+        assert_eq!(NonceCheckpoint::load(&path), None, "dosya yok");
+
+        let cp = checkpoint(44, vec![tx(42, 1_005), tx(43, 1_006)]);
+        cp.save(&path).unwrap();
+        assert_eq!(NonceCheckpoint::load(&path), Some(cp));
+
+        std::fs::write(&path, b"{\"next_nonce\": 4").unwrap();
+        assert_eq!(NonceCheckpoint::load(&path), None, "bozuk → zincir değeri");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
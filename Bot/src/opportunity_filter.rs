@@ -0,0 +1,1010 @@
+// ============================================================================
+//  OPPORTUNITY_FILTER v1.0 — Takılabilir Fırsat Filtre Zinciri
+//
+//  Git/gitme kararı eskiden check_arbitrage_opportunity ve
+//  evaluate_and_execute içine dağılmış if-bloklarıydı; her yeni kontrol
+//  kendi log stiliyle bir blok daha ekliyordu. Artık her kontrol isimli bir
+//  OpportunityFilter'dır ve sıralı bir zincirde çalışır:
+//
+//  ✓ PreSizing  — NR'den önce (CPU tasarrufu: kesin ret NR'yi atlar)
+//      staleness → pool_health → prefilter
+//  ✓ PostSizing — NR boyutlandırmasından sonra
+//      profit_floor → bitmap_quality → block_pin → adverse_selection → competition
+//      → direction_gate
+//  ✓ Execution  — evaluate_and_execute'ta, simülasyondan önce
+//      execution_sanity → freshness_gate → robustness
+//
+//  Zincir filtre başına geçti/ret/degrade sayaçlarını tutar; ilk ret nedeni
+//  tek tip loglanır, son ret stats kutusunda, Degrade nedeni gölge logda
+//  görünür. Sıra OPPORTUNITY_FILTERS ile yapılandırılabilir. Yeni filtreler
+//  (TWAP, zamanlama, bütçe) çekirdek akışa dokunmadan eklenir.
+// ============================================================================
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::direction_gate::{self, DirectionKey};
+use crate::math::PreFilterResult;
+use crate::staleness::{self, Stage};
+use crate::strategy::{competition_penalty, is_competition_skipped};
+use crate::types::{
+    AdverseScenario, BitmapQuality, BotConfig, CompetitionSnapshot, PoolState, ProfitSensitivity,
+};
+
+/// Varsayılan zincir sırası (eski satır içi kontrollerin sırası)
+pub const DEFAULT_FILTER_ORDER: [&str; 12] = [
+    "staleness",
+    "pool_health",
+    "prefilter",
+    "profit_floor",
+    "bitmap_quality",
+    "block_pin",
+    "adverse_selection",
+    "competition",
+    "direction_gate",
+    "execution_sanity",
+    "freshness_gate",
+    "robustness",
+];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Trait + Karar Tipleri
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Filtrenin çalıştığı aşama
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterStage {
+    /// NR boyutlandırmasından önce (spread, fee, likidite, PreFilter)
+    PreSizing,
+    /// NR boyutlandırmasından sonra (kâr, ROI, rakip cezası)
+    PostSizing,
+    /// Yürütme anında (güncel state ile, simülasyondan önce)
+    Execution,
+}
+
+/// Tek filtrenin kararı
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterDecision {
+    /// Devam
+    Pass,
+    /// Fırsat reddedildi — zincir durur
+    Reject(String),
+    /// Fırsat yürütülmez, sadece gölge loglanır — zincir devam eder
+    Degrade(String),
+}
+
+/// Reddeden / degrade eden filtrenin adı ve nedeni
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterVerdict {
+    pub filter: &'static str,
+    pub reason: String,
+}
+
+/// Zincir sonucu (aşama başına)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainOutcome {
+    Pass,
+    Reject(FilterVerdict),
+    Degrade(FilterVerdict),
+}
+
+/// Fırsat filtresi
+pub trait OpportunityFilter: Send + Sync {
+    /// Sayaç ve loglarda görünen ad (OPPORTUNITY_FILTERS'taki ad)
+    fn name(&self) -> &'static str;
+    /// Hangi aşamada çalışır
+    fn stage(&self) -> FilterStage;
+    /// Bağlamı değerlendir
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision;
+}
+
+/// Filtrelerin gördüğü karar girdileri.
+///
+/// Aşamaya göre doldurulur: PreSizing alanları NR'den önce, boyutlandırma
+/// alanları NR'den sonra set edilir. Execution aşamasında state'ler
+/// yürütme anında yeniden okunur.
+pub struct OpportunityContext<'a> {
+    pub config: &'a BotConfig,
+    pub state_a: &'a PoolState,
+    pub state_b: &'a PoolState,
+    /// Yürürlükteki havuz fee'leri (live_fee_bps varsa o)
+    pub fee_a_bps: u32,
+    pub fee_b_bps: u32,
+    pub spread_pct: f64,
+    /// NR'nin dayandığı bitmap kalitesi (Stale = bitmap vardı ama max yaşı aştı)
+    pub bitmap_quality: BitmapQuality,
+    pub sell_hard_cap: f64,
+    pub buy_hard_cap: f64,
+    pub effective_cap: f64,
+    /// L2 + L1 + güvenlik marjı (WETH)
+    pub gas_cost_weth: f64,
+    pub probe_amount: f64,
+    pub prefilter: Option<PreFilterResult>,
+    pub optimal_amount_weth: f64,
+    pub expected_profit_weth: f64,
+    pub roi: f64,
+    /// Rakip front-run senaryosu (NR'den sonra, exact matematikle)
+    pub adverse: Option<AdverseScenario>,
+    /// 1/2 tick ters hareket hassasiyeti (yürütme öncesi, bütçe yetmezse None)
+    pub sensitivity: Option<ProfitSensitivity>,
+    pub buy_price_quote: f64,
+    pub sell_price_quote: f64,
+    pub competition: CompetitionSnapshot,
+    /// (buy_pool, sell_pool) — yön kapısı için (bilinmiyorsa None → geçer)
+    pub direction: Option<DirectionKey>,
+}
+
+impl<'a> OpportunityContext<'a> {
+    /// Boş bağlam — alanlar aşama ilerledikçe doldurulur
+    pub fn new(config: &'a BotConfig, state_a: &'a PoolState, state_b: &'a PoolState) -> Self {
+        Self {
+            config,
+            state_a,
+            state_b,
+            fee_a_bps: 0,
+            fee_b_bps: 0,
+            spread_pct: 0.0,
+            bitmap_quality: BitmapQuality::Fallback,
+            sell_hard_cap: 0.0,
+            buy_hard_cap: 0.0,
+            effective_cap: 0.0,
+            gas_cost_weth: 0.0,
+            probe_amount: 0.0,
+            prefilter: None,
+            optimal_amount_weth: 0.0,
+            expected_profit_weth: 0.0,
+            roi: 0.0,
+            adverse: None,
+            sensitivity: None,
+            buy_price_quote: 0.0,
+            sell_price_quote: 0.0,
+            competition: CompetitionSnapshot::default(),
+            direction: None,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Yerleşik Filtreler
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// v10.0 Hard-Abort: iki havuz da aktif ve veri tespit kesiminde
+/// (uyarlamalı ya da MAX_STALENESS_MS) içinde
+pub struct StalenessFilter;
+
+impl OpportunityFilter for StalenessFilter {
+    fn name(&self) -> &'static str {
+        "staleness"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PreSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        let max = staleness::cutoff(ctx.config, Stage::Detection);
+        if ctx.state_a.is_fresh(max) && ctx.state_b.is_fresh(max) {
+            return FilterDecision::Pass;
+        }
+        if ctx.state_a.is_active() && ctx.state_b.is_active() {
+            let worst = ctx.state_a.staleness_ms().max(ctx.state_b.staleness_ms());
+            FilterDecision::Reject(format!(
+                "\u{1f6a8} HARD-ABORT stale data A={}ms B={}ms (threshold={}ms{})",
+                ctx.state_a.staleness_ms(),
+                ctx.state_b.staleness_ms(),
+                max,
+                tightened_tag(Stage::Detection, worst),
+            ))
+        } else {
+            FilterDecision::Reject(format!(
+                "pool inactive (A={}, B={})",
+                ctx.state_a.is_active(),
+                ctx.state_b.is_active(),
+            ))
+        }
+    }
+}
+
+/// Havuz sağlığı: fee tavanı, bayat bitmap, yetersiz likidite, sığ havuz ekonomisi
+pub struct PoolHealthFilter;
+
+impl OpportunityFilter for PoolHealthFilter {
+    fn name(&self) -> &'static str {
+        "pool_health"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PreSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        let config = ctx.config;
+        if ctx.fee_a_bps > config.max_pool_fee_bps || ctx.fee_b_bps > config.max_pool_fee_bps {
+            return FilterDecision::Reject(format!(
+                "pool fee exceeds safety ceiling: A={}bps B={}bps (max={}bps)",
+                ctx.fee_a_bps, ctx.fee_b_bps, config.max_pool_fee_bps,
+            ));
+        }
+        if let BitmapQuality::Stale { age_blocks } = ctx.bitmap_quality {
+            return FilterDecision::Reject(format!(
+                "stale TickBitmap (age={} blocks, age_limit={} blocks)",
+                age_blocks, config.tick_bitmap_max_age_blocks,
+            ));
+        }
+        if ctx.effective_cap <= 0.001 {
+            return FilterDecision::Reject(format!(
+                "insufficient liquidity (cap={:.6} WETH)",
+                ctx.effective_cap,
+            ));
+        }
+        if ctx.effective_cap < config.max_trade_size_weth * 0.1 {
+            // Kaba tahmin: effective_cap * spread_pct/100 < min_net_profit → kesinlikle kârsız
+            let max_possible_gross = ctx.effective_cap * ctx.spread_pct / 100.0;
+            if max_possible_gross < config.min_net_profit_weth {
+                return FilterDecision::Reject(format!(
+                    "shallow pool + low spread: max_gross={:.8} < min_profit={:.8} WETH (sell_cap={:.4} buy_cap={:.4})",
+                    max_possible_gross, config.min_net_profit_weth, ctx.sell_hard_cap, ctx.buy_hard_cap,
+                ));
+            }
+        }
+        FilterDecision::Pass
+    }
+}
+
+/// Analitik PreFilter: spread fee + gas + bribe'ı kurtarıyor mu?
+pub struct PreFilterGate;
+
+impl OpportunityFilter for PreFilterGate {
+    fn name(&self) -> &'static str {
+        "prefilter"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PreSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        match ctx.prefilter {
+            Some(PreFilterResult::Unprofitable { reason }) => FilterDecision::Reject(format!(
+                "spread {:.4}% | {:?} | gas={:.8} WETH | probe={:.4} WETH",
+                ctx.spread_pct, reason, ctx.gas_cost_weth, ctx.probe_amount,
+            )),
+            _ => FilterDecision::Pass,
+        }
+    }
+}
+
+/// v30.0 kâr tabanı: mutlak kâr VEYA ROI eşiği geçilmeli
+pub struct ProfitFloorFilter;
+
+impl OpportunityFilter for ProfitFloorFilter {
+    fn name(&self) -> &'static str {
+        "profit_floor"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PostSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        let config = ctx.config;
+        if ctx.optimal_amount_weth <= 0.0
+            || (ctx.expected_profit_weth < config.min_net_profit_weth && ctx.roi < config.min_profit_roi)
+        {
+            FilterDecision::Reject(format!(
+                "unprofitable: profit={:.8} WETH, roi={:.6}%, min_profit={:.8}, min_roi={:.4}%",
+                ctx.expected_profit_weth,
+                ctx.roi * 100.0,
+                config.min_net_profit_weth,
+                config.min_profit_roi * 100.0,
+            ))
+        } else {
+            FilterDecision::Pass
+        }
+    }
+}
+
+/// REQUIRE_BITMAP_FOR_EXECUTION: sönümleme sezgisiyle (bitmap yok / bayat)
+/// hesaplanan fırsat yürütülmez, gölge loglanır.
+///
+/// Bayat bitmap varsayılan zincirde zaten pool_health'te reddedilir; bu
+/// filtre o filtre zincirden çıkarıldığında da bayat fırsatları yakalar.
+pub struct BitmapQualityFilter;
+
+impl OpportunityFilter for BitmapQualityFilter {
+    fn name(&self) -> &'static str {
+        "bitmap_quality"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PostSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        if !ctx.config.require_bitmap_for_execution || ctx.bitmap_quality.is_real() {
+            return FilterDecision::Pass;
+        }
+        FilterDecision::Degrade(format!(
+            "profit estimated without real TickBitmap (quality={}, dampening heuristic) -- REQUIRE_BITMAP_FOR_EXECUTION=true",
+            ctx.bitmap_quality,
+        ))
+    }
+}
+
+/// İki havuzun sync okuması aynı bloğa sabitlenmiş olmalı. "latest"e düşen
+/// (budanmış state) ya da farklı bloklarda okunan havuzlarla fırsat tespit
+/// edilir ama yürütülmez — A'yı N'de, B'yi N+1'de okumak hayalet spread üretir.
+pub struct BlockPinFilter;
+
+impl OpportunityFilter for BlockPinFilter {
+    fn name(&self) -> &'static str {
+        "block_pin"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PostSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        match (ctx.state_a.pinned_block, ctx.state_b.pinned_block) {
+            (Some(a), Some(b)) if a == b => FilterDecision::Pass,
+            (a, b) => {
+                let label = |pin: Option<u64>| pin.map_or("latest".to_string(), |n| format!("#{}", n));
+                FilterDecision::Degrade(format!(
+                    "pool reads not pinned to one block: A={} B={} -- execution requires same-block pinned sync",
+                    label(a),
+                    label(b),
+                ))
+            }
+        }
+    }
+}
+
+/// REQUIRE_ADVERSE_POSITIVE: rakip front-run senaryosunda kâr sıfıra
+/// düşüyorsa fırsat yürütülmez, gölge loglanır (senaryo kapalıysa geçer).
+pub struct AdverseSelectionFilter;
+
+impl OpportunityFilter for AdverseSelectionFilter {
+    fn name(&self) -> &'static str {
+        "adverse_selection"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PostSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        match &ctx.adverse {
+            Some(adverse) if ctx.config.require_adverse_positive && !adverse.is_adverse_positive() => {
+                FilterDecision::Degrade(format!(
+                    "unprofitable after {:.4} WETH front-run (base={} wei, adverse=0) -- REQUIRE_ADVERSE_POSITIVE=true",
+                    adverse.frontrun_weth, adverse.base_profit_wei,
+                ))
+            }
+            _ => FilterDecision::Pass,
+        }
+    }
+}
+
+/// Kuyruk derinliği cezası: fırsat yürütülmez, gölge loglanır
+pub struct CompetitionFilter;
+
+impl OpportunityFilter for CompetitionFilter {
+    fn name(&self) -> &'static str {
+        "competition"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PostSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        let penalty = competition_penalty(ctx.competition.competitor_tx_last_block, ctx.config);
+        if is_competition_skipped(penalty, ctx.expected_profit_weth, ctx.roi, ctx.config) {
+            FilterDecision::Degrade(format!(
+                "{} competitor tx in block #{} -- {:?}, profit={:.8} WETH",
+                ctx.competition.competitor_tx_last_block,
+                ctx.competition.block_number,
+                penalty,
+                ctx.expected_profit_weth,
+            ))
+        } else {
+            FilterDecision::Pass
+        }
+    }
+}
+
+/// Yön kapısı: geçmiş başarı oranı düşük yön kısıtlıdır — suspend modunda
+/// hiç, multiplier modunda yalnızca büyütülmüş kâr eşiğiyle yürütülür
+/// (kısıtlı fırsat gölge loglanır)
+pub struct DirectionGateFilter;
+
+impl OpportunityFilter for DirectionGateFilter {
+    fn name(&self) -> &'static str {
+        "direction_gate"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::PostSizing
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        match &ctx.direction {
+            Some(key) => direction_gate::decision(key, ctx.expected_profit_weth, ctx.config.min_net_profit_weth),
+            None => FilterDecision::Pass,
+        }
+    }
+}
+
+/// v12.0 sıfıra bölünme / NaN / Infinity koruması (u128 cast panic'ini önler)
+pub struct ExecutionSanityFilter;
+
+impl OpportunityFilter for ExecutionSanityFilter {
+    fn name(&self) -> &'static str {
+        "execution_sanity"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::Execution
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        if ctx.sell_price_quote <= 0.0
+            || ctx.buy_price_quote <= 0.0
+            || ctx.optimal_amount_weth <= 0.0
+            || !ctx.expected_profit_weth.is_finite()
+        {
+            FilterDecision::Reject(format!(
+                "invalid values: buy={} sell={} amount={} profit={}",
+                ctx.buy_price_quote, ctx.sell_price_quote, ctx.optimal_amount_weth, ctx.expected_profit_weth,
+            ))
+        } else {
+            FilterDecision::Pass
+        }
+    }
+}
+
+/// Aktif havuzlarda tazelik reti: sabit eşikte geçecek veri uyarlamalı
+/// sıkılaştırmaya sayılır ve ret mesajında işaretlenir
+fn tightened_tag(stage: Stage, worst_staleness_ms: u128) -> &'static str {
+    if staleness::record_rejection(stage, worst_staleness_ms) {
+        ", adaptive"
+    } else {
+        ""
+    }
+}
+
+/// v28.0 Freshness Gate: yürütme anında havuz verisi hâlâ taze mi? (MEV koruması)
+/// Yürütme kesimi tespit kesiminden sıkıdır (STALENESS_EXEC_FRACTION)
+pub struct FreshnessGateFilter;
+
+impl OpportunityFilter for FreshnessGateFilter {
+    fn name(&self) -> &'static str {
+        "freshness_gate"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::Execution
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        let max = staleness::cutoff(ctx.config, Stage::Execution);
+        if ctx.state_a.is_fresh(max) && ctx.state_b.is_fresh(max) {
+            return FilterDecision::Pass;
+        }
+        // Pasif havuz sıkılaştırmaya yazılmaz — sabit eşikte de reddedilirdi
+        let tag = if ctx.state_a.is_active() && ctx.state_b.is_active() {
+            tightened_tag(Stage::Execution, ctx.state_a.staleness_ms().max(ctx.state_b.staleness_ms()))
+        } else {
+            ""
+        };
+        FilterDecision::Reject(format!(
+            "\u{1f6d1} pool data too old or stale: A={}ms B={}ms (threshold={}ms{}) — MEV protection: trade skipped",
+            ctx.state_a.staleness_ms(),
+            ctx.state_b.staleness_ms(),
+            max,
+            tag,
+        ))
+    }
+}
+
+/// MIN_ROBUSTNESS: 1 tick ters harekette kârı sıfırlanan ya da sağlamlık
+/// skoru eşiğin altındaki fırsat yürütülmez (hassasiyet ölçülmediyse geçer).
+pub struct RobustnessFilter;
+
+impl OpportunityFilter for RobustnessFilter {
+    fn name(&self) -> &'static str {
+        "robustness"
+    }
+    fn stage(&self) -> FilterStage {
+        FilterStage::Execution
+    }
+    fn evaluate(&self, ctx: &OpportunityContext) -> FilterDecision {
+        let min = ctx.config.min_robustness;
+        match &ctx.sensitivity {
+            Some(s) if min > 0.0 && (!s.survives_1tick() || s.robustness() < min) => {
+                FilterDecision::Reject(format!(
+                    "fragile opportunity: robustness={:.2} < {:.2} (base={} wei, 1tick={} wei, 2tick={} wei) -- MIN_ROBUSTNESS",
+                    s.robustness(),
+                    min,
+                    s.base_profit_wei,
+                    s.profit_after_1tick_wei,
+                    s.profit_after_2tick_wei,
+                ))
+            }
+            _ => FilterDecision::Pass,
+        }
+    }
+}
+
+/// Ada göre yerleşik filtre
+pub fn builtin_filter(name: &str) -> Option<Box<dyn OpportunityFilter>> {
+    match name {
+        "staleness" => Some(Box::new(StalenessFilter)),
+        "pool_health" => Some(Box::new(PoolHealthFilter)),
+        "prefilter" => Some(Box::new(PreFilterGate)),
+        "profit_floor" => Some(Box::new(ProfitFloorFilter)),
+        "bitmap_quality" => Some(Box::new(BitmapQualityFilter)),
+        "block_pin" => Some(Box::new(BlockPinFilter)),
+        "adverse_selection" => Some(Box::new(AdverseSelectionFilter)),
+        "competition" => Some(Box::new(CompetitionFilter)),
+        "direction_gate" => Some(Box::new(DirectionGateFilter)),
+        "execution_sanity" => Some(Box::new(ExecutionSanityFilter)),
+        "freshness_gate" => Some(Box::new(FreshnessGateFilter)),
+        "robustness" => Some(Box::new(RobustnessFilter)),
+        _ => None,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Zincir
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Filtre başına sayaçlar
+struct ChainEntry {
+    filter: Box<dyn OpportunityFilter>,
+    passed: AtomicU64,
+    rejected: AtomicU64,
+    degraded: AtomicU64,
+}
+
+/// Filtre sayaç görüntüsü (stats kutusu)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterStats {
+    pub name: &'static str,
+    pub passed: u64,
+    pub rejected: u64,
+    pub degraded: u64,
+}
+
+/// Sıralı filtre zinciri. Sayaçlar atomik — zincir `&` ile paylaşılır.
+pub struct FilterChain {
+    entries: Vec<ChainEntry>,
+    last_rejection: Mutex<Option<FilterVerdict>>,
+}
+
+impl FilterChain {
+    /// Filtre listesinden zincir oluştur
+    pub fn new(filters: Vec<Box<dyn OpportunityFilter>>) -> Self {
+        Self {
+            entries: filters
+                .into_iter()
+                .map(|filter| ChainEntry {
+                    filter,
+                    passed: AtomicU64::new(0),
+                    rejected: AtomicU64::new(0),
+                    degraded: AtomicU64::new(0),
+                })
+                .collect(),
+            last_rejection: Mutex::new(None),
+        }
+    }
+
+    /// Eski satır içi kontrollerle aynı sırada yerleşik zincir
+    pub fn default_chain() -> Self {
+        Self::new(DEFAULT_FILTER_ORDER.iter().filter_map(|n| builtin_filter(n)).collect())
+    }
+
+    /// OPPORTUNITY_FILTERS sırası (boş = varsayılan). Bilinmeyen adlar uyarıyla atlanır.
+    pub fn from_config(config: &BotConfig) -> Self {
+        if config.opportunity_filters.is_empty() {
+            return Self::default_chain();
+        }
+        let filters = config
+            .opportunity_filters
+            .iter()
+            .filter_map(|name| {
+                let filter = builtin_filter(name);
+                if filter.is_none() {
+                    eprintln!("  ⚠️ [FilterChain] Unknown filter '{}' in OPPORTUNITY_FILTERS — ignored", name);
+                }
+                filter
+            })
+            .collect();
+        Self::new(filters)
+    }
+
+    /// Bir aşamanın filtrelerini sırayla çalıştır.
+    ///
+    /// İlk Reject zinciri durdurur ve tek tip loglanır. Degrade kaydedilir ama
+    /// sonraki filtreler yine çalışır (sonraki Reject önceliklidir).
+    pub fn run(&self, stage: FilterStage, ctx: &OpportunityContext) -> ChainOutcome {
+        let mut degraded: Option<FilterVerdict> = None;
+        for entry in self.entries.iter().filter(|e| e.filter.stage() == stage) {
+            match entry.filter.evaluate(ctx) {
+                FilterDecision::Pass => {
+                    entry.passed.fetch_add(1, Ordering::Relaxed);
+                }
+                FilterDecision::Reject(reason) => {
+                    entry.rejected.fetch_add(1, Ordering::Relaxed);
+                    let verdict = FilterVerdict {
+                        filter: entry.filter.name(),
+                        reason,
+                    };
+                    eprintln!("     \u{23ed}\u{fe0f} [Filter:{}] {}", verdict.filter, verdict.reason);
+                    *self.last_rejection.lock() = Some(verdict.clone());
+                    return ChainOutcome::Reject(verdict);
+                }
+                FilterDecision::Degrade(reason) => {
+                    entry.degraded.fetch_add(1, Ordering::Relaxed);
+                    if degraded.is_none() {
+                        let verdict = FilterVerdict {
+                            filter: entry.filter.name(),
+                            reason,
+                        };
+                        eprintln!(
+                            "     [Filter:{}] shadow-only: {}",
+                            verdict.filter, verdict.reason,
+                        );
+                        degraded = Some(verdict);
+                    }
+                }
+            }
+        }
+        match degraded {
+            Some(verdict) => ChainOutcome::Degrade(verdict),
+            None => ChainOutcome::Pass,
+        }
+    }
+
+    /// Filtre başına sayaçlar (zincir sırasıyla)
+    pub fn stats(&self) -> Vec<FilterStats> {
+        self.entries
+            .iter()
+            .map(|e| FilterStats {
+                name: e.filter.name(),
+                passed: e.passed.load(Ordering::Relaxed),
+                rejected: e.rejected.load(Ordering::Relaxed),
+                degraded: e.degraded.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Son ret (filtre + neden)
+    pub fn last_rejection(&self) -> Option<FilterVerdict> {
+        self.last_rejection.lock().clone()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::PreFilterRejectReason;
+    use std::time::{Duration, Instant};
+
+    fn make_config() -> BotConfig {
+        crate::strategy::gas_spike_tests::make_test_config(0.0002, 0.00005)
+    }
+
+    fn make_state(age_ms: u64, active: bool) -> PoolState {
+        PoolState {
+            is_initialized: active,
+            eth_price_usd: 2500.0,
+            liquidity: 1_000_000,
+            last_update: Instant::now()
+                .checked_sub(Duration::from_millis(age_ms))
+                .unwrap_or_else(Instant::now),
+            // Eski akışta okumalar zaten tek noktadan yapılıyordu: aynı bloğa sabitli
+            pinned_block: Some(100),
+            ..PoolState::default()
+        }
+    }
+
+    /// Karar sonucu: geçti / reddeden kontrol / gölgeye düşüren kontrol
+    #[derive(Debug, PartialEq)]
+    enum Outcome {
+        Pass,
+        Reject(&'static str),
+        Degrade(&'static str),
+    }
+
+    /// Refaktör öncesi check_arbitrage_opportunity / evaluate_and_execute
+    /// kontrollerinin birebir kopyası (aynı sıra, aynı koşullar).
+    fn legacy_inline(ctx: &OpportunityContext) -> Outcome {
+        let config = ctx.config;
+        // Hard-Abort
+        if !ctx.state_a.is_fresh(config.max_staleness_ms) || !ctx.state_b.is_fresh(config.max_staleness_ms) {
+            return Outcome::Reject("staleness");
+        }
+        // Fee tavanı
+        if ctx.fee_a_bps > config.max_pool_fee_bps || ctx.fee_b_bps > config.max_pool_fee_bps {
+            return Outcome::Reject("pool_health");
+        }
+        // Bayat bitmap
+        if matches!(ctx.bitmap_quality, BitmapQuality::Stale { .. }) {
+            return Outcome::Reject("pool_health");
+        }
+        // Likidite
+        if ctx.effective_cap <= 0.001 {
+            return Outcome::Reject("pool_health");
+        }
+        if ctx.effective_cap < config.max_trade_size_weth * 0.1
+            && ctx.effective_cap * ctx.spread_pct / 100.0 < config.min_net_profit_weth
+        {
+            return Outcome::Reject("pool_health");
+        }
+        // PreFilter
+        if let Some(PreFilterResult::Unprofitable { .. }) = ctx.prefilter {
+            return Outcome::Reject("prefilter");
+        }
+        // ProfitGate
+        if ctx.optimal_amount_weth <= 0.0
+            || (ctx.expected_profit_weth < config.min_net_profit_weth && ctx.roi < config.min_profit_roi)
+        {
+            return Outcome::Reject("profit_floor");
+        }
+        // Rakip cezası
+        let degraded = is_competition_skipped(
+            competition_penalty(ctx.competition.competitor_tx_last_block, config),
+            ctx.expected_profit_weth,
+            ctx.roi,
+            config,
+        );
+        // evaluate_and_execute girişi (sadece yürütülecek fırsatlar)
+        if !degraded {
+            if ctx.sell_price_quote <= 0.0
+                || ctx.buy_price_quote <= 0.0
+                || ctx.optimal_amount_weth <= 0.0
+                || !ctx.expected_profit_weth.is_finite()
+            {
+                return Outcome::Reject("execution_sanity");
+            }
+            if !ctx.state_a.is_fresh(config.max_staleness_ms) || !ctx.state_b.is_fresh(config.max_staleness_ms) {
+                return Outcome::Reject("freshness_gate");
+            }
+            return Outcome::Pass;
+        }
+        Outcome::Degrade("competition")
+    }
+
+    /// Yeni akış: PreSizing → PostSizing → (Degrade değilse) Execution
+    fn chained(chain: &FilterChain, ctx: &OpportunityContext) -> Outcome {
+        for stage in [FilterStage::PreSizing, FilterStage::PostSizing] {
+            match chain.run(stage, ctx) {
+                ChainOutcome::Pass => {}
+                ChainOutcome::Reject(v) => return Outcome::Reject(v.filter),
+                ChainOutcome::Degrade(v) => return Outcome::Degrade(v.filter),
+            }
+        }
+        match chain.run(FilterStage::Execution, ctx) {
+            ChainOutcome::Reject(v) => Outcome::Reject(v.filter),
+            _ => Outcome::Pass,
+        }
+    }
+
+    /// Fikstür fırsatlarını eski satır içi mantık ve yeni zincirden geçir —
+    /// her kombinasyonda sonuç aynı olmalı.
+    #[test]
+    fn test_chain_matches_legacy_inline_logic() {
+        let config = make_config();
+        let chain = FilterChain::default_chain();
+        let fresh = make_state(0, true);
+        let stale = make_state(config.max_staleness_ms as u64 + 5_000, true);
+        let inactive = make_state(0, false);
+
+        let profitable = PreFilterResult::Profitable { estimated_profit_weth: 0.01, spread_ratio: 0.02 };
+        let unprofitable = PreFilterResult::Unprofitable { reason: PreFilterRejectReason::SpreadBelowFees };
+
+        let mut cases = 0;
+        for (state_a, state_b) in [(&fresh, &fresh), (&stale, &fresh), (&fresh, &inactive)] {
+            for fee_b_bps in [5u32, config.max_pool_fee_bps + 1] {
+                for bitmap_stale in [false, true] {
+                    for effective_cap in [0.0005, 0.2, 50.0] {
+                        for spread_pct in [0.001, 2.0] {
+                            for prefilter in [profitable, unprofitable] {
+                                for (amount, profit) in [(1.0, 0.01), (1.0, 0.000_01), (0.0, 0.0), (1.0, f64::NAN)] {
+                                    for competitor_txs in [0u32, 3, 10] {
+                                        let mut ctx = OpportunityContext::new(&config, state_a, state_b);
+                                        ctx.fee_a_bps = 5;
+                                        ctx.fee_b_bps = fee_b_bps;
+                                        // Eski akışta bitmap kalitesi kapısı yoktu: taze = Real
+                                        ctx.bitmap_quality = if bitmap_stale {
+                                            BitmapQuality::Stale { age_blocks: 10 }
+                                        } else {
+                                            BitmapQuality::Real
+                                        };
+                                        ctx.effective_cap = effective_cap;
+                                        ctx.spread_pct = spread_pct;
+                                        ctx.prefilter = Some(prefilter);
+                                        ctx.optimal_amount_weth = amount;
+                                        ctx.expected_profit_weth = profit;
+                                        ctx.roi = if amount > 0.0 { profit / amount } else { 0.0 };
+                                        ctx.buy_price_quote = 2450.0;
+                                        ctx.sell_price_quote = 2500.0;
+                                        ctx.competition = CompetitionSnapshot {
+                                            block_number: 100,
+                                            competitor_tx_last_block: competitor_txs,
+                                        };
+                                        assert_eq!(
+                                            chained(&chain, &ctx),
+                                            legacy_inline(&ctx),
+                                            "fee_b={} bitmap_stale={} cap={} spread={} amount={} profit={} txs={}",
+                                            fee_b_bps, bitmap_stale, effective_cap, spread_pct, amount, profit, competitor_txs,
+                                        );
+                                        cases += 1;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(cases, 3 * 2 * 2 * 3 * 2 * 2 * 4 * 3);
+    }
+
+    #[test]
+    fn test_chain_counts_and_last_rejection() {
+        let config = make_config();
+        let chain = FilterChain::default_chain();
+        let fresh = make_state(0, true);
+        let mut ctx = OpportunityContext::new(&config, &fresh, &fresh);
+        ctx.fee_a_bps = 5;
+        ctx.fee_b_bps = 5;
+        ctx.effective_cap = 0.0005;
+
+        let outcome = chain.run(FilterStage::PreSizing, &ctx);
+        assert!(matches!(outcome, ChainOutcome::Reject(ref v) if v.filter == "pool_health"));
+
+        let stats = chain.stats();
+        assert_eq!(stats[0], FilterStats { name: "staleness", passed: 1, rejected: 0, degraded: 0 });
+        assert_eq!(stats[1], FilterStats { name: "pool_health", passed: 0, rejected: 1, degraded: 0 });
+        // Ret sonrası filtreler çalışmaz
+        assert_eq!(stats[2].passed + stats[2].rejected, 0);
+        assert_eq!(chain.last_rejection().map(|v| v.filter), Some("pool_health"));
+    }
+
+    /// Degrade zinciri durdurmaz; sonraki Reject önceliklidir
+    #[test]
+    fn test_degrade_then_reject() {
+        struct AlwaysDegrade;
+        impl OpportunityFilter for AlwaysDegrade {
+            fn name(&self) -> &'static str { "always_degrade" }
+            fn stage(&self) -> FilterStage { FilterStage::PostSizing }
+            fn evaluate(&self, _: &OpportunityContext) -> FilterDecision {
+                FilterDecision::Degrade("test".into())
+            }
+        }
+
+        let config = make_config();
+        let fresh = make_state(0, true);
+        let mut ctx = OpportunityContext::new(&config, &fresh, &fresh);
+        ctx.optimal_amount_weth = 1.0;
+        ctx.expected_profit_weth = 0.01;
+        ctx.roi = 0.01;
+
+        let chain = FilterChain::new(vec![Box::new(AlwaysDegrade), Box::new(ProfitFloorFilter)]);
+        assert!(matches!(
+            chain.run(FilterStage::PostSizing, &ctx),
+            ChainOutcome::Degrade(ref v) if v.filter == "always_degrade"
+        ));
+
+        ctx.optimal_amount_weth = 0.0;
+        assert!(matches!(
+            chain.run(FilterStage::PostSizing, &ctx),
+            ChainOutcome::Reject(ref v) if v.filter == "profit_floor"
+        ));
+    }
+
+    /// REQUIRE_BITMAP_FOR_EXECUTION: Real geçer, Fallback/Stale gölgeye düşer
+    #[test]
+    fn test_bitmap_quality_gating() {
+        let mut config = make_config();
+        let fresh = make_state(0, true);
+        let chain = FilterChain::new(vec![Box::new(BitmapQualityFilter)]);
+
+        let outcome_for = |config: &BotConfig, quality: BitmapQuality| {
+            let mut ctx = OpportunityContext::new(config, &fresh, &fresh);
+            ctx.bitmap_quality = quality;
+            chain.run(FilterStage::PostSizing, &ctx)
+        };
+
+        config.require_bitmap_for_execution = true;
+        assert_eq!(outcome_for(&config, BitmapQuality::Real), ChainOutcome::Pass);
+        for quality in [BitmapQuality::Fallback, BitmapQuality::Stale { age_blocks: 9 }] {
+            match outcome_for(&config, quality) {
+                ChainOutcome::Degrade(v) => {
+                    assert_eq!(v.filter, "bitmap_quality");
+                    assert!(v.reason.contains(&quality.to_string()), "{}", v.reason);
+                }
+                other => panic!("{:?} should be shadow-only, got {:?}", quality, other),
+            }
+        }
+
+        config.require_bitmap_for_execution = false;
+        assert_eq!(outcome_for(&config, BitmapQuality::Fallback), ChainOutcome::Pass);
+    }
+
+    /// Aynı bloğa sabitli havuzlar geçer; farklı blok veya "latest" gölgeye düşer
+    #[test]
+    fn test_block_pin_gating() {
+        let config = make_config();
+        let chain = FilterChain::new(vec![Box::new(BlockPinFilter)]);
+        let pinned_at = |pin: Option<u64>| PoolState { pinned_block: pin, ..make_state(0, true) };
+
+        let outcome_for = |a: Option<u64>, b: Option<u64>| {
+            let (state_a, state_b) = (pinned_at(a), pinned_at(b));
+            chain.run(FilterStage::PostSizing, &OpportunityContext::new(&config, &state_a, &state_b))
+        };
+
+        assert_eq!(outcome_for(Some(100), Some(100)), ChainOutcome::Pass);
+        for (a, b, expected) in [
+            (Some(100), Some(101), "A=#100 B=#101"),
+            (Some(100), None, "A=#100 B=latest"),
+            (None, None, "A=latest B=latest"),
+        ] {
+            match outcome_for(a, b) {
+                ChainOutcome::Degrade(v) => {
+                    assert_eq!(v.filter, "block_pin");
+                    assert!(v.reason.contains(expected), "{}", v.reason);
+                }
+                other => panic!("{:?}/{:?} should be shadow-only, got {:?}", a, b, other),
+            }
+        }
+    }
+
+    /// MIN_ROBUSTNESS: 1 tick'te ölen ya da eşiğin altındaki fırsat reddedilir;
+    /// eşik 0 ya da hassasiyet ölçülmemişse geçer
+    #[test]
+    fn test_robustness_gating() {
+        use alloy::primitives::U256;
+
+        let mut config = make_config();
+        let fresh = make_state(0, true);
+        let chain = FilterChain::new(vec![Box::new(RobustnessFilter)]);
+        let sensitivity = |p1: u64, p2: u64| ProfitSensitivity {
+            base_profit_wei: U256::from(1_000u64),
+            profit_after_1tick_wei: U256::from(p1),
+            profit_after_2tick_wei: U256::from(p2),
+        };
+        let outcome_for = |config: &BotConfig, s: Option<ProfitSensitivity>| {
+            let mut ctx = OpportunityContext::new(config, &fresh, &fresh);
+            ctx.sensitivity = s;
+            chain.run(FilterStage::Execution, &ctx)
+        };
+
+        // Robust: (0.9 + 0.8) / 2 = 0.85; kırılgan: (0.2 + 0.0) / 2 = 0.10; 1 tick'te ölür: 0
+        let robust = sensitivity(900, 800);
+        let fragile = sensitivity(200, 0);
+        let dies = sensitivity(0, 0);
+        assert!((robust.robustness() - 0.85).abs() < 1e-12);
+        assert!((fragile.robustness() - 0.10).abs() < 1e-12);
+        assert_eq!(dies.robustness(), 0.0);
+
+        config.min_robustness = 0.0;
+        for s in [robust.clone(), fragile.clone(), dies.clone()] {
+            assert_eq!(outcome_for(&config, Some(s)), ChainOutcome::Pass, "eşik 0 = kapalı");
+        }
+
+        // En küçük eşik yalnızca "1 tick sonrası kâr > 0" şartı demek
+        config.min_robustness = 0.01;
+        assert_eq!(outcome_for(&config, Some(fragile.clone())), ChainOutcome::Pass);
+        match outcome_for(&config, Some(dies)) {
+            ChainOutcome::Reject(v) => {
+                assert_eq!(v.filter, "robustness");
+                assert!(v.reason.contains("1tick=0 wei"), "{}", v.reason);
+            }
+            other => panic!("1 tick'te ölen fırsat reddedilmeli, got {:?}", other),
+        }
+
+        config.min_robustness = 0.5;
+        assert_eq!(outcome_for(&config, Some(robust)), ChainOutcome::Pass);
+        assert!(matches!(outcome_for(&config, Some(fragile)), ChainOutcome::Reject(ref v) if v.filter == "robustness"));
+        // Bütçe yetmedi → ölçüm yok → filtre karar veremez, geçer
+        assert_eq!(outcome_for(&config, None), ChainOutcome::Pass);
+    }
+
+    #[test]
+    fn test_from_config_order_and_unknown_names() {
+        let mut config = make_config();
+        config.opportunity_filters = vec!["profit_floor".into(), "nope".into(), "staleness".into()];
+        let names: Vec<_> = FilterChain::from_config(&config).stats().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["profit_floor", "staleness"]);
+
+        config.opportunity_filters.clear();
+        let names: Vec<_> = FilterChain::from_config(&config).stats().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, DEFAULT_FILTER_ORDER.to_vec());
+    }
+}
@@ -0,0 +1,513 @@
+// ============================================================================
+//  PAIR SCHEDULER v1.0 — Blok Bütçeli Çift Değerlendirme Önceliği
+//
+//  Çok havuzlu kurulumda her çift her blokta bir NR koşusu demek; oynak
+//  bloklarda tüm çiftlerin değerlendirmesi gecikme bütçesine sığmayabilir.
+//  Çiftler geçmişte ürettikleri değere göre sıralanır, blok bütçesi dolunca
+//  kalan çiftler o blokta atlanır.
+//
+//  ✓ Skor: değerlendirme başına bulunan beklenen kârın üstel ortalaması
+//    (PAIR_SCORE_DECAY) — fırsat çıkmayan değerlendirme 0 ile söndürür;
+//    receipt gelince gerçekleşen kâr modellenenin yerine düzeltilir
+//  ✓ Sıra: skor azalan; eşitlikte en uzun süredir değerlendirilmeyen önce
+//  ✓ Bütçe: STRATEGY_TIME_BUDGET_MS dolunca kalan çiftler atlanır ve sayılır
+//    (ilk sıradaki ve garanti slotundaki çift kesilmez)
+//  ✓ Rotasyon: PAIR_ROTATION_BLOCKS blok değerlendirilmemiş en bayat çift
+//    garanti slotuna (2. sıra) alınır — düşük skorlu çiftler de örneklenir,
+//    skorları tazelenir
+//  ✓ Stats kutusu + /status (pair_schedule)
+// ============================================================================
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use alloy::primitives::Address;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::types::BotConfig;
+
+/// Yönsüz çift anahtarı (küçük adres önce) — combo indeksleri havuz
+/// keşfinde yeniden kurulur, skorlar adres çiftine bağlıdır
+pub type PairKey = (Address, Address);
+
+pub fn pair_key(a: Address, b: Address) -> PairKey {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Değerlendirme maliyeti ortalamasının ağırlığı (yalnızca gözlem)
+const COST_EWMA_WEIGHT: f64 = 0.2;
+
+/// Stats kutusunda listelenen çift sayısı
+const STATS_TOP_PAIRS: usize = 5;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Politika + Plan
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SchedulerPolicy {
+    /// Blok başına değerlendirme bütçesi — None = sınırsız (yalnızca sıralama)
+    pub budget: Option<Duration>,
+    /// Bu kadar blok değerlendirilmeyen çift garanti slotuna adaydır
+    pub rotation_blocks: u64,
+    /// EWMA ağırlığı: yeni değerlendirmenin skora katkısı (0..1]
+    pub decay: f64,
+}
+
+impl SchedulerPolicy {
+    pub fn from_config(config: &BotConfig) -> Self {
+        Self {
+            budget: Some(Duration::from_millis(config.strategy_time_budget_ms)).filter(|b| !b.is_zero()),
+            rotation_blocks: config.pair_rotation_blocks,
+            decay: config.pair_score_decay,
+        }
+    }
+}
+
+/// Bu blokta değerlendirilebilir çift (aktif, kara listede değil, takip beklemiyor)
+#[derive(Debug, Clone, Copy)]
+pub struct Candidate<'a> {
+    pub idx: usize,
+    pub key: PairKey,
+    pub name: &'a str,
+}
+
+/// Bloğun değerlendirme sırası — (combo indeksi, anahtar)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockPlan {
+    pub order: Vec<(usize, PairKey)>,
+    /// Garanti slotundaki çiftin sırası (rotasyon — her zaman 1)
+    pub guaranteed: Option<usize>,
+}
+
+impl BlockPlan {
+    /// İlk sıra ve garanti slotu bütçeyle kesilmez
+    fn protected(&self, rank: usize) -> bool {
+        rank == 0 || self.guaranteed == Some(rank)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Zamanlayıcı
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+struct PairEntry {
+    name: String,
+    /// Değerlendirme başına beklenen değer (WETH, EWMA)
+    score: f64,
+    evaluations: u64,
+    skipped: u64,
+    /// Garanti slotuyla değerlendirmeye alınma sayısı
+    rotations: u64,
+    last_evaluated: Option<u64>,
+    /// Değerlendirme maliyeti (µs, EWMA)
+    cost_us: f64,
+}
+
+/// Çift başına gözlem (/status, stats kutusu)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PairStatus {
+    pub pair: String,
+    pub score_weth: f64,
+    pub evaluations: u64,
+    pub skipped: u64,
+    pub rotations: u64,
+    pub last_evaluated_block: Option<u64>,
+    pub avg_cost_ms: f64,
+}
+
+/// /status → pair_schedule
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScheduleStatus {
+    /// None = bütçe kapalı
+    pub budget_ms: Option<u64>,
+    pub rotation_blocks: u64,
+    pub blocks: u64,
+    /// En az bir çiftin bütçe yüzünden atlandığı bloklar
+    pub cutoff_blocks: u64,
+    pub skipped_evaluations: u64,
+    pub rotations: u64,
+    /// Son bloğun değerlendirme sırası (çift adları)
+    pub last_order: Vec<String>,
+    /// Son blokta bütçeye sığıp değerlendirilen çift sayısı
+    pub last_evaluated: usize,
+    /// Skora göre azalan
+    pub pairs: Vec<PairStatus>,
+}
+
+pub struct PairScheduler {
+    policy: SchedulerPolicy,
+    pairs: HashMap<PairKey, PairEntry>,
+    blocks: u64,
+    cutoff_blocks: u64,
+    skipped: u64,
+    rotations: u64,
+    last_order: Vec<PairKey>,
+    /// Son planlanan blok — kesinti ve değerlendirme sayımı blok başına
+    current_block: u64,
+    cut_this_block: bool,
+    evaluated_this_block: usize,
+}
+
+impl PairScheduler {
+    pub fn new(policy: SchedulerPolicy) -> Self {
+        Self {
+            policy,
+            pairs: HashMap::new(),
+            blocks: 0,
+            cutoff_blocks: 0,
+            skipped: 0,
+            rotations: 0,
+            last_order: Vec::new(),
+            current_block: 0,
+            cut_this_block: false,
+            evaluated_this_block: 0,
+        }
+    }
+
+    fn is_stale(&self, key: &PairKey, block: u64) -> bool {
+        self.pairs[key]
+            .last_evaluated
+            .is_none_or(|last| block.saturating_sub(last) >= self.policy.rotation_blocks)
+    }
+
+    /// Bloğun sırası: skor azalan (eşitlikte en bayat, sonra indeks). Garanti
+    /// slotu: ilk sıra dışındaki bayat çiftlerin en bayatı 2. sıraya alınır.
+    pub fn plan(&mut self, block: u64, candidates: &[Candidate<'_>]) -> BlockPlan {
+        self.blocks += 1;
+        self.current_block = block;
+        self.cut_this_block = false;
+        self.evaluated_this_block = 0;
+        for c in candidates {
+            let entry = self.pairs.entry(c.key).or_default();
+            if entry.name.is_empty() {
+                entry.name = c.name.to_string();
+            }
+        }
+
+        let mut order: Vec<(usize, PairKey)> = candidates.iter().map(|c| (c.idx, c.key)).collect();
+        order.sort_by(|a, b| {
+            let (ea, eb) = (&self.pairs[&a.1], &self.pairs[&b.1]);
+            eb.score
+                .total_cmp(&ea.score)
+                .then(ea.last_evaluated.cmp(&eb.last_evaluated))
+                .then(a.0.cmp(&b.0))
+        });
+
+        let stalest = order
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, (_, key))| self.is_stale(key, block))
+            .min_by_key(|(rank, (_, key))| (self.pairs[key].last_evaluated, *rank))
+            .map(|(rank, _)| rank);
+        let guaranteed = stalest.map(|rank| {
+            let promoted = order.remove(rank);
+            order.insert(1, promoted);
+            self.rotations += 1;
+            if let Some(entry) = self.pairs.get_mut(&promoted.1) {
+                entry.rotations += 1;
+            }
+            1
+        });
+
+        self.last_order = order.iter().map(|(_, key)| *key).collect();
+        BlockPlan { order, guaranteed }
+    }
+
+    /// `rank`'teki çift değerlendirilsin mi? Bütçe (taramanın başından beri
+    /// geçen `elapsed`) dolmuşsa korumasız çift atlanır ve sayılır.
+    pub fn admit(&mut self, plan: &BlockPlan, rank: usize, elapsed: Duration) -> bool {
+        if plan.protected(rank) || self.policy.budget.is_none_or(|budget| elapsed < budget) {
+            return true;
+        }
+        self.skipped += 1;
+        if !self.cut_this_block {
+            self.cut_this_block = true;
+            self.cutoff_blocks += 1;
+        }
+        if let Some(entry) = plan.order.get(rank).and_then(|(_, key)| self.pairs.get_mut(key)) {
+            entry.skipped += 1;
+        }
+        false
+    }
+
+    /// Değerlendirme sonucu: bulunan fırsatın beklenen kârı (yoksa 0) ve süresi
+    pub fn record_evaluation(&mut self, key: PairKey, block: u64, value_weth: f64, cost: Duration) {
+        let decay = self.policy.decay;
+        let Some(entry) = self.pairs.get_mut(&key) else {
+            return;
+        };
+        let value = if value_weth.is_finite() { value_weth } else { 0.0 };
+        entry.score += decay * (value - entry.score);
+        let cost_us = cost.as_micros() as f64;
+        entry.cost_us = if entry.evaluations == 0 {
+            cost_us
+        } else {
+            entry.cost_us + COST_EWMA_WEIGHT * (cost_us - entry.cost_us)
+        };
+        entry.evaluations += 1;
+        entry.last_evaluated = Some(block);
+        if block == self.current_block {
+            self.evaluated_this_block += 1;
+        }
+    }
+
+    /// Receipt: değerlendirmede modellenen kâr yerine gerçekleşen kâr
+    pub fn record_realized(&mut self, key: PairKey, expected_weth: f64, realized_weth: f64) {
+        let decay = self.policy.decay;
+        if let Some(entry) = self.pairs.get_mut(&key) {
+            let correction = realized_weth - expected_weth;
+            if correction.is_finite() {
+                entry.score += decay * correction;
+            }
+        }
+    }
+
+    fn name(&self, key: &PairKey) -> String {
+        self.pairs.get(key).map(|e| e.name.clone()).unwrap_or_default()
+    }
+
+    pub fn status(&self) -> ScheduleStatus {
+        let mut pairs: Vec<PairStatus> = self
+            .pairs
+            .values()
+            .map(|e| PairStatus {
+                pair: e.name.clone(),
+                score_weth: e.score,
+                evaluations: e.evaluations,
+                skipped: e.skipped,
+                rotations: e.rotations,
+                last_evaluated_block: e.last_evaluated,
+                avg_cost_ms: e.cost_us / 1_000.0,
+            })
+            .collect();
+        pairs.sort_by(|a, b| b.score_weth.total_cmp(&a.score_weth).then_with(|| a.pair.cmp(&b.pair)));
+        ScheduleStatus {
+            budget_ms: self.policy.budget.map(|b| b.as_millis() as u64),
+            rotation_blocks: self.policy.rotation_blocks,
+            blocks: self.blocks,
+            cutoff_blocks: self.cutoff_blocks,
+            skipped_evaluations: self.skipped,
+            rotations: self.rotations,
+            last_order: self.last_order.iter().map(|key| self.name(key)).collect(),
+            last_evaluated: self.evaluated_this_block,
+            pairs,
+        }
+    }
+
+    /// Özet satırı + skora göre ilk STATS_TOP_PAIRS çift
+    pub fn stats_lines(&self) -> Vec<String> {
+        let status = self.status();
+        let budget = status.budget_ms.map_or("off".to_string(), |ms| format!("{}ms", ms));
+        let mut lines = vec![format!(
+            "budget {} | {} blocks, {} cut ({:.1}%) | {} pair evals skipped | {} rotations (every {} blocks) | last {}/{} evaluated",
+            budget,
+            status.blocks,
+            status.cutoff_blocks,
+            status.cutoff_blocks as f64 / status.blocks.max(1) as f64 * 100.0,
+            status.skipped_evaluations,
+            status.rotations,
+            status.rotation_blocks,
+            status.last_evaluated,
+            status.last_order.len(),
+        )];
+        lines.extend(status.pairs.iter().take(STATS_TOP_PAIRS).map(|p| {
+            format!(
+                "{}: score {:.8} WETH | {} evals, {} skipped, {} rotated | {:.2}ms avg",
+                p.pair, p.score_weth, p.evaluations, p.skipped, p.rotations, p.avg_cost_ms,
+            )
+        }));
+        lines
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Zamanlayıcı
+// ─────────────────────────────────────────────────────────────────────────────
+
+static SCHEDULER: Mutex<Option<PairScheduler>> = parking_lot::const_mutex(None);
+
+/// main, run_bot öncesi — skorlar reconnect'lerde korunur
+pub fn init(config: &BotConfig) {
+    *SCHEDULER.lock() = Some(PairScheduler::new(SchedulerPolicy::from_config(config)));
+}
+
+/// Kurulmadıysa aday sırası aynen, bütçe yok
+pub fn plan(block: u64, candidates: &[Candidate<'_>]) -> BlockPlan {
+    match SCHEDULER.lock().as_mut() {
+        Some(scheduler) => scheduler.plan(block, candidates),
+        None => BlockPlan { order: candidates.iter().map(|c| (c.idx, c.key)).collect(), guaranteed: None },
+    }
+}
+
+pub fn admit(plan: &BlockPlan, rank: usize, elapsed: Duration) -> bool {
+    SCHEDULER.lock().as_mut().is_none_or(|s| s.admit(plan, rank, elapsed))
+}
+
+pub fn record_evaluation(key: PairKey, block: u64, value_weth: f64, cost: Duration) {
+    if let Some(scheduler) = SCHEDULER.lock().as_mut() {
+        scheduler.record_evaluation(key, block, value_weth, cost);
+    }
+}
+
+/// Receipt görevi: (buy, sell) havuz yönü → yönsüz çift
+pub fn record_realized(buy_pool: Address, sell_pool: Address, expected_weth: f64, realized_weth: f64) {
+    if let Some(scheduler) = SCHEDULER.lock().as_mut() {
+        scheduler.record_realized(pair_key(buy_pool, sell_pool), expected_weth, realized_weth);
+    }
+}
+
+pub fn status() -> Option<ScheduleStatus> {
+    SCHEDULER.lock().as_ref().map(PairScheduler::status)
+}
+
+pub fn stats_lines() -> Vec<String> {
+    SCHEDULER.lock().as_ref().map(PairScheduler::stats_lines).unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(i: u8) -> PairKey {
+        pair_key(Address::with_last_byte(2 * i + 1), Address::with_last_byte(2 * i + 2))
+    }
+
+    fn policy(budget_ms: u64, rotation_blocks: u64, decay: f64) -> SchedulerPolicy {
+        SchedulerPolicy {
+            budget: Some(Duration::from_millis(budget_ms)).filter(|b| !b.is_zero()),
+            rotation_blocks,
+            decay,
+        }
+    }
+
+    const NAMES: [&str; 5] = ["P0", "P1", "P2", "P3", "P4"];
+
+    fn candidates(n: usize) -> Vec<Candidate<'static>> {
+        (0..n).map(|i| Candidate { idx: i, key: key(i as u8), name: NAMES[i] }).collect()
+    }
+
+    /// Ana döngünün tarama döngüsü, sentetik çift maliyetleri ve değerleriyle.
+    /// Dönüş: bu blokta değerlendirilen combo indeksleri (sırayla).
+    fn run_block(s: &mut PairScheduler, block: u64, costs_ms: &[u64], values: &[f64]) -> Vec<usize> {
+        let plan = s.plan(block, &candidates(costs_ms.len()));
+        let mut elapsed = Duration::ZERO;
+        let mut evaluated = Vec::new();
+        for (rank, &(idx, key)) in plan.order.iter().enumerate() {
+            if !s.admit(&plan, rank, elapsed) {
+                continue;
+            }
+            let cost = Duration::from_millis(costs_ms[idx]);
+            elapsed += cost;
+            s.record_evaluation(key, block, values[idx], cost);
+            evaluated.push(idx);
+        }
+        evaluated
+    }
+
+    #[test]
+    fn test_pair_key_is_direction_free() {
+        let (a, b) = (Address::with_last_byte(9), Address::with_last_byte(3));
+        assert_eq!(pair_key(a, b), pair_key(b, a));
+        assert_eq!(pair_key(a, b).0, b);
+    }
+
+    /// Skor sırası + bütçe kesintisi: pahalı çiftler bütçeyi doldurunca
+    /// düşük skorlular atlanır ve sayılır; ilk sıradaki bütçeyi aşsa da çalışır
+    #[test]
+    fn test_ordering_by_score_and_budget_cutoff() {
+        let mut s = PairScheduler::new(policy(100, 1_000, 0.5));
+        // Isınma: hepsi bir kez değerlendirilir (bütçe yok sayılacak kadar ucuz)
+        let values = [0.001, 0.004, 0.0, 0.002];
+        assert_eq!(run_block(&mut s, 1, &[1, 1, 1, 1], &values).len(), 4);
+        let plan = s.plan(2, &candidates(4));
+        assert_eq!(plan.order.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![1, 3, 0, 2]);
+        assert_eq!(plan.guaranteed, None, "nothing stale yet");
+
+        // P1 ve P3 60ms — bütçe (100ms) ikinciden sonra dolar
+        let evaluated = run_block(&mut s, 3, &[60, 60, 60, 60], &values);
+        assert_eq!(evaluated, vec![1, 3]);
+        let status = s.status();
+        assert_eq!((status.cutoff_blocks, status.skipped_evaluations), (1, 2));
+        assert_eq!(status.last_order, vec!["P1", "P3", "P0", "P2"]);
+        assert_eq!(status.last_evaluated, 2);
+        assert_eq!(status.pairs[0].pair, "P1");
+
+        // İlk sıra tek başına bütçeyi aşsa da değerlendirilir
+        assert_eq!(run_block(&mut s, 4, &[500, 500, 500, 500], &values), vec![1]);
+        assert_eq!(s.status().cutoff_blocks, 2);
+
+        // Bütçe kapalı: hepsi, skor sırasıyla
+        let mut unlimited = PairScheduler::new(policy(0, 1_000, 0.5));
+        run_block(&mut unlimited, 1, &[1, 1, 1, 1], &values);
+        assert_eq!(run_block(&mut unlimited, 2, &[900, 900, 900, 900], &values), vec![1, 3, 0, 2]);
+        assert_eq!(unlimited.status().skipped_evaluations, 0);
+    }
+
+    /// Rotasyon: bütçe yalnızca ilk sıraya yetse de her çift en geç
+    /// K + (n - 1) blokta bir değerlendirilir ve skoru tazelenir
+    #[test]
+    fn test_rotation_guarantees_periodic_sampling() {
+        const K: u64 = 10;
+        let n = 5;
+        let mut s = PairScheduler::new(policy(10, K, 0.3));
+        // Düşük skorlu çiftlerin sırası bayatlık sırasından farklı: rotasyon
+        // olmasa 2. sıradaki P1 her blok bütçeye takılırdı
+        let values = [0.01, 0.0003, 0.0002, 0.0001, 0.0];
+        let mut last_seen = vec![0u64; n];
+        let mut max_gap = vec![0u64; n];
+        for block in 1..=400 {
+            for idx in run_block(&mut s, block, &[50; 5], &values) {
+                max_gap[idx] = max_gap[idx].max(block - last_seen[idx]);
+                last_seen[idx] = block;
+            }
+        }
+        for (idx, &gap) in max_gap.iter().enumerate() {
+            assert!(gap < K + n as u64, "P{} starved for {} blocks", idx, gap);
+            assert!(last_seen[idx] > 400 - (K + n as u64), "P{} not sampled recently", idx);
+        }
+        // Yüksek skorlu çift her blok ilk sırada
+        assert_eq!(s.status().pairs[0].pair, "P0");
+        assert_eq!(s.status().pairs[0].evaluations, 400);
+        assert!(s.status().rotations > 0);
+        assert!(s.status().pairs.iter().skip(1).all(|p| p.rotations > 0 && p.skipped > 0));
+    }
+
+    /// Skor, değerlendirme başına kârın üstel ortalaması: fırsat kesilince
+    /// geometrik söner, receipt gerçekleşen kârla düzeltir
+    #[test]
+    fn test_score_decay_and_realized_correction() {
+        let mut s = PairScheduler::new(policy(0, 1_000, 0.5));
+        s.plan(1, &candidates(2));
+        s.record_evaluation(key(0), 1, 0.008, Duration::from_millis(2));
+        assert!((s.status().pairs[0].score_weth - 0.004).abs() < 1e-15);
+        for (block, expected) in [(2, 0.002), (3, 0.001), (4, 0.0005)] {
+            s.record_evaluation(key(0), block, 0.0, Duration::from_millis(4));
+            assert!((s.status().pairs[0].score_weth - expected).abs() < 1e-15);
+        }
+        // NaN değer skoru bozmaz; ortalama maliyet izlenir
+        s.record_evaluation(key(0), 5, f64::NAN, Duration::from_millis(4));
+        let p0 = s.status().pairs.into_iter().find(|p| p.pair == "P0").unwrap();
+        assert!((p0.score_weth - 0.00025).abs() < 1e-15);
+        assert!(p0.avg_cost_ms > 2.0 && p0.avg_cost_ms < 4.0);
+
+        // Revert: beklenen 0.002, gerçekleşen -0.0004 → skor 0.5 × (-0.0024) düşer
+        s.record_realized(key(0), 0.002, -0.0004);
+        let p0 = s.status().pairs.into_iter().find(|p| p.pair == "P0").unwrap();
+        assert!((p0.score_weth - (0.00025 - 0.0012)).abs() < 1e-15);
+        // Negatif skorlu çift sıfır skorlu (hiç fırsat vermemiş) çiftin arkasına düşer
+        let plan = s.plan(6, &candidates(2));
+        assert_eq!(plan.order[0].0, 1);
+    }
+}
@@ -0,0 +1,554 @@
+// ============================================================================
+//  PAPER EXECUTION v1.0 — Gölge Modda Yürütme Defteri Provası
+//
+//  Gölge mod tüm nonce mantığını atlar: NonceManager, uçuştaki TX kaydı,
+//  geri alma yolları ve takılı TX makinesi ilk kez üretimde birlikte çalışır.
+//  PAPER_EXECUTION=true (gölge modu zorunlu kılar) ile fırsatlar gölge
+//  kaydından sonra yürütme hattının tamamından geçer; yalnızca imza ve
+//  private RPC gönderimi sahte receipt üreticisiyle yer değiştirir.
+//
+//  ✓ Kendi NonceManager'ı (0'dan) ve uçuştaki TX kaydı — nonce_state.json,
+//    zincir nonce'u ve gerçek kayıt hiç görülmez
+//  ✓ Sahte receipt: REVM gas'ı × (base fee + bribe), PAPER_FAILURE_RATE
+//    (revert), PAPER_DROP_RATE (receipt yok → deadline'a kadar takılı),
+//    PAPER_RECEIPT_LATENCY_MS (min-max düz dağılım)
+//  ✓ Takılı TX: deadline geçince kayıttan düşer; nonce senkronu
+//    (nonce_checkpoint::sync_target) boşalan nonce'u yeniden verir
+//  ✓ Muhasebe ayrı: kağıt TradeOutcomes, kağıt yön defteri (DirectionGate —
+//    kısıtlama olayları dahil), JSON log seviyesi "paper", snapshot modu
+//    "paper"; gas defteri / ısı haritası / Telegram / gerçek yön kapısı beslenmez
+//  ✓ Hiçbir gerçek gönderim yolu yok: imzalayıcı ve private RPC sağlayıcısı
+//    kağıt şeritte çağrılmaz
+// ============================================================================
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::{keccak256, B256};
+use colored::*;
+use parking_lot::Mutex;
+use rand::Rng;
+
+use crate::direction_gate::{Direction, DirectionGate, DirectionKey, GateEvent, GatePolicy};
+use crate::executor::{InflightGuard, TradeOutcomes};
+use crate::nonce_checkpoint::{self, InflightTx};
+use crate::opportunity_filter::FilterDecision;
+use crate::types::{BotConfig, NonceManager};
+
+/// Kağıt kayıtların etiketi (JSON log seviyesi, snapshot modu)
+pub const PAPER_TAG: &str = "paper";
+
+/// Receipt gecikmesinin üst sınırı — gerçek receipt görevinin zaman aşımı
+const MAX_RECEIPT_LATENCY_MS: u64 = 10_000;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Ayarlar
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// PAPER_RECEIPT_LATENCY_MS: "min-max" (uçlar dahil) ya da "max" (0-max)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyRange {
+    pub min_ms: u64,
+    pub max_ms: u64,
+}
+
+impl LatencyRange {
+    pub const DEFAULT: Self = Self { min_ms: 300, max_ms: 2_500 };
+
+    fn sample(&self, rng: &mut impl Rng) -> Duration {
+        Duration::from_millis(rng.gen_range(self.min_ms..=self.max_ms))
+    }
+}
+
+impl std::str::FromStr for LatencyRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        let parse = |v: &str| v.trim().parse::<u64>().map_err(|_| format!("invalid latency range '{}'", s));
+        let (min_ms, max_ms) = match s.split_once('-') {
+            Some((min, max)) => (parse(min)?, parse(max)?),
+            None => (0, parse(s)?),
+        };
+        if min_ms > max_ms || max_ms > MAX_RECEIPT_LATENCY_MS {
+            return Err(format!(
+                "latency range must satisfy min <= max <= {}, got '{}'",
+                MAX_RECEIPT_LATENCY_MS, s
+            ));
+        }
+        Ok(Self { min_ms, max_ms })
+    }
+}
+
+impl std::fmt::Display for LatencyRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.min_ms, self.max_ms)
+    }
+}
+
+/// Sahte receipt üreticisinin ayarları (PAPER_*)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaperSettings {
+    /// Dahil edilen TX'in revert olasılığı
+    pub failure_rate: f64,
+    /// Receipt'in hiç gelmeme olasılığı (takılı TX)
+    pub drop_rate: f64,
+    pub latency: LatencyRange,
+}
+
+impl PaperSettings {
+    pub fn from_config(config: &BotConfig) -> Self {
+        Self {
+            failure_rate: config.paper_failure_rate,
+            drop_rate: config.paper_drop_rate,
+            latency: config.paper_receipt_latency,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sahte Receipt
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperOutcome {
+    Landed,
+    Reverted,
+    /// Receipt yok — TX deadline'a kadar uçuşta kalır
+    Dropped,
+}
+
+/// Zarlar dışarıdan verilir (0..1) — üretim `thread_rng`, testler sabit
+pub fn draw_outcome(settings: &PaperSettings, drop_roll: f64, fail_roll: f64) -> PaperOutcome {
+    if drop_roll < settings.drop_rate {
+        PaperOutcome::Dropped
+    } else if fail_roll < settings.failure_rate {
+        PaperOutcome::Reverted
+    } else {
+        PaperOutcome::Landed
+    }
+}
+
+/// Kağıt şeride verilen TX — executor'ın gönderim argümanlarının karşılığı
+#[derive(Debug, Clone)]
+pub struct PaperSubmission {
+    /// İmzalanmamış sahte ham TX (nonce + calldata) — hash buradan
+    pub raw_tx: Vec<u8>,
+    pub nonce: u64,
+    pub deadline_block: u64,
+    pub current_block: u64,
+    pub expected_profit_weth: f64,
+    /// REVM gas'ı — receipt'in gas_used'ı
+    pub gas_used: u64,
+    /// base fee + dinamik bribe priority fee (wei/gas)
+    pub gas_price_wei: u128,
+    pub direction: Option<Direction>,
+}
+
+/// İmza yerine: nonce + calldata'dan deterministik sahte ham TX
+pub fn paper_raw_tx(nonce: u64, calldata: &[u8]) -> Vec<u8> {
+    let mut raw = Vec::with_capacity(PAPER_TAG.len() + 8 + calldata.len());
+    raw.extend_from_slice(PAPER_TAG.as_bytes());
+    raw.extend_from_slice(&nonce.to_be_bytes());
+    raw.extend_from_slice(calldata);
+    raw
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kağıt Şerit
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Ledger {
+    in_flight: BTreeMap<u64, InflightTx>,
+    /// Kağıt "zincirin" onaylı sayımı: çözülen en yüksek nonce + 1
+    chain_next: u64,
+    directions: DirectionGate,
+}
+
+/// Kağıt yürütmenin tüm defterleri — reconnect'ler boyunca tek örnek
+pub struct PaperLane {
+    settings: PaperSettings,
+    /// Kağıt nonce sayacı — zincirle ilişkisiz, 0'dan
+    pub nonce_manager: Arc<NonceManager>,
+    pub outcomes: Mutex<TradeOutcomes>,
+    ledger: Mutex<Ledger>,
+    /// Deadline'ı geçip kayıttan düşen (takılı) TX'ler
+    stuck: AtomicU64,
+    /// Takılı nonce'un yeniden verildiği senkronlar
+    replacements: AtomicU64,
+}
+
+impl PaperLane {
+    pub fn new(settings: PaperSettings, policy: GatePolicy) -> Self {
+        Self {
+            settings,
+            nonce_manager: Arc::new(NonceManager::new(0)),
+            outcomes: Mutex::new(TradeOutcomes::default()),
+            ledger: Mutex::new(Ledger {
+                in_flight: BTreeMap::new(),
+                chain_next: 0,
+                directions: DirectionGate::new(policy),
+            }),
+            stuck: AtomicU64::new(0),
+            replacements: AtomicU64::new(0),
+        }
+    }
+
+    /// "Gönder": uçuştaki kayda al, sonucu ve gecikmeyi çek, receipt'i
+    /// arka planda üret. Slot receipt'e (düşen TX'te gecikme sonuna) kadar dolu.
+    pub fn submit(self: &Arc<Self>, submission: PaperSubmission, inflight: Arc<InflightGuard>) -> String {
+        let hash = keccak256(&submission.raw_tx);
+        self.ledger.lock().in_flight.insert(
+            submission.nonce,
+            InflightTx { nonce: submission.nonce, hash, deadline_block: submission.deadline_block },
+        );
+        self.outcomes.lock().submitted += 1;
+
+        let (outcome, latency) = {
+            let mut rng = rand::thread_rng();
+            let outcome = draw_outcome(&self.settings, rng.gen(), rng.gen());
+            (outcome, self.settings.latency.sample(&mut rng))
+        };
+        eprintln!(
+            "     📝 [Paper] TX nonce {} → {:?} in {}ms (deadline #{})",
+            submission.nonce,
+            outcome,
+            latency.as_millis(),
+            submission.deadline_block,
+        );
+        crate::json_logger::log_json(PAPER_TAG, "paper_tx_sent", serde_json::json!({
+            "tx_hash": format!("{:?}", hash),
+            "nonce": submission.nonce,
+            "block": submission.current_block,
+            "deadline_block": submission.deadline_block,
+        }));
+
+        let lane = Arc::clone(self);
+        tokio::spawn(async move {
+            let _inflight = inflight;
+            tokio::time::sleep(latency).await;
+            lane.settle(&submission, hash, outcome);
+        });
+        format!("{:?}", hash)
+    }
+
+    /// Sahte receipt'i muhasebeye işle (gerçek receipt görevinin karşılığı)
+    pub fn settle(&self, submission: &PaperSubmission, hash: B256, outcome: PaperOutcome) {
+        let landed = match outcome {
+            PaperOutcome::Dropped => {
+                // Kayıtta kalır — on_block deadline'da takılı sayar
+                self.outcomes.lock().dropped += 1;
+                crate::json_logger::log_json(PAPER_TAG, "paper_tx_dropped", serde_json::json!({
+                    "tx_hash": format!("{:?}", hash),
+                    "nonce": submission.nonce,
+                }));
+                return;
+            }
+            PaperOutcome::Landed => true,
+            PaperOutcome::Reverted => false,
+        };
+        let gas_spent_wei = submission.gas_used as u128 * submission.gas_price_wei;
+        let included_block = submission.current_block + 1;
+        self.outcomes
+            .lock()
+            .record_receipt(landed, gas_spent_wei, submission.expected_profit_weth);
+        let event = {
+            let mut ledger = self.ledger.lock();
+            ledger.in_flight.remove(&submission.nonce);
+            ledger.chain_next = ledger.chain_next.max(submission.nonce + 1);
+            submission.direction.as_ref().and_then(|direction| {
+                let attempt = crate::direction_gate::Attempt::from_receipt(
+                    landed,
+                    gas_spent_wei,
+                    submission.expected_profit_weth,
+                );
+                ledger.directions.record(direction, attempt, included_block)
+            })
+        };
+        crate::json_logger::log_json(PAPER_TAG, "paper_receipt", serde_json::json!({
+            "tx_hash": format!("{:?}", hash),
+            "nonce": submission.nonce,
+            "block": included_block,
+            "status": landed,
+            "gas_spent_wei": gas_spent_wei.to_string(),
+        }));
+        if let (Some(direction), Some(event)) = (&submission.direction, event) {
+            report_gate_event(&direction.label, &event);
+        }
+    }
+
+    /// İmza / gönderim öncesi iptal — nonce'u çağıran geri alır
+    pub fn record_expired(&self, nonce: u64, current_block: u64, reason: &str) {
+        self.outcomes.lock().expired += 1;
+        crate::json_logger::log_json(PAPER_TAG, "paper_execution_expired", serde_json::json!({
+            "trigger_block": current_block,
+            "nonce": nonce,
+            "reason": reason,
+        }));
+    }
+
+    /// Her blok: deadline'ı geçen TX'leri takılı say ve kayıttan düşür,
+    /// nonce'u gerçek periyodik senkronun kuralıyla (`sync_target`) hizala —
+    /// boşalan nonce yeniden verilir — ve yön defterinin bekleme sürelerini ilerlet
+    pub fn on_block(&self, block: u64) {
+        let (stuck, target, events) = {
+            let mut ledger = self.ledger.lock();
+            let before = ledger.in_flight.len();
+            ledger.in_flight.retain(|_, tx| tx.deadline_block >= block);
+            let stuck = before - ledger.in_flight.len();
+            let live_floor = ledger.in_flight.last_key_value().map(|(&nonce, _)| nonce + 1);
+            let target = nonce_checkpoint::sync_target(ledger.chain_next, live_floor);
+            (stuck, target, ledger.directions.on_block(block))
+        };
+        if stuck > 0 {
+            self.stuck.fetch_add(stuck as u64, Ordering::Relaxed);
+            crate::json_logger::log_json(PAPER_TAG, "paper_tx_stuck", serde_json::json!({
+                "block": block,
+                "count": stuck,
+            }));
+        }
+        // Yalnızca TX düştüğünde hizala — tahsis edilip henüz gönderilmemiş
+        // nonce'lar (imza / bekleme aşaması) kayıtta görünmez
+        let current = self.nonce_manager.current();
+        if stuck > 0 && target < current {
+            self.replacements.fetch_add(1, Ordering::Relaxed);
+            eprintln!(
+                "     📝 [Paper] {} stuck TX past deadline — nonce {} → {} (replacement)",
+                stuck, current, target,
+            );
+            self.nonce_manager.force_set(target);
+        }
+        for (label, event) in &events {
+            report_gate_event(label, event);
+        }
+    }
+
+    /// Kağıt yön defterinin kararı — gerçek yön kapısı filtresiyle aynı kural
+    pub fn decision(&self, key: &DirectionKey, expected_profit_weth: f64, min_profit_weth: f64) -> FilterDecision {
+        self.ledger.lock().directions.decision(key, expected_profit_weth, min_profit_weth)
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.ledger.lock().in_flight.len()
+    }
+
+    pub fn stats_line(&self) -> String {
+        let o = *self.outcomes.lock();
+        let restricted = self
+            .ledger
+            .lock()
+            .directions
+            .summaries()
+            .iter()
+            .filter(|s| s.active_restriction.is_some())
+            .count();
+        format!(
+            "{} sent | {} landed / {} reverted / {} dropped | {} expired | {} stuck ({} replaced) | {} in flight | {} restricted dir | PnL {:+.6} WETH",
+            o.submitted,
+            o.landed,
+            o.reverted,
+            o.dropped,
+            o.expired,
+            self.stuck.load(Ordering::Relaxed),
+            self.replacements.load(Ordering::Relaxed),
+            self.in_flight(),
+            restricted,
+            o.realized_pnl_weth,
+        )
+    }
+}
+
+fn report_gate_event(label: &str, event: &GateEvent) {
+    let (name, detail) = match event {
+        GateEvent::Restricted(r) => (
+            "paper_direction_restricted",
+            format!("restricted until #{} (success {:.0}%)", r.until_block, r.success_rate * 100.0),
+        ),
+        GateEvent::Lifted(reason) => ("paper_direction_lifted", format!("lifted ({})", reason.label())),
+    };
+    eprintln!("     📝 [Paper] direction {} {}", label, detail);
+    crate::json_logger::log_json(PAPER_TAG, name, serde_json::json!({
+        "direction": label,
+        "detail": detail,
+    }));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Şerit
+// ─────────────────────────────────────────────────────────────────────────────
+
+static LANE: Mutex<Option<Arc<PaperLane>>> = parking_lot::const_mutex(None);
+
+/// PAPER_EXECUTION açıksa şeridi kur (main, run_bot öncesi — reconnect'lerde korunur)
+pub fn init(config: &BotConfig) {
+    if !config.paper_execution {
+        return;
+    }
+    let settings = PaperSettings::from_config(config);
+    println!(
+        "  {} Paper Execution: ON — failure {:.0}%, drop {:.0}%, receipt {}ms (no transaction leaves the process)",
+        "📝".cyan(),
+        settings.failure_rate * 100.0,
+        settings.drop_rate * 100.0,
+        settings.latency,
+    );
+    *LANE.lock() = Some(Arc::new(PaperLane::new(settings, GatePolicy::from_config(config))));
+}
+
+/// Executor'a bağlanacak şerit (kapalıysa None)
+pub fn lane() -> Option<Arc<PaperLane>> {
+    LANE.lock().clone()
+}
+
+/// Ana döngü, her blok
+pub fn on_block(block: u64) {
+    if let Some(lane) = lane() {
+        lane.on_block(block);
+    }
+}
+
+pub fn stats_line() -> Option<String> {
+    lane().map(|lane| lane.stats_line())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction_gate::DirectionGateMode;
+    use crate::executor::InflightLimiter;
+    use alloy::primitives::Address;
+
+    fn settings(failure_rate: f64, drop_rate: f64) -> PaperSettings {
+        PaperSettings { failure_rate, drop_rate, latency: LatencyRange { min_ms: 0, max_ms: 0 } }
+    }
+
+    fn policy() -> GatePolicy {
+        GatePolicy {
+            window: 3,
+            min_attempts: 3,
+            min_success_rate: 0.5,
+            mode: DirectionGateMode::Suspend,
+            profit_multiplier: 2.0,
+            cooldown_blocks: 50,
+            edge_widen: 1.5,
+        }
+    }
+
+    fn direction() -> Direction {
+        Direction {
+            key: crate::direction_gate::DirectionKey {
+                buy_pool: Address::with_last_byte(1),
+                sell_pool: Address::with_last_byte(2),
+            },
+            label: "A → B".into(),
+        }
+    }
+
+    fn submission(lane: &PaperLane, block: u64) -> PaperSubmission {
+        let nonce = lane.nonce_manager.get_and_increment();
+        PaperSubmission {
+            raw_tx: paper_raw_tx(nonce, &[0xAB; 4]),
+            nonce,
+            deadline_block: block + 3,
+            current_block: block,
+            expected_profit_weth: 0.002,
+            gas_used: 200_000,
+            gas_price_wei: 1_000_000_000,
+            direction: Some(direction()),
+        }
+    }
+
+    #[test]
+    fn test_outcome_draw_and_latency_parse() {
+        let s = settings(0.25, 0.10);
+        assert_eq!(draw_outcome(&s, 0.05, 0.9), PaperOutcome::Dropped);
+        assert_eq!(draw_outcome(&s, 0.50, 0.2), PaperOutcome::Reverted);
+        assert_eq!(draw_outcome(&s, 0.50, 0.3), PaperOutcome::Landed);
+        assert_eq!(draw_outcome(&settings(0.0, 0.0), 0.0, 0.0), PaperOutcome::Landed);
+
+        assert_eq!("250-4000".parse::<LatencyRange>(), Ok(LatencyRange { min_ms: 250, max_ms: 4_000 }));
+        assert_eq!("800".parse::<LatencyRange>(), Ok(LatencyRange { min_ms: 0, max_ms: 800 }));
+        assert!("900-100".parse::<LatencyRange>().is_err());
+        assert!("0-20000".parse::<LatencyRange>().is_err());
+        assert_eq!(LatencyRange::DEFAULT.to_string().parse::<LatencyRange>(), Ok(LatencyRange::DEFAULT));
+    }
+
+    /// Receipt muhasebesi + kağıt yön defteri: üç revert yönü kısıtlar
+    #[test]
+    fn test_settle_feeds_outcomes_and_paper_direction_breaker() {
+        let lane = PaperLane::new(settings(1.0, 0.0), policy());
+        let landed = submission(&lane, 100);
+        lane.settle(&landed, B256::ZERO, PaperOutcome::Landed);
+        for _ in 0..3 {
+            let reverted = submission(&lane, 101);
+            lane.settle(&reverted, B256::ZERO, PaperOutcome::Reverted);
+        }
+        let o = *lane.outcomes.lock();
+        assert_eq!((o.landed, o.reverted), (1, 3));
+        let gas_weth = 200_000.0 * 1e9 / 1e18;
+        assert!((o.realized_pnl_weth - (0.002 - 4.0 * gas_weth)).abs() < 1e-12);
+        assert_eq!(lane.in_flight(), 0);
+        assert_eq!(lane.nonce_manager.current(), 4);
+
+        let summaries = lane.ledger.lock().directions.summaries();
+        assert_eq!(summaries[0].attempts, 4);
+        assert!(summaries[0].active_restriction.is_some(), "3/3 revert must restrict the paper direction");
+        assert!(lane.stats_line().contains("1 restricted dir"), "{}", lane.stats_line());
+        assert!(matches!(lane.decision(&direction().key, 1.0, 0.0001), FilterDecision::Degrade(_)));
+    }
+
+    /// Receipt gelmeyen TX deadline'a kadar uçuşta kalır, sonra takılı sayılır
+    /// ve senkron boşalan nonce'u yeniden verir (replacement)
+    #[test]
+    fn test_dropped_tx_becomes_stuck_and_nonce_is_reissued() {
+        let lane = PaperLane::new(settings(0.0, 1.0), policy());
+        let first = submission(&lane, 100);
+        lane.settle(&first, B256::ZERO, PaperOutcome::Landed);
+        let dropped = submission(&lane, 100);
+        lane.settle(&dropped, B256::ZERO, PaperOutcome::Dropped);
+        lane.ledger.lock().in_flight.insert(
+            dropped.nonce,
+            InflightTx { nonce: dropped.nonce, hash: B256::ZERO, deadline_block: dropped.deadline_block },
+        );
+        assert_eq!(lane.nonce_manager.current(), 2);
+
+        // Deadline bloğu dahil canlı: nonce canlı TX'in altına inmez
+        lane.on_block(dropped.deadline_block);
+        assert_eq!((lane.in_flight(), lane.nonce_manager.current()), (1, 2));
+
+        lane.on_block(dropped.deadline_block + 1);
+        assert_eq!(lane.in_flight(), 0);
+        assert_eq!(lane.stuck.load(Ordering::Relaxed), 1);
+        assert_eq!(lane.replacements.load(Ordering::Relaxed), 1);
+        assert_eq!(lane.nonce_manager.current(), dropped.nonce, "stuck nonce is reissued");
+        assert_eq!(lane.outcomes.lock().dropped, 1);
+    }
+
+    /// Tam yol: submit kayda alır, receipt arka planda gelir ve slotu bırakır
+    #[tokio::test]
+    async fn test_submit_resolves_in_background_and_releases_slot() {
+        let lane = Arc::new(PaperLane::new(settings(0.0, 0.0), policy()));
+        let limiter = InflightLimiter::new(1);
+        let guard = Arc::new(limiter.try_acquire().unwrap());
+        let sub = submission(&lane, 100);
+        let hash = lane.submit(sub.clone(), guard);
+        assert_eq!(hash, format!("{:?}", keccak256(&sub.raw_tx)));
+        assert_eq!(lane.outcomes.lock().submitted, 1);
+
+        for _ in 0..200 {
+            if lane.outcomes.lock().landed == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(lane.outcomes.lock().landed, 1);
+        assert_eq!(lane.in_flight(), 0);
+        assert_eq!(limiter.active(), 0);
+    }
+}
@@ -10,10 +10,13 @@
 //  Aşama 2: Off-Chain Filtre
 //    ✓ HashMap dedup + veri zenginleştirme (merge)
 //    ✓ V3/CL label + isim analizi (whitelist/blacklist)
+//    ✓ Sabit çarpım (V2 / Aerodrome vAMM) adayları ayrı sınıf, yalnızca
+//      DISCOVER_CONSTANT_PRODUCT=true iken (gölge — kontratta V2 yolu yok);
+//      stable (sAMM) ve V1 eğrileri reddedilir
 //    ✓ Hacim ($10K+), likidite ($50K+), fee (≤%0.05) filtreleri
 //
 //  Aşama 3: On-Chain RPC Doğrulama (Nihai Yargıç)
-//    ✓ slot0() eth_call ile gerçek V3 kanıtı
+//    ✓ slot0() eth_call ile gerçek V3 kanıtı (V2 adayında getReserves())
 //    ✓ Paralel sorgulama (futures::join_all)
 //    ✓ 2s timeout per call
 //    ✓ execution reverted → havuz reddedilir
@@ -36,7 +39,7 @@ use std::collections::HashMap;
 use alloy::primitives::Address;
 use futures_util::future::join_all;
 
-use crate::types::{DexType, PoolConfig};
+use crate::types::{DexType, FeeTier, PoolConfig};
 
 // ─── Sabitler ───
 const BASE_WETH_LOWER: &str = "0x4200000000000000000000000000000000000006";
@@ -46,6 +49,14 @@ const CORE_POOLS_PATH: &str = "core_pools.json";
 /// slot0() fonksiyon seçicisi (4 byte): keccak256("slot0()")[0..4]
 const SLOT0_SELECTOR: [u8; 4] = [0x38, 0x50, 0xc7, 0xbd];
 
+/// getReserves() seçicisi: keccak256("getReserves()")[0..4] — UniswapV2Pair
+/// ve Aerodrome Pool (vAMM) aynı seçiciyi kullanır
+const GET_RESERVES_SELECTOR: [u8; 4] = [0x09, 0x02, 0xf1, 0xac];
+
+/// Sabit çarpım havuzu fee_tier döndürmezse varsayılan komisyon
+/// (UniswapV2 ve Aerodrome volatile varsayılanı %0.30)
+pub const CONSTANT_PRODUCT_DEFAULT_FEE_BPS: u32 = 30;
+
 /// On-Chain doğrulama için eth_call timeout (milisaniye)
 /// v29.0: 2000ms → 500ms. Bayat veri beklemek yerine hızlı başarısızlık.
 const ONCHAIN_CALL_TIMEOUT_MS: u64 = 500;
@@ -157,6 +168,36 @@ pub struct DiscoveredPool {
     pub source: String,
 }
 
+impl DiscoveredPool {
+    /// x·y=k havuzu (UniswapV2 / Aerodrome vAMM) — DexType::UniswapV2 olarak yüklenir
+    pub fn is_constant_product(&self) -> bool {
+        is_constant_product_pool(&self.labels, &self.gecko_name)
+    }
+
+    /// matched_pools.json dex_id'si — sabit çarpım havuzu `infer_dex_type`'ta
+    /// DexType::UniswapV2'ye düşen ayrı bir kimlik alır (aynı DEX'in CL
+    /// havuzuyla karışmaz)
+    fn matched_dex_id(&self) -> String {
+        let dex = self.dex.to_lowercase();
+        if !self.is_constant_product() {
+            self.dex.clone()
+        } else if dex.contains("aerodrome") {
+            "aerodrome-vamm".to_string()
+        } else if dex.ends_with("-v2") || dex.ends_with("_v2") {
+            dex
+        } else {
+            format!("{}-v2", dex)
+        }
+    }
+
+    fn fee_bps(&self) -> u32 {
+        match self.fee_tier {
+            None if self.is_constant_product() => CONSTANT_PRODUCT_DEFAULT_FEE_BPS,
+            fee_tier => fee_tier_to_bps(fee_tier),
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // matched_pools.json Yapıları
 // ─────────────────────────────────────────────────────────────────────────────
@@ -217,18 +258,46 @@ fn fee_tier_to_bps(fee_tier: Option<f64>) -> u32 {
     fee_tier.map(|f| (f * 100.0).round() as u32).unwrap_or(0)
 }
 
+/// Sabit çarpım etiketleri — `is_constant_product_pool` kabul eder
+const CONSTANT_PRODUCT_TAGS: &[&str] = &["v2", "vamm", "volatile"];
+/// Modellenmeyen eğriler: V1, stable (sAMM, x³y+y³x) — her iki sınıfta da REDDET
+const UNSUPPORTED_CURVE_TAGS: &[&str] = &["v1", "stable", "samm"];
+const V3_WHITELIST: &[&str] = &["v3", "v4", "cl", "clamm", "clpool", "slipstream"];
+
+/// Sabit çarpım (x·y=k) adayı mı — Off-Chain Aşama.
+///
+///   1. CL etiketi ya da stable / V1 etiketi → hayır
+///   2. v2 / vamm / volatile etiketi → evet
+///   3. GeckoTerminal havuz adı "vAMM-" / "Volatile" içerir → evet
+pub fn is_constant_product_pool(labels: &Option<Vec<String>>, gecko_name: &Option<String>) -> bool {
+    if let Some(tags) = labels {
+        let tags: Vec<String> = tags.iter().map(|t| t.to_lowercase()).collect();
+        let any_of = |set: &[&str]| tags.iter().any(|t| set.contains(&t.as_str()));
+        if any_of(V3_WHITELIST) || any_of(UNSUPPORTED_CURVE_TAGS) {
+            return false;
+        }
+        if any_of(CONSTANT_PRODUCT_TAGS) {
+            return true;
+        }
+    }
+    gecko_name.as_ref().is_some_and(|name| {
+        let name = name.to_lowercase();
+        !name.contains("samm") && (name.contains("vamm") || name.contains("volatile"))
+    })
+}
+
 /// V3/Konsantre Likidite (CL) doğrulaması — Off-Chain Aşama.
 ///
 /// Üç katmanlı karar ağacı:
 ///   1. Labels beyaz liste: v3, v4, cl, clamm, clpool, slipstream → V3 kesin
-///   2. Labels kara liste: v1, v2, stable, vamm → V2 kesin, REDDET
+///   2. Labels kara liste: v1, v2, stable, vamm → CL değil (v2 / vamm
+///      `is_constant_product_pool` ile ayrıca kabul edilir)
 ///   3. Fee-tier fallback: Aerodrome/PancakeSwap labels döndürmez,
 ///      fee_tier varlığı V3 (CL) göstergesidir.
 ///   4. İsim analizi: GeckoTerminal havuz adında "v3", "cl", "slipstream" geçer mi?
 ///
-/// Hiçbir koşul sağlanmazsa V2 varsayılır ve havuz reddedilir.
+/// Hiçbir koşul sağlanmazsa CL değil sayılır.
 fn is_v3_pool(labels: &Option<Vec<String>>, dex_id: &str, fee_tier: &Option<f64>, gecko_name: &Option<String>) -> bool {
-    const V3_WHITELIST: &[&str] = &["v3", "v4", "cl", "clamm", "clpool", "slipstream"];
     const V2_BLACKLIST: &[&str] = &["v1", "v2", "stable", "vamm"];
 
     // 1. Label tabanlı karar
@@ -265,6 +334,29 @@ fn is_v3_pool(labels: &Option<Vec<String>>, dex_id: &str, fee_tier: &Option<f64>
     false // Bilinmeyen → V2 varsay → REDDET
 }
 
+/// Yükleme anı komisyon doğrulaması: 1..=10000 bps dışındaki havuz atlanır
+/// (`None`), aralık içi ama standart dışı kademe uyarıyla kabul edilir.
+/// Pips/bps birim karışıklığı (×100) burada yakalanır.
+pub fn validated_fee(pool_label: &str, fee_bps: u32) -> Option<FeeTier> {
+    match FeeTier::parse_bps(fee_bps) {
+        Ok(fee) => {
+            if !fee.is_known_tier() {
+                eprintln!(
+                    "  ⚠️ [Fee] {} has unusual fee tier {}bps ({:.2}%) — check units",
+                    pool_label,
+                    fee.bps(),
+                    fee.fraction() * 100.0
+                );
+            }
+            Some(fee)
+        }
+        Err(issue) => {
+            eprintln!("  ⚠️ [Fee] {} rejected: {} — skipping pool", pool_label, issue);
+            None
+        }
+    }
+}
+
 fn infer_tick_spacing(dex_id: &str, fee_bps: u32) -> i32 {
     let dex_lower = dex_id.to_lowercase();
     if dex_lower.contains("pancakeswap") || dex_lower.contains("pancake") {
@@ -297,10 +389,15 @@ fn infer_dex_type(dex_id: &str) -> Option<DexType> {
         "pancakeswap" | "pancakeswap-v3" | "pancakeswap_v3" => Some(DexType::PancakeSwapV3),
         "aerodrome" | "aerodrome-slipstream" | "aerodrome_slipstream" | "aerodrome-cl" => Some(DexType::Aerodrome),
         "uniswap" | "uniswap-v3" | "uniswap_v3" | "uniswapv3" => Some(DexType::UniswapV3),
+        "uniswap-v2" | "uniswap_v2" | "uniswapv2" => Some(DexType::UniswapV2),
+        "aerodrome-vamm" | "aerodrome_vamm" => Some(DexType::UniswapV2),
         "sushiswap" | "sushiswap-v3" | "sushiswap_v3" => Some(DexType::UniswapV3),
         _ => {
             // Fallback: substring eşleşme (yeni DEX ID'ler için)
-            if lower.contains("pancake") {
+            // Sabit çarpım kimliği önce — "<dex>-v2" / "*vamm" (matched_dex_id)
+            if lower.contains("vamm") || lower.ends_with("-v2") || lower.ends_with("_v2") {
+                Some(DexType::UniswapV2)
+            } else if lower.contains("pancake") {
                 Some(DexType::PancakeSwapV3)
             } else if lower.contains("aerodrome") || lower.contains("slipstream") {
                 Some(DexType::Aerodrome)
@@ -552,17 +649,21 @@ fn merge_pool_sources(dex_pairs: Vec<DexPair>, gecko_pools: Vec<GeckoPoolData>)
 // AŞAMA 3: Off-Chain Filtre (Zırh)
 // ─────────────────────────────────────────────────────────────────────────────
 
-fn off_chain_filter(pools: Vec<DiscoveredPool>) -> Vec<DiscoveredPool> {
+/// `accept_constant_product` (DISCOVER_CONSTANT_PRODUCT): V2 / vAMM adayları
+/// da geçer. Kontratta V2 swap yolu yok — bu havuzlar yalnızca gölgede
+/// fiyatlanır, varsayılan kapalıdır.
+fn off_chain_filter(pools: Vec<DiscoveredPool>, accept_constant_product: bool) -> Vec<DiscoveredPool> {
     let before = pools.len();
 
     let mut filtered: Vec<DiscoveredPool> = pools
         .into_iter()
-        // V3 Zırhı — V2 Zombi Bariyeri
+        // Eğri Zırhı — CL (istenirse sabit çarpım V2 / vAMM); stable / V1 reddedilir
         .filter(|p| {
-            let pass = is_v3_pool(&p.labels, &p.dex, &p.fee_tier, &p.gecko_name);
+            let pass = (accept_constant_product && p.is_constant_product())
+                || is_v3_pool(&p.labels, &p.dex, &p.fee_tier, &p.gecko_name);
             if !pass {
                 eprintln!(
-                    "  🛡️ V2 pool rejected: {} ({}) [labels: {:?}]", p.address, p.dex, p.labels
+                    "  🛡️ Unsupported curve rejected: {} ({}) [labels: {:?}]", p.address, p.dex, p.labels
                 );
             }
             pass
@@ -593,7 +694,7 @@ fn off_chain_filter(pools: Vec<DiscoveredPool>) -> Vec<DiscoveredPool> {
     filtered.truncate(100);
 
     eprintln!(
-        "  {} [Off-Chain Filter] {} → {} candidate pools (V3 + liquidity + volume + fee)",
+        "  {} [Off-Chain Filter] {} → {} candidate pools (CL/V2 + liquidity + volume + fee)",
         "🛡️".yellow(), before, filtered.len()
     );
 
@@ -606,7 +707,8 @@ fn off_chain_filter(pools: Vec<DiscoveredPool>) -> Vec<DiscoveredPool> {
 
 /// slot0() eth_call ile havuzun gerçekten V3/CL olduğunu doğrula.
 ///
-/// Her aday havuza `slot0()` selector'ünü gönderir:
+/// Her aday havuza `slot0()` selector'ünü gönderir (sabit çarpım adayına
+/// `getReserves()` — her iki yanıt da ≥ 64 byte):
 /// - Geçerli yanıt (≥ 64 byte) → %100 onaylanmış V3
 /// - execution reverted / 0x / timeout → sahte V3, REDDET
 ///
@@ -632,15 +734,18 @@ pub async fn on_chain_validate(candidates: Vec<DiscoveredPool>) -> Vec<Discovere
         "⛓️".cyan(), candidates.len()
     );
 
-    // slot0() calldata: sadece 4-byte selector
-    let calldata: alloy::primitives::Bytes = alloy::primitives::Bytes::from(SLOT0_SELECTOR.to_vec());
-
     // Her aday için asenkron doğrulama future'ı oluştur
     let futures: Vec<_> = candidates.iter().enumerate().map(|(i, pool)| {
         let rpc_url = rpc_url.clone();
         let pool_address_str = pool.address.clone();
         let pool_dex = pool.dex.clone();
-        let calldata = calldata.clone();
+        // slot0() / getReserves() calldata: sadece 4-byte selector
+        let (selector, call_name) = if pool.is_constant_product() {
+            (GET_RESERVES_SELECTOR, "getReserves")
+        } else {
+            (SLOT0_SELECTOR, "slot0")
+        };
+        let calldata = alloy::primitives::Bytes::from(selector.to_vec());
 
         async move {
             // Her future kendi HTTP client'ını oluşturur (bağımsız timeout)
@@ -696,7 +801,7 @@ pub async fn on_chain_validate(candidates: Vec<DiscoveredPool>) -> Vec<Discovere
                                 if hex_data.is_empty() || hex_data.len() < 128 {
                                     // 128 hex karakter = 64 byte minimum (sqrtPriceX96 + tick)
                                     eprintln!(
-                                        "  ❌ [On-Chain] REJECTED: {} ({}) — slot0 response too short ({}B)", pool_address_str, pool_dex, hex_data.len() / 2
+                                        "  ❌ [On-Chain] REJECTED: {} ({}) — {} response too short ({}B)", pool_address_str, pool_dex, call_name, hex_data.len() / 2
                                     );
                                     return (i, false);
                                 }
@@ -766,8 +871,7 @@ fn match_arbitrage_pairs(pools: &[DiscoveredPool]) -> Vec<MatchedPair> {
         // Aynı slot'ta en yüksek hacimli (volume) havuzu tercih et
         let mut dex_best: HashMap<String, &DiscoveredPool> = HashMap::new();
         for pool in group {
-            let fee_bps = fee_tier_to_bps(pool.fee_tier);
-            let dex_key = format!("{}_{}", pool.dex.to_lowercase(), fee_bps);
+            let dex_key = format!("{}_{}", pool.matched_dex_id().to_lowercase(), pool.fee_bps());
             match dex_best.get(&dex_key) {
                 Some(existing) if existing.volume_24h >= pool.volume_24h => {}
                 _ => { dex_best.insert(dex_key, pool); }
@@ -823,10 +927,10 @@ fn match_arbitrage_pairs(pools: &[DiscoveredPool]) -> Vec<MatchedPair> {
             },
             weth_is_token0: is_t0_weth,
             pools: selected.iter().map(|p| {
-                let fee_bps = fee_tier_to_bps(p.fee_tier);
+                let fee_bps = p.fee_bps();
                 MatchedPoolEntry {
                     address: p.address.clone(),
-                    dex_id: p.dex.clone(),
+                    dex_id: p.matched_dex_id(),
                     fee_bps,
                     tick_spacing: infer_tick_spacing(&p.dex, fee_bps),
                     liquidity_usd: p.liquidity_usd,
@@ -875,7 +979,7 @@ pub async fn cli_discover_pools() -> Result<()> {
         Ok(p) => p,
         Err(e) => {
             eprintln!("  ⚠️  DexScreener API error: {} — checking existing cache...", e);
-            if std::path::Path::new(MATCHED_POOLS_PATH).exists() {
+            if crate::secure_fs::read_path(MATCHED_POOLS_PATH).exists() {
                 eprintln!("  📦 Using existing matched_pools.json cache (DexScreener unreachable)");
                 return Ok(());
             }
@@ -892,7 +996,9 @@ pub async fn cli_discover_pools() -> Result<()> {
     let merged = merge_pool_sources(dex_pairs, gecko_pools);
 
     // ── AŞAMA 3: Off-Chain Filtre ──
-    let candidates = off_chain_filter(merged);
+    let accept_constant_product =
+        crate::env_loader::EnvLoader::new().bool_or("DISCOVER_CONSTANT_PRODUCT", false);
+    let candidates = off_chain_filter(merged, accept_constant_product);
 
     if candidates.is_empty() {
         eprintln!("  {} No candidates passed off-chain filter.", "⚠️".yellow());
@@ -955,7 +1061,7 @@ pub async fn cli_discover_pools() -> Result<()> {
     let config = MatchedPoolsConfig {
         version: "26.0".into(),
         chain_id: 8453,
-        updated_at: chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        updated_at: crate::clock::local_now().format("%Y-%m-%dT%H:%M:%S").to_string(),
         matched_pairs,
     };
 
@@ -978,14 +1084,14 @@ pub async fn cli_discover_pools() -> Result<()> {
 fn write_matched_pools_json(config: &MatchedPoolsConfig) -> Result<()> {
     let json = serde_json::to_string_pretty(config)
         .map_err(|e| eyre::eyre!("JSON serialization error: {}", e))?;
-    std::fs::write(MATCHED_POOLS_PATH, json)
+    crate::secure_fs::write(&crate::secure_fs::data_path(MATCHED_POOLS_PATH), json)
         .map_err(|e| eyre::eyre!("matched_pools.json write error: {}", e))?;
     Ok(())
 }
 
 /// matched_pools.json dosyasını yükle
 pub fn load_matched_pools() -> Result<MatchedPoolsConfig> {
-    let content = std::fs::read_to_string(MATCHED_POOLS_PATH)
+    let content = std::fs::read_to_string(crate::secure_fs::read_path(MATCHED_POOLS_PATH))
         .map_err(|e| eyre::eyre!("matched_pools.json read error: {} — Run `--discover-pools` first", e))?;
     let config: MatchedPoolsConfig = serde_json::from_str(&content)
         .map_err(|e| eyre::eyre!("matched_pools.json parse error: {}", e))?;
@@ -996,10 +1102,11 @@ pub fn load_matched_pools() -> Result<MatchedPoolsConfig> {
 /// core_pools.json varsa matched_pools.json yerine bu kullanılır.
 /// DexScreener bağımlılığını ortadan kaldırır.
 pub fn load_core_pools() -> Option<MatchedPoolsConfig> {
-    if !std::path::Path::new(CORE_POOLS_PATH).exists() {
+    let path = crate::secure_fs::read_path(CORE_POOLS_PATH);
+    if !path.exists() {
         return None;
     }
-    match std::fs::read_to_string(CORE_POOLS_PATH) {
+    match std::fs::read_to_string(&path) {
         Ok(content) => {
             match serde_json::from_str::<MatchedPoolsConfig>(&content) {
                 Ok(config) => {
@@ -1071,12 +1178,14 @@ pub fn build_runtime(config: &MatchedPoolsConfig, max_pools: usize) -> Result<(V
                     Some(dt) => dt,
                     None => continue, // Bilinmeyen DEX — bu havuzu atla
                 };
+                let Some(fee) = validated_fee(&pool_entry.address, pool_entry.fee_bps) else {
+                    continue;
+                };
 
                 let pool_config = PoolConfig {
                     address,
                     name: format!("{}-{}", pool_entry.dex_id, pair.pair_name),
-                    fee_bps: pool_entry.fee_bps,
-                    fee_fraction: pool_entry.fee_bps as f64 / 10_000.0,
+                    fee,
                     token0_decimals: if pair.weth_is_token0 { pair.base_token.decimals } else { pair.quote_token.decimals },
                     token1_decimals: if pair.weth_is_token0 { pair.quote_token.decimals } else { pair.base_token.decimals },
                     dex: dex_type,
@@ -1123,7 +1232,10 @@ pub fn build_runtime(config: &MatchedPoolsConfig, max_pools: usize) -> Result<(V
 ///
 /// Pool validation/GC sonrası havuz indeksleri değiştiğinde çağrılır.
 /// Aynı quote_token_address + base_token_address'e sahip havuzları gruplar
-/// ve tüm 2-havuz kombinasyonlarını üretir.
+/// ve tüm 2-havuz kombinasyonlarını üretir. Çıktı (pool_a_idx, pool_b_idx)
+/// sırasındadır: HashMap gezinme sırası süreçten sürece değişir, combo
+/// indeksi ise blacklist / hata sayacı anahtarıdır — aynı havuz listesi
+/// her yeniden kurulumda aynı sırayı vermeli.
 pub fn rebuild_pair_combos(pools: &[PoolConfig]) -> Vec<PairCombo> {
     let mut pair_groups: HashMap<(Address, Address), Vec<usize>> = HashMap::new();
 
@@ -1157,5 +1269,6 @@ pub fn rebuild_pair_combos(pools: &[PoolConfig]) -> Vec<PairCombo> {
         }
     }
 
+    combos.sort_by_key(|c| (c.pool_a_idx, c.pool_b_idx));
     combos
 }
@@ -0,0 +1,215 @@
+// ============================================================================
+//  POOL SNAPSHOT v1.0 — Fırsat Başına Tutarlı Havuz Görüntüsü
+//
+//  Canlı SharedPoolState'lere event akışı, güvenlik senkronu, optimistik
+//  güncelleme ve bitmap genişletme her an yazar. Bir fırsatın aşamaları
+//  (kontrol → hassasiyet → iki adım → simülasyon → calldata → gölge log)
+//  her biri ayrı `load()` yaptığında aynı fırsat içinde farklı sürümler
+//  görülebilir (yırtık okuma): NR bir state'le boyutlanır, calldata'nın
+//  deadline'ı ve REVM başka bir state'le kurulur.
+//
+//  ✓ capture: havuz başına TEK `load_full` — PoolState (bitmap dahil) Arc
+//    ile paylaşılır, kopyalanmaz
+//  ✓ Donmuş kopya sahipsiz bir ArcSwap'tir: hiçbir yazıcı ona erişemez,
+//    tüm aşamalar mevcut `&[SharedPoolState]` imzalarıyla aynı sürümü okur
+//  ✓ Tazelik korunur: staleness `last_update.elapsed()` — yaş, yürütme
+//    anında hâlâ ölçülür (freshness_gate)
+//  ✓ Pending swap tahmini (PREDICTED_STATE_TRUST_BLOCKS) yakalamada işlenir:
+//    tahminden doğan fırsat, doğrulama / exact kâr / REVM'de de aynı
+//    tahmin edilmiş state'le koşar — onaylı state'e geri düşmez
+//  ✓ Debug build: canlı state okuma sayacı (fırsat başına ≤ havuz sayısı)
+// ============================================================================
+
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+
+use crate::types::SharedPoolState;
+
+/// Tek havuzun donmuş kopyası — yazıcısı olmayan ArcSwap
+fn freeze(live: &SharedPoolState) -> SharedPoolState {
+    #[cfg(debug_assertions)]
+    counters::LIVE_LOADS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    Arc::new(ArcSwap::new(live.load_full()))
+}
+
+/// İki havuzlu combo fırsatı için donmuş çift
+pub fn capture_pair(a: &SharedPoolState, b: &SharedPoolState) -> [SharedPoolState; 2] {
+    #[cfg(debug_assertions)]
+    counters::CAPTURES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    [freeze(a), freeze(b)]
+}
+
+/// Ana döngünün çift taraması: donmuş kopyalar kullanılabilir pending swap
+/// tahminini (fiyat / tick / likidite) taşır. Zincir başı iki havuzun en
+/// yeni bloğu — `check_arbitrage_opportunity` ile aynı güven penceresi.
+pub fn capture_pair_predicted(
+    a: &SharedPoolState,
+    b: &SharedPoolState,
+    trust_blocks: u64,
+) -> [SharedPoolState; 2] {
+    let [a, b] = capture_pair(a, b).map(|frozen| frozen.load_full());
+    let head_block = a.last_block.max(b.last_block);
+    [a, b].map(|state| Arc::new(ArcSwap::new(state.with_prediction(head_block, trust_blocks))))
+}
+
+/// Multi-hop taraması için tüm havuzlar (indeksler canlı dizideki ile aynı)
+pub fn capture_all(states: &[SharedPoolState]) -> Vec<SharedPoolState> {
+    #[cfg(debug_assertions)]
+    counters::CAPTURES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    states.iter().map(freeze).collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Debug Sayacı — canlı state okuma trafiği
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(debug_assertions)]
+mod counters {
+    use std::sync::atomic::AtomicU64;
+
+    pub static CAPTURES: AtomicU64 = AtomicU64::new(0);
+    pub static LIVE_LOADS: AtomicU64 = AtomicU64::new(0);
+}
+
+/// "N snapshots, M live loads (K/snapshot)" — yalnızca debug build
+#[cfg(debug_assertions)]
+pub fn stats_line() -> String {
+    use std::sync::atomic::Ordering;
+    let captures = counters::CAPTURES.load(Ordering::Relaxed);
+    let loads = counters::LIVE_LOADS.load(Ordering::Relaxed);
+    format!(
+        "{} snapshots, {} live loads ({:.1}/snapshot)",
+        captures,
+        loads,
+        loads as f64 / captures.max(1) as f64,
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{OptimisticTrigger, PoolState, PredictedState, TickBitmapData};
+    use alloy::primitives::{Address, TxHash, U256};
+    use std::time::Duration;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Sürüm `k`'nin tüm alanları aynı sayıdan türetilir — karışık sürüm hemen görünür
+    fn versioned(k: u64) -> PoolState {
+        PoolState {
+            tick: k as i32,
+            liquidity: k as u128,
+            sqrt_price_x96: U256::from(k),
+            last_block: k,
+            is_initialized: true,
+            tick_bitmap: Some(Box::new(TickBitmapData { snapshot_block: k, ..TickBitmapData::empty() })),
+            ..Default::default()
+        }
+    }
+
+    fn assert_consistent(state: &PoolState) -> u64 {
+        let k = state.last_block;
+        assert_eq!(state.tick as u64, k);
+        assert_eq!(state.liquidity, k as u128);
+        assert_eq!(state.sqrt_price_x96, U256::from(k));
+        assert_eq!(state.tick_bitmap.as_deref().map(|bm| bm.snapshot_block), Some(k));
+        k
+    }
+
+    #[test]
+    fn test_snapshot_ignores_later_writes() {
+        let live: Vec<SharedPoolState> =
+            (1..=3).map(|k| Arc::new(ArcSwap::from_pointee(versioned(k)))).collect();
+        let pair = capture_pair(&live[0], &live[1]);
+        let all = capture_all(&live);
+
+        live[0].store(Arc::new(versioned(50)));
+        live[1].rcu(|old| PoolState { last_block: old.last_block + 1, ..(**old).clone() });
+
+        assert_eq!(pair[0].load().last_block, 1);
+        assert_eq!(pair[1].load().last_block, 2);
+        assert_eq!(all.iter().map(|s| s.load().last_block).collect::<Vec<_>>(), vec![1, 2, 3]);
+        // Bitmap paylaşılır, kopyalanmaz: donmuş kopya yakalanan Arc'ın kendisi
+        let fresh = capture_pair(&live[2], &live[2]);
+        assert!(Arc::ptr_eq(&fresh[0].load_full(), &live[2].load_full()));
+    }
+
+    /// Yazıcı iş parçacığı sürekli yeni sürüm yayınlarken her yakalama
+    /// tek bir sürümün tamamını görür ve yakalandıktan sonra değişmez.
+    #[test]
+    fn test_snapshot_consistent_under_concurrent_writer() {
+        let live: [SharedPoolState; 2] = [
+            Arc::new(ArcSwap::from_pointee(versioned(0))),
+            Arc::new(ArcSwap::from_pointee(versioned(0))),
+        ];
+        let stop = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let live = live.clone();
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || {
+                let mut k = 0u64;
+                loop {
+                    k += 1;
+                    live[0].store(Arc::new(versioned(k)));
+                    // Alan alan güncelleyen rcu da yırtık sürüm yayınlamaz
+                    live[1].rcu(|_| versioned(k));
+                    if stop.load(Ordering::Relaxed) {
+                        break k;
+                    }
+                }
+            })
+        };
+
+        let mut last_seen = [0u64; 2];
+        for _ in 0..2_000 {
+            let frozen = capture_pair(&live[0], &live[1]);
+            let seen = [assert_consistent(&frozen[0].load()), assert_consistent(&frozen[1].load())];
+            std::thread::yield_now();
+            // Fırsatın sonraki aşamaları aynı sürümü okur
+            assert_eq!(assert_consistent(&frozen[0].load()), seen[0]);
+            assert_eq!(assert_consistent(&frozen[1].load()), seen[1]);
+            // Sürümler yalnızca ileri gider
+            assert!(seen[0] >= last_seen[0] && seen[1] >= last_seen[1]);
+            last_seen = seen;
+        }
+        stop.store(true, Ordering::Relaxed);
+        assert!(writer.join().unwrap() > 0);
+    }
+
+    /// Tahminli yakalama: güven penceresindeki tahmin donmuş kopyaya işlenir,
+    /// canlı state onaylı kalır; zincir başı pencereyi aşınca onaylı state döner
+    #[test]
+    fn test_predicted_capture_carries_prediction() {
+        let mut state = versioned(10);
+        state.predicted_state = Some(Box::new(PredictedState {
+            sqrt_price_x96: U256::from(99u64),
+            sqrt_price_f64: 99.0,
+            tick: 99,
+            liquidity: 99,
+            eth_price_usd: 99.0,
+            base_block: 10,
+            predicted_at: state.last_update + Duration::from_millis(1),
+            trigger: OptimisticTrigger {
+                tx_hash: TxHash::ZERO,
+                sender: Address::ZERO,
+                observed_at: state.last_update,
+            },
+        }));
+        let live = Arc::new(ArcSwap::from_pointee(state));
+        let other = Arc::new(ArcSwap::from_pointee(versioned(10)));
+
+        let pair = capture_pair_predicted(&live, &other, 1);
+        assert_eq!((pair[0].load().tick, pair[0].load().liquidity), (99, 99));
+        assert_eq!(pair[0].load().sqrt_price_x96, U256::from(99u64));
+        assert_consistent(&pair[1].load());
+        assert_consistent(&live.load());
+
+        // Diğer havuz 2 blok ileride → tahmin pencerenin dışında
+        let ahead = Arc::new(ArcSwap::from_pointee(versioned(12)));
+        let pair = capture_pair_predicted(&live, &ahead, 1);
+        assert_consistent(&pair[0].load());
+    }
+}
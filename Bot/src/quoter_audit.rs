@@ -0,0 +1,1009 @@
+// ============================================================================
+//  QUOTER_AUDIT v1.0 — Off-Chain Swap Matematiği ↔ Resmi Quoter Eşliği
+//
+//  Birim testleri matematiği sabit fikstürlerde doğrular; asıl istenen
+//  garanti "botun hesapladığı çıktı = zincirin şu an döndüreceği çıktı"dır.
+//  Her AUDIT_INTERVAL_BLOCKS blokta (ve --audit-now ile) arka planda:
+//  ✓ Her aktif havuzun güncel state'i alınır; WETH satışı 0.1 / 1 / 5 WETH
+//    ve son NR boyutuyla problanır
+//  ✓ Çıktı iki motorla hesaplanır: boyutlandırma yolu (NR'nin f64 girdisi +
+//    fee kesri) ve exact motor (wei girdisi + fee pips)
+//  ✓ Aynı problar resmi QuoterV2'ye (Uniswap V3) / Slipstream QuoterV2'ye
+//    state'in bloğuna sabitli Multicall3 eth_call ile sorulur
+//  ✓ Parite tablosu wei farklarıyla loglanır; havuz × motor başına son
+//    AUDIT_WINDOW denetimin en büyük sapması istatistik kutusunda
+//  ✓ AUDIT_MAX_DEVIATION_BPS aşılırsa uyarı (konsol + Telegram) — bayat
+//    bitmap ya da matematik hatası işareti
+//  ✓ RPC zorlanıyorsa (sync hatası, gecikme spike'ı, önceki denetim sürüyor)
+//    denetim ertelenir; sıcak yol hiçbir zaman beklemez
+//
+//  PancakeSwap V3 havuzları için quoter tanımlı değildir ("no quoter").
+//  Quoter adresleri Base dağıtımlarıdır — diğer profillerde denetim kapalı.
+//  Durum süreç geneli tek kilitte (impact_verification deseni).
+// ============================================================================
+
+use alloy::eips::BlockId;
+use alloy::primitives::{address, Address, Bytes, Signed, Uint, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use arc_swap::ArcSwap;
+use colored::*;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::chain_profile::ChainProfile;
+use crate::errors::{BotError, BotResult};
+use crate::impact_verification::signed_diff;
+use crate::math::exact::{compute_exact_swap, u256_to_f64};
+use crate::state_sync::{with_rpc_retry, CallBudget, IMulticall3, MULTICALL3_ADDRESS};
+use crate::telegram::{TelegramMessage, TelegramSender};
+use crate::types::{weth_to_wei, BotConfig, DexType, PoolConfig, PoolState, SharedPoolState};
+
+/// Uniswap V3 QuoterV2 (Base)
+pub const UNISWAP_QUOTER_V2: Address = address!("3d4e44Eb1374240CE5F1B871ab261CD16335B76a");
+
+/// Aerodrome Slipstream QuoterV2 (Base)
+pub const SLIPSTREAM_QUOTER_V2: Address = address!("254cF9E1E6e233aa1AC962CB9B05b2cfeAaE15b0");
+
+/// Sabit prob boyutları (WETH) — son NR boyutu bunlara eklenir
+pub const PROBE_SIZES_WETH: [f64; 3] = [0.1, 1.0, 5.0];
+
+/// İstatistik kutusundaki kayan en büyük sapmanın penceresi (denetim sayısı)
+pub const AUDIT_WINDOW: usize = 12;
+
+/// Sapma tavanı (bps) — quoter sıfır döndürüp motor döndürmediğinde
+const MAX_DEVIATION_BPS: f64 = 10_000.0;
+
+/// Quoter Multicall3 zaman aşımı — quoter'lar swap'ı zincirde simüle eder
+const AUDIT_TIMEOUT_MS: u64 = 10_000;
+
+sol! {
+    /// Uniswap V3 QuoterV2 — havuz (tokenIn, tokenOut, fee) ile çözülür
+    interface IQuoterV2 {
+        struct QuoteExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint256 amountIn;
+            uint24 fee;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        function quoteExactInputSingle(QuoteExactInputSingleParams memory params)
+            external
+            returns (
+                uint256 amountOut,
+                uint160 sqrtPriceX96After,
+                uint32 initializedTicksCrossed,
+                uint256 gasEstimate
+            );
+    }
+
+    /// Slipstream QuoterV2 — havuz (tokenIn, tokenOut, tickSpacing) ile çözülür
+    interface ISlipstreamQuoterV2 {
+        struct QuoteExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint256 amountIn;
+            int24 tickSpacing;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        function quoteExactInputSingle(QuoteExactInputSingleParams memory params)
+            external
+            returns (
+                uint256 amountOut,
+                uint160 sqrtPriceX96After,
+                uint32 initializedTicksCrossed,
+                uint256 gasEstimate
+            );
+    }
+}
+
+/// Karşılaştırılan off-chain motor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    /// NR boyutlandırma yolu: f64 WETH girdisi + fee kesri
+    Sizing,
+    /// Exact motor: wei girdisi + fee pips
+    Exact,
+}
+
+impl Engine {
+    pub const ALL: [Engine; 2] = [Engine::Sizing, Engine::Exact];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Engine::Sizing => "sizing",
+            Engine::Exact => "exact",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Prob Planı + Karşılaştırma (saf fonksiyonlar)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek (havuz, boyut) probu: iki motorun çıktısı ve quoter'ın cevabı
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeQuote {
+    pub pool_name: String,
+    pub dex: DexType,
+    pub quoter: Address,
+    pub call_data: Bytes,
+    /// Quoter çağrısının sabitlendiği blok — state'in temsil ettiği blok
+    pub block: u64,
+    pub amount_weth: f64,
+    pub sizing_out: U256,
+    pub exact_out: U256,
+    /// Quoter çıktısı — revert / decode edilemezse None
+    pub quoter_out: Option<U256>,
+}
+
+impl ProbeQuote {
+    pub fn engine_out(&self, engine: Engine) -> U256 {
+        match engine {
+            Engine::Sizing => self.sizing_out,
+            Engine::Exact => self.exact_out,
+        }
+    }
+
+    /// Motor − quoter (wei); pozitif = motor iyimser
+    pub fn delta_wei(&self, engine: Engine) -> Option<i128> {
+        self.quoter_out.map(|q| signed_diff(self.engine_out(engine), q))
+    }
+
+    /// |motor − quoter| / quoter (bps)
+    pub fn deviation_bps(&self, engine: Engine) -> Option<f64> {
+        let quoted = self.quoter_out?;
+        let out = self.engine_out(engine);
+        if quoted.is_zero() {
+            return Some(if out.is_zero() { 0.0 } else { MAX_DEVIATION_BPS });
+        }
+        let diff = if out >= quoted { out - quoted } else { quoted - out };
+        Some((u256_to_f64(diff) / u256_to_f64(quoted) * 10_000.0).min(MAX_DEVIATION_BPS))
+    }
+}
+
+/// Prob boyutları: sabit set + (farklıysa) son NR boyutu
+pub fn probe_sizes(nr_size_weth: Option<f64>) -> Vec<f64> {
+    let mut sizes = PROBE_SIZES_WETH.to_vec();
+    if let Some(nr) = nr_size_weth.filter(|s| s.is_finite() && *s > 0.0) {
+        if !sizes.iter().any(|s| (s - nr).abs() < 1e-9) {
+            sizes.push(nr);
+        }
+    }
+    sizes
+}
+
+/// Havuzun quoter'ı ve WETH → quote satışı için calldata (quoter yoksa None)
+pub fn quoter_call(pool: &PoolConfig, amount_in: U256) -> Option<(Address, Bytes)> {
+    let (token_in, token_out) = (pool.base_token_address, pool.quote_token_address);
+    match pool.dex {
+        DexType::UniswapV3 => {
+            let params = IQuoterV2::QuoteExactInputSingleParams {
+                tokenIn: token_in,
+                tokenOut: token_out,
+                amountIn: amount_in,
+                fee: Uint::<24, 1>::from(pool.fee_pips()),
+                sqrtPriceLimitX96: Uint::<160, 3>::ZERO,
+            };
+            let call = IQuoterV2::quoteExactInputSingleCall { params };
+            Some((UNISWAP_QUOTER_V2, Bytes::from(call.abi_encode())))
+        }
+        DexType::Aerodrome => {
+            let params = ISlipstreamQuoterV2::QuoteExactInputSingleParams {
+                tokenIn: token_in,
+                tokenOut: token_out,
+                amountIn: amount_in,
+                tickSpacing: Signed::<24, 1>::try_from(pool.tick_spacing).ok()?,
+                sqrtPriceLimitX96: Uint::<160, 3>::ZERO,
+            };
+            let call = ISlipstreamQuoterV2::quoteExactInputSingleCall { params };
+            Some((SLIPSTREAM_QUOTER_V2, Bytes::from(call.abi_encode())))
+        }
+        DexType::PancakeSwapV3 | DexType::UniswapV2 => None,
+    }
+}
+
+/// Multicall3 sonucunu quoter çıktısına çevir (başarısız / kısa veri → None)
+pub fn decode_quote(dex: DexType, success: bool, data: &[u8]) -> Option<U256> {
+    if !success {
+        return None;
+    }
+    match dex {
+        DexType::UniswapV3 => IQuoterV2::quoteExactInputSingleCall::abi_decode_returns(data)
+            .ok()
+            .map(|r| r.amountOut),
+        DexType::Aerodrome => ISlipstreamQuoterV2::quoteExactInputSingleCall::abi_decode_returns(data)
+            .ok()
+            .map(|r| r.amountOut),
+        DexType::PancakeSwapV3 | DexType::UniswapV2 => None,
+    }
+}
+
+/// Her aktif havuz × boyut için iki motorun çıktısını hesapla. Quoter'ı
+/// olmayan / aktif olmayan havuzlar gerekçesiyle `skipped`e düşer.
+pub fn plan_probes(
+    pools: &[PoolConfig],
+    states: &[Arc<PoolState>],
+    sizes: &[f64],
+) -> AuditReport {
+    let mut report = AuditReport::default();
+    for (pool, state) in pools.iter().zip(states) {
+        if !state.is_active() {
+            report.skipped.push((pool.name.clone(), "inactive"));
+            continue;
+        }
+        let fee = pool.effective_fee(state.live_fee_bps);
+        for &size in sizes {
+            let Ok(amount_in) = weth_to_wei(size) else {
+                report.skipped.push((pool.name.clone(), "invalid size"));
+                break;
+            };
+            let Some((quoter, call_data)) = quoter_call(pool, amount_in) else {
+                report.skipped.push((pool.name.clone(), "no quoter"));
+                break;
+            };
+            let exact_out = compute_exact_swap(
+                state.sqrt_price_x96,
+                state.liquidity,
+                state.tick,
+                amount_in,
+                pool.token0_is_weth,
+                fee.pips(),
+                state.tick_bitmap.as_deref(),
+            )
+            .amount_out;
+            report.probes.push(ProbeQuote {
+                pool_name: pool.name.clone(),
+                dex: pool.dex,
+                quoter,
+                call_data,
+                block: state.last_block,
+                amount_weth: size,
+                sizing_out: crate::math::sizing_leg_output(
+                    size,
+                    state,
+                    fee.fraction(),
+                    pool.token0_is_weth,
+                ),
+                exact_out,
+                quoter_out: None,
+            });
+        }
+    }
+    report
+}
+
+/// Tek denetim turunun sonucu
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditReport {
+    pub probes: Vec<ProbeQuote>,
+    /// (havuz, gerekçe) — denetlenemeyen havuzlar
+    pub skipped: Vec<(String, &'static str)>,
+}
+
+impl AuditReport {
+    /// Havuz başına motor sapmalarının en büyüğü (okunamayan problar hariç).
+    /// Hiçbir probu okunamayan havuz listede yer almaz.
+    pub fn pool_max_bps(&self) -> BTreeMap<String, [f64; 2]> {
+        let mut out: BTreeMap<String, [f64; 2]> = BTreeMap::new();
+        for probe in &self.probes {
+            if probe.quoter_out.is_none() {
+                continue;
+            }
+            let entry = out.entry(probe.pool_name.clone()).or_default();
+            for engine in Engine::ALL {
+                let bps = probe.deviation_bps(engine).unwrap_or(0.0);
+                entry[engine.index()] = entry[engine.index()].max(bps);
+            }
+        }
+        out
+    }
+
+    /// Quoter'ın cevaplamadığı prob sayısı
+    pub fn unreadable(&self) -> usize {
+        self.probes.iter().filter(|p| p.quoter_out.is_none()).count()
+    }
+}
+
+/// Eşiği aşan (havuz, motor) — havuz × motor başına en kötü prob
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParityAlert {
+    pub pool_name: String,
+    pub engine: Engine,
+    pub probe_weth: f64,
+    pub deviation_bps: f64,
+    pub block: u64,
+}
+
+/// Eşiği aşan problardan havuz × motor başına en kötüsünü seç
+pub fn find_alerts(report: &AuditReport, max_deviation_bps: f64) -> Vec<ParityAlert> {
+    let mut worst: BTreeMap<(String, usize), ParityAlert> = BTreeMap::new();
+    for probe in &report.probes {
+        for engine in Engine::ALL {
+            let Some(bps) = probe.deviation_bps(engine) else {
+                continue;
+            };
+            if bps <= max_deviation_bps {
+                continue;
+            }
+            let key = (probe.pool_name.clone(), engine.index());
+            if worst.get(&key).is_none_or(|a| bps > a.deviation_bps) {
+                worst.insert(
+                    key,
+                    ParityAlert {
+                        pool_name: probe.pool_name.clone(),
+                        engine,
+                        probe_weth: probe.amount_weth,
+                        deviation_bps: bps,
+                        block: probe.block,
+                    },
+                );
+            }
+        }
+    }
+    worst.into_values().collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Zamanlayıcı + Kayan İstatistik
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Bu blokta denetim yapılsın mı?
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditDecision {
+    /// Zamanı gelmedi / kapalı / erteleme zaten bildirildi
+    Idle,
+    /// Arka planda başlat
+    Run,
+    /// Zamanı geldi ama RPC zorlanıyor — yalnızca ilk ertelemede döner
+    Deferred(&'static str),
+}
+
+/// RPC bütçesi zorlanıyorsa gerekçe
+pub fn strain_reason(
+    in_flight: bool,
+    sync_errors: usize,
+    sync_ms: f64,
+    latency_spike_threshold_ms: f64,
+) -> Option<&'static str> {
+    if in_flight {
+        Some("previous audit still running")
+    } else if sync_errors > 0 {
+        Some("sync errors this block")
+    } else if sync_ms > latency_spike_threshold_ms {
+        Some("latency spike")
+    } else {
+        None
+    }
+}
+
+/// Havuz başına son AUDIT_WINDOW denetimin motor sapmaları (bps)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoolParity {
+    window: VecDeque<[f64; 2]>,
+}
+
+impl PoolParity {
+    fn push(&mut self, max_bps: [f64; 2]) {
+        if self.window.len() == AUDIT_WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(max_bps);
+    }
+
+    /// Penceredeki en büyük sapma
+    pub fn rolling_max(&self, engine: Engine) -> f64 {
+        self.window
+            .iter()
+            .map(|m| m[engine.index()])
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Aralık + eşik + kayan istatistik
+#[derive(Debug, Clone)]
+pub struct QuoterAuditor {
+    interval_blocks: u64,
+    pub max_deviation_bps: f64,
+    last_run_block: Option<u64>,
+    /// Bu aralıktaki erteleme zaten loglandı
+    deferred: bool,
+    /// Son seçilen fırsatın NR boyutu — sıradaki denetimin ek probu
+    nr_size_weth: Option<f64>,
+    pub pools: BTreeMap<String, PoolParity>,
+    pub runs: u64,
+    pub deferrals: u64,
+    pub warnings: u64,
+}
+
+impl QuoterAuditor {
+    pub fn new(interval_blocks: u64, max_deviation_bps: f64) -> Self {
+        Self {
+            interval_blocks,
+            max_deviation_bps,
+            last_run_block: None,
+            deferred: false,
+            nr_size_weth: None,
+            pools: BTreeMap::new(),
+            runs: 0,
+            deferrals: 0,
+            warnings: 0,
+        }
+    }
+
+    /// İlk uygun blokta, sonra her `interval_blocks` blokta bir. Zorlanma
+    /// varsa zaman kaydırılmaz — sonraki temiz blokta denetim yapılır.
+    pub fn decide(&mut self, block: u64, strain: Option<&'static str>) -> AuditDecision {
+        if self.interval_blocks == 0 {
+            return AuditDecision::Idle;
+        }
+        let due = self
+            .last_run_block
+            .is_none_or(|last| block >= last + self.interval_blocks);
+        if !due {
+            return AuditDecision::Idle;
+        }
+        match strain {
+            Some(_) if self.deferred => AuditDecision::Idle,
+            Some(reason) => {
+                self.deferred = true;
+                self.deferrals += 1;
+                AuditDecision::Deferred(reason)
+            }
+            None => {
+                self.last_run_block = Some(block);
+                self.deferred = false;
+                AuditDecision::Run
+            }
+        }
+    }
+
+    /// Denetim sonucunu pencereye işle, eşiği aşanları döndür
+    pub fn record(&mut self, report: &AuditReport) -> Vec<ParityAlert> {
+        self.runs += 1;
+        for (pool, max_bps) in report.pool_max_bps() {
+            self.pools.entry(pool).or_default().push(max_bps);
+        }
+        let alerts = find_alerts(report, self.max_deviation_bps);
+        self.warnings += alerts.len() as u64;
+        alerts
+    }
+}
+
+/// Süreç geneli denetçi — init öncesi None (denetim yok)
+static AUDITOR: Mutex<Option<QuoterAuditor>> = parking_lot::const_mutex(None);
+
+/// Arka plan denetimi sürüyor mu (üst üste binme engeli)
+static IN_FLIGHT: AtomicBool = AtomicBool::new(false);
+
+/// Aralık ve eşiği config'den al (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    let interval = if config.chain_profile == ChainProfile::Base {
+        config.audit_interval_blocks
+    } else {
+        if config.audit_interval_blocks > 0 {
+            println!(
+                "  {} Quoter audit disabled: quoter addresses are Base deployments (CHAIN_PROFILE={})",
+                "ℹ️".blue(),
+                config.chain_profile,
+            );
+        }
+        0
+    };
+    *AUDITOR.lock() = Some(QuoterAuditor::new(interval, config.audit_max_deviation_bps));
+}
+
+/// Son seçilen fırsatın NR boyutunu kaydet (ana döngü, BestPick sonrası)
+pub fn note_nr_size(amount_weth: f64) {
+    if let Some(auditor) = AUDITOR.lock().as_mut() {
+        auditor.nr_size_weth = Some(amount_weth);
+    }
+}
+
+/// Ana döngüden her blokta: zamanı geldiyse ve RPC zorlanmıyorsa Run
+pub fn poll(
+    block: u64,
+    sync_errors: usize,
+    sync_ms: f64,
+    latency_spike_threshold_ms: f64,
+) -> AuditDecision {
+    let strain = strain_reason(
+        IN_FLIGHT.load(Ordering::Acquire),
+        sync_errors,
+        sync_ms,
+        latency_spike_threshold_ms,
+    );
+    AUDITOR
+        .lock()
+        .as_mut()
+        .map_or(AuditDecision::Idle, |a| a.decide(block, strain))
+}
+
+/// İstatistik kutusu satırı — henüz denetim yoksa None
+pub fn stats_line() -> Option<String> {
+    let guard = AUDITOR.lock();
+    let auditor = guard.as_ref()?;
+    if auditor.runs == 0 && auditor.deferrals == 0 {
+        return None;
+    }
+    let mut line = format!("runs {} (max over last {})", auditor.runs, AUDIT_WINDOW);
+    for (pool, parity) in &auditor.pools {
+        line.push_str(&format!(
+            " | {} exact {:.2} / sizing {:.2} bps",
+            pool,
+            parity.rolling_max(Engine::Exact),
+            parity.rolling_max(Engine::Sizing),
+        ));
+    }
+    line.push_str(&format!(
+        " | warnings {} | deferred {}",
+        auditor.warnings, auditor.deferrals,
+    ));
+    Some(line)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// On-Chain Quoter Okuması + Raporlama
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Quoter çıktılarını oku: blok başına TEK sabitli Multicall3 çağrısı.
+async fn read_quotes<P: Provider + Sync>(provider: &P, probes: &mut [ProbeQuote]) -> BotResult<()> {
+    let mut blocks: Vec<u64> = probes.iter().map(|p| p.block).collect();
+    blocks.sort_unstable();
+    blocks.dedup();
+
+    let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
+    let budget = CallBudget::new("multicall", AUDIT_TIMEOUT_MS);
+    for block in blocks {
+        let indices: Vec<usize> = (0..probes.len()).filter(|&i| probes[i].block == block).collect();
+        let calls: Vec<IMulticall3::Call3> = indices
+            .iter()
+            .map(|&i| IMulticall3::Call3 {
+                target: probes[i].quoter,
+                allowFailure: true,
+                callData: probes[i].call_data.clone(),
+            })
+            .collect();
+        let label = format!("Quoter audit Multicall3 #{}", block);
+        let results = with_rpc_retry(&label, &budget, || {
+            let call = multicall.aggregate3(calls.clone()).block(BlockId::number(block));
+            let label = label.clone();
+            async move { call.call().await.map_err(|e| BotError::from_call(label, e)) }
+        })
+        .await?;
+
+        for (k, &i) in indices.iter().enumerate() {
+            probes[i].quoter_out = results
+                .get(k)
+                .and_then(|r| decode_quote(probes[i].dex, r.success, &r.returnData));
+        }
+    }
+    Ok(())
+}
+
+/// Parite tablosu — her satır bir (havuz, boyut) probu
+fn print_report(report: &AuditReport, max_deviation_bps: f64) {
+    println!(
+        "\n  {} Quoter parity audit ({} probe(s), threshold {:.2} bps):",
+        "🧾".cyan(),
+        report.probes.len(),
+        max_deviation_bps,
+    );
+    println!(
+        "     {:<28} {:>10} {:>9} {:>26} {:>14} {:>14} {:>9}",
+        "pool", "block", "WETH", "quoter out (wei)", "Δ exact", "Δ sizing", "max bps"
+    );
+    for probe in &report.probes {
+        let delta = |engine| {
+            probe
+                .delta_wei(engine)
+                .map_or("?".to_string(), |d| format!("{:+}", d))
+        };
+        let worst = Engine::ALL
+            .iter()
+            .filter_map(|&e| probe.deviation_bps(e))
+            .fold(None, |acc: Option<f64>, bps| Some(acc.map_or(bps, |a| a.max(bps))));
+        let line = format!(
+            "{:<28} {:>10} {:>9.4} {:>26} {:>14} {:>14} {:>9}",
+            probe.pool_name,
+            probe.block,
+            probe.amount_weth,
+            probe.quoter_out.map_or("unreadable".to_string(), |q| q.to_string()),
+            delta(Engine::Exact),
+            delta(Engine::Sizing),
+            worst.map_or("-".to_string(), |w| format!("{:.2}", w)),
+        );
+        match worst {
+            Some(w) if w > max_deviation_bps => eprintln!("     {}", line.red().bold()),
+            None => eprintln!("     {}", line.yellow()),
+            Some(_) => println!("     {}", line),
+        }
+    }
+    for (pool, reason) in &report.skipped {
+        println!("     {} {} — {}", "ℹ️".blue(), pool, reason);
+    }
+}
+
+/// Tek denetim turu: state'leri al → planla → sabitli quoter okuması →
+/// karşılaştır → raporla → istatistik + uyarı. Okuma başarısızsa None.
+pub async fn run_audit<P: Provider + Sync>(
+    provider: &P,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    telegram: &Option<TelegramSender>,
+) -> Option<(AuditReport, Vec<ParityAlert>)> {
+    let nr_size = AUDITOR.lock().as_ref().and_then(|a| a.nr_size_weth);
+    let snapshot: Vec<Arc<PoolState>> = states.iter().map(|s| s.load_full()).collect();
+    let mut report = plan_probes(pools, &snapshot, &probe_sizes(nr_size));
+    if report.probes.is_empty() {
+        return Some((report, Vec::new()));
+    }
+
+    if let Err(e) = read_quotes(provider, &mut report.probes).await {
+        eprintln!("  ⚠️ [QuoterAudit] quoter read failed: {} — audit skipped", e);
+        return None;
+    }
+
+    let (alerts, threshold) = {
+        let mut guard = AUDITOR.lock();
+        match guard.as_mut() {
+            Some(auditor) => (auditor.record(&report), auditor.max_deviation_bps),
+            None => (Vec::new(), f64::INFINITY),
+        }
+    };
+    print_report(&report, threshold);
+
+    crate::json_logger::log_json(
+        "info",
+        "quoter_parity",
+        serde_json::json!({
+            "threshold_bps": threshold,
+            "probes": report.probes.iter().map(|p| serde_json::json!({
+                "pool": p.pool_name,
+                "block": p.block,
+                "amount_weth": p.amount_weth,
+                "quoter_out": p.quoter_out.map(|q| q.to_string()),
+                "exact_out": p.exact_out.to_string(),
+                "sizing_out": p.sizing_out.to_string(),
+                "exact_delta_wei": p.delta_wei(Engine::Exact).map(|d| d.to_string()),
+                "sizing_delta_wei": p.delta_wei(Engine::Sizing).map(|d| d.to_string()),
+            })).collect::<Vec<_>>(),
+            "skipped": report.skipped.iter().map(|(pool, reason)| format!("{}: {}", pool, reason)).collect::<Vec<_>>(),
+            "alerts": alerts.len(),
+        }),
+    );
+
+    for alert in &alerts {
+        eprintln!(
+            "  {} [QuoterAudit] {} {} engine off by {:.2} bps at {} WETH (threshold {:.2}) — stale bitmap or math bug?",
+            "⚠️".yellow(),
+            alert.pool_name,
+            alert.engine.label(),
+            alert.deviation_bps,
+            alert.probe_weth,
+            threshold,
+        );
+        if let Some(tg) = telegram {
+            tg.send(TelegramMessage::QuoterDeviation {
+                pool_name: alert.pool_name.clone(),
+                engine: alert.engine.label().to_string(),
+                probe_weth: alert.probe_weth,
+                deviation_bps: alert.deviation_bps,
+                threshold_bps: threshold,
+                block_number: alert.block,
+            });
+        }
+    }
+    if alerts.is_empty() && report.unreadable() == 0 {
+        println!(
+            "  {} [QuoterAudit] all engines within {:.2} bps of the on-chain quoter",
+            "✅".green(),
+            threshold,
+        );
+    }
+    Some((report, alerts))
+}
+
+/// Denetimi ana döngüden bağımsız başlat (poll → Run sonrası)
+pub fn spawn_audit<P>(
+    provider: P,
+    pools: Vec<PoolConfig>,
+    states: Vec<SharedPoolState>,
+    telegram: Option<TelegramSender>,
+) where
+    P: Provider + Clone + Send + Sync + 'static,
+{
+    IN_FLIGHT.store(true, Ordering::Release);
+    tokio::spawn(async move {
+        run_audit(&provider, &pools, &states, &telegram).await;
+        IN_FLIGHT.store(false, Ordering::Release);
+    });
+}
+
+/// CLI: --audit-now — havuzları güncel bloğa sabitli okuyup tek denetim yap.
+/// Eşik aşılırsa ya da quoter okunamazsa hata ile çıkar.
+pub async fn cli_audit_now(config: &BotConfig, pools: &[PoolConfig]) -> eyre::Result<()> {
+    if config.chain_profile != ChainProfile::Base {
+        return Err(eyre::eyre!(
+            "--audit-now needs CHAIN_PROFILE=base (quoter addresses are Base deployments)"
+        ));
+    }
+    let url: reqwest::Url = config
+        .rpc_http_url
+        .parse()
+        .map_err(|e| eyre::eyre!("RPC_HTTP_URL parse error: {}", e))?;
+    let provider = ProviderBuilder::new().connect_http(url);
+    let block = provider
+        .get_block_number()
+        .await
+        .map_err(|e| eyre::eyre!("block number read failed: {}", e))?;
+
+    println!(
+        "  {} Quoter audit @ block #{} ({} pool(s))",
+        "🧾".cyan(),
+        block,
+        pools.len(),
+    );
+    let states: Vec<SharedPoolState> = pools
+        .iter()
+        .map(|_| Arc::new(ArcSwap::from_pointee(PoolState::default())))
+        .collect();
+    for (pool, state) in pools.iter().zip(&states) {
+        if let Err(e) =
+            crate::state_sync::sync_pool_state(&provider, pool, state, block, BlockId::number(block))
+                .await
+        {
+            eprintln!("  {} {} state sync failed: {}", "⚠️".yellow(), pool.name, e);
+            continue;
+        }
+        if let Err(e) = crate::state_sync::sync_tick_bitmap(
+            &provider,
+            pool,
+            state,
+            block,
+            config.tick_bitmap_range,
+        )
+        .await
+        {
+            eprintln!(
+                "  {} {} tick bitmap sync failed: {} — single-tick math",
+                "⚠️".yellow(),
+                pool.name,
+                e
+            );
+        }
+    }
+
+    init(config);
+    let (report, alerts) = run_audit(&provider, pools, &states, &None)
+        .await
+        .ok_or_else(|| eyre::eyre!("quoter read failed"))?;
+    if !alerts.is_empty() {
+        return Err(eyre::eyre!(
+            "{} pool/engine pair(s) above AUDIT_MAX_DEVIATION_BPS",
+            alerts.len()
+        ));
+    }
+    if report.unreadable() > 0 {
+        return Err(eyre::eyre!("{} probe(s) unreadable from the quoter", report.unreadable()));
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::gas_spike_tests::{make_pool_configs, make_pool_state};
+
+    /// QuoterV2 dönüşü (kayıtlı): amountOut = 2_512_345_678 (USDC, 6 ondalık),
+    /// sqrtPriceX96After, initializedTicksCrossed = 1, gasEstimate = 84_213
+    const RECORDED_UNI_QUOTE: &str = concat!(
+        "0000000000000000000000000000000000000000000000000000000095bf5a4e",
+        "00000000000000000000000000000000000000000cecb8f27f4200f3a0000000",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "00000000000000000000000000000000000000000000000000000000000148f5",
+    );
+
+    /// Kesik dönüş (kayıtlı): yalnızca ilk kelime — düğüm yanıtı yarıda kesilmiş
+    const RECORDED_TRUNCATED_QUOTE: &str =
+        "0000000000000000000000000000000000000000000000000000000095bf5a4e";
+
+    fn recorded(hex: &str) -> Vec<u8> {
+        alloy::primitives::hex::decode(hex).unwrap()
+    }
+
+    fn probe(quoter_out: Option<u64>, exact_out: u64, sizing_out: u64) -> ProbeQuote {
+        ProbeQuote {
+            pool_name: "UniV3-test".into(),
+            dex: DexType::UniswapV3,
+            quoter: UNISWAP_QUOTER_V2,
+            call_data: Bytes::new(),
+            block: 1000,
+            amount_weth: 1.0,
+            sizing_out: U256::from(sizing_out),
+            exact_out: U256::from(exact_out),
+            quoter_out: quoter_out.map(U256::from),
+        }
+    }
+
+    #[test]
+    fn test_decode_recorded_quoter_returns() {
+        let data = recorded(RECORDED_UNI_QUOTE);
+        assert_eq!(decode_quote(DexType::UniswapV3, true, &data), Some(U256::from(2_512_345_678u64)));
+        // Slipstream dönüş düzeni aynı
+        assert_eq!(decode_quote(DexType::Aerodrome, true, &data), Some(U256::from(2_512_345_678u64)));
+        let full = IQuoterV2::quoteExactInputSingleCall::abi_decode_returns(&data).unwrap();
+        assert_eq!(full.initializedTicksCrossed, 1);
+        assert_eq!(full.gasEstimate, U256::from(84_213u64));
+
+        // Revert, kesik veri ve quoter'ı olmayan DEX → okunamadı
+        assert_eq!(decode_quote(DexType::UniswapV3, false, &data), None);
+        assert_eq!(decode_quote(DexType::Aerodrome, true, &recorded(RECORDED_TRUNCATED_QUOTE)), None);
+        assert_eq!(decode_quote(DexType::PancakeSwapV3, true, &data), None);
+    }
+
+    #[test]
+    fn test_quoter_calldata_per_dex() {
+        let pools = make_pool_configs();
+        let amount = U256::from(1_000_000_000_000_000_000u128);
+
+        let (uni_quoter, uni_data) = quoter_call(&pools[0], amount).unwrap();
+        assert_eq!(uni_quoter, UNISWAP_QUOTER_V2);
+        assert_eq!(&uni_data[..4], IQuoterV2::quoteExactInputSingleCall::SELECTOR.as_slice());
+        let decoded = IQuoterV2::quoteExactInputSingleCall::abi_decode(&uni_data).unwrap();
+        assert_eq!(decoded.params.tokenIn, pools[0].base_token_address);
+        assert_eq!(decoded.params.amountIn, amount);
+        assert_eq!(decoded.params.fee, Uint::<24, 1>::from(500u32));
+
+        let (aero_quoter, aero_data) = quoter_call(&pools[1], amount).unwrap();
+        assert_eq!(aero_quoter, SLIPSTREAM_QUOTER_V2);
+        let decoded = ISlipstreamQuoterV2::quoteExactInputSingleCall::abi_decode(&aero_data).unwrap();
+        assert_eq!(decoded.params.tickSpacing.as_i32(), 1);
+        assert_eq!(decoded.params.tokenOut, pools[1].quote_token_address);
+
+        let mut pancake = pools[0].clone();
+        pancake.dex = DexType::PancakeSwapV3;
+        assert!(quoter_call(&pancake, amount).is_none());
+    }
+
+    #[test]
+    fn test_deviation_against_recorded_quote() {
+        let quoted = decode_quote(DexType::UniswapV3, true, &recorded(RECORDED_UNI_QUOTE)).unwrap();
+        let quoted = quoted.to::<u64>();
+
+        // Exact birebir, boyutlandırma yolu 2512 wei (≈0.01 bps) iyimser
+        let p = probe(Some(quoted), quoted, quoted + 2_512);
+        assert_eq!(p.delta_wei(Engine::Exact), Some(0));
+        assert_eq!(p.delta_wei(Engine::Sizing), Some(2_512));
+        assert_eq!(p.deviation_bps(Engine::Exact), Some(0.0));
+        assert!((p.deviation_bps(Engine::Sizing).unwrap() - 0.01).abs() < 1e-4);
+
+        // Motor kötümser → negatif wei farkı, bps mutlak
+        let p = probe(Some(quoted), quoted - 251_234_568, quoted);
+        assert_eq!(p.delta_wei(Engine::Exact), Some(-251_234_568));
+        assert!((p.deviation_bps(Engine::Exact).unwrap() - 1_000.0).abs() < 1e-3);
+
+        // Okunamayan quoter → sapma yok; quoter 0 iken motor >0 → tavan
+        assert_eq!(probe(None, 1, 1).deviation_bps(Engine::Exact), None);
+        assert_eq!(probe(Some(0), 1, 0).deviation_bps(Engine::Exact), Some(MAX_DEVIATION_BPS));
+        assert_eq!(probe(Some(0), 1, 0).deviation_bps(Engine::Sizing), Some(0.0));
+    }
+
+    #[test]
+    fn test_engines_agree_on_fixture_pools() {
+        let pools = make_pool_configs();
+        let states = vec![
+            make_pool_state(2500.0, 50_000_000_000_000_000_000, 1000).load_full(),
+            make_pool_state(2505.0, 50_000_000_000_000_000_000, 1001).load_full(),
+        ];
+        let report = plan_probes(&pools, &states, &probe_sizes(Some(2.75)));
+        assert_eq!(report.probes.len(), 8);
+        assert!(report.skipped.is_empty());
+
+        for p in &report.probes {
+            assert!(!p.exact_out.is_zero(), "{} {} WETH", p.pool_name, p.amount_weth);
+            // Boyutlandırma yolu exact ile aynı tick yolunu izler — fark yalnızca yuvarlama
+            let mut check = p.clone();
+            check.quoter_out = Some(p.exact_out);
+            assert!(check.deviation_bps(Engine::Sizing).unwrap() < 0.01);
+        }
+        // Quoter çağrısı state'in bloğuna sabitlenir
+        assert!(report.probes.iter().filter(|p| p.pool_name == "Aero-test").all(|p| p.block == 1001));
+    }
+
+    #[test]
+    fn test_probe_sizes_and_skips() {
+        assert_eq!(probe_sizes(None), vec![0.1, 1.0, 5.0]);
+        assert_eq!(probe_sizes(Some(1.0)), vec![0.1, 1.0, 5.0]);
+        assert_eq!(probe_sizes(Some(f64::NAN)), vec![0.1, 1.0, 5.0]);
+        assert_eq!(probe_sizes(Some(3.2)), vec![0.1, 1.0, 5.0, 3.2]);
+
+        let mut pools = make_pool_configs();
+        pools[1].dex = DexType::PancakeSwapV3;
+        let states = vec![
+            Arc::new(PoolState::default()),
+            make_pool_state(2500.0, 50_000_000_000_000_000_000, 1000).load_full(),
+        ];
+        let report = plan_probes(&pools, &states, &probe_sizes(None));
+        assert!(report.probes.is_empty());
+        assert_eq!(
+            report.skipped,
+            vec![("UniV3-test".to_string(), "inactive"), ("Aero-test".to_string(), "no quoter")]
+        );
+    }
+
+    #[test]
+    fn test_alerts_and_rolling_max() {
+        let mut auditor = QuoterAuditor::new(500, 10.0);
+        let quoted = 2_512_345_678u64;
+        let clean = AuditReport {
+            probes: vec![probe(Some(quoted), quoted, quoted + 2_512)],
+            skipped: Vec::new(),
+        };
+        assert!(auditor.record(&clean).is_empty());
+
+        // Exact motor %1 sapıyor (bayat bitmap) → tek uyarı, en kötü prob
+        let mut small = probe(Some(quoted), quoted - quoted / 1000, quoted);
+        small.amount_weth = 0.1;
+        let stale = AuditReport {
+            probes: vec![small, probe(Some(quoted), quoted - quoted / 100, quoted), probe(None, 0, 0)],
+            skipped: Vec::new(),
+        };
+        let alerts = auditor.record(&stale);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].engine, Engine::Exact);
+        assert_eq!(alerts[0].probe_weth, 1.0);
+        assert!((alerts[0].deviation_bps - 100.0).abs() < 1e-4);
+        assert_eq!(auditor.warnings, 1);
+
+        let parity = &auditor.pools["UniV3-test"];
+        assert!((parity.rolling_max(Engine::Exact) - 100.0).abs() < 1e-4);
+        assert!((parity.rolling_max(Engine::Sizing) - 0.01).abs() < 1e-4);
+
+        // Pencere dolunca eski sapma düşer
+        for _ in 0..AUDIT_WINDOW {
+            auditor.record(&clean);
+        }
+        assert_eq!(auditor.pools["UniV3-test"].rolling_max(Engine::Exact), 0.0);
+        assert_eq!(auditor.runs, 2 + AUDIT_WINDOW as u64);
+    }
+
+    #[test]
+    fn test_scheduler_interval_and_strain() {
+        let mut auditor = QuoterAuditor::new(500, 10.0);
+        assert_eq!(auditor.decide(100, None), AuditDecision::Run);
+        assert_eq!(auditor.decide(101, None), AuditDecision::Idle);
+        assert_eq!(auditor.decide(599, None), AuditDecision::Idle);
+
+        // Zamanı geldi ama RPC zorlanıyor → bir kez bildir, sonra sessizce bekle
+        let spike = strain_reason(false, 0, 900.0, 500.0);
+        assert_eq!(spike, Some("latency spike"));
+        assert_eq!(auditor.decide(600, spike), AuditDecision::Deferred("latency spike"));
+        assert_eq!(auditor.decide(601, strain_reason(false, 2, 10.0, 500.0)), AuditDecision::Idle);
+        assert_eq!(auditor.deferrals, 1);
+        // İlk temiz blokta çalışır, aralık oradan sayılır
+        assert_eq!(auditor.decide(602, strain_reason(false, 0, 10.0, 500.0)), AuditDecision::Run);
+        assert_eq!(auditor.decide(1101, None), AuditDecision::Idle);
+        assert_eq!(
+            auditor.decide(1102, strain_reason(true, 0, 10.0, 500.0)),
+            AuditDecision::Deferred("previous audit still running")
+        );
+
+        // AUDIT_INTERVAL_BLOCKS=0 → hiç çalışmaz
+        let mut off = QuoterAuditor::new(0, 10.0);
+        assert_eq!(off.decide(1, None), AuditDecision::Idle);
+    }
+}
@@ -0,0 +1,958 @@
+// ============================================================================
+//  REPLAY v1.0 — Kayıtlı Oturumun Deterministik Yeniden Oynatılması
+//
+//  Kayıt (STATE_DIFF_LOG=true):
+//  ✓ state_diff.jsonl — blok başına tek satır: blok girdileri (base fee,
+//    L1 data fee, son simüle gas, rakip TX sayısı) + yalnızca skalerleri
+//    DEĞİŞEN havuzlar. Oturum başında, blok boşluğunda ve her
+//    KEYFRAME_INTERVAL_BLOCKS blokta tüm havuzlar yazılır (full=true)
+//  ✓ shadow_analytics.jsonl satırları block + opportunity_id taşır
+//  ✓ LOG_FORMAT_BINARY=true → aynı satırlar state_diff.bin /
+//    shadow_analytics.bin'e (binlog); okuyucular formatı koklar
+//
+//  Yeniden oynatma (--replay-diff <state_diff.jsonl> [--size-tol-pct X]):
+//  ✓ Blok blok PoolState yeniden kurulur; bitmap snapshots/ arşivinde aynı
+//    snapshot_block ile varsa yüklenir, yoksa fallback olarak sayılır
+//  ✓ Her combo için check_arbitrage_opportunity + exact kâr doğrulaması
+//    (mevcut kod ve mevcut .env ile)
+//  ✓ Orijinal oturumun gölge logu + live snapshot'larıyla (block,
+//    opportunity_id) anahtarında karşılaştırma: aynı fırsat? aynı boyut
+//    (tolerans içinde)? aynı go/no-go?
+//  ✓ Girdisi eksik bloklar (keyframe öncesi / boşluk sonrası) atlanır, sayılır
+// ============================================================================
+
+use alloy::primitives::{hex, Address, U256};
+use arc_swap::ArcSwap;
+use colored::*;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::binlog::{read_json_lines, BinaryLogWriter};
+use crate::math::exact::u256_to_f64;
+use crate::opportunity_filter::FilterChain;
+use crate::pool_discovery::PairCombo;
+use crate::strategy::{check_arbitrage_opportunity, exact_profit_for_opportunity};
+use crate::types::{
+    liquidity_to_f64, ArbitrageOpportunity, BotConfig, CompetitionSnapshot, PoolConfig, PoolState,
+    SharedPoolState, TickBitmapData,
+};
+
+/// Canlı oturumun blok bazlı havuz skalerleri
+pub const STATE_DIFF_LOG_PATH: &str = "state_diff.jsonl";
+
+/// LOG_FORMAT_BINARY=true iken state_diff akışı
+pub const STATE_DIFF_BIN_PATH: &str = "state_diff.bin";
+
+/// Orijinal oturumun gölge kararları (strategy::write_shadow_log)
+pub const SHADOW_LOG_PATH: &str = "shadow_analytics.jsonl";
+
+/// LOG_FORMAT_BINARY=true iken gölge log
+pub const SHADOW_BIN_PATH: &str = "shadow_analytics.bin";
+
+/// --replay-diff çıktısı
+pub const REPLAY_REPORT_PATH: &str = "replay_report.json";
+
+/// Boyut karşılaştırma toleransı (yüzde) — --size-tol-pct ile değişir
+pub const DEFAULT_SIZE_TOLERANCE_PCT: f64 = 1.0;
+
+/// Tam durum (keyframe) aralığı — boşluk sonrası toparlanma süresini sınırlar
+const KEYFRAME_INTERVAL_BLOCKS: u64 = 100;
+
+/// Konsolda listelenen en fazla anlaşmazlık (tamamı rapor dosyasında)
+const MAX_PRINTED_DIVERGENCES: usize = 20;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kayıt Formatı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek havuzun karar girdisi olan skalerleri
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolScalars {
+    pub address: Address,
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    pub liquidity: u128,
+    pub eth_price_usd: f64,
+    pub live_fee_bps: Option<u32>,
+    /// Havuzun son sync bloğu — yalnızca bayat havuzda bloktan farklıdır
+    pub last_block: u64,
+    pub is_stale: bool,
+    #[serde(default)]
+    pub quarantined: bool,
+    /// Canlı bitmap'in snapshot_block'u (bitmap yoksa None)
+    pub bitmap_block: Option<u64>,
+    /// Son sync okumasının sabitlendiği blok (None = "latest"e düşüldü)
+    #[serde(default = "legacy_pinned_block")]
+    pub pinned_block: Option<u64>,
+}
+
+/// Blok sabitleme öncesi kayıtlar: tüm havuzlar ortak bir bloğa sabitli sayılır
+fn legacy_pinned_block() -> Option<u64> {
+    Some(0)
+}
+
+impl PoolScalars {
+    pub fn capture(address: Address, state: &PoolState) -> Self {
+        Self {
+            address,
+            sqrt_price_x96: state.sqrt_price_x96,
+            tick: state.tick,
+            liquidity: state.liquidity,
+            eth_price_usd: state.eth_price_usd,
+            live_fee_bps: state.live_fee_bps,
+            last_block: state.last_block,
+            is_stale: state.is_stale,
+            quarantined: state.quarantined,
+            bitmap_block: state.tick_bitmap.as_deref().map(|bm| bm.snapshot_block),
+            pinned_block: state.pinned_block,
+        }
+    }
+
+    /// last_block ve sabitleme bloğu hariç aynı mı? (sağlıklı havuzda ikisi
+    /// de her blok değişir — yalnızca sabitli/latest ayrımı girdidir)
+    fn same_inputs(&self, other: &Self) -> bool {
+        self.pinned_block.is_some() == other.pinned_block.is_some()
+            && Self { last_block: other.last_block, pinned_block: other.pinned_block, ..self.clone() } == *other
+    }
+
+    /// Kayıttan PoolState kur. Sağlıklı havuzun last_block'u ve sabitleme bloğu replay bloğudur.
+    pub fn to_pool_state(&self, block: u64, tick_bitmap: Option<TickBitmapData>) -> PoolState {
+        PoolState {
+            sqrt_price_x96: self.sqrt_price_x96,
+            sqrt_price_f64: u256_to_f64(self.sqrt_price_x96),
+            tick: self.tick,
+            liquidity: self.liquidity,
+            liquidity_f64: liquidity_to_f64(self.liquidity),
+            eth_price_usd: self.eth_price_usd,
+            last_block: if self.is_stale { self.last_block } else { block },
+            last_update: Instant::now(),
+            is_initialized: true,
+            tick_bitmap: tick_bitmap.map(Box::new),
+            live_fee_bps: self.live_fee_bps,
+            is_stale: self.is_stale,
+            quarantined: self.quarantined,
+            pinned_block: if self.is_stale { self.pinned_block } else { self.pinned_block.map(|_| block) },
+            ..PoolState::default()
+        }
+    }
+}
+
+/// Bloğun havuz dışı karar girdileri
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlockInputs {
+    pub base_fee: u64,
+    pub l1_data_fee_wei: u128,
+    pub last_simulated_gas: Option<u64>,
+    pub competitor_txs: u32,
+}
+
+/// state_diff.jsonl satırı
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRecord {
+    pub block: u64,
+    pub base_fee: u64,
+    pub l1_data_fee_wei: u128,
+    pub last_simulated_gas: Option<u64>,
+    pub competitor_txs: u32,
+    /// true: tüm havuzlar yazıldı (keyframe)
+    #[serde(default)]
+    pub full: bool,
+    /// Degradation ladder'ın bu blokta ulaştığı basamak (0 = hiçbir aşama atılmadı)
+    #[serde(default)]
+    pub shed_rungs: u8,
+    pub pools: Vec<PoolScalars>,
+}
+
+/// Kayıt hedefi: satır başına append (JSONL) veya çerçeveli ikili log
+enum DiffSink {
+    Json(PathBuf),
+    Binary(BinaryLogWriter),
+}
+
+/// Canlı döngüde blok başına bir satır yazan kaydedici
+pub struct StateDiffRecorder {
+    sink: DiffSink,
+    last: HashMap<Address, PoolScalars>,
+    last_block: Option<u64>,
+    last_keyframe: u64,
+}
+
+impl StateDiffRecorder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self::with_sink(DiffSink::Json(path.into()))
+    }
+
+    /// İkili kaydedici (LOG_FORMAT_BINARY) — bekleyen çerçeve Drop'ta yazılır
+    pub fn binary(path: impl Into<PathBuf>) -> Self {
+        Self::with_sink(DiffSink::Binary(BinaryLogWriter::new(path)))
+    }
+
+    fn with_sink(sink: DiffSink) -> Self {
+        Self {
+            sink,
+            last: HashMap::new(),
+            last_block: None,
+            last_keyframe: 0,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match &self.sink {
+            DiffSink::Json(path) => path,
+            DiffSink::Binary(writer) => writer.path(),
+        }
+    }
+
+    /// Önceki bloğa göre değişen havuzlarla satırı üret (yazmadan)
+    pub fn build_record(
+        &mut self,
+        block: u64,
+        inputs: BlockInputs,
+        pools: &[PoolConfig],
+        states: &[SharedPoolState],
+    ) -> BlockRecord {
+        let contiguous = matches!(self.last_block, Some(prev) if block == prev + 1);
+        let full = !contiguous || block >= self.last_keyframe + KEYFRAME_INTERVAL_BLOCKS;
+
+        let mut changed = Vec::new();
+        for (pool, state) in pools.iter().zip(states) {
+            let scalars = PoolScalars::capture(pool.address, &state.load());
+            let unchanged = self
+                .last
+                .get(&pool.address)
+                .is_some_and(|prev| prev.same_inputs(&scalars));
+            if full || !unchanged {
+                changed.push(scalars.clone());
+            }
+            self.last.insert(pool.address, scalars);
+        }
+        if full {
+            self.last_keyframe = block;
+        }
+        self.last_block = Some(block);
+
+        BlockRecord {
+            block,
+            base_fee: inputs.base_fee,
+            l1_data_fee_wei: inputs.l1_data_fee_wei,
+            last_simulated_gas: inputs.last_simulated_gas,
+            competitor_txs: inputs.competitor_txs,
+            full,
+            shed_rungs: 0,
+            pools: changed,
+        }
+    }
+
+    /// Üretilmiş satırı state_diff.jsonl'e (ya da ikili loga) ekle. Canlı
+    /// döngü satırı tarama öncesi üretir, blok sonunda shed_rungs ile yazar.
+    pub fn append(&mut self, record: &BlockRecord) -> std::io::Result<()> {
+        let line = serde_json::to_string(record)?;
+        match &mut self.sink {
+            DiffSink::Json(path) => {
+                let mut file = crate::secure_fs::open_append(path)?;
+                writeln!(file, "{}", line)
+            }
+            DiffSink::Binary(writer) => writer.append_json(&line),
+        }
+    }
+}
+
+/// state_diff oku (JSONL veya ikili) — bozuk satırlar / çerçeveler atlanır
+/// ve sayılır
+pub fn read_state_diff(path: &Path) -> Result<(Vec<BlockRecord>, usize)> {
+    let log = read_json_lines(path)?;
+    let mut records = Vec::new();
+    let mut bad_lines = log.skipped;
+    for line in &log.lines {
+        match serde_json::from_str::<BlockRecord>(line) {
+            Ok(record) => records.push(record),
+            Err(_) => bad_lines += 1,
+        }
+    }
+    Ok((records, bad_lines))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Yeniden Kurulum
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Snapshot arşivinden bitmap'ler — (havuz, snapshot_block) anahtarı
+pub type BitmapArchive = HashMap<(Address, u64), TickBitmapData>;
+
+/// Satırları sırayla uygulayarak bilinen havuz skalerlerini taşır.
+/// Boşluktan sonra (keyframe değilse) bilinenler silinir: arada değişen
+/// havuzların durumu bilinmez.
+#[derive(Default)]
+pub struct Reconstructor {
+    known: HashMap<Address, PoolScalars>,
+    prev_block: Option<u64>,
+}
+
+impl Reconstructor {
+    pub fn apply(&mut self, record: &BlockRecord) {
+        let contiguous = matches!(self.prev_block, Some(prev) if record.block == prev + 1);
+        if record.full || !contiguous {
+            self.known.clear();
+        }
+        for scalars in &record.pools {
+            self.known.insert(scalars.address, scalars.clone());
+        }
+        self.prev_block = Some(record.block);
+    }
+
+    /// Havuzun bu bloktaki durumu. İkinci değer: kayıtta bitmap vardı ama
+    /// arşivde bulunamadı (tek-tick fallback ile hesaplanır).
+    pub fn state(&self, address: Address, block: u64, archive: &BitmapArchive) -> Option<(PoolState, bool)> {
+        let scalars = self.known.get(&address)?;
+        let bitmap = scalars
+            .bitmap_block
+            .and_then(|b| archive.get(&(address, b)).cloned());
+        let fallback = scalars.bitmap_block.is_some() && bitmap.is_none();
+        Some((scalars.to_pool_state(block, bitmap), fallback))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kararlar + Karşılaştırma
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Canlı ve replay kararları için ortak anahtar: "<blok>-<alış havuzu>-<satış havuzu>"
+/// (adreslerin ilk 4 baytı). Blok = çiftin en yeni last_block'u.
+pub fn opportunity_id(block: u64, buy_pool: Address, sell_pool: Address) -> String {
+    format!(
+        "{}-{}-{}",
+        block,
+        hex::encode(&buy_pool.as_slice()[..4]),
+        hex::encode(&sell_pool.as_slice()[..4])
+    )
+}
+
+/// Tek fırsat kararı (orijinal oturum veya replay)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Decision {
+    pub block: u64,
+    pub opportunity_id: String,
+    pub buy_pool: Address,
+    pub sell_pool: Address,
+    pub amount_weth: f64,
+    pub profit_weth: f64,
+    /// Yürütmeye gider mi (gölge modda: simüle edilip loglanır mı)
+    pub go: bool,
+    /// no-go nedeni olan filtre (varsa)
+    pub filter: Option<String>,
+    /// Exact doğrulama kârı (owedToken, wei) — gölge logda yoktur
+    pub exact_profit_wei: Option<U256>,
+}
+
+impl Decision {
+    /// Replay kararı: filtre degrade etmediyse ve exact kâr pozitifse go
+    fn replayed(pools: &[PoolConfig; 2], block: u64, opp: &ArbitrageOpportunity, exact: U256) -> Self {
+        let buy_pool = pools[opp.buy_pool_idx].address;
+        let sell_pool = pools[opp.sell_pool_idx].address;
+        Self {
+            block,
+            opportunity_id: opportunity_id(block, buy_pool, sell_pool),
+            buy_pool,
+            sell_pool,
+            amount_weth: opp.optimal_amount_weth,
+            profit_weth: opp.expected_profit_weth,
+            go: opp.shadow_only.is_none() && !exact.is_zero(),
+            filter: opp.shadow_only.as_ref().map(|v| v.filter.to_string()),
+            exact_profit_wei: Some(exact),
+        }
+    }
+}
+
+/// shadow_analytics.jsonl satırından karar (block/opportunity_id olmayan eski satırlar → None)
+fn decision_from_shadow_line(line: &str) -> Option<Decision> {
+    let v: serde_json::Value = serde_json::from_str(line).ok()?;
+    let mode = v.get("mode")?.as_str()?;
+    Some(Decision {
+        block: v.get("block")?.as_u64()?,
+        opportunity_id: v.get("opportunity_id")?.as_str()?.to_string(),
+        buy_pool: v.get("buy_pool_addr")?.as_str()?.parse().ok()?,
+        sell_pool: v.get("sell_pool_addr")?.as_str()?.parse().ok()?,
+        amount_weth: v.get("optimal_amount_weth")?.as_f64()?,
+        profit_weth: v.get("expected_profit")?.as_f64()?,
+        go: mode == "shadow" || mode == "live",
+        filter: v.get("filter").and_then(|f| f.as_str()).map(String::from),
+        exact_profit_wei: None,
+    })
+}
+
+/// Orijinal oturumun gölge log kararları — JSONL veya ikili (dosya yoksa boş)
+pub fn load_shadow_decisions(path: &Path) -> Vec<Decision> {
+    read_json_lines(path)
+        .map(|log| {
+            log.lines
+                .iter()
+                .map(String::as_str)
+                .filter_map(decision_from_shadow_line)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Snapshot arşivi: bitmap'ler + live modda yürütülen fırsatlar
+pub fn load_snapshot_archive(dir: &Path) -> (BitmapArchive, Vec<Decision>) {
+    let mut bitmaps = BitmapArchive::new();
+    let mut live = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (bitmaps, live);
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(id) = name.strip_suffix(".json.gz") else {
+            continue;
+        };
+        let Ok(snapshot) = crate::snapshot::read_snapshot(dir, id) else {
+            continue;
+        };
+        for pool in &snapshot.pools {
+            if let Some(ref bm) = pool.tick_bitmap {
+                bitmaps
+                    .entry((pool.address, bm.snapshot_block))
+                    .or_insert_with(|| bm.clone());
+            }
+        }
+        if snapshot.mode == "live" && snapshot.pools.len() == 2 {
+            let buy_pool = snapshot.pools[snapshot.buy_pool_idx].address;
+            let sell_pool = snapshot.pools[snapshot.sell_pool_idx].address;
+            live.push(Decision {
+                block: snapshot.block,
+                opportunity_id: opportunity_id(snapshot.block, buy_pool, sell_pool),
+                buy_pool,
+                sell_pool,
+                amount_weth: snapshot.nr.optimal_amount_weth,
+                profit_weth: snapshot.nr.expected_profit_weth,
+                go: true,
+                filter: None,
+                exact_profit_wei: Some(snapshot.exact.profit_wei),
+            });
+        }
+    }
+    (bitmaps, live)
+}
+
+/// Karar anlaşmazlığı — (block, opportunity_id) karar içinde taşınır
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Divergence {
+    /// Canlı bot buldu, replay bulmadı
+    MissingInReplay { recorded: Decision },
+    /// Replay buldu, canlı bot kaydetmedi
+    NewInReplay { replayed: Decision },
+    /// İkisi de buldu, boyut toleransın dışında
+    SizeMismatch { recorded: Decision, replayed: Decision, diff_pct: f64 },
+    /// İkisi de buldu, go/no-go farklı
+    GoNoGo { recorded: Decision, replayed: Decision },
+}
+
+impl Divergence {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Divergence::MissingInReplay { .. } => "missing_in_replay",
+            Divergence::NewInReplay { .. } => "new_in_replay",
+            Divergence::SizeMismatch { .. } => "size_mismatch",
+            Divergence::GoNoGo { .. } => "go_no_go",
+        }
+    }
+
+    pub fn opportunity_id(&self) -> &str {
+        match self {
+            Divergence::MissingInReplay { recorded: d }
+            | Divergence::NewInReplay { replayed: d }
+            | Divergence::SizeMismatch { recorded: d, .. }
+            | Divergence::GoNoGo { recorded: d, .. } => &d.opportunity_id,
+        }
+    }
+}
+
+/// Aynı bloğun kayıtlı ve replay kararlarını karşılaştır.
+/// Dönüş: (eşleşen karar sayısı, anlaşmazlıklar)
+pub fn compare_block(
+    recorded: &[Decision],
+    replayed: &[Decision],
+    tolerance_pct: f64,
+) -> (u64, Vec<Divergence>) {
+    let mut matched = 0;
+    let mut divergences = Vec::new();
+
+    for r in recorded {
+        let Some(p) = replayed.iter().find(|p| p.opportunity_id == r.opportunity_id) else {
+            divergences.push(Divergence::MissingInReplay { recorded: r.clone() });
+            continue;
+        };
+        let diff_pct = (p.amount_weth - r.amount_weth).abs() / r.amount_weth.abs().max(1e-18) * 100.0;
+        if r.go != p.go {
+            divergences.push(Divergence::GoNoGo { recorded: r.clone(), replayed: p.clone() });
+        } else if diff_pct > tolerance_pct {
+            divergences.push(Divergence::SizeMismatch {
+                recorded: r.clone(),
+                replayed: p.clone(),
+                diff_pct,
+            });
+        } else {
+            matched += 1;
+        }
+    }
+    for p in replayed {
+        if !recorded.iter().any(|r| r.opportunity_id == p.opportunity_id) {
+            divergences.push(Divergence::NewInReplay { replayed: p.clone() });
+        }
+    }
+    (matched, divergences)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Replay Motoru
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Orijinal oturumdan okunan her şey
+#[derive(Default)]
+pub struct RecordedSession {
+    pub records: Vec<BlockRecord>,
+    pub bitmaps: BitmapArchive,
+    pub decisions: Vec<Decision>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplayReport {
+    pub blocks_total: u64,
+    pub blocks_replayed: u64,
+    /// Keyframe öncesi / boşluk sonrası — combo havuzlarından biri bilinmiyor
+    pub blocks_skipped_incomplete: u64,
+    /// Kayıtta bitmap vardı, arşivde yok → tek-tick fallback (havuz × blok)
+    pub fallback_bitmaps: u64,
+    pub replay_opportunities: u64,
+    /// Replay edilen bloklardaki kayıtlı kararlar
+    pub recorded_compared: u64,
+    pub matched: u64,
+    pub divergences: Vec<Divergence>,
+}
+
+impl ReplayReport {
+    /// Türe göre anlaşmazlık sayıları (sıralı — rapor çıktısı sabit)
+    pub fn divergence_counts(&self) -> BTreeMap<&'static str, u64> {
+        let mut counts = BTreeMap::new();
+        for d in &self.divergences {
+            *counts.entry(d.kind()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// Kayıtlı oturumu mevcut kod + `config` ile blok blok yeniden oynat.
+pub fn replay_session(
+    session: &RecordedSession,
+    pools: &[PoolConfig],
+    pair_combos: &[PairCombo],
+    config: &BotConfig,
+    tolerance_pct: f64,
+) -> ReplayReport {
+    let filters = FilterChain::from_config(config);
+    let mut recorded_by_block: HashMap<u64, Vec<Decision>> = HashMap::new();
+    for d in &session.decisions {
+        recorded_by_block.entry(d.block).or_default().push(d.clone());
+    }
+
+    let mut report = ReplayReport::default();
+    let mut recon = Reconstructor::default();
+    let mut replayed_blocks: Vec<u64> = Vec::new();
+
+    for record in &session.records {
+        recon.apply(record);
+        report.blocks_total += 1;
+
+        let mut states: HashMap<usize, SharedPoolState> = HashMap::new();
+        let mut complete = true;
+        let mut fallbacks = 0;
+        for combo in pair_combos {
+            for idx in [combo.pool_a_idx, combo.pool_b_idx] {
+                if states.contains_key(&idx) {
+                    continue;
+                }
+                match recon.state(pools[idx].address, record.block, &session.bitmaps) {
+                    Some((state, fallback)) => {
+                        fallbacks += u64::from(fallback);
+                        states.insert(idx, Arc::new(ArcSwap::from_pointee(state)));
+                    }
+                    None => complete = false,
+                }
+            }
+        }
+        if !complete {
+            report.blocks_skipped_incomplete += 1;
+            continue;
+        }
+        report.blocks_replayed += 1;
+        report.fallback_bitmaps += fallbacks;
+        replayed_blocks.push(record.block);
+
+        let competition = CompetitionSnapshot {
+            block_number: record.block,
+            competitor_tx_last_block: record.competitor_txs,
+        };
+        let mut replayed: Vec<Decision> = Vec::new();
+        for combo in pair_combos {
+            let pp = [pools[combo.pool_a_idx].clone(), pools[combo.pool_b_idx].clone()];
+            let ps = [states[&combo.pool_a_idx].clone(), states[&combo.pool_b_idx].clone()];
+            let Some(opportunity) = check_arbitrage_opportunity(
+                &pp,
+                &ps,
+                config,
+                record.base_fee,
+                record.last_simulated_gas,
+                record.l1_data_fee_wei,
+                &competition,
+                &filters,
+                None,
+            ) else {
+                continue;
+            };
+            let (state_a, state_b) = (ps[0].load(), ps[1].load());
+            let exact = exact_profit_for_opportunity(&pp, &state_a, &state_b, &opportunity);
+            let block = state_a.last_block.max(state_b.last_block);
+            replayed.push(Decision::replayed(&pp, block, &opportunity, exact));
+        }
+        report.replay_opportunities += replayed.len() as u64;
+
+        let recorded = recorded_by_block.remove(&record.block).unwrap_or_default();
+        report.recorded_compared += recorded.len() as u64;
+        let (matched, divergences) = compare_block(&recorded, &replayed, tolerance_pct);
+        report.matched += matched;
+        report.divergences.extend(divergences);
+    }
+    report
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CLI: --replay-diff
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Kayıtlı oturumu yeniden oynat, anlaşmazlık raporunu yazdır ve kaydet.
+pub fn cli_replay_diff(
+    path: &str,
+    config: &BotConfig,
+    pools: &[PoolConfig],
+    pair_combos: &[PairCombo],
+    tolerance_pct: f64,
+) -> Result<()> {
+    let (records, bad_lines) = read_state_diff(Path::new(path))?;
+    let snapshot_dir = crate::secure_fs::read_path(crate::snapshot::SNAPSHOT_DIR);
+    let (bitmaps, mut decisions) = load_snapshot_archive(&snapshot_dir);
+    let live_decisions = decisions.len();
+    for shadow_log in [SHADOW_LOG_PATH, SHADOW_BIN_PATH] {
+        decisions.extend(load_shadow_decisions(&crate::secure_fs::read_path(shadow_log)));
+    }
+
+    println!(
+        "  {} Replaying {} block(s) from {} ({} unreadable line(s)) — {} pools, {} combos",
+        "⏪".cyan(),
+        records.len(),
+        path,
+        bad_lines,
+        pools.len(),
+        pair_combos.len(),
+    );
+    println!(
+        "  {} Recorded decisions: {} ({} from {}, {} live from {}/) | archived bitmaps: {}",
+        "📼".cyan(),
+        decisions.len(),
+        decisions.len() - live_decisions,
+        SHADOW_LOG_PATH,
+        live_decisions,
+        snapshot_dir.display(),
+        bitmaps.len(),
+    );
+
+    crate::fee_monitor::init(config);
+    let session = RecordedSession { records, bitmaps, decisions };
+    let report = replay_session(&session, pools, pair_combos, config, tolerance_pct);
+
+    println!();
+    println!(
+        "  Blocks            : {} total | {} replayed | {} skipped (incomplete inputs)",
+        report.blocks_total, report.blocks_replayed, report.blocks_skipped_incomplete,
+    );
+    println!("  Fallback bitmaps  : {} (pool × block, not in snapshot archive)", report.fallback_bitmaps);
+    println!(
+        "  Decisions         : {} replayed | {} recorded compared | {} matched (size tol {}%)",
+        report.replay_opportunities, report.recorded_compared, report.matched, tolerance_pct,
+    );
+    for (kind, count) in report.divergence_counts() {
+        println!("  {:<18}: {}", kind, count);
+    }
+    for d in report.divergences.iter().take(MAX_PRINTED_DIVERGENCES) {
+        println!("    {} {}", d.kind().yellow(), d.opportunity_id());
+    }
+
+    let json = serde_json::to_vec_pretty(&report)?;
+    let report_path = crate::secure_fs::data_path(REPLAY_REPORT_PATH);
+    crate::session_summary::write_atomic(&report_path, &json)?;
+    println!();
+    if report.divergences.is_empty() {
+        println!("  {} No divergences — report: {}", "✅".green(), report_path.display());
+    } else {
+        println!(
+            "  {} {} divergence(s) — full report: {}",
+            "⚠️".yellow(),
+            report.divergences.len(),
+            report_path.display(),
+        );
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::gas_spike_tests::{make_pool_configs, make_pool_state, make_test_config};
+
+    /// Blok başına (A fiyatı, B fiyatı) — spread dardan genişe
+    const SESSION_PRICES: [(f64, f64); 6] = [
+        (2500.0, 2500.25),
+        (2490.0, 2500.0),
+        (2475.0, 2500.0),
+        (2460.0, 2500.0),
+        (2450.0, 2500.0),
+        (2450.0, 2500.0),
+    ];
+    const FIRST_BLOCK: u64 = 1_000;
+    const LIQ: u128 = 50_000_000_000_000_000_000;
+
+    fn combos() -> Vec<PairCombo> {
+        vec![PairCombo { pair_name: "WETH/USDC".into(), pool_a_idx: 0, pool_b_idx: 1 }]
+    }
+
+    fn inputs() -> BlockInputs {
+        BlockInputs {
+            base_fee: 500_000_000_000,
+            l1_data_fee_wei: 0,
+            last_simulated_gas: Some(150_000),
+            competitor_txs: 0,
+        }
+    }
+
+    /// Sentetik canlı oturum: her blok kaydediciden geçer
+    fn recorded_session(pools: &[PoolConfig]) -> Vec<BlockRecord> {
+        let mut recorder = StateDiffRecorder::new("unused.jsonl");
+        SESSION_PRICES
+            .iter()
+            .enumerate()
+            .map(|(i, &(a, b))| {
+                let block = FIRST_BLOCK + i as u64;
+                let states = vec![make_pool_state(a, LIQ, block), make_pool_state(b, LIQ, block)];
+                recorder.build_record(block, inputs(), pools, &states)
+            })
+            .collect()
+    }
+
+    /// Kâr eşiğini yalnızca MIN_NET_PROFIT_WETH belirlesin (ROI yolu kapalı).
+    /// PreFilter probe'u NR boyutuna çekilir ve rüşvetsizdir — yoksa probe
+    /// tahmini optimum kârın altında kalır ve eşiği geçen fırsatları da eler.
+    fn config_with_min_profit(min_profit: f64) -> BotConfig {
+        BotConfig {
+            min_profit_roi: f64::MAX,
+            max_trade_size_weth: 100.0,
+            bribe_pct: 0.0,
+            ..make_test_config(min_profit, 0.00005)
+        }
+    }
+
+    fn decision(id: &str, amount: f64, go: bool) -> Decision {
+        Decision {
+            block: 7,
+            opportunity_id: id.into(),
+            buy_pool: Address::ZERO,
+            sell_pool: Address::ZERO,
+            amount_weth: amount,
+            profit_weth: 0.001,
+            go,
+            filter: None,
+            exact_profit_wei: None,
+        }
+    }
+
+    #[test]
+    fn test_recorder_writes_changed_pools_and_keyframes() {
+        let pools = make_pool_configs();
+        let records = recorded_session(&pools);
+        // İlk satır keyframe: iki havuz da
+        assert!(records[0].full);
+        assert_eq!(records[0].pools.len(), 2);
+        // Blok 2: yalnızca A'nın fiyatı değişti → yalnızca A yazılır
+        assert!(!records[2].full);
+        assert_eq!(records[2].pools.len(), 1);
+        assert_eq!(records[2].pools[0].address, pools[0].address);
+        // Son blok: fiyatlar aynı → yalnızca last_block değişti → satır boş
+        assert!(records[5].pools.is_empty());
+        assert_eq!(records[5].block, FIRST_BLOCK + 5);
+
+        // Boşluk → yeni keyframe
+        let mut recorder = StateDiffRecorder::new("unused.jsonl");
+        let states = vec![make_pool_state(2500.0, LIQ, 1), make_pool_state(2501.0, LIQ, 1)];
+        assert!(recorder.build_record(1, inputs(), &pools, &states).full);
+        assert!(!recorder.build_record(2, inputs(), &pools, &states).full);
+        let after_gap = recorder.build_record(5, inputs(), &pools, &states);
+        assert!(after_gap.full);
+        assert_eq!(after_gap.pools.len(), 2);
+
+        // Satır JSON üzerinden kayıpsız döner
+        let line = serde_json::to_string(&records[0]).unwrap();
+        assert_eq!(serde_json::from_str::<BlockRecord>(&line).unwrap(), records[0]);
+
+        // Atılan basamak satırla taşınır; alan öncesi yazılmış satırlar 0 okunur
+        let shed = BlockRecord { shed_rungs: 3, ..records[2].clone() };
+        let line = serde_json::to_string(&shed).unwrap();
+        assert_eq!(serde_json::from_str::<BlockRecord>(&line).unwrap().shed_rungs, 3);
+        let legacy = line.replace(",\"shed_rungs\":3", "");
+        assert_ne!(legacy, line);
+        assert_eq!(serde_json::from_str::<BlockRecord>(&legacy).unwrap().shed_rungs, 0);
+    }
+
+    #[test]
+    fn test_incomplete_blocks_are_skipped_and_counted() {
+        let pools = make_pool_configs();
+        let mut records = recorded_session(&pools);
+        // Oturum keyframe'siz başlasın (yalnızca A bilinir) ve blok 3 kaybolsun
+        records[0].full = false;
+        records[0].pools.retain(|p| p.address == pools[0].address);
+        records.remove(3);
+
+        let session = RecordedSession { records, ..Default::default() };
+        let report = replay_session(&session, &pools, &combos(), &config_with_min_profit(0.000002), 1.0);
+        assert_eq!(report.blocks_total, 5);
+        // Blok 0: B bilinmiyor. Blok 1-2: B blok 1'de değişti → tam.
+        // Blok 4: boşluk → yalnızca değişen A bilinir. Blok 5: boş satır, B hâlâ yok.
+        assert_eq!(report.blocks_skipped_incomplete, 3);
+        assert_eq!(report.blocks_replayed, 2);
+    }
+
+    #[test]
+    fn test_threshold_change_produces_known_divergences() {
+        let pools = make_pool_configs();
+        let combos = combos();
+        let records = recorded_session(&pools);
+
+        // "Canlı" oturum kararları = baz config ile replay çıktısı
+        let baseline = config_with_min_profit(0.000002);
+        let live = replay_session(
+            &RecordedSession { records: records.clone(), ..Default::default() },
+            &pools,
+            &combos,
+            &baseline,
+            1.0,
+        );
+        let recorded: Vec<Decision> = live
+            .divergences
+            .iter()
+            .filter_map(|d| match d {
+                Divergence::NewInReplay { replayed } => Some(replayed.clone()),
+                _ => None,
+            })
+            .collect();
+        assert!(recorded.len() >= 2, "fixture should produce opportunities: {:?}", recorded);
+
+        let session = RecordedSession { records, decisions: recorded.clone(), ..Default::default() };
+
+        // Aynı kod + aynı config → birebir aynı kararlar
+        let same = replay_session(&session, &pools, &combos, &baseline, 1.0);
+        assert!(same.divergences.is_empty(), "{:?}", same.divergences);
+        assert_eq!(same.matched, recorded.len() as u64);
+
+        // Eşik en küçük iki kârın arasına çekilir → altında kalanlar kaybolur
+        let mut profits: Vec<f64> = recorded.iter().map(|d| d.profit_weth).collect();
+        profits.sort_by(|a, b| a.total_cmp(b));
+        profits.dedup();
+        assert!(profits.len() >= 2, "fixture needs distinct profits: {:?}", profits);
+        let threshold = (profits[0] + profits[1]) / 2.0;
+        let mut expected: Vec<String> = recorded
+            .iter()
+            .filter(|d| d.profit_weth < threshold)
+            .map(|d| d.opportunity_id.clone())
+            .collect();
+
+        let changed = replay_session(&session, &pools, &combos, &config_with_min_profit(threshold), 1.0);
+        let mut missing: Vec<String> = changed
+            .divergences
+            .iter()
+            .map(|d| {
+                assert_eq!(d.kind(), "missing_in_replay", "unexpected divergence {:?}", d);
+                d.opportunity_id().to_string()
+            })
+            .collect();
+        expected.sort();
+        missing.sort();
+        assert_eq!(missing, expected);
+        assert_eq!(changed.matched, (recorded.len() - expected.len()) as u64);
+    }
+
+    #[test]
+    fn test_compare_block_classifies_divergences() {
+        let recorded = vec![
+            decision("a", 1.0, true),
+            decision("b", 1.0, true),
+            decision("c", 1.0, true),
+            decision("d", 1.0, true),
+        ];
+        let replayed = vec![
+            decision("a", 1.005, true), // tolerans içinde
+            decision("b", 1.2, true),   // %20 boyut farkı
+            decision("c", 1.0, false),  // go → no-go
+            decision("e", 0.5, true),   // yeni
+        ];
+        let (matched, divergences) = compare_block(&recorded, &replayed, 1.0);
+        assert_eq!(matched, 1);
+        let kinds: Vec<(&str, &str)> = divergences.iter().map(|d| (d.kind(), d.opportunity_id())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("size_mismatch", "b"),
+                ("go_no_go", "c"),
+                ("missing_in_replay", "d"),
+                ("new_in_replay", "e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shadow_log_line_parsing() {
+        let buy = pools_addr(0);
+        let sell = pools_addr(1);
+        let id = opportunity_id(1234, buy, sell);
+        assert_eq!(id, "1234-d0b53d92-cdac0d6c");
+
+        let line = serde_json::json!({
+            "block": 1234,
+            "opportunity_id": id,
+            "buy_pool_addr": buy.to_string(),
+            "sell_pool_addr": sell.to_string(),
+            "optimal_amount_weth": 0.5,
+            "expected_profit": 0.0012,
+            "mode": "competition-skipped",
+            "filter": "competition",
+        })
+        .to_string();
+        let d = decision_from_shadow_line(&line).expect("parsable");
+        assert_eq!((d.block, d.buy_pool, d.sell_pool), (1234, buy, sell));
+        assert!(!d.go);
+        assert_eq!(d.filter.as_deref(), Some("competition"));
+
+        // block alanı olmayan eski satırlar karşılaştırmaya girmez
+        assert!(decision_from_shadow_line(r#"{"mode":"shadow","expected_profit":0.1}"#).is_none());
+    }
+
+    fn pools_addr(i: usize) -> Address {
+        make_pool_configs()[i].address
+    }
+}
@@ -0,0 +1,832 @@
+// ============================================================================
+//  REVISIT v1.0 — Sessiz Dönemde Gölge Fırsatların Yeniden Değerlendirmesi
+//
+//  Gölge loglar gerçek zaman kısıtı altında yapılmış f64 / exact tahminleri
+//  kaydeder. Bu modül aynı fırsatlara zaman baskısı olmadan döner ve hızlı
+//  yolun masada ne bıraktığını / ne kadar fazla vaat ettiğini ölçer:
+//
+//  ✓ Günün en kârlı REVISIT_TOP_K gölge fırsatı girdileriyle (havuz
+//    yapılandırması + state Arc'ları) bellekte tutulur; gün dönünce sıfırlanır
+//  ✓ Ana döngü her blokta fırsat görüp görmediğini bildirir; REVISIT_QUIET_BLOCKS
+//    ardışık fırsatsız bloktan sonra her sessiz blokta EN FAZLA bir kayıt işlenir
+//  ✓ Yüksek hassasiyet: havuz bytecode'uyla REVM quote (yoksa exact matematik),
+//    snapshot bitmap'inin taranmamış kısmı canlı bitmap'ten genişletilir,
+//    MIN..MAX_TRADE_SIZE_WETH aralığında geniş probe bütçeli arama
+//  ✓ Sıkı süre: REVISIT_BUDGET_MS dolunca en iyi ara sonuç yazılır (complete=false)
+//  ✓ Piyasa canlanınca (fırsat görülünce) işlem bir sonraki probe'da bırakılır,
+//    kayıt deftere geri döner
+//  ✓ revisits.json (satır başına bir kayıt) + stats kutusunda toplam sapma
+// ============================================================================
+
+use colored::*;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::console::eprintln_high;
+use crate::math;
+use crate::simulator::SimulationEngine;
+use crate::types::{ArbitrageOpportunity, BotConfig, PoolConfig, PoolState, SharedPoolState, TickBitmapData};
+
+/// Yeniden değerlendirme kayıtlarının yazıldığı dosya (JSON satırları)
+pub const REVISITS_PATH: &str = "revisits.json";
+
+/// Karesel ızgara nokta sayısı (hızlı yol 6–24 nokta tarar)
+const GRID_POINTS: u32 = 96;
+
+/// Izgaradaki en iyi noktanın komşuluğunda altın oran iterasyonu
+const GOLDEN_ITERATIONS: u32 = 60;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Aday ve Gün Defteri (top-K)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Yeniden değerlendirilecek gölge fırsat — karar anındaki tam girdiler
+#[derive(Debug, Clone)]
+pub struct RevisitCandidate {
+    /// Snapshot id'si (arşiv kapalıysa aynı biçimde üretilir)
+    pub id: String,
+    pub block: u64,
+    pub opportunity: ArbitrageOpportunity,
+    pub pools: [PoolConfig; 2],
+    pub states: [Arc<PoolState>; 2],
+}
+
+impl RevisitCandidate {
+    fn rank(&self) -> f64 {
+        self.opportunity.expected_profit_weth
+    }
+
+    fn pair_label(&self) -> String {
+        format!(
+            "{} → {}",
+            self.pools[self.opportunity.buy_pool_idx].name, self.pools[self.opportunity.sell_pool_idx].name,
+        )
+    }
+}
+
+/// Günün beklenen kâra göre en iyi `capacity` adayı
+#[derive(Debug)]
+struct TopK {
+    capacity: usize,
+    day: Option<chrono::NaiveDate>,
+    /// Kâra göre azalan sırada (K küçük — sıralı ekleme yeterli)
+    entries: Vec<RevisitCandidate>,
+}
+
+impl TopK {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, day: None, entries: Vec::new() }
+    }
+
+    /// Adayı günün listesine sok; listeye girdiyse true
+    fn offer(&mut self, candidate: RevisitCandidate, day: chrono::NaiveDate) -> bool {
+        if self.day != Some(day) {
+            self.day = Some(day);
+            self.entries.clear();
+        }
+        self.insert(candidate)
+    }
+
+    /// Kesintiye uğrayan aday geri döner (gün dönmüşse düşer)
+    fn requeue(&mut self, candidate: RevisitCandidate) {
+        self.insert(candidate);
+    }
+
+    fn insert(&mut self, candidate: RevisitCandidate) -> bool {
+        if !candidate.rank().is_finite() || self.capacity == 0 {
+            return false;
+        }
+        if self.entries.len() >= self.capacity
+            && self.entries.last().is_some_and(|worst| worst.rank() >= candidate.rank())
+        {
+            return false;
+        }
+        let pos = self.entries.partition_point(|e| e.rank() >= candidate.rank());
+        self.entries.insert(pos, candidate);
+        self.entries.truncate(self.capacity);
+        true
+    }
+
+    fn pop_best(&mut self) -> Option<RevisitCandidate> {
+        (!self.entries.is_empty()).then(|| self.entries.remove(0))
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sessizlik Tespiti
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Ardışık fırsatsız blok sayacı
+#[derive(Debug, Clone, Copy)]
+pub struct QuietTracker {
+    threshold: u64,
+    streak: u64,
+}
+
+impl QuietTracker {
+    pub fn new(threshold: u64) -> Self {
+        Self { threshold: threshold.max(1), streak: 0 }
+    }
+
+    /// Bloğu kaydet; `threshold` ardışık fırsatsız bloğa ulaşıldıysa true
+    pub fn observe(&mut self, opportunity_seen: bool) -> bool {
+        self.streak = if opportunity_seen { 0 } else { self.streak.saturating_add(1) };
+        self.streak >= self.threshold
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Bitmap Genişletme
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Snapshot bitmap'inin taranan aralığı korunur; canlı bitmap'in bu aralık
+/// DIŞINDAKİ tick'leri eklenir (uzak tick'ler sessiz dönemde nadiren değişir).
+/// Dönüş: genişletilmiş bitmap + eklenen tick sayısı. Snapshot taranan aralık
+/// taşımıyorsa genişletme yapılmaz.
+pub fn widen_bitmap(snapshot: &TickBitmapData, live: &TickBitmapData) -> (TickBitmapData, usize) {
+    let mut widened = snapshot.clone();
+    let Some((lo, hi)) = snapshot.scanned_ticks else {
+        return (widened, 0);
+    };
+    let mut added = 0;
+    for (&tick, info) in &live.ticks {
+        if !(lo..=hi).contains(&tick) && !widened.ticks.contains_key(&tick) {
+            widened.ticks.insert(tick, *info);
+            added += 1;
+        }
+    }
+    for (&word_pos, &word) in &live.words {
+        widened.words.entry(word_pos).or_insert(word);
+    }
+    if let Some((live_lo, live_hi)) = live.scanned_ticks {
+        widened.scanned_ticks = Some((lo.min(live_lo), hi.max(live_hi)));
+    }
+    (widened, added)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Bütçeli Yeniden Değerlendirme
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Yeniden değerlendirme kaydı — revisits.json satırı
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RevisitRecord {
+    pub ts: String,
+    pub id: String,
+    pub block: u64,
+    pub pair: String,
+    /// "revm" (havuz bytecode'u) | "exact" (bytecode yok)
+    pub engine: &'static str,
+    pub original_amount_weth: f64,
+    /// Hızlı yolun gölge loga yazdığı net kâr
+    pub original_profit_weth: f64,
+    /// Hızlı yolun boyutunda yüksek hassasiyetli net kâr (None: bacak boş)
+    pub original_amount_refined_profit_weth: Option<f64>,
+    pub refined_amount_weth: f64,
+    pub refined_profit_weth: f64,
+    /// refined − original: artı → hızlı yol masada bıraktı, eksi → fazla vaat etti
+    pub discrepancy_weth: f64,
+    /// Canlı bitmap'ten eklenen tick sayısı (iki bacak)
+    pub widened_ticks: usize,
+    pub probes: u32,
+    pub elapsed_ms: f64,
+    /// false: REVISIT_BUDGET_MS doldu, en iyi ara sonuç
+    pub complete: bool,
+}
+
+/// Yeniden değerlendirmenin sonucu
+#[derive(Debug)]
+pub enum RefineOutcome {
+    Done(RevisitRecord),
+    /// Piyasa canlandı — aday işlenmeden geri döner
+    Preempted(Box<RevisitCandidate>),
+}
+
+/// Probe kapısı: her probe öncesi kesinti ve süre sorulur
+struct Gate<'a> {
+    deadline: Instant,
+    yield_now: &'a dyn Fn() -> bool,
+    probes: u32,
+    exhausted: bool,
+}
+
+enum Halt {
+    Preempted,
+    Exhausted,
+}
+
+impl Gate<'_> {
+    fn admit(&mut self) -> Result<(), Halt> {
+        if (self.yield_now)() {
+            return Err(Halt::Preempted);
+        }
+        if Instant::now() >= self.deadline {
+            self.exhausted = true;
+            return Err(Halt::Exhausted);
+        }
+        self.probes += 1;
+        Ok(())
+    }
+}
+
+/// Adayı yüksek hassasiyetle yeniden değerlendir.
+///
+/// `live`: aynı havuzların şimdiki state'i (bitmap genişletmesi için).
+/// `engine` havuz bytecode'larını taşıyorsa kâr REVM quote'larıyla, yoksa
+/// genişletilmiş bitmap üzerinde exact matematikle ölçülür. İlk probe hızlı
+/// yolun boyutudur (süreden bağımsız); sonra [min, max] aralığında karesel
+/// ızgara ve en iyi noktanın komşuluğunda altın oran araması — her probe
+/// öncesi `yield_now` ve `deadline` sorulur.
+pub fn refine(
+    candidate: RevisitCandidate,
+    live: [Option<&TickBitmapData>; 2],
+    engine: Option<&SimulationEngine>,
+    (min_amount, max_amount): (f64, f64),
+    deadline: Instant,
+    yield_now: &dyn Fn() -> bool,
+) -> RefineOutcome {
+    let started = Instant::now();
+    let opp = &candidate.opportunity;
+    let (sell_idx, buy_idx) = (opp.sell_pool_idx, opp.buy_pool_idx);
+
+    // ── Genişletilmiş bitmap'li state kopyaları ──
+    let mut widened_ticks = 0;
+    let states: [PoolState; 2] = std::array::from_fn(|i| {
+        let mut state = (*candidate.states[i]).clone();
+        if let (Some(snapshot), Some(live)) = (state.tick_bitmap.as_deref(), live[i]) {
+            let (widened, added) = widen_bitmap(snapshot, live);
+            widened_ticks += added;
+            state.tick_bitmap = Some(Box::new(widened));
+        }
+        state
+    });
+    let (sell_pool, buy_pool) = (&candidate.pools[sell_idx], &candidate.pools[buy_idx]);
+    let (sell_state, buy_state) = (&states[sell_idx], &states[buy_idx]);
+
+    // ── Brüt kâr (WETH): REVM (bytecode varsa) ya da exact matematik ──
+    let revm = engine.filter(|e| e.can_quote(sell_pool) && e.can_quote(buy_pool));
+    let sorted = |state: &PoolState| match state.tick_bitmap.as_deref() {
+        Some(bm) => math::exact::SortedTicks::from_bitmap(bm, state.tick),
+        None => math::exact::SortedTicks::empty(),
+    };
+    let (sell_ticks, buy_ticks) = (sorted(sell_state), sorted(buy_state));
+    let sell_fee = sell_pool.effective_fee(sell_state.live_fee_bps).fraction();
+    let buy_fee = buy_pool.effective_fee(buy_state.live_fee_bps).fraction();
+    let gross = |amount: f64| -> Option<f64> {
+        let profit = match revm {
+            Some(engine) => crate::strategy::revm_arbitrage_profit(
+                engine, sell_pool, sell_state, buy_pool, buy_state, amount, 0.0, 1.0,
+            )
+            .ok()?,
+            None => math::compute_arbitrage_profit_presorted(
+                amount,
+                sell_state,
+                sell_fee,
+                buy_state,
+                buy_fee,
+                |_| 0.0,
+                1.0,
+                sell_pool.token0_is_weth,
+                buy_pool.token0_is_weth,
+                sell_ticks.get_ordered(sell_pool.token0_is_weth),
+                buy_ticks.get_ordered(!buy_pool.token0_is_weth),
+            ),
+        };
+        profit.is_finite().then_some(profit)
+    };
+
+    // Swap dışı maliyetler hızlı yolun ayrıştırmasından: gas sabit, flash
+    // primi miktarla orantılı
+    let (gas_weth, flash_fee_per_weth) = opp.profit_breakdown.map_or((0.0, 0.0), |b| {
+        let per_weth = if opp.optimal_amount_weth > 0.0 { b.flash_fee_weth / opp.optimal_amount_weth } else { 0.0 };
+        (b.gas_weth, per_weth)
+    });
+    let net = |amount: f64| gross(amount).map(|g| g - gas_weth - flash_fee_per_weth * amount);
+
+    let mut gate = Gate { deadline, yield_now, probes: 0, exhausted: false };
+    if yield_now() {
+        return RefineOutcome::Preempted(Box::new(candidate));
+    }
+    gate.probes += 1;
+    let at_original = net(opp.optimal_amount_weth);
+    let mut best = at_original.map(|p| (opp.optimal_amount_weth, p));
+
+    match search(&net, min_amount, max_amount, &mut gate, &mut best) {
+        Err(Halt::Preempted) => return RefineOutcome::Preempted(Box::new(candidate)),
+        Err(Halt::Exhausted) | Ok(()) => {}
+    }
+
+    let (refined_amount, refined_profit) = best.unwrap_or((0.0, 0.0));
+    RefineOutcome::Done(RevisitRecord {
+        ts: crate::clock::log_timestamp(),
+        id: candidate.id.clone(),
+        block: candidate.block,
+        pair: candidate.pair_label(),
+        engine: if revm.is_some() { "revm" } else { "exact" },
+        original_amount_weth: opp.optimal_amount_weth,
+        original_profit_weth: opp.expected_profit_weth,
+        original_amount_refined_profit_weth: at_original,
+        refined_amount_weth: refined_amount,
+        refined_profit_weth: refined_profit,
+        discrepancy_weth: refined_profit - opp.expected_profit_weth,
+        widened_ticks,
+        probes: gate.probes,
+        elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+        complete: !gate.exhausted,
+    })
+}
+
+/// Karesel ızgara + altın oran araması; `best` yerinde güncellenir
+fn search(
+    net: &dyn Fn(f64) -> Option<f64>,
+    min_amount: f64,
+    max_amount: f64,
+    gate: &mut Gate<'_>,
+    best: &mut Option<(f64, f64)>,
+) -> Result<(), Halt> {
+    if !min_amount.is_finite() || !max_amount.is_finite() || min_amount >= max_amount {
+        return Ok(());
+    }
+    let span = max_amount - min_amount;
+    let grid = |i: u32| min_amount + span * (i as f64 / GRID_POINTS as f64).powi(2);
+    let consider = |amount: f64, best: &mut Option<(f64, f64)>, gate: &mut Gate<'_>| -> Result<Option<f64>, Halt> {
+        gate.admit()?;
+        let profit = net(amount);
+        if let Some(p) = profit {
+            if best.is_none_or(|(_, b)| p > b) {
+                *best = Some((amount, p));
+            }
+        }
+        Ok(profit)
+    };
+
+    let mut best_i = None;
+    let mut best_grid = f64::NEG_INFINITY;
+    for i in 0..=GRID_POINTS {
+        if let Some(p) = consider(grid(i), best, gate)? {
+            if p > best_grid {
+                best_grid = p;
+                best_i = Some(i);
+            }
+        }
+    }
+    let Some(i) = best_i else {
+        return Ok(());
+    };
+
+    // Izgara komşuları arasında altın oran (kâr eğrisi optimum civarında tek tepeli)
+    const INV_PHI: f64 = 0.618_033_988_749_895;
+    let (mut lo, mut hi) = (grid(i.saturating_sub(1)), grid((i + 1).min(GRID_POINTS)));
+    let value = |p: Option<f64>| p.unwrap_or(f64::NEG_INFINITY);
+    let mut x1 = hi - INV_PHI * (hi - lo);
+    let mut x2 = lo + INV_PHI * (hi - lo);
+    let mut f1 = value(consider(x1, best, gate)?);
+    let mut f2 = value(consider(x2, best, gate)?);
+    for _ in 0..GOLDEN_ITERATIONS {
+        if hi - lo <= 1e-9 * hi.max(1.0) {
+            break;
+        }
+        if f1 >= f2 {
+            hi = x2;
+            (x2, f2) = (x1, f1);
+            x1 = hi - INV_PHI * (hi - lo);
+            f1 = value(consider(x1, best, gate)?);
+        } else {
+            lo = x1;
+            (x1, f1) = (x2, f2);
+            x2 = lo + INV_PHI * (hi - lo);
+            f2 = value(consider(x2, best, gate)?);
+        }
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Zamanlayıcı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sapma toplamları (stats kutusu)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct RevisitStats {
+    revisited: u64,
+    budget_hit: u64,
+    preempted: u64,
+    discrepancy_sum_weth: f64,
+    left_on_table_weth: f64,
+    over_promised_weth: f64,
+}
+
+impl RevisitStats {
+    fn record(&mut self, record: &RevisitRecord) {
+        self.revisited += 1;
+        if !record.complete {
+            self.budget_hit += 1;
+        }
+        self.discrepancy_sum_weth += record.discrepancy_weth;
+        if record.discrepancy_weth > 0.0 {
+            self.left_on_table_weth += record.discrepancy_weth;
+        } else {
+            self.over_promised_weth -= record.discrepancy_weth;
+        }
+    }
+}
+
+/// Zamanlayıcının blok kararı
+#[derive(Debug)]
+enum Action {
+    Idle,
+    /// Çalışan değerlendirme iptal edildi (piyasa canlandı)
+    Preempt,
+    Start(Box<RevisitCandidate>, CancellationToken),
+}
+
+/// Defter + sessizlik sayacı + çalışan iş
+struct Revisitor {
+    book: TopK,
+    quiet: QuietTracker,
+    running: Option<CancellationToken>,
+    engine: Option<Arc<SimulationEngine>>,
+    stats: RevisitStats,
+}
+
+impl Revisitor {
+    fn new(top_k: usize, quiet_blocks: u64) -> Self {
+        Self {
+            book: TopK::new(top_k),
+            quiet: QuietTracker::new(quiet_blocks),
+            running: None,
+            engine: None,
+            stats: RevisitStats::default(),
+        }
+    }
+
+    /// Blok sonu: fırsat görüldüyse çalışan iş iptal edilir; sessizlik
+    /// eşiği aşıldıysa ve iş yoksa en iyi aday başlatılır (blok başına bir)
+    fn on_block(&mut self, opportunity_seen: bool) -> Action {
+        let quiet = self.quiet.observe(opportunity_seen);
+        if opportunity_seen {
+            if let Some(token) = self.running.as_ref() {
+                token.cancel();
+                return Action::Preempt;
+            }
+        }
+        if !quiet || self.running.is_some() {
+            return Action::Idle;
+        }
+        match self.book.pop_best() {
+            Some(candidate) => {
+                let token = CancellationToken::new();
+                self.running = Some(token.clone());
+                Action::Start(Box::new(candidate), token)
+            }
+            None => Action::Idle,
+        }
+    }
+
+    fn finish(&mut self, outcome: Option<&RefineOutcome>) {
+        self.running = None;
+        match outcome {
+            Some(RefineOutcome::Done(record)) => self.stats.record(record),
+            Some(RefineOutcome::Preempted(_)) => self.stats.preempted += 1,
+            None => {}
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Zamanlayıcı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// init öncesi / REVISIT_TOP_K=0 → None
+static REVISITOR: Mutex<Option<Revisitor>> = parking_lot::const_mutex(None);
+
+/// REVISIT_TOP_K > 0 ise defteri kur (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    if config.revisit_top_k == 0 {
+        return;
+    }
+    *REVISITOR.lock() = Some(Revisitor::new(config.revisit_top_k, config.revisit_quiet_blocks));
+}
+
+/// Havuz bytecode'larını taşıyan quote motoru (run_bot, her bağlantıda)
+pub fn attach_engine(engine: SimulationEngine) {
+    if let Some(revisitor) = REVISITOR.lock().as_mut() {
+        revisitor.engine = Some(Arc::new(engine));
+    }
+}
+
+/// Gölge fırsatı günün listesine öner — yalnızca Arc/yapılandırma klonları
+pub fn offer(
+    id: Option<&str>,
+    opportunity: &ArbitrageOpportunity,
+    pools: &[PoolConfig],
+    states: [Arc<PoolState>; 2],
+) {
+    let mut guard = REVISITOR.lock();
+    let Some(revisitor) = guard.as_mut() else {
+        return;
+    };
+    let block = states[0].last_block.max(states[1].last_block);
+    revisitor.book.offer(
+        RevisitCandidate {
+            id: id.map_or_else(|| crate::snapshot::new_snapshot_id(block), str::to_string),
+            block,
+            opportunity: opportunity.clone(),
+            pools: [pools[0].clone(), pools[1].clone()],
+            states,
+        },
+        crate::clock::local_date(),
+    );
+}
+
+/// Ana döngü, blok sonunda: bu blokta fırsat görüldü mü?
+///
+/// Değerlendirme blocking havuzunda koşar; ana döngü beklemez. Sonraki
+/// bloklarda fırsat görülürse iptal jetonu bir sonraki probe'da işi durdurur.
+pub fn on_block(opportunity_seen: bool, pools: &[PoolConfig], states: &[SharedPoolState], config: &BotConfig) {
+    let (candidate, token, engine) = {
+        let mut guard = REVISITOR.lock();
+        let Some(revisitor) = guard.as_mut() else {
+            return;
+        };
+        match revisitor.on_block(opportunity_seen) {
+            Action::Start(candidate, token) => (*candidate, token, revisitor.engine.clone()),
+            Action::Preempt | Action::Idle => return,
+        }
+    };
+    // Canlı state'ler adrese göre — bitmap genişletmesi için
+    let live: [Option<Arc<PoolState>>; 2] = std::array::from_fn(|i| {
+        pools
+            .iter()
+            .position(|p| p.address == candidate.pools[i].address)
+            .and_then(|idx| states.get(idx))
+            .map(|s| s.load_full())
+    });
+    let bounds = (config.min_trade_size_weth, config.max_trade_size_weth);
+    let budget = Duration::from_millis(config.revisit_budget_ms);
+
+    tokio::spawn(async move {
+        let joined = tokio::task::spawn_blocking(move || {
+            let live_bitmaps = live.each_ref().map(|s| s.as_ref().and_then(|s| s.tick_bitmap.as_deref()));
+            let deadline = Instant::now() + budget;
+            let outcome =
+                refine(candidate, live_bitmaps, engine.as_deref(), bounds, deadline, &|| token.is_cancelled());
+            if let RefineOutcome::Done(ref record) = outcome {
+                if let Err(e) = append_record(&crate::secure_fs::data_path(REVISITS_PATH), record) {
+                    eprintln!("  ⚠️ [Revisit] {} write error: {}", REVISITS_PATH, e);
+                }
+            }
+            outcome
+        })
+        .await;
+
+        let mut guard = REVISITOR.lock();
+        let Some(revisitor) = guard.as_mut() else {
+            return;
+        };
+        match joined {
+            Ok(outcome) => {
+                revisitor.finish(Some(&outcome));
+                match outcome {
+                    RefineOutcome::Done(record) => report(&record),
+                    RefineOutcome::Preempted(candidate) => revisitor.book.requeue(*candidate),
+                }
+            }
+            Err(e) => {
+                revisitor.finish(None);
+                eprintln!("  ⚠️ [Revisit] task error: {}", e);
+            }
+        }
+    });
+}
+
+fn report(record: &RevisitRecord) {
+    eprintln_high!(
+        "  {} [Revisit] {} #{}: fast {:.6} WETH @ {:.4} → refined {:.6} WETH @ {:.4} ({:+.6}, {}, {} probes{})",
+        "🔁".cyan(),
+        record.pair,
+        record.block,
+        record.original_profit_weth,
+        record.original_amount_weth,
+        record.refined_profit_weth,
+        record.refined_amount_weth,
+        record.discrepancy_weth,
+        record.engine,
+        record.probes,
+        if record.complete { "" } else { ", budget hit" },
+    );
+}
+
+fn append_record(path: &Path, record: &RevisitRecord) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut file = crate::secure_fs::open_append(path)?;
+    writeln!(file, "{}", serde_json::to_string(record).map_err(std::io::Error::other)?)
+}
+
+/// İstatistik kutusu satırı — REVISIT_TOP_K=0 ise None
+pub fn stats_line() -> Option<String> {
+    REVISITOR.lock().as_ref().map(|r| {
+        let s = &r.stats;
+        let mean = if s.revisited > 0 { s.discrepancy_sum_weth / s.revisited as f64 } else { 0.0 };
+        format!(
+            "revisited {} (budget hit {}) | preempted {} | mean Δ {:+.6} WETH | left on table {:.6} | over-promised {:.6} | queued {}",
+            s.revisited,
+            s.budget_hit,
+            s.preempted,
+            mean,
+            s.left_on_table_weth,
+            s.over_promised_weth,
+            r.book.len(),
+        )
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opportunity_filter::FilterChain;
+    use crate::strategy::check_arbitrage_opportunity;
+    use crate::strategy::gas_spike_tests::{make_pool_configs, make_pool_state, make_test_config};
+    use crate::types::{CompetitionSnapshot, TickInfo};
+    use std::cell::Cell;
+
+    fn candidate(profit: f64) -> RevisitCandidate {
+        let pools = make_pool_configs();
+        let liq = 50_000_000_000_000_000_000u128;
+        let states = [make_pool_state(2450.0, liq, 100), make_pool_state(2500.0, liq, 100)];
+        let mut opportunity = check_arbitrage_opportunity(
+            &pools,
+            &states,
+            &make_test_config(0.0002, 0.00005),
+            500_000_000_000,
+            Some(150_000),
+            0,
+            &CompetitionSnapshot::default(),
+            &FilterChain::default_chain(),
+            None,
+        )
+        .expect("2% spread on deep pools");
+        opportunity.expected_profit_weth = profit;
+        RevisitCandidate {
+            id: format!("100-{}", profit),
+            block: 100,
+            opportunity,
+            pools: [pools[0].clone(), pools[1].clone()],
+            states: [states[0].load_full(), states[1].load_full()],
+        }
+    }
+
+    fn info(net: i128) -> TickInfo {
+        TickInfo { liquidity_gross: net.unsigned_abs(), liquidity_net: net, initialized: true, staked_liquidity_net: 0 }
+    }
+
+    #[test]
+    fn test_quiet_tracker_needs_consecutive_empty_blocks() {
+        let mut quiet = QuietTracker::new(3);
+        assert!(!quiet.observe(false));
+        assert!(!quiet.observe(false));
+        assert!(quiet.observe(false));
+        assert!(quiet.observe(false), "stays quiet until an opportunity");
+        assert!(!quiet.observe(true));
+        assert!(!quiet.observe(false), "activity restarts the streak");
+        assert!(!quiet.observe(false));
+        assert!(quiet.observe(false));
+    }
+
+    #[test]
+    fn test_top_k_keeps_days_best_and_resets_on_new_day() {
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let mut book = TopK::new(2);
+        assert!(book.offer(candidate(0.001), day));
+        assert!(book.offer(candidate(0.003), day));
+        assert!(book.offer(candidate(0.002), day), "beats the current worst");
+        assert!(!book.offer(candidate(0.0005), day));
+        assert!(!book.offer(candidate(f64::NAN), day));
+        assert_eq!(book.len(), 2);
+        assert_eq!(book.pop_best().unwrap().rank(), 0.003);
+
+        // Ertesi gün önceki günün listesi düşer
+        assert!(book.offer(candidate(0.0001), day.succ_opt().unwrap()));
+        assert_eq!(book.len(), 1);
+        assert_eq!(book.pop_best().unwrap().rank(), 0.0001);
+        assert!(book.pop_best().is_none());
+    }
+
+    /// Eşik dolunca blok başına bir aday başlar; fırsat görülünce çalışan
+    /// iş iptal edilir, aday deftere döner ve sessizlik yeniden sayılır
+    #[test]
+    fn test_scheduler_starts_on_quiet_and_preempts_on_activity() {
+        let day = chrono::Local::now().date_naive();
+        let mut revisitor = Revisitor::new(4, 3);
+        revisitor.book.offer(candidate(0.002), day);
+        revisitor.book.offer(candidate(0.004), day);
+
+        assert!(matches!(revisitor.on_block(false), Action::Idle));
+        assert!(matches!(revisitor.on_block(false), Action::Idle));
+        let Action::Start(first, token) = revisitor.on_block(false) else {
+            panic!("third quiet block must start the best entry");
+        };
+        assert_eq!(first.rank(), 0.004);
+        assert!(matches!(revisitor.on_block(false), Action::Idle), "one job at a time");
+
+        assert!(matches!(revisitor.on_block(true), Action::Preempt));
+        assert!(token.is_cancelled());
+
+        // İşin kendisi jetonu bir sonraki probe'da görür
+        let outcome = refine(*first, [None, None], None, (0.01, 10.0), Instant::now() + Duration::from_secs(5), &|| {
+            token.is_cancelled()
+        });
+        let RefineOutcome::Preempted(returned) = outcome else {
+            panic!("cancelled job must yield");
+        };
+        revisitor.finish(Some(&RefineOutcome::Preempted(returned.clone())));
+        revisitor.book.requeue(*returned);
+        assert_eq!(revisitor.book.len(), 2);
+        assert_eq!(revisitor.stats.preempted, 1);
+
+        assert!(matches!(revisitor.on_block(false), Action::Idle));
+        assert!(matches!(revisitor.on_block(false), Action::Idle));
+        let Action::Start(again, _) = revisitor.on_block(false) else {
+            panic!("quiet streak restarted after activity");
+        };
+        assert_eq!(again.rank(), 0.004, "preempted entry keeps its place");
+    }
+
+    /// Kesinti ızgaranın ortasında gelirse iş hemen bırakılır
+    #[test]
+    fn test_refine_yields_mid_search() {
+        let calls = Cell::new(0u32);
+        let check = || {
+            calls.set(calls.get() + 1);
+            calls.get() > 10
+        };
+        let far = Instant::now() + Duration::from_secs(5);
+        let outcome = refine(candidate(0.002), [None, None], None, (0.01, 10.0), far, &check);
+        assert!(matches!(outcome, RefineOutcome::Preempted(_)));
+        assert_eq!(calls.get(), 11, "no probe after the yield signal");
+    }
+
+    #[test]
+    fn test_refine_budget_and_full_search() {
+        let never = || false;
+
+        // Süre baştan dolmuş: yalnızca hızlı yolun boyutu ölçülür
+        let c = candidate(0.002);
+        let original = c.opportunity.optimal_amount_weth;
+        let RefineOutcome::Done(clipped) = refine(c, [None, None], None, (0.01, 10.0), Instant::now(), &never) else {
+            panic!("budget exhaustion still records");
+        };
+        assert!(!clipped.complete);
+        assert_eq!(clipped.probes, 1);
+        assert_eq!(clipped.refined_amount_weth, original);
+        assert_eq!(clipped.engine, "exact");
+
+        // Tam arama: en az hızlı yolun boyutundaki kâr kadar iyi
+        let far = Instant::now() + Duration::from_secs(30);
+        let RefineOutcome::Done(full) = refine(candidate(0.002), [None, None], None, (0.01, 10.0), far, &never) else {
+            panic!("uninterrupted search completes");
+        };
+        assert!(full.complete);
+        assert!(full.probes > GRID_POINTS);
+        let at_original = full.original_amount_refined_profit_weth.expect("feasible at fast-path size");
+        assert!(full.refined_profit_weth >= at_original);
+        assert!((0.01..=10.0).contains(&full.refined_amount_weth) || full.refined_amount_weth == original);
+        assert_eq!(full.discrepancy_weth, full.refined_profit_weth - 0.002);
+    }
+
+    /// Snapshot'ın taranan aralığı korunur, yalnızca dışı canlıdan gelir
+    #[test]
+    fn test_widen_bitmap_fills_only_outside_scanned_range() {
+        let snapshot = TickBitmapData {
+            ticks: [(-50, info(100)), (50, info(-100))].into_iter().collect(),
+            scanned_ticks: Some((-100, 100)),
+            ..TickBitmapData::empty()
+        };
+        let live = TickBitmapData {
+            ticks: [(-50, info(999)), (10, info(7)), (-400, info(300)), (400, info(-300))].into_iter().collect(),
+            scanned_ticks: Some((-500, 500)),
+            ..TickBitmapData::empty()
+        };
+        let (widened, added) = widen_bitmap(&snapshot, &live);
+        assert_eq!(added, 2);
+        assert_eq!(widened.ticks[&-50].liquidity_net, 100, "snapshot wins inside its range");
+        assert!(!widened.ticks.contains_key(&10), "live ticks inside the scanned range are ignored");
+        assert_eq!(widened.ticks[&400].liquidity_net, -300);
+        assert_eq!(widened.scanned_ticks, Some((-500, 500)));
+
+        let unscanned = TickBitmapData { scanned_ticks: None, ..snapshot };
+        assert_eq!(widen_bitmap(&unscanned, &live).1, 0);
+    }
+}
@@ -19,7 +19,7 @@
 use alloy::primitives::Address;
 use std::collections::HashMap;
 
-use crate::types::{PoolConfig, SharedPoolState, DexType};
+use crate::types::{liquidity_to_f64, PoolConfig, SharedPoolState, DexType};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Sabitler
@@ -32,8 +32,9 @@ const MAX_HOP_DEPTH: usize = 4;
 /// DFS'in üretebileceği maksimum rota sayısı. Kombinatorik patlamayı önler.
 const MAX_ROUTES: usize = 500;
 
-/// Minimum likidite filtresi — kenar ağırlığı (WETH cinsinden)
-const MIN_EDGE_LIQUIDITY_WETH: f64 = 0.1;
+/// Minimum likidite filtresi — ham L ≥ 0.1 × 10^18. u128 ile karşılaştırılır:
+/// 2^53 üstünde f64 kopya komşu değerleri birleştirir, eşik kayar.
+const MIN_EDGE_LIQUIDITY: u128 = 100_000_000_000_000_000;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Rota Yapıları
@@ -146,25 +147,24 @@ impl LiquidityGraph {
             // Token adreslerini çıkar
             let (token0, token1) = Self::pool_tokens(pool);
 
-            // Likidite tahmini (WETH cinsinden)
+            // Likidite tahmini (WETH cinsinden) — eşik u128, ağırlık f64
             let liq_estimate = if idx < states.len() {
-                let state = states[idx].load();
+                let liquidity = states[idx].load().liquidity;
+                if liquidity < MIN_EDGE_LIQUIDITY {
+                    continue;
+                }
                 // Kaba likidite tahmini: liquidity / 10^18
-                state.liquidity_f64 / 1e18
+                liquidity_to_f64(liquidity) / 1e18
             } else {
                 1.0 // Fallback: bilgi yoksa 1.0 WETH varsay
             };
 
-            if liq_estimate < MIN_EDGE_LIQUIDITY_WETH {
-                continue;
-            }
-
             // token0 → token1 kenarı
             adjacency.entry(token0).or_default().push(Edge {
                 pool_idx: idx,
                 target_token: token1,
                 zero_for_one: true,
-                fee_bps: pool.fee_bps,
+                fee_bps: pool.fee_bps(),
                 dex: pool.dex,
                 liquidity_estimate: liq_estimate,
             });
@@ -174,7 +174,7 @@ impl LiquidityGraph {
                 pool_idx: idx,
                 target_token: token0,
                 zero_for_one: false,
-                fee_bps: pool.fee_bps,
+                fee_bps: pool.fee_bps(),
                 dex: pool.dex,
                 liquidity_estimate: liq_estimate,
             });
@@ -383,7 +383,7 @@ impl LiquidityGraph {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::PoolState;
+    use crate::types::{FeeTier, PoolState};
     use std::sync::Arc;
     use arc_swap::ArcSwap;
     use std::time::Instant;
@@ -414,8 +414,7 @@ mod tests {
         PoolConfig {
             address: test_pool_address(),
             name: format!("test-{:?}-{}", dex, fee_bps),
-            fee_bps,
-            fee_fraction: fee_bps as f64 / 10_000.0,
+            fee: FeeTier::from_bps(fee_bps),
             token0_decimals: if t0_is_weth { 18 } else { 6 },
             token1_decimals: if t0_is_weth { 6 } else { 18 },
             dex,
@@ -437,10 +436,15 @@ mod tests {
             last_block: 100,
             last_update: Instant::now(),
             is_initialized: true,
-            bytecode: None,
             tick_bitmap: None,
             live_fee_bps: None,
             is_stale: false,
+            data_quality_errors: 0,
+            quarantined: false,
+            optimistic_trigger: None,
+            pinned_block: Some(100),
+            predicted_state: None,
+            constant_product: false,
         }))
     }
 
@@ -461,6 +465,31 @@ mod tests {
         assert_eq!(graph.node_count(), 3);
     }
 
+    /// Kenar eşiği u128: 2^53 üstünde f64'te aynı görünen L-1 / L ayrılır
+    #[test]
+    fn test_edge_liquidity_threshold_uses_u128() {
+        let pools = vec![
+            make_pool_config(usdc(), DexType::UniswapV3, 5, true),
+            make_pool_config(dai(), DexType::UniswapV3, 5, true),
+        ];
+        let states: Vec<SharedPoolState> = pools.iter().map(|_| make_active_state()).collect();
+        let with_liquidity = |state: &SharedPoolState, liquidity: u128| {
+            let mut s = (**state.load()).clone();
+            s.set_liquidity(liquidity);
+            state.store(Arc::new(s));
+        };
+        const { assert!(MIN_EDGE_LIQUIDITY > 1u128 << 53) };
+        assert_eq!(liquidity_to_f64(MIN_EDGE_LIQUIDITY - 1), liquidity_to_f64(MIN_EDGE_LIQUIDITY));
+        with_liquidity(&states[0], MIN_EDGE_LIQUIDITY - 1);
+        with_liquidity(&states[1], MIN_EDGE_LIQUIDITY);
+
+        let graph = LiquidityGraph::build(&pools, &states, weth());
+
+        // Yalnızca WETH/DAI havuzunun iki kenarı
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.node_count(), 2);
+    }
+
     #[test]
     fn test_find_two_hop_routes() {
         // WETH/USDC: UniV3 + Aerodrome → 2-hop çapraz-DEX rota
@@ -497,8 +526,7 @@ mod tests {
         pools.push(PoolConfig {
             address: test_pool_address(),
             name: "test-usdc-dai".to_string(),
-            fee_bps: 1,
-            fee_fraction: 0.0001,
+            fee: FeeTier::from_bps(1),
             token0_decimals: 6,
             token1_decimals: 18,
             dex: DexType::UniswapV3,
@@ -0,0 +1,589 @@
+// ============================================================================
+//  RPC CENSUS v1.0 — Çağrı Sayımı + Blok Başına Okuma Belleği
+//
+//  Aynı havuz okuması birden fazla yoldan (sync fallback, iyimser yenileme,
+//  bitmap genişletme) tekrarlanabiliyor; hangi yolun ne kadar RPC harcadığı
+//  ölçülmeden gereksiz okumalar bulunamaz.
+//
+//  ✓ Anahtar: (metot, hedef kontrat, çağrı yeri) — blok başına ve fırsat
+//    başına sayaç + toplam bekleme süresi
+//  ✓ İnce sarmalayıcı: `ProviderRegistry::traced(site)` / `traced(key, fut)`
+//  ✓ Fırsat kapsamı task-local — eşzamanlı görevlerin (pending dinleyici,
+//    arka plan sync) çağrıları fırsata yazılmaz
+//  ✓ REDUNDANCY_WARN: aynı (metot, hedef) tek fırsatta N'den fazla → uyarı
+//  ✓ BlockMemo: abonelik bloğunun hash'ine sabitli idempotent okumalar
+//    (slot0/liquidity) blok başına bir kez; yeni hash gelince temizlenir
+//  ✓ Verbose log (blok özeti) + stats kutusu (en çok çağrılan yerler)
+// ============================================================================
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use alloy::eips::BlockId;
+use alloy::primitives::{Address, B256};
+use parking_lot::Mutex;
+
+use crate::errors::BotResult;
+use crate::types::BotConfig;
+
+/// Stats kutusunda listelenen çağrı yeri sayısı
+const STATS_TOP_SITES: usize = 5;
+
+/// Blok özetinde gösterilen çağrı yeri sayısı
+const BLOCK_TOP_SITES: usize = 3;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Anahtar + Sayaç
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sayım anahtarı — `site` çağrının yapıldığı kod yolu ("optimistic_refresh")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CallKey {
+    pub method: &'static str,
+    pub target: Address,
+    pub site: &'static str,
+}
+
+impl CallKey {
+    pub const fn new(method: &'static str, target: Address, site: &'static str) -> Self {
+        Self { method, target, site }
+    }
+}
+
+/// Çağrının nasıl karşılandığı
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Served {
+    /// Ağa gidildi — bekleme süresiyle
+    Network(Duration),
+    /// BlockMemo'dan — RPC yok
+    Cache,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CallTally {
+    /// Ağa giden çağrılar
+    pub calls: u64,
+    /// Bellekten karşılananlar
+    pub cached: u64,
+    /// Ağ çağrılarının toplam bekleme süresi (µs)
+    pub await_us: u64,
+}
+
+impl CallTally {
+    fn add(&mut self, served: Served) {
+        match served {
+            Served::Network(elapsed) => {
+                self.calls += 1;
+                self.await_us += elapsed.as_micros() as u64;
+            }
+            Served::Cache => self.cached += 1,
+        }
+    }
+
+    fn merge(&mut self, other: &CallTally) {
+        self.calls += other.calls;
+        self.cached += other.cached;
+        self.await_us += other.await_us;
+    }
+}
+
+fn total(calls: &HashMap<CallKey, CallTally>) -> CallTally {
+    calls.values().fold(CallTally::default(), |mut acc, t| {
+        acc.merge(t);
+        acc
+    })
+}
+
+/// En çok çağrılan anahtarlar: ağ çağrısı, eşitlikte bekleme süresi azalan
+fn top_by_count<K: Copy>(tallies: impl Iterator<Item = (K, CallTally)>, n: usize) -> Vec<(K, CallTally)> {
+    let mut sorted: Vec<(K, CallTally)> = tallies.collect();
+    sorted.sort_by_key(|(_, t)| std::cmp::Reverse((t.calls, t.await_us)));
+    sorted.truncate(n);
+    sorted
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Raporlar
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tamamlanan bloğun çağrı özeti (verbose log)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockReport {
+    pub block: u64,
+    pub total: CallTally,
+    pub top: Vec<(CallKey, CallTally)>,
+}
+
+impl BlockReport {
+    pub fn summary(&self) -> String {
+        let top: Vec<String> = self
+            .top
+            .iter()
+            .map(|(key, tally)| format!("{}/{} ×{}", key.site, key.method, tally.calls + tally.cached))
+            .collect();
+        format!(
+            "Block #{}: {} calls, {} cached, {:.1}ms await | top {}",
+            self.block,
+            self.total.calls,
+            self.total.cached,
+            self.total.await_us as f64 / 1000.0,
+            top.join(", "),
+        )
+    }
+}
+
+/// Tek fırsatta eşiği aşan (metot, hedef) okuması
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redundant {
+    pub method: &'static str,
+    pub target: Address,
+    /// Ağa giden çağrı sayısı (bellekten karşılananlar sayılmaz)
+    pub calls: u64,
+    /// Çağrının geldiği yerler
+    pub sites: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpportunityReport {
+    pub total: CallTally,
+    pub redundant: Vec<Redundant>,
+}
+
+impl OpportunityReport {
+    pub fn summary(&self) -> String {
+        let mut line = format!(
+            "{} calls, {} cached, {:.1}ms await",
+            self.total.calls,
+            self.total.cached,
+            self.total.await_us as f64 / 1000.0,
+        );
+        for r in &self.redundant {
+            line.push_str(&format!(
+                " | REDUNDANT {} @ {} ×{} ({})",
+                r.method,
+                r.target,
+                r.calls,
+                r.sites.join(", "),
+            ));
+        }
+        line
+    }
+}
+
+/// Aynı (metot, hedef) ağ çağrısı `threshold`'dan fazlaysa işaretle (0 = kapalı)
+fn find_redundant(calls: &HashMap<CallKey, CallTally>, threshold: u32) -> Vec<Redundant> {
+    if threshold == 0 {
+        return Vec::new();
+    }
+    let mut by_target: HashMap<(&'static str, Address), Redundant> = HashMap::new();
+    for (key, tally) in calls.iter().filter(|(_, t)| t.calls > 0) {
+        let entry = by_target.entry((key.method, key.target)).or_insert_with(|| Redundant {
+            method: key.method,
+            target: key.target,
+            calls: 0,
+            sites: Vec::new(),
+        });
+        entry.calls += tally.calls;
+        entry.sites.push(key.site);
+    }
+    let mut flagged: Vec<Redundant> = by_target
+        .into_values()
+        .filter(|r| r.calls > threshold as u64)
+        .map(|mut r| {
+            r.sites.sort_unstable();
+            r
+        })
+        .collect();
+    flagged.sort_by(|a, b| b.calls.cmp(&a.calls).then(a.method.cmp(b.method)));
+    flagged
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Census
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+pub struct Census {
+    redundancy_warn: u32,
+    block: Option<u64>,
+    block_calls: HashMap<CallKey, CallTally>,
+    /// Oturum toplamı — (çağrı yeri, metot); hedefler birleştirilir
+    sites: HashMap<(&'static str, &'static str), CallTally>,
+    blocks: u64,
+    opportunities: u64,
+    flagged_opportunities: u64,
+}
+
+impl Census {
+    pub fn new(redundancy_warn: u32) -> Self {
+        Self {
+            redundancy_warn,
+            block: None,
+            block_calls: HashMap::new(),
+            sites: HashMap::new(),
+            blocks: 0,
+            opportunities: 0,
+            flagged_opportunities: 0,
+        }
+    }
+
+    fn record(&mut self, key: CallKey, served: Served) {
+        self.block_calls.entry(key).or_default().add(served);
+        self.sites.entry((key.site, key.method)).or_default().add(served);
+    }
+
+    /// Yeni blok — önceki bloğun özeti (çağrı yoksa None)
+    pub fn begin_block(&mut self, block: u64) -> Option<BlockReport> {
+        let previous = self.block.replace(block);
+        if previous == Some(block) {
+            return None;
+        }
+        let calls = std::mem::take(&mut self.block_calls);
+        self.blocks += 1;
+        let previous = previous?;
+        if calls.is_empty() {
+            return None;
+        }
+        Some(BlockReport {
+            block: previous,
+            total: total(&calls),
+            top: top_by_count(calls.into_iter(), BLOCK_TOP_SITES),
+        })
+    }
+
+    /// Fırsat kapsamındaki çağrıları değerlendir
+    pub fn finish_opportunity(&mut self, calls: &HashMap<CallKey, CallTally>) -> OpportunityReport {
+        let report = OpportunityReport {
+            total: total(calls),
+            redundant: find_redundant(calls, self.redundancy_warn),
+        };
+        self.opportunities += 1;
+        if !report.redundant.is_empty() {
+            self.flagged_opportunities += 1;
+        }
+        report
+    }
+
+    pub fn stats_lines(&self) -> Vec<String> {
+        let session = self.sites.values().fold(CallTally::default(), |mut acc, t| {
+            acc.merge(t);
+            acc
+        });
+        if session.calls + session.cached == 0 {
+            return Vec::new();
+        }
+        let mut lines = vec![format!(
+            "{} calls, {} cached over {} blocks | {} opportunities, {} redundant (> {} same reads)",
+            session.calls,
+            session.cached,
+            self.blocks,
+            self.opportunities,
+            self.flagged_opportunities,
+            self.redundancy_warn,
+        )];
+        lines.extend(
+            top_by_count(self.sites.iter().map(|(k, t)| (*k, *t)), STATS_TOP_SITES)
+                .into_iter()
+                .map(|((site, method), tally)| {
+                    format!(
+                        "{} {}: {} calls, {} cached | {:.1}ms total await",
+                        site,
+                        method,
+                        tally.calls,
+                        tally.cached,
+                        tally.await_us as f64 / 1000.0,
+                    )
+                }),
+        );
+        lines
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Fırsat Kapsamı
+// ─────────────────────────────────────────────────────────────────────────────
+
+type ScopeCalls = Arc<Mutex<HashMap<CallKey, CallTally>>>;
+
+tokio::task_local! {
+    static OPPORTUNITY: ScopeCalls;
+}
+
+/// Tek fırsatın (çift değerlendirmesi + bağlı okumaları) çağrıları.
+/// Yalnızca `within` ile sarılan future'lardaki çağrılar sayılır.
+#[derive(Debug, Default)]
+pub struct OpportunityScope {
+    calls: ScopeCalls,
+}
+
+impl OpportunityScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn within<F: Future>(&self, fut: F) -> F::Output {
+        OPPORTUNITY.scope(Arc::clone(&self.calls), fut).await
+    }
+
+    /// Kapsamı kapat — census kurulmamışsa eşik uygulanmaz
+    pub fn finish(self) -> OpportunityReport {
+        let calls = std::mem::take(&mut *self.calls.lock());
+        match CENSUS.lock().as_mut() {
+            Some(census) => census.finish_opportunity(&calls),
+            None => OpportunityReport { total: total(&calls), redundant: Vec::new() },
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Blok Başına Okuma Belleği
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Bir blok hash'ine sabitli okumaların sonuçları. Farklı hash'le gelen ilk
+/// erişim belleği temizler; eski hash'e ait geç kalan sonuç yazılmaz.
+#[derive(Debug)]
+pub struct BlockMemo<K, V> {
+    block: Option<B256>,
+    entries: HashMap<K, V>,
+}
+
+impl<K, V> Default for BlockMemo<K, V> {
+    fn default() -> Self {
+        Self { block: None, entries: HashMap::new() }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> BlockMemo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&mut self, block: B256, key: &K) -> Option<V> {
+        if self.block != Some(block) {
+            self.block = Some(block);
+            self.entries.clear();
+        }
+        self.entries.get(key).cloned()
+    }
+
+    /// Yalnızca bellek hâlâ `block`'taysa yazılır
+    pub fn insert(&mut self, block: B256, key: K, value: V) {
+        if self.block == Some(block) {
+            self.entries.insert(key, value);
+        }
+    }
+}
+
+/// `pinned` hash'inde bellekli okuma: aynı blokta ikinci çağrı ağa gitmez.
+/// Sabit blok yoksa (ilk başlık gelmeden) `fetch(latest)` belleksiz çalışır.
+pub async fn memoized<K, V, F, Fut>(
+    memo: &Mutex<BlockMemo<K, V>>,
+    pinned: Option<B256>,
+    census_key: CallKey,
+    key: K,
+    fetch: F,
+) -> BotResult<V>
+where
+    K: Eq + Hash,
+    V: Clone,
+    F: FnOnce(BlockId) -> Fut,
+    Fut: Future<Output = BotResult<V>>,
+{
+    let Some(hash) = pinned else {
+        return fetch(BlockId::latest()).await;
+    };
+    if let Some(value) = memo.lock().get(hash, &key) {
+        record(census_key, Served::Cache);
+        return Ok(value);
+    }
+    let value = fetch(BlockId::hash(hash)).await?;
+    memo.lock().insert(hash, key, value.clone());
+    Ok(value)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Census
+// ─────────────────────────────────────────────────────────────────────────────
+
+static CENSUS: Mutex<Option<Census>> = parking_lot::const_mutex(None);
+
+/// Abonelik bloğu (numara, hash) — bellekli okumalar buna sabitlenir
+static PINNED: Mutex<Option<(u64, B256)>> = parking_lot::const_mutex(None);
+
+/// main, run_bot öncesi — sayaçlar reconnect'lerde korunur
+pub fn init(config: &BotConfig) {
+    *CENSUS.lock() = Some(Census::new(config.redundancy_warn));
+}
+
+/// Ana döngü, yeni başlıkta: bellekli okumalar bu hash'e sabitlenir; önceki bloğun özeti
+pub fn begin_block(number: u64, hash: B256) -> Option<BlockReport> {
+    *PINNED.lock() = Some((number, hash));
+    CENSUS.lock().as_mut().and_then(|census| census.begin_block(number))
+}
+
+pub fn pinned_block() -> Option<(u64, B256)> {
+    *PINNED.lock()
+}
+
+fn record(key: CallKey, served: Served) {
+    if let Some(census) = CENSUS.lock().as_mut() {
+        census.record(key, served);
+    }
+    let _ = OPPORTUNITY.try_with(|calls| calls.lock().entry(key).or_default().add(served));
+}
+
+/// Çağrıyı bekleme süresiyle say — başarısız çağrı da RPC harcar
+pub async fn traced<T, Fut>(key: CallKey, fut: Fut) -> T
+where
+    Fut: Future<Output = T>,
+{
+    let started = Instant::now();
+    let output = fut.await;
+    record(key, Served::Network(started.elapsed()));
+    output
+}
+
+pub fn stats_lines() -> Vec<String> {
+    CENSUS.lock().as_ref().map(Census::stats_lines).unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::BotError;
+    use alloy::primitives::{U256, U64};
+    use alloy::providers::{Provider, ProviderBuilder, RootProvider};
+    use alloy::transports::mock::Asserter;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const POOL: Address = Address::with_last_byte(0xA1);
+
+    fn key(method: &'static str, site: &'static str) -> CallKey {
+        CallKey::new(method, POOL, site)
+    }
+
+    fn network(ms: u64) -> Served {
+        Served::Network(Duration::from_millis(ms))
+    }
+
+    #[test]
+    fn test_block_report_rolls_over_on_new_block() {
+        let mut census = Census::new(2);
+        assert_eq!(census.begin_block(100), None, "no previous block");
+        census.record(key("slot0+liquidity", "optimistic_refresh"), network(4));
+        census.record(key("slot0+liquidity", "optimistic_refresh"), Served::Cache);
+        census.record(key("ticks_range", "bitmap_extension"), network(10));
+        assert_eq!(census.begin_block(100), None, "same block is not a rollover");
+
+        let report = census.begin_block(101).expect("block 100 had calls");
+        assert_eq!(report.block, 100);
+        assert_eq!(report.total, CallTally { calls: 2, cached: 1, await_us: 14_000 });
+        assert_eq!(report.top[0].0.site, "bitmap_extension", "ties on count break by await time");
+        assert!(report.summary().contains("2 calls, 1 cached, 14.0ms await"));
+        assert_eq!(census.begin_block(102), None, "block 101 was empty");
+    }
+
+    #[test]
+    fn test_redundancy_flagged_per_method_and_target_across_sites() {
+        let mut census = Census::new(2);
+        let mut calls: HashMap<CallKey, CallTally> = HashMap::new();
+        calls.entry(key("slot0+liquidity", "optimistic_refresh")).or_default().add(network(1));
+        calls.entry(key("slot0+liquidity", "sync_fallback")).or_default().add(network(1));
+        // Bellekten karşılanan okuma RPC değildir — eşiğe sayılmaz
+        calls.entry(key("slot0+liquidity", "optimistic_refresh")).or_default().add(Served::Cache);
+        assert!(census.finish_opportunity(&calls).redundant.is_empty(), "2 calls is not more than 2");
+
+        calls.entry(key("slot0+liquidity", "sync_fallback")).or_default().add(network(1));
+        let other = CallKey::new("slot0+liquidity", Address::with_last_byte(0xB2), "optimistic_refresh");
+        calls.entry(other).or_default().add(network(1));
+        let report = census.finish_opportunity(&calls);
+        assert_eq!(
+            report.redundant,
+            vec![Redundant {
+                method: "slot0+liquidity",
+                target: POOL,
+                calls: 3,
+                sites: vec!["optimistic_refresh", "sync_fallback"],
+            }]
+        );
+        assert_eq!(report.total.calls, 4);
+        assert_eq!((census.opportunities, census.flagged_opportunities), (2, 1));
+
+        assert!(Census::new(0).finish_opportunity(&calls).redundant.is_empty(), "0 disables the warning");
+    }
+
+    #[test]
+    fn test_stats_lines_rank_sites_by_count() {
+        let mut census = Census::new(2);
+        assert!(census.stats_lines().is_empty());
+        for _ in 0..3 {
+            census.record(key("slot0+liquidity", "optimistic_refresh"), network(2));
+        }
+        census.record(key("ticks_range", "bitmap_extension"), network(50));
+        let lines = census.stats_lines();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("4 calls, 0 cached"));
+        assert_eq!(lines[1], "optimistic_refresh slot0+liquidity: 3 calls, 0 cached | 6.0ms total await");
+        assert!(lines[2].starts_with("bitmap_extension ticks_range: 1 calls"));
+    }
+
+    #[test]
+    fn test_block_memo_invalidates_on_new_hash_and_drops_late_writes() {
+        let (a, b) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let mut memo: BlockMemo<Address, u32> = BlockMemo::new();
+        assert_eq!(memo.get(a, &POOL), None);
+        memo.insert(a, POOL, 7);
+        assert_eq!(memo.get(a, &POOL), Some(7));
+
+        assert_eq!(memo.get(b, &POOL), None, "new block clears the memo");
+        memo.insert(a, POOL, 7);
+        assert_eq!(memo.get(b, &POOL), None, "a result read at the old hash is not cached");
+    }
+
+    /// Sabit bloktaki ikinci okuma bellekten gelir; yeni blok yeniden okutur.
+    /// Okuma, mock node'a giden gerçek bir eth_blockNumber çağrısıdır.
+    #[tokio::test]
+    async fn test_second_read_in_same_block_is_served_from_cache() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let memo: Mutex<BlockMemo<Address, U256>> = Mutex::new(BlockMemo::new());
+        let fetches = AtomicU32::new(0);
+        let census_key = key("slot0+liquidity", "optimistic_refresh");
+        let read = |block: BlockId| {
+            fetches.fetch_add(1, Ordering::Relaxed);
+            assert!(matches!(block, BlockId::Hash(_)), "memoized reads are pinned to the block hash");
+            let provider = provider.clone();
+            traced(census_key, async move {
+                provider
+                    .get_block_number()
+                    .await
+                    .map(U256::from)
+                    .map_err(|e| BotError::from_transport("eth_blockNumber", e))
+            })
+        };
+
+        let scope = OpportunityScope::new();
+        let pinned = Some(B256::with_last_byte(0x50));
+        asserter.push_success(&U64::from(500));
+        let first = scope.within(memoized(&memo, pinned, census_key, POOL, read)).await.unwrap();
+        // Node'da kuyrukta yanıt yok — ikinci okuma ağa giderse hata döner
+        let second = scope.within(memoized(&memo, pinned, census_key, POOL, read)).await.unwrap();
+        assert_eq!((first, second), (U256::from(500), U256::from(500)));
+        assert_eq!(fetches.load(Ordering::Relaxed), 1);
+        let report = scope.finish();
+        assert_eq!((report.total.calls, report.total.cached), (1, 1));
+
+        asserter.push_success(&U64::from(501));
+        let third = memoized(&memo, Some(B256::with_last_byte(0x51)), census_key, POOL, read).await.unwrap();
+        assert_eq!(third, U256::from(501), "new block hash invalidates the memo");
+        assert_eq!(fetches.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,435 @@
+// ============================================================================
+//  RUNTIME_HEALTH v1.0 — Tokio Executor Stall Tespiti
+//
+//  Bazı bloklarda sync_ms, RPC yavaşlığı görünmeden 200ms+ sıçrar: blok
+//  akışını taşıyan worker thread'i senkron bir iş (disk yazımı, REVM, KDF)
+//  bloklamıştır. Bu modül bunu ölçer ve isimlendirir:
+//
+//  ✓ Jitter probe: 10ms uyuyan arka plan görevi; uyanma gecikmesi
+//    histograma, RUNTIME_STALL_THRESHOLD_MS üstü gecikme stall penceresi
+//  ✓ İşaretli senkron bölümler (section / blocking_section): eşiği aşan
+//    bölüm doğrudan stall; probe'un yakaladığı stall en çok örtüşen bölüme
+//    atfedilir
+//  ✓ Blok penceresi bir stall ile örtüşürse blok işaretlenir (stats + uyarı)
+//  ✓ Worker busy oranları: tokio runtime metrics (worker_total_busy_duration)
+//  ✓ --cfg tokio_unstable ile derlenmişse worker başına ortalama poll süresi
+//
+//  Çok thread'li runtime'da tek worker'ın bloklanması probe'u her zaman
+//  geciktirmez (probe başka worker'da koşabilir). Bu yüzden bilinen senkron
+//  noktalar bölüm olarak işaretlenir — tespit probe'a bağlı kalmaz.
+// ============================================================================
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::executor::{HistogramSnapshot, LatencyHistogram};
+use crate::types::BotConfig;
+
+/// Probe uyku aralığı
+pub const PROBE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Saklanan son stall / bölüm sayısı
+const RECENT_CAPACITY: usize = 64;
+
+/// Bundan kısa bölümler atıf için saklanmaz
+const MIN_SECTION: Duration = Duration::from_millis(1);
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Ölçüm Matematiği
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Uyku aşımı: beklenenden ne kadar geç uyanıldı (erken uyanma → 0)
+pub fn overshoot(expected: Duration, observed: Duration) -> Duration {
+    observed.saturating_sub(expected)
+}
+
+/// Aralıkta worker'ın meşgul geçirdiği pay (0..=1); duvar süresi 0 → 0
+pub fn busy_ratio(busy: Duration, wall: Duration) -> f64 {
+    if wall.is_zero() {
+        return 0.0;
+    }
+    (busy.as_secs_f64() / wall.as_secs_f64()).clamp(0.0, 1.0)
+}
+
+/// İki [başlangıç, bitiş) penceresinin kesişim süresi
+fn overlap(a: (Instant, Instant), b: (Instant, Instant)) -> Duration {
+    a.1.min(b.1).saturating_duration_since(a.0.max(b.0))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Stall Kaydı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Worker'ın görev değiştiremediği pencere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stall {
+    pub start: Instant,
+    pub end: Instant,
+    /// Pencereyle örtüşen işaretli senkron bölüm (None = atfedilemedi)
+    pub culprit: Option<&'static str>,
+}
+
+impl Stall {
+    pub fn duration(&self) -> Duration {
+        self.end.saturating_duration_since(self.start)
+    }
+
+    fn window(&self) -> (Instant, Instant) {
+        (self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SectionSpan {
+    label: &'static str,
+    start: Instant,
+    end: Instant,
+}
+
+#[derive(Default)]
+struct Recent {
+    stalls: VecDeque<Stall>,
+    sections: VecDeque<SectionSpan>,
+}
+
+/// Stall dedektörü — süreçte tek örnek (`init`), testlerde yerel
+pub struct HealthMonitor {
+    threshold: Duration,
+    recent: Mutex<Recent>,
+    /// Probe uyanma gecikmesi (µs)
+    probe_overshoot: LatencyHistogram,
+    /// Stall süreleri (µs) — en uzun non-yielding poll'un vekili
+    stall_duration: LatencyHistogram,
+    /// Son busy örneği: (an, worker başına toplam meşgul süre)
+    busy_baseline: Mutex<Option<(Instant, Vec<Duration>)>>,
+}
+
+impl HealthMonitor {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            recent: Mutex::new(Recent::default()),
+            probe_overshoot: LatencyHistogram::new(),
+            stall_duration: LatencyHistogram::new(),
+            busy_baseline: Mutex::new(None),
+        }
+    }
+
+    /// Senkron bölümü işaretle — guard düşünce süre kaydedilir
+    pub fn section(&self, label: &'static str) -> Section<'_> {
+        Section { monitor: Some(self), label, start: Instant::now() }
+    }
+
+    fn close_section(&self, label: &'static str, start: Instant, end: Instant) {
+        let elapsed = end.saturating_duration_since(start);
+        if elapsed < MIN_SECTION {
+            return;
+        }
+        {
+            let mut recent = self.recent.lock();
+            recent.sections.push_back(SectionSpan { label, start, end });
+            if recent.sections.len() > RECENT_CAPACITY {
+                recent.sections.pop_front();
+            }
+        }
+        if elapsed >= self.threshold {
+            self.record_stall(Stall { start, end, culprit: Some(label) });
+        }
+    }
+
+    /// Probe turu: `sleep_start`'ta PROBE_INTERVAL uykusuna girildi, `woke`'ta
+    /// uyanıldı. Eşiği aşan gecikme, uyanışın gecikmesi gereken pencere olarak
+    /// kaydedilir ve en çok örtüşen bölüme atfedilir.
+    fn observe_probe(&self, sleep_start: Instant, woke: Instant) {
+        let late = overshoot(PROBE_INTERVAL, woke.saturating_duration_since(sleep_start));
+        self.probe_overshoot.record(late.as_micros() as u64);
+        if late < self.threshold {
+            return;
+        }
+        let window = (sleep_start + PROBE_INTERVAL, woke);
+        let culprit = self
+            .recent
+            .lock()
+            .sections
+            .iter()
+            .map(|s| (s.label, overlap(window, (s.start, s.end))))
+            .filter(|(_, shared)| !shared.is_zero())
+            .max_by_key(|(_, shared)| *shared)
+            .map(|(label, _)| label);
+        self.record_stall(Stall { start: window.0, end: window.1, culprit });
+    }
+
+    /// Örtüşen kayıtlar birleşir (bölüm + probe aynı stall'ı iki kez görür);
+    /// histograma yalnızca yeni stall yazılır
+    fn record_stall(&self, stall: Stall) {
+        let mut recent = self.recent.lock();
+        if let Some(existing) = recent
+            .stalls
+            .iter_mut()
+            .find(|s| !overlap(s.window(), stall.window()).is_zero())
+        {
+            existing.start = existing.start.min(stall.start);
+            existing.end = existing.end.max(stall.end);
+            existing.culprit = existing.culprit.or(stall.culprit);
+            return;
+        }
+        recent.stalls.push_back(stall);
+        if recent.stalls.len() > RECENT_CAPACITY {
+            recent.stalls.pop_front();
+        }
+        self.stall_duration.record(stall.duration().as_micros() as u64);
+    }
+
+    /// Pencereyle en çok örtüşen stall (blok işaretleme)
+    pub fn stall_overlapping(&self, start: Instant, end: Instant) -> Option<Stall> {
+        self.recent
+            .lock()
+            .stalls
+            .iter()
+            .map(|s| (*s, overlap(s.window(), (start, end))))
+            .filter(|(_, shared)| !shared.is_zero())
+            .max_by_key(|(_, shared)| *shared)
+            .map(|(stall, _)| stall)
+    }
+
+    /// Sonsuz probe döngüsü — `tokio::spawn` ile koşar
+    pub async fn run_probe(&self) {
+        loop {
+            let sleep_start = Instant::now();
+            tokio::time::sleep(PROBE_INTERVAL).await;
+            self.observe_probe(sleep_start, Instant::now());
+        }
+    }
+
+    /// Önceki çağrıdan bu yana worker başına busy oranı. İlk çağrı yalnızca
+    /// taban örneği alır (None); runtime dışında da None.
+    pub fn worker_busy_ratios(&self) -> Option<Vec<f64>> {
+        let metrics = tokio::runtime::Handle::try_current().ok()?.metrics();
+        let busy: Vec<Duration> = (0..metrics.num_workers())
+            .map(|w| metrics.worker_total_busy_duration(w))
+            .collect();
+        let now = Instant::now();
+        let mut baseline = self.busy_baseline.lock();
+        let ratios = match baseline.as_ref() {
+            Some((at, prev)) if prev.len() == busy.len() => {
+                let wall = now.saturating_duration_since(*at);
+                Some(busy.iter().zip(prev).map(|(b, p)| busy_ratio(b.saturating_sub(*p), wall)).collect())
+            }
+            _ => None,
+        };
+        *baseline = Some((now, busy));
+        ratios
+    }
+}
+
+/// İşaretli senkron bölüm guard'ı — drop'ta süre kaydedilir
+pub struct Section<'a> {
+    monitor: Option<&'a HealthMonitor>,
+    label: &'static str,
+    start: Instant,
+}
+
+impl Drop for Section<'_> {
+    fn drop(&mut self) {
+        if let Some(monitor) = self.monitor {
+            monitor.close_section(self.label, self.start, Instant::now());
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Dedektör
+// ─────────────────────────────────────────────────────────────────────────────
+
+static MONITOR: OnceLock<HealthMonitor> = OnceLock::new();
+
+/// Dedektörü kur ve probe görevini başlat (RUNTIME_STALL_THRESHOLD_MS=0 → kapalı).
+/// Runtime içinden bir kez çağrılır.
+pub fn init(config: &BotConfig) {
+    if config.runtime_stall_threshold_ms == 0 {
+        return;
+    }
+    let threshold = Duration::from_millis(config.runtime_stall_threshold_ms);
+    if MONITOR.set(HealthMonitor::new(threshold)).is_ok() {
+        if let Some(monitor) = MONITOR.get() {
+            tokio::spawn(monitor.run_probe());
+        }
+    }
+}
+
+/// Süreç dedektöründe senkron bölüm (kapalıysa no-op guard)
+pub fn section(label: &'static str) -> Section<'static> {
+    match MONITOR.get() {
+        Some(monitor) => monitor.section(label),
+        None => Section { monitor: None, label, start: Instant::now() },
+    }
+}
+
+/// Sırası korunması gereken senkron işi (disk yazımı) işaretle ve çok
+/// thread'li runtime'da `block_in_place` ile worker'ı devret — kuyruğundaki
+/// görevler diğer worker'lara geçer. Tek thread'li runtime'da (testler) ve
+/// runtime dışında doğrudan çalışır.
+pub fn blocking_section<R>(label: &'static str, work: impl FnOnce() -> R) -> R {
+    use tokio::runtime::{Handle, RuntimeFlavor};
+
+    let _section = section(label);
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(work)
+        }
+        _ => work(),
+    }
+}
+
+/// Blok penceresiyle örtüşen stall (dedektör kapalıysa None)
+pub fn stall_overlapping(start: Instant, end: Instant) -> Option<Stall> {
+    MONITOR.get()?.stall_overlapping(start, end)
+}
+
+/// Oturum özeti için (probe gecikmesi, stall süresi) histogramları
+pub fn histogram_snapshots() -> Option<(HistogramSnapshot, HistogramSnapshot)> {
+    let monitor = MONITOR.get()?;
+    Some((monitor.probe_overshoot.snapshot(), monitor.stall_duration.snapshot()))
+}
+
+/// --cfg tokio_unstable: worker başına ortalama poll süresi
+#[cfg(tokio_unstable)]
+fn mean_poll_times() -> Vec<Duration> {
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        return Vec::new();
+    };
+    let metrics = handle.metrics();
+    (0..metrics.num_workers()).map(|w| metrics.worker_mean_poll_time(w)).collect()
+}
+
+/// İstatistik kutusu satırı — probe, stall'lar ve worker busy oranları
+pub fn stats_line() -> Option<String> {
+    let monitor = MONITOR.get()?;
+    let (probes, avg_us, _, max_us) = monitor.probe_overshoot.summary();
+    let (stalls, _, _, max_stall_us) = monitor.stall_duration.summary();
+    let mut line = format!(
+        "probe {} (avg +{}µs, max +{:.1}ms) | stalls {} (max {:.1}ms)",
+        probes,
+        avg_us,
+        max_us as f64 / 1000.0,
+        stalls,
+        max_stall_us as f64 / 1000.0,
+    );
+    if let Some(ratios) = monitor.worker_busy_ratios() {
+        let busy: Vec<String> = ratios.iter().map(|r| format!("{:.0}%", r * 100.0)).collect();
+        line.push_str(&format!(" | busy [{}]", busy.join(" ")));
+    }
+    #[cfg(tokio_unstable)]
+    {
+        let polls: Vec<String> = mean_poll_times().iter().map(|d| format!("{}µs", d.as_micros())).collect();
+        line.push_str(&format!(" | mean poll [{}]", polls.join(" ")));
+    }
+    Some(line)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_overshoot_and_busy_ratio_math() {
+        assert_eq!(overshoot(ms(10), ms(9)), Duration::ZERO);
+        assert_eq!(overshoot(ms(10), ms(10)), Duration::ZERO);
+        assert_eq!(overshoot(ms(10), ms(112)), ms(102));
+
+        assert_eq!(busy_ratio(ms(500), ms(1_000)), 0.5);
+        assert_eq!(busy_ratio(ms(2_000), ms(1_000)), 1.0);
+        assert_eq!(busy_ratio(ms(5), Duration::ZERO), 0.0);
+
+        let t = Instant::now();
+        assert_eq!(overlap((t, t + ms(50)), (t + ms(40), t + ms(90))), ms(10));
+        assert_eq!(overlap((t, t + ms(50)), (t + ms(50), t + ms(90))), Duration::ZERO);
+    }
+
+    /// Eşik altı gecikme stall değildir; eşik üstü pencere örtüşen bölüme
+    /// atfedilir; bölüm + probe aynı stall'ı tek kayıtta birleştirir
+    #[test]
+    fn test_probe_threshold_attribution_and_merge() {
+        let monitor = HealthMonitor::new(ms(50));
+        let t = Instant::now();
+
+        monitor.close_section("shadow_log_write", t + ms(5), t + ms(40));
+        monitor.observe_probe(t, t + ms(13));
+        assert!(monitor.stall_overlapping(t, t + ms(60)).is_none(), "3ms gecikme stall değil");
+
+        monitor.observe_probe(t + ms(20), t + ms(100));
+        let stall = monitor.stall_overlapping(t, t + ms(120)).expect("70ms gecikme stall");
+        assert_eq!((stall.start, stall.end), (t + ms(30), t + ms(100)));
+        assert_eq!(stall.culprit, Some("shadow_log_write"));
+
+        monitor.close_section("revm_scan", t + ms(200), t + ms(300));
+        monitor.observe_probe(t + ms(195), t + ms(305));
+        let merged = monitor.stall_overlapping(t + ms(150), t + ms(400)).expect("bölüm eşiği aştı");
+        assert_eq!((merged.start, merged.end), (t + ms(200), t + ms(305)));
+        assert_eq!(merged.culprit, Some("revm_scan"));
+        assert_eq!(monitor.stall_duration.count(), 2, "birleşen kayıt iki kez sayılmaz");
+        assert_eq!(monitor.probe_overshoot.count(), 3);
+    }
+
+    /// Bloklara bölünmüş akışta kasıtlı 100ms blocking çağrı, yalnızca
+    /// içinde yapıldığı bloğa işaretlenir: bölümle sarılıysa etiketiyle,
+    /// sarılı değilse probe tarafından (atıfsız) yakalanır.
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_injected_blocking_call_flagged_on_its_block() {
+        let monitor = Arc::new(HealthMonitor::new(ms(50)));
+        let probe = tokio::spawn({
+            let monitor = Arc::clone(&monitor);
+            async move { monitor.run_probe().await }
+        });
+        tokio::time::sleep(ms(30)).await;
+
+        // Sonraki blok başlığını bekleme — probe bu arada en az bir tur atar
+        let gap = || tokio::time::sleep(ms(15));
+
+        // Blok 1: temiz (yalnızca await)
+        let b1 = Instant::now();
+        tokio::time::sleep(ms(20)).await;
+        let b1 = (b1, Instant::now());
+        gap().await;
+
+        // Blok 2: işaretli bölümde 100ms senkron iş
+        let b2 = Instant::now();
+        {
+            let _io = monitor.section("injected_block_io");
+            std::thread::sleep(ms(100));
+        }
+        tokio::time::sleep(ms(20)).await;
+        let b2 = (b2, Instant::now());
+        gap().await;
+
+        // Blok 3: işaretsiz 100ms senkron iş — yalnızca probe görür
+        let b3 = Instant::now();
+        std::thread::sleep(ms(100));
+        tokio::time::sleep(ms(20)).await;
+        let b3 = (b3, Instant::now());
+        probe.abort();
+
+        assert!(monitor.stall_overlapping(b1.0, b1.1).is_none(), "temiz blok işaretlenmemeli");
+
+        let stall = monitor.stall_overlapping(b2.0, b2.1).expect("blok 2 stall ile örtüşmeli");
+        assert!(stall.duration() >= ms(90), "{:?}", stall.duration());
+        assert_eq!(stall.culprit, Some("injected_block_io"));
+        assert!(stall.start > b1.1 && stall.end <= b2.1, "stall komşu bloklara taşmamalı");
+
+        let stall = monitor.stall_overlapping(b3.0, b3.1).expect("probe blok 3'teki stall'ı görmeli");
+        assert!(stall.duration() >= ms(50), "{:?}", stall.duration());
+        assert_eq!(stall.culprit, None);
+        assert!(stall.start > b2.1, "blok 2'nin stall'ı ile karışmamalı");
+    }
+}
@@ -0,0 +1,351 @@
+// ============================================================================
+//  SECURE_FS v1.0 — Çıktı Dosyaları İçin Merkezi Oluşturma + DATA_DIR
+//
+//  Gölge loglar, state diff'ler, snapshot'lar, gas defteri ve oturum
+//  özetleri işlem aktivitesini açık eder; paylaşımlı bir makinede varsayılan
+//  izinlerle (0o644) yazılmamalı.
+//
+//  ✓ Tüm çıktı dosyaları 0o600, dizinler 0o700 ile oluşturulur (unix);
+//    diğer platformlarda işletim sistemi varsayılanı (ACL eklenmez)
+//  ✓ Keystore / kurma dosyası grup ya da herkes tarafından yazılabilir bir
+//    dizine yazılmaz — dosyayı değiştirebilen biri anahtarı / kurmayı değiştirir
+//  ✓ DATA_DIR → tüm artefaktlar tek dizin altında (boş = çalışma dizini)
+//  ✓ Eski konumlardan (çalışma dizini) geriye uyumlu okuma: yeni konumda
+//    dosya yoksa eskisi okunur, sonraki yazım yeni konuma gider
+//  ✓ Başlangıçta çözümlenen yollar loglanır
+//
+//  İzin bitleri yalnızca oluşturma anında uygulanır; önceden var olan
+//  dosyanın izinleri değiştirilmez (atomik yazımlar yeni dosya üretir).
+// ============================================================================
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use colored::*;
+use parking_lot::Mutex;
+
+/// Çıktı dosyalarının izinleri (yalnızca sahip okur/yazar)
+pub const FILE_MODE: u32 = 0o600;
+/// Çıktı dizinlerinin izinleri (yalnızca sahip girer)
+pub const DIR_MODE: u32 = 0o700;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// DATA_DIR Çözümlemesi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Artefakt kök dizini — göreli adlar bunun altına çözümlenir
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    /// DATA_DIR değerinden (boş / tanımsız → çalışma dizini)
+    pub fn from_env_value(value: Option<&str>) -> Self {
+        let root = value
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Çalışma dizini mi? (eski konumla aynı — geri dönüş gereksiz)
+    pub fn is_cwd(&self) -> bool {
+        self.root == Path::new(".")
+    }
+
+    /// Yazım yolu: <root>/<ad>. Mutlak adlar olduğu gibi kalır.
+    pub fn path(&self, name: impl AsRef<Path>) -> PathBuf {
+        if self.is_cwd() {
+            return name.as_ref().to_path_buf();
+        }
+        self.root.join(name)
+    }
+
+    /// Okuma yolu: yeni konumda yoksa ve eski (çalışma dizini) konumda varsa
+    /// eskisi döner. İkisi de yoksa yeni konum (okuyucu "yok" olarak ele alır).
+    pub fn read_path(&self, name: impl AsRef<Path>) -> PathBuf {
+        let name = name.as_ref();
+        let path = self.path(name);
+        if path.exists() || self.is_cwd() || !name.exists() {
+            return path;
+        }
+        name.to_path_buf()
+    }
+}
+
+/// Süreç geneli kök — init öncesi çalışma dizini
+static DATA_DIR: Mutex<Option<DataDir>> = parking_lot::const_mutex(None);
+
+/// DATA_DIR'i env'den oku ve dizini (0o700) oluştur. CLI alt komutları
+/// da aynı yolları kullansın diye BotConfig'den önce, main'in başında çağrılır.
+pub fn init_from_env() -> io::Result<DataDir> {
+    let data_dir = DataDir::from_env_value(std::env::var("DATA_DIR").ok().as_deref());
+    if !data_dir.is_cwd() {
+        create_dir_all(data_dir.root())?;
+    }
+    *DATA_DIR.lock() = Some(data_dir.clone());
+    Ok(data_dir)
+}
+
+fn current() -> DataDir {
+    DATA_DIR
+        .lock()
+        .clone()
+        .unwrap_or_else(|| DataDir::from_env_value(None))
+}
+
+/// Artefaktın yazım yolu (DATA_DIR altında)
+pub fn data_path(name: impl AsRef<Path>) -> PathBuf {
+    current().path(name)
+}
+
+/// Artefaktın okuma yolu (eski konuma geri dönüşlü)
+pub fn read_path(name: impl AsRef<Path>) -> PathBuf {
+    current().read_path(name)
+}
+
+/// Başlangıç logu: kök + artefakt başına çözümlenen yol
+pub fn log_resolved_paths(artifacts: &[&str]) {
+    let data_dir = current();
+    println!(
+        "  {} Data Dir: {} (files {:o}, dirs {:o})",
+        "📁".cyan(),
+        data_dir.root().display(),
+        FILE_MODE,
+        DIR_MODE,
+    );
+    for name in artifacts {
+        let read = data_dir.read_path(name);
+        let write = data_dir.path(name);
+        if read == write {
+            println!("     {:<26} → {}", name, write.display());
+        } else {
+            println!(
+                "     {:<26} → {} (reading legacy {})",
+                name,
+                write.display(),
+                read.display()
+            );
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Dosya / Dizin Oluşturma
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Dizini (ve eksik ebeveynleri) 0o700 ile oluştur
+pub fn create_dir_all(dir: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(DIR_MODE);
+    }
+    builder.create(dir)
+}
+
+/// Dosyanın ebeveyn dizinini oluştur (göreli çıplak ad → çalışma dizini, işlem yok)
+pub fn create_parent_dir(path: &Path) -> io::Result<()> {
+    match path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        Some(dir) => create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+fn private_options() -> OpenOptions {
+    let mut options = OpenOptions::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(FILE_MODE);
+    }
+    options
+}
+
+/// Sona ekleme için aç (yoksa 0o600 ile oluşturulur)
+pub fn open_append(path: &Path) -> io::Result<File> {
+    create_parent_dir(path)?;
+    private_options().create(true).append(true).open(path)
+}
+
+/// Yazma için oluştur / kes (yoksa 0o600 ile oluşturulur)
+pub fn create(path: &Path) -> io::Result<File> {
+    create_parent_dir(path)?;
+    private_options().write(true).create(true).truncate(true).open(path)
+}
+
+/// Okuma + yazma, içerik korunur (kilit dosyaları)
+pub fn open_read_write(path: &Path) -> io::Result<File> {
+    private_options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Tüm içeriği yaz (0o600)
+pub fn write(path: &Path, bytes: impl AsRef<[u8]>) -> io::Result<()> {
+    create(path)?.write_all(bytes.as_ref())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gizli Dosyalar (Keystore / Kurma Dosyası)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Dizin grup ya da herkes tarafından yazılabilir mi? (unix dışı: hayır)
+pub fn is_shared_writable(dir: &Path) -> io::Result<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        Ok(fs::metadata(dir)?.permissions().mode() & 0o022 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        fs::metadata(dir).map(|_| false)
+    }
+}
+
+/// Gizli dosyanın dizinini hazırla: yoksa 0o700 ile oluştur, varsa ve
+/// başkaları yazabiliyorsa reddet (PermissionDenied).
+pub fn ensure_private_parent(path: &Path) -> io::Result<()> {
+    create_parent_dir(path)?;
+    let dir = match path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        Some(dir) => dir,
+        None => Path::new("."),
+    };
+    if is_shared_writable(dir)? {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "refusing to write {}: directory {} is group/world-writable (chmod go-w it or pick another location)",
+                path.display(),
+                dir.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Gizli dosyayı yaz: güvenli dizin + 0o600 + atomik rename
+pub fn write_secret(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    ensure_private_parent(path)?;
+    crate::session_summary::write_atomic(path, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("arb_secure_fs_{}_{}", tag, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[cfg(unix)]
+    fn mode(path: &Path) -> u32 {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_created_files_and_dirs_are_private() {
+        let dir = temp_dir("modes");
+        let nested = dir.join("snapshots").join("deep");
+        create_dir_all(&nested).unwrap();
+        assert_eq!(mode(&dir), DIR_MODE);
+        assert_eq!(mode(&nested), DIR_MODE);
+
+        let appended = dir.join("logs").join("shadow_analytics.jsonl");
+        writeln!(open_append(&appended).unwrap(), "{{}}").unwrap();
+        assert_eq!(mode(&dir.join("logs")), DIR_MODE, "ebeveyn de özel oluşturulmalı");
+        assert_eq!(mode(&appended), FILE_MODE);
+
+        let written = dir.join("tokens.json");
+        write(&written, b"{}").unwrap();
+        assert_eq!(mode(&written), FILE_MODE);
+
+        let secret = dir.join("keys").join("bot.json");
+        write_secret(&secret, b"{}").unwrap();
+        assert_eq!(mode(&secret), FILE_MODE);
+        assert_eq!(fs::read(&secret).unwrap(), b"{}");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secret_refused_in_shared_writable_dir() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = temp_dir("shared");
+        create_dir_all(&dir).unwrap();
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o777)).unwrap();
+
+        let path = dir.join("ARMED");
+        let err = write_secret(&path, b"{}").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(err.to_string().contains("group/world-writable"), "{}", err);
+        assert!(!path.exists(), "reddedilen dosya yazılmamalı");
+
+        // Grup yazabilir de yeterli sebep; düzeltince yazım geçer
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o770)).unwrap();
+        assert!(write_secret(&path, b"{}").is_err());
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700)).unwrap();
+        write_secret(&path, b"{}").unwrap();
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_data_dir_resolution() {
+        let cwd = DataDir::from_env_value(None);
+        assert!(cwd.is_cwd());
+        assert_eq!(DataDir::from_env_value(Some("  ")), cwd, "boş DATA_DIR = çalışma dizini");
+        assert_eq!(cwd.path("trades.json"), PathBuf::from("trades.json"));
+
+        let data_dir = DataDir::from_env_value(Some("/var/lib/arb"));
+        assert_eq!(data_dir.root(), Path::new("/var/lib/arb"));
+        assert_eq!(data_dir.path("gas_ledger.json"), PathBuf::from("/var/lib/arb/gas_ledger.json"));
+        assert_eq!(data_dir.path("snapshots"), PathBuf::from("/var/lib/arb/snapshots"));
+        // Kullanıcının verdiği mutlak yol (SUMMARY_DIR=/srv/...) kökü ezer
+        assert_eq!(data_dir.path("/srv/summaries"), PathBuf::from("/srv/summaries"));
+    }
+
+    #[test]
+    fn test_legacy_path_fallback_reads() {
+        let root = temp_dir("legacy_root");
+        create_dir_all(&root).unwrap();
+        let data_dir = DataDir::from_env_value(root.to_str());
+
+        // Eski konum çalışma dizinini temsil eden mutlak olmayan bir ad olmalı;
+        // testte göreli ad cwd'ye (crate dizini) yazılır
+        let name = format!("arb_secure_fs_legacy_{}.json", std::process::id());
+        let legacy = PathBuf::from(&name);
+        let fresh = root.join(&name);
+
+        // Hiçbiri yok → yeni konum
+        assert_eq!(data_dir.read_path(&name), fresh);
+
+        // Yalnızca eski konum → eskisi okunur, yazım yine yeni konuma
+        fs::write(&legacy, b"old").unwrap();
+        assert_eq!(data_dir.read_path(&name), legacy);
+        assert_eq!(data_dir.path(&name), fresh);
+
+        // Yeni konum yazıldıktan sonra eski yok sayılır
+        write(&fresh, b"new").unwrap();
+        assert_eq!(data_dir.read_path(&name), fresh);
+        assert_eq!(fs::read(data_dir.read_path(&name)).unwrap(), b"new");
+
+        let _ = fs::remove_file(&legacy);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,634 @@
+// ============================================================================
+//  SESSION_SUMMARY v1.0 — Makine Okunur Oturum Özeti (Filo Toplama)
+//
+//  Birden çok instance (farklı havuz çiftleri) çalıştıran operatör için
+//  terminal kazımadan gece toplanabilecek tek dosya:
+//
+//  ✓ SUMMARY_DIR/session_summary_<UTC zaman>_<neden>.json
+//  ✓ Yazım anları: temiz kapanış (Ctrl+C), MAX_RETRIES çıkışı, UTC gün dönümü
+//  ✓ İçerik: config parmak izi (sırlar hariç), başlangıç/bitiş zamanı, tüm
+//    ArbitrageStats sayaçları, aşama başına gecikme histogramları, işlem
+//    sonuçları (landed/reverted/PnL/gas), gölge toplamları, reconnect /
+//    breaker / warmup olay sayıları, derleme zamanı git hash'i (build.rs),
+//    yön başına receipt sonuçları ve etkin yön kısıtlamaları (v2)
+//  ✓ Atomik yazım: <dosya>.tmp + rename — yarım dosya asla görünmez
+//  ✓ Gün dönümü yazımı stats kopyasıyla spawn_blocking'de — hot loop beklemez
+// ============================================================================
+
+use chrono::{DateTime, NaiveDate, Utc};
+use eyre::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::direction_gate::DirectionSummary;
+use crate::executor::{HistogramSnapshot, MevExecutor, TradeOutcomes};
+use crate::types::{ArbitrageStats, BotConfig, PoolConfig};
+use crate::warmup::WarmupGate;
+
+/// Şema değiştiğinde artırılır — toplayıcı eski dosyaları ayırt edebilsin
+pub const SUMMARY_SCHEMA_VERSION: u32 = 2;
+
+/// build.rs'in gömdüğü commit hash'i
+pub const GIT_HASH: &str = env!("GIT_HASH");
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Özet Şeması
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Özetin yazılma nedeni
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryReason {
+    /// Ctrl+C / SIGINT ile temiz kapanış
+    Shutdown,
+    /// MAX_RETRIES aşıldı — bot kendini durdurdu
+    BreakerExit,
+    /// UTC gün dönümü — oturum sürüyor
+    DailyRollover,
+    /// TUI'den anlık istek (`s` tuşu) — oturum sürüyor
+    OnDemand,
+}
+
+impl SummaryReason {
+    fn label(self) -> &'static str {
+        match self {
+            SummaryReason::Shutdown => "shutdown",
+            SummaryReason::BreakerExit => "breaker_exit",
+            SummaryReason::DailyRollover => "daily_rollover",
+            SummaryReason::OnDemand => "on_demand",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_hash: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PoolFingerprint {
+    pub name: String,
+    pub address: String,
+    pub dex: String,
+    pub fee_bps: u32,
+}
+
+/// Çalışan yapılandırmanın kimliği. `hash` aynı havuz + eşik setinde
+/// her instance'ta aynıdır; sırlar (URL, key, token) hiç yazılmaz.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigFingerprint {
+    pub hash: String,
+    pub mode: &'static str,
+    pub pools: Vec<PoolFingerprint>,
+    pub settings: BTreeMap<&'static str, String>,
+}
+
+/// Maskelenmiş olsa bile özete girmeyecek değişkenler
+pub(crate) fn is_secret_key(key: &str) -> bool {
+    ["URL", "KEY", "TOKEN", "CHAT_ID", "PASSWORD", "_PATH"]
+        .iter()
+        .any(|marker| key.contains(marker))
+}
+
+impl ConfigFingerprint {
+    pub fn new(config: &BotConfig, pools: &[PoolConfig]) -> Self {
+        let mode = if config.execution_enabled() { "live" } else { "shadow" };
+        Self::from_parts(mode, pools, config.redacted_entries())
+    }
+
+    pub fn from_parts(
+        mode: &'static str,
+        pools: &[PoolConfig],
+        entries: Vec<(&'static str, String)>,
+    ) -> Self {
+        let pools: Vec<PoolFingerprint> = pools
+            .iter()
+            .map(|p| PoolFingerprint {
+                name: p.name.clone(),
+                address: p.address.to_string(),
+                dex: p.dex.to_string(),
+                fee_bps: p.fee_bps(),
+            })
+            .collect();
+        let settings: BTreeMap<&'static str, String> = entries
+            .into_iter()
+            .filter(|(key, _)| !is_secret_key(key))
+            .collect();
+
+        let mut hasher = Sha256::new();
+        hasher.update(mode.as_bytes());
+        for p in &pools {
+            hasher.update(format!("|{}:{}:{}", p.address, p.dex, p.fee_bps).as_bytes());
+        }
+        for (key, value) in &settings {
+            hasher.update(format!("|{}={}", key, value).as_bytes());
+        }
+        let hash = hex::encode(&hasher.finalize()[..8]);
+
+        Self { hash, mode, pools, settings }
+    }
+}
+
+/// Executor kaynaklı toplamlar — reconnect'te executor yeniden kurulduğu
+/// için önceki bağlantılarınki `carried` olarak taşınır.
+#[derive(Debug, Clone, Default)]
+struct ExecutorTotals {
+    sign: HistogramSnapshot,
+    send: HistogramSnapshot,
+    trades: TradeOutcomes,
+}
+
+impl ExecutorTotals {
+    fn capture(executor: &MevExecutor) -> Self {
+        Self {
+            sign: executor.sign_latency.snapshot(),
+            send: executor.send_latency.snapshot(),
+            trades: *executor.trade_outcomes.lock(),
+        }
+    }
+
+    fn merge(&mut self, other: &ExecutorTotals) {
+        self.sign.merge(&other.sign);
+        self.send.merge(&other.send);
+        self.trades.merge(&other.trades);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ShadowTotals {
+    pub sim_success: u64,
+    pub sim_fail: u64,
+    pub cumulative_profit_weth: f64,
+    pub competition_skips: u64,
+    pub competition_skips_would_succeed: u64,
+    pub constant_product_shadowed: u64,
+    pub sim_math_discrepancy_skips: u64,
+    pub warmup_suppressed: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SessionEvents {
+    pub reconnects: u64,
+    pub breaker_trips: u64,
+    pub warmup_resets: u64,
+    pub warmup_interrupted: u64,
+    pub data_quality_errors: u64,
+    pub rpc_retries: u64,
+    pub unpinned_syncs: u64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct SessionSummary {
+    pub schema_version: u32,
+    pub reason: SummaryReason,
+    pub build: BuildInfo,
+    pub started_at: String,
+    pub ended_at: String,
+    pub config: ConfigFingerprint,
+    pub stats: ArbitrageStats,
+    /// Aşama adı → histogram (µs kovaları)
+    pub latency: BTreeMap<&'static str, HistogramSnapshot>,
+    pub trades: TradeOutcomes,
+    pub shadow: ShadowTotals,
+    pub events: SessionEvents,
+    /// (buy, sell) yönü başına landed / reverted / PnL + etkin kısıtlama
+    pub directions: Vec<DirectionSummary>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Oturum İzleyici
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// İmza / gönderim (executor) + sessiz / tam blok işleme süreleri (FAST_PATH_SYNC)
+/// + runtime probe gecikmesi ve executor stall süreleri (RUNTIME_STALL_THRESHOLD_MS)
+fn latency_stages(totals: &ExecutorTotals) -> BTreeMap<&'static str, HistogramSnapshot> {
+    let mut stages = BTreeMap::from([
+        ("sign", totals.sign.clone()),
+        ("send", totals.send.clone()),
+        ("block_quiet", crate::state_sync::QUIET_BLOCK_LATENCY.snapshot()),
+        ("block_full", crate::state_sync::FULL_BLOCK_LATENCY.snapshot()),
+    ]);
+    if let Some((probe, stall)) = crate::runtime_health::histogram_snapshots() {
+        stages.insert("runtime_probe", probe);
+        stages.insert("runtime_stall", stall);
+    }
+    stages
+}
+
+/// main() içinde bir kez oluşturulur; reconnect'ler boyunca yaşar.
+pub struct SessionTracker {
+    dir: PathBuf,
+    started_at: DateTime<Utc>,
+    summary_day: NaiveDate,
+    executor: Option<Arc<MevExecutor>>,
+    carried: ExecutorTotals,
+    /// Yeniden bağlanma sayısı (main döngüsü artırır)
+    pub reconnects: u64,
+}
+
+impl SessionTracker {
+    pub fn new(dir: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            dir: crate::secure_fs::data_path(dir),
+            started_at: now,
+            summary_day: now.date_naive(),
+            executor: None,
+            carried: ExecutorTotals::default(),
+            reconnects: 0,
+        }
+    }
+
+    /// Yeni bağlantının executor'ını izle; öncekinin sayaçları taşınır
+    pub fn attach_executor(&mut self, executor: Arc<MevExecutor>) {
+        if let Some(previous) = self.executor.replace(executor) {
+            self.carried.merge(&ExecutorTotals::capture(&previous));
+        }
+    }
+
+    /// UTC gün değiştiyse true (gün başına bir kez)
+    pub fn rollover_due(&mut self, now: DateTime<Utc>) -> bool {
+        let today = now.date_naive();
+        if today == self.summary_day {
+            return false;
+        }
+        self.summary_day = today;
+        true
+    }
+
+    /// Taşınan + aktif executor toplamları
+    fn totals(&self) -> ExecutorTotals {
+        let mut totals = self.carried.clone();
+        if let Some(ref executor) = self.executor {
+            totals.merge(&ExecutorTotals::capture(executor));
+        }
+        totals
+    }
+
+    /// Aşama adı → histogram (özetteki `latency` alanıyla aynı kaynak)
+    pub fn latency(&self) -> BTreeMap<&'static str, HistogramSnapshot> {
+        latency_stages(&self.totals())
+    }
+
+    /// O anki sayaçların kopyasından özet oluştur (I/O yok)
+    pub fn build(
+        &self,
+        reason: SummaryReason,
+        now: DateTime<Utc>,
+        config: ConfigFingerprint,
+        stats: &ArbitrageStats,
+        warmup: &WarmupGate,
+    ) -> SessionSummary {
+        let totals = self.totals();
+        let latency = latency_stages(&totals);
+
+        SessionSummary {
+            schema_version: SUMMARY_SCHEMA_VERSION,
+            reason,
+            build: BuildInfo {
+                version: env!("CARGO_PKG_VERSION"),
+                git_hash: GIT_HASH,
+            },
+            started_at: self.started_at.to_rfc3339(),
+            ended_at: now.to_rfc3339(),
+            config,
+            stats: stats.clone(),
+            latency,
+            trades: totals.trades,
+            shadow: ShadowTotals {
+                sim_success: stats.shadow_sim_success,
+                sim_fail: stats.shadow_sim_fail,
+                cumulative_profit_weth: stats.shadow_cumulative_profit,
+                competition_skips: stats.competition_skips,
+                competition_skips_would_succeed: stats.competition_skips_would_succeed,
+                constant_product_shadowed: stats.constant_product_shadowed,
+                sim_math_discrepancy_skips: stats.sim_math_discrepancy_skips,
+                warmup_suppressed: stats.warmup_suppressed,
+            },
+            events: SessionEvents {
+                reconnects: self.reconnects,
+                breaker_trips: stats.breaker_trips,
+                warmup_resets: warmup.resets(),
+                warmup_interrupted: warmup.interrupted(),
+                data_quality_errors: crate::state_sync::data_quality_errors(),
+                rpc_retries: crate::state_sync::rpc_retries(),
+                unpinned_syncs: crate::state_sync::unpinned_syncs(),
+            },
+            directions: crate::direction_gate::summaries(),
+        }
+    }
+
+    /// Kapanış yolunda senkron yazım (Ctrl+C, MAX_RETRIES çıkışı)
+    pub fn write_final(
+        &self,
+        reason: SummaryReason,
+        config: &BotConfig,
+        pools: &[PoolConfig],
+        stats: &ArbitrageStats,
+        warmup: &WarmupGate,
+    ) {
+        let fingerprint = ConfigFingerprint::new(config, pools);
+        let summary = self.build(reason, crate::clock::now(), fingerprint, stats, warmup);
+        match write_summary(&self.dir, &summary) {
+            Ok(path) => println!("  🧾 Session summary written: {}", path.display()),
+            Err(e) => eprintln!("  ⚠️ [Summary] Write error: {}", e),
+        }
+    }
+
+    /// Oturum sürerken yazım (gün dönümü, TUI isteği) — hot loop'u bekletmez
+    pub fn spawn_write(&self, summary: SessionSummary) {
+        let dir = self.dir.clone();
+        let label = summary.reason.label();
+        tokio::task::spawn_blocking(move || match write_summary(&dir, &summary) {
+            Ok(path) => eprintln!("  🧾 [Summary] {} summary written: {}", label, path.display()),
+            Err(e) => eprintln!("  ⚠️ [Summary] {} write error: {}", label, e),
+        });
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Disk I/O — Atomik Yazım
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// <dir>/session_summary_<YYYYMMDDTHHMMSSZ>_<neden>.json
+pub fn summary_path(dir: &Path, summary: &SessionSummary) -> PathBuf {
+    let stamp = DateTime::parse_from_rfc3339(&summary.ended_at)
+        .map(|t| t.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string())
+        .unwrap_or_else(|_| crate::clock::now().format("%Y%m%dT%H%M%SZ").to_string());
+    dir.join(format!("session_summary_{}_{}.json", stamp, summary.reason.label()))
+}
+
+pub fn write_summary(dir: &Path, summary: &SessionSummary) -> Result<PathBuf> {
+    crate::secure_fs::create_dir_all(dir)?;
+    let path = summary_path(dir, summary);
+    let json = serde_json::to_vec_pretty(summary)?;
+    write_atomic(&path, &json)?;
+    Ok(path)
+}
+
+/// tmp dosyaya yaz + fsync + rename. Okuyucu ya eski dosyayı ya da
+/// tam yeni dosyayı görür. tmp 0o600 ile oluşur — rename izni korur.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    write_atomic_with(path, bytes, |from, to| std::fs::rename(from, to))
+}
+
+fn write_atomic_with(
+    path: &Path,
+    bytes: &[u8],
+    rename: impl FnOnce(&Path, &Path) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let tmp = tmp_path(path);
+    {
+        let mut file = crate::secure_fs::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+    if let Err(e) = rename(&tmp, path) {
+        // Yarım kalan tmp dizinde birikmesin; hedef dosyaya dokunulmadı
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DexType, FeeTier};
+    use alloy::primitives::address;
+    use chrono::TimeZone;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "arb_summary_{}_{}_{}",
+            tag,
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or(0)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn make_pool() -> PoolConfig {
+        PoolConfig {
+            address: address!("d0b53D9277642d899DF5C87A3966A349A798F224"),
+            name: "WETH/USDC-UniV3".into(),
+            fee: FeeTier::from_bps(5),
+            token0_decimals: 18,
+            token1_decimals: 6,
+            dex: DexType::UniswapV3,
+            token0_is_weth: true,
+            tick_spacing: 10,
+            quote_token_address: address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+            base_token_address: address!("4200000000000000000000000000000000000006"),
+        }
+    }
+
+    fn entries(min_profit: &str) -> Vec<(&'static str, String)> {
+        vec![
+            ("RPC_WSS_URL", "wss://***".into()),
+            ("PRIVATE_KEY", "0x12…ab".into()),
+            ("TELEGRAM_BOT_TOKEN", "12…xy".into()),
+            ("TELEGRAM_CHAT_ID", "98…76".into()),
+            ("KEYSTORE_PATH", "/home/bot/keystore.json".into()),
+            ("MIN_NET_PROFIT_WETH", min_profit.into()),
+            ("CIRCUIT_BREAKER_THRESHOLD", "3".into()),
+        ]
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn make_summary(reason: SummaryReason) -> SessionSummary {
+        let mut tracker = SessionTracker::new("unused", at(1, 8));
+        tracker.reconnects = 2;
+        let executor = Arc::new(MevExecutor::new(None, 0.25, None, 2));
+        executor.sign_latency.record(300);
+        executor.trade_outcomes.lock().record_receipt(true, 2_000_000_000_000, 0.01);
+        tracker.attach_executor(executor);
+
+        let mut stats = ArbitrageStats::new();
+        stats.total_blocks_processed = 42;
+        stats.breaker_trips = 1;
+        stats.shadow_sim_success = 7;
+        let warmup = WarmupGate::new(3);
+        let fingerprint = ConfigFingerprint::from_parts("shadow", &[make_pool()], entries("0.0002"));
+        tracker.build(reason, at(1, 20), fingerprint, &stats, &warmup)
+    }
+
+    fn keys(value: &serde_json::Value) -> Vec<String> {
+        let mut keys: Vec<String> = value.as_object().expect("object").keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_summary_schema() {
+        let json = serde_json::to_value(make_summary(SummaryReason::Shutdown)).unwrap();
+        assert_eq!(
+            keys(&json),
+            [
+                "build", "config", "directions", "ended_at", "events", "latency", "reason",
+                "schema_version", "shadow", "started_at", "stats", "trades",
+            ]
+        );
+        // Receipt gelmeden yön satırı yok
+        assert_eq!(json["directions"], serde_json::json!([]));
+        assert_eq!(json["schema_version"], SUMMARY_SCHEMA_VERSION);
+        assert_eq!(json["reason"], "shutdown");
+        assert!(!json["build"]["git_hash"].as_str().unwrap().is_empty());
+        assert_eq!(json["started_at"], "2026-03-01T08:00:00+00:00");
+
+        // Her ArbitrageStats sayacı var, Instant alanları yok
+        let stats = &json["stats"];
+        for counter in [
+            "total_blocks_processed", "total_opportunities", "profitable_opportunities",
+            "executed_trades", "failed_simulations", "max_spread_pct", "max_profit_weth",
+            "total_potential_profit", "active_transport", "avg_block_latency_ms",
+            "min_block_latency_ms", "tick_bitmap_syncs", "consecutive_failures", "failure_kinds",
+            "max_block_latency_ms", "latency_spikes", "shadow_sim_success", "shadow_sim_fail",
+            "shadow_cumulative_profit", "competition_penalty_blocks", "competition_skips",
+            "competition_skips_would_succeed", "constant_product_shadowed", "sim_math_checks", "sim_math_max_discrepancy_wei",
+            "sim_math_discrepancy_skips", "bitmap_quality_counts", "sim_source_counts",
+            "warmup_suppressed", "sim_gas_checks", "gas_rejected_opportunities",
+            "breaker_trips", "blocks_skipped_backlog", "quiet_blocks", "bitmap_extensions",
+            "state_divergence_alarms", "bytecode_changes", "direction_invariant_rejections",
+            "inflight_cap_skips", "peak_inflight_executions", "reversed_route_wins",
+            "foregone_route_improvement_weth", "profit_attribution", "shed_blocks",
+            "sync_latency_by_transport",
+        ] {
+            assert!(stats.get(counter).is_some(), "missing stats.{}", counter);
+        }
+        assert!(stats.get("session_start").is_none());
+        assert_eq!(stats["total_blocks_processed"], 42);
+
+        assert_eq!(keys(&json["latency"]), ["block_full", "block_quiet", "send", "sign"]);
+        assert_eq!(
+            keys(&json["latency"]["sign"]),
+            ["bucket_counts", "bucket_upper_us", "count", "max_us", "total_us"]
+        );
+        assert_eq!(json["latency"]["sign"]["count"], 1);
+        assert_eq!(
+            keys(&json["trades"]),
+            [
+                "correction_pnl_weth", "corrections", "dropped", "expired", "finalized", "gas_spent_wei", "landed",
+                "provisional", "realized_pnl_weth", "reorged", "reverted", "submitted", "vanished",
+            ]
+        );
+        assert_eq!(json["trades"]["landed"], 1);
+        assert_eq!(json["shadow"]["sim_success"], 7);
+        assert_eq!(
+            keys(&json["events"]),
+            [
+                "breaker_trips",
+                "data_quality_errors",
+                "reconnects",
+                "rpc_retries",
+                "unpinned_syncs",
+                "warmup_interrupted",
+                "warmup_resets"
+            ]
+        );
+        assert_eq!(json["events"]["reconnects"], 2);
+        assert_eq!(json["events"]["breaker_trips"], 1);
+    }
+
+    #[test]
+    fn test_config_fingerprint_excludes_secrets() {
+        let fp = ConfigFingerprint::from_parts("shadow", &[make_pool()], entries("0.0002"));
+        let settings: Vec<&str> = fp.settings.keys().copied().collect();
+        assert_eq!(settings, ["CIRCUIT_BREAKER_THRESHOLD", "MIN_NET_PROFIT_WETH"]);
+        let json = serde_json::to_string(&fp).unwrap();
+        assert!(!json.contains("wss://") && !json.contains("keystore"));
+
+        assert_eq!(fp.hash.len(), 16);
+        let same = ConfigFingerprint::from_parts("shadow", &[make_pool()], entries("0.0002"));
+        assert_eq!(fp.hash, same.hash);
+        let other = ConfigFingerprint::from_parts("shadow", &[make_pool()], entries("0.0005"));
+        assert_ne!(fp.hash, other.hash);
+    }
+
+    #[test]
+    fn test_executor_totals_survive_reconnect() {
+        let mut tracker = SessionTracker::new("unused", at(1, 8));
+        let first = Arc::new(MevExecutor::new(None, 0.25, None, 2));
+        first.send_latency.record(2_000);
+        first.trade_outcomes.lock().record_receipt(false, 1_000_000_000_000, 0.01);
+        tracker.attach_executor(first);
+
+        let second = Arc::new(MevExecutor::new(None, 0.25, None, 2));
+        second.send_latency.record(60_000);
+        second.trade_outcomes.lock().record_receipt(true, 1_000_000_000_000, 0.01);
+        tracker.attach_executor(second);
+
+        let fp = ConfigFingerprint::from_parts("shadow", &[], Vec::new());
+        let summary = tracker.build(
+            SummaryReason::DailyRollover,
+            at(2, 0),
+            fp,
+            &ArbitrageStats::new(),
+            &WarmupGate::new(0),
+        );
+        assert_eq!(summary.latency["send"].count, 2);
+        assert_eq!(summary.latency["send"].max_us, 60_000);
+        assert_eq!((summary.trades.landed, summary.trades.reverted), (1, 1));
+        assert_eq!(summary.trades.gas_spent_wei, 2_000_000_000_000);
+        assert!((summary.trades.realized_pnl_weth - (0.01 - 0.000002)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rollover_once_per_utc_day() {
+        let mut tracker = SessionTracker::new("unused", at(1, 8));
+        assert!(!tracker.rollover_due(at(1, 23)));
+        assert!(tracker.rollover_due(at(2, 0)));
+        assert!(!tracker.rollover_due(at(2, 12)));
+        assert!(tracker.rollover_due(at(4, 1)));
+    }
+
+    #[test]
+    fn test_write_summary_file_name_and_content() {
+        let dir = temp_dir("write");
+        let summary = make_summary(SummaryReason::DailyRollover);
+        let path = write_summary(&dir, &summary).unwrap();
+        assert_eq!(
+            path.file_name().unwrap().to_str().unwrap(),
+            "session_summary_20260301T200000Z_daily_rollover.json"
+        );
+        let parsed: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(parsed["reason"], "daily_rollover");
+        assert!(!tmp_path(&path).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_atomic_write_failure_before_rename_keeps_old_file() {
+        let dir = temp_dir("atomic");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session_summary.json");
+        std::fs::write(&path, b"old").unwrap();
+
+        // tmp yazıldı, rename'den önce süreç "çöktü"
+        let result = write_atomic_with(&path, b"new", |_, _| {
+            Err(std::io::Error::other("simulated crash before rename"))
+        });
+        assert!(result.is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"old");
+        assert!(!tmp_path(&path).exists(), "tmp file must be cleaned up");
+
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
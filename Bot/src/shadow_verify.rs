@@ -0,0 +1,436 @@
+// ============================================================================
+//  SHADOW_VERIFY v1.0 — Gölge Fırsatların Zincir Üstü Akıbeti
+//
+//  Gölge log fırsatın karar anındaki kârını kaydeder; fırsatın birkaç blok
+//  sonra zincirde hâlâ yaşayıp yaşamadığını söylemez. Bu modül her gölge
+//  kaydını SHADOW_VERIFY_BLOCKS blok sonra yeniden fiyatlar:
+//
+//  ✓ Gölge kaydı yazılınca fırsat (opportunity_id + havuz yapılandırması +
+//    state Arc'ları) bekleme kuyruğuna girer
+//  ✓ Ana döngü her blokta vadesi gelen kayıtları doğrulama görevine devreder
+//  ✓ Görev iki havuzun slot0 + liquidity'sini hedef bloğa sabitli okur ve
+//    aynı optimal_amount_weth ile exact kârı yeniden hesaplar
+//  ✓ Gerçekleşecek kâr = beklenen kâr + (hedef bloktaki exact kâr − karar
+//    anındaki exact kâr) — flash / gas kalemleri sabit tutulur
+//  ✓ shadow_analytics'e "shadow_verify" kaydı, gölge kaydıyla aynı
+//    opportunity_id ile (replay / --verify-log bu kayıtları karar saymaz)
+//  ✓ Hız sınırı: dakikada en fazla SHADOW_VERIFY_PER_MIN kayıt; aşan ya da
+//    kuyruk doluyken gelen kayıtlar düşürülür ve sayılır
+//
+//  Not: tick bitmap karar anındakidir — hedef bloğa kadar eklenen/çekilen
+//  likidite başlangıç liquidity'si dışında yansımaz.
+//  Doğrulama ana döngüyü beklemez: RPC okuması ayrı görevde, kuyruk süreç
+//  geneli tek kilitte (impact_verification deseni).
+// ============================================================================
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::console::println_low;
+use crate::types::{ArbitrageOpportunity, BotConfig, PoolConfig, PoolState};
+
+/// Vadesi gelmemiş kayıt üst sınırı (RPC geride kalırsa bellek büyümesin)
+const MAX_PENDING: usize = 256;
+
+/// Hız sınırı penceresi
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kayıt ve Yeniden Fiyatlama
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Doğrulanacak gölge fırsat — karar anındaki tam girdiler
+#[derive(Debug, Clone)]
+pub struct ShadowEntry {
+    /// Gölge log satırının opportunity_id'si
+    pub entry_id: String,
+    /// Gölge kaydının bloğu
+    pub block: u64,
+    /// Okumaların sabitlendiği blok (block + SHADOW_VERIFY_BLOCKS)
+    pub verify_block: u64,
+    pub opportunity: ArbitrageOpportunity,
+    pub pools: [PoolConfig; 2],
+    pub states: [Arc<PoolState>; 2],
+}
+
+/// Havuzun hedef bloktaki okuması: (sqrtPriceX96, tick, liquidity)
+pub type PoolRead = (alloy::primitives::U256, i32, u128);
+
+/// Hedef bloktaki yeniden fiyatlama sonucu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerifyOutcome {
+    /// Karar anındaki state'te exact kâr (WETH)
+    pub base_exact_weth: f64,
+    /// Hedef bloktaki state'te aynı miktarla exact kâr (WETH)
+    pub later_exact_weth: f64,
+    /// Hedef blokta gerçekleşecek net kâr (beklenen kârla aynı maliyetler)
+    pub realized_profit_weth: f64,
+}
+
+impl VerifyOutcome {
+    pub fn still_profitable(&self) -> bool {
+        self.realized_profit_weth > 0.0
+    }
+}
+
+impl ShadowEntry {
+    /// Hedef bloktaki okumalarla aynı miktarı yeniden fiyatla. Yalnızca
+    /// fiyat / tick / liquidity değişir; bitmap karar anındaki haliyle kalır.
+    pub fn reprice(&self, observed: [PoolRead; 2]) -> VerifyOutcome {
+        let later = [0, 1].map(|i| {
+            let (sqrt_price_x96, tick, liquidity) = observed[i];
+            PoolState {
+                sqrt_price_x96,
+                tick,
+                liquidity,
+                last_block: self.verify_block,
+                ..(*self.states[i]).clone()
+            }
+        });
+        let base_exact_weth = crate::strategy::exact_profit_weth_for_opportunity(
+            &self.pools,
+            &self.states[0],
+            &self.states[1],
+            &self.opportunity,
+        );
+        let later_exact_weth =
+            crate::strategy::exact_profit_weth_for_opportunity(&self.pools, &later[0], &later[1], &self.opportunity);
+        VerifyOutcome {
+            base_exact_weth,
+            later_exact_weth,
+            realized_profit_weth: self.opportunity.expected_profit_weth + later_exact_weth - base_exact_weth,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Bekleme Kuyruğu + Hız Sınırı
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct VerifyStats {
+    verified: u64,
+    still_profitable: u64,
+    above_min: u64,
+    dropped: u64,
+    failed: u64,
+}
+
+#[derive(Debug)]
+struct ShadowVerifier {
+    delay_blocks: u64,
+    per_min: usize,
+    /// Vade (verify_block) sırasında — gölge kayıtları blok sırasıyla gelir
+    pending: VecDeque<ShadowEntry>,
+    /// Son RATE_WINDOW içinde kabul edilen kayıtların zamanları
+    admitted: VecDeque<Instant>,
+    stats: VerifyStats,
+}
+
+impl ShadowVerifier {
+    fn new(delay_blocks: u64, per_min: usize) -> Self {
+        Self {
+            delay_blocks,
+            per_min,
+            pending: VecDeque::new(),
+            admitted: VecDeque::new(),
+            stats: VerifyStats::default(),
+        }
+    }
+
+    /// Kaydı kuyruğa al — hız sınırı aşıldıysa ya da kuyruk doluysa düşür
+    fn submit(&mut self, mut entry: ShadowEntry, now: Instant) -> bool {
+        while self.admitted.front().is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW) {
+            self.admitted.pop_front();
+        }
+        if self.admitted.len() >= self.per_min || self.pending.len() >= MAX_PENDING {
+            self.stats.dropped += 1;
+            return false;
+        }
+        self.admitted.push_back(now);
+        entry.verify_block = entry.block + self.delay_blocks;
+        self.pending.push_back(entry);
+        true
+    }
+
+    /// `block` itibarıyla vadesi gelen kayıtlar
+    fn take_due(&mut self, block: u64) -> Vec<ShadowEntry> {
+        let due = self.pending.iter().take_while(|e| e.verify_block <= block).count();
+        self.pending.drain(..due).collect()
+    }
+
+    fn record(&mut self, outcome: &VerifyOutcome, min_profit_weth: f64) {
+        self.stats.verified += 1;
+        if outcome.still_profitable() {
+            self.stats.still_profitable += 1;
+        }
+        if outcome.realized_profit_weth >= min_profit_weth {
+            self.stats.above_min += 1;
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Doğrulayıcı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// init öncesi / SHADOW_VERIFY_BLOCKS=0 → None
+static VERIFIER: Mutex<Option<ShadowVerifier>> = parking_lot::const_mutex(None);
+
+/// Ana döngüden doğrulama görevine kuyruk — her bağlantıda yenilenir
+static DUE: Mutex<Option<mpsc::UnboundedSender<ShadowEntry>>> = parking_lot::const_mutex(None);
+
+/// SHADOW_VERIFY_BLOCKS > 0 ise kuyruğu kur (main, run_bot öncesi)
+pub fn init(config: &BotConfig) {
+    if config.shadow_verify_blocks == 0 {
+        return;
+    }
+    *VERIFIER.lock() = Some(ShadowVerifier::new(
+        config.shadow_verify_blocks,
+        config.shadow_verify_per_min as usize,
+    ));
+}
+
+/// Gölge kaydını doğrulamaya öner — yalnızca Arc/yapılandırma klonları
+pub fn submit(entry_id: String, opportunity: &ArbitrageOpportunity, pools: &[PoolConfig], states: [Arc<PoolState>; 2]) {
+    let mut guard = VERIFIER.lock();
+    let Some(verifier) = guard.as_mut() else {
+        return;
+    };
+    let block = states[0].last_block.max(states[1].last_block);
+    verifier.submit(
+        ShadowEntry {
+            entry_id,
+            block,
+            verify_block: block,
+            opportunity: opportunity.clone(),
+            pools: [pools[0].clone(), pools[1].clone()],
+            states,
+        },
+        Instant::now(),
+    );
+}
+
+/// Ana döngü, blok başında: vadesi gelen kayıtları göreve devret (beklemez)
+pub fn on_block(block: u64) {
+    let due = match VERIFIER.lock().as_mut() {
+        Some(verifier) => verifier.take_due(block),
+        None => return,
+    };
+    if due.is_empty() {
+        return;
+    }
+    match DUE.lock().as_ref() {
+        Some(tx) => {
+            for entry in due {
+                let _ = tx.send(entry);
+            }
+        }
+        // Görev yok (bağlantı arası) — kayıtlar doğrulanamadı
+        None => {
+            if let Some(verifier) = VERIFIER.lock().as_mut() {
+                verifier.stats.dropped += due.len() as u64;
+            }
+        }
+    }
+}
+
+/// İstatistik kutusu satırı — doğrulanan kayıt yoksa None
+pub fn stats_line() -> Option<String> {
+    let guard = VERIFIER.lock();
+    let verifier = guard.as_ref()?;
+    let stats = verifier.stats;
+    if stats.verified == 0 && stats.dropped == 0 && stats.failed == 0 {
+        return None;
+    }
+    Some(format!(
+        "+{} blocks: {} verified | still profitable {} ({:.1}%) | above min {} | dropped {} | failed {}",
+        verifier.delay_blocks,
+        stats.verified,
+        stats.still_profitable,
+        if stats.verified > 0 { stats.still_profitable as f64 / stats.verified as f64 * 100.0 } else { 0.0 },
+        stats.above_min,
+        stats.dropped,
+        stats.failed,
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sabitli Okuma → Yeniden Fiyatlama → Log
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Doğrulama görevini başlat (run_bot, bağlantı başına). Kayıt başına iki
+/// sabitli havuz okuması; bağlantı kesilince görev biter.
+pub fn spawn_verifier<P>(provider: P, config: &BotConfig, cancel: CancellationToken)
+where
+    P: alloy::providers::Provider + Clone + Send + Sync + 'static,
+{
+    if VERIFIER.lock().is_none() {
+        return;
+    }
+    let (tx, mut rx) = mpsc::unbounded_channel::<ShadowEntry>();
+    *DUE.lock() = Some(tx);
+    let min_profit_weth = config.min_net_profit_weth;
+    let binary = config.log_format_binary;
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                entry = rx.recv() => match entry {
+                    Some(entry) => verify_entry(&provider, &entry, min_profit_weth, binary).await,
+                    None => break,
+                },
+            }
+        }
+    });
+}
+
+/// İki havuzu hedef blokta oku, kârı yeniden hesapla, kaydı gölge loga ekle
+async fn verify_entry<P: alloy::providers::Provider + Sync>(
+    provider: &P,
+    entry: &ShadowEntry,
+    min_profit_weth: f64,
+    binary: bool,
+) {
+    let (read_a, read_b) = tokio::join!(
+        crate::state_sync::read_pool_at_block(provider, &entry.pools[0], entry.verify_block),
+        crate::state_sync::read_pool_at_block(provider, &entry.pools[1], entry.verify_block),
+    );
+    let observed = match (read_a, read_b) {
+        (Ok(a), Ok(b)) => [a, b],
+        (Err(e), _) | (_, Err(e)) => {
+            if let Some(verifier) = VERIFIER.lock().as_mut() {
+                verifier.stats.failed += 1;
+            }
+            eprintln!(
+                "     ⚠️  [ShadowVerify] {} read at block #{} failed: {}",
+                entry.entry_id, entry.verify_block, e
+            );
+            return;
+        }
+    };
+    let outcome = entry.reprice(observed);
+    if let Some(verifier) = VERIFIER.lock().as_mut() {
+        verifier.record(&outcome, min_profit_weth);
+    }
+
+    println_low!(
+        "     🔁 [ShadowVerify] {} @ #{} | expected {:.8} → realized {:.8} WETH{}",
+        entry.entry_id,
+        entry.verify_block,
+        entry.opportunity.expected_profit_weth,
+        outcome.realized_profit_weth,
+        if outcome.still_profitable() { "" } else { " (gone)" },
+    );
+    let record = serde_json::json!({
+        "timestamp": crate::clock::log_timestamp(),
+        "record": "shadow_verify",
+        "opportunity_id": entry.entry_id,
+        "shadow_block": entry.block,
+        "verify_block": entry.verify_block,
+        "optimal_amount_weth": (entry.opportunity.optimal_amount_weth * 1e8).round() / 1e8,
+        "expected_profit": (entry.opportunity.expected_profit_weth * 1e8).round() / 1e8,
+        "base_exact_profit": (outcome.base_exact_weth * 1e8).round() / 1e8,
+        "later_exact_profit": (outcome.later_exact_weth * 1e8).round() / 1e8,
+        "realized_profit": (outcome.realized_profit_weth * 1e8).round() / 1e8,
+        "still_profitable": outcome.still_profitable(),
+        "above_min_profit": outcome.realized_profit_weth >= min_profit_weth,
+        "later_ticks": [observed[0].1, observed[1].1],
+    });
+    crate::runtime_health::blocking_section("shadow_log_write", || {
+        crate::strategy::append_shadow_entry(&record, binary)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::gas_spike_tests::{make_pool_configs, make_pool_state};
+    use crate::types::BitmapQuality;
+
+    const DEEP: u128 = 50_000_000_000_000_000_000;
+
+    fn entry(block: u64) -> ShadowEntry {
+        let pools = make_pool_configs();
+        ShadowEntry {
+            entry_id: format!("{}-test", block),
+            block,
+            verify_block: block,
+            opportunity: ArbitrageOpportunity {
+                buy_pool_idx: 0,
+                sell_pool_idx: 1,
+                optimal_amount_weth: 0.5,
+                expected_profit_weth: 0.002,
+                buy_price_quote: 2450.0,
+                sell_price_quote: 2500.0,
+                spread_pct: 2.04,
+                reference_price_quote: 2475.0,
+                nr_converged: true,
+                nr_iterations: 5,
+                shadow_only: None,
+                bitmap_quality: BitmapQuality::Real,
+                adverse: None,
+                sensitivity: None,
+                two_step: None,
+                scan_boundary: None,
+                flash_route: None,
+                profit_breakdown: None,
+                tick_crossings: None,
+                modeled_gas: None,
+                priced_gas: 150_000,
+            },
+            pools: [pools[0].clone(), pools[1].clone()],
+            states: [
+                make_pool_state(2450.0, DEEP, block).load_full(),
+                make_pool_state(2500.0, DEEP, block).load_full(),
+            ],
+        }
+    }
+
+    fn read(state: &PoolState) -> PoolRead {
+        (state.sqrt_price_x96, state.tick, state.liquidity)
+    }
+
+    #[test]
+    fn test_unchanged_pools_realize_expected_profit() {
+        let entry = entry(100);
+        let outcome = entry.reprice([read(&entry.states[0]), read(&entry.states[1])]);
+        assert!(outcome.base_exact_weth > 0.0);
+        assert_eq!(outcome.later_exact_weth, outcome.base_exact_weth);
+        assert_eq!(outcome.realized_profit_weth, entry.opportunity.expected_profit_weth);
+        assert!(outcome.still_profitable());
+    }
+
+    #[test]
+    fn test_closed_spread_is_no_longer_profitable() {
+        let entry = entry(100);
+        // Pool A pool B'nin fiyatına yakınsadı — spread kapandı
+        let closed = make_pool_state(2500.0, DEEP, 102).load_full();
+        let outcome = entry.reprice([read(&closed), read(&entry.states[1])]);
+        assert!(outcome.later_exact_weth < outcome.base_exact_weth);
+        assert!(!outcome.still_profitable(), "{:?}", outcome);
+    }
+
+    #[test]
+    fn test_due_entries_and_rate_limit() {
+        let mut verifier = ShadowVerifier::new(2, 2);
+        let now = Instant::now();
+        assert!(verifier.submit(entry(100), now));
+        assert!(verifier.submit(entry(101), now));
+        // Dakikalık sınır doldu
+        assert!(!verifier.submit(entry(101), now));
+        assert_eq!(verifier.stats.dropped, 1);
+
+        assert!(verifier.take_due(101).is_empty());
+        let due = verifier.take_due(102);
+        assert_eq!(due.iter().map(|e| (e.block, e.verify_block)).collect::<Vec<_>>(), [(100, 102)]);
+        assert_eq!(verifier.take_due(110).len(), 1);
+
+        // Pencere kayınca yeniden kabul
+        assert!(verifier.submit(entry(120), now + RATE_WINDOW));
+    }
+}
@@ -0,0 +1,575 @@
+// ============================================================================
+//  SNAPSHOT v1.0 — Fırsat Anı Tam Girdi Arşivi (Anlaşmazlık Analizi)
+//
+//  Özellikler:
+//  ✓ Yürütmeye ulaşan her fırsat için (live veya shadow) tam girdi kaydı:
+//    her iki havuzun PoolState skalerleri, tam TickBitmapData (words + ticks),
+//    NR izi, exact kâr değerlendirmesi, calldata ve fee parametreleri
+//  ✓ snapshots/<opportunity_id>.json.gz — gzip sıkıştırılmış JSON
+//  ✓ Arka plan yazıcı (mpsc + spawn_blocking) — hot path ASLA bloklanmaz
+//  ✓ Sınırlı saklama: SNAPSHOT_KEEP (varsayılan 200) en yeni dosya tutulur
+//  ✓ --inspect-snapshot <id> [--recompute] ile inceleme ve yeniden hesaplama
+// ============================================================================
+
+use alloy::primitives::{Address, U256};
+use colored::*;
+use eyre::Result;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::mpsc;
+
+use crate::math;
+use crate::types::{ArbitrageOpportunity, PoolConfig, PoolState, TickBitmapData};
+
+/// Snapshot dosyalarının yazıldığı dizin
+pub const SNAPSHOT_DIR: &str = "snapshots";
+
+/// Arka plan yazıcı kanalı — main() içinde bir kez başlatılır.
+/// Başlatılmamışsa (SNAPSHOT_KEEP=0) submit() sessizce hiçbir şey yapmaz.
+static SNAPSHOT_TX: OnceLock<mpsc::Sender<SnapshotJob>> = OnceLock::new();
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Serileştirilebilir Snapshot Yapıları
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek havuzun fırsat anındaki tam durumu (yapılandırma + state skalerleri)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolSnapshot {
+    pub address: Address,
+    pub name: String,
+    pub dex: String,
+    pub fee_bps: u32,
+    /// Exact hesapta kullanılan fee (1e6 bazında)
+    pub fee_pips: u32,
+    pub live_fee_bps: Option<u32>,
+    pub token0_is_weth: bool,
+    pub tick_spacing: i32,
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    pub liquidity: u128,
+    pub eth_price_usd: f64,
+    pub last_block: u64,
+    pub staleness_ms: u64,
+    pub is_stale: bool,
+    /// Tam TickBitmap (words + ticks) — yoksa tek-tick hesaplama yapılmıştır
+    pub tick_bitmap: Option<TickBitmapData>,
+}
+
+/// Newton-Raphson / secant optimizasyon izi
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NrTrace {
+    pub optimal_amount_weth: f64,
+    pub expected_profit_weth: f64,
+    pub converged: bool,
+    pub iterations: u32,
+    /// Kârın ifade edildiği referans fiyat (log_integrity digest girdisi)
+    #[serde(default)]
+    pub reference_price_quote: f64,
+}
+
+/// Exact (U256) kâr değerlendirmesi — minProfit bu değerden türetilir
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExactProfitEval {
+    pub amount_wei: U256,
+    pub uni_zero_for_one: bool,
+    pub aero_zero_for_one: bool,
+    /// compute_exact_directional_profit çıktısı (owedToken cinsinden, wei)
+    pub profit_wei: U256,
+    pub slippage_bps: u64,
+    pub min_profit: u128,
+}
+
+/// Fırsat anındaki tam karar girdileri
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpportunitySnapshot {
+    pub id: String,
+    pub timestamp: String,
+    /// "live", "shadow" veya "paper" (PAPER_EXECUTION)
+    pub mode: String,
+    pub block: u64,
+    pub buy_pool_idx: usize,
+    pub sell_pool_idx: usize,
+    pub buy_price_quote: f64,
+    pub sell_price_quote: f64,
+    pub spread_pct: f64,
+    /// [pool_a (UniV3 — flash swap kaynağı), pool_b (satış hedefi)]
+    pub pools: Vec<PoolSnapshot>,
+    pub nr: NrTrace,
+    pub exact: ExactProfitEval,
+    pub calldata_hex: String,
+    pub deadline_block: u32,
+    pub bribe_pct: f64,
+    pub flash_loan_fee_bps: f64,
+}
+
+impl OpportunitySnapshot {
+    /// Kayıtlı girdilerden exact kârı yeniden hesapla.
+    /// Tekrarlanabilirlik kontrolü: sonuç `exact.profit_wei` ile birebir eşleşmeli.
+    pub fn recompute_exact_profit(&self) -> U256 {
+        let a = &self.pools[0];
+        let b = &self.pools[1];
+        math::exact::compute_exact_directional_profit(
+            a.sqrt_price_x96,
+            a.liquidity,
+            a.tick,
+            a.fee_pips,
+            a.tick_bitmap.as_ref(),
+            b.sqrt_price_x96,
+            b.liquidity,
+            b.tick,
+            b.fee_pips,
+            b.tick_bitmap.as_ref(),
+            self.exact.amount_wei,
+            self.exact.uni_zero_for_one,
+            self.exact.aero_zero_for_one,
+        )
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Arka Plan Yazıcı İşi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Hot path'ten yazıcıya gönderilen ham iş.
+///
+/// State'ler `Arc<PoolState>` olarak taşınır (ArcSwap::load_full) — bitmap
+/// klonlama ve serileştirme maliyeti tamamen arka plan görevine kalır.
+pub struct SnapshotJob {
+    /// Gönderim anında belirlenir — gölge log satırı aynı id'yi taşır
+    pub id: String,
+    pub mode: &'static str,
+    pub opportunity: ArbitrageOpportunity,
+    pub pools: [PoolConfig; 2],
+    pub states: [Arc<PoolState>; 2],
+    pub amount_wei: U256,
+    pub uni_zero_for_one: bool,
+    pub aero_zero_for_one: bool,
+    /// Live yolda hesaplanmış exact kâr. None ise yazıcı aynı girdilerle hesaplar.
+    pub exact_profit_wei: Option<U256>,
+    pub slippage_bps: u64,
+    pub min_profit: u128,
+    pub calldata: Vec<u8>,
+    pub deadline_block: u32,
+    pub bribe_pct: f64,
+    pub flash_loan_fee_bps: f64,
+}
+
+impl SnapshotJob {
+    /// Ham işi serileştirilebilir snapshot'a dönüştür
+    pub(crate) fn into_snapshot(self) -> OpportunitySnapshot {
+        let block = self.states[0].last_block.max(self.states[1].last_block);
+        let pools: Vec<PoolSnapshot> = self
+            .pools
+            .iter()
+            .zip(self.states.iter())
+            .map(|(cfg, st)| PoolSnapshot {
+                address: cfg.address,
+                name: cfg.name.clone(),
+                dex: cfg.dex.to_string(),
+                fee_bps: cfg.fee_bps(),
+                fee_pips: cfg.fee_pips(),
+                live_fee_bps: st.live_fee_bps,
+                token0_is_weth: cfg.token0_is_weth,
+                tick_spacing: cfg.tick_spacing,
+                sqrt_price_x96: st.sqrt_price_x96,
+                tick: st.tick,
+                liquidity: st.liquidity,
+                eth_price_usd: st.eth_price_usd,
+                last_block: st.last_block,
+                staleness_ms: st.staleness_ms() as u64,
+                is_stale: st.is_stale,
+                tick_bitmap: st.tick_bitmap.as_deref().cloned(),
+            })
+            .collect();
+
+        let opp = &self.opportunity;
+        let mut snapshot = OpportunitySnapshot {
+            id: self.id,
+            timestamp: crate::clock::log_timestamp(),
+            mode: self.mode.to_string(),
+            block,
+            buy_pool_idx: opp.buy_pool_idx,
+            sell_pool_idx: opp.sell_pool_idx,
+            buy_price_quote: opp.buy_price_quote,
+            sell_price_quote: opp.sell_price_quote,
+            spread_pct: opp.spread_pct,
+            pools,
+            nr: NrTrace {
+                optimal_amount_weth: opp.optimal_amount_weth,
+                expected_profit_weth: opp.expected_profit_weth,
+                converged: opp.nr_converged,
+                iterations: opp.nr_iterations,
+                reference_price_quote: opp.reference_price_quote,
+            },
+            exact: ExactProfitEval {
+                amount_wei: self.amount_wei,
+                uni_zero_for_one: self.uni_zero_for_one,
+                aero_zero_for_one: self.aero_zero_for_one,
+                profit_wei: self.exact_profit_wei.unwrap_or(U256::ZERO),
+                slippage_bps: self.slippage_bps,
+                min_profit: self.min_profit,
+            },
+            calldata_hex: crate::calldata_codec::format_compact_calldata_hex(&self.calldata),
+            deadline_block: self.deadline_block,
+            bribe_pct: self.bribe_pct,
+            flash_loan_fee_bps: self.flash_loan_fee_bps,
+        };
+
+        if self.exact_profit_wei.is_none() {
+            snapshot.exact.profit_wei = snapshot.recompute_exact_profit();
+        }
+        snapshot
+    }
+}
+
+/// Snapshot yazıcı arka plan görevini başlat.
+///
+/// main() içinde bir kez çağrılır; reconnect döngüsü boyunca yaşar.
+/// `keep == 0` ise arşivleme kapalıdır ve görev başlatılmaz.
+pub fn spawn_snapshot_writer(keep: usize) {
+    if keep == 0 {
+        return;
+    }
+    let (tx, mut rx) = mpsc::channel::<SnapshotJob>(64);
+    if SNAPSHOT_TX.set(tx).is_err() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let dir = crate::secure_fs::data_path(SNAPSHOT_DIR);
+        while let Some(job) = rx.recv().await {
+            let dir = dir.clone();
+            let result = tokio::task::spawn_blocking(move || -> Result<String> {
+                let snapshot = job.into_snapshot();
+                write_snapshot(&dir, &snapshot)?;
+                enforce_retention(&dir, keep)?;
+                Ok(snapshot.id)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(_id)) => {}
+                Ok(Err(e)) => eprintln!("  ⚠️ [Snapshot] Write error: {}", e),
+                Err(e) => eprintln!("  ⚠️ [Snapshot] Writer task error: {}", e),
+            }
+        }
+    });
+}
+
+/// Snapshot id'si: "<blok>-<unix ms>"
+pub fn new_snapshot_id(block: u64) -> String {
+    format!("{}-{}", block, crate::clock::now().timestamp_millis())
+}
+
+/// Non-blocking snapshot gönder.
+/// Yazıcı başlatılmamışsa veya kanal doluysa iş sessizce düşürülür.
+pub fn submit(job: SnapshotJob) {
+    if let Some(tx) = SNAPSHOT_TX.get() {
+        let _ = tx.try_send(job);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Disk I/O — gzip JSON + Saklama Politikası
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Snapshot dosya yolu: <dir>/<id>.json.gz
+pub fn snapshot_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.json.gz", id))
+}
+
+/// Snapshot'ı gzip sıkıştırılmış JSON olarak yaz
+pub fn write_snapshot(dir: &Path, snapshot: &OpportunitySnapshot) -> Result<PathBuf> {
+    crate::secure_fs::create_dir_all(dir)?;
+    let path = snapshot_path(dir, &snapshot.id);
+    let file = crate::secure_fs::create(&path)?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    let json = serde_json::to_vec(snapshot)?;
+    encoder.write_all(&json)?;
+    encoder.finish()?;
+    Ok(path)
+}
+
+/// Snapshot'ı diskten oku
+pub fn read_snapshot(dir: &Path, id: &str) -> Result<OpportunitySnapshot> {
+    let path = snapshot_path(dir, id);
+    let file = std::fs::File::open(&path)
+        .map_err(|e| eyre::eyre!("Snapshot could not be opened ({}): {}", path.display(), e))?;
+    let mut decoder = GzDecoder::new(file);
+    let mut json = Vec::new();
+    decoder.read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// En yeni `keep` snapshot dışındakileri sil. Dönüş: silinen dosya sayısı.
+pub fn enforce_retention(dir: &Path, keep: usize) -> Result<usize> {
+    let mut entries: Vec<(std::time::SystemTime, PathBuf)> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.to_string_lossy().ends_with(".json.gz"))
+        .filter_map(|p| {
+            let modified = std::fs::metadata(&p).and_then(|m| m.modified()).ok()?;
+            Some((modified, p))
+        })
+        .collect();
+
+    if entries.len() <= keep {
+        return Ok(0);
+    }
+
+    // En yeni önde — aynı mtime'da dosya adına göre (id = blok-ms) sırala
+    entries.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+    let mut deleted = 0;
+    for (_, path) in entries.into_iter().skip(keep) {
+        if std::fs::remove_file(&path).is_ok() {
+            deleted += 1;
+        }
+    }
+    Ok(deleted)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CLI: --inspect-snapshot <id> [--recompute]
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Snapshot'ı okunabilir biçimde yazdır, istenirse exact kârı yeniden hesapla
+pub fn cli_inspect_snapshot(id: &str, recompute: bool) -> Result<()> {
+    let snapshot = read_snapshot(&crate::secure_fs::read_path(SNAPSHOT_DIR), id)?;
+    println!("{}", serde_json::to_string_pretty(&snapshot)?);
+
+    if recompute {
+        let recomputed = snapshot.recompute_exact_profit();
+        println!();
+        println!("  Recorded profit   : {} wei", snapshot.exact.profit_wei);
+        println!("  Recomputed profit : {} wei", recomputed);
+        if recomputed == snapshot.exact.profit_wei {
+            println!("  {} Reproducible — exact profit matches", "✅".green());
+        } else {
+            return Err(eyre::eyre!(
+                "Snapshot {} is NOT reproducible: recorded={} recomputed={}",
+                id,
+                snapshot.exact.profit_wei,
+                recomputed
+            ));
+        }
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::types::{DexType, FeeTier, TickInfo};
+    use alloy::primitives::address;
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "arb_snapshot_{}_{}_{}",
+            tag,
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn make_config(addr: Address, dex: DexType, fee_bps: u32, tick_spacing: i32) -> PoolConfig {
+        PoolConfig {
+            address: addr,
+            name: format!("{}-test", dex),
+            fee: FeeTier::from_bps(fee_bps),
+            token0_decimals: 18,
+            token1_decimals: 6,
+            dex,
+            token0_is_weth: true,
+            tick_spacing,
+            quote_token_address: address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+            base_token_address: address!("4200000000000000000000000000000000000006"),
+        }
+    }
+
+    fn make_bitmap(center: i32, spacing: i32) -> TickBitmapData {
+        let mut ticks = HashMap::new();
+        let mut words = HashMap::new();
+        for i in 1..=5 {
+            for t in [center - i * spacing * 10, center + i * spacing * 10] {
+                let t = t - t.rem_euclid(spacing);
+                ticks.insert(
+                    t,
+                    TickInfo {
+                        liquidity_gross: 2_000_000_000_000_000_000,
+                        liquidity_net: if t < center { 1_000_000_000_000_000_000 } else { -1_000_000_000_000_000_000 },
+                        initialized: true,
+                        staked_liquidity_net: 0,
+                    },
+                );
+                let compressed = t.div_euclid(spacing);
+                let word = (compressed >> 8) as i16;
+                let bit = (compressed & 0xFF) as usize;
+                let entry = words.entry(word).or_insert(U256::ZERO);
+                *entry |= U256::from(1u64) << bit;
+            }
+        }
+        TickBitmapData {
+            words,
+            ticks,
+            snapshot_block: 100,
+            sync_duration_us: 1234,
+            scan_range: 100,
+            scanned_ticks: Some((center - 100 * spacing, center + 100 * spacing)),
+            staked: None,
+            last_full_sync_block: 100,
+        }
+    }
+
+    fn make_state(eth_price: f64, spacing: i32) -> Arc<PoolState> {
+        let price_ratio = eth_price * 1e-12;
+        let tick = (price_ratio.ln() / 1.0001_f64.ln()).floor() as i32;
+        Arc::new(PoolState {
+            sqrt_price_x96: math::exact::get_sqrt_ratio_at_tick(tick),
+            sqrt_price_f64: price_ratio.sqrt() * 2.0_f64.powi(96),
+            tick,
+            liquidity: 5_000_000_000_000_000_000,
+            liquidity_f64: 5e18,
+            eth_price_usd: eth_price,
+            last_block: 100,
+            last_update: Instant::now(),
+            is_initialized: true,
+            tick_bitmap: Some(Box::new(make_bitmap(tick, spacing))),
+            live_fee_bps: None,
+            is_stale: false,
+            data_quality_errors: 0,
+            quarantined: false,
+            optimistic_trigger: None,
+            pinned_block: Some(100),
+            predicted_state: None,
+            constant_product: false,
+        })
+    }
+
+    pub(crate) fn make_job() -> SnapshotJob {
+        let pool_a = make_config(address!("d0b53D9277642d899DF5C87A3966A349A798F224"), DexType::UniswapV3, 5, 10);
+        let pool_b = make_config(address!("cDAC0d6c6C59727a65F871236188350531885C43"), DexType::Aerodrome, 1, 1);
+        SnapshotJob {
+            id: "100-1700000000000".to_string(),
+            mode: "shadow",
+            opportunity: ArbitrageOpportunity {
+                buy_pool_idx: 1,
+                sell_pool_idx: 0,
+                optimal_amount_weth: 0.5,
+                expected_profit_weth: 0.00125,
+                buy_price_quote: 2500.0,
+                sell_price_quote: 2525.0,
+                spread_pct: 1.0,
+                reference_price_quote: 2512.5,
+                nr_converged: true,
+                nr_iterations: 7,
+                shadow_only: None,
+                bitmap_quality: crate::types::BitmapQuality::Real,
+                adverse: None,
+                sensitivity: None,
+                two_step: None,
+                scan_boundary: None,
+                flash_route: None,
+                profit_breakdown: None,
+                tick_crossings: None,
+                modeled_gas: None,
+                priced_gas: 150_000,
+            },
+            pools: [pool_a, pool_b],
+            states: [make_state(2525.0, 10), make_state(2500.0, 1)],
+            amount_wei: U256::from(500_000_000_000_000_000u128),
+            uni_zero_for_one: true,
+            aero_zero_for_one: false,
+            exact_profit_wei: None,
+            slippage_bps: 9500,
+            min_profit: 0,
+            calldata: vec![0xAB; 134],
+            deadline_block: 103,
+            bribe_pct: 0.25,
+            flash_loan_fee_bps: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip_gzip() {
+        let dir = temp_dir("roundtrip");
+        let snapshot = make_job().into_snapshot();
+        assert_eq!(snapshot.pools.len(), 2);
+        assert!(snapshot.pools[0].tick_bitmap.is_some());
+
+        let path = write_snapshot(&dir, &snapshot).expect("write");
+        assert!(path.to_string_lossy().ends_with(".json.gz"));
+
+        let loaded = read_snapshot(&dir, &snapshot.id).expect("read");
+        assert_eq!(loaded, snapshot, "Round-trip sonrası snapshot birebir aynı olmalı");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_snapshot_recompute_matches_recorded_profit() {
+        let dir = temp_dir("recompute");
+        let job = make_job();
+
+        // Yazıcının kullandığı girdilerle bağımsız exact hesaplama
+        let expected = math::exact::compute_exact_directional_profit(
+            job.states[0].sqrt_price_x96,
+            job.states[0].liquidity,
+            job.states[0].tick,
+            job.pools[0].fee_pips(),
+            job.states[0].tick_bitmap.as_deref(),
+            job.states[1].sqrt_price_x96,
+            job.states[1].liquidity,
+            job.states[1].tick,
+            job.pools[1].fee_pips(),
+            job.states[1].tick_bitmap.as_deref(),
+            job.amount_wei,
+            job.uni_zero_for_one,
+            job.aero_zero_for_one,
+        );
+
+        let snapshot = job.into_snapshot();
+        assert_eq!(snapshot.exact.profit_wei, expected);
+
+        write_snapshot(&dir, &snapshot).expect("write");
+        let loaded = read_snapshot(&dir, &snapshot.id).expect("read");
+        assert_eq!(
+            loaded.recompute_exact_profit(),
+            loaded.exact.profit_wei,
+            "Diskten okunan snapshot ile yeniden hesaplanan kâr birebir eşleşmeli"
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_snapshot_retention_keeps_newest() {
+        let dir = temp_dir("retention");
+        let base = make_job().into_snapshot();
+        for i in 0..5 {
+            let mut s = base.clone();
+            s.id = format!("100-{}", 1000 + i);
+            write_snapshot(&dir, &s).expect("write");
+        }
+
+        let deleted = enforce_retention(&dir, 3).expect("retention");
+        assert_eq!(deleted, 2);
+        let remaining = std::fs::read_dir(&dir).unwrap().count();
+        assert_eq!(remaining, 3);
+        assert!(snapshot_path(&dir, "100-1004").exists(), "En yeni snapshot korunmalı");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
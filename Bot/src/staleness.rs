@@ -0,0 +1,427 @@
+// ============================================================================
+//  STALENESS CALIBRATION v1.0 — Gözlenen Sequencer Zamanlamasından Tazelik Eşiği
+//
+//  Sabit MAX_STALENESS_MS keyfi: 2s bloklu Base'te 3000ms neredeyse her veriyi
+//  bir sonraki bloğa kadar geçirir, agresif bir değer ise normal jitter'da
+//  sahte ret üretir. Eşik gözlenen dağılımdan türetilir:
+//
+//  ✓ Tahminci: son WINDOW bloğun başlık varış aralıkları (atlanan bloklar
+//    bölünerek blok başına) ve senkron tamamlanma süreleri
+//  ✓ Tespit kesimi = p95 blok aralığı − güvenlik payı (p95 senkron süresi):
+//    bir sonraki blok senkronu bitmeden önceki veri hâlâ güncel sayılır
+//  ✓ Yürütme kesimi = tespit kesimi × STALENESS_EXEC_FRACTION (daha sıkı)
+//  ✓ Her RECALIBRATE_EVERY blokta yeniden hesap, [STALENESS_MIN_MS,
+//    STALENESS_MAX_MS] aralığına kırpılır; değer değişince loglanır
+//  ✓ MAX_STALENESS_MS: kalibrasyon öncesi fallback; ADAPTIVE_STALENESS=false
+//    ise iki aşamada da override
+//  ✓ Sabit eşikte geçip uyarlamalı kesimde kalan retler aşama başına ayrı
+//    sayılır — sıkılaştırmanın faydası stats kutusundan izlenir
+// ============================================================================
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use parking_lot::Mutex;
+
+use crate::types::BotConfig;
+
+/// Dağılım penceresi (blok)
+pub const WINDOW: usize = 1_000;
+
+/// Yeniden hesap aralığı (blok)
+pub const RECALIBRATE_EVERY: u64 = 300;
+
+/// Bundan az aralık örneğiyle kalibrasyon yapılmaz (fallback sürer)
+const MIN_SAMPLES: usize = 100;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kesimler
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Eşiğin uygulandığı aşama
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// PreSizing staleness filtresi + multi-hop hard-abort
+    Detection,
+    /// Yürütme anı freshness gate (tek ve multi-hop)
+    Execution,
+}
+
+/// Etkin kesimlerin kaynağı
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CutoffSource {
+    /// ADAPTIVE_STALENESS=false — MAX_STALENESS_MS iki aşamada da
+    Fixed,
+    /// Yeterli örnek yok — MAX_STALENESS_MS
+    Fallback,
+    /// Gözlenen dağılımdan
+    Adaptive,
+}
+
+impl CutoffSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            CutoffSource::Fixed => "fixed override",
+            CutoffSource::Fallback => "fallback, calibrating",
+            CutoffSource::Adaptive => "adaptive",
+        }
+    }
+}
+
+/// Tespit + yürütme tazelik eşikleri (ms)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cutoffs {
+    pub detection_ms: u128,
+    pub execution_ms: u128,
+    pub source: CutoffSource,
+}
+
+impl Cutoffs {
+    fn fixed(ms: u128, source: CutoffSource) -> Self {
+        Self { detection_ms: ms, execution_ms: ms, source }
+    }
+
+    pub fn get(&self, stage: Stage) -> u128 {
+        match stage {
+            Stage::Detection => self.detection_ms,
+            Stage::Execution => self.execution_ms,
+        }
+    }
+}
+
+/// Kırpma sınırları + yürütme oranı
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min_ms: u64,
+    pub max_ms: u64,
+    pub execution_fraction: f64,
+}
+
+impl Bounds {
+    /// Güncelleme kuralı: tespit = p95 aralık − p95 senkron, yürütme = oran ×
+    /// tespit; ikisi de [min, max]'a kırpılır, yürütme tespiti aşmaz
+    pub fn derive(&self, p95_interval_ms: f64, p95_sync_ms: f64) -> (u128, u128) {
+        let clamp = |ms: f64| (ms.max(0.0).round() as u64).clamp(self.min_ms, self.max_ms) as u128;
+        let detection = clamp(p95_interval_ms - p95_sync_ms);
+        let execution = clamp(detection as f64 * self.execution_fraction).min(detection);
+        (detection, execution)
+    }
+}
+
+/// Kesim değişikliği (log)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Recalibration {
+    pub previous: Cutoffs,
+    pub current: Cutoffs,
+    pub p95_interval_ms: f64,
+    pub p95_sync_ms: f64,
+    pub samples: usize,
+}
+
+/// En yakın sıra yöntemiyle p95 (boş → None)
+fn percentile_95(values: &VecDeque<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<f64> = values.iter().copied().collect();
+    sorted.sort_by(f64::total_cmp);
+    let rank = (sorted.len() as f64 * 0.95).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tahminci
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct StalenessEstimator {
+    fixed_ms: u128,
+    adaptive: bool,
+    bounds: Bounds,
+    intervals: VecDeque<f64>,
+    syncs: VecDeque<f64>,
+    /// Son işlenen başlık (numara, varış anı)
+    last_head: Option<(u64, Instant)>,
+    blocks_since: u64,
+    current: Cutoffs,
+    /// Son kalibrasyonun girdileri (p95 aralık, p95 senkron)
+    last_p95: Option<(f64, f64)>,
+    /// Uyarlamalı sıkılaştırmaya yazılan retler [tespit, yürütme]
+    tightened: [u64; 2],
+}
+
+impl StalenessEstimator {
+    pub fn new(fixed_ms: u128, adaptive: bool, bounds: Bounds) -> Self {
+        let source = if adaptive { CutoffSource::Fallback } else { CutoffSource::Fixed };
+        Self {
+            fixed_ms,
+            adaptive,
+            bounds,
+            intervals: VecDeque::with_capacity(WINDOW),
+            syncs: VecDeque::with_capacity(WINDOW),
+            last_head: None,
+            blocks_since: 0,
+            current: Cutoffs::fixed(fixed_ms, source),
+            last_p95: None,
+            tightened: [0; 2],
+        }
+    }
+
+    pub fn from_config(config: &BotConfig) -> Self {
+        Self::new(
+            config.max_staleness_ms,
+            config.adaptive_staleness,
+            Bounds {
+                min_ms: config.staleness_min_ms,
+                max_ms: config.staleness_max_ms,
+                execution_fraction: config.staleness_exec_fraction,
+            },
+        )
+    }
+
+    pub fn cutoffs(&self) -> Cutoffs {
+        self.current
+    }
+
+    /// Yeni başlık: varış aralığı önceki başlıktan (atlanan bloklar bölünür)
+    pub fn observe_head(&mut self, number: u64, at: Instant, sync_ms: u128) -> Option<Recalibration> {
+        let interval = match self.last_head {
+            Some((prev, prev_at)) if number > prev => {
+                Some(at.saturating_duration_since(prev_at).as_secs_f64() * 1000.0 / (number - prev) as f64)
+            }
+            _ => None,
+        };
+        self.last_head = Some((number, at));
+        self.observe(interval, sync_ms as f64)
+    }
+
+    /// Bir bloğun örnekleri; RECALIBRATE_EVERY'de kesim değiştiyse döner
+    pub fn observe(&mut self, interval_ms: Option<f64>, sync_ms: f64) -> Option<Recalibration> {
+        if !self.adaptive {
+            return None;
+        }
+        if let Some(ms) = interval_ms.filter(|ms| ms.is_finite()) {
+            if self.intervals.len() == WINDOW {
+                self.intervals.pop_front();
+            }
+            self.intervals.push_back(ms);
+        }
+        if self.syncs.len() == WINDOW {
+            self.syncs.pop_front();
+        }
+        self.syncs.push_back(sync_ms);
+
+        self.blocks_since += 1;
+        if self.blocks_since < RECALIBRATE_EVERY {
+            return None;
+        }
+        self.blocks_since = 0;
+        self.recalibrate()
+    }
+
+    fn recalibrate(&mut self) -> Option<Recalibration> {
+        if self.intervals.len() < MIN_SAMPLES {
+            return None;
+        }
+        let p95_interval_ms = percentile_95(&self.intervals)?;
+        let p95_sync_ms = percentile_95(&self.syncs).unwrap_or(0.0);
+        self.last_p95 = Some((p95_interval_ms, p95_sync_ms));
+        let (detection_ms, execution_ms) = self.bounds.derive(p95_interval_ms, p95_sync_ms);
+        let next = Cutoffs { detection_ms, execution_ms, source: CutoffSource::Adaptive };
+        if next == self.current {
+            return None;
+        }
+        let previous = std::mem::replace(&mut self.current, next);
+        Some(Recalibration {
+            previous,
+            current: next,
+            p95_interval_ms,
+            p95_sync_ms,
+            samples: self.intervals.len(),
+        })
+    }
+
+    /// Ret, sabit eşikte geçecek miydi? Geçecekse sıkılaştırmaya yazılır
+    pub fn record_rejection(&mut self, stage: Stage, worst_staleness_ms: u128) -> bool {
+        if worst_staleness_ms > self.fixed_ms {
+            return false;
+        }
+        self.tightened[stage as usize] += 1;
+        true
+    }
+
+    pub fn stats_line(&self) -> String {
+        let mut line = format!(
+            "detect {}ms | exec {}ms ({})",
+            self.current.detection_ms,
+            self.current.execution_ms,
+            self.current.source.label(),
+        );
+        if let Some((interval, sync)) = self.last_p95 {
+            line.push_str(&format!(" — p95 interval {:.0}ms, sync {:.0}ms", interval, sync));
+        }
+        if self.tightened.iter().any(|n| *n > 0) {
+            line.push_str(&format!(
+                " | tightened rejects det {} / exec {}",
+                self.tightened[0], self.tightened[1],
+            ));
+        }
+        line
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Global Tahminci
+// ─────────────────────────────────────────────────────────────────────────────
+
+static ESTIMATOR: Mutex<Option<StalenessEstimator>> = parking_lot::const_mutex(None);
+
+/// main, run_bot öncesi — dağılım reconnect'lerde korunur
+pub fn init(config: &BotConfig) {
+    *ESTIMATOR.lock() = Some(StalenessEstimator::from_config(config));
+}
+
+/// Ana döngü, senkron bittikten sonra
+pub fn observe_block(number: u64, arrived_at: Instant, sync_ms: u128) -> Option<Recalibration> {
+    ESTIMATOR
+        .lock()
+        .as_mut()
+        .and_then(|e| e.observe_head(number, arrived_at, sync_ms))
+}
+
+/// `stage` için uygulanan eşik. ADAPTIVE_STALENESS=false olan config (ör. A/B
+/// ikincil katmanı) ve başlatılmamış tahminci → MAX_STALENESS_MS
+pub fn cutoff(config: &BotConfig, stage: Stage) -> u128 {
+    if !config.adaptive_staleness {
+        return config.max_staleness_ms;
+    }
+    ESTIMATOR
+        .lock()
+        .as_ref()
+        .map_or(config.max_staleness_ms, |e| e.cutoffs().get(stage))
+}
+
+/// Aktif havuzlardaki tazelik reti — en bayat havuz sabit eşiğin içindeyse
+/// ret uyarlamalı sıkılaştırmaya aittir
+pub fn record_rejection(stage: Stage, worst_staleness_ms: u128) -> bool {
+    ESTIMATOR
+        .lock()
+        .as_mut()
+        .is_some_and(|e| e.record_rejection(stage, worst_staleness_ms))
+}
+
+pub fn stats_line() -> Option<String> {
+    ESTIMATOR.lock().as_ref().map(StalenessEstimator::stats_line)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const BOUNDS: Bounds = Bounds { min_ms: 300, max_ms: 3_000, execution_fraction: 0.75 };
+
+    /// `blocks` blok boyunca sentetik aralık / senkron dağılımı besle
+    fn feed(
+        est: &mut StalenessEstimator,
+        blocks: u64,
+        interval: impl Fn(u64) -> f64,
+        sync: impl Fn(u64) -> f64,
+    ) -> Vec<Recalibration> {
+        (0..blocks)
+            .filter_map(|i| est.observe(Some(interval(i)), sync(i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_p95_cutoffs_from_synthetic_distribution() {
+        let mut est = StalenessEstimator::new(3_000, true, BOUNDS);
+        // Aralıklar: %90 tam 2000ms, %10 jitter 2400ms; senkron %95 ≤ 150ms
+        let changes = feed(
+            &mut est,
+            RECALIBRATE_EVERY,
+            |i| if i % 10 == 0 { 2_400.0 } else { 2_000.0 },
+            |i| if i % 20 == 0 { 600.0 } else { 150.0 },
+        );
+        assert_eq!(changes.len(), 1);
+        // p95 aralık = 2400 (üst %10), p95 senkron = 150 (üst %5 = 600 dışı)
+        assert_eq!(changes[0].p95_interval_ms, 2_400.0);
+        assert_eq!(changes[0].p95_sync_ms, 150.0);
+        let cut = est.cutoffs();
+        assert_eq!(cut.source, CutoffSource::Adaptive);
+        assert_eq!(cut.detection_ms, 2_250);
+        assert_eq!(cut.execution_ms, 1_688);
+        assert_eq!(changes[0].previous, Cutoffs::fixed(3_000, CutoffSource::Fallback));
+    }
+
+    #[test]
+    fn test_fallback_until_recalibration_and_enough_samples() {
+        let mut est = StalenessEstimator::new(2_000, true, BOUNDS);
+        assert!(feed(&mut est, RECALIBRATE_EVERY - 1, |_| 1_000.0, |_| 100.0).is_empty());
+        assert_eq!(est.cutoffs(), Cutoffs::fixed(2_000, CutoffSource::Fallback));
+
+        // Aralık örneği olmadan (ilk başlık, geri giden numara) kalibrasyon yok
+        let mut sparse = StalenessEstimator::new(2_000, true, BOUNDS);
+        for _ in 0..RECALIBRATE_EVERY {
+            assert!(sparse.observe(None, 100.0).is_none());
+        }
+        assert_eq!(sparse.cutoffs().source, CutoffSource::Fallback);
+    }
+
+    #[test]
+    fn test_clamped_update_rule_and_change_only_logging() {
+        // Çok hızlı zincir → min'e, çok yavaş → max'e kırpılır
+        assert_eq!(BOUNDS.derive(250.0, 100.0), (300, 300));
+        assert_eq!(BOUNDS.derive(12_000.0, 200.0), (3_000, 2_250));
+        // Senkron aralığı aşarsa negatif değil min
+        assert_eq!(BOUNDS.derive(2_000.0, 2_500.0), (300, 300));
+
+        let mut est = StalenessEstimator::new(3_000, true, BOUNDS);
+        assert_eq!(feed(&mut est, RECALIBRATE_EVERY, |_| 2_000.0, |_| 200.0).len(), 1);
+        // Aynı dağılım → kesim değişmez, log yok
+        assert!(feed(&mut est, RECALIBRATE_EVERY, |_| 2_000.0, |_| 200.0).is_empty());
+        // Sequencer yavaşlar → pencere kayınca yeni kesim
+        let changes = feed(&mut est, 3 * RECALIBRATE_EVERY, |_| 4_000.0, |_| 200.0);
+        let last = est.cutoffs();
+        assert_eq!((last.detection_ms, last.execution_ms), (3_000, 2_250));
+        assert!(changes.iter().all(|c| c.previous != c.current));
+    }
+
+    #[test]
+    fn test_fixed_override_precedence() {
+        let mut est = StalenessEstimator::new(1_500, false, BOUNDS);
+        assert!(feed(&mut est, 2 * RECALIBRATE_EVERY, |_| 2_000.0, |_| 100.0).is_empty());
+        assert_eq!(est.cutoffs(), Cutoffs::fixed(1_500, CutoffSource::Fixed));
+
+        // Override edilmiş config, global tahminci kalibre olsa da sabit değeri alır
+        let mut config = BotConfig::for_tests();
+        config.adaptive_staleness = false;
+        config.max_staleness_ms = 1_234;
+        assert_eq!(cutoff(&config, Stage::Detection), 1_234);
+        assert_eq!(cutoff(&config, Stage::Execution), 1_234);
+    }
+
+    #[test]
+    fn test_head_intervals_and_tightening_attribution() {
+        let mut est = StalenessEstimator::new(3_000, true, BOUNDS);
+        let t0 = Instant::now();
+        est.observe_head(100, t0, 50);
+        est.observe_head(101, t0 + Duration::from_millis(2_000), 50);
+        // 3 blok atlandı → 6000ms / 3
+        est.observe_head(104, t0 + Duration::from_millis(8_000), 50);
+        // Reorg: aynı numara aralık üretmez
+        est.observe_head(104, t0 + Duration::from_millis(8_500), 50);
+        assert_eq!(est.intervals, VecDeque::from(vec![2_000.0, 2_000.0]));
+        assert_eq!(est.syncs.len(), 4);
+
+        // 2500ms bayat veri: sabit 3000'de geçerdi → sıkılaştırma; 3500ms değil
+        assert!(est.record_rejection(Stage::Execution, 2_500));
+        assert!(!est.record_rejection(Stage::Detection, 3_500));
+        assert_eq!(est.tightened, [0, 1]);
+        assert!(est.stats_line().contains("tightened rejects det 0 / exec 1"));
+    }
+}
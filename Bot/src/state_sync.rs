@@ -7,6 +7,8 @@
 //  ✓ Aerodrome ticks() ABI 10 parametre olarak güncellendi
 //    (Uniswap V3'ten farklı: ekstra stakedLiquidityNet + rewardGrowthOutsideX128)
 //  ✓ Pool adresi doğrulama rehberi eklendi
+//  ✓ Slipstream stakedLiquidityNet decode edilir; tam bitmap sync'inde
+//    stakedLiquidity() görüntüsüyle (aynı eth_call) doğrulanır
 //
 //  v9.0 Yenilikler:
 //  ✓ Pending TX stream (eth_subscribe newPendingTransactions)
@@ -27,18 +29,32 @@
 //    5. [YENİ] Pending TX stream ile blok öncesi iyimser gücelleme
 // ============================================================================
 
-use alloy::primitives::{address, Address, Bytes, U256};
+use alloy::eips::BlockId;
+use alloy::primitives::{address, Address, Bytes, B256, I256, U256};
 use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
 use alloy::sol;
 use alloy::sol_types::SolCall;
-use eyre::Result;
 use futures_util::future::join_all;
 use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
 use std::time::Instant;
 
+use crate::console::{eprintln_high, println_high};
+use crate::errors::{BotError, BotResult};
+use crate::executor::LatencyHistogram;
+use crate::rpc_census::{self, BlockMemo, CallKey};
+use crate::simulator::{balance_mapping_key, SimulationEngine};
+use crate::transport::ProviderRegistry;
 use crate::math::compute_eth_price;
-use crate::math::exact::u256_to_f64;
-use crate::types::{DexType, PoolConfig, SharedPoolState, TickBitmapData, TickInfo};
+use crate::math::exact::{u256_to_f64, MAX_TICK, MIN_TICK};
+use crate::types::{
+    DataQualityIssue, DexType, OptimisticTrigger, PoolConfig, PoolState, PredictedState, ScanEdge,
+    SharedPoolState, StakedLiquidity, StaticPoolStore, TickBitmapData, TickInfo,
+};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Base L2 GasPriceOracle — L1 Data Fee Tahmin Kontratı
@@ -50,12 +66,10 @@ use crate::types::{DexType, PoolConfig, SharedPoolState, TickBitmapData, TickInf
 // GasPriceOracle kontratı (0x420...00F) işlemin calldata'sını alıp
 // L1 veri ücretini wei cinsinden döndürür.
 //
-// Adres: 0x420000000000000000000000000000000000000F (Base, OP Mainnet, vb.)
+// Adres zincir profilinden gelir (config.l1_fee_oracle): OP Stack'te
+// 0x420000000000000000000000000000000000000F, Arbitrum'da oracle yoktur.
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Base GasPriceOracle adresi (tüm OP Stack ağlarında standart)
-const GAS_PRICE_ORACLE_ADDRESS: Address = address!("420000000000000000000000000000000000000F");
-
 sol! {
     #[sol(rpc)]
     interface IGasPriceOracle {
@@ -69,7 +83,7 @@ sol! {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Multicall3 adresi — Base, Ethereum, Arbitrum, Optimism vb. hepsi aynı
-const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+pub(crate) const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
 
 sol! {
     #[sol(rpc)]
@@ -207,6 +221,10 @@ sol! {
 
         function fee() external view returns (uint24);
 
+        function stakedLiquidity() external view returns (uint128);
+
+        function unstakedFee() external view returns (uint24);
+
         function ticks(int24 tick) external view returns (
             uint128 liquidityGross,
             int128 liquidityNet,
@@ -224,6 +242,98 @@ sol! {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Uniswap V2 / Aerodrome vAMM Havuz Arayüzü (getReserves → 3 word)
+//
+// UniswapV2Pair rezervleri uint112/uint32, Aerodrome Pool uint256 döner —
+// ABI'de ikisi de 3 × 32 byte; uint256 olarak okumak her ikisini de çözer.
+// ─────────────────────────────────────────────────────────────────────────────
+
+sol! {
+    #[sol(rpc)]
+    interface IUniswapV2Pair {
+        function getReserves() external view returns (
+            uint256 reserve0,
+            uint256 reserve1,
+            uint256 blockTimestampLast
+        );
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Veri Kalitesi Kapısı — NaN/Inf Zehirlenme Dedektörü
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Art arda bu kadar yazımı reddedilen havuz karantinaya alınır
+/// (is_active=false → fırsat taramasından çıkar; geçerli ilk yazımla döner).
+const DATA_QUALITY_QUARANTINE_AFTER: u32 = 3;
+
+/// Oturum boyunca reddedilen PoolState yazımı sayısı
+static DATA_QUALITY_ERRORS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Şimdiye kadar reddedilen (bozuk veri) sync yazımı sayısı
+pub fn data_quality_errors() -> u64 {
+    DATA_QUALITY_ERRORS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Sync yazımını doğrulayarak uygula — sync_pool_state, Multicall3 sync ve
+/// optimistic_refresh_pool'un tek yazım noktası.
+///
+/// `update` mevcut state'in kopyasına uygulanır ve `PoolState::validate` ile
+/// kontrol edilir. Geçersizse güncelleme işlenmez: eski state aynen kalır,
+/// yalnızca ardışık hata sayacı artar; eşik dolunca havuz karantinaya girer.
+fn commit_validated(
+    pool_config: &PoolConfig,
+    pool_state: &SharedPoolState,
+    update: impl Fn(&mut PoolState),
+) -> std::result::Result<(), DataQualityIssue> {
+    let was_quarantined = pool_state.load().quarantined;
+    let mut rejected: Option<DataQualityIssue> = None;
+    pool_state.rcu(|old| {
+        let mut next = (**old).clone();
+        update(&mut next);
+        match next.validate() {
+            Ok(()) => {
+                rejected = None;
+                next.data_quality_errors = 0;
+                next.quarantined = false;
+                next
+            }
+            Err(issue) => {
+                rejected = Some(issue);
+                let mut kept = (**old).clone();
+                kept.data_quality_errors += 1;
+                kept.quarantined |= kept.data_quality_errors >= DATA_QUALITY_QUARANTINE_AFTER;
+                kept
+            }
+        }
+    });
+
+    let Some(issue) = rejected else {
+        if was_quarantined {
+            eprintln_high!(
+                "  \u{2705} [DataQuality] [{}] valid update received — quarantine lifted",
+                pool_config.name,
+            );
+        }
+        return Ok(());
+    };
+
+    DATA_QUALITY_ERRORS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let state = pool_state.load();
+    eprintln_high!(
+        "  \u{1f9ea} [DataQuality] [{}] update rejected: {} — previous state kept ({}/{})",
+        pool_config.name, issue, state.data_quality_errors, DATA_QUALITY_QUARANTINE_AFTER,
+    );
+    if !was_quarantined && state.quarantined {
+        eprintln_high!(
+            "  \u{1f6ab} [DataQuality] [{}] QUARANTINED after {} consecutive bad updates",
+            pool_config.name, state.data_quality_errors,
+        );
+    }
+    Err(issue)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tek Havuz Durum Senkronizasyonu
 // ─────────────────────────────────────────────────────────────────────────────
@@ -239,14 +349,310 @@ const SYNC_TIMEOUT_MS: u64 = 3000;
 /// Her havuz için 3 çağrı (slot0+liquidity+fee) → chunk=50 → 150 call/batch.
 const MULTICALL_CHUNK_SIZE: usize = 30;
 
-/// Maksimum yeniden deneme sayısı (timeout sonrası)
-const SYNC_MAX_RETRIES: u32 = 2;
+// ─────────────────────────────────────────────────────────────────────────────
+// Geçici RPC Hatalarında Yeniden Deneme (Multicall3 + sync_pool_state)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Yeniden denemeler arası bekleme aralığı (ms) — rastgele jitter
+const RETRY_BACKOFF_MIN_MS: u64 = 25;
+const RETRY_BACKOFF_MAX_MS: u64 = 75;
+
+/// MULTICALL_RETRIES (state_sync hot path'i config taşımaz)
+static MULTICALL_RETRIES: AtomicU32 = AtomicU32::new(2);
+/// Oturum boyunca yapılan toplam yeniden deneme sayısı
+static RPC_RETRIES: AtomicU64 = AtomicU64::new(0);
+
+/// Yeniden deneme sınırını config'den yükle (main, run_bot öncesi).
+pub fn init_retry_policy(config: &crate::types::BotConfig) {
+    MULTICALL_RETRIES.store(config.multicall_retries, Ordering::Relaxed);
+}
+
+/// Oturum boyunca geçici hata sonrası yapılan yeniden deneme sayısı
+pub fn rpc_retries() -> u64 {
+    RPC_RETRIES.load(Ordering::Relaxed)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sync Zamanlaması — RPC Beklemesi ve Yerel İşlem Ayrı
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// sync_ms ağ + sağlayıcı süresini ve yerel işi (decode, matematik, state
+// yazımı) birlikte ölçer; gecikme artınca hangisinin yavaşladığı görünmez.
+// TimingGuard aşama başına ikisini ayırır: rpc() ile sarılan await'ler
+// (yeniden denemeler dahil) RPC kovasına, guard düşene kadar kalan süre
+// yerel kovaya yazılır. Multicall yolları ayrıca istek / yanıt ABI
+// boyutlarını kaydeder — adaptif aralık patlaması yanıtı şişirirse görünür.
+// Davranış değişmez; yalnızca ölçüm.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Zamanlaması ayrı tutulan sync aşamaları
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStage {
+    /// sync_pool_state — havuz başına slot0 + liquidity + fee
+    PoolSync,
+    /// sync_all_pools_multicall — toplu Multicall3
+    Multicall,
+    /// read_all_slot0 — FAST_PATH_SYNC faz 1
+    Slot0Probe,
+    /// tickBitmap + ticks Multicall3 (tam tarama ve kenar genişletme)
+    TickBitmap,
+}
+
+impl SyncStage {
+    pub const ALL: [SyncStage; 4] =
+        [Self::PoolSync, Self::Multicall, Self::Slot0Probe, Self::TickBitmap];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PoolSync => "pool_sync",
+            Self::Multicall => "multicall",
+            Self::Slot0Probe => "slot0_probe",
+            Self::TickBitmap => "tick_bitmap",
+        }
+    }
+
+    /// Aşamanın süreç geneli sayaçları
+    pub fn timing(self) -> &'static StageTiming {
+        &SYNC_TIMING[self as usize]
+    }
+}
+
+/// Aşama başına RPC / yerel süre histogramları (µs) ve Multicall payload sayaçları
+pub struct StageTiming {
+    pub rpc: LatencyHistogram,
+    pub local: LatencyHistogram,
+    payloads: AtomicU64,
+    request_bytes: AtomicU64,
+    response_bytes: AtomicU64,
+    max_response_bytes: AtomicU64,
+}
+
+/// Multicall payload boyutlarının özeti
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayloadSummary {
+    pub calls: u64,
+    pub request_bytes: u64,
+    pub response_bytes: u64,
+    pub max_response_bytes: u64,
+}
+
+impl PayloadSummary {
+    pub fn avg_request_bytes(&self) -> u64 {
+        self.request_bytes / self.calls.max(1)
+    }
+
+    pub fn avg_response_bytes(&self) -> u64 {
+        self.response_bytes / self.calls.max(1)
+    }
+}
+
+impl StageTiming {
+    pub const fn new() -> Self {
+        Self {
+            rpc: LatencyHistogram::new(),
+            local: LatencyHistogram::new(),
+            payloads: AtomicU64::new(0),
+            request_bytes: AtomicU64::new(0),
+            response_bytes: AtomicU64::new(0),
+            max_response_bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Başarılı Multicall3 çağrısının istek calldata'sı ve yanıt boyutu (byte)
+    pub fn record_payload(&self, request_bytes: usize, response_bytes: usize) {
+        self.payloads.fetch_add(1, Ordering::Relaxed);
+        self.request_bytes.fetch_add(request_bytes as u64, Ordering::Relaxed);
+        self.response_bytes.fetch_add(response_bytes as u64, Ordering::Relaxed);
+        self.max_response_bytes.fetch_max(response_bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn payload_summary(&self) -> PayloadSummary {
+        PayloadSummary {
+            calls: self.payloads.load(Ordering::Relaxed),
+            request_bytes: self.request_bytes.load(Ordering::Relaxed),
+            response_bytes: self.response_bytes.load(Ordering::Relaxed),
+            max_response_bytes: self.max_response_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for StageTiming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static SYNC_TIMING: [StageTiming; SyncStage::ALL.len()] = [const { StageTiming::new() }; SyncStage::ALL.len()];
+
+/// Bir sync çağrısının süresini RPC ve yerel kovalara bölen guard.
+///
+/// `rpc()` ile sarılan await'lerin süresi toplanır; guard düşünce (hata,
+/// `?` veya timeout ile iptal dahil) RPC toplamı ve geri kalan süre
+/// histogramlara birer örnek olarak yazılır.
+pub struct TimingGuard<'a> {
+    timing: &'a StageTiming,
+    started: Instant,
+    rpc_us: u64,
+}
+
+impl<'a> TimingGuard<'a> {
+    pub fn start(timing: &'a StageTiming) -> Self {
+        Self { timing, started: Instant::now(), rpc_us: 0 }
+    }
+
+    /// Ağ + sağlayıcı beklemesini RPC kovasına say
+    pub async fn rpc<F: Future>(&mut self, call: F) -> F::Output {
+        let started = Instant::now();
+        let output = call.await;
+        self.rpc_us += started.elapsed().as_micros() as u64;
+        output
+    }
+
+    pub fn record_payload(&self, request_bytes: usize, response_bytes: usize) {
+        self.timing.record_payload(request_bytes, response_bytes);
+    }
+}
+
+impl Drop for TimingGuard<'_> {
+    fn drop(&mut self) {
+        let total_us = self.started.elapsed().as_micros() as u64;
+        self.timing.rpc.record(self.rpc_us);
+        self.timing.local.record(total_us.saturating_sub(self.rpc_us));
+    }
+}
+
+/// `aggregate3(Call3[])` calldata'sının ABI boyutu (selector dahil)
+fn aggregate3_request_bytes(calls: &[IMulticall3::Call3]) -> usize {
+    4 + 64 + calls.iter().map(|c| 160 + c.callData.len().div_ceil(32) * 32).sum::<usize>()
+}
+
+/// `Result[]` dönüşünün ABI boyutu
+fn aggregate3_response_bytes(results: &[IMulticall3::Result]) -> usize {
+    64 + results.iter().map(|r| 128 + r.returnData.len().div_ceil(32) * 32).sum::<usize>()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Blok Sabitleme — Tüm Havuzlar Aynı Bloktan Okunur
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sabitlenen blok düğümde bulunamadığı için "latest"e düşen sync okumaları
+static UNPINNED_SYNCS: AtomicU64 = AtomicU64::new(0);
+
+/// Oturum boyunca "latest"e düşen (sabitlenemeyen) sync okuması sayısı
+pub fn unpinned_syncs() -> u64 {
+    UNPINNED_SYNCS.load(Ordering::Relaxed)
+}
+
+/// `read`'i abonelik başlığının bloğunda çalıştır. Düğüm o bloğun state'ini
+/// tutmuyorsa (budanmış / "header not found") bir kez "latest"te okur.
+///
+/// Dönüş: (okuma, sabitlendi mi). "latest" okuması PoolState'e
+/// `pinned_block = None` olarak yazılır — strateji o havuzla yürütme yapmaz.
+async fn read_pinned_or_latest<T, F, Fut>(
+    context: &str,
+    block_id: BlockId,
+    read: F,
+) -> BotResult<(T, bool)>
+where
+    F: Fn(BlockId) -> Fut,
+    Fut: Future<Output = BotResult<T>>,
+{
+    match read(block_id).await {
+        Err(e) if e.is_block_unavailable() && block_id != BlockId::latest() => {
+            UNPINNED_SYNCS.fetch_add(1, Ordering::Relaxed);
+            eprintln_high!(
+                "  \u{1f4cc} [{}] block {} unavailable on node — reading latest (unpinned): {}",
+                context, block_id, e,
+            );
+            Ok((read(BlockId::latest()).await?, false))
+        }
+        other => other.map(|value| (value, true)),
+    }
+}
+
+/// Bir çağrı grubunun toplam süre bütçesi — kategori zaman aşımı.
+///
+/// Yeniden denemeler bütçeyi uzatmaz: her deneme aynı son tarihe karşı
+/// koşar, beklemesi son tarihi aşacak deneme hiç başlatılmaz.
+pub struct CallBudget {
+    deadline: tokio::time::Instant,
+    category: &'static str,
+    timeout_ms: u64,
+}
+
+impl CallBudget {
+    pub fn new(category: &'static str, timeout_ms: u64) -> Self {
+        Self {
+            deadline: tokio::time::Instant::now() + std::time::Duration::from_millis(timeout_ms),
+            category,
+            timeout_ms,
+        }
+    }
+
+    fn timeout_error(&self, context: &str) -> BotError {
+        BotError::RpcTimeout {
+            context: context.to_string(),
+            category: self.category,
+            timeout_ms: self.timeout_ms,
+        }
+    }
+}
+
+/// `op`'u geçici (taşıma / timeout) hatalarda MULTICALL_RETRIES kez daha dene.
+///
+/// Decode ve veri hataları (`!is_retryable()`) ilk denemede döner; her
+/// yeniden deneme `rpc_retries()` sayacını artırır. Bütçe dolarsa son
+/// hata (ya da bütçe timeout'u) döner — blok döngüsü bekletilmez.
+pub async fn with_rpc_retry<T, F, Fut>(context: &str, budget: &CallBudget, op: F) -> BotResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = BotResult<T>>,
+{
+    retry_transient(context, budget, MULTICALL_RETRIES.load(Ordering::Relaxed), op).await
+}
+
+async fn retry_transient<T, F, Fut>(
+    context: &str,
+    budget: &CallBudget,
+    max_retries: u32,
+    mut op: F,
+) -> BotResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = BotResult<T>>,
+{
+    let mut attempt = 0u32;
+    loop {
+        let err = match tokio::time::timeout_at(budget.deadline, op()).await {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(e)) => e,
+            Err(_elapsed) => return Err(budget.timeout_error(context)),
+        };
+        if !err.is_retryable() || attempt >= max_retries {
+            return Err(err);
+        }
+        let backoff_ms = RETRY_BACKOFF_MIN_MS
+            + rand::random::<u64>() % (RETRY_BACKOFF_MAX_MS - RETRY_BACKOFF_MIN_MS + 1);
+        let resume_at = tokio::time::Instant::now() + std::time::Duration::from_millis(backoff_ms);
+        if resume_at >= budget.deadline {
+            return Err(err);
+        }
+        attempt += 1;
+        RPC_RETRIES.fetch_add(1, Ordering::Relaxed);
+        eprintln_high!(
+            "  \u{26a1} [{}] {} — retry {}/{} in {}ms",
+            context, err.kind(), attempt, max_retries, backoff_ms,
+        );
+        tokio::time::sleep_until(resume_at).await;
+    }
+}
 
 /// Tek bir havuzun durumunu RPC üzerinden oku ve SharedPoolState'e yaz
 ///
-/// v17.0: Sıkı timeout (500ms) + yeniden deneme (2 kez) mekanizması.
-///        RPC gecikmesi spike'ı (>500ms) durumunda eski veriyle devam etmek
-///        yerine hızlıca yeniden dener. 2 deneme sonrası hata döner.
+/// Geçici RPC hataları `with_rpc_retry` ile jitter'lı olarak yeniden
+/// denenir; tüm denemeler toplam SYNC_TIMEOUT_MS bütçesiyle sınırlıdır.
+/// Bozuk veri / decode hatası yeniden denenmez — DQ sayacı zaten
+/// commit_validated'da arttı.
 ///
 /// v10.0: slot0 ve liquidity sorguları artık paralel (tokio::join!)
 ///        Eski: 2 sıralı RPC çağrısı (2 RTT)
@@ -256,51 +662,19 @@ pub async fn sync_pool_state<P: Provider + Sync>(
     pool_config: &PoolConfig,
     pool_state: &SharedPoolState,
     block_number: u64,
-) -> Result<()> {
-    let mut last_err: Option<eyre::Report> = None;
-
-    for attempt in 0..=SYNC_MAX_RETRIES {
-        match tokio::time::timeout(
-            std::time::Duration::from_millis(SYNC_TIMEOUT_MS),
-            sync_pool_state_inner(provider, pool_config, pool_state, block_number),
-        )
-        .await
-        {
-            Ok(Ok(())) => return Ok(()),
-            Ok(Err(e)) => {
-                // RPC hatası (timeout değil) — yeniden deneme
-                if attempt < SYNC_MAX_RETRIES {
-                    eprintln!(
-                        "  \u{26a1} [{}] Sync error (attempt {}/{}): {}",
-                        pool_config.name,
-                        attempt + 1,
-                        SYNC_MAX_RETRIES + 1,
-                        e
-                    );
-                }
-                last_err = Some(e);
-            }
-            Err(_elapsed) => {
-                // Timeout — yeniden deneme
-                if attempt < SYNC_MAX_RETRIES {
-                    eprintln!(
-                        "  \u{26a1} [{}] Sync timeout ({}ms, attempt {}/{})",
-                        pool_config.name,
-                        SYNC_TIMEOUT_MS,
-                        attempt + 1,
-                        SYNC_MAX_RETRIES + 1,
-                    );
-                }
-                last_err = Some(eyre::eyre!(
-                    "[{}] sync_pool_state timeout ({}ms)",
-                    pool_config.name,
-                    SYNC_TIMEOUT_MS
-                ));
-            }
-        }
-    }
+    block_id: BlockId,
+) -> BotResult<()> {
+    let budget = CallBudget::new("sync", SYNC_TIMEOUT_MS);
+    with_rpc_retry(&format!("[{}] sync_pool_state", pool_config.name), &budget, || {
+        sync_pool_state_inner(provider, pool_config, pool_state, block_number, block_id)
+    })
+    .await
+}
 
-    Err(last_err.unwrap_or_else(|| eyre::eyre!("[{}] sync failed", pool_config.name)))
+#[cfg(test)]
+fn sync_timeout(pool_config: &PoolConfig) -> BotError {
+    CallBudget::new("sync", SYNC_TIMEOUT_MS)
+        .timeout_error(&format!("[{}] sync_pool_state", pool_config.name))
 }
 
 /// sync_pool_state iç implementasyonu (timeout wrapper'sız)
@@ -309,24 +683,65 @@ async fn sync_pool_state_inner<P: Provider + Sync>(
     pool_config: &PoolConfig,
     pool_state: &SharedPoolState,
     block_number: u64,
-) -> Result<()> {
+    block_id: BlockId,
+) -> BotResult<()> {
+    let mut timing = TimingGuard::start(SyncStage::PoolSync.timing());
+    let context = format!("[{}] sync_pool_state", pool_config.name);
+    let ((sqrt_price_x96, tick, liquidity, live_fee_bps), pinned) = timing
+        .rpc(read_pinned_or_latest(&context, block_id, |block| {
+            read_pool_slot0(provider, pool_config, block)
+        }))
+        .await?;
+
+    apply_pool_read(
+        pool_config,
+        pool_state,
+        sqrt_price_x96,
+        tick,
+        liquidity,
+        live_fee_bps,
+        block_number,
+        pinned,
+    )
+    .map_err(|issue| BotError::pool_data(&pool_config.name, issue))
+}
+
+/// Havuzun slot0 + liquidity'si geçmiş bir blokta (gölge doğrulaması).
+/// Sabitli okuma — düğüm bloğu tutmuyorsa "latest"e düşmez, hata döner.
+pub async fn read_pool_at_block<P: Provider + Sync>(
+    provider: &P,
+    pool_config: &PoolConfig,
+    block: u64,
+) -> BotResult<(U256, i32, u128)> {
+    let budget = CallBudget::new("sync", SYNC_TIMEOUT_MS);
+    let (sqrt_price_x96, tick, liquidity, _) =
+        with_rpc_retry(&format!("[{}] read_pool_at_block", pool_config.name), &budget, || {
+            read_pool_slot0(provider, pool_config, BlockId::number(block))
+        })
+        .await?;
+    Ok((sqrt_price_x96, tick, liquidity))
+}
+
+/// slot0 + liquidity + fee — üçü de aynı `block` üzerinde (tek RTT, paralel)
+async fn read_pool_slot0<P: Provider + Sync>(
+    provider: &P,
+    pool_config: &PoolConfig,
+    block: BlockId,
+) -> BotResult<(U256, i32, u128, Option<u32>)> {
     let (sqrt_price_x96, tick, liquidity, live_fee_bps) = match pool_config.dex {
         DexType::UniswapV3 => {
             let pool = IUniswapV3Pool::new(pool_config.address, provider);
-            let slot0_call = pool.slot0();
-            let liq_call = pool.liquidity();
-            let fee_call = pool.fee();
+            let slot0_call = pool.slot0().block(block);
+            let liq_call = pool.liquidity().block(block);
+            let fee_call = pool.fee().block(block);
             let (slot0_result, liq_result, fee_result) =
                 tokio::join!(slot0_call.call(), liq_call.call(), fee_call.call(),);
             let slot0 = slot0_result.map_err(|e| {
-                eyre::eyre!(
-                    "[{}] slot0 read error (V3/7-field/uint8): {}",
-                    pool_config.name,
-                    e
-                )
+                BotError::from_call(format!("[{}] slot0 (V3/7-field/uint8)", pool_config.name), e)
+            })?;
+            let liq = liq_result.map_err(|e| {
+                BotError::from_call(format!("[{}] liquidity", pool_config.name), e)
             })?;
-            let liq = liq_result
-                .map_err(|e| eyre::eyre!("[{}] liquidity read error: {}", pool_config.name, e))?;
             let fee_bps: Option<u32> = fee_result.ok().map(|f| {
                 let fee_u32: u32 = f.to();
                 fee_u32 / 100
@@ -335,22 +750,23 @@ async fn sync_pool_state_inner<P: Provider + Sync>(
         }
         DexType::PancakeSwapV3 => {
             let pool = IPancakeSwapV3Pool::new(pool_config.address, provider);
-            let slot0_call = pool.slot0();
-            let liq_call = pool.liquidity();
-            let fee_call = pool.fee();
+            let slot0_call = pool.slot0().block(block);
+            let liq_call = pool.liquidity().block(block);
+            let fee_call = pool.fee().block(block);
             let (slot0_result, liq_result, fee_result) =
                 tokio::join!(slot0_call.call(), liq_call.call(), fee_call.call(),);
             let slot0 = slot0_result.map_err(|e| {
-                eyre::eyre!(
-                    "[{}] slot0 read error (PCS-V3/7-field/uint32): {}\n\
-                    → Is the pool address a valid PancakeSwap V3 Pool? Check: {}",
-                    pool_config.name,
+                BotError::from_call(
+                    format!(
+                        "[{}] slot0 (PCS-V3/7-field/uint32) — is {} a valid PancakeSwap V3 Pool?",
+                        pool_config.name, pool_config.address
+                    ),
                     e,
-                    pool_config.address
                 )
             })?;
-            let liq = liq_result
-                .map_err(|e| eyre::eyre!("[{}] liquidity read error: {}", pool_config.name, e))?;
+            let liq = liq_result.map_err(|e| {
+                BotError::from_call(format!("[{}] liquidity", pool_config.name), e)
+            })?;
             let fee_bps: Option<u32> = fee_result.ok().map(|f| {
                 let fee_u32: u32 = f.to();
                 fee_u32 / 100
@@ -359,61 +775,109 @@ async fn sync_pool_state_inner<P: Provider + Sync>(
         }
         DexType::Aerodrome => {
             let pool = IAerodromePool::new(pool_config.address, provider);
-            let slot0_call = pool.slot0();
-            let liq_call = pool.liquidity();
-            let fee_call = pool.fee();
+            let slot0_call = pool.slot0().block(block);
+            let liq_call = pool.liquidity().block(block);
+            let fee_call = pool.fee().block(block);
             let (slot0_result, liq_result, fee_result) =
                 tokio::join!(slot0_call.call(), liq_call.call(), fee_call.call(),);
             let slot0 = slot0_result.map_err(|e| {
-                eyre::eyre!(
-                    "[{}] slot0 read error (Aero/6-field): {}\n\
-                    → Is the pool address a valid Aerodrome CLPool? Check: {}",
-                    pool_config.name,
+                BotError::from_call(
+                    format!(
+                        "[{}] slot0 (Aero/6-field) — is {} a valid Aerodrome CLPool?",
+                        pool_config.name, pool_config.address
+                    ),
                     e,
-                    pool_config.address
                 )
             })?;
-            let liq = liq_result
-                .map_err(|e| eyre::eyre!("[{}] liquidity read error: {}", pool_config.name, e))?;
+            let liq = liq_result.map_err(|e| {
+                BotError::from_call(format!("[{}] liquidity", pool_config.name), e)
+            })?;
             let fee_bps: Option<u32> = fee_result.ok().map(|f| {
                 let fee_u32: u32 = f.to();
                 fee_u32 / 100
             });
             (slot0.sqrtPriceX96, slot0.tick.as_i32(), liq, fee_bps)
         }
+        // V2'de fee() yok — yapılandırılan kademe kullanılır
+        DexType::UniswapV2 => {
+            let (sqrt_price_x96, tick, liquidity) = read_pool_reserves(provider, pool_config, block).await?;
+            return Ok((sqrt_price_x96, tick, liquidity, None));
+        }
     };
 
-    let sqrt_price_f64: f64 = u256_to_f64(U256::from(sqrt_price_x96));
-    let liquidity_f64: f64 = u256_to_f64(U256::from(liquidity));
+    Ok((U256::from(sqrt_price_x96), tick, liquidity, live_fee_bps))
+}
 
-    let eth_price = compute_eth_price(
-        sqrt_price_f64,
+/// getReserves() → sanal CL state (sqrtPriceX96, tick, L) — V2 havuzları
+async fn read_pool_reserves<P: Provider + Sync>(
+    provider: &P,
+    pool_config: &PoolConfig,
+    block: BlockId,
+) -> BotResult<(U256, i32, u128)> {
+    let pair = IUniswapV2Pair::new(pool_config.address, provider);
+    let reserves = pair.getReserves().block(block).call().await.map_err(|e| {
+        BotError::from_call(format!("[{}] getReserves (V2)", pool_config.name), e)
+    })?;
+    crate::math::exact::v2_virtual_state(reserves.reserve0, reserves.reserve1).ok_or_else(|| {
+        BotError::AbiDecode {
+            context: format!("[{}] getReserves", pool_config.name),
+            what: format!("reserves {}/{} (empty or out of price range)", reserves.reserve0, reserves.reserve1),
+        }
+    })
+}
+
+/// slot0 + liquidity + fee okumasından türetilen state'i doğrulayıp yaz
+#[allow(clippy::too_many_arguments)]
+fn apply_pool_read(
+    pool_config: &PoolConfig,
+    pool_state: &SharedPoolState,
+    sqrt_price_x96: U256,
+    tick: i32,
+    liquidity: u128,
+    live_fee_bps: Option<u32>,
+    block_number: u64,
+    pinned: bool,
+) -> std::result::Result<(), DataQualityIssue> {
+    let sqrt_price_f64: f64 = u256_to_f64(sqrt_price_x96);
+    let eth_price = pool_eth_price(pool_config, sqrt_price_x96, tick, liquidity);
+
+    commit_validated(pool_config, pool_state, |s| {
+        s.sqrt_price_x96 = sqrt_price_x96;
+        s.sqrt_price_f64 = sqrt_price_f64;
+        s.tick = tick;
+        s.set_liquidity(liquidity);
+        s.eth_price_usd = eth_price;
+        s.last_block = block_number;
+        s.pinned_block = pinned.then_some(block_number);
+        s.last_update = Instant::now();
+        s.is_initialized = true;
+        s.is_stale = false;
+        s.live_fee_bps = crate::fee_monitor::gate_live_fee(pool_config.fee_bps(), live_fee_bps);
+        s.optimistic_trigger = None;
+        s.constant_product = pool_config.is_constant_product();
+    })
+}
+
+/// Okunan state'in ETH fiyatı — V2'de rezerv oranından, CL'de
+/// sqrtPrice + tick çapraz doğrulamasıyla
+fn pool_eth_price(pool_config: &PoolConfig, sqrt_price_x96: U256, tick: i32, liquidity: u128) -> f64 {
+    if pool_config.is_constant_product() {
+        let (reserve0, reserve1) = crate::math::exact::v2_reserves(sqrt_price_x96, liquidity);
+        return crate::math::compute_eth_price_from_reserves(
+            reserve0,
+            reserve1,
+            pool_config.token0_decimals,
+            pool_config.token1_decimals,
+            pool_config.token0_is_weth,
+        );
+    }
+    compute_eth_price(
+        u256_to_f64(sqrt_price_x96),
         tick,
         pool_config.token0_decimals,
         pool_config.token1_decimals,
         pool_config.token0_is_weth,
-    );
-
-    {
-        let sqrt_price_x96_val = U256::from(sqrt_price_x96);
-        pool_state.rcu(|old| {
-            let mut s = (**old).clone();
-            s.sqrt_price_x96 = sqrt_price_x96_val;
-            s.sqrt_price_f64 = sqrt_price_f64;
-            s.tick = tick;
-            s.liquidity = liquidity;
-            s.liquidity_f64 = liquidity_f64;
-            s.eth_price_usd = eth_price;
-            s.last_block = block_number;
-            s.last_update = Instant::now();
-            s.is_initialized = true;
-            s.is_stale = false;
-            s.live_fee_bps = live_fee_bps;
-            s
-        });
-    }
-
-    Ok(())
+    )
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -439,6 +903,35 @@ fn encode_fee_call() -> Vec<u8> {
     IUniswapV3Pool::feeCall::abi_encode(&call)
 }
 
+/// getReserves() — V2 / Aerodrome vAMM (selector: 0x0902f1ac)
+fn encode_get_reserves_call() -> Vec<u8> {
+    let call = IUniswapV2Pair::getReservesCall {};
+    IUniswapV2Pair::getReservesCall::abi_encode(&call)
+}
+
+/// stakedLiquidity() — yalnızca Aerodrome Slipstream (gauge'daki aktif likidite)
+fn encode_staked_liquidity_call() -> Vec<u8> {
+    let call = IAerodromePool::stakedLiquidityCall {};
+    IAerodromePool::stakedLiquidityCall::abi_encode(&call)
+}
+
+/// unstakedFee() — yalnızca Aerodrome Slipstream (pips, 1e6 = %100)
+fn encode_unstaked_fee_call() -> Vec<u8> {
+    let call = IAerodromePool::unstakedFeeCall {};
+    IAerodromePool::unstakedFeeCall::abi_encode(&call)
+}
+
+/// Multicall3 sonucundan getReserves'i decode et → sanal CL state
+/// (sqrtPriceX96, tick, L). Kısa veri / boş havuz → None.
+fn decode_reserves_result(data: &[u8]) -> Option<(U256, i32, u128)> {
+    if data.len() < 96 {
+        return None;
+    }
+    let reserve0 = U256::from_be_slice(&data[0..32]);
+    let reserve1 = U256::from_be_slice(&data[32..64]);
+    crate::math::exact::v2_virtual_state(reserve0, reserve1)
+}
+
 /// Multicall3 sonucundan slot0 verisini decode et (DEX tipine göre)
 ///
 /// # Dönüş
@@ -494,6 +987,65 @@ fn decode_fee_result(data: &[u8]) -> Option<u32> {
     Some(fee_raw / 100)
 }
 
+/// Tek bir havuzun zincirdeki fee() değerini oku (bps).
+///
+/// fee_monitor'ün saatlik ücret doğrulaması için — hot path'te kullanılmaz.
+pub async fn read_pool_fee_bps<P: Provider + Sync>(provider: &P, pool_config: &PoolConfig) -> Option<u32> {
+    // V2 ücreti sabit (fee() yok) — yapılandırılan kademe geçerli
+    if pool_config.is_constant_product() {
+        return None;
+    }
+    let tx = TransactionRequest::default()
+        .to(pool_config.address)
+        .input(Bytes::from(encode_fee_call()).into());
+    match provider.call(tx).await {
+        Ok(raw) => decode_fee_result(&raw),
+        Err(e) => {
+            eprintln_high!("  ⚠️ [FeeMonitor] [{}] fee() read failed: {}", pool_config.name, e);
+            None
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kontrat Pause View'ı — Havuz Batch'ine Eklenir (contract_state)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// contract_state'in pause view'ını batch'in sonuna ekle — eklendiyse indeksi
+fn attach_pause_view(calls: &mut Vec<IMulticall3::Call3>) -> Option<usize> {
+    let (target, call_data) = crate::contract_state::piggyback_call()?;
+    calls.push(IMulticall3::Call3 { target, allowFailure: true, callData: call_data });
+    Some(calls.len() - 1)
+}
+
+/// Eklenen view'ın sonucunu havuz sonuçlarından ayır. Uzunluk beklenenden
+/// farklıysa okuma yapılmaz — havuz sonuçları kaymış olabilir.
+fn detach_pause_view(results: &mut Vec<IMulticall3::Result>, slot: Option<usize>) {
+    if slot.is_some_and(|slot| results.len() == slot + 1) {
+        if let Some(result) = results.pop() {
+            crate::contract_state::observe(result.success, &result.returnData);
+        }
+    }
+}
+
+/// Pause view'ını tek başına oku — bu blokta view'ı taşıyan batch yoksa
+/// (başlangıç sync'i taşımadıysa, FAST_PATH_SYNC kapalıyken sync'siz bloklar)
+pub async fn read_pause_view<P: Provider + Sync>(provider: &P, block_id: BlockId) -> BotResult<()> {
+    let mut calls = Vec::with_capacity(1);
+    let Some(slot) = attach_pause_view(&mut calls) else {
+        return Ok(());
+    };
+    let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
+    let budget = CallBudget::new("pause_view", SLOT0_PROBE_TIMEOUT_MS);
+    let mut results = with_rpc_retry("Multicall3 pause view", &budget, || {
+        let call = multicall.aggregate3(calls.clone()).block(block_id);
+        async move { call.call().await.map_err(|e| BotError::from_call("Multicall3 pause view", e)) }
+    })
+    .await?;
+    detach_pause_view(&mut results, Some(slot));
+    Ok(())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Toplu Multicall3 State Sync — Tüm Havuzları TEK eth_call ile Oku
 // ─────────────────────────────────────────────────────────────────────────────
@@ -518,27 +1070,40 @@ pub async fn sync_all_pools_multicall<P: Provider + Sync>(
     pools: &[PoolConfig],
     states: &[SharedPoolState],
     block_number: u64,
-) -> Vec<Result<()>> {
+    block_id: BlockId,
+) -> Vec<BotResult<()>> {
     let pool_count = pools.len();
     if pool_count == 0 {
         return vec![];
     }
 
-    let mut results: Vec<Result<()>> = (0..pool_count).map(|_| Ok(())).collect();
+    let mut timing = TimingGuard::start(SyncStage::Multicall.timing());
+    let mut results: Vec<BotResult<()>> = (0..pool_count).map(|_| Ok(())).collect();
 
     // Havuzları chunk'lara böl (her chunk max MULTICALL_CHUNK_SIZE havuz)
     for chunk_start in (0..pool_count).step_by(MULTICALL_CHUNK_SIZE) {
         let chunk_end = (chunk_start + MULTICALL_CHUNK_SIZE).min(pool_count);
         let chunk_size = chunk_end - chunk_start;
 
-        // Her havuz için 3 çağrı: slot0, liquidity, fee
+        // CL havuzu için 3 çağrı: slot0, liquidity, fee — V2 havuzu için tek
+        // getReserves. Havuzun ilk çağrısının indeksi `offsets`'te tutulur.
         let slot0_calldata = encode_slot0_call();
         let liquidity_calldata = encode_liquidity_call();
         let fee_calldata = encode_fee_call();
 
         let mut calls: Vec<IMulticall3::Call3> = Vec::with_capacity(chunk_size * 3);
+        let mut offsets: Vec<usize> = Vec::with_capacity(chunk_size);
 
         for pool in pools.iter().take(chunk_end).skip(chunk_start) {
+            offsets.push(calls.len());
+            if pool.is_constant_product() {
+                calls.push(IMulticall3::Call3 {
+                    target: pool.address,
+                    allowFailure: true,
+                    callData: Bytes::from(encode_get_reserves_call()),
+                });
+                continue;
+            }
             // slot0
             calls.push(IMulticall3::Call3 {
                 target: pool.address,
@@ -558,57 +1123,56 @@ pub async fn sync_all_pools_multicall<P: Provider + Sync>(
                 callData: Bytes::from(fee_calldata.clone()),
             });
         }
+        let expected_results = calls.len();
+        // Kontrat pause view'ı ilk chunk'a eklenir — ayrı eth_call yok
+        let pause_slot = if chunk_start == 0 { attach_pause_view(&mut calls) } else { None };
 
-        // Multicall3 ile TEK eth_call
+        // Multicall3 ile TEK eth_call, abonelik bloğuna sabitli
+        // (geçici hatada bütçe içinde yeniden dene)
         let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
-        let mc_result = match tokio::time::timeout(
-            std::time::Duration::from_millis(SYNC_TIMEOUT_MS),
-            multicall.aggregate3(calls).call(),
-        )
-        .await
-        {
-            Ok(Ok(res)) => res,
-            Ok(Err(e)) => {
-                // Multicall3 çağrısı başarısız — tüm chunk'ı stale işaretle
-                for i in chunk_start..chunk_end {
-                    states[i].rcu(|old| {
-                        let mut s = (**old).clone();
-                        s.is_stale = true;
-                        s
-                    });
-                    results[i] = Err(eyre::eyre!(
-                        "[{}] Multicall3 batch sync error: {}",
-                        pools[i].name,
-                        e
-                    ));
-                }
-                continue;
+        let budget = CallBudget::new("multicall", SYNC_TIMEOUT_MS);
+        let read_at = |block: BlockId| {
+            let call = multicall.aggregate3(calls.clone()).block(block);
+            async move {
+                call.call().await.map_err(|e| BotError::from_call("Multicall3 batch sync", e))
+            }
+        };
+        let batch = timing
+            .rpc(with_rpc_retry("Multicall3 batch sync", &budget, || {
+                read_pinned_or_latest("Multicall3 batch sync", block_id, read_at)
+            }))
+            .await;
+        let (mut mc_result, pinned) = match batch {
+            Ok(res) => {
+                timing.record_payload(aggregate3_request_bytes(&calls), aggregate3_response_bytes(&res.0));
+                res
             }
-            Err(_elapsed) => {
-                // Timeout — tüm chunk'ı stale işaretle
+            Err(batch_err) => {
+                // Multicall3 çağrısı başarısız / timeout — tüm chunk'ı stale işaretle
                 for i in chunk_start..chunk_end {
                     states[i].rcu(|old| {
                         let mut s = (**old).clone();
                         s.is_stale = true;
                         s
                     });
-                    results[i] = Err(eyre::eyre!(
-                        "[{}] Multicall3 sync timeout ({}ms)",
-                        pools[i].name,
-                        SYNC_TIMEOUT_MS
-                    ));
+                    results[i] = Err(batch_err.replicate(format!(
+                        "[{}] Multicall3 batch sync",
+                        pools[i].name
+                    )));
+                }
+                if matches!(batch_err, BotError::RpcTimeout { .. }) {
+                    eprintln_high!(
+                        "  \u{26a0}\u{fe0f} [Multicall3] Chunk {}-{} timeout ({}ms) — {} pools marked as STALE",
+                        chunk_start, chunk_end, SYNC_TIMEOUT_MS, chunk_size,
+                    );
                 }
-                eprintln!(
-                    "  \u{26a0}\u{fe0f} [Multicall3] Chunk {}-{} timeout ({}ms) — {} pools marked as STALE",
-                    chunk_start, chunk_end, SYNC_TIMEOUT_MS, chunk_size,
-                );
                 continue;
             }
         };
+        detach_pause_view(&mut mc_result, pause_slot);
 
-        let expected_results = chunk_size * 3;
         if mc_result.len() != expected_results {
-            eprintln!(
+            eprintln_high!(
                 "  ⚠️ [Multicall3] Unexpected result length: expected={} got={} (chunk {}-{})",
                 expected_results,
                 mc_result.len(),
@@ -617,11 +1181,43 @@ pub async fn sync_all_pools_multicall<P: Provider + Sync>(
             );
         }
 
-        // Sonuçları decode et — her havuz için 3 sonuç (slot0, liquidity, fee)
+        // Sonuçları decode et — CL havuzu için 3 sonuç (slot0, liquidity, fee)
         for (idx_in_chunk, pool_idx) in (chunk_start..chunk_end).enumerate() {
-            let base = idx_in_chunk * 3;
+            let base = offsets[idx_in_chunk];
 
-            // Sonuçları al
+            if pools[pool_idx].is_constant_product() {
+                let reserves = match mc_result.get(base) {
+                    Some(r) if r.success => decode_reserves_result(&r.returnData),
+                    _ => None,
+                };
+                results[pool_idx] = match reserves {
+                    Some((sqrt_price_x96, tick, liquidity)) => apply_pool_read(
+                        &pools[pool_idx],
+                        &states[pool_idx],
+                        sqrt_price_x96,
+                        tick,
+                        liquidity,
+                        None,
+                        block_number,
+                        pinned,
+                    )
+                    .map_err(|issue| BotError::pool_data(&pools[pool_idx].name, issue)),
+                    None => {
+                        states[pool_idx].rcu(|old| {
+                            let mut s = (**old).clone();
+                            s.is_stale = true;
+                            s
+                        });
+                        Err(BotError::AbiDecode {
+                            context: format!("[{}] Multicall3", pools[pool_idx].name),
+                            what: "getReserves (execution reverted or empty pool?)".into(),
+                        })
+                    }
+                };
+                continue;
+            }
+
+            // Sonuçları al
             let slot0_res = mc_result.get(base);
             let liq_res = mc_result.get(base + 1);
             let fee_res = mc_result.get(base + 2);
@@ -653,36 +1249,18 @@ pub async fn sync_all_pools_multicall<P: Provider + Sync>(
             // slot0 veya liquidity başarısız → havuz STALE
             match (slot0_data, liquidity_data) {
                 (Some((sqrt_price_x96, tick)), Some(liquidity)) => {
-                    // Başarılı — state güncelle
-                    let sqrt_price_f64: f64 = u256_to_f64(sqrt_price_x96);
-                    let liquidity_f64: f64 = u256_to_f64(U256::from(liquidity));
-
-                    let eth_price = compute_eth_price(
-                        sqrt_price_f64,
+                    // Başarılı okuma — doğrulanırsa state güncellenir
+                    results[pool_idx] = apply_pool_read(
+                        &pools[pool_idx],
+                        &states[pool_idx],
+                        sqrt_price_x96,
                         tick,
-                        pools[pool_idx].token0_decimals,
-                        pools[pool_idx].token1_decimals,
-                        pools[pool_idx].token0_is_weth,
-                    );
-
-                    {
-                        states[pool_idx].rcu(|old| {
-                            let mut s = (**old).clone();
-                            s.sqrt_price_x96 = sqrt_price_x96;
-                            s.sqrt_price_f64 = sqrt_price_f64;
-                            s.tick = tick;
-                            s.liquidity = liquidity;
-                            s.liquidity_f64 = liquidity_f64;
-                            s.eth_price_usd = eth_price;
-                            s.last_block = block_number;
-                            s.last_update = Instant::now();
-                            s.is_initialized = true;
-                            s.is_stale = false;
-                            s.live_fee_bps = fee_bps;
-                            s
-                        });
-                    }
-                    results[pool_idx] = Ok(());
+                        liquidity,
+                        fee_bps,
+                        block_number,
+                        pinned,
+                    )
+                    .map_err(|issue| BotError::pool_data(&pools[pool_idx].name, issue));
                 }
                 _ => {
                     // Decode failed — mark pool as STALE
@@ -691,10 +1269,10 @@ pub async fn sync_all_pools_multicall<P: Provider + Sync>(
                         s.is_stale = true;
                         s
                     });
-                    results[pool_idx] = Err(eyre::eyre!(
-                        "[{}] Multicall3 slot0/liquidity decode failed (execution reverted?)",
-                        pools[pool_idx].name
-                    ));
+                    results[pool_idx] = Err(BotError::AbiDecode {
+                        context: format!("[{}] Multicall3", pools[pool_idx].name),
+                        what: "slot0/liquidity (execution reverted?)".into(),
+                    });
                 }
             }
         }
@@ -703,6 +1281,193 @@ pub async fn sync_all_pools_multicall<P: Provider + Sync>(
     results
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// İki Fazlı Sync — slot0 Hızlı Yolu (FAST_PATH_SYNC)
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Sessiz bloklarda (havuzlarımızda swap yok) fırsat kontrolü ve ardındaki
+// defter tutma boşa çalışır. Faz 1 yalnızca slot0'ı okur; hiçbir havuzun
+// fiyatı son değerlendirmeden beri değişmediyse blok "sessiz" sayılır.
+// Likidite yalnızca mint/burn ile değişir — kaçırılmış olanları periyodik
+// tam sync (FULL_SYNC_INTERVAL_BLOCKS) yakalar.
+
+/// Faz 1 zaman bütçesi — aşılırsa blok tam yoldan işlenir
+const SLOT0_PROBE_TIMEOUT_MS: u64 = 500;
+
+/// Sessiz blokların işleme süresi (µs)
+pub static QUIET_BLOCK_LATENCY: LatencyHistogram = LatencyHistogram::new();
+/// Tam yoldan (fırsat kontrolü dahil) işlenen blokların süresi (µs)
+pub static FULL_BLOCK_LATENCY: LatencyHistogram = LatencyHistogram::new();
+
+/// Faz 1: tüm havuzların yalnızca slot0'ı, abonelik bloğuna sabitli Multicall3.
+///
+/// Havuz başına sqrtPriceX96 (çağrı / decode başarısızsa None). Budanmış
+/// blokta "latest"e düşülmez — o bloğun fiyatını kanıtlamayan okuma hatadır.
+pub async fn read_all_slot0<P: Provider + Sync>(
+    provider: &P,
+    pools: &[PoolConfig],
+    block_id: BlockId,
+) -> BotResult<Vec<Option<U256>>> {
+    let slot0_calldata = Bytes::from(encode_slot0_call());
+    let reserves_calldata = Bytes::from(encode_get_reserves_call());
+    let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
+    let budget = CallBudget::new("slot0_probe", SLOT0_PROBE_TIMEOUT_MS);
+    let mut timing = TimingGuard::start(SyncStage::Slot0Probe.timing());
+    let mut prices = Vec::with_capacity(pools.len());
+
+    // Havuz başına tek çağrı — tam sync chunk'ıyla aynı payload boyutu
+    for (chunk_idx, chunk) in pools.chunks(MULTICALL_CHUNK_SIZE * 3).enumerate() {
+        let mut calls: Vec<IMulticall3::Call3> = chunk
+            .iter()
+            .map(|pool| IMulticall3::Call3 {
+                target: pool.address,
+                allowFailure: true,
+                callData: if pool.is_constant_product() {
+                    reserves_calldata.clone()
+                } else {
+                    slot0_calldata.clone()
+                },
+            })
+            .collect();
+        // Kontrat pause view'ı — tam sync'siz bloklarda da okunur
+        let pause_slot = if chunk_idx == 0 { attach_pause_view(&mut calls) } else { None };
+        let mut results = timing
+            .rpc(with_rpc_retry("Multicall3 slot0 probe", &budget, || {
+                let call = multicall.aggregate3(calls.clone()).block(block_id);
+                async move {
+                    call.call().await.map_err(|e| BotError::from_call("Multicall3 slot0 probe", e))
+                }
+            }))
+            .await?;
+        timing.record_payload(aggregate3_request_bytes(&calls), aggregate3_response_bytes(&results));
+        detach_pause_view(&mut results, pause_slot);
+        prices.extend(chunk.iter().enumerate().map(|(i, pool)| {
+            results
+                .get(i)
+                .filter(|r| r.success)
+                .and_then(|r| {
+                    if pool.is_constant_product() {
+                        decode_reserves_result(&r.returnData).map(|(sqrt_price_x96, _, _)| sqrt_price_x96)
+                    } else {
+                        decode_slot0_result(&r.returnData, pool.dex).map(|(sqrt_price_x96, _)| sqrt_price_x96)
+                    }
+                })
+        }));
+    }
+    Ok(prices)
+}
+
+/// Bloğun işleniş yolu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockSync {
+    /// Hiçbir havuzun fiyatı değişmedi — faz 2 ve fırsat kontrolü atlanır
+    Quiet,
+    /// Fiyat değişti / faz 1 okunamadı / hızlı yol kapalı — olağan yol
+    Changed,
+    /// Periyodik tam sync (slot0 + liquidity + fee) — kaçırılmış mint/burn'e karşı
+    FullSync,
+}
+
+impl BlockSync {
+    pub fn runs_opportunity_check(self) -> bool {
+        self != Self::Quiet
+    }
+}
+
+/// Hızlı yol durumu — run_bot başına bir tane
+#[derive(Debug)]
+pub struct FastPathSync {
+    enabled: bool,
+    full_sync_interval: u64,
+    last_full_sync: u64,
+    /// Fırsat kontrolünün son gördüğü sqrtPriceX96 (havuz sırasıyla)
+    evaluated: Vec<U256>,
+}
+
+impl FastPathSync {
+    /// `start_block`: ilk sync'in yapıldığı blok
+    pub fn new(config: &crate::types::BotConfig, start_block: u64) -> Self {
+        Self {
+            enabled: config.fast_path_sync,
+            full_sync_interval: config.full_sync_interval_blocks,
+            last_full_sync: start_block,
+            evaluated: Vec::new(),
+        }
+    }
+
+    /// Periyodik tam sync zamanı mı? Aralık gerçek blok numaralarıyla ölçülür;
+    /// bitmap yaş sınırını aşan bir atlama (uzun takılma: event'ler de kaçmış
+    /// olabilir) sync'i hemen tetikler.
+    pub fn full_sync_due(&self, block: u64, backlog_skipped: u64, max_age_blocks: u64) -> bool {
+        block.saturating_sub(self.last_full_sync) >= self.full_sync_interval
+            || backlog_skipped > max_age_blocks
+    }
+
+    /// Faz 1 okuması gerekli mi? (tam sync zaten her şeyi okuyacaksa hayır)
+    pub fn probes(&self, full_sync_due: bool) -> bool {
+        self.enabled && !full_sync_due
+    }
+
+    pub fn record_full_sync(&mut self, block: u64) {
+        self.last_full_sync = block;
+    }
+
+    /// Faz 1 sonucunu sınıflandır. Sessiz: her havuzun okunan fiyatı hem
+    /// saklanan state'le hem son değerlendirmeyle aynı — zincirle uyuşmayan
+    /// iyimser (bekleyen TX) güncellemesi olan blok sessiz sayılmaz.
+    pub fn classify(
+        &self,
+        full_sync: bool,
+        slot0: Option<&[Option<U256>]>,
+        states: &[SharedPoolState],
+    ) -> BlockSync {
+        if full_sync {
+            return BlockSync::FullSync;
+        }
+        let Some(slot0) = slot0.filter(|_| self.enabled) else {
+            return BlockSync::Changed;
+        };
+        if slot0.len() != states.len() || self.evaluated.len() != states.len() {
+            return BlockSync::Changed;
+        }
+        let unchanged = slot0
+            .iter()
+            .zip(states)
+            .zip(&self.evaluated)
+            .all(|((read, state), seen)| {
+                let st = state.load();
+                st.is_initialized
+                    && !st.is_stale
+                    && *read == Some(st.sqrt_price_x96)
+                    && st.sqrt_price_x96 == *seen
+            });
+        if unchanged {
+            BlockSync::Quiet
+        } else {
+            BlockSync::Changed
+        }
+    }
+
+    /// Fırsat kontrolü çalıştı — sonraki blokların karşılaştırma tabanı
+    pub fn record_evaluated(&mut self, states: &[SharedPoolState]) {
+        self.evaluated = states.iter().map(|s| s.load().sqrt_price_x96).collect();
+    }
+}
+
+/// Sessiz blok: fiyat bu blokta zincirde doğrulandı — yalnızca blok ve
+/// zaman damgası ilerler (likidite, bitmap, ücret dokunulmaz)
+pub fn mark_quiet_block(states: &[SharedPoolState], block_number: u64) {
+    for state in states {
+        state.rcu(|old| {
+            let mut s = (**old).clone();
+            s.last_block = block_number;
+            s.pinned_block = Some(block_number);
+            s.last_update = Instant::now();
+            s
+        });
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Havuz Sağlamlık Kontrolü (Pool Sanity Check) — Başlangıç Doğrulama
 // ─────────────────────────────────────────────────────────────────────────────
@@ -742,31 +1507,29 @@ pub async fn validate_pools<P: Provider + Sync>(provider: &P, pools: &[PoolConfi
     }
 
     let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
-    let mc_results = match tokio::time::timeout(
-        std::time::Duration::from_millis(10_000), // Başlangıç — daha uzun timeout
-        multicall.aggregate3(calls).call(),
-    )
+    // Başlangıç — daha uzun bütçe
+    let budget = CallBudget::new("validation", 10_000);
+    let mc_results = match with_rpc_retry("PoolValidation Multicall3", &budget, || {
+        let call = multicall.aggregate3(calls.clone());
+        async move {
+            call.call().await.map_err(|e| BotError::from_call("PoolValidation Multicall3", e))
+        }
+    })
     .await
     {
-        Ok(Ok(res)) => res,
-        Ok(Err(e)) => {
-            eprintln!(
+        Ok(res) => res,
+        Err(e) => {
+            eprintln_high!(
                 "  \u{274c} [PoolValidation] Multicall3 validation error: {} — validation skipped",
                 e
             );
             return vec![];
         }
-        Err(_) => {
-            eprintln!(
-                "  \u{274c} [PoolValidation] Multicall3 validation timeout (10s) — validation skipped"
-            );
-            return vec![];
-        }
     };
 
     let expected_results = pool_count * 2;
     if mc_results.len() != expected_results {
-        eprintln!(
+        eprintln_high!(
             "  ⚠️ [PoolValidation] Unexpected Multicall3 result length: expected={} got={}",
             expected_results,
             mc_results.len(),
@@ -800,7 +1563,7 @@ pub async fn validate_pools<P: Provider + Sync>(provider: &P, pools: &[PoolConfi
 
         if !slot0_valid || !liq_valid {
             invalid_indices.push(i);
-            eprintln!(
+            eprintln_high!(
                 "  \u{274c} [PoolValidation] {} ({}) — INVALID (slot0={}, liquidity={}) → will be removed",
                 pools[i].name,
                 pools[i].address,
@@ -817,19 +1580,38 @@ pub async fn validate_pools<P: Provider + Sync>(provider: &P, pools: &[PoolConfi
 // TickBitmap Off-Chain Okuma — Derinlik Haritası
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Solidity TickBitmap.position(): sıkıştırılmış tick → (word, bit).
+/// `>>` aritmetik kaydırmadır (floor) ve `& 0xFF` Solidity'deki
+/// uint8(uint24(tick % 256)) ile aynı biti verir — negatif word'lerde de.
+#[inline]
+fn bitmap_position(compressed: i32) -> (i16, u8) {
+    ((compressed >> 8) as i16, (compressed & 0xFF) as u8)
+}
+
+/// Tick → sıkıştırılmış tick: Solidity'deki gibi negatifler için floor
+/// division (tick_spacing > 0 iken div_euclid = floor)
+#[inline]
+fn compress_tick(tick: i32, tick_spacing: i32) -> i32 {
+    tick.div_euclid(tick_spacing)
+}
+
 /// TickBitmap word pozisyonunu hesapla
 /// tick_index / tick_spacing → compressed tick → word = compressed >> 8
 #[inline]
 fn tick_to_word_pos(tick: i32, tick_spacing: i32) -> i16 {
-    // Compressed tick: Solidity'deki gibi negatifler için floor division
-    let compressed = if tick < 0 && tick % tick_spacing != 0 {
-        tick / tick_spacing - 1
-    } else {
-        tick / tick_spacing
-    };
-    (compressed >> 8) as i16
+    bitmap_position(compress_tick(tick, tick_spacing)).0
+}
+
+/// bitmap_position'ın tersi: (word, bit) → tick. word << 8 | bit, negatif
+/// word'lerde de floor semantiğiyle tutarlı (word -1, bit 255 → compressed -1).
+#[inline]
+fn bitmap_tick(word_pos: i16, bit: u8, tick_spacing: i32) -> i32 {
+    (((word_pos as i32) << 8) | bit as i32) * tick_spacing
 }
 
+/// Havuz başına TickBitmap sync bütçesi (ms) — yeniden denemeler dahil
+const BITMAP_TIMEOUT_MS: u64 = 500;
+
 /// Bir bitmap word'ündeki tüm başlatılmış tick indekslerini çıkar
 fn extract_initialized_bits(word: U256, word_pos: i16, tick_spacing: i32) -> Vec<i32> {
     let mut ticks = Vec::new();
@@ -837,18 +1619,36 @@ fn extract_initialized_bits(word: U256, word_pos: i16, tick_spacing: i32) -> Vec
         return ticks;
     }
 
-    for bit in 0..256u16 {
-        let mask = U256::from(1u64) << bit;
-        if word & mask != U256::ZERO {
-            let compressed = (word_pos as i32) * 256 + bit as i32;
-            let tick = compressed * tick_spacing;
-            ticks.push(tick);
+    for bit in 0..=u8::MAX {
+        if word.bit(bit as usize) {
+            ticks.push(bitmap_tick(word_pos, bit, tick_spacing));
         }
     }
 
     ticks
 }
 
+/// Sync değişmezi: word'den çıkarılan her tick aynı word'e (ve bite) geri
+/// eşlenmeli. Eşlenmeyen tick zincirde yoktur — ticks() okuması boş döner
+/// ve derinlik sessizce küçülür; bu yüzden düşürülür ve veri kalitesi
+/// hatası olarak sayılır. Düşürülen tick sayısını döner.
+fn retain_roundtrip_ticks(ticks: &mut Vec<i32>, word_pos: i16, tick_spacing: i32, pool_name: &str) -> usize {
+    let before = ticks.len();
+    ticks.retain(|&tick| {
+        tick % tick_spacing == 0
+            && bitmap_position(compress_tick(tick, tick_spacing)).0 == word_pos
+    });
+    let dropped = before - ticks.len();
+    if dropped > 0 {
+        DATA_QUALITY_ERRORS.fetch_add(dropped as u64, std::sync::atomic::Ordering::Relaxed);
+        eprintln_high!(
+            "  \u{1f9ea} [DataQuality] [{}] {} bitmap tick(s) do not map back to word {} — dropped",
+            pool_name, dropped, word_pos,
+        );
+    }
+    dropped
+}
+
 /// Havuzun TickBitmap'ini belirli bir aralıkta oku — Multicall3 ile TEK RPC
 ///
 /// Bu fonksiyon:
@@ -864,7 +1664,11 @@ pub async fn sync_tick_bitmap<P: Provider + Sync>(
     pool_state: &SharedPoolState,
     block_number: u64,
     scan_range: u32,
-) -> Result<()> {
+) -> BotResult<()> {
+    // V2 havuzunun tick'i / bitmap'i yok — likidite tüm eğriye yayılı
+    if pool_config.is_constant_product() {
+        return Ok(());
+    }
     let start = Instant::now();
 
     let current_tick = pool_state.load().tick;
@@ -874,200 +1678,540 @@ pub async fn sync_tick_bitmap<P: Provider + Sync>(
     let tick_lo = current_tick - (scan_range as i32 * tick_spacing);
     let tick_hi = current_tick + (scan_range as i32 * tick_spacing);
 
-    // Word pozisyon aralığı
-    let word_lo = tick_to_word_pos(tick_lo, tick_spacing);
-    let word_hi = tick_to_word_pos(tick_hi, tick_spacing);
+    // İki Multicall3 adımı (word'ler + tick'ler) ve yeniden denemeleri
+    // sync_all_tick_bitmaps'in havuz başı timeout'unu paylaşır
+    let budget = CallBudget::new("bitmap", BITMAP_TIMEOUT_MS);
 
-    let mut bitmap_data = TickBitmapData::empty();
+    let mut bitmap_data = fetch_tick_range(provider, pool_config, tick_lo, tick_hi, &budget, true).await?;
     bitmap_data.scan_range = scan_range;
     bitmap_data.snapshot_block = block_number;
+    bitmap_data.last_full_sync_block = block_number;
+    bitmap_data.sync_duration_us = start.elapsed().as_micros() as u64;
 
-    // ══════════════════════════════════════════════════════════════════════
-    //  ADIM 1: tickBitmap word'lerini Multicall3 ile TEK ÇAĞRIDA oku
-    // ══════════════════════════════════════════════════════════════════════
+    // Slipstream: decode edilen staked net'ler stakedLiquidity() ile tutarlı mı?
+    bitmap_data.staked = checked_staked_snapshot(&bitmap_data, bitmap_data.staked, &pool_config.name);
 
-    let word_positions: Vec<i16> = (word_lo..=word_hi).collect();
-    let mut all_initialized_ticks: Vec<i32> = Vec::new();
+    // State'e yaz
+    {
+        pool_state.rcu(|old| {
+            let mut s = (**old).clone();
+            s.tick_bitmap = Some(Box::new(bitmap_data.clone()));
+            s
+        });
+    }
 
-    if !word_positions.is_empty() {
-        // Her word pozisyonu için calldata oluştur
-        let calls: Vec<IMulticall3::Call3> = word_positions
-            .iter()
-            .map(|&word_pos| {
-                let calldata = encode_tick_bitmap_call(pool_config.dex, word_pos);
-                IMulticall3::Call3 {
-                    target: pool_config.address,
-                    allowFailure: true,
-                    callData: Bytes::from(calldata),
-                }
-            })
-            .collect();
+    Ok(())
+}
 
-        // Multicall3 ile tek eth_call
-        let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
-        let results = multicall.aggregate3(calls).call().await.map_err(|e| {
-            eyre::eyre!("[{}] Multicall3 tickBitmap error: {}", pool_config.name, e)
-        })?;
-
-        // Sonuçları çözümle
-        for (i, result) in results.iter().enumerate() {
-            if result.success && result.returnData.len() >= 32 {
-                let word = U256::from_be_slice(&result.returnData[result.returnData.len() - 32..]);
-                let word_pos = word_positions[i];
-                if word != U256::ZERO {
-                    bitmap_data.words.insert(word_pos, word);
-                    let initialized = extract_initialized_bits(word, word_pos, tick_spacing);
-                    all_initialized_ticks.extend(initialized);
-                }
-            }
-        }
+/// Staked görüntüsünü haritanın staked net'leriyle doğrula — `verified`
+/// bayrağı yalnızca tutarlıysa set edilir. Görüntü yoksa None.
+fn checked_staked_snapshot(
+    bitmap: &TickBitmapData,
+    snapshot: Option<StakedLiquidity>,
+    pool_name: &str,
+) -> Option<StakedLiquidity> {
+    let mut snapshot = snapshot?;
+    match bitmap.verify_staked(&snapshot) {
+        Ok(()) => snapshot.verified = true,
+        Err(reason) => eprintln_high!(
+            "  ⚠️ [{}] stakedLiquidity check failed: {} — staked nets unverified",
+            pool_name, reason
+        ),
     }
+    Some(snapshot)
+}
 
-    // ══════════════════════════════════════════════════════════════════════
-    //  ADIM 2: Başlatılmış tick detaylarını Multicall3 ile TEK ÇAĞRIDA oku
-    // ══════════════════════════════════════════════════════════════════════
+/// Periyodik bitmap yenilemesinin yolu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapRefresh {
+    /// Tam tarama (bitmap yoktu ya da TICK_BITMAP_FULL_RESYNC_BLOCKS doldu)
+    Full,
+    /// Delta: detayı okunan ve haritadan düşen (pencere dışı / biti sönmüş) tick sayısı
+    Incremental { ticks_read: usize, evicted: usize },
+}
 
-    // Tarama aralığındaki tick'leri filtrele
-    all_initialized_ticks.retain(|t| *t >= tick_lo && *t <= tick_hi);
+/// Periyodik TickBitmap yenilemesi — tam tarama yerine delta.
+///
+/// Penceredeki (current_tick ± scan_range) word'ler tek Multicall3'te okunur
+/// (birkaç çağrı). Tick detayları yalnızca şunlar için okunur ve mevcut
+/// haritaya birleştirilir:
+///   - önceki taranan aralığın dışında kalan (fiyatın yeni girdiği) tick'ler
+///   - word'ü mevcut haritadakinden farklı olan (tick eklenmiş/silinmiş) word'lerin tick'leri
+///
+/// Pencere dışına düşen ya da biti sönmüş tick'ler silinir. Word'ü değişmeyen tick'ler Mint/Burn
+/// event'leriyle güncel tutulur; tam haritanın yaşı `full_resync_blocks` ile
+/// sınırlıdır. Bitmap yoksa veya tam tarama vadesi geldiyse `sync_tick_bitmap`.
+///
+/// Slipstream staked görüntüsü (slot0 / liquidity / stakedLiquidity /
+/// unstakedFee) her delta turunda tick detaylarıyla aynı Multicall3'te
+/// yeniden okunur ve birleşmiş haritayla doğrulanır — aktif likidite
+/// değiştikçe tam sync'teki görüntü bayatlamaz.
+pub async fn sync_tick_bitmap_incremental<P: Provider + Sync>(
+    provider: &P,
+    pool_config: &PoolConfig,
+    pool_state: &SharedPoolState,
+    block_number: u64,
+    scan_range: u32,
+    full_resync_blocks: u64,
+) -> BotResult<BitmapRefresh> {
+    let start = Instant::now();
+    let (current_tick, previous) = {
+        let state = pool_state.load();
+        let previous = state.tick_bitmap.as_deref().filter(|bm| {
+            bm.scanned_ticks.is_some() && block_number.saturating_sub(bm.last_full_sync_block) < full_resync_blocks
+        });
+        (state.tick, previous.cloned())
+    };
+    let Some(mut bitmap) = previous else {
+        sync_tick_bitmap(provider, pool_config, pool_state, block_number, scan_range).await?;
+        return Ok(BitmapRefresh::Full);
+    };
 
-    if !all_initialized_ticks.is_empty() {
-        // Her tick için calldata oluştur
-        let tick_calls: Vec<IMulticall3::Call3> = all_initialized_ticks
-            .iter()
-            .map(|&tick| {
-                let tick_i24 = tick.clamp(-887272, 887272);
-                let calldata = encode_ticks_call(pool_config.dex, tick_i24);
-                IMulticall3::Call3 {
-                    target: pool_config.address,
-                    allowFailure: true,
-                    callData: Bytes::from(calldata),
-                }
-            })
-            .collect();
+    let tick_spacing = pool_config.tick_spacing.max(1);
+    let tick_lo = current_tick - (scan_range as i32 * tick_spacing);
+    let tick_hi = current_tick + (scan_range as i32 * tick_spacing);
 
-        let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
-        let tick_results = multicall
-            .aggregate3(tick_calls)
-            .call()
-            .await
-            .map_err(|e| eyre::eyre!("[{}] Multicall3 ticks error: {}", pool_config.name, e))?;
-
-        // Sonuçları çözümle
-        for (i, result) in tick_results.iter().enumerate() {
-            if result.success && result.returnData.len() >= 64 {
-                // İlk 32 byte = liquidityGross (uint128), sonraki 32 byte = liquidityNet (int128)
-                // ABI decode: her parametre 32 byte padded
-                if let Some((liq_gross, liq_net, initialized)) =
-                    decode_ticks_result(&result.returnData)
-                {
-                    if initialized {
-                        bitmap_data.ticks.insert(
-                            all_initialized_ticks[i],
-                            TickInfo {
-                                liquidity_gross: liq_gross,
-                                liquidity_net: liq_net,
-                                initialized: true,
-                            },
-                        );
-                    }
-                }
-            }
-        }
-    }
+    let budget = CallBudget::new("bitmap", BITMAP_TIMEOUT_MS);
+    let mut timing = TimingGuard::start(SyncStage::TickBitmap.timing());
+    let words = read_bitmap_words(provider, pool_config, tick_lo, tick_hi, &budget, &mut timing).await?;
+    let stale_ticks = bitmap_delta_ticks(&bitmap, &words, (tick_lo, tick_hi), tick_spacing, &pool_config.name);
+    let (infos, snapshot_results) = read_tick_infos(
+        provider,
+        pool_config,
+        &stale_ticks,
+        staked_snapshot_calls(pool_config),
+        &budget,
+        &mut timing,
+    )
+    .await?;
+    drop(timing);
 
-    let elapsed_us = start.elapsed().as_micros() as u64;
-    bitmap_data.sync_duration_us = elapsed_us;
+    let evicted = apply_bitmap_delta(&mut bitmap, words, &stale_ticks, infos, (tick_lo, tick_hi), tick_spacing);
+    // Görüntü okunamadıysa bayat olanı tutmak yerine düşür (tam sync ile aynı)
+    bitmap.staked = checked_staked_snapshot(&bitmap, decode_staked_snapshot(&snapshot_results), &pool_config.name);
+    bitmap.scan_range = scan_range;
+    bitmap.snapshot_block = block_number;
+    bitmap.sync_duration_us = start.elapsed().as_micros() as u64;
 
-    // State'e yaz
-    {
-        pool_state.rcu(|old| {
-            let mut s = (**old).clone();
-            s.tick_bitmap = Some(bitmap_data.clone());
-            s
-        });
+    pool_state.rcu(|old| {
+        let mut s = (**old).clone();
+        s.tick_bitmap = Some(Box::new(bitmap.clone()));
+        s
+    });
+
+    Ok(BitmapRefresh::Incremental { ticks_read: stale_ticks.len(), evicted })
+}
+
+/// Delta yenilemede detayı yeniden okunacak tick'ler: pencerede, biti set ve
+/// (önceki taranan aralığın dışında ya da word'ü değişmiş). Artan sırada.
+fn bitmap_delta_ticks(
+    previous: &TickBitmapData,
+    words: &HashMap<i16, U256>,
+    (tick_lo, tick_hi): (i32, i32),
+    tick_spacing: i32,
+    pool_name: &str,
+) -> Vec<i32> {
+    let known = previous.scanned_ticks.map(|(lo, hi)| lo..=hi);
+    let mut stale = Vec::new();
+    for (&word_pos, &word) in words {
+        let changed = previous.words.get(&word_pos) != Some(&word);
+        let mut initialized = extract_initialized_bits(word, word_pos, tick_spacing);
+        retain_roundtrip_ticks(&mut initialized, word_pos, tick_spacing, pool_name);
+        stale.extend(initialized.into_iter().filter(|t| {
+            (tick_lo..=tick_hi).contains(t) && (changed || !known.as_ref().is_some_and(|k| k.contains(t)))
+        }));
     }
+    stale.sort_unstable();
+    stale
+}
 
-    Ok(())
+/// Delta okumasını mevcut haritaya birleştir: word'ler pencerenin okumasıyla
+/// değişir, okunan tick'ler yenilenir, pencere dışı / biti sönmüş tick'ler
+/// silinir. Silinen tick sayısını döner.
+fn apply_bitmap_delta(
+    bitmap: &mut TickBitmapData,
+    words: HashMap<i16, U256>,
+    refreshed: &[i32],
+    infos: HashMap<i32, TickInfo>,
+    (tick_lo, tick_hi): (i32, i32),
+    tick_spacing: i32,
+) -> usize {
+    for tick in refreshed {
+        bitmap.ticks.remove(tick);
+    }
+    bitmap.ticks.extend(infos);
+    bitmap.words = words;
+
+    let before = bitmap.ticks.len();
+    let words = &bitmap.words;
+    bitmap.ticks.retain(|&tick, _| {
+        let (word_pos, bit) = bitmap_position(compress_tick(tick, tick_spacing));
+        (tick_lo..=tick_hi).contains(&tick) && words.get(&word_pos).is_some_and(|w| w.bit(bit as usize))
+    });
+    bitmap.scanned_ticks = Some((tick_lo, tick_hi));
+    before - bitmap.ticks.len()
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Havuz Bytecode Önbellekleme (REVM Simülasyonu İçin)
-// ─────────────────────────────────────────────────────────────────────────────
+/// Kenar genişletmesinde taranan kenarın ötesinde okunan word sayısı
+const BITMAP_EXTENSION_WORDS: i32 = 2;
 
-pub async fn cache_pool_bytecode<P: Provider + Sync>(
-    provider: &P,
+/// Kenar genişletme bütçesi (ms) — blok içi, fırsat kararından önce
+const BITMAP_EXTENSION_TIMEOUT_MS: u64 = 250;
+
+/// Taranan aralığın `edge` kenarının ötesindeki BITMAP_EXTENSION_WORDS
+/// word'ü oku ve havuzun mevcut bitmap'ine birleştir.
+///
+/// NR optimumu tarama kenarına dayandığında blok içinde çağrılır: yalnızca
+/// etkilenen bacak ve yön okunur (2 Multicall3, dar aralık). Mevcut bitmap
+/// yoksa veya taranan aralığı bilinmiyorsa genişletilecek kenar yoktur.
+pub async fn extend_tick_bitmap<P: Provider + Clone + Sync>(
+    providers: &ProviderRegistry<P>,
     pool_config: &PoolConfig,
     pool_state: &SharedPoolState,
-) -> Result<()> {
-    let code = provider
-        .get_code_at(pool_config.address)
-        .await
-        .map_err(|e| eyre::eyre!("[{}] Bytecode read error: {}", pool_config.name, e))?;
+    edge: ScanEdge,
+    block_number: u64,
+) -> BotResult<()> {
+    let start = Instant::now();
+    let Some((lo, hi)) = pool_state.load().tick_bitmap.as_deref().and_then(|bm| bm.scanned_ticks) else {
+        return Ok(());
+    };
+    let (tick_lo, tick_hi) = extension_range(lo, hi, edge, pool_config.tick_spacing.max(1));
+
+    // Özel endpoint'e düşen fallback kendi bütçesiyle başlar
+    let mut extension = providers
+        .traced("bitmap_extension")
+        .call("ticks_range", pool_config.address, |p| async move {
+            let budget = CallBudget::new("bitmap_extension", BITMAP_EXTENSION_TIMEOUT_MS);
+            fetch_tick_range(&p, pool_config, tick_lo, tick_hi, &budget, false).await
+        })
+        .await?;
+    extension.snapshot_block = block_number;
+    extension.sync_duration_us = start.elapsed().as_micros() as u64;
 
-    let code_vec = code.to_vec();
     pool_state.rcu(|old| {
         let mut s = (**old).clone();
-        s.bytecode = Some(code_vec.clone());
+        if let Some(bm) = s.tick_bitmap.as_mut() {
+            bm.merge(extension.clone());
+        }
         s
     });
 
     Ok(())
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Toplu Senkronizasyon
-// ─────────────────────────────────────────────────────────────────────────────
+/// Genişletme aralığı: kenarın hemen ötesinden BITMAP_EXTENSION_WORDS
+/// word (her biri 256 × tick_spacing tick) boyunca.
+fn extension_range(lo: i32, hi: i32, edge: ScanEdge, tick_spacing: i32) -> (i32, i32) {
+    let span = BITMAP_EXTENSION_WORDS * 256 * tick_spacing;
+    match edge {
+        ScanEdge::Upper => (hi + 1, (hi + span).min(MAX_TICK)),
+        ScanEdge::Lower => ((lo - span).max(MIN_TICK), lo - 1),
+    }
+}
 
-pub async fn sync_all_pools<P: Provider + Sync>(
+/// [tick_lo, tick_hi] aralığındaki bitmap word'lerini ve başlatılmış tick
+/// detaylarını oku. snapshot_block / scan_range / süre çağıranın işidir.
+///
+/// `staked_snapshot`: Aerodrome havuzlarında slot0 / liquidity /
+/// stakedLiquidity / unstakedFee tick'lerle aynı Multicall3'e eklenir
+/// (tam sync; kenar genişletmesi okumaz).
+async fn fetch_tick_range<P: Provider + Sync>(
     provider: &P,
-    pools: &[PoolConfig],
-    states: &[SharedPoolState],
-    block_number: u64,
-) -> Vec<Result<()>> {
-    // v10.0: sync_all_pools artık sync_all_pools_multicall'a delege eder.
-    // Multicall3 başarısız olursa (ağ hatası, kontrat sorunu) tekil fallback'e düşer.
-    //
-    // Stale Data Politikası (v10.0):
-    // Sync başarısız olan havuzlar is_stale=true olarak işaretlenir.
-    // Eski veri KESİNLİKLE KULLANILMAZ — hayalet kâr hesaplamasını önler.
+    pool_config: &PoolConfig,
+    tick_lo: i32,
+    tick_hi: i32,
+    budget: &CallBudget,
+    staked_snapshot: bool,
+) -> BotResult<TickBitmapData> {
+    let mut timing = TimingGuard::start(SyncStage::TickBitmap.timing());
+    let tick_spacing = pool_config.tick_spacing.max(1);
 
-    // Önce Multicall3 ile toplu sync dene
-    let multicall_results = sync_all_pools_multicall(provider, pools, states, block_number).await;
+    let mut bitmap_data = TickBitmapData::empty();
+    bitmap_data.scanned_ticks = Some((tick_lo, tick_hi));
 
-    // Multicall3'te başarısız olan havuzlar için tekil fallback
-    const FALLBACK_TIMEOUT_MS: u64 = 500;
-    const FALLBACK_MAX_RETRIES: u32 = 1;
+    // ADIM 1: tickBitmap word'leri (tek Multicall3)
+    bitmap_data.words = read_bitmap_words(provider, pool_config, tick_lo, tick_hi, budget, &mut timing).await?;
+    let mut all_initialized_ticks: Vec<i32> = Vec::new();
+    for (&word_pos, &word) in &bitmap_data.words {
+        let mut initialized = extract_initialized_bits(word, word_pos, tick_spacing);
+        retain_roundtrip_ticks(&mut initialized, word_pos, tick_spacing, &pool_config.name);
+        all_initialized_ticks.extend(initialized);
+    }
+    all_initialized_ticks.sort_unstable();
 
-    let mut final_results = multicall_results;
+    // ADIM 2: Başlatılmış tick detayları (tek Multicall3)
+    // Tarama aralığındaki tick'leri filtrele
+    all_initialized_ticks.retain(|t| *t >= tick_lo && *t <= tick_hi);
 
-    // Multicall3'te başarısız olan havuzların indekslerini topla
-    let failed_indices: Vec<usize> = final_results
+    // Slipstream: staked doğrulaması için aktif likidite görüntüsü tick'lerle
+    // aynı blokta okunmalı — aynı Multicall3'ün sonuna eklenir
+    let snapshot_calls = if staked_snapshot { staked_snapshot_calls(pool_config) } else { Vec::new() };
+
+    let (ticks, snapshot_results) =
+        read_tick_infos(provider, pool_config, &all_initialized_ticks, snapshot_calls, budget, &mut timing).await?;
+    bitmap_data.ticks = ticks;
+    if !snapshot_results.is_empty() {
+        bitmap_data.staked = decode_staked_snapshot(&snapshot_results);
+    }
+
+    Ok(bitmap_data)
+}
+
+/// Slipstream staked görüntüsü çağrıları (yalnızca Aerodrome) — tick
+/// detaylarıyla aynı Multicall3'ün sonuna eklenir
+fn staked_snapshot_calls(pool_config: &PoolConfig) -> Vec<Vec<u8>> {
+    if pool_config.dex != DexType::Aerodrome {
+        return Vec::new();
+    }
+    vec![
+        encode_slot0_call(),
+        encode_liquidity_call(),
+        encode_staked_liquidity_call(),
+        encode_unstaked_fee_call(),
+    ]
+}
+
+/// [tick_lo, tick_hi]'yi kapsayan tickBitmap(wordPos) word'lerini Multicall3
+/// ile TEK ÇAĞRIDA oku. Yalnızca sıfır olmayan word'ler döner.
+async fn read_bitmap_words<P: Provider + Sync>(
+    provider: &P,
+    pool_config: &PoolConfig,
+    tick_lo: i32,
+    tick_hi: i32,
+    budget: &CallBudget,
+    timing: &mut TimingGuard<'_>,
+) -> BotResult<HashMap<i16, U256>> {
+    let tick_spacing = pool_config.tick_spacing.max(1);
+    let word_positions: Vec<i16> =
+        (tick_to_word_pos(tick_lo, tick_spacing)..=tick_to_word_pos(tick_hi, tick_spacing)).collect();
+    let mut words = HashMap::new();
+    if word_positions.is_empty() {
+        return Ok(words);
+    }
+
+    // Her word pozisyonu için calldata oluştur
+    let calls: Vec<IMulticall3::Call3> = word_positions
         .iter()
-        .enumerate()
-        .filter(|(_, r)| r.is_err())
-        .map(|(i, _)| i)
+        .map(|&word_pos| {
+            let calldata = encode_tick_bitmap_call(pool_config.dex, word_pos);
+            IMulticall3::Call3 {
+                target: pool_config.address,
+                allowFailure: true,
+                callData: Bytes::from(calldata),
+            }
+        })
         .collect();
 
-    for i in failed_indices {
-        // Tekil fallback dene
-        let config = pools[i].clone();
-        let state = states[i].clone();
+    // Multicall3 ile tek eth_call
+    let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
+    let context = format!("[{}] Multicall3 tickBitmap", pool_config.name);
+    let ctx = context.as_str();
+    let results = timing
+        .rpc(with_rpc_retry(ctx, budget, || {
+            let call = multicall.aggregate3(calls.clone());
+            async move { call.call().await.map_err(|e| BotError::from_call(ctx, e)) }
+        }))
+        .await?;
+    timing.record_payload(aggregate3_request_bytes(&calls), aggregate3_response_bytes(&results));
+
+    // Sonuçları çözümle
+    for (i, result) in results.iter().enumerate() {
+        if result.success && result.returnData.len() >= 32 {
+            let word = U256::from_be_slice(&result.returnData[result.returnData.len() - 32..]);
+            if word != U256::ZERO {
+                words.insert(word_positions[i], word);
+            }
+        }
+    }
+    Ok(words)
+}
 
-        let mut fallback_ok = false;
-        for attempt in 0..=FALLBACK_MAX_RETRIES {
-            match tokio::time::timeout(
-                std::time::Duration::from_millis(FALLBACK_TIMEOUT_MS),
-                sync_pool_state(provider, &config, &state, block_number),
-            )
-            .await
-            {
-                Ok(Ok(())) => {
+/// `ticks` için ticks(tick) detaylarını Multicall3 ile TEK ÇAĞRIDA oku.
+/// `extra_calls` aynı çağrının sonuna eklenir; sonuçları ikinci eleman olarak döner.
+async fn read_tick_infos<P: Provider + Sync>(
+    provider: &P,
+    pool_config: &PoolConfig,
+    ticks: &[i32],
+    extra_calls: Vec<Vec<u8>>,
+    budget: &CallBudget,
+    timing: &mut TimingGuard<'_>,
+) -> BotResult<(HashMap<i32, TickInfo>, Vec<IMulticall3::Result>)> {
+    let mut infos = HashMap::new();
+    if ticks.is_empty() && extra_calls.is_empty() {
+        return Ok((infos, Vec::new()));
+    }
+
+    // Her tick için calldata oluştur
+    let tick_calls: Vec<IMulticall3::Call3> = ticks
+        .iter()
+        .map(|&tick| {
+            let tick_i24 = tick.clamp(-887272, 887272);
+            encode_ticks_call(pool_config.dex, tick_i24)
+        })
+        .chain(extra_calls)
+        .map(|calldata| IMulticall3::Call3 {
+            target: pool_config.address,
+            allowFailure: true,
+            callData: Bytes::from(calldata),
+        })
+        .collect();
+
+    let multicall = IMulticall3::new(MULTICALL3_ADDRESS, provider);
+    let context = format!("[{}] Multicall3 ticks", pool_config.name);
+    let ctx = context.as_str();
+    let mut tick_results = timing
+        .rpc(with_rpc_retry(ctx, budget, || {
+            let call = multicall.aggregate3(tick_calls.clone());
+            async move { call.call().await.map_err(|e| BotError::from_call(ctx, e)) }
+        }))
+        .await?;
+    timing.record_payload(
+        aggregate3_request_bytes(&tick_calls),
+        aggregate3_response_bytes(&tick_results),
+    );
+
+    let split = ticks.len().min(tick_results.len());
+    let extra_results = tick_results.split_off(split);
+
+    // Sonuçları çözümle
+    for (i, result) in tick_results.iter().enumerate() {
+        if result.success && result.returnData.len() >= 64 {
+            // ABI decode: her parametre 32 byte padded (bkz. decode_ticks_result)
+            if let Some(info) = decode_ticks_result(&result.returnData).filter(|t| t.initialized) {
+                infos.insert(ticks[i], info);
+            }
+        }
+    }
+    Ok((infos, extra_results))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Havuz Bytecode Önbellekleme (REVM Simülasyonu İçin)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Havuz bytecode'unu zincirden oku ve StaticPoolStore'a yaz.
+///
+/// Bytecode artık PoolState'te tutulmaz — her blokta klonlanan hot-path
+/// state'i ~22KB/havuz şişiriyordu. SimulationEngine::cache_bytecodes
+/// bu store'dan okur.
+pub async fn cache_pool_bytecode<P: Provider + Sync>(
+    provider: &P,
+    pool_config: &PoolConfig,
+    static_store: &StaticPoolStore,
+) -> BotResult<()> {
+    let code = provider
+        .get_code_at(pool_config.address)
+        .await
+        .map_err(|e| BotError::from_transport(format!("[{}] bytecode", pool_config.name), e))?;
+
+    static_store
+        .write()
+        .entry(pool_config.address)
+        .or_default()
+        .bytecode = Some(Arc::new(code.to_vec()));
+
+    Ok(())
+}
+
+/// Arbitraj kontratının deploy edilmiş bytecode'unu zincirden oku ve
+/// SimulationEngine'e yaz (cache_pool_bytecode'un kontrat karşılığı).
+/// initialize_base_db kontrat hesabına bu kodu yükler. Adreste kod yoksa
+/// (deploy edilmemiş) motor değişmez ve 0 döner.
+pub async fn cache_contract_bytecode<P: Provider + Sync>(
+    provider: &P,
+    contract: Address,
+    sim_engine: &mut SimulationEngine,
+) -> BotResult<usize> {
+    let code = provider
+        .get_code_at(contract)
+        .await
+        .map_err(|e| BotError::from_transport(format!("[{}] contract bytecode", contract), e))?;
+    if !code.is_empty() {
+        sim_engine.set_contract_bytecode(code.to_vec());
+    }
+    Ok(code.len())
+}
+
+/// `holder`'ın token bakiyesini balanceOf mapping storage'ından oku
+/// (slot indeksi token probe'unun bulduğu). REVM base_db'ye aynen yazılır.
+pub async fn read_token_balance<P: Provider + Sync>(
+    provider: &P,
+    token: Address,
+    holder: Address,
+    mapping_index: u64,
+) -> BotResult<U256> {
+    provider
+        .get_storage_at(token, balance_mapping_key(holder, mapping_index))
+        .await
+        .map_err(|e| BotError::from_transport(format!("[{}] balance of {}", token, holder), e))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Toplu Senkronizasyon
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub async fn sync_all_pools<P: Provider + Clone + Sync>(
+    providers: &ProviderRegistry<P>,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    block_number: u64,
+    block_id: BlockId,
+) -> Vec<BotResult<()>> {
+    // v10.0: sync_all_pools artık sync_all_pools_multicall'a delege eder.
+    // Multicall3 başarısız olursa (ağ hatası, kontrat sorunu) tekil fallback'e düşer.
+    //
+    // Stale Data Politikası (v10.0):
+    // Sync başarısız olan havuzlar is_stale=true olarak işaretlenir.
+    // Eski veri KESİNLİKLE KULLANILMAZ — hayalet kâr hesaplamasını önler.
+
+    // Önce Multicall3 ile toplu sync dene — özel endpoint'li havuzlar hariç.
+    // Tüm okumalar `block_id`'ye sabitlenir: A'yı N'de, B'yi N+1'de okuyup
+    // hayalet spread üretmek yerine bütün havuzlar aynı bloğu görür.
+    let multicall_results = sync_pools_by_endpoint(providers, pools, states, block_number, block_id).await;
+
+    // Multicall3'te başarısız olan havuzlar için tekil fallback
+    const FALLBACK_TIMEOUT_MS: u64 = 500;
+    const FALLBACK_MAX_RETRIES: u32 = 1;
+
+    let mut final_results = multicall_results;
+
+    // Multicall3'te başarısız olan havuzların indekslerini topla
+    let failed_indices: Vec<usize> = final_results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.is_err())
+        .map(|(i, _)| i)
+        .collect();
+
+    for i in failed_indices {
+        // Tekil fallback dene
+        let config = pools[i].clone();
+        let state = states[i].clone();
+
+        // Bozuk veri tekil okumayla düzelmez — fallback denenmeden STALE
+        let mut fallback_ok = false;
+        let skip_fallback = matches!(final_results[i], Err(BotError::PoolDataInvalid { .. }));
+        for attempt in 0..=FALLBACK_MAX_RETRIES {
+            if skip_fallback {
+                break;
+            }
+            let (pool_config, pool_state) = (&config, &state);
+            match tokio::time::timeout(
+                std::time::Duration::from_millis(FALLBACK_TIMEOUT_MS),
+                providers.traced("sync_fallback").call("slot0+liquidity+fee", config.address, |p| async move {
+                    sync_pool_state(&p, pool_config, pool_state, block_number, block_id).await
+                }),
+            )
+            .await
+            {
+                Ok(Ok(())) => {
                     fallback_ok = true;
                     break;
                 }
                 Ok(Err(e)) => {
-                    if attempt < FALLBACK_MAX_RETRIES {
-                        eprintln!(
+                    let retry = e.is_retryable() && attempt < FALLBACK_MAX_RETRIES;
+                    if retry {
+                        eprintln_high!(
                             "     \u{26a1} [{}] Fallback sync error ({}/{}): {}",
                             config.name,
                             attempt + 1,
@@ -1075,16 +2219,25 @@ pub async fn sync_all_pools<P: Provider + Sync>(
                             e,
                         );
                     }
+                    final_results[i] = Err(e);
+                    if !retry {
+                        break;
+                    }
                 }
                 Err(_) => {
                     if attempt < FALLBACK_MAX_RETRIES {
-                        eprintln!(
+                        eprintln_high!(
                             "     \u{26a1} [{}] Fallback sync timeout ({}/{})",
                             config.name,
                             attempt + 1,
                             FALLBACK_MAX_RETRIES + 1,
                         );
                     }
+                    final_results[i] = Err(BotError::RpcTimeout {
+                        context: format!("[{}] fallback sync", config.name),
+                        category: "fallback",
+                        timeout_ms: FALLBACK_TIMEOUT_MS,
+                    });
                 }
             }
         }
@@ -1099,9 +2252,11 @@ pub async fn sync_all_pools<P: Provider + Sync>(
                 s.is_stale = true;
                 s
             });
-            eprintln!(
-                "     \u{1f6a8} [{}] Sync completely failed — marked as STALE (data age: {}ms)",
-                config.name, staleness,
+            eprintln_high!(
+                "     \u{1f6a8} [{}] Sync completely failed ({}) — marked as STALE (data age: {}ms)",
+                config.name,
+                final_results[i].as_ref().err().map_or("unknown", BotError::kind),
+                staleness,
             );
         }
     }
@@ -1109,6 +2264,81 @@ pub async fn sync_all_pools<P: Provider + Sync>(
     final_results
 }
 
+/// Primary'den okunan havuzlar tek Multicall3 ile, özel endpoint'i olanlar
+/// (POOL_A/B_RPC_URL) kendi endpoint'lerinden tekil sync ile — paralel.
+/// Sonuçlar `pools` sırasıyla döner.
+async fn sync_pools_by_endpoint<P: Provider + Clone + Sync>(
+    providers: &ProviderRegistry<P>,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    block_number: u64,
+    block_id: BlockId,
+) -> Vec<BotResult<()>> {
+    let (dedicated, shared): (Vec<usize>, Vec<usize>) =
+        (0..pools.len()).partition(|&i| providers.dedicated_for(pools[i].address).is_some());
+
+    let census_key = CallKey::new("aggregate3", MULTICALL3_ADDRESS, "pool_sync");
+    let started = Instant::now();
+    if dedicated.is_empty() {
+        let results = rpc_census::traced(
+            census_key,
+            sync_all_pools_multicall(providers.primary(), pools, states, block_number, block_id),
+        )
+        .await;
+        providers.primary_endpoint().latency.record(started.elapsed().as_micros() as u64);
+        return results;
+    }
+
+    let shared_pools: Vec<PoolConfig> = shared.iter().map(|&i| pools[i].clone()).collect();
+    let shared_states: Vec<SharedPoolState> = shared.iter().map(|&i| Arc::clone(&states[i])).collect();
+    let multicall = async {
+        let results = rpc_census::traced(
+            census_key,
+            sync_all_pools_multicall(providers.primary(), &shared_pools, &shared_states, block_number, block_id),
+        )
+        .await;
+        if !shared_pools.is_empty() {
+            providers.primary_endpoint().latency.record(started.elapsed().as_micros() as u64);
+        }
+        results
+    };
+    let singles = join_all(dedicated.iter().map(|&i| {
+        let (pool_config, pool_state) = (&pools[i], &states[i]);
+        providers.traced("pool_sync").call("slot0+liquidity+fee", pool_config.address, move |p| async move {
+            sync_pool_state(&p, pool_config, pool_state, block_number, block_id).await
+        })
+    }));
+    let (multicall_results, single_results) = tokio::join!(multicall, singles);
+
+    let mut results: Vec<Option<BotResult<()>>> = (0..pools.len()).map(|_| None).collect();
+    for (i, result) in shared.into_iter().zip(multicall_results) {
+        results[i] = Some(result);
+    }
+    for (i, result) in dedicated.into_iter().zip(single_results) {
+        results[i] = Some(result);
+    }
+    results
+        .into_iter()
+        .map(|r| r.expect("every pool is synced by exactly one endpoint"))
+        .collect()
+}
+
+/// Havuz başına sync sonuçlarını tek sonuca indir: başarısız olan varsa
+/// indeksleriyle MulticallPartialFailure
+pub fn sync_outcome(results: &[BotResult<()>]) -> BotResult<()> {
+    let failed_indices: Vec<usize> = results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.is_err())
+        .map(|(i, _)| i)
+        .collect();
+    if failed_indices.is_empty() {
+        Ok(())
+    } else {
+        Err(BotError::MulticallPartialFailure { failed_indices })
+    }
+}
+
 /// Tüm havuzların TickBitmap'lerini senkronize et
 ///
 /// Her havuz için:
@@ -1118,46 +2348,72 @@ pub async fn sync_all_pools<P: Provider + Sync>(
 ///
 /// v29.0: 2000ms → 500ms. Bayat veri beklemek yerine hızlı başarısızlık.
 /// Multicall3 başarısız olduğunda tekil fallback 500ms ile hızlıca dener.
-pub async fn sync_all_tick_bitmaps<P: Provider + Sync>(
-    provider: &P,
+pub async fn sync_all_tick_bitmaps<P: Provider + Clone + Sync>(
+    providers: &ProviderRegistry<P>,
     pools: &[PoolConfig],
     states: &[SharedPoolState],
     block_number: u64,
     scan_range: u32,
-) -> Vec<Result<()>> {
-    const BITMAP_TIMEOUT_MS: u64 = 500;
+) -> Vec<BotResult<()>> {
+    let futures: Vec<_> = pools
+        .iter()
+        .zip(states.iter())
+        .map(|(config, state)| {
+            within_bitmap_timeout(
+                &config.name,
+                providers.traced("bitmap_sync").call("tick_bitmap", config.address, move |p| async move {
+                    sync_tick_bitmap(&p, config, state, block_number, scan_range).await
+                }),
+            )
+        })
+        .collect();
+    join_all(futures).await
+}
 
+/// Periyodik yenileme: tüm havuzlarda `sync_tick_bitmap_incremental`
+/// (ilk sync ve yeniden bağlanma `sync_all_tick_bitmaps` ile tam tarar)
+pub async fn refresh_all_tick_bitmaps<P: Provider + Clone + Sync>(
+    providers: &ProviderRegistry<P>,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    block_number: u64,
+    scan_range: u32,
+    full_resync_blocks: u64,
+) -> Vec<BotResult<BitmapRefresh>> {
     let futures: Vec<_> = pools
         .iter()
         .zip(states.iter())
         .map(|(config, state)| {
-            let name = config.name.clone();
-            async move {
-                match tokio::time::timeout(
-                    std::time::Duration::from_millis(BITMAP_TIMEOUT_MS),
-                    sync_tick_bitmap(provider, config, state, block_number, scan_range),
-                )
-                .await
-                {
-                    Ok(result) => result,
-                    Err(_) => {
-                        eprintln!(
-                            "     ⚠️ [TickBitmap] {} sync timeout ({}ms) — keeping existing data",
-                            name, BITMAP_TIMEOUT_MS,
-                        );
-                        Err(eyre::eyre!(
-                            "[{}] TickBitmap timeout ({}ms)",
-                            name,
-                            BITMAP_TIMEOUT_MS
-                        ))
-                    }
-                }
-            }
+            within_bitmap_timeout(
+                &config.name,
+                providers.traced("bitmap_refresh").call("tick_bitmap", config.address, move |p| async move {
+                    sync_tick_bitmap_incremental(&p, config, state, block_number, scan_range, full_resync_blocks)
+                        .await
+                }),
+            )
         })
         .collect();
     join_all(futures).await
 }
 
+/// Havuz başı bitmap okumasını BITMAP_TIMEOUT_MS ile sınırla — aşımda mevcut veri korunur
+async fn within_bitmap_timeout<T>(name: &str, read: impl Future<Output = BotResult<T>>) -> BotResult<T> {
+    match tokio::time::timeout(std::time::Duration::from_millis(BITMAP_TIMEOUT_MS), read).await {
+        Ok(result) => result,
+        Err(_) => {
+            eprintln_high!(
+                "     ⚠️ [TickBitmap] {} sync timeout ({}ms) — keeping existing data",
+                name, BITMAP_TIMEOUT_MS,
+            );
+            Err(BotError::RpcTimeout {
+                context: format!("[{}] TickBitmap", name),
+                category: "bitmap",
+                timeout_ms: BITMAP_TIMEOUT_MS,
+            })
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // L1 Data Fee Tahmini (Base / OP Stack)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1173,7 +2429,7 @@ pub async fn sync_all_tick_bitmaps<P: Provider + Sync>(
 ///
 /// # Dönüş
 /// L1 data fee (wei). Hata/timeout durumunda önbellek, yoksa konservatif fallback döner.
-pub async fn estimate_l1_data_fee<P: Provider + Sync>(provider: &P) -> u128 {
+pub async fn estimate_l1_data_fee<P: Provider + Sync>(provider: &P, oracle: Address) -> u128 {
     use std::sync::atomic::{AtomicU64, Ordering};
 
     const L1_FEE_TIMEOUT_MS: u64 = 1000;
@@ -1186,7 +2442,7 @@ pub async fn estimate_l1_data_fee<P: Provider + Sync>(provider: &P) -> u128 {
 
     match tokio::time::timeout(
         std::time::Duration::from_millis(L1_FEE_TIMEOUT_MS),
-        estimate_l1_data_fee_inner(provider),
+        estimate_l1_data_fee_inner(provider, oracle),
     )
     .await
     {
@@ -1201,13 +2457,13 @@ pub async fn estimate_l1_data_fee<P: Provider + Sync>(provider: &P) -> u128 {
             // Timeout — önbellekten son bilinen değeri kullan
             let cached = CACHED_L1_FEE.load(Ordering::Relaxed);
             if cached > 0 {
-                eprintln!(
+                eprintln_high!(
                     "  ⚠️ [L1 Fee] GasPriceOracle query timed out ({}ms) — using cache ({} wei)",
                     L1_FEE_TIMEOUT_MS, cached,
                 );
                 cached as u128
             } else {
-                eprintln!(
+                eprintln_high!(
                     "  ⚠️ [L1 Fee] GasPriceOracle query timed out ({}ms) — using conservative fallback",
                     L1_FEE_TIMEOUT_MS,
                 );
@@ -1218,7 +2474,7 @@ pub async fn estimate_l1_data_fee<P: Provider + Sync>(provider: &P) -> u128 {
 }
 
 /// estimate_l1_data_fee iç implementasyonu (timeout wrapper'sız)
-async fn estimate_l1_data_fee_inner<P: Provider + Sync>(provider: &P) -> u128 {
+async fn estimate_l1_data_fee_inner<P: Provider + Sync>(provider: &P, oracle: Address) -> u128 {
     const FALLBACK_FEE_WEI: u128 = 5_000_000_000_000; // 0.000005 ETH (Base post-EIP-4844)
                                                       // OPT-G: Gercekci calldata pattern — 134 byte compact calldata.
                                                       // Gercek calldata'da ~%35 zero byte bulunur (adres leading zeros,
@@ -1241,7 +2497,7 @@ async fn estimate_l1_data_fee_inner<P: Provider + Sync>(provider: &P) -> u128 {
         data
     };
 
-    let oracle = IGasPriceOracle::new(GAS_PRICE_ORACLE_ADDRESS, provider);
+    let oracle = IGasPriceOracle::new(oracle, provider);
     match oracle.getL1Fee(representative_calldata.into()).call().await {
         Ok(fee) => {
             // U256 → u128 safe conversion
@@ -1253,7 +2509,7 @@ async fn estimate_l1_data_fee_inner<P: Provider + Sync>(provider: &P) -> u128 {
                 // Base mainnet'te 134-byte calldata için L1 fee asla 0 olmamalı.
                 // Konservatif fallback kullan ve uyar.
                 if fee_u128 == 0 {
-                    eprintln!(
+                    eprintln_high!(
                         "  ⚠️ [L1 Fee] GasPriceOracle.getL1Fee() returned 0 — oracle data feed may be faulty, using conservative fallback",
                     );
                     FALLBACK_FEE_WEI
@@ -1263,7 +2519,7 @@ async fn estimate_l1_data_fee_inner<P: Provider + Sync>(provider: &P) -> u128 {
             }
         }
         Err(e) => {
-            eprintln!(
+            eprintln_high!(
                 "  ⚠️ L1 data fee estimation failed (fallback: conservative estimate): {}",
                 e
             );
@@ -1275,12 +2531,11 @@ async fn estimate_l1_data_fee_inner<P: Provider + Sync>(provider: &P) -> u128 {
 pub async fn cache_all_bytecodes<P: Provider + Sync>(
     provider: &P,
     pools: &[PoolConfig],
-    states: &[SharedPoolState],
-) -> Vec<Result<()>> {
+    static_store: &StaticPoolStore,
+) -> Vec<BotResult<()>> {
     let futures: Vec<_> = pools
         .iter()
-        .zip(states.iter())
-        .map(|(config, state)| cache_pool_bytecode(provider, config, state))
+        .map(|config| cache_pool_bytecode(provider, config, static_store))
         .collect();
     join_all(futures).await
 }
@@ -1330,10 +2585,11 @@ fn encode_ticks_call(_dex: DexType, tick: i32) -> Vec<u8> {
 ///   [224..256] bool   initialized          — tüm DEX'lerde 8. alan
 ///
 /// Aerodrome ek alanları (stakedLiquidityNet, rewardGrowthOutsideX128)
-/// 8. alandan SONRA gelir, dolayısıyla initialized offset'i değişmez.
+/// 8. alandan SONRA gelir, dolayısıyla initialized offset'i değişmez:
+///   [256..288] int128 stakedLiquidityNet   — yalnızca 320 byte'lık yanıtta
 ///
-/// Dönüş: (liquidityGross, liquidityNet, initialized)
-fn decode_ticks_result(data: &[u8]) -> Option<(u128, i128, bool)> {
+/// Dönüş: TickInfo (UniV3/PCS yanıtında staked_liquidity_net = 0)
+fn decode_ticks_result(data: &[u8]) -> Option<TickInfo> {
     if data.len() < 256 {
         return None;
     }
@@ -1348,7 +2604,83 @@ fn decode_ticks_result(data: &[u8]) -> Option<(u128, i128, bool)> {
     // v22.0: Offset doğrulaması — 8. word tüm DEX'lerde aynı (index=7)
     let initialized = data[255] != 0;
 
-    Some((liq_gross, liq_net, initialized))
+    // stakedLiquidityNet: int128 (son 16 byte of ninth 32-byte word)
+    let staked_net = match data.get(272..288) {
+        Some(word) => i128::from_be_bytes(word.try_into().ok()?),
+        None => 0,
+    };
+
+    Some(TickInfo {
+        liquidity_gross: liq_gross,
+        liquidity_net: liq_net,
+        initialized,
+        staked_liquidity_net: staked_net,
+    })
+}
+
+/// Tick çağrılarının ardına eklenen Slipstream görüntüsünü çöz:
+/// [slot0, liquidity, stakedLiquidity, unstakedFee] — hepsi aynı bloktan.
+/// unstakedFee okunamazsa görüntü yine geçerlidir (fee alanı None).
+fn decode_staked_snapshot(results: &[IMulticall3::Result]) -> Option<StakedLiquidity> {
+    fn data(result: &IMulticall3::Result) -> Option<&[u8]> {
+        result.success.then_some(&result.returnData[..])
+    }
+    let [slot0, liquidity, staked, unstaked_fee] = results else {
+        return None;
+    };
+    let (_, tick) = decode_slot0_result(data(slot0)?, DexType::Aerodrome)?;
+    Some(StakedLiquidity {
+        tick,
+        liquidity: decode_liquidity_result(data(liquidity)?)?,
+        staked_liquidity: decode_liquidity_result(data(staked)?)?,
+        unstaked_fee_pips: data(unstaked_fee)
+            .and_then(|d| d.get(28..32))
+            .and_then(|w| w.try_into().ok())
+            .map(u32::from_be_bytes),
+        verified: false,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Rakip Arbitraj Tespiti (Kuyruk Derinliği Sezgisi)
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Son blokta izlenen havuzlara veya bilinen aggregator router'larına giden
+// birden fazla işlem varsa, gördüğümüz spread daha hızlı botların bıraktığı
+// artıktır. Blok başına TEK eth_getBlockByNumber(full) çağrısı yapılır.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// İşlem hedeflerinden izlenen adreslere (havuz + router) gidenleri say.
+pub fn count_competitor_txs<I>(tx_targets: I, watched: &[Address]) -> u32
+where
+    I: IntoIterator<Item = Option<Address>>,
+{
+    tx_targets
+        .into_iter()
+        .flatten()
+        .filter(|to| watched.contains(to))
+        .count() as u32
+}
+
+/// Bloğun işlem listesini oku ve izlenen adreslere giden işlemleri say.
+///
+/// RPC hatasında None döner — çağıran taraf cezayı uygulamaz.
+pub async fn fetch_competitor_tx_count<P: Provider + Sync>(
+    provider: &P,
+    block_number: u64,
+    watched: &[Address],
+) -> Option<u32> {
+    use alloy::consensus::Transaction as TxTrait;
+
+    let block = provider
+        .get_block_by_number(alloy::eips::BlockNumberOrTag::Number(block_number))
+        .full()
+        .await
+        .ok()??;
+    Some(count_competitor_txs(
+        block.transactions.txns().map(TxTrait::to),
+        watched,
+    ))
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1375,32 +2707,231 @@ fn decode_ticks_result(data: &[u8]) -> Option<(u128, i128, bool)> {
 /// swap(address,bool,int256,uint160,bytes) → 0x128acb08
 const SWAP_SELECTOR: [u8; 4] = [0x12, 0x8a, 0xcb, 0x08];
 
-/// Pending TX'in izlenen bir havuza swap olup olmadığını kontrol et
+/// Pending TX'in etkilediği izlenen havuzları bul — her havuz en fazla bir kez.
+///
+/// - Havuza doğrudan swap() çağrısı → o havuz
+/// - Havuza swap dışı çağrı (mint/burn/collect) → boş
+/// - Başka bir kontrata çağrı (router / aggregator multicall) → calldata'da
+///   adresi geçen tüm izlenen havuzlar, havuz sırasıyla
 ///
-/// Dönen değer: (havuz_indeksi, is_swap) — swap değilse None
+/// Doğrudan swap() çağrısının parametreleri `decode_pending_swap` ile
+/// çözülür ve `apply_pending_swap` ile yerel olarak uygulanır.
 pub fn check_pending_tx_relevance(
     tx_to: Option<Address>,
     tx_input: &[u8],
     pool_addresses: &[Address],
-) -> Option<usize> {
-    let to = tx_to?;
-
-    // Hedef adres izlenen havuzlardan biri mi?
-    let pool_idx = pool_addresses.iter().position(|&addr| addr == to)?;
+) -> Vec<usize> {
+    let Some(to) = tx_to else {
+        return Vec::new();
+    };
 
     // Calldata en az 4 byte (selector) olmalı
     if tx_input.len() < 4 {
+        return Vec::new();
+    }
+
+    // Hedef adres izlenen havuzlardan biri mi? → yalnızca swap selektörü sayılır
+    if let Some(pool_idx) = pool_addresses.iter().position(|&addr| addr == to) {
+        return if tx_input[0..4] == SWAP_SELECTOR {
+            vec![pool_idx]
+        } else {
+            Vec::new()
+        };
+    }
+
+    // Router/aggregator: argümanlarda geçen havuz adresleri (ABI word'ü veya paketli yol)
+    let args = &tx_input[4..];
+    pool_addresses
+        .iter()
+        .enumerate()
+        .filter(|(_, addr)| args.windows(20).any(|w| w == addr.as_slice()))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Havuza doğrudan gönderilmiş swap() çağrısının parametreleri
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingSwap {
+    pub zero_for_one: bool,
+    /// > 0: exact input, < 0: exact output (havuz kontratındaki anlamıyla)
+    pub amount_specified: I256,
+    pub sqrt_price_limit_x96: U256,
+}
+
+/// swap(address,bool,int256,uint160,bytes) calldata'sını çöz.
+///
+/// Calldata formatı (selector sonrası, ABI-encoded):
+///   [4..36]    address recipient
+///   [36..68]   bool    zeroForOne
+///   [68..100]  int256  amountSpecified
+///   [100..132] uint160 sqrtPriceLimitX96
+///   [132..]    bytes   data (offset + içerik — kullanılmaz)
+///
+/// Selector farklıysa ya da başlık 132 byte'tan kısaysa None.
+pub fn decode_pending_swap(tx_input: &[u8]) -> Option<PendingSwap> {
+    if tx_input.len() < 132 || tx_input[0..4] != SWAP_SELECTOR {
+        return None;
+    }
+    Some(PendingSwap {
+        zero_for_one: tx_input[67] != 0,
+        amount_specified: I256::from_raw(U256::from_be_slice(&tx_input[68..100])),
+        sqrt_price_limit_x96: U256::from_be_slice(&tx_input[100..132]),
+    })
+}
+
+/// Pending swap'ı havuzun onaylı state'i (varsa taze tahmini) ve tick
+/// bitmap'i üzerinde exact multi-tick swap ile uygula; sonucu
+/// `PoolState::predicted_state`'e yaz. Onaylı alanlara dokunulmaz.
+///
+/// Yalnızca exact input (amountSpecified > 0) modellenir; exact output,
+/// çıktısız swap veya aktif olmayan havuz None döner — çağıran slot0
+/// yenilemesine düşer. Fiyat limitine ulaşan swap limitte durdurulur;
+/// likidite de limite kadar geçilen tick'lerden yeniden hesaplanır.
+pub fn apply_pending_swap(
+    pool_config: &PoolConfig,
+    pool_state: &SharedPoolState,
+    swap: PendingSwap,
+    trigger: OptimisticTrigger,
+) -> Option<OptimisticUpdate> {
+    if !swap.amount_specified.is_positive() {
+        return None;
+    }
+    let confirmed = pool_state.load_full();
+    if !confirmed.is_active() {
+        return None;
+    }
+    // Aynı blokta zincirlenen pending swap'lar önceki tahminin üstüne uygulanır
+    let base = Arc::clone(&confirmed).with_prediction(confirmed.last_block, 0);
+    let (mut next, amount_out) = crate::math::exact::advance_pool_state(
+        &base,
+        pool_config,
+        swap.amount_specified.into_raw(),
+        swap.zero_for_one,
+    );
+    if amount_out.is_zero() {
         return None;
     }
 
-    // Swap selektörü mü?
-    if tx_input[0..4] == SWAP_SELECTOR {
-        Some(pool_idx)
+    let limit = swap.sqrt_price_limit_x96;
+    let past_limit = if swap.zero_for_one {
+        next.sqrt_price_x96 < limit
     } else {
-        None
+        !limit.is_zero() && next.sqrt_price_x96 > limit
+    };
+    if past_limit {
+        next.sqrt_price_x96 = limit;
+        next.sqrt_price_f64 = u256_to_f64(limit);
+        next.tick = crate::math::exact::tick_after_swap(limit, swap.zero_for_one);
+        // Limit ötesinde geçilen tick'lerin liquidityNet'i geri alınır
+        next.set_liquidity(crate::math::exact::liquidity_at_sqrt_price(
+            base.liquidity,
+            base.tick,
+            limit,
+            swap.zero_for_one,
+            base.tick_bitmap.as_deref(),
+        ));
+        next.eth_price_usd = compute_eth_price(
+            next.sqrt_price_f64,
+            next.tick,
+            pool_config.token0_decimals,
+            pool_config.token1_decimals,
+            pool_config.token0_is_weth,
+        );
+    }
+
+    let prediction = PredictedState {
+        sqrt_price_x96: next.sqrt_price_x96,
+        sqrt_price_f64: next.sqrt_price_f64,
+        tick: next.tick,
+        liquidity: next.liquidity,
+        eth_price_usd: next.eth_price_usd,
+        base_block: confirmed.last_block,
+        predicted_at: Instant::now(),
+        trigger,
+    };
+    // Okuma ile yazım arasında onaylı yazım geldiyse tahmin atılır
+    let mut stored = false;
+    pool_state.rcu(|old| {
+        stored = old.last_block == confirmed.last_block && old.last_update == confirmed.last_update;
+        let mut s = (**old).clone();
+        if stored {
+            s.predicted_state = Some(Box::new(prediction));
+        }
+        s
+    });
+    if !stored {
+        return None;
+    }
+
+    Some(OptimisticUpdate {
+        pool: pool_config.name.clone(),
+        pool_address: pool_config.address,
+        old_price: base.eth_price_usd,
+        new_price: prediction.eth_price_usd,
+        block: confirmed.last_block,
+        trigger,
+        latency: trigger.observed_at.elapsed(),
+    })
+}
+
+/// İzlenen havuzlar arasında bilinen en güncel blok — iyimser yazımların
+/// blok etiketi (etkilenen havuzun kendi last_block'u geride kalmış olabilir)
+pub fn latest_known_block(states: &[SharedPoolState]) -> u64 {
+    states.iter().map(|s| s.load().last_block).max().unwrap_or(0)
+}
+
+/// Uygulanmış iyimser güncellemenin denetim kaydı
+#[derive(Debug, Clone)]
+pub struct OptimisticUpdate {
+    pub pool: String,
+    pub pool_address: Address,
+    pub old_price: f64,
+    pub new_price: f64,
+    pub block: u64,
+    pub trigger: OptimisticTrigger,
+    /// TX gözleminden state yazımına kadar geçen süre
+    pub latency: std::time::Duration,
+}
+
+impl OptimisticUpdate {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "ts": crate::clock::log_timestamp(),
+            "pool": self.pool,
+            "pool_address": format!("{:?}", self.pool_address),
+            "old_price": self.old_price,
+            "new_price": self.new_price,
+            "block": self.block,
+            "trigger_tx": format!("{:?}", self.trigger.tx_hash),
+            "trigger_sender": format!("{:?}", self.trigger.sender),
+            "latency_us": self.latency.as_micros() as u64,
+        })
     }
 }
 
+/// OPTIMISTIC_UPDATE_LOG=true iken iyimser güncellemelerin yazıldığı dosya
+pub const OPTIMISTIC_UPDATES_PATH: &str = "optimistic_updates.jsonl";
+
+/// Kaydı JSONL dosyasına ekle (satır başına bir güncelleme)
+pub fn append_optimistic_update(
+    path: &std::path::Path,
+    update: &OptimisticUpdate,
+) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut file = crate::secure_fs::open_append(path)?;
+    writeln!(file, "{}", update.to_json())?;
+    Ok(())
+}
+
+/// İyimser yenilemenin slot0/liquidity okumaları — abonelik bloğunun
+/// hash'inde havuz başına bir kez; aynı havuza dokunan sonraki pending
+/// TX'ler o blok boyunca bellekten okur
+static OPTIMISTIC_READS: LazyLock<parking_lot::Mutex<OptimisticReadMemo>> =
+    LazyLock::new(|| parking_lot::Mutex::new(BlockMemo::new()));
+
+/// Havuz adresi → (sqrtPriceX96, tick, liquidity)
+type OptimisticReadMemo = BlockMemo<Address, (U256, i32, u128)>;
+
 /// Pending swap TX varsa havuz durumunu iyimser olarak güncelle
 ///
 /// Bu fonksiyon tam bir fiyat hesabı YAPMAZ — sadece havuzun
@@ -1411,110 +2942,141 @@ pub fn check_pending_tx_relevance(
 /// - `provider`: RPC sağlayıcı (anlık slot0 sorgusu için)
 /// - `pool_config`: Etkilenen havuzun yapılandırması
 /// - `pool_state`: Güncellenen havuz durumu (write lock alır)
-/// - `current_block`: Mevcut blok numarası
+/// - `current_block`: Bilinen en güncel blok (bkz. `latest_known_block`)
+/// - `pinned`: Abonelik bloğunun hash'i — okuma buna sabitlenir ve blok
+///   başına bellekte tutulur (None → latest, belleksiz)
+/// - `trigger`: Yenilemeyi tetikleyen pending TX — state'e işlenir
 ///
 /// # Dönüş
-/// - Ok(true): Durum güncellendi (yeni swap tespit edildi)
-/// - Ok(false): Güncelleme gerekmedi
+/// - Ok(Some(kayıt)): Durum güncellendi (yeni swap tespit edildi)
+/// - Ok(None): Güncelleme gerekmedi
 /// - Err: RPC hatası
-pub async fn optimistic_refresh_pool<P: Provider + Sync>(
-    provider: &P,
+pub async fn optimistic_refresh_pool<P: Provider + Clone + Sync>(
+    providers: &ProviderRegistry<P>,
     pool_config: &PoolConfig,
     pool_state: &SharedPoolState,
     current_block: u64,
-) -> Result<bool> {
-    // Havuzun güncel slot0 ve liquidity değerlerini anlık oku
-    // v10.0: Paralel okuma (tokio::join!) — tek RTT (~1-3ms)
+    pinned: Option<B256>,
+    trigger: OptimisticTrigger,
+) -> BotResult<Option<OptimisticUpdate>> {
+    // Havuzun kendi endpoint'inden (tanımlıysa — hata → primary) oku
+    const METHOD: &str = "slot0+liquidity";
+    const SITE: &str = "optimistic_refresh";
+    let census_key = CallKey::new(METHOD, pool_config.address, SITE);
+    let (sqrt_price_x96, tick, liquidity) =
+        rpc_census::memoized(&OPTIMISTIC_READS, pinned, census_key, pool_config.address, |block| {
+            providers.traced(SITE).call(METHOD, pool_config.address, move |p| async move {
+                read_slot0_liquidity(&p, pool_config, block).await
+            })
+        })
+        .await?;
+
+    apply_optimistic_read(
+        pool_config,
+        pool_state,
+        sqrt_price_x96,
+        tick,
+        liquidity,
+        current_block,
+        trigger,
+    )
+}
+
+/// Havuzun slot0 ve liquidity değerlerini `block`'ta oku.
+/// v10.0: Paralel okuma (tokio::join!) — tek RTT (~1-3ms)
+async fn read_slot0_liquidity<P: Provider + Sync>(
+    provider: &P,
+    pool_config: &PoolConfig,
+    block: BlockId,
+) -> BotResult<(U256, i32, u128)> {
     let (sqrt_price_x96, tick, liquidity) = match pool_config.dex {
         DexType::UniswapV3 => {
             let pool = IUniswapV3Pool::new(pool_config.address, provider);
-            let slot0_call = pool.slot0();
-            let liq_call = pool.liquidity();
+            let slot0_call = pool.slot0().block(block);
+            let liq_call = pool.liquidity().block(block);
             let (slot0_result, liq_result) = tokio::join!(slot0_call.call(), liq_call.call(),);
             let slot0 = slot0_result.map_err(|e| {
-                eyre::eyre!(
-                    "[OPT:{}] slot0 read error (V3/uint8): {}",
-                    pool_config.name,
-                    e
-                )
+                BotError::from_call(format!("[OPT:{}] slot0 (V3/uint8)", pool_config.name), e)
             })?;
             let liq = liq_result.map_err(|e| {
-                eyre::eyre!("[OPT:{}] liquidity read error: {}", pool_config.name, e)
+                BotError::from_call(format!("[OPT:{}] liquidity", pool_config.name), e)
             })?;
             (slot0.sqrtPriceX96, slot0.tick.as_i32(), liq)
         }
         DexType::PancakeSwapV3 => {
             let pool = IPancakeSwapV3Pool::new(pool_config.address, provider);
-            let slot0_call = pool.slot0();
-            let liq_call = pool.liquidity();
+            let slot0_call = pool.slot0().block(block);
+            let liq_call = pool.liquidity().block(block);
             let (slot0_result, liq_result) = tokio::join!(slot0_call.call(), liq_call.call(),);
             let slot0 = slot0_result.map_err(|e| {
-                eyre::eyre!(
-                    "[OPT:{}] slot0 read error (PCS-V3/uint32): {}",
-                    pool_config.name,
-                    e
-                )
+                BotError::from_call(format!("[OPT:{}] slot0 (PCS-V3/uint32)", pool_config.name), e)
             })?;
             let liq = liq_result.map_err(|e| {
-                eyre::eyre!("[OPT:{}] liquidity read error: {}", pool_config.name, e)
+                BotError::from_call(format!("[OPT:{}] liquidity", pool_config.name), e)
             })?;
             (slot0.sqrtPriceX96, slot0.tick.as_i32(), liq)
         }
         DexType::Aerodrome => {
             let pool = IAerodromePool::new(pool_config.address, provider);
-            let slot0_call = pool.slot0();
-            let liq_call = pool.liquidity();
+            let slot0_call = pool.slot0().block(block);
+            let liq_call = pool.liquidity().block(block);
             let (slot0_result, liq_result) = tokio::join!(slot0_call.call(), liq_call.call(),);
             let slot0 = slot0_result.map_err(|e| {
-                eyre::eyre!(
-                    "[OPT:{}] slot0 read error (Aero/6-field): {}",
-                    pool_config.name,
-                    e
-                )
+                BotError::from_call(format!("[OPT:{}] slot0 (Aero/6-field)", pool_config.name), e)
             })?;
             let liq = liq_result.map_err(|e| {
-                eyre::eyre!("[OPT:{}] liquidity read error: {}", pool_config.name, e)
+                BotError::from_call(format!("[OPT:{}] liquidity", pool_config.name), e)
             })?;
             (slot0.sqrtPriceX96, slot0.tick.as_i32(), liq)
         }
+        DexType::UniswapV2 => return read_pool_reserves(provider, pool_config, block).await,
     };
 
-    let sqrt_price_f64: f64 = u256_to_f64(U256::from(sqrt_price_x96));
-    let liquidity_f64: f64 = u256_to_f64(U256::from(liquidity));
+    Ok((U256::from(sqrt_price_x96), tick, liquidity))
+}
 
-    let eth_price = compute_eth_price(
-        sqrt_price_f64,
-        tick,
-        pool_config.token0_decimals,
-        pool_config.token1_decimals,
-        pool_config.token0_is_weth,
-    );
+/// İyimser okumayı yaz — fiyat değişmediyse state'e dokunulmaz (Ok(None)).
+/// Yazılan state tetikleyen TX'i taşır; kayıt eski/yeni fiyatı içerir.
+pub fn apply_optimistic_read(
+    pool_config: &PoolConfig,
+    pool_state: &SharedPoolState,
+    sqrt_price_x96: U256,
+    tick: i32,
+    liquidity: u128,
+    current_block: u64,
+    trigger: OptimisticTrigger,
+) -> BotResult<Option<OptimisticUpdate>> {
+    let sqrt_price_f64: f64 = u256_to_f64(sqrt_price_x96);
+    let eth_price = pool_eth_price(pool_config, sqrt_price_x96, tick, liquidity);
 
     // Mevcut state ile karşılaştır — fiyat değişmişse güncelle
-    let price_changed = {
-        let state = pool_state.load();
-        (state.eth_price_usd - eth_price).abs() > 0.001 // >$0.001 fark
-    };
+    let old_price = pool_state.load().eth_price_usd;
+    if (old_price - eth_price).abs() <= 0.001 {
+        return Ok(None); // >$0.001 fark yok
+    }
 
-    if price_changed {
-        let sqrt_price_x96_val = U256::from(sqrt_price_x96);
-        pool_state.rcu(|old| {
-            let mut s = (**old).clone();
-            s.sqrt_price_x96 = sqrt_price_x96_val;
-            s.sqrt_price_f64 = sqrt_price_f64;
-            s.tick = tick;
-            s.liquidity = liquidity;
-            s.liquidity_f64 = liquidity_f64;
-            s.eth_price_usd = eth_price;
-            s.last_block = current_block;
-            s.last_update = Instant::now();
-            s.is_stale = false;
-            s
-        });
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+    commit_validated(pool_config, pool_state, |s| {
+        s.sqrt_price_x96 = sqrt_price_x96;
+        s.sqrt_price_f64 = sqrt_price_f64;
+        s.tick = tick;
+        s.set_liquidity(liquidity);
+        s.eth_price_usd = eth_price;
+        s.last_block = current_block;
+        s.last_update = Instant::now();
+        s.is_stale = false;
+        s.optimistic_trigger = Some(trigger);
+    })
+    .map_err(|issue| BotError::pool_data(&pool_config.name, issue))?;
+
+    Ok(Some(OptimisticUpdate {
+        pool: pool_config.name.clone(),
+        pool_address: pool_config.address,
+        old_price,
+        new_price: eth_price,
+        block: current_block,
+        trigger,
+        latency: trigger.observed_at.elapsed(),
+    }))
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1563,15 +3125,30 @@ const BURN_EVENT_TOPIC: [u8; 32] = [
     0xcd, 0xbc, 0x45, 0x90, 0x8a, 0xcf, 0xd6, 0x7e, 0x02, 0x8c, 0xd5, 0x68, 0xda, 0x98, 0x98, 0x2c,
 ];
 
-/// Swap event log verisinden havuz durumunu çıkar ve güncelle.
+/// Swap event log verisini çöz → (sqrtPriceX96, liquidity, tick).
 ///
-/// Log Data formatı (non-indexed parametreler, ABI-encoded):
+/// Log Data formatı (non-indexed parametreler, ABI-encoded; V3 ve Slipstream aynı):
 ///   [0..32]    int256  amount0
 ///   [32..64]   int256  amount1
 ///   [64..96]   uint160 sqrtPriceX96 (sağ hizalı, 32 byte padded)
 ///   [96..128]  uint128 liquidity (sağ hizalı, 32 byte padded)
 ///   [128..160] int24   tick (sağ hizalı, 32 byte padded, sign-extended)
 ///
+/// 160 byte'tan kısa veri None.
+pub fn decode_swap_event(log_data: &[u8]) -> Option<(U256, u128, i32)> {
+    if log_data.len() < 160 {
+        return None;
+    }
+    let sqrt_price_x96 = U256::from_be_slice(&log_data[64..96]);
+    // Son 16 byte = uint128
+    let liquidity = u128::from_be_bytes(log_data[112..128].try_into().ok()?);
+    // Son 4 byte'ı int32 olarak oku, sonra -887272..887272 aralığına sınırla
+    let tick = i32::from_be_bytes(log_data[156..160].try_into().ok()?).clamp(MIN_TICK, MAX_TICK);
+    Some((sqrt_price_x96, liquidity, tick))
+}
+
+/// Swap event log verisinden havuz durumunu çıkar ve güncelle.
+///
 /// # Dönüş
 /// Ok(true) → durum güncellendi, Ok(false) → güncelleme gerekmedi
 pub fn process_swap_event_log(
@@ -1580,7 +3157,7 @@ pub fn process_swap_event_log(
     log_block_number: u64,
     pools: &[PoolConfig],
     states: &[SharedPoolState],
-) -> Result<bool> {
+) -> BotResult<bool> {
     // Log adresi hangi havuza ait?
     let pool_idx = pools.iter().position(|p| p.address == log_address);
 
@@ -1589,29 +3166,14 @@ pub fn process_swap_event_log(
         None => return Ok(false), // Bilinmeyen havuz, atla
     };
 
-    // Log data en az 160 byte olmalı (5 × 32 byte)
-    if log_data.len() < 160 {
+    let Some((sqrt_price_x96, liquidity, tick)) = decode_swap_event(log_data) else {
         return Ok(false);
-    }
-
-    // sqrtPriceX96 çıkar (offset 64..96, uint160)
-    let sqrt_price_x96 = U256::from_be_slice(&log_data[64..96]);
-
-    // liquidity çıkar (offset 96..128, uint128)
-    let liquidity_bytes = &log_data[112..128]; // Son 16 byte = uint128
-    let liquidity = u128::from_be_bytes(liquidity_bytes.try_into().unwrap_or([0u8; 16]));
-
-    // tick çıkar (offset 128..160, int24 olarak sign-extended int256)
-    // Son 4 byte'ı int32 olarak oku, sonra -887272..887272 aralığına sınırla
-    let tick_bytes = &log_data[156..160]; // Son 4 byte
-    let tick_raw = i32::from_be_bytes(tick_bytes.try_into().unwrap_or([0u8; 4]));
-    let tick = tick_raw.clamp(-887272, 887272);
+    };
 
     let config = &pools[pool_idx];
 
-    // f64 dönüşümleri
+    // f64 dönüşümü (likiditeninki set_liquidity içinde)
     let sqrt_price_f64: f64 = u256_to_f64(sqrt_price_x96);
-    let liquidity_f64: f64 = liquidity as f64;
 
     // ETH fiyatı hesapla
     let eth_price = compute_eth_price(
@@ -1629,13 +3191,13 @@ pub fn process_swap_event_log(
             s.sqrt_price_x96 = sqrt_price_x96;
             s.sqrt_price_f64 = sqrt_price_f64;
             s.tick = tick;
-            s.liquidity = liquidity;
-            s.liquidity_f64 = liquidity_f64;
+            s.set_liquidity(liquidity);
             s.eth_price_usd = eth_price;
             s.last_block = log_block_number;
             s.last_update = Instant::now();
             s.is_initialized = true;
             s.is_stale = false;
+            s.optimistic_trigger = None;
             s
         });
     }
@@ -1658,7 +3220,7 @@ pub fn process_mint_event_log(
     log_block_number: u64,
     pools: &[PoolConfig],
     states: &[SharedPoolState],
-) -> Result<bool> {
+) -> BotResult<bool> {
     let pool_idx = match pools.iter().position(|p| p.address == log_address) {
         Some(idx) => idx,
         None => return Ok(false),
@@ -1689,8 +3251,7 @@ pub fn process_mint_event_log(
         }
         // Mevcut tick aralıktaysa aktif likiditeyi artır
         if s.tick >= tick_lower && s.tick < tick_upper {
-            s.liquidity = s.liquidity.saturating_add(amount);
-            s.liquidity_f64 = s.liquidity as f64;
+            s.set_liquidity(s.liquidity.saturating_add(amount));
         }
         s.last_block = log_block_number;
         s.last_update = Instant::now();
@@ -1715,7 +3276,7 @@ pub fn process_burn_event_log(
     log_block_number: u64,
     pools: &[PoolConfig],
     states: &[SharedPoolState],
-) -> Result<bool> {
+) -> BotResult<bool> {
     let pool_idx = match pools.iter().position(|p| p.address == log_address) {
         Some(idx) => idx,
         None => return Ok(false),
@@ -1746,8 +3307,7 @@ pub fn process_burn_event_log(
         }
         // Mevcut tick aralıktaysa aktif likiditeyi azalt
         if s.tick >= tick_lower && s.tick < tick_upper {
-            s.liquidity = s.liquidity.saturating_sub(amount);
-            s.liquidity_f64 = s.liquidity as f64;
+            s.set_liquidity(s.liquidity.saturating_sub(amount));
         }
         s.last_block = log_block_number;
         s.last_update = Instant::now();
@@ -1759,13 +3319,15 @@ pub fn process_burn_event_log(
 /// Event-driven havuz dinleyici başlat (Swap + Mint + Burn).
 ///
 /// Havuz adreslerindeki Swap, Mint ve Burn eventlerini WebSocket/IPC üzerinden dinler.
-/// Her event geldiğinde havuz state'ini ve tick bitmap'ini anlık günceller.
+/// Her event geldiğinde havuz state'ini ve tick bitmap'ini anlık günceller;
+/// Swap eventleri ayrıca tazelik kahinine (`oracle`) işlenir.
 pub async fn start_pool_event_listener<P: Provider + Sync>(
     provider: &P,
     pools: &[PoolConfig],
     states: &[SharedPoolState],
+    oracle: &EventFreshnessOracle,
     cancel: tokio_util::sync::CancellationToken,
-) -> Result<()> {
+) -> BotResult<()> {
     use alloy::rpc::types::Filter;
 
     let pool_addresses: Vec<Address> = pools.iter().map(|p| p.address).collect();
@@ -1781,10 +3343,10 @@ pub async fn start_pool_event_listener<P: Provider + Sync>(
     let sub = provider
         .subscribe_logs(&filter)
         .await
-        .map_err(|e| eyre::eyre!("Pool event subscription error: {}", e))?;
+        .map_err(|e| BotError::from_transport("Pool event subscription", e))?;
     let mut stream = sub.into_stream();
 
-    println!(
+    println_high!(
         "  Event-driven pool listener active ({} pools, Swap+Mint+Burn)",
         pools.len()
     );
@@ -1792,7 +3354,7 @@ pub async fn start_pool_event_listener<P: Provider + Sync>(
     loop {
         tokio::select! {
             _ = cancel.cancelled() => {
-                eprintln!("  [EventListener] Restarting with updated pool list...");
+                eprintln_high!("  [EventListener] Restarting with updated pool list...");
                 return Ok(());
             }
             log_opt = stream.next() => {
@@ -1810,53 +3372,178 @@ pub async fn start_pool_event_listener<P: Provider + Sync>(
                 let topic0 = topics[0];
 
                 if topic0 == swap_topic {
+                    if let Some((sqrt_price_x96, _, tick)) = decode_swap_event(log_data) {
+                        oracle.record(log_address, SwapObservation { block: block_number, sqrt_price_x96, tick });
+                    }
                     match process_swap_event_log(log_data, log_address, block_number, pools, states) {
                         Ok(true) => {
                             if let Some(idx) = pools.iter().position(|p| p.address == log_address) {
                                 let state = states[idx].load();
-                                eprintln!(
+                                eprintln_high!(
                                     "     [Event] {} Swap -> {:.2}$ | Tick: {} | Block: #{}",
                                     pools[idx].name, state.eth_price_usd, state.tick, block_number,
                                 );
                             }
                         }
                         Ok(false) => {}
-                        Err(e) => eprintln!("     [Event] Swap log error: {}", e),
+                        Err(e) => eprintln_high!("     [Event] Swap log error: {}", e),
                     }
                 } else if topic0 == mint_topic {
                     let b256_topics: Vec<alloy::primitives::B256> = topics.to_vec();
                     match process_mint_event_log(log_data, &b256_topics, log_address, block_number, pools, states) {
                         Ok(true) => {
                             if let Some(idx) = pools.iter().position(|p| p.address == log_address) {
-                                eprintln!(
+                                eprintln_high!(
                                     "     [Event] Mint @ {} | Block: #{}",
                                     pools[idx].name, block_number,
                                 );
                             }
                         }
                         Ok(false) => {}
-                        Err(e) => eprintln!("     [Event] Mint log error: {}", e),
+                        Err(e) => eprintln_high!("     [Event] Mint log error: {}", e),
                     }
                 } else if topic0 == burn_topic {
                     let b256_topics: Vec<alloy::primitives::B256> = topics.to_vec();
                     match process_burn_event_log(log_data, &b256_topics, log_address, block_number, pools, states) {
                         Ok(true) => {
                             if let Some(idx) = pools.iter().position(|p| p.address == log_address) {
-                                eprintln!(
+                                eprintln_high!(
                                     "     [Event] Burn @ {} | Block: #{}",
                                     pools[idx].name, block_number,
                                 );
                             }
                         }
                         Ok(false) => {}
-                        Err(e) => eprintln!("     [Event] Burn log error: {}", e),
+                        Err(e) => eprintln_high!("     [Event] Burn log error: {}", e),
                     }
                 }
             }
         }
     }
 
-    Err(eyre::eyre!("Pool event stream closed"))
+    Err(BotError::RpcTransport {
+        context: "Pool event listener".into(),
+        source: "stream closed".into(),
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Event Tazelik Kahini — Swap Eventi ↔ Sabitli Sync Ayrışma Alarmı
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Yük dengeli endpoint'lerde eth_call gecikmeli bir replikaya düşebilir:
+// başlıklar yeni gelir ama okunan state eskidir. Swap eventi o bloktaki son
+// (sqrtPriceX96, tick) değerini taşır — event bloğunda ya da sonrasında
+// sabitlenmiş bir okuma bununla uyuşmuyorsa okuma bayattır. Havuz bayat
+// işaretlenir ve yedek HTTP provider'dan (RPC_HTTP_URL) yeniden okunur.
+
+/// Havuzun son Swap eventindeki fiyat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapObservation {
+    pub block: u64,
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+}
+
+/// Sabitli sync'in son Swap eventiyle uyuşmazlığı
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateDivergence {
+    pub pool_idx: usize,
+    /// State'in sabitlendiği blok (≥ event bloğu)
+    pub synced_block: u64,
+    pub synced_tick: i32,
+    pub event: SwapObservation,
+}
+
+/// Sabitli okuma event'le çelişiyor mu? Yalnızca event bloğunda ya da
+/// sonrasında sabitlenmiş state karşılaştırılır. `tolerance_ticks` = 0:
+/// birebir (sqrtPriceX96 dahil).
+pub fn state_diverges(state: &PoolState, event: &SwapObservation, tolerance_ticks: u32) -> bool {
+    let Some(pinned) = state.pinned_block else {
+        return false;
+    };
+    if !state.is_initialized || pinned < event.block {
+        return false;
+    }
+    state.tick.abs_diff(event.tick) > tolerance_ticks
+        || (tolerance_ticks == 0 && state.sqrt_price_x96 != event.sqrt_price_x96)
+}
+
+/// Havuz adresi → son Swap eventi. Event dinleyici yazar, ana döngü okur.
+#[derive(Debug, Default)]
+pub struct EventFreshnessOracle {
+    /// (son event, bu event için alarm verildi mi)
+    last: parking_lot::Mutex<HashMap<Address, (SwapObservation, bool)>>,
+}
+
+impl EventFreshnessOracle {
+    /// Eventi kaydet — daha eski bloktan gelen event yok sayılır; aynı
+    /// bloktaki sonraki event (log sırası) öncekinin yerine geçer.
+    pub fn record(&self, pool: Address, observation: SwapObservation) {
+        let mut last = self.last.lock();
+        match last.get(&pool) {
+            Some((prev, _)) if prev.block > observation.block => {}
+            _ => {
+                last.insert(pool, (observation, false));
+            }
+        }
+    }
+
+    /// Ayrışan havuzları bul ve bayat işaretle. Aynı event için en fazla bir
+    /// alarm — yeniden sync de uyuşmazsa yeni event gelene kadar susar.
+    pub fn check(
+        &self,
+        pools: &[PoolConfig],
+        states: &[SharedPoolState],
+        tolerance_ticks: u32,
+    ) -> Vec<StateDivergence> {
+        let mut last = self.last.lock();
+        let mut divergences = Vec::new();
+        for (pool_idx, (pool, state)) in pools.iter().zip(states).enumerate() {
+            let Some((event, alarmed)) = last.get_mut(&pool.address) else {
+                continue;
+            };
+            let st = state.load();
+            if *alarmed || !state_diverges(&st, event, tolerance_ticks) {
+                continue;
+            }
+            *alarmed = true;
+            divergences.push(StateDivergence {
+                pool_idx,
+                synced_block: st.pinned_block.unwrap_or(st.last_block),
+                synced_tick: st.tick,
+                event: *event,
+            });
+            state.rcu(|old| {
+                let mut s = (**old).clone();
+                s.is_stale = true;
+                s
+            });
+        }
+        divergences
+    }
+}
+
+/// Ayrışan havuzları yedek provider'dan `block`'a sabitli yeniden oku.
+/// Dönüş: başarıyla yeniden sync edilen havuz sayısı.
+pub async fn resync_diverged<P: Provider + Sync>(
+    provider: &P,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    divergences: &[StateDivergence],
+    block: u64,
+) -> usize {
+    let mut resynced = 0;
+    for d in divergences {
+        let (Some(pool), Some(state)) = (pools.get(d.pool_idx), states.get(d.pool_idx)) else {
+            continue;
+        };
+        match sync_pool_state(provider, pool, state, block, BlockId::number(block)).await {
+            Ok(()) => resynced += 1,
+            Err(e) => eprintln_high!("     ⚠️ [Divergence] {} fallback re-sync failed: {}", pool.name, e),
+        }
+    }
+    resynced
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1896,10 +3583,15 @@ mod rpc_failover_tests {
             last_block: block,
             last_update: Instant::now(),
             is_initialized: true,
-            bytecode: None,
             tick_bitmap: None,
             live_fee_bps: None,
             is_stale: false,
+            data_quality_errors: 0,
+            quarantined: false,
+            optimistic_trigger: None,
+            pinned_block: Some(block),
+            predicted_state: None,
+            constant_product: false,
         }))
     }
 
@@ -2058,3 +3750,1551 @@ mod rpc_failover_tests {
                                                    // retry 10+: min(6) clamp → 6400ms (< 10000 cap)
     }
 }
+
+#[cfg(test)]
+mod competition_tests {
+    use super::count_competitor_txs;
+    use alloy::primitives::{address, Address};
+
+    const POOL_A: Address = address!("d0b53D9277642d899DF5C87A3966A349A798F224");
+    const POOL_B: Address = address!("cDAC0d6c6C59727a65F871236188350531885C43");
+    const ROUTER: Address = address!("6fF5693b99212Da76ad316178A184AB56D299b43");
+    const OTHER: Address = address!("4200000000000000000000000000000000000006");
+
+    #[test]
+    fn test_counts_only_watched_targets() {
+        let watched = [POOL_A, POOL_B, ROUTER];
+        let block_txs = vec![
+            Some(POOL_A),
+            Some(OTHER),
+            Some(ROUTER),
+            None, // kontrat deploy
+            Some(POOL_B),
+            Some(OTHER),
+        ];
+        assert_eq!(count_competitor_txs(block_txs, &watched), 3);
+    }
+
+    #[test]
+    fn test_empty_block_and_empty_watchlist() {
+        assert_eq!(count_competitor_txs(Vec::new(), &[POOL_A]), 0);
+        assert_eq!(count_competitor_txs(vec![Some(POOL_A), Some(POOL_B)], &[]), 0);
+    }
+
+    #[test]
+    fn test_repeated_hits_on_same_pool_are_counted() {
+        let block_txs = vec![Some(POOL_A); 4];
+        assert_eq!(count_competitor_txs(block_txs, &[POOL_A]), 4);
+    }
+}
+
+/// Test havuzu: WETH/USDC (18/6), UniV3 %0.05, tick_spacing 10 — yalnızca ad değişir
+#[cfg(test)]
+pub(crate) fn test_pool_config(name: impl Into<String>) -> PoolConfig {
+    PoolConfig {
+        address: address!("d0b53D9277642d899DF5C87A3966A349A798F224"),
+        name: name.into(),
+        fee: crate::types::FeeTier::from_bps(5),
+        token0_decimals: 18,
+        token1_decimals: 6,
+        dex: DexType::UniswapV3,
+        token0_is_weth: true,
+        tick_spacing: 10,
+        quote_token_address: address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+        base_token_address: address!("4200000000000000000000000000000000000006"),
+    }
+}
+
+/// Test havuzunun senkron durumu: tick -197_000 ≈ 2780 USDC
+#[cfg(test)]
+pub(crate) const TEST_TICK: i32 = -197_000;
+#[cfg(test)]
+pub(crate) const TEST_LIQ: u128 = 5_000_000_000_000_000_000;
+
+/// ABI word: işaret genişletmeli int
+#[cfg(test)]
+pub(crate) fn word_i32(value: i32) -> [u8; 32] {
+    let mut word = if value < 0 { [0xff; 32] } else { [0; 32] };
+    word[28..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// V3 slot0 dönüşü (7 alan): sqrtPriceX96, tick, dört sıfır alan, unlocked
+#[cfg(test)]
+pub(crate) fn slot0_return(tick: i32) -> Vec<u8> {
+    let mut data = crate::math::exact::get_sqrt_ratio_at_tick(tick).to_be_bytes::<32>().to_vec();
+    data.extend_from_slice(&word_i32(tick));
+    for field in [0, 0, 0, 0, 1] {
+        data.extend_from_slice(&word_i32(field));
+    }
+    data
+}
+
+#[cfg(test)]
+mod optimistic_tests {
+    use super::*;
+    use crate::math::exact::get_sqrt_ratio_at_tick;
+    use alloy::primitives::{address, b256};
+    use arc_swap::ArcSwap;
+
+    const POOL_A: Address = address!("d0b53D9277642d899DF5C87A3966A349A798F224");
+    const POOL_B: Address = address!("cDAC0d6c6C59727a65F871236188350531885C43");
+    const ROUTER: Address = address!("6fF5693b99212Da76ad316178A184AB56D299b43");
+    const SENDER: Address = address!("2222222222222222222222222222222222222222");
+
+    fn synced_state(config: &PoolConfig, block: u64) -> SharedPoolState {
+        let state: SharedPoolState = Arc::new(ArcSwap::from_pointee(PoolState::default()));
+        apply_pool_read(config, &state, get_sqrt_ratio_at_tick(TEST_TICK), TEST_TICK, TEST_LIQ, None, block, false)
+            .expect("valid read must commit");
+        state
+    }
+
+    fn trigger() -> OptimisticTrigger {
+        OptimisticTrigger {
+            tx_hash: b256!("00000000000000000000000000000000000000000000000000000000deadbeef"),
+            sender: SENDER,
+            observed_at: Instant::now(),
+        }
+    }
+
+    /// Aggregator multicall: A iki kez ABI word'ü olarak, B paketli yolda hizasız
+    fn aggregator_calldata() -> Vec<u8> {
+        let mut input = vec![0xac, 0x96, 0x50, 0xd8]; // multicall(bytes[])
+        for addr in [POOL_A, POOL_A] {
+            input.extend_from_slice(&[0u8; 12]);
+            input.extend_from_slice(addr.as_slice());
+        }
+        input.extend_from_slice(&[0x01, 0xf4, 0x00]);
+        input.extend_from_slice(POOL_B.as_slice());
+        input.extend_from_slice(&[0u8; 7]);
+        input
+    }
+
+    #[test]
+    fn test_direct_pool_calls() {
+        let pools = [POOL_A, POOL_B];
+        let mut swap = SWAP_SELECTOR.to_vec();
+        swap.extend_from_slice(&[0u8; 32]);
+        assert_eq!(check_pending_tx_relevance(Some(POOL_B), &swap, &pools), vec![1]);
+
+        // mint() havuza gider ama fiyatı oynatmaz
+        let mint = [0x3c, 0x8a, 0x7d, 0x8d, 0x00];
+        assert!(check_pending_tx_relevance(Some(POOL_A), &mint, &pools).is_empty());
+        assert!(check_pending_tx_relevance(Some(POOL_A), &SWAP_SELECTOR[..3], &pools).is_empty());
+        assert!(check_pending_tx_relevance(None, &swap, &pools).is_empty());
+    }
+
+    #[test]
+    fn test_aggregator_tx_matches_each_pool_once() {
+        let pools = [POOL_A, POOL_B];
+        let input = aggregator_calldata();
+        assert_eq!(check_pending_tx_relevance(Some(ROUTER), &input, &pools), vec![0, 1]);
+        assert_eq!(check_pending_tx_relevance(Some(ROUTER), &input, &[POOL_B]), vec![0]);
+
+        // Kesik calldata'da B yok; adres içermeyen router çağrısı → boş
+        assert!(check_pending_tx_relevance(Some(ROUTER), &input[..40], &[POOL_B]).is_empty());
+        assert!(check_pending_tx_relevance(Some(ROUTER), &[0xac, 0x96, 0x50, 0xd8], &pools).is_empty());
+    }
+
+    #[test]
+    fn test_latest_known_block_across_pools() {
+        let states = [
+            synced_state(&PoolConfig { address: POOL_A, ..test_pool_config("opt-a") }, 105),
+            synced_state(&PoolConfig { address: POOL_B, ..test_pool_config("opt-b") }, 100),
+        ];
+        assert_eq!(latest_known_block(&states), 105);
+        assert_eq!(latest_known_block(&[]), 0);
+    }
+
+    /// Pending TX → relevance → her etkilenen havuza bir iyimser yazım.
+    /// Geride kalan havuz (B, blok 100) en güncel blokla etiketlenir.
+    #[test]
+    fn test_multi_pool_refresh_records_trigger() {
+        let pools = [
+            PoolConfig { address: POOL_A, ..test_pool_config("opt-a") },
+            PoolConfig { address: POOL_B, ..test_pool_config("opt-b") },
+        ];
+        let states = [synced_state(&pools[0], 105), synced_state(&pools[1], 100)];
+        let addrs = [POOL_A, POOL_B];
+        let trig = trigger();
+
+        let affected = check_pending_tx_relevance(Some(ROUTER), &aggregator_calldata(), &addrs);
+        let current_block = latest_known_block(&states);
+        let new_tick = TEST_TICK + 20;
+        for &idx in &affected {
+            let old_price = states[idx].load().eth_price_usd;
+            let update = apply_optimistic_read(
+                &pools[idx],
+                &states[idx],
+                get_sqrt_ratio_at_tick(new_tick),
+                new_tick,
+                TEST_LIQ,
+                current_block,
+                trig,
+            )
+            .expect("valid read")
+            .expect("price moved");
+            assert_eq!(update.pool_address, addrs[idx]);
+            assert_eq!(update.old_price, old_price);
+            assert_eq!(update.block, 105);
+            assert_eq!(update.trigger.tx_hash, trig.tx_hash);
+
+            let st = states[idx].load();
+            assert_eq!(st.tick, new_tick);
+            assert_eq!(st.last_block, 105);
+            assert_eq!(st.optimistic_trigger, Some(trig));
+            assert_eq!(st.eth_price_usd, update.new_price);
+        }
+        assert_eq!(affected.len(), 2);
+
+        // Aynı okuma tekrar → fiyat değişmedi, yazım yok
+        let again = apply_optimistic_read(
+            &pools[0], &states[0], get_sqrt_ratio_at_tick(new_tick), new_tick, TEST_LIQ, 106, trigger(),
+        )
+        .expect("valid read");
+        assert!(again.is_none());
+        assert_eq!(states[0].load().optimistic_trigger, Some(trig));
+
+        // Onaylı blok sync iz bilgisini temizler
+        apply_pool_read(&pools[0], &states[0], get_sqrt_ratio_at_tick(TEST_TICK), TEST_TICK, TEST_LIQ, None, 107, false)
+            .expect("valid read must commit");
+        assert_eq!(states[0].load().optimistic_trigger, None);
+    }
+
+    /// swap(recipient, zeroForOne, amountSpecified, sqrtPriceLimitX96, "")
+    fn swap_calldata(zero_for_one: bool, amount_specified: I256, limit: U256) -> Vec<u8> {
+        let mut input = SWAP_SELECTOR.to_vec();
+        input.extend_from_slice(&[0u8; 12]);
+        input.extend_from_slice(SENDER.as_slice());
+        input.extend_from_slice(&U256::from(zero_for_one as u8).to_be_bytes::<32>());
+        input.extend_from_slice(&amount_specified.into_raw().to_be_bytes::<32>());
+        input.extend_from_slice(&limit.to_be_bytes::<32>());
+        input.extend_from_slice(&U256::from(160u64).to_be_bytes::<32>()); // data offset
+        input.extend_from_slice(&[0u8; 32]); // data uzunluğu 0
+        input
+    }
+
+    /// Başlatılmış tick'leri ±30 aralıkta olan havuz (tick geçişleri sayılır)
+    fn state_with_bitmap(config: &PoolConfig, block: u64) -> SharedPoolState {
+        let state = synced_state(config, block);
+        let ticks: HashMap<_, _> = (-30..=30)
+            .map(|i| {
+                let info = TickInfo { liquidity_gross: 1, liquidity_net: 0, initialized: true, staked_liquidity_net: 0 };
+                (TEST_TICK + i * 10, info)
+            })
+            .collect();
+        state.rcu(|old| PoolState {
+            tick_bitmap: Some(Box::new(TickBitmapData { ticks: ticks.clone(), ..TickBitmapData::empty() })),
+            ..(**old).clone()
+        });
+        state
+    }
+
+    /// Pending swap calldata'sı çözülür ve yerel olarak uygulanır: WETH
+    /// satışı fiyatı düşürür, WETH alımı yükseltir; onaylı alanlar değişmez
+    #[test]
+    fn test_pending_swap_predicts_price_direction() {
+        let config = PoolConfig { address: POOL_A, ..test_pool_config("opt-a") };
+        let weth_in = I256::from_raw(U256::from(200_000_000_000_000_000_000u128)); // 200 WETH
+        let usdc_in = I256::from_raw(U256::from(600_000_000_000u64)); // 600k USDC
+        for (zero_for_one, amount, limit) in [
+            (true, weth_in, crate::math::exact::MIN_SQRT_RATIO_PLUS_1),
+            (false, usdc_in, crate::math::exact::MAX_SQRT_RATIO_MINUS_1),
+        ] {
+            let state = state_with_bitmap(&config, 100);
+            let confirmed = state.load_full();
+            let input = swap_calldata(zero_for_one, amount, limit);
+            assert_eq!(check_pending_tx_relevance(Some(POOL_A), &input, &[POOL_A]), vec![0]);
+            let swap = decode_pending_swap(&input).expect("swap calldata");
+            assert_eq!(swap, PendingSwap { zero_for_one, amount_specified: amount, sqrt_price_limit_x96: limit });
+
+            let update = apply_pending_swap(&config, &state, swap, trigger()).expect("exact input swap");
+            let st = state.load_full();
+            let predicted = st.predicted_state.as_deref().copied().expect("prediction stored");
+            if zero_for_one {
+                assert!(predicted.eth_price_usd < confirmed.eth_price_usd);
+                assert!(predicted.tick < TEST_TICK - 10, "çok tick: {}", predicted.tick);
+            } else {
+                assert!(predicted.eth_price_usd > confirmed.eth_price_usd);
+                assert!(predicted.tick > TEST_TICK + 10, "çok tick: {}", predicted.tick);
+            }
+            assert_eq!((update.old_price, update.new_price), (confirmed.eth_price_usd, predicted.eth_price_usd));
+            assert_eq!((st.tick, st.sqrt_price_x96, st.last_block), (TEST_TICK, confirmed.sqrt_price_x96, 100));
+
+            // Güven penceresinde tahmin okunur; pencere dışında ya da onaylı yazımdan sonra onaylı state
+            assert_eq!(Arc::clone(&st).with_prediction(101, 1).tick, predicted.tick);
+            assert_eq!(Arc::clone(&st).with_prediction(102, 1).tick, TEST_TICK);
+            apply_pool_read(&config, &state, get_sqrt_ratio_at_tick(TEST_TICK), TEST_TICK, TEST_LIQ, None, 101, false)
+                .expect("valid read must commit");
+            assert_eq!(state.load_full().with_prediction(101, 1).tick, TEST_TICK);
+        }
+    }
+
+    /// Fiyat limiti swap'ı durdurur; zincirlenen tahmin öncekinin üstüne kurulur;
+    /// exact output ve kesik calldata modellenmez
+    #[test]
+    fn test_pending_swap_limit_chain_and_unsupported() {
+        let config = PoolConfig { address: POOL_A, ..test_pool_config("opt-a") };
+        let state = state_with_bitmap(&config, 100);
+        let weth = |amount: u128| I256::from_raw(U256::from(amount));
+
+        let limit = get_sqrt_ratio_at_tick(TEST_TICK - 35);
+        let input = swap_calldata(true, weth(200_000_000_000_000_000_000), limit);
+        apply_pending_swap(&config, &state, decode_pending_swap(&input).unwrap(), trigger()).unwrap();
+        let first = state.load().predicted_state.as_deref().copied().unwrap();
+        assert_eq!(first.sqrt_price_x96, limit);
+        assert_eq!(first.tick, TEST_TICK - 36);
+
+        let input = swap_calldata(true, weth(1_000_000_000_000_000_000), crate::math::exact::MIN_SQRT_RATIO_PLUS_1);
+        let update = apply_pending_swap(&config, &state, decode_pending_swap(&input).unwrap(), trigger()).unwrap();
+        assert_eq!(update.old_price, first.eth_price_usd);
+        assert!(state.load().predicted_state.as_ref().unwrap().sqrt_price_x96 < limit);
+
+        let exact_out = swap_calldata(true, -weth(1_000_000), U256::ZERO);
+        assert!(apply_pending_swap(&config, &state, decode_pending_swap(&exact_out).unwrap(), trigger()).is_none());
+        assert!(decode_pending_swap(&input[..131]).is_none());
+        let mut mint = input.clone();
+        mint[0..4].copy_from_slice(&[0x3c, 0x8a, 0x7d, 0x8d]);
+        assert!(decode_pending_swap(&mint).is_none());
+    }
+
+    /// Limitte kesilen swap'ın likiditesi limit tarafındaki aralıktan gelir —
+    /// motorun limit ötesinde geçtiği başlatılmış tick sayılmaz
+    #[test]
+    fn test_pending_swap_limit_restores_liquidity_past_limit() {
+        let config = PoolConfig { address: POOL_A, ..test_pool_config("opt-a") };
+        let state = synced_state(&config, 100);
+        let info = |liquidity_net: i128| TickInfo {
+            liquidity_gross: liquidity_net.unsigned_abs(),
+            liquidity_net,
+            initialized: true,
+            staked_liquidity_net: 0,
+        };
+        let half = (TEST_LIQ / 2) as i128;
+        let ticks: HashMap<i32, TickInfo> = [(TEST_TICK - 10, info(half)), (TEST_TICK - 20, info(half / 2))].into();
+        state.rcu(|old| PoolState {
+            tick_bitmap: Some(Box::new(TickBitmapData { ticks: ticks.clone(), ..TickBitmapData::empty() })),
+            ..(**old).clone()
+        });
+
+        let amount = I256::from_raw(U256::from(200_000_000_000_000_000_000u128));
+        let unlimited = crate::math::exact::advance_pool_state(&state.load(), &config, amount.into_raw(), true).0;
+        assert!(unlimited.tick < TEST_TICK - 20, "motor limit ötesine geçmeli: {}", unlimited.tick);
+        assert_eq!(unlimited.liquidity, TEST_LIQ - TEST_LIQ / 2 - TEST_LIQ / 4);
+
+        let limit = get_sqrt_ratio_at_tick(TEST_TICK - 15);
+        let input = swap_calldata(true, amount, limit);
+        apply_pending_swap(&config, &state, decode_pending_swap(&input).unwrap(), trigger()).unwrap();
+        let predicted = state.load().predicted_state.as_deref().copied().unwrap();
+        assert_eq!((predicted.sqrt_price_x96, predicted.tick), (limit, TEST_TICK - 16));
+        assert_eq!(predicted.liquidity, TEST_LIQ - TEST_LIQ / 2);
+    }
+
+    #[test]
+    fn test_optimistic_update_log_lines() {
+        let path = std::env::temp_dir().join(format!(
+            "optimistic_updates_{}_{}.jsonl",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        let _ = std::fs::remove_file(&path);
+        let update = OptimisticUpdate {
+            pool: "opt-test".into(),
+            pool_address: POOL_A,
+            old_price: 2780.0,
+            new_price: 2785.5,
+            block: 105,
+            trigger: trigger(),
+            latency: std::time::Duration::from_micros(1500),
+        };
+        append_optimistic_update(&path, &update).expect("append");
+        append_optimistic_update(&path, &update).expect("append");
+
+        let text = std::fs::read_to_string(&path).expect("read back");
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("each line is JSON"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["pool"], "opt-test");
+        assert_eq!(lines[0]["new_price"], 2785.5);
+        assert_eq!(lines[0]["latency_us"], 1500);
+        assert_eq!(
+            lines[0]["trigger_tx"],
+            "0x00000000000000000000000000000000000000000000000000000000deadbeef"
+        );
+        assert_eq!(lines[0]["trigger_sender"], format!("{:?}", SENDER));
+    }
+}
+
+#[cfg(test)]
+mod error_mapping_tests {
+    use super::*;
+    use crate::math::exact::get_sqrt_ratio_at_tick;
+    use alloy::primitives::B256;
+    use arc_swap::ArcSwap;
+
+    /// Bozuk iyimser okuma → PoolDataInvalid (tekrar denenmez), state korunur
+    #[test]
+    fn test_garbage_pool_read_maps_to_pool_data_invalid() {
+        let config = test_pool_config("err-test");
+        let state: SharedPoolState = Arc::new(ArcSwap::from_pointee(PoolState::default()));
+        apply_pool_read(&config, &state, get_sqrt_ratio_at_tick(TEST_TICK), TEST_TICK, TEST_LIQ, None, 100, false)
+            .expect("valid read must commit");
+
+        let trigger = OptimisticTrigger {
+            tx_hash: B256::ZERO,
+            sender: Address::ZERO,
+            observed_at: Instant::now(),
+        };
+        let err = apply_optimistic_read(&config, &state, U256::ZERO, TEST_TICK + 20, TEST_LIQ, 101, trigger)
+            .expect_err("zero sqrtPrice must be rejected");
+        match &err {
+            BotError::PoolDataInvalid { pool, .. } => assert_eq!(pool, "err-test"),
+            other => panic!("expected PoolDataInvalid, got {:?}", other),
+        }
+        assert!(!err.is_retryable());
+        assert!(err.to_string().starts_with("[err-test] invalid pool data: "), "{}", err);
+        assert_eq!(state.load().tick, TEST_TICK);
+        assert_eq!(state.load().optimistic_trigger, None);
+    }
+
+    #[test]
+    fn test_sync_timeout_is_retryable_and_readable() {
+        let err = sync_timeout(&test_pool_config("err-test"));
+        assert_eq!(err.kind(), "rpc_timeout");
+        assert!(err.is_retryable());
+        assert_eq!(err.to_string(), "[err-test] sync_pool_state: RPC timeout (sync, 3000ms)");
+    }
+
+    #[test]
+    fn test_sync_outcome_collects_failed_indices() {
+        let results: Vec<BotResult<()>> = vec![
+            Ok(()),
+            Err(BotError::AbiDecode { context: "[b] Multicall3".into(), what: "slot0".into() }),
+            Ok(()),
+            Err(sync_timeout(&test_pool_config("err-test"))),
+        ];
+        match sync_outcome(&results) {
+            Err(BotError::MulticallPartialFailure { failed_indices }) => {
+                assert_eq!(failed_indices, vec![1, 3])
+            }
+            other => panic!("expected MulticallPartialFailure, got {:?}", other),
+        }
+        assert!(sync_outcome(&[Ok(()), Ok(())]).is_ok());
+        assert!(sync_outcome(&[]).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod retry_tests {
+    use super::*;
+    use alloy::transports::TransportErrorKind;
+    use std::cell::Cell;
+
+    /// İlk `failures` çağrıda `err` döndüren, sonra başarılı olan sahte çağrı
+    fn flaky_call<'a>(
+        attempts: &'a Cell<u32>,
+        failures: u32,
+        err: fn() -> BotError,
+    ) -> impl FnMut() -> std::future::Ready<BotResult<u64>> + 'a {
+        move || {
+            attempts.set(attempts.get() + 1);
+            std::future::ready(if attempts.get() <= failures { Err(err()) } else { Ok(42) })
+        }
+    }
+
+    fn transport_blip() -> BotError {
+        BotError::from_transport("[pool-a] Multicall3 tickBitmap", TransportErrorKind::backend_gone())
+    }
+
+    #[tokio::test]
+    async fn test_transport_error_retried_once_then_succeeds() {
+        let attempts = Cell::new(0);
+        let retries_before = rpc_retries();
+        let budget = CallBudget::new("bitmap", 1_000);
+
+        let call = flaky_call(&attempts, 1, transport_blip);
+        let result = retry_transient("[pool-a] Multicall3 tickBitmap", &budget, 2, call).await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.get(), 2, "exactly one retry");
+        assert!(rpc_retries() > retries_before);
+    }
+
+    #[tokio::test]
+    async fn test_decode_error_is_not_retried() {
+        let attempts = Cell::new(0);
+        let budget = CallBudget::new("bitmap", 1_000);
+        let decode = || BotError::AbiDecode { context: "[pool-a] Multicall3".into(), what: "slot0".into() };
+
+        let call = flaky_call(&attempts, 1, decode);
+        let err = retry_transient("[pool-a] Multicall3", &budget, 2, call).await.unwrap_err();
+
+        assert_eq!(err.kind(), "abi_decode");
+        assert_eq!(attempts.get(), 1, "decode errors must not be retried");
+    }
+
+    /// Yeniden denemeler kategori bütçesini aşamaz: yanıt vermeyen çağrı
+    /// bütçe dolunca RpcTimeout ile döner
+    #[tokio::test]
+    async fn test_retries_bounded_by_category_budget() {
+        let budget = CallBudget::new("bitmap", 40);
+        let started = Instant::now();
+
+        let hung = std::future::pending::<BotResult<()>>;
+        let err = retry_transient("[pool-a] Multicall3", &budget, 5, hung).await.unwrap_err();
+
+        assert_eq!(err.to_string(), "[pool-a] Multicall3: RPC timeout (bitmap, 40ms)");
+        assert!(started.elapsed() < std::time::Duration::from_millis(500));
+
+        // Sürekli taşıma hatası: sınır kadar deneme, sonra son hata
+        let attempts = Cell::new(0);
+        let budget = CallBudget::new("bitmap", 1_000);
+        let call = flaky_call(&attempts, u32::MAX, transport_blip);
+        let err = retry_transient("[pool-a] Multicall3", &budget, 2, call).await.unwrap_err();
+        assert_eq!(err.kind(), "rpc_transport");
+        assert_eq!(attempts.get(), 3);
+    }
+}
+
+#[cfg(test)]
+mod data_quality_tests {
+    use super::{
+        apply_pool_read, commit_validated, data_quality_errors, DATA_QUALITY_QUARANTINE_AFTER, test_pool_config,
+        TEST_LIQ, TEST_TICK,
+    };
+    use crate::math::exact::get_sqrt_ratio_at_tick;
+    use crate::types::*;
+    use alloy::primitives::U256;
+    use arc_swap::ArcSwap;
+    use std::sync::Arc;
+
+    /// RPC katmanı olmadan sync: okunan ham değerler doğrudan yazım yoluna verilir
+    fn mock_sync(
+        state: &SharedPoolState,
+        sqrt_price_x96: U256,
+        tick: i32,
+        liquidity: u128,
+    ) -> Result<(), DataQualityIssue> {
+        apply_pool_read(&test_pool_config("dq-test"), state, sqrt_price_x96, tick, liquidity, None, 200, true)
+    }
+
+    fn synced_state() -> SharedPoolState {
+        let state: SharedPoolState = Arc::new(ArcSwap::from_pointee(PoolState::default()));
+        mock_sync(&state, get_sqrt_ratio_at_tick(TEST_TICK), TEST_TICK, TEST_LIQ)
+            .expect("valid read must commit");
+        state
+    }
+
+    #[test]
+    fn test_valid_read_commits() {
+        let state = synced_state();
+        let st = state.load();
+        assert!(st.is_active());
+        assert_eq!(st.tick, TEST_TICK);
+        assert_eq!(st.data_quality_errors, 0);
+        assert!(st.eth_price_usd > 1000.0 && st.eth_price_usd < 5000.0);
+    }
+
+    #[test]
+    fn test_bad_reads_keep_previous_state() {
+        let state = synced_state();
+        let before = state.load_full();
+        let global_before = data_quality_errors();
+        let good_sqrt = get_sqrt_ratio_at_tick(TEST_TICK);
+
+        // (sqrt, tick, liquidity, beklenen alan)
+        let bad_reads = [
+            (U256::ZERO, TEST_TICK, TEST_LIQ, "sqrt_price_f64"),
+            (good_sqrt, 900_000, TEST_LIQ, "tick"),
+            (good_sqrt, TEST_TICK, 0, "liquidity_f64"),
+            // tick 0 → 1 WETH = 1e12 USDC: saçma fiyat
+            (get_sqrt_ratio_at_tick(0), 0, TEST_LIQ, "eth_price_usd"),
+            // slot0.tick sqrtPriceX96 ile çelişiyor
+            (good_sqrt, TEST_TICK + 50, TEST_LIQ, "tick"),
+        ];
+        for (i, (sqrt, tick, liq, field)) in bad_reads.into_iter().enumerate() {
+            let issue = mock_sync(&state, sqrt, tick, liq).expect_err("bad read must be rejected");
+            assert_eq!(issue.field, field, "case {}: {}", i, issue);
+            let st = state.load();
+            assert_eq!(st.sqrt_price_x96, before.sqrt_price_x96);
+            assert_eq!(st.tick, before.tick);
+            assert_eq!(st.eth_price_usd, before.eth_price_usd);
+            assert_eq!(st.last_block, before.last_block);
+        }
+        assert!(data_quality_errors() >= global_before + bad_reads.len() as u64);
+    }
+
+    #[test]
+    fn test_nan_and_inf_f64_copies_rejected() {
+        let state = synced_state();
+        let before = state.load_full();
+        let config = test_pool_config("dq-test");
+
+        type Poison = fn(&mut PoolState);
+        let poison: [(Poison, &str); 4] = [
+            (|s: &mut PoolState| s.sqrt_price_f64 = f64::NAN, "sqrt_price_f64"),
+            (|s: &mut PoolState| s.liquidity_f64 = f64::INFINITY, "liquidity_f64"),
+            (|s: &mut PoolState| s.eth_price_usd = f64::NAN, "eth_price_usd"),
+            // f64 kopya U256 kaynağından %1 sapmış
+            (|s: &mut PoolState| s.sqrt_price_f64 *= 1.01, "sqrt_price_x96"),
+        ];
+        for (update, field) in poison {
+            let issue = commit_validated(&config, &state, update).expect_err("poison must be rejected");
+            assert_eq!(issue.field, field);
+            assert!(state.load().sqrt_price_f64.is_finite());
+            assert_eq!(state.load().sqrt_price_f64, before.sqrt_price_f64);
+        }
+    }
+
+    #[test]
+    fn test_consecutive_failures_quarantine_until_valid_read() {
+        let state = synced_state();
+        for n in 1..=DATA_QUALITY_QUARANTINE_AFTER {
+            assert!(!state.load().quarantined);
+            mock_sync(&state, U256::ZERO, TEST_TICK, TEST_LIQ).unwrap_err();
+            assert_eq!(state.load().data_quality_errors, n);
+        }
+        let st = state.load();
+        assert!(st.quarantined);
+        assert!(!st.is_active(), "quarantined pool must leave the pipeline");
+
+        // Geçerli ilk okuma karantinayı kaldırır ve sayacı sıfırlar
+        mock_sync(&state, get_sqrt_ratio_at_tick(TEST_TICK + 10), TEST_TICK + 10, TEST_LIQ).unwrap();
+        let st = state.load();
+        assert!(!st.quarantined);
+        assert_eq!(st.data_quality_errors, 0);
+        assert!(st.is_active());
+    }
+}
+
+#[cfg(test)]
+mod bitmap_extension_tests {
+    use super::{extension_range, tick_to_word_pos, BITMAP_EXTENSION_WORDS};
+    use crate::types::ScanEdge;
+
+    /// Genişletme kenara bitişik başlar, taranan aralıkla çakışmaz ve
+    /// tam BITMAP_EXTENSION_WORDS word ötesine uzanır
+    #[test]
+    fn test_extension_range_adjacent_to_edge() {
+        let spacing = 10;
+        let (lo, hi) = (-5000, 5000);
+
+        let (up_lo, up_hi) = extension_range(lo, hi, ScanEdge::Upper, spacing);
+        assert_eq!(up_lo, hi + 1);
+        assert_eq!(up_hi - hi, BITMAP_EXTENSION_WORDS * 256 * spacing);
+        // Kenar word'ü yeniden okunur (birleştirmede yeni okuma kazanır)
+        assert_eq!(tick_to_word_pos(up_lo, spacing), tick_to_word_pos(hi, spacing));
+
+        let (down_lo, down_hi) = extension_range(lo, hi, ScanEdge::Lower, spacing);
+        assert_eq!(down_hi, lo - 1);
+        assert_eq!(lo - down_lo, BITMAP_EXTENSION_WORDS * 256 * spacing);
+
+        // Fiyat aralığının uçlarında kırpılır
+        let (_, top) = extension_range(lo, 887_000, ScanEdge::Upper, 60);
+        assert_eq!(top, 887_272);
+    }
+}
+
+#[cfg(test)]
+mod bitmap_delta_tests {
+    use super::{apply_bitmap_delta, bitmap_delta_ticks, bitmap_position, checked_staked_snapshot, compress_tick};
+    use crate::types::{StakedLiquidity, TickBitmapData, TickInfo};
+    use alloy::primitives::U256;
+    use std::collections::HashMap;
+
+    const SPACING: i32 = 10;
+
+    fn info(liquidity_net: i128) -> TickInfo {
+        TickInfo {
+            liquidity_gross: liquidity_net.unsigned_abs(),
+            liquidity_net,
+            initialized: true,
+            staked_liquidity_net: 0,
+        }
+    }
+
+    /// Verilen tick'lerin bitlerini set eden word haritası
+    fn words_for(ticks: &[i32]) -> HashMap<i16, U256> {
+        let mut words: HashMap<i16, U256> = HashMap::new();
+        for &tick in ticks {
+            let (word_pos, bit) = bitmap_position(compress_tick(tick, SPACING));
+            let word = words.entry(word_pos).or_insert(U256::ZERO);
+            *word |= U256::from(1u8) << bit as usize;
+        }
+        words
+    }
+
+    fn previous(ticks: &[i32], scanned: (i32, i32)) -> TickBitmapData {
+        let mut bitmap = TickBitmapData::empty();
+        bitmap.words = words_for(ticks);
+        bitmap.ticks = ticks.iter().map(|&t| (t, info(1_000))).collect();
+        bitmap.scanned_ticks = Some(scanned);
+        bitmap
+    }
+
+    /// Değişmeyen word'lerin tick'leri yeniden okunmaz; değişen word'deki
+    /// ve pencereye yeni giren tick'ler okunur
+    #[test]
+    fn test_delta_reads_only_changed_words_and_new_range() {
+        // word 0: tick 100, 200 (değişmez) — word 1: tick 2600 (+2700 eklenir)
+        let prev = previous(&[100, 200, 2600], (-500, 3000));
+        // Pencere yukarı kaydı: 3000..4000 yeni; 3500 orada başlatılmış
+        let now = words_for(&[100, 200, 2600, 2700, 3500]);
+
+        let stale = bitmap_delta_ticks(&prev, &now, (0, 4000), SPACING, "test");
+        assert_eq!(stale, vec![2600, 2700, 3500]);
+    }
+
+    /// Birleştirme: okunan tick'ler yenilenir, pencere dışına düşen ve biti
+    /// sönen tick'ler silinir
+    #[test]
+    fn test_apply_delta_refreshes_and_evicts() {
+        let mut bitmap = previous(&[-300, 100, 200, 2600], (-500, 3000));
+        // -300 pencere dışında kalır, 200'ün biti söner, 2600 yeniden okunur
+        let now = words_for(&[-300, 100, 2600]);
+        let refreshed = [2600];
+        let infos: HashMap<i32, TickInfo> = [(2600, info(-7))].into_iter().collect();
+
+        let evicted = apply_bitmap_delta(&mut bitmap, now, &refreshed, infos, (0, 4000), SPACING);
+
+        assert_eq!(evicted, 2);
+        let mut kept: Vec<i32> = bitmap.ticks.keys().copied().collect();
+        kept.sort_unstable();
+        assert_eq!(kept, vec![100, 2600]);
+        assert_eq!(bitmap.ticks[&2600].liquidity_net, -7);
+        assert_eq!(bitmap.ticks[&100].liquidity_net, 1_000);
+        assert_eq!(bitmap.scanned_ticks, Some((0, 4000)));
+    }
+
+    /// Delta turunda okunan staked görüntüsü eskisinin yerine geçer ve
+    /// birleşmiş haritayla doğrulanır; okunamazsa bayat görüntü düşer
+    #[test]
+    fn test_delta_refreshes_staked_snapshot() {
+        let mut bitmap = previous(&[100, 2600], (-500, 3000));
+        let stale = StakedLiquidity {
+            tick: 500,
+            liquidity: 5_000,
+            staked_liquidity: 1_000,
+            unstaked_fee_pips: Some(100_000),
+            verified: true,
+        };
+        bitmap.staked = Some(stale);
+
+        // 2600'de stake edilmiş pozisyon açıldı: yukarıda staked 3_000'e çıkar
+        let now = words_for(&[100, 2600]);
+        let staked_mint = TickInfo { staked_liquidity_net: 2_000, ..info(2_000) };
+        let infos: HashMap<i32, TickInfo> = [(2600, staked_mint)].into_iter().collect();
+        apply_bitmap_delta(&mut bitmap, now, &[2600], infos, (0, 4000), SPACING);
+
+        let fresh = StakedLiquidity { tick: 2700, liquidity: 7_000, staked_liquidity: 3_000, verified: false, ..stale };
+        let checked = checked_staked_snapshot(&bitmap, Some(fresh), "test").expect("fresh snapshot kept");
+        assert_eq!((checked.tick, checked.staked_liquidity, checked.verified), (2700, 3_000, true));
+
+        // Haritayla çelişen görüntü tutulur ama doğrulanmış sayılmaz
+        let inconsistent = StakedLiquidity { staked_liquidity: 0, ..fresh };
+        assert!(!checked_staked_snapshot(&bitmap, Some(inconsistent), "test").unwrap().verified);
+        assert_eq!(checked_staked_snapshot(&bitmap, None, "test"), None);
+    }
+}
+
+#[cfg(test)]
+mod bitmap_position_tests {
+    use super::{extract_initialized_bits, retain_roundtrip_ticks, tick_to_word_pos};
+    use crate::math::exact::{MAX_TICK, MIN_TICK};
+    use alloy::primitives::U256;
+
+    const SPACINGS: [i32; 4] = [1, 10, 60, 200];
+
+    /// Referans: Solidity TickBitmap.position() + nextInitializedTickWithinOneWord
+    /// sıkıştırması, Solidity'nin sıfıra doğru kesen `/` ve `%` semantiğiyle
+    fn solidity_position(tick: i32, spacing: i32) -> (i16, u8) {
+        let mut compressed = tick / spacing;
+        if tick < 0 && tick % spacing != 0 {
+            compressed -= 1;
+        }
+        let word_pos = (compressed >> 8) as i16;
+        let bit_pos = (compressed % 256) as u32 as u8; // uint8(uint24(tick % 256))
+        (word_pos, bit_pos)
+    }
+
+    /// Sınanan word'ler: sıfır çevresi, WETH/USDC bölgesi (≈ −200k), simetriği
+    /// ve fiyat aralığının uçları
+    fn word_ranges(spacing: i32) -> Vec<i16> {
+        let around = |tick: i32| {
+            let w = solidity_position(tick, spacing).0;
+            [w - 1, w, w + 1]
+        };
+        let mut words: Vec<i16> = [-200_000, 0, 200_000, MIN_TICK, MAX_TICK]
+            .into_iter()
+            .flat_map(around)
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+        words
+    }
+
+    /// Word aralıklarındaki her tick (spacing katı olmayanlar dahil) referansla
+    /// aynı word'e düşer
+    #[test]
+    fn test_word_pos_matches_solidity_for_every_tick() {
+        for spacing in SPACINGS {
+            for word_pos in word_ranges(spacing) {
+                let first = (word_pos as i32) * 256 * spacing;
+                for tick in first..first + 256 * spacing {
+                    assert_eq!(
+                        tick_to_word_pos(tick, spacing),
+                        solidity_position(tick, spacing).0,
+                        "tick {} spacing {}",
+                        tick,
+                        spacing,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Her bit tek başına çıkarılınca referansın aynı (word, bit)'ine geri
+    /// eşlenen, spacing katı bir tick verir — word sınırları dahil
+    #[test]
+    fn test_extracted_ticks_round_trip_to_word_and_bit() {
+        for spacing in SPACINGS {
+            for word_pos in word_ranges(spacing) {
+                for bit in 0..256usize {
+                    let ticks = extract_initialized_bits(U256::from(1u64) << bit, word_pos, spacing);
+                    assert_eq!(ticks.len(), 1);
+                    let tick = ticks[0];
+                    assert_eq!(tick % spacing, 0, "tick {} spacing {}", tick, spacing);
+                    assert_eq!(
+                        solidity_position(tick, spacing),
+                        (word_pos, bit as u8),
+                        "word {} bit {} spacing {}",
+                        word_pos,
+                        bit,
+                        spacing,
+                    );
+                    assert_eq!(tick_to_word_pos(tick, spacing), word_pos);
+                }
+            }
+        }
+    }
+
+    /// Dolu word: 256 ardışık tick, ilki word'ün alt sınırı; sync değişmezi
+    /// hiçbirini düşürmez, başka word'ün tick'ini düşürür
+    #[test]
+    fn test_full_negative_word_and_roundtrip_invariant() {
+        let spacing = 10;
+        let word_pos = tick_to_word_pos(-200_000, spacing);
+        let mut ticks = extract_initialized_bits(U256::MAX, word_pos, spacing);
+        assert_eq!(ticks.len(), 256);
+        assert_eq!(ticks[0], (word_pos as i32) * 256 * spacing);
+        assert!(ticks.windows(2).all(|w| w[1] - w[0] == spacing));
+        assert_eq!(tick_to_word_pos(ticks[255] + spacing, spacing), word_pos + 1);
+        assert_eq!(retain_roundtrip_ticks(&mut ticks, word_pos, spacing, "test"), 0);
+
+        let mut foreign = vec![ticks[0], ticks[0] - spacing, ticks[3] + 1];
+        assert_eq!(retain_roundtrip_ticks(&mut foreign, word_pos, spacing, "test"), 2);
+        assert_eq!(foreign, vec![ticks[0]]);
+    }
+}
+
+#[cfg(test)]
+mod slipstream_tick_tests {
+    use super::{decode_staked_snapshot, decode_ticks_result, IAerodromePool, IMulticall3};
+    use crate::math::exact::get_sqrt_ratio_at_tick;
+    use alloy::primitives::{hex, Bytes, Signed, U160, U256};
+    use alloy::sol_types::SolCall;
+
+    /// Slipstream CLPool.ticks() 10 alanlı yanıt düzeni (320 byte), word word:
+    /// liquidityGross, liquidityNet (-1e18), feeGrowthOutside0/1, tickCumulativeOutside
+    /// (negatif), secondsPerLiquidityOutside, secondsOutside, initialized,
+    /// stakedLiquidityNet (-4e17), rewardGrowthOutside
+    const SLIPSTREAM_TICKS_RETURN: &str = concat!(
+        "000000000000000000000000000000000000000000000000112210f47de98115",
+        "fffffffffffffffffffffffffffffffffffffffffffffffff21f494c589c0000",
+        "000000000000000000000000000000000000000000000000000000000000001f",
+        "000000000000000000000000000000000000000000000000000000000000002e",
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffff8a432eb",
+        "000000000000000000000000000000000000000000000000000000000000003d",
+        "000000000000000000000000000000000000000000000000000000006553f100",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "fffffffffffffffffffffffffffffffffffffffffffffffffa72ea1e89d80000",
+        "000000000000000000000000000000000000000000000000000000000000004c",
+    );
+
+    fn ok(data: Vec<u8>) -> IMulticall3::Result {
+        IMulticall3::Result { success: true, returnData: Bytes::from(data) }
+    }
+
+    fn word(value: u128) -> Vec<u8> {
+        U256::from(value).to_be_bytes::<32>().to_vec()
+    }
+
+    #[test]
+    fn test_decode_slipstream_ticks_fixture() {
+        let data = hex::decode(SLIPSTREAM_TICKS_RETURN).unwrap();
+        assert_eq!(data.len(), 320);
+        let info = decode_ticks_result(&data).expect("320-byte layout must decode");
+        assert_eq!(info.liquidity_gross, 1_234_567_890_123_456_789);
+        assert_eq!(info.liquidity_net, -1_000_000_000_000_000_000);
+        assert_eq!(info.staked_liquidity_net, -400_000_000_000_000_000);
+        assert!(info.initialized);
+
+        // UniV3/PCS: 8 alan — staked pay yok; kısa yanıt reddedilir
+        let univ3 = decode_ticks_result(&data[..256]).unwrap();
+        assert_eq!(univ3.liquidity_net, info.liquidity_net);
+        assert_eq!(univ3.staked_liquidity_net, 0);
+        assert!(decode_ticks_result(&data[..255]).is_none());
+    }
+
+    /// sol! arayüzünün kodladığı yanıt aynı offset'lerden çözülür
+    #[test]
+    fn test_decode_matches_aerodrome_abi_encoding() {
+        let ret = IAerodromePool::ticksReturn {
+            liquidityGross: 9_000,
+            liquidityNet: 6_500,
+            feeGrowthOutside0X128: U256::MAX,
+            feeGrowthOutside1X128: U256::MAX,
+            tickCumulativeOutside: Signed::try_from(-77i64).unwrap(),
+            secondsPerLiquidityOutsideX128: U160::MAX,
+            secondsOutside: u32::MAX,
+            initialized: true,
+            stakedLiquidityNet: -2_500,
+            rewardGrowthOutsideX128: U256::MAX,
+        };
+        let data = IAerodromePool::ticksCall::abi_encode_returns(&ret);
+        let info = decode_ticks_result(&data).unwrap();
+        assert_eq!((info.liquidity_gross, info.liquidity_net), (9_000, 6_500));
+        assert_eq!(info.staked_liquidity_net, -2_500);
+        assert!(info.initialized);
+    }
+
+    #[test]
+    fn test_staked_snapshot_decoded_from_appended_calls() {
+        const TICK: i32 = -195_010;
+        let mut slot0 = get_sqrt_ratio_at_tick(TICK).to_be_bytes::<32>().to_vec();
+        let mut tick_word = [0xff; 32];
+        tick_word[28..].copy_from_slice(&TICK.to_be_bytes());
+        slot0.extend_from_slice(&tick_word);
+        slot0.extend_from_slice(&[0u8; 4 * 32]);
+
+        let results = vec![ok(slot0), ok(word(5_000)), ok(word(2_000)), ok(word(100_000))];
+        let snapshot = decode_staked_snapshot(&results).expect("snapshot must decode");
+        assert_eq!((snapshot.tick, snapshot.liquidity, snapshot.staked_liquidity), (TICK, 5_000, 2_000));
+        assert_eq!(snapshot.unstaked_fee_pips, Some(100_000));
+        assert!(!snapshot.verified, "verification is the caller's job");
+
+        // unstakedFee okunamadı: görüntü yine geçerli
+        let mut no_fee = results.clone();
+        no_fee[3].success = false;
+        assert_eq!(decode_staked_snapshot(&no_fee).unwrap().unstaked_fee_pips, None);
+
+        // stakedLiquidity okunamadı ya da ek çağrı yok: görüntü yok
+        let mut no_staked = results.clone();
+        no_staked[2].success = false;
+        assert!(decode_staked_snapshot(&no_staked).is_none());
+        assert!(decode_staked_snapshot(&results[..3]).is_none());
+        assert!(decode_staked_snapshot(&[]).is_none());
+    }
+}
+
+#[cfg(test)]
+mod block_pin_tests {
+    use super::{
+        sync_all_pools_multicall, sync_pool_state, unpinned_syncs, slot0_return, test_pool_config, word_i32,
+        TEST_LIQ, TEST_TICK,
+    };
+    use crate::types::*;
+    use alloy::eips::BlockId;
+    use alloy::network::Ethereum;
+    use alloy::primitives::{b256, Bytes, B256, U256};
+    use alloy::providers::{
+        Caller, EthCall, EthCallManyParams, EthCallParams, Provider, ProviderBuilder, ProviderCall,
+        RootProvider,
+    };
+    use alloy::rpc::types::TransactionRequest;
+    use alloy::transports::mock::Asserter;
+    use alloy::transports::TransportResult;
+    use arc_swap::ArcSwap;
+    use std::sync::{Arc, Mutex};
+
+    const HEAD_HASH: B256 = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+
+    type Recorded = Arc<Mutex<Vec<Option<BlockId>>>>;
+
+    /// Mock provider: her eth_call'un taşıdığı BlockId'yi kaydeder
+    struct RecordingProvider {
+        inner: RootProvider,
+        blocks: Recorded,
+    }
+
+    struct RecordingCaller {
+        inner: RootProvider,
+        blocks: Recorded,
+    }
+
+    impl Caller<Ethereum, Bytes> for RecordingCaller {
+        fn call(
+            &self,
+            params: EthCallParams<Ethereum>,
+        ) -> TransportResult<ProviderCall<EthCallParams<Ethereum>, Bytes>> {
+            self.blocks.lock().unwrap().push(params.block());
+            Caller::<Ethereum, Bytes>::call(&self.inner.weak_client(), params)
+        }
+
+        fn estimate_gas(
+            &self,
+            params: EthCallParams<Ethereum>,
+        ) -> TransportResult<ProviderCall<EthCallParams<Ethereum>, Bytes>> {
+            Caller::<Ethereum, Bytes>::estimate_gas(&self.inner.weak_client(), params)
+        }
+
+        fn call_many(
+            &self,
+            params: EthCallManyParams<'_>,
+        ) -> TransportResult<ProviderCall<EthCallManyParams<'static>, Bytes>> {
+            Caller::<Ethereum, Bytes>::call_many(&self.inner.weak_client(), params)
+        }
+    }
+
+    impl Provider for RecordingProvider {
+        fn root(&self) -> &RootProvider {
+            &self.inner
+        }
+
+        fn call(&self, tx: TransactionRequest) -> EthCall<Ethereum, Bytes> {
+            EthCall::call(
+                RecordingCaller { inner: self.inner.clone(), blocks: self.blocks.clone() },
+                tx,
+            )
+        }
+    }
+
+    fn recording() -> (RecordingProvider, Asserter, Recorded) {
+        let asserter = Asserter::new();
+        let inner = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let blocks = Recorded::default();
+        (RecordingProvider { inner, blocks: blocks.clone() }, asserter, blocks)
+    }
+
+    /// slot0 (7 alan) + liquidity + fee yanıtlarını sırayla kuyruğa ekle
+    fn push_pool_reads(asserter: &Asserter) {
+        asserter.push_success(&Bytes::from(slot0_return(TEST_TICK)));
+        asserter.push_success(&Bytes::from(U256::from(TEST_LIQ).to_be_bytes::<32>().to_vec()));
+        asserter.push_success(&Bytes::from(word_i32(500).to_vec()));
+    }
+
+    fn new_state() -> SharedPoolState {
+        Arc::new(ArcSwap::from_pointee(PoolState::default()))
+    }
+
+    #[tokio::test]
+    async fn test_sync_reads_attach_subscription_block_hash() {
+        let (provider, asserter, blocks) = recording();
+        let state = new_state();
+        push_pool_reads(&asserter);
+
+        sync_pool_state(&provider, &test_pool_config("pin-test"), &state, 100, BlockId::hash(HEAD_HASH))
+            .await
+            .unwrap();
+
+        let recorded = blocks.lock().unwrap().clone();
+        assert_eq!(recorded, vec![Some(BlockId::hash(HEAD_HASH)); 3], "slot0, liquidity, fee");
+        assert_eq!(state.load().pinned_block, Some(100));
+        assert_eq!(state.load().tick, TEST_TICK);
+
+        // Multicall3 toplu okuması da aynı hash'e sabitlenir (boş Result[] yanıtı)
+        blocks.lock().unwrap().clear();
+        let mut empty_results = vec![0u8; 64];
+        empty_results[31] = 0x20;
+        asserter.push_success(&Bytes::from(empty_results));
+        let (pools, states) = ([test_pool_config("pin-test")], [new_state()]);
+        sync_all_pools_multicall(&provider, &pools, &states, 100, BlockId::hash(HEAD_HASH)).await;
+        assert_eq!(*blocks.lock().unwrap(), vec![Some(BlockId::hash(HEAD_HASH))]);
+    }
+
+    /// Budanmış blok: bir kez "latest" ile okunur, havuz sabitsiz işaretlenir
+    #[tokio::test]
+    async fn test_pruned_block_falls_back_to_latest_unpinned() {
+        let (provider, asserter, blocks) = recording();
+        let state = new_state();
+        let before = unpinned_syncs();
+
+        // slot0 hata, liquidity/fee yanıtları aynı turda tüketilir
+        asserter.push_failure_msg("header not found");
+        asserter.push_success(&Bytes::from(U256::from(TEST_LIQ).to_be_bytes::<32>().to_vec()));
+        asserter.push_success(&Bytes::from(word_i32(500).to_vec()));
+        push_pool_reads(&asserter);
+
+        sync_pool_state(&provider, &test_pool_config("pin-test"), &state, 100, BlockId::hash(HEAD_HASH))
+            .await
+            .unwrap();
+
+        let recorded = blocks.lock().unwrap().clone();
+        assert_eq!(recorded[..3], [Some(BlockId::hash(HEAD_HASH)); 3]);
+        assert_eq!(recorded[3..], [Some(BlockId::latest()); 3]);
+        assert!(state.load().is_initialized);
+        assert_eq!(state.load().pinned_block, None);
+        assert!(unpinned_syncs() > before);
+    }
+}
+
+#[cfg(test)]
+mod fast_path_tests {
+    use super::{
+        mark_quiet_block, read_all_slot0, BlockSync, FastPathSync, IMulticall3, slot0_return, test_pool_config,
+        TEST_LIQ, TEST_TICK,
+    };
+    use crate::math::exact::get_sqrt_ratio_at_tick;
+    use crate::types::*;
+    use alloy::eips::BlockId;
+    use alloy::primitives::Bytes;
+    use alloy::providers::ProviderBuilder;
+    use alloy::sol_types::SolCall;
+    use alloy::transports::mock::Asserter;
+    use arc_swap::ArcSwap;
+    use std::sync::Arc;
+
+    fn synced_state(tick: i32) -> SharedPoolState {
+        Arc::new(ArcSwap::from_pointee(PoolState {
+            sqrt_price_x96: get_sqrt_ratio_at_tick(tick),
+            tick,
+            liquidity: TEST_LIQ,
+            is_initialized: true,
+            last_block: 100,
+            ..PoolState::default()
+        }))
+    }
+
+    /// Multicall3 yanıtı: havuz başına bir V3 slot0 dönüşü
+    fn push_slot0_results(asserter: &Asserter, ticks: &[i32]) {
+        let results: Vec<IMulticall3::Result> = ticks
+            .iter()
+            .map(|&tick| IMulticall3::Result { success: true, returnData: Bytes::from(slot0_return(tick)) })
+            .collect();
+        let encoded = IMulticall3::aggregate3Call::abi_encode_returns(&results);
+        asserter.push_success(&Bytes::from(encoded));
+    }
+
+    fn fast_path(states: &[SharedPoolState]) -> FastPathSync {
+        let mut fp = FastPathSync::new(&BotConfig::for_tests(), 100);
+        fp.record_evaluated(states);
+        fp
+    }
+
+    /// Değişmemiş slot0 → sessiz blok: fırsat kontrolü atlanır, likidite korunur
+    #[tokio::test]
+    async fn test_unchanged_slot0_is_quiet_block() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let pools = [test_pool_config("fast-path"), test_pool_config("fast-path")];
+        let states = [synced_state(TEST_TICK), synced_state(TEST_TICK)];
+        let fp = fast_path(&states);
+        push_slot0_results(&asserter, &[TEST_TICK, TEST_TICK]);
+
+        assert!(fp.probes(false));
+        let slot0 = read_all_slot0(&provider, &pools, BlockId::number(101)).await.unwrap();
+        assert_eq!(slot0, vec![Some(get_sqrt_ratio_at_tick(TEST_TICK)); 2]);
+
+        let sync = fp.classify(false, Some(&slot0), &states);
+        assert_eq!(sync, BlockSync::Quiet);
+        assert!(!sync.runs_opportunity_check());
+
+        mark_quiet_block(&states, 101);
+        for state in &states {
+            let st = state.load();
+            assert_eq!((st.last_block, st.pinned_block), (101, Some(101)));
+            assert_eq!(st.liquidity, 5_000_000_000_000_000_000);
+        }
+    }
+
+    /// Tek havuzun fiyatı oynadı → olağan yol
+    #[tokio::test]
+    async fn test_changed_slot0_runs_full_path() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let pools = [test_pool_config("fast-path"), test_pool_config("fast-path")];
+        let states = [synced_state(TEST_TICK), synced_state(TEST_TICK)];
+        let fp = fast_path(&states);
+        push_slot0_results(&asserter, &[TEST_TICK, TEST_TICK + 10]);
+
+        let slot0 = read_all_slot0(&provider, &pools, BlockId::number(101)).await.unwrap();
+        assert_eq!(fp.classify(false, Some(&slot0), &states), BlockSync::Changed);
+
+        // Event state'i güncelledi ama fırsat kontrolü henüz görmedi → yine değişmiş
+        let moved = [synced_state(TEST_TICK), synced_state(TEST_TICK + 10)];
+        assert_eq!(fp.classify(false, Some(&slot0), &moved), BlockSync::Changed);
+
+        // Faz 1 okunamadı → olağan yol
+        assert_eq!(fp.classify(false, None, &states), BlockSync::Changed);
+    }
+
+    /// Periyodik tam sync aralıkta zorlanır; faz 1 o blokta çalışmaz
+    #[test]
+    fn test_periodic_full_sync_overrides_quiet() {
+        let states = [synced_state(TEST_TICK)];
+        let mut fp = fast_path(&states);
+        let slot0 = [Some(get_sqrt_ratio_at_tick(TEST_TICK))];
+
+        assert!(!fp.full_sync_due(149, 0, 50));
+        assert!(fp.full_sync_due(150, 0, 50));
+        assert!(!fp.probes(true));
+        assert_eq!(fp.classify(true, Some(&slot0), &states), BlockSync::FullSync);
+
+        fp.record_full_sync(150);
+        assert!(!fp.full_sync_due(151, 0, 50));
+        // Bitmap yaş sınırını aşan atlama aralığı beklemez
+        assert!(fp.full_sync_due(151, 60, 50));
+
+        // Hızlı yol kapalı: slot0 okunmaz, her blok olağan yoldan
+        let mut config = BotConfig::for_tests();
+        config.fast_path_sync = false;
+        let off = FastPathSync::new(&config, 100);
+        assert!(!off.probes(false));
+        assert_eq!(off.classify(false, Some(&slot0), &states), BlockSync::Changed);
+    }
+}
+
+#[cfg(test)]
+mod event_oracle_tests {
+    use super::{
+        decode_swap_event, resync_diverged, EventFreshnessOracle, SwapObservation, slot0_return, test_pool_config,
+        word_i32, TEST_LIQ, TEST_TICK,
+    };
+    use crate::math::exact::get_sqrt_ratio_at_tick;
+    use crate::types::*;
+    use alloy::primitives::{Bytes, U256};
+    use alloy::providers::ProviderBuilder;
+    use alloy::transports::mock::Asserter;
+    use arc_swap::ArcSwap;
+    use std::sync::Arc;
+
+    /// Swap(amount0, amount1, sqrtPriceX96, liquidity, tick) log verisi
+    fn swap_log(tick: i32) -> Vec<u8> {
+        let mut data = word_i32(-1_000_000).to_vec();
+        data.extend_from_slice(&word_i32(2_500));
+        data.extend_from_slice(&get_sqrt_ratio_at_tick(tick).to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(TEST_LIQ).to_be_bytes::<32>());
+        data.extend_from_slice(&word_i32(tick));
+        data
+    }
+
+    fn observation(block: u64, tick: i32) -> SwapObservation {
+        SwapObservation { block, sqrt_price_x96: get_sqrt_ratio_at_tick(tick), tick }
+    }
+
+    fn pinned_state(block: u64, tick: i32) -> SharedPoolState {
+        Arc::new(ArcSwap::from_pointee(PoolState {
+            sqrt_price_x96: get_sqrt_ratio_at_tick(tick),
+            tick,
+            liquidity: TEST_LIQ,
+            is_initialized: true,
+            last_block: block,
+            pinned_block: Some(block),
+            ..PoolState::default()
+        }))
+    }
+
+    #[test]
+    fn test_decode_swap_event_payload() {
+        let (sqrt, liq, tick) = decode_swap_event(&swap_log(TEST_TICK)).unwrap();
+        assert_eq!((sqrt, liq, tick), (get_sqrt_ratio_at_tick(TEST_TICK), TEST_LIQ, TEST_TICK));
+
+        let (_, _, tick) = decode_swap_event(&swap_log(42)).unwrap();
+        assert_eq!(tick, 42);
+
+        assert_eq!(decode_swap_event(&swap_log(TEST_TICK)[..159]), None);
+    }
+
+    /// Uyuşan okuma sessiz; çelişen okuma tek alarm verir ve havuzu bayatlatır
+    #[test]
+    fn test_oracle_agree_and_disagree() {
+        let pools = [test_pool_config("event-oracle")];
+        let oracle = EventFreshnessOracle::default();
+        oracle.record(pools[0].address, observation(100, TEST_TICK));
+
+        let agreeing = [pinned_state(100, TEST_TICK)];
+        assert!(oracle.check(&pools, &agreeing, 0).is_empty());
+
+        // Event bloğundan önce sabitlenmiş okuma karşılaştırılmaz
+        let earlier = [pinned_state(99, TEST_TICK - 50)];
+        assert!(oracle.check(&pools, &earlier, 1).is_empty());
+
+        let lagging = [pinned_state(101, TEST_TICK - 50)];
+        let divergences = oracle.check(&pools, &lagging, 1);
+        assert_eq!(divergences.len(), 1);
+        assert_eq!((divergences[0].synced_block, divergences[0].synced_tick), (101, TEST_TICK - 50));
+        assert_eq!(divergences[0].event, observation(100, TEST_TICK));
+        assert!(lagging[0].load().is_stale);
+
+        // Aynı event için ikinci alarm yok; yeni event yeniden silahlandırır
+        assert!(oracle.check(&pools, &lagging, 1).is_empty());
+        oracle.record(pools[0].address, observation(99, TEST_TICK + 10));
+        assert!(oracle.check(&pools, &lagging, 1).is_empty());
+        oracle.record(pools[0].address, observation(101, TEST_TICK));
+        assert_eq!(oracle.check(&pools, &lagging, 1).len(), 1);
+
+        // Tolerans içindeki fark alarm değil
+        oracle.record(pools[0].address, observation(102, TEST_TICK));
+        assert!(oracle.check(&pools, &[pinned_state(102, TEST_TICK + 1)], 1).is_empty());
+    }
+
+    /// Ayrışan havuz yedek provider'dan yeniden okunur ve alarm susar
+    #[tokio::test]
+    async fn test_divergence_triggers_fallback_resync() {
+        let asserter = Asserter::new();
+        let fallback = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let pools = [test_pool_config("event-oracle")];
+        let states = [pinned_state(101, TEST_TICK - 50)];
+        let oracle = EventFreshnessOracle::default();
+        oracle.record(pools[0].address, observation(101, TEST_TICK));
+
+        let divergences = oracle.check(&pools, &states, 1);
+        assert_eq!(divergences.len(), 1);
+
+        asserter.push_success(&Bytes::from(slot0_return(TEST_TICK)));
+        asserter.push_success(&Bytes::from(U256::from(TEST_LIQ).to_be_bytes::<32>().to_vec()));
+        asserter.push_success(&Bytes::from(word_i32(500).to_vec()));
+
+        assert_eq!(resync_diverged(&fallback, &pools, &states, &divergences, 102).await, 1);
+        let st = states[0].load();
+        assert!(!st.is_stale);
+        assert_eq!((st.tick, st.pinned_block), (TEST_TICK, Some(102)));
+
+        oracle.record(pools[0].address, observation(102, TEST_TICK));
+        assert!(oracle.check(&pools, &states, 1).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod sync_timing_tests {
+    use super::*;
+    use crate::math::exact::get_sqrt_ratio_at_tick;
+    use alloy::network::Ethereum;
+    use alloy::providers::{
+        Caller, EthCall, EthCallManyParams, EthCallParams, ProviderBuilder, ProviderCall, RootProvider,
+    };
+    use alloy::transports::mock::Asserter;
+    use alloy::transports::TransportResult;
+    use std::time::Duration;
+
+    const RPC_DELAY: Duration = Duration::from_millis(40);
+
+    /// Mock provider: her eth_call yanıtından önce RPC_DELAY kadar bekler
+    struct SlowProvider {
+        inner: RootProvider,
+    }
+
+    struct SlowCaller {
+        inner: RootProvider,
+    }
+
+    impl Caller<Ethereum, Bytes> for SlowCaller {
+        fn call(
+            &self,
+            params: EthCallParams<Ethereum>,
+        ) -> TransportResult<ProviderCall<EthCallParams<Ethereum>, Bytes>> {
+            std::thread::sleep(RPC_DELAY);
+            Caller::<Ethereum, Bytes>::call(&self.inner.weak_client(), params)
+        }
+
+        fn estimate_gas(
+            &self,
+            params: EthCallParams<Ethereum>,
+        ) -> TransportResult<ProviderCall<EthCallParams<Ethereum>, Bytes>> {
+            Caller::<Ethereum, Bytes>::estimate_gas(&self.inner.weak_client(), params)
+        }
+
+        fn call_many(
+            &self,
+            params: EthCallManyParams<'_>,
+        ) -> TransportResult<ProviderCall<EthCallManyParams<'static>, Bytes>> {
+            Caller::<Ethereum, Bytes>::call_many(&self.inner.weak_client(), params)
+        }
+    }
+
+    impl Provider for SlowProvider {
+        fn root(&self) -> &RootProvider {
+            &self.inner
+        }
+
+        fn call(&self, tx: TransactionRequest) -> EthCall<Ethereum, Bytes> {
+            EthCall::call(SlowCaller { inner: self.inner.clone() }, tx)
+        }
+    }
+
+    /// Kontrollü uyku: await RPC kovasına, senkron iş yerel kovaya düşer
+    #[tokio::test]
+    async fn test_guard_attributes_await_and_local_work_separately() {
+        let timing = StageTiming::new();
+        {
+            let mut guard = TimingGuard::start(&timing);
+            guard.rpc(tokio::time::sleep(Duration::from_millis(40))).await;
+            std::thread::sleep(Duration::from_millis(25));
+            guard.rpc(tokio::time::sleep(Duration::from_millis(10))).await;
+        }
+
+        let (rpc_count, rpc_avg_us, _, _) = timing.rpc.summary();
+        let (local_count, local_avg_us, _, _) = timing.local.summary();
+        assert_eq!((rpc_count, local_count), (1, 1), "guard başına birer örnek");
+        assert!((50_000..75_000).contains(&rpc_avg_us), "rpc {}µs", rpc_avg_us);
+        assert!((25_000..40_000).contains(&local_avg_us), "local {}µs", local_avg_us);
+        assert_eq!(timing.payload_summary(), PayloadSummary::default());
+    }
+
+    /// Yavaş sağlayıcı: gecikme slot0 probe'un RPC kovasına yazılır,
+    /// yanıt boyutu ABI kodlamasıyla birebir kaydedilir
+    #[tokio::test]
+    async fn test_slow_provider_delay_lands_in_rpc_bucket() {
+        let asserter = Asserter::new();
+        let provider = SlowProvider {
+            inner: ProviderBuilder::default().connect_mocked_client(asserter.clone()),
+        };
+        let slot0 = IMulticall3::Result { success: true, returnData: Bytes::from(slot0_return(TEST_TICK)) };
+        let results = vec![slot0];
+        let response = IMulticall3::aggregate3Call::abi_encode_returns(&results);
+        asserter.push_success(&Bytes::from(response.clone()));
+
+        let timing = SyncStage::Slot0Probe.timing();
+        let rpc_before = timing.rpc.snapshot().total_us;
+        let local_before = timing.local.snapshot().total_us;
+        let payload_before = timing.payload_summary();
+
+        let prices = read_all_slot0(&provider, &[test_pool_config("timing")], BlockId::number(101)).await.unwrap();
+        assert_eq!(prices, vec![Some(get_sqrt_ratio_at_tick(TEST_TICK))]);
+
+        let rpc_us = timing.rpc.snapshot().total_us - rpc_before;
+        let local_us = timing.local.snapshot().total_us - local_before;
+        assert!(rpc_us >= RPC_DELAY.as_micros() as u64, "rpc {}µs", rpc_us);
+        assert!(local_us < RPC_DELAY.as_micros() as u64, "local {}µs", local_us);
+
+        let payload = timing.payload_summary();
+        assert!(payload.calls > payload_before.calls);
+        assert!(payload.response_bytes - payload_before.response_bytes >= response.len() as u64);
+        assert!(payload.max_response_bytes >= response.len() as u64);
+    }
+
+    #[test]
+    fn test_aggregate3_payload_sizes_match_abi_encoding() {
+        let calls: Vec<IMulticall3::Call3> = [encode_slot0_call(), encode_ticks_call(DexType::UniswapV3, -10)]
+            .into_iter()
+            .map(|data| IMulticall3::Call3 {
+                target: test_pool_config("timing").address,
+                allowFailure: true,
+                callData: Bytes::from(data),
+            })
+            .collect();
+        let request = IMulticall3::aggregate3Call { calls: calls.clone() }.abi_encode();
+        assert_eq!(aggregate3_request_bytes(&calls), request.len());
+
+        let results = vec![
+            IMulticall3::Result { success: true, returnData: Bytes::from(slot0_return(TEST_TICK)) },
+            IMulticall3::Result { success: false, returnData: Bytes::from(vec![0u8; 5]) },
+        ];
+        let response = IMulticall3::aggregate3Call::abi_encode_returns(&results);
+        assert_eq!(aggregate3_response_bytes(&results), response.len());
+    }
+}
+
+#[cfg(test)]
+mod constant_product_tests {
+    use super::{
+        read_all_slot0, sync_all_pools_multicall, sync_tick_bitmap, IMulticall3, slot0_return, test_pool_config,
+        TEST_LIQ, TEST_TICK,
+    };
+    use crate::math::exact::v2_virtual_state;
+    use crate::types::*;
+    use alloy::eips::BlockId;
+    use alloy::primitives::{Bytes, U256};
+    use alloy::providers::ProviderBuilder;
+    use alloy::sol_types::SolCall;
+    use alloy::transports::mock::Asserter;
+    use arc_swap::ArcSwap;
+    use std::sync::Arc;
+
+    fn make_pool(dex: DexType) -> PoolConfig {
+        PoolConfig {
+            fee: FeeTier::from_bps(if dex == DexType::UniswapV2 { 30 } else { 5 }),
+            dex,
+            ..test_pool_config(format!("{}", dex))
+        }
+    }
+
+    /// 1000 WETH / 2.5M USDC → 2500 USDC
+    fn reserves() -> (U256, U256) {
+        (U256::from(1_000u64) * U256::from(10u64).pow(U256::from(18u64)), U256::from(2_500_000_000_000u64))
+    }
+
+    fn word(value: U256) -> Vec<u8> {
+        value.to_be_bytes::<32>().to_vec()
+    }
+
+    fn reserves_result() -> IMulticall3::Result {
+        let (reserve0, reserve1) = reserves();
+        let data = [word(reserve0), word(reserve1), word(U256::from(1_700_000_000u64))].concat();
+        IMulticall3::Result { success: true, returnData: Bytes::from(data) }
+    }
+
+    fn push_results(asserter: &Asserter, results: Vec<IMulticall3::Result>) {
+        let encoded = IMulticall3::aggregate3Call::abi_encode_returns(&results);
+        asserter.push_success(&Bytes::from(encoded));
+    }
+
+    /// Karışık batch: CL havuzu slot0+liquidity+fee, V2 havuzu tek getReserves
+    #[tokio::test]
+    async fn test_multicall_syncs_v2_pool_from_reserves() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let pools = [make_pool(DexType::UniswapV3), make_pool(DexType::UniswapV2)];
+        let states: [SharedPoolState; 2] =
+            [Arc::new(ArcSwap::from_pointee(PoolState::default())), Arc::new(ArcSwap::from_pointee(PoolState::default()))];
+
+        let ok = |data: Vec<u8>| IMulticall3::Result { success: true, returnData: Bytes::from(data) };
+        push_results(
+            &asserter,
+            vec![ok(slot0_return(TEST_TICK)), ok(word(U256::from(TEST_LIQ))), ok(word(U256::from(500u64))), reserves_result()],
+        );
+
+        let results = sync_all_pools_multicall(&provider, &pools, &states, 101, BlockId::number(101)).await;
+        assert!(results.iter().all(|r| r.is_ok()), "{:?}", results);
+
+        let cl = states[0].load();
+        assert_eq!((cl.tick, cl.liquidity, cl.constant_product), (TEST_TICK, TEST_LIQ, false));
+        let v2 = states[1].load();
+        let (reserve0, reserve1) = reserves();
+        let (sqrt_price_x96, tick, liquidity) = v2_virtual_state(reserve0, reserve1).unwrap();
+        assert!(v2.constant_product && v2.is_active());
+        assert_eq!((v2.sqrt_price_x96, v2.tick, v2.liquidity), (sqrt_price_x96, tick, liquidity));
+        assert!((v2.eth_price_usd - 2500.0).abs() < 1e-6, "{}", v2.eth_price_usd);
+        assert_eq!(v2.live_fee_bps, None, "V2'de fee() yok — yapılandırılan kademe");
+
+        // Faz 1 probe'u V2 havuzunda getReserves'ten aynı sqrtPrice'ı okur
+        push_results(&asserter, vec![reserves_result()]);
+        let probe = read_all_slot0(&provider, &pools[1..], BlockId::number(102)).await.unwrap();
+        assert_eq!(probe, vec![Some(sqrt_price_x96)]);
+
+        // Bitmap sync'i V2 havuzunu RPC'siz atlar (kuyrukta yanıt yok)
+        sync_tick_bitmap(&provider, &pools[1], &states[1], 102, 50).await.unwrap();
+        assert!(states[1].load().tick_bitmap.is_none());
+    }
+
+    /// Boş havuz (rezerv sıfır) → havuz STALE, hata raporlanır
+    #[tokio::test]
+    async fn test_empty_v2_pool_marked_stale() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let pools = [make_pool(DexType::UniswapV2)];
+        let states: [SharedPoolState; 1] = [Arc::new(ArcSwap::from_pointee(PoolState::default()))];
+        let empty = [word(U256::ZERO), word(U256::ZERO), word(U256::ZERO)].concat();
+        push_results(&asserter, vec![IMulticall3::Result { success: true, returnData: Bytes::from(empty) }]);
+
+        let results = sync_all_pools_multicall(&provider, &pools, &states, 101, BlockId::number(101)).await;
+        assert!(results[0].is_err());
+        assert!(states[0].load().is_stale);
+    }
+}
@@ -0,0 +1,888 @@
+// ============================================================================
+//  STATUS_SERVER v1.0 — Salt Okunur Durum Uç Noktası (HTTP/JSON)
+//
+//  Harici panel ve risk izleme betikleri için botun anlık iç durumu:
+//  ✓ GET /status                — tam anlık görüntü
+//  ✓ GET /pools                 — havuz başına PoolState skalerleri + bitmap özeti
+//  ✓ GET /opportunities?limit=N — son fırsatlar ve kararları (en yeni başta)
+//  ✓ GET /config                — etkin ayarlar (--print-config maskesi +
+//    anahtar yolu / kurma dosyası da maskeli)
+//  ✓ GET /metrics               — sync aşaması başına RPC / yerel süre
+//    histogramları ve Multicall payload boyutları (Prometheus metin formatı)
+//    + metrics modülünün sayaç / havuz göstergesi / gecikme histogramları
+//  ✓ METRICS_LISTEN_ADDR        — yalnızca /metrics sunan ayrı dinleyici
+//  ✓ Her yükte schema_version — alan değişikliği harici araçlarda algılanır
+//
+//  Güvenlik:
+//  ✓ Yalnızca GET; değiştiren uç nokta yok, gizli değer yok
+//  ✓ STATUS_LISTEN_ADDR loopback dışıysa STATUS_ALLOW_REMOTE=true gerekir
+//
+//  Ana döngü blok sonunda görüntüyü ArcSwap'a yazar (sayaçlar küçük bir
+//  StatsCounters kopyası, havuz satırları skaler); bağlantılar
+//  bu görüntüyü kendi görevlerinde serileştirir. Eşzamanlı bağlantı sayısı
+//  sınırlı, her okuma / yazma zaman aşımlı — yavaş istemci döngüyü
+//  bekletemez, fazla bağlantı hemen kapatılır.
+// ============================================================================
+
+use std::collections::{BTreeMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::Address;
+use arc_swap::ArcSwap;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+
+use crate::dashboard::TradeRow;
+use crate::env_loader::redact_secret;
+use crate::executor::HistogramSnapshot;
+use crate::pair_scheduler::ScheduleStatus;
+use crate::state_sync::{PayloadSummary, SyncStage};
+use crate::types::{ArbitrageStats, BotConfig, PoolConfig, SharedPoolState};
+
+/// Yük şeması — alan eklenince / anlamı değişince artırılır
+pub const STATUS_SCHEMA_VERSION: u32 = 4;
+
+/// Tutulan son fırsat sayısı (/opportunities üst sınırı)
+pub const MAX_STATUS_OPPORTUNITIES: usize = 200;
+
+/// limit verilmezse dönen fırsat sayısı
+const DEFAULT_OPPORTUNITY_LIMIT: usize = 50;
+
+/// /metrics yanıtı (Prometheus metin formatı 0.0.4)
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// İstek satırı + başlıklar için üst sınır
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// --print-config'te açık görünen ama anahtara / yürütme emniyetine yakın
+/// değerler — uç noktada yalnızca tanımlı olup olmadıkları görünür
+const KEY_ADJACENT_FIELDS: [&str; 2] = ["KEYSTORE_PATH", "ARM_FILE"];
+
+/// Bağlantı sınırları
+#[derive(Debug, Clone, Copy)]
+pub struct ServeLimits {
+    /// Aynı anda işlenen bağlantı; fazlası kabul edilip hemen kapatılır
+    pub max_connections: usize,
+    /// İstek okuma ve yanıt yazma için ayrı ayrı süre
+    pub io_timeout: Duration,
+}
+
+impl Default for ServeLimits {
+    fn default() -> Self {
+        Self {
+            max_connections: 8,
+            io_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Yük Tipleri
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BitmapStatus {
+    pub initialized_ticks: usize,
+    pub words: usize,
+    pub snapshot_block: u64,
+    /// Görüntü bloğuna göre yaş
+    pub age_blocks: u64,
+    pub scan_range: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PoolStatus {
+    pub address: Address,
+    pub name: String,
+    pub dex: String,
+    pub fee_bps: u32,
+    pub live_fee_bps: Option<u32>,
+    /// U256 / u128 değerleri ondalık metin (JSON sayı hassasiyeti yetmez)
+    pub sqrt_price_x96: String,
+    pub tick: i32,
+    pub liquidity: String,
+    pub eth_price_usd: f64,
+    pub last_block: u64,
+    /// Görüntü anında state'in yaşı (ms) (v3)
+    pub staleness_ms: u64,
+    pub pinned_block: Option<u64>,
+    pub is_active: bool,
+    pub is_stale: bool,
+    pub quarantined: bool,
+    pub data_quality_errors: u32,
+    pub bitmap: Option<BitmapStatus>,
+}
+
+impl PoolStatus {
+    fn capture(config: &PoolConfig, shared: &SharedPoolState, block_number: u64) -> Self {
+        let state = shared.load();
+        Self {
+            address: config.address,
+            name: config.name.clone(),
+            dex: config.dex.to_string(),
+            fee_bps: config.fee_bps(),
+            live_fee_bps: state.live_fee_bps,
+            sqrt_price_x96: state.sqrt_price_x96.to_string(),
+            tick: state.tick,
+            liquidity: state.liquidity.to_string(),
+            eth_price_usd: state.eth_price_usd,
+            last_block: state.last_block,
+            staleness_ms: state.staleness_ms() as u64,
+            pinned_block: state.pinned_block,
+            is_active: state.is_active(),
+            is_stale: state.is_stale,
+            quarantined: state.quarantined,
+            data_quality_errors: state.data_quality_errors,
+            bitmap: state.tick_bitmap.as_deref().map(|bm| BitmapStatus {
+                initialized_ticks: bm.ticks.len(),
+                words: bm.words.len(),
+                snapshot_block: bm.snapshot_block,
+                age_blocks: block_number.saturating_sub(bm.snapshot_block),
+                scan_range: bm.scan_range,
+            }),
+        }
+    }
+}
+
+/// Fırsat ve kararı (TUI tablosuyla aynı satırlar)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpportunityStatus {
+    pub block: u64,
+    pub pair: String,
+    pub amount_weth: f64,
+    pub profit_weth: f64,
+    /// executed / shadow / skip:<filtre> / failed
+    pub decision: String,
+}
+
+impl From<&TradeRow> for OpportunityStatus {
+    fn from(row: &TradeRow) -> Self {
+        Self {
+            block: row.block,
+            pair: row.pair.clone(),
+            amount_weth: row.amount_weth,
+            profit_weth: row.profit_weth,
+            decision: row.status.label(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ExecutionStatus {
+    pub execution_enabled: bool,
+    pub inflight: u64,
+    pub max_inflight: u32,
+    pub peak_inflight: u64,
+}
+
+/// Yürütmeyi durdurabilen kapılar
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GateStatus {
+    pub warmup_armed: bool,
+    pub warmup_label: Option<String>,
+    /// Circuit breaker'ın kara listede tuttuğu çiftler
+    pub blacklisted_pairs: Vec<String>,
+    pub pipeline_ms: u128,
+    pub pipeline_budget_ms: u128,
+    pub paused: bool,
+    pub approval_block: Option<String>,
+    pub bytecode_block: Option<String>,
+    /// Kontratın pause view'ı — okunamadıysa / view yoksa None
+    pub contract_paused: Option<bool>,
+}
+
+/// /status ve /metrics'in dışa verdiği oturum sayaçları (v4). Blok sonunda
+/// ArbitrageStats'tan birkaç u64 kopyalanır — tam sayaç yapısı kopyalanmaz.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct StatsCounters {
+    pub total_blocks_processed: u64,
+    pub total_opportunities: u64,
+    pub profitable_opportunities: u64,
+    pub executed_trades: u64,
+    pub failed_simulations: u64,
+    pub tick_bitmap_syncs: u64,
+}
+
+impl From<&ArbitrageStats> for StatsCounters {
+    fn from(stats: &ArbitrageStats) -> Self {
+        Self {
+            total_blocks_processed: stats.total_blocks_processed,
+            total_opportunities: stats.total_opportunities,
+            profitable_opportunities: stats.profitable_opportunities,
+            executed_trades: stats.executed_trades,
+            failed_simulations: stats.failed_simulations,
+            tick_bitmap_syncs: stats.tick_bitmap_syncs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigStatus {
+    pub schema_version: u32,
+    pub entries: BTreeMap<&'static str, String>,
+}
+
+impl ConfigStatus {
+    /// --print-config maskesi + anahtara yakın alanlar
+    pub fn from_config(config: &BotConfig) -> Self {
+        let entries = config
+            .redacted_entries()
+            .into_iter()
+            .map(|(key, value)| {
+                if KEY_ADJACENT_FIELDS.contains(&key) && value != "(not set)" {
+                    (key, redact_secret(Some(&value)))
+                } else {
+                    (key, value)
+                }
+            })
+            .collect();
+        Self { schema_version: STATUS_SCHEMA_VERSION, entries }
+    }
+}
+
+/// GET /status yükü
+#[derive(Clone, Serialize)]
+pub struct StatusSnapshot {
+    pub schema_version: u32,
+    /// RFC 3339 (UTC)
+    pub generated_at: String,
+    pub block_number: u64,
+    pub pools: Vec<PoolStatus>,
+    /// En yeni başta
+    pub opportunities: Vec<OpportunityStatus>,
+    pub execution: ExecutionStatus,
+    pub gates: GateStatus,
+    /// Çift önceliği: skorlar, bütçe kesintileri, rotasyon (v2)
+    pub pair_schedule: Option<ScheduleStatus>,
+    /// Dışa verilen sayaçlar — /status ve /metrics aynı değerleri okur
+    pub stats: Option<StatsCounters>,
+    /// Oturum boyunca sabit — her blokta yalnızca Arc klonlanır
+    #[serde(serialize_with = "serialize_shared")]
+    pub config: Arc<ConfigStatus>,
+}
+
+fn serialize_shared<S: serde::Serializer, T: Serialize>(value: &Arc<T>, s: S) -> Result<S::Ok, S::Error> {
+    value.as_ref().serialize(s)
+}
+
+impl StatusSnapshot {
+    fn empty(config: Arc<ConfigStatus>) -> Self {
+        Self {
+            schema_version: STATUS_SCHEMA_VERSION,
+            generated_at: crate::clock::now().to_rfc3339(),
+            block_number: 0,
+            pools: Vec::new(),
+            opportunities: Vec::new(),
+            execution: ExecutionStatus::default(),
+            gates: GateStatus::default(),
+            pair_schedule: None,
+            stats: None,
+            config,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PoolsPayload<'a> {
+    schema_version: u32,
+    block_number: u64,
+    pools: &'a [PoolStatus],
+}
+
+#[derive(Serialize)]
+struct OpportunitiesPayload<'a> {
+    schema_version: u32,
+    block_number: u64,
+    opportunities: &'a [OpportunityStatus],
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Yayıncı (ana döngü tarafı)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// run_bot'un blok sonunda verdiği görünüm (dashboard::BlockView karşılığı)
+pub struct StatusView<'a> {
+    pub block_number: u64,
+    pub pools: &'a [PoolConfig],
+    pub states: &'a [SharedPoolState],
+    pub stats: &'a ArbitrageStats,
+    pub execution: ExecutionStatus,
+    pub gates: GateStatus,
+    pub pair_schedule: Option<ScheduleStatus>,
+}
+
+/// Ana döngünün tuttuğu yayıncı: fırsat halkası + yayınlanan görüntü
+pub struct StatusFeed {
+    config: Arc<ConfigStatus>,
+    opportunities: VecDeque<OpportunityStatus>,
+    current: Arc<ArcSwap<StatusSnapshot>>,
+}
+
+impl StatusFeed {
+    pub fn new(config: &BotConfig) -> Self {
+        let config = Arc::new(ConfigStatus::from_config(config));
+        let current = Arc::new(ArcSwap::from_pointee(StatusSnapshot::empty(Arc::clone(&config))));
+        Self {
+            config,
+            opportunities: VecDeque::with_capacity(MAX_STATUS_OPPORTUNITIES),
+            current,
+        }
+    }
+
+    /// Sunucuya verilecek okuma ucu
+    pub fn reader(&self) -> Arc<ArcSwap<StatusSnapshot>> {
+        Arc::clone(&self.current)
+    }
+
+    /// Fırsat satırı — en yeni başa, MAX_STATUS_OPPORTUNITIES üstü düşer
+    pub fn record(&mut self, row: &TradeRow) {
+        self.opportunities.push_front(OpportunityStatus::from(row));
+        self.opportunities.truncate(MAX_STATUS_OPPORTUNITIES);
+    }
+
+    /// Blok sonu görüntüsü: kopyalar burada alınır, sunucu yalnızca Arc okur
+    pub fn publish(&self, view: StatusView<'_>) {
+        let snapshot = StatusSnapshot {
+            schema_version: STATUS_SCHEMA_VERSION,
+            generated_at: crate::clock::now().to_rfc3339(),
+            block_number: view.block_number,
+            pools: view
+                .pools
+                .iter()
+                .zip(view.states)
+                .map(|(config, shared)| PoolStatus::capture(config, shared, view.block_number))
+                .collect(),
+            opportunities: self.opportunities.iter().cloned().collect(),
+            execution: view.execution,
+            gates: view.gates,
+            pair_schedule: view.pair_schedule,
+            stats: Some(StatsCounters::from(view.stats)),
+            config: Arc::clone(&self.config),
+        };
+        self.current.store(Arc::new(snapshot));
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Yönlendirme
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// İstek satırından (yöntem, hedef) → (HTTP durum kodu, JSON gövde)
+pub fn route(snapshot: &StatusSnapshot, method: &str, target: &str) -> (u16, String) {
+    if method != "GET" {
+        return (405, error_body("read-only endpoint: only GET is supported"));
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let body = match path {
+        "/status" => serde_json::to_string(snapshot),
+        "/pools" => serde_json::to_string(&PoolsPayload {
+            schema_version: STATUS_SCHEMA_VERSION,
+            block_number: snapshot.block_number,
+            pools: &snapshot.pools,
+        }),
+        "/opportunities" => {
+            let limit = match query_param(query, "limit").map(str::parse::<usize>) {
+                None => DEFAULT_OPPORTUNITY_LIMIT,
+                Some(Ok(limit)) => limit.clamp(1, MAX_STATUS_OPPORTUNITIES),
+                Some(Err(_)) => return (400, error_body("limit must be a non-negative integer")),
+            };
+            let shown = limit.min(snapshot.opportunities.len());
+            serde_json::to_string(&OpportunitiesPayload {
+                schema_version: STATUS_SCHEMA_VERSION,
+                block_number: snapshot.block_number,
+                opportunities: &snapshot.opportunities[..shown],
+            })
+        }
+        "/config" => serde_json::to_string(snapshot.config.as_ref()),
+        _ => {
+            return (
+                404,
+                error_body("unknown route: /status, /pools, /opportunities, /config, /metrics"),
+            )
+        }
+    };
+    match body {
+        Ok(body) => (200, body),
+        Err(e) => (500, error_body(&e.to_string())),
+    }
+}
+
+/// Multicall metriği: (ad, açıklama, tip, PayloadSummary'den değer)
+type PayloadMetric = (&'static str, &'static str, &'static str, fn(&PayloadSummary) -> u64);
+
+/// Prometheus metin formatı: sync aşaması başına RPC / yerel süre
+/// histogramları (saniye) ve Multicall istek / yanıt byte sayaçları
+pub fn render_metrics() -> String {
+    let mut out = String::new();
+    for (name, help, local) in [
+        ("arb_sync_rpc_seconds", "Sync RPC await time (network + provider) per call", false),
+        ("arb_sync_local_seconds", "Sync local processing time (decode, math, state writes) per call", true),
+    ] {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} histogram\n", name, help, name));
+        for stage in SyncStage::ALL {
+            let timing = stage.timing();
+            let hist = if local { &timing.local } else { &timing.rpc };
+            write_histogram(&mut out, name, &format!("stage=\"{}\"", stage.label()), &hist.snapshot());
+        }
+    }
+    let payload_metrics: [PayloadMetric; 4] = [
+        ("arb_sync_multicall_calls_total", "Successful Multicall3 calls", "counter", |p| p.calls),
+        ("arb_sync_multicall_request_bytes_total", "Multicall3 request calldata bytes", "counter", |p| {
+            p.request_bytes
+        }),
+        ("arb_sync_multicall_response_bytes_total", "Multicall3 response bytes", "counter", |p| {
+            p.response_bytes
+        }),
+        ("arb_sync_multicall_response_bytes_max", "Largest Multicall3 response (bytes)", "gauge", |p| {
+            p.max_response_bytes
+        }),
+    ];
+    for (name, help, kind, value) in payload_metrics {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", name, help, name, kind));
+        for stage in SyncStage::ALL {
+            let payload = stage.timing().payload_summary();
+            out.push_str(&format!("{}{{stage=\"{}\"}} {}\n", name, stage.label(), value(&payload)));
+        }
+    }
+    out
+}
+
+/// Kümülatif `_bucket` satırları + `_sum` / `_count`. `labels` hazır
+/// etiket listesidir (`stage="multicall"`); boşsa seri etiketsizdir.
+pub(crate) fn write_histogram(out: &mut String, name: &str, labels: &str, snapshot: &HistogramSnapshot) {
+    let (bucket_prefix, series) = if labels.is_empty() {
+        (String::new(), String::new())
+    } else {
+        (format!("{},", labels), format!("{{{}}}", labels))
+    };
+    let mut cumulative = 0;
+    for (i, count) in snapshot.bucket_counts.iter().enumerate() {
+        cumulative += count;
+        let le = match snapshot.bucket_upper_us.get(i) {
+            Some(upper_us) => format!("{}", *upper_us as f64 / 1e6),
+            None => "+Inf".to_string(),
+        };
+        out.push_str(&format!("{}_bucket{{{}le=\"{}\"}} {}\n", name, bucket_prefix, le, cumulative));
+    }
+    out.push_str(&format!(
+        "{}_sum{} {}\n{}_count{} {}\n",
+        name,
+        series,
+        snapshot.total_us as f64 / 1e6,
+        name,
+        series,
+        snapshot.count,
+    ));
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "schema_version": STATUS_SCHEMA_VERSION, "error": message }).to_string()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sunucu
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Dinleyicinin sunduğu uç noktalar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Routes {
+    /// STATUS_LISTEN_ADDR: tüm salt okunur uç noktalar
+    All,
+    /// METRICS_LISTEN_ADDR: yalnızca /metrics (diğerleri 404)
+    MetricsOnly,
+}
+
+/// Dinleyiciyi aç ve arka planda sun. Bağlanamazsa hata döner (bot sürer).
+pub async fn spawn(
+    addr: SocketAddr,
+    reader: Arc<ArcSwap<StatusSnapshot>>,
+    routes: Routes,
+    cancel: CancellationToken,
+) -> std::io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr).await?;
+    let local = listener.local_addr()?;
+    tokio::spawn(serve(listener, reader, routes, ServeLimits::default(), cancel));
+    Ok(local)
+}
+
+/// Kabul döngüsü — her bağlantı kendi görevinde, en fazla `max_connections`
+pub async fn serve(
+    listener: TcpListener,
+    reader: Arc<ArcSwap<StatusSnapshot>>,
+    routes: Routes,
+    limits: ServeLimits,
+    cancel: CancellationToken,
+) {
+    let slots = Arc::new(Semaphore::new(limits.max_connections));
+    loop {
+        let stream = tokio::select! {
+            _ = cancel.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    eprintln!("  ⚠️ [Status] accept error: {}", e);
+                    continue;
+                }
+            },
+        };
+        // Sınır doluysa bağlantı beklemeden kapanır (drop)
+        let Ok(permit) = Arc::clone(&slots).try_acquire_owned() else {
+            continue;
+        };
+        let reader = Arc::clone(&reader);
+        tokio::spawn(async move {
+            let _permit = permit;
+            let _ = handle_connection(stream, &reader, routes, limits.io_timeout).await;
+        });
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    reader: &ArcSwap<StatusSnapshot>,
+    routes: Routes,
+    io_timeout: Duration,
+) -> std::io::Result<()> {
+    let request = match tokio::time::timeout(io_timeout, read_request_head(&mut stream)).await {
+        Ok(read) => read?,
+        Err(_) => return Ok(()),
+    };
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (200, METRICS_CONTENT_TYPE, crate::metrics::render(&reader.load())),
+        (Some(_), Some(_)) if routes == Routes::MetricsOnly => {
+            (404, "application/json", error_body("unknown route: only /metrics is served here"))
+        }
+        (Some(method), Some(target)) => {
+            let (status, body) = route(&reader.load_full(), method, target);
+            (status, "application/json", body)
+        }
+        _ => (400, "application/json", error_body("malformed request line")),
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason_phrase(status),
+        content_type,
+        body.len(),
+        body,
+    );
+    match tokio::time::timeout(io_timeout, stream.write_all(response.as_bytes())).await {
+        Ok(written) => written,
+        Err(_) => Ok(()),
+    }
+}
+
+/// Başlıkların sonuna (\r\n\r\n) kadar oku; istek satırını döndür
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 || buf.len() + n > MAX_REQUEST_BYTES {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let head = String::from_utf8_lossy(&buf);
+    Ok(head.lines().next().unwrap_or("").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dashboard::RowStatus;
+    use crate::types::{DexType, FeeTier, PoolState, TickBitmapData};
+    use alloy::primitives::{address, U256};
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    fn make_pool() -> PoolConfig {
+        PoolConfig {
+            address: address!("d0b53D9277642d899DF5C87A3966A349A798F224"),
+            name: "UniV3-WETH/USDC".into(),
+            fee: FeeTier::from_bps(5),
+            token0_decimals: 18,
+            token1_decimals: 6,
+            dex: DexType::UniswapV3,
+            token0_is_weth: true,
+            tick_spacing: 10,
+            quote_token_address: address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+            base_token_address: address!("4200000000000000000000000000000000000006"),
+        }
+    }
+
+    fn make_state() -> SharedPoolState {
+        Arc::new(ArcSwap::from_pointee(PoolState {
+            sqrt_price_x96: U256::from(1u128 << 96),
+            tick: -197_000,
+            liquidity: 5_000_000_000_000_000_000,
+            eth_price_usd: 2_500.0,
+            is_initialized: true,
+            last_block: 1_000,
+            tick_bitmap: Some(Box::new(TickBitmapData {
+                words: HashMap::new(),
+                ticks: HashMap::new(),
+                snapshot_block: 990,
+                sync_duration_us: 0,
+                scan_range: 500,
+                ..TickBitmapData::empty()
+            })),
+            ..PoolState::default()
+        }))
+    }
+
+    fn row(block: u64, status: RowStatus) -> TradeRow {
+        TradeRow {
+            block,
+            pair: "WETH/USDC".into(),
+            amount_weth: 1.5,
+            profit_weth: 0.002,
+            status,
+        }
+    }
+
+    fn published_feed() -> StatusFeed {
+        let mut config = BotConfig::for_tests();
+        config.private_key = Some("0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef".into());
+        config.telegram_bot_token = Some("123456:SECRET-TOKEN".into());
+        config.keystore_path = Some("/home/arb/keystore.json".into());
+        config.arm_file = "/run/arb/ARMED".into();
+        config.rpc_http_url = "https://base.example.io/v2/APIKEY".into();
+
+        let mut feed = StatusFeed::new(&config);
+        for block in 0..(MAX_STATUS_OPPORTUNITIES as u64 + 5) {
+            feed.record(&row(block, RowStatus::Skipped("warmup")));
+        }
+        feed.record(&row(1_000, RowStatus::Executed));
+        let pools = [make_pool()];
+        let states = [make_state()];
+        feed.publish(StatusView {
+            block_number: 1_000,
+            pools: &pools,
+            states: &states,
+            stats: &ArbitrageStats::new(),
+            execution: ExecutionStatus { execution_enabled: true, inflight: 1, max_inflight: 2, peak_inflight: 2 },
+            gates: GateStatus { warmup_armed: true, contract_paused: Some(true), ..GateStatus::default() },
+            pair_schedule: Some(ScheduleStatus { budget_ms: Some(400), rotation_blocks: 20, ..Default::default() }),
+        });
+        feed
+    }
+
+    fn get(feed: &StatusFeed, target: &str) -> serde_json::Value {
+        let (status, body) = route(&feed.reader().load(), "GET", target);
+        assert_eq!(status, 200, "{} → {}", target, body);
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[test]
+    fn test_route_payload_shapes() {
+        let feed = published_feed();
+
+        let status = get(&feed, "/status");
+        assert_eq!(status["schema_version"], STATUS_SCHEMA_VERSION);
+        assert_eq!(status["block_number"], 1_000);
+        for key in ["generated_at", "pools", "opportunities", "execution", "gates", "pair_schedule", "stats", "config"] {
+            assert!(!status[key].is_null(), "missing {}", key);
+        }
+        assert_eq!(status["stats"]["executed_trades"], 0);
+        assert!(status["stats"]["active_transport"].is_null(), "yalnızca dışa verilen sayaçlar");
+        assert_eq!(status["execution"]["inflight"], 1);
+        assert_eq!(status["gates"]["warmup_armed"], true);
+        assert_eq!(status["gates"]["contract_paused"], true);
+        assert_eq!(status["pair_schedule"]["budget_ms"], 400);
+
+        let pools = get(&feed, "/pools");
+        assert_eq!(pools["schema_version"], STATUS_SCHEMA_VERSION);
+        let pool = &pools["pools"][0];
+        assert_eq!(pool["name"], "UniV3-WETH/USDC");
+        assert_eq!(pool["tick"], -197_000);
+        assert_eq!(pool["liquidity"], "5000000000000000000");
+        assert_eq!(pool["bitmap"]["snapshot_block"], 990);
+        assert_eq!(pool["bitmap"]["age_blocks"], 10);
+
+        // En yeni başta; limit kırpılır
+        let opps = get(&feed, "/opportunities?limit=3");
+        assert_eq!(opps["schema_version"], STATUS_SCHEMA_VERSION);
+        let list = opps["opportunities"].as_array().unwrap();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list[0]["decision"], "executed");
+        assert_eq!(list[1]["decision"], "skip:warmup");
+        let all = get(&feed, "/opportunities?limit=100000");
+        assert_eq!(all["opportunities"].as_array().unwrap().len(), MAX_STATUS_OPPORTUNITIES);
+        let default = get(&feed, "/opportunities");
+        assert_eq!(default["opportunities"].as_array().unwrap().len(), DEFAULT_OPPORTUNITY_LIMIT);
+
+        let config = get(&feed, "/config");
+        assert_eq!(config["schema_version"], STATUS_SCHEMA_VERSION);
+        assert_eq!(config["entries"]["BYTECODE_RECHECK_BLOCKS"], "1000");
+
+        // Salt okunur: yazma yöntemleri ve bilinmeyen yollar reddedilir
+        let snapshot = feed.reader().load_full();
+        assert_eq!(route(&snapshot, "POST", "/status").0, 405);
+        assert_eq!(route(&snapshot, "DELETE", "/config").0, 405);
+        assert_eq!(route(&snapshot, "GET", "/pause").0, 404);
+        assert_eq!(route(&snapshot, "GET", "/opportunities?limit=-1").0, 400);
+    }
+
+    /// Anahtar ve anahtara yakın alanlar hiçbir yükte açık görünmez
+    #[test]
+    fn test_secrets_and_key_adjacent_fields_redacted() {
+        let feed = published_feed();
+        let config = get(&feed, "/config");
+        let entries = &config["entries"];
+        for key in ["PRIVATE_KEY", "TELEGRAM_BOT_TOKEN", "KEYSTORE_PATH", "ARM_FILE"] {
+            let value = entries[key].as_str().unwrap();
+            assert!(value.starts_with("***"), "{} not masked: {}", key, value);
+        }
+        assert_eq!(entries["RPC_HTTP_URL"], "https://base.example.io/***");
+
+        let (_, full) = route(&feed.reader().load(), "GET", "/status");
+        for secret in ["deadbeef", "SECRET-TOKEN", "keystore.json", "/run/arb", "APIKEY"] {
+            assert!(!full.contains(secret), "{} leaked in /status", secret);
+        }
+    }
+
+    #[test]
+    fn test_metrics_exposition_has_cumulative_histograms_per_stage() {
+        SyncStage::Multicall.timing().rpc.record(2_000);
+        SyncStage::Multicall.timing().record_payload(1_000, 40_000);
+
+        let body = render_metrics();
+        assert!(body.contains("# TYPE arb_sync_rpc_seconds histogram"));
+        assert!(body.contains("# TYPE arb_sync_local_seconds histogram"));
+        for stage in SyncStage::ALL {
+            for series in ["arb_sync_rpc_seconds", "arb_sync_local_seconds"] {
+                for suffix in ["_bucket{stage=\"{}\",le=\"+Inf\"}", "_sum{stage=\"{}\"}", "_count{stage=\"{}\"}"] {
+                    let prefix = format!("{}{} ", series, suffix.replace("{}", stage.label()));
+                    assert!(body.lines().any(|l| l.starts_with(&prefix)), "missing {}", prefix);
+                }
+            }
+        }
+
+        // Kovalar kümülatif (azalmaz)
+        let buckets: Vec<u64> = body
+            .lines()
+            .filter(|l| l.starts_with("arb_sync_rpc_seconds_bucket{stage=\"multicall\""))
+            .map(|l| l.rsplit(' ').next().unwrap().parse().unwrap())
+            .collect();
+        assert_eq!(buckets.len(), crate::executor::LATENCY_BUCKETS_US.len() + 1);
+        assert!(buckets.windows(2).all(|w| w[0] <= w[1]));
+        assert!(body.contains("arb_sync_rpc_seconds_bucket{stage=\"multicall\",le=\"0.005\"}"));
+
+        let max_line = body
+            .lines()
+            .find(|l| l.starts_with("arb_sync_multicall_response_bytes_max{stage=\"multicall\"}"))
+            .unwrap();
+        assert!(max_line.rsplit(' ').next().unwrap().parse::<u64>().unwrap() >= 40_000);
+    }
+
+    async fn request(addr: SocketAddr, target: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    /// Sessiz istemciler bağlantı sınırını doldurur: yayın beklemez, fazla
+    /// bağlantı hemen kapanır, zaman aşımından sonra sunucu yeniden cevap verir
+    #[tokio::test]
+    async fn test_slow_clients_cannot_back_pressure_publisher() {
+        let feed = published_feed();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let limits = ServeLimits { max_connections: 2, io_timeout: Duration::from_millis(300) };
+        let cancel = CancellationToken::new();
+        tokio::spawn(serve(listener, feed.reader(), Routes::All, limits, cancel.clone()));
+
+        let response = request(addr, "/pools").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        assert!(response.contains(&format!("\"schema_version\":{}", STATUS_SCHEMA_VERSION)));
+
+        // İstek göndermeyen iki istemci tüm yuvaları tutar
+        let _idle_a = TcpStream::connect(addr).await.unwrap();
+        let _idle_b = TcpStream::connect(addr).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Yayıncı etkilenmez
+        let pools = [make_pool()];
+        let states = [make_state()];
+        let started = Instant::now();
+        for block in 1_001..1_101 {
+            feed.publish(StatusView {
+                block_number: block,
+                pools: &pools,
+                states: &states,
+                stats: &ArbitrageStats::new(),
+                execution: ExecutionStatus::default(),
+                gates: GateStatus::default(),
+                pair_schedule: None,
+            });
+        }
+        assert!(started.elapsed() < Duration::from_millis(250), "publish blocked: {:?}", started.elapsed());
+
+        // Fazla bağlantı beklemeden kapatılır
+        let mut extra = TcpStream::connect(addr).await.unwrap();
+        let mut buf = Vec::new();
+        let read = tokio::time::timeout(Duration::from_millis(200), extra.read_to_end(&mut buf)).await;
+        assert!(matches!(read, Ok(Ok(0)) | Ok(Err(_))), "over-limit connection must be closed");
+
+        // Sessiz istemciler zaman aşımına uğrar → yuvalar boşalır, son görüntü sunulur
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let response = request(addr, "/status").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        assert!(response.contains("\"block_number\":1100"));
+        cancel.cancel();
+    }
+
+    /// Metrik dinleyicisi yalnızca /metrics sunar; dolu port hata döner
+    /// (bot sürer), panik yok
+    #[tokio::test]
+    async fn test_metrics_only_listener_and_taken_port() {
+        let feed = published_feed();
+        let cancel = CancellationToken::new();
+        let addr = spawn("127.0.0.1:0".parse().unwrap(), feed.reader(), Routes::MetricsOnly, cancel.clone())
+            .await
+            .unwrap();
+
+        let response = request(addr, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+        assert!(response.contains(METRICS_CONTENT_TYPE));
+        assert!(response.contains("arb_blocks_processed_total 0"));
+        assert!(response.contains("arb_pool_tick{pool=\"UniV3-WETH/USDC\""));
+
+        let response = request(addr, "/status").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"), "{}", response);
+
+        let taken = spawn(addr, feed.reader(), Routes::All, cancel.clone()).await;
+        assert!(taken.is_err(), "second bind on {} must fail", addr);
+        cancel.cancel();
+    }
+}
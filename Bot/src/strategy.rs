@@ -22,36 +22,258 @@
 
 use alloy::primitives::{Address, U256};
 use alloy::providers::Provider;
-use alloy::signers::local::PrivateKeySigner;
 use colored::*;
-use chrono::Local;
 use std::io::Write;
 use std::sync::Arc;
 
 use crate::types::*;
 use crate::math;
-use crate::simulator::SimulationEngine;
+use crate::console::{eprintln_high, println_high};
+use crate::direction_gate::{Direction, DirectionKey};
+use crate::opportunity_filter::{ChainOutcome, FilterChain, FilterStage, OpportunityContext};
+use crate::errors::{BotError, BotResult};
+use crate::executor::{InflightGuard, InflightLimiter, OpportunityExpiry};
+use crate::log_event::{LogEvent, OpportunityFields};
+use crate::log_integrity::{DecisionInputs, LogIntegrity};
+use crate::simulator::{DeadlineSemantics, SimulationEngine};
 
-use zeroize::Zeroize;
 
 // �����������������������������������������������������������������������������
 // Zaman Damgas�
 // �����������������������������������������������������������������������������
 
 fn timestamp() -> String {
-    Local::now().format("%H:%M:%S%.3f").to_string()
+    crate::clock::local_now().format("%H:%M:%S%.3f").to_string()
 }
 
 // �����������������������������������������������������������������������������
 // Arbitraj F�rsat Tespiti
 // �����������������������������������������������������������������������������
 
+/// Rakip arbitraj yoğunluğuna göre uygulanacak ceza
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompetitionPenalty {
+    /// Ceza yok — normal eşikler
+    None,
+    /// Kâr ve ROI eşikleri bu çarpanla yükseltilir
+    RaiseThreshold(f64),
+    /// Blok tamamen atlanır (fırsatlar sadece gölge loglanır)
+    SkipBlock,
+}
+
+/// Önceki bloktaki rakip işlem sayısından cezayı belirle.
+pub fn competition_penalty(competitor_txs: u32, config: &BotConfig) -> CompetitionPenalty {
+    if config.competition_skip_limit > 0 && competitor_txs > config.competition_skip_limit {
+        CompetitionPenalty::SkipBlock
+    } else if config.competition_min_txs > 0
+        && competitor_txs >= config.competition_min_txs
+        && config.competition_profit_mult > 1.0
+    {
+        CompetitionPenalty::RaiseThreshold(config.competition_profit_mult)
+    } else {
+        CompetitionPenalty::None
+    }
+}
+
+/// Cezayı fırsatın kâr/ROI değerlerine uygula — true: fırsat atlanmalı.
+pub fn is_competition_skipped(
+    penalty: CompetitionPenalty,
+    expected_profit_weth: f64,
+    roi: f64,
+    config: &BotConfig,
+) -> bool {
+    match penalty {
+        CompetitionPenalty::None => false,
+        CompetitionPenalty::SkipBlock => true,
+        CompetitionPenalty::RaiseThreshold(mult) => {
+            expected_profit_weth < config.min_net_profit_weth * mult
+                && roi < config.min_profit_roi * mult
+        }
+    }
+}
+
+/// Referans boyutta (SPREAD_PROBE_SIZE_WETH) yürütülebilir fiyatlar, quote/WETH.
+///
+/// Sığ havuzun spot fiyatı gürültülüdür: derin havuza göre %2 "spread"
+/// gösterse de küçük bir işlem bile fiyatı ezer. Spread ön elemesi spot
+/// yerine bu fiyatlarla yapılır. AMM fee'si dahil değildir (PreFilter düşer).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExecutablePrice {
+    /// `probe` WETH satınca WETH başına alınan ortalama quote (0 = satılamaz)
+    pub sell: f64,
+    /// `probe` WETH almak için WETH başına ödenen ortalama quote (∞ = alınamaz)
+    pub buy: f64,
+}
+
+/// Tek havuzun yürütülebilir fiyatları. Aktif likiditeyle tek adım swap
+/// (boş tick listesi — allocation yok); referans boyut küçük olduğundan
+/// tick geçişi nadirdir. Oranlar ham birimlerde hesaplanıp spot fiyata
+/// uygulanır, böylece decimal farkları sonucu etkilemez.
+/// `probe_weth <= 0` → spot fiyat (eski davranış).
+pub fn executable_price(pool: &PoolConfig, state: &PoolState, probe_weth: f64) -> ExecutablePrice {
+    let spot = state.eth_price_usd;
+    let unusable = ExecutablePrice { sell: 0.0, buy: f64::INFINITY };
+    if probe_weth <= 0.0 {
+        return ExecutablePrice { sell: spot, buy: spot };
+    }
+    // Ham quote / ham WETH spot oranı (sqrtPriceX96'dan)
+    let sqrt_ratio = math::exact::u256_to_f64(state.sqrt_price_x96) / 2f64.powi(96);
+    let ratio_t1_t0 = sqrt_ratio * sqrt_ratio;
+    let spot_raw = if pool.token0_is_weth { ratio_t1_t0 } else { 1.0 / ratio_t1_t0 };
+    if spot <= 0.0 || !spot_raw.is_finite() || spot_raw <= 0.0 || state.liquidity == 0 {
+        return unusable;
+    }
+
+    let probe_wei = probe_weth * 1e18;
+    let swap = |amount_in: f64, zero_for_one: bool| {
+        let result = math::exact::compute_exact_swap_presorted(
+            state.sqrt_price_x96,
+            state.liquidity,
+            state.tick,
+            U256::from(safe_f64_to_u128(amount_in)),
+            zero_for_one,
+            0,
+            &[],
+        );
+        math::exact::u256_to_f64(result.amount_out)
+    };
+
+    // WETH → quote: alınan / spot'ta beklenen
+    let quote_out = swap(probe_wei, pool.token0_is_weth);
+    let sell = spot * quote_out / (probe_wei * spot_raw);
+    // quote → WETH: spot'ta probe WETH eden quote ile alınan WETH
+    let weth_out = swap(probe_wei * spot_raw, !pool.token0_is_weth);
+    let buy = if weth_out > 0.0 { spot * probe_wei / weth_out } else { f64::INFINITY };
+
+    if sell.is_finite() && !buy.is_nan() {
+        ExecutablePrice { sell, buy }
+    } else {
+        unusable
+    }
+}
+
+/// Ucuz havuzdan al → pahalı havuza sat yönünde yürütülebilir spread (%).
+/// Referans boyutta kapanan / ters dönen spread 0 döner.
+pub fn executable_spread_pct(buy_pool: &ExecutablePrice, sell_pool: &ExecutablePrice) -> f64 {
+    if buy_pool.buy.is_finite() && buy_pool.buy > 0.0 {
+        ((sell_pool.sell - buy_pool.buy) / buy_pool.buy * 100.0).max(0.0)
+    } else {
+        0.0
+    }
+}
+
+/// `executable_price`'ın sabit nokta karşılığı (SIZING_NUMERICS=fixed):
+/// fiyat spot f64'ünden değil, swap çıktılarının ham oranından ve havuz
+/// decimal'larından türetilir — sonuç bitleri platformdan bağımsız.
+fn fixed_executable_price(pool: &PoolConfig, state: &PoolState, probe_weth: f64) -> math::fixed::ExecPrice {
+    let quote_decimals = if pool.token0_is_weth { pool.token1_decimals } else { pool.token0_decimals };
+    let probe_wei = if probe_weth > 0.0 { math::exact::f64_to_u256_wei(probe_weth) } else { U256::ZERO };
+    math::fixed::exec_price(state, pool.token0_is_weth, quote_decimals, probe_wei)
+}
+
+/// Ön elemenin iki havuz fiyatı — SIZING_NUMERICS'e göre f64 ya da Q63.64
+enum PairPrices {
+    F64(ExecutablePrice, ExecutablePrice),
+    Fixed(math::fixed::ExecPrice, math::fixed::ExecPrice),
+}
+
+impl PairPrices {
+    fn new(numerics: SizingNumerics, pools: &[PoolConfig], state_a: &PoolState, state_b: &PoolState, probe_weth: f64) -> Self {
+        match numerics {
+            SizingNumerics::F64 => PairPrices::F64(
+                executable_price(&pools[0], state_a, probe_weth),
+                executable_price(&pools[1], state_b, probe_weth),
+            ),
+            SizingNumerics::Fixed => PairPrices::Fixed(
+                fixed_executable_price(&pools[0], state_a, probe_weth),
+                fixed_executable_price(&pools[1], state_b, probe_weth),
+            ),
+        }
+    }
+
+    /// (A'dan al → B'ye sat, B'den al → A'ya sat) yürütülebilir spread (%)
+    fn spreads(&self) -> (f64, f64) {
+        match self {
+            PairPrices::F64(a, b) => (executable_spread_pct(a, b), executable_spread_pct(b, a)),
+            PairPrices::Fixed(a, b) => (
+                math::fixed::spread_pct(a, b).to_f64(),
+                math::fixed::spread_pct(b, a).to_f64(),
+            ),
+        }
+    }
+
+    /// `buy_idx` havuzundan alış yönünde PreFilter. Alınamayan havuz
+    /// (f64: ∞, fixed: None) geçersiz fiyat olarak reddedilir.
+    fn prefilter(&self, filter: &math::PreFilter, buy_idx: usize, probe_weth: f64) -> math::PreFilterResult {
+        match self {
+            PairPrices::F64(a, b) => {
+                let (buy, sell) = if buy_idx == 0 { (a, b) } else { (b, a) };
+                filter.check(buy.buy, sell.sell.max(buy.buy), probe_weth)
+            }
+            PairPrices::Fixed(a, b) => {
+                let (buy, sell) = if buy_idx == 0 { (a, b) } else { (b, a) };
+                let buy_price = buy.buy.unwrap_or(math::fixed::Fx::ZERO);
+                filter.check_fixed(buy_price, sell.sell.max(buy_price), math::fixed::Fx::from_f64(probe_weth))
+            }
+        }
+    }
+}
+
+/// REVM boyutlandırmasına geçmeden önce havuzların swap edebildiğini
+/// doğrulayan küçük probe (WETH)
+const REVM_PROBE_WETH: f64 = 0.0001;
+
+/// REVM quote'larıyla iki bacaklı arbitraj kârı (quote cinsinden, gas düşülmüş).
+/// `math::compute_arbitrage_profit_presorted` ile aynı formül; swap
+/// çıktıları havuzların kendi bytecode'undan gelir.
+#[allow(clippy::too_many_arguments)]
+pub fn revm_arbitrage_profit(
+    engine: &SimulationEngine,
+    sell_pool: &PoolConfig,
+    sell_state: &PoolState,
+    buy_pool: &PoolConfig,
+    buy_state: &PoolState,
+    amount_in_weth: f64,
+    gas_cost_quote: f64,
+    eth_price_quote: f64,
+) -> BotResult<f64> {
+    let amount_in_wei = weth_to_wei(amount_in_weth)?;
+    if amount_in_wei.is_zero() {
+        return Err(BotError::SimulationFailed { reason: "zero amount".into() });
+    }
+    let quote_out =
+        engine.quote_swap(sell_pool, sell_state, amount_in_wei, sell_pool.token0_is_weth)?;
+    let weth_out =
+        engine.quote_swap(buy_pool, buy_state, quote_out, !buy_pool.token0_is_weth)?;
+
+    let pnl_weth = if weth_out >= amount_in_wei {
+        math::exact::u256_to_f64(weth_out - amount_in_wei) / 1e18
+    } else {
+        -(math::exact::u256_to_f64(amount_in_wei - weth_out) / 1e18)
+    };
+    Ok(pnl_weth * eth_price_quote - gas_cost_quote)
+}
+
+/// Likidite ağırlıklı referans fiyat (kâr matematiğindeki quote ↔ WETH
+/// dönüşümleri için). Düz ortalamada sığ havuzun bozuk spot'u referansı
+/// kendine çeker; burada her fiyat havuzun WETH derinliğiyle ağırlıklanır.
+pub fn liquidity_weighted_mid(price_a: f64, depth_a: f64, price_b: f64, depth_b: f64) -> f64 {
+    let (w_a, w_b) = (depth_a.max(0.0), depth_b.max(0.0));
+    let total = w_a + w_b;
+    if total > 0.0 && total.is_finite() {
+        (price_a * w_a + price_b * w_b) / total
+    } else {
+        (price_a + price_b) / 2.0
+    }
+}
+
 /// Her iki havuzun fiyatlar�n� kar��la�t�r ve f�rsat varsa tespit et
 ///
 /// F�rsat Ko�ullar�:
 ///   1. Her iki havuz aktif ve veriler taze
 ///   2. Fiyat fark� (spread) > minimum e�ik
 ///   3. Newton-Raphson ile hesaplanan k�r > minimum net k�r
+#[allow(clippy::too_many_arguments)]
 pub fn check_arbitrage_opportunity(
     pools: &[PoolConfig],
     states: &[SharedPoolState],
@@ -59,70 +281,52 @@ pub fn check_arbitrage_opportunity(
     block_base_fee: u64,
     last_simulated_gas: Option<u64>,
     l1_data_fee_wei: u128,
+    competition: &CompetitionSnapshot,
+    filters: &FilterChain,
+    quoter: Option<&SimulationEngine>,
 ) -> Option<ArbitrageOpportunity> {
     if pools.len() < 2 || states.len() < 2 {
         return None;
     }
 
-    // Read lock — çok kısa süreli
-    let state_a = states[0].load_full().as_ref().clone();
-    let state_b = states[1].load_full().as_ref().clone();
+    // Arc klonu — PoolState (bitmap dahil) kopyalanmaz. Ana döngü buraya
+    // pool_snapshot ile donmuş state'ler verir; sonraki aşamalar aynı sürümü okur.
+    let state_a = states[0].load_full();
+    let state_b = states[1].load_full();
+    // Pending swap tahmini, onaylı state'ten tazeyse ve zincir başı güven
+    // penceresindeyse onaylı fiyatın yerine geçer
+    let head_block = state_a.last_block.max(state_b.last_block);
+    let state_a = state_a.with_prediction(head_block, config.predicted_state_trust_blocks);
+    let state_b = state_b.with_prediction(head_block, config.predicted_state_trust_blocks);
 
-    // v10.0: Hard-Abort — Stale Data Guard (is_fresh = is_active + staleness eşiği)
-    // is_active(): is_initialized && !is_stale && eth_price>0 && liquidity>0
-    // is_fresh(): is_active() + staleness_ms() <= max_staleness_ms
-    // Havuz verisinin yaşı max_staleness_ms eşiğini aştığında HARD-ABORT.
-    // Eski/bayat veri ile arbitraj hesaplamak hayalet kâr üretir ve kesin fon kaybına yol açar.
-    {
-        let fresh_a = state_a.is_fresh(config.max_staleness_ms);
-        let fresh_b = state_b.is_fresh(config.max_staleness_ms);
-        if !fresh_a || !fresh_b {
-            if state_a.is_active() && state_b.is_active() {
-                // Havuzlar aktif ama veri yaşlı — HARD-ABORT loglama
-                eprintln!(
-                    "     \u{1f6a8} [HARD-ABORT] Stale data detected! A={}ms B={}ms (threshold={}ms) — opportunity CANCELLED",
-                    state_a.staleness_ms(), state_b.staleness_ms(), config.max_staleness_ms,
-                );
-            }
-            return None;
-        }
-    }
+    // v10.0: Hard-Abort (stale data) artık "staleness" filtresinde —
+    // opportunity_filter::StalenessFilter, PreSizing aşamasında ilk sırada.
 
     // ��� v19.0: Havuz Komisyon G�venlik Tavan� (Sadece Uyar�) �����
     // v19.0: Statik fee reddi kald�r�ld�. Komisyon filtresi art�k
     // PreFilter'�n dinamik net k�rl�l�k hesab�n�n par�as�.
     // Sadece �ok y�ksek fee'li havuzlarda (>max_pool_fee_bps) g�venlik reddi.
-    {
-        let fee_a_bps = state_a.live_fee_bps.unwrap_or(pools[0].fee_bps);
-        let fee_b_bps = state_b.live_fee_bps.unwrap_or(pools[1].fee_bps);
-        if fee_a_bps > config.max_pool_fee_bps || fee_b_bps > config.max_pool_fee_bps {
-            eprintln!(
-                "     \u{23ed}\u{fe0f} [FeeFilter] Pool fee exceeds safety ceiling: A={}bps B={}bps (max={}bps)",
-                fee_a_bps, fee_b_bps, config.max_pool_fee_bps,
-            );
-            return None;
-        }
-        // v19.0: Y�ksek ama kabul edilebilir fee'ler loglans�n
-        let total_fee_bps = fee_a_bps + fee_b_bps;
-        if total_fee_bps > 30 {
-            eprintln!(
-                "     \u{2139}\u{fe0f} [FeeInfo] High total fee: A={}bps + B={}bps = {}bps \u{2192} delegated to dynamic profitability check",
-                fee_a_bps, fee_b_bps, total_fee_bps,
-            );
-        }
-    }
+    let fee_a_bps = pools[0].effective_fee(state_a.live_fee_bps).bps();
+    let fee_b_bps = pools[1].effective_fee(state_b.live_fee_bps).bps();
+    // Tavan reddi "pool_health" filtresinde; burada sadece değerler okunur.
 
     let price_a = state_a.eth_price_usd;
     let price_b = state_b.eth_price_usd;
-
-    // Spread hesapla
-    let spread = (price_a - price_b).abs();
-    let min_price = price_a.min(price_b);
-    let spread_pct = if min_price > 0.0 {
-        (spread / min_price) * 100.0
-    } else {
+    if price_a.min(price_b) <= 0.0 {
         return None;
-    };
+    }
+
+    // Spread referans boyuttaki yürütülebilir fiyatlardan hesaplanır —
+    // sığ havuzun spot'undaki hayali spread NR'ye ulaşmaz
+    let exec_prices = PairPrices::new(
+        config.sizing_numerics,
+        pools,
+        &state_a,
+        &state_b,
+        config.spread_probe_size_weth,
+    );
+    let (spread_a_to_b, spread_b_to_a) = exec_prices.spreads();
+    let spread_pct = spread_a_to_b.max(spread_b_to_a);
 
     // L1 data fee � WETH (t�m gas hesaplar�nda kullan�lacak)
     let l1_data_fee_weth = l1_data_fee_wei as f64 / 1e18;
@@ -134,7 +338,13 @@ pub fn check_arbitrage_opportunity(
     // PreFilter'dan �NCE hesaplan�r ve probe_amount olarak kullan�l�r.
 
     // Y�n belirleme: Ucuzdan al, pahal�ya sat
-    let (buy_idx, sell_idx) = if price_a < price_b {
+    // Yürütülebilir spread iki yönde de kapalıysa spot yönü korunur
+    let a_is_cheaper = if spread_a_to_b != spread_b_to_a {
+        spread_a_to_b > spread_b_to_a
+    } else {
+        price_a < price_b
+    };
+    let (buy_idx, sell_idx) = if a_is_cheaper {
         (0, 1) // A ucuz, B pahal�
     } else {
         (1, 0) // B ucuz, A pahal�
@@ -142,7 +352,6 @@ pub fn check_arbitrage_opportunity(
 
     let buy_state = if buy_idx == 0 { &state_a } else { &state_b };
     let sell_state = if sell_idx == 0 { &state_a } else { &state_b };
-    let avg_price_in_quote = (price_a + price_b) / 2.0;
 
     // ��� TickBitmap referanslar� (varsa + v28.0: tazelik do�rulamas�) �
     // v28.0: TickBitmap'in ya�� tick_bitmap_max_age_blocks'u a��yorsa
@@ -151,173 +360,275 @@ pub fn check_arbitrage_opportunity(
     let current_block = sell_state.last_block.max(buy_state.last_block);
     let bitmap_max_age = config.tick_bitmap_max_age_blocks;
 
-    let sell_bitmap_stale = sell_state.tick_bitmap.as_ref().is_some_and(|bm| {
-        current_block.saturating_sub(bm.snapshot_block) > bitmap_max_age
-    });
-    let buy_bitmap_stale = buy_state.tick_bitmap.as_ref().is_some_and(|bm| {
-        current_block.saturating_sub(bm.snapshot_block) > bitmap_max_age
-    });
-
-    // v30.0: Bitmap VARDI ama stale ise havuzu bu blok icin tamamen atla.
-    // Bitmap hic yoksa (None) single-tick fallback devam eder (yeni havuzlar).
-    if sell_bitmap_stale || buy_bitmap_stale {
-        eprintln!(
-            "     [TickBitmap] Stale bitmap detected -- skipping pool entirely this block (age_limit={})",
-            bitmap_max_age,
-        );
-        return None;
-    }
+    // v30.0: Bitmap VARDI ama stale ise havuz bu blok atlanır ("pool_health").
+    // Bitmap hic yoksa (None) single-tick fallback devam eder (yeni havuzlar)
+    // ama kalite Fallback olarak işaretlenir ("bitmap_quality" filtresi).
+    let bitmap_quality = BitmapQuality::classify(
+        sell_state.tick_bitmap.as_deref(),
+        buy_state.tick_bitmap.as_deref(),
+        current_block,
+        bitmap_max_age,
+    );
 
-    let sell_bitmap = sell_state.tick_bitmap.as_ref().filter(|bm| {
-        current_block.saturating_sub(bm.snapshot_block) <= bitmap_max_age
-    });
-    let buy_bitmap = buy_state.tick_bitmap.as_ref().filter(|bm| {
-        current_block.saturating_sub(bm.snapshot_block) <= bitmap_max_age
-    });
+    let sell_bitmap = sell_state
+        .tick_bitmap
+        .as_deref()
+        .filter(|bm| bm.is_fresh(current_block, bitmap_max_age));
+    let buy_bitmap = buy_state
+        .tick_bitmap
+        .as_deref()
+        .filter(|bm| bm.is_fresh(current_block, bitmap_max_age));
 
     // ��� v11.0: Hard Liquidity Cap � PreFilter + NR �ncesi Havuz Derinlik Kontrol� �
     // Havuzun ger�ek mevcut likiditesini hesapla (TickBitmap'ten).
     // WETH/USDC havuzlar�nda 18 vs 6 decimal uyumsuzlu�u burada yakalan�r.
     // v27.0: effective_cap art�k PreFilter'a da beslenir (probe_amount).
-    let sell_hard_cap = math::exact::hard_liquidity_cap_weth(
-        sell_state.sqrt_price_x96,
-        sell_state.liquidity,
-        sell_state.tick,
-        pools[sell_idx].token0_is_weth,
-        sell_bitmap,
-        pools[sell_idx].tick_spacing,
-    );
-    let buy_hard_cap = math::exact::hard_liquidity_cap_weth(
-        buy_state.sqrt_price_x96,
-        buy_state.liquidity,
-        buy_state.tick,
-        pools[buy_idx].token0_is_weth,
-        buy_bitmap,
-        pools[buy_idx].tick_spacing,
-    );
+    let sell_hard_cap = math::exact::pool_liquidity_cap_weth(sell_state, &pools[sell_idx], sell_bitmap);
+    let buy_hard_cap = math::exact::pool_liquidity_cap_weth(buy_state, &pools[buy_idx], buy_bitmap);
     let effective_cap = sell_hard_cap.min(buy_hard_cap);
 
-    // v28.0: S�� havuz ��k�� kap�s� � effective_cap ile gas maliyetini kar��la�t�r.
-    // Havuz derinli�i gas maliyetinin 10 kat�ndan azsa, k�rl� i�lem imk�ns�z.
-    // Bu erken ��k��, NR + PreFilter hesaplamalar�n� tamamen atlar � CPU tasarrufu.
-    if effective_cap <= 0.001 {
-        eprintln!(
-            "     \u{23ed}\u{fe0f} [Liquidity] Insufficient liquidity — skipping NR (cap={:.6} WETH)",
-            effective_cap,
+    // Quote ↔ WETH dönüşümleri için likidite ağırlıklı referans fiyat
+    let (depth_a, depth_b) = if buy_idx == 0 {
+        (buy_hard_cap, sell_hard_cap)
+    } else {
+        (sell_hard_cap, buy_hard_cap)
+    };
+    let reference_price = liquidity_weighted_mid(price_a, depth_a, price_b, depth_b);
+
+    // Yetersiz likidite + sığ havuz ekonomisi artık "pool_health" filtresinde.
+
+    // OPT-I: Gas cost tek sefer hesapla — PreFilter ve NR ayni degeri kullanir.
+    // OPT-H: Guvenlik marji %20 -> %10 (Base L2'de gas spike nadir).
+    // Gas bileşimi: taban + geçiş başına gas × (iki bacağın tick geçişi);
+    // model oturana kadar son REVM gas'ı boyuttan bağımsız kullanılır.
+    let gas_estimate = crate::gas_calibration::estimate(last_simulated_gas);
+    let gas_cost_weth_at = |crossings: u32| {
+        gas_cost_weth(gas_estimate.units(crossings), block_base_fee, l1_data_fee_weth, config.gas_cost_fallback_weth)
+    };
+    // PreFilter geçişsiz (en küçük) gas ile — boyut henüz bilinmiyor
+    let dynamic_gas_cost_weth = gas_cost_weth_at(0);
+
+    // PreFilter — sonuç "prefilter" filtresine beslenir
+    let probe_amount = f64::min(config.max_trade_size_weth * 0.5, effective_cap);
+    let prefilter = math::PreFilter {
+        fee_a: pools[0].effective_fee(state_a.live_fee_bps).fraction(),
+        fee_b: pools[1].effective_fee(state_b.live_fee_bps).fraction(),
+        estimated_gas_cost_weth: dynamic_gas_cost_weth,
+        min_profit_weth: config.min_net_profit_weth,
+        bribe_pct: config.effective_bribe_pct(),
+    };
+    let prefilter_result = exec_prices.prefilter(&prefilter, buy_idx, probe_amount);
+
+    // ── Filtre Zinciri: PreSizing (staleness → pool_health → prefilter) ──
+    // Kesin ret NR'yi tamamen atlar — CPU tasarrufu.
+    let mut ctx = OpportunityContext::new(config, &state_a, &state_b);
+    ctx.fee_a_bps = fee_a_bps;
+    ctx.fee_b_bps = fee_b_bps;
+    ctx.spread_pct = spread_pct;
+    ctx.bitmap_quality = bitmap_quality;
+    ctx.sell_hard_cap = sell_hard_cap;
+    ctx.buy_hard_cap = buy_hard_cap;
+    ctx.effective_cap = effective_cap;
+    ctx.gas_cost_weth = dynamic_gas_cost_weth;
+    ctx.probe_amount = probe_amount;
+    ctx.prefilter = Some(prefilter_result);
+    ctx.direction = Some(DirectionKey {
+        buy_pool: pools[buy_idx].address,
+        sell_pool: pools[sell_idx].address,
+    });
+    ctx.buy_price_quote = buy_state.eth_price_usd;
+    ctx.sell_price_quote = sell_state.eth_price_usd;
+    ctx.competition = *competition;
+
+    let mut shadow_only = match filters.run(FilterStage::PreSizing, &ctx) {
+        ChainOutcome::Reject(_) => return None,
+        ChainOutcome::Degrade(verdict) => Some(verdict),
+        ChainOutcome::Pass => None,
+    };
+
+    // v19.0: Yüksek ama kabul edilebilir fee'ler loglansın
+    let total_fee_bps = fee_a_bps + fee_b_bps;
+    if total_fee_bps > 30 {
+        eprintln_high!(
+            "     \u{2139}\u{fe0f} [FeeInfo] High total fee: A={}bps + B={}bps = {}bps \u{2192} delegated to dynamic profitability check",
+            fee_a_bps, fee_b_bps, total_fee_bps,
         );
-        return None;
     }
-
-    // v28.0: Dinamik likidite uyar�s� + ekonomik uygulanabilirlik kontrol�
+    // v28.0: Dinamik likidite uyarısı
     if effective_cap < config.max_trade_size_weth * 0.1 {
-        eprintln!(
+        eprintln_high!(
             "     \u{26a0}\u{fe0f} [Liquidity] Pool depth shallow: sell_cap={:.4} buy_cap={:.4} effective_cap={:.4} WETH (MAX_TRADE={:.1})",
             sell_hard_cap, buy_hard_cap, effective_cap, config.max_trade_size_weth,
         );
-        // v28.0: S�� havuzda gas maliyetini kar��layacak spread var m�?
-        // Kaba tahmin: effective_cap * spread_pct/100 < min_net_profit � kesinlikle k�rs�z
-        let max_possible_gross = effective_cap * spread_pct / 100.0;
-        if max_possible_gross < config.min_net_profit_weth {
-            eprintln!(
-                "     \u{23ed}\u{fe0f} [EconViability] Shallow pool + low spread — profit impossible: max_gross={:.8} < min_profit={:.8} WETH",
-                max_possible_gross, config.min_net_profit_weth,
-            );
-            return None;
-        }
     }
-
-    // OPT-I: Gas cost tek sefer hesapla — PreFilter ve NR ayni degeri kullanir.
-    // OPT-H: Guvenlik marji %20 -> %10 (Base L2'de gas spike nadir).
-    let gas_estimate: u64 = last_simulated_gas.unwrap_or(200_000);
-    let dynamic_gas_cost_weth = if block_base_fee > 0 {
-        let l2 = (gas_estimate as f64 * block_base_fee as f64) / 1e18;
-        ((l2 + l1_data_fee_weth) * 1.10).max(0.000001)
-    } else {
-        ((config.gas_cost_fallback_weth + l1_data_fee_weth) * 1.10).max(0.000001)
-    };
-
-    // PreFilter
-    {
-        let pre_filter = math::PreFilter {
-            fee_a: state_a.live_fee_bps.map(|b| b as f64 / 10_000.0).unwrap_or(pools[0].fee_fraction),
-            fee_b: state_b.live_fee_bps.map(|b| b as f64 / 10_000.0).unwrap_or(pools[1].fee_fraction),
-            estimated_gas_cost_weth: dynamic_gas_cost_weth,
-            min_profit_weth: config.min_net_profit_weth,
-            bribe_pct: config.bribe_pct,
-        };
-
-        let probe_amount = f64::min(config.max_trade_size_weth * 0.5, effective_cap);
-
-        match pre_filter.check(price_a, price_b, probe_amount) {
-            math::PreFilterResult::Unprofitable { reason } => {
-                eprintln!(
-                    "     \u{23ed}\u{fe0f} [PreFilter] Spread {:.4}% | {:?} | gas={:.8} WETH | probe={:.4} WETH",
-                    spread_pct,
-                    reason,
-                    dynamic_gas_cost_weth,
-                    probe_amount,
-                );
-                return None;
-            }
-            math::PreFilterResult::Profitable { estimated_profit_weth, spread_ratio } => {
-                eprintln!(
-                    "     \u{2705} [PreFilter] PASSED | spread_ratio={:.6} | est_profit={:.8} WETH | probe={:.4} WETH",
-                    spread_ratio,
-                    estimated_profit_weth,
-                    probe_amount,
-                );
-            }
-        }
+    if let math::PreFilterResult::Profitable { estimated_profit_weth, spread_ratio } = prefilter_result {
+        eprintln_high!(
+            "     \u{2705} [PreFilter] PASSED | spread_ratio={:.6} | est_profit={:.8} WETH | probe={:.4} WETH",
+            spread_ratio,
+            estimated_profit_weth,
+            probe_amount,
+        );
     }
 
-    // Gas cost'u quote cinsine cevir (NR icin)
-    let dynamic_gas_cost_quote = dynamic_gas_cost_weth * avg_price_in_quote;
+    // Gas cost'u quote cinsine cevir (NR icin, boyuttaki geçiş sayısına göre)
+    let gas_cost_quote_at = |crossings: u32| gas_cost_weth_at(crossings) * reference_price;
 
     // ��� Newton-Raphson Optimal Miktar Hesaplama ������������������
     // v6.0: TickBitmap varsa multi-tick hassasiyetinde, yoksa dampening
     // v16.0: Canl� on-chain fee kullan�m� (live_fee_bps varsa statik fee yerine)
-    let sell_fee = sell_state.live_fee_bps.map(|b| b as f64 / 10_000.0).unwrap_or(pools[sell_idx].fee_fraction);
-    let buy_fee = buy_state.live_fee_bps.map(|b| b as f64 / 10_000.0).unwrap_or(pools[buy_idx].fee_fraction);
+    let sell_fee = pools[sell_idx].effective_fee(sell_state.live_fee_bps).fraction();
+    let buy_fee = pools[buy_idx].effective_fee(buy_state.live_fee_bps).fraction();
     // v28.0: NR'ye max_trade_size_weth yerine effective_cap g�nder.
     // Eski: config.max_trade_size_weth (50.0) � NR i�inde tekrar cap hesapl�yor,
     //        �ift hesaplama + s�� havuzlarda gereksiz tarama aral���.
     // Yeni: effective_cap zaten min(sell_cap, buy_cap) olarak hesapland�,
     //        NR bunu �st s�n�r olarak al�r � tutarl� ve h�zl�.
     let nr_max = effective_cap.min(config.max_trade_size_weth);
-    let nr_result = math::find_optimal_amount_with_bitmap(
+    let math_nr = || {
+        math::find_optimal_amount_with_bitmap(
+            sell_state,
+            sell_fee,
+            buy_state,
+            buy_fee,
+            &gas_cost_quote_at,
+            reference_price, // ger�ek fiyat � k�r quote cinsinden d�ner
+            nr_max,
+            config.min_trade_size_weth,
+            pools[sell_idx].token0_is_weth,
+            pools[sell_idx].tick_spacing,
+            pools[buy_idx].tick_spacing,
+            sell_bitmap,
+            buy_bitmap,
+            pools[buy_idx].token0_is_weth,
+            config.sizing_numerics,
+        )
+    };
+
+    // Bitmap yoksa tek aralık matematiği kaba kalır — SIZING_BACKEND izin
+    // veriyorsa kâr fonksiyonu havuz bytecode'u üzerinde REVM quote kullanır.
+    let revm_ready = quoter
+        .is_some_and(|q| q.can_quote(&pools[sell_idx]) && q.can_quote(&pools[buy_idx]));
+    let sizing = config
+        .sizing_backend
+        .resolve(sell_bitmap.is_some() && buy_bitmap.is_some(), revm_ready);
+    // REVM quote geçiş sayısı vermez — boyuttaki geçişler exact yoldan sayılır
+    let crossing_counter = math::CrossingCounter::new(
         sell_state,
         sell_fee,
         buy_state,
         buy_fee,
-        dynamic_gas_cost_quote,
-        avg_price_in_quote, // ger�ek fiyat � k�r quote cinsinden d�ner
-        nr_max,
         pools[sell_idx].token0_is_weth,
-        pools[sell_idx].tick_spacing,
-        pools[buy_idx].tick_spacing,
+        pools[buy_idx].token0_is_weth,
         sell_bitmap,
         buy_bitmap,
-        pools[buy_idx].token0_is_weth,
     );
+    let (nr_result, sizing) = match (sizing, quoter) {
+        (SizingBackend::Revm, Some(engine)) => {
+            let profit_fn = |amount: f64| {
+                let crossings = crossing_counter.at(amount).map_or(0, |c| c.total());
+                revm_arbitrage_profit(
+                    engine,
+                    &pools[sell_idx],
+                    sell_state,
+                    &pools[buy_idx],
+                    buy_state,
+                    amount,
+                    gas_cost_quote_at(crossings),
+                    reference_price,
+                )
+            };
+            // Tek küçük probe: havuz eksik state ile swap edemiyorsa matematiğe dön
+            match profit_fn(REVM_PROBE_WETH) {
+                Ok(_) => (
+                    math::find_optimal_amount_with(
+                        |amount| profit_fn(amount).unwrap_or(f64::NEG_INFINITY),
+                        nr_max,
+                        config.min_trade_size_weth,
+                        reference_price,
+                        math::NrBudget::REVM,
+                    ),
+                    SizingBackend::Revm,
+                ),
+                Err(e) => {
+                    eprintln_high!(
+                        "     \u{26a0}\u{fe0f} [Sizing] REVM quote failed ({}) \u{2192} math fallback",
+                        e
+                    );
+                    (math_nr(), SizingBackend::Math)
+                }
+            }
+        }
+        _ => (math_nr(), SizingBackend::Math),
+    };
+
+    // Büyük boyutta f64 optimumu exact kârın tepesinden kayabilir (çok tick
+    // geçişi) — iki bitmap varsa optimum minProfit'in swap yolunda yeniden aranır
+    let nr_result = if sizing == SizingBackend::Math
+        && sell_bitmap.is_some()
+        && buy_bitmap.is_some()
+        && nr_result.optimal_amount >= config.exact_sizing_min_weth
+    {
+        refine_exact_optimum(
+            pools,
+            &state_a,
+            &state_b,
+            buy_idx,
+            reference_price,
+            config.min_trade_size_weth,
+            nr_max,
+            nr_result,
+            &|amount: f64| gas_cost_quote_at(crossing_counter.at(amount).map_or(0, |c| c.total())),
+        )
+    } else {
+        nr_result
+    };
 
     // NR k�r� quote (cbBTC) cinsinden d�nd� � WETH�e �evir
-    let expected_profit_weth = if avg_price_in_quote > 0.0 {
-        nr_result.expected_profit / avg_price_in_quote
+    let expected_profit_weth = if reference_price > 0.0 {
+        nr_result.expected_profit / reference_price
     } else {
         return None;
     };
 
+    // Flash loan primi — fee_monitor'ün yürürlükteki değeri (Aave FLASHLOAN_PREMIUM_TOTAL)
+    let flash_fee_weth =
+        nr_result.optimal_amount * crate::fee_monitor::effective_flash_loan_fee_bps() / 10_000.0;
+    let expected_profit_weth = expected_profit_weth - flash_fee_weth;
+
+    // Optimumdaki geçişler → gas kalemi ve yürütme gas tahmini
+    let tick_crossings = crossing_counter.at(nr_result.optimal_amount);
+    let optimum_crossings = tick_crossings.map_or(0, |c| c.total());
+    let modeled_gas = tick_crossings.map(|c| gas_estimate.units(c.total()));
+    let dynamic_gas_cost_weth = gas_cost_weth_at(optimum_crossings);
+
+    // Kâr ayrıştırması: NR optimumundaki exact swap yolundan bacak ücretleri
+    // (REVM boyutlandırmada da bacaklar exact matematikle okunur)
+    let profit_breakdown = math::compute_arbitrage_profit_breakdown_with_bitmap(
+        nr_result.optimal_amount,
+        sell_state,
+        sell_fee,
+        buy_state,
+        buy_fee,
+        math::AttributionCosts { flash_fee_weth, gas_weth: dynamic_gas_cost_weth },
+        pools[sell_idx].token0_is_weth,
+        pools[buy_idx].token0_is_weth,
+        sell_bitmap,
+        buy_bitmap,
+    );
+
     // v15.0 DEBUG: NR sonu� detaylar� � f�rsat filtreleme nedenini g�ster
     // (Bu loglar canl�ya ge�i� onay�na kadar kald�r�lmamal�)
-    eprintln!(
-        "     \u{1f52c} [DEBUG NR] spread={:.4}% | nr_profit_weth={:.8} | min_required={:.8} | nr_amount={:.6} | converged={} | gas_cost_weth={:.8} (L1={:.8})",
+    eprintln_high!(
+        "     \u{1f52c} [DEBUG NR] spread={:.4}% | nr_profit_weth={:.8} | min_required={:.8} | nr_amount={:.6} | converged={} | sizing={} | gas_cost_weth={:.8} (L1={:.8}, {} ticks crossed)",
         spread_pct,
         expected_profit_weth,
         config.min_net_profit_weth,
         nr_result.optimal_amount,
         nr_result.converged,
+        sizing,
         dynamic_gas_cost_weth,
         l1_data_fee_weth,
+        optimum_crossings,
     );
 
     // K�rl� de�ilse f�rsat� atla
@@ -329,19 +640,73 @@ pub fn check_arbitrage_opportunity(
     } else {
         0.0
     };
-    if nr_result.optimal_amount <= 0.0
-        || (expected_profit_weth < config.min_net_profit_weth && roi < config.min_profit_roi)
-    {
-        eprintln!(
-            "     [ProfitGate] Unprofitable -- profit={:.8} WETH, roi={:.6}%, min_profit={:.8}, min_roi={:.4}%",
-            expected_profit_weth,
-            roi * 100.0,
-            config.min_net_profit_weth,
-            config.min_profit_roi * 100.0,
-        );
-        return None;
+
+    // ── Sermaye riski: rakip aynı rotada bizden önce işlem yaparsa ──
+    // Exact matematikle iki havuz da ilerletilir; kâr eşiği zincirde değerlendirilir.
+    // Degradation ladder 3. basamakta atılır (filtre ölçümsüz geçer).
+    let frontrun_weth = config.adverse_frontrun.resolve(nr_result.optimal_amount);
+    let adverse = (frontrun_weth > 0.0
+        && expected_profit_weth > 0.0
+        && crate::degradation::allows(crate::degradation::ADVERSE_SCENARIO))
+    .then(|| {
+        evaluate_adverse_scenario(
+            pools,
+            &state_a,
+            &state_b,
+            buy_idx,
+            reference_price,
+            nr_result.optimal_amount,
+            frontrun_weth,
+        )
+    });
+
+    // ── Filtre Zinciri: PostSizing (profit_floor → bitmap_quality → block_pin → adverse_selection → competition) ──
+    // Bitmap kalitesi, blok sabitleme, ters seçilim ve kuyruk derinliği cezası Degrade döner: fırsat gölge log için korunur.
+    ctx.optimal_amount_weth = nr_result.optimal_amount;
+    ctx.expected_profit_weth = expected_profit_weth;
+    ctx.gas_cost_weth = dynamic_gas_cost_weth;
+    ctx.roi = roi;
+    ctx.adverse = adverse.clone();
+    match filters.run(FilterStage::PostSizing, &ctx) {
+        ChainOutcome::Reject(_) => return None,
+        ChainOutcome::Degrade(verdict) => {
+            shadow_only.get_or_insert(verdict);
+        }
+        ChainOutcome::Pass => {}
     }
 
+    // Optimum bir bacakta taranan bitmap kenarına dayanıyorsa işaretle —
+    // eski/eksik bitmap'te kenar zaten anlamsız (genişletme tazelemez)
+    let scan_boundary = if bitmap_quality == BitmapQuality::Real && nr_result.optimal_amount > 0.0 {
+        detect_scan_boundary(
+            pools,
+            &state_a,
+            &state_b,
+            buy_idx,
+            reference_price,
+            nr_result.optimal_amount,
+        )
+    } else {
+        None
+    };
+
+    // Flash kaynağı seçimi: iki sıralama da exact motorla boyutlandırılır.
+    // Kontrat pool_a=UniV3 kaynağını sabit bekler — sonuç yalnızca raporlanır.
+    // Degradation ladder 5. basamakta atılır (tek yönlü tespit sürer).
+    let flash_route = (expected_profit_weth > 0.0
+        && crate::degradation::allows(crate::degradation::BOTH_DIRECTIONS))
+    .then(|| {
+        compare_flash_routes(
+            pools,
+            &state_a,
+            &state_b,
+            buy_idx,
+            reference_price,
+            nr_max,
+            crate::fee_monitor::effective_flash_loan_fee_bps(),
+        )
+    });
+
     Some(ArbitrageOpportunity {
         buy_pool_idx: buy_idx,
         sell_pool_idx: sell_idx,
@@ -350,8 +715,32 @@ pub fn check_arbitrage_opportunity(
         buy_price_quote: buy_state.eth_price_usd,
         sell_price_quote: sell_state.eth_price_usd,
         spread_pct,
+        reference_price_quote: reference_price,
         nr_converged: nr_result.converged,
         nr_iterations: nr_result.iterations,
+        shadow_only,
+        bitmap_quality,
+        adverse,
+        sensitivity: None,
+        two_step: None,
+        scan_boundary,
+        flash_route,
+        profit_breakdown,
+        tick_crossings,
+        modeled_gas,
+        priced_gas: gas_estimate.units(optimum_crossings),
+    })
+}
+
+/// Sabit çarpım (V2) bacaklı fırsat yalnızca gölge: kontratta yalnızca
+/// `IUniswapV3Pool.swap` + `uniswapV3SwapCallback` var, REVM de V2 havuz
+/// state'ini enjekte etmez — yürütülen işlem zincirde revert eder.
+/// Kontrata V2 swap yolu eklenene kadar fiyatlanır, loglanır, gönderilmez.
+pub fn constant_product_verdict(pools: &[PoolConfig]) -> Option<crate::opportunity_filter::FilterVerdict> {
+    let pool = pools.iter().find(|p| p.is_constant_product())?;
+    Some(crate::opportunity_filter::FilterVerdict {
+        filter: "constant_product",
+        reason: format!("{} is constant-product — executor contract has no V2 swap path", pool.name),
     })
 }
 
@@ -379,53 +768,52 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
     block_timestamp: u64,
     block_base_fee: u64,
     block_latency_ms: f64,
-    _l1_data_fee_wei: u128,
+    l1_data_fee_wei: u128,
     mev_executor: &Arc<crate::executor::MevExecutor>,
     telegram_sender: &Option<crate::telegram::TelegramSender>,
     tg_counters: &mut crate::telegram::TelemetryCounters,
+    filters: &FilterChain,
+    live: &mut LiveOpportunities,
 ) -> Option<u64> {
     let _buy_pool = &pools[opportunity.buy_pool_idx];
     let _sell_pool = &pools[opportunity.sell_pool_idx];
 
-    // ��� v12.0: S�f�ra B�l�nme / NaN / Infinity Korumas� �������������
-    // RPC kopuklu�u veya s�f�r sqrtPriceX96 durumunda fiyatlar 0.0 olabilir.
-    // Float b�l�m sonucu Infinity � u128'e cast'te Rust panic! verir.
-    // Bu kontrol thread ��kmesini �nler ve d�ng�y� sessizce atlar.
-    if opportunity.sell_price_quote <= 0.0
-        || opportunity.buy_price_quote <= 0.0
-        || opportunity.optimal_amount_weth <= 0.0
-        || !opportunity.expected_profit_weth.is_finite()
-    {
-        return None;
-    }
-
-    // ��� v28.0: Veri Tazeli�i Kap�s� (Freshness Gate) ��������������
-    // Eski veriyle yap�lan sim�lasyon ve i�lem, frontrun/sandwich sald�r�lar�na
-    // kar�� savunmas�zd�r. ��lem g�nderilmeden �nce havuz verilerinin
-    // max_staleness_ms e�i�ini a�mad��� do�rulan�r.
+    // ── Filtre Zinciri: Execution (execution_sanity → freshness_gate) ──
+    // v12.0 NaN/Infinity koruması ve v28.0 Freshness Gate artık isimli filtreler.
+    // `states` fırsatın donmuş snapshot'ı (pool_snapshot) — tespitteki sürümle
+    // aynı; freshness_gate verinin yaşını yürütme anında ölçer (MEV koruması).
     {
         let state_a_guard = states[0].load();
         let state_b_guard = states[1].load();
-        if !state_a_guard.is_fresh(config.max_staleness_ms) || !state_b_guard.is_fresh(config.max_staleness_ms) {
-            let staleness_a = state_a_guard.staleness_ms();
-            let staleness_b = state_b_guard.staleness_ms();
-            drop(state_a_guard);
-            drop(state_b_guard);
-            eprintln!(
-                "     \u{1f6d1} [FreshnessGate] Pool data too old or stale: A={}ms B={}ms (threshold={}ms) — MEV protection: trade skipped",
-                staleness_a, staleness_b, config.max_staleness_ms,
-            );
+        let mut ctx = OpportunityContext::new(config, &state_a_guard, &state_b_guard);
+        ctx.optimal_amount_weth = opportunity.optimal_amount_weth;
+        ctx.expected_profit_weth = opportunity.expected_profit_weth;
+        ctx.buy_price_quote = opportunity.buy_price_quote;
+        ctx.sell_price_quote = opportunity.sell_price_quote;
+        ctx.spread_pct = opportunity.spread_pct;
+        ctx.sensitivity = opportunity.sensitivity.clone();
+        if let ChainOutcome::Reject(_) = filters.run(FilterStage::Execution, &ctx) {
             return None;
         }
     }
 
+    // ── Toz koruması: taban altı / geçersiz miktar calldata'ya ulaşmaz ──
+    // Simülasyon, snapshot ve yürütme calldata'sı bu miktardan kurulur.
+    let trade_amount = match execution_trade_amount(opportunity, pools, config) {
+        Ok(amount) => amount,
+        Err(e) => {
+            eprintln_high!("     {} [DustGuard] opportunity dropped: {}", "🧹".yellow(), e);
+            return None;
+        }
+    };
+
     // ��� �statistik G�ncelle �������������������������������������
     // v15.0: total_opportunities ve max_spread_pct art�k main.rs'de
     // her blokta g�ncelleniyor (f�rsat ko�ulundan ba��ms�z).
     // Burada sadece sim�lasyona �zg� istatistikler kal�yor.
 
-    // ��� REVM Sim�lasyonu ��������������������������������������
-    let sim_result = sim_engine.validate_mathematical(
+    // ── 1. Matematiksel doğrulama (her zaman — gas sezgisel tahmin) ──
+    let math_result = sim_engine.validate_mathematical(
         pools,
         states,
         opportunity.buy_pool_idx,
@@ -433,8 +821,9 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
         opportunity.optimal_amount_weth,
     );
 
-    // Kontrat adresi varsa tam REVM sim�lasyonu da yap
-    let revm_result = if let Some(contract_addr) = config.contract_address {
+    // ── 2. REVM simülasyonu (yalnızca kontrat adresi varsa — gas ölçülür) ──
+    // Yürütme detayları REVM ↔ exact matematik karşılaştırmasında da kullanılır
+    let revm_detail = if let Some(contract_addr) = config.contract_address {
         // v11.0 Calldata: Y�n ve token hesaplama
         //   buy_pool_idx=0 (UniV3 ucuz): uni=1(oneForZero�WETH al), aero=0(zeroForOne�WETH sat)
         //   buy_pool_idx=1 (Slip ucuz):  uni=0(zeroForOne�Quote al), aero=1(oneForZero�Quote sat)
@@ -445,6 +834,16 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
                 &pools[0].base_token_address,
                 &pools[0].quote_token_address,
             );
+        // REVM kârı owedToken bakiye farkıyla ölçer — exact modelle aynı birim olmalı
+        let sim_profit = OwedProfit::from_exact(&pools[0], uni_dir, U256::ZERO);
+        if !enforce_direction_invariants(
+            pools,
+            (uni_dir, aero_dir, owed_token, received_token),
+            &sim_profit,
+            stats,
+        ) {
+            return None;
+        }
 
         // === v11.0: D�NAM�K DECIMAL AMOUNT HESAPLAMA ===
         // Kritik d�zeltme: Input tokeni WETH mi Quote mi?
@@ -452,19 +851,13 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
         //   - Quote input � amount * eth_price * 10^quote_decimals
         // Eski hata: Her zaman 10^18 kullan�l�yordu � Quote input'ta
         //            hatal� hesaplama olu�uyordu.
-        let weth_input = crate::types::is_weth_input(uni_dir, pools[0].token0_is_weth);
-        let amount_wei = crate::types::weth_amount_to_input_wei(
-            opportunity.optimal_amount_weth,
-            weth_input,
-            (opportunity.buy_price_quote + opportunity.sell_price_quote) / 2.0,
-            if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals },
-        );
+        let amount_wei = trade_amount.input_wei();
 
         // v9.0: Deadline block hesapla (v11.0: minimum +3 tolerans)
         let current_block = states[0].load().last_block;
-        let deadline_block = current_block as u32 + config.deadline_blocks.max(3);
+        let deadline_block = compute_deadline_block(current_block, config);
 
-        let calldata = crate::simulator::encode_compact_calldata(
+        let calldata = crate::calldata_codec::encode_compact_calldata(
             pools[0].address,  // pool_a (always UniV3)
             pools[1].address,  // pool_b (always Slipstream)
             owed_token,
@@ -476,12 +869,10 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
             deadline_block,
         );
 
-        let caller = config.private_key.as_ref()
-            .and_then(|pk| pk.parse::<PrivateKeySigner>().ok())
-            .map(|signer| signer.address())
-            .unwrap_or_default();
+        // Başlangıçta bir kez çözülmüş imzalayıcı adresi (key parse yok)
+        let caller = mev_executor.signer_address();
 
-        sim_engine.simulate(
+        let detailed = sim_engine.simulate_detailed(
             pools,
             states,
             caller,
@@ -491,26 +882,135 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
             current_block,
             block_timestamp,
             block_base_fee,
-        )
+            Some(owed_token),
+        );
+        Some((detailed, amount_wei, uni_dir == 0, aero_dir == 0))
     } else {
-        sim_result.clone()
+        None
     };
 
-    // Dinamik gas: REVM sim�lasyonundan gelen kesin gas de�eri
-    let simulated_gas_used = revm_result.gas_used;
+    // Raporlanan sonuç: REVM koştuysa onunki, yoksa matematiksel doğrulama.
+    // Kaynak etiketi gas'ın ölçüm mü sezgisel tahmin mi olduğunu taşır.
+    let sim_result = revm_detail
+        .as_ref()
+        .map_or(&math_result, |(detail, ..)| &detail.result);
+    stats.record_sim_source(sim_result.source);
+
+    // Dinamik gas (gas limiti / bribe / sonraki blok maliyeti): yalnızca REVM
+    // ölçümü — sezgisel tahmin yerine geçiş modelinin tahmini (yoksa GAS_ESTIMATE)
+    let simulated_gas_used = sim_result.execution_gas(opportunity.modeled_gas.unwrap_or(config.gas_estimate));
+    if let (SimSource::Revm, true, Some(crossings)) =
+        (sim_result.source, sim_result.success, opportunity.tick_crossings)
+    {
+        crate::gas_calibration::record_simulation(crossings.total(), sim_result.gas_used);
+    }
 
     // Sim�lasyon ba�ar�s�z � i�lemi atla
-    if !sim_result.success {
+    if !math_result.success {
         stats.failed_simulations += 1;
         // v10.0: Circuit breaker
         stats.consecutive_failures += 1;
         tg_counters.reverts += 1;
-        print_simulation_failure(opportunity, &sim_result, pools);
+        crate::circuit_breaker::record_failure(
+            crate::circuit_breaker::FailureKind::Simulation,
+            math_result.error.as_deref().unwrap_or("math validation failed"),
+        );
+        let block = states[0].load().last_block.max(states[1].load().last_block);
+        print_simulation_failure(opportunity, &math_result, pools, block);
         return None;
     }
 
     // Sim�lasyon ba�ar�l� � ard���k ba�ar�s�zl�k sayac�n� s�f�rla
     stats.consecutive_failures = 0;
+    crate::circuit_breaker::record_validation();
+
+    // REVM'de gerçekleşen kâr exact matematikle uyuşmalı — storage layout,
+    // decimal ve kontrat muhasebe hatalarını otomatik yakalar
+    if let Some((detail, amount_wei, uni_zero_for_one, aero_zero_for_one)) = &revm_detail {
+        if let Some(revm_profit) = detail.realized_profit().filter(|_| detail.result.success) {
+            let exact_profit = exact_profit_for_amount(
+                pools,
+                &states[0].load(),
+                &states[1].load(),
+                *amount_wei,
+                *uni_zero_for_one,
+                *aero_zero_for_one,
+            );
+            let discrepancy = sim_math_discrepancy(revm_profit, exact_profit);
+            let discrepancy_u128 = u128::try_from(discrepancy).unwrap_or(u128::MAX);
+            stats.sim_math_checks += 1;
+            stats.sim_math_max_discrepancy_wei = stats.sim_math_max_discrepancy_wei.max(discrepancy_u128);
+            eprintln_high!(
+                "     🧮 [SimMath] REVM profit={} | exact profit={} | discrepancy={} wei | swaps={}",
+                revm_profit, exact_profit, discrepancy, detail.swaps.len(),
+            );
+            if config.sim_math_max_discrepancy > 0 && discrepancy_u128 > config.sim_math_max_discrepancy {
+                stats.sim_math_discrepancy_skips += 1;
+                for swap in &detail.swaps {
+                    eprintln_high!(
+                        "       Swap @ {} amount0={} amount1={}",
+                        swap.pool, swap.amount0, swap.amount1,
+                    );
+                }
+                eprintln_high!(
+                    "     🛑 [SimMath] Discrepancy {} wei > SIM_MATH_MAX_DISCREPANCY {} — execution skipped",
+                    discrepancy_u128, config.sim_math_max_discrepancy,
+                );
+                return None;
+            }
+        }
+    }
+
+    // İkinci kârlılık kapısı: tespit kârı tahmini gas ile fiyatlandı —
+    // REVM ölçtüyse net kâr simüle gas ile yeniden hesaplanır
+    let gas_check = (sim_result.source == SimSource::Revm && sim_result.success).then(|| {
+        let (state_a, state_b) = (states[0].load(), states[1].load());
+        SimGasCheck::new(
+            opportunity,
+            pools,
+            [&state_a, &state_b],
+            sim_result.gas_used,
+            block_base_fee,
+            l1_data_fee_wei as f64 / 1e18,
+            config,
+        )
+    });
+    if let Some(check) = gas_check.as_ref() {
+        stats.sim_gas_checks += 1;
+        if !check.passes(opportunity.optimal_amount_weth, config) {
+            stats.gas_rejected_opportunities += 1;
+            eprintln_high!(
+                "     ⛽ [GasGate] gas {} → {} | profit {:.8} → {:.8} WETH < min {:.8} — execution skipped",
+                check.assumed_gas, check.simulated_gas, check.pre_sim_profit_weth,
+                check.post_sim_profit_weth, config.min_net_profit_weth,
+            );
+            let mut skipped = opportunity.clone();
+            skipped.shadow_only = Some(crate::opportunity_filter::FilterVerdict {
+                filter: "sim_gas",
+                reason: format!(
+                    "post-sim profit {:.8} WETH (gas {} → {})",
+                    check.post_sim_profit_weth, check.assumed_gas, check.simulated_gas,
+                ),
+            });
+            let (state_a, state_b) = (states[0].load(), states[1].load());
+            let inputs = DecisionInputs::capture(opportunity, pools, [&**state_a, &**state_b]);
+            write_shadow_log(
+                &skipped,
+                &math_result,
+                pools,
+                state_a.last_block.max(state_b.last_block),
+                config,
+                sim_result.gas_used,
+                sim_result.source,
+                0.0,
+                block_latency_ms,
+                "sim_gas-skipped",
+                LogIntegrity::new(config, &inputs, None),
+                Some(check),
+            );
+            return Some(simulated_gas_used);
+        }
+    }
 
     // JSON structured log: profitable opportunity
     crate::json_logger::log_opportunity(
@@ -526,22 +1026,32 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
     if opportunity.expected_profit_weth > stats.max_profit_weth {
         stats.max_profit_weth = opportunity.expected_profit_weth;
     }
+    if let Some(route) = opportunity.flash_route.as_ref().filter(|r| r.is_material()) {
+        stats.reversed_route_wins += 1;
+        stats.foregone_route_improvement_weth += route.foregone_improvement_weth();
+    }
+    // Dinamik bribe tahmini (ayrıştırma + loglama) — gerçek değeri MevExecutor belirler
+    let dynamic_bribe_weth = opportunity.expected_profit_weth * config.effective_bribe_pct();
+    if let Some(breakdown) = opportunity.profit_breakdown {
+        stats.profit_attribution.record(&breakdown.with_bribe(dynamic_bribe_weth));
+    }
 
-    print_opportunity_report(opportunity, &sim_result, pools, config);
+    let report_block = states[0].load().last_block.max(states[1].load().last_block);
+    print_opportunity_report(opportunity, sim_result, pools, config, report_block);
 
     // ��� KONTRAT TET�KLEME VEYA G�LGE MOD LOGLAMA �������������
     if config.shadow_mode() {
         // === G�LGE MODU: ��lem atlan�r, detaylar loglan�r ===
 
         // v23.0 (Y-1): G�lge modu ekonomik uygulanabilirlik istatistikleri
-        if sim_result.success {
+        if math_result.success {
             stats.shadow_sim_success += 1;
             stats.shadow_cumulative_profit += opportunity.expected_profit_weth;
         } else {
             stats.shadow_sim_fail += 1;
         }
 
-        println!(
+        println_high!(
             "  {} {}",
             "??".yellow(),
             "SHADOW MODE: Trade skipped — details logged to shadow_analytics.jsonl".yellow().bold()
@@ -550,7 +1060,7 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
         let total_shadow = stats.shadow_sim_success + stats.shadow_sim_fail;
         if total_shadow > 0 && total_shadow.is_multiple_of(10) {
             let success_rate = (stats.shadow_sim_success as f64 / total_shadow as f64) * 100.0;
-            println!(
+            println_high!(
                 "  {} Shadow Summary: {} opportunities | Sim success: {:.1}% | Cumulative profit: {:.6} WETH",
                 "??".cyan(),
                 total_shadow,
@@ -559,26 +1069,132 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
             );
         }
 
-        // Dinamik bribe hesab� (loglama i�in)
-        let dynamic_bribe_weth = opportunity.expected_profit_weth * config.bribe_pct;
+        // Log satırı, digest ve snapshot aynı state Arc'larından türetilir
+        let state_arcs = [states[0].load_full(), states[1].load_full()];
+        let inputs = DecisionInputs::capture(opportunity, pools, [&*state_arcs[0], &*state_arcs[1]]);
+        let log_block = state_arcs[0].last_block.max(state_arcs[1].last_block);
+
+        // Fırsat anındaki tam girdileri arka plan arşivine gönder (hot path dışı)
+        let snapshot_id = submit_opportunity_snapshot(
+            "shadow",
+            opportunity,
+            pools,
+            state_arcs.clone(),
+            trade_amount.input_wei(),
+            config,
+            None,
+            0,
+            0,
+        );
+        // Günün en kârlı gölge fırsatları sessiz dönemde yeniden değerlendirilir
+        crate::revisit::offer(snapshot_id.as_deref(), opportunity, pools, state_arcs.clone());
+        // SHADOW_VERIFY_BLOCKS sonra aynı miktar zincirde yeniden fiyatlanır
+        crate::shadow_verify::submit(
+            crate::replay::opportunity_id(
+                log_block,
+                pools[opportunity.buy_pool_idx].address,
+                pools[opportunity.sell_pool_idx].address,
+            ),
+            opportunity,
+            pools,
+            state_arcs,
+        );
 
         // Shadow log kayd� (v10.0: yap�land�r�lm�� JSONL)
         write_shadow_log(
             opportunity,
-            &sim_result,
+            &math_result,
             pools,
+            log_block,
             config,
-            simulated_gas_used,
+            sim_result.gas_used,
+            sim_result.source,
             dynamic_bribe_weth,
             block_latency_ms,
+            "shadow",
+            LogIntegrity::new(config, &inputs, snapshot_id),
+            gas_check.as_ref(),
         );
-    } else if config.execution_enabled() {
-        let pk = config.private_key.clone()
-            .expect("BUG: execution_enabled() true ama private_key None");
-        let contract_addr = config.contract_address
-            .expect("BUG: execution_enabled() true ama contract_address None");
-        let trade_weth = opportunity.optimal_amount_weth;
-        let _buy_price = opportunity.buy_price_quote;
+    }
+
+    // PAPER_EXECUTION: gölge kaydından sonra aynı yürütme hattı — kağıt
+    // nonce'u, kağıt uçuştaki kaydı ve sahte receipt'lerle
+    let paper = mev_executor.paper();
+    if config.execution_enabled() || paper.is_some() {
+        let nonce_manager = paper.map_or(nonce_manager, |p| &p.nonce_manager);
+
+        // Kontrat admin tarafından duraklatılmışsa TX revert edip gas yakar —
+        // yürütme gölgeye düşer, gölge loga "contract paused" ile yazılır
+        if let Some(reason) = crate::contract_state::execution_block() {
+            println_high!(
+                "  {} {}",
+                "🛑".red(),
+                "CONTRACT PAUSED: trade skipped — shadow until the contract is unpaused".red().bold()
+            );
+            log_execution_skip(
+                opportunity,
+                crate::opportunity_filter::FilterVerdict { filter: "contract_paused", reason: reason.into() },
+                &math_result,
+                sim_result,
+                pools,
+                states,
+                config,
+                block_latency_ms,
+            );
+            return Some(simulated_gas_used);
+        }
+
+        // Kağıt yön defteri: sahte revert'lerle kısıtlanan yön kağıtta da yürütülmez
+        if let Some(paper) = paper {
+            let key = Direction::new(&pools[opportunity.buy_pool_idx], &pools[opportunity.sell_pool_idx]).key;
+            if let crate::opportunity_filter::FilterDecision::Degrade(reason) =
+                paper.decision(&key, opportunity.expected_profit_weth, config.min_net_profit_weth)
+            {
+                println_high!("  {} {}", "📝".yellow(), format!("PAPER DIRECTION GATE: {}", reason).yellow());
+                log_execution_skip(
+                    opportunity,
+                    crate::opportunity_filter::FilterVerdict { filter: "direction_gate", reason },
+                    &math_result,
+                    sim_result,
+                    pools,
+                    states,
+                    config,
+                    block_latency_ms,
+                );
+                return Some(simulated_gas_used);
+            }
+        }
+
+        // Eşzamanlı yürütme sınırı — slot nonce tahsisinden önce alınır.
+        // Doluysa fırsat kuyruğa alınmaz: gölge loga "inflight cap" ile yazılır.
+        let Some(inflight) = reserve_execution_slot(&mev_executor.inflight, stats) else {
+            println_high!(
+                "  {} {}",
+                "⏸️".yellow(),
+                format!(
+                    "INFLIGHT CAP: {} executions in flight — trade skipped (MAX_INFLIGHT_EXECUTIONS={})",
+                    mev_executor.inflight.active(),
+                    config.max_inflight_executions,
+                ).yellow().bold()
+            );
+            log_execution_skip(
+                opportunity,
+                crate::opportunity_filter::FilterVerdict { filter: "inflight_cap", reason: "inflight cap".into() },
+                &math_result,
+                sim_result,
+                pools,
+                states,
+                config,
+                block_latency_ms,
+            );
+            return Some(simulated_gas_used);
+        };
+
+        // Kağıt şerit kontrat adresi olmadan da çalışır (calldata gönderilmez)
+        let contract_addr = config.contract_address
+            .or(paper.map(|_| Address::ZERO))
+            .expect("BUG: execution_enabled() true ama contract_address None");
+        let _buy_price = opportunity.buy_price_quote;
 
         // v30.0: base_token_address kullan�l�r � cbETH/WETH gibi non-WETH-base �iftleri i�in kritik
         let (uni_dir, aero_dir, owed_token, received_token) =
@@ -591,7 +1207,7 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
 
         // v11.0: Deadline block hesapla (minimum +3 tolerans)
         let current_block = states[0].load().last_block;
-        let deadline_block = current_block as u32 + config.deadline_blocks.max(3);
+        let deadline_block = compute_deadline_block(current_block, config);
 
         // v21.0: Bribe hesab� MevExecutor::compute_dynamic_bribe'a devredildi.
         // MevExecutor, expected_profit_weth + simulated_gas + block_base_fee
@@ -606,39 +1222,41 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
         // Yeni sistem: Flash swap ak���n� birebir modelleyen
         // compute_exact_directional_profit kullan�l�r.
         // Bu fonksiyon do�rudan owedToken cinsinden k�r d�nd�r�r.
-        let exact_min_profit = {
-            let pool_a_state = states[0].load();
-            let pool_b_state = states[1].load();
-            let pool_a_fee_pips = pools[0].fee_bps * 100;
-            let pool_b_fee_pips = pools[1].fee_bps * 100;
-
-            let weth_input = crate::types::is_weth_input(uni_dir, pools[0].token0_is_weth);
-            let sim_amount_wei = crate::types::weth_amount_to_input_wei(
-                opportunity.optimal_amount_weth,
-                weth_input,
-                (opportunity.buy_price_quote + opportunity.sell_price_quote) / 2.0,
-                if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals },
-            );
-
-            let uni_zero_for_one = uni_dir == 0;
-            let aero_zero_for_one = aero_dir == 0;
-
-            math::exact::compute_exact_directional_profit(
-                pool_a_state.sqrt_price_x96,
-                pool_a_state.liquidity,
-                pool_a_state.tick,
-                pool_a_fee_pips,
-                pool_a_state.tick_bitmap.as_ref(),
-                pool_b_state.sqrt_price_x96,
-                pool_b_state.liquidity,
-                pool_b_state.tick,
-                pool_b_fee_pips,
-                pool_b_state.tick_bitmap.as_ref(),
-                sim_amount_wei,
-                uni_zero_for_one,
-                aero_zero_for_one,
-            )
-        };
+        // Snapshot arşivi, kârın hesaplandığı Arc'ların aynısını kaydeder
+        let pool_a_state = states[0].load_full();
+        let pool_b_state = states[1].load_full();
+        let weth_input = crate::types::is_weth_input(uni_dir, pools[0].token0_is_weth);
+        let sim_amount_wei = trade_amount.input_wei();
+        // Aynı exact swap yolundan kâr ayrıştırması (işlem logu) — owedToken = girdi tokeni
+        let owed_unit_weth = input_wei_to_weth(
+            U256::from(1u8),
+            weth_input,
+            opportunity.reference_price_quote,
+            if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals },
+        );
+        let costs = opportunity.profit_breakdown.map_or_else(Default::default, |b| math::AttributionCosts {
+            flash_fee_weth: b.flash_fee_weth,
+            gas_weth: b.gas_weth,
+        });
+        let (exact_min_profit, exact_breakdown) = exact_attributed_profit_for_amount(
+            pools,
+            &pool_a_state,
+            &pool_b_state,
+            sim_amount_wei,
+            uni_dir == 0,
+            aero_dir == 0,
+            owed_unit_weth,
+            costs,
+        );
+        // Receipt sonrası doğrulama için bacak başına öngörülen son fiyat
+        let predicted_impact = crate::impact_verification::PredictedImpact::for_pair(
+            pools,
+            &pool_a_state,
+            &pool_b_state,
+            sim_amount_wei,
+            uni_dir == 0,
+            aero_dir == 0,
+        );
 
         // v24.0: Desimal-duyarl� dinamik slippage
         let slippage_bps = {
@@ -651,62 +1269,111 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
                 &pools[opportunity.sell_pool_idx],
             )
         };
-        let min_profit = compute_min_profit_exact(exact_min_profit, slippage_bps);
+        // Havuz etkisi öngörüden saptıysa geçici olarak daha muhafazakâr
+        let slippage_bps = crate::impact_verification::apply_slippage_bump(slippage_bps, current_block);
+        // minProfit, birimi etiketli exact kârdan (owedToken cinsinden)
+        let owed_profit = OwedProfit::from_exact(&pools[0], uni_dir, exact_min_profit);
+        let min_profit = compute_min_profit_exact(owed_profit.wei, slippage_bps);
+
+        // Yön / token / kâr birimi değişmezi — ihlal gönderilmez (slot drop'ta bırakılır)
+        if !enforce_direction_invariants(
+            pools,
+            (uni_dir, aero_dir, owed_token, received_token),
+            &owed_profit,
+            stats,
+        ) {
+            return Some(simulated_gas_used);
+        }
+
+        let inputs = DecisionInputs::capture(opportunity, pools, [&*pool_a_state, &*pool_b_state]);
+        let snapshot_id = submit_opportunity_snapshot(
+            if paper.is_some() { crate::paper::PAPER_TAG } else { "live" },
+            opportunity,
+            pools,
+            [pool_a_state, pool_b_state],
+            sim_amount_wei,
+            config,
+            Some(exact_min_profit),
+            slippage_bps,
+            min_profit,
+        );
+        // İşlem satırı da aynı bütünlük bloğunu taşır (bot_logs.jsonl)
+        let opportunity_id = crate::replay::opportunity_id(
+            current_block,
+            pools[opportunity.buy_pool_idx].address,
+            pools[opportunity.sell_pool_idx].address,
+        );
+        let log_level = if paper.is_some() { crate::paper::PAPER_TAG } else { "trade" };
+        crate::json_logger::log_json(log_level, "execution_inputs", serde_json::json!({
+            "block": current_block,
+            "opportunity_id": opportunity_id,
+            "profit_breakdown": exact_breakdown.map(|b| profit_breakdown_json(&b.with_bribe(dynamic_bribe_weth))),
+            "integrity": LogIntegrity::new(config, &inputs, snapshot_id),
+        }));
 
         // Atomik nonce al
         let nonce = nonce_manager.get_and_increment();
         let nm_clone = Arc::clone(nonce_manager);
 
-        stats.executed_trades += 1;
-
-        // v32.0: Telegram — Alfa Bildirimi (başarılı arbitraj)
-        let gas_cost_weth = simulated_gas_used as f64 * block_base_fee as f64 / 1e18;
-        if let Some(ref tg) = telegram_sender {
-            tg.send(crate::telegram::TelegramMessage::AlphaSuccess {
-                buy_pool: pools[opportunity.buy_pool_idx].name.clone(),
-                sell_pool: pools[opportunity.sell_pool_idx].name.clone(),
-                gross_profit_weth: opportunity.expected_profit_weth,
-                gas_cost_weth,
-                net_profit_weth: opportunity.expected_profit_weth - gas_cost_weth,
-                latency_ms: block_latency_ms,
-                tx_hash: format!("nonce:{}", nonce_manager.current().saturating_sub(1)),
-            });
+        // Kağıt yürütme gerçek sayaçlara ve Telegram'a girmez (kağıt defteri ayrı)
+        if paper.is_none() {
+            stats.executed_trades += 1;
+
+            // v32.0: Telegram — Alfa Bildirimi (başarılı arbitraj)
+            let gas_cost_weth = simulated_gas_used as f64 * block_base_fee as f64 / 1e18;
+            if let Some(ref tg) = telegram_sender {
+                tg.send(crate::telegram::TelegramMessage::AlphaSuccess {
+                    buy_pool: pools[opportunity.buy_pool_idx].name.clone(),
+                    sell_pool: pools[opportunity.sell_pool_idx].name.clone(),
+                    gross_profit_weth: opportunity.expected_profit_weth,
+                    gas_cost_weth,
+                    net_profit_weth: opportunity.expected_profit_weth - gas_cost_weth,
+                    latency_ms: block_latency_ms,
+                    tx_hash: format!("nonce:{}", nonce_manager.current().saturating_sub(1)),
+                });
+            }
+            tg_counters.successful_trades += 1;
+            tg_counters.net_period_profit_weth += opportunity.expected_profit_weth - gas_cost_weth;
         }
-        tg_counters.successful_trades += 1;
-        tg_counters.net_period_profit_weth += opportunity.expected_profit_weth - gas_cost_weth;
 
         let pool_a_addr = pools[0].address;
         let pool_b_addr = pools[1].address;
 
         // REVM'den gelen kesin gas de�erini aktar (sabit 350K yerine)
-        let sim_gas = simulated_gas_used;
-
-        // v11.0: ETH fiyat� ve token s�ras� bilgisini execute_on_chain'e aktar
-        let eth_price_for_exec = (opportunity.buy_price_quote + opportunity.sell_price_quote) / 2.0;
-        let t0_is_weth = pools[0].token0_is_weth;
+        let gas = crate::gas_calibration::GasSample::planned(
+            simulated_gas_used,
+            opportunity.tick_crossings,
+            opportunity.modeled_gas,
+        );
 
         // v13.0: block_base_fee'yi execute'a aktar (max_fee_per_gas hesab� i�in)
         let base_fee_for_exec = block_base_fee;
-        let qt_decimals = if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals };
 
         let expected_profit = opportunity.expected_profit_weth;
+        // Kırılgan fırsat bloğu kaçırırsa ölür — bribe sağlamlık skoruyla artar
+        let robustness = opportunity.sensitivity.as_ref().map(|s| s.robustness());
         let mev_exec = Arc::clone(mev_executor);
+        // Yeni blokta spread kapanırsa ana döngü görevi iptal eder
+        let expiry = live.track_pair(current_block, deadline_semantics().last_valid(deadline_block) as u64, pools, opportunity);
+        let direction = Direction::new(&pools[opportunity.buy_pool_idx], &pools[opportunity.sell_pool_idx]);
 
         tokio::spawn(async move {
             execute_on_chain_protected(
-                mev_exec, pk, contract_addr,
+                mev_exec, contract_addr,
                 pool_a_addr, pool_b_addr,
                 owed_token, received_token,
-                trade_weth, uni_dir, aero_dir,
+                trade_amount, uni_dir, aero_dir,
                 min_profit, deadline_block,
-                sim_gas,
+                gas,
                 nonce, nm_clone,
-                eth_price_for_exec,
-                t0_is_weth,
                 base_fee_for_exec,
-                qt_decimals,
                 expected_profit,
                 current_block,
+                inflight,
+                expiry,
+                predicted_impact,
+                robustness,
+                direction,
             ).await;
         });
     }
@@ -716,10 +1383,781 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
     Some(simulated_gas_used)
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// REVM ↔ Exact Matematik Kâr Karşılaştırması
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Flash swap akışını birebir modelleyen exact kâr (owedToken cinsinden, wei).
+/// Fee, f64 yoluyla aynı kaynaktan gelir: canlı `fee()` varsa o, yoksa yapılandırılan.
+fn exact_profit_for_amount(
+    pools: &[PoolConfig],
+    pool_a_state: &PoolState,
+    pool_b_state: &PoolState,
+    amount_wei: U256,
+    uni_zero_for_one: bool,
+    aero_zero_for_one: bool,
+) -> U256 {
+    math::exact::compute_exact_directional_profit(
+        pool_a_state.sqrt_price_x96,
+        pool_a_state.liquidity,
+        pool_a_state.tick,
+        pools[0].effective_fee(pool_a_state.live_fee_bps).pips(),
+        pool_a_state.tick_bitmap.as_deref(),
+        pool_b_state.sqrt_price_x96,
+        pool_b_state.liquidity,
+        pool_b_state.tick,
+        pools[1].effective_fee(pool_b_state.live_fee_bps).pips(),
+        pool_b_state.tick_bitmap.as_deref(),
+        amount_wei,
+        uni_zero_for_one,
+        aero_zero_for_one,
+    )
+}
+
+/// `exact_profit_for_amount` + aynı swap yolundan kâr ayrıştırması (WETH)
+#[allow(clippy::too_many_arguments)]
+fn exact_attributed_profit_for_amount(
+    pools: &[PoolConfig],
+    pool_a_state: &PoolState,
+    pool_b_state: &PoolState,
+    amount_wei: U256,
+    uni_zero_for_one: bool,
+    aero_zero_for_one: bool,
+    owed_unit_weth: f64,
+    costs: math::AttributionCosts,
+) -> (U256, Option<math::ProfitBreakdown>) {
+    math::exact::compute_exact_directional_profit_attributed(
+        pool_a_state.sqrt_price_x96,
+        pool_a_state.liquidity,
+        pool_a_state.tick,
+        pools[0].effective_fee(pool_a_state.live_fee_bps).pips(),
+        pool_a_state.tick_bitmap.as_deref(),
+        pool_b_state.sqrt_price_x96,
+        pool_b_state.liquidity,
+        pool_b_state.tick,
+        pools[1].effective_fee(pool_b_state.live_fee_bps).pips(),
+        pool_b_state.tick_bitmap.as_deref(),
+        amount_wei,
+        uni_zero_for_one,
+        aero_zero_for_one,
+        owed_unit_weth,
+        costs,
+    )
+}
+
+/// Fırsatın NR boyutundaki exact kârı — yönler ve wei dönüşümü
+/// submit_opportunity_snapshot ile aynı (replay doğrulaması).
+pub(crate) fn exact_profit_for_opportunity(
+    pools: &[PoolConfig],
+    pool_a_state: &PoolState,
+    pool_b_state: &PoolState,
+    opportunity: &ArbitrageOpportunity,
+) -> U256 {
+    let (amount_wei, uni_zfo, aero_zfo) = exact_swap_params(
+        pools,
+        opportunity.buy_pool_idx,
+        opportunity.reference_price_quote,
+        opportunity.optimal_amount_weth,
+    );
+    exact_profit_for_amount(pools, pool_a_state, pool_b_state, amount_wei, uni_zfo, aero_zfo)
+}
+
+/// `exact_profit_for_opportunity` WETH cinsinden — owedToken wei'si fırsatın
+/// referans fiyatıyla çevrilir (gölge doğrulaması)
+pub(crate) fn exact_profit_weth_for_opportunity(
+    pools: &[PoolConfig],
+    pool_a_state: &PoolState,
+    pool_b_state: &PoolState,
+    opportunity: &ArbitrageOpportunity,
+) -> f64 {
+    let (uni_dir, ..) = compute_directions_and_tokens(
+        opportunity.buy_pool_idx,
+        pools[0].token0_is_weth,
+        &pools[0].base_token_address,
+        &pools[0].quote_token_address,
+    );
+    input_wei_to_weth(
+        exact_profit_for_opportunity(pools, pool_a_state, pool_b_state, opportunity),
+        is_weth_input(uni_dir, pools[0].token0_is_weth),
+        opportunity.reference_price_quote,
+        if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals },
+    )
+}
+
+/// f64 NR optimumunu ipucu alarak U256 exact kâr üzerinde yeniden arar.
+/// Exact optimum; exact kâr, gas (geçiş sayısı) ve flash primi farkıyla
+/// f64 optimumundan kârlıysa onu döndürür — beklenen kâr quote cinsinden kalır.
+#[allow(clippy::too_many_arguments)]
+fn refine_exact_optimum(
+    pools: &[PoolConfig],
+    pool_a_state: &PoolState,
+    pool_b_state: &PoolState,
+    buy_idx: usize,
+    reference_price: f64,
+    min_weth: f64,
+    max_weth: f64,
+    nr_result: math::OptimalAmountResult,
+    gas_cost_quote_at: &dyn Fn(f64) -> f64,
+) -> math::OptimalAmountResult {
+    let (hint_wei, uni_zfo, aero_zfo) =
+        exact_swap_params(pools, buy_idx, reference_price, nr_result.optimal_amount);
+    let (min_wei, ..) = exact_swap_params(pools, buy_idx, reference_price, min_weth);
+    let (max_wei, ..) = exact_swap_params(pools, buy_idx, reference_price, max_weth);
+    let (amount_wei, profit_wei, evaluations) = math::exact::find_optimal_amount_exact(
+        pool_a_state.sqrt_price_x96,
+        pool_a_state.liquidity,
+        pool_a_state.tick,
+        pools[0].effective_fee(pool_a_state.live_fee_bps).pips(),
+        pool_a_state.tick_bitmap.as_deref(),
+        pool_b_state.sqrt_price_x96,
+        pool_b_state.liquidity,
+        pool_b_state.tick,
+        pools[1].effective_fee(pool_b_state.live_fee_bps).pips(),
+        pool_b_state.tick_bitmap.as_deref(),
+        uni_zfo,
+        aero_zfo,
+        min_wei,
+        max_wei,
+        Some(hint_wei),
+    );
+    if amount_wei.is_zero() || amount_wei == hint_wei {
+        return nr_result;
+    }
+
+    // owedToken wei → WETH (owedToken = flash swap girdisi)
+    let weth_input = crate::types::is_weth_input(if uni_zfo { 0 } else { 1 }, pools[0].token0_is_weth);
+    let quote_decimals = if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals };
+    let to_weth = |wei: U256| input_wei_to_weth(wei, weth_input, reference_price, quote_decimals);
+    let hint_profit_wei = exact_profit_for_amount(pools, pool_a_state, pool_b_state, hint_wei, uni_zfo, aero_zfo);
+
+    let amount_weth = to_weth(amount_wei);
+    let size_delta_weth = amount_weth - nr_result.optimal_amount;
+    let gain_quote = (to_weth(profit_wei) - to_weth(hint_profit_wei)) * reference_price
+        - (gas_cost_quote_at(amount_weth) - gas_cost_quote_at(nr_result.optimal_amount))
+        - size_delta_weth * crate::fee_monitor::effective_flash_loan_fee_bps() / 10_000.0 * reference_price;
+    if gain_quote <= 0.0 {
+        return nr_result;
+    }
+    math::OptimalAmountResult {
+        optimal_amount: amount_weth,
+        expected_profit: nr_result.expected_profit + gain_quote,
+        converged: true,
+        iterations: nr_result.iterations + evaluations,
+    }
+}
+
+/// WETH boyutunu flash swap girdisine (wei) çevir + iki bacağın zeroForOne yönü
+fn exact_swap_params(
+    pools: &[PoolConfig],
+    buy_idx: usize,
+    reference_price: f64,
+    amount_weth: f64,
+) -> (U256, bool, bool) {
+    let (uni_dir, aero_dir, _, _) = compute_directions_and_tokens(
+        buy_idx,
+        pools[0].token0_is_weth,
+        &pools[0].base_token_address,
+        &pools[0].quote_token_address,
+    );
+    // Geçersiz miktar sıfır girdi olur — kâr da sıfır (analiz yolu, calldata yok)
+    let amount_wei = crate::types::weth_amount_to_input_wei(
+        amount_weth,
+        crate::types::is_weth_input(uni_dir, pools[0].token0_is_weth),
+        reference_price,
+        if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals },
+    )
+    .unwrap_or_default();
+    (amount_wei, uni_dir == 0, aero_dir == 0)
+}
+
+/// Gas kaleminin WETH maliyeti (L2 yürütme + L1 data, %10 güvenlik marjı).
+/// Base fee bilinmiyorsa GAS_COST_FALLBACK_WETH kullanılır.
+fn gas_cost_weth(gas_units: u64, block_base_fee: u64, l1_data_fee_weth: f64, fallback_weth: f64) -> f64 {
+    if block_base_fee > 0 {
+        let l2 = (gas_units as f64 * block_base_fee as f64) / 1e18;
+        ((l2 + l1_data_fee_weth) * 1.10).max(0.000001)
+    } else {
+        ((fallback_weth + l1_data_fee_weth) * 1.10).max(0.000001)
+    }
+}
+
+/// REVM sonrası ikinci kârlılık kapısı: tespitteki kâr tahmini gas ile
+/// fiyatlandı, burada net kâr aynı miktarda exact kâr fonksiyonundan simüle
+/// gas ile yeniden hesaplanır (aynı blok, aynı L1 ücreti). Gas kalemi
+/// doğrusal düşülmez — tespitten beri değişen havuz durumu da kâra yansır.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SimGasCheck {
+    assumed_gas: u64,
+    simulated_gas: u64,
+    pre_sim_profit_weth: f64,
+    post_sim_profit_weth: f64,
+}
+
+impl SimGasCheck {
+    fn new(
+        opportunity: &ArbitrageOpportunity,
+        pools: &[PoolConfig],
+        states: [&PoolState; 2],
+        simulated_gas: u64,
+        block_base_fee: u64,
+        l1_data_fee_weth: f64,
+        config: &BotConfig,
+    ) -> Self {
+        let (sell_idx, buy_idx) = (opportunity.sell_pool_idx, opportunity.buy_pool_idx);
+        let (sell_state, buy_state) = (states[sell_idx], states[buy_idx]);
+        // Tespitle aynı bitmap tazelik kuralı — eski bitmap tek aralığa düşer
+        let current_block = sell_state.last_block.max(buy_state.last_block);
+        let [sell_bitmap, buy_bitmap] = [sell_state, buy_state].map(|state| {
+            state
+                .tick_bitmap
+                .as_deref()
+                .filter(|bm| bm.is_fresh(current_block, config.tick_bitmap_max_age_blocks))
+        });
+        let amount_weth = opportunity.optimal_amount_weth;
+        let costs = math::AttributionCosts {
+            flash_fee_weth: amount_weth * crate::fee_monitor::effective_flash_loan_fee_bps() / 10_000.0,
+            gas_weth: gas_cost_weth(simulated_gas, block_base_fee, l1_data_fee_weth, config.gas_cost_fallback_weth),
+        };
+        let post_sim_profit_weth = math::compute_arbitrage_profit_breakdown_with_bitmap(
+            amount_weth,
+            sell_state,
+            pools[sell_idx].effective_fee(sell_state.live_fee_bps).fraction(),
+            buy_state,
+            pools[buy_idx].effective_fee(buy_state.live_fee_bps).fraction(),
+            costs,
+            pools[sell_idx].token0_is_weth,
+            pools[buy_idx].token0_is_weth,
+            sell_bitmap,
+            buy_bitmap,
+        )
+        // Bacak boş / havuz tükeniyor → yürütülemez
+        .map_or(f64::NEG_INFINITY, |b| b.net_weth);
+        Self {
+            assumed_gas: opportunity.priced_gas,
+            simulated_gas,
+            pre_sim_profit_weth: opportunity.expected_profit_weth,
+            post_sim_profit_weth,
+        }
+    }
+
+    /// profit_floor filtresiyle aynı kural: net kâr ve ROI birlikte eşiğin altındaysa ret
+    fn passes(&self, amount_weth: f64, config: &BotConfig) -> bool {
+        if amount_weth <= 0.0 {
+            return false;
+        }
+        let roi = self.post_sim_profit_weth / amount_weth;
+        self.post_sim_profit_weth >= config.min_net_profit_weth || roi >= config.min_profit_roi
+    }
+}
+
+/// REVM'de gerçekleşen kâr ile exact matematik kârı arasındaki mutlak fark (wei).
+fn sim_math_discrepancy(revm_profit: U256, exact_profit: U256) -> U256 {
+    if revm_profit > exact_profit {
+        revm_profit - exact_profit
+    } else {
+        exact_profit - revm_profit
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Kâr Hassasiyeti — 1/2 Tick Ters Fiyat Hareketi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Hassasiyet ölçümü için pipeline bütçesinde kalması gereken en az süre.
+/// Daha azı kaldıysa ölçüm atlanır (filtre ve bribe ölçümsüz davranır).
+pub const SENSITIVITY_MIN_BUDGET: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Fırsatın 1 ve 2 tick ters fiyat hareketine hassasiyeti.
+///
+/// Her havuz kendi swap yönümüzde (spread'i kapatan yön) ayrı ayrı kaydırılır;
+/// tick sayısı başına kötü sonuç tutulur. Base dışında tam 4 exact kâr çağrısı.
+pub fn evaluate_profit_sensitivity(
+    pools: &[PoolConfig],
+    state_a: &PoolState,
+    state_b: &PoolState,
+    buy_idx: usize,
+    reference_price: f64,
+    amount_weth: f64,
+) -> ProfitSensitivity {
+    let (amount_wei, uni_zfo, aero_zfo) =
+        exact_swap_params(pools, buy_idx, reference_price, amount_weth);
+    let profit = |a: &PoolState, b: &PoolState| {
+        exact_profit_for_amount(pools, a, b, amount_wei, uni_zfo, aero_zfo)
+    };
+    let after_ticks = |ticks: u32| {
+        let a_shifted = math::exact::shift_pool_state_ticks(state_a, &pools[0], ticks, uni_zfo);
+        let b_shifted = math::exact::shift_pool_state_ticks(state_b, &pools[1], ticks, aero_zfo);
+        profit(&a_shifted, state_b).min(profit(state_a, &b_shifted))
+    };
+    ProfitSensitivity {
+        base_profit_wei: profit(state_a, state_b),
+        profit_after_1tick_wei: after_ticks(1),
+        profit_after_2tick_wei: after_ticks(2),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// İki Adımlı Boyutlandırma — Kısmi Dolum
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// İki adımlı plan için pipeline bütçesinde kalması gereken en az süre.
+/// İkinci fırsat kontrolü tam bir boyutlandırma turudur (NR + exact).
+pub const TWO_STEP_MIN_BUDGET: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Seçilen fırsatın iki adımlı planı.
+///
+/// Tek seferlik optimum iki havuzda exact motorla rota sırasıyla yürütülür
+/// (A kendi yönünde, B A'dan çıkan miktarla) ve fırsat kontrolü işlem sonrası
+/// state'te aynı filtrelerle tekrarlanır. Aynı yönde MIN_NET_PROFIT_WETH
+/// üstünde ikinci fırsat yoksa None — yön dönmesi optimumun aşıldığı demektir.
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_two_step_plan(
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    opportunity: &ArbitrageOpportunity,
+    config: &BotConfig,
+    block_base_fee: u64,
+    last_simulated_gas: Option<u64>,
+    l1_data_fee_wei: u128,
+    competition: &CompetitionSnapshot,
+    filters: &FilterChain,
+    quoter: Option<&SimulationEngine>,
+) -> Option<TwoStepPlan> {
+    if opportunity.optimal_amount_weth <= 0.0 || pools.len() < 2 || states.len() < 2 {
+        return None;
+    }
+    let (amount_wei, uni_zfo, aero_zfo) = exact_swap_params(
+        pools,
+        opportunity.buy_pool_idx,
+        opportunity.reference_price_quote,
+        opportunity.optimal_amount_weth,
+    );
+    let (a_after, received) =
+        math::exact::advance_pool_state(&states[0].load_full(), &pools[0], amount_wei, uni_zfo);
+    if received.is_zero() {
+        return None;
+    }
+    let (b_after, _) =
+        math::exact::advance_pool_state(&states[1].load_full(), &pools[1], received, aero_zfo);
+    let post_trade: [SharedPoolState; 2] = [
+        Arc::new(arc_swap::ArcSwap::from_pointee(a_after)),
+        Arc::new(arc_swap::ArcSwap::from_pointee(b_after)),
+    ];
+
+    let second = check_arbitrage_opportunity(
+        pools,
+        &post_trade,
+        config,
+        block_base_fee,
+        last_simulated_gas,
+        l1_data_fee_wei,
+        competition,
+        filters,
+        quoter,
+    )?;
+    let supported = second.buy_pool_idx == opportunity.buy_pool_idx
+        && second.expected_profit_weth >= config.min_net_profit_weth;
+    supported.then_some(TwoStepPlan {
+        first_amount_weth: opportunity.optimal_amount_weth,
+        first_profit_weth: opportunity.expected_profit_weth,
+        second_amount_weth: second.optimal_amount_weth,
+        second_profit_weth: second.expected_profit_weth,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sermaye Riski — Ters Seçilim (Rakip Front-Run) Senaryosu
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Revert sınırı aramasında front-run boyutunun en fazla kaç kez ikiye katlanacağı
+const ADVERSE_BOUNDARY_DOUBLINGS: u32 = 24;
+/// Sınır bulunduktan sonraki ikiye bölme adımı (~%0.02 hassasiyet)
+const ADVERSE_BOUNDARY_BISECTIONS: u32 = 12;
+
+/// Rakip aynı rotada `frontrun_weth` ile bizden önce işlem yaparsa bizim
+/// exact kârımız (owedToken wei). İki havuz da exact swap motoruyla ilerletilir.
+fn exact_profit_after_frontrun(
+    pools: &[PoolConfig],
+    state_a: &PoolState,
+    state_b: &PoolState,
+    frontrun_wei: U256,
+    amount_wei: U256,
+    uni_zero_for_one: bool,
+    aero_zero_for_one: bool,
+) -> U256 {
+    let (a_after, received) =
+        math::exact::advance_pool_state(state_a, &pools[0], frontrun_wei, uni_zero_for_one);
+    let (b_after, _) =
+        math::exact::advance_pool_state(state_b, &pools[1], received, aero_zero_for_one);
+    exact_profit_for_amount(
+        pools,
+        &a_after,
+        &b_after,
+        amount_wei,
+        uni_zero_for_one,
+        aero_zero_for_one,
+    )
+}
+
+/// Ters seçilim senaryosu: base kâr, front-run sonrası kâr ve minProfit'in
+/// tetiklendiği en küçük front-run boyutu (revert sınırı).
+///
+/// Front-run büyüdükçe kârın azaldığı varsayılır: sınır önce ikiye katlayarak
+/// kuşatılır, sonra ikiye bölerek daraltılır.
+fn evaluate_adverse_scenario(
+    pools: &[PoolConfig],
+    state_a: &PoolState,
+    state_b: &PoolState,
+    buy_idx: usize,
+    reference_price: f64,
+    amount_weth: f64,
+    frontrun_weth: f64,
+) -> AdverseScenario {
+    let sell_idx = 1 - buy_idx;
+    let (uni_dir, aero_dir, _, _) = compute_directions_and_tokens(
+        buy_idx,
+        pools[0].token0_is_weth,
+        &pools[0].base_token_address,
+        &pools[0].quote_token_address,
+    );
+    let (uni_zfo, aero_zfo) = (uni_dir == 0, aero_dir == 0);
+
+    // evaluate_and_execute ile aynı WETH → girdi token dönüşümü
+    let weth_input = crate::types::is_weth_input(uni_dir, pools[0].token0_is_weth);
+    let quote_decimals =
+        if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals };
+    let to_input_wei = |weth: f64| {
+        crate::types::weth_amount_to_input_wei(weth, weth_input, reference_price, quote_decimals)
+            .unwrap_or_default()
+    };
+    let amount_wei = to_input_wei(amount_weth);
+
+    let base_profit_wei =
+        exact_profit_for_amount(pools, state_a, state_b, amount_wei, uni_zfo, aero_zfo);
+    let (buy_state, sell_state) = if buy_idx == 0 { (state_a, state_b) } else { (state_b, state_a) };
+    let slippage_bps = determine_slippage_factor_bps(
+        buy_state.liquidity,
+        sell_state.liquidity,
+        &pools[buy_idx],
+        &pools[sell_idx],
+    );
+    let min_profit = compute_min_profit_exact(base_profit_wei, slippage_bps);
+
+    let profit_after = |frontrun: f64| {
+        exact_profit_after_frontrun(
+            pools,
+            state_a,
+            state_b,
+            to_input_wei(frontrun),
+            amount_wei,
+            uni_zfo,
+            aero_zfo,
+        )
+    };
+    let trips = |frontrun: f64| profit_after(frontrun) < U256::from(min_profit);
+
+    // minProfit = 0 ise kontrat kontrolü hiç tetiklenmez
+    let revert_boundary_weth = if min_profit == 0 {
+        None
+    } else {
+        let mut lo = 0.0;
+        let mut hi = amount_weth.max(1e-6);
+        let mut doublings = 0;
+        while !trips(hi) && doublings < ADVERSE_BOUNDARY_DOUBLINGS {
+            lo = hi;
+            hi *= 2.0;
+            doublings += 1;
+        }
+        if trips(hi) {
+            for _ in 0..ADVERSE_BOUNDARY_BISECTIONS {
+                let mid = (lo + hi) / 2.0;
+                if trips(mid) {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            Some(hi)
+        } else {
+            None
+        }
+    };
+
+    AdverseScenario {
+        frontrun_weth,
+        base_profit_wei,
+        adverse_profit_wei: profit_after(frontrun_weth),
+        min_profit,
+        revert_boundary_weth,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Bitmap Tarama Kenarı — Optimum Bilinmeyen Likiditeye Dayanıyor mu?
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Kenar payı, tick_spacing katı olarak (BOUNDARY_MARGIN_TICKS = 2 × tick_spacing)
+const BOUNDARY_MARGIN_SPACINGS: i32 = 2;
+
+/// NR boyutu exact matematikle iki bacakta da yürütülür; swap sonrası tick
+/// taranan bitmap aralığının kenarına BOUNDARY_MARGIN_SPACINGS × tick_spacing'ten
+/// yakınsa o bacak döner. Kenarın ötesi okunmadığından optimum orada
+/// kesilmiş olabilir — ana döngü o kenarı genişletip boyutlandırmayı tekrarlar.
+fn detect_scan_boundary(
+    pools: &[PoolConfig],
+    state_a: &PoolState,
+    state_b: &PoolState,
+    buy_idx: usize,
+    reference_price: f64,
+    amount_weth: f64,
+) -> Option<ScanBoundaryHit> {
+    let (uni_dir, aero_dir, _, _) = compute_directions_and_tokens(
+        buy_idx,
+        pools[0].token0_is_weth,
+        &pools[0].base_token_address,
+        &pools[0].quote_token_address,
+    );
+    let weth_input = crate::types::is_weth_input(uni_dir, pools[0].token0_is_weth);
+    let quote_decimals =
+        if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals };
+    let amount_wei = crate::types::weth_amount_to_input_wei(amount_weth, weth_input, reference_price, quote_decimals)
+        .unwrap_or_default();
+
+    let (a_after, received) =
+        math::exact::advance_pool_state(state_a, &pools[0], amount_wei, uni_dir == 0);
+    let (b_after, _) = math::exact::advance_pool_state(state_b, &pools[1], received, aero_dir == 0);
+
+    [(state_a, a_after.tick), (state_b, b_after.tick)]
+        .into_iter()
+        .enumerate()
+        .find_map(|(pool_idx, (before, tick_after))| {
+            let margin = BOUNDARY_MARGIN_SPACINGS * pools[pool_idx].tick_spacing.max(1);
+            let edge = before.tick_bitmap.as_deref()?.scan_edge_near(tick_after, margin)?;
+            Some(ScanBoundaryHit { pool_idx, edge })
+        })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Flash Kaynağı Seçimi — Pool A vs Pool B Sıralaması
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Rota boyutlandırma aramasının altın oran iterasyonu (aralık × 0.618^40 hassasiyet)
+const FLASH_ROUTE_SEARCH_ITERATIONS: u32 = 40;
+
+/// `weth_amount_to_input_wei`'nin tersi: girdi token wei → WETH
+fn input_wei_to_weth(
+    amount_wei: U256,
+    is_weth_input: bool,
+    eth_price_quote: f64,
+    quote_token_decimals: u8,
+) -> f64 {
+    let amount = math::exact::u256_to_f64(amount_wei);
+    if is_weth_input {
+        amount / 1e18
+    } else if eth_price_quote > 0.0 {
+        amount / 10f64.powi(quote_token_decimals as i32) / eth_price_quote
+    } else {
+        0.0
+    }
+}
+
+/// Ters sıralamanın exact kârı: receivedToken önce pool B'ye (Slipstream),
+/// çıktısı pool A'ya (UniV3) girer. Bacak yönleri mevcut rotayla aynı;
+/// kâr receivedToken cinsinden (wei).
+fn exact_reversed_profit_for_amount(
+    pools: &[PoolConfig],
+    pool_a_state: &PoolState,
+    pool_b_state: &PoolState,
+    amount_wei: U256,
+    uni_zero_for_one: bool,
+    aero_zero_for_one: bool,
+) -> U256 {
+    math::exact::compute_exact_directional_profit(
+        pool_b_state.sqrt_price_x96,
+        pool_b_state.liquidity,
+        pool_b_state.tick,
+        pools[1].effective_fee(pool_b_state.live_fee_bps).pips(),
+        pool_b_state.tick_bitmap.as_deref(),
+        pool_a_state.sqrt_price_x96,
+        pool_a_state.liquidity,
+        pool_a_state.tick,
+        pools[0].effective_fee(pool_a_state.live_fee_bps).pips(),
+        pool_a_state.tick_bitmap.as_deref(),
+        amount_wei,
+        aero_zero_for_one,
+        uni_zero_for_one,
+    )
+}
+
+/// Tek tepeli net kâr fonksiyonunun [0, max_weth] aralığındaki maksimumu
+/// (altın oran araması). Dönüş: (boyut, net kâr) WETH; pozitif kâr yoksa (0, 0).
+fn maximize_route_profit(profit: impl Fn(f64) -> f64, max_weth: f64) -> (f64, f64) {
+    let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (0.0, max_weth.max(0.0));
+    let mut x1 = hi - inv_phi * (hi - lo);
+    let mut x2 = lo + inv_phi * (hi - lo);
+    let (mut f1, mut f2) = (profit(x1), profit(x2));
+    for _ in 0..FLASH_ROUTE_SEARCH_ITERATIONS {
+        if f1 < f2 {
+            lo = x1;
+            (x1, f1) = (x2, f2);
+            x2 = lo + inv_phi * (hi - lo);
+            f2 = profit(x2);
+        } else {
+            hi = x2;
+            (x2, f2) = (x1, f1);
+            x1 = hi - inv_phi * (hi - lo);
+            f1 = profit(x1);
+        }
+    }
+    let (amount, best) = if f1 >= f2 { (x1, f1) } else { (x2, f2) };
+    if best > 0.0 { (amount, best) } else { (0.0, 0.0) }
+}
+
+/// İki sıralamayı aynı aralıkta boyutlandırıp karşılaştır (kâr fonksiyonları WETH → net WETH).
+fn select_flash_route(
+    current: impl Fn(f64) -> f64,
+    reversed: impl Fn(f64) -> f64,
+    max_weth: f64,
+) -> FlashRouteComparison {
+    let (current_amount_weth, current_profit_weth) = maximize_route_profit(current, max_weth);
+    let (reversed_amount_weth, reversed_profit_weth) = maximize_route_profit(reversed, max_weth);
+    FlashRouteComparison {
+        current_amount_weth,
+        current_profit_weth,
+        reversed_amount_weth,
+        reversed_profit_weth,
+        preferred: if reversed_profit_weth > current_profit_weth {
+            FlashSource::PoolB
+        } else {
+            FlashSource::PoolA
+        },
+    }
+}
+
+/// Fırsatın iki flash kaynağı sıralamasını exact motorla değerlendir.
+///
+/// Pool A kaynak: owedToken → A → B → owedToken (kontratın yürüttüğü rota).
+/// Pool B kaynak: receivedToken → B → A → receivedToken — son satış UniV3'e.
+/// Net kâr = exact kâr − flash primi; iki rota da [0, max_weth]'te boyutlandırılır.
+fn compare_flash_routes(
+    pools: &[PoolConfig],
+    state_a: &PoolState,
+    state_b: &PoolState,
+    buy_idx: usize,
+    reference_price: f64,
+    max_weth: f64,
+    flash_fee_bps: f64,
+) -> FlashRouteComparison {
+    let (uni_dir, aero_dir, _, _) = compute_directions_and_tokens(
+        buy_idx,
+        pools[0].token0_is_weth,
+        &pools[0].base_token_address,
+        &pools[0].quote_token_address,
+    );
+    let (uni_zfo, aero_zfo) = (uni_dir == 0, aero_dir == 0);
+    // Ters rotanın girdisi mevcut rotanın çıktı token'ı
+    let weth_input = crate::types::is_weth_input(uni_dir, pools[0].token0_is_weth);
+    let quote_decimals =
+        if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals };
+    let to_wei = |weth: f64, weth_in: bool| {
+        crate::types::weth_amount_to_input_wei(weth, weth_in, reference_price, quote_decimals)
+            .unwrap_or_default()
+    };
+    let to_weth = |wei: U256, weth_in: bool| input_wei_to_weth(wei, weth_in, reference_price, quote_decimals);
+    let premium = |weth: f64| weth * flash_fee_bps / 10_000.0;
+
+    select_flash_route(
+        |weth| {
+            let profit = exact_profit_for_amount(
+                pools, state_a, state_b, to_wei(weth, weth_input), uni_zfo, aero_zfo,
+            );
+            to_weth(profit, weth_input) - premium(weth)
+        },
+        |weth| {
+            let profit = exact_reversed_profit_for_amount(
+                pools, state_a, state_b, to_wei(weth, !weth_input), uni_zfo, aero_zfo,
+            );
+            to_weth(profit, !weth_input) - premium(weth)
+        },
+        max_weth,
+    )
+}
+
+/// Gölge log alanı: kontrat yükseltmesini veriyle gerekçelendirmek için
+fn profit_breakdown_json(b: &math::ProfitBreakdown) -> serde_json::Value {
+    let round = |weth: f64| (weth * 1e8).round() / 1e8;
+    serde_json::json!({
+        "gross_weth": round(b.gross_weth),
+        "fee_leg1_weth": round(b.fee_leg1_weth),
+        "fee_leg2_weth": round(b.fee_leg2_weth),
+        "flash_fee_weth": round(b.flash_fee_weth),
+        "gas_weth": round(b.gas_weth),
+        "bribe_weth": round(b.bribe_weth),
+        "net_weth": round(b.net_weth),
+    })
+}
+
+fn flash_route_json(route: &FlashRouteComparison) -> serde_json::Value {
+    serde_json::json!({
+        "preferred": route.preferred.to_string(),
+        "current_amount_weth": (route.current_amount_weth * 1e8).round() / 1e8,
+        "current_profit_weth": (route.current_profit_weth * 1e8).round() / 1e8,
+        "reversed_amount_weth": (route.reversed_amount_weth * 1e8).round() / 1e8,
+        "reversed_profit_weth": (route.reversed_profit_weth * 1e8).round() / 1e8,
+        "foregone_improvement_weth": (route.foregone_improvement_weth() * 1e8).round() / 1e8,
+        "material": route.is_material(),
+    })
+}
+
 // �����������������������������������������������������������������������������
 // G�lge Modu (Shadow Mode) � JSON Loglama
 // �����������������������������������������������������������������������������
 
+/// Yürütme anında kapıya takılan fırsat (kontrat pause, inflight cap) —
+/// gölge loga kapı gerekçesiyle, "<filtre>-skipped" modunda yazılır
+#[allow(clippy::too_many_arguments)]
+fn log_execution_skip(
+    opportunity: &ArbitrageOpportunity,
+    verdict: crate::opportunity_filter::FilterVerdict,
+    math_result: &SimulationResult,
+    sim_result: &SimulationResult,
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    config: &BotConfig,
+    block_latency_ms: f64,
+) {
+    // Kağıt modda fırsat gölge loga zaten yazıldı — yalnızca kağıt kaydı
+    if config.paper_execution {
+        crate::json_logger::log_json(crate::paper::PAPER_TAG, "paper_execution_skipped", serde_json::json!({
+            "block": states[0].load().last_block.max(states[1].load().last_block),
+            "direction": format!("{} → {}", pools[opportunity.buy_pool_idx].name, pools[opportunity.sell_pool_idx].name),
+            "filter": verdict.filter,
+            "reason": verdict.reason,
+        }));
+        return;
+    }
+    let mode = format!("{}-skipped", verdict.filter);
+    let mut skipped = opportunity.clone();
+    skipped.shadow_only = Some(verdict);
+    let (state_a, state_b) = (states[0].load(), states[1].load());
+    let inputs = DecisionInputs::capture(opportunity, pools, [&**state_a, &**state_b]);
+    write_shadow_log(
+        &skipped,
+        math_result,
+        pools,
+        state_a.last_block.max(state_b.last_block),
+        config,
+        sim_result.gas_used,
+        sim_result.source,
+        0.0,
+        block_latency_ms,
+        &mode,
+        LogIntegrity::new(config, &inputs, None),
+        None,
+    );
+}
+
 /// G�lge modunda bulunan f�rsat�n t�m detaylar�n� shadow_analytics.jsonl
 /// dosyas�na sat�r sat�r (JSON Lines / NDJSON format�nda) append eder.
 ///
@@ -730,21 +2168,21 @@ pub async fn evaluate_and_execute<P: Provider + Sync>(
 /// Bu dosya birka� g�n sonra a��l�p:
 ///   "Bot 1000 f�rsat bulmu�, ger�ek TX atsayd�k toplam 450$ kazanacakt�k"
 /// analizini yapmak i�in kullan�l�r.
+#[allow(clippy::too_many_arguments)]
 fn write_shadow_log(
     opportunity: &ArbitrageOpportunity,
     sim_result: &SimulationResult,
     pools: &[PoolConfig],
-    _config: &BotConfig,
+    block: u64,
+    config: &BotConfig,
     simulated_gas: u64,
+    gas_source: SimSource,
     dynamic_bribe_weth: f64,
     latency_ms: f64,
+    mode: &str,
+    integrity: LogIntegrity,
+    gas_check: Option<&SimGasCheck>,
 ) {
-    let buy_pool = &pools[opportunity.buy_pool_idx];
-    let sell_pool = &pools[opportunity.sell_pool_idx];
-
-    // pool_pair: "UniV3-WETH/cbBTC - Aero-WETH/cbBTC"
-    let pool_pair = format!("{} - {}", buy_pool.name, sell_pool.name);
-
     // Simulated profit = expected profit if sim succeeded, 0 otherwise
     let simulated_profit_weth = if sim_result.success {
         opportunity.expected_profit_weth
@@ -753,57 +2191,118 @@ fn write_shadow_log(
     };
 
     // JSONL yap�land�r�lm�� log sat�r�
-    let log_entry = serde_json::json!({
-        "timestamp": chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f").to_string(),
-        "pool_pair": pool_pair,
-        "buy_pool": buy_pool.name,
-        "buy_pool_addr": format!("{}", buy_pool.address),
-        "buy_price_quote": (opportunity.buy_price_quote * 1e6).round() / 1e6,
-        "sell_pool": sell_pool.name,
-        "sell_pool_addr": format!("{}", sell_pool.address),
-        "sell_price_quote": (opportunity.sell_price_quote * 1e6).round() / 1e6,
-        "spread_pct": (opportunity.spread_pct * 1e6).round() / 1e6,
-        "optimal_amount_weth": (opportunity.optimal_amount_weth * 1e8).round() / 1e8,
-        "expected_profit": (opportunity.expected_profit_weth * 1e8).round() / 1e8,
+    let mut log_entry = serde_json::json!({
+        "timestamp": crate::clock::log_timestamp(),
         "simulated_profit": (simulated_profit_weth * 1e8).round() / 1e8,
         "gas_used": simulated_gas,
+        // "math_fallback" → gas_used sezgisel tahmin, REVM ölçümü değil
+        "gas_source": gas_source.label(),
+        // REVM gas'ıyla yeniden hesaplanan net kâr (tespit tahmini gas'a karşı)
+        "sim_gas": gas_check.map(|c| serde_json::json!({
+            "assumed_gas": c.assumed_gas,
+            "simulated_gas": c.simulated_gas,
+            "pre_sim_profit": (c.pre_sim_profit_weth * 1e8).round() / 1e8,
+            "post_sim_profit": (c.post_sim_profit_weth * 1e8).round() / 1e8,
+        })),
         "dynamic_bribe": (dynamic_bribe_weth * 1e8).round() / 1e8,
         "latency_ms": (latency_ms * 10.0).round() / 10.0,
         "nr_converged": opportunity.nr_converged,
         "nr_iterations": opportunity.nr_iterations,
+        "bitmap_quality": opportunity.bitmap_quality.to_string(),
+        // Ters seçilim senaryosu — wei değerleri owedToken cinsinden (string: u128/U256)
+        "adverse": opportunity.adverse.as_ref().map(|a| serde_json::json!({
+            "frontrun_weth": (a.frontrun_weth * 1e8).round() / 1e8,
+            "base_profit_wei": a.base_profit_wei.to_string(),
+            "adverse_profit_wei": a.adverse_profit_wei.to_string(),
+            "min_profit": a.min_profit.to_string(),
+            "revert_boundary_weth": a.revert_boundary_weth.map(|w| (w * 1e8).round() / 1e8),
+        })),
+        // 1/2 tick ters hareket hassasiyeti — wei değerleri owedToken cinsinden
+        "sensitivity": opportunity.sensitivity.as_ref().map(|s| serde_json::json!({
+            "base_profit_wei": s.base_profit_wei.to_string(),
+            "profit_after_1tick_wei": s.profit_after_1tick_wei.to_string(),
+            "profit_after_2tick_wei": s.profit_after_2tick_wei.to_string(),
+            "robustness": (s.robustness() * 1e4).round() / 1e4,
+        })),
+        // İki adımlı plan (TWO_STEP_SIZING) — 2. adım işlem sonrası state'te boyutlandırıldı
+        "two_step": opportunity.two_step.as_ref().map(|p| serde_json::json!({
+            "first_amount_weth": (p.first_amount_weth * 1e8).round() / 1e8,
+            "first_profit_weth": (p.first_profit_weth * 1e8).round() / 1e8,
+            "second_amount_weth": (p.second_amount_weth * 1e8).round() / 1e8,
+            "second_profit_weth": (p.second_profit_weth * 1e8).round() / 1e8,
+            "total_profit_weth": (p.total_profit_weth() * 1e8).round() / 1e8,
+        })),
+        // Flash kaynağı karşılaştırması — pool B kaynak anlamlı ölçüde iyiyse material=true
+        "flash_route": opportunity.flash_route.as_ref().map(flash_route_json),
+        // Kâr ayrıştırması: gross − LP ücretleri − flash − gas − bribe = net
+        "profit_breakdown": opportunity
+            .profit_breakdown
+            .map(|b| profit_breakdown_json(&b.with_bribe(dynamic_bribe_weth))),
         "sim_success": sim_result.success,
         "sim_error": sim_result.error.as_deref(),
-        "mode": "shadow",
+        "mode": mode,
+        // Degrade eden filtre (varsa) — gölge log, stats ve ret sayaçları aynı adı kullanır
+        "filter": opportunity.shadow_only.as_ref().map(|v| v.filter),
+        "filter_reason": opportunity.shadow_only.as_ref().map(|v| v.reason.as_str()),
+        // Bütünlük: build + config parmak izi + karar girdisi digest'i (--verify-log)
+        "integrity": integrity,
     });
+    // Kimlik + fiyat alanları LOG_FORMAT=json olaylarıyla ortak yapıdan
+    // (block + opportunity_id replay anahtarı — --replay-diff bunlarla eşleştirir)
+    if let (Some(entry), Ok(serde_json::Value::Object(fields))) = (
+        log_entry.as_object_mut(),
+        serde_json::to_value(OpportunityFields::new(opportunity, pools, block)),
+    ) {
+        entry.extend(fields);
+    }
+
+    // Disk yazımı (rotasyon + append) sıcak yoldaki worker'ı bloklamasın
+    crate::runtime_health::blocking_section("shadow_log_write", || {
+        append_shadow_entry(&log_entry, config.log_format_binary)
+    });
+}
 
+/// Gölge log satırını dosyaya ekle (50MB'ta rotasyon; LOG_FORMAT_BINARY → binlog)
+pub(crate) fn append_shadow_entry(log_entry: &serde_json::Value, binary: bool) {
     // v22.1: Dosya boyutu kontrol� � 50MB'� a�arsa rotate et
-    let log_path = std::path::Path::new("shadow_analytics.jsonl");
+    // LOG_FORMAT_BINARY → shadow_analytics.bin (binlog), aynı alanlar
+    let (log_path, ext) = if binary {
+        (crate::secure_fs::data_path(crate::replay::SHADOW_BIN_PATH), "bin")
+    } else {
+        (crate::secure_fs::data_path(crate::replay::SHADOW_LOG_PATH), "jsonl")
+    };
     const MAX_LOG_SIZE: u64 = 50 * 1024 * 1024; // 50 MB
-    if let Ok(metadata) = std::fs::metadata(log_path) {
+    if let Ok(metadata) = std::fs::metadata(&log_path) {
         if metadata.len() >= MAX_LOG_SIZE {
-            let rotated = format!("shadow_analytics.{}.jsonl",
-                chrono::Local::now().format("%Y%m%d_%H%M%S"));
-            let _ = std::fs::rename(log_path, &rotated);
-            eprintln!("  ?? Shadow log rotated — {}", rotated);
+            let rotated = crate::secure_fs::data_path(format!("shadow_analytics.{}.{}",
+                crate::clock::local_now().format("%Y%m%d_%H%M%S"), ext));
+            let _ = std::fs::rename(&log_path, &rotated);
+            eprintln_high!("  ?? Shadow log rotated — {}", rotated.display());
+        }
+    }
+
+    if binary {
+        // Bekleyen çerçeve flush anında dosyayı yeniden açar — rotasyon güvenli
+        let mut writer = SHADOW_BINARY_LOG.lock();
+        let writer = writer.get_or_insert_with(|| crate::binlog::BinaryLogWriter::new(&log_path));
+        if let Err(e) = writer.append_json(&log_entry.to_string()) {
+            eprintln_high!("  {} shadow_analytics.bin write error: {}", "⚠️".yellow(), e);
         }
+        return;
     }
 
     // Dosyaya append (sat�r sat�r)
-    match std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(log_path)
-    {
+    match crate::secure_fs::open_append(&log_path) {
         Ok(mut file) => {
             if let Err(e) = writeln!(file, "{}", log_entry) {
-                eprintln!(
+                eprintln_high!(
                     "  {} shadow_analytics.jsonl write error: {}",
                     "??".yellow(), e
                 );
             }
         }
         Err(e) => {
-            eprintln!(
+            eprintln_high!(
                 "  {} shadow_analytics.jsonl open error: {}",
                 "??".yellow(), e
             );
@@ -811,56 +2310,172 @@ fn write_shadow_log(
     }
 }
 
-// �����������������������������������������������������������������������������
-// Kontrat Tetikleme (Zincir �zeri) � MevExecutor �zerinden Private RPC
-// �����������������������������������������������������������������������������
+/// İkili gölge log yazıcısı — çerçeve FRAME_RECORDS satırda bir yazılır
+static SHADOW_BINARY_LOG: parking_lot::Mutex<Option<crate::binlog::BinaryLogWriter>> =
+    parking_lot::const_mutex(None);
 
-// v21.0: ProviderBuilder ve TransactionRequest art�k MevExecutor'da kullan�l�r.
-// strategy.rs do�rudan TX olu�turmaz.
+/// Bekleyen ikili gölge log satırlarını diske yaz (kapanışta çağrılır —
+/// static yazıcının Drop'u çalışmaz)
+pub fn flush_shadow_log() {
+    if let Some(writer) = SHADOW_BINARY_LOG.lock().as_mut() {
+        if let Err(e) = writer.flush() {
+            eprintln_high!("  {} shadow_analytics.bin flush error: {}", "⚠️".yellow(), e);
+        }
+    }
+}
 
-/// v21.0: Arbitraj kontrat�n� MevExecutor �zerinden Private RPC ile tetikle.
+/// Bir filtrenin Degrade ettiği fırsatı matematiksel simülasyondan geçir ve
+/// "<filtre>-skipped" etiketiyle gölge loga yaz (ör. "competition-skipped").
 ///
-/// Public mempool kullan�lmaz � t�m i�lemler eth_sendRawTransaction ile Private RPC'ye g�nderilir.
-/// Private RPC yoksa veya ba�ar�s�zsa i�lem �PTAL ED�L�R (nonce geri al�n�r).
-#[allow(clippy::too_many_arguments)]
-async fn execute_on_chain_protected(
-    mev_executor: Arc<crate::executor::MevExecutor>,
-    private_key: String,
-    contract_address: Address,
-    pool_a: Address,
-    pool_b: Address,
-    owed_token: Address,
-    received_token: Address,
-    trade_size_weth: f64,
-    uni_direction: u8,
-    aero_direction: u8,
-    min_profit: u128,
+/// Sezginin isabeti böyle ölçülür: atlanan fırsatların çoğu simülasyonda
+/// başarılıysa filtre fazla agresiftir. Dönüş: simülasyon başarılı mı?
+pub fn shadow_log_degraded(
+    pools: &[PoolConfig],
+    states: &[SharedPoolState],
+    opportunity: &ArbitrageOpportunity,
+    sim_engine: &SimulationEngine,
+    config: &BotConfig,
+) -> bool {
+    let sim_result = sim_engine.validate_mathematical(
+        pools,
+        states,
+        opportunity.buy_pool_idx,
+        opportunity.sell_pool_idx,
+        opportunity.optimal_amount_weth,
+    );
+    let (state_a, state_b) = (states[0].load(), states[1].load());
+    let inputs = DecisionInputs::capture(opportunity, pools, [&**state_a, &**state_b]);
+    write_shadow_log(
+        opportunity,
+        &sim_result,
+        pools,
+        state_a.last_block.max(state_b.last_block),
+        config,
+        sim_result.gas_used,
+        sim_result.source,
+        0.0,
+        0.0,
+        &format!(
+            "{}-skipped",
+            opportunity.shadow_only.as_ref().map(|v| v.filter).unwrap_or("filter"),
+        ),
+        LogIntegrity::new(config, &inputs, None),
+        None,
+    );
+    crate::revisit::offer(None, opportunity, pools, [states[0].load_full(), states[1].load_full()]);
+    sim_result.success
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Fırsat Snapshot Arşivi — Anlaşmazlık Analizi için Tam Girdi Kaydı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Yürütmeye ulaşan fırsatın tam karar girdilerini snapshot yazıcısına gönder.
+///
+/// Sadece Arc klonları ve 134-byte calldata hazırlanır; bitmap klonlama,
+/// serileştirme ve gzip sıkıştırma arka plan görevinde yapılır.
+/// `exact_profit_wei` None ise (shadow) yazıcı aynı girdilerle hesaplar.
+/// Dönüş: snapshot id'si (arşivleme kapalıysa None) — log satırı bunu taşır.
+#[allow(clippy::too_many_arguments)]
+fn submit_opportunity_snapshot(
+    mode: &'static str,
+    opportunity: &ArbitrageOpportunity,
+    pools: &[PoolConfig],
+    state_arcs: [Arc<PoolState>; 2],
+    amount_wei: U256,
+    config: &BotConfig,
+    exact_profit_wei: Option<U256>,
+    slippage_bps: u64,
+    min_profit: u128,
+) -> Option<String> {
+    if config.snapshot_keep == 0 {
+        return None;
+    }
+
+    let (uni_dir, aero_dir, owed_token, received_token) =
+        compute_directions_and_tokens(
+            opportunity.buy_pool_idx,
+            pools[0].token0_is_weth,
+            &pools[0].base_token_address,
+            &pools[0].quote_token_address,
+        );
+    let deadline_block = compute_deadline_block(state_arcs[0].last_block, config);
+    let calldata = crate::calldata_codec::encode_compact_calldata(
+        pools[0].address,
+        pools[1].address,
+        owed_token,
+        received_token,
+        amount_wei,
+        uni_dir,
+        aero_dir,
+        min_profit,
+        deadline_block,
+    );
+
+    let id = crate::snapshot::new_snapshot_id(state_arcs[0].last_block.max(state_arcs[1].last_block));
+    crate::snapshot::submit(crate::snapshot::SnapshotJob {
+        id: id.clone(),
+        mode,
+        opportunity: opportunity.clone(),
+        pools: [pools[0].clone(), pools[1].clone()],
+        states: state_arcs,
+        amount_wei,
+        uni_zero_for_one: uni_dir == 0,
+        aero_zero_for_one: aero_dir == 0,
+        exact_profit_wei,
+        slippage_bps,
+        min_profit,
+        calldata,
+        deadline_block,
+        bribe_pct: config.effective_bribe_pct(),
+        flash_loan_fee_bps: config.flash_loan_fee_bps,
+    });
+    Some(id)
+}
+
+// �����������������������������������������������������������������������������
+// Kontrat Tetikleme (Zincir �zeri) � MevExecutor �zerinden Private RPC
+// �����������������������������������������������������������������������������
+
+// v21.0: ProviderBuilder ve TransactionRequest art�k MevExecutor'da kullan�l�r.
+// strategy.rs do�rudan TX olu�turmaz.
+
+/// v21.0: Arbitraj kontrat�n� MevExecutor �zerinden Private RPC ile tetikle.
+///
+/// Public mempool kullan�lmaz � t�m i�lemler eth_sendRawTransaction ile Private RPC'ye g�nderilir.
+/// Private RPC yoksa veya ba�ar�s�zsa i�lem �PTAL ED�L�R (nonce geri al�n�r).
+#[allow(clippy::too_many_arguments)]
+async fn execute_on_chain_protected(
+    mev_executor: Arc<crate::executor::MevExecutor>,
+    contract_address: Address,
+    pool_a: Address,
+    pool_b: Address,
+    owed_token: Address,
+    received_token: Address,
+    trade_amount: TradeAmount,
+    uni_direction: u8,
+    aero_direction: u8,
+    min_profit: u128,
     deadline_block: u32,
-    simulated_gas: u64,
+    gas: crate::gas_calibration::GasSample,
     nonce: u64,
     nonce_manager: Arc<NonceManager>,
-    eth_price_in_quote: f64,
-    token0_is_weth: bool,
     block_base_fee: u64,
-    quote_token_decimals: u8,
     expected_profit_weth: f64,
     current_block: u64,
+    inflight: InflightGuard,
+    expiry: OpportunityExpiry,
+    impact: crate::impact_verification::PredictedImpact,
+    robustness: Option<f64>,
+    direction: Direction,
 ) {
-    println!("\n  {} {}", "??".yellow(), "CONTRACT EXECUTION STARTED (Private RPC)".yellow().bold());
-
-    // v10.0: Private key g�venli bellek y�netimi
-    let mut pk_owned = private_key;
-
-    // Calldata olu�tur
-    let weth_input = crate::types::is_weth_input(uni_direction, token0_is_weth);
-    let amount_in_wei = crate::types::weth_amount_to_input_wei(
-        trade_size_weth,
-        weth_input,
-        eth_price_in_quote,
-        quote_token_decimals,
-    );
+    let lane = if mev_executor.paper().is_some() { "PAPER — simulated receipt" } else { "Private RPC" };
+    println_high!("\n  {} {}", "??".yellow(), format!("CONTRACT EXECUTION STARTED ({})", lane).yellow().bold());
+
+    // Calldata yalnızca doğrulanmış miktardan kurulur (MIN_TRADE_SIZE_WETH + kontrollü wei)
+    let amount_in_wei = trade_amount.input_wei();
 
-    let calldata = crate::simulator::encode_compact_calldata(
+    let calldata = crate::calldata_codec::encode_compact_calldata(
         pool_a,
         pool_b,
         owed_token,
@@ -872,47 +2487,228 @@ async fn execute_on_chain_protected(
         deadline_block,
     );
 
-    let calldata_hex = crate::simulator::format_compact_calldata_hex(&calldata);
-    println!(
+    let calldata_hex = crate::calldata_codec::format_compact_calldata_hex(&calldata);
+    println_high!(
         "  {} Compact calldata (134 bytes): {}...{}",
         "??".cyan(),
         &calldata_hex[..22],
         &calldata_hex[calldata_hex.len().saturating_sub(10)..],
     );
 
-    println!(
+    println_high!(
         "  {} Sending TX (Private RPC)... (amount: {:.6} WETH, nonce: {}, deadline: block #{}, payload: 134 bytes)",
-        "??".yellow(), trade_size_weth, nonce, deadline_block
+        "??".yellow(), trade_amount.weth(), nonce, deadline_block
     );
 
     // MevExecutor �zerinden g�nder � Private RPC yoksa otomatik iptal
-    let result = mev_executor.execute_protected(
-        &pk_owned,
-        contract_address,
-        &calldata,
-        nonce,
-        expected_profit_weth,
-        simulated_gas,
-        block_base_fee,
-        current_block,
-        &nonce_manager,
-    ).await;
-
-    // �mza tamamland� � private key bellekten g�venle silinir
-    pk_owned.zeroize();
+    let result = run_guarded_execution(inflight, nonce, &nonce_manager, |inflight| {
+        mev_executor.execute_protected(
+            contract_address,
+            &calldata,
+            nonce,
+            expected_profit_weth,
+            gas,
+            block_base_fee,
+            current_block,
+            deadline_semantics().last_valid(deadline_block) as u64,
+            crate::gas_calibration::pair_route(uni_direction),
+            inflight,
+            &nonce_manager,
+            &expiry,
+            Some(impact),
+            robustness,
+            Some(direction),
+        )
+    }).await;
 
     match result {
         Ok(hash) => {
-            println!("  {} TX successful (Private RPC): {}", "?".green(), hash.green().bold());
+            println_high!("  {} TX successful (Private RPC): {}", "?".green(), hash.green().bold());
         }
         Err(e) => {
-            // TX zincire gitmediyse local nonce geri alınır.
-            nonce_manager.force_set(nonce);
-            println!("  {} TX error: {}", "?".red(), format!("{}", e).red());
+            println_high!("  {} TX error: {}", "?".red(), format!("{}", e).red());
         }
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Fırsat Ömrü — Uçuştaki Yürütmelerin Yeni Blokta Yeniden Doğrulanması
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Uçuştaki fırsatın yeni blokta nasıl yeniden doğrulanacağı
+enum Liveness {
+    /// İki havuzlu fırsat: referans boyuttaki yürütülebilir spread
+    /// iki havuzun fee toplamını hâlâ aşıyor mu? Fırsat donmuş snapshot'la
+    /// değerlendirilir — kayıt yalnızca havuz adreslerini tutar, yeniden
+    /// doğrulama ana döngünün canlı state'lerini okur.
+    Pair { buy: Address, sell: Address },
+    /// Multi-hop rota: ucuz yeniden kontrol yok — daha yeni bir blok
+    /// gönderilmemiş TX'i geçersiz kılar
+    Route,
+}
+
+impl Liveness {
+    /// Fırsat ölmüşse sebebi (canlıysa None)
+    fn dead_reason(&self, probe_weth: f64, pools: &[PoolConfig], states: &[SharedPoolState]) -> Option<String> {
+        let (buy, sell) = match self {
+            Liveness::Route => return Some("multi-hop route superseded by newer block".into()),
+            Liveness::Pair { buy, sell } => (buy, sell),
+        };
+        let leg = |address: &Address| {
+            let idx = pools.iter().position(|p| p.address == *address)?;
+            Some((&pools[idx], states.get(idx)?.load_full()))
+        };
+        let (Some((buy_pool, buy_state)), Some((sell_pool, sell_state))) = (leg(buy), leg(sell)) else {
+            return Some("pool no longer tracked".into());
+        };
+        let spread_pct = executable_spread_pct(
+            &executable_price(buy_pool, &buy_state, probe_weth),
+            &executable_price(sell_pool, &sell_state, probe_weth),
+        );
+        let fee_pct = (buy_pool.effective_fee(buy_state.live_fee_bps).bps()
+            + sell_pool.effective_fee(sell_state.live_fee_bps).bps()) as f64
+            / 100.0;
+        (spread_pct <= fee_pct).then(|| {
+            format!(
+                "spread {:.4}% <= fees {:.4}% at block #{}",
+                spread_pct,
+                fee_pct,
+                buy_state.last_block.max(sell_state.last_block),
+            )
+        })
+    }
+}
+
+struct LiveExecution {
+    trigger_block: u64,
+    deadline_block: u64,
+    liveness: Liveness,
+    expiry: OpportunityExpiry,
+}
+
+/// Uçuştaki yürütmelerin kaydı. Her spawn edilen görev bir
+/// `OpportunityExpiry` taşır; ana döngü her blok sync'inden sonra
+/// `revalidate` çağırır ve tetik bloğundan yeni bir blokta fırsat ölmüşse
+/// (veya deadline geçmişse) görevi imza/gönderim öncesi durdurur.
+#[derive(Default)]
+pub struct LiveOpportunities {
+    entries: Vec<LiveExecution>,
+    /// Bu blokta kaydedilen yürütmelerin gönderim bütçesi sonu
+    send_deadline: Option<std::time::Instant>,
+}
+
+impl LiveOpportunities {
+    /// Yeni blok: bu bloğun fırsatları en geç `deadline`'a kadar gönderilmeli
+    /// (gönderim zamanlaması bekleme bütçesini buradan alır)
+    pub fn set_send_deadline(&mut self, deadline: std::time::Instant) {
+        self.send_deadline = Some(deadline);
+    }
+
+    /// İki havuzlu fırsatı kaydet — görev dönen sinyali taşır
+    pub fn track_pair(
+        &mut self,
+        trigger_block: u64,
+        deadline_block: u64,
+        pools: &[PoolConfig],
+        opportunity: &ArbitrageOpportunity,
+    ) -> OpportunityExpiry {
+        self.track(trigger_block, deadline_block, Liveness::Pair {
+            buy: pools[opportunity.buy_pool_idx].address,
+            sell: pools[opportunity.sell_pool_idx].address,
+        })
+    }
+
+    /// Multi-hop rotayı kaydet
+    pub fn track_route(&mut self, trigger_block: u64, deadline_block: u64) -> OpportunityExpiry {
+        self.track(trigger_block, deadline_block, Liveness::Route)
+    }
+
+    fn track(&mut self, trigger_block: u64, deadline_block: u64, liveness: Liveness) -> OpportunityExpiry {
+        let expiry = OpportunityExpiry::with_send_deadline(self.send_deadline);
+        self.entries.push(LiveExecution {
+            trigger_block,
+            deadline_block,
+            liveness,
+            expiry: expiry.clone(),
+        });
+        expiry
+    }
+
+    /// `block` sync'i bittikten sonra kayıtları canlı `states` ile yeniden
+    /// doğrula. Dönüş: bu çağrıda iptal edilen yürütme sayısı. Canlı kalanlar
+    /// deadline'a kadar izlenir (bitmiş görevde iptal etkisizdir).
+    pub fn revalidate(
+        &mut self,
+        block: u64,
+        probe_weth: f64,
+        pools: &[PoolConfig],
+        states: &[SharedPoolState],
+    ) -> usize {
+        let mut expired = 0;
+        self.entries.retain(|entry| {
+            if entry.expiry.is_expired() {
+                return false;
+            }
+            let reason = if block > entry.deadline_block {
+                Some(format!("deadline block #{} passed", entry.deadline_block))
+            } else if block <= entry.trigger_block {
+                None
+            } else {
+                entry.liveness.dead_reason(probe_weth, pools, states)
+            };
+            match reason {
+                Some(reason) => {
+                    entry.expiry.expire(reason);
+                    expired += 1;
+                    false
+                }
+                None => true,
+            }
+        });
+        expired
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Eşzamanlı Yürütme Slotu
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Yürütme slotu al; sınır doluysa atlanan fırsatı say (`None`).
+/// Zirve eşzamanlılık her denemede stats'a yansıtılır (reconnect'te
+/// executor yenilense de oturum zirvesi korunur).
+fn reserve_execution_slot(limiter: &InflightLimiter, stats: &mut ArbitrageStats) -> Option<InflightGuard> {
+    let guard = limiter.try_acquire();
+    if guard.is_none() {
+        stats.inflight_cap_skips += 1;
+    }
+    stats.peak_inflight_executions = stats.peak_inflight_executions.max(limiter.peak());
+    guard
+}
+
+/// Yürütme görevinin slot sahipliği: `send` slotun bir kopyasını alır
+/// (başarıda receipt görevine taşınır). Gönderim başarısızsa TX zincire
+/// gitmemiştir — nonce NonceManager'a geri verilir ve slot ancak bundan
+/// sonra bırakılır, böylece bekleyen yürütme geri verilen nonce'u alır.
+/// Eşzamanlı uçuştaki daha yüksek nonce'lar yeniden verilmez.
+async fn run_guarded_execution<F, Fut>(
+    inflight: InflightGuard,
+    nonce: u64,
+    nonce_manager: &NonceManager,
+    send: F,
+) -> BotResult<String>
+where
+    F: FnOnce(Arc<InflightGuard>) -> Fut,
+    Fut: std::future::Future<Output = BotResult<String>>,
+{
+    let inflight = Arc::new(inflight);
+    let result = send(Arc::clone(&inflight)).await;
+    if result.is_err() {
+        nonce_manager.release(nonce);
+    }
+    drop(inflight);
+    result
+}
+
 // �����������������������������������������������������������������������������
 // Y�n ve Token Hesaplama Yard�mc�lar�
 // �����������������������������������������������������������������������������
@@ -948,13 +2744,208 @@ fn compute_directions_and_tokens(
     } else {
         // token0 = quote, token1 = base (ters d�zen: cbETH < WETH)
         if buy_pool_idx == 0 {
-            (0u8, 1u8, *base_token_address, *quote_token_address) // owe Base, receive Quote
+            // Pool 0'dan base (token1) al: quote (token0) öde → zeroForOne(0), borç Quote
+            (0u8, 1u8, *quote_token_address, *base_token_address) // owe Quote, receive Base
         } else {
-            (1u8, 0u8, *quote_token_address, *base_token_address) // owe Quote, receive Base
+            // Pool 0'a base (token1) öde → oneForZero(1), borç Base
+            (1u8, 0u8, *base_token_address, *quote_token_address) // owe Base, receive Quote
+        }
+    }
+}
+
+/// Fırsatın yürütme miktarı — MIN_TRADE_SIZE_WETH tabanı + kontrollü wei dönüşümü.
+/// Taban altı, NaN ya da u128 dışı miktar Err döner; calldata kurulmaz.
+fn execution_trade_amount(
+    opportunity: &ArbitrageOpportunity,
+    pools: &[PoolConfig],
+    config: &BotConfig,
+) -> BotResult<TradeAmount> {
+    let (uni_dir, ..) = compute_directions_and_tokens(
+        opportunity.buy_pool_idx,
+        pools[0].token0_is_weth,
+        &pools[0].base_token_address,
+        &pools[0].quote_token_address,
+    );
+    TradeAmount::new(
+        opportunity.optimal_amount_weth,
+        config.min_trade_size_weth,
+        crate::types::is_weth_input(uni_dir, pools[0].token0_is_weth),
+        opportunity.reference_price_quote,
+        if pools[0].token0_is_weth { pools[0].token1_decimals } else { pools[0].token0_decimals },
+    )
+}
+
+/// Havuzun (token0, token1) adresleri — PoolConfig base/quote + sıra bayrağından
+fn pool_token_pair(pool: &PoolConfig) -> (Address, Address) {
+    if pool.token0_is_weth {
+        (pool.base_token_address, pool.quote_token_address)
+    } else {
+        (pool.quote_token_address, pool.base_token_address)
+    }
+}
+
+/// owedToken cinsinden kâr (wei) — birim, kârı hesaplayan yoldan etiketlenir.
+///
+/// Exact yön modeli kârı pool_a'ya girdi olarak ödenen token cinsinden verir:
+/// uni_direction=0 (zeroForOne) → token0, 1 → token1. Etiket
+/// compute_directions_and_tokens'tan bağımsız türetilir ki çapraz kontrol olsun.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct OwedProfit {
+    pub token: Address,
+    pub wei: U256,
+}
+
+impl OwedProfit {
+    pub(crate) fn from_exact(pool_a: &PoolConfig, uni_dir: u8, wei: U256) -> Self {
+        let (token0, token1) = pool_token_pair(pool_a);
+        Self {
+            token: if uni_dir == 0 { token0 } else { token1 },
+            wei,
+        }
+    }
+}
+
+/// Flash swap yön / token değişmezinin ihlali — kod ya da config hatası
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DirectionInvariantError {
+    /// owedToken, pool_a'nın uni_direction ile talep edeceği token değil
+    OwedToken { expected: Address, got: Address },
+    /// receivedToken, pool_a'nın diğer tokeni değil
+    ReceivedToken { expected: Address, got: Address },
+    /// pool_b aynı token çiftini taşımıyor
+    PoolBPair,
+    /// aero_direction receivedToken'ı pool_b'ye satmıyor (pool_b token sırası farklı)
+    AeroDirection { expected: u8, got: u8 },
+    /// minProfit owedToken dışında bir token cinsinden hesaplanmış
+    ProfitUnit { profit_token: Address, owed_token: Address },
+}
+
+impl std::fmt::Display for DirectionInvariantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OwedToken { expected, got } => {
+                write!(f, "owed token {} but pool_a demands {}", got, expected)
+            }
+            Self::ReceivedToken { expected, got } => {
+                write!(f, "received token {} but pool_a pays {}", got, expected)
+            }
+            Self::PoolBPair => write!(f, "pool_b does not trade the pool_a token pair"),
+            Self::AeroDirection { expected, got } => write!(
+                f,
+                "aero direction {} does not sell the received token into pool_b (expected {})",
+                got, expected
+            ),
+            Self::ProfitUnit { profit_token, owed_token } => write!(
+                f,
+                "minProfit computed in {} units but the contract checks {}",
+                profit_token, owed_token
+            ),
+        }
+    }
+}
+
+/// Calldata'ya girmeden önce yön / token / kâr birimi tutarlılığı.
+///
+/// - owedToken = pool_a'nın uni_direction ile talep ettiği token
+///   (zeroForOne → token0, oneForZero → token1), receivedToken = diğeri
+/// - pool_b aynı çifti taşır ve aero_direction receivedToken'ı satar
+///   (receivedToken = pool_b.token0 → zeroForOne)
+/// - minProfit owedToken cinsindendir (kontrat balAfter(owedToken) ölçer)
+fn check_direction_invariants(
+    pools: &[PoolConfig],
+    uni_dir: u8,
+    aero_dir: u8,
+    owed_token: Address,
+    received_token: Address,
+    profit: &OwedProfit,
+) -> Result<(), DirectionInvariantError> {
+    let (a0, a1) = pool_token_pair(&pools[0]);
+    let (expected_owed, expected_received) = if uni_dir == 0 { (a0, a1) } else { (a1, a0) };
+    if owed_token != expected_owed {
+        return Err(DirectionInvariantError::OwedToken { expected: expected_owed, got: owed_token });
+    }
+    if received_token != expected_received {
+        return Err(DirectionInvariantError::ReceivedToken {
+            expected: expected_received,
+            got: received_token,
+        });
+    }
+    let (b0, b1) = pool_token_pair(&pools[1]);
+    if !((b0 == a0 && b1 == a1) || (b0 == a1 && b1 == a0)) {
+        return Err(DirectionInvariantError::PoolBPair);
+    }
+    let expected_aero = if received_token == b0 { 0 } else { 1 };
+    if aero_dir != expected_aero {
+        return Err(DirectionInvariantError::AeroDirection { expected: expected_aero, got: aero_dir });
+    }
+    if profit.token != owed_token {
+        return Err(DirectionInvariantError::ProfitUnit {
+            profit_token: profit.token,
+            owed_token,
+        });
+    }
+    Ok(())
+}
+
+/// Değişmez kontrolü + ihlalde yüksek sesli ret: debug build'de panik,
+/// release'de sayaç + hata logu (fırsat gönderilmez).
+fn enforce_direction_invariants(
+    pools: &[PoolConfig],
+    directions: (u8, u8, Address, Address),
+    profit: &OwedProfit,
+    stats: &mut ArbitrageStats,
+) -> bool {
+    let (uni_dir, aero_dir, owed_token, received_token) = directions;
+    let result = check_direction_invariants(pools, uni_dir, aero_dir, owed_token, received_token, profit);
+    debug_assert!(
+        result.is_ok(),
+        "flash swap direction invariant violated ({} / {}): {:?}",
+        pools[0].name,
+        pools[1].name,
+        result
+    );
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            stats.direction_invariant_rejections += 1;
+            eprintln_high!(
+                "  {} {} [{} / {}]: {} — opportunity REJECTED (code/config bug, not sent)",
+                "🛑".red(),
+                "DIRECTION INVARIANT".red().bold(),
+                pools[0].name,
+                pools[1].name,
+                e,
+            );
+            crate::json_logger::log_json("error", "direction_invariant", serde_json::json!({
+                "pool_a": pools[0].name,
+                "pool_b": pools[1].name,
+                "error": e.to_string(),
+            }));
+            false
         }
     }
 }
 
+/// Kontratın deadline semantiği — başlangıçta REVM ile doğrulanır;
+/// doğrulanamazsa kontrat kaynağındaki inclusive varsayım geçerlidir.
+static DEADLINE_SEMANTICS: parking_lot::Mutex<DeadlineSemantics> =
+    parking_lot::const_mutex(DeadlineSemantics::Inclusive);
+
+/// Doğrulanan deadline semantiğini kaydet (main, başlangıçta)
+pub fn set_deadline_semantics(semantics: DeadlineSemantics) {
+    *DEADLINE_SEMANTICS.lock() = semantics;
+}
+
+pub fn deadline_semantics() -> DeadlineSemantics {
+    *DEADLINE_SEMANTICS.lock()
+}
+
+/// Calldata'ya yazılacak deadlineBlock. Son yürütülebilir blok her iki
+/// semantikte de current + DEADLINE_BLOCKS (minimum +3 tolerans) kalır.
+fn compute_deadline_block(current_block: u64, config: &BotConfig) -> u32 {
+    deadline_semantics().encode(current_block as u32 + config.deadline_blocks.max(3))
+}
+
 /// minProfit hesapla (owedToken cinsinden, uint128 wei)
 ///
 /// math::exact::compute_exact_arbitrage_profit ile hesaplanan
@@ -978,6 +2969,10 @@ fn compute_min_profit_exact(exact_profit_wei: U256, slippage_factor_bps: u64) ->
     }
 }
 
+/// determine_slippage_factor_bps eşikleri (18-desimale normalize likidite)
+const DEEP_POOL_NORMALIZED_LIQUIDITY: u128 = 1_000_000_000_000_000; // 1e15
+const MID_POOL_NORMALIZED_LIQUIDITY: u128 = 10_000_000_000_000; // 1e13
+
 /// Havuz likidite derinli�ine g�re slippage fakt�r� hesapla (bps cinsinden)
 ///
 /// v24.0: Token desimal-duyarl� normalizasyon.
@@ -998,23 +2993,25 @@ fn determine_slippage_factor_bps(
 ) -> u64 {
     // Her havuzun likiditesini 18-desimale normalize et.
     // Uniswap V3'te L parametresi sqrt(token0 * token1) biriminde olup
-    // desimal fark� (token0_decimals + token1_decimals) / 2 kadar dengelenmeli.
-    let normalize = |liq: u128, pool: &PoolConfig| -> f64 {
-        let avg_decimals = (pool.token0_decimals as f64 + pool.token1_decimals as f64) / 2.0;
-        let scale = 10f64.powi(18 - avg_decimals as i32);
-        liq as f64 * scale
+    // desimal farkı (token0_decimals + token1_decimals) / 2 kadar dengelenmeli.
+    // u128 üzerinde: 2^53 üstündeki likiditeler f64'te birleşip eşiği yanlış
+    // taraftan geçebilir. Bölmede taban alınır — floor(L/d) ≥ T ⇔ L ≥ T·d.
+    let normalize = |liq: u128, pool: &PoolConfig| -> u128 {
+        let avg_decimals = (u32::from(pool.token0_decimals) + u32::from(pool.token1_decimals)) / 2;
+        match 18u32.checked_sub(avg_decimals) {
+            Some(up) => liq.saturating_mul(10u128.pow(up)),
+            None => 10u128.checked_pow(avg_decimals - 18).map_or(0, |down| liq / down),
+        }
     };
 
-    let norm_buy = normalize(buy_liquidity, buy_pool);
-    let norm_sell = normalize(sell_liquidity, sell_pool);
-    let min_normalized = norm_buy.min(norm_sell);
+    let min_normalized = normalize(buy_liquidity, buy_pool).min(normalize(sell_liquidity, sell_pool));
 
-    if min_normalized >= 1e15 {
-        9950 // %99.5 � derin havuz
-    } else if min_normalized >= 1e13 {
-        9900 // %99.0 � orta derinlik
+    if min_normalized >= DEEP_POOL_NORMALIZED_LIQUIDITY {
+        9950 // %99.5 — derin havuz
+    } else if min_normalized >= MID_POOL_NORMALIZED_LIQUIDITY {
+        9900 // %99.0 — orta derinlik
     } else {
-        9500 // %95.0 � s�� havuz, konservatif
+        9500 // %95.0 — sığ havuz, konservatif
     }
 }
 
@@ -1026,9 +3023,19 @@ fn determine_slippage_factor_bps(
 fn print_simulation_failure(
     opp: &ArbitrageOpportunity,
     sim: &SimulationResult,
-    _pools: &[PoolConfig],
+    pools: &[PoolConfig],
+    block: u64,
 ) {
-    println!(
+    let emitted = crate::log_event::json(|| {
+        LogEvent::SimulationFailure(crate::log_event::SimulationFailure {
+            fields: OpportunityFields::new(opp, pools, block),
+            sim_error: sim.error.clone().unwrap_or_else(|| "Unknown".into()),
+        })
+    });
+    if emitted {
+        return;
+    }
+    println_high!(
         "     {} [{}] REVM Simulation FAILED | Spread: {:.4}% | Reason: {}",
         "??".yellow(),
         timestamp().dimmed(),
@@ -1037,69 +3044,170 @@ fn print_simulation_failure(
     );
 }
 
+/// Rapor satırı: motor adı + gas metni — sezgisel gas ölçüm gibi gösterilmez
+fn sim_report_labels(sim: &SimulationResult) -> (&'static str, String) {
+    match sim.source {
+        SimSource::Revm => ("REVM Simulation ", sim.gas_used.to_string()),
+        SimSource::MathFallback => ("Math Validation ", format!("{} (heuristic)", sim.gas_used)),
+    }
+}
+
 /// K�rl� f�rsat raporu
 fn print_opportunity_report(
     opp: &ArbitrageOpportunity,
     sim: &SimulationResult,
     pools: &[PoolConfig],
     config: &BotConfig,
+    block: u64,
 ) {
+    let emitted = crate::log_event::json(|| {
+        LogEvent::Opportunity(crate::log_event::Opportunity {
+            fields: OpportunityFields::new(opp, pools, block),
+            gas_used: sim.gas_used,
+            sim_source: sim.source.label(),
+        })
+    });
+    if emitted {
+        return;
+    }
     let buy = &pools[opp.buy_pool_idx];
     let sell = &pools[opp.sell_pool_idx];
 
-    println!();
-    println!("{}", "  -===========================================================�".red().bold());
-    println!("{}", "  �     ??????  PROFITABLE ARBITRAGE OPPORTUNITY  ??????              �".red().bold());
-    println!("{}", "  �===========================================================�".red().bold());
-    println!("  {}  Time             : {}", "�".red(), timestamp().white().bold());
-    println!(
+    println_high!();
+    println_high!("{}", "  -===========================================================�".red().bold());
+    println_high!("{}", "  �     ??????  PROFITABLE ARBITRAGE OPPORTUNITY  ??????              �".red().bold());
+    println_high!("{}", "  �===========================================================�".red().bold());
+    println_high!("  {}  Time             : {}", "�".red(), timestamp().white().bold());
+    println_high!(
         "  {}  Direction        : {} — {}",
         "�".red(),
         format!("BUY from {} ({:.6} Q)", buy.name, opp.buy_price_quote).green().bold(),
         format!("SELL to {} ({:.6} Q)", sell.name, opp.sell_price_quote).red().bold(),
     );
-    println!("  {}  Spread           : {:.4}%", "�".red(), opp.spread_pct);
-    println!("  {}  ������������������������������������������������������", "�".red());
-    println!(
+    println_high!("  {}  Spread           : {:.4}%", "�".red(), opp.spread_pct);
+    println_high!(
+        "  {}  Bitmap Quality   : {}",
+        "�".red(),
+        if opp.bitmap_quality.is_real() {
+            opp.bitmap_quality.to_string().green()
+        } else {
+            format!("{} (dampening heuristic — rough estimate)", opp.bitmap_quality).yellow()
+        },
+    );
+    println_high!("  {}  ������������������������������������������������������", "�".red());
+    println_high!(
         "  {}  Optimal Amount   : {} WETH (Newton-Raphson: {}i, {})",
         "�".red(),
         format!("{:.6}", opp.optimal_amount_weth).white().bold(),
         opp.nr_iterations,
         if opp.nr_converged { "converged".green() } else { "not converged".yellow() },
     );
-    println!(
+    println_high!(
         "  {}  ?? NET PROFIT    : {:.6} WETH",
         "�".red(),
         format!("{:.6}", opp.expected_profit_weth).green().bold(),
     );
-    println!(
-        "  {}  REVM Simulation  : {} (Gas: {})",
+    if let Some(b) = opp.profit_breakdown {
+        // Bribe tahmini yürütmedekiyle aynı — net satırı tablonun toplamıdır
+        let b = b.with_bribe(opp.expected_profit_weth * config.effective_bribe_pct());
+        let share = |weth: f64| if b.gross_weth > 0.0 { weth / b.gross_weth * 100.0 } else { 0.0 };
+        println_high!("  {}  Profit Breakdown : (WETH, % of gross)", "�".red());
+        for (label, weth) in [
+            ("Gross spread ", b.gross_weth),
+            ("LP fee leg 1 ", -b.fee_leg1_weth),
+            ("LP fee leg 2 ", -b.fee_leg2_weth),
+            ("Flash fee    ", -b.flash_fee_weth),
+            ("Gas          ", -b.gas_weth),
+            ("Bribe (est.) ", -b.bribe_weth),
+            ("Net          ", b.net_weth),
+        ] {
+            println_high!("  {}    {} : {:>12.8} ({:>6.1}%)", "�".red(), label, weth, share(weth));
+        }
+    }
+    if let Some(ref adv) = opp.adverse {
+        let adverse_profit = format!(
+            "{} wei (base {} wei, minProfit {})",
+            adv.adverse_profit_wei, adv.base_profit_wei, adv.min_profit,
+        );
+        println_high!(
+            "  {}  Adverse Scenario : {:.4} WETH front-run → {}",
+            "�".red(),
+            adv.frontrun_weth,
+            if adv.would_revert() { adverse_profit.yellow() } else { adverse_profit.green() },
+        );
+        println_high!(
+            "  {}  Revert Boundary  : {}",
+            "�".red(),
+            adv.revert_boundary_weth
+                .map_or("not reached (search bound)".to_string(), |w| format!("{:.4} WETH front-run", w)),
+        );
+    }
+    if let Some(ref sens) = opp.sensitivity {
+        let profits = format!(
+            "{} / {} / {} wei (robustness {:.2})",
+            sens.base_profit_wei, sens.profit_after_1tick_wei, sens.profit_after_2tick_wei, sens.robustness(),
+        );
+        println_high!(
+            "  {}  Tick Sensitivity : base / 1 tick / 2 tick → {}",
+            "�".red(),
+            if sens.survives_1tick() { profits.green() } else { profits.yellow() },
+        );
+    }
+    if let Some(ref plan) = opp.two_step {
+        println_high!(
+            "  {}  Two-Step Plan    : {:.6} WETH → {:.8} + {:.6} WETH → {:.8} = {} WETH",
+            "�".red(),
+            plan.first_amount_weth,
+            plan.first_profit_weth,
+            plan.second_amount_weth,
+            plan.second_profit_weth,
+            format!("{:.8} (×{:.2})", plan.total_profit_weth(), plan.uplift()).green(),
+        );
+    }
+    if let Some(route) = opp.flash_route.as_ref().filter(|r| r.is_material()) {
+        println_high!(
+            "  {}  Flash Route      : {} → {:.6} WETH @ {:.4} WETH (pool_a {:.6} WETH, foregone {})",
+            "�".red(),
+            route.preferred,
+            route.reversed_profit_weth,
+            route.reversed_amount_weth,
+            route.current_profit_weth,
+            format!("{:.6}", route.foregone_improvement_weth()).yellow(),
+        );
+    }
+    if let Some(history) = crate::heatmap::current_context(crate::clock::now()) {
+        println_high!("  {}  Hour History     : {}", "�".red(), history.dimmed());
+    }
+    let (sim_label, gas_label) = sim_report_labels(sim);
+    println_high!(
+        "  {}  {} : {} (Gas: {})",
         "�".red(),
+        sim_label,
         if sim.success { "PASSED".green().bold() } else { "FAILED".red().bold() },
-        sim.gas_used,
+        gas_label,
     );
 
     if config.execution_enabled() {
-        println!(
+        println_high!(
             "  {}  Status           : {}",
             "�".red(),
             "?? EXECUTING CONTRACT...".yellow().bold()
         );
     } else if config.shadow_mode() {
-        println!(
+        println_high!(
             "  {}  Status           : {}",
             "�".red(),
             "?? SHADOW MODE — logged to shadow_analytics.jsonl".yellow().bold()
         );
     } else {
-        println!(
+        println_high!(
             "  {}  Status           : {}",
             "�".red(),
             "?? Observation Mode (execution disabled)".dimmed()
         );
     }
-    println!("{}", "  L===========================================================-".red().bold());
-    println!();
+    println_high!("{}", "  L===========================================================-".red().bold());
+    println_high!();
 }
 
 // �����������������������������������������������������������������������������
@@ -1147,6 +3255,7 @@ pub fn check_multi_hop_opportunities(
 ) -> Vec<crate::types::MultiHopOpportunity> {
     let mut opportunities = Vec::new();
     let l1_data_fee_weth = l1_data_fee_wei as f64 / 1e18;
+    let max_staleness = crate::staleness::cutoff(config, crate::staleness::Stage::Detection);
 
     for (route_idx, route) in routes.iter().enumerate() {
         // Sadece 3+ hop rotalar�n� i�le (2-hop'lar mevcut sistem taraf�ndan kapsan�yor)
@@ -1159,7 +3268,7 @@ pub fn check_multi_hop_opportunities(
         let all_active = route.hops.iter().all(|hop| {
             if hop.pool_idx < states.len() {
                 let state = states[hop.pool_idx].load();
-                state.is_active() && state.staleness_ms() <= config.max_staleness_ms
+                state.is_active() && state.staleness_ms() <= max_staleness
             } else {
                 false
             }
@@ -1181,12 +3290,8 @@ pub fn check_multi_hop_opportunities(
 
         // Multi-hop gas tahmini: base 310K + hop ba��na 130K ek
         let multi_hop_gas: u64 = 310_000 + (route.hop_count() as u64 - 2) * 130_000;
-        let dynamic_gas_cost_weth = if block_base_fee > 0 {
-            let l2 = (multi_hop_gas as f64 * block_base_fee as f64) / 1e18;
-            ((l2 + l1_data_fee_weth) * 1.10).max(0.000001)
-        } else {
-            ((config.gas_cost_fallback_weth + l1_data_fee_weth) * 1.10).max(0.000001)
-        };
+        let dynamic_gas_cost_weth =
+            gas_cost_weth(multi_hop_gas, block_base_fee, l1_data_fee_weth, config.gas_cost_fallback_weth);
 
         // Ortalama ETH fiyat� (ilk havuzdan)
         let avg_price = pool_states[0].eth_price_usd.max(1.0);
@@ -1200,6 +3305,7 @@ pub fn check_multi_hop_opportunities(
             gas_cost_usd,
             avg_price,
             config.max_trade_size_weth,
+            config.min_trade_size_weth,
         );
 
         // K�r� WETH'e �evir
@@ -1295,6 +3401,7 @@ pub async fn evaluate_and_execute_multi_hop<P: Provider + Sync>(
     mev_executor: &Arc<crate::executor::MevExecutor>,
     telegram_sender: &Option<crate::telegram::TelegramSender>,
     tg_counters: &mut crate::telegram::TelemetryCounters,
+    live: &mut LiveOpportunities,
 ) -> Option<u64> {
     // Sıfır/NaN koruması
     if opportunity.optimal_amount_weth <= 0.0
@@ -1304,18 +3411,27 @@ pub async fn evaluate_and_execute_multi_hop<P: Provider + Sync>(
     }
 
     // Veri tazeliği kontrolü — tüm hop havuzları is_fresh() ile
+    let max_staleness = crate::staleness::cutoff(config, crate::staleness::Stage::Execution);
     for &pool_idx in &opportunity.pool_indices {
         if pool_idx >= states.len() { return None; }
         let state = states[pool_idx].load();
-        if !state.is_fresh(config.max_staleness_ms) {
-            eprintln!(
-                "     \u{1f6d1} [Multi-Hop FreshnessGate] Pool #{} stale/outdated: {}ms (threshold={}ms)",
-                pool_idx, state.staleness_ms(), config.max_staleness_ms,
+        if !state.is_fresh(max_staleness) {
+            let adaptive = state.is_active()
+                && crate::staleness::record_rejection(crate::staleness::Stage::Execution, state.staleness_ms());
+            eprintln_high!(
+                "     🛑 [Multi-Hop FreshnessGate] Pool #{} stale/outdated: {}ms (threshold={}ms{})",
+                pool_idx, state.staleness_ms(), max_staleness, if adaptive { ", adaptive" } else { "" },
             );
             return None;
         }
     }
 
+    // V2 bacağı olan rota kontratta yürütülemez (bkz. constant_product_verdict)
+    if let Some(pool) = opportunity.pool_indices.iter().map(|&i| &pools[i]).find(|p| p.is_constant_product()) {
+        println_high!("     👻 [Multi-Hop] {} is constant-product — route priced only, not executed", pool.name);
+        return None;
+    }
+
     // Hop adresleri ve y�nleri
     let pool_addrs: Vec<Address> = opportunity.pool_indices.iter()
         .map(|&i| pools[i].address).collect();
@@ -1336,13 +3452,13 @@ pub async fn evaluate_and_execute_multi_hop<P: Provider + Sync>(
     );
 
     if exact_profit.is_zero() {
-        eprintln!("     ?? [Multi-Hop] Exact profit zero — skipping");
+        eprintln_high!("     ?? [Multi-Hop] Exact profit zero — skipping");
         return None;
     }
 
     // Deadline block
     let current_block = states[opportunity.pool_indices[0]].load().last_block;
-    let deadline_block = current_block as u32 + config.deadline_blocks.max(3);
+    let deadline_block = compute_deadline_block(current_block, config);
 
     // Dinamik slippage-adjusted minProfit
     let min_liq = opportunity.pool_indices.iter()
@@ -1355,19 +3471,18 @@ pub async fn evaluate_and_execute_multi_hop<P: Provider + Sync>(
     } else {
         9500
     };
+    let slippage_bps = crate::impact_verification::apply_slippage_bump(slippage_bps, current_block);
     let min_profit = compute_min_profit_exact(exact_profit, slippage_bps);
 
     // Multi-hop calldata olu�tur
-    let calldata = crate::simulator::encode_multi_hop_calldata(
+    let calldata = crate::calldata_codec::encode_multi_hop_calldata(
         &pool_addrs, &dirs_u8, amount_wei, min_profit, deadline_block,
     );
 
     // REVM sim�lasyonu (kontrat adresi varsa)
-    let revm_result = if let Some(contract_addr) = config.contract_address {
-        let caller = config.private_key.as_ref()
-            .and_then(|pk| pk.parse::<PrivateKeySigner>().ok())
-            .map(|signer| signer.address())
-            .unwrap_or_default();
+    let sim_result = if let Some(contract_addr) = config.contract_address {
+        // Başlangıçta bir kez çözülmüş imzalayıcı adresi (key parse yok)
+        let caller = mev_executor.signer_address();
 
         sim_engine.simulate(
             pools,
@@ -1391,105 +3506,160 @@ pub async fn evaluate_and_execute_multi_hop<P: Provider + Sync>(
         )
     };
 
-    let simulated_gas_used = revm_result.gas_used;
+    stats.record_sim_source(sim_result.source);
+    // Gas limiti / bribe yalnızca REVM ölçümüyle — sezgisel tahmin yerine GAS_ESTIMATE
+    let simulated_gas_used = sim_result.execution_gas(config.gas_estimate);
 
-    if !revm_result.success {
+    if !sim_result.success {
         stats.failed_simulations += 1;
         stats.consecutive_failures += 1;
         tg_counters.reverts += 1;
-        eprintln!(
+        crate::circuit_breaker::record_failure(
+            crate::circuit_breaker::FailureKind::Simulation,
+            sim_result.error.as_deref().unwrap_or("multi-hop simulation failed"),
+        );
+        eprintln_high!(
             "     ?? [Multi-Hop] REVM Simulation FAILED: {}",
-            revm_result.error.as_deref().unwrap_or("Unknown"),
+            sim_result.error.as_deref().unwrap_or("Unknown"),
         );
         return None;
     }
 
     stats.consecutive_failures = 0;
+    crate::circuit_breaker::record_validation();
     stats.profitable_opportunities += 1;
     stats.total_potential_profit += opportunity.expected_profit_weth;
     if opportunity.expected_profit_weth > stats.max_profit_weth {
         stats.max_profit_weth = opportunity.expected_profit_weth;
     }
 
-    println!();
-    println!("{}", "  -===========================================================�".red().bold());
-    println!("{}", "  �  ????  MULTI-HOP PROFITABLE ARBITRAGE OPPORTUNITY  ????           �".red().bold());
-    println!("{}", "  �===========================================================�".red().bold());
-    println!("  {}  Route            : {} ({})", "�".red(), opportunity.label, opportunity.hop_count);
-    println!("  {}  Optimal Amount   : {:.6} WETH", "�".red(), opportunity.optimal_amount_weth);
-    println!("  {}  ?? NET PROFIT    : {:.6} WETH", "�".red(), opportunity.expected_profit_weth);
-    println!("  {}  Exact Profit     : {} wei", "�".red(), exact_profit);
-    println!("  {}  Calldata         : {} bytes ({}-hop)", "�".red(), calldata.len(), opportunity.hop_count);
-    println!("  {}  REVM Simulation  : PASSED (Gas: {})", "�".red(), simulated_gas_used);
-    println!("{}", "  L===========================================================-".red().bold());
-    println!();
+    println_high!();
+    println_high!("{}", "  -===========================================================�".red().bold());
+    println_high!("{}", "  �  ????  MULTI-HOP PROFITABLE ARBITRAGE OPPORTUNITY  ????           �".red().bold());
+    println_high!("{}", "  �===========================================================�".red().bold());
+    println_high!("  {}  Route            : {} ({})", "�".red(), opportunity.label, opportunity.hop_count);
+    println_high!("  {}  Optimal Amount   : {:.6} WETH", "�".red(), opportunity.optimal_amount_weth);
+    println_high!("  {}  ?? NET PROFIT    : {:.6} WETH", "�".red(), opportunity.expected_profit_weth);
+    println_high!("  {}  Exact Profit     : {} wei", "�".red(), exact_profit);
+    println_high!("  {}  Calldata         : {} bytes ({}-hop)", "�".red(), calldata.len(), opportunity.hop_count);
+    let (sim_label, gas_label) = sim_report_labels(&sim_result);
+    println_high!("  {}  {} : PASSED (Gas: {})", "�".red(), sim_label, gas_label);
+    println_high!("{}", "  L===========================================================-".red().bold());
+    println_high!();
 
     // G�lge modu veya ger�ek y�r�tme
     if config.shadow_mode() {
-        if revm_result.success {
+        if sim_result.success {
             stats.shadow_sim_success += 1;
             stats.shadow_cumulative_profit += opportunity.expected_profit_weth;
         } else {
             stats.shadow_sim_fail += 1;
         }
-        println!(
+        println_high!(
             "  {} {}",
             "??".yellow(),
             "SHADOW MODE: Multi-hop trade skipped — logged to shadow log".yellow().bold()
         );
-    } else if config.execution_enabled() {
-        let pk = config.private_key.clone()
-            .expect("BUG: execution_enabled() true ama private_key None");
+    }
+
+    // PAPER_EXECUTION: ikili yolla aynı kağıt şerit
+    let paper = mev_executor.paper();
+    if config.execution_enabled() || paper.is_some() {
+        let nonce_manager = paper.map_or(nonce_manager, |p| &p.nonce_manager);
         let contract_addr = config.contract_address
+            .or(paper.map(|_| Address::ZERO))
             .expect("BUG: execution_enabled() true ama contract_address None");
 
+        // Duraklatılmış kontrat — ikili yolla aynı kapı
+        if crate::contract_state::execution_block().is_some() {
+            println_high!(
+                "  {} {}",
+                "🛑".red(),
+                "CONTRACT PAUSED: multi-hop trade skipped".red().bold()
+            );
+            return Some(simulated_gas_used);
+        }
+
+        // Eşzamanlı yürütme sınırı — ikili yolla aynı slotlar
+        let Some(inflight) = reserve_execution_slot(&mev_executor.inflight, stats) else {
+            println_high!(
+                "  {} {}",
+                "⏸️".yellow(),
+                format!(
+                    "INFLIGHT CAP: {} executions in flight — multi-hop trade skipped",
+                    mev_executor.inflight.active(),
+                ).yellow().bold()
+            );
+            return Some(simulated_gas_used);
+        };
+
         let nonce = nonce_manager.get_and_increment();
         let nm_clone = Arc::clone(nonce_manager);
 
-        stats.executed_trades += 1;
-
-        // v32.0: Telegram — Multi-hop Alfa Bildirimi
-        let gas_cost_weth_mh = simulated_gas_used as f64 * block_base_fee as f64 / 1e18;
-        if let Some(ref tg) = telegram_sender {
-            tg.send(crate::telegram::TelegramMessage::AlphaSuccess {
-                buy_pool: format!("Multi-Hop {}", opportunity.label),
-                sell_pool: format!("{}-hop", opportunity.hop_count),
-                gross_profit_weth: opportunity.expected_profit_weth,
-                gas_cost_weth: gas_cost_weth_mh,
-                net_profit_weth: opportunity.expected_profit_weth - gas_cost_weth_mh,
-                latency_ms: _block_latency_ms,
-                tx_hash: format!("nonce:{}", nonce_manager.current().saturating_sub(1)),
-            });
+        if paper.is_none() {
+            stats.executed_trades += 1;
+
+            // v32.0: Telegram — Multi-hop Alfa Bildirimi
+            let gas_cost_weth_mh = simulated_gas_used as f64 * block_base_fee as f64 / 1e18;
+            if let Some(ref tg) = telegram_sender {
+                tg.send(crate::telegram::TelegramMessage::AlphaSuccess {
+                    buy_pool: format!("Multi-Hop {}", opportunity.label),
+                    sell_pool: format!("{}-hop", opportunity.hop_count),
+                    gross_profit_weth: opportunity.expected_profit_weth,
+                    gas_cost_weth: gas_cost_weth_mh,
+                    net_profit_weth: opportunity.expected_profit_weth - gas_cost_weth_mh,
+                    latency_ms: _block_latency_ms,
+                    tx_hash: format!("nonce:{}", nonce_manager.current().saturating_sub(1)),
+                });
+            }
+            tg_counters.successful_trades += 1;
+            tg_counters.net_period_profit_weth += opportunity.expected_profit_weth - gas_cost_weth_mh;
         }
-        tg_counters.successful_trades += 1;
-        tg_counters.net_period_profit_weth += opportunity.expected_profit_weth - gas_cost_weth_mh;
 
-        let sim_gas = simulated_gas_used;
+        let gas = crate::gas_calibration::GasSample::planned(simulated_gas_used, None, None);
         let expected_profit = opportunity.expected_profit_weth;
         let mev_exec = Arc::clone(mev_executor);
         let calldata_owned = calldata;
+        // Rota ucuzca yeniden doğrulanamaz — yeni blok gönderilmemiş TX'i iptal eder
+        let last_valid_block = deadline_semantics().last_valid(deadline_block) as u64;
+        let expiry = live.track_route(current_block, last_valid_block);
+
+        let lane = if paper.is_some() { "PAPER — simulated receipt" } else { "Private RPC" };
 
         tokio::spawn(async move {
-            println!("\n  {} {}", "????".yellow(), "MULTI-HOP CONTRACT EXECUTION STARTED (Private RPC)".yellow().bold());
+            println_high!(
+                "\n  {} {}",
+                "????".yellow(),
+                format!("MULTI-HOP CONTRACT EXECUTION STARTED ({})", lane).yellow().bold()
+            );
 
-            let result = mev_exec.execute_protected(
-                &pk,
-                contract_addr,
-                &calldata_owned,
-                nonce,
-                expected_profit,
-                sim_gas,
-                block_base_fee,
-                current_block,
-                &nm_clone,
-            ).await;
+            // İkili yolla aynı sahiplik: gönderilmeyen TX'in nonce'u geri alınır
+            let result = run_guarded_execution(inflight, nonce, &nm_clone, |inflight| {
+                mev_exec.execute_protected(
+                    contract_addr,
+                    &calldata_owned,
+                    nonce,
+                    expected_profit,
+                    gas,
+                    block_base_fee,
+                    current_block,
+                    last_valid_block,
+                    crate::gas_calibration::MULTI_HOP_ROUTE,
+                    inflight,
+                    &nm_clone,
+                    &expiry,
+                    None,
+                    None,
+                    None,
+                )
+            }).await;
 
             match result {
                 Ok(hash) => {
-                    println!("  {} Multi-hop TX successful (Private RPC): {}", "?".green(), hash.green().bold());
+                    println_high!("  {} Multi-hop TX successful (Private RPC): {}", "?".green(), hash.green().bold());
                 }
                 Err(e) => {
-                    println!("  {} Multi-hop TX error: {}", "?".red(), format!("{}", e).red());
+                    println_high!("  {} Multi-hop TX error: {}", "?".red(), format!("{}", e).red());
                 }
             }
         });
@@ -1499,7 +3669,7 @@ pub async fn evaluate_and_execute_multi_hop<P: Provider + Sync>(
 }
 
 #[cfg(test)]
-mod gas_spike_tests {
+pub(crate) mod gas_spike_tests {
     use super::*;
     use alloy::primitives::{address, Address};
     use std::sync::Arc;
@@ -1508,60 +3678,22 @@ mod gas_spike_tests {
 
     const POOL_A_ADDR: Address = address!("d0b53D9277642d899DF5C87A3966A349A798F224");
     const POOL_B_ADDR: Address = address!("cDAC0d6c6C59727a65F871236188350531885C43");
-    const WETH_ADDR: Address = address!("4200000000000000000000000000000000000006");
 
-    fn make_test_config(min_profit: f64, gas_cost_fallback: f64) -> BotConfig {
+    pub(crate) fn make_test_config(min_profit: f64, gas_cost_fallback: f64) -> BotConfig {
         BotConfig {
-            rpc_wss_url: "wss://test".into(),
-            rpc_http_url: "https://test".into(),
-            rpc_ipc_path: None,
-            transport_mode: TransportMode::Ws,
-            private_key: None,
-            contract_address: None,
-            weth_address: WETH_ADDR,
             gas_cost_fallback_weth: gas_cost_fallback,
-            flash_loan_fee_bps: 0.0,
             min_net_profit_weth: min_profit,
-            min_profit_roi: 0.0005,
-            stats_interval: 100,
-            max_retries: 0,
-            initial_retry_delay_secs: 2,
-            max_retry_delay_secs: 60,
-            max_staleness_ms: 5000,
-            max_trade_size_weth: 50.0,
-            chain_id: 8453,
-            tick_bitmap_range: 500,
-            tick_bitmap_max_age_blocks: 5,
-            execution_enabled_flag: false,
-            admin_address: None,
-            deadline_blocks: 2,
-            bribe_pct: 0.25,
-            keystore_path: None,
-            key_manager_active: false,
-            circuit_breaker_threshold: 3,
-            rpc_wss_url_backup: None,
-            latency_spike_threshold_ms: 200.0,
-            private_rpc_url: None,
-            rpc_wss_url_extra: Vec::new(),
             max_pool_fee_bps: 200, // Test: y�ksek tavan � gas spike testleri fee filtresinden etkilenmesin
-            min_tvl_usd: 1_000_000.0,
-            min_volume_24h_usd: 500_000.0,
-            max_tracked_pools: 4,
-            telegram_bot_token: None,
-            telegram_chat_id: None,
-            telegram_enabled: false,
-            telegram_shift_interval_secs: 21600,
-            telegram_balance_warn_eth: 0.05,
+            ..BotConfig::for_tests()
         }
     }
 
-    fn make_pool_configs() -> Vec<PoolConfig> {
+    pub(crate) fn make_pool_configs() -> Vec<PoolConfig> {
         vec![
             PoolConfig {
                 address: POOL_A_ADDR,
                 name: "UniV3-test".into(),
-                fee_bps: 5,
-                fee_fraction: 0.0005,
+                fee: FeeTier::from_bps(5),
                 token0_decimals: 18,
                 token1_decimals: 8,
                 dex: DexType::UniswapV3,
@@ -1573,8 +3705,7 @@ mod gas_spike_tests {
             PoolConfig {
                 address: POOL_B_ADDR,
                 name: "Aero-test".into(),
-                fee_bps: 100,
-                fee_fraction: 0.01,
+                fee: FeeTier::from_bps(100),
                 token0_decimals: 18,
                 token1_decimals: 8,
                 dex: DexType::Aerodrome,
@@ -1586,7 +3717,7 @@ mod gas_spike_tests {
         ]
     }
 
-    fn make_pool_state(eth_price: f64, liq: u128, block: u64) -> SharedPoolState {
+    pub(crate) fn make_pool_state(eth_price: f64, liq: u128, block: u64) -> SharedPoolState {
         // sqrtPriceX96 hesapla � math.rs::make_test_pool ile tutarl� form�l
         let price_ratio = eth_price * 1e-12; // token1/token0 raw fiyat oran�
         let sqrt_price = price_ratio.sqrt();
@@ -1605,10 +3736,15 @@ mod gas_spike_tests {
             last_block: block,
             last_update: Instant::now(),
             is_initialized: true,
-            bytecode: None,
             tick_bitmap: None,
             live_fee_bps: None,
             is_stale: false,
+            data_quality_errors: 0,
+            quarantined: false,
+            optimistic_trigger: None,
+            pinned_block: Some(block),
+            predicted_state: None,
+            constant_product: false,
         }))
     }
 
@@ -1652,7 +3788,7 @@ mod gas_spike_tests {
         // K���k spread � Newton-Raphson �ok d���k optimal miktar hesaplar
         // � k�r�n gas'� kar��lay�p kar��lamayaca�� NR'a ba�l�
         let result_normal = check_arbitrage_opportunity(
-            &pools, &states, &config, normal_base_fee, last_sim_gas, 0,
+            &pools, &states, &config, normal_base_fee, last_sim_gas, 0, &CompetitionSnapshot::default(), &FilterChain::default_chain(), None,
         );
         // Not: NR sonucu spread'e ve likiditeye ba�l� � bu test gas etkisini �l�er
 
@@ -1663,7 +3799,7 @@ mod gas_spike_tests {
         let spike_base_fee: u64 = 500_000_000_000_000; // 500K Gwei (a��r� spike)
 
         let result_spike = check_arbitrage_opportunity(
-            &pools, &states, &config, spike_base_fee, last_sim_gas, 0,
+            &pools, &states, &config, spike_base_fee, last_sim_gas, 0, &CompetitionSnapshot::default(), &FilterChain::default_chain(), None,
         );
 
         // Gas spike durumunda f�rsat kesinlikle reddedilmeli
@@ -1677,14 +3813,14 @@ mod gas_spike_tests {
         // 150K gas � 0.000015 WETH, 1.5M gas � 0.00015 WETH
         let high_gas = Some(1_500_000u64); // 10x daha fazla gas
         let result_high_gas = check_arbitrage_opportunity(
-            &pools, &states, &config, normal_base_fee, high_gas, 0,
+            &pools, &states, &config, normal_base_fee, high_gas, 0, &CompetitionSnapshot::default(), &FilterChain::default_chain(), None,
         );
 
         // Y�ksek gas tahminiyle maliyet artar � baz� f�rsatlar reddedilir
         // Bu testin amac�: last_simulated_gas'�n ger�ekten kullan�ld���n� kan�tlamak
         // E�er h�l� hardcoded 150K kullan�lsayd�, high_gas parametresi etkisiz olurdu
         let result_low_gas = check_arbitrage_opportunity(
-            &pools, &states, &config, normal_base_fee, Some(10_000u64), 0, // �ok d���k gas
+            &pools, &states, &config, normal_base_fee, Some(10_000u64), 0, &CompetitionSnapshot::default(), &FilterChain::default_chain(), None, // �ok d���k gas
         );
 
         // D���k gas � d���k maliyet � f�rsat bulma olas�l��� ARTAR
@@ -1726,7 +3862,7 @@ mod gas_spike_tests {
         let last_sim_gas = Some(150_000u64);
 
         let result = check_arbitrage_opportunity(
-            &pools, &states, &config, spike_base_fee, last_sim_gas, 0,
+            &pools, &states, &config, spike_base_fee, last_sim_gas, 0, &CompetitionSnapshot::default(), &FilterChain::default_chain(), None,
         );
 
         // B�y�k spread gas spike'�n� kar��lamal�
@@ -1760,7 +3896,7 @@ mod gas_spike_tests {
 
         // base_fee = 0 � config.gas_cost_fallback_weth (0.00005 WETH)
         let result = check_arbitrage_opportunity(
-            &pools, &states, &config, 0, Some(150_000), 0,
+            &pools, &states, &config, 0, Some(150_000), 0, &CompetitionSnapshot::default(), &FilterChain::default_chain(), None,
         );
 
         assert!(
@@ -1768,4 +3904,1471 @@ mod gas_spike_tests {
             "base_fee=0 durumunda config fallback ile f�rsat bulunmal�"
         );
     }
+
+    /// Rakip işlem sayısına göre ceza kademeleri (min=2, mult=2.0, limit=4)
+    #[test]
+    fn test_competition_penalty_levels() {
+        let config = make_test_config(0.0002, 0.00005);
+        assert_eq!(competition_penalty(0, &config), CompetitionPenalty::None);
+        assert_eq!(competition_penalty(1, &config), CompetitionPenalty::None);
+        assert_eq!(competition_penalty(2, &config), CompetitionPenalty::RaiseThreshold(2.0));
+        assert_eq!(competition_penalty(4, &config), CompetitionPenalty::RaiseThreshold(2.0));
+        assert_eq!(competition_penalty(5, &config), CompetitionPenalty::SkipBlock);
+
+        // Kapalı ayarlar: hiçbir sayı ceza üretmez
+        let mut off = make_test_config(0.0002, 0.00005);
+        off.competition_min_txs = 0;
+        off.competition_skip_limit = 0;
+        assert_eq!(competition_penalty(100, &off), CompetitionPenalty::None);
+    }
+
+    #[test]
+    fn test_competition_raised_threshold_application() {
+        let config = make_test_config(0.0002, 0.00005); // min_roi = 0.0005
+        let raise = CompetitionPenalty::RaiseThreshold(2.0);
+        // Kâr yükseltilmiş eşiğin altında, ROI de → atla
+        assert!(is_competition_skipped(raise, 0.0003, 0.0006, &config));
+        // Kâr yükseltilmiş eşiği (0.0004) geçiyor → devam
+        assert!(!is_competition_skipped(raise, 0.0005, 0.0006, &config));
+        // ROI yükseltilmiş eşiği (0.001) geçiyor → devam
+        assert!(!is_competition_skipped(raise, 0.0003, 0.002, &config));
+        assert!(is_competition_skipped(CompetitionPenalty::SkipBlock, 1.0, 1.0, &config));
+        assert!(!is_competition_skipped(CompetitionPenalty::None, 0.0, 0.0, &config));
+    }
+
+    /// Kârlı fırsat rakip yoğunluğunda yürütülmez ama gölge log için döner.
+    #[test]
+    fn test_competition_skip_tags_opportunity() {
+        let pools = make_pool_configs();
+        let config = make_test_config(0.0002, 0.00005);
+        let liq = 50_000_000_000_000_000_000u128;
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, liq, 100),
+            make_pool_state(2500.0, liq, 100),
+        ];
+
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let busy = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 6 };
+
+        let opp = check_arbitrage_opportunity(
+            &pools, &states, &config, 500_000_000_000, Some(150_000), 0, &quiet, &FilterChain::default_chain(), None,
+        )
+        .expect("Büyük spread ile fırsat bulunmalı");
+        assert!(opp.shadow_only.is_none());
+
+        let opp = check_arbitrage_opportunity(
+            &pools, &states, &config, 500_000_000_000, Some(150_000), 0, &busy, &FilterChain::default_chain(), None,
+        )
+        .expect("Rakip cezası fırsatı silmemeli — gölge log için dönmeli");
+        assert_eq!(
+            opp.shadow_only.as_ref().map(|v| v.filter),
+            Some("competition"),
+            "Limit üstü rakip sayısında fırsat atlanmalı",
+        );
+    }
+
+    /// V2 bacağı olan çift gölgede kalır — CL-CL çift etkilenmez
+    #[test]
+    fn test_constant_product_leg_is_shadow_only() {
+        let mut pools = make_pool_configs();
+        assert!(constant_product_verdict(&pools).is_none());
+        pools[1].dex = DexType::UniswapV2;
+        pools[1].name = "UniV2-test".into();
+        let verdict = constant_product_verdict(&pools).expect("V2 leg must be gated");
+        assert_eq!(verdict.filter, "constant_product");
+        assert!(verdict.reason.contains("UniV2-test"));
+    }
+
+    /// Donmuş snapshot üzerindeki kontrol, yakalamadan sonra canlı state'e
+    /// yazılan sürümü görmez — fırsatın aşamaları aynı sürümle tekrarlanır.
+    #[test]
+    fn test_check_on_snapshot_ignores_live_writes() {
+        let pools = make_pool_configs();
+        let config = make_test_config(0.0002, 0.00005);
+        let liq = 50_000_000_000_000_000_000u128;
+        let live: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, liq, 100),
+            make_pool_state(2500.0, liq, 100),
+        ];
+        let frozen = crate::pool_snapshot::capture_pair(&live[0], &live[1]);
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let check = |states: &[SharedPoolState]| {
+            check_arbitrage_opportunity(
+                &pools, states, &config, 500_000_000_000, Some(150_000), 0, &quiet, &FilterChain::default_chain(), None,
+            )
+        };
+        let before = check(&frozen).expect("Büyük spread ile fırsat bulunmalı");
+
+        // Spread kapanır: canlı state artık fırsat vermez, snapshot aynı kalır
+        live[1].store(make_pool_state(2450.0, liq, 101).load_full());
+        assert!(check(&live).is_none());
+        let after = check(&frozen).expect("Snapshot canlı yazıyı görmemeli");
+        assert_eq!(after.optimal_amount_weth, before.optimal_amount_weth);
+        assert_eq!(after.expected_profit_weth, before.expected_profit_weth);
+        assert_eq!(after.buy_pool_idx, before.buy_pool_idx);
+    }
+
+    /// Derin havuz + neredeyse boş havuz: spot spread %2 gösterir ama 0.1 WETH'lik
+    /// yürütülebilir spread ~0 — fırsat NR boyutlandırmasına hiç ulaşmaz.
+    #[test]
+    fn test_executable_spread_screens_out_shallow_pool() {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let config = make_test_config(0.0002, 0.00005);
+        let deep = 50_000_000_000_000_000_000u128;
+        let shallow = 5_000_000_000_000u128; // ~0.1 WETH sanal rezerv
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, deep, 100),
+            make_pool_state(2500.0, shallow, 100),
+        ];
+        let (sa, sb) = (states[0].load_full(), states[1].load_full());
+
+        let naive_spread = (sb.eth_price_usd - sa.eth_price_usd) / sa.eth_price_usd * 100.0;
+        assert!(naive_spread > 2.0);
+
+        let probe = config.spread_probe_size_weth;
+        let ea = executable_price(&pools[0], &sa, probe);
+        let eb = executable_price(&pools[1], &sb, probe);
+        // Derin havuz: referans boyutta spot'tan ayırt edilemez
+        assert!((ea.sell / 2450.0 - 1.0).abs() < 1e-4, "deep sell={}", ea.sell);
+        assert!((ea.buy / 2450.0 - 1.0).abs() < 1e-4, "deep buy={}", ea.buy);
+        // Sığ havuz: 0.1 WETH fiyatı ezer
+        assert!(eb.sell < 2450.0 * 0.9, "shallow sell={}", eb.sell);
+        assert!(eb.buy > 2500.0 * 1.1, "shallow buy={}", eb.buy);
+        let exec_spread = executable_spread_pct(&ea, &eb).max(executable_spread_pct(&eb, &ea));
+        assert!(exec_spread < 0.01, "exec_spread={}", exec_spread);
+
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let chain = FilterChain::default_chain();
+        assert!(check_arbitrage_opportunity(
+            &pools, &states, &config, 500_000_000_000, Some(150_000), 0, &quiet, &chain, None,
+        )
+        .is_none());
+
+        // PostSizing filtreleri hiç çalışmadı → NR çağrılmadı
+        for stats in chain.stats() {
+            if matches!(
+                stats.name,
+                "profit_floor"
+                    | "bitmap_quality"
+                    | "block_pin"
+                    | "adverse_selection"
+                    | "competition"
+                    | "direction_gate"
+            ) {
+                assert_eq!(stats.passed + stats.rejected + stats.degraded, 0, "{} ran", stats.name);
+            }
+        }
+        let rejection = chain.last_rejection().expect("PreSizing aşamasında reddedilmeli");
+        assert!(matches!(rejection.filter, "pool_health" | "prefilter"), "{:?}", rejection);
+    }
+
+    /// Derin iki havuzda yürütülebilir spread spot spread'e eşittir;
+    /// referans fiyat sığ havuz tarafından çekilmez.
+    #[test]
+    fn test_executable_spread_deep_pools_and_weighted_mid() {
+        let pools = make_pool_configs();
+        let liq = 50_000_000_000_000_000_000u128;
+        let (sa, sb) = (
+            make_pool_state(2450.0, liq, 100).load_full(),
+            make_pool_state(2500.0, liq, 100).load_full(),
+        );
+        let ea = executable_price(&pools[0], &sa, 0.1);
+        let eb = executable_price(&pools[1], &sb, 0.1);
+        let naive = (2500.0 - 2450.0) / 2450.0 * 100.0;
+        assert!((executable_spread_pct(&ea, &eb) - naive).abs() < 1e-3);
+        assert_eq!(executable_spread_pct(&eb, &ea), 0.0);
+
+        // Probe 0 → spot (eski davranış)
+        assert_eq!(executable_price(&pools[0], &sa, 0.0), ExecutablePrice { sell: 2450.0, buy: 2450.0 });
+
+        let mid = liquidity_weighted_mid(2450.0, 100.0, 2500.0, 0.01);
+        assert!((mid - 2450.0).abs() < 0.01, "mid={}", mid);
+        assert_eq!(liquidity_weighted_mid(2450.0, 0.0, 2500.0, 0.0), 2475.0);
+        assert_eq!(liquidity_weighted_mid(2450.0, 5.0, 2500.0, 5.0), 2475.0);
+    }
+
+    fn bitmap_at(snapshot_block: u64) -> TickBitmapData {
+        TickBitmapData { snapshot_block, ..TickBitmapData::empty() }
+    }
+
+    /// Eksik / bayat / taze bitmap → Fallback / Stale / Real
+    #[test]
+    fn test_bitmap_quality_classification() {
+        let fresh = bitmap_at(98);
+        let stale = bitmap_at(90);
+        let max_age = 5;
+
+        assert!(fresh.is_fresh(100, max_age));
+        assert!(!stale.is_fresh(100, max_age));
+        assert_eq!(stale.age_blocks(100), 10);
+
+        assert_eq!(BitmapQuality::classify(Some(&fresh), Some(&fresh), 100, max_age), BitmapQuality::Real);
+        assert_eq!(BitmapQuality::classify(None, Some(&fresh), 100, max_age), BitmapQuality::Fallback);
+        assert_eq!(BitmapQuality::classify(Some(&fresh), None, 100, max_age), BitmapQuality::Fallback);
+        assert_eq!(BitmapQuality::classify(None, None, 100, max_age), BitmapQuality::Fallback);
+        assert_eq!(
+            BitmapQuality::classify(Some(&fresh), Some(&stale), 100, max_age),
+            BitmapQuality::Stale { age_blocks: 10 },
+        );
+        // Bayat + eksik: bayat öncelikli (eski pool_health reddi korunur)
+        assert_eq!(
+            BitmapQuality::classify(Some(&stale), None, 100, max_age),
+            BitmapQuality::Stale { age_blocks: 10 },
+        );
+    }
+
+    fn with_bitmap(state: &SharedPoolState, bitmap: TickBitmapData) {
+        let mut s = (*state.load_full()).clone();
+        s.tick_bitmap = Some(Box::new(bitmap));
+        state.store(Arc::new(s));
+    }
+
+    fn bitmap_with_ticks(block: u64, scanned: (i32, i32), ticks: &[(i32, i128)]) -> TickBitmapData {
+        TickBitmapData {
+            ticks: ticks
+                .iter()
+                .map(|&(t, net)| {
+                    (t, TickInfo { liquidity_gross: net.unsigned_abs(), liquidity_net: net, initialized: true, staked_liquidity_net: 0 })
+                })
+                .collect(),
+            snapshot_block: block,
+            scanned_ticks: Some(scanned),
+            ..TickBitmapData::empty()
+        }
+    }
+
+    /// Sığ satış havuzunun bitmap'i optimumdan dar taranmış: sert tavan
+    /// optimumu taranan alt kenara kırpar → fırsat kenarı işaretler.
+    /// Kenarın ötesi birleştirilince tavan kalkar, optimum dışarı kayar.
+    #[test]
+    fn test_scan_boundary_extension_moves_optimum_outward() {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let config = make_test_config(0.0002, 0.00005);
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, 50_000_000_000_000_000_000, 100), // derin, ucuz
+            make_pool_state(2500.0, 100_000_000_000_000_000, 100),    // sığ, pahalı (satış)
+        ];
+        let (tick_a, tick_b) = (states[0].load().tick, states[1].load().tick);
+
+        // A: geniş tarama; B: yalnızca ±40 tick (~4 WETH satış derinliği)
+        with_bitmap(
+            &states[0],
+            bitmap_with_ticks(100, (tick_a - 5000, tick_a + 5000), &[(tick_a - 5000, 1), (tick_a + 5000, -1)]),
+        );
+        let edge = tick_b - 40;
+        with_bitmap(&states[1], bitmap_with_ticks(100, (edge, tick_b + 40), &[(edge, 1_000_000_000_000_000)]));
+
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let chain = FilterChain::default_chain();
+        let check = || {
+            check_arbitrage_opportunity(
+                &pools, &states, &config, 100_000_000_000, Some(150_000), 0, &quiet, &chain, None,
+            )
+            .expect("spread fee'leri rahatça aşıyor")
+        };
+
+        let clipped = check();
+        assert_eq!(clipped.sell_pool_idx, 1);
+        assert_eq!(clipped.bitmap_quality, BitmapQuality::Real);
+        assert_eq!(clipped.scan_boundary, Some(ScanBoundaryHit { pool_idx: 1, edge: ScanEdge::Lower }));
+
+        // Hedefli genişletme: kenarın ötesindeki 2 word, bir sonraki okuma
+        let extension = bitmap_with_ticks(101, (edge - 512, edge - 1), &[(edge - 200, 1_000_000_000_000_000)]);
+        let mut merged = states[1].load().tick_bitmap.as_deref().cloned().unwrap();
+        merged.merge(extension);
+        assert_eq!(merged.scanned_ticks, Some((edge - 512, tick_b + 40)));
+        with_bitmap(&states[1], merged);
+
+        let extended = check();
+        assert!(
+            extended.optimal_amount_weth > clipped.optimal_amount_weth * 1.5,
+            "optimum did not move outward: {} → {}",
+            clipped.optimal_amount_weth,
+            extended.optimal_amount_weth,
+        );
+        assert!(extended.expected_profit_weth > clipped.expected_profit_weth);
+        assert_eq!(extended.scan_boundary, None);
+    }
+
+    /// Sığ satış havuzunda tek seferlik optimum MAX_TRADE_SIZE tavanına
+    /// dayanırsa kalan spread ikinci işlemi taşır. Tavan = serbest optimumun
+    /// yarısı: kâr boyutta ~karesel olduğundan tek seferlik kâr serbest kârın
+    /// ~3/4'ü, iki adım toplamı ~tamamı → toplam ≈ tek seferlik × 4/3.
+    /// Tavan yoksa optimum spread'i kapatır, ikinci adım çıkmaz.
+    #[test]
+    fn test_two_step_plan_beats_capped_single_shot() {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, 50_000_000_000_000_000_000, 100), // derin, ucuz
+            make_pool_state(2500.0, 100_000_000_000_000_000, 100),    // sığ, pahalı (satış)
+        ];
+        // İki havuz da geniş taranmış: sert tavan optimumu kırpmaz
+        for state in &states {
+            let tick = state.load().tick;
+            with_bitmap(state, bitmap_with_ticks(100, (tick - 5000, tick + 5000), &[(tick - 5000, 1), (tick + 5000, -1)]));
+        }
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let chain = FilterChain::default_chain();
+        let check = |config: &BotConfig| {
+            check_arbitrage_opportunity(&pools, &states, config, 0, Some(150_000), 0, &quiet, &chain, None)
+                .expect("spread fee'leri rahatça aşıyor")
+        };
+        let plan_for = |config: &BotConfig, opp: &ArbitrageOpportunity| {
+            evaluate_two_step_plan(&pools, &states, opp, config, 0, Some(150_000), 0, &quiet, &chain, None)
+        };
+
+        let uncapped_config = make_test_config(0.0002, 0.00005);
+        let uncapped = check(&uncapped_config);
+        assert_eq!(uncapped.sell_pool_idx, 1);
+        assert!(uncapped.optimal_amount_weth < uncapped_config.max_trade_size_weth);
+        assert_eq!(plan_for(&uncapped_config, &uncapped), None, "free optimum leaves no second trade");
+
+        let capped_config = BotConfig {
+            max_trade_size_weth: uncapped.optimal_amount_weth / 2.0,
+            ..make_test_config(0.0002, 0.00005)
+        };
+        let single = check(&capped_config);
+        assert!((single.optimal_amount_weth - capped_config.max_trade_size_weth).abs() < 1e-6);
+        let plan = plan_for(&capped_config, &single).expect("capped optimum leaves a profitable second trade");
+
+        assert_eq!(plan.first_amount_weth, single.optimal_amount_weth);
+        assert_eq!(plan.first_profit_weth, single.expected_profit_weth);
+        assert!(plan.second_amount_weth <= capped_config.max_trade_size_weth + 1e-9);
+        assert!(plan.second_profit_weth >= capped_config.min_net_profit_weth);
+        // Her adım kendi gas'ını öder: toplam serbest optimumu geçemez
+        assert!(plan.total_profit_weth() <= uncapped.expected_profit_weth * 1.01);
+        assert!(
+            (plan.uplift() - 4.0 / 3.0).abs() < 0.1,
+            "two-step uplift {:.4} (single {:.8}, total {:.8})",
+            plan.uplift(),
+            single.expected_profit_weth,
+            plan.total_profit_weth(),
+        );
+    }
+
+    /// Slipstream: staked net'ler liquidity_net'in içindedir, unstakedFee
+    /// yalnızca LP fee'sini gauge ile böler — ikisi de swap maliyetini
+    /// değiştirmez. Kâr modeli havuzun canlı fee()'sini kullanır: sınırdaki
+    /// fırsatın kararı fee düşünce "go", yapılandırılmış fee'de "no-go" olur.
+    #[test]
+    fn test_slipstream_fee_model_borderline_decision() {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, 50_000_000_000_000_000_000, 100), // derin, ucuz
+            make_pool_state(2500.0, 100_000_000_000_000_000, 100),    // Aerodrome, satış
+        ];
+        for state in &states {
+            let tick = state.load().tick;
+            with_bitmap(state, bitmap_with_ticks(100, (tick - 5000, tick + 5000), &[(tick - 5000, 1), (tick + 5000, -1)]));
+        }
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let chain = FilterChain::default_chain();
+        let check = |config: &BotConfig| {
+            check_arbitrage_opportunity(&pools, &states, config, 0, Some(150_000), 0, &quiet, &chain, None)
+        };
+        // Kâr eşiğini yalnızca MIN_NET_PROFIT_WETH belirlesin (ROI yolu kapalı)
+        let with_min_profit = |min_profit: f64| BotConfig {
+            min_profit_roi: f64::MAX,
+            ..make_test_config(min_profit, 0.00005)
+        };
+
+        let baseline = check(&with_min_profit(0.0002)).expect("spread fee'leri rahatça aşıyor");
+        assert_eq!(baseline.sell_pool_idx, 1);
+
+        // Satış havuzunun tamamı stake edilmiş, unstakedFee %50: kâr birebir aynı
+        let (tick_b, liquidity_b) = (states[1].load().tick, states[1].load().liquidity);
+        let mut staked_bm = states[1].load().tick_bitmap.as_deref().cloned().unwrap();
+        for info in staked_bm.ticks.values_mut() {
+            info.staked_liquidity_net = info.liquidity_net;
+        }
+        let snapshot = StakedLiquidity {
+            tick: tick_b,
+            liquidity: liquidity_b,
+            staked_liquidity: liquidity_b,
+            unstaked_fee_pips: Some(500_000),
+            verified: true,
+        };
+        assert_eq!(staked_bm.verify_staked(&snapshot), Ok(()));
+        staked_bm.staked = Some(snapshot);
+        with_bitmap(&states[1], staked_bm);
+        let staked = check(&with_min_profit(0.0002)).unwrap();
+        assert_eq!(staked.optimal_amount_weth, baseline.optimal_amount_weth);
+        assert_eq!(staked.expected_profit_weth, baseline.expected_profit_weth);
+
+        // Canlı fee() 100 → 50 bps: kâr artar
+        let set_live_fee = |live_fee_bps: Option<u32>| {
+            let mut s = (*states[1].load_full()).clone();
+            s.live_fee_bps = live_fee_bps;
+            states[1].store(Arc::new(s));
+        };
+        set_live_fee(Some(50));
+        let cheaper = check(&with_min_profit(0.0002)).unwrap();
+        assert!(cheaper.expected_profit_weth > baseline.expected_profit_weth);
+
+        // Eşik iki kârın ortasında: yalnızca düşük fee ile "go"
+        let borderline = with_min_profit((baseline.expected_profit_weth + cheaper.expected_profit_weth) / 2.0);
+        assert!(check(&borderline).is_some(), "lower live fee must clear the borderline threshold");
+        set_live_fee(None);
+        assert!(check(&borderline).is_none(), "configured fee must fall below the borderline threshold");
+
+        // Exact kâr (REVM karşılaştırması / exact optimum) da canlı fee'yi okur
+        let exact_at = |live_fee_bps: Option<u32>| {
+            set_live_fee(live_fee_bps);
+            exact_profit_for_opportunity(&pools, &states[0].load(), &states[1].load(), &baseline)
+        };
+        let (exact_static, exact_live) = (exact_at(None), exact_at(Some(50)));
+        assert!(exact_live > exact_static, "live {exact_live} <= static {exact_static}");
+    }
+
+    /// Tavan tozda kalınca (MAX 0.005 < MIN 0.01) NR fırsat üretmez; toz
+    /// fırsat elde kalsa bile yürütme miktarı kurulamaz — calldata'ya ulaşmaz
+    #[test]
+    fn test_dust_opportunity_rejected_before_calldata() {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let liq = 50_000_000_000_000_000_000u128;
+        let states: Vec<SharedPoolState> = vec![make_pool_state(2450.0, liq, 100), make_pool_state(2500.0, liq, 100)];
+        for state in &states {
+            let tick = state.load().tick;
+            with_bitmap(state, bitmap_with_ticks(100, (tick - 5000, tick + 5000), &[(tick - 5000, 1), (tick + 5000, -1)]));
+        }
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let chain = FilterChain::default_chain();
+        let dust_capped = |min_trade_size_weth: f64| BotConfig {
+            max_trade_size_weth: 0.005,
+            min_trade_size_weth,
+            min_profit_roi: f64::MAX,
+            ..make_test_config(0.00001, 0.0)
+        };
+        let check = |config: &BotConfig| {
+            check_arbitrage_opportunity(&pools, &states, config, 0, Some(150_000), 0, &quiet, &chain, None)
+        };
+
+        // Eski sabit taban (0.0001): toz boyutta kârlı fırsat bulunur
+        let loose = dust_capped(0.0001);
+        let dust = check(&loose).expect("dust-sized spread is profitable on paper");
+        assert!(dust.optimal_amount_weth <= 0.005, "{}", dust.optimal_amount_weth);
+        let amount = execution_trade_amount(&dust, &pools, &loose).expect("above the old floor");
+        assert_eq!(amount.weth(), dust.optimal_amount_weth);
+        assert!(!amount.input_wei().is_zero());
+
+        // Varsayılan taban (0.01): NR tozu aramaz, fırsat yok
+        let strict = dust_capped(0.01);
+        assert!(check(&strict).is_none(), "NR must not size below MIN_TRADE_SIZE_WETH");
+
+        // Savunma katmanı: toz fırsat yürütme yoluna sızarsa miktar reddedilir
+        let err = execution_trade_amount(&dust, &pools, &strict).unwrap_err();
+        assert_eq!(err.kind(), "invalid_amount");
+    }
+
+    /// Farklı bloklarda (ya da "latest"te) okunmuş havuzlarla fırsat tespit
+    /// edilir ama yürütülmez; aynı bloğa sabitli okuma yürütmeye uygundur.
+    #[test]
+    fn test_mixed_block_reads_block_execution() {
+        let pools = make_pool_configs();
+        let liq = 50_000_000_000_000_000_000u128;
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, liq, 100),
+            make_pool_state(2500.0, liq, 100),
+        ];
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let mut config = make_test_config(0.0002, 0.00005);
+        config.require_bitmap_for_execution = false;
+        let set_pin = |state: &SharedPoolState, pin: Option<u64>| {
+            let mut s = state.load_full().as_ref().clone();
+            s.pinned_block = pin;
+            state.store(Arc::new(s));
+        };
+        let check = |states: &[SharedPoolState]| {
+            check_arbitrage_opportunity(
+                &pools, states, &config, 500_000_000_000, Some(150_000), 0, &quiet, &FilterChain::default_chain(), None,
+            )
+            .expect("Fırsat tespiti devam etmeli")
+        };
+
+        assert!(check(&states).shadow_only.is_none());
+
+        for pin_b in [Some(101), None] {
+            set_pin(&states[1], pin_b);
+            let verdict = check(&states).shadow_only.expect("Karışık blok verisiyle yürütme yapılmamalı");
+            assert_eq!(verdict.filter, "block_pin");
+            assert!(verdict.reason.contains("A=#100"), "{}", verdict.reason);
+        }
+
+        // Her iki havuz yeni blokta yeniden sabitlendi → yürütmeye uygun
+        set_pin(&states[0], Some(101));
+        set_pin(&states[1], Some(101));
+        assert!(check(&states).shadow_only.is_none());
+    }
+
+    /// Bitmap'siz fırsat REQUIRE_BITMAP_FOR_EXECUTION=true iken gölgeye düşer,
+    /// bayat bitmap'li fırsat pool_health'te reddedilir.
+    #[test]
+    fn test_bitmap_quality_execution_gating() {
+        let pools = make_pool_configs();
+        let liq = 50_000_000_000_000_000_000u128;
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, liq, 100),
+            make_pool_state(2500.0, liq, 100),
+        ];
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+
+        let mut config = make_test_config(0.0002, 0.00005);
+        config.require_bitmap_for_execution = true;
+        let opp = check_arbitrage_opportunity(
+            &pools, &states, &config, 500_000_000_000, Some(150_000), 0, &quiet, &FilterChain::default_chain(), None,
+        )
+        .expect("Bitmap'siz fırsat gölge log için dönmeli");
+        assert_eq!(opp.bitmap_quality, BitmapQuality::Fallback);
+        let verdict = opp.shadow_only.expect("Fallback fırsat yürütülmemeli");
+        assert_eq!(verdict.filter, "bitmap_quality");
+        assert!(verdict.reason.contains("fallback"));
+
+        // Bayrak kapalı: aynı fırsat yürütmeye uygun ama kalite yine raporlanır
+        config.require_bitmap_for_execution = false;
+        let opp = check_arbitrage_opportunity(
+            &pools, &states, &config, 500_000_000_000, Some(150_000), 0, &quiet, &FilterChain::default_chain(), None,
+        )
+        .expect("Fırsat bulunmalı");
+        assert_eq!(opp.bitmap_quality, BitmapQuality::Fallback);
+        assert!(opp.shadow_only.is_none());
+
+        // Bayat bitmap: NR'ye girmeden reddedilir
+        for state in &states {
+            let mut s = state.load_full().as_ref().clone();
+            s.tick_bitmap = Some(Box::new(bitmap_at(90)));
+            state.store(Arc::new(s));
+        }
+        assert!(check_arbitrage_opportunity(
+            &pools, &states, &config, 500_000_000_000, Some(150_000), 0, &quiet, &FilterChain::default_chain(), None,
+        )
+        .is_none());
+    }
+    /// Gözlenen state'te kârlı fırsat, büyük bir rakip front-run'ı sonrası
+    /// spread kapanıp tersine döndüğünde zarara düşer:
+    /// REQUIRE_ADVERSE_POSITIVE=true iken gölgeye alınır.
+    #[test]
+    fn test_adverse_scenario_gating() {
+        // Exact matematik için fikstür fiyatıyla tutarlı quote decimals (1e-12 oran → 6)
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let liq = 50_000_000_000_000_000_000u128;
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, liq, 100),
+            make_pool_state(2500.0, liq, 100),
+        ];
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let check = |config: &BotConfig| {
+            check_arbitrage_opportunity(
+                &pools, &states, config, 500_000_000_000, Some(150_000), 0, &quiet, &FilterChain::default_chain(), None,
+            )
+            .expect("Fırsat gölge log için de dönmeli")
+        };
+
+        let mut config = make_test_config(0.0002, 0.00005);
+        config.require_adverse_positive = true;
+
+        // Senaryo kapalı → değerlendirme yok
+        assert!(check(&config).adverse.is_none());
+
+        // Küçük front-run: kâr azalır ama pozitif kalır
+        config.adverse_frontrun = FrontrunSize::Weth(0.01);
+        let opp = check(&config);
+        let adv = opp.adverse.clone().expect("Senaryo değerlendirilmeli");
+        assert!(adv.base_profit_wei > U256::ZERO);
+        assert!(adv.is_adverse_positive());
+        assert!(adv.adverse_profit_wei <= adv.base_profit_wei);
+        assert!(opp.shadow_only.is_none());
+        let boundary = adv.revert_boundary_weth.expect("Derin havuzda da sınır bulunmalı");
+        assert!(boundary > 0.01, "boundary={}", boundary);
+
+        // Spread'i tersine çeviren front-run: kâr sıfır → minProfit revert, yürütme yok
+        config.adverse_frontrun = FrontrunSize::Weth(20_000.0);
+        let opp = check(&config);
+        let adv = opp.adverse.clone().unwrap();
+        assert!(!adv.is_adverse_positive());
+        assert!(adv.would_revert());
+        assert!(adv.frontrun_weth > boundary);
+        assert_eq!(opp.shadow_only.as_ref().map(|v| v.filter), Some("adverse_selection"));
+
+        // Bayrak kapalı → yalnızca raporlanır
+        config.require_adverse_positive = false;
+        let opp = check(&config);
+        assert!(!opp.adverse.unwrap().is_adverse_positive());
+        assert!(opp.shadow_only.is_none());
+    }
+
+    /// Kâr hassasiyeti: iki havuz ayrı ayrı bizim swap yönümüzde 1/2 tick
+    /// kaydırılır. Geniş spread (~300 tick) tick hareketine duyarsız, fee
+    /// eşiğinin ~1.5 tick üstündeki dar spread 2 tick'te ölür; MIN_ROBUSTNESS
+    /// ve bribe bu skoru kullanır.
+    #[test]
+    fn test_profit_sensitivity_fixtures() {
+        use crate::opportunity_filter::RobustnessFilter;
+
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let liq = 50_000_000_000_000_000_000u128;
+        let state_a = make_pool_state(2500.0, liq, 100).load_full().as_ref().clone();
+        // B, A'nın `ticks` üstünde: ETH B'de pahalı → A'dan al (buy_idx=0), B'ye sat
+        let above_a = |ticks: i32| PoolState {
+            tick: state_a.tick + ticks,
+            sqrt_price_x96: math::exact::get_sqrt_ratio_at_tick(state_a.tick + ticks),
+            ..state_a.clone()
+        };
+        // Fee eşiği: (1 - 0.0005)(1 - 0.01) → ~105.5 tick
+        let sensitivity_at = |ticks: i32| {
+            evaluate_profit_sensitivity(&pools, &state_a, &above_a(ticks), 0, 2500.0, 0.01)
+        };
+
+        // Ters yön kârı düşürür: elle kaydırılan state'lerle birebir, kötü olanı tutulur
+        let wide = sensitivity_at(300);
+        let (amount_wei, uni_zfo, aero_zfo) = exact_swap_params(&pools, 0, 2500.0, 0.01);
+        assert!(!uni_zfo && aero_zfo, "A'da WETH alınır (fiyat yukarı), B'de satılır (fiyat aşağı)");
+        let state_b = above_a(300);
+        let shifted_a = math::exact::shift_pool_state_ticks(&state_a, &pools[0], 1, uni_zfo);
+        let shifted_b = math::exact::shift_pool_state_ticks(&state_b, &pools[1], 1, aero_zfo);
+        let via_a = exact_profit_for_amount(&pools, &shifted_a, &state_b, amount_wei, uni_zfo, aero_zfo);
+        let via_b = exact_profit_for_amount(&pools, &state_a, &shifted_b, amount_wei, uni_zfo, aero_zfo);
+        assert!(via_a < wide.base_profit_wei && via_b < wide.base_profit_wei);
+        assert_eq!(wide.profit_after_1tick_wei, via_a.min(via_b));
+        assert!(wide.base_profit_wei > wide.profit_after_1tick_wei);
+        assert!(wide.profit_after_1tick_wei > wide.profit_after_2tick_wei);
+        assert!(wide.robustness() > 0.95, "robustness={}", wide.robustness());
+
+        // ~1.5 tick marj: 1 tick sonrası küçük kâr, 2 tick sonrası fee eşiğinin altı
+        let fragile = sensitivity_at(107);
+        assert!(fragile.base_profit_wei > U256::ZERO);
+        assert!(fragile.survives_1tick());
+        assert!(fragile.profit_after_1tick_wei < fragile.base_profit_wei);
+        assert_eq!(fragile.profit_after_2tick_wei, U256::ZERO);
+        assert!(fragile.robustness() < 0.5, "robustness={}", fragile.robustness());
+
+        // Eşiğin altında 1 tick'te ölen fırsat
+        let dies = sensitivity_at(106);
+        assert!(dies.base_profit_wei > U256::ZERO);
+        assert!(!dies.survives_1tick());
+
+        // MIN_ROBUSTNESS: 0 kapalı, küçük eşik yalnızca 1 tick şartı, 0.5 kırılganı eler
+        let fresh = make_pool_state(2500.0, liq, 100).load_full();
+        let chain = FilterChain::new(vec![Box::new(RobustnessFilter)]);
+        let executes = |min_robustness: f64, sensitivity: &ProfitSensitivity| {
+            let config = BotConfig { min_robustness, ..make_test_config(0.0002, 0.00005) };
+            let mut ctx = OpportunityContext::new(&config, &fresh, &fresh);
+            ctx.sensitivity = Some(sensitivity.clone());
+            chain.run(FilterStage::Execution, &ctx) == ChainOutcome::Pass
+        };
+        for s in [&wide, &fragile, &dies] {
+            assert!(executes(0.0, s));
+        }
+        assert!(executes(0.01, &wide) && executes(0.01, &fragile) && !executes(0.01, &dies));
+        assert!(executes(0.5, &wide) && !executes(0.5, &fragile));
+
+        // Bribe: kırılgan fırsat daha yüksek pay öder
+        let executor = crate::executor::MevExecutor::new(None, 0.25, None, 2);
+        let bribe_pct = |s: &ProfitSensitivity| {
+            executor.compute_dynamic_bribe(0.01, 250_000, 10_000_000, Some(s.robustness())).effective_pct
+        };
+        assert!(bribe_pct(&dies) > bribe_pct(&fragile));
+        assert!(bribe_pct(&fragile) > bribe_pct(&wide));
+    }
+
+    /// ADVERSE_FRONTRUN_WETH: sabit miktar veya işlem boyutunun yüzdesi
+    #[test]
+    fn test_frontrun_size_parsing() {
+        assert_eq!("0.5".parse::<FrontrunSize>(), Ok(FrontrunSize::Weth(0.5)));
+        assert_eq!(" 25% ".parse::<FrontrunSize>(), Ok(FrontrunSize::PctOfTrade(25.0)));
+        assert!("-1".parse::<FrontrunSize>().is_err());
+        assert!("abc%".parse::<FrontrunSize>().is_err());
+        assert!((FrontrunSize::PctOfTrade(25.0).resolve(4.0) - 1.0).abs() < 1e-12);
+        assert_eq!(FrontrunSize::Weth(0.5).resolve(4.0), 0.5);
+        assert_eq!(FrontrunSize::PctOfTrade(25.0).to_string(), "25%");
+    }
+
+    /// SIZING_BACKEND çözümü: auto yalnızca bitmap yoksa revm'e geçer,
+    /// bytecode yoksa her mod math'e düşer.
+    #[test]
+    fn test_sizing_backend_resolution() {
+        use SizingBackend::*;
+        let cases = [
+            // (mod, bitmap var, revm hazır, beklenen)
+            (Math, false, true, Math),
+            (Revm, true, true, Revm),
+            (Revm, false, false, Math),
+            (Auto, true, true, Math),
+            (Auto, false, true, Revm),
+            (Auto, false, false, Math),
+        ];
+        for (mode, has_bitmaps, revm_ready, want) in cases {
+            assert_eq!(mode.resolve(has_bitmaps, revm_ready), want, "{} {} {}", mode, has_bitmaps, revm_ready);
+        }
+    }
+
+    /// Bytecode önbelleği boş motor verilirse revm/auto modları math ile
+    /// aynı boyutu üretir.
+    #[test]
+    fn test_revm_sizing_falls_back_to_math_without_bytecode() {
+        let pools = make_pool_configs();
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, 50_000_000_000_000_000_000u128, 100),
+            make_pool_state(2500.0, 50_000_000_000_000_000_000u128, 100),
+        ];
+        let engine = SimulationEngine::new(8453);
+        let run = |backend: SizingBackend| {
+            let config = BotConfig { sizing_backend: backend, ..make_test_config(0.0002, 0.00005) };
+            check_arbitrage_opportunity(
+                &pools, &states, &config, 1_000_000_000, None, 0,
+                &CompetitionSnapshot::default(), &FilterChain::default_chain(), Some(&engine),
+            )
+            .expect("%2 spread kârlı olmalı")
+        };
+
+        let baseline = run(SizingBackend::Math);
+        for backend in [SizingBackend::Revm, SizingBackend::Auto] {
+            let opp = run(backend);
+            assert_eq!(opp.optimal_amount_weth, baseline.optimal_amount_weth, "{}", backend);
+            assert_eq!(opp.expected_profit_weth, baseline.expected_profit_weth, "{}", backend);
+        }
+    }
+
+    /// Diferansiyel test: sabit nokta ön eleme + kaba tarama, fixture
+    /// yelpazesinde f64 yoluyla aynı go/no-go kararını verir ve boyut %1
+    /// içinde kalır. Kâr eşiğine epsilon yakınlıktaki sınır fırsatları
+    /// kapsam dışı — orada iki yolun ayrışabilmesi beklenen davranış
+    /// (f64 spot'u tick'e yuvarlanmamış fiyattan, fixed sqrtPriceX96'dan gelir).
+    #[test]
+    fn test_fixed_numerics_agree_with_f64() {
+        let pools = make_pool_configs();
+        let liq = 50_000_000_000_000_000_000u128;
+        let cases = [
+            (2450.0, 2500.0, true),
+            (2500.0, 2560.0, true),
+            (2530.0, 2470.0, true),
+            (2500.0, 2500.25, false),
+            (2500.0, 2510.0, false),
+            (2500.0, 2515.0, false),
+        ];
+        for (price_a, price_b, profitable) in cases {
+            let states = vec![make_pool_state(price_a, liq, 100), make_pool_state(price_b, liq, 100)];
+            let run = |numerics: SizingNumerics| {
+                let config = BotConfig { sizing_numerics: numerics, ..make_test_config(0.0002, 0.00005) };
+                check_arbitrage_opportunity(
+                    &pools, &states, &config, 500_000_000_000, Some(150_000), 0,
+                    &CompetitionSnapshot::default(), &FilterChain::default_chain(), None,
+                )
+            };
+            let (f64_opp, fixed_opp) = (run(SizingNumerics::F64), run(SizingNumerics::Fixed));
+            assert_eq!(f64_opp.is_some(), profitable, "{}/{}", price_a, price_b);
+            assert_eq!(fixed_opp.is_some(), profitable, "{}/{}", price_a, price_b);
+            if let (Some(f), Some(x)) = (f64_opp, fixed_opp) {
+                assert_eq!((f.buy_pool_idx, f.sell_pool_idx), (x.buy_pool_idx, x.sell_pool_idx));
+                let rel = (f.optimal_amount_weth - x.optimal_amount_weth).abs() / f.optimal_amount_weth;
+                assert!(rel < 0.01, "{}/{}: f64={} fixed={}", price_a, price_b, f.optimal_amount_weth, x.optimal_amount_weth);
+                assert!((f.spread_pct - x.spread_pct).abs() < 0.05, "{} vs {}", f.spread_pct, x.spread_pct);
+            }
+        }
+    }
+
+    /// Kontratsız doğrulama MathFallback etiketi taşır; yürütme yolu
+    /// sezgisel gas yerine GAS_ESTIMATE kullanır, REVM ölçümünü ise olduğu gibi.
+    #[test]
+    fn test_execution_gas_selected_by_sim_source() {
+        let pools = make_pool_configs();
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2500.0, 10_000_000_000_000_000_000u128, 100),
+            make_pool_state(2520.0, 10_000_000_000_000_000_000u128, 100),
+        ];
+        let config = BotConfig { gas_estimate: 420_000, ..make_test_config(0.0002, 0.00005) };
+
+        let math = SimulationEngine::new(8453).validate_mathematical(&pools, &states, 0, 1, 1.0);
+        assert_eq!(math.source, SimSource::MathFallback);
+        assert_ne!(math.gas_used, config.gas_estimate, "sezgisel gas ile karışmamalı");
+        assert_eq!(math.execution_gas(config.gas_estimate), 420_000);
+        assert_eq!(sim_report_labels(&math).0, "Math Validation ");
+        assert!(sim_report_labels(&math).1.ends_with("(heuristic)"));
+
+        let revm = SimulationResult { success: true, gas_used: 187_000, error: None, source: SimSource::Revm };
+        assert_eq!(revm.execution_gas(config.gas_estimate), 187_000);
+        assert_eq!(sim_report_labels(&revm), ("REVM Simulation ", "187000".to_string()));
+
+        let mut stats = ArbitrageStats::new();
+        stats.record_sim_source(math.source);
+        stats.record_sim_source(revm.source);
+        stats.record_sim_source(revm.source);
+        assert_eq!(stats.sim_source_counts, [2, 1]);
+    }
+    /// Fırsatın kâr ayrıştırması NR kârıyla aynı swap yolundan gelir:
+    /// net = beklenen kâr, bileşenler net'e toplanır, oturum toplamı birikir
+    #[test]
+    fn test_opportunity_profit_breakdown_matches_expected_profit() {
+        let pools = make_pool_configs();
+        let config = make_test_config(0.0002, 0.00005);
+        let liq = 50_000_000_000_000_000_000u128;
+        let states = vec![make_pool_state(2450.0, liq, 100), make_pool_state(2500.0, liq, 100)];
+
+        let opp = check_arbitrage_opportunity(
+            &pools, &states, &config, 500_000_000_000, Some(150_000), 0,
+            &CompetitionSnapshot::default(), &FilterChain::default_chain(), None,
+        )
+        .expect("%2 spread kârlı olmalı");
+        let b = opp.profit_breakdown.expect("kârlı fırsatta ayrıştırma var");
+
+        assert!((b.net_weth - opp.expected_profit_weth).abs() < 1e-8, "{:?} vs {}", b, opp.expected_profit_weth);
+        assert!((b.component_sum() - b.net_weth).abs() < 1e-12);
+        assert!(b.fee_leg1_weth > 0.0 && b.fee_leg2_weth > 0.0 && b.gas_weth > 0.0);
+        // 1. bacak pahalı havuza (Aero %1) satış, 2. bacak UniV3 (%0.05) — birinci bacak baskın
+        assert!(b.fee_leg1_weth > b.fee_leg2_weth * 10.0);
+
+        let with_bribe = b.with_bribe(0.0001);
+        assert!((with_bribe.net_weth - (b.net_weth - 0.0001)).abs() < 1e-15);
+        assert!((with_bribe.component_sum() - with_bribe.net_weth).abs() < 1e-12);
+
+        let mut stats = ArbitrageStats::new();
+        stats.profit_attribution.record(&with_bribe);
+        stats.profit_attribution.record(&with_bribe);
+        let totals = &stats.profit_attribution.totals;
+        assert_eq!(stats.profit_attribution.opportunities, 2);
+        assert!((totals.gross_weth - 2.0 * b.gross_weth).abs() < 1e-12);
+        let share = stats.profit_attribution.fee_share_of_gross_pct;
+        assert!((share - b.lp_fees_weth() / b.gross_weth * 100.0).abs() < 1e-9);
+        assert!(profit_breakdown_json(&with_bribe)["net_weth"].is_number());
+    }
+}
+
+#[cfg(test)]
+mod inflight_cap_tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    /// Üç eşzamanlı fırsat, sınır 2: üçüncüsü atlanır. Yavaş mock gönderim —
+    /// biri başarılı (slot receipt görevinde kalır), biri başarısız (nonce
+    /// geri alınır, slot bırakılır). Her iki yolda da izinler geri döner.
+    #[tokio::test]
+    async fn test_third_concurrent_execution_skipped_and_permits_returned() {
+        let limiter = InflightLimiter::new(2);
+        let mut stats = ArbitrageStats::new();
+        let nonce_manager = Arc::new(NonceManager::new(5));
+
+        let ok_slot = reserve_execution_slot(&limiter, &mut stats).expect("slot 1");
+        let ok_nonce = nonce_manager.get_and_increment();
+        let fail_slot = reserve_execution_slot(&limiter, &mut stats).expect("slot 2");
+        let fail_nonce = nonce_manager.get_and_increment();
+        assert!(reserve_execution_slot(&limiter, &mut stats).is_none(), "third must be skipped");
+        assert_eq!(stats.inflight_cap_skips, 1);
+        assert_eq!(stats.peak_inflight_executions, 2);
+        assert_eq!(limiter.active(), 2);
+
+        // Başarılı yol: gönderim sonrası slot receipt görevine taşınır
+        let (receipt_tx, receipt_rx) = oneshot::channel::<()>();
+        let nm = Arc::clone(&nonce_manager);
+        let ok_task = tokio::spawn(async move {
+            run_guarded_execution(ok_slot, ok_nonce, &nm, |inflight| async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                tokio::spawn(async move {
+                    let _inflight = inflight;
+                    let _ = receipt_rx.await;
+                });
+                Ok("0xabc".to_string())
+            })
+            .await
+        });
+
+        // Başarısız yol: yavaş gönderim hatası
+        let nm = Arc::clone(&nonce_manager);
+        let fail_task = tokio::spawn(async move {
+            run_guarded_execution(fail_slot, fail_nonce, &nm, |_inflight| async move {
+                tokio::time::sleep(Duration::from_millis(40)).await;
+                Err(BotError::ExecutionUnavailable { reason: "mock send failed" })
+            })
+            .await
+        });
+
+        assert_eq!(ok_task.await.unwrap().unwrap(), "0xabc");
+        assert!(fail_task.await.unwrap().is_err());
+        // Hata yolu: nonce geri alındı, slotu bırakıldı; başarılı olanınki receipt'te
+        assert_eq!(nonce_manager.current(), fail_nonce);
+        assert_eq!(limiter.active(), 1);
+        let retry = reserve_execution_slot(&limiter, &mut stats).expect("failed slot returned");
+        assert!(reserve_execution_slot(&limiter, &mut stats).is_none());
+        assert_eq!(stats.inflight_cap_skips, 2);
+        drop(retry);
+
+        // Receipt geldi → son slot da döner
+        receipt_tx.send(()).unwrap();
+        for _ in 0..100 {
+            if limiter.active() == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(limiter.active(), 0);
+        let a = reserve_execution_slot(&limiter, &mut stats).expect("slot 1 free");
+        let b = reserve_execution_slot(&limiter, &mut stats).expect("slot 2 free");
+        drop((a, b));
+        assert_eq!(stats.peak_inflight_executions, 2);
+        assert_eq!(stats.inflight_cap_skips, 2);
+    }
+}
+
+#[cfg(test)]
+mod expiry_tests {
+    use super::*;
+    use super::gas_spike_tests::{make_pool_configs, make_pool_state, make_test_config};
+    use crate::executor::staged_send;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::{oneshot, Notify};
+
+    const DEEP: u128 = 50_000_000_000_000_000_000;
+
+    fn opportunity() -> ArbitrageOpportunity {
+        ArbitrageOpportunity {
+            buy_pool_idx: 0,
+            sell_pool_idx: 1,
+            optimal_amount_weth: 0.5,
+            expected_profit_weth: 0.002,
+            buy_price_quote: 2450.0,
+            sell_price_quote: 2500.0,
+            spread_pct: 2.04,
+            reference_price_quote: 2475.0,
+            nr_converged: true,
+            nr_iterations: 5,
+            shadow_only: None,
+            bitmap_quality: BitmapQuality::Real,
+            adverse: None,
+            sensitivity: None,
+            two_step: None,
+            scan_boundary: None,
+            flash_route: None,
+            profit_breakdown: None,
+            tick_crossings: None,
+            modeled_gas: None,
+            priced_gas: 150_000,
+        }
+    }
+
+    fn pools_and_states() -> (Vec<PoolConfig>, Vec<SharedPoolState>) {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let states = vec![make_pool_state(2450.0, DEEP, 100), make_pool_state(2500.0, DEEP, 100)];
+        (pools, states)
+    }
+
+    /// İmza yavaşken yeni blok spread'i kapatır: görev gönderim öncesi
+    /// durur, TX ağa çıkmaz, nonce geri alınır ve slot bırakılır.
+    #[tokio::test]
+    async fn test_superseding_block_cancels_slow_execution_before_send() {
+        let (pools, states) = pools_and_states();
+        let probe = make_test_config(0.0002, 0.00005).spread_probe_size_weth;
+        let limiter = InflightLimiter::new(1);
+        let mut stats = ArbitrageStats::new();
+        let nonce_manager = Arc::new(NonceManager::new(5));
+        let mut live = LiveOpportunities::default();
+
+        let slot = reserve_execution_slot(&limiter, &mut stats).expect("slot");
+        let nonce = nonce_manager.get_and_increment();
+        let expiry = live.track_pair(100, 103, &pools, &opportunity());
+        let watch = expiry.clone();
+
+        let (signing_tx, signing_rx) = oneshot::channel::<()>();
+        let release = Arc::new(Notify::new());
+        let sends = Arc::new(AtomicUsize::new(0));
+        let task = {
+            let (nm, release, sends) = (Arc::clone(&nonce_manager), Arc::clone(&release), Arc::clone(&sends));
+            tokio::spawn(async move {
+                run_guarded_execution(slot, nonce, &nm, |_inflight| {
+                    let sign = async move {
+                        let _ = signing_tx.send(());
+                        release.notified().await;
+                        Ok(vec![0x02])
+                    };
+                    staged_send(&expiry, sign, |_raw_tx| async move {
+                        sends.fetch_add(1, Ordering::SeqCst);
+                        Ok("0xabc".to_string())
+                    })
+                })
+                .await
+            })
+        };
+        signing_rx.await.expect("signing started");
+
+        // Tetik bloğu ve spread'i hâlâ açık yeni blok → fırsat canlı
+        assert_eq!(live.revalidate(100, probe, &pools, &states), 0);
+        assert_eq!(live.revalidate(101, probe, &pools, &states), 0);
+        assert!(!watch.is_expired());
+
+        // Blok 102: satış havuzu alış fiyatına iner → spread fee'lerin altında
+        states[1].store(make_pool_state(2450.0, DEEP, 102).load_full());
+        assert_eq!(live.revalidate(102, probe, &pools, &states), 1);
+        assert!(watch.is_expired());
+        assert!(live.entries.is_empty());
+
+        release.notify_one();
+        let err = task.await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), "execution_expired");
+        assert!(err.to_string().contains("before send"), "{}", err);
+        assert_eq!(sends.load(Ordering::SeqCst), 0, "superseded TX must not be sent");
+        assert_eq!(nonce_manager.current(), nonce, "nonce returned");
+        assert_eq!(limiter.active(), 0);
+    }
+
+    /// Multi-hop rota ilk yeni blokta, canlı çift deadline geçince düşer;
+    /// iptal edilmiş sinyal imza aşamasına hiç girmez.
+    #[tokio::test]
+    async fn test_route_and_deadline_expiry() {
+        let (pools, states) = pools_and_states();
+        let mut live = LiveOpportunities::default();
+        let route = live.track_route(100, 103);
+        let pair = live.track_pair(100, 103, &pools, &opportunity());
+
+        assert_eq!(live.revalidate(101, 0.1, &pools, &states), 1);
+        assert!(route.is_expired());
+        assert!(!pair.is_expired());
+        assert_eq!(live.revalidate(103, 0.1, &pools, &states), 0);
+        assert_eq!(live.revalidate(104, 0.1, &pools, &states), 1);
+        assert!(pair.is_expired());
+
+        let signed = AtomicUsize::new(0);
+        let err = staged_send(
+            &route,
+            async {
+                signed.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![0x02])
+            },
+            |_raw_tx| async { Ok("0xabc".to_string()) },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("before signing"), "{}", err);
+        assert!(err.to_string().contains("superseded"), "{}", err);
+        assert_eq!(signed.load(Ordering::SeqCst), 0);
+    }
+
+    /// PAPER_EXECUTION: yeni blokta ölen fırsat kağıt şeritte de gerçek
+    /// yolun geri alma yolundan geçer — kağıt nonce'u geri alınır, slot
+    /// bırakılır, sayaç kağıt defterine yazılır; tekrar denemede aynı nonce
+    /// kağıt olarak "gönderilir". Gerçek sonuç defterine hiçbir şey yazılmaz.
+    #[tokio::test]
+    async fn test_paper_lane_rolls_back_superseded_execution() {
+        use crate::direction_gate::GatePolicy;
+        use crate::executor::MevExecutor;
+        use crate::paper::{LatencyRange, PaperLane, PaperSettings};
+
+        let (pools, states) = pools_and_states();
+        let config = make_test_config(0.0002, 0.00005);
+        let settings = PaperSettings { failure_rate: 0.0, drop_rate: 0.0, latency: LatencyRange { min_ms: 0, max_ms: 0 } };
+        let lane = Arc::new(PaperLane::new(settings, GatePolicy::from_config(&config)));
+        let exec = MevExecutor::new(None, 0.25, None, 1).with_paper_lane(Some(Arc::clone(&lane)));
+        let nonce_manager = Arc::clone(&exec.paper().expect("paper lane").nonce_manager);
+        let mut stats = ArbitrageStats::new();
+        let mut live = LiveOpportunities::default();
+
+        let attempt = |expiry: OpportunityExpiry, slot: InflightGuard, nonce: u64| {
+            let (exec, nm) = (&exec, &nonce_manager);
+            async move {
+                run_guarded_execution(slot, nonce, nm, |inflight| async move {
+                    exec.execute_protected(
+                        Address::ZERO, &[0xAB; 134], nonce, 0.002,
+                        crate::gas_calibration::GasSample::planned(250_000, None, None), 10_000_000, 100, 103,
+                        "test", inflight, nm, &expiry, None, None, None,
+                    )
+                    .await
+                })
+                .await
+            }
+        };
+
+        // Spread kapandı → fırsat imza öncesi ölür
+        let expiry = live.track_pair(100, 103, &pools, &opportunity());
+        states[1].store(make_pool_state(2450.0, DEEP, 101).load_full());
+        assert_eq!(live.revalidate(101, config.spread_probe_size_weth, &pools, &states), 1);
+        let slot = reserve_execution_slot(&exec.inflight, &mut stats).expect("slot");
+        let nonce = nonce_manager.get_and_increment();
+        let err = attempt(expiry, slot, nonce).await.unwrap_err();
+        assert_eq!(err.kind(), "execution_expired");
+        assert_eq!(nonce_manager.current(), nonce, "paper nonce returned");
+        assert_eq!(exec.inflight.active(), 0);
+
+        // Tekrar: aynı nonce kağıt olarak gönderilir, receipt slotu bırakır
+        let slot = reserve_execution_slot(&exec.inflight, &mut stats).expect("slot returned");
+        let retry = nonce_manager.get_and_increment();
+        assert_eq!(retry, nonce);
+        assert!(attempt(OpportunityExpiry::default(), slot, retry).await.is_ok());
+        for _ in 0..200 {
+            if lane.outcomes.lock().landed == 1 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        let paper = *lane.outcomes.lock();
+        assert_eq!((paper.expired, paper.submitted, paper.landed), (1, 1, 1));
+        assert_eq!(exec.inflight.active(), 0);
+        assert_eq!(*exec.trade_outcomes.lock(), crate::executor::TradeOutcomes::default());
+    }
+
+    #[test]
+    fn test_simulated_gas_reprices_profit_before_execution() {
+        let config = BotConfig { min_profit_roi: f64::MAX, ..make_test_config(0.0015, 0.00005) };
+        let opp = opportunity();
+        let (pools, states) = pools_and_states();
+        let (state_a, state_b) = (states[0].load(), states[1].load());
+        let check = |gas, base_fee, states: [&PoolState; 2]| {
+            SimGasCheck::new(&opp, &pools, states, gas, base_fee, 0.0, &config)
+        };
+        const GWEI: u64 = 1_000_000_000;
+
+        // Simüle gas tahminle aynı → kâr exact yoldan, tespitteki sabit 0.002 değil
+        let same = check(150_000, GWEI, [&state_a, &state_b]);
+        assert_eq!((same.assumed_gas, same.simulated_gas), (150_000, 150_000));
+        assert!((same.pre_sim_profit_weth - 0.002).abs() < 1e-12);
+        assert!(same.post_sim_profit_weth.is_finite());
+        assert!((same.post_sim_profit_weth - opp.expected_profit_weth).abs() > 1e-6);
+
+        // Aynı miktar ve durumda ağır gas kârı tam gas farkı kadar düşürür
+        let heavier = check(500_000, GWEI, [&state_a, &state_b]);
+        let rejected = check(700_000, GWEI, [&state_a, &state_b]);
+        assert!((same.post_sim_profit_weth - heavier.post_sim_profit_weth - 0.000385).abs() < 1e-12);
+        assert!((same.post_sim_profit_weth - rejected.post_sim_profit_weth - 0.000605).abs() < 1e-12);
+
+        // Eşik 500k ile 700k arasında → yalnızca 700k reddedilir
+        let gate = BotConfig {
+            min_net_profit_weth: same.post_sim_profit_weth - 0.0005,
+            min_profit_roi: f64::MAX,
+            ..make_test_config(0.0015, 0.00005)
+        };
+        assert!(same.passes(opp.optimal_amount_weth, &gate));
+        assert!(heavier.passes(opp.optimal_amount_weth, &gate));
+        assert!(!rejected.passes(opp.optimal_amount_weth, &gate));
+        assert!(!heavier.passes(0.0, &gate));
+
+        // Tespitten sonra spread kapandı → aynı gas ile de kâr yok
+        let closed = make_pool_state(2450.0, DEEP, 100);
+        let closed = closed.load();
+        let stale = check(150_000, GWEI, [&state_a, &closed]);
+        assert!(stale.post_sim_profit_weth < 0.0, "{:?}", stale);
+        assert!(!stale.passes(opp.optimal_amount_weth, &gate));
+
+        // Base fee yok → gas kalemi sabit fallback, simüle gas kârı değiştirmez
+        let fallback = check(700_000, 0, [&state_a, &state_b]);
+        assert_eq!(fallback.post_sim_profit_weth, check(150_000, 0, [&state_a, &state_b]).post_sim_profit_weth);
+    }
+}
+
+#[cfg(test)]
+mod flash_route_tests {
+    use super::*;
+    use super::gas_spike_tests::{make_pool_configs, make_pool_state, make_test_config};
+
+    /// Bilinen tepe noktalı kâr eğrileri: mevcut rota 1.0 WETH'te 0.01,
+    /// ters rota 1.5 WETH'te 0.0225 WETH kazandırır → fark tam 0.0125 WETH.
+    #[test]
+    fn test_reversed_route_wins_by_known_margin() {
+        let current = |x: f64| 0.02 * x - 0.01 * x * x;
+        let reversed = |x: f64| 0.03 * x - 0.01 * x * x;
+        let route = select_flash_route(current, reversed, 10.0);
+
+        assert!((route.current_amount_weth - 1.0).abs() < 1e-6, "{:?}", route);
+        assert!((route.current_profit_weth - 0.01).abs() < 1e-12);
+        assert!((route.reversed_amount_weth - 1.5).abs() < 1e-6, "{:?}", route);
+        assert!((route.reversed_profit_weth - 0.0225).abs() < 1e-12);
+        assert_eq!(route.preferred, FlashSource::PoolB);
+        assert!((route.foregone_improvement_weth() - 0.0125).abs() < 1e-12);
+        assert!(route.is_material());
+
+        // Gölge log alanı aynı sayıları taşır
+        let json = flash_route_json(&route);
+        assert_eq!(json["preferred"], "pool_b");
+        assert_eq!(json["foregone_improvement_weth"], 0.0125);
+        assert_eq!(json["reversed_profit_weth"], 0.0225);
+        assert_eq!(json["material"], true);
+
+        // Roller değişince mevcut rota korunur, kaçırılan kâr yok
+        let route = select_flash_route(reversed, current, 10.0);
+        assert_eq!(route.preferred, FlashSource::PoolA);
+        assert_eq!(route.foregone_improvement_weth(), 0.0);
+        assert!(!route.is_material());
+        assert_eq!(flash_route_json(&route)["preferred"], "pool_a");
+    }
+
+    /// Ters rota %1 daha iyi: tercih edilir ama kontrat yükseltmesi için
+    /// REVERSED_ROUTE_MIN_GAIN_PCT eşiğinin altında. Hiç kâr yoksa (0, 0).
+    #[test]
+    fn test_materiality_threshold_and_unprofitable_routes() {
+        let current = |x: f64| 0.02 * x - 0.01 * x * x;
+        let route = select_flash_route(current, |x| current(x) * 1.01, 10.0);
+        assert_eq!(route.preferred, FlashSource::PoolB);
+        assert!(route.foregone_improvement_weth() > 0.0);
+        assert!(!route.is_material());
+
+        let losing = |x: f64| -0.001 * x;
+        let route = select_flash_route(losing, losing, 10.0);
+        assert_eq!((route.current_amount_weth, route.current_profit_weth), (0.0, 0.0));
+        assert_eq!((route.reversed_amount_weth, route.reversed_profit_weth), (0.0, 0.0));
+        assert_eq!(route.preferred, FlashSource::PoolA);
+    }
+
+    /// Havuzlar yer değiştirince (pool B kaynak = yeni pool A kaynak) ters
+    /// rota ile mevcut rota birebir yer değiştirir — exact motorun iki
+    /// sıralamayı aynı bacaklarla değerlendirdiğinin kanıtı. Derinlik
+    /// asimetrisi sıralamalardan birini öne geçirir; kazanan ve fark aynalanır.
+    #[test]
+    fn test_pool_swap_mirrors_route_comparison() {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let states = [
+            make_pool_state(2450.0, 50_000_000_000_000_000_000, 100), // derin, ucuz
+            make_pool_state(2500.0, 2_000_000_000_000_000_000, 100),  // 25× sığ, pahalı
+        ];
+        let (a, b) = (states[0].load_full(), states[1].load_full());
+        let reference_price = 2475.0;
+        let fee_bps = 5.0;
+
+        let original = compare_flash_routes(&pools, &a, &b, 0, reference_price, 10.0, fee_bps);
+        assert!(original.current_profit_weth > 0.0, "{:?}", original);
+        assert!(original.reversed_profit_weth > 0.0, "{:?}", original);
+        assert_ne!(original.current_profit_weth, original.reversed_profit_weth);
+
+        let swapped_pools = [pools[1].clone(), pools[0].clone()];
+        let swapped = compare_flash_routes(&swapped_pools, &b, &a, 1, reference_price, 10.0, fee_bps);
+        assert_eq!(swapped.current_amount_weth, original.reversed_amount_weth);
+        assert_eq!(swapped.current_profit_weth, original.reversed_profit_weth);
+        assert_eq!(swapped.reversed_amount_weth, original.current_amount_weth);
+        assert_eq!(swapped.reversed_profit_weth, original.current_profit_weth);
+        assert_ne!(swapped.preferred, original.preferred);
+
+        let (winner, loser) =
+            if original.preferred == FlashSource::PoolB { (&original, &swapped) } else { (&swapped, &original) };
+        let margin = winner.reversed_profit_weth - winner.current_profit_weth;
+        assert!(margin > 0.0);
+        assert_eq!(winner.foregone_improvement_weth(), margin);
+        assert_eq!(loser.foregone_improvement_weth(), 0.0);
+    }
+
+    /// Kârlı fırsat karşılaştırmayı taşır — gölge log ve stats bunu okur
+    #[test]
+    fn test_opportunity_carries_flash_route() {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let liq = 50_000_000_000_000_000_000u128;
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, liq, 100),
+            make_pool_state(2500.0, liq, 100),
+        ];
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let opp = check_arbitrage_opportunity(
+            &pools,
+            &states,
+            &make_test_config(0.0002, 0.00005),
+            500_000_000_000,
+            Some(150_000),
+            0,
+            &quiet,
+            &FilterChain::default_chain(),
+            None,
+        )
+        .expect("Fırsat bulunmalı");
+        let route = opp.flash_route.expect("Kârlı fırsatta rota karşılaştırması olmalı");
+        assert!(route.current_profit_weth > 0.0);
+        assert!(route.current_amount_weth > 0.0);
+        assert_eq!(
+            route.preferred == FlashSource::PoolB,
+            route.reversed_profit_weth > route.current_profit_weth,
+        );
+    }
+
+    /// Ladder tam atılmışken (seviye 6) tespit tek yönlü sürer, isteğe bağlı
+    /// değerlendirmeler yapılmaz — bayat state koruyucusu yine reddeder
+    #[test]
+    fn test_shed_ladder_keeps_guards() {
+        let mut pools = make_pool_configs();
+        for pool in &mut pools {
+            pool.token1_decimals = 6;
+        }
+        let liq = 50_000_000_000_000_000_000u128;
+        let states: Vec<SharedPoolState> = vec![
+            make_pool_state(2450.0, liq, 100),
+            make_pool_state(2500.0, liq, 100),
+        ];
+        let quiet = CompetitionSnapshot { block_number: 100, competitor_tx_last_block: 0 };
+        let mut config = make_test_config(0.0002, 0.00005);
+        config.adverse_frontrun = FrontrunSize::Weth(0.01);
+        let check = |states: &[SharedPoolState]| {
+            check_arbitrage_opportunity(
+                &pools, states, &config, 500_000_000_000, Some(150_000), 0, &quiet, &FilterChain::default_chain(), None,
+            )
+        };
+        let full = check(&states).expect("Fırsat bulunmalı");
+        assert!(full.adverse.is_some() && full.flash_route.is_some());
+
+        let shed = crate::degradation::with_shed(crate::degradation::RUNGS as u8, || check(&states))
+            .expect("Çekirdek tespit atılmış seviyede de çalışır");
+        assert!(shed.adverse.is_none());
+        assert!(shed.flash_route.is_none());
+        assert_eq!(shed.optimal_amount_weth, full.optimal_amount_weth);
+        assert_eq!(shed.expected_profit_weth, full.expected_profit_weth);
+
+        let mut stale = states[1].load_full().as_ref().clone();
+        stale.is_stale = true;
+        let stale_states = vec![states[0].clone(), Arc::new(arc_swap::ArcSwap::from_pointee(stale))];
+        assert!(check(&stale_states).is_none());
+        assert!(crate::degradation::with_shed(crate::degradation::RUNGS as u8, || check(&stale_states)).is_none());
+    }
+}
+
+#[cfg(test)]
+mod direction_invariant_tests {
+    use super::*;
+    use super::gas_spike_tests::make_pool_configs;
+
+    /// (buy_pool_idx, pool_a token0=base, pool_b token0=base) için yönler + kâr etiketi
+    fn construct(
+        buy_pool_idx: usize,
+        a_token0_is_base: bool,
+        b_token0_is_base: bool,
+    ) -> (Vec<PoolConfig>, (u8, u8, Address, Address), OwedProfit) {
+        let mut pools = make_pool_configs();
+        pools[0].token0_is_weth = a_token0_is_base;
+        pools[1].token0_is_weth = b_token0_is_base;
+        let directions = compute_directions_and_tokens(
+            buy_pool_idx,
+            pools[0].token0_is_weth,
+            &pools[0].base_token_address,
+            &pools[0].quote_token_address,
+        );
+        let profit = OwedProfit::from_exact(&pools[0], directions.0, U256::from(1_000u64));
+        (pools, directions, profit)
+    }
+
+    #[test]
+    fn test_all_eight_orderings() {
+        for buy_pool_idx in [0usize, 1] {
+            for a_order in [true, false] {
+                for b_order in [true, false] {
+                    let (pools, (uni, aero, owed, received), profit) =
+                        construct(buy_pool_idx, a_order, b_order);
+                    let result = check_direction_invariants(&pools, uni, aero, owed, received, &profit);
+                    let case = format!("buy={} a_token0_is_base={} b_token0_is_base={}", buy_pool_idx, a_order, b_order);
+
+                    // pool_a her sırada tutarlı: owed = talep edilen, received = diğeri
+                    let (a0, a1) = pool_token_pair(&pools[0]);
+                    assert_eq!(owed, if uni == 0 { a0 } else { a1 }, "{}", case);
+                    assert_eq!(received, if uni == 0 { a1 } else { a0 }, "{}", case);
+                    assert_eq!(profit.token, owed, "{}", case);
+                    // Base alım yönü: buy=0 → pool_a'dan base alınır (received = base)
+                    let base = pools[0].base_token_address;
+                    assert_eq!(received == base, buy_pool_idx == 0, "{}", case);
+
+                    if a_order == b_order {
+                        assert_eq!(result, Ok(()), "{}", case);
+                    } else {
+                        // aero yönü pool_a sırasından türetilir — pool_b ters sıralıysa
+                        // receivedToken yerine owedToken satılırdı: ret
+                        assert_eq!(
+                            result,
+                            Err(DirectionInvariantError::AeroDirection { expected: 1 - aero, got: aero }),
+                            "{}",
+                            case
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_profit_unit_and_token_mismatches_rejected() {
+        let (pools, (uni, aero, owed, received), profit) = construct(0, true, true);
+        assert_eq!(check_direction_invariants(&pools, uni, aero, owed, received, &profit), Ok(()));
+
+        // minProfit diğer tokenin biriminde (ör. owed token1 iken token0 kârı)
+        let wrong_unit = OwedProfit::from_exact(&pools[0], 1 - uni, profit.wei);
+        assert_eq!(
+            check_direction_invariants(&pools, uni, aero, owed, received, &wrong_unit),
+            Err(DirectionInvariantError::ProfitUnit { profit_token: received, owed_token: owed })
+        );
+
+        // owed / received yer değiştirmiş (eski ters sıra hatası)
+        assert_eq!(
+            check_direction_invariants(&pools, uni, aero, received, owed, &profit),
+            Err(DirectionInvariantError::OwedToken { expected: owed, got: received })
+        );
+
+        // pool_b farklı bir çift
+        let mut foreign = pools.clone();
+        foreign[1].quote_token_address = Address::repeat_byte(0x77);
+        assert_eq!(
+            check_direction_invariants(&foreign, uni, aero, owed, received, &profit),
+            Err(DirectionInvariantError::PoolBPair)
+        );
+    }
+
+    #[test]
+    fn test_consistent_construction_passes_enforcement_without_stat() {
+        let (pools, directions, profit) = construct(1, false, false);
+        let mut stats = ArbitrageStats::new();
+        assert!(enforce_direction_invariants(&pools, directions, &profit, &mut stats));
+        assert_eq!(stats.direction_invariant_rejections, 0);
+    }
+}
+
+#[cfg(test)]
+mod liquidity_threshold_tests {
+    use super::*;
+
+    fn pool(token0_decimals: u8, token1_decimals: u8) -> PoolConfig {
+        PoolConfig { token0_decimals, token1_decimals, ..gas_spike_tests::make_pool_configs()[0].clone() }
+    }
+
+    /// 2^53 üstünde komşu likiditeler f64'te birleşir; u128 eşiği ayırır
+    #[test]
+    fn test_slippage_factor_distinguishes_values_f64_conflated() {
+        // 18/24 desimal → normalize = L / 10^3: eşik L = 10^18 (> 2^53)
+        let deep = pool(18, 24);
+        let at = 1_000_000_000_000_000_000u128;
+        let below = at - 1;
+        assert!(below > 1u128 << 53);
+        assert_eq!(below as f64, at as f64, "f64 path sees the same liquidity");
+        assert_eq!(determine_slippage_factor_bps(at, at, &deep, &deep), 9950);
+        assert_eq!(determine_slippage_factor_bps(below, at, &deep, &deep), 9900);
+    }
+
+    /// Bölümsüz yol: eşikler tam sınırda, desimal normalizasyonu korunur
+    #[test]
+    fn test_slippage_factor_thresholds_exact() {
+        let weth_weth = pool(18, 18);
+        let weth_usdc = pool(18, 6);
+        assert_eq!(determine_slippage_factor_bps(1_000_000_000_000_000, u128::MAX, &weth_weth, &weth_weth), 9950);
+        assert_eq!(determine_slippage_factor_bps(999_999_999_999_999, u128::MAX, &weth_weth, &weth_weth), 9900);
+        assert_eq!(determine_slippage_factor_bps(10_000_000_000_000, u128::MAX, &weth_weth, &weth_weth), 9900);
+        assert_eq!(determine_slippage_factor_bps(9_999_999_999_999, u128::MAX, &weth_weth, &weth_weth), 9500);
+        // USDC havuzu: 1e9 raw ≡ 1e15 normalize
+        assert_eq!(determine_slippage_factor_bps(1_000_000_000, u128::MAX, &weth_usdc, &weth_weth), 9950);
+        assert_eq!(determine_slippage_factor_bps(999_999_999, u128::MAX, &weth_usdc, &weth_weth), 9900);
+        // Taşma doygunlukla çözülür
+        assert_eq!(determine_slippage_factor_bps(u128::MAX, u128::MAX, &weth_usdc, &weth_usdc), 9950);
+    }
 }
@@ -0,0 +1,427 @@
+// ============================================================================
+//  SUBMIT_PACING v1.0 — Gönderim Zamanlaması (Jitter + Yerel Aralık Lease'i)
+//
+//  Aynı makinede farklı çiftlerde çalışan iki örnek ilişkili fiyat
+//  hareketinde aynı blokta fırsat bulur ve mikrosaniye arayla gönderir:
+//  bağlantı başına hız sınırı tetiklenir, ikinci TX FIFO sırasını kaybeder.
+//
+//  ✓ SUBMIT_JITTER_MS (ör. 0-15): her işlemde aralıktan düz rastgele
+//    gecikme, eth_sendRawTransaction öncesi
+//  ✓ MIN_SUBMIT_SPACING_MS: DATA_DIR'deki submit_spacing.lease üzerinden
+//    yerel örnekler arası en az aralık — sonraki gönderen bekler
+//  ✓ Lease: flock altında kısa kritik bölge, JSON kayıt (PID + ayrılan
+//    gönderim anı, unix ms); bekleyen örnek slotunu önceden ayırır
+//  ✓ Bayat kayıt yok sayılır: PID ölü, okunamaz ya da çok ileri tarihli
+//  ✓ Bekleme gönderim bütçesini (blok pipeline bütçesinin kalanı) aşarsa
+//    TX gönderilmez — ExecutionExpired, fırsat sonraki blokta yeniden doğar
+//
+//  İki özellik de varsayılan kapalı. Lease'i paylaşmak için örnekler aynı
+//  DATA_DIR'i kullanmalıdır.
+// ============================================================================
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::console::eprintln_high;
+use crate::errors::{BotError, BotResult};
+use crate::types::BotConfig;
+
+/// DATA_DIR altındaki lease dosyası
+pub const SPACING_LEASE_PATH: &str = "submit_spacing.lease";
+
+/// Bundan ileri tarihli ayırma geçersiz sayılır (saat sıçraması / bozuk kayıt)
+const MAX_RESERVATION_AHEAD_MS: u64 = 5_000;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Jitter Aralığı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// SUBMIT_JITTER_MS: "min-max" (uçlar dahil) ya da "max" (0-max). "0" = kapalı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JitterRange {
+    pub min_ms: u64,
+    pub max_ms: u64,
+}
+
+impl JitterRange {
+    pub const OFF: Self = Self { min_ms: 0, max_ms: 0 };
+
+    pub fn is_off(&self) -> bool {
+        self.max_ms == 0
+    }
+
+    /// Aralıktan düz rastgele gecikme
+    fn sample(&self, rng: &mut impl Rng) -> Duration {
+        Duration::from_millis(rng.gen_range(self.min_ms..=self.max_ms))
+    }
+}
+
+impl std::str::FromStr for JitterRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        let parse = |v: &str| v.trim().parse::<u64>().map_err(|_| format!("invalid jitter range '{}'", s));
+        let (min_ms, max_ms) = match s.split_once('-') {
+            Some((min, max)) => (parse(min)?, parse(max)?),
+            None => (0, parse(s)?),
+        };
+        if min_ms > max_ms || max_ms > 1_000 {
+            return Err(format!("jitter range must satisfy min <= max <= 1000, got '{}'", s));
+        }
+        Ok(Self { min_ms, max_ms })
+    }
+}
+
+impl std::fmt::Display for JitterRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.min_ms, self.max_ms)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Aralık Lease'i
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Lease dosyasındaki kayıt: son (ya da ayrılmış) gönderim anı ve sahibi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct LeaseRecord {
+    pid: u32,
+    /// Unix ms — bekleyen örnekte gelecekteki slot
+    slot_ms: u64,
+}
+
+/// Ayırma sonucu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reservation {
+    /// Slot ayrıldı: `wait` sonra gönder (0 = hemen)
+    Slot { slot_ms: u64, wait: Duration },
+    /// Gereken bekleme bütçeyi aşıyor — kayıt değişmedi, gönderilmez
+    Defer { wait: Duration },
+}
+
+/// Kayıt hâlâ geçerli mi? Ölü PID'in ya da çok ileri tarihli slotun
+/// ayırması kimseyi bekletmez.
+fn live_record(record: Option<LeaseRecord>, now_ms: u64, pid_alive: impl Fn(u32) -> bool) -> Option<LeaseRecord> {
+    record.filter(|r| r.slot_ms <= now_ms + MAX_RESERVATION_AHEAD_MS && pid_alive(r.pid))
+}
+
+/// Saf karar: önceki kayda göre bu sürecin slotu. Aynı sürecin kaydı
+/// aralık gerektirmez (uçuştaki yürütmeler kendi aralarında beklemez) ama
+/// ileri tarihli slotu korunur.
+fn decide(previous: Option<LeaseRecord>, pid: u32, now_ms: u64, spacing_ms: u64, budget: Duration) -> Reservation {
+    let slot_ms = match previous {
+        None => now_ms,
+        Some(r) if r.pid == pid => now_ms.max(r.slot_ms),
+        Some(r) => now_ms.max(r.slot_ms + spacing_ms),
+    };
+    let wait = match previous {
+        Some(r) if r.pid == pid => Duration::ZERO,
+        _ => Duration::from_millis(slot_ms - now_ms),
+    };
+    if wait > budget {
+        return Reservation::Defer { wait };
+    }
+    Reservation::Slot { slot_ms, wait }
+}
+
+/// Lease dosyasında slot ayır: flock altında oku → karar ver → yaz.
+/// Kritik bölge yalnızca küçük bir dosya okuması + yazmasıdır.
+fn reserve(path: &Path, pid: u32, now_ms: u64, spacing_ms: u64, budget: Duration) -> io::Result<Reservation> {
+    crate::secure_fs::create_parent_dir(path)?;
+    let mut file = crate::secure_fs::open_read_write(path)?;
+    crate::instance_lock::lock_blocking(&file)?;
+    let previous = live_record(read_record(&mut file), now_ms, crate::instance_lock::pid_alive);
+    let reservation = decide(previous, pid, now_ms, spacing_ms, budget);
+    if let Reservation::Slot { slot_ms, .. } = reservation {
+        write_record(&mut file, &LeaseRecord { pid, slot_ms })?;
+    }
+    // fd kapanınca flock bırakılır
+    Ok(reservation)
+}
+
+fn read_record(file: &mut File) -> Option<LeaseRecord> {
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    serde_json::from_str(content.trim()).ok()
+}
+
+fn write_record(file: &mut File, record: &LeaseRecord) -> io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    let json = serde_json::to_string(record).map_err(io::Error::other)?;
+    writeln!(file, "{}", json)
+}
+
+fn unix_ms() -> u64 {
+    crate::clock::now().timestamp_millis().max(0) as u64
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gönderim Zamanlayıcısı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Executor'a bağlanan zamanlayıcı — iki özellik de kapalıysa kurulmaz
+#[derive(Debug)]
+pub struct SubmitPacer {
+    jitter: JitterRange,
+    spacing_ms: u64,
+    lease_path: PathBuf,
+    jittered: AtomicU64,
+    jitter_ms_total: AtomicU64,
+    waited: AtomicU64,
+    wait_ms_total: AtomicU64,
+    deferred: AtomicU64,
+    lease_errors: AtomicU64,
+}
+
+impl SubmitPacer {
+    /// SUBMIT_JITTER_MS ve MIN_SUBMIT_SPACING_MS ikisi de kapalıysa None
+    pub fn from_config(config: &BotConfig) -> Option<Self> {
+        if config.submit_jitter_ms.is_off() && config.min_submit_spacing_ms == 0 {
+            return None;
+        }
+        Some(Self::new(
+            config.submit_jitter_ms,
+            config.min_submit_spacing_ms,
+            crate::secure_fs::data_path(SPACING_LEASE_PATH),
+        ))
+    }
+
+    fn new(jitter: JitterRange, spacing_ms: u64, lease_path: PathBuf) -> Self {
+        Self {
+            jitter,
+            spacing_ms,
+            lease_path,
+            jittered: AtomicU64::new(0),
+            jitter_ms_total: AtomicU64::new(0),
+            waited: AtomicU64::new(0),
+            wait_ms_total: AtomicU64::new(0),
+            deferred: AtomicU64::new(0),
+            lease_errors: AtomicU64::new(0),
+        }
+    }
+
+    /// Gönderim öncesi: jitter, sonra yerel aralık slotu. `budget` gönderimin
+    /// gecikebileceği süredir (None = bilinmiyor: en çok jitter üst ucu +
+    /// bir aralık). Bekleme bütçeyi aşarsa `ExecutionExpired` — çağıran
+    /// nonce'u geri alır. Lease okunamazsa aralık atlanır (gönderim engellenmez).
+    pub async fn pace(&self, budget: Option<Duration>) -> BotResult<()> {
+        let mut budget = budget.unwrap_or(Duration::from_millis(self.jitter.max_ms + self.spacing_ms));
+
+        if !self.jitter.is_off() {
+            let delay = self.jitter.sample(&mut rand::thread_rng()).min(budget);
+            self.jittered.fetch_add(1, Ordering::Relaxed);
+            self.jitter_ms_total.fetch_add(delay.as_millis() as u64, Ordering::Relaxed);
+            tokio::time::sleep(delay).await;
+            budget = budget.saturating_sub(delay);
+        }
+        if self.spacing_ms == 0 {
+            return Ok(());
+        }
+
+        let (path, spacing_ms) = (self.lease_path.clone(), self.spacing_ms);
+        let reserved = tokio::task::spawn_blocking(move || {
+            reserve(&path, std::process::id(), unix_ms(), spacing_ms, budget)
+        })
+        .await
+        .unwrap_or_else(|e| Err(io::Error::other(e)));
+
+        match reserved {
+            Ok(Reservation::Slot { wait, .. }) => {
+                if !wait.is_zero() {
+                    self.waited.fetch_add(1, Ordering::Relaxed);
+                    self.wait_ms_total.fetch_add(wait.as_millis() as u64, Ordering::Relaxed);
+                    eprintln_high!(
+                        "     ⏳ [Pacing] local instance submitted recently — waiting {}ms",
+                        wait.as_millis(),
+                    );
+                    tokio::time::sleep(wait).await;
+                }
+                Ok(())
+            }
+            Ok(Reservation::Defer { wait }) => {
+                self.deferred.fetch_add(1, Ordering::Relaxed);
+                Err(BotError::ExecutionExpired {
+                    stage: "submit spacing",
+                    reason: format!(
+                        "local instance holds the next {}ms; budget left {}ms — deferred to next block",
+                        wait.as_millis(),
+                        budget.as_millis(),
+                    ),
+                })
+            }
+            Err(e) => {
+                self.lease_errors.fetch_add(1, Ordering::Relaxed);
+                eprintln_high!(
+                    "     ⚠️ [Pacing] {} unavailable ({}) — sending without spacing",
+                    self.lease_path.display(),
+                    e,
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// İstatistik kutusu satırı
+    pub fn stats_line(&self) -> String {
+        let avg = |total: &AtomicU64, n: &AtomicU64| {
+            total.load(Ordering::Relaxed) as f64 / n.load(Ordering::Relaxed).max(1) as f64
+        };
+        format!(
+            "jitter {} (avg {:.1}ms) | spacing waits {} (avg {:.1}ms) | deferred {} | lease errors {}",
+            self.jittered.load(Ordering::Relaxed),
+            avg(&self.jitter_ms_total, &self.jittered),
+            self.waited.load(Ordering::Relaxed),
+            avg(&self.wait_ms_total, &self.waited),
+            self.deferred.load(Ordering::Relaxed),
+            self.lease_errors.load(Ordering::Relaxed),
+        )
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    const SPACING: u64 = 10;
+    const BUDGET: Duration = Duration::from_millis(50);
+    /// pid_max (en çok 2^22) üzerinde: hiçbir süreç bu PID'yi taşıyamaz
+    const DEAD_PID: u32 = 999_999_999;
+
+    /// İki canlı "örnek": bu süreç ve test koşucusunun ebeveyni
+    fn pids() -> (u32, u32) {
+        (std::process::id(), std::os::unix::process::parent_id())
+    }
+
+    fn temp_lease(tag: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("arb_submit_pacing_{}_{}", tag, std::process::id()))
+            .join(SPACING_LEASE_PATH);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn slot(slot_ms: u64, wait_ms: u64) -> Reservation {
+        Reservation::Slot { slot_ms, wait: Duration::from_millis(wait_ms) }
+    }
+
+    #[test]
+    fn test_jitter_range_parse() {
+        assert_eq!("0-15".parse(), Ok(JitterRange { min_ms: 0, max_ms: 15 }));
+        assert_eq!(" 15 ".parse(), Ok(JitterRange { min_ms: 0, max_ms: 15 }));
+        assert_eq!("0".parse(), Ok(JitterRange::OFF));
+        assert!("15-5".parse::<JitterRange>().is_err());
+        assert!("0-5000".parse::<JitterRange>().is_err());
+        assert!("abc".parse::<JitterRange>().is_err());
+        assert_eq!(JitterRange { min_ms: 3, max_ms: 15 }.to_string(), "3-15");
+
+        let range = JitterRange { min_ms: 3, max_ms: 15 };
+        let mut rng = rand::thread_rng();
+        assert!((0..1_000).all(|_| (3..=15).contains(&range.sample(&mut rng).as_millis())));
+    }
+
+    /// İki örnek çekişir: sonraki bekler ve slotunu ayırır, bütçeyi aşan
+    /// bekleme kaydı değiştirmeden ertelenir
+    #[test]
+    fn test_two_contending_submitters_spaced_or_deferred() {
+        let path = temp_lease("contend");
+        let (a, b) = pids();
+
+        assert_eq!(reserve(&path, a, 1_000, SPACING, BUDGET).unwrap(), slot(1_000, 0));
+        // B 4ms sonra: A'nın slotundan SPACING sonrasına kadar bekler
+        assert_eq!(reserve(&path, b, 1_004, SPACING, BUDGET).unwrap(), slot(1_010, 6));
+        // A, B'nin ayrılmış slotunu görür: 1_020'ye kadar 15ms
+        assert_eq!(reserve(&path, a, 1_005, SPACING, BUDGET).unwrap(), slot(1_020, 15));
+        // B'nin bütçesi 5ms: gereken 25ms bekleme → ertele, kayıt A'da kalır
+        assert_eq!(
+            reserve(&path, b, 1_005, SPACING, Duration::from_millis(5)).unwrap(),
+            Reservation::Defer { wait: Duration::from_millis(25) },
+        );
+        assert_eq!(reserve(&path, b, 1_031, SPACING, BUDGET).unwrap(), slot(1_031, 0));
+        // Aynı sürecin uçuştaki ikinci yürütmesi beklemez, ileri slot korunur
+        assert_eq!(reserve(&path, b, 1_032, SPACING, BUDGET).unwrap(), slot(1_032, 0));
+        assert_eq!(decide(Some(LeaseRecord { pid: b, slot_ms: 1_040 }), b, 1_032, SPACING, BUDGET), slot(1_040, 0));
+    }
+
+    /// Bayat kayıt (ölü PID, bozuk içerik, çok ileri tarih) kimseyi bekletmez
+    #[test]
+    fn test_stale_lease_ignored() {
+        let path = temp_lease("stale");
+        let (a, _) = pids();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        let dead = LeaseRecord { pid: DEAD_PID, slot_ms: 1_000 };
+        std::fs::write(&path, serde_json::to_string(&dead).unwrap()).unwrap();
+        assert_eq!(reserve(&path, a, 1_000, SPACING, BUDGET).unwrap(), slot(1_000, 0));
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(reserve(&path, a, 2_000, SPACING, BUDGET).unwrap(), slot(2_000, 0));
+
+        let far = Some(LeaseRecord { pid: 1, slot_ms: 3_000 + MAX_RESERVATION_AHEAD_MS + 1 });
+        assert_eq!(live_record(far, 3_000, |_| true), None);
+        assert_eq!(live_record(far, 3_002, |_| true), far);
+    }
+
+    /// Gerçek saat + iki iş parçacığı: farklı örneklerin ayrılan slotları
+    /// arasında her zaman en az SPACING ms
+    #[test]
+    fn test_concurrent_submitters_never_share_spacing_window() {
+        let path = temp_lease("threads");
+        let (a, b) = pids();
+        let run = |pid: u32| {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                let mut slots = Vec::new();
+                for _ in 0..8 {
+                    if let Reservation::Slot { slot_ms, wait } = reserve(&path, pid, unix_ms(), SPACING, BUDGET).unwrap() {
+                        slots.push((slot_ms, pid));
+                        std::thread::sleep(wait);
+                    }
+                }
+                slots
+            })
+        };
+        let (ta, tb) = (run(a), run(b));
+        let mut slots: Vec<(u64, u32)> = ta.join().unwrap();
+        slots.extend(tb.join().unwrap());
+        slots.sort();
+
+        assert!(slots.len() >= 8, "the wide budget must let most submissions through");
+        for pair in slots.windows(2) {
+            let ((first, first_pid), (second, second_pid)) = (pair[0], pair[1]);
+            if first_pid != second_pid {
+                assert!(second - first >= SPACING, "{:?} too close", pair);
+            }
+        }
+    }
+
+    /// Bütçe yoksa (0) ve yer tutulmuşsa gönderim ertelenir; kapalı aralık beklemez
+    #[tokio::test]
+    async fn test_pace_defers_past_budget() {
+        let path = temp_lease("pace");
+        let (_, other) = pids();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let held = LeaseRecord { pid: other, slot_ms: unix_ms() + 1_000 };
+        std::fs::write(&path, serde_json::to_string(&held).unwrap()).unwrap();
+
+        let pacer = SubmitPacer::new(JitterRange::OFF, SPACING, path.clone());
+        let err = pacer.pace(Some(Duration::ZERO)).await.unwrap_err();
+        assert!(matches!(err, BotError::ExecutionExpired { stage: "submit spacing", .. }), "{err}");
+        assert_eq!(pacer.deferred.load(Ordering::Relaxed), 1);
+
+        let jitter_only = SubmitPacer::new(JitterRange { min_ms: 1, max_ms: 2 }, 0, path);
+        jitter_only.pace(None).await.unwrap();
+        assert_eq!(jitter_only.jittered.load(Ordering::Relaxed), 1);
+    }
+}
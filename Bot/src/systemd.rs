@@ -0,0 +1,280 @@
+// ============================================================================
+//  SYSTEMD v1.0 — sd_notify Entegrasyonu (opsiyonel, --features systemd)
+//
+//  WatchdogSec ile çalışan servislerde takılı kalan süreç (canlı ama blok
+//  işlemeyen döngü — ör. bloklayan gölge log yazıcısı) systemd tarafından
+//  yeniden başlatılır. Heartbeat yalnızca sessiz aboneliği yakalar; bu modül
+//  döngünün kendisinin ilerlediğini kanıtlar.
+//
+//  ✓ READY=1: ilk başarılı tam senkronizasyondan sonra (süreç başına bir kez)
+//  ✓ WATCHDOG=1: yalnızca uçtan uca işlenen blok sonunda ve blok beklenen
+//    sürede bittiyse — duran döngü ping atmayı bırakır, systemd yeniden başlatır
+//  ✓ STOPPING=1: düzgün kapanış yolunda
+//  ✓ NOTIFY_SOCKET yoksa (systemd dışı) ya da feature kapalıysa hepsi no-op
+//
+//  Bağımlılıksız: NOTIFY_SOCKET'e tek bir Unix datagram (yol veya '@'
+//  soyut ad). WATCHDOG_USEC / WATCHDOG_PID systemd'nin verdiği ortamdan okunur.
+// ============================================================================
+
+use parking_lot::Mutex;
+use std::os::unix::net::UnixDatagram;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Mesaj Biçimi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// sd_notify durum bloğu: satır başına KEY=VALUE. Değerdeki satır sonları
+/// boşluğa çevrilir — aksi halde systemd sonraki satırı ayrı atama okur.
+pub fn format_state(assignments: &[(&str, &str)]) -> String {
+    assignments
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value.replace(['\n', '\r'], " ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// NOTIFY_SOCKET hedefi
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketTarget {
+    Path(PathBuf),
+    /// '@' ile başlayan Linux soyut ad alanı
+    Abstract(Vec<u8>),
+}
+
+impl SocketTarget {
+    /// Desteklenmeyen biçim (ör. vsock:) → None
+    pub fn parse(raw: &str) -> Option<Self> {
+        if let Some(name) = raw.strip_prefix('@') {
+            return Some(Self::Abstract(name.as_bytes().to_vec()));
+        }
+        raw.starts_with('/').then(|| Self::Path(PathBuf::from(raw)))
+    }
+}
+
+/// WATCHDOG_USEC → zaman aşımı. WATCHDOG_PID başka bir süreci gösteriyorsa
+/// (systemd ana PID'e bakıyor) watchdog bize ait değildir.
+pub fn watchdog_timeout(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    if pid.is_some_and(|p| p.trim().parse::<u32>().ok() != Some(own_pid)) {
+        return None;
+    }
+    let usec = usec?.trim().parse::<u64>().ok().filter(|&u| u > 0)?;
+    Some(Duration::from_micros(usec))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Datagram Gönderici
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct Notifier {
+    socket: UnixDatagram,
+    target: SocketTarget,
+}
+
+impl Notifier {
+    pub fn new(target: SocketTarget) -> std::io::Result<Self> {
+        Ok(Self { socket: UnixDatagram::unbound()?, target })
+    }
+
+    /// Feature kapalı veya NOTIFY_SOCKET yok → None
+    fn from_env() -> Option<Self> {
+        if !cfg!(feature = "systemd") {
+            return None;
+        }
+        let target = SocketTarget::parse(&std::env::var("NOTIFY_SOCKET").ok()?)?;
+        match Self::new(target) {
+            Ok(notifier) => Some(notifier),
+            Err(e) => {
+                eprintln!("  ⚠️  [systemd] notify socket unavailable: {} — sd_notify disabled", e);
+                None
+            }
+        }
+    }
+
+    /// Tek datagram — hata döner ama çağıranlar uyarıyla geçer (bot durmaz)
+    pub fn notify(&self, assignments: &[(&str, &str)]) -> std::io::Result<()> {
+        let message = format_state(assignments);
+        match &self.target {
+            SocketTarget::Path(path) => self.socket.send_to(message.as_bytes(), path).map(drop),
+            SocketTarget::Abstract(name) => {
+                #[cfg(target_os = "linux")]
+                {
+                    use std::os::linux::net::SocketAddrExt;
+                    let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+                    self.socket.send_to_addr(message.as_bytes(), &addr).map(drop)
+                }
+                #[cfg(not(target_os = "linux"))]
+                {
+                    let _ = name;
+                    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "abstract NOTIFY_SOCKET"))
+                }
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Watchdog Zamanlaması
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Blok sonu ping kararı. systemd önerisi: zaman aşımının yarısında bir ping.
+/// Blok işleme süresi de bu yarıyı aşıyorsa blok "beklenen sürede" bitmemiştir
+/// (takılıp sonradan çözülen döngü) — o blok ping saymaz.
+pub struct Watchdog {
+    notifier: Notifier,
+    ping_every: Duration,
+    last_ping: Option<Instant>,
+}
+
+impl Watchdog {
+    pub fn new(notifier: Notifier, timeout: Duration) -> Self {
+        Self { notifier, ping_every: timeout / 2, last_ping: None }
+    }
+
+    /// Uçtan uca işlenen blok — ping atıldıysa true
+    pub fn on_block_processed(&mut self, now: Instant, block_elapsed: Duration) -> bool {
+        if block_elapsed > self.ping_every {
+            return false;
+        }
+        if self.last_ping.is_some_and(|t| now.duration_since(t) < self.ping_every) {
+            return false;
+        }
+        match self.notifier.notify(&[("WATCHDOG", "1")]) {
+            Ok(()) => {
+                self.last_ping = Some(now);
+                true
+            }
+            Err(e) => {
+                eprintln!("  ⚠️  [systemd] WATCHDOG=1 failed: {}", e);
+                false
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Süreç Geneli Yaşam Döngüsü
+// ─────────────────────────────────────────────────────────────────────────────
+
+static NOTIFIER: LazyLock<Option<Notifier>> = LazyLock::new(Notifier::from_env);
+
+static WATCHDOG: LazyLock<Mutex<Option<Watchdog>>> = LazyLock::new(|| {
+    let timeout = watchdog_timeout(
+        std::env::var("WATCHDOG_USEC").ok().as_deref(),
+        std::env::var("WATCHDOG_PID").ok().as_deref(),
+        std::process::id(),
+    );
+    let watchdog = timeout.zip(Notifier::from_env()).map(|(timeout, notifier)| Watchdog::new(notifier, timeout));
+    Mutex::new(watchdog)
+});
+
+static READY_SENT: AtomicBool = AtomicBool::new(false);
+
+fn send(assignments: &[(&str, &str)]) {
+    if let Some(notifier) = NOTIFIER.as_ref() {
+        if let Err(e) = notifier.notify(assignments) {
+            eprintln!("  ⚠️  [systemd] sd_notify {} failed: {}", assignments[0].0, e);
+        }
+    }
+}
+
+/// İlk başarılı tam senkronizasyon — reconnect'lerde tekrar gönderilmez
+pub fn ready(active_pools: usize, block: u64) {
+    if READY_SENT.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    let status = format!("synced {} pools at block {}", active_pools, block);
+    send(&[("READY", "1"), ("STATUS", &status)]);
+}
+
+/// Ana döngü blok sonu: watchdog'u besle (yalnızca beklenen sürede bittiyse)
+pub fn block_processed(block_elapsed: Duration) {
+    if let Some(watchdog) = WATCHDOG.lock().as_mut() {
+        watchdog.on_block_processed(Instant::now(), block_elapsed);
+    }
+}
+
+/// Düzgün kapanış başladı
+pub fn stopping() {
+    send(&[("STOPPING", "1"), ("STATUS", "graceful shutdown")]);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_state() {
+        assert_eq!(format_state(&[("READY", "1")]), "READY=1");
+        assert_eq!(
+            format_state(&[("READY", "1"), ("STATUS", "synced 4 pools\nat block 9")]),
+            "READY=1\nSTATUS=synced 4 pools at block 9"
+        );
+        assert_eq!(format_state(&[("WATCHDOG", "1")]), "WATCHDOG=1");
+    }
+
+    #[test]
+    fn test_parse_socket_and_watchdog_env() {
+        assert_eq!(
+            SocketTarget::parse("/run/systemd/notify"),
+            Some(SocketTarget::Path(PathBuf::from("/run/systemd/notify")))
+        );
+        assert_eq!(SocketTarget::parse("@sd-notify"), Some(SocketTarget::Abstract(b"sd-notify".to_vec())));
+        assert_eq!(SocketTarget::parse("vsock:2:1234"), None);
+
+        assert_eq!(watchdog_timeout(Some("30000000"), None, 7), Some(Duration::from_secs(30)));
+        assert_eq!(watchdog_timeout(Some("30000000"), Some("7"), 7), Some(Duration::from_secs(30)));
+        assert_eq!(watchdog_timeout(Some("30000000"), Some("8"), 7), None);
+        assert_eq!(watchdog_timeout(Some("0"), None, 7), None);
+        assert_eq!(watchdog_timeout(None, None, 7), None);
+    }
+
+    /// Sahte NOTIFY_SOCKET: döngü blok işledikçe ping, durunca / takılınca ping yok
+    #[test]
+    fn test_stalled_loop_stops_watchdog_pings() {
+        let dir = std::env::temp_dir().join(format!("arb_sdnotify_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        crate::secure_fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notify.sock");
+        let listener = UnixDatagram::bind(&path).unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let drain = || {
+            let mut buf = [0u8; 256];
+            let mut out = Vec::new();
+            while let Ok(n) = listener.recv(&mut buf) {
+                out.push(String::from_utf8_lossy(&buf[..n]).into_owned());
+            }
+            out
+        };
+
+        let notifier = Notifier::new(SocketTarget::Path(path.clone())).unwrap();
+        let mut watchdog = Watchdog::new(notifier, Duration::from_secs(10));
+        let fast = Duration::from_millis(40);
+        let t0 = Instant::now();
+
+        // Sağlıklı döngü: 2s'lik bloklar, 20s → ping her 5s'de bir
+        for block in 0..10u64 {
+            watchdog.on_block_processed(t0 + Duration::from_secs(2 * block), fast);
+        }
+        let pings = drain();
+        assert_eq!(pings, vec!["WATCHDOG=1"; 4]);
+
+        // Takılan döngü: blok 30s sürdü (bloklayan yazıcı) — ping yok
+        assert!(!watchdog.on_block_processed(t0 + Duration::from_secs(50), Duration::from_secs(30)));
+        // Duran döngü: çağrı hiç gelmez — soket sessiz kalır
+        assert!(drain().is_empty());
+
+        // Döngü toparlanınca ping yeniden başlar
+        assert!(watchdog.on_block_processed(t0 + Duration::from_secs(52), fast));
+        assert_eq!(drain(), vec!["WATCHDOG=1"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
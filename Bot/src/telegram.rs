@@ -10,7 +10,6 @@
 //  ✓ Sıfır ek bağımlılık (reqwest + serde_json zaten mevcut)
 // ============================================================================
 
-use chrono::Local;
 use std::time::Instant;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
@@ -121,6 +120,92 @@ pub enum TelegramMessage {
         local_nonce: u64,
         chain_nonce: u64,
     },
+
+    // ── Ücret Rejimi Değişikliği (fee_monitor) ──
+    FeeDrift {
+        source: String,
+        in_force_bps: f64,
+        on_chain_bps: f64,
+        auto_updated: bool,
+    },
+
+    // ── Eksik Token Onayı (approval_audit) ──
+    ApprovalMissing {
+        missing: Vec<String>,
+        downgraded: bool,
+    },
+
+    // ── Model Hatası (impact_verification) ──
+    ImpactDeviation {
+        pool_name: String,
+        tick_deviation: i32,
+        threshold_ticks: u32,
+        block_number: u64,
+        /// Devreye giren slippage artışı (0 = yok)
+        slippage_bump_bps: u64,
+    },
+
+    // ── Bytecode Değişimi (bytecode_watchdog) ──
+    BytecodeChanged {
+        /// "rol adres" etiketleri
+        changed: Vec<String>,
+        /// Arbitraj kontratı değişti → el sıkışması bitene kadar gölge mod
+        contract_upgraded: bool,
+    },
+
+    // ── Quoter Eşlik Denetimi (quoter_audit) ──
+    QuoterDeviation {
+        pool_name: String,
+        /// "exact" / "sizing"
+        engine: String,
+        probe_weth: f64,
+        deviation_bps: f64,
+        threshold_bps: f64,
+        block_number: u64,
+    },
+
+    // ── Kalıcı Spread / Yapısal Ayrışma (divergence_guard) ──
+    StructuralDivergence {
+        pair_name: String,
+        price_a: f64,
+        price_b: f64,
+        spread_pct: f64,
+        blocks: u64,
+        /// Silinince onaylanmış sayılan marker dosyası
+        marker: String,
+    },
+
+    StructuralDivergenceCleared {
+        pair_name: String,
+        /// "spread closed" / "acknowledged"
+        reason: String,
+    },
+
+    /// Arbitraj kontratının pause view'ı true döndü — yürütme gölgede
+    ContractPaused {
+        contract: String,
+        block_number: u64,
+    },
+
+    ContractUnpaused {
+        contract: String,
+        block_number: u64,
+        /// Duraklatmanın görüldüğü bloktan bu yana (oturum içinde görüldüyse)
+        paused_blocks: Option<u64>,
+    },
+
+    CircuitBreakerPaused {
+        block_number: u64,
+        /// Son hatalar, satır başına "tür: neden"
+        reasons: String,
+        /// None: deneme kullanıldı, operatör sıfırlaması bekleniyor
+        probe_in_secs: Option<u64>,
+    },
+
+    CircuitBreakerResumed {
+        block_number: u64,
+        reason: String,
+    },
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -316,7 +401,7 @@ async fn send_to_telegram(
 ///
 /// HTML parse_mode kullanılır (MarkdownV2'nin escape sorunlarından kaçınmak için).
 fn format_message(msg: &TelegramMessage) -> String {
-    let ts = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let ts = crate::clock::local_now().format("%Y-%m-%d %H:%M:%S").to_string();
 
     match msg {
         // ── Kural 1: Alfa Bildirimi ──
@@ -551,6 +636,206 @@ fn format_message(msg: &TelegramMessage) -> String {
                 local_nonce, chain_nonce, ts,
             )
         }
+
+        TelegramMessage::FeeDrift {
+            source,
+            in_force_bps,
+            on_chain_bps,
+            auto_updated,
+        } => {
+            let action = if *auto_updated {
+                "🔁 Kar modeli zincirdeki degerle guncellendi"
+            } else {
+                "🛑 Yapilandirilan deger korundu — kar modeli HATALI olabilir"
+            };
+            format!(
+                "🚨 <b>UCRET REJIMI DEGISTI</b>\n\
+                 \n\
+                 🏷️ Kaynak: {}\n\
+                 ⚙️ Yururlukteki: {:.2} bps | Zincir: {:.2} bps\n\
+                 {}\n\
+                 ⏰ {}\n",
+                source, in_force_bps, on_chain_bps, action, ts,
+            )
+        }
+
+        TelegramMessage::ApprovalMissing { missing, downgraded } => {
+            let action = if *downgraded {
+                "👻 Yurutme GOLGE moda dusuruldu"
+            } else {
+                "⚠️ Yurutme devam ediyor — bu harcayicilar uzerinden TX revert eder"
+            };
+            format!(
+                "🚨 <b>TOKEN ONAYI EKSIK</b>\n\
+                 \n\
+                 🔐 {}\n\
+                 {}\n\
+                 ⏰ {}\n",
+                missing.join(", "), action, ts,
+            )
+        }
+
+        TelegramMessage::ImpactDeviation {
+            pool_name,
+            tick_deviation,
+            threshold_ticks,
+            block_number,
+            slippage_bump_bps,
+        } => {
+            let action = if *slippage_bump_bps > 0 {
+                format!("🛡️ minProfit slippage +{} bps (gecici)", slippage_bump_bps)
+            } else {
+                "⚠️ Kar modeli sapiyor olabilir (fee / bitmap)".to_string()
+            };
+            format!(
+                "⚠️ <b>HAVUZ ETKISI SAPTI</b>\n\
+                 \n\
+                 🏊 Havuz: {}\n\
+                 📐 Sapma: {:+} tick (esik {})\n\
+                 📦 Blok: #{}\n\
+                 {}\n\
+                 ⏰ {}\n",
+                pool_name, tick_deviation, threshold_ticks, block_number, action, ts,
+            )
+        }
+
+        TelegramMessage::BytecodeChanged { changed, contract_upgraded } => {
+            let action = if *contract_upgraded {
+                "👻 Kontrat yukseltildi — el sikismasi bitene kadar GOLGE mod"
+            } else {
+                "🔧 REVM base_db yeni bytecode ile yeniden kuruldu"
+            };
+            format!(
+                "🚨 <b>BYTECODE DEGISTI</b>\n\
+                 \n\
+                 🧬 {}\n\
+                 {}\n\
+                 ⛽ Gas kalibrasyonu sifirlandi\n\
+                 ⏰ {}\n",
+                changed.join(", "), action, ts,
+            )
+        }
+
+        TelegramMessage::QuoterDeviation {
+            pool_name,
+            engine,
+            probe_weth,
+            deviation_bps,
+            threshold_bps,
+            block_number,
+        } => {
+            format!(
+                "⚠️ <b>QUOTER ESLIGI BOZULDU</b>\n\
+                 \n\
+                 🏊 Havuz: {}\n\
+                 🧮 Motor: {} ({} WETH)\n\
+                 📐 Sapma: {:.2} bps (esik {:.2})\n\
+                 📦 Blok: #{}\n\
+                 🔍 Bitmap bayat ya da matematik hatasi olabilir\n\
+                 ⏰ {}\n",
+                pool_name, engine, probe_weth, deviation_bps, threshold_bps, block_number, ts,
+            )
+        }
+
+        // ── Yapısal Ayrışma ──
+        TelegramMessage::StructuralDivergence {
+            pair_name,
+            price_a,
+            price_b,
+            spread_pct,
+            blocks,
+            marker,
+        } => {
+            format!(
+                "🚨 <b>YAPISAL AYRISMA - CIFT GOLGEDE</b>\n\
+                 \n\
+                 🏊 Cift: {}\n\
+                 💱 Fiyatlar: {:.6} / {:.6} (spread %{:.3})\n\
+                 ⏳ Sure: {} blok (~{}s) kapanmadi\n\
+                 🔍 Depeg, token gocu ya da cozumleme hatasi olabilir\n\
+                 ✋ Onay: {} dosyasini sil\n\
+                 ⏰ {}\n",
+                pair_name,
+                price_a,
+                price_b,
+                spread_pct,
+                blocks,
+                blocks * 2,
+                marker,
+                ts,
+            )
+        }
+
+        TelegramMessage::StructuralDivergenceCleared { pair_name, reason } => {
+            format!(
+                "✅ <b>YAPISAL AYRISMA KALKTI</b>\n\
+                 \n\
+                 🏊 Cift: {}\n\
+                 📋 Neden: {}\n\
+                 ⏰ {}\n",
+                pair_name, reason, ts,
+            )
+        }
+
+        // ── Kontrat Pause Kapısı ──
+        TelegramMessage::ContractPaused { contract, block_number } => {
+            format!(
+                "🛑 <b>KONTRAT DURAKLATILDI</b>\n\
+                 \n\
+                 📜 Kontrat: <code>{}</code>\n\
+                 📦 Blok: #{}\n\
+                 👻 Yurutme GOLGE modda — TX gonderilmiyor\n\
+                 ⏰ {}\n",
+                contract, block_number, ts,
+            )
+        }
+
+        TelegramMessage::ContractUnpaused { contract, block_number, paused_blocks } => {
+            let duration = paused_blocks
+                .map(|b| format!("⏳ Sure: {} blok (~{}s)\n", b, b * 2))
+                .unwrap_or_default();
+            format!(
+                "✅ <b>KONTRAT DEVAM EDIYOR</b>\n\
+                 \n\
+                 📜 Kontrat: <code>{}</code>\n\
+                 📦 Blok: #{}\n\
+                 {}\
+                 🚀 Yurutme yeniden acik\n\
+                 ⏰ {}\n",
+                contract, block_number, duration, ts,
+            )
+        }
+
+        TelegramMessage::CircuitBreakerPaused { block_number, reasons, probe_in_secs } => {
+            let next = probe_in_secs
+                .map(|s| format!("🔎 Deneme: {}s sonra\n", s))
+                .unwrap_or_else(|| "🔧 Operator sifirlamasi bekleniyor (circuit_breaker.json)\n".to_string());
+            format!(
+                "🛑 <b>BOT DURAKLATILDI (CIRCUIT BREAKER)</b>\n\
+                 \n\
+                 📦 Blok: #{}\n\
+                 ❌ Son hatalar:\n<code>{}</code>\n\
+                 {}\
+                 📡 Senkron ve spread logu suruyor\n\
+                 ⏰ {}\n",
+                block_number,
+                // Hata metni HTML parse modunu bozmasın
+                reasons.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"),
+                next,
+                ts,
+            )
+        }
+
+        TelegramMessage::CircuitBreakerResumed { block_number, reason } => {
+            format!(
+                "✅ <b>BOT DEVAM EDIYOR</b>\n\
+                 \n\
+                 📦 Blok: #{}\n\
+                 🔓 Neden: {}\n\
+                 ⏰ {}\n",
+                block_number, reason, ts,
+            )
+        }
     }
 }
 
@@ -614,6 +899,60 @@ mod tests {
         assert!(text.contains("guvenli moda"));
     }
 
+    #[test]
+    fn test_structural_divergence_format() {
+        let msg = TelegramMessage::StructuralDivergence {
+            pair_name: "UniV3-USDC ↔ Aero-USDC".to_string(),
+            price_a: 2500.0,
+            price_b: 2512.5,
+            spread_pct: 0.5,
+            blocks: 300,
+            marker: "data/divergence/a_b.marker".to_string(),
+        };
+        let text = format_message(&msg);
+        assert!(text.contains("YAPISAL AYRISMA"));
+        assert!(text.contains("2500.000000 / 2512.500000"));
+        assert!(text.contains("300 blok (~600s)"));
+        assert!(text.contains("data/divergence/a_b.marker"));
+    }
+
+    #[test]
+    fn test_contract_pause_format() {
+        let paused = format_message(&TelegramMessage::ContractPaused {
+            contract: "0x00000000000000000000000000000000000000aa".to_string(),
+            block_number: 1_000,
+        });
+        assert!(paused.contains("KONTRAT DURAKLATILDI"));
+        assert!(paused.contains("#1000"));
+
+        let resumed = format_message(&TelegramMessage::ContractUnpaused {
+            contract: "0x00000000000000000000000000000000000000aa".to_string(),
+            block_number: 1_150,
+            paused_blocks: Some(150),
+        });
+        assert!(resumed.contains("KONTRAT DEVAM EDIYOR"));
+        assert!(resumed.contains("150 blok (~300s)\n🚀"));
+    }
+
+    #[test]
+    fn test_circuit_breaker_pause_format() {
+        let probing = format_message(&TelegramMessage::CircuitBreakerPaused {
+            block_number: 2_000,
+            reasons: "revert: status=0".to_string(),
+            probe_in_secs: Some(900),
+        });
+        assert!(probing.contains("BOT DURAKLATILDI"));
+        assert!(probing.contains("<code>revert: status=0</code>"));
+        assert!(probing.contains("900s sonra"));
+
+        let awaiting = format_message(&TelegramMessage::CircuitBreakerPaused {
+            block_number: 2_500,
+            reasons: String::new(),
+            probe_in_secs: None,
+        });
+        assert!(awaiting.contains("Operator sifirlamasi"));
+    }
+
     #[test]
     fn test_sender_nonblocking() {
         // Kanal kapasitesi 1 olan sender — 3 mesaj gönder, sadece 1 kabul edilir
@@ -0,0 +1,549 @@
+// ============================================================================
+//  TOKEN PROBE — Standart Dışı Token Davranış Testi
+//
+//  Transfer ücreti alan (fee-on-transfer) veya rebase yapan bir token'da
+//  tüm kâr hesapları sessizce yanlış olur, kontrat da anlaşılmaz şekilde
+//  revert eder. Başlangıçta yapılandırılmış her token için bir kez:
+//
+//  ✓ Token'ın gerçek bytecode'u REVM'e yüklenir (proxy ise implementation da)
+//  ✓ balanceOf mapping slot'u keşfedilir: 0..=10 slot'larına override yazılır,
+//    balanceOf yazılan değeri döndürene kadar denenir
+//  ✓ Sentetik bakiyeli holder'dan bilinen miktar transfer simüle edilir:
+//    alıcı tam miktarı almalı, gönderen tam miktar kadar azalmalı,
+//    totalSupply değişmemeli — aksi halde FeeOnTransfer / Rebasing
+//  ✓ Sonuç tokens.json'a bytecode hash'iyle yazılır; token yükseltilmedikçe
+//    sonraki açılışlarda yeniden probe edilmez
+//
+//  Bayraklı token içeren çiftler ALLOW_NONSTANDARD_TOKENS=true olmadıkça
+//  yalnızca gölge loglanır (filtre adı "token_behavior").
+// ============================================================================
+
+use alloy::hex;
+use alloy::primitives::{address, b256, keccak256, Address, Bytes, B256, U256};
+use alloy::providers::Provider;
+use revm::{
+    context::{Context, Journal, TxEnv},
+    context_interface::result::ExecutionResult,
+    database::InMemoryDB,
+    handler::{ExecuteEvm, MainBuilder},
+    primitives::{hardfork::SpecId, TxKind},
+    state::{AccountInfo, EvmState},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use crate::errors::{BotError, BotResult};
+use crate::simulator::{balance_mapping_key, insert_code, BALANCE_OF_SELECTOR, TRANSFER_SELECTOR};
+use crate::types::PoolConfig;
+
+/// Probe sonuç önbelleği (çalışma dizininde)
+pub const TOKENS_CACHE_PATH: &str = "tokens.json";
+
+/// balanceOf mapping'i / totalSupply için denenen slot aralığı (0..=N).
+/// OZ ERC20 → 0, WETH9 → 3, FiatToken (USDC) → 9.
+const SLOT_CANDIDATES: u64 = 10;
+
+/// EIP-1967: keccak256("eip1967.proxy.implementation") - 1
+const EIP1967_IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+/// ZeppelinOS AdminUpgradeabilityProxy (FiatTokenProxy / USDC):
+/// keccak256("org.zeppelinos.proxy.implementation")
+const ZEPPELINOS_IMPLEMENTATION_SLOT: B256 =
+    b256!("7050c9e0f4ca769c69bd3a8ef740bc37934f8e2c036e5a723fd8ee048ed3f8c3");
+
+/// totalSupply()
+const TOTAL_SUPPLY_SELECTOR: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
+
+/// Sentetik bakiyeli gönderici (EOA)
+const PROBE_HOLDER: Address = address!("00000000000000000000000000000000000c0de3");
+/// Transferin alıcısı
+const PROBE_RECIPIENT: Address = address!("00000000000000000000000000000000000c0de4");
+/// Slot keşfinde yazılan ayırt edici bakiye (PROBE_AMOUNT'tan büyük)
+const PROBE_BALANCE: U256 = U256::from_limbs([0x5eed_5eed_5eed_5eed, 1, 0, 0]);
+/// Transfer miktarı — 10_000'in katı, fee bps'i tam bölünür
+const PROBE_AMOUNT: U256 = U256::from_limbs([1_000_000_000_000, 0, 0, 0]);
+/// Tek probe çağrısı için gas limiti
+const PROBE_GAS_LIMIT: u64 = 1_000_000;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sınıflandırma
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Token'ın transfer davranışı
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TokenBehavior {
+    /// Alıcı tam miktarı aldı, gönderen tam miktar kadar azaldı, arz sabit
+    Standard,
+    /// Alıcı eksik aldı — kesinti / miktar (bps)
+    FeeOnTransfer { fee_bps: u32 },
+    /// Arz değişti veya bakiyeler transfer miktarıyla açıklanamıyor
+    Rebasing,
+    /// Probe sonuç veremedi (slot bulunamadı, revert) — engellenmez, uyarılır
+    Inconclusive,
+}
+
+impl TokenBehavior {
+    /// Yürütmeyi engelleyen davranış mı?
+    pub fn is_nonstandard(&self) -> bool {
+        matches!(self, Self::FeeOnTransfer { .. } | Self::Rebasing)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Standard => "standard".into(),
+            Self::FeeOnTransfer { fee_bps } => format!("fee-on-transfer ({}bps)", fee_bps),
+            Self::Rebasing => "rebasing".into(),
+            Self::Inconclusive => "inconclusive".into(),
+        }
+    }
+}
+
+/// Tek token'ın probe sonucu (tokens.json kaydı)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenProbe {
+    pub behavior: TokenBehavior,
+    /// Keşfedilen balanceOf mapping slot'u
+    pub balance_slot: Option<u64>,
+    /// Probe edilen bytecode'un hash'i (proxy ise implementation dahil)
+    pub code_hash: B256,
+    /// Gözlenen bakiye/arz değişimi veya sonuçsuzluk nedeni
+    pub detail: Option<String>,
+}
+
+/// Transfer öncesi/sonrası gözlem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TransferObservation {
+    /// Gönderenin bakiyesindeki azalma
+    sent: U256,
+    /// Alıcının bakiyesindeki artış
+    received: U256,
+    supply_before: U256,
+    supply_after: U256,
+}
+
+impl TransferObservation {
+    fn classify(&self, amount: U256) -> TokenBehavior {
+        if self.received < amount {
+            let fee = amount - self.received;
+            let fee_bps: u32 = (fee * U256::from(10_000u64) / amount).saturating_to();
+            return TokenBehavior::FeeOnTransfer { fee_bps: fee_bps.max(1) };
+        }
+        if self.received != amount || self.sent != amount || self.supply_before != self.supply_after {
+            return TokenBehavior::Rebasing;
+        }
+        TokenBehavior::Standard
+    }
+
+    fn detail(&self) -> String {
+        format!(
+            "sent {} → received {} | totalSupply {} → {}",
+            self.sent, self.received, self.supply_before, self.supply_after
+        )
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Token Bytecode (Proxy Dahil)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Proxy token'ın implementation'ı
+#[derive(Debug, Clone)]
+pub struct ProxyImplementation {
+    /// Implementation adresinin okunduğu storage slot'u
+    pub slot: B256,
+    pub address: Address,
+    pub code: Vec<u8>,
+}
+
+/// Probe girdisi: token bytecode'u + (proxy ise) implementation
+#[derive(Debug, Clone, Default)]
+pub struct TokenCode {
+    pub code: Vec<u8>,
+    pub proxy: Option<ProxyImplementation>,
+}
+
+impl TokenCode {
+    /// Önbellek anahtarı — token veya implementation yükseltilirse değişir
+    pub fn code_hash(&self) -> B256 {
+        let mut bytes = self.code.clone();
+        if let Some(ref proxy) = self.proxy {
+            bytes.extend_from_slice(&proxy.code);
+        }
+        keccak256(bytes)
+    }
+
+    /// Token (+ implementation) yüklü boş db
+    fn base_db(&self, token: Address) -> InMemoryDB {
+        let mut db = InMemoryDB::default();
+        insert_code(&mut db, token, &self.code);
+        if let Some(ref proxy) = self.proxy {
+            insert_code(&mut db, proxy.address, &proxy.code);
+            let _ = db.insert_account_storage(
+                token,
+                U256::from_be_bytes(proxy.slot.0),
+                U256::from_be_slice(proxy.address.as_slice()),
+            );
+        }
+        db.insert_account_info(PROBE_HOLDER, AccountInfo::from_balance(U256::ZERO));
+        db
+    }
+}
+
+/// Token bytecode'unu al; EIP-1967 / ZeppelinOS proxy ise implementation'ı da al
+pub async fn fetch_token_code<P: Provider + Sync>(
+    provider: &P,
+    token: Address,
+) -> BotResult<TokenCode> {
+    let code = provider
+        .get_code_at(token)
+        .await
+        .map_err(|e| BotError::from_transport(format!("[{}] token bytecode", token), e))?;
+    let mut token_code = TokenCode { code: code.to_vec(), proxy: None };
+
+    for slot in [EIP1967_IMPLEMENTATION_SLOT, ZEPPELINOS_IMPLEMENTATION_SLOT] {
+        let value = provider
+            .get_storage_at(token, U256::from_be_bytes(slot.0))
+            .await
+            .map_err(|e| BotError::from_transport(format!("[{}] proxy slot", token), e))?;
+        if value.is_zero() {
+            continue;
+        }
+        let implementation = Address::from_slice(&value.to_be_bytes::<32>()[12..]);
+        let code = provider.get_code_at(implementation).await.map_err(|e| {
+            BotError::from_transport(format!("[{}] implementation bytecode", token), e)
+        })?;
+        token_code.proxy = Some(ProxyImplementation {
+            slot,
+            address: implementation,
+            code: code.to_vec(),
+        });
+        break;
+    }
+    Ok(token_code)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// REVM Probe
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// PROBE_HOLDER'dan tek çağrı — (dönüş verisi, state diff)
+fn call(db: &InMemoryDB, chain_id: u64, to: Address, data: Vec<u8>) -> Result<(Bytes, EvmState), String> {
+    let ctx: Context<revm::context::BlockEnv, _, _, InMemoryDB, Journal<InMemoryDB>, ()> =
+        Context::new(db.clone(), SpecId::CANCUN).modify_cfg_chained(|cfg| {
+            cfg.chain_id = chain_id;
+        });
+
+    let tx = TxEnv::builder()
+        .caller(PROBE_HOLDER)
+        .chain_id(Some(chain_id))
+        .kind(TxKind::Call(to))
+        .data(Bytes::from(data))
+        .gas_limit(PROBE_GAS_LIMIT)
+        .nonce(0)
+        .build()
+        .map_err(|e| format!("TxEnv build failed: {:?}", e))?;
+
+    let mut evm = ctx.build_mainnet();
+    let result_and_state = evm.transact(tx).map_err(|e| format!("EVM error: {:?}", e))?;
+    match result_and_state.result {
+        ExecutionResult::Success { output, .. } => Ok((output.data().clone(), result_and_state.state)),
+        ExecutionResult::Revert { output, .. } => Err(format!("REVERT: 0x{}", hex::encode(&output))),
+        ExecutionResult::Halt { reason, .. } => Err(format!("HALT: {:?}", reason)),
+    }
+}
+
+/// Tek uint256 dönen view çağrısı
+fn read_word(db: &InMemoryDB, chain_id: u64, token: Address, data: Vec<u8>) -> Result<U256, String> {
+    let (output, _) = call(db, chain_id, token, data)?;
+    if output.len() < 32 {
+        return Err(format!("short return ({} bytes)", output.len()));
+    }
+    Ok(U256::from_be_slice(&output[..32]))
+}
+
+fn balance_of_calldata(holder: Address) -> Vec<u8> {
+    let mut data = BALANCE_OF_SELECTOR.to_vec();
+    data.extend_from_slice(&[0u8; 12]);
+    data.extend_from_slice(holder.as_slice());
+    data
+}
+
+fn transfer_calldata(to: Address, amount: U256) -> Vec<u8> {
+    let mut data = TRANSFER_SELECTOR.to_vec();
+    data.extend_from_slice(&[0u8; 12]);
+    data.extend_from_slice(to.as_slice());
+    data.extend_from_slice(&amount.to_be_bytes::<32>());
+    data
+}
+
+/// Çağrının storage yazımlarını db'ye işle (sonraki okumalar görsün)
+fn apply_storage(db: &mut InMemoryDB, state: &EvmState) {
+    for (addr, account) in state {
+        for (slot, value) in &account.storage {
+            if value.original_value != value.present_value {
+                let _ = db.insert_account_storage(*addr, *slot, value.present_value);
+            }
+        }
+    }
+}
+
+/// Aday slot'lara sırayla PROBE_BALANCE yaz; view çağrısı aynı değeri
+/// döndüğü ilk slot'u ver. `key` aday indeksi storage anahtarına çevirir.
+fn find_slot(
+    db: &InMemoryDB,
+    chain_id: u64,
+    token: Address,
+    view: &[u8],
+    key: impl Fn(u64) -> U256,
+) -> Option<u64> {
+    (0..=SLOT_CANDIDATES).find(|&slot| {
+        let mut db = db.clone();
+        let _ = db.insert_account_storage(token, key(slot), PROBE_BALANCE);
+        read_word(&db, chain_id, token, view.to_vec()) == Ok(PROBE_BALANCE)
+    })
+}
+
+/// balanceOf mapping slot'unu keşfet: her aday slot için holder'ın mapping
+/// anahtarına override yazılır; balanceOf yazılan değeri dönerse slot budur.
+pub fn find_balance_slot(db: &InMemoryDB, chain_id: u64, token: Address, holder: Address) -> Option<u64> {
+    find_slot(db, chain_id, token, &balance_of_calldata(holder), |slot| {
+        balance_mapping_key(holder, slot)
+    })
+}
+
+/// totalSupply slot'u — bulunursa holder bakiyesiyle tutarlı bir arz yazılır
+/// (aksi halde yakım yapan token'lar sıfır arzda underflow ile revert eder)
+fn find_supply_slot(db: &InMemoryDB, chain_id: u64, token: Address) -> Option<u64> {
+    find_slot(db, chain_id, token, &TOTAL_SUPPLY_SELECTOR, U256::from)
+}
+
+/// Holder bakiyesi, alıcı bakiyesi, totalSupply
+fn measure(db: &InMemoryDB, chain_id: u64, token: Address) -> Result<[U256; 3], String> {
+    Ok([
+        read_word(db, chain_id, token, balance_of_calldata(PROBE_HOLDER))?,
+        read_word(db, chain_id, token, balance_of_calldata(PROBE_RECIPIENT))?,
+        read_word(db, chain_id, token, TOTAL_SUPPLY_SELECTOR.to_vec())?,
+    ])
+}
+
+fn observe_transfer(db: &mut InMemoryDB, chain_id: u64, token: Address) -> Result<TransferObservation, String> {
+    let [holder_before, recipient_before, supply_before] = measure(db, chain_id, token)?;
+    let (_, state) = call(db, chain_id, token, transfer_calldata(PROBE_RECIPIENT, PROBE_AMOUNT))?;
+    apply_storage(db, &state);
+    let [holder_after, recipient_after, supply_after] = measure(db, chain_id, token)?;
+    Ok(TransferObservation {
+        sent: holder_before.saturating_sub(holder_after),
+        received: recipient_after.saturating_sub(recipient_before),
+        supply_before,
+        supply_after,
+    })
+}
+
+/// Token'ın transfer davranışını REVM'de ölç
+pub fn probe_token(token: Address, code: &TokenCode, chain_id: u64) -> TokenProbe {
+    let code_hash = code.code_hash();
+    let inconclusive = |balance_slot: Option<u64>, detail: String| TokenProbe {
+        behavior: TokenBehavior::Inconclusive,
+        balance_slot,
+        code_hash,
+        detail: Some(detail),
+    };
+    if code.code.is_empty() {
+        return inconclusive(None, "no bytecode".into());
+    }
+
+    let mut db = code.base_db(token);
+    let Some(slot) = find_balance_slot(&db, chain_id, token, PROBE_HOLDER) else {
+        return inconclusive(
+            None,
+            format!("balanceOf slot not found in 0..={}", SLOT_CANDIDATES),
+        );
+    };
+    if let Some(supply_slot) = find_supply_slot(&db, chain_id, token) {
+        let _ = db.insert_account_storage(token, U256::from(supply_slot), PROBE_BALANCE);
+    }
+    let _ = db.insert_account_storage(token, balance_mapping_key(PROBE_HOLDER, slot), PROBE_BALANCE);
+
+    match observe_transfer(&mut db, chain_id, token) {
+        Ok(observation) => TokenProbe {
+            behavior: observation.classify(PROBE_AMOUNT),
+            balance_slot: Some(slot),
+            code_hash,
+            detail: Some(observation.detail()),
+        },
+        Err(e) => inconclusive(Some(slot), format!("transfer probe failed: {}", e)),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// tokens.json Önbelleği
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Token adresi → probe sonucu
+pub type TokenCache = BTreeMap<Address, TokenProbe>;
+
+/// Önbelleği oku — dosya yoksa ya da bozuksa boş (hepsi yeniden probe edilir)
+pub fn load_cache(path: &Path) -> TokenCache {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            eprintln!(
+                "  ⚠️  {} parse error: {} — re-probing all tokens",
+                path.display(),
+                e
+            );
+            TokenCache::new()
+        }),
+        Err(_) => TokenCache::new(),
+    }
+}
+
+pub fn save_cache(path: &Path, cache: &TokenCache) -> std::io::Result<()> {
+    let json = serde_json::to_vec_pretty(cache).map_err(std::io::Error::other)?;
+    crate::session_summary::write_atomic(path, &json)
+}
+
+/// Aynı bytecode için önbellekte sonuç varsa onu döndür, yoksa probe edip
+/// önbelleğe yaz. İkinci değer: sonuç önbellekten mi geldi.
+pub fn cached_or_probe(
+    cache: &mut TokenCache,
+    token: Address,
+    code: &TokenCode,
+    chain_id: u64,
+) -> (TokenProbe, bool) {
+    let code_hash = code.code_hash();
+    if let Some(hit) = cache.get(&token).filter(|p| p.code_hash == code_hash) {
+        return (hit.clone(), true);
+    }
+    let probe = probe_token(token, code, chain_id);
+    cache.insert(token, probe.clone());
+    (probe, false)
+}
+
+/// Havuzun bayraklı (fee-on-transfer / rebasing) token'ı, varsa
+pub fn flagged_token(pool: &PoolConfig, nonstandard: &HashSet<Address>) -> Option<Address> {
+    [pool.base_token_address, pool.quote_token_address]
+        .into_iter()
+        .find(|token| nonstandard.contains(token))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: Address = address!("00000000000000000000000000000000000070c0");
+
+    /// totalSupply'ın tutulduğu slot
+    const SUPPLY_SLOT: u8 = 2;
+
+    /// Elle yazılmış minimal ERC20: balances mapping'i `mapping_slot`'ta,
+    /// totalSupply SUPPLY_SLOT'ta. fee=true → transfer miktarının %1'i yakılır
+    /// (alıcıya amount - amount/100 gider, totalSupply azalır).
+    fn erc20(mapping_slot: u8, fee: bool) -> Vec<u8> {
+        let mut code = vec![0x60, 0x00, 0x35, 0x60, 0xe0, 0x1c]; // selector
+        code.extend_from_slice(&[0x80, 0x63]); // DUP1 PUSH4 balanceOf
+        code.extend_from_slice(&BALANCE_OF_SELECTOR);
+        code.extend_from_slice(&[0x14, 0x60, 0x00, 0x57]);
+        let balance_of_label = code.len() - 2;
+        code.extend_from_slice(&[0x80, 0x63]); // DUP1 PUSH4 totalSupply
+        code.extend_from_slice(&TOTAL_SUPPLY_SELECTOR);
+        code.extend_from_slice(&[0x14, 0x60, 0x00, 0x57]);
+        let total_supply_label = code.len() - 2;
+        code.push(0x63); // PUSH4 transfer
+        code.extend_from_slice(&TRANSFER_SELECTOR);
+        code.extend_from_slice(&[0x14, 0x60, 0x00, 0x57]);
+        let transfer_label = code.len() - 2;
+        code.extend_from_slice(&[0x60, 0x00, 0x80, 0xfd]); // revert
+
+        // mapping anahtarı: mstore(0, <adres>) önceden yapılmış olmalı
+        let key = [0x60, mapping_slot, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00, 0x20];
+
+        // balanceOf(address) → sload(key(calldata[4..36]))
+        code[balance_of_label] = code.len() as u8;
+        code.extend_from_slice(&[0x5b, 0x60, 0x04, 0x35, 0x60, 0x00, 0x52]);
+        code.extend_from_slice(&key);
+        code.extend_from_slice(&[0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+
+        // totalSupply() → sload(SUPPLY_SLOT)
+        code[total_supply_label] = code.len() as u8;
+        code.extend_from_slice(&[0x5b, 0x60, SUPPLY_SLOT, 0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+
+        // transfer(to, amount)
+        code[transfer_label] = code.len() as u8;
+        code.extend_from_slice(&[0x5b, 0x60, 0x24, 0x35]); // [amt]
+        code.extend_from_slice(&[0x33, 0x60, 0x00, 0x52]); // mstore(0, caller)
+        code.extend_from_slice(&key); // [amt, fk]
+        code.extend_from_slice(&[0x80, 0x54, 0x82, 0x90, 0x03, 0x90, 0x55]); // bal[from] -= amt
+        if fee {
+            code.extend_from_slice(&[0x80, 0x60, 0x64, 0x90, 0x04]); // [amt, fee]
+            code.extend_from_slice(&[0x80, 0x60, SUPPLY_SLOT, 0x54, 0x03, 0x60, SUPPLY_SLOT, 0x55]); // supply -= fee
+            code.extend_from_slice(&[0x90, 0x03]); // [amt - fee]
+        }
+        code.extend_from_slice(&[0x60, 0x04, 0x35, 0x60, 0x00, 0x52]); // mstore(0, to)
+        code.extend_from_slice(&key); // [recv, tk]
+        code.extend_from_slice(&[0x80, 0x54, 0x82, 0x01, 0x90, 0x55, 0x50]); // bal[to] += recv
+        code.extend_from_slice(&[0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+        code
+    }
+
+    fn token_code(code: Vec<u8>) -> TokenCode {
+        TokenCode { code, proxy: None }
+    }
+
+    #[test]
+    fn test_standard_token_classified_standard() {
+        let probe = probe_token(TOKEN, &token_code(erc20(3, false)), 8453);
+        assert_eq!(probe.behavior, TokenBehavior::Standard, "{:?}", probe.detail);
+        assert_eq!(probe.balance_slot, Some(3));
+        assert!(!probe.behavior.is_nonstandard());
+    }
+
+    #[test]
+    fn test_fee_token_classified_fee_on_transfer() {
+        let probe = probe_token(TOKEN, &token_code(erc20(0, true)), 8453);
+        assert_eq!(probe.behavior, TokenBehavior::FeeOnTransfer { fee_bps: 100 }, "{:?}", probe.detail);
+        assert_eq!(probe.balance_slot, Some(0));
+        assert!(probe.behavior.is_nonstandard());
+    }
+
+    #[test]
+    fn test_unreadable_balance_is_inconclusive() {
+        // balanceOf her zaman 0 döner → slot bulunamaz
+        let zero = vec![0x60, 0x20, 0x60, 0x00, 0xf3];
+        let probe = probe_token(TOKEN, &token_code(zero), 8453);
+        assert_eq!(probe.behavior, TokenBehavior::Inconclusive);
+        assert_eq!(probe.balance_slot, None);
+        assert!(!probe.behavior.is_nonstandard());
+        assert_eq!(
+            probe_token(TOKEN, &TokenCode::default(), 8453).detail.as_deref(),
+            Some("no bytecode")
+        );
+    }
+
+    #[test]
+    fn test_cache_reused_until_bytecode_changes() {
+        let mut cache = TokenCache::new();
+        let standard = token_code(erc20(0, false));
+        let (first, cached) = cached_or_probe(&mut cache, TOKEN, &standard, 8453);
+        assert!(!cached);
+        let (second, cached) = cached_or_probe(&mut cache, TOKEN, &standard, 8453);
+        assert!(cached);
+        assert_eq!(first, second);
+
+        // Yükseltilmiş bytecode → önbellek geçersiz, yeniden probe
+        let upgraded = token_code(erc20(0, true));
+        let (third, cached) = cached_or_probe(&mut cache, TOKEN, &upgraded, 8453);
+        assert!(!cached);
+        assert!(third.behavior.is_nonstandard());
+
+        let json = serde_json::to_string(&cache).unwrap();
+        let restored: TokenCache = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, cache);
+        assert!(json.contains("\"kind\":\"fee_on_transfer\""), "{}", json);
+    }
+}
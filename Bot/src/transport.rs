@@ -1,414 +1,1193 @@
 // ============================================================================
-//  TRANSPORT v10.0 — HFT RPC Pool + IPC Öncelikli Bağlantı Yönetimi
+//  TRANSPORT v11.0 — Paylaşımlı Bağlantı Yöneticisi + IPC Öncelikli Transport
 //
 //  Özellikler:
-//  ✓ IPC (Unix Domain Socket / Named Pipe) öncelikli bağlantı
-//  ✓ IPC çökerse Round-Robin WSS fallback (3 endpoint)
-//  ✓ Arka plan sağlık kontrolü (2s geride kalan node geçici olarak devre dışı)
-//  ✓ Zero-copy provider referansları
-//  ✓ Lock-free okuma (parking_lot::RwLock)
+//  ✓ IPC (Unix Domain Socket / Named Pipe) öncelikli primary bağlantı
+//  ✓ Tek ConnectionManager: primary + standby WSS + opsiyonel submit endpoint
+//  ✓ Alt sistemlere ucuz provider klonları — ayrı soket açılmaz
+//  ✓ Aynı soket üzerinde referans sayılı abonelikler (blok / pending / log)
+//  ✓ Bağlantı başına merkezi sağlık (2s yoklama, 2+ blok geride → devre dışı)
+//  ✓ Aralıklı yeniden bağlanma — turda tek soket
+//  ✓ Havuz başına okuma endpoint'i (ProviderRegistry) — primary'ye fallback
 // ============================================================================
 
+use alloy::primitives::Address;
 use alloy::providers::{Provider, ProviderBuilder, RootProvider, WsConnect};
 use eyre::Result;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, AtomicBool, Ordering};
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, AtomicUsize, AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::console::eprintln_high;
+use crate::errors::BotResult;
+use crate::executor::LatencyHistogram;
+use crate::types::TransportMode;
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Node Sağlık Durumu
+// Bağlantı Yöneticisi — Paylaşımlı Transport Havuzu
 // ─────────────────────────────────────────────────────────────────────────────
+//
+// Blok aboneliği, pending TX dinleyicisi, swap event dinleyicisi, state sync
+// ve whitelist TX provider'ı buradan alır; hiçbiri kendi soketini açmaz.
+// Abonelikler aynı soket üzerinde çoğullanır ve referans sayılır. Sağlık
+// bağlantı başına tek yerde tutulur; kopan bağlantılar tek tek ve aralıklı
+// kurulur — bir ağ kesintisinde tüm soketler aynı anda el sıkışmaya girmez.
+
+/// İki yeniden bağlanma arasındaki en kısa süre
+const RECONNECT_SPACING: Duration = Duration::from_secs(3);
+/// Sağlık yoklama aralığı
+const HEALTH_INTERVAL: Duration = Duration::from_secs(2);
+/// En yüksek bloğun bu kadar gerisindeki okuma bağlantısı devre dışı kalır
+const MAX_LAG_BLOCKS: u64 = 2;
+
+/// Bağlantının görevi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionRole {
+    /// Abonelikler + okumalar (IPC tanımlıysa IPC, değilse RPC_WSS_URL)
+    Primary,
+    /// Primary düşerse devralan okuma bağlantıları (yedek / ek WSS)
+    Standby,
+    /// TX gönderimi (PRIVATE_RPC_URL) — tanımsız / düşükse primary
+    Submit,
+}
+
+impl ConnectionRole {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Standby => "standby",
+            Self::Submit => "submit",
+        }
+    }
+
+    /// Blok gecikmesi karşılaştırmasına katılır mı (private submit
+    /// endpoint'leri head takibi için güvenilir değildir)
+    fn tracks_head(self) -> bool {
+        !matches!(self, Self::Submit)
+    }
+}
+
+/// Bağlantının gerçek transport'u — URL şemasından, şemasız yol IPC soketidir
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportKind {
+    Ipc,
+    Ws,
+    Http,
+}
+
+impl TransportKind {
+    pub fn of(url: &str) -> Self {
+        if url.starts_with("ws://") || url.starts_with("wss://") {
+            Self::Ws
+        } else if url.starts_with("http://") || url.starts_with("https://") {
+            Self::Http
+        } else {
+            Self::Ipc
+        }
+    }
+
+    /// Stats / gecikme karşılaştırma etiketi
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ipc => "IPC",
+            Self::Ws => "WSS",
+            Self::Http => "HTTP",
+        }
+    }
+}
+
+/// Konfigürasyondan bağlantı planı. IPC tanımlı ve TRANSPORT_MODE=ipc|auto
+/// ise primary IPC olur ve ana WSS ilk standby'a düşer (soket yoksa / el
+/// sıkışma başarısızsa okumalar oradan sürer); ws / http modunda IPC yolu
+/// yok sayılır. Yedek + ek WSS'ler standby, private RPC submit.
+pub fn plan_endpoints(
+    mode: TransportMode,
+    ipc_path: Option<&str>,
+    wss_url: &str,
+    backup: Option<&str>,
+    extra: &[String],
+    submit: Option<&str>,
+) -> Vec<(ConnectionRole, String)> {
+    let mut plan = Vec::with_capacity(3 + extra.len());
+    let ipc_path = ipc_path.filter(|_| matches!(mode, TransportMode::Ipc | TransportMode::Auto));
+    if let Some(ipc) = ipc_path {
+        plan.push((ConnectionRole::Primary, ipc.to_string()));
+        plan.push((ConnectionRole::Standby, wss_url.to_string()));
+    } else {
+        plan.push((ConnectionRole::Primary, wss_url.to_string()));
+    }
+    plan.extend(
+        backup
+            .into_iter()
+            .chain(extra.iter().map(String::as_str))
+            .map(|url| (ConnectionRole::Standby, url.to_string())),
+    );
+    if let Some(url) = submit {
+        plan.push((ConnectionRole::Submit, url.to_string()));
+    }
+    plan
+}
+
+/// Transport kurucusu — testlerde mock transport ile değiştirilir
+pub trait Connector: Send + Sync + 'static {
+    type Provider: Clone + Send + Sync + 'static;
+
+    /// URL'ye / IPC yoluna bağlan
+    fn connect(&self, url: &str) -> impl Future<Output = Result<Self::Provider>> + Send;
+
+    /// Sağlık yoklaması — güncel blok numarası
+    fn probe(&self, provider: &Self::Provider) -> impl Future<Output = Result<u64>> + Send;
+}
+
+/// Gerçek transport'lar: ws(s):// → WebSocket, http(s):// → HTTP, diğerleri IPC yolu
+pub struct RpcConnector;
+
+impl Connector for RpcConnector {
+    type Provider = RootProvider;
+
+    async fn connect(&self, url: &str) -> Result<RootProvider> {
+        match TransportKind::of(url) {
+            TransportKind::Ws => try_connect_ws(url).await,
+            TransportKind::Http => {
+                let http_url: reqwest::Url = url
+                    .parse()
+                    .map_err(|e| eyre::eyre!("URL parse error: {}", e))?;
+                Ok(ProviderBuilder::default().connect_http(http_url))
+            }
+            TransportKind::Ipc => try_connect_ipc(url).await,
+        }
+    }
 
-/// Tek bir RPC node'unun durumu
-struct NodeState {
-    /// Provider instance (bağlantı kurulmuşsa) — v22.0: RwLock ile cache
-    provider: RwLock<Option<RootProvider>>,
-    /// WebSocket URL'i
+    async fn probe(&self, provider: &RootProvider) -> Result<u64> {
+        provider
+            .get_block_number()
+            .await
+            .map_err(|e| eyre::eyre!("eth_blockNumber failed: {}", e))
+    }
+}
+
+/// Yönetilen tek bağlantı — provider, sağlık ve abonelik sayaçları
+pub struct ManagedConnection<P> {
+    role: ConnectionRole,
+    /// Ham URL / IPC yolu (yeniden bağlanma için)
     url: String,
-    /// Node sağlıklı mı? (atomik — lock-free okuma)
+    /// Maskelenmiş URL (banner / log)
+    label: String,
+    provider: RwLock<Option<P>>,
+    /// Her kurulumda artar — eski soketten alınmış handle'ı ayırt eder
+    generation: AtomicU64,
+    /// Bağlantı sağlıklı mı? (atomik — lock-free okuma)
     healthy: AtomicBool,
-    /// Son bilinen blok numarası
-    last_block: AtomicUsize,
+    /// Yoklama / yeniden bağlanma hatası + alt sistemlerin bildirdiği hatalar
+    failures: AtomicU64,
+    /// Başlangıç sonrası yeniden kurulum sayısı
+    reconnects: AtomicU64,
+    /// Bu soket üzerindeki canlı abonelik kiralamaları
+    subscribers: AtomicUsize,
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// RPC Pool — IPC Öncelikli, Round-Robin WSS Fallback
-// ─────────────────────────────────────────────────────────────────────────────
+impl<P> ManagedConnection<P> {
+    fn new(role: ConnectionRole, url: String) -> Self {
+        let label = if url.contains("://") {
+            crate::env_loader::redact_url(&url)
+        } else {
+            url.clone()
+        };
+        Self {
+            role,
+            url,
+            label,
+            provider: RwLock::new(None),
+            generation: AtomicU64::new(0),
+            healthy: AtomicBool::new(false),
+            failures: AtomicU64::new(0),
+            reconnects: AtomicU64::new(0),
+            subscribers: AtomicUsize::new(0),
+        }
+    }
 
-/// HFT-grade RPC bağlantı havuzu.
-///
-/// Öncelik sırası:
-///   1. Yerel IPC soketi (sub-0.1ms gecikme)
-///   2. WSS endpoint'leri (Round-Robin, sağlık kontrolü ile)
-///
-/// Sağlık kontrolü:
-///   - Arka plan task her 2 saniyede tüm node'ları yoklar
-///   - `eth_blockNumber` ile güncel blok sorgulanır
-///   - En yüksek blok sayısına göre 2+ blok geride kalan node devre dışı bırakılır
-pub struct RpcPool {
-    /// IPC provider (varsa — en düşük gecikme)
-    ipc_provider: RwLock<Option<RootProvider>>,
-    /// IPC sağlıklı mı?
-    ipc_healthy: AtomicBool,
-    /// IPC yolu (reconnect için)
-    ipc_path: Option<String>,
-    /// WSS node listesi
-    ws_nodes: Vec<Arc<NodeState>>,
-    /// Round-Robin sayacı (atomik)
-    rr_counter: AtomicUsize,
-    /// Pool aktif mi?
-    active: AtomicBool,
-}
-
-impl RpcPool {
-    /// Yeni RPC Pool oluştur.
-    ///
-    /// # Argümanlar
-    /// - `ipc_path`: Opsiyonel IPC soket yolu
-    /// - `ws_urls`: WebSocket URL listesi (en az 1)
-    pub fn new(ipc_path: Option<String>, ws_urls: &[String]) -> Self {
-        let ws_nodes: Vec<Arc<NodeState>> = ws_urls
-            .iter()
-            .map(|url| {
-                Arc::new(NodeState {
-                    provider: RwLock::new(None),
-                    url: url.clone(),
-                    healthy: AtomicBool::new(false),
-                    last_block: AtomicUsize::new(0),
-                })
-            })
-            .collect();
+    /// Yeni provider'ı yerleştir — nesil artar, bağlantı sağlıklı sayılır
+    fn install(&self, provider: P) {
+        let mut slot = self.provider.write();
+        *slot = Some(provider);
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.healthy.store(true, Ordering::Release);
+    }
 
-        Self {
-            ipc_provider: RwLock::new(None),
-            ipc_healthy: AtomicBool::new(false),
-            ipc_path,
-            ws_nodes,
-            rr_counter: AtomicUsize::new(0),
-            active: AtomicBool::new(true),
+    /// Hata say, düşük işaretle — sağlıklıdan düşüğe geçişte true
+    fn mark_down(&self) -> bool {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        self.healthy.swap(false, Ordering::AcqRel)
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Acquire)
+    }
+
+    pub fn subscribers(&self) -> usize {
+        self.subscribers.load(Ordering::Acquire)
+    }
+}
+
+impl<P: Clone> ManagedConnection<P> {
+    /// Provider klonu + nesli (aynı kilit altında okunur)
+    fn current(&self) -> Option<(P, u64)> {
+        let slot = self.provider.read();
+        slot.as_ref()
+            .map(|p| (p.clone(), self.generation.load(Ordering::Acquire)))
+    }
+}
+
+/// Paylaşımlı bağlantıdan alınmış ucuz provider klonu. `Deref` ile `&P`
+/// bekleyen mevcut imzalar değişmeden çalışır (`handle.provider()`).
+#[derive(Clone)]
+pub struct ProviderHandle<P> {
+    provider: P,
+    conn: Arc<ManagedConnection<P>>,
+    generation: u64,
+}
+
+impl<P> ProviderHandle<P> {
+    /// Alttaki provider
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Handle'ın geldiği bağlantının rolü (fallback sonrası farklı olabilir)
+    pub fn role(&self) -> ConnectionRole {
+        self.conn.role
+    }
+
+    /// Handle'ın gerçekte kullandığı transport (IPC primary düşükse WSS standby)
+    pub fn transport(&self) -> TransportKind {
+        TransportKind::of(&self.conn.url)
+    }
+
+    /// Handle hâlâ bağlantının güncel soketini mi gösteriyor
+    fn is_current(&self) -> bool {
+        self.conn.generation.load(Ordering::Acquire) == self.generation
+    }
+
+    /// Çağıranın gördüğü transport hatasını merkezi sağlığa işle. Yeniden
+    /// kurulmuş bağlantıya eski soketin hatası yansıtılmaz.
+    pub fn report_failure(&self) {
+        if self.is_current() && self.conn.mark_down() {
+            eprintln_high!(
+                "  ⚠️  {} {} reported failing by subscriber — health check will verify",
+                self.conn.role.label(),
+                self.conn.label,
+            );
         }
     }
+}
+
+impl<P> std::ops::Deref for ProviderHandle<P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        &self.provider
+    }
+}
+
+/// Referans sayılı abonelik kiralaması. Düştüğünde bağlantının abone sayısı
+/// azalır; soket, üzerinde abonelik kalmasa da yönetici tarafından açık tutulur.
+pub struct SubscriptionLease<P> {
+    handle: ProviderHandle<P>,
+}
+
+impl<P> std::ops::Deref for SubscriptionLease<P> {
+    type Target = ProviderHandle<P>;
+
+    fn deref(&self) -> &ProviderHandle<P> {
+        &self.handle
+    }
+}
 
-    /// Tüm bağlantıları başlat (IPC + WSS).
-    /// Döngü dışında bir kez çağrılır — allocation burada yapılır.
-    pub async fn connect_all(&mut self) -> Result<()> {
-        // 1. IPC bağlantısı (varsa)
-        if let Some(ref ipc_path) = self.ipc_path {
-            match self.try_connect_ipc(ipc_path).await {
+impl<P> Drop for SubscriptionLease<P> {
+    fn drop(&mut self) {
+        self.handle.conn.subscribers.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Yeniden bağlanma sıralayıcısı: turda en fazla bir bağlantı, iki kurulum
+/// arasında en az `spacing`; düşük bağlantılar sırayla (biri diğerini aç bırakmaz).
+pub struct ReconnectStagger {
+    spacing: Duration,
+    last: Option<Instant>,
+    cursor: usize,
+}
+
+impl ReconnectStagger {
+    pub fn new(spacing: Duration) -> Self {
+        Self { spacing, last: None, cursor: 0 }
+    }
+
+    /// `down` (artan indeks) içinden şimdi yeniden kurulacak bağlantı
+    pub fn pick(&mut self, now: Instant, down: &[usize]) -> Option<usize> {
+        let first = *down.first()?;
+        if self
+            .last
+            .is_some_and(|last| now.saturating_duration_since(last) < self.spacing)
+        {
+            return None;
+        }
+        let idx = down.iter().copied().find(|&i| i >= self.cursor).unwrap_or(first);
+        self.cursor = idx + 1;
+        self.last = Some(now);
+        Some(idx)
+    }
+}
+
+/// Küçük transport havuzunun sahibi: primary, opsiyonel standby'lar ve
+/// opsiyonel submit endpoint'i.
+pub struct ConnectionManager<C: Connector> {
+    connector: C,
+    connections: Vec<Arc<ManagedConnection<C::Provider>>>,
+    stagger: Mutex<ReconnectStagger>,
+}
+
+/// Yönetici kaynaklı yeniden kurulum — LOG_FORMAT=json iken `reconnect`
+/// olayı (kurulum anında, bekleme yok); yazıldıysa true
+fn emit_reconnect(attempt: u64, reason: &str) -> bool {
+    crate::log_event::json(|| {
+        crate::log_event::LogEvent::Reconnect(crate::log_event::Reconnect {
+            attempt: u32::try_from(attempt).unwrap_or(u32::MAX),
+            delay_ms: 0,
+            reason: reason.to_string(),
+        })
+    })
+}
+
+/// Üretimdeki yönetici
+pub type RpcConnections = ConnectionManager<RpcConnector>;
+
+impl<C: Connector> ConnectionManager<C> {
+    /// Plandaki bağlantıları sırayla kur. Kurulamayan bağlantı düşük kalır ve
+    /// sağlık turunda aralıklı yeniden denenir; hiçbir okuma bağlantısı
+    /// kurulamazsa hata.
+    pub async fn connect(connector: C, plan: Vec<(ConnectionRole, String)>) -> Result<Self> {
+        let mut connections = Vec::with_capacity(plan.len());
+        for (role, url) in plan {
+            let conn = ManagedConnection::new(role, url);
+            match connector.connect(&conn.url).await {
                 Ok(provider) => {
-                    *self.ipc_provider.write() = Some(provider);
-                    self.ipc_healthy.store(true, Ordering::Release);
-                    eprintln!("  ✅ IPC connection established: {}", ipc_path);
+                    conn.install(provider);
+                    eprintln_high!("  ✅ {} connection established: {}", role.label(), conn.label);
                 }
                 Err(e) => {
-                    eprintln!("  ⚠️  IPC connection failed (WSS fallback active): {}", e);
+                    conn.failures.fetch_add(1, Ordering::Relaxed);
+                    eprintln_high!("  ⚠️  {} connection failed: {} — {}", role.label(), conn.label, e);
                 }
             }
+            connections.push(Arc::new(conn));
         }
 
-        // 2. WSS bağlantıları — paralel değil sıralı (alloy WsConnect thread-safe değil)
-        for node in &self.ws_nodes {
-            match Self::try_connect_ws(&node.url).await {
-                Ok(provider) => {
-                    node.healthy.store(true, Ordering::Release);
-                    eprintln!("  ✅ WSS connection established: {}", &node.url[..node.url.len().min(40)]);
+        if !connections.iter().any(|c| c.role.tracks_head() && c.is_healthy()) {
+            return Err(eyre::eyre!("Could not connect to any RPC endpoint!"));
+        }
 
-                    // v22.0: Provider'ı cache'e al — get_provider her seferinde
-                    // yeni bağlantı açmak yerine cache'den klonlar
-                    *node.provider.write() = Some(provider);
-                }
-                Err(e) => {
-                    eprintln!("  ⚠️  WSS connection failed: {} — {}", &node.url[..node.url.len().min(40)], e);
+        Ok(Self {
+            connector,
+            connections,
+            stagger: Mutex::new(ReconnectStagger::new(RECONNECT_SPACING)),
+        })
+    }
+
+    /// Rol için sağlıklı bağlantıdan handle. Primary düşmüşse standby'lar,
+    /// submit tanımsız / düşükse primary sırası izlenir.
+    pub fn acquire(&self, role: ConnectionRole) -> Result<ProviderHandle<C::Provider>> {
+        use ConnectionRole::*;
+        let order: &[ConnectionRole] = match role {
+            Primary | Standby => &[Primary, Standby],
+            Submit => &[Submit, Primary, Standby],
+        };
+        for &wanted in order {
+            for conn in self.connections.iter().filter(|c| c.role == wanted && c.is_healthy()) {
+                if let Some((provider, generation)) = conn.current() {
+                    return Ok(ProviderHandle { provider, conn: Arc::clone(conn), generation });
                 }
             }
         }
+        Err(eyre::eyre!(
+            "No healthy {} connection — waiting for health check",
+            role.label()
+        ))
+    }
 
-        // En az bir bağlantı var mı?
-        let has_ipc = self.ipc_healthy.load(Ordering::Acquire);
-        let has_ws = self.ws_nodes.iter().any(|n| n.healthy.load(Ordering::Acquire));
+    /// `acquire` + abonelik sayacı. Kiralama yaşadıkça bağlantının abone
+    /// sayısında görünür.
+    pub fn subscribe(&self, role: ConnectionRole) -> Result<SubscriptionLease<C::Provider>> {
+        let handle = self.acquire(role)?;
+        handle.conn.subscribers.fetch_add(1, Ordering::AcqRel);
+        Ok(SubscriptionLease { handle })
+    }
 
-        if !has_ipc && !has_ws {
-            return Err(eyre::eyre!("Could not connect to any RPC endpoint!"));
+    /// Tek sağlık turu: tüm bağlantıları yokla, geride kalan okuma
+    /// bağlantısını devre dışı bırak, kopanlardan en fazla birini yeniden kur.
+    pub async fn health_tick(&self, now: Instant) {
+        let mut probes = Vec::with_capacity(self.connections.len());
+        let mut broken = Vec::new();
+        for (idx, conn) in self.connections.iter().enumerate() {
+            match conn.current() {
+                Some((provider, _)) => probes.push((idx, self.connector.probe(&provider).await)),
+                None => broken.push(idx),
+            }
         }
 
-        Ok(())
-    }
+        let max_head = probes
+            .iter()
+            .filter(|(idx, _)| self.connections[*idx].role.tracks_head())
+            .filter_map(|(_, r)| r.as_ref().ok().copied())
+            .max()
+            .unwrap_or(0);
 
-    /// En düşük gecikmeli sağlıklı provider'ı döndür.
-    /// Öncelik: IPC > Round-Robin WSS
-    pub async fn get_provider(&self) -> Result<RootProvider> {
-        // 1. IPC sağlıklıysa onu kullan
-        if self.ipc_healthy.load(Ordering::Acquire) {
-            let guard = self.ipc_provider.read();
-            if let Some(ref provider) = *guard {
-                return Ok(provider.clone());
+        for (idx, result) in probes {
+            let conn = &self.connections[idx];
+            match result {
+                Ok(block) => {
+                    let lag = if conn.role.tracks_head() { max_head.saturating_sub(block) } else { 0 };
+                    if lag >= MAX_LAG_BLOCKS {
+                        if conn.healthy.swap(false, Ordering::AcqRel) {
+                            eprintln_high!(
+                                "  ⚠️  {} {} fell behind (block #{} vs max #{}) — temporarily disabled",
+                                conn.role.label(), conn.label, block, max_head,
+                            );
+                        }
+                    } else if !conn.healthy.swap(true, Ordering::AcqRel) {
+                        eprintln_high!(
+                            "  🔄 {} {} healthy again (block #{})",
+                            conn.role.label(), conn.label, block,
+                        );
+                    }
+                }
+                Err(e) => {
+                    if conn.mark_down() {
+                        eprintln_high!(
+                            "  ⚠️  {} {} health check failed: {}",
+                            conn.role.label(), conn.label, e,
+                        );
+                    }
+                    broken.push(idx);
+                }
             }
         }
+        broken.sort_unstable();
 
-        // 2. WSS Round-Robin
-        let node_count = self.ws_nodes.len();
-        if node_count == 0 {
-            return Err(eyre::eyre!("WSS node list empty!"));
+        let pick = self.stagger.lock().pick(now, &broken);
+        if let Some(idx) = pick {
+            self.reconnect(idx).await;
         }
+    }
 
-        // Sağlıklı node bul (en fazla node_count deneme)
-        for _ in 0..node_count {
-            let idx = self.rr_counter.fetch_add(1, Ordering::Relaxed) % node_count;
-            let node = &self.ws_nodes[idx];
-
-            if !node.healthy.load(Ordering::Acquire) {
-                continue;
+    async fn reconnect(&self, idx: usize) {
+        let conn = &self.connections[idx];
+        match self.connector.connect(&conn.url).await {
+            Ok(provider) => {
+                conn.install(provider);
+                let attempt = conn.reconnects.fetch_add(1, Ordering::Relaxed) + 1;
+                let reason = format!("{} {} reconnected", conn.role.label(), conn.label);
+                if !emit_reconnect(attempt, &reason) {
+                    eprintln_high!("  🔄 {}", reason);
+                }
             }
+            Err(e) => {
+                conn.failures.fetch_add(1, Ordering::Relaxed);
+                let attempt = conn.reconnects.load(Ordering::Relaxed) + 1;
+                let reason = format!("{} {} reconnect failed: {}", conn.role.label(), conn.label, e);
+                if !emit_reconnect(attempt, &reason) {
+                    eprintln_high!("  ⚠️  {}", reason);
+                }
+            }
+        }
+    }
 
-            // v22.0: Cache'den klonla — her seferinde yeni bağlantı açmak yerine
-            // mevcut provider'ı kullan. Bağlantı kopmuşsa yeniden bağlan.
-            {
-                let guard = node.provider.read();
-                if let Some(ref provider) = *guard {
-                    return Ok(provider.clone());
+    /// Arka plan sağlık turu (2s) — `cancel` ile run_bot çıkışında durur
+    pub fn spawn_health_checker(self: &Arc<Self>, cancel: CancellationToken) {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEALTH_INTERVAL);
+            interval.tick().await; // ilk anlık tur atlanır — bağlantılar yeni kuruldu
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = interval.tick() => manager.health_tick(Instant::now()).await,
                 }
             }
+        });
+    }
 
-            // Cache boş — yeni bağlantı aç ve cache'e al
-            match Self::try_connect_ws(&node.url).await {
-                Ok(provider) => {
-                    let cloned = provider.clone();
-                    *node.provider.write() = Some(provider);
-                    return Ok(cloned);
+    /// Sağlıklı bağlantı sayısı
+    pub fn healthy_count(&self) -> usize {
+        self.connections.iter().filter(|c| c.is_healthy()).count()
+    }
+
+    /// Transport bilgi stringi (banner / dashboard): bağlantı başına sağlık,
+    /// abone sayısı, yeniden kurulum ve hata sayaçları
+    pub fn transport_info(&self) -> String {
+        self.connections
+            .iter()
+            .map(|c| {
+                let mut line = format!(
+                    "{} {}",
+                    c.role.label(),
+                    if c.is_healthy() { "✅" } else { "❌" }
+                );
+                let subs = c.subscribers();
+                if subs > 0 {
+                    line.push_str(&format!(" {} sub", subs));
                 }
-                Err(e) => {
-                    node.healthy.store(false, Ordering::Release);
-                    eprintln!("  ⚠️  WSS node {} connection lost: {}", idx, e);
-                    continue;
+                let reconnects = c.reconnects.load(Ordering::Relaxed);
+                if reconnects > 0 {
+                    line.push_str(&format!(" ↻{}", reconnects));
                 }
-            }
+                let failures = c.failures.load(Ordering::Relaxed);
+                if failures > 0 {
+                    line.push_str(&format!(" ✗{}", failures));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+// ── İç Bağlantı Yardımcıları ────────────────────────────────────────────────
+
+/// IPC sokete bağlan (Unix: /path/to/geth.ipc, Windows: \\.\pipe\geth.ipc)
+/// ve eth_blockNumber ile el sıkışmayı doğrula. Soket yoksa veya el sıkışma
+/// başarısızsa hata — ConnectionManager okumaları WSS standby'a devreder ve
+/// IPC'yi sağlık turunda yeniden dener.
+async fn try_connect_ipc(ipc_path: &str) -> Result<RootProvider> {
+    if !std::path::Path::new(ipc_path).exists() {
+        return Err(eyre::eyre!("IPC socket not found: {}", ipc_path));
+    }
+    let provider = ProviderBuilder::default()
+        .connect_ipc(alloy::providers::IpcConnect::new(ipc_path.to_string()))
+        .await
+        .map_err(|e| eyre::eyre!("IPC connection error ({}): {}", ipc_path, e))?;
+    let block = provider
+        .get_block_number()
+        .await
+        .map_err(|e| eyre::eyre!("IPC handshake failed ({}): {}", ipc_path, e))?;
+    eprintln_high!("  ✅ IPC connection verified: {} (block #{})", ipc_path, block);
+    Ok(provider)
+}
+
+/// WebSocket'e bağlan
+async fn try_connect_ws(url: &str) -> Result<RootProvider> {
+    let ws = WsConnect::new(url);
+    let provider = ProviderBuilder::default()
+        .connect_ws(ws)
+        .await
+        .map_err(|e| eyre::eyre!("WSS connection error ({}): {}", &url[..url.len().min(40)], e))?;
+
+    Ok(provider)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Havuz Başına Okuma Endpoint'leri (POOL_A_RPC_URL / POOL_B_RPC_URL)
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Sağlayıcılar havuz tipine göre farklı hızlıdır (ör. biri UniV3 eth_call'da,
+// diğeri Slipstream'de hızlı). Tanımlı havuz için sync_pool_state,
+// sync_tick_bitmap ve optimistic_refresh_pool o havuzun endpoint'ini kullanır;
+// blok aboneliği ve yürütme primary'de kalır. Havuza özel endpoint'teki hata
+// o çağrı için primary'ye düşer.
+
+/// Tek okuma endpoint'i — gecikme ve sağlık sayaçları etiket başına
+pub struct ReadEndpoint<P> {
+    /// "primary", "pool_a", "pool_b" — istatistik etiketi
+    pub label: &'static str,
+    /// Maskelenmiş URL (banner / log)
+    pub url: String,
+    provider: P,
+    /// Başarılı + başarısız çağrıların süresi
+    pub latency: LatencyHistogram,
+    failures: AtomicU64,
+    fallbacks: AtomicU64,
+}
+
+impl<P> ReadEndpoint<P> {
+    fn new(label: &'static str, url: String, provider: P) -> Self {
+        Self {
+            label,
+            url,
+            provider,
+            latency: LatencyHistogram::default(),
+            failures: AtomicU64::new(0),
+            fallbacks: AtomicU64::new(0),
         }
+    }
 
-        Err(eyre::eyre!("All RPC nodes disabled — waiting for health check"))
+    /// Hata dönen çağrı sayısı
+    pub fn failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
     }
 
-    /// Arka plan sağlık kontrolü task'ı başlat.
-    ///
-    /// Her 2 saniyede:
-    ///   1. Tüm node'lardan `eth_blockNumber` sorgular
-    ///   2. En yüksek blok sayısını belirler
-    ///   3. 2+ blok gerisinde kalan node'ları devre dışı bırakır
-    ///   4. Devre dışı node'ları yeniden bağlanmaya çalışır
-    pub fn spawn_health_checker(self: &Arc<Self>) {
-        let pool = Arc::clone(self);
+    /// Bu endpoint başarısız olduğu için primary'ye düşen çağrı sayısı
+    pub fn fallbacks(&self) -> u64 {
+        self.fallbacks.load(Ordering::Relaxed)
+    }
 
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(2));
+    /// Çağrıyı süresini ve sonucunu kaydederek çalıştır
+    async fn timed<T, Fut>(&self, fut: Fut) -> BotResult<T>
+    where
+        Fut: std::future::Future<Output = BotResult<T>>,
+    {
+        let started = std::time::Instant::now();
+        let result = fut.await;
+        self.latency.record(started.elapsed().as_micros() as u64);
+        if result.is_err() {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+}
 
-            loop {
-                interval.tick().await;
+/// Havuz adresi → okuma endpoint'i. Atanmamış havuzlar primary'yi kullanır.
+/// Klonlar endpoint'leri (ve sayaçlarını) paylaşır.
+pub struct ProviderRegistry<P> {
+    primary: Arc<ReadEndpoint<P>>,
+    dedicated: Vec<Arc<ReadEndpoint<P>>>,
+    routes: HashMap<Address, usize>,
+}
 
-                if !pool.active.load(Ordering::Acquire) {
-                    break;
-                }
+impl<P> Clone for ProviderRegistry<P> {
+    fn clone(&self) -> Self {
+        Self {
+            primary: Arc::clone(&self.primary),
+            dedicated: self.dedicated.clone(),
+            routes: self.routes.clone(),
+        }
+    }
+}
 
-                // IPC sağlık kontrolü
-                if let Some(ref _ipc_path) = pool.ipc_path {
-                    let ipc_ok = {
-                        let provider_clone = {
-                            let guard = pool.ipc_provider.read();
-                            guard.clone()
-                        };
-                        if let Some(ref provider) = provider_clone {
-                            provider.get_block_number().await.is_ok()
-                        } else {
-                            false
-                        }
-                    };
-                    pool.ipc_healthy.store(ipc_ok, Ordering::Release);
-                }
+impl<P: Clone> ProviderRegistry<P> {
+    /// Yalnızca primary — her havuz primary'den okunur
+    pub fn new(primary: P) -> Self {
+        Self {
+            primary: Arc::new(ReadEndpoint::new("primary", "primary".into(), primary)),
+            dedicated: Vec::new(),
+            routes: HashMap::new(),
+        }
+    }
 
-                // WSS node'ları kontrol et — blok numaralarını topla
-                let mut block_numbers: Vec<(usize, u64)> = Vec::with_capacity(pool.ws_nodes.len());
-
-                for (idx, node) in pool.ws_nodes.iter().enumerate() {
-                    // v22.0: İlk olarak cache'deki provider'ı dene
-                    let cached_provider = {
-                        let guard = node.provider.read();
-                        guard.clone()
-                    };
-                    
-                    let cached_ok = if let Some(ref provider) = cached_provider {
-                        match provider.get_block_number().await {
-                            Ok(bn) => {
-                                node.last_block.store(bn as usize, Ordering::Release);
-                                block_numbers.push((idx, bn));
-                                if !node.healthy.load(Ordering::Acquire) {
-                                    node.healthy.store(true, Ordering::Release);
-                                    eprintln!(
-                                        "  🔄 WSS node #{} healthy again (blok #{})",
-                                        idx, bn
-                                    );
-                                }
-                                true
-                            }
-                            Err(_) => false
-                        }
-                    } else {
-                        false
-                    };
-
-                    if !cached_ok {
-                        // Cache'deki provider başarısız — yeniden bağlan
-                        match Self::try_connect_ws(&node.url).await {
-                            Ok(provider) => {
-                                match provider.get_block_number().await {
-                                    Ok(bn) => {
-                                        node.last_block.store(bn as usize, Ordering::Release);
-                                        block_numbers.push((idx, bn));
-                                        *node.provider.write() = Some(provider);
-                                        node.healthy.store(true, Ordering::Release);
-                                        eprintln!(
-                                            "  🔄 WSS node #{} reconnected (blok #{})",
-                                            idx, bn
-                                        );
-                                    }
-                                    Err(_) => {
-                                        *node.provider.write() = None;
-                                        node.healthy.store(false, Ordering::Release);
-                                    }
-                                }
-                            }
-                            Err(_) => {
-                                *node.provider.write() = None;
-                                node.healthy.store(false, Ordering::Release);
-                            }
-                        }
-                    }
-                }
+    /// `pool` okumalarını ayrı bir endpoint'e yönlendir
+    pub fn with_pool_endpoint(mut self, label: &'static str, url: String, provider: P, pool: Address) -> Self {
+        self.routes.insert(pool, self.dedicated.len());
+        self.dedicated.push(Arc::new(ReadEndpoint::new(label, url, provider)));
+        self
+    }
 
-                // En yüksek blok numarasını bul
-                if let Some(max_block) = block_numbers.iter().map(|(_, b)| *b).max() {
-                    // 2+ blok geride kalan node'ları devre dışı bırak
-                    for (idx, bn) in &block_numbers {
-                        if max_block.saturating_sub(*bn) >= 2 {
-                            let node = &pool.ws_nodes[*idx];
-                            if node.healthy.load(Ordering::Acquire) {
-                                node.healthy.store(false, Ordering::Release);
-                                eprintln!(
-                                    "  ⚠️  WSS node #{} fell behind (blok #{} vs max #{}) — temporarily disabled",
-                                    idx, bn, max_block
-                                );
-                            }
-                        }
-                    }
-                }
+    /// Primary provider (Multicall3 toplu sync, aboneliksiz okumalar)
+    pub fn primary(&self) -> &P {
+        &self.primary.provider
+    }
+
+    /// Primary endpoint'in sayaçları
+    pub fn primary_endpoint(&self) -> &ReadEndpoint<P> {
+        &self.primary
+    }
+
+    /// Havuzun özel endpoint'i (yoksa None → primary)
+    pub fn dedicated_for(&self, pool: Address) -> Option<&ReadEndpoint<P>> {
+        self.routes.get(&pool).map(|&i| &*self.dedicated[i])
+    }
+
+    /// Havuzu okuyan endpoint
+    pub fn endpoint_for(&self, pool: Address) -> &ReadEndpoint<P> {
+        self.dedicated_for(pool).unwrap_or(&*self.primary)
+    }
+
+    /// Primary + özel endpoint'ler (istatistik kutusu)
+    pub fn endpoints(&self) -> impl Iterator<Item = &ReadEndpoint<P>> {
+        std::iter::once(&*self.primary).chain(self.dedicated.iter().map(|e| &**e))
+    }
+
+    /// `pool` için okuma: özel endpoint varsa önce o, hata verirse aynı
+    /// çağrı primary'de tekrarlanır. Atanmamış havuz doğrudan primary.
+    pub async fn call<T, F, Fut>(&self, pool: Address, op: F) -> BotResult<T>
+    where
+        F: Fn(P) -> Fut,
+        Fut: std::future::Future<Output = BotResult<T>>,
+    {
+        let Some(endpoint) = self.dedicated_for(pool) else {
+            return self.primary.timed(op(self.primary.provider.clone())).await;
+        };
+        match endpoint.timed(op(endpoint.provider.clone())).await {
+            Ok(value) => Ok(value),
+            Err(e) => {
+                endpoint.fallbacks.fetch_add(1, Ordering::Relaxed);
+                eprintln_high!(
+                    "     ⚡ [{}] {} read failed ({}) — falling back to primary",
+                    endpoint.label,
+                    pool,
+                    e.kind(),
+                );
+                self.primary.timed(op(self.primary.provider.clone())).await
             }
-        });
+        }
     }
 
-    /// Pool'u kapat (health checker durdurulur)
-    #[allow(dead_code)]
-    pub fn shutdown(&self) {
-        self.active.store(false, Ordering::Release);
+    /// RPC census'a `site` çağrı yeri etiketiyle sayılan okumalar
+    pub fn traced(&self, site: &'static str) -> TracedRegistry<'_, P> {
+        TracedRegistry { registry: self, site }
     }
+}
 
-    // ── İç Bağlantı Yardımcıları ────────────────────────────────────────────
+/// `ProviderRegistry::call`'u (metot, havuz, çağrı yeri) anahtarı ve bekleme
+/// süresiyle sayan ince sarmalayıcı — fallback dahil tek çağrı sayılır
+pub struct TracedRegistry<'a, P> {
+    registry: &'a ProviderRegistry<P>,
+    site: &'static str,
+}
 
-    /// IPC sokete bağlan — gerçek Unix domain socket veya WSS/HTTP fallback.
-    ///
-    /// v25.0: Alloy IPC desteği ile gerçek IPC bağlantısı denenir.
-    /// IPC başarısız olursa sırasıyla WSS ve HTTP fallback uygulanır.
-    async fn try_connect_ipc(&self, ipc_path: &str) -> Result<RootProvider> {
-        // 1. Gerçek IPC soketi dene (Unix: /path/to/geth.ipc, Windows: \\.\pipe\geth.ipc)
-        if std::path::Path::new(ipc_path).exists() {
-            eprintln!(
-                "  ℹ️  IPC socket file found: {} — trying direct IPC connection",
-                ipc_path
-            );
-            let ipc_connect = alloy::providers::IpcConnect::new(ipc_path.to_string());
-            match ProviderBuilder::default()
-                .connect_ipc(ipc_connect)
-                .await
-            {
+impl<P> Clone for TracedRegistry<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for TracedRegistry<'_, P> {}
+
+impl<P: Clone> TracedRegistry<'_, P> {
+    pub async fn call<T, F, Fut>(self, method: &'static str, pool: Address, op: F) -> BotResult<T>
+    where
+        F: Fn(P) -> Fut,
+        Fut: std::future::Future<Output = BotResult<T>>,
+    {
+        let key = crate::rpc_census::CallKey::new(method, pool, self.site);
+        crate::rpc_census::traced(key, self.registry.call(pool, op)).await
+    }
+}
+
+impl ProviderRegistry<RootProvider> {
+    /// POOL_A_RPC_URL / POOL_B_RPC_URL → pools[0] / pools[1] (pool_a / pool_b
+    /// kontrat sırası). Bağlanamayan endpoint atlanır, havuz primary'de kalır.
+    pub async fn connect(
+        primary: RootProvider,
+        pools: &[crate::types::PoolConfig],
+        pool_a_url: Option<&str>,
+        pool_b_url: Option<&str>,
+    ) -> Self {
+        let mut registry = Self::new(primary);
+        for (label, url, idx) in [("pool_a", pool_a_url, 0), ("pool_b", pool_b_url, 1)] {
+            let (Some(url), Some(pool)) = (url, pools.get(idx)) else {
+                continue;
+            };
+            match connect_read_endpoint(url).await {
                 Ok(provider) => {
-                    let _block = provider.get_block_number().await
-                        .map_err(|e| eyre::eyre!("IPC health check failed: {}", e))?;
-                    eprintln!("  ✅ Real IPC connection successful: {}", ipc_path);
-                    return Ok(provider);
-                }
-                Err(e) => {
-                    eprintln!("  ⚠️  Real IPC connection failed: {} — trying WSS fallback", e);
+                    registry = registry.with_pool_endpoint(
+                        label,
+                        crate::env_loader::redact_url(url),
+                        provider,
+                        pool.address,
+                    );
                 }
+                Err(e) => eprintln_high!(
+                    "  ⚠️  {} endpoint unavailable ({}) — {} stays on primary",
+                    label, e, pool.name,
+                ),
             }
         }
+        registry
+    }
+}
+
+/// HTTP(S) veya WS(S) okuma provider'ı — bağlantı eth_blockNumber ile doğrulanır
+async fn connect_read_endpoint(url: &str) -> Result<RootProvider> {
+    let provider = if url.starts_with("ws://") || url.starts_with("wss://") {
+        try_connect_ws(url).await?
+    } else {
+        let http_url: reqwest::Url = url
+            .parse()
+            .map_err(|e| eyre::eyre!("URL parse error: {}", e))?;
+        ProviderBuilder::default().connect_http(http_url)
+    };
+    provider
+        .get_block_number()
+        .await
+        .map_err(|e| eyre::eyre!("health check failed: {}", e))?;
+    Ok(provider)
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+    use alloy::primitives::{address, U64};
+    use alloy::transports::mock::Asserter;
+
+    const POOL_A: Address = address!("d0b53D9277642d899DF5C87A3966A349A798F224");
+    const POOL_B: Address = address!("b2cc224c1c9feE385f8ad6a55b4d94E92359DC59");
+    const POOL_C: Address = address!("3333333333333333333333333333333333333333");
+
+    fn mocked() -> (RootProvider, Asserter) {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        (provider, asserter)
+    }
+
+    async fn block_number(provider: RootProvider) -> BotResult<u64> {
+        provider
+            .get_block_number()
+            .await
+            .map_err(|e| crate::errors::BotError::from_transport("eth_blockNumber", e))
+    }
+
+    #[tokio::test]
+    async fn test_registry_routes_pools_to_their_endpoint() {
+        let (primary, primary_mock) = mocked();
+        let (pool_a, pool_a_mock) = mocked();
+        let registry = ProviderRegistry::new(primary)
+            .with_pool_endpoint("pool_a", "https://a.example".into(), pool_a, POOL_A);
+
+        assert_eq!(registry.endpoint_for(POOL_A).label, "pool_a");
+        assert_eq!(registry.endpoint_for(POOL_B).label, "primary");
+        assert!(registry.dedicated_for(POOL_C).is_none());
+
+        pool_a_mock.push_success(&U64::from(100));
+        primary_mock.push_success(&U64::from(200));
+        assert_eq!(registry.call(POOL_A, block_number).await.unwrap(), 100);
+        assert_eq!(registry.call(POOL_B, block_number).await.unwrap(), 200);
+
+        let labels: Vec<_> = registry.endpoints().map(|e| e.label).collect();
+        assert_eq!(labels, ["primary", "pool_a"]);
+        let a = registry.endpoint_for(POOL_A);
+        assert_eq!((a.latency.count(), a.failures(), a.fallbacks()), (1, 0, 0));
+        assert_eq!(registry.primary_endpoint().latency.count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_registry_falls_back_to_primary_on_endpoint_failure() {
+        let (primary, primary_mock) = mocked();
+        let (pool_b, pool_b_mock) = mocked();
+        let registry = ProviderRegistry::new(primary)
+            .with_pool_endpoint("pool_b", "wss://b.example".into(), pool_b, POOL_B);
+        // Klon aynı sayaçları paylaşır
+        let clone = registry.clone();
+
+        pool_b_mock.push_failure_msg("slipstream read timed out");
+        primary_mock.push_success(&U64::from(42));
+        assert_eq!(clone.call(POOL_B, block_number).await.unwrap(), 42);
+
+        let b = registry.endpoint_for(POOL_B);
+        assert_eq!((b.latency.count(), b.failures(), b.fallbacks()), (1, 1, 1));
+        assert_eq!(registry.primary_endpoint().latency.count(), 1);
+        assert_eq!(registry.primary_endpoint().failures(), 0);
 
-        // 2. WSS fallback — ipc_path bir URL olabilir (wss://...)
-        if ipc_path.starts_with("wss://") || ipc_path.starts_with("ws://") {
-            eprintln!("  ℹ️  IPC path in URL format — connecting as WSS: {}", ipc_path);
-            let ws = WsConnect::new(ipc_path);
-            let provider = ProviderBuilder::default()
-                .connect_ws(ws)
-                .await
-                .map_err(|e| eyre::eyre!("WSS connection error ({}): {}", ipc_path, e))?;
-            let _block = provider.get_block_number().await
-                .map_err(|e| eyre::eyre!("WSS health check failed: {}", e))?;
-            return Ok(provider);
+        // Primary de başarısızsa hata çağırana döner (ikinci deneme yok)
+        pool_b_mock.push_failure_msg("down");
+        primary_mock.push_failure_msg("down too");
+        let err = registry.call(POOL_B, block_number).await.unwrap_err();
+        assert_eq!(err.kind(), "rpc_transport");
+        assert_eq!((b.failures(), b.fallbacks()), (2, 2));
+        assert_eq!(registry.primary_endpoint().failures(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_traced_call_counts_into_opportunity_scope() {
+        let (primary, primary_mock) = mocked();
+        let (pool_a, pool_a_mock) = mocked();
+        let registry = ProviderRegistry::new(primary)
+            .with_pool_endpoint("pool_a", "https://a.example".into(), pool_a, POOL_A);
+        let scope = crate::rpc_census::OpportunityScope::new();
+
+        // Fallback'li okuma da fırsatta tek çağrı sayılır
+        pool_a_mock.push_failure_msg("timeout");
+        primary_mock.push_success(&U64::from(7));
+        primary_mock.push_success(&U64::from(8));
+        let traced = registry.traced("bitmap_extension");
+        assert_eq!(scope.within(traced.call("ticks_range", POOL_A, block_number)).await.unwrap(), 7);
+        assert_eq!(scope.within(traced.call("ticks_range", POOL_B, block_number)).await.unwrap(), 8);
+        // Kapsam dışındaki çağrı fırsata yazılmaz
+        primary_mock.push_success(&U64::from(9));
+        assert_eq!(traced.call("ticks_range", POOL_B, block_number).await.unwrap(), 9);
+
+        let report = scope.finish();
+        assert_eq!((report.total.calls, report.total.cached), (2, 0));
+        assert!(report.redundant.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod connection_tests {
+    use super::*;
+    use alloy::primitives::U64;
+    use alloy::transports::mock::Asserter;
+    use std::collections::HashSet;
+
+    /// URL başına mock transport; aynı URL'ye her bağlanış aynı yanıt
+    /// kuyruğunu paylaşan yeni bir provider döndürür.
+    #[derive(Default)]
+    struct MockConnector {
+        nodes: HashMap<String, Asserter>,
+        refused: Mutex<HashSet<String>>,
+        dials: Mutex<Vec<String>>,
+    }
+
+    impl MockConnector {
+        fn with_nodes(urls: &[&str]) -> Self {
+            Self {
+                nodes: urls.iter().map(|u| (u.to_string(), Asserter::new())).collect(),
+                ..Default::default()
+            }
+        }
+    }
+
+    impl Connector for MockConnector {
+        type Provider = RootProvider;
+
+        async fn connect(&self, url: &str) -> Result<RootProvider> {
+            self.dials.lock().push(url.to_string());
+            if self.refused.lock().contains(url) {
+                return Err(eyre::eyre!("connection refused"));
+            }
+            Ok(ProviderBuilder::default().connect_mocked_client(self.nodes[url].clone()))
         }
 
-        // 3. HTTP fallback — ipc_path bir HTTP URL olabilir
-        if ipc_path.starts_with("http://") || ipc_path.starts_with("https://") {
-            eprintln!("  ℹ️  IPC path in HTTP format — HTTP provider unusable (PubSub required)");
+        async fn probe(&self, provider: &RootProvider) -> Result<u64> {
+            RpcConnector.probe(provider).await
         }
+    }
 
-        // 4. Son çare: IPC dosyası bulunamadı, local WSS dene
-        eprintln!(
-            "  ⚠️  IPC socket '{}' not found — trying local WSS fallback (ws://127.0.0.1:8546)",
-            ipc_path
+    fn head(manager: &ConnectionManager<MockConnector>, url: &str, block: u64) {
+        manager.connector.nodes[url].push_success(&U64::from(block));
+    }
+
+    fn fail(manager: &ConnectionManager<MockConnector>, url: &str) {
+        manager.connector.nodes[url].push_failure_msg("socket closed");
+    }
+
+    fn dials(manager: &ConnectionManager<MockConnector>) -> usize {
+        manager.connector.dials.lock().len()
+    }
+
+    fn plan(entries: &[(ConnectionRole, &str)]) -> Vec<(ConnectionRole, String)> {
+        entries.iter().map(|(r, u)| (*r, u.to_string())).collect()
+    }
+
+    #[test]
+    fn test_plan_endpoints_roles() {
+        let extra = vec!["wss://extra".to_string()];
+        let plan = plan_endpoints(
+            TransportMode::Auto,
+            None,
+            "wss://main",
+            Some("wss://backup"),
+            &extra,
+            Some("https://private"),
         );
-        let ws_url = "ws://127.0.0.1:8546";
-        let ws = WsConnect::new(ws_url);
-        let provider = ProviderBuilder::default()
-            .connect_ws(ws)
-            .await
-            .map_err(|e| eyre::eyre!("Local WSS fallback error: {}", e))?;
-        let _block = provider.get_block_number().await
-            .map_err(|e| eyre::eyre!("Local WSS health check failed: {}", e))?;
+        let roles: Vec<_> = plan.iter().map(|(r, u)| (r.label(), u.as_str())).collect();
+        assert_eq!(
+            roles,
+            [
+                ("primary", "wss://main"),
+                ("standby", "wss://backup"),
+                ("standby", "wss://extra"),
+                ("submit", "https://private"),
+            ]
+        );
+
+        // IPC primary olur, ana WSS ilk standby'a düşer
+        for mode in [TransportMode::Ipc, TransportMode::Auto] {
+            let plan = plan_endpoints(mode, Some("/tmp/geth.ipc"), "wss://main", None, &[], None);
+            assert_eq!(
+                plan,
+                [
+                    (ConnectionRole::Primary, "/tmp/geth.ipc".to_string()),
+                    (ConnectionRole::Standby, "wss://main".to_string()),
+                ]
+            );
+        }
 
-        Ok(provider)
+        // ws / http modunda IPC yolu yok sayılır
+        for mode in [TransportMode::Ws, TransportMode::Http] {
+            let plan = plan_endpoints(mode, Some("/tmp/geth.ipc"), "wss://main", None, &[], None);
+            assert_eq!(plan, [(ConnectionRole::Primary, "wss://main".to_string())]);
+        }
+    }
+
+    #[test]
+    fn test_transport_kind_from_url() {
+        assert_eq!(TransportKind::of("/tmp/geth.ipc"), TransportKind::Ipc);
+        assert_eq!(TransportKind::of(r"\\.\pipe\geth.ipc"), TransportKind::Ipc);
+        assert_eq!(TransportKind::of("wss://base.example.io/KEY"), TransportKind::Ws);
+        assert_eq!(TransportKind::of("https://base.example.io/KEY"), TransportKind::Http);
+        assert_eq!(TransportKind::Ipc.label(), "IPC");
     }
 
-    /// WebSocket'e bağlan
-    async fn try_connect_ws(url: &str) -> Result<RootProvider> {
-        let ws = WsConnect::new(url);
-        let provider = ProviderBuilder::default()
-            .connect_ws(ws)
+    #[tokio::test]
+    async fn test_missing_ipc_socket_fails_without_silent_fallback() {
+        let err = RpcConnector
+            .connect("/nonexistent/arbitraj_botu_test.ipc")
             .await
-            .map_err(|e| eyre::eyre!("WSS connection error ({}): {}", &url[..url.len().min(40)], e))?;
+            .expect_err("missing socket must not connect");
+        assert!(err.to_string().contains("IPC socket not found"), "{}", err);
+    }
 
-        Ok(provider)
+    #[tokio::test]
+    async fn test_ipc_primary_down_hands_reads_to_wss_standby() {
+        let connector = MockConnector::with_nodes(&["/tmp/geth.ipc", "wss://main"]);
+        connector.refused.lock().insert("/tmp/geth.ipc".into());
+        let manager = ConnectionManager::connect(
+            connector,
+            plan_endpoints(TransportMode::Ipc, Some("/tmp/geth.ipc"), "wss://main", None, &[], None),
+        )
+        .await
+        .unwrap();
+        let handle = manager.acquire(ConnectionRole::Primary).unwrap();
+        assert_eq!(handle.role(), ConnectionRole::Standby);
+        assert_eq!(handle.transport().label(), "WSS");
+
+        // IPC geri gelince primary yeniden IPC
+        manager.connector.refused.lock().clear();
+        head(&manager, "wss://main", 10);
+        manager.health_tick(Instant::now()).await;
+        assert_eq!(manager.acquire(ConnectionRole::Primary).unwrap().transport(), TransportKind::Ipc);
     }
 
-    /// Aktif sağlıklı node sayısı
-    pub fn healthy_node_count(&self) -> usize {
-        let ipc = if self.ipc_healthy.load(Ordering::Acquire) { 1 } else { 0 };
-        let ws = self.ws_nodes.iter()
-            .filter(|n| n.healthy.load(Ordering::Acquire))
-            .count();
-        ipc + ws
+    #[tokio::test]
+    async fn test_handles_share_one_connection() {
+        let connector = MockConnector::with_nodes(&["ws://primary", "ws://standby"]);
+        let manager = ConnectionManager::connect(
+            connector,
+            plan(&[(ConnectionRole::Primary, "ws://primary"), (ConnectionRole::Standby, "ws://standby")]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(dials(&manager), 2);
+
+        let a = manager.acquire(ConnectionRole::Primary).unwrap();
+        let b = manager.acquire(ConnectionRole::Primary).unwrap();
+        assert!(Arc::ptr_eq(&a.conn, &b.conn));
+        assert_eq!(a.role(), ConnectionRole::Primary);
+        // Submit tanımsız → primary
+        assert_eq!(manager.acquire(ConnectionRole::Submit).unwrap().role(), ConnectionRole::Primary);
+        // Handle'lar yeni soket açmaz
+        assert_eq!(dials(&manager), 2);
+
+        // Deref uyumluluk katmanı: handle doğrudan provider gibi kullanılır
+        head(&manager, "ws://primary", 77);
+        assert_eq!(b.get_block_number().await.unwrap(), 77);
+
+        // Abonelikler aynı soket üzerinde referans sayılır
+        let blocks = manager.subscribe(ConnectionRole::Primary).unwrap();
+        let pending = manager.subscribe(ConnectionRole::Primary).unwrap();
+        assert_eq!(a.conn.subscribers(), 2);
+        drop(pending);
+        assert_eq!(a.conn.subscribers(), 1);
+        assert_eq!(manager.transport_info(), "primary ✅ 1 sub | standby ✅");
+        drop(blocks);
+        assert_eq!(a.conn.subscribers(), 0);
+        assert_eq!(dials(&manager), 2);
     }
 
-    /// Transport bilgi stringi (banner için)
-    pub fn transport_info(&self) -> String {
-        let ipc_status = if self.ipc_healthy.load(Ordering::Acquire) {
-            "IPC ✅"
-        } else if self.ipc_path.is_some() {
-            "IPC ❌"
-        } else {
-            "IPC yok"
-        };
+    #[tokio::test]
+    async fn test_health_bookkeeping_and_fallback() {
+        let connector = MockConnector::with_nodes(&["ws://primary", "ws://standby"]);
+        let manager = ConnectionManager::connect(
+            connector,
+            plan(&[(ConnectionRole::Primary, "ws://primary"), (ConnectionRole::Standby, "ws://standby")]),
+        )
+        .await
+        .unwrap();
+        let handle = manager.acquire(ConnectionRole::Primary).unwrap();
+        let primary = Arc::clone(&handle.conn);
+        let t0 = Instant::now();
+
+        // Yoklama hatası + başarısız yeniden bağlanma → primary düşük, okumalar standby'da
+        manager.connector.refused.lock().insert("ws://primary".into());
+        fail(&manager, "ws://primary");
+        head(&manager, "ws://standby", 100);
+        manager.health_tick(t0).await;
+        assert!(!primary.is_healthy());
+        assert_eq!(primary.failures.load(Ordering::Relaxed), 2);
+        assert_eq!(manager.acquire(ConnectionRole::Primary).unwrap().role(), ConnectionRole::Standby);
+        assert_eq!(manager.healthy_count(), 1);
+
+        // Güncel sokete ait handle hatası sayılır
+        handle.report_failure();
+        assert_eq!(primary.failures.load(Ordering::Relaxed), 3);
 
-        let ws_healthy = self.ws_nodes.iter()
-            .filter(|n| n.healthy.load(Ordering::Acquire))
-            .count();
-        let ws_total = self.ws_nodes.len();
+        // Aralık dolunca primary yeniden kurulur
+        manager.connector.refused.lock().clear();
+        fail(&manager, "ws://primary");
+        head(&manager, "ws://standby", 101);
+        manager.health_tick(t0 + RECONNECT_SPACING).await;
+        assert!(primary.is_healthy());
+        assert_eq!(primary.reconnects.load(Ordering::Relaxed), 1);
+        assert_eq!(primary.failures.load(Ordering::Relaxed), 4);
+        assert_eq!(manager.acquire(ConnectionRole::Primary).unwrap().role(), ConnectionRole::Primary);
+
+        // Eski soketten alınmış handle'ın hatası yeni bağlantıyı düşürmez
+        assert!(!handle.is_current());
+        handle.report_failure();
+        assert!(primary.is_healthy());
+        assert_eq!(primary.failures.load(Ordering::Relaxed), 4);
+        assert_eq!(manager.transport_info(), "primary ✅ ↻1 ✗4 | standby ✅");
+
+        // Geride kalan bağlantı devre dışı kalır ama yeniden kurulmaz
+        let before = dials(&manager);
+        head(&manager, "ws://primary", 105);
+        head(&manager, "ws://standby", 102);
+        manager.health_tick(t0 + RECONNECT_SPACING * 2).await;
+        let standby = manager.connections[1].as_ref();
+        assert!(!standby.is_healthy());
+        assert_eq!(standby.failures.load(Ordering::Relaxed), 0);
+        assert_eq!(dials(&manager), before);
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_are_staggered() {
+        let urls = ["ws://primary", "ws://standby-1", "ws://standby-2"];
+        let connector = MockConnector::with_nodes(&urls);
+        connector.refused.lock().extend(urls[1..].iter().map(|u| u.to_string()));
+        let manager = ConnectionManager::connect(
+            connector,
+            plan(&[
+                (ConnectionRole::Primary, urls[0]),
+                (ConnectionRole::Standby, urls[1]),
+                (ConnectionRole::Standby, urls[2]),
+            ]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(manager.healthy_count(), 1);
+        manager.connector.refused.lock().clear();
+        let t0 = Instant::now();
+
+        // İki bağlantı düşük — turda yalnızca biri kurulur
+        head(&manager, urls[0], 100);
+        manager.health_tick(t0).await;
+        assert_eq!(manager.connector.dials.lock()[3..], ["ws://standby-1".to_string()]);
+        assert_eq!(manager.healthy_count(), 2);
+
+        // Aralık dolmadan ikinci soket açılmaz
+        head(&manager, urls[0], 100);
+        head(&manager, urls[1], 100);
+        manager.health_tick(t0 + Duration::from_secs(1)).await;
+        assert_eq!(dials(&manager), 4);
+
+        head(&manager, urls[0], 101);
+        head(&manager, urls[1], 101);
+        manager.health_tick(t0 + RECONNECT_SPACING).await;
+        assert_eq!(manager.connector.dials.lock()[4..], ["ws://standby-2".to_string()]);
+        assert_eq!(manager.healthy_count(), 3);
+    }
 
-        format!("{} | WSS {}/{} active", ipc_status, ws_healthy, ws_total)
+    #[test]
+    fn test_stagger_round_robins_down_connections() {
+        let t0 = Instant::now();
+        let mut stagger = ReconnectStagger::new(Duration::from_secs(3));
+        assert_eq!(stagger.pick(t0, &[]), None);
+        assert_eq!(stagger.pick(t0, &[0, 2]), Some(0));
+        assert_eq!(stagger.pick(t0 + Duration::from_secs(2), &[0, 2]), None);
+        // Tekrar düşen primary, sıradaki bağlantıyı aç bırakmaz
+        assert_eq!(stagger.pick(t0 + Duration::from_secs(3), &[0, 2]), Some(2));
+        assert_eq!(stagger.pick(t0 + Duration::from_secs(6), &[0, 2]), Some(0));
     }
 }
@@ -16,53 +16,25 @@
 //  ✓ Multi-transport yapılandırması (IPC > WSS > HTTP)
 // ============================================================================
 
-use alloy::primitives::{address, Address, U256};
+use alloy::primitives::{Address, TxHash, I256, U256};
 use eyre::Result;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::time::Instant;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use arc_swap::ArcSwap;
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Token Whitelist — Güvenli Token Listesi (Base Network)
-// ─────────────────────────────────────────────────────────────────────────────
-//
-// v10.1: Sadece yüksek likiditeli, kanıtlanmış tokenlar beyaz listede.
-// Egzotik veya yeni çıkan tokenlar ile işlem yapılması engellenir.
-// Bu, rüg-pull, düşük likidite kayası ve token manipulasyonu risklerini
-// ortadan kaldırır.
-//
-// Desteklenen tokenlar:
-//   • WETH  — Wrapped Ether (Base canonical)
-//   • USDC  — USD Coin (Circle, bridged)
-//   • USDT  — Tether USD (bridged)
-//   • DAI   — Dai Stablecoin (bridged)
-//   • cbETH — Coinbase Wrapped Staked ETH
-// ─────────────────────────────────────────────────────────────────────────────
-
-/// Base Network üzerindeki güvenli token adresleri (donanım kodlu whitelist)
-pub fn token_whitelist() -> HashSet<Address> {
-    HashSet::from([
-        // WETH — Base canonical
-        address!("4200000000000000000000000000000000000006"),
-        // USDC — Circle (bridged)
-        address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
-        // USDbC — USD Base Coin (bridged via Base bridge)
-        address!("d9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA"),
-        // DAI — Dai Stablecoin (bridged)
-        address!("50c5725949A6F0c72E6C4a641F24049A917DB0Cb"),
-        // cbETH — Coinbase Wrapped Staked ETH
-        address!("2Ae3F1Ec7F1F5012CFEab0185bfc7aa3cf0DEc22"),
-        // cbBTC — Coinbase Wrapped BTC (8 decimals)
-        address!("cbB7C0000aB88B473b1f5aFd9ef808440eed33Bf"),
-        // AERO — Aerodrome Finance token
-        address!("940181a94A35A4569E4529A3CDfB74e38FD98631"),
-        // DEGEN — Degen token (18 decimals)
-        address!("4ed4E862860beD51a9570b96d89aF5E1B0Efefed"),
-    ])
-}
-
+use crate::approval_audit::{ApprovalAuditMode, RequiredSpenders};
+use crate::chain_profile::{ChainProfile, ChainSettings, PriorityFeeStrategy};
+use crate::contract_state::PauseSelector;
+use crate::env_loader::{redact_secret, redact_url, EnvLoader};
+use crate::errors::{BotError, BotResult};
+use crate::direction_gate::DirectionGateMode;
+use crate::gas_calibration::GasBufferMode;
+use crate::key_manager::{KeyManager, KeySource};
+use crate::log_event::LogFormat;
+use crate::submit_pacing::JitterRange;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
 
 /// uni_direction=0 → zeroForOne=true  → token0 input
 /// uni_direction=1 → zeroForOne=false → token1 input
@@ -89,22 +61,90 @@ pub fn is_weth_input(uni_direction: u8, token0_is_weth: bool) -> bool {
 /// - Hedef WETH ise: amount_weth * 10^18
 /// - Hedef quote token ise: amount_weth * eth_price_quote * 10^quote_decimals
 ///
-/// Bu fonksiyon calldata'ya yazılacak amount değerini üretir.
+/// Bu fonksiyon calldata'ya yazılacak amount değerini üretir; dönüşüm
+/// kontrollüdür (bkz. token_amount_to_wei).
 pub fn weth_amount_to_input_wei(
     optimal_amount_weth: f64,
     is_weth_input: bool,
     eth_price_quote: f64,
     quote_token_decimals: u8,
-) -> U256 {
+) -> BotResult<U256> {
     if is_weth_input {
         // Input WETH → 18 decimals
-        U256::from(safe_f64_to_u128(optimal_amount_weth * 1e18))
+        weth_to_wei(optimal_amount_weth)
     } else {
         // Input quote token → quote_token_decimals
         // WETH cinsinden miktar × ETH/Quote fiyatı × 10^decimals
-        let scale = 10f64.powi(quote_token_decimals as i32);
-        let quote_amount = optimal_amount_weth * eth_price_quote * scale;
-        U256::from(safe_f64_to_u128(quote_amount))
+        token_amount_to_wei(optimal_amount_weth * eth_price_quote, quote_token_decimals)
+    }
+}
+
+/// WETH miktarı → wei (10^18), kontrollü. Bkz. token_amount_to_wei.
+pub fn weth_to_wei(amount_weth: f64) -> BotResult<U256> {
+    token_amount_to_wei(amount_weth, 18)
+}
+
+/// İnsan-okur token miktarı → en küçük birim (× 10^decimals), kontrollü.
+///
+/// safe_f64_to_u128'in aksine sessizce kırpmaz: NaN/∞, negatif ve u128
+/// aralığını aşan değerler hata döner — calldata'ya yazılacak miktar asla
+/// 0'a ya da u128::MAX'a doyurulmaz. Sonuç en yakın wei'ye yuvarlanır;
+/// f64 ~15-16 anlamlı basamak taşır, 2^53 wei (~0.009 WETH) üstünde son
+/// basamaklar kaybolur (göreli hata ≤ f64::EPSILON).
+pub fn token_amount_to_wei(amount: f64, decimals: u8) -> BotResult<U256> {
+    let invalid = |reason: &str| BotError::InvalidAmount { value: amount, reason: reason.to_string() };
+    if !amount.is_finite() {
+        return Err(invalid("not finite"));
+    }
+    if amount < 0.0 {
+        return Err(invalid("negative"));
+    }
+    let scaled = amount * 10f64.powi(decimals as i32);
+    // u128::MAX as f64 == 2^128 — eşitlik de aralık dışıdır
+    if !scaled.is_finite() || scaled >= u128::MAX as f64 {
+        return Err(invalid("exceeds u128 wei range"));
+    }
+    Ok(U256::from(scaled.round() as u128))
+}
+
+/// Yürütülecek işlemin doğrulanmış miktarı: MIN_TRADE_SIZE_WETH tabanının
+/// üstünde, girdi wei'si kontrollü dönüştürülmüş. Yürütme calldata'sı
+/// yalnızca bundan kurulur — toz miktar TX'e ulaşamaz.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeAmount {
+    weth: f64,
+    input_wei: U256,
+}
+
+impl TradeAmount {
+    pub fn new(
+        amount_weth: f64,
+        min_trade_size_weth: f64,
+        is_weth_input: bool,
+        eth_price_quote: f64,
+        quote_token_decimals: u8,
+    ) -> BotResult<Self> {
+        if amount_weth.is_nan() || amount_weth < min_trade_size_weth {
+            return Err(BotError::InvalidAmount {
+                value: amount_weth,
+                reason: format!("below MIN_TRADE_SIZE_WETH ({})", min_trade_size_weth),
+            });
+        }
+        let input_wei =
+            weth_amount_to_input_wei(amount_weth, is_weth_input, eth_price_quote, quote_token_decimals)?;
+        if input_wei.is_zero() {
+            return Err(BotError::InvalidAmount { value: amount_weth, reason: "rounds to zero wei".into() });
+        }
+        Ok(Self { weth: amount_weth, input_wei })
+    }
+
+    pub fn weth(&self) -> f64 {
+        self.weth
+    }
+
+    /// Calldata'ya yazılan girdi token miktarı (wei)
+    pub fn input_wei(&self) -> U256 {
+        self.input_wei
     }
 }
 
@@ -136,6 +176,9 @@ pub enum DexType {
     /// PancakeSwap V3 — slot0 feeProtocol alanı uint32 (Uniswap V3'te uint8)
     PancakeSwapV3,
     Aerodrome,
+    /// Uniswap V2 tarzı x·y=k havuzu (Aerodrome vAMM dahil) — slot0/tick yok,
+    /// durum getReserves() ile okunur
+    UniswapV2,
 }
 
 impl std::fmt::Display for DexType {
@@ -144,6 +187,7 @@ impl std::fmt::Display for DexType {
             DexType::UniswapV3 => write!(f, "Uniswap V3"),
             DexType::PancakeSwapV3 => write!(f, "PancakeSwap V3"),
             DexType::Aerodrome => write!(f, "Aerodrome"),
+            DexType::UniswapV2 => write!(f, "Uniswap V2"),
         }
     }
 }
@@ -185,7 +229,7 @@ impl std::fmt::Display for TransportMode {
 /// Her tick sınırında likidite değişimi net olarak kaydedilir.
 /// liquidityNet > 0 → o tick'e girildiğinde likidite ARTAR
 /// liquidityNet < 0 → o tick'e girildiğinde likidite AZALIR
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct TickInfo {
     /// Toplam brüt likidite (pozisyon açma/kapama için)
@@ -196,6 +240,39 @@ pub struct TickInfo {
     pub liquidity_net: i128,
     /// Bu tick başlatılmış mı? (bitmap'te 1 ise true)
     pub initialized: bool,
+    /// Slipstream: liquidity_net'in gauge'da stake edilmiş payı. Swap için
+    /// aktif likidite yine liquidity_net'tir (staked pay zaten içinde) —
+    /// bu alan yalnızca stakedLiquidity() doğrulaması için. UniV3/PCS: 0.
+    /// Stake gauge üzerinden olur (Mint/Burn event'i yok): tam bitmap
+    /// sync'inde tazelenir.
+    #[serde(default)]
+    pub staked_liquidity_net: i128,
+}
+
+/// Slipstream havuzunun tick'lerle aynı eth_call'da okunan aktif likidite
+/// anlık görüntüsü (slot0.tick, liquidity(), stakedLiquidity(), unstakedFee()).
+///
+/// unstakedFee yalnızca stake edilmemiş LP'lerin fee payının gauge'a giden
+/// kısmıdır: swap yapanın ödediği fee() değişmez, kâr modeline girmez.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakedLiquidity {
+    pub tick: i32,
+    pub liquidity: u128,
+    pub staked_liquidity: u128,
+    /// unstakedFee() — pips (1e6 = %100); okunamadıysa None
+    pub unstaked_fee_pips: Option<u32>,
+    /// Taranan aralıktaki staked net'ler bu görüntüyle tutarlı mı?
+    pub verified: bool,
+}
+
+impl StakedLiquidity {
+    /// Aktif likiditenin stake edilmiş payı (0..1)
+    pub fn staked_share(&self) -> f64 {
+        if self.liquidity == 0 {
+            return 0.0;
+        }
+        self.staked_liquidity as f64 / self.liquidity as f64
+    }
 }
 
 /// Off-chain TickBitmap derinlik haritası
@@ -206,7 +283,7 @@ pub struct TickInfo {
 ///
 /// Bu yapı, "50 ETH satarsam hangi 3 tick'i patlatırım?" sorusuna
 /// mikrosaniye içinde cevap verir.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TickBitmapData {
     /// Bitmap kelime haritası: wordPos → bitmap (256-bit)
     /// Her bit, tick_spacing'e göre belirli bir tick'in başlatılmış olup
@@ -225,6 +302,36 @@ pub struct TickBitmapData {
 
     /// Taranan tick aralığı (current_tick ± range)
     pub scan_range: u32,
+
+    /// Tick detayları eksiksiz okunan kapalı aralık [alt, üst].
+    /// Kenar genişletmeleri birleştirildikçe büyür; None = bilinmiyor
+    /// (eski snapshot veya sentetik bitmap).
+    #[serde(default)]
+    pub scanned_ticks: Option<(i32, i32)>,
+
+    /// Slipstream stakedLiquidity anlık görüntüsü (tam sync; UniV3/PCS: None)
+    #[serde(default)]
+    pub staked: Option<StakedLiquidity>,
+
+    /// Son tam taramanın bloğu — artımlı yenilemeler snapshot_block'u
+    /// ilerletir, tam haritanın yaşı bununla sınırlanır
+    #[serde(default)]
+    pub last_full_sync_block: u64,
+}
+
+/// Bitmap tarama aralığının kenarı
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanEdge {
+    Lower,
+    Upper,
+}
+
+/// NR optimumunun taranan bitmap kenarına dayandığı bacak
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanBoundaryHit {
+    /// Çift içindeki havuz indeksi (0 veya 1)
+    pub pool_idx: usize,
+    pub edge: ScanEdge,
 }
 
 impl TickBitmapData {
@@ -236,7 +343,92 @@ impl TickBitmapData {
             snapshot_block: 0,
             sync_duration_us: 0,
             scan_range: 0,
+            scanned_ticks: None,
+            staked: None,
+            last_full_sync_block: 0,
+        }
+    }
+
+    /// Swap sonrası tick taranan aralığın kenarına `margin` tick'ten yakın
+    /// (veya aralığın dışında) mı? Aralık bilinmiyorsa None.
+    pub fn scan_edge_near(&self, tick: i32, margin: i32) -> Option<ScanEdge> {
+        let (lo, hi) = self.scanned_ticks?;
+        if tick >= hi.saturating_sub(margin) {
+            Some(ScanEdge::Upper)
+        } else if tick <= lo.saturating_add(margin) {
+            Some(ScanEdge::Lower)
+        } else {
+            None
+        }
+    }
+
+    /// Başka bir okumayı (ör. kenar genişletmesi) bu bitmap'e birleştir.
+    ///
+    /// Çakışan word ve tick girdilerinde snapshot_block'u yeni olan kazanır
+    /// (eşitlikte gelen veri). Taranan aralık birleşimdir; snapshot_block
+    /// en eski parçanın bloğu olur — tazelik kontrolü ihtiyatlı kalır.
+    /// Staked anlık görüntüsü tam sync'e aittir, korunur.
+    pub fn merge(&mut self, other: TickBitmapData) {
+        let other_wins = other.snapshot_block >= self.snapshot_block;
+        for (word_pos, word) in other.words {
+            if other_wins || !self.words.contains_key(&word_pos) {
+                self.words.insert(word_pos, word);
+            }
+        }
+        for (tick, info) in other.ticks {
+            if other_wins || !self.ticks.contains_key(&tick) {
+                self.ticks.insert(tick, info);
+            }
+        }
+        self.scanned_ticks = match (self.scanned_ticks, other.scanned_ticks) {
+            (Some((a_lo, a_hi)), Some((b_lo, b_hi))) => Some((a_lo.min(b_lo), a_hi.max(b_hi))),
+            (a, b) => a.or(b),
+        };
+        self.snapshot_block = self.snapshot_block.min(other.snapshot_block);
+        self.sync_duration_us = self.sync_duration_us.saturating_add(other.sync_duration_us);
+        self.scan_range = self.scan_range.max(other.scan_range);
+    }
+
+    /// Taranan aralıktaki staked net'leri `snapshot` ile doğrula.
+    ///
+    /// Anlık görüntünün tick'inden iki yöne tick tick yürünür; her geçişte
+    /// toplam ve staked likidite birlikte güncellenir. Stake edilen pozisyonlar
+    /// toplamın alt kümesidir: her aralıkta 0 ≤ staked ≤ toplam olmalıdır.
+    /// Aykırılık yanlış decode edilmiş (ya da çift sayılmış) staked net'e işaret eder.
+    pub fn verify_staked(&self, snapshot: &StakedLiquidity) -> Result<(), String> {
+        let (Ok(liquidity), Ok(staked)) = (
+            i128::try_from(snapshot.liquidity),
+            i128::try_from(snapshot.staked_liquidity),
+        ) else {
+            return Err("liquidity exceeds i128".into());
+        };
+        let check = |tick: i32, total: i128, staked: i128| {
+            if (0..=total).contains(&staked) {
+                Ok(())
+            } else {
+                Err(format!("staked {} outside 0..={} past tick {}", staked, total, tick))
+            }
+        };
+        check(snapshot.tick, liquidity, staked)?;
+
+        let mut ticks: Vec<(&i32, &TickInfo)> = self.ticks.iter().collect();
+        ticks.sort_unstable_by_key(|(t, _)| **t);
+
+        // Yukarı: tick > current geçilince net eklenir
+        let (mut total_up, mut staked_up) = (liquidity, staked);
+        for (&tick, info) in ticks.iter().copied().filter(|(t, _)| **t > snapshot.tick) {
+            total_up = total_up.saturating_add(info.liquidity_net);
+            staked_up = staked_up.saturating_add(info.staked_liquidity_net);
+            check(tick, total_up, staked_up)?;
+        }
+        // Aşağı: tick ≤ current geçilince net çıkarılır
+        let (mut total_down, mut staked_down) = (liquidity, staked);
+        for (&tick, info) in ticks.iter().rev().copied().filter(|(t, _)| **t <= snapshot.tick) {
+            total_down = total_down.saturating_sub(info.liquidity_net);
+            staked_down = staked_down.saturating_sub(info.staked_liquidity_net);
+            check(tick, total_down, staked_down)?;
         }
+        Ok(())
     }
 
     /// Toplam başlatılmış tick sayısı
@@ -244,6 +436,16 @@ impl TickBitmapData {
         self.ticks.len()
     }
 
+    /// Snapshot'ın yaşı (blok)
+    pub fn age_blocks(&self, current_block: u64) -> u64 {
+        current_block.saturating_sub(self.snapshot_block)
+    }
+
+    /// Bitmap hâlâ kullanılabilir mi? (yaş ≤ TICK_BITMAP_MAX_AGE_BLOCKS)
+    pub fn is_fresh(&self, current_block: u64, max_age_blocks: u64) -> bool {
+        self.age_blocks(current_block) <= max_age_blocks
+    }
+
     /// Mint event'inden in-memory güncelleme.
     /// tickLower sınırında liquidityNet += amount, tickUpper'da -= amount.
     pub fn update_from_mint(&mut self, tick_lower: i32, tick_upper: i32, amount: u128, tick_spacing: i32) {
@@ -264,6 +466,7 @@ impl TickBitmapData {
             liquidity_gross: 0,
             liquidity_net: 0,
             initialized: false,
+            staked_liquidity_net: 0,
         });
 
         let abs_delta = liquidity_net_delta.unsigned_abs();
@@ -290,8 +493,9 @@ impl TickBitmapData {
     }
 
     /// Bitmap'te belirli bir tick'in bit'ini XOR ile flip et.
+    /// Sıkıştırma floor division'dır (state_sync::tick_to_word_pos ile aynı).
     fn flip_bitmap_bit(&mut self, tick: i32, tick_spacing: i32) {
-        let compressed = tick / tick_spacing;
+        let compressed = tick.div_euclid(tick_spacing);
         let word_pos = (compressed >> 8) as i16;
         let bit_pos = (compressed & 0xFF) as u8;
         let mask = U256::from(1u64) << bit_pos;
@@ -304,12 +508,70 @@ impl TickBitmapData {
 // Havuz Yapılandırması
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// DEX'lerde karşılaşılan standart komisyon kademeleri (bps):
+/// %0.01, %0.05, %0.25, %0.30, %1.00
+pub const KNOWN_FEE_TIERS_BPS: [u32; 5] = [1, 5, 25, 30, 100];
+
+/// Kabul edilen en yüksek komisyon (bps) — %100
+pub const MAX_FEE_BPS: u32 = 10_000;
+
+/// Havuz komisyonu — tek kanonik değer (bps); exact motorun beklediği pips
+/// (1e6 bazı) ve f64 yolunun kullandığı oran buradan türetilir. Çağrı
+/// noktalarında elle ×100 / ÷10_000 dönüşümü yapılmaz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FeeTier {
+    bps: u32,
+}
+
+impl FeeTier {
+    /// Doğrulamasız kurulum — sabitler ve zaten doğrulanmış değerler için
+    pub const fn from_bps(bps: u32) -> Self {
+        Self { bps }
+    }
+
+    /// Yükleme anı doğrulaması: 1..=MAX_FEE_BPS dışı reddedilir.
+    /// Aralık içindeki standart dışı değerler kabul edilir; uyarı çağırana
+    /// kalır (`is_known_tier`).
+    pub fn parse_bps(bps: u32) -> std::result::Result<Self, DataQualityIssue> {
+        if (1..=MAX_FEE_BPS).contains(&bps) {
+            Ok(Self { bps })
+        } else {
+            Err(DataQualityIssue {
+                field: "fee_bps",
+                value: bps.to_string(),
+                expected: "1..=10000 bps",
+            })
+        }
+    }
+
+    /// Bilinen DEX kademelerinden biri mi?
+    pub fn is_known_tier(self) -> bool {
+        KNOWN_FEE_TIERS_BPS.contains(&self.bps)
+    }
+
+    /// Baz puan (1e4 bazı) — %0.05 = 5
+    pub const fn bps(self) -> u32 {
+        self.bps
+    }
+
+    /// Exact motorun beklediği pips (1e6 bazı) — %0.05 = 500
+    pub const fn pips(self) -> u32 {
+        self.bps * 100
+    }
+
+    /// f64 yolunun kullandığı oran — %0.05 = 0.0005
+    pub fn fraction(self) -> f64 {
+        self.bps as f64 / 10_000.0
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
     pub address: Address,
     pub name: String,
-    pub fee_bps: u32,
-    pub fee_fraction: f64,
+    /// Yapılandırılan komisyon — okumalar `fee_bps()` / `fee_pips()` /
+    /// `fee_fraction()` üzerinden
+    pub fee: FeeTier,
     pub token0_decimals: u8,
     pub token1_decimals: u8,
     pub dex: DexType,
@@ -323,6 +585,35 @@ pub struct PoolConfig {
     pub base_token_address: Address,
 }
 
+impl PoolConfig {
+    /// Yapılandırılan komisyon (bps)
+    pub fn fee_bps(&self) -> u32 {
+        self.fee.bps()
+    }
+
+    /// Yapılandırılan komisyon (pips, 1e6 bazı)
+    pub fn fee_pips(&self) -> u32 {
+        self.fee.pips()
+    }
+
+    /// Yapılandırılan komisyon (oran)
+    pub fn fee_fraction(&self) -> f64 {
+        self.fee.fraction()
+    }
+
+    /// Hesapta kullanılacak komisyon: canlı on-chain değer varsa o,
+    /// yoksa yapılandırılan kademe
+    pub fn effective_fee(&self, live_fee_bps: Option<u32>) -> FeeTier {
+        live_fee_bps.map_or(self.fee, FeeTier::from_bps)
+    }
+
+    /// Sabit çarpım (V2) havuzu mu? Tick bitmap'i yoktur; state rezervlerden
+    /// tek aralıklı sanal CL state olarak türetilir
+    pub fn is_constant_product(&self) -> bool {
+        self.dex == DexType::UniswapV2
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Havuz Anlık Durumu (RAM'de tutulur)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -337,7 +628,8 @@ pub struct PoolState {
     pub tick: i32,
     /// Anlık likidite (u128)
     pub liquidity: u128,
-    /// Likidite float versiyonu (hızlı hesap için)
+    /// Likidite float versiyonu — KAYIPLI (bkz. liquidity_to_f64); yalnızca
+    /// swap matematiğinin içi ve görüntüleme. Eşikler `liquidity` (u128) ile.
     pub liquidity_f64: f64,
     /// WETH fiyatı quote token cinsinden — ör: 25.5 (cbBTC) veya 2500.0 (USDC)
     pub eth_price_usd: f64,
@@ -347,11 +639,10 @@ pub struct PoolState {
     pub last_update: Instant,
     /// Havuz başlatıldı mı?
     pub is_initialized: bool,
-    /// Havuz bytecode'u (REVM için önbellek)
-    pub bytecode: Option<Vec<u8>>,
     /// Off-chain TickBitmap derinlik haritası
-    /// "50 ETH satarsam hangi tick'leri patlatırım?" sorusunu yanıtlar
-    pub tick_bitmap: Option<TickBitmapData>,
+    /// "50 ETH satarsam hangi tick'leri patlatırım?" sorusunu yanıtlar.
+    /// Kutulu — PoolState her yazımda kopyalanır, satır içi harita onu şişirir.
+    pub tick_bitmap: Option<Box<TickBitmapData>>,
     /// Zincirden okunan canlı fee (basis points, ör: 500 = %0.05)
     /// None ise config'teki statik fee_bps kullanılır
     pub live_fee_bps: Option<u32>,
@@ -359,6 +650,49 @@ pub struct PoolState {
     /// işaretlenir. is_stale=true olan havuzlarla arbitraj YAPILMAZ.
     /// Başarılı sync sonrası otomatik olarak false'a döner.
     pub is_stale: bool,
+    /// Art arda reddedilen (PoolState::validate'ten geçemeyen) sync yazımı
+    pub data_quality_errors: u32,
+    /// Çok sayıda bozuk veri sonrası karantina — geçerli ilk yazımla kalkar
+    pub quarantined: bool,
+    /// Son yazım pending TX kaynaklı iyimser yenilemeyse tetikleyen TX.
+    /// Blok sync veya Swap eventi onaylı veri yazınca None'a döner.
+    pub optimistic_trigger: Option<OptimisticTrigger>,
+    /// Son RPC sync okumasının sabitlendiği blok (abonelik başlığının hash'i).
+    /// None: düğüm bloğu tutmadığı için "latest"e düşüldü ya da henüz sync yok.
+    /// Swap/Mint/Burn eventleri bu alana dokunmaz — sabitli tabanın üstüne işlenir.
+    pub pinned_block: Option<u64>,
+    /// Pending swap calldata'sının bu state üzerinde yerel olarak uygulanmış
+    /// sonucu. Onaylı state'in yerine geçmez — okuyan taraf
+    /// `with_prediction` ile tazelik ve güven penceresini sınar.
+    /// Kutulu — PoolState her yazımda kopyalanır, tahmin çoğu zaman yoktur.
+    pub predicted_state: Option<Box<PredictedState>>,
+    /// Sabit çarpım (V2) havuzu — sqrtPriceX96/likidite getReserves'ten
+    /// türetilmiş sanal değerlerdir, swap bacakları x·y=k ile hesaplanır
+    pub constant_product: bool,
+}
+
+/// Pending swap'ın multi-tick exact swap ile öngörülen sonucu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PredictedState {
+    pub sqrt_price_x96: U256,
+    pub sqrt_price_f64: f64,
+    pub tick: i32,
+    pub liquidity: u128,
+    pub eth_price_usd: f64,
+    /// Tahminin üzerine kurulduğu onaylı state'in bloğu
+    pub base_block: u64,
+    /// Tahmin anı — onaylı state bundan sonra yazıldıysa tahmin eskimiştir
+    pub predicted_at: Instant,
+    pub trigger: OptimisticTrigger,
+}
+
+/// İyimser havuz yenilemesini tetikleyen pending TX (denetim izi)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptimisticTrigger {
+    pub tx_hash: TxHash,
+    pub sender: Address,
+    /// TX'in dinleyiciye ulaştığı an
+    pub observed_at: Instant,
 }
 
 impl Default for PoolState {
@@ -373,19 +707,164 @@ impl Default for PoolState {
             last_block: 0,
             last_update: Instant::now(),
             is_initialized: false,
-            bytecode: None,
             tick_bitmap: None,
             live_fee_bps: None,
             is_stale: false,
+            data_quality_errors: 0,
+            quarantined: false,
+            optimistic_trigger: None,
+            pinned_block: None,
+            predicted_state: None,
+            constant_product: false,
         }
     }
 }
 
+/// PoolState::validate sınırları — dışı, bozuk RPC/parse verisi demektir
+const SQRT_PRICE_F64_RANGE: (f64, f64) = (1e10, 1e40);
+const ETH_PRICE_RANGE: (f64, f64) = (1.0, 1e7);
+/// f64 ↔ U256 temsilleri arasında izin verilen göreli fark
+const REPR_TOLERANCE: f64 = 1e-9;
+/// u128 → f64 yuvarlamasının göreli hata sınırı (2^-53, emniyet payıyla 2^-52)
+const LIQUIDITY_F64_EPSILON: f64 = f64::EPSILON;
+
+/// u128 likiditenin f64 kopyası — doğrudan cast, KAYIPLI: 2^53 (~9e15)
+/// üstünde en yakın temsil edilebilir f64'e yuvarlanır, farklı iki likidite
+/// aynı f64'e düşebilir (Base WETH/USDC havuzu bu sınırın üstünde).
+/// Eşik/karşılaştırma kararları her zaman u128 değer üzerinde verilir.
+pub fn liquidity_to_f64(liquidity: u128) -> f64 {
+    liquidity as f64
+}
+
+/// Doğrulamadan geçemeyen alan ve ham değeri
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataQualityIssue {
+    pub field: &'static str,
+    pub value: String,
+    pub expected: &'static str,
+}
+
+impl std::fmt::Display for DataQualityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={} (expected {})", self.field, self.value, self.expected)
+    }
+}
+
+impl std::error::Error for DataQualityIssue {}
+
 impl PoolState {
     /// Havuz aktif mi? (veriler geçerli mi?)
     /// v10.0: is_stale=true olan havuzlar artık aktif sayılmaz.
+    /// Karantinadaki havuzlar da (bozuk veri) aktif sayılmaz.
     pub fn is_active(&self) -> bool {
-        self.is_initialized && !self.is_stale && self.eth_price_usd > 0.0 && self.liquidity > 0
+        self.is_initialized
+            && !self.is_stale
+            && !self.quarantined
+            && self.eth_price_usd > 0.0
+            && self.liquidity > 0
+    }
+
+    /// Kullanılabilir tahmin: onaylı state'ten sonra üretilmiş, aynı taban
+    /// bloğun üstünde ve zincir başı (`head_block`) tabandan en fazla
+    /// `trust_blocks` ileride
+    pub fn usable_prediction(&self, head_block: u64, trust_blocks: u64) -> Option<PredictedState> {
+        self.predicted_state.as_deref().copied().filter(|p| {
+            p.predicted_at > self.last_update
+                && p.base_block == self.last_block
+                && head_block <= p.base_block.saturating_add(trust_blocks)
+        })
+    }
+
+    /// Kullanılabilir tahmin varsa fiyat/tick/likiditesi tahminden alınmış
+    /// kopya; yoksa aynı Arc (kopya yok)
+    pub fn with_prediction(self: Arc<Self>, head_block: u64, trust_blocks: u64) -> Arc<Self> {
+        let Some(p) = self.usable_prediction(head_block, trust_blocks) else {
+            return self;
+        };
+        let mut next = (*self).clone();
+        next.sqrt_price_x96 = p.sqrt_price_x96;
+        next.sqrt_price_f64 = p.sqrt_price_f64;
+        next.tick = p.tick;
+        next.set_liquidity(p.liquidity);
+        next.eth_price_usd = p.eth_price_usd;
+        Arc::new(next)
+    }
+
+    /// Likiditeyi yaz — u128 kesin değer + kayıplı f64 kopyası birlikte
+    pub fn set_liquidity(&mut self, liquidity: u128) {
+        self.liquidity = liquidity;
+        self.liquidity_f64 = liquidity_to_f64(liquidity);
+    }
+
+    /// f64 likidite (swap matematiği / görüntüleme). Debug derlemede iki
+    /// temsilin beklenen yuvarlama hatasından fazla ayrışması yakalanır —
+    /// f64 kopyayı u128'i güncellemeden değiştiren yol var demektir.
+    pub fn liquidity_approx(&self) -> f64 {
+        debug_assert!(
+            (self.liquidity_f64 - liquidity_to_f64(self.liquidity)).abs()
+                <= liquidity_to_f64(self.liquidity) * LIQUIDITY_F64_EPSILON,
+            "liquidity_f64 {} diverged from liquidity {}",
+            self.liquidity_f64,
+            self.liquidity,
+        );
+        self.liquidity_f64
+    }
+
+    /// Sync yazımından önce merkezi tutarlılık kontrolü.
+    /// NaN/Inf, aralık dışı değerler ve f64 ↔ U256 uyumsuzluğu reddedilir;
+    /// hata, başarısız olan ilk alanı ve ham değerini taşır.
+    pub fn validate(&self) -> std::result::Result<(), DataQualityIssue> {
+        let issue = |field, value: String, expected| {
+            Err(DataQualityIssue { field, value, expected })
+        };
+        let (sqrt_min, sqrt_max) = SQRT_PRICE_F64_RANGE;
+        if !(self.sqrt_price_f64.is_finite()
+            && self.sqrt_price_f64 > sqrt_min
+            && self.sqrt_price_f64 < sqrt_max)
+        {
+            return issue("sqrt_price_f64", self.sqrt_price_f64.to_string(), "finite, in (1e10, 1e40)");
+        }
+        if !(crate::math::exact::MIN_TICK..=crate::math::exact::MAX_TICK).contains(&self.tick) {
+            return issue("tick", self.tick.to_string(), "within ±887272");
+        }
+        if !(self.liquidity_f64.is_finite() && self.liquidity_f64 > 0.0) {
+            return issue("liquidity_f64", self.liquidity_f64.to_string(), "finite, > 0");
+        }
+        let (price_min, price_max) = ETH_PRICE_RANGE;
+        if !(self.eth_price_usd.is_finite()
+            && self.eth_price_usd > price_min
+            && self.eth_price_usd < price_max)
+        {
+            return issue("eth_price_usd", self.eth_price_usd.to_string(), "finite, in (1, 1e7)");
+        }
+
+        // f64 kopyalar kesin değerlerden türetilmiş olmalı
+        let relative_gap = |approx: f64, exact: f64| (approx - exact).abs() / exact.abs().max(1.0);
+        let sqrt_exact = crate::math::exact::u256_to_f64(self.sqrt_price_x96);
+        if relative_gap(self.sqrt_price_f64, sqrt_exact) > REPR_TOLERANCE {
+            return issue(
+                "sqrt_price_x96",
+                format!("{} (f64 copy {})", self.sqrt_price_x96, self.sqrt_price_f64),
+                "equal to sqrt_price_f64",
+            );
+        }
+        if relative_gap(self.liquidity_f64, self.liquidity as f64) > REPR_TOLERANCE {
+            return issue(
+                "liquidity",
+                format!("{} (f64 copy {})", self.liquidity, self.liquidity_f64),
+                "equal to liquidity_f64",
+            );
+        }
+        // slot0.tick, sqrtPriceX96'nın tick'i ya da (sınırda) bir altı olabilir
+        let implied_tick = crate::math::exact::get_tick_at_sqrt_ratio(self.sqrt_price_x96);
+        if (self.tick - implied_tick).abs() > 1 {
+            return issue(
+                "tick",
+                format!("{} (sqrtPriceX96 implies {})", self.tick, implied_tick),
+                "consistent with sqrtPriceX96",
+            );
+        }
+        Ok(())
     }
 
     /// Verinin yaşı (milisaniye)
@@ -403,11 +882,30 @@ impl PoolState {
 /// Thread-safe havuz durumu (Lock-free: ArcSwap ile atomik pointer swap)
 pub type SharedPoolState = Arc<ArcSwap<PoolState>>;
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Havuz Statik Verisi (PoolState'ten ayrıldı)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Havuzun neredeyse hiç değişmeyen verisi — REVM için bytecode.
+///
+/// Eskiden PoolState içindeydi (~22KB/havuz); her blokta yapılan state
+/// klonlarında ve her sync rcu'sunda boşuna kopyalanıyordu. Artık ayrı
+/// tutulur ve yalnızca SimulationEngine tarafından okunur.
+#[derive(Debug, Clone, Default)]
+pub struct StaticPoolData {
+    /// Havuz bytecode'u (REVM için önbellek) — Arc: klonlama ücretsiz
+    pub bytecode: Option<Arc<Vec<u8>>>,
+}
+
+/// Havuz adresi → statik veri. Bytecode yükleme (başlangıç + hot-reload)
+/// yazar, SimulationEngine::cache_bytecodes okur.
+pub type StaticPoolStore = Arc<RwLock<HashMap<Address, StaticPoolData>>>;
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Dinamik Atomik Nonce Yöneticisi
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Lock-free, atomik nonce yöneticisi.
+/// Atomik nonce yöneticisi.
 ///
 /// Problem: Her blokta `provider.get_transaction_count()` çağırmak sıralı
 /// RPC gecikmesi yaratır ve yarış durumuna (race condition) açıktır.
@@ -421,9 +919,18 @@ pub type SharedPoolState = Arc<ArcSwap<PoolState>>;
 /// TX #2 gönder → nonce = 43, AtomicU64::fetch_add(1) → nonce = 44
 /// ```
 ///
-/// Sıfır ek gecikme, sıfır kilit çekişmesi.
+/// Gönderilemeyen TX'in nonce'u sayacı geri sarmaz: yürütmeler eşzamanlı
+/// uçuşta olabilir (42 düşerken 43 bekliyor) ve geri sarma sıradaki TX'e
+/// bekleyen 43'ü yeniden verirdi. Bunun yerine nonce `release` ile
+/// "ayrılmış ama düşmüş" kümesine girer ve sıradaki tahsis önce oradan
+/// (en küçüğünden) yapılır. Yalnızca sayacın hemen altındaki nonce'lar
+/// sayaca geri katlanır. Küme küçüktür (eşzamanlı uçuş sınırı kadar).
 pub struct NonceManager {
+    /// Hiç verilmemiş ilk nonce (yüksek su işareti)
     current_nonce: AtomicU64,
+    /// Ayrılmış ama gönderilemeyen nonce'lar — hepsi `current_nonce` altında.
+    /// Sayacın tüm değişiklikleri de bu kilit altında yapılır.
+    released: Mutex<BTreeSet<u64>>,
 }
 
 impl NonceManager {
@@ -431,30 +938,70 @@ impl NonceManager {
     pub fn new(initial_nonce: u64) -> Self {
         Self {
             current_nonce: AtomicU64::new(initial_nonce),
+            released: Mutex::new(BTreeSet::new()),
         }
     }
 
-    /// Mevcut nonce'u al ve atomik olarak 1 artır.
-    /// Dönen değer: TX'e yazılacak nonce (artmadan önceki değer)
+    /// TX'e yazılacak nonce'u ayır: önce düşmüş nonce'ların en küçüğü,
+    /// yoksa sayacın değeri (sayaç 1 artar).
     pub fn get_and_increment(&self) -> u64 {
-        self.current_nonce.fetch_add(1, Ordering::SeqCst)
+        let mut released = self.released.lock();
+        match released.pop_first() {
+            Some(nonce) => nonce,
+            None => self.current_nonce.fetch_add(1, Ordering::SeqCst),
+        }
     }
 
-    /// Mevcut nonce'u oku (artırmadan)
+    /// Mevcut nonce'u oku (artırmadan) — hiç verilmemiş ilk nonce
     pub fn current(&self) -> u64 {
         self.current_nonce.load(Ordering::SeqCst)
     }
 
+    /// Yeniden verilmeyi bekleyen düşmüş nonce sayısı
+    pub fn released_count(&self) -> usize {
+        self.released.lock().len()
+    }
+
+    /// Gönderilemeyen TX'in nonce'unu geri ver (TX zincire gitmedi).
+    /// Sayacın hemen altındaysa sayaç geri çekilir (altındaki düşmüşler de
+    /// katlanır); değilse sıradaki tahsise kümeden verilir.
+    pub fn release(&self, nonce: u64) {
+        let mut released = self.released.lock();
+        if nonce >= self.current_nonce.load(Ordering::SeqCst) {
+            // Hiç verilmemiş (ya da force_set ile aşılmış) — geri verilecek bir şey yok
+            return;
+        }
+        released.insert(nonce);
+        let mut next = self.current_nonce.load(Ordering::SeqCst);
+        while next > 0 && released.remove(&(next - 1)) {
+            next -= 1;
+        }
+        self.current_nonce.store(next, Ordering::SeqCst);
+    }
+
+    /// Periyodik senkron: zincirin pending sayımı `onchain_nonce` altındaki
+    /// düşmüş nonce'lar zincirde başka bir TX ile tüketilmiştir — bayat
+    /// ayrımları sil. Silinen sayısını döner.
+    pub fn reconcile(&self, onchain_nonce: u64) -> usize {
+        let mut released = self.released.lock();
+        let before = released.len();
+        released.retain(|&nonce| nonce >= onchain_nonce);
+        before - released.len()
+    }
 
-    /// Nonce'u belirli bir değere zorla ayarla (RPC senkronizasyonu için)
+    /// Nonce'u belirli bir değere zorla ayarla (RPC senkronizasyonu için).
+    /// Yeni sayacın üstünde kalan düşmüş nonce'lar sayaçtan yeniden
+    /// verileceği için kümeden çıkarılır.
     pub fn force_set(&self, nonce: u64) {
+        let mut released = self.released.lock();
+        released.retain(|&n| n < nonce);
         self.current_nonce.store(nonce, Ordering::SeqCst);
     }
 }
 
 impl std::fmt::Debug for NonceManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "NonceManager(nonce={})", self.current())
+        write!(f, "NonceManager(nonce={}, released={})", self.current(), self.released_count())
     }
 }
 
@@ -462,6 +1009,308 @@ impl std::fmt::Debug for NonceManager {
 // Arbitraj Fırsatı
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Fırsatın likidite matematiğinin dayandığı TickBitmap kalitesi.
+///
+/// Bitmap yoksa veya bayatsa swap matematiği tick başına 0.997 sönümleme
+/// sezgisine düşer — kâr tahmini çok daha kabadır.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BitmapQuality {
+    /// İki bacak da taze bitmap ile hesaplandı
+    Real,
+    /// En az bir bacağın bitmap'i max yaşı aştı (o bacak sönümlemeye düştü)
+    Stale { age_blocks: u64 },
+    /// En az bir bacakta bitmap hiç yok — sönümleme sezgisi
+    Fallback,
+}
+
+impl BitmapQuality {
+    /// İki bacağın bitmap'lerinden kaliteyi türet.
+    /// Bayat bitmap, eksik bitmap'ten önceliklidir (eski "stale" reddi korunur).
+    pub fn classify(
+        sell: Option<&TickBitmapData>,
+        buy: Option<&TickBitmapData>,
+        current_block: u64,
+        max_age_blocks: u64,
+    ) -> Self {
+        let stale_age = [sell, buy]
+            .into_iter()
+            .flatten()
+            .filter(|bm| !bm.is_fresh(current_block, max_age_blocks))
+            .map(|bm| bm.age_blocks(current_block))
+            .max();
+        match stale_age {
+            Some(age_blocks) => BitmapQuality::Stale { age_blocks },
+            None if sell.is_none() || buy.is_none() => BitmapQuality::Fallback,
+            None => BitmapQuality::Real,
+        }
+    }
+
+    /// Kâr tahmini gerçek tick verisine mi dayanıyor?
+    pub fn is_real(&self) -> bool {
+        matches!(self, BitmapQuality::Real)
+    }
+}
+
+impl std::fmt::Display for BitmapQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BitmapQuality::Real => write!(f, "real"),
+            BitmapQuality::Stale { age_blocks } => write!(f, "stale({} blocks)", age_blocks),
+            BitmapQuality::Fallback => write!(f, "fallback"),
+        }
+    }
+}
+
+/// Ters seçilim senaryosundaki hipotetik rakip front-run boyutu.
+/// ADVERSE_FRONTRUN_WETH: sabit miktar ("0.5") veya işlem boyutunun yüzdesi ("25%").
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrontrunSize {
+    /// Sabit WETH miktarı
+    Weth(f64),
+    /// Bizim işlem boyutumuzun yüzdesi
+    PctOfTrade(f64),
+}
+
+impl FrontrunSize {
+    /// İşlem boyutuna göre WETH cinsinden front-run (0 = senaryo kapalı)
+    pub fn resolve(self, trade_weth: f64) -> f64 {
+        match self {
+            FrontrunSize::Weth(w) => w,
+            FrontrunSize::PctOfTrade(pct) => trade_weth * pct / 100.0,
+        }
+    }
+}
+
+impl std::str::FromStr for FrontrunSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        let (value, is_pct) = match s.strip_suffix('%') {
+            Some(pct) => (pct.trim(), true),
+            None => (s, false),
+        };
+        let v: f64 = value.parse().map_err(|_| format!("invalid front-run size '{}'", s))?;
+        if !v.is_finite() || v < 0.0 {
+            return Err(format!("front-run size must be >= 0, got '{}'", s));
+        }
+        Ok(if is_pct { FrontrunSize::PctOfTrade(v) } else { FrontrunSize::Weth(v) })
+    }
+}
+
+impl std::fmt::Display for FrontrunSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrontrunSize::Weth(w) => write!(f, "{}", w),
+            FrontrunSize::PctOfTrade(pct) => write!(f, "{}%", pct),
+        }
+    }
+}
+
+/// NR boyutlandırmasında swap çıktısının kaynağı (SIZING_BACKEND).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizingBackend {
+    /// Exact U256 matematik (bitmap yoksa tek aralık)
+    Math,
+    /// Havuz bytecode'u üzerinde REVM quote (probe başına ~0.5ms)
+    Revm,
+    /// Her iki havuzda taze bitmap varsa Math, yoksa Revm
+    Auto,
+}
+
+impl SizingBackend {
+    /// Fırsat bazında kullanılacak somut backend (Math veya Revm).
+    /// REVM hazır değilse (bytecode yok) her zaman Math.
+    pub fn resolve(self, has_bitmaps: bool, revm_ready: bool) -> SizingBackend {
+        match self {
+            SizingBackend::Revm | SizingBackend::Auto if !revm_ready => SizingBackend::Math,
+            SizingBackend::Auto if has_bitmaps => SizingBackend::Math,
+            SizingBackend::Auto => SizingBackend::Revm,
+            backend => backend,
+        }
+    }
+}
+
+impl std::fmt::Display for SizingBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizingBackend::Math => write!(f, "math"),
+            SizingBackend::Revm => write!(f, "revm"),
+            SizingBackend::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// Ön eleme (yürütülebilir fiyat + PreFilter) ve NR kaba taramasının
+/// sayı temsili (SIZING_NUMERICS).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizingNumerics {
+    /// f64 — hızlı, ama son bitler CPU / derleyici / libm'e göre değişebilir
+    F64,
+    /// Q63.64 sabit nokta + U256 — her platformda bit-özdeş (math::fixed)
+    Fixed,
+}
+
+impl std::fmt::Display for SizingNumerics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizingNumerics::F64 => write!(f, "f64"),
+            SizingNumerics::Fixed => write!(f, "fixed"),
+        }
+    }
+}
+
+/// Sermaye riski değerlendirmesi: rakip aynı yönde bizden önce işlem yaparsa.
+///
+/// Kârlar owedToken cinsinden (wei) — kontratın minProfit kontrolüyle aynı birim.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdverseScenario {
+    /// Uygulanan hipotetik front-run (WETH)
+    pub frontrun_weth: f64,
+    /// Gözlenen state üzerinde exact kâr
+    pub base_profit_wei: U256,
+    /// Front-run sonrası exact kâr (zararda 0)
+    pub adverse_profit_wei: U256,
+    /// Base kârdan türetilen minProfit (calldata'ya yazılacak değer)
+    pub min_profit: u128,
+    /// minProfit kontrolünü tetikleyen en küçük front-run (WETH).
+    /// Arama sınırına kadar tetiklenmezse None.
+    pub revert_boundary_weth: Option<f64>,
+}
+
+impl AdverseScenario {
+    /// Front-run sonrası hâlâ kârlı mı?
+    pub fn is_adverse_positive(&self) -> bool {
+        !self.adverse_profit_wei.is_zero()
+    }
+
+    /// Front-run sonrası kontrat minProfit nedeniyle revert eder mi?
+    pub fn would_revert(&self) -> bool {
+        self.adverse_profit_wei < U256::from(self.min_profit)
+    }
+}
+
+/// Kâr hassasiyeti: havuz fiyatları 1 ve 2 tick ters yönde kaydığında exact kâr.
+///
+/// Her tick sayısı için iki havuz ayrı ayrı (bizim swap yönümüzde) kaydırılır,
+/// kötü olan sonuç tutulur. Kârlar owedToken cinsinden (wei).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfitSensitivity {
+    /// Gözlenen state üzerinde exact kâr
+    pub base_profit_wei: U256,
+    /// 1 tick ters hareket sonrası exact kâr (zararda 0)
+    pub profit_after_1tick_wei: U256,
+    /// 2 tick ters hareket sonrası exact kâr (zararda 0)
+    pub profit_after_2tick_wei: U256,
+}
+
+impl ProfitSensitivity {
+    /// 1 tick ters hareket sonrası hâlâ kârlı mı?
+    pub fn survives_1tick(&self) -> bool {
+        !self.profit_after_1tick_wei.is_zero()
+    }
+
+    /// Sağlamlık skoru [0, 1]: 1 ve 2 tick sonrası korunan kâr oranlarının
+    /// ortalaması. 1.0 = tick hareketine duyarsız, 0.0 = 1 tick'te ölür.
+    pub fn robustness(&self) -> f64 {
+        if self.base_profit_wei.is_zero() {
+            return 0.0;
+        }
+        let base = crate::math::exact::u256_to_f64(self.base_profit_wei);
+        let kept = |profit: U256| (crate::math::exact::u256_to_f64(profit) / base).clamp(0.0, 1.0);
+        (kept(self.profit_after_1tick_wei) + kept(self.profit_after_2tick_wei)) / 2.0
+    }
+}
+
+/// İki adımlı (kısmi dolum) plan: tek seferlik optimum iki havuzda exact
+/// motorla yürütülür, fırsat kontrolü işlem sonrası state'te tekrarlanır.
+///
+/// Optimum bir tavana (MAX_TRADE_SIZE_WETH / sığ bacak) dayandıysa kalan
+/// spread ikinci bir işlemi taşıyabilir. Kârlar WETH cinsinden, gas düşülmüş.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoStepPlan {
+    /// 1. adım boyutu — tek seferlik optimum (WETH)
+    pub first_amount_weth: f64,
+    /// 1. adımın beklenen net kârı (WETH)
+    pub first_profit_weth: f64,
+    /// 2. adım boyutu — işlem sonrası state'teki optimum (WETH)
+    pub second_amount_weth: f64,
+    /// 2. adımın beklenen net kârı (WETH)
+    pub second_profit_weth: f64,
+}
+
+impl TwoStepPlan {
+    /// İki adımın toplam net kârı (WETH)
+    pub fn total_profit_weth(&self) -> f64 {
+        self.first_profit_weth + self.second_profit_weth
+    }
+
+    /// Toplam kârın tek seferlik kâra oranı (tek seferlik kâr ≤ 0 ise 0)
+    pub fn uplift(&self) -> f64 {
+        if self.first_profit_weth > 0.0 {
+            self.total_profit_weth() / self.first_profit_weth
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Ters rota, mevcut rotayı en az bu kadar WETH geçmeli
+pub const REVERSED_ROUTE_MIN_GAIN_WETH: f64 = 0.00001;
+/// ... ve mevcut rotanın net kârını en az bu yüzde kadar aşmalı
+pub const REVERSED_ROUTE_MIN_GAIN_PCT: f64 = 5.0;
+
+/// Flash swap kaynağı. Kontrat calldata'sı pool_a=UniV3'ü sabit kaynak
+/// olarak bekler — PoolB yalnızca gölge raporlamada önerilir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlashSource {
+    /// UniV3'ten borç al, Slipstream'e sat (mevcut kontrat rotası)
+    PoolA,
+    /// Slipstream'den borç al, UniV3'e sat (kontrat yükseltmesi gerektirir)
+    PoolB,
+}
+
+impl std::fmt::Display for FlashSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlashSource::PoolA => write!(f, "pool_a"),
+            FlashSource::PoolB => write!(f, "pool_b"),
+        }
+    }
+}
+
+/// İki flash kaynağı sıralamasının exact motorla karşılaştırması.
+///
+/// Her sıralama kendi optimum boyutunda değerlendirilir. Kârlar WETH cinsinden,
+/// flash primi düşülmüş; gas iki sıralamada aynı olduğundan karşılaştırmaya girmez.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlashRouteComparison {
+    /// Pool A kaynaklı rotanın optimum boyutu (WETH)
+    pub current_amount_weth: f64,
+    /// Pool A kaynaklı rotanın net kârı (WETH)
+    pub current_profit_weth: f64,
+    /// Pool B kaynaklı rotanın optimum boyutu (WETH)
+    pub reversed_amount_weth: f64,
+    /// Pool B kaynaklı rotanın net kârı (WETH)
+    pub reversed_profit_weth: f64,
+    /// Daha kârlı kaynak (eşitlikte PoolA)
+    pub preferred: FlashSource,
+}
+
+impl FlashRouteComparison {
+    /// Mevcut kontrat rotası yüzünden kaçırılan kâr (WETH, ≥ 0)
+    pub fn foregone_improvement_weth(&self) -> f64 {
+        (self.reversed_profit_weth - self.current_profit_weth).max(0.0)
+    }
+
+    /// Ters rota kontrat yükseltmesini haklı çıkaracak kadar iyi mi?
+    pub fn is_material(&self) -> bool {
+        let gain = self.foregone_improvement_weth();
+        gain >= REVERSED_ROUTE_MIN_GAIN_WETH
+            && gain >= self.current_profit_weth.abs() * REVERSED_ROUTE_MIN_GAIN_PCT / 100.0
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ArbitrageOpportunity {
     /// Ucuz havuz indeksi (buradan al)
@@ -476,12 +1325,58 @@ pub struct ArbitrageOpportunity {
     pub buy_price_quote: f64,
     /// Satış fiyatı (pahalı havuz ETH/Quote)
     pub sell_price_quote: f64,
-    /// Spread yüzdesi
+    /// Yürütülebilir spread yüzdesi (SPREAD_PROBE_SIZE_WETH referans boyutunda)
     pub spread_pct: f64,
+    /// Likidite ağırlıklı referans fiyat — quote ↔ WETH dönüşümleri bununla yapılır
+    pub reference_price_quote: f64,
     /// Newton-Raphson yakınsadı mı?
     pub nr_converged: bool,
     /// Newton-Raphson iterasyon sayısı
     pub nr_iterations: u32,
+    /// Bir filtre Degrade kararı verdiyse: yürütülmez, sadece gölge loglanır
+    /// (ör. rakip arbitraj cezası). None = yürütmeye uygun.
+    pub shadow_only: Option<crate::opportunity_filter::FilterVerdict>,
+    /// NR kâr tahmininin dayandığı TickBitmap kalitesi
+    pub bitmap_quality: BitmapQuality,
+    /// Ters seçilim (rakip front-run) senaryosu — ADVERSE_FRONTRUN_WETH=0 ise None
+    pub adverse: Option<AdverseScenario>,
+    /// 1/2 tick ters hareket hassasiyeti — yalnızca seçilen fırsat için,
+    /// pipeline bütçesi yetmezse None
+    pub sensitivity: Option<ProfitSensitivity>,
+    /// İki adımlı plan — yalnızca seçilen fırsat için (TWO_STEP_SIZING),
+    /// ikinci kârlı işlem yoksa ya da bütçe yetmezse None
+    pub two_step: Option<TwoStepPlan>,
+    /// Optimum bir bacakta taranan bitmap kenarına dayandıysa o bacak —
+    /// ana döngü kenarı genişletip boyutlandırmayı bir kez tekrarlar
+    pub scan_boundary: Option<ScanBoundaryHit>,
+    /// Flash kaynağı karşılaştırması (pool A vs pool B) — kârsız fırsatta None
+    pub flash_route: Option<FlashRouteComparison>,
+    /// Beklenen kârın spread / LP ücreti / flash / gas ayrıştırması (rüşvet hariç)
+    pub profit_breakdown: Option<crate::math::ProfitBreakdown>,
+    /// Optimum boyutta bacakların tick geçişleri (optimum yoksa None)
+    pub tick_crossings: Option<crate::math::TickCrossings>,
+    /// Geçiş modelinin optimum boyuttaki gas tahmini
+    pub modeled_gas: Option<u64>,
+    /// Beklenen kârdaki gas kaleminin fiyatlandığı gas birimi — REVM sonrası
+    /// kâr bu tahmin yerine simüle gas ile yeniden hesaplanır
+    pub priced_gas: u64,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Rakip Arbitraj Yoğunluğu (Kuyruk Derinliği Sezgisi)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Önceki bloktaki rakip işlem yoğunluğu.
+///
+/// Son blokta izlenen havuzlara veya bilinen router'lara giden birden fazla
+/// işlem varsa, görülen spread büyük olasılıkla daha hızlı botların
+/// artığıdır — beklenen kâr bayattır.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompetitionSnapshot {
+    /// Sayımın yapıldığı blok
+    pub block_number: u64,
+    /// İzlenen havuz / router adreslerine giden işlem sayısı
+    pub competitor_tx_last_block: u32,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -515,6 +1410,24 @@ pub struct MultiHopOpportunity {
 // REVM Simülasyon Sonucu
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Simülasyon sonucunun kaynağı — gas değerinin ölçüm mü tahmin mi olduğu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimSource {
+    /// Gerçek REVM yürütmesi — gas_used ölçülmüş değer
+    Revm,
+    /// validate_mathematical — gas_used sezgisel tahmin (tick geçişi sayısından)
+    MathFallback,
+}
+
+impl SimSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            SimSource::Revm => "revm",
+            SimSource::MathFallback => "math_fallback",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SimulationResult {
     /// Simülasyon başarılı mı?
@@ -523,6 +1436,57 @@ pub struct SimulationResult {
     pub gas_used: u64,
     /// Hata mesajı (varsa)
     pub error: Option<String>,
+    /// Sonucu üreten motor
+    pub source: SimSource,
+}
+
+impl SimulationResult {
+    /// Gas limiti / bribe / maliyet hesabına girecek gas. Yalnızca REVM
+    /// ölçümü kullanılır; sezgisel tahmin yerine GAS_ESTIMATE döner.
+    pub fn execution_gas(&self, gas_estimate: u64) -> u64 {
+        match self.source {
+            SimSource::Revm => self.gas_used,
+            SimSource::MathFallback => gas_estimate,
+        }
+    }
+}
+
+/// REVM yürütmesinden decode edilen Swap event'i (havuz bazlı gerçekleşen miktarlar)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapLog {
+    /// Event'i yayan havuz
+    pub pool: Address,
+    /// token0 miktarı (pozitif: havuza giren, negatif: havuzdan çıkan)
+    pub amount0: I256,
+    /// token1 miktarı
+    pub amount1: I256,
+}
+
+/// Tam REVM yürütme sonucu — loglar + owedToken state diff'i.
+///
+/// Simülasyonun içinde gerçekleşen miktarlar, f64/exact matematiğin
+/// karşılaştırılacağı referanstır (storage layout / decimal hatalarını yakalar).
+#[derive(Debug, Clone)]
+pub struct DetailedSimulationResult {
+    /// Başarı / gas / hata özeti
+    pub result: SimulationResult,
+    /// Decode edilmiş Swap event'leri (yayılma sırasıyla)
+    pub swaps: Vec<SwapLog>,
+    /// Kontratın owedToken bakiyesi (önce, sonra) — state diff'ten
+    pub owed_token_balance: Option<(U256, U256)>,
+    /// ArbitrageExecuted event'indeki kâr (kontratın kendi hesabı)
+    pub reported_profit: Option<U256>,
+}
+
+impl DetailedSimulationResult {
+    /// REVM'de gerçekleşen kâr (owedToken cinsinden, wei).
+    /// Önce state diff bakiye farkı, yoksa ArbitrageExecuted event'i.
+    pub fn realized_profit(&self) -> Option<U256> {
+        match self.owed_token_balance {
+            Some((before, after)) => Some(after.saturating_sub(before)),
+            None => self.reported_profit,
+        }
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -544,9 +1508,14 @@ pub struct BotConfig {
     pub private_key: Option<String>,
     /// Arbitraj kontrat adresi (opsiyonel)
     pub contract_address: Option<Address>,
-    /// WETH token adresi (Base: 0x4200000000000000000000000000000000000006)
-    /// v12.0: Hardcoded — .env'den okunmaz, Base ağında sabittir.
+    /// Kontratın pause view'ı (`paused()` ya da 4-byte selector) — her blok
+    /// okunur, duraklatılmışsa yürütme gölgeye düşer
+    pub contract_pause_selector: PauseSelector,
+    /// WETH token adresi — zincir profilinden (Base/OP: 0x4200…0006),
+    /// WETH_ADDRESS ile ezilebilir
     pub weth_address: Address,
+    /// USDC token adresi — zincir profilinden, USDC_ADDRESS ile ezilebilir
+    pub usdc_address: Address,
     /// Tahmini gas maliyeti fallback (WETH cinsinden)
     pub gas_cost_fallback_weth: f64,
     /// Flash loan ücreti (basis points)
@@ -564,12 +1533,21 @@ pub struct BotConfig {
         pub initial_retry_delay_secs: u64,
     /// Maksimum bekleme süresi (saniye) — v10.1: agresif reconnect ile kullanılmıyor
         pub max_retry_delay_secs: u64,
-    /// Veri tazelik eşiği (milisaniye)
+    /// Sabit veri tazelik eşiği (milisaniye) — uyarlamalı kalibrasyon öncesi
+    /// fallback; ADAPTIVE_STALENESS=false ise tespit ve yürütmede override
     pub max_staleness_ms: u128,
     /// Maksimum flash loan boyutu (WETH)
     pub max_trade_size_weth: f64,
-    /// Base zincir ID
+    /// Minimum işlem boyutu (WETH) — NR alt sınırı ve calldata öncesi toz kontrolü
+    pub min_trade_size_weth: f64,
+    /// Etkin zincir profili (CHAIN_PROFILE=base|optimism|arbitrum|custom)
+    pub chain_profile: ChainProfile,
+    /// Zincir ID — profilden; REVM cfg, imzalama ve başlangıç doğrulaması
     pub chain_id: u64,
+    /// L1 data fee oracle'ı (OP Stack GasPriceOracle). None → L1 ücreti yok
+    pub l1_fee_oracle: Option<Address>,
+    /// Priority fee stratejisi: FIFO bribe (kâr payı) veya standart EIP-1559
+    pub priority_fee_strategy: PriorityFeeStrategy,
     /// TickBitmap tarama yarıçapı (mevcut tick ± range)
     /// v26.0: 500 → 100. No profitable arb moves price >5%.
     /// Narrower range cuts RPC data by ~80% and reduces parse overhead.
@@ -593,8 +1571,9 @@ pub struct BotConfig {
     pub bribe_pct: f64,
     /// Şifreli keystore dosya yolu (v9.0 key management)
         pub keystore_path: Option<String>,
-    /// Key Manager modu aktif mi? (auto_load tarafından ayarlanır)
-    pub key_manager_active: bool,
+    /// Yüklenen anahtarın kaynağı — env'den okunmaz, `attach_key_manager`
+    /// ile KeyManager'dan türetilir. "Keystore aktif mi?" bilgisi buradan gelir.
+    pub key_source: KeySource,
     /// v10.1: Circuit breaker eşiği — kaç ardışık başarısızlıkta bot kapanır
     /// Varsayılan: 3. .env'den CIRCUIT_BREAKER_THRESHOLD ile ayarlanabilir.
     pub circuit_breaker_threshold: u32,
@@ -604,6 +1583,10 @@ pub struct BotConfig {
     /// v15.0: Gecikme spike uyarı eşiği (ms)
     /// Bu değerin üzerinde gecikme loglanır.
     pub latency_spike_threshold_ms: f64,
+    /// Executor stall eşiği (ms): runtime probe'unun 10ms uykusu bu kadar
+    /// geç uyanırsa ya da işaretli senkron bölüm bu kadar sürerse stall
+    /// sayılır ve bloğa işlenir. 0 = runtime sağlık ölçümü kapalı.
+    pub runtime_stall_threshold_ms: u64,
     /// v10.0: Private/Flashbots RPC URL (MEV koruması için)
     /// Tanımlıysa eth_sendRawTransaction kullanılır, yoksa işlem İPTAL EDİLİR
     pub private_rpc_url: Option<String>,
@@ -633,156 +1616,526 @@ pub struct BotConfig {
     pub telegram_shift_interval_secs: u64,
     /// Doomsday bakiye eşiği (ETH, default: 0.05)
     pub telegram_balance_warn_eth: f64,
+
+    /// Fırsat snapshot arşivinde tutulacak en yeni dosya sayısı
+    /// (snapshots/<id>.json.gz). 0 = arşivleme kapalı. Varsayılan: 200.
+    pub snapshot_keep: usize,
+
+    /// Aave V3 Pool adresi (zincir profilinden) — FLASHLOAN_PREMIUM_TOTAL saatlik doğrulaması.
+    pub aave_pool_address: Address,
+    /// Zincirdeki ücret yapılandırılandan farklıysa kâr modelini otomatik güncelle.
+    /// false: yüksek sesle uyar ve yapılandırılan değeri koru.
+    pub auto_update_fees: bool,
+
+    /// Rakip sayımına dahil edilecek ek adresler (aggregator router'ları)
+    pub competition_routers: Vec<Address>,
+    /// Ceza eşiği: son blokta en az bu kadar rakip işlem (0 = kapalı)
+    pub competition_min_txs: u32,
+    /// Ceza: kâr ve ROI eşikleri bu çarpanla yükseltilir
+    pub competition_profit_mult: f64,
+    /// Rakip işlem sayısı bu limiti aşarsa blok tamamen atlanır (0 = kapalı)
+    pub competition_skip_limit: u32,
+
+    /// REVM kârı ile exact matematik kârı arasındaki izin verilen fark
+    /// (owedToken wei). Aşılırsa işlem yürütülmez. 0 = sadece logla.
+    pub sim_math_max_discrepancy: u128,
+
+    /// Arka plan likidite analitiği için tur başına zaman bütçesi (ms).
+    /// Bütçe aşılırsa tur yarıda bırakılır. 0 = analitik kapalı.
+    pub liquidity_analytics_budget_ms: u64,
+
+    /// Fırsat filtre zinciri sırası (OPPORTUNITY_FILTERS, virgülle ayrılmış).
+    /// Boş = varsayılan sıra (opportunity_filter::DEFAULT_FILTER_ORDER).
+    pub opportunity_filters: Vec<String>,
+
+    /// Gerçek (taze) TickBitmap'e dayanmayan fırsatlar yürütülmez, gölge loglanır.
+    /// false: sönümleme sezgisiyle hesaplanan fırsatlar da yürütülebilir.
+    pub require_bitmap_for_execution: bool,
+
+    /// Opsiyonel ikinci blok sinyali kaynağı (SEQUENCER_WS_URL).
+    /// newHeads'i birincil RPC'den önce veren uç nokta; tanımsızsa tek kaynak.
+    pub sequencer_ws_url: Option<String>,
+
+    /// Ters seçilim senaryosundaki hipotetik rakip front-run (0 = kapalı)
+    pub adverse_frontrun: FrontrunSize,
+
+    /// Front-run senaryosunda kâr sıfıra düşen fırsatlar yürütülmez, gölge loglanır
+    pub require_adverse_positive: bool,
+
+    /// Yürütme için gereken en düşük sağlamlık skoru (0 = kapalı). Açıkken
+    /// 1 tick ters harekette kârı sıfırlanan fırsat da reddedilir.
+    pub min_robustness: f64,
+
+    /// Bağlantı sonrası yürütme açılmadan önce gereken ardışık temiz blok (0 = kapalı)
+    pub warmup_clean_blocks: u32,
+
+    /// session_summary JSON dosyalarının dizini (kapanış + UTC gün dönümü)
+    pub summary_dir: String,
+
+    /// Spread ön elemesinde yürütülebilir fiyatın ölçüldüğü referans boyut (WETH).
+    /// 0 = spot fiyat karşılaştırması.
+    pub spread_probe_size_weth: f64,
+
+    /// NR kâr fonksiyonunun swap kaynağı: math | revm | auto (bitmap yoksa revm)
+    pub sizing_backend: SizingBackend,
+
+    /// Ön eleme + NR kaba taraması: f64 | fixed (deterministik sabit nokta)
+    pub sizing_numerics: SizingNumerics,
+
+    /// Seçilen fırsat için iki adımlı (kısmi dolum) plan: optimum sonrası
+    /// state'te ikinci kârlı işlem aranır, rapor + gölge loga yazılır
+    pub two_step_sizing: bool,
+
+    /// İki adımlı planın ikinci adımı yürütülür: ilk TX'in receipt'i
+    /// onaylandıktan sonra taze state aynı yönü hâlâ destekliyorsa
+    /// (TWO_STEP_SIZING gerektirir)
+    pub execute_followup: bool,
+
+    /// Her iyimser havuz yenilemesi optimistic_updates.jsonl'e de yazılır
+    pub optimistic_update_log: bool,
+
+    /// Fee-on-transfer / rebasing olarak bayraklanan token'ları içeren çiftlerde
+    /// de yürüt (varsayılan: yalnızca gölge log)
+    pub allow_nonstandard_tokens: bool,
+
+    /// A/B gölge değerlendirmesi için ikincil parametre katmanı (env formatı)
+    pub secondary_config_path: Option<String>,
+
+    /// Kontrat token onayı denetimi: off | soft (uyar) | hard (gölge moda düş)
+    pub approval_audit_mode: ApprovalAuditMode,
+
+    /// Onayı zorunlu harcayıcılar: none | flash_lender | pools | all
+    pub approval_required_spenders: RequiredSpenders,
+
+    /// Blok başına havuz skalerlerini state_diff.jsonl'e yaz (--replay-diff girdisi)
+    pub state_diff_log: bool,
+
+    /// state_diff ve gölge logu ikili formatta (.bin) yazılır — okuyucular
+    /// iki formatı da koklar, --convert-log JSONL'e çevirir
+    pub log_format_binary: bool,
+
+    /// true: biriken blok başlıkları tek tek işlenir (analitik için tamlık);
+    /// false: geride kalınca en yeni başlığa atlanır
+    pub process_all_blocks: bool,
+
+    /// İki fazlı sync: önce yalnızca slot0 okunur; hiçbir fiyat değişmediyse
+    /// blok sessiz sayılır, fırsat kontrolü atlanır
+    pub fast_path_sync: bool,
+
+    /// Keşif V2 / vAMM (sabit çarpım) havuzlarını da alsın mı. Kontratta V2
+    /// swap yolu yok — bu havuzlu fırsatlar yalnızca gölgede fiyatlanır;
+    /// kapalıyken keşif ve sync onlar için RPC harcamaz
+    pub discover_constant_product: bool,
+
+    /// Periyodik tam sync (slot0 + liquidity + fee) aralığı, blok — event'i
+    /// kaçırılmış likidite değişikliklerine karşı
+    pub full_sync_interval_blocks: u64,
+
+    /// REVM ölçümü yokken gas limiti / bribe hesabında kullanılan gas
+    pub gas_estimate: u64,
+
+    /// Multicall3 / sync çağrısı geçici RPC hatasında kaç kez daha denenir
+    /// (yalnızca taşıma / timeout; decode hataları hiç denenmez)
+    pub multicall_retries: u32,
+
+    /// Gas limiti tamponu: fixed (×1.10) ya da auto (receipt p95 × güvenlik payı)
+    pub gas_buffer_mode: GasBufferMode,
+
+    /// auto tamponun devreye girmesi için gereken canlı işlem sayısı
+    /// (geçiş modelinin oturtulması için gereken REVM / receipt örneği)
+    pub min_calibration_samples: u32,
+
+    /// Geçiş modeli önseli: flash swap + iki swap'ın geçişsiz gas'ı
+    pub gas_base_overhead: u64,
+
+    /// Geçiş modeli önseli: tick geçişi başına ek gas
+    pub gas_per_tick_crossing: u64,
+
+    /// true: geçiş modeli REVM ölçümleri + receipt'lerle oturtulur;
+    /// false: her zaman GAS_BASE_OVERHEAD / GAS_PER_TICK_CROSSING
+    pub gas_crossing_calibration: bool,
+
+    /// İşlem sonrası etki doğrulaması: bir bacağın gözlenen son tick'i
+    /// öngörüden bu kadar saparsa uyarı (konsol + Telegram)
+    pub impact_warn_ticks: u32,
+
+    /// Uyarı sonrası minProfit slippage faktöründen düşülen bps (0 = kapalı)
+    pub impact_slippage_bump_bps: u64,
+
+    /// Slippage artışının geçerli kaldığı blok sayısı
+    pub impact_bump_blocks: u64,
+
+    /// Quoter eşlik denetimi aralığı (blok): her havuzun off-chain swap
+    /// çıktıları resmi quoter kontratıyla karşılaştırılır (0 = kapalı)
+    pub audit_interval_blocks: u64,
+
+    /// Motor çıktısının quoter'dan bu kadar (bps) sapması uyarı üretir
+    pub audit_max_deviation_bps: f64,
+
+    /// Swap eventi ile sabitli sync okuması arasında izin verilen tick farkı;
+    /// aşılırsa ayrışma alarmı + yedek RPC'den yeniden sync (0 = birebir)
+    pub event_divergence_ticks: u32,
+
+    /// base_db bytecode'larının kod hash'inin zincirle yeniden karşılaştırıldığı
+    /// blok aralığı; değişiklikte base_db yeniden kurulur (0 = kapalı)
+    pub bytecode_recheck_blocks: u64,
+
+    /// Aynı anda uçuşta olabilecek yürütme görevi sayısı — doluysa fırsat atlanır
+    pub max_inflight_executions: u32,
+
+    /// Yön kapısı penceresi: (buy_pool, sell_pool) başına başarı oranının
+    /// hesaplandığı son deneme sayısı (0 = kapı kapalı, yalnızca istatistik)
+    pub direction_gate_window: u32,
+
+    /// Kısıtlama kararı için pencerede gereken en az deneme
+    pub direction_gate_min_attempts: u32,
+
+    /// Pencere başarı oranı bunun altına inerse yön kısıtlanır
+    pub direction_gate_min_success_rate: f64,
+
+    /// Kısıtlama biçimi: multiplier (kâr eşiği × çarpan) ya da suspend (hiç gönderme)
+    pub direction_gate_mode: DirectionGateMode,
+
+    /// multiplier modunda kısıtlı yönün MIN_NET_PROFIT_WETH çarpanı
+    pub direction_gate_profit_multiplier: f64,
+
+    /// Kısıtlamanın kendiliğinden kalktığı blok sayısı
+    pub direction_gate_cooldown_blocks: u64,
+
+    /// Gölge fırsatların modellenen kârı kısıtlama anındaki kenarın bu katına
+    /// çıkarsa kısıtlama erken kalkar
+    pub direction_gate_edge_widen: f64,
+
+    /// Çiftin spot spread'i bu kadar ardışık blok PERSISTENT_SPREAD_FLOOR_PCT
+    /// üstünde kalırsa (arada RESET altına inmeden) yapısal ayrışma sayılır:
+    /// çift gölgeye alınır (0 = kapalı)
+    pub persistent_spread_blocks: u64,
+
+    /// Kalıcı ayrışma sayacını ilerleten spot spread tabanı (%)
+    pub persistent_spread_floor_pct: f64,
+
+    /// Spread bunun altına inince sayaç sıfırlanır / ayrışma kapanmış sayılır (%)
+    pub persistent_spread_reset_pct: f64,
+
+    /// Her gönderim öncesi aralıktan rastgele gecikme (ms) — 0 = kapalı
+    pub submit_jitter_ms: JitterRange,
+
+    /// Aynı DATA_DIR'i paylaşan yerel örneklerin gönderimleri arası en az
+    /// süre (submit_spacing.lease) — 0 = kapalı
+    pub min_submit_spacing_ms: u64,
+
+    /// Günün en kârlı kaç gölge fırsatı sessiz dönemde tam hassasiyetle
+    /// yeniden değerlendirilir (revisits.json) — 0 = kapalı
+    pub revisit_top_k: usize,
+
+    /// Yeniden değerlendirme için fırsatsız geçmesi gereken ardışık blok
+    pub revisit_quiet_blocks: u64,
+
+    /// Tek kaydın yeniden değerlendirme süresi üst sınırı (ms)
+    pub revisit_budget_ms: u64,
+
+    /// pools[0] (pool_a) okumaları için ayrı endpoint (HTTP/WSS) — hata → primary
+    pub pool_a_rpc_url: Option<String>,
+
+    /// pools[1] (pool_b) okumaları için ayrı endpoint (HTTP/WSS) — hata → primary
+    pub pool_b_rpc_url: Option<String>,
+
+    /// Canlı yürütmenin ikinci emniyeti: --arm ile yazılan kurma dosyası
+    /// (yürütücü adresi + bitiş zamanı). Yoksa / süresi dolmuşsa gölge mod.
+    pub arm_file: String,
+
+    /// Salt okunur durum uç noktasının adresi (GET /status, /pools,
+    /// /opportunities, /config). None = kapalı
+    pub status_listen_addr: Option<std::net::SocketAddr>,
+
+    /// STATUS_LISTEN_ADDR'in loopback dışı bir arayüze bağlanmasına izin ver
+    pub status_allow_remote: bool,
+
+    /// Kağıt yürütme: gölge modda fırsatlar yürütme hattından (nonce,
+    /// uçuştaki kayıt, geri alma, takılı TX) sahte receipt'lerle geçer.
+    /// Gölge modu zorunlu kılar — hiçbir TX gönderilmez
+    pub paper_execution: bool,
+
+    /// Kağıt TX'in dahil edilip revert etme olasılığı (0..1)
+    pub paper_failure_rate: f64,
+
+    /// Kağıt TX'in receipt'inin hiç gelmeme (takılı kalma) olasılığı (0..1)
+    pub paper_drop_rate: f64,
+
+    /// Kağıt receipt gecikmesi (ms, min-max düz dağılım)
+    pub paper_receipt_latency: crate::paper::LatencyRange,
+
+    /// Blok başına çift değerlendirme bütçesi (ms). Çiftler EV skoruna göre
+    /// sıralanır, bütçe dolunca kalanlar atlanır. 0 = kapalı (yalnızca sıra)
+    pub strategy_time_budget_ms: u64,
+
+    /// Bu kadar blok değerlendirilmeyen çift garanti slotuyla değerlendirilir
+    pub pair_rotation_blocks: u64,
+
+    /// Çift EV skorunun üstel ortalama ağırlığı (0.01..1, yüksek = hızlı unutma)
+    pub pair_score_decay: f64,
+
+    /// Aynı (metot, hedef) okuması tek fırsatta bundan fazla yapılırsa
+    /// RPC census gereksiz okuma uyarısı verir. 0 = kapalı
+    pub redundancy_warn: u32,
+
+    /// Degradation ladder ilk basamağının eşiği (blok bütçesinin yüzdesi).
+    /// Basamaklar bu değerden %100'e eşit aralıklı atılır. 0 = kapalı
+    pub degrade_start_pct: u64,
+
+    /// Atılan basamak, bu kadar ardışık sakin bloktan sonra geri açılır
+    pub degrade_recovery_blocks: u64,
+
+    /// Receipt bu kadar blok sonra yeniden okunur; o zamana kadar işlem
+    /// sonucu geçicidir (reorg'da düzeltme kaydıyla değişir)
+    pub confirmation_blocks: u64,
+
+    /// Tazelik eşiğini gözlenen blok aralığı / senkron süresinden türet
+    pub adaptive_staleness: bool,
+
+    /// Uyarlamalı tazelik kesimlerinin alt sınırı (ms)
+    pub staleness_min_ms: u64,
+
+    /// Uyarlamalı tazelik kesimlerinin üst sınırı (ms)
+    pub staleness_max_ms: u64,
+
+    /// Yürütme kesimi = tespit kesimi × bu oran
+    pub staleness_exec_fraction: f64,
+
+    /// Gölge fırsat bu kadar blok sonra zincirde yeniden fiyatlanır (0 = kapalı)
+    pub shadow_verify_blocks: u64,
+
+    /// Dakikada en fazla doğrulanan gölge fırsat (aşanlar düşürülür)
+    pub shadow_verify_per_min: u32,
+
+    /// Artımlı bitmap tazelemesi arasında bu kadar blokta bir tam tarama (kayma sınırı)
+    pub tick_bitmap_full_resync_blocks: u64,
+
+    /// Yalnızca GET /metrics sunan Prometheus dinleyicisi. None = kapalı
+    /// (STATUS_LISTEN_ADDR'deki /metrics yine çalışır)
+    pub metrics_listen_addr: Option<std::net::SocketAddr>,
+
+    /// İki havuzda bitmap varken f64 optimumu bu boyutu (WETH) aşarsa
+    /// optimum U256 exact kâr üzerinde yeniden aranır
+    pub exact_sizing_min_weth: f64,
+
+    /// Pending swap tahmini, zincir başı taban bloğundan en fazla bu kadar
+    /// ilerideyken onaylı state yerine kullanılır
+    pub predicted_state_trust_blocks: u64,
+
+    /// Global circuit breaker tetiklenince değerlendirme bu kadar saniye
+    /// durur; sonra tek bir deneme ile yeniden açılır
+    pub circuit_breaker_cooldown_secs: u64,
+
+    /// true: breaker tetiklenince süreç exit(1) ile kapanır (eski davranış)
+    pub circuit_breaker_hard_exit: bool,
+
+    /// Terminal çıktı biçimi: pretty (emoji) | json (satır başına bir nesne)
+    pub log_format: LogFormat,
 }
 
+/// Salt okunur dinleyici adresi (durum / metrik). Varsayılan olarak yalnızca
+/// loopback; dışa açmak STATUS_ALLOW_REMOTE=true ister.
+fn listen_addr(env: &mut EnvLoader, var: &'static str, allow_remote: bool) -> Option<std::net::SocketAddr> {
+    let raw = env.optional_string(var, None)?;
+    match raw.parse::<std::net::SocketAddr>() {
+        Ok(addr) if addr.ip().is_loopback() || allow_remote => Some(addr),
+        Ok(addr) => {
+            env.issue(
+                var,
+                format!("'{}' is not a loopback address", addr),
+                "use 127.0.0.1:<port>, or set STATUS_ALLOW_REMOTE=true to expose it",
+            );
+            None
+        }
+        Err(_) => {
+            env.issue(
+                var,
+                format!("'{}' is not a socket address", raw),
+                "expected ip:port, e.g. 127.0.0.1:9090",
+            );
+            None
+        }
+    }
+}
+
+/// Toplu doğrulama raporundaki ipuçları
+const F64_HINT: &str = "expected a decimal number, e.g. 0.25";
+const UINT_HINT: &str = "expected a non-negative integer";
+
 /// Hard-limit fee tier sabiti (basis points). Bu değer üzerindeki havuzlar
 /// takibe alınmaz. %0.05 = 5 bps.
 pub const MAX_FEE_TIER_BPS: u32 = 5;
 
 impl BotConfig {
-    /// .env dosyasından yapılandırmayı oku
+    /// .env dosyasından yapılandırmayı oku.
+    /// Tüm sorunlar tek bir hata raporunda toplanır; varsayılana düşen
+    /// değişkenler başlangıçta tek tek yazdırılır.
     pub fn from_env() -> Result<Self> {
-        let rpc_wss_url = std::env::var("RPC_WSS_URL")
-            .map_err(|_| eyre::eyre!("RPC_WSS_URL must be defined in .env!"))?;
-
-        if rpc_wss_url.is_empty() || rpc_wss_url.starts_with("wss://your-") {
-            return Err(eyre::eyre!("RPC_WSS_URL must be a valid URL!"));
+        let (config, defaults) = Self::load_from_env()?;
+        for line in &defaults {
+            println!("  ℹ️  Config: {}", line);
         }
+        Ok(config)
+    }
 
-        // v15.0: Yedek RPC URL (opsiyonel)
-        let rpc_wss_url_backup = std::env::var("RPC_WSS_URL_BACKUP")
-            .ok()
-            .filter(|u| !u.is_empty() && !u.starts_with("wss://your-"));
+    /// Yapılandırmayı oku — (config, "using default ..." satırları).
+    /// Geçersiz/eksik değişkenlerin hepsi tek bir eyre hatasında listelenir.
+    pub fn load_from_env() -> Result<(Self, Vec<String>)> {
+        Self::load_with(EnvLoader::new())
+    }
 
-        let rpc_http_url = std::env::var("RPC_HTTP_URL")
-            .map_err(|_| eyre::eyre!("RPC_HTTP_URL must be defined in .env!"))?;
+    /// load_from_env ile aynı, ama `overlay` değerleri süreç ortamını ezer
+    /// (SECONDARY_CONFIG_PATH — A/B gölge varyantı)
+    pub fn load_with_overlay(overlay: Vec<(String, String)>) -> Result<(Self, Vec<String>)> {
+        Self::load_with(EnvLoader::with_overlay(overlay))
+    }
 
-        if rpc_http_url.is_empty() || rpc_http_url.starts_with("https://your-") {
-            return Err(eyre::eyre!("RPC_HTTP_URL must be a valid URL!"));
-        }
+    fn load_with(mut env: EnvLoader) -> Result<(Self, Vec<String>)> {
+
+        let rpc_wss_url = env.required_url("RPC_WSS_URL", &["wss://", "ws://"], "wss://your-");
+
+        // v15.0: Yedek RPC URL (opsiyonel)
+        let rpc_wss_url_backup = env.optional_string("RPC_WSS_URL_BACKUP", Some("wss://your-"));
+
+        let rpc_http_url =
+            env.required_url("RPC_HTTP_URL", &["https://", "http://"], "https://your-");
 
-        let private_key = std::env::var("PRIVATE_KEY")
-            .ok()
-            .filter(|pk| !pk.is_empty() && pk != "your-private-key-here");
+        let private_key = env.optional_string("PRIVATE_KEY", Some("your-private-key-here"));
 
-        let contract_address = std::env::var("ARBITRAGE_CONTRACT_ADDRESS")
-            .ok()
-            .filter(|addr| !addr.is_empty() && addr != "0xYourContractAddress")
-            .and_then(|addr| addr.parse::<Address>().ok());
+        let contract_address =
+            env.optional_address("ARBITRAGE_CONTRACT_ADDRESS", Some("0xYourContractAddress"));
 
-        // ── WETH Adresi (Base sabit) ─────────────────────────────
-        // v12.0: Legacy env var'lar (WETH_ADDRESS, QUOTE_TOKEN_*,
-        // WETH_IS_TOKEN0, TOKEN0_DECIMALS, TOKEN1_DECIMALS) görmezden geliniyor.
-        // Havuz bazlı token bilgileri matched_pools.json'dan geliyor.
-        let weth_address: Address = address!("4200000000000000000000000000000000000006");
+        // ── Zincir Profili ───────────────────────────────────────
+        // CHAIN_ID, WETH/USDC, L1 fee oracle, Aave Pool ve priority fee
+        // stratejisi profil tablosundan; her biri env ile ezilebilir.
+        // Legacy QUOTE_TOKEN_*, WETH_IS_TOKEN0, TOKEN*_DECIMALS görmezden
+        // geliniyor — havuz bazlı token bilgileri matched_pools.json'dan.
+        let chain = ChainSettings::load(&mut env);
 
-        let gas_cost_fallback_weth = Self::parse_env_f64("GAS_COST_FALLBACK_WETH", 0.00005);
-        let flash_loan_fee_bps = Self::parse_env_f64("FLASH_LOAN_FEE_BPS", 0.0);
+        let gas_cost_fallback_weth = env.parse_or("GAS_COST_FALLBACK_WETH", 0.00005, F64_HINT);
+        let flash_loan_fee_bps = env.parse_or("FLASH_LOAN_FEE_BPS", 0.0, F64_HINT);
         // v26.0: Default 0.001 → 0.000005 WETH (Base L2 micro-profit strategy)
         // L2 gas is ~$0.01, collect frequent micro profits instead of rare large ones
-        let min_net_profit_weth = Self::parse_env_f64("MIN_NET_PROFIT_WETH", 0.000005);
+        let min_net_profit_weth = env.parse_or("MIN_NET_PROFIT_WETH", 0.000005, F64_HINT);
         // v30.0: Dinamik ROI esigi -- islem hacminin yuzde kaci kadar net kar gerekli
-        let min_profit_roi = Self::parse_env_f64("MIN_PROFIT_ROI", 0.0005); // %0.05
+        let min_profit_roi = env.parse_or("MIN_PROFIT_ROI", 0.0005, F64_HINT); // %0.05
         // v28.0: Default 50.0 → 5.0 WETH. Base L2 havuz derinlikleri genelde
         // 0.05-2 WETH aralığındadır. Bot effective_cap ile sınırlar ama yüksek
         // default NR tarama aralığını şişirir ve hesaplama süresi harcar.
-        let max_trade_size_weth = Self::parse_env_f64("MAX_TRADE_SIZE_WETH", 5.0);
+        let max_trade_size_weth = env.parse_or("MAX_TRADE_SIZE_WETH", 5.0, F64_HINT);
+        // Toz boyutlarda kâr birkaç yüz wei'dir, gas her zaman aşar
+        let min_trade_size_weth = env.parse_in_range("MIN_TRADE_SIZE_WETH", 0.01, 0.0001, 1.0, F64_HINT);
+        if min_trade_size_weth >= max_trade_size_weth {
+            env.issue(
+                "MIN_TRADE_SIZE_WETH",
+                format!("'{}' is not below MAX_TRADE_SIZE_WETH ({})", min_trade_size_weth, max_trade_size_weth),
+                "must be smaller than MAX_TRADE_SIZE_WETH",
+            );
+        }
 
-        let stats_interval = std::env::var("STATS_INTERVAL")
-            .unwrap_or_else(|_| "10".into())
-            .parse::<u64>()
-            .unwrap_or(10);
+        let stats_interval = env.parse_or("STATS_INTERVAL", 10u64, UINT_HINT);
 
-        let max_retries = std::env::var("MAX_RETRIES")
-            .unwrap_or_else(|_| "0".into())
-            .parse::<u32>()
-            .unwrap_or(0);
+        let max_retries = env.parse_or("MAX_RETRIES", 0u32, UINT_HINT);
 
         // v28.0: Default 2000 → 3000ms (SYNC_TIMEOUT_MS ile uyumlu)
-        let max_staleness_ms = std::env::var("MAX_STALENESS_MS")
-            .unwrap_or_else(|_| "3000".into())
-            .parse::<u128>()
-            .unwrap_or(3000);
-
-        let chain_id = std::env::var("CHAIN_ID")
-            .unwrap_or_else(|_| "8453".into())
-            .parse::<u64>()
-            .unwrap_or(8453);
+        let max_staleness_ms = env.parse_or("MAX_STALENESS_MS", 3000u128, UINT_HINT);
+        let staleness_min_ms = env.parse_in_range("STALENESS_MIN_MS", 300u64, 50, 60_000, UINT_HINT);
+        let staleness_max_ms = env.parse_in_range("STALENESS_MAX_MS", 3000u64, 50, 60_000, UINT_HINT);
+        if staleness_min_ms > staleness_max_ms {
+            env.issue(
+                "STALENESS_MIN_MS",
+                format!("'{}' is above STALENESS_MAX_MS ({})", staleness_min_ms, staleness_max_ms),
+                "must not exceed STALENESS_MAX_MS",
+            );
+        }
 
         // ── IPC ve Transport Ayarları ─────────────────────────────
-        let rpc_ipc_path = std::env::var("RPC_IPC_PATH")
-            .ok()
-            .filter(|p| !p.is_empty());
-
-        let transport_mode = match std::env::var("TRANSPORT_MODE")
-            .unwrap_or_else(|_| "auto".into())
-            .to_lowercase()
-            .as_str()
-        {
-            "ipc" => TransportMode::Ipc,
-            "ws" | "wss" | "websocket" => TransportMode::Ws,
-            "http" | "https" => TransportMode::Http,
-            _ => TransportMode::Auto,
-        };
+        let rpc_ipc_path = env.optional_string("RPC_IPC_PATH", None);
+
+        let transport_mode = env.choice(
+            "TRANSPORT_MODE",
+            &[
+                ("auto", TransportMode::Auto),
+                ("ipc", TransportMode::Ipc),
+                ("ws", TransportMode::Ws),
+                ("wss", TransportMode::Ws),
+                ("websocket", TransportMode::Ws),
+                ("http", TransportMode::Http),
+                ("https", TransportMode::Http),
+            ],
+            ("auto", TransportMode::Auto),
+        );
+        if transport_mode == TransportMode::Ipc && rpc_ipc_path.is_none() {
+            env.issue(
+                "TRANSPORT_MODE",
+                "'ipc' needs an IPC socket but RPC_IPC_PATH is not set",
+                "set RPC_IPC_PATH=/path/to/node.ipc, or use TRANSPORT_MODE=auto",
+            );
+        }
 
         // ── TickBitmap Ayarları ───────────────────────────────────
         // v26.0: Default 500 → 100. Arbitrage never moves price >5%.
         // Reduces RPC payload ~80%, cuts parsing overhead significantly.
-        let tick_bitmap_range = std::env::var("TICK_BITMAP_RANGE")
-            .unwrap_or_else(|_| "100".into())
-            .parse::<u32>()
-            .unwrap_or(100);
+        let tick_bitmap_range = env.parse_or("TICK_BITMAP_RANGE", 100u32, UINT_HINT);
 
-        let tick_bitmap_max_age_blocks = std::env::var("TICK_BITMAP_MAX_AGE_BLOCKS")
-            .unwrap_or_else(|_| "5".into())
-            .parse::<u64>()
-            .unwrap_or(5);
+        let tick_bitmap_max_age_blocks = env.parse_or("TICK_BITMAP_MAX_AGE_BLOCKS", 5u64, UINT_HINT);
 
         // ── Gölge Modu (Shadow Mode) ─────────────────────────────
         // EXECUTION_ENABLED=true → gerçek TX gönder
         // EXECUTION_ENABLED=false veya tanımsız → sadece logla
-        let execution_enabled_flag = std::env::var("EXECUTION_ENABLED")
-            .unwrap_or_else(|_| "false".into())
-            .to_lowercase()
-            .parse::<bool>()
-            .unwrap_or(false);
+        let execution_enabled_flag = env.bool_or("EXECUTION_ENABLED", false);
 
         // ── v9.0: Yeni Güvenlik ve Performans Ayarları ───────────
 
         // Admin adresi (fon çekme yetkisi — kontrat v9.0)
-        let admin_address = std::env::var("ADMIN_ADDRESS")
-            .ok()
-            .filter(|addr| !addr.is_empty())
-            .and_then(|addr| addr.parse::<Address>().ok());
-
-        // Deadline block offset (varsayılan: 2 blok)
-        let deadline_blocks = std::env::var("DEADLINE_BLOCKS")
-            .unwrap_or_else(|_| "2".into())
-            .parse::<u32>()
-            .unwrap_or(2);
-
-        // Dinamik bribe yüzdesi (varsayılan: %25)
-        let bribe_pct = Self::parse_env_f64("BRIBE_PCT", 0.25);
-
-        // v10.1: Circuit breaker eşiği (varsayılan: 3)
-        let circuit_breaker_threshold = std::env::var("CIRCUIT_BREAKER_THRESHOLD")
-            .unwrap_or_else(|_| "3".into())
-            .parse::<u32>()
-            .unwrap_or(3);
+        let admin_address = env.optional_address("ADMIN_ADDRESS", None);
+
+        // Deadline block offset (varsayılan: 2 blok, 1..=100)
+        // 0 → işlem aynı blokta geçersiz; 100+ → bayat fiyatla dahil edilme riski
+        let deadline_blocks = env.parse_in_range("DEADLINE_BLOCKS", 2u32, 1, 100, UINT_HINT);
+
+        // Dinamik bribe yüzdesi (varsayılan: %25, 0..=0.9)
+        // 0.9 üstü kârın neredeyse tamamını builder'a bırakır
+        let bribe_pct = env.parse_in_range("BRIBE_PCT", 0.25, 0.0, 0.9, F64_HINT);
+
+        // v10.1: Circuit breaker eşiği (varsayılan: 3, en az 1)
+        let circuit_breaker_threshold =
+            env.parse_in_range("CIRCUIT_BREAKER_THRESHOLD", 3u32, 1, u32::MAX, UINT_HINT);
 
         // Şifreli keystore dosya yolu
-        let keystore_path = std::env::var("KEYSTORE_PATH")
-            .ok()
-            .filter(|p| !p.is_empty());
+        let keystore_path = env.optional_string("KEYSTORE_PATH", None);
+
+        let sequencer_ws_url = env.optional_string("SEQUENCER_WS_URL", Some("wss://your-"));
+        if let Some(url) = sequencer_ws_url.as_deref() {
+            if !url.starts_with("wss://") && !url.starts_with("ws://") {
+                env.issue(
+                    "SEQUENCER_WS_URL",
+                    format!("'{}' is not a valid URL", redact_url(url)),
+                    "must start with wss:// or ws://",
+                );
+            }
+        }
 
-        Ok(Self {
+        // Durum uç noktası varsayılan olarak yalnızca localhost'a bağlanır
+        let status_allow_remote = env.bool_or("STATUS_ALLOW_REMOTE", false);
+        let status_listen_addr = listen_addr(&mut env, "STATUS_LISTEN_ADDR", status_allow_remote);
+        let metrics_listen_addr = listen_addr(&mut env, "METRICS_LISTEN_ADDR", status_allow_remote);
+
+        let config = Self {
             rpc_wss_url,
             rpc_http_url,
             rpc_ipc_path,
             transport_mode,
             private_key,
             contract_address,
-            weth_address,
+            contract_pause_selector: env.parse_or(
+                "CONTRACT_PAUSE_SELECTOR",
+                PauseSelector::DEFAULT,
+                "expected a parameterless view signature like paused() or a 4-byte selector like 0x5c975abb",
+            ),
+            weth_address: chain.weth,
+            usdc_address: chain.usdc,
             gas_cost_fallback_weth,
             flash_loan_fee_bps,
             min_net_profit_weth,
@@ -793,7 +2146,11 @@ impl BotConfig {
             max_retry_delay_secs: 60,
             max_staleness_ms,
             max_trade_size_weth,
-            chain_id,
+            min_trade_size_weth,
+            chain_profile: chain.profile,
+            chain_id: chain.chain_id,
+            l1_fee_oracle: chain.l1_fee_oracle,
+            priority_fee_strategy: chain.priority_fee,
             tick_bitmap_range,
             tick_bitmap_max_age_blocks,
             execution_enabled_flag,
@@ -801,53 +2158,413 @@ impl BotConfig {
             deadline_blocks,
             bribe_pct,
             keystore_path,
-            key_manager_active: false, // main.rs'de KeyManager başlatıldıktan sonra güncellenir
+            key_source: KeySource::None, // attach_key_manager ile doldurulur
             circuit_breaker_threshold,
             rpc_wss_url_backup,
-            latency_spike_threshold_ms: Self::parse_env_f64("LATENCY_SPIKE_THRESHOLD_MS", 200.0),
-            private_rpc_url: std::env::var("PRIVATE_RPC_URL")
-                .ok()
-                .filter(|u| !u.is_empty()),
+            latency_spike_threshold_ms: env.parse_or("LATENCY_SPIKE_THRESHOLD_MS", 200.0, F64_HINT),
+            runtime_stall_threshold_ms: env.parse_in_range("RUNTIME_STALL_THRESHOLD_MS", 50u64, 0, 10_000, UINT_HINT),
+            private_rpc_url: env.optional_string("PRIVATE_RPC_URL", None),
             rpc_wss_url_extra: {
-                let mut extras = Vec::new();
                 // RPC_WSS_URL_2, RPC_WSS_URL_3 opsiyonel ek endpoint'ler
-                for key in &["RPC_WSS_URL_2", "RPC_WSS_URL_3"] {
-                    if let Ok(url) = std::env::var(key) {
-                        if !url.is_empty() && !url.starts_with("wss://your-") {
-                            extras.push(url);
-                        }
-                    }
-                }
-                extras
+                ["RPC_WSS_URL_2", "RPC_WSS_URL_3"]
+                    .into_iter()
+                    .filter_map(|key| env.optional_string(key, Some("wss://your-")))
+                    .collect()
             },
-            max_pool_fee_bps: std::env::var("MAX_POOL_FEE_BPS")
-                .unwrap_or_else(|_| MAX_FEE_TIER_BPS.to_string())
-                .parse::<u32>()
-                .unwrap_or(MAX_FEE_TIER_BPS),
-            min_tvl_usd: Self::parse_env_f64("MIN_TVL_USD", 1_000_000.0),
-            min_volume_24h_usd: Self::parse_env_f64("MIN_VOLUME_24H_USD", 500_000.0),
-            max_tracked_pools: std::env::var("MAX_TRACKED_POOLS")
-                .unwrap_or_else(|_| "4".into())
-                .parse::<usize>()
-                .unwrap_or(4),
+            max_pool_fee_bps: env.parse_or("MAX_POOL_FEE_BPS", MAX_FEE_TIER_BPS, UINT_HINT),
+            min_tvl_usd: env.parse_or("MIN_TVL_USD", 1_000_000.0, F64_HINT),
+            min_volume_24h_usd: env.parse_or("MIN_VOLUME_24H_USD", 500_000.0, F64_HINT),
+            max_tracked_pools: env.parse_or("MAX_TRACKED_POOLS", 4usize, UINT_HINT),
             // ── v32.0: Telegram Telemetri ──
-            telegram_bot_token: std::env::var("TELEGRAM_BOT_TOKEN")
-                .ok()
-                .filter(|t| !t.is_empty()),
-            telegram_chat_id: std::env::var("TELEGRAM_CHAT_ID")
-                .ok()
-                .filter(|c| !c.is_empty()),
-            telegram_enabled: std::env::var("TELEGRAM_ENABLED")
-                .unwrap_or_else(|_| "false".into())
-                .to_lowercase()
-                .parse::<bool>()
-                .unwrap_or(false),
-            telegram_shift_interval_secs: std::env::var("TELEGRAM_SHIFT_INTERVAL_SECS")
-                .unwrap_or_else(|_| "21600".into())
-                .parse::<u64>()
-                .unwrap_or(21600),
-            telegram_balance_warn_eth: Self::parse_env_f64("TELEGRAM_BALANCE_WARN_ETH", 0.05),
-        })
+            telegram_bot_token: env.optional_string("TELEGRAM_BOT_TOKEN", None),
+            telegram_chat_id: env.optional_string("TELEGRAM_CHAT_ID", None),
+            telegram_enabled: env.bool_or("TELEGRAM_ENABLED", false),
+            telegram_shift_interval_secs: env.parse_or(
+                "TELEGRAM_SHIFT_INTERVAL_SECS",
+                21600u64,
+                UINT_HINT,
+            ),
+            telegram_balance_warn_eth: env.parse_or("TELEGRAM_BALANCE_WARN_ETH", 0.05, F64_HINT),
+            snapshot_keep: env.parse_or("SNAPSHOT_KEEP", 200usize, UINT_HINT),
+            aave_pool_address: chain.aave_pool,
+            auto_update_fees: env.bool_or("AUTO_UPDATE_FEES", false),
+            competition_routers: env.address_list("COMPETITION_ROUTERS"),
+            competition_min_txs: env.parse_or("COMPETITION_MIN_TXS", 2u32, UINT_HINT),
+            competition_profit_mult: env.parse_or("COMPETITION_PROFIT_MULT", 2.0, F64_HINT),
+            competition_skip_limit: env.parse_or("COMPETITION_SKIP_LIMIT", 4u32, UINT_HINT),
+            sim_math_max_discrepancy: env.parse_or("SIM_MATH_MAX_DISCREPANCY", 0u128, UINT_HINT),
+            liquidity_analytics_budget_ms: env.parse_or(
+                "LIQUIDITY_ANALYTICS_BUDGET_MS",
+                300u64,
+                UINT_HINT,
+            ),
+            opportunity_filters: env
+                .optional_string("OPPORTUNITY_FILTERS", None)
+                .unwrap_or_default()
+                .split(',')
+                .map(|f| f.trim().to_lowercase())
+                .filter(|f| !f.is_empty())
+                .collect(),
+            require_bitmap_for_execution: env.bool_or("REQUIRE_BITMAP_FOR_EXECUTION", true),
+            sequencer_ws_url,
+            adverse_frontrun: env.parse_or(
+                "ADVERSE_FRONTRUN_WETH",
+                FrontrunSize::PctOfTrade(25.0),
+                "expected WETH amount (0.5) or percent of trade size (25%)",
+            ),
+            require_adverse_positive: env.bool_or("REQUIRE_ADVERSE_POSITIVE", false),
+            min_robustness: env.parse_in_range("MIN_ROBUSTNESS", 0.0, 0.0, 1.0, F64_HINT),
+            warmup_clean_blocks: env.parse_or("WARMUP_CLEAN_BLOCKS", 3u32, UINT_HINT),
+            summary_dir: env
+                .optional_string("SUMMARY_DIR", None)
+                .unwrap_or_else(|| "session_summaries".into()),
+            spread_probe_size_weth: env.parse_or("SPREAD_PROBE_SIZE_WETH", 0.1, F64_HINT),
+            sizing_backend: env.choice(
+                "SIZING_BACKEND",
+                &[
+                    ("math", SizingBackend::Math),
+                    ("revm", SizingBackend::Revm),
+                    ("auto", SizingBackend::Auto),
+                ],
+                ("math", SizingBackend::Math),
+            ),
+            sizing_numerics: env.choice(
+                "SIZING_NUMERICS",
+                &[("f64", SizingNumerics::F64), ("fixed", SizingNumerics::Fixed)],
+                ("f64", SizingNumerics::F64),
+            ),
+            two_step_sizing: env.bool_or("TWO_STEP_SIZING", false),
+            execute_followup: env.bool_or("EXECUTE_FOLLOWUP", false),
+            optimistic_update_log: env.bool_or("OPTIMISTIC_UPDATE_LOG", false),
+            allow_nonstandard_tokens: env.bool_or("ALLOW_NONSTANDARD_TOKENS", false),
+            secondary_config_path: env.optional_string("SECONDARY_CONFIG_PATH", None),
+            approval_audit_mode: env.choice(
+                "APPROVAL_AUDIT_MODE",
+                &[
+                    ("off", ApprovalAuditMode::Off),
+                    ("soft", ApprovalAuditMode::Soft),
+                    ("hard", ApprovalAuditMode::Hard),
+                ],
+                ("soft", ApprovalAuditMode::Soft),
+            ),
+            approval_required_spenders: env.choice(
+                "APPROVAL_REQUIRED_SPENDERS",
+                &[
+                    ("none", RequiredSpenders::None),
+                    ("flash_lender", RequiredSpenders::FlashLender),
+                    ("pools", RequiredSpenders::Pools),
+                    ("all", RequiredSpenders::All),
+                ],
+                ("none", RequiredSpenders::None),
+            ),
+            state_diff_log: env.bool_or("STATE_DIFF_LOG", false),
+            log_format_binary: env.bool_or("LOG_FORMAT_BINARY", false),
+            process_all_blocks: env.bool_or("PROCESS_ALL_BLOCKS", false),
+            fast_path_sync: env.bool_or("FAST_PATH_SYNC", true),
+            discover_constant_product: env.bool_or("DISCOVER_CONSTANT_PRODUCT", false),
+            full_sync_interval_blocks: env.parse_in_range("FULL_SYNC_INTERVAL_BLOCKS", 50u64, 1, 10_000, UINT_HINT),
+            gas_estimate: env.parse_in_range("GAS_ESTIMATE", 350_000u64, 21_000, 1_500_000, UINT_HINT),
+            multicall_retries: env.parse_in_range("MULTICALL_RETRIES", 2u32, 0, 5, UINT_HINT),
+            gas_buffer_mode: env.choice(
+                "GAS_BUFFER_MODE",
+                &[("fixed", GasBufferMode::Fixed), ("auto", GasBufferMode::Auto)],
+                ("fixed", GasBufferMode::Fixed),
+            ),
+            min_calibration_samples: env.parse_in_range("MIN_CALIBRATION_SAMPLES", 20u32, 1, 10_000, UINT_HINT),
+            gas_base_overhead: env.parse_in_range("GAS_BASE_OVERHEAD", 160_000u64, 21_000, 1_500_000, UINT_HINT),
+            gas_per_tick_crossing: env.parse_in_range("GAS_PER_TICK_CROSSING", 20_000u64, 0, 200_000, UINT_HINT),
+            gas_crossing_calibration: env.bool_or("GAS_CROSSING_CALIBRATION", true),
+            impact_warn_ticks: env.parse_in_range("IMPACT_WARN_TICKS", 10u32, 1, 100_000, UINT_HINT),
+            impact_slippage_bump_bps: env.parse_in_range("IMPACT_SLIPPAGE_BUMP_BPS", 0u64, 0, 2_000, UINT_HINT),
+            impact_bump_blocks: env.parse_in_range("IMPACT_BUMP_BLOCKS", 300u64, 1, 100_000, UINT_HINT),
+            audit_interval_blocks: env.parse_in_range("AUDIT_INTERVAL_BLOCKS", 500u64, 0, 1_000_000, UINT_HINT),
+            audit_max_deviation_bps: env.parse_in_range("AUDIT_MAX_DEVIATION_BPS", 10.0, 0.0, 10_000.0, F64_HINT),
+            event_divergence_ticks: env.parse_in_range("EVENT_DIVERGENCE_TICKS", 1u32, 0, 1_000, UINT_HINT),
+            bytecode_recheck_blocks: env.parse_in_range("BYTECODE_RECHECK_BLOCKS", 1000u64, 0, 1_000_000, UINT_HINT),
+            max_inflight_executions: env.parse_in_range("MAX_INFLIGHT_EXECUTIONS", 2u32, 1, 16, UINT_HINT),
+            direction_gate_window: env.parse_in_range("DIRECTION_GATE_WINDOW", 20u32, 0, 1_000, UINT_HINT),
+            direction_gate_min_attempts: env.parse_in_range("DIRECTION_GATE_MIN_ATTEMPTS", 5u32, 1, 1_000, UINT_HINT),
+            direction_gate_min_success_rate: env.parse_in_range("DIRECTION_GATE_MIN_SUCCESS_RATE", 0.3, 0.0, 1.0, F64_HINT),
+            direction_gate_mode: env.choice(
+                "DIRECTION_GATE_MODE",
+                &[("multiplier", DirectionGateMode::Multiplier), ("suspend", DirectionGateMode::Suspend)],
+                ("multiplier", DirectionGateMode::Multiplier),
+            ),
+            direction_gate_profit_multiplier: env.parse_in_range("DIRECTION_GATE_PROFIT_MULTIPLIER", 2.0, 1.0, 100.0, F64_HINT),
+            direction_gate_cooldown_blocks: env.parse_in_range("DIRECTION_GATE_COOLDOWN_BLOCKS", 1800u64, 1, 1_000_000, UINT_HINT),
+            direction_gate_edge_widen: env.parse_in_range("DIRECTION_GATE_EDGE_WIDEN", 1.5, 1.0, 100.0, F64_HINT),
+            persistent_spread_blocks: env.parse_in_range("PERSISTENT_SPREAD_BLOCKS", 300u64, 0, 1_000_000, UINT_HINT),
+            persistent_spread_floor_pct: env.parse_in_range("PERSISTENT_SPREAD_FLOOR_PCT", 0.5, 0.01, 100.0, F64_HINT),
+            persistent_spread_reset_pct: env.parse_in_range("PERSISTENT_SPREAD_RESET_PCT", 0.1, 0.0, 100.0, F64_HINT),
+            submit_jitter_ms: env.parse_or(
+                "SUBMIT_JITTER_MS",
+                JitterRange::OFF,
+                "expected max or min-max in ms (<= 1000), e.g. 0-15",
+            ),
+            min_submit_spacing_ms: env.parse_in_range("MIN_SUBMIT_SPACING_MS", 0u64, 0, 1_000, UINT_HINT),
+            revisit_top_k: env.parse_in_range("REVISIT_TOP_K", 8usize, 0, 64, UINT_HINT),
+            revisit_quiet_blocks: env.parse_in_range("REVISIT_QUIET_BLOCKS", 30u64, 1, 100_000, UINT_HINT),
+            revisit_budget_ms: env.parse_in_range("REVISIT_BUDGET_MS", 250u64, 10, 10_000, UINT_HINT),
+            pool_a_rpc_url: env.optional_string("POOL_A_RPC_URL", None),
+            pool_b_rpc_url: env.optional_string("POOL_B_RPC_URL", None),
+            arm_file: env
+                .optional_string("ARM_FILE", None)
+                .unwrap_or_else(|| "ARMED".into()),
+            status_listen_addr,
+            status_allow_remote,
+            paper_execution: env.bool_or("PAPER_EXECUTION", false),
+            paper_failure_rate: env.parse_in_range("PAPER_FAILURE_RATE", 0.1, 0.0, 1.0, F64_HINT),
+            paper_drop_rate: env.parse_in_range("PAPER_DROP_RATE", 0.02, 0.0, 1.0, F64_HINT),
+            paper_receipt_latency: env.parse_or(
+                "PAPER_RECEIPT_LATENCY_MS",
+                crate::paper::LatencyRange::DEFAULT,
+                "expected max or min-max in ms (<= 10000), e.g. 300-2500",
+            ),
+            strategy_time_budget_ms: env.parse_in_range("STRATEGY_TIME_BUDGET_MS", 400u64, 0, 1_500, UINT_HINT),
+            pair_rotation_blocks: env.parse_in_range("PAIR_ROTATION_BLOCKS", 20u64, 1, 10_000, UINT_HINT),
+            pair_score_decay: env.parse_in_range("PAIR_SCORE_DECAY", 0.1, 0.01, 1.0, F64_HINT),
+            redundancy_warn: env.parse_in_range("REDUNDANCY_WARN", 2u32, 0, 1_000, UINT_HINT),
+            degrade_start_pct: env.parse_in_range("DEGRADE_START_PCT", 50u64, 0, 95, UINT_HINT),
+            degrade_recovery_blocks: env.parse_in_range("DEGRADE_RECOVERY_BLOCKS", 5u64, 1, 1_000, UINT_HINT),
+            confirmation_blocks: env.parse_in_range("CONFIRMATION_BLOCKS", 10u64, 1, 1_000, UINT_HINT),
+            adaptive_staleness: env.bool_or("ADAPTIVE_STALENESS", true),
+            staleness_min_ms,
+            staleness_max_ms,
+            staleness_exec_fraction: env.parse_in_range("STALENESS_EXEC_FRACTION", 0.75, 0.1, 1.0, F64_HINT),
+            shadow_verify_blocks: env.parse_in_range("SHADOW_VERIFY_BLOCKS", 2u64, 0, 100, UINT_HINT),
+            shadow_verify_per_min: env.parse_in_range("SHADOW_VERIFY_PER_MIN", 30u32, 1, 600, UINT_HINT),
+            tick_bitmap_full_resync_blocks: env.parse_in_range("TICK_BITMAP_FULL_RESYNC_BLOCKS", 600u64, 1, 100_000, UINT_HINT),
+            metrics_listen_addr,
+            exact_sizing_min_weth: env.parse_in_range("EXACT_SIZING_MIN_WETH", 1.0, 0.0, 1000.0, F64_HINT),
+            predicted_state_trust_blocks: env.parse_in_range("PREDICTED_STATE_TRUST_BLOCKS", 1u64, 0, 10, UINT_HINT),
+            circuit_breaker_cooldown_secs: env.parse_in_range("CIRCUIT_BREAKER_COOLDOWN_SECS", 900u64, 1, 86_400, UINT_HINT),
+            circuit_breaker_hard_exit: env.bool_or("CIRCUIT_BREAKER_HARD_EXIT", false),
+            log_format: env.choice(
+                "LOG_FORMAT",
+                &[("pretty", LogFormat::Pretty), ("json", LogFormat::Json)],
+                ("pretty", LogFormat::Pretty),
+            ),
+        };
+
+        let defaults = env.finish()?;
+        Ok((config, defaults))
+    }
+
+    /// --print-config: çözümlenmiş yapılandırma (gizli değerler maskeli).
+    /// Destek taleplerinde .env'i paylaşmadan etkin ayarları göstermek için.
+    pub fn redacted_entries(&self) -> Vec<(&'static str, String)> {
+        let opt_addr = |a: &Option<Address>| a.map_or("(not set)".to_string(), |a| a.to_string());
+        let opt_str = |s: &Option<String>| s.clone().unwrap_or_else(|| "(not set)".into());
+        vec![
+            ("RPC_WSS_URL", redact_url(&self.rpc_wss_url)),
+            ("RPC_HTTP_URL", redact_url(&self.rpc_http_url)),
+            (
+                "RPC_WSS_URL_BACKUP",
+                self.rpc_wss_url_backup
+                    .as_deref()
+                    .map_or("(not set)".into(), redact_url),
+            ),
+            (
+                "RPC_WSS_URL_EXTRA",
+                self.rpc_wss_url_extra
+                    .iter()
+                    .map(|u| redact_url(u))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            ("RPC_IPC_PATH", opt_str(&self.rpc_ipc_path)),
+            ("TRANSPORT_MODE", format!("{:?}", self.transport_mode)),
+            ("CHAIN_PROFILE", self.chain_profile.to_string()),
+            ("CHAIN_ID", self.chain_id.to_string()),
+            ("WETH_ADDRESS", self.weth_address.to_string()),
+            ("USDC_ADDRESS", self.usdc_address.to_string()),
+            (
+                "L1_FEE_ORACLE_ADDRESS",
+                self.l1_fee_oracle.map_or("none".into(), |a| a.to_string()),
+            ),
+            ("PRIORITY_FEE_STRATEGY", self.priority_fee_strategy.to_string()),
+            ("PRIVATE_KEY", redact_secret(self.private_key.as_deref())),
+            ("KEYSTORE_PATH", opt_str(&self.keystore_path)),
+            ("ARBITRAGE_CONTRACT_ADDRESS", opt_addr(&self.contract_address)),
+            ("CONTRACT_PAUSE_SELECTOR", self.contract_pause_selector.to_string()),
+            ("ADMIN_ADDRESS", opt_addr(&self.admin_address)),
+            (
+                "PRIVATE_RPC_URL",
+                self.private_rpc_url
+                    .as_deref()
+                    .map_or("(not set)".into(), redact_url),
+            ),
+            ("EXECUTION_ENABLED", self.execution_enabled_flag.to_string()),
+            ("GAS_COST_FALLBACK_WETH", self.gas_cost_fallback_weth.to_string()),
+            ("FLASH_LOAN_FEE_BPS", self.flash_loan_fee_bps.to_string()),
+            ("AAVE_POOL_ADDRESS", self.aave_pool_address.to_string()),
+            ("AUTO_UPDATE_FEES", self.auto_update_fees.to_string()),
+            ("MIN_NET_PROFIT_WETH", self.min_net_profit_weth.to_string()),
+            ("MIN_PROFIT_ROI", self.min_profit_roi.to_string()),
+            ("MAX_TRADE_SIZE_WETH", self.max_trade_size_weth.to_string()),
+            ("MIN_TRADE_SIZE_WETH", self.min_trade_size_weth.to_string()),
+            ("MAX_STALENESS_MS", self.max_staleness_ms.to_string()),
+            ("STATS_INTERVAL", self.stats_interval.to_string()),
+            ("MAX_RETRIES", self.max_retries.to_string()),
+            ("TICK_BITMAP_RANGE", self.tick_bitmap_range.to_string()),
+            ("TICK_BITMAP_MAX_AGE_BLOCKS", self.tick_bitmap_max_age_blocks.to_string()),
+            ("DEADLINE_BLOCKS", self.deadline_blocks.to_string()),
+            ("BRIBE_PCT", self.bribe_pct.to_string()),
+            ("CIRCUIT_BREAKER_THRESHOLD", self.circuit_breaker_threshold.to_string()),
+            ("LATENCY_SPIKE_THRESHOLD_MS", self.latency_spike_threshold_ms.to_string()),
+            ("RUNTIME_STALL_THRESHOLD_MS", self.runtime_stall_threshold_ms.to_string()),
+            ("MAX_POOL_FEE_BPS", self.max_pool_fee_bps.to_string()),
+            ("MIN_TVL_USD", self.min_tvl_usd.to_string()),
+            ("MIN_VOLUME_24H_USD", self.min_volume_24h_usd.to_string()),
+            ("MAX_TRACKED_POOLS", self.max_tracked_pools.to_string()),
+            ("TELEGRAM_ENABLED", self.telegram_enabled.to_string()),
+            ("TELEGRAM_BOT_TOKEN", redact_secret(self.telegram_bot_token.as_deref())),
+            ("TELEGRAM_CHAT_ID", redact_secret(self.telegram_chat_id.as_deref())),
+            ("TELEGRAM_SHIFT_INTERVAL_SECS", self.telegram_shift_interval_secs.to_string()),
+            ("TELEGRAM_BALANCE_WARN_ETH", self.telegram_balance_warn_eth.to_string()),
+            ("SNAPSHOT_KEEP", self.snapshot_keep.to_string()),
+            (
+                "COMPETITION_ROUTERS",
+                self.competition_routers
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            ("COMPETITION_MIN_TXS", self.competition_min_txs.to_string()),
+            ("COMPETITION_PROFIT_MULT", self.competition_profit_mult.to_string()),
+            ("COMPETITION_SKIP_LIMIT", self.competition_skip_limit.to_string()),
+            ("SIM_MATH_MAX_DISCREPANCY", self.sim_math_max_discrepancy.to_string()),
+            (
+                "LIQUIDITY_ANALYTICS_BUDGET_MS",
+                self.liquidity_analytics_budget_ms.to_string(),
+            ),
+            ("OPPORTUNITY_FILTERS", self.opportunity_filters.join(",")),
+            (
+                "REQUIRE_BITMAP_FOR_EXECUTION",
+                self.require_bitmap_for_execution.to_string(),
+            ),
+            (
+                "SEQUENCER_WS_URL",
+                self.sequencer_ws_url
+                    .as_deref()
+                    .map_or("(not set)".into(), redact_url),
+            ),
+            ("ADVERSE_FRONTRUN_WETH", self.adverse_frontrun.to_string()),
+            ("REQUIRE_ADVERSE_POSITIVE", self.require_adverse_positive.to_string()),
+            ("MIN_ROBUSTNESS", self.min_robustness.to_string()),
+            ("WARMUP_CLEAN_BLOCKS", self.warmup_clean_blocks.to_string()),
+            ("SUMMARY_DIR", self.summary_dir.clone()),
+            ("SPREAD_PROBE_SIZE_WETH", self.spread_probe_size_weth.to_string()),
+            ("SIZING_BACKEND", self.sizing_backend.to_string()),
+            ("SIZING_NUMERICS", self.sizing_numerics.to_string()),
+            ("TWO_STEP_SIZING", self.two_step_sizing.to_string()),
+            ("EXECUTE_FOLLOWUP", self.execute_followup.to_string()),
+            ("OPTIMISTIC_UPDATE_LOG", self.optimistic_update_log.to_string()),
+            ("ALLOW_NONSTANDARD_TOKENS", self.allow_nonstandard_tokens.to_string()),
+            ("SECONDARY_CONFIG_PATH", opt_str(&self.secondary_config_path)),
+            ("APPROVAL_AUDIT_MODE", self.approval_audit_mode.to_string()),
+            ("APPROVAL_REQUIRED_SPENDERS", self.approval_required_spenders.to_string()),
+            ("STATE_DIFF_LOG", self.state_diff_log.to_string()),
+            ("LOG_FORMAT_BINARY", self.log_format_binary.to_string()),
+            ("PROCESS_ALL_BLOCKS", self.process_all_blocks.to_string()),
+            ("FAST_PATH_SYNC", self.fast_path_sync.to_string()),
+            ("DISCOVER_CONSTANT_PRODUCT", self.discover_constant_product.to_string()),
+            ("FULL_SYNC_INTERVAL_BLOCKS", self.full_sync_interval_blocks.to_string()),
+            ("GAS_ESTIMATE", self.gas_estimate.to_string()),
+            ("MULTICALL_RETRIES", self.multicall_retries.to_string()),
+            ("GAS_BUFFER_MODE", self.gas_buffer_mode.to_string()),
+            ("MIN_CALIBRATION_SAMPLES", self.min_calibration_samples.to_string()),
+            ("GAS_BASE_OVERHEAD", self.gas_base_overhead.to_string()),
+            ("GAS_PER_TICK_CROSSING", self.gas_per_tick_crossing.to_string()),
+            ("GAS_CROSSING_CALIBRATION", self.gas_crossing_calibration.to_string()),
+            ("IMPACT_WARN_TICKS", self.impact_warn_ticks.to_string()),
+            ("IMPACT_SLIPPAGE_BUMP_BPS", self.impact_slippage_bump_bps.to_string()),
+            ("IMPACT_BUMP_BLOCKS", self.impact_bump_blocks.to_string()),
+            ("AUDIT_INTERVAL_BLOCKS", self.audit_interval_blocks.to_string()),
+            ("AUDIT_MAX_DEVIATION_BPS", self.audit_max_deviation_bps.to_string()),
+            ("EVENT_DIVERGENCE_TICKS", self.event_divergence_ticks.to_string()),
+            ("BYTECODE_RECHECK_BLOCKS", self.bytecode_recheck_blocks.to_string()),
+            ("MAX_INFLIGHT_EXECUTIONS", self.max_inflight_executions.to_string()),
+            ("DIRECTION_GATE_WINDOW", self.direction_gate_window.to_string()),
+            ("DIRECTION_GATE_MIN_ATTEMPTS", self.direction_gate_min_attempts.to_string()),
+            ("DIRECTION_GATE_MIN_SUCCESS_RATE", self.direction_gate_min_success_rate.to_string()),
+            ("DIRECTION_GATE_MODE", self.direction_gate_mode.to_string()),
+            ("DIRECTION_GATE_PROFIT_MULTIPLIER", self.direction_gate_profit_multiplier.to_string()),
+            ("DIRECTION_GATE_COOLDOWN_BLOCKS", self.direction_gate_cooldown_blocks.to_string()),
+            ("DIRECTION_GATE_EDGE_WIDEN", self.direction_gate_edge_widen.to_string()),
+            ("PERSISTENT_SPREAD_BLOCKS", self.persistent_spread_blocks.to_string()),
+            ("PERSISTENT_SPREAD_FLOOR_PCT", self.persistent_spread_floor_pct.to_string()),
+            ("PERSISTENT_SPREAD_RESET_PCT", self.persistent_spread_reset_pct.to_string()),
+            ("SUBMIT_JITTER_MS", self.submit_jitter_ms.to_string()),
+            ("MIN_SUBMIT_SPACING_MS", self.min_submit_spacing_ms.to_string()),
+            ("REVISIT_TOP_K", self.revisit_top_k.to_string()),
+            ("REVISIT_QUIET_BLOCKS", self.revisit_quiet_blocks.to_string()),
+            ("REVISIT_BUDGET_MS", self.revisit_budget_ms.to_string()),
+            (
+                "POOL_A_RPC_URL",
+                self.pool_a_rpc_url
+                    .as_deref()
+                    .map_or("(not set)".into(), redact_url),
+            ),
+            (
+                "POOL_B_RPC_URL",
+                self.pool_b_rpc_url
+                    .as_deref()
+                    .map_or("(not set)".into(), redact_url),
+            ),
+            ("ARM_FILE", self.arm_file.clone()),
+            (
+                "STATUS_LISTEN_ADDR",
+                self.status_listen_addr.map_or("(not set)".into(), |a| a.to_string()),
+            ),
+            ("STATUS_ALLOW_REMOTE", self.status_allow_remote.to_string()),
+            ("PAPER_EXECUTION", self.paper_execution.to_string()),
+            ("PAPER_FAILURE_RATE", self.paper_failure_rate.to_string()),
+            ("PAPER_DROP_RATE", self.paper_drop_rate.to_string()),
+            ("PAPER_RECEIPT_LATENCY_MS", self.paper_receipt_latency.to_string()),
+            ("STRATEGY_TIME_BUDGET_MS", self.strategy_time_budget_ms.to_string()),
+            ("PAIR_ROTATION_BLOCKS", self.pair_rotation_blocks.to_string()),
+            ("PAIR_SCORE_DECAY", self.pair_score_decay.to_string()),
+            ("REDUNDANCY_WARN", self.redundancy_warn.to_string()),
+            ("DEGRADE_START_PCT", self.degrade_start_pct.to_string()),
+            ("DEGRADE_RECOVERY_BLOCKS", self.degrade_recovery_blocks.to_string()),
+            ("CONFIRMATION_BLOCKS", self.confirmation_blocks.to_string()),
+            ("ADAPTIVE_STALENESS", self.adaptive_staleness.to_string()),
+            ("STALENESS_MIN_MS", self.staleness_min_ms.to_string()),
+            ("STALENESS_MAX_MS", self.staleness_max_ms.to_string()),
+            ("STALENESS_EXEC_FRACTION", self.staleness_exec_fraction.to_string()),
+            ("SHADOW_VERIFY_BLOCKS", self.shadow_verify_blocks.to_string()),
+            ("SHADOW_VERIFY_PER_MIN", self.shadow_verify_per_min.to_string()),
+            ("TICK_BITMAP_FULL_RESYNC_BLOCKS", self.tick_bitmap_full_resync_blocks.to_string()),
+            (
+                "METRICS_LISTEN_ADDR",
+                self.metrics_listen_addr.map_or("(not set)".into(), |a| a.to_string()),
+            ),
+            ("EXACT_SIZING_MIN_WETH", self.exact_sizing_min_weth.to_string()),
+            ("PREDICTED_STATE_TRUST_BLOCKS", self.predicted_state_trust_blocks.to_string()),
+            ("CIRCUIT_BREAKER_COOLDOWN_SECS", self.circuit_breaker_cooldown_secs.to_string()),
+            ("CIRCUIT_BREAKER_HARD_EXIT", self.circuit_breaker_hard_exit.to_string()),
+            ("LOG_FORMAT", self.log_format.to_string()),
+        ]
+    }
+
+    /// KeyManager'ın yüklediği anahtarı config'e bağla.
+    /// Keystore'dan gelen key private_key'e de aktarılır (geriye uyumluluk);
+    /// env'den gelen PRIVATE_KEY zaten load_from_env'de okunmuştur.
+    pub fn attach_key_manager(&mut self, key_manager: &KeyManager) {
+        if !key_manager.has_key() {
+            return;
+        }
+        self.key_source = key_manager.source().clone();
+        if self.private_key.is_none() {
+            self.private_key = key_manager.private_key().map(|k| k.to_string());
+        }
+    }
+
+    /// Anahtar şifreli keystore'dan mı yüklendi?
+    pub fn key_manager_active(&self) -> bool {
+        matches!(self.key_source, KeySource::EncryptedKeystore(_))
     }
 
     /// Kontrat tetikleme modu aktif mi?
@@ -855,23 +2572,186 @@ impl BotConfig {
     ///   1. EXECUTION_ENABLED=true (.env)
     ///   2. Private key mevcut (keystore VEYA env var)
     ///   3. ARBITRAGE_CONTRACT_ADDRESS tanımlı
+    ///   4. PAPER_EXECUTION kapalı
     pub fn execution_enabled(&self) -> bool {
         self.execution_enabled_flag
-            && (self.private_key.is_some() || self.key_manager_active)
+            && !self.paper_execution
+            && (self.private_key.is_some() || self.key_manager_active())
             && self.contract_address.is_some()
     }
 
     /// Gölge modu aktif mi? (Loglama yapılır ama TX gönderilmez)
+    /// PAPER_EXECUTION gölge modu zorunlu kılar.
     pub fn shadow_mode(&self) -> bool {
-        !self.execution_enabled_flag
+        !self.execution_enabled_flag || self.paper_execution
+    }
+
+    /// Güvenli token listesi (zincir profili + etkin WETH/USDC)
+    pub fn token_whitelist(&self) -> HashSet<Address> {
+        crate::chain_profile::token_whitelist(self.chain_profile, self.weth_address, self.usdc_address)
     }
 
-    /// .env'den f64 oku
-    fn parse_env_f64(key: &str, default: f64) -> f64 {
-        std::env::var(key)
-            .unwrap_or_else(|_| default.to_string())
-            .parse::<f64>()
-            .unwrap_or(default)
+    /// Kâr modelinde builder'a giden pay: FIFO sıralamalı zincirlerde
+    /// BRIBE_PCT, EIP-1559 stratejisinde 0 (bahşiş kârdan bağımsız ve sabit)
+    pub fn effective_bribe_pct(&self) -> f64 {
+        match self.priority_fee_strategy {
+            PriorityFeeStrategy::FifoBribe => self.bribe_pct,
+            PriorityFeeStrategy::Eip1559Competitive => 0.0,
+        }
+    }
+
+    /// Testler için env'e dokunmayan yapılandırma.
+    /// Değerler strateji fikstürlerinin beklediği sabitlerdir; yan etkisi
+    /// olan özellikler (snapshot arşivi, likidite analitiği, ısınma kapısı,
+    /// session summary) kapalıdır.
+    /// Alan gerektiren testler struct update ile üzerine yazar:
+    /// `BotConfig { bribe_pct: 0.5, ..BotConfig::for_tests() }`
+    #[cfg(test)]
+    pub fn for_tests() -> Self {
+        let base = ChainProfile::Base.defaults().expect("base profile has a table row");
+        Self {
+            rpc_wss_url: "wss://test".into(),
+            rpc_http_url: "https://test".into(),
+            rpc_ipc_path: None,
+            transport_mode: TransportMode::Ws,
+            private_key: None,
+            contract_address: None,
+            contract_pause_selector: PauseSelector::DEFAULT,
+            weth_address: base.weth,
+            usdc_address: base.usdc,
+            gas_cost_fallback_weth: 0.00005,
+            flash_loan_fee_bps: 0.0,
+            min_net_profit_weth: 0.000005,
+            min_profit_roi: 0.0005,
+            stats_interval: 100,
+            max_retries: 0,
+            initial_retry_delay_secs: 2,
+            max_retry_delay_secs: 60,
+            max_staleness_ms: 5000,
+            max_trade_size_weth: 50.0,
+            // NR'nin eski sabit tabanı — fixture'lar küçük boyutlarda da optimum arar
+            min_trade_size_weth: 0.0001,
+            chain_profile: ChainProfile::Base,
+            chain_id: base.chain_id,
+            l1_fee_oracle: base.l1_fee_oracle,
+            priority_fee_strategy: base.priority_fee,
+            tick_bitmap_range: 500,
+            tick_bitmap_max_age_blocks: 5,
+            execution_enabled_flag: false,
+            admin_address: None,
+            deadline_blocks: 2,
+            bribe_pct: 0.25,
+            keystore_path: None,
+            key_source: KeySource::None,
+            circuit_breaker_threshold: 3,
+            rpc_wss_url_backup: None,
+            latency_spike_threshold_ms: 200.0,
+            runtime_stall_threshold_ms: 50,
+            private_rpc_url: None,
+            rpc_wss_url_extra: Vec::new(),
+            max_pool_fee_bps: MAX_FEE_TIER_BPS,
+            min_tvl_usd: 1_000_000.0,
+            min_volume_24h_usd: 500_000.0,
+            max_tracked_pools: 4,
+            telegram_bot_token: None,
+            telegram_chat_id: None,
+            telegram_enabled: false,
+            telegram_shift_interval_secs: 21600,
+            telegram_balance_warn_eth: 0.05,
+            snapshot_keep: 0,
+            aave_pool_address: base.aave_pool,
+            auto_update_fees: false,
+            competition_routers: Vec::new(),
+            competition_min_txs: 2,
+            competition_profit_mult: 2.0,
+            competition_skip_limit: 4,
+            sim_math_max_discrepancy: 0,
+            liquidity_analytics_budget_ms: 0,
+            opportunity_filters: Vec::new(),
+            // Fikstürlerde bitmap yok — kalite kapısı ayrı testte açılır
+            require_bitmap_for_execution: false,
+            sequencer_ws_url: None,
+            adverse_frontrun: FrontrunSize::Weth(0.0),
+            require_adverse_positive: false,
+            min_robustness: 0.0,
+            warmup_clean_blocks: 0,
+            summary_dir: String::new(),
+            spread_probe_size_weth: 0.1,
+            sizing_backend: SizingBackend::Math,
+            sizing_numerics: SizingNumerics::F64,
+            two_step_sizing: false,
+            execute_followup: false,
+            optimistic_update_log: false,
+            allow_nonstandard_tokens: false,
+            secondary_config_path: None,
+            approval_audit_mode: ApprovalAuditMode::Off,
+            approval_required_spenders: RequiredSpenders::None,
+            state_diff_log: false,
+            log_format_binary: false,
+            process_all_blocks: false,
+            fast_path_sync: true,
+            discover_constant_product: false,
+            full_sync_interval_blocks: 50,
+            gas_estimate: 350_000,
+            multicall_retries: 2,
+            gas_buffer_mode: GasBufferMode::Fixed,
+            min_calibration_samples: 20,
+            gas_base_overhead: 160_000,
+            gas_per_tick_crossing: 20_000,
+            gas_crossing_calibration: true,
+            impact_warn_ticks: 10,
+            impact_slippage_bump_bps: 0,
+            impact_bump_blocks: 300,
+            audit_interval_blocks: 0,
+            audit_max_deviation_bps: 10.0,
+            event_divergence_ticks: 1,
+            bytecode_recheck_blocks: 1000,
+            max_inflight_executions: 2,
+            direction_gate_window: 0,
+            direction_gate_min_attempts: 5,
+            direction_gate_min_success_rate: 0.3,
+            direction_gate_mode: DirectionGateMode::Multiplier,
+            direction_gate_profit_multiplier: 2.0,
+            direction_gate_cooldown_blocks: 1800,
+            direction_gate_edge_widen: 1.5,
+            persistent_spread_blocks: 300,
+            persistent_spread_floor_pct: 0.5,
+            persistent_spread_reset_pct: 0.1,
+            submit_jitter_ms: JitterRange::OFF,
+            min_submit_spacing_ms: 0,
+            revisit_top_k: 0,
+            revisit_quiet_blocks: 30,
+            revisit_budget_ms: 250,
+            pool_a_rpc_url: None,
+            pool_b_rpc_url: None,
+            arm_file: String::new(),
+            status_listen_addr: None,
+            status_allow_remote: false,
+            paper_execution: false,
+            paper_failure_rate: 0.1,
+            paper_drop_rate: 0.02,
+            paper_receipt_latency: crate::paper::LatencyRange::DEFAULT,
+            strategy_time_budget_ms: 400,
+            pair_rotation_blocks: 20,
+            pair_score_decay: 0.1,
+            redundancy_warn: 2,
+            degrade_start_pct: 50,
+            degrade_recovery_blocks: 5,
+            confirmation_blocks: 10,
+            adaptive_staleness: true,
+            staleness_min_ms: 300,
+            staleness_max_ms: 3000,
+            staleness_exec_fraction: 0.75,
+            shadow_verify_blocks: 2,
+            shadow_verify_per_min: 30,
+            tick_bitmap_full_resync_blocks: 600,
+            metrics_listen_addr: None,
+            exact_sizing_min_weth: 1.0,
+            predicted_state_trust_blocks: 1,
+            circuit_breaker_cooldown_secs: 900,
+            circuit_breaker_hard_exit: false,
+            log_format: LogFormat::Pretty,
+        }
     }
 }
 
@@ -885,6 +2765,9 @@ impl BotConfig {
 // Arbitraj İstatistikleri
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Oturum sayaçları — session_summary.json'a olduğu gibi yazılır
+/// (Instant alanları hariç; başlangıç/bitiş zamanı özette ayrıca var).
+#[derive(Clone, Serialize)]
 pub struct ArbitrageStats {
     pub total_blocks_processed: u64,
     pub total_opportunities: u64,
@@ -894,8 +2777,9 @@ pub struct ArbitrageStats {
     pub max_spread_pct: f64,
     pub max_profit_weth: f64,
     pub total_potential_profit: f64,
+    #[serde(skip)]
     pub session_start: Instant,
-    /// Transport türü (aktif bağlantı)
+    /// Ana döngünün gerçekte kullandığı transport ("IPC" / "WSS" / "HTTP")
     pub active_transport: String,
     /// Ortalama blok işleme gecikmesi (ms)
     pub avg_block_latency_ms: f64,
@@ -906,10 +2790,14 @@ pub struct ArbitrageStats {
     /// v10.0: Ardışık başarısızlık sayacı (circuit breaker için)
     /// 3 ardışık simülasyon/TX başarısızlığında bot geçici olarak durur
     pub consecutive_failures: u32,
+    /// Hata türüne göre sayaçlar (revert / RPC / simülasyon) — circuit_breaker'dan
+    pub failure_kinds: crate::circuit_breaker::FailureCounts,
     /// v15.0: Maksimum blok işleme gecikmesi (ms)
     pub max_block_latency_ms: f64,
     /// v15.0: Gecikme spike sayısı (threshold üzerinde)
     pub latency_spikes: u64,
+    /// İşlenmesi bir executor stall'ı ile örtüşen blok sayısı (runtime_health)
+    pub stalled_blocks: u64,
     /// v23.0 (Y-1): Gölge modunda simülasyon başarılı fırsat sayısı
     pub shadow_sim_success: u64,
     /// v23.0 (Y-1): Gölge modunda simülasyon başarısız fırsat sayısı
@@ -917,7 +2805,83 @@ pub struct ArbitrageStats {
     /// v23.0 (Y-1): Gölge modunda kümülatif potansiyel kâr (WETH)
     pub shadow_cumulative_profit: f64,
     /// v32.0: Son vardiya raporu gönderim zamanı
+    #[serde(skip)]
     pub last_shift_report: Instant,
+    /// Rakip arbitraj cezasının devrede olduğu blok sayısı
+    pub competition_penalty_blocks: u64,
+    /// Rakip cezası nedeniyle atlanan fırsat sayısı
+    pub competition_skips: u64,
+    /// Atlanan fırsatlardan simülasyonu başarılı olanlar (sezgi doğrulaması)
+    pub competition_skips_would_succeed: u64,
+    /// V2 / vAMM bacağı nedeniyle yalnızca gölgede kalan fırsatlar
+    /// (kontratta V2 swap yolu yok)
+    pub constant_product_shadowed: u64,
+    /// REVM ↔ exact matematik karşılaştırma sayısı
+    pub sim_math_checks: u64,
+    /// Gözlenen en büyük REVM ↔ exact kâr farkı (owedToken wei)
+    pub sim_math_max_discrepancy_wei: u128,
+    /// Fark eşiği aşıldığı için yürütülmeyen fırsatlar
+    pub sim_math_discrepancy_skips: u64,
+    /// REVM gas'ıyla net kârın yeniden hesaplandığı fırsat sayısı
+    pub sim_gas_checks: u64,
+    /// Simüle gas ile net kâr eşiğin altına düştüğü için yürütülmeyen fırsatlar
+    pub gas_rejected_opportunities: u64,
+    /// Fırsatların bitmap kalitesi dağılımı: [real, stale, fallback]
+    pub bitmap_quality_counts: [u64; 3],
+    /// Değerlendirilen fırsatların simülasyon kaynağı: [revm, math_fallback]
+    pub sim_source_counts: [u64; 2],
+    /// Isınma kapısı kapalıyken gölgeye alınan fırsat sayısı
+    pub warmup_suppressed: u64,
+    /// Circuit breaker'ın çift kara listeye aldığı sayısı
+    pub breaker_trips: u64,
+    /// Geride kalınca işlenmeden atlanan (en yeniye atlanılan) blok sayısı
+    pub blocks_skipped_backlog: u64,
+    /// Faz 1'de fiyatı değişmemiş bulunan (fırsat kontrolü atlanan) bloklar
+    pub quiet_blocks: u64,
+    /// Swap eventiyle çelişen sabitli okuma alarmları (bayat replika şüphesi)
+    pub state_divergence_alarms: u64,
+    /// Zincirde kod hash'i değişmiş bulunan bytecode'lar (base_db yeniden kuruldu)
+    pub bytecode_changes: u64,
+    /// Flash swap yön / token / kâr birimi değişmezi ihlali (kod/config hatası, gönderilmedi)
+    pub direction_invariant_rejections: u64,
+    /// Optimum tarama kenarına dayandığı için yapılan bitmap genişletmeleri
+    pub bitmap_extensions: u64,
+    /// Eşzamanlı yürütme sınırı dolu olduğu için atlanan fırsatlar
+    pub inflight_cap_skips: u64,
+    /// Oturumdaki en yüksek eşzamanlı yürütme sayısı
+    pub peak_inflight_executions: u64,
+    /// Ters flash rotasının (pool B kaynak) anlamlı ölçüde daha kârlı olduğu fırsatlar
+    pub reversed_route_wins: u64,
+    /// Bu fırsatlarda mevcut kontrat rotası yüzünden kaçırılan toplam kâr (WETH)
+    pub foregone_route_improvement_weth: f64,
+    /// Raporlanan fırsatların kâr ayrıştırması toplamı (spread vs ücret/gas sürtünmesi)
+    pub profit_attribution: crate::math::ProfitAttribution,
+    /// Sync / simülasyon / gönderim hataları, BotError varyant adına göre
+    pub error_counts: std::collections::BTreeMap<&'static str, u64>,
+    /// A/B gölge değerlendirmesi (SECONDARY_CONFIG_PATH tanımlıysa)
+    pub ab_shadow: crate::ab_shadow::AbStats,
+    /// Degradation ladder: basamağın atıldığı blok sayısı (`[r-1]` = basamak r)
+    pub shed_blocks: [u64; crate::degradation::RUNGS],
+    /// Transport başına senkron gecikmesi — IPC ↔ WSS karşılaştırması
+    /// (IPC düşüp reconnect'te WSS'e geçilirse iki satır oluşur)
+    pub sync_latency_by_transport: std::collections::BTreeMap<&'static str, TransportLatency>,
+}
+
+/// Tek transport üzerindeki blok senkron gecikmesi
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TransportLatency {
+    pub blocks: u64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+impl TransportLatency {
+    fn record(&mut self, latency_ms: f64) {
+        let n = self.blocks as f64;
+        self.avg_ms = (self.avg_ms * n + latency_ms) / (n + 1.0);
+        self.max_ms = self.max_ms.max(latency_ms);
+        self.blocks += 1;
+    }
 }
 
 impl ArbitrageStats {
@@ -937,12 +2901,42 @@ impl ArbitrageStats {
             min_block_latency_ms: f64::MAX,
             tick_bitmap_syncs: 0,
             consecutive_failures: 0,
+            failure_kinds: crate::circuit_breaker::FailureCounts::default(),
             max_block_latency_ms: 0.0,
             latency_spikes: 0,
+            stalled_blocks: 0,
             shadow_sim_success: 0,
             shadow_sim_fail: 0,
             shadow_cumulative_profit: 0.0,
             last_shift_report: Instant::now(),
+            competition_penalty_blocks: 0,
+            competition_skips: 0,
+            competition_skips_would_succeed: 0,
+            constant_product_shadowed: 0,
+            sim_math_checks: 0,
+            sim_math_max_discrepancy_wei: 0,
+            sim_math_discrepancy_skips: 0,
+            sim_gas_checks: 0,
+            gas_rejected_opportunities: 0,
+            bitmap_quality_counts: [0; 3],
+            sim_source_counts: [0; 2],
+            warmup_suppressed: 0,
+            breaker_trips: 0,
+            blocks_skipped_backlog: 0,
+            quiet_blocks: 0,
+            state_divergence_alarms: 0,
+            bytecode_changes: 0,
+            direction_invariant_rejections: 0,
+            bitmap_extensions: 0,
+            inflight_cap_skips: 0,
+            peak_inflight_executions: 0,
+            reversed_route_wins: 0,
+            foregone_route_improvement_weth: 0.0,
+            profit_attribution: crate::math::ProfitAttribution::default(),
+            error_counts: std::collections::BTreeMap::new(),
+            ab_shadow: crate::ab_shadow::AbStats::default(),
+            shed_blocks: [0; crate::degradation::RUNGS],
+            sync_latency_by_transport: std::collections::BTreeMap::new(),
         }
     }
 
@@ -963,6 +2957,60 @@ impl ArbitrageStats {
         }
     }
 
+    /// Senkron gecikmesini blokta kullanılan transport'a da yaz
+    pub fn record_transport_latency(&mut self, transport: &'static str, latency_ms: f64) {
+        self.sync_latency_by_transport
+            .entry(transport)
+            .or_default()
+            .record(latency_ms);
+    }
+
+    /// Transport karşılaştırma satırı: "IPC 1.2ms avg / 4.0ms max (340 blocks) | WSS ..."
+    pub fn transport_latency_line(&self) -> Option<String> {
+        if self.sync_latency_by_transport.is_empty() {
+            return None;
+        }
+        Some(
+            self.sync_latency_by_transport
+                .iter()
+                .map(|(transport, l)| {
+                    format!("{} {:.1}ms avg / {:.1}ms max ({} blocks)", transport, l.avg_ms, l.max_ms, l.blocks)
+                })
+                .collect::<Vec<_>>()
+                .join(" | "),
+        )
+    }
+
+    /// Fırsatın bitmap kalitesini say
+    pub fn record_bitmap_quality(&mut self, quality: BitmapQuality) {
+        let idx = match quality {
+            BitmapQuality::Real => 0,
+            BitmapQuality::Stale { .. } => 1,
+            BitmapQuality::Fallback => 2,
+        };
+        self.bitmap_quality_counts[idx] += 1;
+    }
+
+    pub fn record_sim_source(&mut self, source: SimSource) {
+        let idx = match source {
+            SimSource::Revm => 0,
+            SimSource::MathFallback => 1,
+        };
+        self.sim_source_counts[idx] += 1;
+    }
+
+    /// Bloğun ulaştığı seviyeye kadar atılan her basamağı say
+    pub fn record_shed_level(&mut self, level: u8) {
+        for blocks in self.shed_blocks.iter_mut().take(level as usize) {
+            *blocks += 1;
+        }
+    }
+
+    /// Hatayı varyant adıyla say
+    pub fn record_error(&mut self, err: &crate::errors::BotError) {
+        *self.error_counts.entry(err.kind()).or_insert(0) += 1;
+    }
+
     pub fn uptime_str(&self) -> String {
         let secs = self.session_start.elapsed().as_secs();
         let h = secs / 3600;
@@ -971,3 +3019,367 @@ impl ArbitrageStats {
         format!("{:02}:{:02}:{:02}", h, m, s)
     }
 }
+
+#[cfg(test)]
+mod tick_bitmap_tests {
+    use super::*;
+
+    fn info(liquidity_net: i128) -> TickInfo {
+        TickInfo {
+            liquidity_gross: liquidity_net.unsigned_abs(),
+            liquidity_net,
+            initialized: true,
+            staked_liquidity_net: 0,
+        }
+    }
+
+    fn bitmap(block: u64, range: (i32, i32), words: &[(i16, u64)], ticks: &[(i32, i128)]) -> TickBitmapData {
+        TickBitmapData {
+            words: words.iter().map(|&(w, v)| (w, U256::from(v))).collect(),
+            ticks: ticks.iter().map(|&(t, l)| (t, info(l))).collect(),
+            snapshot_block: block,
+            scanned_ticks: Some(range),
+            ..TickBitmapData::empty()
+        }
+    }
+
+    #[test]
+    fn test_merge_overlapping_words_favor_newer_snapshot() {
+        let mut base = bitmap(100, (-600, 600), &[(-1, 0b01), (0, 0b01)], &[(0, 5)]);
+        let ext = bitmap(101, (610, 1800), &[(0, 0b11), (1, 0b10)], &[(600, 7)]);
+
+        base.merge(ext);
+
+        // Word 0 iki okumada da var → yeni bloktaki değer
+        assert_eq!(base.words[&0], U256::from(0b11u64));
+        assert_eq!(base.words[&-1], U256::from(0b01u64));
+        assert_eq!(base.words[&1], U256::from(0b10u64));
+        assert_eq!(base.ticks.len(), 2);
+        assert_eq!(base.scanned_ticks, Some((-600, 1800)));
+        // Tazelik en eski parçaya göre
+        assert_eq!(base.snapshot_block, 100);
+    }
+
+    #[test]
+    fn test_merge_conflicting_ticks_favor_newer_snapshot() {
+        // Eski veri sonradan birleştirilirse mevcut yeni girdiyi ezmez
+        let mut newer = bitmap(105, (-60, 60), &[(0, 0b1)], &[(0, 900), (60, -40)]);
+        let older = bitmap(103, (-120, 0), &[(0, 0b0)], &[(0, 100), (-60, 30)]);
+
+        newer.merge(older);
+
+        assert_eq!(newer.ticks[&0].liquidity_net, 900);
+        assert_eq!(newer.ticks[&-60].liquidity_net, 30);
+        assert_eq!(newer.words[&0], U256::from(1u64));
+        assert_eq!(newer.scanned_ticks, Some((-120, 60)));
+        assert_eq!(newer.snapshot_block, 103);
+
+        // Ters sıra: yeni okuma eski bitmap'e birleştirilirse yeni kazanır
+        let mut older = bitmap(103, (-120, 0), &[], &[(0, 100)]);
+        older.merge(bitmap(105, (-60, 60), &[], &[(0, 900)]));
+        assert_eq!(older.ticks[&0].liquidity_net, 900);
+    }
+
+    #[test]
+    fn test_scan_edge_near_uses_margin_and_unknown_range() {
+        let bm = bitmap(1, (-1000, 1000), &[], &[]);
+        assert_eq!(bm.scan_edge_near(0, 20), None);
+        assert_eq!(bm.scan_edge_near(985, 20), Some(ScanEdge::Upper));
+        assert_eq!(bm.scan_edge_near(1500, 20), Some(ScanEdge::Upper));
+        assert_eq!(bm.scan_edge_near(-980, 20), Some(ScanEdge::Lower));
+        assert_eq!(bm.scan_edge_near(-979, 20), None);
+        assert_eq!(TickBitmapData::empty().scan_edge_near(0, 20), None);
+    }
+
+    #[test]
+    fn test_verify_staked_walks_both_directions() {
+        let staked = |net: i128, staked_net: i128| TickInfo { staked_liquidity_net: staked_net, ..info(net) };
+        let mut bm = bitmap(1, (-120, 120), &[], &[]);
+        bm.ticks = [(-60, staked(800, 300)), (0, staked(200, 100)), (60, staked(-300, -100)), (120, staked(-700, -300))]
+            .into_iter()
+            .collect();
+        let snapshot = StakedLiquidity {
+            tick: 0,
+            liquidity: 1_000,
+            staked_liquidity: 400,
+            unstaked_fee_pips: Some(100_000),
+            verified: false,
+        };
+        assert!((snapshot.staked_share() - 0.4).abs() < 1e-12);
+        assert_eq!(bm.verify_staked(&snapshot), Ok(()));
+
+        // Çift sayılmış staked net: 60'ın ötesinde staked negatife düşer
+        bm.ticks.insert(60, staked(-300, -500));
+        let err = bm.verify_staked(&snapshot).unwrap_err();
+        assert!(err.contains("past tick 60"), "{err}");
+
+        // Getter toplamı aşan staked: doğrudan reddedilir
+        let over = StakedLiquidity { staked_liquidity: 1_200, ..snapshot };
+        assert!(bm.verify_staked(&over).is_err());
+    }
+}
+
+#[cfg(test)]
+mod wei_conversion_tests {
+    use super::*;
+
+    fn reason(result: BotResult<U256>) -> String {
+        match result {
+            Err(BotError::InvalidAmount { reason, .. }) => reason,
+            other => panic!("expected InvalidAmount, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_weth_to_wei_rejects_non_finite_and_negative() {
+        assert_eq!(reason(weth_to_wei(f64::NAN)), "not finite");
+        assert_eq!(reason(weth_to_wei(f64::INFINITY)), "not finite");
+        assert_eq!(reason(weth_to_wei(f64::NEG_INFINITY)), "not finite");
+        assert_eq!(reason(weth_to_wei(-0.5)), "negative");
+        assert_eq!(reason(token_amount_to_wei(-1e-12, 6)), "negative");
+    }
+
+    /// u128 üstü: eski saturating dönüşüm u128::MAX yazardı
+    #[test]
+    fn test_weth_to_wei_rejects_above_u128() {
+        assert_eq!(reason(weth_to_wei(3.5e20)), "exceeds u128 wei range");
+        assert_eq!(reason(weth_to_wei(3.41e20)), "exceeds u128 wei range");
+        assert_eq!(reason(token_amount_to_wei(1e300, 18)), "exceeds u128 wei range");
+        assert!(weth_to_wei(1e20).is_ok(), "1e38 wei < 2^128");
+    }
+
+    #[test]
+    fn test_weth_to_wei_exact_and_precision_bound() {
+        assert_eq!(weth_to_wei(0.0).unwrap(), U256::ZERO);
+        assert_eq!(weth_to_wei(0.1).unwrap(), U256::from(100_000_000_000_000_000u128));
+        assert_eq!(weth_to_wei(5.0).unwrap(), U256::from(5_000_000_000_000_000_000u128));
+        assert_eq!(token_amount_to_wei(2500.5, 6).unwrap(), U256::from(2_500_500_000u64));
+        // Wei altı miktar en yakın wei'ye yuvarlanır
+        assert_eq!(weth_to_wei(1e-19).unwrap(), U256::ZERO);
+        assert_eq!(weth_to_wei(6e-19).unwrap(), U256::from(1u8));
+
+        // 19 anlamlı basamak f64'e sığmaz: kayıp göreli EPSILON mertebesinde kalır
+        let exact = 1_234_567_890_123_456_789_000u128;
+        #[allow(clippy::excessive_precision)]
+        let wei: u128 = weth_to_wei(1234.567890123456789).unwrap().to();
+        let relative_error = wei.abs_diff(exact) as f64 / exact as f64;
+        assert!(relative_error <= 2.0 * f64::EPSILON, "relative error {relative_error:e}");
+    }
+
+    #[test]
+    fn test_trade_amount_enforces_min_trade_size() {
+        let dust = TradeAmount::new(0.005, 0.01, true, 2500.0, 6).unwrap_err();
+        assert!(matches!(dust, BotError::InvalidAmount { value, .. } if value == 0.005));
+        assert!(TradeAmount::new(f64::NAN, 0.01, true, 2500.0, 6).is_err());
+        assert!(TradeAmount::new(0.5, 0.01, false, f64::INFINITY, 6).is_err());
+        // Taban 0 olsa da sıfır wei'ye yuvarlanan miktar calldata'ya yazılmaz
+        assert!(TradeAmount::new(1e-19, 0.0, true, 2500.0, 6).is_err());
+
+        let at_floor = TradeAmount::new(0.01, 0.01, true, 2500.0, 6).unwrap();
+        assert_eq!(at_floor.weth(), 0.01);
+        assert_eq!(at_floor.input_wei(), U256::from(10_000_000_000_000_000u128));
+        let quote = TradeAmount::new(0.02, 0.01, false, 2500.0, 6).unwrap();
+        assert_eq!(quote.input_wei(), U256::from(50_000_000u64), "0.02 WETH × 2500 USDC");
+    }
+}
+
+#[cfg(test)]
+mod fee_tier_tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_accessors_round_trip_for_every_known_tier() {
+        for bps in KNOWN_FEE_TIERS_BPS {
+            let fee = FeeTier::parse_bps(bps).expect("bilinen kademe geçerli olmalı");
+            assert!(fee.is_known_tier());
+            assert_eq!(fee.bps(), bps);
+            assert_eq!(fee.pips(), bps * 100, "pips = bps × 100");
+            assert_eq!(fee.pips() as u64 * 10_000, fee.bps() as u64 * 1_000_000);
+            assert!((fee.fraction() - fee.pips() as f64 / 1_000_000.0).abs() < 1e-15);
+            assert_eq!((fee.fraction() * 10_000.0).round() as u32, bps);
+            assert_eq!(crate::math::exact::fee_fraction_to_pips(fee.fraction()), fee.pips());
+        }
+        // Somut değerler: %0.05 havuz
+        let fee = FeeTier::from_bps(5);
+        assert_eq!(fee.pips(), 500);
+        assert_eq!(fee.fraction(), 0.0005);
+    }
+
+    #[test]
+    fn test_fee_parse_rejects_out_of_range_and_flags_unusual_tiers() {
+        assert!(FeeTier::parse_bps(0).is_err());
+        let err = FeeTier::parse_bps(500 * 100).unwrap_err();
+        assert_eq!(err.field, "fee_bps");
+        assert_eq!(err.value, "50000");
+        assert!(FeeTier::parse_bps(MAX_FEE_BPS).is_ok());
+
+        // Aralık içi ama standart dışı: kabul edilir, uyarı için işaretlenir
+        let unusual = FeeTier::parse_bps(7).unwrap();
+        assert!(!unusual.is_known_tier());
+    }
+
+    #[test]
+    fn test_effective_fee_prefers_live_value() {
+        let cfg = PoolConfig {
+            address: Address::ZERO,
+            name: "test".into(),
+            fee: FeeTier::from_bps(5),
+            token0_decimals: 18,
+            token1_decimals: 6,
+            dex: DexType::UniswapV3,
+            token0_is_weth: true,
+            tick_spacing: 10,
+            quote_token_address: Address::ZERO,
+            base_token_address: Address::ZERO,
+        };
+        assert_eq!(cfg.effective_fee(None).pips(), 500);
+        assert_eq!(cfg.effective_fee(Some(30)).pips(), 3_000);
+        assert_eq!(cfg.effective_fee(Some(30)).fraction(), 0.003);
+    }
+}
+
+#[cfg(test)]
+mod liquidity_repr_tests {
+    use super::*;
+
+    /// 2^53 üstü: f64 kopya komşu değerleri birleştirir, u128 ayırır
+    #[test]
+    fn test_f64_copy_conflates_above_2_pow_53() {
+        let edge = 1u128 << 53;
+        assert_ne!(liquidity_to_f64(edge - 1), liquidity_to_f64(edge));
+        assert_eq!(liquidity_to_f64(edge), liquidity_to_f64(edge + 1));
+
+        // Base WETH/USDC mertebesi: kayıp göreli epsilon içinde kalır
+        let mut state = PoolState::default();
+        for liquidity in [edge - 1, edge + 1, 3_456_789_012_345_678_901, u128::MAX] {
+            state.set_liquidity(liquidity);
+            assert_eq!(state.liquidity, liquidity);
+            let approx = state.liquidity_approx();
+            assert!((approx - liquidity as f64).abs() <= liquidity as f64 * LIQUIDITY_F64_EPSILON);
+        }
+    }
+
+    /// f64 kopyası u128'den koparsa debug derlemede yakalanır
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "diverged")]
+    fn test_diverged_f64_copy_trips_debug_assertion() {
+        let mut state = PoolState::default();
+        state.set_liquidity(10_000_000_000_000_000_000);
+        state.liquidity = 20_000_000_000_000_000_000;
+        state.liquidity_approx();
+    }
+}
+
+#[cfg(test)]
+mod transport_latency_tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_latency_split_by_transport() {
+        let mut stats = ArbitrageStats::new();
+        assert_eq!(stats.transport_latency_line(), None);
+        for ms in [1.0, 3.0] {
+            stats.record_transport_latency("IPC", ms);
+        }
+        stats.record_transport_latency("WSS", 12.0);
+        assert_eq!(
+            stats.sync_latency_by_transport["IPC"],
+            TransportLatency { blocks: 2, avg_ms: 2.0, max_ms: 3.0 }
+        );
+        assert_eq!(
+            stats.transport_latency_line().unwrap(),
+            "IPC 2.0ms avg / 3.0ms max (2 blocks) | WSS 12.0ms avg / 12.0ms max (1 blocks)"
+        );
+    }
+}
+
+#[cfg(test)]
+mod nonce_manager_tests {
+    use super::*;
+
+    /// 42 düşerken 43 uçuşta: eski geri sarma (sayaç = 42) sıradaki iki
+    /// tahsiste 43'ü ikinci kez verirdi. Şimdi 42 yeniden verilir, sonra 44.
+    #[test]
+    fn test_interleaved_failure_never_duplicates_inflight_nonce() {
+        let nm = NonceManager::new(42);
+        let a = nm.get_and_increment();
+        let b = nm.get_and_increment();
+        assert_eq!((a, b), (42, 43));
+
+        nm.release(a);
+        assert_eq!(nm.current(), 44, "counter does not rewind past the in-flight 43");
+        assert_eq!(nm.released_count(), 1);
+
+        let c = nm.get_and_increment();
+        let d = nm.get_and_increment();
+        assert_eq!((c, d), (42, 44));
+        assert_eq!(nm.released_count(), 0);
+    }
+
+    /// Uzun karışık dizi: canlı nonce'lar hiçbir anda çakışmaz ve düşen
+    /// her nonce sayaç ilerlemeden önce yeniden kullanılır
+    #[test]
+    fn test_allocate_fail_allocate_sequence_is_unique() {
+        let nm = NonceManager::new(100);
+        let mut live: HashSet<u64> = HashSet::new();
+        for step in 0..60u64 {
+            let nonce = nm.get_and_increment();
+            assert!(live.insert(nonce), "nonce {} handed out twice (step {})", nonce, step);
+            // Her üçüncü tahsis başarısız; ara sıra en eski canlı olan da
+            if step % 3 == 0 {
+                live.remove(&nonce);
+                nm.release(nonce);
+            }
+            if let Some(&oldest) = live.iter().min().filter(|_| step % 7 == 0) {
+                live.remove(&oldest);
+                nm.release(oldest);
+            }
+        }
+        // Düşmüşler tahsis edilmeden sayaç yükselmez: boşluk yok
+        let mut all: Vec<u64> = live.iter().copied().collect();
+        while nm.released_count() > 0 {
+            all.push(nm.get_and_increment());
+        }
+        all.sort_unstable();
+        let expected: Vec<u64> = (100..nm.current()).collect();
+        assert_eq!(all, expected);
+    }
+
+    /// Sayacın hemen altındaki düşüşler sayaca katlanır (boşluk kalmaz)
+    #[test]
+    fn test_tail_release_folds_back_into_counter() {
+        let nm = NonceManager::new(10);
+        let nonces: Vec<u64> = (0..3).map(|_| nm.get_and_increment()).collect();
+        nm.release(nonces[0]);
+        assert_eq!((nm.current(), nm.released_count()), (13, 1));
+        nm.release(nonces[2]);
+        assert_eq!((nm.current(), nm.released_count()), (12, 1));
+        nm.release(nonces[1]);
+        assert_eq!((nm.current(), nm.released_count()), (10, 0));
+        // Hiç verilmemiş nonce'un geri verilmesi etkisiz
+        nm.release(10);
+        assert_eq!((nm.current(), nm.released_count()), (10, 0));
+    }
+
+    /// Zincirin tükettiği düşmüşler uzlaştırmada silinir; force_set yeni
+    /// sayacın üstünde kalanları düşürür
+    #[test]
+    fn test_reconcile_and_force_set_clear_stale_reservations() {
+        let nm = NonceManager::new(20);
+        let nonces: Vec<u64> = (0..5).map(|_| nm.get_and_increment()).collect();
+        nm.release(nonces[0]);
+        nm.release(nonces[2]);
+        assert_eq!(nm.released_count(), 2);
+
+        // 20 dış bir TX ile tüketildi (pending = 21)
+        assert_eq!(nm.reconcile(21), 1);
+        assert_eq!(nm.get_and_increment(), 22);
+
+        nm.release(22);
+        nm.force_set(22);
+        assert_eq!(nm.released_count(), 0);
+        assert_eq!(nm.get_and_increment(), 22);
+        assert_eq!(nm.get_and_increment(), 23);
+    }
+}
@@ -0,0 +1,592 @@
+// ============================================================================
+//  V3 FIXTURE — Hermetik UniswapV3 Havuzu (REVM Testleri)
+//
+//  REVM quote testleri ya gerçek havuz bytecode'u için RPC istiyordu ya da
+//  swap() yerine sabit delta dönen el yazımı stub'lara dayanıyordu. Burada
+//  fixtures/v3pool/minimal_v3_pool.easm (kanonik storage düzeni + exact-input
+//  swap) test anında derlenir; solc / forge / ağ gerekmez:
+//
+//  ✓ Derleyici: .easm'nin küçük sözdizimi (etiket, PUSH, .define, $param);
+//    token0 / token1 / fee / tickSpacing kanonikteki gibi koda gömülür
+//  ✓ Slot yardımcıları: ticks / tickBitmap / positions mapping anahtarları
+//    ve Tick.Info / Position kelimeleri — state_sync'in okuduğu düzenle aynı
+//  ✓ V3PoolBuilder: pozisyonlardan (alt, üst, L) tick'leri, bitmap
+//    kelimelerini ve aktif likiditeyi türetir; (SimulationEngine, PoolState,
+//    TickBitmapData) döner. slot0 / liquidity'yi motor PoolState'ten yazar,
+//    geri kalan storage what-if katmanıyla (StateOverrides.storage) kalıcıdır
+//
+//  Aynı pozisyon kümesi hem REVM'deki havuza hem botun exact matematiğine
+//  verilir: iki taraf aynı tick geçişlerini görür.
+// ============================================================================
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use alloy::primitives::{address, keccak256, Address, B256, I256, U256};
+use parking_lot::RwLock;
+
+use crate::math;
+use crate::simulator::{SimulationEngine, StateOverrides};
+use crate::types::{PoolConfig, PoolState, StaticPoolData, StaticPoolStore, TickBitmapData, TickInfo};
+
+/// Havuz kaynağı — derleme parametreleri: TOKEN0, TOKEN1, FEE, TICK_SPACING
+const POOL_SOURCE: &str = include_str!("../fixtures/v3pool/minimal_v3_pool.easm");
+
+/// Kanonik UniswapV3Pool storage slot'ları
+pub const LIQUIDITY_SLOT: u64 = 4;
+pub const TICKS_SLOT: u64 = 5;
+pub const TICK_BITMAP_SLOT: u64 = 6;
+pub const POSITIONS_SLOT: u64 = 7;
+
+/// Builder'ın yazdığı pozisyonların sahibi (positions anahtarı)
+pub const POSITION_OWNER: Address = address!("00000000000000000000000000000000000f1f01");
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Derleyici
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Kaynakta kullanılabilen opcode'lar (DUP1-16 / SWAP1-16 ayrıca)
+fn opcode(mnemonic: &str) -> Option<u8> {
+    let op = match mnemonic {
+        "STOP" => 0x00,
+        "ADD" => 0x01,
+        "MUL" => 0x02,
+        "SUB" => 0x03,
+        "DIV" => 0x04,
+        "SDIV" => 0x05,
+        "MOD" => 0x06,
+        "SMOD" => 0x07,
+        "ADDMOD" => 0x08,
+        "MULMOD" => 0x09,
+        "EXP" => 0x0a,
+        "SIGNEXTEND" => 0x0b,
+        "LT" => 0x10,
+        "GT" => 0x11,
+        "SLT" => 0x12,
+        "SGT" => 0x13,
+        "EQ" => 0x14,
+        "ISZERO" => 0x15,
+        "AND" => 0x16,
+        "OR" => 0x17,
+        "XOR" => 0x18,
+        "NOT" => 0x19,
+        "BYTE" => 0x1a,
+        "SHL" => 0x1b,
+        "SHR" => 0x1c,
+        "SAR" => 0x1d,
+        "SHA3" => 0x20,
+        "ADDRESS" => 0x30,
+        "ORIGIN" => 0x32,
+        "CALLER" => 0x33,
+        "CALLVALUE" => 0x34,
+        "CALLDATALOAD" => 0x35,
+        "CALLDATASIZE" => 0x36,
+        "CALLDATACOPY" => 0x37,
+        "RETURNDATASIZE" => 0x3d,
+        "RETURNDATACOPY" => 0x3e,
+        "POP" => 0x50,
+        "MLOAD" => 0x51,
+        "MSTORE" => 0x52,
+        "SLOAD" => 0x54,
+        "SSTORE" => 0x55,
+        "JUMP" => 0x56,
+        "JUMPI" => 0x57,
+        "GAS" => 0x5a,
+        "JUMPDEST" => 0x5b,
+        "CALL" => 0xf1,
+        "RETURN" => 0xf3,
+        "STATICCALL" => 0xfa,
+        "REVERT" => 0xfd,
+        _ => {
+            let (base, n) = if let Some(n) = mnemonic.strip_prefix("DUP") {
+                (0x7f, n)
+            } else if let Some(n) = mnemonic.strip_prefix("SWAP") {
+                (0x8f, n)
+            } else {
+                return None;
+            };
+            let n: u8 = n.parse().ok().filter(|n| (1..=16).contains(n))?;
+            base + n
+        }
+    };
+    Some(op)
+}
+
+/// En dar PUSH: 0 → PUSH0, aksi halde PUSH1..PUSH32
+fn push_bytes(value: U256) -> Vec<u8> {
+    let len = value.byte_len();
+    let mut out = Vec::with_capacity(1 + len);
+    out.push(0x5f + len as u8);
+    out.extend_from_slice(&value.to_be_bytes::<32>()[32 - len..]);
+    out
+}
+
+enum Item {
+    /// `etiket:` — JUMPDEST
+    Label(String),
+    /// `@etiket` — PUSH2 adres
+    Ref(String),
+    Raw(Vec<u8>),
+}
+
+/// .easm kaynağını bytecode'a derle (iki geçiş: etiket adresleri, sonra
+/// çıktı). Hata mesajı satır numarasını içerir.
+pub fn assemble(source: &str, params: &[(&str, U256)]) -> Result<Vec<u8>, String> {
+    let mut defines: HashMap<&str, U256> = HashMap::new();
+    let mut items = Vec::new();
+
+    for (index, line) in source.lines().enumerate() {
+        let line_no = index + 1;
+        let code = line.split(';').next().unwrap_or("").trim();
+        let tokens: Vec<&str> = code.split_whitespace().collect();
+        if tokens.is_empty() {
+            continue;
+        }
+        if tokens[0] == ".define" {
+            let [_, name, value] = tokens[..] else {
+                return Err(format!("line {}: .define NAME VALUE", line_no));
+            };
+            let value = value.parse::<U256>().map_err(|e| format!("line {}: {}", line_no, e))?;
+            if defines.insert(name, value).is_some() {
+                return Err(format!("line {}: {} defined twice", line_no, name));
+            }
+            continue;
+        }
+        for token in tokens {
+            let item = if let Some(label) = token.strip_suffix(':') {
+                Item::Label(label.to_string())
+            } else if let Some(label) = token.strip_prefix('@') {
+                Item::Ref(label.to_string())
+            } else if let Some(name) = token.strip_prefix('%') {
+                let value = defines.get(name).ok_or_else(|| format!("line {}: unknown define {}", line_no, name))?;
+                Item::Raw(push_bytes(*value))
+            } else if let Some(name) = token.strip_prefix('$') {
+                let (_, value) = params
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .ok_or_else(|| format!("line {}: missing param {}", line_no, name))?;
+                Item::Raw(push_bytes(*value))
+            } else if token.starts_with(|c: char| c.is_ascii_digit()) {
+                let value = token.parse::<U256>().map_err(|e| format!("line {}: {}: {}", line_no, token, e))?;
+                Item::Raw(push_bytes(value))
+            } else {
+                let op = opcode(token).ok_or_else(|| format!("line {}: unknown opcode {}", line_no, token))?;
+                Item::Raw(vec![op])
+            };
+            items.push(item);
+        }
+    }
+
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut pc = 0usize;
+    for item in &items {
+        match item {
+            Item::Label(name) => {
+                if labels.insert(name, pc).is_some() {
+                    return Err(format!("label {} defined twice", name));
+                }
+                pc += 1;
+            }
+            Item::Ref(_) => pc += 3,
+            Item::Raw(bytes) => pc += bytes.len(),
+        }
+    }
+    if pc > usize::from(u16::MAX) {
+        return Err(format!("code too large for PUSH2 labels ({} bytes)", pc));
+    }
+
+    let mut code = Vec::with_capacity(pc);
+    for item in &items {
+        match item {
+            Item::Label(_) => code.push(0x5b),
+            Item::Ref(name) => {
+                let target = labels.get(name.as_str()).ok_or_else(|| format!("unknown label {}", name))?;
+                code.push(0x61);
+                code.extend_from_slice(&(*target as u16).to_be_bytes());
+            }
+            Item::Raw(bytes) => code.extend_from_slice(bytes),
+        }
+    }
+    Ok(code)
+}
+
+/// Havuz bytecode'u: immutable'lar pool config'den gömülür
+pub fn pool_code(pool: &PoolConfig) -> Vec<u8> {
+    let (token0, token1) = pool_tokens(pool);
+    let params = [
+        ("TOKEN0", U256::from_be_slice(token0.as_slice())),
+        ("TOKEN1", U256::from_be_slice(token1.as_slice())),
+        ("FEE", U256::from(pool.fee_pips())),
+        ("TICK_SPACING", U256::from(pool.tick_spacing as u32)),
+    ];
+    assemble(POOL_SOURCE, &params).expect("minimal_v3_pool.easm derlenmeli")
+}
+
+/// (token0, token1) — base token WETH tarafıdır
+fn pool_tokens(pool: &PoolConfig) -> (Address, Address) {
+    if pool.token0_is_weth {
+        (pool.base_token_address, pool.quote_token_address)
+    } else {
+        (pool.quote_token_address, pool.base_token_address)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Storage Slot Yardımcıları
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `mapping(intN => …)` anahtarı: keccak256(int256(key) ++ uint256(slot))
+fn signed_mapping_slot(key: i64, slot: u64) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(&I256::try_from(key).unwrap().into_raw().to_be_bytes::<32>());
+    preimage[32..].copy_from_slice(&U256::from(slot).to_be_bytes::<32>());
+    U256::from_be_bytes(keccak256(preimage).0)
+}
+
+/// `ticks[tick]` — Tick.Info'nun ilk kelimesi (alanlar +0..+3)
+pub fn tick_info_slot(tick: i32) -> U256 {
+    signed_mapping_slot(tick.into(), TICKS_SLOT)
+}
+
+/// `tickBitmap[word]`
+pub fn bitmap_word_slot(word: i16) -> U256 {
+    signed_mapping_slot(word.into(), TICK_BITMAP_SLOT)
+}
+
+/// PositionKey: keccak256(abi.encodePacked(owner, int24 lower, int24 upper))
+pub fn position_key(owner: Address, tick_lower: i32, tick_upper: i32) -> B256 {
+    let mut preimage = [0u8; 26];
+    preimage[..20].copy_from_slice(owner.as_slice());
+    preimage[20..23].copy_from_slice(&tick_lower.to_be_bytes()[1..]);
+    preimage[23..].copy_from_slice(&tick_upper.to_be_bytes()[1..]);
+    keccak256(preimage)
+}
+
+/// `positions[key]` — Position.Info'nun ilk kelimesi (alanlar +0..+3)
+pub fn position_slot(key: B256) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(key.as_slice());
+    preimage[32..].copy_from_slice(&U256::from(POSITIONS_SLOT).to_be_bytes::<32>());
+    U256::from_be_bytes(keccak256(preimage).0)
+}
+
+/// Tick.Info yaz: gross | net << 128 ve `initialized` (kelime +3, bit 248).
+/// feeGrowthOutside / oracle alanları sıfır kalır.
+pub fn write_tick(storage: &mut BTreeMap<U256, U256>, tick: i32, liquidity_gross: u128, liquidity_net: i128) {
+    let base = tick_info_slot(tick);
+    let net = U256::from(liquidity_net as u128);
+    storage.insert(base, U256::from(liquidity_gross) | (net << 128));
+    storage.insert(base + U256::from(3u64), U256::from(1u64) << 248);
+}
+
+/// tickBitmap kelimesini yaz
+pub fn write_bitmap_word(storage: &mut BTreeMap<U256, U256>, word: i16, bits: U256) {
+    storage.insert(bitmap_word_slot(word), bits);
+}
+
+/// Position.Info yaz: liquidity (fee / tokensOwed alanları sıfır)
+pub fn write_position(
+    storage: &mut BTreeMap<U256, U256>,
+    owner: Address,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+) {
+    storage.insert(position_slot(position_key(owner, tick_lower, tick_upper)), U256::from(liquidity));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Builder
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Pozisyon kümesinden hermetik havuz kurar.
+///
+/// Fiyat varsayılanı tick 0; pozisyon sınırları tick_spacing'in katı olmalı.
+#[derive(Debug, Clone)]
+pub struct V3PoolBuilder {
+    pool: PoolConfig,
+    sqrt_price_x96: U256,
+    positions: Vec<(i32, i32, u128)>,
+    block: u64,
+}
+
+impl V3PoolBuilder {
+    pub fn new(pool: PoolConfig) -> Self {
+        Self { pool, sqrt_price_x96: math::exact::get_sqrt_ratio_at_tick(0), positions: Vec::new(), block: 1 }
+    }
+
+    /// Fiyatı tick'in tam sınırına koy
+    pub fn at_tick(self, tick: i32) -> Self {
+        self.at_sqrt_price(math::exact::get_sqrt_ratio_at_tick(tick))
+    }
+
+    pub fn at_sqrt_price(mut self, sqrt_price_x96: U256) -> Self {
+        self.sqrt_price_x96 = sqrt_price_x96;
+        self
+    }
+
+    /// [tick_lower, tick_upper) aralığına likidite ekle (mint karşılığı)
+    pub fn position(mut self, tick_lower: i32, tick_upper: i32, liquidity: u128) -> Self {
+        let spacing = self.pool.tick_spacing;
+        assert!(tick_lower < tick_upper, "pozisyon aralığı boş: [{}, {})", tick_lower, tick_upper);
+        assert!(
+            tick_lower % spacing == 0 && tick_upper % spacing == 0,
+            "pozisyon sınırları tick_spacing={} katı olmalı",
+            spacing
+        );
+        assert!(tick_lower >= math::exact::MIN_TICK && tick_upper <= math::exact::MAX_TICK);
+        self.positions.push((tick_lower, tick_upper, liquidity));
+        self
+    }
+
+    pub fn pool(&self) -> &PoolConfig {
+        &self.pool
+    }
+
+    pub fn tick(&self) -> i32 {
+        math::exact::get_tick_at_sqrt_ratio(self.sqrt_price_x96)
+    }
+
+    /// Mevcut tick'i kapsayan pozisyonların toplamı
+    pub fn active_liquidity(&self) -> u128 {
+        let tick = self.tick();
+        self.positions
+            .iter()
+            .filter(|(lower, upper, _)| *lower <= tick && tick < *upper)
+            .map(|(_, _, liquidity)| liquidity)
+            .sum()
+    }
+
+    /// Başlatılmış tick'ler: tick → (gross, net)
+    fn tick_liquidity(&self) -> BTreeMap<i32, (u128, i128)> {
+        let mut ticks: BTreeMap<i32, (u128, i128)> = BTreeMap::new();
+        for &(lower, upper, liquidity) in &self.positions {
+            let signed = i128::try_from(liquidity).expect("liquidity int128'e sığmalı");
+            let entry = ticks.entry(lower).or_default();
+            entry.0 += liquidity;
+            entry.1 += signed;
+            let entry = ticks.entry(upper).or_default();
+            entry.0 += liquidity;
+            entry.1 -= signed;
+        }
+        ticks
+    }
+
+    /// Bitmap kelimeleri: compressed = tick / spacing, kelime = compressed >> 8
+    fn bitmap_words(&self) -> HashMap<i16, U256> {
+        let mut words: HashMap<i16, U256> = HashMap::new();
+        for tick in self.tick_liquidity().keys() {
+            let compressed = tick / self.pool.tick_spacing;
+            *words.entry((compressed >> 8) as i16).or_default() |= U256::from(1u64) << (compressed & 0xff) as usize;
+        }
+        words
+    }
+
+    /// ticks + tickBitmap + positions slot'ları (slot0 / liquidity hariç —
+    /// onları motor PoolState'ten yazar)
+    pub fn storage(&self) -> BTreeMap<U256, U256> {
+        let mut storage = BTreeMap::new();
+        for (tick, (gross, net)) in self.tick_liquidity() {
+            write_tick(&mut storage, tick, gross, net);
+        }
+        for (word, bits) in self.bitmap_words() {
+            write_bitmap_word(&mut storage, word, bits);
+        }
+        for &(lower, upper, liquidity) in &self.positions {
+            write_position(&mut storage, POSITION_OWNER, lower, upper, liquidity);
+        }
+        storage
+    }
+
+    /// Havuz storage'ını taşıyan what-if katmanı. Testler kendi
+    /// geçersiz kılmalarını bunun üstüne ekler (set_overrides katmanı değiştirir).
+    pub fn overrides(&self) -> StateOverrides {
+        let mut overrides = StateOverrides::default();
+        overrides.storage.insert(self.pool.address, self.storage());
+        overrides
+    }
+
+    /// Botun tick haritası — REVM havuzuyla aynı tick'ler, tam kapsam
+    pub fn bitmap(&self) -> TickBitmapData {
+        let ticks = self
+            .tick_liquidity()
+            .into_iter()
+            .map(|(tick, (gross, net))| {
+                let info = TickInfo {
+                    liquidity_gross: gross,
+                    liquidity_net: net,
+                    initialized: true,
+                    staked_liquidity_net: 0,
+                };
+                (tick, info)
+            })
+            .collect();
+        TickBitmapData {
+            words: self.bitmap_words(),
+            ticks,
+            snapshot_block: self.block,
+            scanned_ticks: Some((math::exact::MIN_TICK, math::exact::MAX_TICK)),
+            ..TickBitmapData::empty()
+        }
+    }
+
+    pub fn state(&self) -> PoolState {
+        let tick = self.tick();
+        let liquidity = self.active_liquidity();
+        let sqrt_price_f64 = math::exact::u256_to_f64(self.sqrt_price_x96);
+        PoolState {
+            sqrt_price_x96: self.sqrt_price_x96,
+            sqrt_price_f64,
+            tick,
+            liquidity,
+            liquidity_f64: liquidity as f64,
+            eth_price_usd: math::compute_eth_price(
+                sqrt_price_f64,
+                tick,
+                self.pool.token0_decimals,
+                self.pool.token1_decimals,
+                self.pool.token0_is_weth,
+            ),
+            last_block: self.block,
+            is_initialized: true,
+            tick_bitmap: Some(Box::new(self.bitmap())),
+            ..PoolState::default()
+        }
+    }
+
+    /// Bytecode'u önbelleğe almış ve havuz storage'ını katman olarak taşıyan
+    /// motor + eşleşen PoolState + TickBitmapData
+    pub fn build(&self) -> (SimulationEngine, PoolState, TickBitmapData) {
+        let store: StaticPoolStore = Arc::new(RwLock::new(HashMap::new()));
+        store
+            .write()
+            .insert(self.pool.address, StaticPoolData { bytecode: Some(Arc::new(pool_code(&self.pool))) });
+        let mut engine = SimulationEngine::new(8453);
+        engine.cache_bytecodes(std::slice::from_ref(&self.pool), &store);
+        engine.set_overrides(self.overrides());
+        (engine, self.state(), self.bitmap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DexType, FeeTier};
+
+    const POOL: Address = address!("00000000000000000000000000000000000f1f00");
+    const WETH: Address = address!("4200000000000000000000000000000000000006");
+    const USDC: Address = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+
+    fn pool_config() -> PoolConfig {
+        PoolConfig {
+            address: POOL,
+            name: "fixture-v3".into(),
+            fee: FeeTier::from_bps(30),
+            token0_decimals: 18,
+            token1_decimals: 6,
+            dex: DexType::UniswapV3,
+            token0_is_weth: true,
+            tick_spacing: 60,
+            quote_token_address: USDC,
+            base_token_address: WETH,
+        }
+    }
+
+    #[test]
+    fn test_assembler_push_widths_and_labels() {
+        let code = assemble("0 0xff 0x100 @end JUMP\nend: STOP", &[]).unwrap();
+        assert_eq!(code, vec![0x5f, 0x60, 0xff, 0x61, 0x01, 0x00, 0x61, 0x00, 0x0a, 0x56, 0x5b, 0x00]);
+
+        let code = assemble(".define X 7 ; yorum\n%X $P DUP16 SWAP1", &[("P", U256::from(2u64))]).unwrap();
+        assert_eq!(code, vec![0x60, 0x07, 0x60, 0x02, 0x8f, 0x90]);
+
+        assert!(assemble("@nowhere JUMP", &[]).unwrap_err().contains("nowhere"));
+        assert!(assemble("FROB", &[]).unwrap_err().contains("line 1"));
+        assert!(assemble("DUP17", &[]).is_err());
+        assert!(assemble("$TOKEN0", &[]).unwrap_err().contains("TOKEN0"));
+    }
+
+    /// Slot anahtarları kanonik Solidity kodlamasıyla: negatif int24 / int16
+    /// işaret genişletilerek 32 byte'a yayılır, PositionKey 26 byte paketlenir
+    #[test]
+    fn test_mapping_slots_follow_solidity_encoding() {
+        let mut preimage = [0xffu8; 64];
+        preimage[31] = 0xc4; // -60
+        preimage[32..].copy_from_slice(&U256::from(TICKS_SLOT).to_be_bytes::<32>());
+        assert_eq!(tick_info_slot(-60), U256::from_be_bytes(keccak256(preimage).0));
+
+        let mut preimage = [0u8; 64];
+        preimage[31] = 3;
+        preimage[63] = TICK_BITMAP_SLOT as u8;
+        assert_eq!(bitmap_word_slot(3), U256::from_be_bytes(keccak256(preimage).0));
+
+        let key = position_key(POSITION_OWNER, -120, 60);
+        let mut packed = POSITION_OWNER.to_vec();
+        packed.extend_from_slice(&[0xff, 0xff, 0x88, 0x00, 0x00, 0x3c]);
+        assert_eq!(key, keccak256(&packed));
+        assert_ne!(position_slot(key), tick_info_slot(-120));
+    }
+
+    #[test]
+    fn test_builder_derives_ticks_bitmap_and_active_liquidity() {
+        let builder = V3PoolBuilder::new(pool_config())
+            .at_tick(-30)
+            .position(-600, 600, 1_000)
+            .position(-60, 0, 500)
+            .position(-15_960, -15_360, 7);
+        let (_, state, bitmap) = builder.build();
+
+        assert_eq!(state.tick, -30);
+        assert_eq!(state.liquidity, 1_500);
+        assert_eq!(state.tick_bitmap.as_deref().map(|b| b.ticks.len()), Some(6));
+
+        let net = |tick: i32| bitmap.ticks.get(&tick).map(|info| info.liquidity_net);
+        assert_eq!(net(-600), Some(1_000));
+        assert_eq!(net(0), Some(-500));
+        assert_eq!(net(-15_360), Some(-7));
+        assert_eq!(net(30), None);
+
+        // compressed -1 → kelime -1 bit 255; -256 → kelime -1 bit 0; -266 → kelime -2 bit 246
+        assert!(bitmap.words[&-1].bit(255) && bitmap.words[&-1].bit(0));
+        assert!(bitmap.words[&-2].bit(246));
+        assert_eq!(bitmap.words[&0], U256::from(1u64) | (U256::from(1u64) << 10));
+        assert_eq!(bitmap.words.len(), 3);
+
+        let storage = builder.storage();
+        let info = storage[&tick_info_slot(-60)];
+        assert_eq!(info & U256::from(u128::MAX), U256::from(500u64));
+        assert_eq!(storage[&(tick_info_slot(-60) + U256::from(3u64))], U256::from(1u64) << 248);
+        assert_eq!(storage[&position_slot(position_key(POSITION_OWNER, -60, 0))], U256::from(500u64));
+    }
+
+    /// Derlenen havuz getter'ları builder'ın yazdığı storage'ı kanonik ABI
+    /// ile okur ve swap deltaları exact matematikle birebir eşleşir
+    #[test]
+    fn test_fixture_pool_quotes_single_range_exactly() {
+        let pool = pool_config();
+        let liquidity = 2_000_000_000_000_000_000u128;
+        let (sim, state, _) = V3PoolBuilder::new(pool.clone())
+            .at_tick(-197_280)
+            .position(-887_220, 887_220, liquidity)
+            .build();
+
+        for (amount_in, zero_for_one) in [
+            (U256::from(10u64).pow(U256::from(15u64)), true),
+            (U256::from(10u64).pow(U256::from(17u64)), true),
+            (U256::from(1_000_000u64), false),
+            (U256::from(250_000_000u64), false),
+        ] {
+            let revm = sim.quote_swap(&pool, &state, amount_in, zero_for_one).unwrap();
+            let exact = math::exact::compute_exact_swap_presorted(
+                state.sqrt_price_x96,
+                liquidity,
+                state.tick,
+                amount_in,
+                zero_for_one,
+                pool.fee_pips(),
+                &[],
+            );
+            assert_eq!(revm, exact.amount_out, "amount={} zfo={}", amount_in, zero_for_one);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+// ============================================================================
+//  WARMUP v1.0 — Soğuk Başlangıç Isınma Kapısı
+//
+//  Bağlantı (ilk açılış ya da reconnect) sonrasındaki ilk bloklarda state
+//  henüz oturmamış olabilir: event listener yeni bağlanmıştır, bitmap'ler
+//  yeniden çekilmektedir, safety net henüz bir tur dönmemiştir. Bu pencerede
+//  bulunan fırsatlar yarım veriyle hesaplanır.
+//
+//  ✓ Her başarılı bağlantıdan sonra yürütme kapanır — fırsatlar yalnızca
+//    gölge loglanır (filtre adı "warmup")
+//  ✓ WARMUP_CLEAN_BLOCKS ardışık temiz blok (tüm havuzlar senkron, bitmap'ler
+//    taze, sync hatası yok) tamamlanınca kapı açılır ("armed")
+//  ✓ Isınma sırasında herhangi bir hata sayacı sıfırlar
+//  ✓ Circuit breaker'ın süre sonu denemesi (probe) kapı açılana kadar bekler
+//
+//  WARMUP_CLEAN_BLOCKS=0 → kapı devre dışı, bağlantı anında armed.
+// ============================================================================
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Blok Sağlığı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek bir bloğun ısınma açısından sağlık özeti
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHealth {
+    /// Tüm havuzlar başlatılmış ve stale değil
+    pub all_synced: bool,
+    /// Bitmap'i olan tüm havuzlarda bitmap TICK_BITMAP_MAX_AGE_BLOCKS içinde
+    pub bitmaps_fresh: bool,
+    /// Bu blokta başarısız olan sync çağrısı sayısı
+    pub sync_errors: usize,
+}
+
+impl BlockHealth {
+    /// Bloğu kirli yapan ilk neden (temizse None)
+    pub fn problem(&self) -> Option<&'static str> {
+        if self.sync_errors > 0 {
+            Some("sync error")
+        } else if !self.all_synced {
+            Some("pools not synced")
+        } else if !self.bitmaps_fresh {
+            Some("stale bitmap")
+        } else {
+            None
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Isınma Kapısı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Bir bloğun kapı üzerindeki etkisi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarmupStep {
+    /// Kapı zaten açık — değişiklik yok
+    Armed,
+    /// Temiz blok sayıldı, henüz yeterli değil
+    Progress { clean: u32, required: u32 },
+    /// Bu blokla eşik doldu — yürütme açıldı
+    JustArmed,
+    /// Isınma sırasında hata — sayaç sıfırlandı
+    Reset(&'static str),
+}
+
+/// Bağlantı başına ısınma durum makinesi.
+/// main() içinde bir kez oluşturulur; her run_bot girişinde `on_connect`
+/// ile yeniden kapanır, böylece reconnect sayıları da korunur.
+#[derive(Debug, Clone)]
+pub struct WarmupGate {
+    required: u32,
+    clean_streak: u32,
+    armed: bool,
+    /// Isınma sırasında hata nedeniyle sıfırlama sayısı
+    resets: u64,
+    /// Isınma tamamlanmadan gelen reconnect sayısı
+    interrupted: u64,
+    /// Toplam başarılı bağlantı sayısı
+    connects: u64,
+}
+
+impl WarmupGate {
+    pub fn new(required: u32) -> Self {
+        Self {
+            required,
+            clean_streak: 0,
+            armed: required == 0,
+            resets: 0,
+            interrupted: 0,
+            connects: 0,
+        }
+    }
+
+    /// Başarılı bağlantı (ilk ya da reconnect) — kapı yeniden kapanır
+    pub fn on_connect(&mut self) {
+        if self.connects > 0 && !self.armed {
+            self.interrupted += 1;
+        }
+        self.connects += 1;
+        self.clean_streak = 0;
+        self.armed = self.required == 0;
+    }
+
+    /// Tamamlanan bloğu işle. Kapı açıldıktan sonra hatalar burada
+    /// değil circuit breaker'da ele alınır.
+    pub fn on_block(&mut self, health: &BlockHealth) -> WarmupStep {
+        if self.armed {
+            return WarmupStep::Armed;
+        }
+        if let Some(problem) = health.problem() {
+            if self.clean_streak > 0 {
+                self.resets += 1;
+            }
+            self.clean_streak = 0;
+            return WarmupStep::Reset(problem);
+        }
+        self.clean_streak += 1;
+        if self.clean_streak >= self.required {
+            self.armed = true;
+            WarmupStep::JustArmed
+        } else {
+            WarmupStep::Progress {
+                clean: self.clean_streak,
+                required: self.required,
+            }
+        }
+    }
+
+    pub fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    pub fn resets(&self) -> u64 {
+        self.resets
+    }
+
+    pub fn interrupted(&self) -> u64 {
+        self.interrupted
+    }
+
+    /// Blok satırı / istatistik için kısa durum etiketi
+    pub fn status_label(&self) -> String {
+        if self.armed {
+            "armed".to_string()
+        } else {
+            format!("warmup {}/{}", self.clean_streak, self.required)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLEAN: BlockHealth = BlockHealth {
+        all_synced: true,
+        bitmaps_fresh: true,
+        sync_errors: 0,
+    };
+
+    #[test]
+    fn test_arms_after_consecutive_clean_blocks() {
+        let mut gate = WarmupGate::new(3);
+        gate.on_connect();
+        assert!(!gate.is_armed());
+        assert_eq!(gate.on_block(&CLEAN), WarmupStep::Progress { clean: 1, required: 3 });
+        assert_eq!(gate.on_block(&CLEAN), WarmupStep::Progress { clean: 2, required: 3 });
+        assert_eq!(gate.status_label(), "warmup 2/3");
+        assert_eq!(gate.on_block(&CLEAN), WarmupStep::JustArmed);
+        assert!(gate.is_armed());
+        assert_eq!(gate.on_block(&CLEAN), WarmupStep::Armed);
+        assert_eq!(gate.status_label(), "armed");
+    }
+
+    #[test]
+    fn test_error_during_warmup_resets_counter() {
+        let mut gate = WarmupGate::new(3);
+        gate.on_connect();
+        gate.on_block(&CLEAN);
+        gate.on_block(&CLEAN);
+
+        let sync_error = BlockHealth { sync_errors: 1, ..CLEAN };
+        assert_eq!(gate.on_block(&sync_error), WarmupStep::Reset("sync error"));
+        assert_eq!(gate.resets(), 1);
+        assert_eq!(gate.status_label(), "warmup 0/3");
+
+        let stale = BlockHealth { bitmaps_fresh: false, ..CLEAN };
+        assert_eq!(gate.on_block(&stale), WarmupStep::Reset("stale bitmap"));
+        // Zaten sıfırdaydı — ikinci sıfırlama sayılmaz
+        assert_eq!(gate.resets(), 1);
+
+        let unsynced = BlockHealth { all_synced: false, ..CLEAN };
+        assert_eq!(gate.on_block(&unsynced), WarmupStep::Reset("pools not synced"));
+
+        for _ in 0..2 {
+            gate.on_block(&CLEAN);
+        }
+        assert!(!gate.is_armed());
+        assert_eq!(gate.on_block(&CLEAN), WarmupStep::JustArmed);
+    }
+
+    #[test]
+    fn test_reconnect_mid_warmup_restarts() {
+        let mut gate = WarmupGate::new(3);
+        gate.on_connect();
+        gate.on_block(&CLEAN);
+        gate.on_block(&CLEAN);
+
+        // Eşiğe bir blok kala bağlantı koptu
+        gate.on_connect();
+        assert_eq!(gate.interrupted(), 1);
+        assert_eq!(gate.status_label(), "warmup 0/3");
+        assert_eq!(gate.on_block(&CLEAN), WarmupStep::Progress { clean: 1, required: 3 });
+
+        // Armed iken reconnect de kapıyı kapatır, ama yarıda kalma sayılmaz
+        gate.on_block(&CLEAN);
+        gate.on_block(&CLEAN);
+        assert!(gate.is_armed());
+        gate.on_connect();
+        assert!(!gate.is_armed());
+        assert_eq!(gate.interrupted(), 1);
+    }
+
+    #[test]
+    fn test_armed_gate_ignores_errors_and_zero_disables() {
+        let mut gate = WarmupGate::new(1);
+        gate.on_connect();
+        assert_eq!(gate.on_block(&CLEAN), WarmupStep::JustArmed);
+        let broken = BlockHealth { sync_errors: 2, all_synced: false, bitmaps_fresh: false };
+        assert_eq!(gate.on_block(&broken), WarmupStep::Armed);
+        assert!(gate.is_armed());
+
+        let mut disabled = WarmupGate::new(0);
+        disabled.on_connect();
+        assert!(disabled.is_armed());
+        assert_eq!(disabled.on_block(&broken), WarmupStep::Armed);
+    }
+}
@@ -0,0 +1,732 @@
+// ============================================================================
+//  WHAT-IF v1.0 — Kayıtlı Blok Durumu Üzerinde Hipotetik Değerlendirme
+//
+//  Kullanım:
+//    --what-if --block N --override '<ifade>' [--override ...]
+//              [--combo I] [--state-diff state_diff.jsonl]
+//
+//  İfadeler:
+//  ✓ poolA|poolB.<liquidity|tick|sqrtPriceX96> (=|+=|-=|*=|/=) <değer>
+//    Değer: ondalık veya 0x hex tamsayı (alt çizgi serbest); *= ve /=
+//    ondalık kesir de kabul eder (liquidity*=1.5). tick yalnızca =, +=, -=
+//  ✓ 0x<adres>[<slot>]=<değer> — ham storage yazımı (yalnızca =; yalnızca
+//    REVM db'sine gider, matematik yolu havuz skalerlerini görür)
+//
+//  Akış:
+//  ✓ Blok N'in durumu state_diff.jsonl'dan Reconstructor ile kurulur
+//    (bitmap snapshots/ arşivinden, replay ile aynı yol)
+//  ✓ Göreli ifadeler sırayla mevcut duruma göre MUTLAK StateOverrides'a
+//    çözülür; aynı katman hem PoolState kopyalarına (f64 matematik yolu)
+//    hem SimulationEngine::set_overrides'a (REVM) verilir
+//  ✓ Önce / sonra: check_arbitrage_opportunity + exact kâr + REVM tur
+//    kârı, yan yana yazdırılır
+// ============================================================================
+
+use alloy::primitives::{Address, I256, U256};
+use alloy::providers::ProviderBuilder;
+use arc_swap::ArcSwap;
+use colored::*;
+use eyre::{bail, eyre, Result};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::math::exact::{get_sqrt_ratio_at_tick, u256_to_f64, MAX_TICK, MIN_TICK};
+use crate::opportunity_filter::FilterChain;
+use crate::pool_discovery::PairCombo;
+use crate::replay::{load_snapshot_archive, read_state_diff, BitmapArchive, BlockRecord, Reconstructor};
+use crate::simulator::{SimulationEngine, StateOverrides};
+use crate::strategy::{check_arbitrage_opportunity, exact_profit_for_opportunity, revm_arbitrage_profit};
+use crate::types::{
+    ArbitrageOpportunity, BotConfig, CompetitionSnapshot, PoolConfig, PoolState, SharedPoolState,
+    StaticPoolStore,
+};
+
+/// REVM için havuz bytecode'u okuma süresi — aşılırsa yalnızca matematik
+const BYTECODE_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// *= / /= ondalık kesirlerinde en fazla basamak
+const MAX_FRACTION_DIGITS: usize = 18;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// İfade Ayrıştırıcı
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Çiftin hangi bacağı (combo'nun pool_a / pool_b'si)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolLeg {
+    A,
+    B,
+}
+
+impl PoolLeg {
+    fn index(self) -> usize {
+        match self {
+            PoolLeg::A => 0,
+            PoolLeg::B => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolField {
+    SqrtPriceX96,
+    Tick,
+    Liquidity,
+}
+
+/// Mevcut değere uygulanacak işlem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjustment {
+    Set(I256),
+    Add(I256),
+    Sub(I256),
+    /// değer × num / den (aşağı yuvarlanır)
+    Scale { num: U256, den: U256 },
+}
+
+impl Adjustment {
+    fn apply(self, current: I256) -> Option<I256> {
+        match self {
+            Adjustment::Set(v) => Some(v),
+            Adjustment::Add(v) => current.checked_add(v),
+            Adjustment::Sub(v) => current.checked_sub(v),
+            Adjustment::Scale { num, den } => {
+                if current.is_negative() {
+                    return None;
+                }
+                let scaled = current.into_raw().checked_mul(num)?.checked_div(den)?;
+                I256::try_from(scaled).ok()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverrideExpr {
+    Pool { leg: PoolLeg, field: PoolField, adjustment: Adjustment },
+    Storage { address: Address, slot: U256, value: U256 },
+}
+
+/// Tek --override ifadesini ayrıştır
+pub fn parse_override(input: &str) -> Result<OverrideExpr> {
+    let expr = input.trim();
+    let eq = expr
+        .find('=')
+        .ok_or_else(|| eyre!("override '{}': missing '=' (e.g. poolA.liquidity*=2)", input))?;
+    let (mut lhs, rhs) = (&expr[..eq], expr[eq + 1..].trim());
+    let op = match lhs.chars().last() {
+        Some(c @ ('+' | '-' | '*' | '/')) => {
+            lhs = &lhs[..lhs.len() - 1];
+            Some(c)
+        }
+        _ => None,
+    };
+    let lhs = lhs.trim();
+    if rhs.is_empty() {
+        bail!("override '{}': missing value", input);
+    }
+
+    // ── Ham storage: 0x<adres>[<slot>]=<değer> ──
+    if let Some(target) = lhs.strip_suffix(']') {
+        let (address, slot) = target
+            .split_once('[')
+            .ok_or_else(|| eyre!("override '{}': expected 0x<address>[<slot>]", input))?;
+        if op.is_some() {
+            bail!("override '{}': storage overrides only support '='", input);
+        }
+        let address: Address = address
+            .trim()
+            .parse()
+            .map_err(|e| eyre!("override '{}': bad address: {}", input, e))?;
+        return Ok(OverrideExpr::Storage {
+            address,
+            slot: parse_unsigned(slot.trim()).map_err(|e| eyre!("override '{}': slot {}", input, e))?,
+            value: parse_unsigned(rhs).map_err(|e| eyre!("override '{}': value {}", input, e))?,
+        });
+    }
+
+    // ── Havuz skaleri: poolA.liquidity*=2 ──
+    let (leg, field) = lhs
+        .split_once('.')
+        .ok_or_else(|| eyre!("override '{}': expected poolA.<field> or poolB.<field>", input))?;
+    let leg = match leg.trim().to_ascii_lowercase().as_str() {
+        "poola" | "pool_a" => PoolLeg::A,
+        "poolb" | "pool_b" => PoolLeg::B,
+        other => bail!("override '{}': unknown pool '{}' (poolA | poolB)", input, other),
+    };
+    let field = match field.trim().to_ascii_lowercase().as_str() {
+        "liquidity" => PoolField::Liquidity,
+        "tick" => PoolField::Tick,
+        "sqrtpricex96" | "sqrt_price_x96" => PoolField::SqrtPriceX96,
+        other => bail!(
+            "override '{}': unknown field '{}' (liquidity | tick | sqrtPriceX96)",
+            input,
+            other
+        ),
+    };
+
+    let value_err = |e: String| eyre!("override '{}': value {}", input, e);
+    let adjustment = match op {
+        None => Adjustment::Set(parse_signed(rhs).map_err(value_err)?),
+        Some('+') => Adjustment::Add(parse_signed(rhs).map_err(value_err)?),
+        Some('-') => Adjustment::Sub(parse_signed(rhs).map_err(value_err)?),
+        Some(op) => {
+            if field == PoolField::Tick {
+                bail!("override '{}': tick only supports =, += and -=", input);
+            }
+            let (num, den) = parse_ratio(rhs).map_err(value_err)?;
+            if op == '*' {
+                Adjustment::Scale { num, den }
+            } else if num.is_zero() {
+                bail!("override '{}': division by zero", input);
+            } else {
+                Adjustment::Scale { num: den, den: num }
+            }
+        }
+    };
+    Ok(OverrideExpr::Pool { leg, field, adjustment })
+}
+
+/// Negatif olmayan tamsayı: ondalık veya 0x hex, alt çizgi serbest
+fn parse_unsigned(raw: &str) -> std::result::Result<U256, String> {
+    let cleaned = raw.replace('_', "");
+    let (digits, radix) = match cleaned.strip_prefix("0x").or_else(|| cleaned.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None => (cleaned.as_str(), 10),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(format!("'{}' is not an integer", raw));
+    }
+    U256::from_str_radix(digits, radix as u64).map_err(|e| format!("'{}': {}", raw, e))
+}
+
+fn parse_signed(raw: &str) -> std::result::Result<I256, String> {
+    let (negative, magnitude) = match raw.strip_prefix('-') {
+        Some(rest) => (true, rest.trim()),
+        None => (false, raw),
+    };
+    let value = I256::try_from(parse_unsigned(magnitude)?)
+        .map_err(|_| format!("'{}' does not fit in int256", raw))?;
+    if negative {
+        value.checked_neg().ok_or_else(|| format!("'{}' does not fit in int256", raw))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Çarpan / bölen: tamsayı veya ondalık kesir ("1.5" → 15 / 10)
+fn parse_ratio(raw: &str) -> std::result::Result<(U256, U256), String> {
+    let cleaned = raw.replace('_', "");
+    let Some((int, frac)) = cleaned.split_once('.') else {
+        return Ok((parse_unsigned(raw)?, U256::from(1u64)));
+    };
+    let digits = format!("{}{}", int, frac);
+    if digits.is_empty() || frac.len() > MAX_FRACTION_DIGITS || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("'{}' is not a decimal (max {} fraction digits)", raw, MAX_FRACTION_DIGITS));
+    }
+    let num = U256::from_str_radix(&digits, 10).map_err(|e| format!("'{}': {}", raw, e))?;
+    Ok((num, U256::from(10u64).pow(U256::from(frac.len()))))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Mutlak Katmana Çözümleme
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// İfadeleri sırayla uygula: her ifade önceki ifadelerin hipotetik
+/// değerini görür (poolA.tick+=10 iki kez → +20). tick ve sqrtPriceX96 aynı
+/// fiyatı taşır — sonuncusu geçerlidir, diğeri ondan türetilir.
+pub fn resolve_overrides(
+    exprs: &[OverrideExpr],
+    pools: [&PoolConfig; 2],
+    states: [&PoolState; 2],
+) -> Result<StateOverrides> {
+    let min_sqrt = get_sqrt_ratio_at_tick(MIN_TICK);
+    let max_sqrt = get_sqrt_ratio_at_tick(MAX_TICK);
+    let mut out = StateOverrides::default();
+
+    for expr in exprs {
+        let (leg, field, adjustment) = match *expr {
+            OverrideExpr::Storage { address, slot, value } => {
+                out.storage.entry(address).or_default().insert(slot, value);
+                continue;
+            }
+            OverrideExpr::Pool { leg, field, adjustment } => (leg, field, adjustment),
+        };
+        let pool = pools[leg.index()];
+        let entry = out.pools.entry(pool.address).or_default();
+        let (sqrt_price_x96, tick, liquidity) = entry.resolve(states[leg.index()]);
+        let overflow = || eyre!("{} {:?}: arithmetic overflow", pool.name, field);
+
+        match field {
+            PoolField::Liquidity => {
+                let current = I256::try_from(U256::from(liquidity)).map_err(|_| overflow())?;
+                let next = adjustment.apply(current).ok_or_else(overflow)?;
+                let next = u128::try_from(next)
+                    .ok()
+                    .filter(|l| *l > 0)
+                    .ok_or_else(|| eyre!("{} liquidity {} out of range (1..=u128::MAX)", pool.name, next))?;
+                entry.liquidity = Some(next);
+            }
+            PoolField::Tick => {
+                let current = I256::try_from(i64::from(tick)).map_err(|_| overflow())?;
+                let next = adjustment.apply(current).ok_or_else(overflow)?;
+                let next = i32::try_from(next)
+                    .ok()
+                    .filter(|t| (MIN_TICK..=MAX_TICK).contains(t))
+                    .ok_or_else(|| eyre!("{} tick {} out of range ({}..={})", pool.name, next, MIN_TICK, MAX_TICK))?;
+                entry.tick = Some(next);
+                entry.sqrt_price_x96 = None;
+            }
+            PoolField::SqrtPriceX96 => {
+                let current = I256::try_from(sqrt_price_x96).map_err(|_| overflow())?;
+                let next = adjustment.apply(current).ok_or_else(overflow)?;
+                if next.is_negative() || next.into_raw() < min_sqrt || next.into_raw() >= max_sqrt {
+                    bail!("{} sqrtPriceX96 {} out of range [{}, {})", pool.name, next, min_sqrt, max_sqrt);
+                }
+                entry.sqrt_price_x96 = Some(next.into_raw());
+                entry.tick = None;
+            }
+        }
+    }
+    Ok(out)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Önce / Sonra Değerlendirmesi
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tek durumun (gerçek veya hipotetik) fırsat değerlendirmesi
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    pub states: [PoolState; 2],
+    pub opportunity: Option<ArbitrageOpportunity>,
+    /// U256 exact matematikle doğrulanmış kâr (WETH)
+    pub exact_profit_weth: Option<f64>,
+    /// NR optimumunda REVM tur kârı (WETH, gas hariç) — motor yoksa None
+    pub revm_profit_weth: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WhatIfReport {
+    pub block: u64,
+    pub overrides: StateOverrides,
+    /// Kayıtta bitmap vardı ama arşivde yok — tek-tick fallback
+    pub bitmap_fallback: bool,
+    pub before: Evaluation,
+    pub after: Evaluation,
+}
+
+/// Blok N'in kayıtlı durumunu kur, ifadeleri uygula, iki tarafı değerlendir.
+/// Motor verilmişse önce katmansız, sonra katmanlı çalışır ve katman
+/// dönüşte kaldırılır.
+pub fn what_if_at_block(
+    records: &[BlockRecord],
+    archive: &BitmapArchive,
+    block: u64,
+    pools: &[PoolConfig; 2],
+    exprs: &[OverrideExpr],
+    config: &BotConfig,
+    mut engine: Option<&mut SimulationEngine>,
+) -> Result<WhatIfReport> {
+    let mut recon = Reconstructor::default();
+    let mut record_at_block = None;
+    for record in records.iter().take_while(|r| r.block <= block) {
+        recon.apply(record);
+        if record.block == block {
+            record_at_block = Some(record);
+        }
+    }
+    let record = record_at_block.ok_or_else(|| match (records.first(), records.last()) {
+        (Some(first), Some(last)) => eyre!(
+            "block {} is not in the state diff (recorded {}..={})",
+            block,
+            first.block,
+            last.block
+        ),
+        _ => eyre!("state diff is empty"),
+    })?;
+
+    let mut bitmap_fallback = false;
+    let mut known = Vec::with_capacity(2);
+    for pool in pools {
+        let (state, fallback) = recon.state(pool.address, block, archive).ok_or_else(|| {
+            eyre!("{} state unknown at block {} (no keyframe since the last gap)", pool.name, block)
+        })?;
+        bitmap_fallback |= fallback;
+        known.push(state);
+    }
+    let before: [PoolState; 2] = [known[0].clone(), known[1].clone()];
+
+    let overrides = resolve_overrides(exprs, [&pools[0], &pools[1]], [&before[0], &before[1]])?;
+    let after = [
+        overrides.apply_to_state(&pools[0], &before[0]),
+        overrides.apply_to_state(&pools[1], &before[1]),
+    ];
+
+    let filters = FilterChain::from_config(config);
+    if let Some(engine) = engine.as_deref_mut() {
+        engine.clear_overrides();
+    }
+    let before = evaluate(pools, before, record, config, &filters, engine.as_deref());
+    if let Some(engine) = engine.as_deref_mut() {
+        engine.set_overrides(overrides.clone());
+    }
+    let after = evaluate(pools, after, record, config, &filters, engine.as_deref());
+    if let Some(engine) = engine {
+        engine.clear_overrides();
+    }
+
+    Ok(WhatIfReport { block, overrides, bitmap_fallback, before, after })
+}
+
+fn evaluate(
+    pools: &[PoolConfig; 2],
+    states: [PoolState; 2],
+    record: &BlockRecord,
+    config: &BotConfig,
+    filters: &FilterChain,
+    engine: Option<&SimulationEngine>,
+) -> Evaluation {
+    let shared: Vec<SharedPoolState> =
+        states.iter().map(|s| Arc::new(ArcSwap::from_pointee(s.clone()))).collect();
+    let competition = CompetitionSnapshot {
+        block_number: record.block,
+        competitor_tx_last_block: record.competitor_txs,
+    };
+    let opportunity = check_arbitrage_opportunity(
+        pools,
+        &shared,
+        config,
+        record.base_fee,
+        record.last_simulated_gas,
+        record.l1_data_fee_wei,
+        &competition,
+        filters,
+        engine,
+    );
+    let exact_profit_weth = opportunity.as_ref().map(|opp| {
+        u256_to_f64(exact_profit_for_opportunity(pools, &states[0], &states[1], opp)) / 1e18
+    });
+    let revm_profit_weth = match (engine, &opportunity) {
+        (Some(engine), Some(opp)) => revm_arbitrage_profit(
+            engine,
+            &pools[opp.sell_pool_idx],
+            &states[opp.sell_pool_idx],
+            &pools[opp.buy_pool_idx],
+            &states[opp.buy_pool_idx],
+            opp.optimal_amount_weth,
+            0.0,
+            1.0,
+        )
+        .ok(),
+        _ => None,
+    };
+    Evaluation { states, opportunity, exact_profit_weth, revm_profit_weth }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CLI: --what-if
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct WhatIfArgs {
+    pub block: u64,
+    pub overrides: Vec<String>,
+    pub combo: usize,
+    pub state_diff: String,
+}
+
+/// İki havuzun bytecode'unu RPC_HTTP_URL'den oku. Başarısızsa None:
+/// değerlendirme yalnızca matematik motoruyla yapılır.
+async fn load_quote_engine(config: &BotConfig, pools: &[PoolConfig; 2]) -> Option<SimulationEngine> {
+    let url: reqwest::Url = match config.rpc_http_url.parse() {
+        Ok(url) => url,
+        Err(e) => {
+            eprintln!("  {} RPC_HTTP_URL parse error ({}) — math engine only", "⚠️".yellow(), e);
+            return None;
+        }
+    };
+    let provider = ProviderBuilder::new().connect_http(url);
+    let store: StaticPoolStore = Arc::new(RwLock::new(HashMap::new()));
+    let fetch = crate::state_sync::cache_all_bytecodes(&provider, pools, &store);
+    match tokio::time::timeout(BYTECODE_FETCH_TIMEOUT, fetch).await {
+        Ok(results) => {
+            if let Some(Err(e)) = results.into_iter().find(|r| r.is_err()) {
+                eprintln!("  {} Pool bytecode fetch failed ({}) — math engine only", "⚠️".yellow(), e);
+                return None;
+            }
+        }
+        Err(_) => {
+            eprintln!(
+                "  {} Pool bytecode fetch timed out after {}s — math engine only",
+                "⚠️".yellow(),
+                BYTECODE_FETCH_TIMEOUT.as_secs()
+            );
+            return None;
+        }
+    }
+    let mut engine = SimulationEngine::new(config.chain_id);
+    engine.cache_bytecodes(pools, &store);
+    Some(engine)
+}
+
+/// Kayıtlı bloğu hipotez altında yeniden değerlendir ve yan yana yazdır.
+pub async fn cli_what_if(
+    args: &WhatIfArgs,
+    config: &BotConfig,
+    pools: &[PoolConfig],
+    pair_combos: &[PairCombo],
+) -> Result<()> {
+    let exprs = args
+        .overrides
+        .iter()
+        .map(|raw| parse_override(raw))
+        .collect::<Result<Vec<_>>>()?;
+    let combo = pair_combos.get(args.combo).ok_or_else(|| {
+        eyre!("--combo {} out of range ({} combo(s) loaded)", args.combo, pair_combos.len())
+    })?;
+    let pp = [pools[combo.pool_a_idx].clone(), pools[combo.pool_b_idx].clone()];
+
+    let (records, _) = read_state_diff(Path::new(&args.state_diff))?;
+    let snapshot_dir = crate::secure_fs::read_path(crate::snapshot::SNAPSHOT_DIR);
+    let (bitmaps, _) = load_snapshot_archive(&snapshot_dir);
+    crate::fee_monitor::init(config);
+    let mut engine = load_quote_engine(config, &pp).await;
+
+    let report = what_if_at_block(&records, &bitmaps, args.block, &pp, &exprs, config, engine.as_mut())?;
+
+    println!();
+    println!(
+        "  {} What-if @ block {} — {} (A: {} | B: {})",
+        "🔮".cyan(),
+        report.block,
+        combo.pair_name,
+        pp[0].name,
+        pp[1].name,
+    );
+    for raw in &args.overrides {
+        println!("     override: {}", raw.yellow());
+    }
+    for (address, slots) in &report.overrides.storage {
+        for (slot, value) in slots {
+            println!("     storage : {}[{:#x}] = {:#x}", address, slot, value);
+        }
+    }
+    if report.bitmap_fallback {
+        println!(
+            "  {} Bitmap snapshot missing from {}/ — single-tick fallback math",
+            "⚠️".yellow(),
+            snapshot_dir.display()
+        );
+    }
+    println!();
+    print_side_by_side(&report, engine.is_some());
+    Ok(())
+}
+
+fn print_side_by_side(report: &WhatIfReport, revm: bool) {
+    let row = |label: &str, f: &dyn Fn(&Evaluation) -> String| {
+        println!("  {:<22} {:<26} {}", label, f(&report.before), f(&report.after));
+    };
+    let weth = |v: Option<f64>| v.map_or_else(|| "-".to_string(), |v| format!("{:.8}", v));
+    let opp_weth = |f: fn(&ArbitrageOpportunity) -> f64| {
+        move |e: &Evaluation| weth(e.opportunity.as_ref().map(f))
+    };
+
+    println!("  {:<22} {} {}", "", format!("{:<26}", "BEFORE").bold(), "AFTER".bold());
+    row("Price A (quote)", &|e| format!("{:.4}", e.states[0].eth_price_usd));
+    row("Price B (quote)", &|e| format!("{:.4}", e.states[1].eth_price_usd));
+    row("Tick A / B", &|e| format!("{} / {}", e.states[0].tick, e.states[1].tick));
+    row("Liquidity A", &|e| e.states[0].liquidity.to_string());
+    row("Liquidity B", &|e| e.states[1].liquidity.to_string());
+    row("Opportunity", &|e| match &e.opportunity {
+        Some(opp) => format!(
+            "buy {} → sell {} ({:.4}%)",
+            ["A", "B"][opp.buy_pool_idx],
+            ["A", "B"][opp.sell_pool_idx],
+            opp.spread_pct
+        ),
+        None => "none".to_string(),
+    });
+    row("Optimal amount (WETH)", &opp_weth(|o| o.optimal_amount_weth));
+    row("Expected profit (WETH)", &opp_weth(|o| o.expected_profit_weth));
+    row("Exact profit (WETH)", &|e| weth(e.exact_profit_weth));
+    if revm {
+        row("REVM round trip (WETH)", &|e| weth(e.revm_profit_weth));
+    }
+    row("Decision", &|e| match &e.opportunity {
+        Some(opp) => match &opp.shadow_only {
+            Some(verdict) => format!("shadow ({})", verdict.filter),
+            None => "GO".to_string(),
+        },
+        None => "-".to_string(),
+    });
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Testler
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{BlockInputs, StateDiffRecorder};
+    use crate::simulator::PoolOverride;
+    use crate::strategy::gas_spike_tests::{make_pool_configs, make_pool_state, make_test_config};
+    use alloy::primitives::address;
+
+    const BLOCK: u64 = 1_000;
+    const LIQ: u128 = 50_000_000_000_000_000_000;
+
+    fn int(v: i64) -> I256 {
+        I256::try_from(v).unwrap()
+    }
+
+    fn pool_expr(leg: PoolLeg, field: PoolField, adjustment: Adjustment) -> OverrideExpr {
+        OverrideExpr::Pool { leg, field, adjustment }
+    }
+
+    #[test]
+    fn test_parse_valid_expressions() {
+        let scale = |num: u64, den: u64| Adjustment::Scale { num: U256::from(num), den: U256::from(den) };
+        let cases = [
+            ("poolA.liquidity*=2", pool_expr(PoolLeg::A, PoolField::Liquidity, scale(2, 1))),
+            ("poolB.tick+=50", pool_expr(PoolLeg::B, PoolField::Tick, Adjustment::Add(int(50)))),
+            (" poolb.TICK -= 0x10 ", pool_expr(PoolLeg::B, PoolField::Tick, Adjustment::Sub(int(16)))),
+            ("poolA.tick=-100", pool_expr(PoolLeg::A, PoolField::Tick, Adjustment::Set(int(-100)))),
+            ("pool_a.liquidity/=1.5", pool_expr(PoolLeg::A, PoolField::Liquidity, scale(10, 15))),
+            ("poolA.liquidity*=0.25", pool_expr(PoolLeg::A, PoolField::Liquidity, scale(25, 100))),
+            (
+                "poolB.sqrtPriceX96=79_228_162_514_264_337_593_543_950_336",
+                pool_expr(
+                    PoolLeg::B,
+                    PoolField::SqrtPriceX96,
+                    Adjustment::Set(I256::try_from(U256::from(1u8) << 96).unwrap()),
+                ),
+            ),
+            (
+                "0x4200000000000000000000000000000000000006[0x3]=1_000",
+                OverrideExpr::Storage {
+                    address: address!("4200000000000000000000000000000000000006"),
+                    slot: U256::from(3u64),
+                    value: U256::from(1_000u64),
+                },
+            ),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(parse_override(raw).unwrap(), expected, "{}", raw);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_expressions() {
+        for raw in [
+            "poolA.liquidity",                                 // '=' yok
+            "poolA.liquidity=",                                // değer yok
+            "poolC.tick=1",                                    // bilinmeyen havuz
+            "poolA.fee=1",                                     // bilinmeyen alan
+            "liquidity=1",                                     // bacak yok
+            "poolA.tick*=2",                                   // tick ölçeklenmez
+            "poolA.liquidity/=0",                              // sıfıra bölme
+            "poolA.liquidity*=-2",                             // negatif çarpan
+            "poolA.liquidity=abc",                             // sayı değil
+            "poolA.liquidity=0xzz",                            // bozuk hex
+            "poolA.liquidity*=1.0000000000000000001",          // 19 ondalık
+            "0x4200000000000000000000000000000000000006[1]+=1", // storage yalnızca =
+            "0x42[1]=1",                                       // kısa adres
+        ] {
+            assert!(parse_override(raw).is_err(), "should reject '{}'", raw);
+        }
+    }
+
+    #[test]
+    fn test_relative_expressions_resolve_to_absolute_layer() {
+        let pools = make_pool_configs();
+        let a = (**make_pool_state(2450.0, LIQ, BLOCK).load()).clone();
+        let b = (**make_pool_state(2500.0, LIQ, BLOCK).load()).clone();
+        let exprs: Vec<OverrideExpr> = ["poolA.liquidity*=2", "poolB.tick+=50", "poolB.tick+=50"]
+            .iter()
+            .map(|raw| parse_override(raw).unwrap())
+            .collect();
+
+        let overrides = resolve_overrides(&exprs, [&pools[0], &pools[1]], [&a, &b]).unwrap();
+        assert_eq!(overrides.pools[&pools[0].address].liquidity, Some(2 * LIQ));
+        // Ardışık ifadeler birikir; fiyat tick'ten türetilir
+        let ov_b = overrides.pools[&pools[1].address];
+        assert_eq!(ov_b, PoolOverride { tick: Some(b.tick + 100), ..Default::default() });
+        let shifted = overrides.apply_to_state(&pools[1], &b);
+        assert_eq!(shifted.sqrt_price_x96, get_sqrt_ratio_at_tick(b.tick + 100));
+        // 100 tick ≈ %1.005 fiyat artışı (aynı formülle hesaplanan taban fiyata göre)
+        let mut unshifted = StateOverrides::default();
+        unshifted.pools.insert(pools[1].address, PoolOverride { tick: Some(b.tick), ..Default::default() });
+        let base_price = unshifted.apply_to_state(&pools[1], &b).eth_price_usd;
+        assert!(shifted.eth_price_usd > base_price * 1.01, "{} vs {}", shifted.eth_price_usd, base_price);
+        assert_eq!(shifted.liquidity, b.liquidity);
+
+        // Aralık dışı sonuçlar reddedilir
+        let bad = [
+            parse_override("poolA.liquidity-=50_000_000_000_000_000_000").unwrap(),
+            parse_override(&format!("poolA.tick={}", MAX_TICK + 1)).unwrap(),
+            parse_override("poolA.sqrtPriceX96=1").unwrap(),
+        ];
+        for expr in bad {
+            assert!(resolve_overrides(&[expr], [&pools[0], &pools[1]], [&a, &b]).is_err(), "{:?}", expr);
+        }
+    }
+
+    /// Uçtan uca: kayıtlı blok → iki havuzun likiditesi 2x → NR optimumu büyür.
+    /// Tek aralık matematiğinde hem iç optimum hem derinlik tavanı L ile
+    /// doğrusal ölçeklenir; MAX_TRADE tavanı devre dışı bırakılır.
+    #[test]
+    fn test_liquidity_doubling_moves_nr_optimum_up() {
+        let pools = make_pool_configs();
+        let mut recorder = StateDiffRecorder::new("unused.jsonl");
+        let states = vec![make_pool_state(2450.0, LIQ, BLOCK), make_pool_state(2500.0, LIQ, BLOCK)];
+        let inputs = BlockInputs {
+            base_fee: 500_000_000_000,
+            l1_data_fee_wei: 0,
+            last_simulated_gas: Some(150_000),
+            competitor_txs: 0,
+        };
+        let records = vec![recorder.build_record(BLOCK, inputs, &pools, &states)];
+        let config = BotConfig {
+            min_profit_roi: f64::MAX,
+            max_trade_size_weth: 1_000_000.0,
+            ..make_test_config(0.000002, 0.00005)
+        };
+        let pp = [pools[0].clone(), pools[1].clone()];
+        let exprs = [
+            parse_override("poolA.liquidity*=2").unwrap(),
+            parse_override("poolB.liquidity*=2").unwrap(),
+        ];
+
+        let report = what_if_at_block(&records, &BitmapArchive::new(), BLOCK, &pp, &exprs, &config, None).unwrap();
+        assert_eq!(report.after.states[0].liquidity, 2 * LIQ);
+        assert_eq!(report.after.states[1].liquidity_f64, 2.0 * LIQ as f64);
+        // Yalnızca L değişti — fiyatlar aynı kalır
+        assert_eq!(report.after.states[0].eth_price_usd, report.before.states[0].eth_price_usd);
+
+        let before = report.before.opportunity.as_ref().expect("fixture spread is profitable");
+        let after = report.after.opportunity.as_ref().expect("deeper pools stay profitable");
+        assert_eq!(after.buy_pool_idx, before.buy_pool_idx);
+        assert!(
+            after.optimal_amount_weth > before.optimal_amount_weth,
+            "optimum should grow: {} -> {}",
+            before.optimal_amount_weth,
+            after.optimal_amount_weth
+        );
+        assert!(after.expected_profit_weth > before.expected_profit_weth);
+
+        // Boş ifade listesi → iki taraf aynı
+        let same = what_if_at_block(&records, &BitmapArchive::new(), BLOCK, &pp, &[], &config, None).unwrap();
+        assert_eq!(
+            same.after.opportunity.map(|o| o.optimal_amount_weth),
+            same.before.opportunity.map(|o| o.optimal_amount_weth)
+        );
+
+        // Kayıtta olmayan blok
+        assert!(what_if_at_block(&records, &BitmapArchive::new(), BLOCK + 1, &pp, &exprs, &config, None).is_err());
+    }
+}
@@ -27,6 +27,8 @@ echo -e "\n\033[1;36m[2/4] 🧠 Kuantum Motoru Testi (Rust Proptest) Başlıyor.
 cd $BOT_DIR
 # Rust motorunu milyonlarca ihtimale karşı ekstrem sayılarla test et
 cargo test --release
+# Kompakt calldata: Rust çözücüsü ↔ gerçek kontrat (diferansiyel REVM — artifact Aşama 1'de derlendi)
+CONTRACT_ARTIFACT="../$CONTRACT_DIR/out/Arbitraj.sol/ArbitrajBotu.json" cargo test --release -- --ignored calldata_diff
 if [ $? -eq 0 ]; then
     echo -e "✅ \033[1;32mAŞAMA 2 BAŞARILI: Kuantum Beyin Asla Çökmüyor.\033[0m"
 else